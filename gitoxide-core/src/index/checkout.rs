@@ -85,6 +85,7 @@ pub fn checkout_exclusive(
         bytes_written,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
+        symlinks_written_as_files,
     } = match repo {
         Some(repo) => gix::worktree::state::checkout(
             &mut index,
@@ -141,9 +142,18 @@ pub fn checkout_exclusive(
     if !collisions.is_empty() {
         messages.push(format!("encountered {} collision(s)", collisions.len()));
         for col in collisions {
-            writeln!(err, "{}: collision ({:?})", col.path, col.error_kind).ok();
+            match col.colliding_path {
+                Some(other) => writeln!(err, "{}: collision with {} ({:?})", col.path, other, col.error_kind),
+                None => writeln!(err, "{}: collision ({:?})", col.path, col.error_kind),
+            }
+            .ok();
         }
     }
+    if symlinks_written_as_files != 0 {
+        messages.push(format!(
+            "wrote {symlinks_written_as_files} symlink(s) as file(s) as the filesystem doesn't support symlinks"
+        ));
+    }
     if !delayed_paths_unknown.is_empty() {
         messages.push(format!(
             "A delayed process provided us with {} paths we never sent to it",
@@ -114,23 +114,30 @@ pub(crate) mod function {
         };
 
         if let Some(gix::worktree::state::checkout::Outcome { collisions, errors, .. }) = outcome {
-            if !(collisions.is_empty() && errors.is_empty()) {
-                let mut messages = Vec::new();
-                if !errors.is_empty() {
-                    messages.push(format!("kept going through {} errors(s)", errors.len()));
-                    for record in errors {
-                        writeln!(err, "{}: {}", record.path, record.error).ok();
+            if !collisions.is_empty() {
+                // Like `git`, collisions are only worth a warning as the clone itself was successful.
+                writeln!(
+                    err,
+                    "warning: the following paths have collided (e.g. case-sensitive paths\n\
+                     on a case-insensitive filesystem) and only one from the same\n\
+                     colliding group is in the working tree:\n"
+                )
+                .ok();
+                for col in collisions {
+                    match col.colliding_path {
+                        Some(other) => writeln!(err, "  '{}'\n  '{}'", col.path, other),
+                        None => writeln!(err, "  '{}'", col.path),
                     }
+                    .ok();
                 }
-                if !collisions.is_empty() {
-                    messages.push(format!("encountered {} collision(s)", collisions.len()));
-                    for col in collisions {
-                        writeln!(err, "{}: collision ({:?})", col.path, col.error_kind).ok();
-                    }
+            }
+            if !errors.is_empty() {
+                for record in &errors {
+                    writeln!(err, "{}: {}", record.path, record.error).ok();
                 }
                 bail!(
-                    "One or more errors occurred - checkout is incomplete: {}",
-                    messages.join(", ")
+                    "One or more errors occurred - checkout is incomplete: kept going through {} errors(s)",
+                    errors.len()
                 );
             }
         }
@@ -51,6 +51,8 @@ pub fn describe(
         always,
         statistics,
         max_candidates,
+        patterns,
+        exclude_patterns,
         long_format,
         dirty_suffix,
     }: describe::Options,
@@ -68,8 +70,14 @@ pub fn describe(
     } else {
         Default::default()
     };
-    let resolution = commit
-        .describe()
+    let mut describe = commit.describe();
+    for pattern in patterns {
+        describe = describe.match_pattern(pattern);
+    }
+    for pattern in exclude_patterns {
+        describe = describe.exclude_pattern(pattern);
+    }
+    let resolution = describe
         .names(select_ref)
         .traverse_first_parent(first_parent)
         .id_as_fallback(always)
@@ -98,6 +106,8 @@ pub mod describe {
         pub long_format: bool,
         pub statistics: bool,
         pub max_candidates: usize,
+        pub patterns: Vec<String>,
+        pub exclude_patterns: Vec<String>,
         pub dirty_suffix: Option<String>,
     }
 }
@@ -21,7 +21,7 @@ pub fn function(repo: gix::Repository, action: gix::credentials::program::main::
             cascade
                 .invoke(
                     match action {
-                        Get => gix::credentials::helper::Action::Get(context),
+                        Get => gix::credentials::helper::Action::Get(Box::new(context)),
                         Erase => gix::credentials::helper::Action::Erase(context.to_bstring()),
                         Store => gix::credentials::helper::Action::Store(context.to_bstring()),
                     },
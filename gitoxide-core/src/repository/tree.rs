@@ -200,3 +200,156 @@ fn format_entry(
         filename
     )
 }
+
+pub mod diff {
+    #[derive(Debug, Default, Clone)]
+    pub struct Options {
+        /// If `true`, don't track renames even if they are configured.
+        pub no_renames: bool,
+        /// If set, track renames with the given similarity threshold in the range of `0.0` to `1.0`, even if
+        /// rename tracking was disabled by configuration.
+        pub rename_threshold: Option<f32>,
+        /// If set, the maximum amount of files on either side to consider for similarity checks, overriding `diff.renameLimit`.
+        pub rename_limit: Option<usize>,
+        /// If set, track copies with the given similarity threshold in the range of `0.0` to `1.0`, along with renames.
+        pub copy_threshold: Option<f32>,
+        /// If `true`, track copies with all files of the old tree as possible sources, not only the modified ones.
+        ///
+        /// Note that if the limit is exceeded, only exact copies are found among the unmodified files.
+        pub find_copies_harder: bool,
+        /// If set, print the changes as patch with the given amount of context lines, instead of only listing the changed files.
+        pub patch: Option<gix::diff::blob::unified_diff::ContextSize>,
+        /// If `true`, write binary patches for binary files when printing a patch, instead of only noting that they differ.
+        pub binary: bool,
+        /// If set, print the amount of changed lines per file in the given format, instead of listing the changed files.
+        pub stat: Option<Stat>,
+    }
+
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub enum Stat {
+        /// Print the inserted and removed lines of each file separated by tabs, like `git diff --numstat`.
+        Numstat,
+        /// Print a graph of the changes in each file and a summary line, like `git diff --stat`.
+        Stat,
+    }
+}
+
+pub fn diff(
+    repo: gix::Repository,
+    old_treeish: &str,
+    new_treeish: &str,
+    diff::Options {
+        no_renames,
+        rename_threshold,
+        rename_limit,
+        copy_threshold,
+        find_copies_harder,
+        patch,
+        binary,
+        stat,
+    }: diff::Options,
+    format: OutputFormat,
+    mut out: impl io::Write,
+    mut err: impl io::Write,
+) -> anyhow::Result<()> {
+    if format != OutputFormat::Human {
+        bail!("Only human output format is supported at the moment");
+    }
+
+    let old = treeish_to_tree(Some(old_treeish), &repo)?;
+    let new = treeish_to_tree(Some(new_treeish), &repo)?;
+
+    let mut changes = old.changes()?;
+    changes.track_path();
+    if no_renames {
+        changes.track_rewrites(None);
+    } else if rename_threshold.is_some() || rename_limit.is_some() || copy_threshold.is_some() || find_copies_harder {
+        let mut rewrites = gix::diff::new_rewrites(&repo.config_snapshot(), true)?.unwrap_or_default();
+        if let Some(threshold) = rename_threshold {
+            rewrites.percentage = (threshold < 1.0).then_some(threshold);
+        }
+        if let Some(limit) = rename_limit {
+            rewrites.limit = limit;
+        }
+        if copy_threshold.is_some() || find_copies_harder {
+            let copies = rewrites.copies.get_or_insert_with(Default::default);
+            if let Some(threshold) = copy_threshold {
+                copies.percentage = (threshold < 1.0).then_some(threshold);
+            }
+            if find_copies_harder {
+                copies.source = gix::diff::rewrites::CopySource::FromSetOfModifiedFilesAndAllSources;
+            }
+        }
+        changes.track_rewrites(Some(rewrites));
+    }
+
+    if let Some(stat) = stat {
+        let stats = changes.stats(&new)?;
+        match stat {
+            diff::Stat::Numstat => stats.write_numstat(&mut out)?,
+            diff::Stat::Stat => stats.write_stat(&mut out, Default::default())?,
+        }
+        return Ok(());
+    }
+
+    let mut resource_cache = match patch {
+        Some(_) => Some(repo.diff_resource_cache(gix::diff::blob::pipeline::Mode::ToGit, Default::default())?),
+        None => None,
+    };
+    let outcome = changes.for_each_to_obtain_tree(&new, |change| -> anyhow::Result<_> {
+        use gix::object::tree::diff::change::Event::*;
+        if let Some((context_size, resource_cache)) = patch.zip(resource_cache.as_mut()) {
+            change.write_patch(
+                resource_cache,
+                gix::object::tree::diff::change::write_patch::Options {
+                    context_size,
+                    binary,
+                    mark_dirty_submodules: false,
+                },
+                &mut out,
+            )?;
+            return Ok(gix::object::tree::diff::Action::Continue);
+        }
+        match change.event {
+            Addition { entry_mode, .. } if !entry_mode.is_tree() => writeln!(out, "A\t{}", change.location)?,
+            Deletion { entry_mode, .. } if !entry_mode.is_tree() => writeln!(out, "D\t{}", change.location)?,
+            Modification { entry_mode, .. } if !entry_mode.is_tree() => writeln!(out, "M\t{}", change.location)?,
+            Rewrite {
+                source_location,
+                diff,
+                copy,
+                ..
+            } => writeln!(
+                out,
+                "{}{:03}\t{source_location}\t{}",
+                if copy { "C" } else { "R" },
+                diff.map_or(100, |stats| (stats.similarity * 100.0) as u32),
+                change.location
+            )?,
+            Addition { .. } | Deletion { .. } | Modification { .. } => {}
+        }
+        Ok(gix::object::tree::diff::Action::Continue)
+    })?;
+
+    if let Some(rewrites) = outcome.rewrites {
+        for (kind, num_skipped) in [
+            (
+                "rename",
+                rewrites.num_similarity_checks_skipped_for_rename_tracking_due_to_limit,
+            ),
+            (
+                "copy",
+                rewrites.num_similarity_checks_skipped_for_copy_tracking_due_to_limit,
+            ),
+        ] {
+            if num_skipped != 0 {
+                writeln!(
+                    err,
+                    "warning: skipped inexact {kind} detection as {num_skipped} possible pairs exceed the limit of {limit}*{limit}, consider increasing it with --rename-limit",
+                    limit = rewrites.options.limit
+                )?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,198 @@
+//! Apply the hunks of a patch to the content of a single file.
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    /// The error returned by [`apply()`](super::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Hunk {} at line {old_start} does not apply", hunk + 1)]
+        HunkDoesNotApply {
+            /// The zero-based index of the hunk in the patch.
+            hunk: usize,
+            old_start: u32,
+        },
+    }
+}
+
+pub(crate) mod function {
+    use bstr::ByteSlice;
+
+    use super::apply::Error;
+    use crate::{
+        patch::{Hunk, Line},
+        Options, Whitespace,
+    };
+
+    /// Apply `hunks` to `content` in order, and return the new content, just like `git apply` does.
+    ///
+    /// Each hunk is searched for close to the line it is supposed to apply to, taking into account the lines that previous
+    /// hunks added or removed, so hunks may still apply if the lines they touch moved a bit.
+    /// Hunks that start at the first line or that have no trailing context must match at the beginning or the end of
+    /// `content` respectively, unless context lines are ignored as allowed by `options.fuzz`.
+    pub fn apply(content: &[u8], hunks: &[Hunk], options: Options) -> Result<Vec<u8>, Error> {
+        let mut image: Vec<&[u8]> = content.lines_with_terminator().collect();
+        // The difference between the lines in `image` and the ones the line numbers of the patch refer to.
+        let mut offset = 0isize;
+        for (hunk_index, hunk) in hunks.iter().enumerate() {
+            let (pos, expected, lines) = find_hunk(&image, hunk, offset, options).ok_or(Error::HunkDoesNotApply {
+                hunk: hunk_index,
+                old_start: hunk.old_start,
+            })?;
+
+            let mut preimage_len = 0;
+            let mut postimage = Vec::with_capacity(lines.len());
+            for line in lines {
+                match line {
+                    Line::Context(_) => {
+                        postimage.push(image[pos + preimage_len]);
+                        preimage_len += 1;
+                    }
+                    Line::Removal(_) => preimage_len += 1,
+                    Line::Addition(line) => postimage.push(line.as_slice()),
+                }
+            }
+            offset += (pos as isize - expected as isize) + (postimage.len() as isize - preimage_len as isize);
+            image.splice(pos..pos + preimage_len, postimage);
+        }
+        Ok(image.concat())
+    }
+
+    /// The index in the image the first line of `hunk` is expected at.
+    fn expected_position(hunk: &Hunk, offset: isize) -> usize {
+        // Hunks without old lines are inserted after the line they refer to.
+        let line = if hunk.old_len == 0 {
+            hunk.old_start
+        } else {
+            hunk.old_start.saturating_sub(1)
+        };
+        (line as isize + offset).max(0) as usize
+    }
+
+    /// Find the position at which `hunk` applies in `image`, and return it along with the position it was expected at
+    /// and the lines of the hunk to apply there, which may lack some of the context lines if `options.fuzz` allowed
+    /// to ignore them.
+    fn find_hunk<'a>(
+        image: &[&[u8]],
+        hunk: &'a Hunk,
+        offset: isize,
+        options: Options,
+    ) -> Option<(usize, usize, &'a [Line])> {
+        let is_context = |line: &Line| matches!(line, Line::Context(_));
+        let mut leading = hunk.lines.iter().take_while(|line| is_context(line)).count();
+        let mut trailing = if leading == hunk.lines.len() {
+            0
+        } else {
+            hunk.lines.iter().rev().take_while(|line| is_context(line)).count()
+        };
+        let min_leading = leading.saturating_sub(options.fuzz as usize);
+        let min_trailing = trailing.saturating_sub(options.fuzz as usize);
+
+        let mut match_beginning = hunk.old_start <= 1;
+        // Hunks without any context can't tell if they are at the end.
+        let mut match_end = trailing == 0 && leading != 0;
+        let mut lines = hunk.lines.as_slice();
+        let mut expected = expected_position(hunk, offset);
+        loop {
+            if let Some(pos) = find_lines(image, lines, expected, match_beginning, match_end, options.whitespace) {
+                return Some((pos, expected, lines));
+            }
+            if leading <= min_leading && trailing <= min_trailing {
+                return None;
+            }
+            if match_beginning || match_end {
+                match_beginning = false;
+                match_end = false;
+                continue;
+            }
+            if leading > min_leading && (leading >= trailing || trailing <= min_trailing) {
+                lines = &lines[1..];
+                leading -= 1;
+                expected += 1;
+            } else {
+                lines = &lines[..lines.len() - 1];
+                trailing -= 1;
+            }
+        }
+    }
+
+    /// Find the position of the context and removed `lines` in `image` that is closest to `expected`.
+    fn find_lines(
+        image: &[&[u8]],
+        lines: &[Line],
+        expected: usize,
+        match_beginning: bool,
+        match_end: bool,
+        whitespace: Whitespace,
+    ) -> Option<usize> {
+        let preimage: Vec<&[u8]> = lines
+            .iter()
+            .filter_map(|line| match line {
+                Line::Context(line) | Line::Removal(line) => Some(line.as_slice()),
+                Line::Addition(_) => None,
+            })
+            .collect();
+        let last_pos = image.len().checked_sub(preimage.len())?;
+        let matches_at = |pos: usize| {
+            image[pos..pos + preimage.len()]
+                .iter()
+                .zip(preimage.iter())
+                .all(|(actual, expected)| lines_match(actual, expected, whitespace))
+        };
+
+        if match_beginning {
+            return (!match_end || last_pos == 0)
+                .then_some(0)
+                .filter(|pos| matches_at(*pos));
+        }
+        if match_end {
+            return Some(last_pos).filter(|pos| matches_at(*pos));
+        }
+        let expected = expected.min(last_pos);
+        (0..=last_pos)
+            .flat_map(|distance| {
+                let backward = expected.checked_sub(distance);
+                let forward = (distance != 0)
+                    .then_some(expected + distance)
+                    .filter(|pos| *pos <= last_pos);
+                backward.into_iter().chain(forward)
+            })
+            .find(|pos| matches_at(*pos))
+    }
+
+    fn lines_match(actual: &[u8], expected: &[u8], whitespace: Whitespace) -> bool {
+        match whitespace {
+            Whitespace::Exact => actual == expected,
+            Whitespace::IgnoreChanges => {
+                let is_whitespace = |b: u8| b.is_ascii_whitespace();
+                let trim = |line: &[u8]| -> usize {
+                    line.len() - line.iter().rev().take_while(|b| is_whitespace(**b)).count()
+                };
+                let (a, b) = (&actual[..trim(actual)], &expected[..trim(expected)]);
+                let (mut i, mut j) = (0, 0);
+                while i < a.len() && j < b.len() {
+                    if is_whitespace(a[i]) {
+                        if !is_whitespace(b[j]) {
+                            return false;
+                        }
+                        while i < a.len() && is_whitespace(a[i]) {
+                            i += 1;
+                        }
+                        while j < b.len() && is_whitespace(b[j]) {
+                            j += 1;
+                        }
+                        continue;
+                    }
+                    if a[i] != b[j] {
+                        return false;
+                    }
+                    i += 1;
+                    j += 1;
+                }
+                i == a.len() && j == b.len()
+            }
+        }
+    }
+}
+pub use function::apply;
@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_object::tree::EntryKind;
+
+use crate::{
+    apply::Error,
+    patch::{Content, Rewrite},
+    Change, Entry, Options, Patch,
+};
+
+/// Apply all `patches` in order, obtaining the current version of files with `read`, and return the resulting
+/// changes sorted by location, with `options` controlling how hunks are matched.
+///
+/// `read` is called with a location relative to the root of the repository and returns the file at that location, or
+/// `None` if it doesn't exist. Later patches see the changes of earlier ones, so the same file may be patched multiple
+/// times, and nothing is returned unless all patches apply, just like `git apply` does.
+pub fn apply<E>(
+    patches: &[Patch],
+    mut read: impl FnMut(&BStr) -> Result<Option<Entry>, E>,
+    options: Options,
+) -> Result<Vec<Change>, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let _span = gix_trace::coarse!("gix_apply::apply()", patches = patches.len());
+    // Files as changed by the patches so far, or `None` if they were deleted.
+    let mut changed = BTreeMap::<BString, Option<Entry>>::new();
+    let mut current = |changed: &BTreeMap<BString, Option<Entry>>, location: &BStr| -> Result<Option<Entry>, Error> {
+        match changed.get(location) {
+            Some(entry) => Ok(entry.clone()),
+            None => read(location).map_err(|err| Error::Read {
+                location: location.to_owned(),
+                source: Box::new(err),
+            }),
+        }
+    };
+
+    for patch in patches {
+        let old = match patch.old_path.as_ref() {
+            Some(location) => Some(
+                current(&changed, location.as_bstr())?.ok_or_else(|| Error::MissingFile {
+                    location: location.clone(),
+                })?,
+            ),
+            None => None,
+        };
+        if let Some(location) = patch
+            .new_path
+            .as_ref()
+            .filter(|new_path| patch.old_path.as_ref() != Some(*new_path))
+        {
+            if current(&changed, location.as_bstr())?.is_some() {
+                return Err(Error::AlreadyExists {
+                    location: location.clone(),
+                });
+            }
+        }
+
+        let location = patch
+            .new_path
+            .as_ref()
+            .or(patch.old_path.as_ref())
+            .expect("patches always have a path");
+        let old_data = old.as_ref().map_or(&[][..], |entry| entry.data.as_slice());
+        let data = match &patch.content {
+            Content::Hunks(hunks) if hunks.is_empty() => old_data.to_owned(),
+            Content::Hunks(hunks) => crate::blob::apply(old_data, hunks, options).map_err(|err| Error::ApplyHunks {
+                location: location.clone(),
+                source: err,
+            })?,
+            Content::Binary(binary) => binary.forward.apply(old_data).map_err(|err| Error::ApplyBinary {
+                location: location.clone(),
+                source: err,
+            })?,
+            Content::BinaryWithoutData => {
+                return Err(Error::BinaryWithoutData {
+                    location: location.clone(),
+                })
+            }
+        };
+
+        match patch.new_path.as_ref() {
+            None => {
+                if !data.is_empty() {
+                    return Err(Error::DeletionLeavesContent {
+                        location: location.clone(),
+                    });
+                }
+                changed.insert(location.clone(), None);
+            }
+            Some(new_path) => {
+                let mode = patch
+                    .new_mode
+                    .or_else(|| old.as_ref().map(|entry| entry.mode))
+                    .unwrap_or(EntryKind::Blob);
+                changed.insert(new_path.clone(), Some(Entry { mode, data }));
+                if let Some(old_path) = patch.old_path.as_ref().filter(|old_path| *old_path != new_path) {
+                    if patch.rewrite != Some(Rewrite::Copy) {
+                        changed.insert(old_path.clone(), None);
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(changed
+        .into_iter()
+        .map(|(location, entry)| Change { location, entry })
+        .collect())
+}
@@ -0,0 +1,101 @@
+//! Parse patches in the unified diff format as written by `git diff`, and apply them to the content of files or to trees,
+//! as the foundation for `git apply`.
+#![deny(rust_2018_idioms, missing_docs)]
+#![forbid(unsafe_code)]
+
+use bstr::BString;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod patch;
+pub use patch::Patch;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod blob;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree;
+
+mod function;
+pub use function::apply;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    use bstr::BString;
+
+    /// The error returned by [`apply()`](super::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read '{location}'")]
+        Read {
+            location: BString,
+            source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        },
+        #[error("'{location}' doesn't exist")]
+        MissingFile { location: BString },
+        #[error("'{location}' already exists")]
+        AlreadyExists { location: BString },
+        #[error("Patch failed for '{location}'")]
+        ApplyHunks {
+            location: BString,
+            source: crate::blob::apply::Error,
+        },
+        #[error("The binary patch for '{location}' could not be applied")]
+        ApplyBinary {
+            location: BString,
+            source: gix_diff::blob::binary_patch::apply::Error,
+        },
+        #[error("Cannot apply the patch for the binary file '{location}' without its full content")]
+        BinaryWithoutData { location: BString },
+        #[error("The patch deleting '{location}' leaves some of its content")]
+        DeletionLeavesContent { location: BString },
+    }
+}
+
+/// The way whitespace is treated when finding the lines a hunk applies to.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Whitespace {
+    /// Lines must match exactly.
+    #[default]
+    Exact,
+    /// Sequences of whitespace are considered equal no matter their length, and whitespace at the end of lines is ignored,
+    /// like `git apply --ignore-space-change` does.
+    ///
+    /// The context lines of the result keep the whitespace of the lines they matched.
+    IgnoreChanges,
+}
+
+/// Options for applying patches.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The maximum amount of context lines at the beginning and at the end of a hunk that may be ignored if the hunk can't
+    /// be applied with all of them, similar to the `--fuzz` option of `patch`.
+    ///
+    /// `git` doesn't ignore any context lines by default either, and `git apply -C<n>` with `n` lines of context
+    /// to match is the same as a fuzz of `3 - n` for patches with the default of 3 context lines.
+    pub fuzz: u32,
+    /// How to treat whitespace when finding the lines a hunk applies to.
+    pub whitespace: Whitespace,
+}
+
+/// A file as seen and produced by [`apply()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Entry {
+    /// The kind of file.
+    pub mode: gix_object::tree::EntryKind,
+    /// The content of the file, which is the target of the link for symbolic links.
+    pub data: Vec<u8>,
+}
+
+/// A change to a file that results from [applying patches](apply()).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The location of the file relative to the root of the repository.
+    pub location: BString,
+    /// The new version of the file, or `None` if it was deleted.
+    pub entry: Option<Entry>,
+}
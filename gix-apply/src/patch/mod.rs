@@ -0,0 +1,119 @@
+//! Types to represent the changes to a single file as described by a patch, and a way to [parse](parse()) them.
+use bstr::{BStr, BString, ByteSlice};
+use gix_diff::blob::binary_patch::BinaryPatch;
+use gix_object::tree::EntryKind;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse;
+pub use parse::function::parse;
+
+/// The changes to a single file, as described by one section of a patch, like the one starting with `diff --git`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Patch {
+    /// The location of the file before the change, relative to the root of the repository, or `None` if it was added.
+    pub old_path: Option<BString>,
+    /// The location of the file after the change, relative to the root of the repository, or `None` if it was deleted.
+    pub new_path: Option<BString>,
+    /// The kind of file before the change, if known.
+    pub old_mode: Option<EntryKind>,
+    /// The kind of file after the change, if known. If `None`, the kind of the file doesn't change.
+    pub new_mode: Option<EntryKind>,
+    /// Set if the file at `new_path` was created by renaming or copying the file at `old_path`.
+    pub rewrite: Option<Rewrite>,
+    /// The changes to the content of the file.
+    pub content: Content,
+}
+
+impl Patch {
+    /// Return `true` if the file is added by this patch.
+    pub fn is_addition(&self) -> bool {
+        self.old_path.is_none()
+    }
+
+    /// Return `true` if the file is deleted by this patch.
+    pub fn is_deletion(&self) -> bool {
+        self.new_path.is_none()
+    }
+}
+
+/// Return `true` if `location` is a path relative to the root of the repository that is safe to read from and write to.
+///
+/// This isn't the case if it's absolute, contains empty, `.` or `..` components, or if any of its components is `.git`,
+/// which would allow patches to change files outside of the worktree or within the repository itself.
+/// These are the paths that `git apply` rejects as well.
+pub fn is_valid_location(location: &BStr) -> bool {
+    let is_separator = |b: &u8| *b == b'/' || (cfg!(windows) && *b == b'\\');
+    !location.contains(&0)
+        && location.split(is_separator).all(|component| {
+            // On Windows, `.git.` and `.git ` refer to `.git`, and `C:` to a drive.
+            let component = if cfg!(windows) {
+                if component.contains(&b':') {
+                    return false;
+                }
+                component.trim_end_with(|c| c == '.' || c == ' ')
+            } else {
+                component
+            };
+            !(component.is_empty()
+                || component == b"."
+                || component == b".."
+                || component.eq_ignore_ascii_case(b".git"))
+        })
+}
+
+/// The way a file was created from another file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Rewrite {
+    /// The file was renamed, so the source doesn't exist anymore.
+    Rename,
+    /// The file was copied, so the source remains.
+    Copy,
+}
+
+/// The changes to the content of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Content {
+    /// The content changes as described by these hunks, in order, which is empty if the content doesn't change.
+    Hunks(Vec<Hunk>),
+    /// The content changes as described by a binary patch.
+    Binary(BinaryPatch),
+    /// The file is binary, and the patch only notes that it differs without providing the data to apply the change.
+    BinaryWithoutData,
+}
+
+/// A set of changed lines along with their context, as introduced by a `@@ -<old> +<new> @@` line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    /// The one-based number of the first line in the old version of the file, or `0` if the hunk doesn't have old lines
+    /// and is applied at the beginning of the file.
+    pub old_start: u32,
+    /// The amount of context and removed lines.
+    pub old_len: u32,
+    /// The one-based number of the first line in the new version of the file.
+    pub new_start: u32,
+    /// The amount of context and added lines.
+    pub new_len: u32,
+    /// All lines of the hunk, in order.
+    pub lines: Vec<Line>,
+}
+
+/// A line of a [`Hunk`], including its line feed unless it's the last line of a file that doesn't end with one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Line {
+    /// A line that is the same in both versions.
+    Context(BString),
+    /// A line that only exists in the old version.
+    Removal(BString),
+    /// A line that only exists in the new version.
+    Addition(BString),
+}
+
+impl Line {
+    /// Return the content of the line.
+    pub fn content(&self) -> &BString {
+        match self {
+            Line::Context(line) | Line::Removal(line) | Line::Addition(line) => line,
+        }
+    }
+}
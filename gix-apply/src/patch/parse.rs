@@ -0,0 +1,461 @@
+use bstr::BString;
+
+/// Options for [`parse()`](function::parse()).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The amount of leading components to remove from the paths in the patch, with `1` being the default to remove the
+    /// `a/` and `b/` prefixes written by `git diff`, just like `git apply -p1` does.
+    pub strip: usize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options { strip: 1 }
+    }
+}
+
+/// The error returned by [`parse()`](function::parse()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The input doesn't contain a patch")]
+    NoPatch,
+    #[error("Line {line_number}: could not determine the paths of the patched file")]
+    MissingPath { line_number: usize },
+    #[error("Line {line_number}: the path '{path}' doesn't have enough leading components to strip, or would leave the worktree")]
+    InvalidPath { line_number: usize, path: BString },
+    #[error("Line {line_number}: the mode in '{line}' isn't valid")]
+    InvalidMode { line_number: usize, line: BString },
+    #[error("Line {line_number}: '{line}' isn't a valid hunk header")]
+    InvalidHunkHeader { line_number: usize, line: BString },
+    #[error("Line {line_number}: '{line}' isn't a valid line of a hunk")]
+    InvalidHunkLine { line_number: usize, line: BString },
+    #[error("The patch ended in the middle of a hunk")]
+    UnexpectedEof,
+    #[error("Line {line_number}: could not parse the binary patch")]
+    BinaryPatch {
+        line_number: usize,
+        source: gix_diff::blob::binary_patch::parse::Error,
+    },
+}
+
+pub(crate) mod function {
+    use bstr::{BString, ByteSlice, ByteVec};
+    use gix_diff::blob::unified_diff::HunkHeader;
+    use gix_object::tree::{EntryKind, EntryMode};
+
+    use super::{Error, Options};
+    use crate::patch::{is_valid_location, Content, Hunk, Line, Patch, Rewrite};
+
+    /// Parse all patches in `input`, in the unified diff format as written by `git diff`, with `options`, and return the
+    /// changes they describe for each file, in order.
+    ///
+    /// Patches in the format of `git`, which start with `diff --git`, may contain mode changes, renames, copies and
+    /// binary patches, while traditional patches that only start with `---` and `+++` lines just contain hunks.
+    /// Lines that aren't part of a patch, like the message of a patch sent by email, are skipped.
+    pub fn parse(input: &[u8], options: Options) -> Result<Vec<Patch>, Error> {
+        let mut lines = Lines {
+            input,
+            pos: 0,
+            line_number: 1,
+        };
+        let mut out = Vec::new();
+        while let Some(line) = lines.peek() {
+            if line.starts_with(b"diff --git ") {
+                out.push(parse_git_patch(&mut lines, options)?);
+            } else if line.starts_with(b"--- ")
+                && matches!(lines.peek_second(), Some(next) if next.starts_with(b"+++ "))
+            {
+                out.push(parse_traditional_patch(&mut lines, options)?);
+            } else {
+                lines.next();
+            }
+        }
+        if out.is_empty() {
+            return Err(Error::NoPatch);
+        }
+        Ok(out)
+    }
+
+    struct Lines<'a> {
+        input: &'a [u8],
+        pos: usize,
+        /// The one-based number of the next line.
+        line_number: usize,
+    }
+
+    impl<'a> Lines<'a> {
+        /// Return the next line including its line feed, if present, without consuming it.
+        fn peek_with_newline(&self) -> Option<&'a [u8]> {
+            let rest = &self.input[self.pos..];
+            if rest.is_empty() {
+                return None;
+            }
+            Some(rest.find_byte(b'\n').map_or(rest, |pos| &rest[..=pos]))
+        }
+
+        /// Return the next line without its line feed, without consuming it.
+        fn peek(&self) -> Option<&'a [u8]> {
+            self.peek_with_newline().map(trim_newline)
+        }
+
+        /// Return the line after the next one without its line feed.
+        fn peek_second(&self) -> Option<&'a [u8]> {
+            let first = self.peek_with_newline()?;
+            Lines {
+                input: self.input,
+                pos: self.pos + first.len(),
+                line_number: self.line_number + 1,
+            }
+            .peek()
+        }
+
+        fn next_with_newline(&mut self) -> Option<&'a [u8]> {
+            let line = self.peek_with_newline()?;
+            self.pos += line.len();
+            self.line_number += 1;
+            Some(line)
+        }
+
+        fn next(&mut self) -> Option<&'a [u8]> {
+            self.next_with_newline().map(trim_newline)
+        }
+    }
+
+    fn trim_newline(line: &[u8]) -> &[u8] {
+        line.strip_suffix(b"\n").unwrap_or(line)
+    }
+
+    fn parse_git_patch(lines: &mut Lines<'_>, options: Options) -> Result<Patch, Error> {
+        let header_line_number = lines.line_number;
+        let header = lines.next().expect("peeked");
+        let names = git_header_names(&header[b"diff --git ".len()..], options.strip);
+        let (mut old_path, mut new_path) = match names {
+            Some((old, new)) => (Some(old), Some(new)),
+            None => (None, None),
+        };
+        let mut patch = Patch {
+            old_path: None,
+            new_path: None,
+            old_mode: None,
+            new_mode: None,
+            rewrite: None,
+            content: Content::Hunks(Vec::new()),
+        };
+        let (mut is_addition, mut is_deletion) = (false, false);
+        // Paths in these lines never have the `a/` and `b/` prefixes.
+        let rewrite_strip = options.strip.saturating_sub(1);
+
+        while let Some(line) = lines.peek() {
+            let line_number = lines.line_number;
+            let mode = |value: &[u8]| {
+                parse_mode(value).ok_or_else(|| Error::InvalidMode {
+                    line_number,
+                    line: line.into(),
+                })
+            };
+            if let Some(value) = line.strip_prefix(b"old mode ") {
+                patch.old_mode = Some(mode(value)?);
+            } else if let Some(value) = line.strip_prefix(b"new mode ") {
+                patch.new_mode = Some(mode(value)?);
+            } else if let Some(value) = line.strip_prefix(b"deleted file mode ") {
+                patch.old_mode = Some(mode(value)?);
+                is_deletion = true;
+            } else if let Some(value) = line.strip_prefix(b"new file mode ") {
+                patch.new_mode = Some(mode(value)?);
+                is_addition = true;
+            } else if let Some((rewrite, value)) = line
+                .strip_prefix(b"rename from ")
+                .map(|value| (Rewrite::Rename, value))
+                .or_else(|| line.strip_prefix(b"copy from ").map(|value| (Rewrite::Copy, value)))
+            {
+                patch.rewrite = Some(rewrite);
+                old_path = Some(path(value, rewrite_strip, line_number)?);
+            } else if let Some((rewrite, value)) = line
+                .strip_prefix(b"rename to ")
+                .map(|value| (Rewrite::Rename, value))
+                .or_else(|| line.strip_prefix(b"copy to ").map(|value| (Rewrite::Copy, value)))
+            {
+                patch.rewrite = Some(rewrite);
+                new_path = Some(path(value, rewrite_strip, line_number)?);
+            } else if let Some(value) = line.strip_prefix(b"index ") {
+                if let Some((_ids, value)) = value.split_once_str(" ") {
+                    let mode = mode(value)?;
+                    patch.old_mode = Some(mode);
+                    patch.new_mode = Some(mode);
+                }
+            } else if line.starts_with(b"similarity index ") || line.starts_with(b"dissimilarity index ") {
+            } else if line.starts_with(b"--- ") {
+                let (old, new) = parse_file_names(lines, options.strip)?;
+                old_path = old.or(old_path);
+                new_path = new.or(new_path);
+                patch.content = Content::Hunks(parse_hunks(lines)?);
+                break;
+            } else if line == b"GIT binary patch" {
+                let (binary, consumed) =
+                    gix_diff::blob::binary_patch::parse(&lines.input[lines.pos..]).map_err(|err| {
+                        Error::BinaryPatch {
+                            line_number,
+                            source: err,
+                        }
+                    })?;
+                let end = lines.pos + consumed;
+                while lines.pos < end {
+                    lines.next();
+                }
+                patch.content = Content::Binary(binary);
+                break;
+            } else if line.starts_with(b"Binary files ") {
+                lines.next();
+                patch.content = Content::BinaryWithoutData;
+                break;
+            } else {
+                break;
+            }
+            lines.next();
+        }
+
+        if old_path.is_none() && new_path.is_none() {
+            return Err(Error::MissingPath {
+                line_number: header_line_number,
+            });
+        }
+        patch.old_path = if is_addition {
+            None
+        } else {
+            old_path.or(new_path.clone())
+        };
+        patch.new_path = if is_deletion {
+            None
+        } else {
+            new_path.or(patch.old_path.clone())
+        };
+        if is_addition {
+            patch.old_mode = None;
+        }
+        if is_deletion {
+            patch.new_mode = None;
+        }
+        validate_locations(patch, header_line_number)
+    }
+
+    fn parse_traditional_patch(lines: &mut Lines<'_>, options: Options) -> Result<Patch, Error> {
+        let line_number = lines.line_number;
+        let (old_path, new_path) = parse_file_names(lines, options.strip)?;
+        if old_path.is_none() && new_path.is_none() {
+            return Err(Error::MissingPath { line_number });
+        }
+        let patch = Patch {
+            old_path,
+            new_path,
+            old_mode: None,
+            new_mode: None,
+            rewrite: None,
+            content: Content::Hunks(parse_hunks(lines)?),
+        };
+        validate_locations(patch, line_number)
+    }
+
+    /// Fail if a path of `patch`, which starts at `line_number`, [isn't valid](is_valid_location()).
+    fn validate_locations(patch: Patch, line_number: usize) -> Result<Patch, Error> {
+        if let Some(path) = [&patch.old_path, &patch.new_path]
+            .into_iter()
+            .flatten()
+            .find(|path| !is_valid_location(path.as_bstr()))
+        {
+            return Err(Error::InvalidPath {
+                line_number,
+                path: path.clone(),
+            });
+        }
+        Ok(patch)
+    }
+
+    /// Parse the `---` and `+++` lines, and return the paths they refer to, or `None` for `/dev/null`.
+    fn parse_file_names(lines: &mut Lines<'_>, strip: usize) -> Result<(Option<BString>, Option<BString>), Error> {
+        let mut name = |prefix: &[u8]| -> Result<Option<BString>, Error> {
+            let line_number = lines.line_number;
+            let line = lines.next().ok_or(Error::UnexpectedEof)?;
+            let value = line.strip_prefix(prefix).ok_or_else(|| Error::InvalidHunkLine {
+                line_number,
+                line: line.into(),
+            })?;
+            let value = match unquote(value) {
+                Some((value, _rest)) => value,
+                // Traditional patches may have a timestamp after the path, separated by a tab.
+                None => value.split_str("\t").next().unwrap_or(value).trim_end().into(),
+            };
+            if value == "/dev/null" {
+                return Ok(None);
+            }
+            path(&value, strip, line_number).map(Some)
+        };
+        Ok((name(b"--- ")?, name(b"+++ ")?))
+    }
+
+    fn parse_hunks(lines: &mut Lines<'_>) -> Result<Vec<Hunk>, Error> {
+        let mut hunks = Vec::new();
+        while let Some(header) = lines.peek().filter(|line| line.starts_with(b"@@ -")) {
+            let line_number = lines.line_number;
+            let mut hunk = parse_hunk_header(header).ok_or_else(|| Error::InvalidHunkHeader {
+                line_number,
+                line: header.into(),
+            })?;
+            lines.next();
+
+            let (mut old_remaining, mut new_remaining) = (hunk.old_len, hunk.new_len);
+            while old_remaining > 0 || new_remaining > 0 {
+                let line_number = lines.line_number;
+                let line = lines.next_with_newline().ok_or(Error::UnexpectedEof)?;
+                let invalid = || Error::InvalidHunkLine {
+                    line_number,
+                    line: trim_newline(line).into(),
+                };
+                let (kind, content) = match line.first() {
+                    Some(b' ') => (b' ', &line[1..]),
+                    // Empty context lines may have lost their leading space, for instance when sent by email.
+                    Some(b'\n') => (b' ', line),
+                    Some(b'-') => (b'-', &line[1..]),
+                    Some(b'+') => (b'+', &line[1..]),
+                    Some(b'\\') => {
+                        remove_newline_of_last_line(&mut hunk);
+                        continue;
+                    }
+                    _ => return Err(invalid()),
+                };
+                let content = BString::from(content);
+                match kind {
+                    b' ' if old_remaining > 0 && new_remaining > 0 => {
+                        old_remaining -= 1;
+                        new_remaining -= 1;
+                        hunk.lines.push(Line::Context(content));
+                    }
+                    b'-' if old_remaining > 0 => {
+                        old_remaining -= 1;
+                        hunk.lines.push(Line::Removal(content));
+                    }
+                    b'+' if new_remaining > 0 => {
+                        new_remaining -= 1;
+                        hunk.lines.push(Line::Addition(content));
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+            if matches!(lines.peek(), Some(line) if line.starts_with(b"\\")) {
+                lines.next();
+                remove_newline_of_last_line(&mut hunk);
+            }
+            hunks.push(hunk);
+        }
+        Ok(hunks)
+    }
+
+    /// Handle a `\ No newline at end of file` line, which refers to the line before it.
+    fn remove_newline_of_last_line(hunk: &mut Hunk) {
+        if let Some(line) = hunk.lines.last_mut() {
+            let content = match line {
+                Line::Context(line) | Line::Removal(line) | Line::Addition(line) => line,
+            };
+            if content.last() == Some(&b'\n') {
+                content.pop();
+            }
+        }
+    }
+
+    /// Parse a line like `@@ -1,2 +1,3 @@ optional section heading`.
+    fn parse_hunk_header(line: &[u8]) -> Option<Hunk> {
+        let header = HunkHeader::parse(line)?;
+        Some(Hunk {
+            old_start: header.before_hunk_start,
+            old_len: header.before_hunk_len,
+            new_start: header.after_hunk_start,
+            new_len: header.after_hunk_len,
+            lines: Vec::new(),
+        })
+    }
+
+    fn parse_mode(value: &[u8]) -> Option<EntryKind> {
+        let mode = u32::from_str_radix(value.to_str().ok()?.trim(), 8).ok()?;
+        Some(EntryMode::try_from(mode).ok()?.kind())
+    }
+
+    /// Remove `strip` leading components from `path`.
+    fn path(path: &[u8], strip: usize, line_number: usize) -> Result<BString, Error> {
+        strip_components(path, strip).ok_or_else(|| Error::InvalidPath {
+            line_number,
+            path: path.into(),
+        })
+    }
+
+    fn strip_components(path: &[u8], strip: usize) -> Option<BString> {
+        let mut rest = path;
+        for _ in 0..strip {
+            let pos = rest.find_byte(b'/')?;
+            rest = &rest[pos + 1..];
+        }
+        (!rest.is_empty()).then(|| rest.into())
+    }
+
+    /// Obtain the old and new path from the rest of a `diff --git a/<old> b/<new>` line, which is only possible if both
+    /// are quoted, or if they are the same once `strip` leading components are removed as there is no way to tell
+    /// where unquoted paths with spaces end otherwise. This is the same way `git` does it.
+    fn git_header_names(rest: &[u8], strip: usize) -> Option<(BString, BString)> {
+        if let Some((old, rest)) = unquote(rest) {
+            let rest = rest.strip_prefix(b" ")?;
+            let new = match unquote(rest) {
+                Some((new, _)) => new,
+                None => rest.into(),
+            };
+            return Some((strip_components(&old, strip)?, strip_components(&new, strip)?));
+        }
+        if let Some(pos) = rest.find(b" \"") {
+            let (new, _) = unquote(&rest[pos + 1..])?;
+            return Some((strip_components(&rest[..pos], strip)?, strip_components(&new, strip)?));
+        }
+        rest.iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b' ')
+            .find_map(|(pos, _)| {
+                let old = strip_components(&rest[..pos], strip)?;
+                let new = strip_components(&rest[pos + 1..], strip)?;
+                (old == new).then_some((old, new))
+            })
+    }
+
+    /// Unquote the C-style quoted string at the beginning of `input` and return it along with the rest of `input`,
+    /// or return `None` if `input` doesn't start with a quote or isn't quoted correctly.
+    fn unquote(input: &[u8]) -> Option<(BString, &[u8])> {
+        let mut rest = input.strip_prefix(b"\"")?;
+        let mut out = BString::default();
+        loop {
+            let (&byte, tail) = rest.split_first()?;
+            rest = tail;
+            match byte {
+                b'"' => return Some((out, rest)),
+                b'\\' => {
+                    let (&escaped, tail) = rest.split_first()?;
+                    rest = tail;
+                    out.push_byte(match escaped {
+                        b'a' => 0x07,
+                        b'b' => 0x08,
+                        b'f' => 0x0c,
+                        b'n' => b'\n',
+                        b'r' => b'\r',
+                        b't' => b'\t',
+                        b'v' => 0x0b,
+                        b'0'..=b'3' => {
+                            let digits = rest.get(..2)?;
+                            if !digits.iter().all(|b| (b'0'..=b'7').contains(b)) {
+                                return None;
+                            }
+                            rest = &rest[2..];
+                            ((escaped - b'0') << 6) | ((digits[0] - b'0') << 3) | (digits[1] - b'0')
+                        }
+                        other => other,
+                    });
+                }
+                other => out.push_byte(other),
+            }
+        }
+    }
+}
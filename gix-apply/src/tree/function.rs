@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_hash::ObjectId;
+use gix_object::{
+    tree::{EntryKind, EntryMode},
+    FindExt,
+};
+
+use crate::{tree::apply::Error, Entry, Options, Patch};
+
+/// Apply all `patches` in order to `tree`, and write the resulting tree along with all of its subtrees and blobs using
+/// `write_object` to return its id, with `options` controlling how hunks are matched.
+///
+/// `objects` provides access to all trees and blobs. Submodules are patched just like `git` does it, by treating them as
+/// a file containing `Subproject commit <id>`.
+pub fn apply<E>(
+    tree: &gix_hash::oid,
+    patches: &[Patch],
+    objects: &impl gix_object::Find,
+    mut write_object: impl FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, E>,
+    options: Options,
+) -> Result<ObjectId, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut buf = Vec::new();
+    let mut entries = flatten_tree(tree, objects, &mut buf)?;
+    let changes = crate::apply(
+        patches,
+        |location| -> Result<_, gix_object::find::existing_object::Error> {
+            let (mode, id) = match entries.get(location) {
+                Some(entry) => *entry,
+                None => return Ok(None),
+            };
+            let kind = mode.kind();
+            let data = if kind == EntryKind::Commit {
+                format!("Subproject commit {id}\n").into_bytes()
+            } else {
+                objects.find_blob(&id, &mut buf)?.data.to_owned()
+            };
+            Ok(Some(Entry { mode: kind, data }))
+        },
+        options,
+    )?;
+
+    let mut write = |object: &dyn gix_object::WriteTo| -> Result<ObjectId, Error> {
+        write_object(object).map_err(|err| Error::WriteObject(Box::new(err)))
+    };
+    for change in changes {
+        let Some(Entry { mode, data }) = change.entry else {
+            entries.remove(&change.location);
+            continue;
+        };
+        let id = if mode == EntryKind::Commit {
+            submodule_commit(&data).ok_or_else(|| Error::InvalidSubmodule {
+                location: change.location.clone(),
+            })?
+        } else {
+            write(&gix_object::BlobRef { data: &data })?
+        };
+        entries.insert(change.location, (mode.into(), id));
+    }
+
+    for location in entries.keys() {
+        for pos in location.find_iter(b"/") {
+            let directory = location[..pos].as_bstr();
+            if entries.contains_key(directory) {
+                return Err(Error::FileDirectoryConflict {
+                    location: directory.to_owned(),
+                });
+            }
+        }
+    }
+    let entries: Vec<_> = entries
+        .iter()
+        .map(|(location, entry)| (location.as_bstr(), *entry))
+        .collect();
+    write_tree(&entries, &mut write)
+}
+
+/// Parse the content of a patched submodule, which is `Subproject commit <id>`.
+fn submodule_commit(data: &[u8]) -> Option<ObjectId> {
+    let hex = data.strip_prefix(b"Subproject commit ")?.trim_end();
+    ObjectId::from_hex(hex).ok()
+}
+
+/// Collect all non-tree entries of the tree with `id`, recursively, keyed by their location.
+fn flatten_tree(
+    id: &gix_hash::oid,
+    objects: &impl gix_object::Find,
+    buf: &mut Vec<u8>,
+) -> Result<BTreeMap<BString, (EntryMode, ObjectId)>, Error> {
+    let mut out = BTreeMap::new();
+    let mut trees = vec![(BString::default(), id.to_owned())];
+    while let Some((prefix, id)) = trees.pop() {
+        if id.is_empty_tree() {
+            continue;
+        }
+        for entry in &objects.find_tree(&id, buf)?.entries {
+            let mut location = prefix.clone();
+            if !location.is_empty() {
+                location.push_byte(b'/');
+            }
+            location.push_str(entry.filename);
+            let (mode, id) = (EntryMode::from(entry.mode.kind()), entry.oid.to_owned());
+            if mode.is_tree() {
+                trees.push((location, id));
+            } else {
+                out.insert(location, (mode, id));
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Write the tree with `entries`, sorted by location, along with all of its subtrees.
+fn write_tree(
+    entries: &[(&BStr, (EntryMode, ObjectId))],
+    write: &mut dyn FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, Error>,
+) -> Result<ObjectId, Error> {
+    let mut tree = gix_object::Tree::empty();
+    let mut entries = entries;
+    while let Some(((location, (mode, id)), rest)) = entries.split_first() {
+        match location.find_byte(b'/') {
+            None => {
+                tree.entries.push(gix_object::tree::Entry {
+                    mode: *mode,
+                    filename: (*location).to_owned(),
+                    oid: *id,
+                });
+                entries = rest;
+            }
+            Some(pos) => {
+                let directory = &location[..=pos];
+                let len = entries
+                    .iter()
+                    .take_while(|(location, _)| location.starts_with(directory))
+                    .count();
+                let subtree: Vec<_> = entries[..len]
+                    .iter()
+                    .map(|(location, entry)| (location[directory.len()..].as_bstr(), *entry))
+                    .collect();
+                tree.entries.push(gix_object::tree::Entry {
+                    mode: EntryKind::Tree.into(),
+                    filename: location[..pos].into(),
+                    oid: write_tree(&subtree, write)?,
+                });
+                entries = &entries[len..];
+            }
+        }
+    }
+    tree.entries.sort();
+    write(&tree)
+}
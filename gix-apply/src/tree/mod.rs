@@ -0,0 +1,26 @@
+//! Apply patches to a tree without the need for a worktree or an index, like `git apply --cached` does for the index.
+
+mod function;
+pub use function::apply;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    use bstr::BString;
+
+    /// The error returned by [`apply()`](super::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindExistingObject(#[from] gix_object::find::existing_object::Error),
+        #[error(transparent)]
+        Apply(#[from] crate::apply::Error),
+        #[error("The patched submodule at '{location}' doesn't refer to a commit")]
+        InvalidSubmodule { location: BString },
+        #[error("'{location}' would be both a file and a directory")]
+        FileDirectoryConflict { location: BString },
+        #[error("Could not write a patched object")]
+        WriteObject(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use bstr::{BStr, BString};
+use gix_apply::{Change, Entry, Options};
+use gix_object::tree::EntryKind;
+
+mod blob;
+mod patch;
+
+fn parse(input: &str) -> Vec<gix_apply::Patch> {
+    gix_apply::patch::parse(input.as_bytes(), Default::default()).expect("valid patch")
+}
+
+fn apply(
+    patches: &[gix_apply::Patch],
+    files: &[(&str, EntryKind, &str)],
+) -> Result<Vec<Change>, gix_apply::apply::Error> {
+    let files: BTreeMap<BString, Entry> = files
+        .iter()
+        .map(|(location, mode, data)| {
+            (
+                (*location).into(),
+                Entry {
+                    mode: *mode,
+                    data: data.as_bytes().to_owned(),
+                },
+            )
+        })
+        .collect();
+    gix_apply::apply(
+        patches,
+        |location: &BStr| -> Result<_, std::convert::Infallible> { Ok(files.get(location).cloned()) },
+        Options::default(),
+    )
+}
+
+fn change(location: &str, entry: Option<(EntryKind, &str)>) -> Change {
+    Change {
+        location: location.into(),
+        entry: entry.map(|(mode, data)| Entry {
+            mode,
+            data: data.as_bytes().to_owned(),
+        }),
+    }
+}
+
+#[test]
+fn modifications_additions_deletions_and_renames() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse(
+        "diff --git a/a b/a
+index 0719398..0d3c4a3 100644
+--- a/a
++++ b/a
+@@ -1,3 +1,3 @@
+ 1
+-2
++two
+ 3
+diff --git a/gone b/gone
+deleted file mode 100644
+index 3e75765..0000000
+--- a/gone
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+diff --git a/n b/n
+new file mode 100755
+index 0000000..3e75765
+--- /dev/null
++++ b/n
+@@ -0,0 +1 @@
++new
+diff --git a/sp ace b/new name
+similarity index 100%
+rename from sp ace
+rename to new name
+",
+    );
+    let changes = apply(
+        &patches,
+        &[
+            ("a", EntryKind::Blob, "1\n2\n3\n"),
+            ("gone", EntryKind::Blob, "gone\n"),
+            ("sp ace", EntryKind::Link, "target"),
+        ],
+    )?;
+    assert_eq!(
+        changes,
+        [
+            change("a", Some((EntryKind::Blob, "1\ntwo\n3\n"))),
+            change("gone", None),
+            change("n", Some((EntryKind::BlobExecutable, "new\n"))),
+            change("new name", Some((EntryKind::Link, "target"))),
+            change("sp ace", None),
+        ],
+        "renames keep the kind of the file unless it's changed"
+    );
+    Ok(())
+}
+
+#[test]
+fn later_patches_see_the_changes_of_earlier_ones() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse(
+        "--- a/a
++++ b/a
+@@ -1 +1 @@
+-1
++2
+--- a/a
++++ b/a
+@@ -1 +1 @@
+-2
++3
+diff --git a/a b/b
+copy from a
+copy to b
+",
+    );
+    let changes = apply(&patches, &[("a", EntryKind::Blob, "1\n")])?;
+    assert_eq!(
+        changes,
+        [
+            change("a", Some((EntryKind::Blob, "3\n"))),
+            change("b", Some((EntryKind::Blob, "3\n"))),
+        ],
+        "copies keep their source"
+    );
+    Ok(())
+}
+
+#[test]
+fn binary_patches() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse(
+        "diff --git a/bin b/bin
+index 677273046bce3115f56c248238f3b83f77cfc239..f94e94b0c923986186c01b9a7d5b9760c724f749 100644
+GIT binary patch
+literal 7
+OcmZQzWJ=1+GXeku=mBp4
+
+literal 6
+NcmZQzWJ=1+0{{Yf0X+Z!
+
+",
+    );
+    let changes = apply(&patches, &[("bin", EntryKind::Blob, "\0\x01\x02bin")])?;
+    assert_eq!(changes, [change("bin", Some((EntryKind::Blob, "\0\x01\x02bin2")))]);
+
+    let patches = parse(
+        "diff --git a/bin b/bin
+index 6772730..f94e94b 100644
+Binary files a/bin and b/bin differ
+",
+    );
+    assert!(matches!(
+        apply(&patches, &[("bin", EntryKind::Blob, "\0\x01\x02bin")]),
+        Err(gix_apply::apply::Error::BinaryWithoutData { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let patches = parse("--- a/a\n+++ b/a\n@@ -1 +1 @@\n-1\n+2\n");
+    assert!(matches!(
+        apply(&patches, &[]),
+        Err(gix_apply::apply::Error::MissingFile { location }) if location == "a"
+    ));
+    assert!(matches!(
+        apply(&patches, &[("a", EntryKind::Blob, "3\n")]),
+        Err(gix_apply::apply::Error::ApplyHunks { location, .. }) if location == "a"
+    ));
+
+    let patches = parse("--- /dev/null\n+++ b/a\n@@ -0,0 +1 @@\n+1\n");
+    assert!(matches!(
+        apply(&patches, &[("a", EntryKind::Blob, "1\n")]),
+        Err(gix_apply::apply::Error::AlreadyExists { location }) if location == "a"
+    ));
+
+    let patches = parse("--- a/a\n+++ /dev/null\n@@ -1 +0,0 @@\n-1\n");
+    assert!(matches!(
+        apply(&patches, &[("a", EntryKind::Blob, "1\n2\n")]),
+        Err(gix_apply::apply::Error::DeletionLeavesContent { location }) if location == "a"
+    ));
+}
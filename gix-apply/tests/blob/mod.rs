@@ -0,0 +1,186 @@
+use gix_apply::{
+    blob::apply,
+    patch::{Content, Hunk},
+    Options, Whitespace,
+};
+
+fn hunks(patch: &str) -> Vec<Hunk> {
+    let mut patches = gix_apply::patch::parse(patch.as_bytes(), Default::default()).expect("valid patch");
+    match patches.pop().expect("one patch").content {
+        Content::Hunks(hunks) => hunks,
+        _ => unreachable!("text patch"),
+    }
+}
+
+fn apply_str(content: &str, patch: &str, options: Options) -> Result<String, apply::Error> {
+    apply(content.as_bytes(), &hunks(patch), options).map(|out| String::from_utf8(out).expect("valid UTF-8"))
+}
+
+const BASE: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+
+#[test]
+fn hunks_apply_where_they_are_expected() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -1,3 +1,3 @@
+-1
++one
+ 2
+ 3
+@@ -7,4 +7,5 @@
+ 7
+ 8
+-9
++nine
++nine and a half
+ 10
+";
+    assert_eq!(
+        apply_str(BASE, patch, Options::default())?,
+        "one\n2\n3\n4\n5\n6\n7\n8\nnine\nnine and a half\n10\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn hunks_apply_to_moved_lines() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -4,3 +4,3 @@
+ 4
+-5
++five
+ 6
+";
+    assert_eq!(
+        apply_str(&format!("0\n0\n{BASE}"), patch, Options::default())?,
+        "0\n0\n1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n",
+        "lines that moved down are found"
+    );
+    assert_eq!(
+        apply_str(&BASE[4..], patch, Options::default())?,
+        "3\n4\nfive\n6\n7\n8\n9\n10\n",
+        "lines that moved up are found"
+    );
+    Ok(())
+}
+
+#[test]
+fn anchored_hunks_must_match_at_the_beginning_or_end() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -1,2 +1,2 @@
+-1
++one
+ 2
+";
+    assert!(matches!(
+        apply_str(&format!("0\n{BASE}"), patch, Options::default()),
+        Err(apply::Error::HunkDoesNotApply { hunk: 0, old_start: 1 })
+    ));
+
+    let patch = "--- a/f
++++ b/f
+@@ -9,2 +9,2 @@
+ 9
+-10
++ten
+";
+    assert!(matches!(
+        apply_str(&format!("{BASE}11\n"), patch, Options::default()),
+        Err(apply::Error::HunkDoesNotApply { hunk: 0, .. })
+    ));
+    assert_eq!(
+        apply_str(
+            &format!("{BASE}11\n"),
+            patch,
+            Options {
+                fuzz: 1,
+                ..Default::default()
+            }
+        )?,
+        "1\n2\n3\n4\n5\n6\n7\n8\n9\nten\n11\n",
+        "with fuzz, the hunk isn't anchored anymore"
+    );
+    Ok(())
+}
+
+#[test]
+fn fuzz_ignores_context_lines() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -3,5 +3,5 @@
+ 3
+ 4
+-5
++five
+ 6
+ X
+";
+    assert!(apply_str(BASE, patch, Options::default()).is_err());
+    assert_eq!(
+        apply_str(
+            BASE,
+            patch,
+            Options {
+                fuzz: 1,
+                ..Default::default()
+            }
+        )?,
+        "1\n2\n3\n4\nfive\n6\n7\n8\n9\n10\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn whitespace_changes_can_be_ignored() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -1,3 +1,3 @@
+ a  b
+-c
++C
+ d
+";
+    let content = "a\tb \nc\nd\t\n";
+    assert!(apply_str(content, patch, Options::default()).is_err());
+    assert!(
+        apply_str(
+            "ab\nc\nd\n",
+            patch,
+            Options {
+                whitespace: Whitespace::IgnoreChanges,
+                ..Default::default()
+            }
+        )
+        .is_err(),
+        "whitespace can't be removed entirely"
+    );
+    assert_eq!(
+        apply_str(
+            content,
+            patch,
+            Options {
+                whitespace: Whitespace::IgnoreChanges,
+                ..Default::default()
+            }
+        )?,
+        "a\tb \nC\nd\t\n",
+        "context lines keep their original whitespace"
+    );
+    Ok(())
+}
+
+#[test]
+fn missing_newline_at_end_of_file() -> Result<(), apply::Error> {
+    let patch = "--- a/f
++++ b/f
+@@ -1,2 +1,2 @@
+ 1
+-2
+\\ No newline at end of file
++2
+";
+    assert_eq!(apply_str("1\n2", patch, Options::default())?, "1\n2\n");
+    Ok(())
+}
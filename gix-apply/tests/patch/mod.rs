@@ -0,0 +1,263 @@
+use gix_apply::patch::{parse, parse::Options, Content, Hunk, Line, Patch, Rewrite};
+use gix_object::tree::EntryKind;
+
+fn parse_with_defaults(input: &str) -> Result<Vec<Patch>, parse::Error> {
+    parse(input.as_bytes(), Options::default())
+}
+
+#[test]
+fn git_patch_with_modes_renames_and_hunks() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse_with_defaults(
+        "From 1234 Mon Sep 17 00:00:00 2001
+Subject: [PATCH] a message that is skipped
+
+---
+ a | 2 +-
+ 1 file changed
+
+diff --git a/a b/a
+index 0719398..0d3c4a3 100644
+--- a/a
++++ b/a
+@@ -1,3 +1,3 @@ section heading
+ 1
+-2
+
++two
+\\ No newline at end of file
+diff --git a/sp ace b/new name
+old mode 100644
+new mode 100755
+similarity index 90%
+rename from sp ace
+rename to new name
+index 1234567..89abcde
+--- a/sp ace
++++ b/new name
+@@ -1 +1 @@
+-x
++y
+diff --git a/n b/n
+new file mode 120000
+index 0000000..3e75765
+--- /dev/null
++++ b/n
+@@ -0,0 +1 @@
++target
+\\ No newline at end of file
+diff --git a/gone b/gone
+deleted file mode 100644
+index 3e75765..0000000
+--- a/gone
++++ /dev/null
+@@ -1 +0,0 @@
+-gone
+-- 
+2.45.0
+",
+    )?;
+    assert_eq!(
+        patches,
+        [
+            Patch {
+                old_path: Some("a".into()),
+                new_path: Some("a".into()),
+                old_mode: Some(EntryKind::Blob),
+                new_mode: Some(EntryKind::Blob),
+                rewrite: None,
+                content: Content::Hunks(vec![Hunk {
+                    old_start: 1,
+                    old_len: 3,
+                    new_start: 1,
+                    new_len: 3,
+                    lines: vec![
+                        Line::Context("1\n".into()),
+                        Line::Removal("2\n".into()),
+                        Line::Context("\n".into()),
+                        Line::Addition("two".into()),
+                    ]
+                }])
+            },
+            Patch {
+                old_path: Some("sp ace".into()),
+                new_path: Some("new name".into()),
+                old_mode: Some(EntryKind::Blob),
+                new_mode: Some(EntryKind::BlobExecutable),
+                rewrite: Some(Rewrite::Rename),
+                content: Content::Hunks(vec![Hunk {
+                    old_start: 1,
+                    old_len: 1,
+                    new_start: 1,
+                    new_len: 1,
+                    lines: vec![Line::Removal("x\n".into()), Line::Addition("y\n".into())]
+                }])
+            },
+            Patch {
+                old_path: None,
+                new_path: Some("n".into()),
+                old_mode: None,
+                new_mode: Some(EntryKind::Link),
+                rewrite: None,
+                content: Content::Hunks(vec![Hunk {
+                    old_start: 0,
+                    old_len: 0,
+                    new_start: 1,
+                    new_len: 1,
+                    lines: vec![Line::Addition("target".into())]
+                }])
+            },
+            Patch {
+                old_path: Some("gone".into()),
+                new_path: None,
+                old_mode: Some(EntryKind::Blob),
+                new_mode: None,
+                rewrite: None,
+                content: Content::Hunks(vec![Hunk {
+                    old_start: 1,
+                    old_len: 1,
+                    new_start: 0,
+                    new_len: 0,
+                    lines: vec![Line::Removal("gone\n".into())]
+                }])
+            },
+        ],
+        "empty lines are context lines, and text around the patches is ignored"
+    );
+    assert!(patches[2].is_addition());
+    assert!(patches[3].is_deletion());
+    Ok(())
+}
+
+#[test]
+fn mode_changes_and_binary_patches_without_hunks() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse_with_defaults(
+        "diff --git a/x b/x
+old mode 100644
+new mode 100755
+diff --git a/bin b/bin
+index 677273046bce3115f56c248238f3b83f77cfc239..f94e94b0c923986186c01b9a7d5b9760c724f749 100644
+GIT binary patch
+literal 7
+OcmZQzWJ=1+GXeku=mBp4
+
+literal 6
+NcmZQzWJ=1+0{{Yf0X+Z!
+
+diff --git a/other b/other
+index 6772730..f94e94b 100644
+Binary files a/other and b/other differ
+",
+    )?;
+    assert_eq!(patches.len(), 3);
+    assert_eq!(patches[0].old_path.as_ref().expect("set"), "x");
+    assert_eq!(patches[0].new_mode, Some(EntryKind::BlobExecutable));
+    assert_eq!(patches[0].content, Content::Hunks(Vec::new()));
+    match &patches[1].content {
+        Content::Binary(binary) => assert_eq!(binary.forward.data, b"\0\x01\x02bin2"),
+        other => unreachable!("expected binary patch, got {other:?}"),
+    }
+    assert_eq!(patches[2].new_path.as_ref().expect("set"), "other");
+    assert_eq!(patches[2].content, Content::BinaryWithoutData);
+    Ok(())
+}
+
+#[test]
+fn quoted_paths_and_traditional_patches() -> Result<(), Box<dyn std::error::Error>> {
+    let patches = parse_with_defaults(
+        "diff --git \"a/t\\303\\244b\\t\" \"b/t\\303\\244b\\t\"
+--- \"a/t\\303\\244b\\t\"
++++ \"b/t\\303\\244b\\t\"
+@@ -1 +1 @@
+-a
++b
+--- dir/old.txt\t2024-01-01 00:00:00.000000000 +0100
++++ dir/new.txt\t2024-01-01 00:00:00.000000000 +0100
+@@ -1 +1,2 @@
+ a
++b
+",
+    )?;
+    assert_eq!(patches[0].old_path.as_ref().expect("set"), "täb\t");
+    assert_eq!(patches[0].new_path.as_ref().expect("set"), "täb\t");
+    assert_eq!(patches[1].old_path.as_ref().expect("set"), "old.txt");
+    assert_eq!(
+        patches[1].new_path.as_ref().expect("set"),
+        "new.txt",
+        "one leading component is stripped by default"
+    );
+
+    let patches = parse(b"--- a/dir/a\n+++ b/dir/a\n@@ -1 +1 @@\n-a\n+b\n", Options { strip: 2 })?;
+    assert_eq!(patches[0].old_path.as_ref().expect("set"), "a");
+    assert!(matches!(
+        parse(b"--- a\n+++ b\n@@ -1 +1 @@\n-a\n+b\n", Options { strip: 1 }),
+        Err(parse::Error::InvalidPath { line_number: 1, .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn paths_leaving_the_worktree_or_within_git_dirs_are_rejected() {
+    for (input, expected_path) in [
+        (
+            "diff --git a/../../x b/../../x\n--- a/../../x\n+++ b/../../x\n",
+            "../../x",
+        ),
+        ("diff --git a/dir/../x b/dir/../x\nnew file mode 100644\n", "dir/../x"),
+        ("diff --git a/./x b/./x\nnew file mode 100644\n", "./x"),
+        ("--- /dev/null\n+++ b//etc/passwd\n", "/etc/passwd"),
+        ("--- /dev/null\n+++ b/dir//x\n", "dir//x"),
+        ("--- /dev/null\n+++ b/dir/\n", "dir/"),
+        (
+            "diff --git a/.git/hooks/post-checkout b/.git/hooks/post-checkout\nnew file mode 100755\n",
+            ".git/hooks/post-checkout",
+        ),
+        (
+            "diff --git a/sub/.GIT/config b/sub/.GIT/config\ndeleted file mode 100644\n",
+            "sub/.GIT/config",
+        ),
+        (
+            "diff --git a/x b/y\nsimilarity index 100%\nrename from x\nrename to ../y\n",
+            "../y",
+        ),
+    ] {
+        match parse_with_defaults(input) {
+            Err(parse::Error::InvalidPath { line_number: 1, path }) => assert_eq!(path, expected_path, "{input:?}"),
+            res => panic!("{input:?} should have been rejected, but got {res:?}"),
+        }
+    }
+
+    let patches = parse_with_defaults("--- /dev/null\n+++ b/dir/.gitignore\n@@ -0,0 +1 @@\n+a\n").expect("valid");
+    assert_eq!(
+        patches[0].new_path.as_ref().expect("set"),
+        "dir/.gitignore",
+        "only `.git` itself is special"
+    );
+}
+
+#[test]
+fn errors() {
+    assert!(matches!(
+        parse_with_defaults("just a message\n"),
+        Err(parse::Error::NoPatch)
+    ));
+    assert!(matches!(
+        parse_with_defaults("--- a/a\n+++ b/a\n@@ -1 +1 @@\n-a\n"),
+        Err(parse::Error::UnexpectedEof)
+    ));
+    assert!(matches!(
+        parse_with_defaults("--- a/a\n+++ b/a\n@@ -1 +1 @@\n-a\n*b\n"),
+        Err(parse::Error::InvalidHunkLine { line_number: 5, .. })
+    ));
+    assert!(matches!(
+        parse_with_defaults("--- a/a\n+++ b/a\n@@ -x +1 @@\n"),
+        Err(parse::Error::InvalidHunkHeader { line_number: 3, .. })
+    ));
+    assert!(matches!(
+        parse_with_defaults("diff --git a/a b/a\nold mode 999\n"),
+        Err(parse::Error::InvalidMode { line_number: 2, .. })
+    ));
+    assert!(matches!(
+        parse_with_defaults("diff --git a/a b/a\nindex 1..2 100644\nGIT binary patch\nliteral x\n"),
+        Err(parse::Error::BinaryPatch { line_number: 3, .. })
+    ));
+}
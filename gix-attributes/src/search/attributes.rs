@@ -89,6 +89,17 @@ impl Search {
         collection.update_from_list(last);
     }
 
+    /// Add an already parsed pattern `list`, like one previously obtained with [`last_pattern_list()`](Self::last_pattern_list()),
+    /// and update `collection` with the attribute names it contains.
+    pub fn add_pattern_list(
+        &mut self,
+        mut list: gix_glob::search::pattern::List<Attributes>,
+        collection: &mut MetadataCollection,
+    ) {
+        collection.update_from_list(&mut list);
+        self.patterns.push(list);
+    }
+
     /// Pop the last attribute patterns list from our queue.
     pub fn pop_pattern_list(&mut self) -> Option<gix_glob::search::pattern::List<Attributes>> {
         self.patterns.pop()
@@ -119,6 +130,11 @@ impl Search {
     pub fn num_pattern_lists(&self) -> usize {
         self.patterns.len()
     }
+
+    /// Return the pattern list that was added last, if there is one.
+    pub fn last_pattern_list(&self) -> Option<&gix_glob::search::pattern::List<Attributes>> {
+        self.patterns.last()
+    }
 }
 
 impl Pattern for Attributes {
@@ -50,8 +50,75 @@ pub fn decode(data: &[u8]) -> Result<(Vec, &[u8]), decode::Error> {
     ))
 }
 
+mod encode {
+    use super::{Vec, RLW_LARGEST_LITERAL_COUNT, RLW_LARGEST_RUNNING_COUNT, RLW_RUNNING_BITS};
+
+    impl Vec {
+        /// Create a new instance holding `num_bits`, of which all bits whose index is in `set_bits` are set to true.
+        ///
+        /// Indices that are out of bounds are ignored.
+        pub fn from_set_bits(num_bits: usize, set_bits: impl IntoIterator<Item = usize>) -> Self {
+            let mut words = vec![0u64; (num_bits + 63) / 64];
+            for index in set_bits.into_iter().filter(|index| *index < num_bits) {
+                words[index / 64] |= 1 << (index % 64);
+            }
+
+            let mut bits = std::vec::Vec::with_capacity(words.len() + 1);
+            let mut pos = 0;
+            let rlw = loop {
+                let rlw = bits.len();
+                let run_bit = matches!(words.get(pos), Some(word) if *word == u64::MAX);
+                let clean_word = if run_bit { u64::MAX } else { 0 };
+                let mut run_len = 0;
+                while pos < words.len() && words[pos] == clean_word && run_len < RLW_LARGEST_RUNNING_COUNT {
+                    run_len += 1;
+                    pos += 1;
+                }
+                let literals_start = pos;
+                while pos < words.len()
+                    && words[pos] != 0
+                    && words[pos] != u64::MAX
+                    && ((pos - literals_start) as u64) < RLW_LARGEST_LITERAL_COUNT
+                {
+                    pos += 1;
+                }
+                bits.push(
+                    u64::from(run_bit) | run_len << 1 | ((pos - literals_start) as u64) << (1 + RLW_RUNNING_BITS),
+                );
+                bits.extend_from_slice(&words[literals_start..pos]);
+                if pos == words.len() {
+                    break rlw;
+                }
+            };
+            Vec {
+                num_bits: num_bits.try_into().expect("no more than 4 billion bits"),
+                bits,
+                rlw: rlw as u64,
+            }
+        }
+
+        /// Serialize this instance to `out` in the format understood by [`decode()`](super::decode()).
+        pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+            out.write_all(&self.num_bits.to_be_bytes())?;
+            out.write_all(
+                &u32::try_from(self.bits.len())
+                    .expect("no more than 4 billion words")
+                    .to_be_bytes(),
+            )?;
+            for word in &self.bits {
+                out.write_all(&word.to_be_bytes())?;
+            }
+            out.write_all(
+                &u32::try_from(self.rlw)
+                    .expect("run length word offsets fit into 32 bits")
+                    .to_be_bytes(),
+            )
+        }
+    }
+}
+
 mod access {
-    use super::Vec;
+    use super::{Vec, RLW_LARGEST_RUNNING_COUNT, RLW_RUNNING_BITS};
 
     impl Vec {
         /// Call `f(index)` for each bit that is true, given the index of the bit that identifies it uniquely within the bit array.
@@ -112,13 +179,14 @@ mod access {
     fn rlw_runbit_is_set(w: &u64) -> bool {
         w & 1 == 1
     }
-
-    const RLW_RUNNING_BITS: u64 = 4 * 8;
-    const RLW_LARGEST_RUNNING_COUNT: u64 = (1 << RLW_RUNNING_BITS) - 1;
 }
 
+const RLW_RUNNING_BITS: u64 = 4 * 8;
+const RLW_LITERAL_BITS: u64 = 64 - 1 - RLW_RUNNING_BITS;
+const RLW_LARGEST_RUNNING_COUNT: u64 = (1 << RLW_RUNNING_BITS) - 1;
+const RLW_LARGEST_LITERAL_COUNT: u64 = (1 << RLW_LITERAL_BITS) - 1;
+
 /// A growable collection of u64 that are seen as stream of individual bits.
-#[allow(dead_code)]
 #[derive(Clone)]
 pub struct Vec {
     num_bits: u32,
@@ -0,0 +1,18 @@
+use bstr::BString;
+
+/// The error returned by [`file()`](crate::file()) and [`incremental()`](crate::incremental()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The file to blame at '{file_path}' wasn't found in the tree of commit {commit_id}")]
+    FileMissing {
+        file_path: BString,
+        commit_id: gix_hash::ObjectId,
+    },
+    #[error(transparent)]
+    FindCommit(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    FindObject(#[from] gix_object::find::existing_object::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+}
@@ -0,0 +1,336 @@
+use std::{borrow::Cow, collections::BinaryHeap, ops::Range};
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_diff::blob::{intern::InternedInput, sources::byte_lines_with_terminator};
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+
+use crate::{BlameEntry, Error, Options, Outcome, Statistics};
+
+/// Lines of the blamed file which are yet to be attributed to a commit.
+#[derive(Debug, Clone)]
+struct UnblamedHunk {
+    /// The lines in the blamed file.
+    range_in_blamed_file: Range<u32>,
+    /// The zero-based index of the first of these lines in the version of the file of the suspect commit.
+    suspect_start: u32,
+}
+
+/// A commit which may have introduced the lines of its `hunks`.
+struct Suspect {
+    /// The id of the file in the tree of the commit.
+    blob_id: ObjectId,
+    hunks: Vec<UnblamedHunk>,
+}
+
+/// A parent of a suspect commit.
+struct Parent {
+    id: ObjectId,
+    commit_time: gix_date::SecondsSinceUnixEpoch,
+    /// The id of the file in the tree of the parent, or `None` if it doesn't exist there.
+    blob_id: Option<ObjectId>,
+}
+
+/// A change as produced by diffing the version of the file in a parent with the one in the suspect, as line ranges in both.
+type Change = (Range<u32>, Range<u32>);
+
+/// Find the commits that introduced each line of the file at `file_path` as of the commit `suspect`, looking up all objects
+/// in `odb`, similar to `git blame <suspect> -- <file_path>`.
+///
+/// See [`incremental()`] for details on how lines are attributed to commits.
+pub fn file(
+    odb: &impl gix_object::Find,
+    suspect: ObjectId,
+    file_path: &BStr,
+    options: Options,
+) -> Result<Outcome, Error> {
+    let mut entries = Vec::new();
+    let crate::incremental::Outcome { blob, statistics } =
+        incremental(odb, suspect, file_path, options, |entry| entries.push(entry))?;
+    Ok(Outcome {
+        entries: coalesce(entries),
+        blob,
+        statistics,
+    })
+}
+
+/// Like [`file()`], but call `on_entry` with each entry as soon as its lines are attributed to a commit, similar to
+/// `git blame --incremental`.
+///
+/// Entries are final once they are passed to `on_entry`, but are emitted in no particular order, and adjacent entries of the
+/// same commit aren't necessarily merged.
+///
+/// Commits are visited from the most recent to the oldest committer time, and each passes the lines it didn't change on to
+/// the first parent having them as well. Lines which it changed are attributed to it, unless it's one of the
+/// [ignored commits](Options::ignore_revs).
+pub fn incremental(
+    odb: &impl gix_object::Find,
+    suspect: ObjectId,
+    file_path: &BStr,
+    options: Options,
+    mut on_entry: impl FnMut(BlameEntry),
+) -> Result<crate::incremental::Outcome, Error> {
+    let _span = gix_trace::coarse!("gix_blame::incremental()", ?file_path, ?suspect);
+
+    let mut buf = Vec::new();
+    let mut buf2 = Vec::new();
+    let mut commit = odb.find_commit_iter(&suspect, &mut buf)?;
+    let tree_id = commit.tree_id()?;
+    let commit_time = commit.committer()?.time.seconds;
+    let blob_id = blob_id_at_path(odb, tree_id, file_path, &mut buf2)?.ok_or_else(|| Error::FileMissing {
+        file_path: file_path.to_owned(),
+        commit_id: suspect,
+    })?;
+    let blob = odb.find_blob(&blob_id, &mut buf)?.data.to_owned();
+    let num_lines = byte_lines_with_terminator(&blob).count() as u32;
+
+    let mut stats = Statistics::default();
+    let mut queue = BinaryHeap::new();
+    let mut suspects = gix_hashtable::HashMap::default();
+    if num_lines != 0 {
+        queue.push((commit_time, suspect));
+        suspects.insert(
+            suspect,
+            Suspect {
+                blob_id,
+                hunks: vec![UnblamedHunk {
+                    range_in_blamed_file: 0..num_lines,
+                    suspect_start: 0,
+                }],
+            },
+        );
+    }
+
+    // A commit may be visited more than once if one of its descendants has an older committer time, which is fine as the
+    // hunks it receives later are processed just the same.
+    while let Some((_, suspect)) = queue.pop() {
+        let Some(Suspect { blob_id, mut hunks }) = suspects.remove(&suspect) else {
+            continue;
+        };
+        stats.commits_traversed += 1;
+
+        let parent_ids: Vec<_> = odb.find_commit_iter(&suspect, &mut buf)?.parent_ids().collect();
+        let mut parents = Vec::with_capacity(parent_ids.len());
+        for id in parent_ids {
+            let mut commit = odb.find_commit_iter(&id, &mut buf)?;
+            let tree_id = commit.tree_id()?;
+            let commit_time = commit.committer()?.time.seconds;
+            parents.push(Parent {
+                id,
+                commit_time,
+                blob_id: blob_id_at_path(odb, tree_id, file_path, &mut buf2)?,
+            });
+        }
+
+        let mut pass_to_parent = |parent: &Parent, parent_blob_id: ObjectId, passed: Vec<UnblamedHunk>| {
+            if passed.is_empty() {
+                return;
+            }
+            suspects
+                .entry(parent.id)
+                .or_insert_with(|| {
+                    queue.push((parent.commit_time, parent.id));
+                    Suspect {
+                        blob_id: parent_blob_id,
+                        hunks: Vec::new(),
+                    }
+                })
+                .hunks
+                .extend(passed);
+        };
+
+        if let Some(parent) = parents.iter().find(|parent| parent.blob_id == Some(blob_id)) {
+            pass_to_parent(parent, blob_id, hunks);
+            continue;
+        }
+
+        let suspect_data = odb.find_blob(&blob_id, &mut buf)?.data;
+        let mut first_parent_changes = None;
+        for parent in &parents {
+            let Some(parent_blob_id) = parent.blob_id else {
+                continue;
+            };
+            let parent_data = odb.find_blob(&parent_blob_id, &mut buf2)?.data;
+            let (changes, num_suspect_lines) = diff(parent_data, suspect_data, &options);
+            stats.blob_diffs += 1;
+
+            let unchanged = unchanged_lines(&changes, num_suspect_lines);
+            let (passed, remaining) = split_hunks(hunks, &unchanged);
+            pass_to_parent(parent, parent_blob_id, passed);
+            hunks = remaining;
+            if first_parent_changes.is_none() {
+                first_parent_changes = Some((parent, parent_blob_id, changes, num_suspect_lines));
+            }
+            if hunks.is_empty() {
+                break;
+            }
+        }
+
+        if options.ignore_revs.contains(&suspect) {
+            if let Some((parent, parent_blob_id, changes, num_suspect_lines)) = first_parent_changes {
+                let counterparts = changed_line_counterparts(&changes, num_suspect_lines);
+                let (passed, remaining) = split_hunks(hunks, &counterparts);
+                pass_to_parent(parent, parent_blob_id, passed);
+                hunks = remaining;
+            }
+        }
+
+        let entries = hunks.into_iter().map(|hunk| BlameEntry {
+            start_in_blamed_file: hunk.range_in_blamed_file.start,
+            start_in_source_file: hunk.suspect_start,
+            len: hunk.range_in_blamed_file.end - hunk.range_in_blamed_file.start,
+            commit_id: suspect,
+        });
+        for entry in coalesce(entries.collect()) {
+            on_entry(entry);
+        }
+    }
+
+    Ok(crate::incremental::Outcome {
+        blob,
+        statistics: stats,
+    })
+}
+
+/// Return the id of the blob at `path` in the tree with `tree_id`, or `None` if there is no such blob.
+fn blob_id_at_path(
+    odb: &impl gix_object::Find,
+    tree_id: ObjectId,
+    path: &BStr,
+    buf: &mut Vec<u8>,
+) -> Result<Option<ObjectId>, Error> {
+    let mut id = tree_id;
+    let mut components = path.split_str("/").filter(|c| !c.is_empty()).peekable();
+    while let Some(component) = components.next() {
+        let is_last = components.peek().is_none();
+        let entry = odb
+            .find_tree_iter(&id, buf)?
+            .filter_map(Result::ok)
+            .find(|entry| entry.filename == component);
+        match entry {
+            Some(entry) if is_last && entry.mode.is_blob_or_symlink() => return Ok(Some(entry.oid.to_owned())),
+            Some(entry) if !is_last && entry.mode.is_tree() => id = entry.oid.to_owned(),
+            _ => return Ok(None),
+        }
+    }
+    Ok(None)
+}
+
+/// Diff the lines of the version of the file in a parent, `before`, with the ones in the suspect, `after`, and return all
+/// changes along with the amount of lines in `after`.
+fn diff(before: &[u8], after: &[u8], options: &Options) -> (Vec<Change>, u32) {
+    let mut input = InternedInput::default();
+    input.update_before(tokens(before, options.ignore_whitespace));
+    input.update_after(tokens(after, options.ignore_whitespace));
+
+    let mut changes = Vec::new();
+    gix_diff::blob::diff(options.algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+        changes.push((before, after));
+    });
+    (changes, input.after.len() as u32)
+}
+
+/// Return the lines of `data` as tokens for diffing, with all whitespace removed if `ignore_whitespace` is `true`.
+fn tokens(data: &[u8], ignore_whitespace: bool) -> impl Iterator<Item = Cow<'_, BStr>> {
+    byte_lines_with_terminator(data).map(move |line| {
+        if ignore_whitespace {
+            Cow::Owned(
+                line.iter()
+                    .copied()
+                    .filter(|b| !b.is_ascii_whitespace())
+                    .collect::<BString>(),
+            )
+        } else {
+            Cow::Borrowed(line.as_bstr())
+        }
+    })
+}
+
+/// Map each of the `num_suspect_lines` lines of the suspect to its line in the parent if it is unchanged, using `changes`.
+fn unchanged_lines(changes: &[Change], num_suspect_lines: u32) -> Vec<Option<u32>> {
+    let mut lines = Vec::with_capacity(num_suspect_lines as usize);
+    let mut parent_line = 0;
+    for (before, after) in changes {
+        let num_unchanged = after.start - lines.len() as u32;
+        lines.extend((parent_line..parent_line + num_unchanged).map(Some));
+        lines.extend(after.clone().map(|_| None));
+        parent_line = before.end;
+    }
+    let num_unchanged = num_suspect_lines - lines.len() as u32;
+    lines.extend((parent_line..parent_line + num_unchanged).map(Some));
+    lines
+}
+
+/// Map each of the `num_suspect_lines` lines of the suspect that was changed according to `changes` to the line at the same
+/// position among the lines it replaced in the parent, if there is one.
+fn changed_line_counterparts(changes: &[Change], num_suspect_lines: u32) -> Vec<Option<u32>> {
+    let mut lines = vec![None; num_suspect_lines as usize];
+    for (before, after) in changes {
+        for (suspect_line, parent_line) in after.clone().zip(before.clone()) {
+            lines[suspect_line as usize] = Some(parent_line);
+        }
+    }
+    lines
+}
+
+/// Split `hunks` into the ones which can be passed on to a parent according to `parent_lines`, which maps lines of the
+/// suspect to lines in the parent, and the ones which remain with the suspect.
+fn split_hunks(hunks: Vec<UnblamedHunk>, parent_lines: &[Option<u32>]) -> (Vec<UnblamedHunk>, Vec<UnblamedHunk>) {
+    let mut passed = Vec::new();
+    let mut remaining = Vec::new();
+    for hunk in hunks {
+        let mut start = hunk.range_in_blamed_file.start;
+        let mut first_suspect_line = hunk.suspect_start;
+        while start < hunk.range_in_blamed_file.end {
+            // Extend the run for as long as lines keep mapping to consecutive parent lines, or keep not mapping at all.
+            let first = parent_lines[first_suspect_line as usize];
+            let mut len = 1;
+            while start + len < hunk.range_in_blamed_file.end {
+                let next = parent_lines[(first_suspect_line + len) as usize];
+                let continues = match (first, next) {
+                    (Some(first), Some(next)) => next == first + len,
+                    (None, None) => true,
+                    _ => false,
+                };
+                if !continues {
+                    break;
+                }
+                len += 1;
+            }
+            let range_in_blamed_file = start..start + len;
+            match first {
+                Some(parent_start) => passed.push(UnblamedHunk {
+                    range_in_blamed_file,
+                    suspect_start: parent_start,
+                }),
+                None => remaining.push(UnblamedHunk {
+                    range_in_blamed_file,
+                    suspect_start: first_suspect_line,
+                }),
+            }
+            start += len;
+            first_suspect_line += len;
+        }
+    }
+    (passed, remaining)
+}
+
+/// Sort `entries` by their position in the blamed file and merge adjacent ones of the same commit which are adjacent in
+/// the source file as well.
+fn coalesce(mut entries: Vec<BlameEntry>) -> Vec<BlameEntry> {
+    entries.sort_by_key(|entry| entry.start_in_blamed_file);
+    let mut out: Vec<BlameEntry> = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match out.last_mut() {
+            Some(previous)
+                if previous.commit_id == entry.commit_id
+                    && previous.range_in_blamed_file().end == entry.start_in_blamed_file
+                    && previous.range_in_source_file().end == entry.start_in_source_file =>
+            {
+                previous.len += entry.len;
+            }
+            _ => out.push(entry),
+        }
+    }
+    out
+}
@@ -0,0 +1,94 @@
+//! Find the commits that last changed each line of a file, similar to what [`git blame`](https://git-scm.com/docs/git-blame) does.
+//!
+//! Starting at a commit, lines are passed on to the parents which contain them unchanged, until they reach the commit which
+//! introduced them. Renames aren't followed, so lines of a file that was renamed are attributed to the commit that renamed it.
+#![deny(rust_2018_idioms, missing_docs)]
+#![forbid(unsafe_code)]
+
+use std::ops::Range;
+
+use gix_hash::ObjectId;
+
+mod error;
+pub use error::Error;
+
+mod file;
+pub use file::{file, incremental};
+
+/// Options for use in [`file()`] and [`incremental()`].
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// Commits which are skipped when assigning blame, like those listed in the files configured with `blame.ignoreRevsFile`.
+    ///
+    /// Lines changed by these commits are passed on to the line at the same position within the change in their first parent
+    /// containing the file, and are only attributed to the ignored commit if there is no such line.
+    pub ignore_revs: gix_hashtable::HashSet<ObjectId>,
+    /// If `true`, lines which only differ in whitespace are considered unchanged, similar to `git blame -w`.
+    pub ignore_whitespace: bool,
+    /// The algorithm to use when diffing the versions of the file.
+    pub algorithm: gix_diff::blob::Algorithm,
+}
+
+/// A range of lines of the blamed file along with the commit which introduced them.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct BlameEntry {
+    /// The zero-based index of the first line of this entry in the blamed file.
+    pub start_in_blamed_file: u32,
+    /// The zero-based index of the first line of this entry in the file as of `commit_id`, which may differ if lines were
+    /// added or removed in front of it later.
+    pub start_in_source_file: u32,
+    /// The amount of lines in this entry, which is never zero.
+    pub len: u32,
+    /// The commit which introduced the lines of this entry.
+    pub commit_id: ObjectId,
+}
+
+impl BlameEntry {
+    /// Return the range of lines of this entry in the blamed file.
+    pub fn range_in_blamed_file(&self) -> Range<u32> {
+        self.start_in_blamed_file..self.start_in_blamed_file + self.len
+    }
+
+    /// Return the range of lines of this entry in the file as of the commit which introduced them.
+    pub fn range_in_source_file(&self) -> Range<u32> {
+        self.start_in_source_file..self.start_in_source_file + self.len
+    }
+}
+
+/// Information about the work performed by [`file()`] and [`incremental()`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    /// The amount of commits that were looked at to pass lines on to their parents.
+    pub commits_traversed: usize,
+    /// The amount of times two versions of the blamed file were diffed.
+    pub blob_diffs: usize,
+}
+
+/// The result of [`file()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// Entries covering all lines of the blamed file, sorted by their position in it.
+    ///
+    /// Adjacent lines introduced by the same commit are merged into a single entry if they are adjacent in the source
+    /// file as well.
+    pub entries: Vec<BlameEntry>,
+    /// The content of the blamed file.
+    pub blob: Vec<u8>,
+    /// Additional information about the performed work.
+    pub statistics: Statistics,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod incremental {
+    use crate::Statistics;
+
+    /// The result of [`incremental()`](crate::incremental()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The content of the blamed file.
+        pub blob: Vec<u8>,
+        /// Additional information about the performed work.
+        pub statistics: Statistics,
+    }
+}
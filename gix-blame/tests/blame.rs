@@ -0,0 +1,149 @@
+use std::path::{Path, PathBuf};
+
+use gix_blame::{BlameEntry, Options};
+use gix_hash::ObjectId;
+
+pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+fn fixture() -> Result<(PathBuf, gix_odb::Handle, ObjectId)> {
+    let dir = gix_testtools::scripted_fixture_read_only("make_blame_repo.sh")?;
+    let odb = gix_odb::at(dir.join("repo/.git/objects"))?;
+    let head = read_id(&dir.join("head"))?;
+    Ok((dir, odb, head))
+}
+
+fn read_id(path: &Path) -> Result<ObjectId> {
+    Ok(ObjectId::from_hex(std::fs::read_to_string(path)?.trim().as_bytes())?)
+}
+
+/// Return `(line_in_blamed_file, commit_id, line_in_source_file)` for each line listed by `git blame --incremental`
+/// in the baseline file `name`.
+fn baseline_lines(dir: &Path, name: &str) -> Result<Vec<(u32, ObjectId, u32)>> {
+    let mut lines = Vec::new();
+    for line in std::fs::read_to_string(dir.join(name))?.lines() {
+        let tokens: Vec<_> = line.split(' ').collect();
+        let Ok(id) = ObjectId::from_hex(tokens[0].as_bytes()) else {
+            continue;
+        };
+        let [source, blamed, len]: [u32; 3] = [tokens[1].parse()?, tokens[2].parse()?, tokens[3].parse()?];
+        lines.extend((0..len).map(|offset| (blamed - 1 + offset, id, source - 1 + offset)));
+    }
+    lines.sort();
+    Ok(lines)
+}
+
+fn lines(entries: &[BlameEntry]) -> Vec<(u32, ObjectId, u32)> {
+    entries
+        .iter()
+        .flat_map(|entry| {
+            entry
+                .range_in_blamed_file()
+                .zip(entry.range_in_source_file())
+                .map(|(blamed, source)| (blamed, entry.commit_id, source))
+        })
+        .collect()
+}
+
+fn assert_matches_baseline(file_path: &str, baseline: &str, options: Options) -> Result {
+    let (dir, odb, head) = fixture()?;
+    let outcome = gix_blame::file(&odb, head, file_path.into(), options)?;
+    assert_eq!(
+        lines(&outcome.entries),
+        baseline_lines(&dir, baseline)?,
+        "{file_path} is blamed just like `git` does"
+    );
+    assert_eq!(
+        outcome.blob,
+        std::fs::read(dir.join("repo").join(file_path))?,
+        "the blamed version of the file is returned"
+    );
+    Ok(())
+}
+
+#[test]
+fn file_with_merge_commits() -> Result {
+    assert_matches_baseline("file", "file.baseline", Options::default())
+}
+
+#[test]
+fn file_in_sub_directory() -> Result {
+    let (_dir, odb, head) = fixture()?;
+    let outcome = gix_blame::file(&odb, head, "dir/nested".into(), Options::default())?;
+    assert_eq!(
+        outcome.entries.len(),
+        2,
+        "adjacent lines of the same commit are merged into one entry"
+    );
+    assert_matches_baseline("dir/nested", "nested.baseline", Options::default())
+}
+
+#[test]
+fn file_added_after_the_root_commit() -> Result {
+    assert_matches_baseline("added", "added.baseline", Options::default())
+}
+
+#[test]
+fn ignore_whitespace() -> Result {
+    assert_matches_baseline(
+        "file",
+        "file-ignore-whitespace.baseline",
+        Options {
+            ignore_whitespace: true,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn ignore_revs() -> Result {
+    let (dir, _odb, _head) = fixture()?;
+    let ignored = read_id(&dir.join("repo/ignore-revs"))?;
+    assert_matches_baseline(
+        "file",
+        "file-ignore-revs.baseline",
+        Options {
+            ignore_revs: std::iter::once(ignored).collect(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn missing_file_is_an_error() -> Result {
+    let (_dir, odb, head) = fixture()?;
+    for path in ["does-not-exist", "dir", "file/nested"] {
+        let err = gix_blame::file(&odb, head, path.into(), Options::default()).unwrap_err();
+        assert!(
+            matches!(err, gix_blame::Error::FileMissing { commit_id, .. } if commit_id == head),
+            "{path} isn't a file"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn incremental_emits_final_entries_while_traversing() -> Result {
+    let (dir, odb, head) = fixture()?;
+    let mut entries = Vec::new();
+    let outcome = gix_blame::incremental(&odb, head, "file".into(), Options::default(), |entry| {
+        entries.push(entry)
+    })?;
+    assert_eq!(
+        entries.first().map(|entry| entry.commit_id),
+        Some(head),
+        "the lines changed by the commit that is visited first are emitted first"
+    );
+
+    let mut lines = lines(&entries);
+    lines.sort();
+    assert_eq!(
+        lines,
+        baseline_lines(&dir, "file.baseline")?,
+        "each line is emitted exactly once, and never re-attributed"
+    );
+
+    let expected = gix_blame::file(&odb, head, "file".into(), Options::default())?;
+    assert_eq!(outcome.blob, expected.blob);
+    assert_eq!(outcome.statistics, expected.statistics);
+    Ok(())
+}
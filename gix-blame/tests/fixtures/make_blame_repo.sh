@@ -0,0 +1,43 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  printf 'a\nb\nc\nd\ne\n' > file
+  mkdir dir && printf 'one\ntwo\n' > dir/nested
+  git add . && git commit -q -m c1
+
+  printf 'a\nb\nC\nd\ne\nf\n' > file
+  printf 'one\ntwo\nthree\n' > dir/nested
+  git add . && git commit -q -m c2
+
+  git checkout -q -b side
+  printf 'A\nb\nC\nd\ne\nf\n' > file
+  git commit -q -am c3-side
+
+  git checkout -q main
+  printf 'a\nb\nC\nd\nE\nf\n' > file
+  printf 'new\n' > added
+  git add . && git commit -q -m c3-main
+
+  git merge -q --no-edit side
+  printf 'A\nb\nC\nd\nE\nf\n' > file
+  git add file && git commit -q --amend --no-edit
+
+  printf 'A\n  b\nC\n\td\nE\nf\n' > file
+  git commit -q -am c4-whitespace
+
+  printf 'A;\n  b\nC\n\td\nE\nf;\ng\n' > file
+  git commit -q -am c5-to-ignore
+  git rev-parse HEAD > ignore-revs
+
+  printf 'A;\n  b\nC\n\td\nE\nf;\ng\nh\n' > file
+  git commit -q -am c6
+
+  git rev-parse HEAD > ../head
+  git blame --incremental file > ../file.baseline
+  git blame --incremental -w file > ../file-ignore-whitespace.baseline
+  git blame --incremental --ignore-revs-file ignore-revs file > ../file-ignore-revs.baseline
+  git blame --incremental dir/nested > ../nested.baseline
+  git blame --incremental added > ../added.baseline
+)
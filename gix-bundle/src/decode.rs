@@ -0,0 +1,148 @@
+use std::io::BufRead;
+
+use bstr::{BString, ByteSlice};
+
+use crate::{Capability, Header, Prerequisite, Ref, Version};
+
+/// The error returned by [`Header::from_read()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read the bundle header")]
+    Io(#[from] std::io::Error),
+    #[error("Expected a bundle signature like '# v2 git bundle', got {line:?}")]
+    Signature { line: BString },
+    #[error("Unsupported capability {name:?}")]
+    UnsupportedCapability { name: BString },
+    #[error("Unsupported object format {format:?}")]
+    UnsupportedObjectFormat { format: BString },
+    #[error("Capabilities must follow the signature directly, but found {line:?}")]
+    MisplacedCapability { line: BString },
+    #[error("Could not parse object id in line {line:?}")]
+    ObjectId {
+        line: BString,
+        source: gix_hash::decode::Error,
+    },
+    #[error("Expected a reference line like '<id> <name>', got {line:?}")]
+    Ref { line: BString },
+    #[error("The header ended before the empty line that separates it from the pack")]
+    UnexpectedEof,
+}
+
+/// The signature of a [`V2`](Version::V2) bundle.
+pub const SIGNATURE_V2: &[u8] = b"# v2 git bundle";
+/// The signature of a [`V3`](Version::V3) bundle.
+pub const SIGNATURE_V3: &[u8] = b"# v3 git bundle";
+
+/// Decoding
+impl Header {
+    /// Read a bundle header from `read`, which is expected to start at the beginning of the bundle,
+    /// and return it along with the amount of bytes consumed, which is the position at which the pack starts.
+    ///
+    /// `read` won't be read past the header.
+    pub fn from_read(read: &mut dyn BufRead) -> Result<(Header, u64), Error> {
+        let mut consumed = 0;
+        let mut buf = Vec::new();
+        let mut next_line = |buf: &mut Vec<u8>| -> Result<bool, Error> {
+            buf.clear();
+            let num_read = read.read_until(b'\n', buf)?;
+            consumed += num_read as u64;
+            if buf.last() != Some(&b'\n') {
+                return Ok(false);
+            }
+            buf.pop();
+            Ok(true)
+        };
+
+        if !next_line(&mut buf)? {
+            return Err(Error::Signature { line: buf.into() });
+        }
+        let version = match buf.as_slice() {
+            SIGNATURE_V2 => Version::V2,
+            SIGNATURE_V3 => Version::V3,
+            _ => return Err(Error::Signature { line: buf.into() }),
+        };
+
+        let mut header = Header {
+            version,
+            object_hash: gix_hash::Kind::Sha1,
+            capabilities: Vec::new(),
+            prerequisites: Vec::new(),
+            refs: Vec::new(),
+        };
+        let mut may_have_capabilities = version == Version::V3;
+        loop {
+            if !next_line(&mut buf)? {
+                return Err(Error::UnexpectedEof);
+            }
+            if buf.is_empty() {
+                break;
+            }
+            if let Some(capability) = buf.strip_prefix(b"@") {
+                if !may_have_capabilities {
+                    return Err(Error::MisplacedCapability { line: buf.into() });
+                }
+                let capability = parse_capability(capability);
+                match capability.name.as_slice() {
+                    b"object-format" => {
+                        let format = capability.value.clone().unwrap_or_default();
+                        header.object_hash = format
+                            .to_str()
+                            .ok()
+                            .and_then(|format| format.parse().ok())
+                            .ok_or(Error::UnsupportedObjectFormat { format })?;
+                    }
+                    b"filter" => {}
+                    _ => return Err(Error::UnsupportedCapability { name: capability.name }),
+                }
+                header.capabilities.push(capability);
+                continue;
+            }
+            may_have_capabilities = false;
+
+            let hex_len = header.object_hash.len_in_hex();
+            if let Some(prerequisite) = buf.strip_prefix(b"-") {
+                let id = parse_id(prerequisite.get(..hex_len).unwrap_or(prerequisite), &buf)?;
+                let comment = prerequisite[hex_len..]
+                    .strip_prefix(b" ")
+                    .unwrap_or(&prerequisite[hex_len..]);
+                header.prerequisites.push(Prerequisite {
+                    id,
+                    comment: comment.into(),
+                });
+            } else {
+                let (id, name) = buf.split_once_str(b" ").ok_or_else(|| Error::Ref {
+                    line: buf.as_slice().into(),
+                })?;
+                if name.is_empty() {
+                    return Err(Error::Ref { line: buf.into() });
+                }
+                header.refs.push(Ref {
+                    name: name.into(),
+                    target: parse_id(id, &buf)?,
+                });
+            }
+        }
+        Ok((header, consumed))
+    }
+}
+
+fn parse_capability(capability: &[u8]) -> Capability {
+    match capability.split_once_str(b"=") {
+        Some((name, value)) => Capability {
+            name: name.into(),
+            value: Some(value.into()),
+        },
+        None => Capability {
+            name: capability.into(),
+            value: None,
+        },
+    }
+}
+
+fn parse_id(hex: &[u8], line: &[u8]) -> Result<gix_hash::ObjectId, Error> {
+    gix_hash::ObjectId::from_hex(hex).map_err(|source| Error::ObjectId {
+        line: line.into(),
+        source,
+    })
+}
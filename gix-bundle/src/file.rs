@@ -0,0 +1,147 @@
+use std::{
+    io::{BufRead, Seek, SeekFrom},
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_features::progress::DynNestedProgress;
+
+use crate::{File, Header, Prerequisite};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init {
+    /// The error returned by [`File::at()`][crate::File::at()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not open bundle file at '{}'", path.display())]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error("Could not decode the header of bundle file at '{}'", path.display())]
+        Decode {
+            source: crate::decode::Error,
+            path: std::path::PathBuf,
+        },
+    }
+}
+
+/// Instantiation
+impl File {
+    /// Open the bundle file at `path` and read its header.
+    pub fn at(path: impl Into<PathBuf>) -> Result<Self, init::Error> {
+        let path = path.into();
+        let mut read = std::fs::File::open(&path)
+            .map(std::io::BufReader::new)
+            .map_err(|source| init::Error::Io {
+                source,
+                path: path.clone(),
+            })?;
+        let (header, pack_offset) = Header::from_read(&mut read).map_err(|source| init::Error::Decode {
+            source,
+            path: path.clone(),
+        })?;
+        Ok(File {
+            header,
+            path,
+            pack_offset,
+        })
+    }
+}
+
+/// Access
+impl File {
+    /// The header of the bundle.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// The path from which the bundle was read.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The offset in bytes at which the pack starts.
+    pub fn pack_offset(&self) -> u64 {
+        self.pack_offset
+    }
+
+    /// Open the bundle file again and return a reader positioned at the beginning of the embedded pack.
+    pub fn pack_reader(&self) -> std::io::Result<impl BufRead> {
+        let mut file = std::fs::File::open(&self.path)?;
+        file.seek(SeekFrom::Start(self.pack_offset))?;
+        Ok(std::io::BufReader::new(file))
+    }
+
+    /// Return all prerequisites that `objects` doesn't contain, which must be empty for the pack to be usable.
+    pub fn missing_prerequisites(&self, objects: &dyn gix_object::Exists) -> Vec<&Prerequisite> {
+        self.header
+            .prerequisites
+            .iter()
+            .filter(|prerequisite| !objects.exists(&prerequisite.id))
+            .collect()
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod write_pack {
+    /// The error returned by [`File::write_pack_to_directory()`][crate::File::write_pack_to_directory()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not read the pack of bundle file at '{}'", path.display())]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error("The bundle uses {actual} object ids, but {expected} was expected")]
+        ObjectHashMismatch {
+            expected: gix_hash::Kind,
+            actual: gix_hash::Kind,
+        },
+        #[error(transparent)]
+        WritePack(#[from] gix_pack::bundle::write::Error),
+    }
+}
+
+/// Unbundling
+impl File {
+    /// Write the embedded pack along with a newly created index into `directory`, typically the `objects/pack` directory
+    /// of an object database, to make the objects of this bundle available.
+    ///
+    /// `thin_pack_base_object_lookup` is needed if the bundle has [prerequisites](Header::prerequisites), as the pack
+    /// may then refer to objects it doesn't contain. See [`gix_pack::Bundle::write_to_directory()`] for details on all other
+    /// parameters.
+    ///
+    /// _Note_ that the references of the bundle aren't created, as this is left to the caller.
+    pub fn write_pack_to_directory(
+        &self,
+        directory: Option<&Path>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        thin_pack_base_object_lookup: Option<impl gix_object::Find>,
+        options: gix_pack::bundle::write::Options,
+    ) -> Result<gix_pack::bundle::write::Outcome, write_pack::Error> {
+        if options.object_hash != self.header.object_hash {
+            return Err(write_pack::Error::ObjectHashMismatch {
+                expected: options.object_hash,
+                actual: self.header.object_hash,
+            });
+        }
+        let mut pack = self.pack_reader().map_err(|source| write_pack::Error::Io {
+            source,
+            path: self.path.clone(),
+        })?;
+        Ok(gix_pack::Bundle::write_to_directory(
+            &mut pack,
+            directory,
+            progress,
+            should_interrupt,
+            thin_pack_base_object_lookup,
+            options,
+        )?)
+    }
+}
@@ -0,0 +1,80 @@
+//! Read and write [`git bundle`](https://git-scm.com/docs/gitformat-bundle) files, which contain references along with a pack holding
+//! the objects they need, to allow transferring repositories without a network connection.
+#![deny(rust_2018_idioms, missing_docs)]
+#![forbid(unsafe_code)]
+
+use std::path::PathBuf;
+
+use bstr::BString;
+
+/// The version of a bundle file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Version {
+    /// The original format, which implies SHA1 and doesn't support capabilities.
+    V2,
+    /// The format which supports capabilities, for example to specify the object hash.
+    V3,
+}
+
+/// A capability of a [`V3`](Version::V3) bundle, like `object-format=sha1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Capability {
+    /// The name of the capability, like `object-format`.
+    pub name: BString,
+    /// The value of the capability, if one was provided after the `=` sign.
+    pub value: Option<BString>,
+}
+
+/// An object that must exist in the repository receiving the bundle, as the pack refers to it without containing it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Prerequisite {
+    /// The id of the object that has to be present.
+    pub id: gix_hash::ObjectId,
+    /// A comment, typically the subject of the commit, which may be empty.
+    pub comment: BString,
+}
+
+/// A reference contained in the bundle.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Ref {
+    /// The full name of the reference, like `refs/heads/main` or `HEAD`.
+    pub name: BString,
+    /// The object the reference points to, which is contained in the pack or is one of the prerequisites.
+    pub target: gix_hash::ObjectId,
+}
+
+/// The header of a bundle file, which precedes the pack.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Header {
+    /// The version of the bundle.
+    pub version: Version,
+    /// The hash used for all object ids in the bundle and its pack.
+    pub object_hash: gix_hash::Kind,
+    /// All capabilities in the order they were listed, only present in [`V3`](Version::V3) bundles.
+    pub capabilities: Vec<Capability>,
+    /// The objects that need to be present for the pack to be usable.
+    pub prerequisites: Vec<Prerequisite>,
+    /// All references contained in the bundle.
+    pub refs: Vec<Ref>,
+}
+
+/// A bundle file on disk.
+#[derive(Debug, Clone)]
+pub struct File {
+    header: Header,
+    path: PathBuf,
+    pack_offset: u64,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod file;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod write;
+pub use write::function::write_to;
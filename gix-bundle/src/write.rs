@@ -0,0 +1,346 @@
+use std::io::Write;
+
+use crate::{Header, Version};
+
+/// The error returned by [`write_to()`](crate::write_to()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not write the bundle")]
+    Io(#[from] std::io::Error),
+    #[error("Objects using {object_hash} can only be written to v3 bundles")]
+    UnsupportedObjectHash { object_hash: gix_hash::Kind },
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error("Refusing to create an empty bundle as none of the references is outside of the excluded history")]
+    Empty,
+    #[error("The operation was interrupted")]
+    Interrupted,
+}
+
+/// Configuration for [`write_to()`](crate::write_to()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The version of the bundle to write.
+    ///
+    /// [`V3`](Version::V3) bundles always state the object hash with the `object-format` capability.
+    pub version: Version,
+    /// The kind of hash used by all objects that are written, which is also the kind of hash used in the pack.
+    pub object_hash: gix_hash::Kind,
+    /// The amount of threads to use when generating the pack, or `None` to use all logical cores.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            version: Version::V3,
+            object_hash: gix_hash::Kind::Sha1,
+            thread_limit: None,
+        }
+    }
+}
+
+/// The outcome of [`write_to()`](crate::write_to()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The header that was written.
+    pub header: Header,
+    /// The amount of objects in the pack.
+    pub num_objects: u32,
+    /// The checksum of the pack, which is also the last bytes of the bundle.
+    pub pack_hash: gix_hash::ObjectId,
+}
+
+/// Encoding
+impl Header {
+    /// Serialize this header to `out`, including the empty line which separates it from the pack.
+    ///
+    /// Note that capabilities are only written for [`V3`](Version::V3) bundles.
+    pub fn write_to(&self, out: &mut dyn Write) -> std::io::Result<()> {
+        out.write_all(match self.version {
+            Version::V2 => crate::decode::SIGNATURE_V2,
+            Version::V3 => crate::decode::SIGNATURE_V3,
+        })?;
+        out.write_all(b"\n")?;
+        if self.version == Version::V3 {
+            for capability in &self.capabilities {
+                out.write_all(b"@")?;
+                out.write_all(&capability.name)?;
+                if let Some(value) = &capability.value {
+                    out.write_all(b"=")?;
+                    out.write_all(value)?;
+                }
+                out.write_all(b"\n")?;
+            }
+        }
+        for prerequisite in &self.prerequisites {
+            write!(out, "-{}", prerequisite.id)?;
+            if !prerequisite.comment.is_empty() {
+                out.write_all(b" ")?;
+                out.write_all(&prerequisite.comment)?;
+            }
+            out.write_all(b"\n")?;
+        }
+        for r in &self.refs {
+            write!(out, "{} ", r.target)?;
+            out.write_all(&r.name)?;
+            out.write_all(b"\n")?;
+        }
+        out.write_all(b"\n")
+    }
+}
+
+pub(crate) mod function {
+    use std::{io::Write, sync::atomic::AtomicBool};
+
+    use gix_features::progress::{Count, DynNestedProgress, Progress};
+    use gix_hash::ObjectId;
+    use gix_hashtable::HashSet;
+    use gix_object::{CommitRefIter, Kind, TagRefIter, TreeRefIter};
+    use gix_pack::{data::output, FindExt};
+
+    use super::{Error, Options, Outcome};
+    use crate::{Capability, Header, Prerequisite, Ref, Version};
+
+    /// Write a bundle with the given `refs` to `out`, along with a pack containing all objects reachable from them,
+    /// but none of the objects reachable from the `excluded` objects, similar to `git bundle create <out> <refs> ^<excluded>`.
+    ///
+    /// Commits that aren't contained in the bundle, but which are parents of commits in the bundle, become its
+    /// [prerequisites](Header::prerequisites), and the pack won't contain any tree or blob that is reachable from them.
+    /// References whose peeled target is reachable from `excluded` are omitted, just like `git` does, and if that leaves
+    /// no reference, [`Error::Empty`] is returned.
+    /// To write a revision range like `a..b`, pass `b` in `refs` and exclude `a`.
+    /// Annotated tags are peeled to find the commits to traverse, and are contained in the pack.
+    ///
+    /// `objects` must contain all objects reachable from `refs` and `excluded`, and should not apply replacements.
+    /// The pack is never thin, i.e. it doesn't contain deltas against objects outside of it.
+    pub fn write_to<Find>(
+        out: &mut dyn Write,
+        refs: Vec<Ref>,
+        excluded: &[ObjectId],
+        objects: Find,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        Options {
+            version,
+            object_hash,
+            thread_limit,
+        }: Options,
+    ) -> Result<Outcome, Error>
+    where
+        Find: gix_pack::Find + Send + Clone + 'static,
+    {
+        if version == Version::V2 && object_hash != gix_hash::Kind::Sha1 {
+            return Err(Error::UnsupportedObjectHash { object_hash });
+        }
+        let mut buf = Vec::new();
+
+        let mut uninteresting = HashSet::default();
+        {
+            let mut commits = Vec::new();
+            for id in excluded {
+                let (_tags, peeled, kind) = peel(&objects, *id, &mut buf)?;
+                if kind == Kind::Commit {
+                    commits.push(peeled);
+                }
+            }
+            while let Some(id) = commits.pop() {
+                check_interrupt(should_interrupt)?;
+                if !uninteresting.insert(id) {
+                    continue;
+                }
+                let data = objects.find(&id, &mut buf)?.0;
+                commits.extend(CommitRefIter::from_bytes(data.data).parent_ids());
+            }
+        }
+
+        let mut header_refs = Vec::new();
+        let mut ids = Vec::new();
+        let mut seen = HashSet::default();
+        let mut commits = Vec::new();
+        let mut tips = Vec::new();
+        for r in refs {
+            let (tags, peeled, kind) = peel(&objects, r.target, &mut buf)?;
+            if uninteresting.contains(&peeled) {
+                continue;
+            }
+            header_refs.push(r);
+            for tag in tags {
+                if seen.insert(tag) {
+                    ids.push(tag);
+                }
+            }
+            if kind == Kind::Commit {
+                commits.push(peeled);
+            } else {
+                tips.push(peeled);
+            }
+        }
+        if header_refs.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let mut prerequisites = Vec::new();
+        let mut prerequisite_ids = HashSet::default();
+        let mut trees = Vec::new();
+        let mut parents = Vec::new();
+        while let Some(id) = commits.pop() {
+            check_interrupt(should_interrupt)?;
+            if !seen.insert(id) {
+                continue;
+            }
+            ids.push(id);
+            let data = objects.find(&id, &mut buf)?.0;
+            let mut iter = CommitRefIter::from_bytes(data.data);
+            trees.push(iter.tree_id()?);
+            parents.extend(iter.parent_ids());
+            for parent in parents.drain(..) {
+                if !uninteresting.contains(&parent) {
+                    commits.push(parent);
+                } else if prerequisite_ids.insert(parent) {
+                    let data = objects.find(&parent, &mut buf)?.0;
+                    let commit = gix_object::CommitRef::from_bytes(data.data)?;
+                    prerequisites.push(Prerequisite {
+                        id: parent,
+                        comment: commit.message_summary().into_owned(),
+                    });
+                }
+            }
+        }
+
+        let mut known = HashSet::default();
+        for prerequisite in &prerequisites {
+            let data = objects.find(&prerequisite.id, &mut buf)?.0;
+            let tree = CommitRefIter::from_bytes(data.data).tree_id()?;
+            collect_tree_objects(&objects, tree, &mut known, &mut Vec::new(), &mut buf, should_interrupt)?;
+        }
+        seen.extend(known);
+        for id in trees.into_iter().chain(tips) {
+            collect_tree_objects(&objects, id, &mut seen, &mut ids, &mut buf, should_interrupt)?;
+        }
+
+        let header = Header {
+            version,
+            object_hash,
+            capabilities: match version {
+                Version::V2 => Vec::new(),
+                Version::V3 => vec![Capability {
+                    name: "object-format".into(),
+                    value: Some(object_hash.to_string().to_ascii_lowercase().into()),
+                }],
+            },
+            prerequisites,
+            refs: header_refs,
+        };
+        header.write_to(out)?;
+
+        let (counts, _) = {
+            let mut count_progress = progress.add_child("counting".into());
+            count_progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+            output::count::objects_unthreaded(
+                &objects,
+                &mut ids.into_iter().map(Ok),
+                &count_progress,
+                should_interrupt,
+                output::count::objects::ObjectExpansion::AsIs,
+            )?
+        };
+        let num_objects = counts.len() as u32;
+        let entries = gix_features::parallel::InOrderIter::from(output::entry::iter_from_counts(
+            counts,
+            objects,
+            Box::new(progress.add_child("resolving".into())),
+            output::entry::iter_from_counts::Options {
+                thread_limit,
+                ..Default::default()
+            },
+        ));
+        let mut write_progress = progress.add_child("writing".into());
+        write_progress.init(None, gix_features::progress::bytes());
+        let mut pack = output::bytes::FromEntriesIter::new(
+            entries,
+            out,
+            num_objects,
+            gix_pack::data::Version::default(),
+            object_hash,
+        );
+        for written in pack.by_ref() {
+            check_interrupt(should_interrupt)?;
+            write_progress.inc_by(written? as usize);
+        }
+        let pack_hash = pack.digest().expect("iteration is done");
+
+        Ok(Outcome {
+            header,
+            num_objects,
+            pack_hash,
+        })
+    }
+
+    /// Follow `id` through all tags and return the ids of these tags, along with the peeled object and its kind.
+    fn peel(
+        objects: &dyn gix_pack::Find,
+        mut id: ObjectId,
+        buf: &mut Vec<u8>,
+    ) -> Result<(Vec<ObjectId>, ObjectId, Kind), Error> {
+        let mut tags = Vec::new();
+        loop {
+            let data = objects.find(&id, buf)?.0;
+            if data.kind != Kind::Tag {
+                return Ok((tags, id, data.kind));
+            }
+            tags.push(id);
+            id = TagRefIter::from_bytes(data.data).target_id()?;
+        }
+    }
+
+    /// Put `id` and, if it's a tree, all objects reachable from it that aren't yet in `seen` into `out`.
+    /// Submodule commits are skipped.
+    fn collect_tree_objects(
+        objects: &dyn gix_pack::Find,
+        id: ObjectId,
+        seen: &mut HashSet<ObjectId>,
+        out: &mut Vec<ObjectId>,
+        buf: &mut Vec<u8>,
+        should_interrupt: &AtomicBool,
+    ) -> Result<(), Error> {
+        if !seen.insert(id) {
+            return Ok(());
+        }
+        out.push(id);
+        let mut trees = vec![id];
+        while let Some(id) = trees.pop() {
+            check_interrupt(should_interrupt)?;
+            let data = objects.find(&id, buf)?.0;
+            if data.kind != Kind::Tree {
+                continue;
+            }
+            for entry in TreeRefIter::from_bytes(data.data) {
+                let entry = entry?;
+                if entry.mode.is_commit() || !seen.insert(entry.oid.to_owned()) {
+                    continue;
+                }
+                out.push(entry.oid.to_owned());
+                if entry.mode.is_tree() {
+                    trees.push(entry.oid.to_owned());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_interrupt(should_interrupt: &AtomicBool) -> Result<(), Error> {
+        if should_interrupt.load(std::sync::atomic::Ordering::Relaxed) {
+            Err(Error::Interrupted)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,440 @@
+pub type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
+    gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
+}
+
+mod decode {
+    use bstr::ByteSlice;
+    use gix_bundle::{decode, Capability, Header, Prerequisite, Ref, Version};
+
+    use crate::hex_to_id;
+
+    fn header(input: &[u8]) -> Result<(Header, u64), decode::Error> {
+        Header::from_read(&mut &input[..])
+    }
+
+    #[test]
+    fn v2_with_prerequisites_and_refs() -> crate::Result {
+        let input = b"# v2 git bundle
+-06ca6d74cd899ef7b35748ffaf2e0a99c5ac8505 c2
+-16ca6d74cd899ef7b35748ffaf2e0a99c5ac8505
+ab4e69157b50406b844341ce3a00c985a50543f6 refs/heads/main
+ab4e69157b50406b844341ce3a00c985a50543f6 HEAD
+
+PACK";
+        let (header, pack_offset) = header(input)?;
+        assert_eq!(
+            &input[pack_offset as usize..],
+            b"PACK",
+            "the pack starts right after the header"
+        );
+        assert_eq!(
+            header,
+            Header {
+                version: Version::V2,
+                object_hash: gix_hash::Kind::Sha1,
+                capabilities: Vec::new(),
+                prerequisites: vec![
+                    Prerequisite {
+                        id: hex_to_id("06ca6d74cd899ef7b35748ffaf2e0a99c5ac8505"),
+                        comment: "c2".into(),
+                    },
+                    Prerequisite {
+                        id: hex_to_id("16ca6d74cd899ef7b35748ffaf2e0a99c5ac8505"),
+                        comment: "".into(),
+                    }
+                ],
+                refs: vec![
+                    Ref {
+                        name: "refs/heads/main".into(),
+                        target: hex_to_id("ab4e69157b50406b844341ce3a00c985a50543f6"),
+                    },
+                    Ref {
+                        name: "HEAD".into(),
+                        target: hex_to_id("ab4e69157b50406b844341ce3a00c985a50543f6"),
+                    }
+                ]
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn v3_with_capabilities() -> crate::Result {
+        let (header, _) = header(
+            b"# v3 git bundle
+@object-format=sha1
+@filter=blob:none
+ab4e69157b50406b844341ce3a00c985a50543f6 refs/heads/main
+
+",
+        )?;
+        assert_eq!(header.version, Version::V3);
+        assert_eq!(header.object_hash, gix_hash::Kind::Sha1);
+        assert_eq!(
+            header.capabilities,
+            [
+                Capability {
+                    name: "object-format".into(),
+                    value: Some("sha1".into())
+                },
+                Capability {
+                    name: "filter".into(),
+                    value: Some("blob:none".into())
+                }
+            ]
+        );
+        assert_eq!(header.refs.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        for (input, expected) in [
+            (&b"# v4 git bundle\n\n"[..], "Expected a bundle signature"),
+            (b"", "Expected a bundle signature"),
+            (b"# v2 git bundle\n@object-format=sha1\n\n", "Capabilities must follow"),
+            (
+                b"# v3 git bundle\nab4e69157b50406b844341ce3a00c985a50543f6 HEAD\n@object-format=sha1\n\n",
+                "Capabilities must follow",
+            ),
+            (b"# v3 git bundle\n@object-format=md5\n\n", "Unsupported object format"),
+            (b"# v3 git bundle\n@unknown\n\n", "Unsupported capability"),
+            (b"# v2 git bundle\n-abc comment\n\n", "Could not parse object id"),
+            (
+                b"# v2 git bundle\nab4e69157b50406b844341ce3a00c985a50543f6\n\n",
+                "Expected a reference line",
+            ),
+            (
+                b"# v2 git bundle\nab4e69157b50406b844341ce3a00c985a50543f6 \n\n",
+                "Expected a reference line",
+            ),
+            (
+                b"# v2 git bundle\nab4e69157b50406b844341ce3a00c985a50543f6 HEAD\n",
+                "The header ended",
+            ),
+        ] {
+            let err = header(input).unwrap_err();
+            assert!(
+                err.to_string().starts_with(expected),
+                "{input:?}: '{err}' should start with '{expected}'",
+                input = input.as_bstr()
+            );
+        }
+    }
+}
+
+mod file {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_bundle::{File, Version};
+    use gix_object::Exists;
+
+    use crate::hex_to_id;
+
+    fn fixture(name: &str) -> crate::Result<File> {
+        let dir = gix_testtools::scripted_fixture_read_only("make_bundles.sh")?;
+        Ok(File::at(dir.join(name))?)
+    }
+
+    fn write_options() -> gix_pack::bundle::write::Options {
+        gix_pack::bundle::write::Options {
+            object_hash: gix_hash::Kind::Sha1,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unbundle_into_empty_object_database() -> crate::Result {
+        let bundle = fixture("all.bundle")?;
+        let header = bundle.header();
+        assert_eq!(header.version, Version::V2);
+        assert!(header.prerequisites.is_empty());
+        assert_eq!(
+            header.refs.iter().map(|r| r.name.to_string()).collect::<Vec<_>>(),
+            ["refs/heads/main", "refs/tags/v1", "HEAD"]
+        );
+
+        let objects_dir = gix_testtools::tempfile::tempdir()?;
+        let pack_dir = objects_dir.path().join("pack");
+        std::fs::create_dir(&pack_dir)?;
+        let outcome = bundle.write_pack_to_directory(
+            Some(&pack_dir),
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+            None::<gix_object::find::Never>,
+            write_options(),
+        )?;
+        assert_eq!(
+            outcome.index.num_objects, 6,
+            "three commits, the empty tree, one tree and one blob"
+        );
+
+        let odb = gix_odb::at(objects_dir.path())?;
+        assert!(bundle.missing_prerequisites(&odb).is_empty());
+        for r in &header.refs {
+            assert!(odb.exists(&r.target), "{} is contained in the pack", r.name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn unbundle_with_prerequisites() -> crate::Result {
+        let bundle = fixture("incremental.bundle")?;
+        let header = bundle.header();
+        assert_eq!(header.prerequisites.len(), 1);
+        assert_eq!(header.prerequisites[0].comment, "c2");
+
+        let empty = gix_testtools::tempfile::tempdir()?;
+        let empty_odb = gix_odb::at(empty.path())?;
+        assert_eq!(
+            bundle.missing_prerequisites(&empty_odb),
+            [&header.prerequisites[0]],
+            "an empty repository can't use this bundle"
+        );
+
+        let repo_odb = gix_odb::at(bundle.path().parent().expect("parent").join("repo/.git/objects"))?;
+        assert!(bundle.missing_prerequisites(&repo_odb).is_empty());
+        let outcome = bundle.write_pack_to_directory(
+            None,
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+            Some(repo_odb),
+            write_options(),
+        )?;
+        assert_eq!(outcome.index.num_objects, 3, "one commit, one tree and one blob");
+        Ok(())
+    }
+
+    #[test]
+    fn v3_bundle() -> crate::Result {
+        let bundle = fixture("v3.bundle")?;
+        let header = bundle.header();
+        assert_eq!(header.version, Version::V3);
+        assert_eq!(header.object_hash, gix_hash::Kind::Sha1);
+        assert_eq!(header.refs.len(), 1);
+        assert_eq!(header.refs[0].name, "refs/heads/main");
+        assert_eq!(
+            header.refs[0].target,
+            hex_to_id("d3f1efc9bf70a356487dffcaea60ab17a29c017e"),
+            "the tip of main"
+        );
+        Ok(())
+    }
+}
+
+mod write {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_bundle::{write, Capability, File, Header, Prerequisite, Ref, Version};
+    use gix_object::Exists;
+
+    use crate::hex_to_id;
+
+    fn repo_odb() -> crate::Result<gix_odb::Cache<gix_odb::store::Handle<std::sync::Arc<gix_odb::Store>>>> {
+        let dir = gix_testtools::scripted_fixture_read_only("make_bundles.sh")?;
+        Ok(gix_odb::at(dir.join("repo/.git/objects"))?.into_arc()?)
+    }
+
+    fn main_tip() -> gix_hash::ObjectId {
+        hex_to_id("d3f1efc9bf70a356487dffcaea60ab17a29c017e")
+    }
+
+    fn fixture_ref(name: &str) -> crate::Result<gix_hash::ObjectId> {
+        let dir = gix_testtools::scripted_fixture_read_only("make_bundles.sh")?;
+        let hex = std::fs::read_to_string(dir.join("repo/.git").join(name))?;
+        Ok(hex_to_id(hex.trim()))
+    }
+
+    fn write_bundle(
+        refs: Vec<Ref>,
+        excluded: &[gix_hash::ObjectId],
+        options: write::Options,
+    ) -> crate::Result<(gix_testtools::tempfile::TempDir, File, write::Outcome)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let path = dir.path().join("out.bundle");
+        let mut out = std::fs::File::create(&path)?;
+        let outcome = gix_bundle::write_to(
+            &mut out,
+            refs,
+            excluded,
+            repo_odb()?,
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+            options,
+        )?;
+        drop(out);
+        let bundle = File::at(path)?;
+        assert_eq!(
+            bundle.header(),
+            &outcome.header,
+            "the written header is read back as is"
+        );
+        Ok((dir, bundle, outcome))
+    }
+
+    fn unbundle(
+        bundle: &File,
+        odb: Option<gix_odb::Handle>,
+    ) -> crate::Result<(gix_testtools::tempfile::TempDir, gix_odb::Handle)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let objects_dir = dir.path().to_owned();
+        let pack_dir = objects_dir.join("pack");
+        std::fs::create_dir(&pack_dir)?;
+        bundle.write_pack_to_directory(
+            Some(&pack_dir),
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+            odb,
+            gix_pack::bundle::write::Options {
+                object_hash: gix_hash::Kind::Sha1,
+                ..Default::default()
+            },
+        )?;
+        Ok((dir, gix_odb::at(objects_dir)?))
+    }
+
+    #[test]
+    fn header_round_trip() -> crate::Result {
+        for version in [Version::V2, Version::V3] {
+            let header = Header {
+                version,
+                object_hash: gix_hash::Kind::Sha1,
+                capabilities: match version {
+                    Version::V2 => Vec::new(),
+                    Version::V3 => vec![
+                        Capability {
+                            name: "object-format".into(),
+                            value: Some("sha1".into()),
+                        },
+                        Capability {
+                            name: "filter".into(),
+                            value: None,
+                        },
+                    ],
+                },
+                prerequisites: vec![
+                    Prerequisite {
+                        id: hex_to_id("06ca6d74cd899ef7b35748ffaf2e0a99c5ac8505"),
+                        comment: "a subject".into(),
+                    },
+                    Prerequisite {
+                        id: hex_to_id("16ca6d74cd899ef7b35748ffaf2e0a99c5ac8505"),
+                        comment: "".into(),
+                    },
+                ],
+                refs: vec![Ref {
+                    name: "refs/heads/main".into(),
+                    target: main_tip(),
+                }],
+            };
+            let mut buf = Vec::new();
+            header.write_to(&mut buf)?;
+            buf.extend_from_slice(b"PACK");
+            let (actual, pack_offset) = Header::from_read(&mut buf.as_slice())?;
+            assert_eq!(actual, header);
+            assert_eq!(&buf[pack_offset as usize..], b"PACK");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn everything_reachable_from_refs() -> crate::Result {
+        let annotated_tag = fixture_ref("refs/tags/v2")?;
+        let refs = vec![
+            Ref {
+                name: "refs/heads/main".into(),
+                target: main_tip(),
+            },
+            Ref {
+                name: "refs/tags/v2".into(),
+                target: annotated_tag,
+            },
+        ];
+        let (_dir, bundle, outcome) = write_bundle(refs.clone(), &[], Default::default())?;
+        let header = bundle.header();
+        assert_eq!(header.version, Version::V3);
+        assert_eq!(
+            header.capabilities,
+            [Capability {
+                name: "object-format".into(),
+                value: Some("sha1".into())
+            }]
+        );
+        assert!(header.prerequisites.is_empty());
+        assert_eq!(header.refs, refs);
+        assert_eq!(
+            outcome.num_objects, 7,
+            "the annotated tag, three commits, the empty tree, one tree and one blob"
+        );
+
+        let (_objects_dir, odb) = unbundle(&bundle, None)?;
+        assert!(odb.exists(&annotated_tag));
+        assert!(odb.exists(&main_tip()));
+        Ok(())
+    }
+
+    #[test]
+    fn with_prerequisites() -> crate::Result {
+        let c2 = fixture_ref("refs/tags/v1")?;
+        let refs = vec![
+            Ref {
+                name: "refs/heads/main".into(),
+                target: main_tip(),
+            },
+            Ref {
+                name: "refs/tags/v1".into(),
+                target: c2,
+            },
+        ];
+        let (_dir, bundle, outcome) = write_bundle(
+            refs,
+            &[c2],
+            write::Options {
+                version: Version::V2,
+                ..Default::default()
+            },
+        )?;
+        let header = bundle.header();
+        assert_eq!(header.version, Version::V2);
+        assert_eq!(
+            header.prerequisites,
+            [Prerequisite {
+                id: c2,
+                comment: "c2".into()
+            }]
+        );
+        assert_eq!(
+            header.refs.iter().map(|r| r.name.to_string()).collect::<Vec<_>>(),
+            ["refs/heads/main"],
+            "refs pointing to excluded commits are omitted"
+        );
+        assert_eq!(outcome.num_objects, 3, "one commit, one tree and one blob");
+
+        let repo = gix_testtools::scripted_fixture_read_only("make_bundles.sh")?.join("repo/.git/objects");
+        let (_objects_dir, unbundled) = unbundle(&bundle, Some(gix_odb::at(repo)?))?;
+        assert!(unbundled.exists(&main_tip()));
+        assert!(!unbundled.exists(&c2), "prerequisites aren't contained");
+        Ok(())
+    }
+
+    #[test]
+    fn refs_within_excluded_history_are_an_error() -> crate::Result {
+        let c2 = fixture_ref("refs/tags/v1")?;
+        let err = write_bundle(
+            vec![Ref {
+                name: "refs/tags/v1".into(),
+                target: c2,
+            }],
+            &[main_tip()],
+            Default::default(),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(err.downcast_ref::<write::Error>(), Some(write::Error::Empty)),
+            "{err:?}"
+        );
+        Ok(())
+    }
+}
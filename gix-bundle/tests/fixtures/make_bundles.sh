@@ -0,0 +1,18 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q repo
+(cd repo
+  git commit -q --allow-empty -m c1
+  git commit -q --allow-empty -m c2
+  git tag v1
+  echo content > file && git add file && git commit -q -m c3
+
+  git bundle create -q ../all.bundle --all
+  git bundle create -q ../incremental.bundle v1..main
+  git bundle create -q --version=3 ../v3.bundle main
+)
+
+(cd repo
+  git tag -a -m "annotated" v2
+)
@@ -36,6 +36,14 @@ impl Graph {
         self.files.iter().flat_map(File::iter_ids)
     }
 
+    /// Returns `true` if all files in the chain store [corrected commit dates](Commit::corrected_commit_date()).
+    ///
+    /// Like `git`, these should only be used if that's the case, as otherwise the dates of commits in different files
+    /// aren't comparable.
+    pub fn has_corrected_commit_dates(&self) -> bool {
+        self.files.iter().all(File::has_corrected_commit_dates)
+    }
+
     /// Translate the given `id` to its position in the file.
     pub fn lookup(&self, id: impl AsRef<gix_hash::oid>) -> Option<Position> {
         Some(self.lookup_by_id(id.as_ref())?.graph_pos)
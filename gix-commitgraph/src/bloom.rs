@@ -0,0 +1,125 @@
+//! Changed-path Bloom filters as written by `git commit-graph write --changed-paths`, which tell if a commit
+//! *may* have changed a path in comparison to its first parent, or if it definitely didn't.
+use bstr::{BStr, ByteSlice};
+
+/// The size of the header of the Bloom filter data chunk, which contains the [`Settings`].
+pub(crate) const DATA_HEADER_LEN: usize = 3 * 4;
+
+const SEED0: u32 = 0x293a_e76f;
+const SEED1: u32 = 0x7e64_6e2c;
+
+/// The parameters used to create all Bloom filters of a commit-graph file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Settings {
+    /// The version of the hash function, which is `1` for the original implementation of `git` that mishandles bytes with
+    /// the high bit set, or `2` for the fixed version.
+    pub hash_version: u32,
+    /// The amount of hashes each path is hashed into, `7` by default.
+    pub num_hashes: u32,
+    /// The amount of bits each path takes in a filter, `10` by default.
+    pub bits_per_entry: u32,
+}
+
+impl Settings {
+    pub(crate) fn from_bytes(data: &[u8]) -> Option<Self> {
+        let read_u32 = |pos: usize| -> u32 { u32::from_be_bytes(data[pos..][..4].try_into().expect("4 bytes")) };
+        if data.len() < DATA_HEADER_LEN {
+            return None;
+        }
+        let settings = Settings {
+            hash_version: read_u32(0),
+            num_hashes: read_u32(4),
+            bits_per_entry: read_u32(8),
+        };
+        matches!(settings.hash_version, 1 | 2).then_some(settings)
+    }
+}
+
+/// The Bloom filter of a single commit, with all paths it changed in comparison to its first parent hashed into it,
+/// along with all of their leading directories.
+#[derive(Debug, Copy, Clone)]
+pub struct Filter<'a> {
+    data: &'a [u8],
+    settings: Settings,
+}
+
+impl<'a> Filter<'a> {
+    pub(crate) fn new(data: &'a [u8], settings: Settings) -> Self {
+        Filter { data, settings }
+    }
+
+    /// The settings that were used to create this filter.
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// Return `false` if the commit definitely didn't change the slash-separated `path` relative to the root of the
+    /// repository in comparison to its first parent, or `true` if it may have changed it.
+    ///
+    /// Like `git`, the path and all of its leading directories must be contained to possibly be changed, which reduces
+    /// the rate of false positives. Note that `path` must not have a trailing slash.
+    pub fn maybe_contains_path(&self, path: &BStr) -> bool {
+        let mut path = path.as_bytes();
+        loop {
+            if !self.maybe_contains(path) {
+                return false;
+            }
+            match path.rfind_byte(b'/') {
+                Some(pos) => path = &path[..pos],
+                None => return true,
+            }
+        }
+    }
+
+    /// Return `false` if `key` definitely isn't contained in this filter, or `true` if it may be.
+    fn maybe_contains(&self, key: &[u8]) -> bool {
+        if self.data.is_empty() {
+            return true;
+        }
+        let hash_version = self.settings.hash_version;
+        let hash0 = murmur3(SEED0, key, hash_version);
+        let hash1 = murmur3(SEED1, key, hash_version);
+        let num_bits = self.data.len() as u64 * 8;
+        (0..self.settings.num_hashes).all(|idx| {
+            let bit = u64::from(hash0.wrapping_add(idx.wrapping_mul(hash1))) % num_bits;
+            self.data[(bit / 8) as usize] & (1 << (bit % 8)) != 0
+        })
+    }
+}
+
+/// The 32-bit variant of the murmur3 hash function, with `hash_version` `1` sign-extending bytes with the high bit set
+/// just like the original implementation in `git` does.
+fn murmur3(mut seed: u32, data: &[u8], hash_version: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+    let byte = |b: u8| -> u32 {
+        if hash_version == 1 {
+            b as i8 as u32
+        } else {
+            u32::from(b)
+        }
+    };
+
+    let mut blocks = data.chunks_exact(4);
+    for block in blocks.by_ref() {
+        let k = byte(block[0]) | byte(block[1]) << 8 | byte(block[2]) << 16 | byte(block[3]) << 24;
+        seed ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        seed = seed.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+    let tail = blocks.remainder();
+    if !tail.is_empty() {
+        let k = tail
+            .iter()
+            .enumerate()
+            .fold(0u32, |k, (idx, b)| k ^ byte(*b) << (idx * 8));
+        seed ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    seed ^= data.len() as u32;
+    seed ^= seed >> 16;
+    seed = seed.wrapping_mul(0x85eb_ca6b);
+    seed ^= seed >> 13;
+    seed = seed.wrapping_mul(0xc2b2_ae35);
+    seed ^= seed >> 16;
+    seed
+}
@@ -4,7 +4,8 @@ use std::{
 };
 
 use crate::{
-    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH},
+    bloom,
+    file::{self, commit::Commit, COMMIT_DATA_ENTRY_SIZE_SANS_HASH, CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW},
     File,
 };
 
@@ -95,6 +96,12 @@ impl File {
         None
     }
 
+    /// Returns `true` if this file stores corrected commit dates, i.e. generation numbers v2, in addition to
+    /// topological levels.
+    pub fn has_corrected_commit_dates(&self) -> bool {
+        self.generation_data_offset.is_some()
+    }
+
     /// Returns the number of commits in this graph file.
     ///
     /// The maximum valid `file::Position` that can be used with this file is one less than
@@ -127,6 +134,33 @@ impl File {
         &self.data[start..][..entry_size]
     }
 
+    /// Returns the changed-path Bloom filter for the given commit, if this file has Bloom filter chunks.
+    pub(crate) fn bloom_filter(&self, pos: file::Position) -> Option<bloom::Filter<'_>> {
+        let (data_range, settings) = self.bloom_data.clone()?;
+        let index = &self.data[self.bloom_index_offset?..];
+        let read_end = |pos: usize| -> usize { u32::from_be_bytes(index[pos * 4..][..4].try_into().unwrap()) as usize };
+        let pos = pos.0 as usize;
+        let start = if pos == 0 { 0 } else { read_end(pos - 1) };
+        let end = read_end(pos);
+        let data = &self.data[data_range];
+        (start <= end && end <= data.len()).then(|| bloom::Filter::new(&data[start..end], settings))
+    }
+
+    /// Returns the offset of the corrected commit date from the committer date of the given commit, if this file has a
+    /// Generation Data (GDA2) chunk and the offset isn't pointing past the end of the Generation Data Overflow (GDO2) chunk.
+    pub(crate) fn corrected_commit_date_offset(&self, pos: file::Position) -> Option<u64> {
+        let offset = &self.data[self.generation_data_offset? + pos.0 as usize * 4..][..4];
+        let offset = u32::from_be_bytes(offset.try_into().unwrap());
+        if offset & CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW == 0 {
+            return Some(offset.into());
+        }
+        let overflow = &self.data[self.generation_data_overflow_range.clone()?];
+        let pos = (offset ^ CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW) as usize * 8;
+        overflow
+            .get(pos..pos + 8)
+            .map(|bytes| u64::from_be_bytes(bytes.try_into().unwrap()))
+    }
+
     /// Returns the byte slice for this file's entire Extra Edge List (EDGE) chunk.
     pub(crate) fn extra_edges_data(&self) -> Option<&[u8]> {
         Some(&self.data[self.extra_edges_list_range.clone()?])
@@ -5,6 +5,7 @@ use std::{
 };
 
 use crate::{
+    bloom,
     file::{self, EXTENDED_EDGES_MASK, LAST_EXTENDED_EDGE_MASK, NO_PARENT},
     File, Position,
 };
@@ -50,9 +51,6 @@ impl<'a> Commit<'a> {
             root_tree_id: gix_hash::oid::from_bytes_unchecked(&bytes[..file.hash_len]),
             parent1: ParentEdge::from_raw(read_u32(&bytes[file.hash_len..][..4])),
             parent2: ParentEdge::from_raw(read_u32(&bytes[file.hash_len + 4..][..4])),
-            // TODO: Add support for corrected commit date offset overflow.
-            //      See https://github.com/git/git/commit/e8b63005c48696a26f976f5f9b0ccaf1983e439d and
-            //          https://github.com/git/git/commit/f90fca638e99a031dce8e3aca72427b2f9b4bb38 for more details and hints at a test.
             generation: read_u32(&bytes[file.hash_len + 8..][..4]) >> 2,
             commit_timestamp: u64::from_be_bytes(bytes[file.hash_len + 8..][..8].try_into().unwrap())
                 & 0x0003_ffff_ffff,
@@ -74,6 +72,18 @@ impl<'a> Commit<'a> {
         self.generation
     }
 
+    /// Returns the corrected commit date of this commit, also known as generation number v2, or `None` if the
+    /// commit-graph file was written without it.
+    ///
+    /// It's the committer timestamp of this commit, raised to be at least one more than the corrected commit dates of
+    /// all of its parents. Thus, just like [topological levels](Self::generation()), it can be used to cut traversals
+    /// short, but it's also correct in the presence of clock skew, while usually being the more effective cutoff.
+    pub fn corrected_commit_date(&self) -> Option<u64> {
+        self.file
+            .corrected_commit_date_offset(self.pos)
+            .map(|offset| self.commit_timestamp + offset)
+    }
+
     /// Returns an iterator over the parent positions for lookup in the owning [Graph][crate::Graph].
     pub fn iter_parents(self) -> Parents<'a> {
         // I didn't find a combinator approach that a) was as strict as ParentIterator, b) supported
@@ -104,6 +114,12 @@ impl<'a> Commit<'a> {
     pub fn root_tree_id(&self) -> &gix_hash::oid {
         self.root_tree_id
     }
+
+    /// Return the changed-path Bloom filter of this commit, which tells if a path may have changed in comparison to its
+    /// first parent, or `None` if the commit-graph file was written without them or if they are unusable.
+    pub fn changed_paths(&self) -> Option<bloom::Filter<'a>> {
+        self.file.bloom_filter(self.pos)
+    }
 }
 
 impl<'a> Debug for Commit<'a> {
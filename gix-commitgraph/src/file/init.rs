@@ -2,9 +2,12 @@ use std::path::Path;
 use std::path::PathBuf;
 
 use crate::{
+    bloom,
     file::{
-        ChunkId, BASE_GRAPHS_LIST_CHUNK_ID, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH,
-        EXTENDED_EDGES_LIST_CHUNK_ID, FAN_LEN, HEADER_LEN, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID, SIGNATURE,
+        ChunkId, BASE_GRAPHS_LIST_CHUNK_ID, BLOOM_FILTER_DATA_CHUNK_ID, BLOOM_FILTER_INDEX_CHUNK_ID,
+        COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH, EXTENDED_EDGES_LIST_CHUNK_ID, FAN_LEN,
+        GENERATION_DATA_CHUNK_ID, GENERATION_DATA_OVERFLOW_CHUNK_ID, HEADER_LEN, OID_FAN_CHUNK_ID, OID_LOOKUP_CHUNK_ID,
+        SIGNATURE,
     },
     File,
 };
@@ -184,6 +187,39 @@ impl File {
 
         let extra_edges_list_range = chunks.usize_offset_by_id(EXTENDED_EDGES_LIST_CHUNK_ID).ok();
 
+        let generation_data_offset = chunks
+            .validated_usize_offset_by_id(GENERATION_DATA_CHUNK_ID, |chunk_range| {
+                let chunk_size = chunk_range.len();
+                let expected_size = commit_data_count as usize * 4;
+                if chunk_size != expected_size {
+                    return Err(Error::InvalidChunkSize {
+                        id: GENERATION_DATA_CHUNK_ID,
+                        msg: format!("expected chunk length {expected_size}, got {chunk_size}"),
+                    });
+                }
+                Ok(chunk_range.start)
+            })
+            .ok()
+            .transpose()?;
+        let generation_data_overflow_range = chunks.usize_offset_by_id(GENERATION_DATA_OVERFLOW_CHUNK_ID).ok();
+
+        // Like `git`, ignore changed-path Bloom filters we can't use instead of failing, as they are optional.
+        let bloom_index_range = chunks
+            .usize_offset_by_id(BLOOM_FILTER_INDEX_CHUNK_ID)
+            .ok()
+            .filter(|range| range.len() == commit_data_count as usize * 4);
+        let bloom_data = chunks
+            .usize_offset_by_id(BLOOM_FILTER_DATA_CHUNK_ID)
+            .ok()
+            .and_then(|range| {
+                let settings = bloom::Settings::from_bytes(&data[range.clone()])?;
+                Some((range.start + bloom::DATA_HEADER_LEN..range.end, settings))
+            });
+        let (bloom_index_offset, bloom_data) = match (bloom_index_range, bloom_data) {
+            (Some(index_range), Some(data)) => (Some(index_range.start), Some(data)),
+            _ => (None, None),
+        };
+
         let trailer = &data[chunks.highest_offset() as usize..];
         if trailer.len() != object_hash.len_in_bytes() {
             return Err(Error::Trailer(format!(
@@ -220,9 +256,13 @@ impl File {
         Ok(File {
             base_graph_count,
             base_graphs_list_offset,
+            bloom_index_offset,
+            bloom_data,
             commit_data_offset,
             data,
             extra_edges_list_range,
+            generation_data_offset,
+            generation_data_overflow_range,
             fan,
             oid_lookup_offset,
             path,
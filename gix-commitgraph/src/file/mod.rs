@@ -8,6 +8,9 @@ mod access;
 pub mod commit;
 mod init;
 pub mod verify;
+///
+#[allow(clippy::empty_docs)]
+pub mod write;
 
 const COMMIT_DATA_ENTRY_SIZE_SANS_HASH: usize = 16;
 pub(crate) const FAN_LEN: usize = 256;
@@ -17,8 +20,12 @@ const SIGNATURE: &[u8] = b"CGPH";
 
 type ChunkId = gix_chunk::Id;
 const BASE_GRAPHS_LIST_CHUNK_ID: ChunkId = *b"BASE";
+const BLOOM_FILTER_INDEX_CHUNK_ID: ChunkId = *b"BIDX";
+const BLOOM_FILTER_DATA_CHUNK_ID: ChunkId = *b"BDAT";
 const COMMIT_DATA_CHUNK_ID: ChunkId = *b"CDAT";
 const EXTENDED_EDGES_LIST_CHUNK_ID: ChunkId = *b"EDGE";
+const GENERATION_DATA_CHUNK_ID: ChunkId = *b"GDA2";
+const GENERATION_DATA_OVERFLOW_CHUNK_ID: ChunkId = *b"GDO2";
 const OID_FAN_CHUNK_ID: ChunkId = *b"OIDF";
 const OID_LOOKUP_CHUNK_ID: ChunkId = *b"OIDL";
 
@@ -27,6 +34,7 @@ const OID_LOOKUP_CHUNK_ID: ChunkId = *b"OIDL";
 const NO_PARENT: u32 = 0x7000_0000;
 const EXTENDED_EDGES_MASK: u32 = 0x8000_0000;
 const LAST_EXTENDED_EDGE_MASK: u32 = 0x8000_0000;
+const CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW: u32 = 0x8000_0000;
 
 /// The position of a given commit within a graph file, starting at 0.
 ///
@@ -0,0 +1,314 @@
+use std::io::Write;
+
+use crate::{
+    file::{
+        ChunkId, COMMIT_DATA_CHUNK_ID, COMMIT_DATA_ENTRY_SIZE_SANS_HASH, CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW,
+        EXTENDED_EDGES_LIST_CHUNK_ID, EXTENDED_EDGES_MASK, FAN_LEN, GENERATION_DATA_CHUNK_ID,
+        GENERATION_DATA_OVERFLOW_CHUNK_ID, HEADER_LEN, LAST_EXTENDED_EDGE_MASK, NO_PARENT, OID_FAN_CHUNK_ID,
+        OID_LOOKUP_CHUNK_ID, SIGNATURE,
+    },
+    File, GENERATION_NUMBER_MAX, MAX_COMMITS,
+};
+
+/// The largest committer timestamp that can be stored in the commit data chunk, as it only has 34 bits for it.
+const MAX_COMMIT_TIMESTAMP: u64 = 0x0003_ffff_ffff;
+
+mod error {
+    /// The error returned by [`File::write_to()`][crate::File::write_to()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error("The parent {parent} of commit {id} isn't one of the commits to write")]
+        MissingParent {
+            id: gix_hash::ObjectId,
+            parent: gix_hash::ObjectId,
+        },
+        #[error("A commit-graph can hold at most {max} commits, got {actual}", max = crate::MAX_COMMITS)]
+        TooManyCommits { actual: usize },
+    }
+}
+pub use error::Error;
+
+/// A commit to write into a commit-graph file with [`File::write_to()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Commit {
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The id of the tree the commit points to.
+    pub root_tree_id: gix_hash::ObjectId,
+    /// The ids of all parents of the commit, in order, each of which must be written as well.
+    pub parents: Vec<gix_hash::ObjectId>,
+    /// The committer timestamp of the commit in seconds since 1970-01-01 00:00:00 UTC.
+    ///
+    /// Note that only 34 bits are stored, and larger values are truncated.
+    pub commit_timestamp: u64,
+}
+
+/// Options for use in [`File::write_to()`].
+#[derive(Debug, Copy, Clone)]
+pub struct Options {
+    /// The kind of hash used by all commits.
+    pub object_hash: gix_hash::Kind,
+    /// If `true`, write corrected commit dates, i.e. generation numbers v2, in addition to topological levels, just like
+    /// `git` does by default or with `commitGraph.generationVersion = 2`.
+    pub corrected_commit_dates: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            object_hash: gix_hash::Kind::Sha1,
+            corrected_commit_dates: true,
+        }
+    }
+}
+
+/// The result of [`File::write_to()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of commits that were written.
+    pub num_commits: u32,
+    /// The checksum of the written file, which is also its trailer.
+    pub checksum: gix_hash::ObjectId,
+}
+
+/// The information computed for each commit before writing it.
+struct Entry {
+    commit: Commit,
+    parents: Vec<u32>,
+    generation: u32,
+    corrected_commit_date: u64,
+}
+
+impl File {
+    /// Write a single commit-graph file containing all `commits` to `out`, similar to `git commit-graph write`.
+    ///
+    /// The parents of all `commits` must be contained in `commits` as well, as the written file doesn't build upon other
+    /// commit-graph files. Topological levels are always written, and corrected commit dates are written if configured in
+    /// `options`, along with offsets that don't fit into 31 bits.
+    /// Changed-path Bloom filters aren't written.
+    pub fn write_to(
+        mut commits: Vec<Commit>,
+        out: &mut dyn std::io::Write,
+        Options {
+            object_hash,
+            corrected_commit_dates,
+        }: Options,
+    ) -> Result<Outcome, Error> {
+        commits.sort_by_key(|c| c.id);
+        commits.dedup_by(|a, b| a.id == b.id);
+        let num_commits: u32 = commits
+            .len()
+            .try_into()
+            .ok()
+            .filter(|num_commits| *num_commits <= MAX_COMMITS)
+            .ok_or(Error::TooManyCommits { actual: commits.len() })?;
+
+        let entries = compute_generations(commits)?;
+        let hash_len = object_hash.len_in_bytes();
+        let num_extra_edges: usize = entries
+            .iter()
+            .filter(|entry| entry.parents.len() > 2)
+            .map(|entry| entry.parents.len() - 1)
+            .sum();
+        let num_overflowing_offsets = if corrected_commit_dates {
+            entries
+                .iter()
+                .filter(|entry| corrected_commit_date_offset(entry) >= CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW.into())
+                .count()
+        } else {
+            0
+        };
+
+        let mut cf = gix_chunk::file::Index::for_writing();
+        cf.plan_chunk(OID_FAN_CHUNK_ID, (FAN_LEN * 4) as u64);
+        cf.plan_chunk(OID_LOOKUP_CHUNK_ID, (entries.len() * hash_len) as u64);
+        cf.plan_chunk(
+            COMMIT_DATA_CHUNK_ID,
+            (entries.len() * (hash_len + COMMIT_DATA_ENTRY_SIZE_SANS_HASH)) as u64,
+        );
+        if corrected_commit_dates {
+            cf.plan_chunk(GENERATION_DATA_CHUNK_ID, (entries.len() * 4) as u64);
+            if num_overflowing_offsets > 0 {
+                cf.plan_chunk(GENERATION_DATA_OVERFLOW_CHUNK_ID, (num_overflowing_offsets * 8) as u64);
+            }
+        }
+        if num_extra_edges > 0 {
+            cf.plan_chunk(EXTENDED_EDGES_LIST_CHUNK_ID, (num_extra_edges * 4) as u64);
+        }
+
+        let mut out = gix_features::hash::Write::new(out, object_hash);
+        out.write_all(SIGNATURE)?;
+        out.write_all(&[
+            1, /* version */
+            object_hash as u8,
+            cf.num_chunks() as u8,
+            0, /* base graphs */
+        ])?;
+
+        let mut chunk_write = cf.into_write(&mut out, HEADER_LEN)?;
+        while let Some(chunk_to_write) = chunk_write.next_chunk() {
+            write_chunk(chunk_to_write, &entries, &mut chunk_write)?;
+        }
+
+        let checksum: gix_hash::ObjectId = out.hash.digest().into();
+        out.inner.write_all(checksum.as_slice())?;
+        Ok(Outcome { num_commits, checksum })
+    }
+}
+
+fn write_chunk(id: ChunkId, entries: &[Entry], out: &mut dyn std::io::Write) -> std::io::Result<()> {
+    match id {
+        OID_FAN_CHUNK_ID => {
+            let mut fan = [0u32; FAN_LEN];
+            for entry in entries {
+                fan[entry.commit.id.first_byte() as usize] += 1;
+            }
+            let mut count = 0;
+            for num_ids in fan {
+                count += num_ids;
+                out.write_all(&count.to_be_bytes())?;
+            }
+        }
+        OID_LOOKUP_CHUNK_ID => {
+            for entry in entries {
+                out.write_all(entry.commit.id.as_slice())?;
+            }
+        }
+        COMMIT_DATA_CHUNK_ID => {
+            let mut extra_edge_index = 0;
+            for entry in entries {
+                out.write_all(entry.commit.root_tree_id.as_slice())?;
+                let parent1 = entry.parents.first().copied().unwrap_or(NO_PARENT);
+                let parent2 = match entry.parents.len() {
+                    0 | 1 => NO_PARENT,
+                    2 => entry.parents[1],
+                    num_parents => {
+                        let edge = EXTENDED_EDGES_MASK | extra_edge_index;
+                        extra_edge_index += num_parents as u32 - 1;
+                        edge
+                    }
+                };
+                out.write_all(&parent1.to_be_bytes())?;
+                out.write_all(&parent2.to_be_bytes())?;
+                let timestamp = entry.commit.commit_timestamp & MAX_COMMIT_TIMESTAMP;
+                let generation_and_high_bits = (entry.generation << 2) | (timestamp >> 32) as u32;
+                out.write_all(&generation_and_high_bits.to_be_bytes())?;
+                out.write_all(&(timestamp as u32).to_be_bytes())?;
+            }
+        }
+        GENERATION_DATA_CHUNK_ID => {
+            let mut overflow_index = 0;
+            for entry in entries {
+                let offset = corrected_commit_date_offset(entry);
+                let offset = if offset >= CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW.into() {
+                    overflow_index += 1;
+                    CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW | (overflow_index - 1)
+                } else {
+                    offset as u32
+                };
+                out.write_all(&offset.to_be_bytes())?;
+            }
+        }
+        GENERATION_DATA_OVERFLOW_CHUNK_ID => {
+            for entry in entries {
+                let offset = corrected_commit_date_offset(entry);
+                if offset >= CORRECTED_COMMIT_DATE_OFFSET_OVERFLOW.into() {
+                    out.write_all(&offset.to_be_bytes())?;
+                }
+            }
+        }
+        EXTENDED_EDGES_LIST_CHUNK_ID => {
+            for entry in entries.iter().filter(|entry| entry.parents.len() > 2) {
+                let (last, others) = entry.parents[1..].split_last().expect("more than two parents");
+                for parent in others {
+                    out.write_all(&parent.to_be_bytes())?;
+                }
+                out.write_all(&(LAST_EXTENDED_EDGE_MASK | last).to_be_bytes())?;
+            }
+        }
+        unknown => unreachable!("BUG: forgot to implement chunk {:?}", std::str::from_utf8(&unknown)),
+    }
+    Ok(())
+}
+
+/// Return the amount of seconds the corrected commit date of `entry` is ahead of its committer timestamp.
+fn corrected_commit_date_offset(entry: &Entry) -> u64 {
+    entry.corrected_commit_date - (entry.commit.commit_timestamp & MAX_COMMIT_TIMESTAMP)
+}
+
+/// Resolve the parents of all `commits`, which are sorted by id, to their positions, and compute their topological levels
+/// and corrected commit dates, parents first.
+fn compute_generations(commits: Vec<Commit>) -> Result<Vec<Entry>, Error> {
+    let parents = commits
+        .iter()
+        .map(|commit| {
+            commit
+                .parents
+                .iter()
+                .map(|parent| {
+                    commits
+                        .binary_search_by(|other| other.id.cmp(parent))
+                        .map(|pos| pos as u32)
+                        .map_err(|_| Error::MissingParent {
+                            id: commit.id,
+                            parent: *parent,
+                        })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut entries: Vec<_> = commits
+        .into_iter()
+        .zip(parents)
+        .map(|(commit, parents)| Entry {
+            commit,
+            parents,
+            generation: 0,
+            corrected_commit_date: 0,
+        })
+        .collect();
+
+    // A generation of 0 marks commits which weren't computed yet, as the generation of commits without parents is 1.
+    let mut stack = Vec::new();
+    for start in 0..entries.len() {
+        stack.push(start);
+        while let Some(&pos) = stack.last() {
+            if entries[pos].generation != 0 {
+                stack.pop();
+                continue;
+            }
+            let num_pending = stack.len();
+            stack.extend(
+                entries[pos]
+                    .parents
+                    .iter()
+                    .map(|parent| *parent as usize)
+                    .filter(|parent| entries[*parent].generation == 0),
+            );
+            if stack.len() != num_pending {
+                continue;
+            }
+            stack.pop();
+
+            let entry = &entries[pos];
+            let (max_parent_generation, max_parent_date) =
+                entry.parents.iter().fold((0, None), |(generation, date), parent| {
+                    let parent = &entries[*parent as usize];
+                    (
+                        generation.max(parent.generation),
+                        date.max(Some(parent.corrected_commit_date)),
+                    )
+                });
+            let timestamp = entry.commit.commit_timestamp & MAX_COMMIT_TIMESTAMP;
+            let generation = (max_parent_generation + 1).min(GENERATION_NUMBER_MAX);
+            let corrected_commit_date = max_parent_date.map_or(timestamp, |date| timestamp.max(date + 1));
+            let entry = &mut entries[pos];
+            entry.generation = generation;
+            entry.corrected_commit_date = corrected_commit_date;
+        }
+    }
+    Ok(entries)
+}
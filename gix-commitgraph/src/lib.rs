@@ -1,4 +1,4 @@
-//! Read, verify, and traverse git commit graphs.
+//! Read, verify, traverse, and write git commit graphs.
 //!
 //! A [commit graph][Graph] is an index of commits in the git commit history.
 //! The [Graph] stores commit data in a way that accelerates lookups considerably compared to
@@ -24,9 +24,13 @@ use std::path::Path;
 pub struct File {
     base_graph_count: u8,
     base_graphs_list_offset: Option<usize>,
+    bloom_index_offset: Option<usize>,
+    bloom_data: Option<(std::ops::Range<usize>, bloom::Settings)>,
     commit_data_offset: usize,
     data: memmap2::Mmap,
     extra_edges_list_range: Option<std::ops::Range<usize>>,
+    generation_data_offset: Option<usize>,
+    generation_data_overflow_range: Option<std::ops::Range<usize>>,
     fan: [u32; file::FAN_LEN],
     oid_lookup_offset: usize,
     path: std::path::PathBuf,
@@ -49,6 +53,7 @@ pub fn at(path: impl AsRef<Path>) -> Result<Graph, init::Error> {
 }
 
 mod access;
+pub mod bloom;
 pub mod file;
 ///
 #[allow(clippy::empty_docs)]
@@ -37,6 +37,12 @@ fn single_commit_huge_dates_overflow_v1() {
         "this is the value we would want to see, but it's not possible in V1"
     );
     assert_eq!(actual.generation(), 1, "generations are fine though");
+    assert_eq!(
+        actual.corrected_commit_date(),
+        None,
+        "corrected commit dates are only written with generation numbers v2"
+    );
+    assert!(!cg.has_corrected_commit_dates());
 }
 
 #[test]
@@ -69,6 +75,25 @@ fn generation_numbers_overflow_is_handled_in_chained_graph() {
     }
 }
 
+#[test]
+fn corrected_commit_dates_with_offset_overflow_in_chained_graph() {
+    let names = ["future-1", "old-1", "future-2", "old-2", "extra"];
+    let (cg, refs) = graph_and_expected("generation_number_overflow.sh", &names);
+    assert!(cg.has_corrected_commit_dates());
+    for (name, expected) in names.iter().zip(4147483646..) {
+        let commit = cg.commit_by_id(refs[*name].id).expect("present");
+        assert_eq!(
+            commit.corrected_commit_date(),
+            Some(expected),
+            "{name}: each commit is at least one second younger than its parent, even those from the past whose offsets don't fit into 31 bits"
+        );
+        assert!(
+            commit.corrected_commit_date() >= Some(commit.committer_timestamp()),
+            "corrected dates are never older than the actual commit time"
+        );
+    }
+}
+
 #[test]
 fn octupus_merges() {
     let (cg, refs) = graph_and_expected(
@@ -111,3 +136,44 @@ fn two_parents() {
     assert_eq!(cg.commit_at(refs["parent2"].pos()).generation(), 1);
     assert_eq!(cg.commit_at(refs["child"].pos()).generation(), 2);
 }
+
+#[test]
+fn changed_paths() {
+    let (cg, refs) = graph_and_expected("changed_paths.sh", &["root", "change-a", "change-c", "change-u"]);
+    check_common(&cg, &refs);
+
+    let paths = ["a", "dir", "dir/b", "dir/sub", "dir/sub/c", "dir/ü.txt"];
+    for (name, changed) in [
+        ("root", &paths[..]),
+        ("change-a", &["a"][..]),
+        ("change-c", &["dir", "dir/sub", "dir/sub/c"]),
+        ("change-u", &["dir", "dir/ü.txt"]),
+    ] {
+        let filter = cg
+            .commit_at(refs[name].pos())
+            .changed_paths()
+            .expect("written with changed paths");
+        assert_eq!(
+            filter.settings().hash_version,
+            1,
+            "git writes the first version by default"
+        );
+        for path in paths {
+            assert_eq!(
+                filter.maybe_contains_path(path.into()),
+                changed.contains(&path),
+                "{name}: {path}"
+            );
+        }
+        assert!(
+            !filter.maybe_contains_path("dir/sub/c/does-not-exist".into()),
+            "{name}: paths within changed files aren't contained"
+        );
+    }
+}
+
+#[test]
+fn without_changed_paths() {
+    let (cg, refs) = graph_and_expected("single_parent.sh", &["parent", "child"]);
+    assert!(cg.commit_at(refs["child"].pos()).changed_paths().is_none());
+}
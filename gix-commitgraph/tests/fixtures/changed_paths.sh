@@ -0,0 +1,28 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config core.quotePath false
+
+mkdir -p dir/sub
+echo a >a
+echo b >dir/b
+echo c >dir/sub/c
+echo u >"dir/ü.txt"
+git add .
+git commit -q -m root
+git tag root
+
+echo a2 >a
+git commit -q -am change-a
+git tag change-a
+
+echo c2 >dir/sub/c
+git commit -q -am change-c
+git tag change-c
+
+echo u2 >"dir/ü.txt"
+git commit -q -am change-u
+git tag change-u
+
+git commit-graph write --no-progress --reachable --changed-paths
@@ -0,0 +1,109 @@
+use gix_commitgraph::{file::write, Graph};
+use gix_testtools::scripted_fixture_read_only;
+
+/// Write all commits of the commit-graph created by `script` into a single file, and return both graphs.
+fn rewrite(script: &str, options: write::Options) -> gix_testtools::Result<(Graph, Graph, write::Outcome)> {
+    let info_dir = scripted_fixture_read_only(script)?.join(".git/objects/info");
+    let expected = Graph::from_info_dir(&info_dir)?;
+    let commits = expected
+        .iter_commits()
+        .map(|commit| -> gix_testtools::Result<_> {
+            Ok(write::Commit {
+                id: commit.id().to_owned(),
+                root_tree_id: commit.root_tree_id().to_owned(),
+                parents: commit
+                    .iter_parents()
+                    .map(|pos| pos.map(|pos| expected.id_at(pos).to_owned()))
+                    .collect::<Result<_, _>>()?,
+                commit_timestamp: commit.committer_timestamp(),
+            })
+        })
+        .collect::<gix_testtools::Result<Vec<_>>>()?;
+
+    let dir = gix_testtools::tempfile::tempdir()?;
+    let path = dir.path().join("commit-graph");
+    let outcome = gix_commitgraph::File::write_to(commits, &mut std::fs::File::create(&path)?, options)?;
+    let actual = Graph::from_file(&path)?;
+    actual.verify_integrity(|_| Ok::<_, std::convert::Infallible>(()))?;
+    Ok((expected, actual, outcome))
+}
+
+fn assert_same_commits(expected: &Graph, actual: &Graph) {
+    assert_eq!(actual.num_commits(), expected.num_commits());
+    for expected_commit in expected.iter_commits() {
+        let id = expected_commit.id();
+        let actual_commit = actual.commit_by_id(id).expect("all commits are written");
+        assert_eq!(actual_commit.root_tree_id(), expected_commit.root_tree_id(), "{id}");
+        assert_eq!(
+            actual_commit.committer_timestamp(),
+            expected_commit.committer_timestamp(),
+            "{id}"
+        );
+        assert_eq!(actual_commit.generation(), expected_commit.generation(), "{id}");
+        let parent_ids = |graph: &Graph, commit: gix_commitgraph::file::Commit<'_>| {
+            commit
+                .iter_parents()
+                .map(|pos| graph.id_at(pos.expect("valid")).to_owned())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            parent_ids(actual, actual_commit),
+            parent_ids(expected, expected_commit),
+            "{id}"
+        );
+    }
+}
+
+#[test]
+fn corrected_commit_dates_with_offset_overflow_round_trip() -> gix_testtools::Result {
+    let (expected, actual, outcome) = rewrite("generation_number_overflow.sh", Default::default())?;
+    assert_eq!(outcome.num_commits, 5);
+    assert_eq!(actual.num_commits(), 5, "the chain of three files is written into one");
+    assert_same_commits(&expected, &actual);
+    assert!(actual.has_corrected_commit_dates());
+    for expected_commit in expected.iter_commits() {
+        assert_eq!(
+            actual
+                .commit_by_id(expected_commit.id())
+                .expect("present")
+                .corrected_commit_date(),
+            expected_commit.corrected_commit_date(),
+            "corrected commit dates are the same as the ones written by git, even those with overflowing offsets"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn without_corrected_commit_dates() -> gix_testtools::Result {
+    let (expected, actual, _) = rewrite(
+        "generation_number_overflow.sh",
+        write::Options {
+            corrected_commit_dates: false,
+            ..Default::default()
+        },
+    )?;
+    assert_same_commits(&expected, &actual);
+    assert!(!actual.has_corrected_commit_dates());
+    Ok(())
+}
+
+#[test]
+fn octopus_merges_use_extra_edges() -> gix_testtools::Result {
+    let (expected, actual, _) = rewrite("octopus_merges.sh", Default::default())?;
+    assert_same_commits(&expected, &actual);
+    Ok(())
+}
+
+#[test]
+fn missing_parents_are_an_error() {
+    let id = |hex: &str| gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("valid");
+    let commit = write::Commit {
+        id: id("1111111111111111111111111111111111111111"),
+        root_tree_id: id("4b825dc642cb6eb9a060e54bf8d69288fbee4904"),
+        parents: vec![id("2222222222222222222222222222222222222222")],
+        commit_timestamp: 0,
+    };
+    let err = gix_commitgraph::File::write_to(vec![commit], &mut Vec::new(), Default::default()).unwrap_err();
+    assert!(matches!(err, write::Error::MissingParent { .. }));
+}
@@ -9,7 +9,7 @@ pub fn main() -> Result<(), gix_credentials::program::main::Error> {
             gix_credentials::helper::Cascade::default()
                 .invoke(
                     match action {
-                        Get => gix_credentials::helper::Action::Get(context),
+                        Get => gix_credentials::helper::Action::Get(Box::new(context)),
                         Erase => gix_credentials::helper::Action::Erase(context.to_bstring()),
                         Store => gix_credentials::helper::Action::Store(context.to_bstring()),
                     },
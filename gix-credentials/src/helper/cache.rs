@@ -0,0 +1,123 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    helper::{cascade::is_expired, Action, Outcome, Result},
+    protocol::Context,
+};
+
+/// An in-memory credential store that works like `git credential-cache`, but without the need for a daemon.
+///
+/// Credentials are added when [storing](Action::Store) them and are forgotten after [`timeout`](Cache::new())
+/// or once they are [erased](Action::Erase), or if their `password_expiry_utc` lies in the past.
+///
+/// All clones share the same storage, which allows to keep credentials across multiple [cascades](crate::helper::Cascade).
+#[derive(Clone)]
+pub struct Cache {
+    entries: Arc<Mutex<Vec<Entry>>>,
+    timeout: Duration,
+}
+
+struct Entry {
+    ctx: Context,
+    stored_at: Instant,
+}
+
+impl std::fmt::Debug for Cache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Don't show the credentials themselves.
+        f.debug_struct("Cache")
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for Cache {
+    /// Keep credentials for 15 minutes, like `git credential-cache` does by default.
+    fn default() -> Self {
+        Cache::new(Duration::from_secs(900))
+    }
+}
+
+impl Cache {
+    /// Create a new empty cache that forgets credentials `timeout` after they were stored.
+    pub fn new(timeout: Duration) -> Self {
+        Cache {
+            entries: Default::default(),
+            timeout,
+        }
+    }
+
+    /// Perform `action` like a credential helper program would if [invoked](crate::helper::invoke()) with it.
+    ///
+    /// When getting credentials, the context of `action` is expected to be destructured into its parts already,
+    /// as is the case when used within a [`Cascade`](crate::helper::Cascade).
+    pub fn invoke(&self, action: &Action) -> Result {
+        let mut entries = self.entries.lock().expect("no panics while holding the lock");
+        let timeout = self.timeout;
+        entries.retain(|entry| {
+            entry.stored_at.elapsed() < timeout && !entry.ctx.password_expiry_utc.is_some_and(is_expired)
+        });
+        match action {
+            Action::Get(want) => Ok(entries
+                .iter()
+                .rev()
+                .find(|entry| matches(want, &entry.ctx))
+                .map(|entry| Outcome {
+                    username: entry.ctx.username.clone(),
+                    password: entry.ctx.password.clone(),
+                    quit: false,
+                    next: entry.ctx.clone().into(),
+                })),
+            Action::Store(payload) => {
+                let ctx = Context::from_bytes(payload)?;
+                if ctx.protocol.is_some() && ctx.host.is_some() && ctx.username.is_some() && ctx.password.is_some() {
+                    entries.retain(|entry| !is_same_identity(&ctx, &entry.ctx));
+                    entries.push(Entry {
+                        ctx: Context {
+                            url: None,
+                            quit: None,
+                            wwwauth: Vec::new(),
+                            ..ctx
+                        },
+                        stored_at: Instant::now(),
+                    });
+                }
+                Ok(None)
+            }
+            Action::Erase(payload) => {
+                let want = Context::from_bytes(payload)?;
+                entries.retain(|entry| {
+                    !(matches(&want, &entry.ctx)
+                        && want
+                            .password
+                            .as_ref()
+                            .map_or(true, |password| entry.ctx.password.as_ref() == Some(password)))
+                });
+                Ok(None)
+            }
+        }
+    }
+
+    /// Forget all stored credentials.
+    pub fn clear(&self) {
+        self.entries.lock().expect("no panics while holding the lock").clear();
+    }
+}
+
+/// Return `true` if all fields that are set in `want` have the same value in `have`.
+fn matches(want: &Context, have: &Context) -> bool {
+    fn field_matches<T: PartialEq>(want: &Option<T>, have: &Option<T>) -> bool {
+        want.as_ref().map_or(true, |want| have.as_ref() == Some(want))
+    }
+    field_matches(&want.protocol, &have.protocol)
+        && field_matches(&want.host, &have.host)
+        && field_matches(&want.path, &have.path)
+        && field_matches(&want.username, &have.username)
+}
+
+fn is_same_identity(a: &Context, b: &Context) -> bool {
+    a.protocol == b.protocol && a.host == b.host && a.path == b.path && a.username == b.username
+}
@@ -7,6 +7,8 @@ impl Default for Cascade {
             stderr: true,
             use_http_path: false,
             query_user_only: false,
+            cache: None,
+            prompt: None,
         }
     }
 }
@@ -59,15 +61,35 @@ impl Cascade {
         self.query_user_only = toggle;
         self
     }
+
+    /// Consult `cache` before running any program when getting credentials, and keep it up to date when storing or
+    /// erasing them.
+    ///
+    /// As the `cache` shares its storage with all of its clones, it can be used across multiple cascades.
+    pub fn cache(mut self, cache: helper::Cache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Use `prompt` to ask the user for missing usernames and passwords instead of the [terminal](gix_prompt::Terminal),
+    /// which allows applications to provide their own user interface.
+    ///
+    /// Note that it's not called if prompting is disabled when [invoking](Self::invoke()) the cascade.
+    pub fn prompt(mut self, prompt: impl gix_prompt::Prompt + Send + Sync + 'static) -> Self {
+        self.prompt = Some(std::sync::Arc::new(prompt));
+        self
+    }
 }
 
 /// Finalize
 impl Cascade {
     /// Invoke the cascade by `invoking` each program with `action`, and configuring potential prompts with `prompt` options.
     /// The latter can also be used to disable the prompt entirely when setting the `mode` to [`Disable`][gix_prompt::Mode::Disable];=.
+    /// Prompts are shown with the [custom prompt](Self::prompt()) if set, or in the terminal after trying the `askpass` program.
     ///
-    /// When _getting_ credentials, all programs are asked until the credentials are complete, stopping the cascade.
-    /// When _storing_ or _erasing_ all programs are instructed in order.
+    /// When _getting_ credentials, the [cache](Self::cache) and then all programs are asked until the credentials are
+    /// complete, stopping the cascade. Passwords that helpers report as expired via `password_expiry_utc` are ignored.
+    /// When _storing_ or _erasing_ the cache and all programs are instructed in order.
     #[allow(clippy::result_large_err)]
     pub fn invoke(&mut self, mut action: helper::Action, mut prompt: gix_prompt::Options<'_>) -> protocol::Result {
         let mut url = action
@@ -83,37 +105,22 @@ impl Cascade {
             .transpose()?
             .and_then(|ctx| ctx.url.take());
 
-        for program in &mut self.programs {
+        let mut is_complete = false;
+        if let Some(cache) = &self.cache {
+            if let Some(outcome) = cache.invoke(&action)? {
+                let ctx = Context::from_bytes(&outcome.next.previous_output)?;
+                is_complete = merge_helper_output(&mut action, ctx, &mut url, self.use_http_path)?;
+            }
+        }
+
+        for program in self.programs.iter_mut().filter(|_| !is_complete) {
             program.stderr = self.stderr;
             match helper::invoke::raw(program, &action) {
                 Ok(None) => {}
                 Ok(Some(stdout)) => {
                     let ctx = Context::from_bytes(&stdout)?;
-                    if let Some(dst_ctx) = action.context_mut() {
-                        if let Some(src) = ctx.path {
-                            dst_ctx.path = Some(src);
-                        }
-                        for (src, dst) in [
-                            (ctx.protocol, &mut dst_ctx.protocol),
-                            (ctx.host, &mut dst_ctx.host),
-                            (ctx.username, &mut dst_ctx.username),
-                            (ctx.password, &mut dst_ctx.password),
-                        ] {
-                            if let Some(src) = src {
-                                *dst = Some(src);
-                            }
-                        }
-                        if let Some(src) = ctx.url {
-                            dst_ctx.url = Some(src);
-                            url = dst_ctx.destructure_url_in_place(self.use_http_path)?.url.take();
-                        }
-                        if dst_ctx.username.is_some() && dst_ctx.password.is_some() {
-                            break;
-                        }
-                        if ctx.quit.unwrap_or_default() {
-                            dst_ctx.quit = ctx.quit;
-                            break;
-                        }
+                    if merge_helper_output(&mut action, ctx, &mut url, self.use_http_path)? {
+                        break;
                     }
                 }
                 Err(helper::Error::CredentialsHelperFailed { .. }) => continue, // ignore helpers that we can't call
@@ -125,10 +132,15 @@ impl Cascade {
         if prompt.mode != gix_prompt::Mode::Disable {
             if let Some(ctx) = action.context_mut() {
                 ctx.url = url;
+                let prompter: &dyn gix_prompt::Prompt = match self.prompt.as_deref() {
+                    Some(prompt) => prompt,
+                    None => &gix_prompt::Terminal,
+                };
                 if ctx.username.is_none() {
                     let message = ctx.to_prompt("Username");
                     prompt.mode = gix_prompt::Mode::Visible;
-                    ctx.username = gix_prompt::ask(&message, &prompt)
+                    ctx.username = prompter
+                        .ask(&message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
                             prompt: message,
                             source: err,
@@ -138,7 +150,8 @@ impl Cascade {
                 if ctx.password.is_none() {
                     let message = ctx.to_prompt("Password");
                     prompt.mode = gix_prompt::Mode::Hidden;
-                    ctx.password = gix_prompt::ask(&message, &prompt)
+                    ctx.password = prompter
+                        .ask(&message, &prompt)
                         .map_err(|err| protocol::Error::Prompt {
                             prompt: message,
                             source: err,
@@ -159,3 +172,59 @@ impl Cascade {
         )
     }
 }
+
+/// Merge `ctx` as obtained from a helper into the context of `action`, and return `true` if no more helpers should be asked.
+#[allow(clippy::result_large_err)]
+fn merge_helper_output(
+    action: &mut helper::Action,
+    mut ctx: Context,
+    url: &mut Option<bstr::BString>,
+    use_http_path: bool,
+) -> Result<bool, protocol::Error> {
+    let Some(dst_ctx) = action.context_mut() else {
+        return Ok(false);
+    };
+    if ctx.password_expiry_utc.is_some_and(is_expired) {
+        ctx.password = None;
+        ctx.password_expiry_utc = None;
+    }
+    if let Some(src) = ctx.path {
+        dst_ctx.path = Some(src);
+    }
+    for (src, dst) in [
+        (ctx.protocol, &mut dst_ctx.protocol),
+        (ctx.host, &mut dst_ctx.host),
+        (ctx.username, &mut dst_ctx.username),
+        (ctx.password, &mut dst_ctx.password),
+        (ctx.oauth_refresh_token, &mut dst_ctx.oauth_refresh_token),
+        (ctx.authtype, &mut dst_ctx.authtype),
+        (ctx.credential, &mut dst_ctx.credential),
+    ] {
+        if let Some(src) = src {
+            *dst = Some(src);
+        }
+    }
+    if let Some(src) = ctx.password_expiry_utc {
+        dst_ctx.password_expiry_utc = Some(src);
+    }
+    if let Some(src) = ctx.url {
+        dst_ctx.url = Some(src);
+        *url = dst_ctx.destructure_url_in_place(use_http_path)?.url.take();
+    }
+    if dst_ctx.username.is_some() && dst_ctx.password.is_some() {
+        return Ok(true);
+    }
+    if ctx.quit.unwrap_or_default() {
+        dst_ctx.quit = ctx.quit;
+        return Ok(true);
+    }
+    Ok(false)
+}
+
+/// Return `true` if `seconds` since the unix epoch lie in the past.
+pub(crate) fn is_expired(seconds: i64) -> bool {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64);
+    seconds <= now
+}
@@ -16,6 +16,10 @@ pub struct Cascade {
     /// If true, default false, when getting credentials, we will set a bogus password to only obtain the user name.
     /// Storage and cancellation work the same, but without a password set.
     pub query_user_only: bool,
+    /// If set, an in-memory cache to consult before running any of the `programs`.
+    pub cache: Option<Cache>,
+    /// If set, the prompt to use for asking the user for missing usernames and passwords instead of the terminal.
+    pub prompt: Option<std::sync::Arc<dyn gix_prompt::Prompt + Send + Sync>>,
 }
 
 /// The outcome of the credentials helper [invocation][crate::helper::invoke()].
@@ -65,7 +69,7 @@ pub enum Error {
 #[derive(Clone, Debug)]
 pub enum Action {
     /// Provide credentials using the given repository context, which must include the repository url.
-    Get(Context),
+    Get(Box<Context>),
     /// Approve the credentials as identified by the previous input provided as `BString`, containing information from [`Context`].
     Store(BString),
     /// Reject the credentials as identified by the previous input provided as `BString`. containing information from [`Context`].
@@ -77,10 +81,10 @@ impl Action {
     /// Create a `Get` action with context containing the given URL.
     /// Note that this creates an `Action` suitable for the credential helper cascade only.
     pub fn get_for_url(url: impl Into<BString>) -> Action {
-        Action::Get(Context {
+        Action::Get(Box::new(Context {
             url: Some(url.into()),
             ..Default::default()
-        })
+        }))
     }
 }
 
@@ -166,6 +170,11 @@ impl NextAction {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod cache;
+pub use cache::Cache;
+
 mod cascade;
 pub(crate) mod invoke;
 
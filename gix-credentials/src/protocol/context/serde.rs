@@ -24,11 +24,16 @@ mod write {
                     write_key(&mut out, key, value.as_ref()).ok();
                 }
             }
+            let password_expiry_utc = self.password_expiry_utc.map(|seconds| seconds.to_string());
             for (key, value) in [
                 ("protocol", &self.protocol),
                 ("host", &self.host),
                 ("username", &self.username),
                 ("password", &self.password),
+                ("password_expiry_utc", &password_expiry_utc),
+                ("oauth_refresh_token", &self.oauth_refresh_token),
+                ("authtype", &self.authtype),
+                ("credential", &self.credential),
             ] {
                 if let Some(value) = value {
                     validate(key, value.as_str().into())
@@ -36,6 +41,11 @@ mod write {
                     write_key(&mut out, key, value.as_bytes().as_bstr()).ok();
                 }
             }
+            for value in &self.wwwauth {
+                let key = "wwwauth[]";
+                validate(key, value.as_ref()).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                write_key(&mut out, key, value.as_ref()).ok();
+            }
             Ok(())
         }
 
@@ -85,7 +95,13 @@ pub mod decode {
             }) {
                 let (key, value) = res?;
                 match key {
-                    "protocol" | "host" | "username" | "password" => {
+                    "protocol"
+                    | "host"
+                    | "username"
+                    | "password"
+                    | "oauth_refresh_token"
+                    | "authtype"
+                    | "credential" => {
                         if !value.is_utf8() {
                             return Err(Error::IllformedUtf8InValue { key: key.into(), value });
                         }
@@ -95,9 +111,19 @@ pub mod decode {
                             "host" => &mut ctx.host,
                             "username" => &mut ctx.username,
                             "password" => &mut ctx.password,
+                            "oauth_refresh_token" => &mut ctx.oauth_refresh_token,
+                            "authtype" => &mut ctx.authtype,
+                            "credential" => &mut ctx.credential,
                             _ => unreachable!("checked field names in match above"),
                         } = Some(value);
                     }
+                    "password_expiry_utc" => {
+                        // Like git, ignore values that aren't a valid timestamp.
+                        ctx.password_expiry_utc = value.to_str().ok().and_then(|seconds| seconds.parse().ok());
+                    }
+                    // An empty value resets the list, as is customary for multi-valued keys.
+                    "wwwauth[]" if value.is_empty() => ctx.wwwauth.clear(),
+                    "wwwauth[]" => ctx.wwwauth.push(value),
                     "url" => ctx.url = Some(value),
                     "path" => ctx.path = Some(value),
                     "quit" => {
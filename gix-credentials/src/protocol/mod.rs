@@ -27,7 +27,7 @@ pub enum Error {
     #[error(transparent)]
     InvokeHelper(#[from] helper::Error),
     #[error("Could not obtain identity for context: {}", { let mut buf = Vec::<u8>::new(); context.write_to(&mut buf).ok(); String::from_utf8_lossy(&buf).into_owned() })]
-    IdentityMissing { context: Context },
+    IdentityMissing { context: Box<Context> },
     #[error("The handler asked to stop trying to obtain credentials")]
     Quit,
     #[error("Couldn't obtain {prompt}")]
@@ -48,6 +48,22 @@ pub struct Context {
     pub username: Option<String>,
     /// The credential’s password, if we are asking it to be stored.
     pub password: Option<String>,
+    /// The time in seconds since the unix epoch after which the password is no longer valid, as is common for access tokens.
+    ///
+    /// Passwords that are already expired when obtained from a helper are ignored.
+    pub password_expiry_utc: Option<i64>,
+    /// An OAuth refresh token that may accompany a password which is an OAuth access token.
+    ///
+    /// It isn't used for authentication, but passed on to helpers so they can store it and use it to obtain a new access token.
+    pub oauth_refresh_token: Option<String>,
+    /// The values of all `WWW-Authenticate` headers the server responded with, in order, for use by helpers to determine
+    /// the kind of credential to provide.
+    pub wwwauth: Vec<BString>,
+    /// The authentication scheme to use with [`credential`](Self::credential), like `Bearer`, as an alternative to
+    /// `username` and `password`.
+    pub authtype: Option<String>,
+    /// The pre-encoded credential to use with [`authtype`](Self::authtype), like a bearer token.
+    pub credential: Option<String>,
     /// When this special attribute is read by git credential, the value is parsed as a URL and treated as if its constituent
     /// parts were read (e.g., url=<https://example.com> would behave as if
     /// protocol=https and host=example.com had been provided). This can help callers avoid parsing URLs themselves.
@@ -59,9 +75,12 @@ pub struct Context {
 /// Convert the outcome of a helper invocation to a helper result, assuring that the identity is complete in the process.
 #[allow(clippy::result_large_err)]
 pub fn helper_outcome_to_result(outcome: Option<helper::Outcome>, action: helper::Action) -> Result {
-    fn redact(mut ctx: Context) -> Context {
-        if let Some(pw) = ctx.password.as_mut() {
-            *pw = "<redacted>".into()
+    fn redact(mut ctx: Box<Context>) -> Box<Context> {
+        for secret in [&mut ctx.password, &mut ctx.oauth_refresh_token, &mut ctx.credential]
+            .into_iter()
+            .flatten()
+        {
+            *secret = "<redacted>".into()
         }
         ctx
     }
@@ -0,0 +1,5 @@
+#!/bin/bash
+
+echo username=user
+echo password=expired
+echo password_expiry_utc=1
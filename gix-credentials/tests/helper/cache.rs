@@ -0,0 +1,113 @@
+use gix_credentials::{
+    helper::{Action, Cache},
+    protocol::Context,
+};
+
+fn ctx(host: &str, username: Option<&str>, password: Option<&str>) -> Context {
+    Context {
+        protocol: Some("https".into()),
+        host: Some(host.into()),
+        username: username.map(Into::into),
+        password: password.map(Into::into),
+        ..Default::default()
+    }
+}
+
+fn get(cache: &Cache, want: Context) -> Option<(Option<String>, Option<String>)> {
+    cache
+        .invoke(&Action::Get(Box::new(want)))
+        .unwrap()
+        .map(|outcome| (outcome.username, outcome.password))
+}
+
+#[test]
+fn stored_credentials_can_be_retrieved_until_erased() {
+    let cache = Cache::default();
+    assert_eq!(get(&cache, ctx("example.com", None, None)), None, "nothing stored yet");
+
+    let stored = ctx("example.com", Some("user"), Some("pass"));
+    assert!(cache.invoke(&Action::Store(stored.to_bstring())).unwrap().is_none());
+    assert_eq!(
+        get(&cache, ctx("example.com", None, None)),
+        Some((Some("user".into()), Some("pass".into())))
+    );
+    assert_eq!(
+        get(&cache, ctx("example.com", Some("other"), None)),
+        None,
+        "usernames have to match if given"
+    );
+    assert_eq!(get(&cache, ctx("other.com", None, None)), None, "hosts have to match");
+
+    cache
+        .invoke(&Action::Erase(
+            ctx("example.com", Some("user"), Some("wrong")).to_bstring(),
+        ))
+        .unwrap();
+    assert!(
+        get(&cache, ctx("example.com", None, None)).is_some(),
+        "the password has to match when erasing if given"
+    );
+    cache.invoke(&Action::Erase(stored.to_bstring())).unwrap();
+    assert_eq!(get(&cache, ctx("example.com", None, None)), None);
+}
+
+#[test]
+fn storing_replaces_credentials_of_the_same_user() {
+    let cache = Cache::default();
+    for password in ["old", "new"] {
+        cache
+            .invoke(&Action::Store(
+                ctx("example.com", Some("user"), Some(password)).to_bstring(),
+            ))
+            .unwrap();
+    }
+    assert_eq!(
+        get(&cache, ctx("example.com", Some("user"), None)),
+        Some((Some("user".into()), Some("new".into())))
+    );
+    cache.clear();
+    assert_eq!(get(&cache, ctx("example.com", None, None)), None);
+}
+
+#[test]
+fn incomplete_credentials_are_not_stored() {
+    let cache = Cache::default();
+    cache
+        .invoke(&Action::Store(ctx("example.com", Some("user"), None).to_bstring()))
+        .unwrap();
+    assert_eq!(get(&cache, ctx("example.com", None, None)), None);
+}
+
+#[test]
+fn credentials_expire_after_the_timeout_or_their_expiry_date() {
+    let cache = Cache::new(std::time::Duration::ZERO);
+    cache
+        .invoke(&Action::Store(
+            ctx("example.com", Some("user"), Some("pass")).to_bstring(),
+        ))
+        .unwrap();
+    assert_eq!(
+        get(&cache, ctx("example.com", None, None)),
+        None,
+        "timed out immediately"
+    );
+
+    let cache = Cache::default();
+    let expired = Context {
+        password_expiry_utc: Some(1),
+        ..ctx("example.com", Some("user"), Some("pass"))
+    };
+    cache.invoke(&Action::Store(expired.to_bstring())).unwrap();
+    assert_eq!(get(&cache, ctx("example.com", None, None)), None, "expired long ago");
+}
+
+#[test]
+fn debug_does_not_reveal_credentials() {
+    let cache = Cache::default();
+    cache
+        .invoke(&Action::Store(
+            ctx("example.com", Some("user"), Some("secret")).to_bstring(),
+        ))
+        .unwrap();
+    assert!(!format!("{cache:?}").contains("secret"));
+}
@@ -110,6 +110,103 @@ mod invoke {
         );
     }
 
+    #[test]
+    fn expired_passwords_are_ignored() {
+        let actual = invoke_cascade(["expired-password", "password"], action_get())
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity, identity("user", "pass"));
+        let ctx: Context = (&actual.next).try_into().unwrap();
+        assert_eq!(ctx.password_expiry_utc, None, "the expiry date went with the password");
+    }
+
+    #[test]
+    fn custom_prompts_are_used_for_missing_values_unless_disabled() {
+        let prompt = |message: &str, opts: &gix_prompt::Options<'_>| -> Result<String, gix_prompt::Error> {
+            Ok(match opts.mode {
+                gix_prompt::Mode::Visible => {
+                    assert_eq!(message, "Username for https://example.com: ");
+                    "prompted-user".into()
+                }
+                gix_prompt::Mode::Hidden => {
+                    assert_eq!(message, "Password for https://prompted-user@example.com: ");
+                    "prompted-pass".into()
+                }
+                gix_prompt::Mode::Disable => unreachable!("not called if prompts are disabled"),
+            })
+        };
+        let actual = Cascade::default()
+            .prompt(prompt)
+            .invoke(Action::get_for_url("https://example.com"), Default::default())
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity, identity("prompted-user", "prompted-pass"));
+
+        let err = Cascade::default()
+            .prompt(prompt)
+            .invoke(
+                Action::get_for_url("https://example.com"),
+                gix_prompt::Options {
+                    mode: gix_prompt::Mode::Disable,
+                    askpass: None,
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(err, gix_credentials::protocol::Error::IdentityMissing { .. }),
+            "the prompt isn't used if disabled"
+        );
+    }
+
+    #[test]
+    fn cached_credentials_are_used_without_running_helpers() {
+        let cache = gix_credentials::helper::Cache::default();
+        let stored = Context {
+            protocol: Some("https".into()),
+            host: Some("example.com".into()),
+            username: Some("cached-user".into()),
+            password: Some("cached-pass".into()),
+            ..Default::default()
+        };
+        cache.invoke(&Action::Store(stored.to_bstring())).unwrap();
+
+        let actual = Cascade::default()
+            .cache(cache.clone())
+            .extend(fixtures(["custom-helper"]))
+            .invoke(
+                Action::get_for_url("https://example.com/repo"),
+                gix_prompt::Options {
+                    mode: gix_prompt::Mode::Disable,
+                    askpass: None,
+                },
+            )
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(actual.identity, identity("cached-user", "cached-pass"));
+
+        Cascade::default()
+            .cache(cache.clone())
+            .invoke(actual.next.erase(), Default::default())
+            .unwrap();
+        let actual = Cascade::default()
+            .cache(cache)
+            .extend(fixtures(["custom-helper"]))
+            .invoke(
+                Action::get_for_url("https://example.com/repo"),
+                gix_prompt::Options {
+                    mode: gix_prompt::Mode::Disable,
+                    askpass: None,
+                },
+            )
+            .unwrap()
+            .expect("credentials");
+        assert_eq!(
+            actual.identity,
+            identity("user-script", "pass-script"),
+            "erasing removed the credentials from the cache"
+        );
+    }
+
     #[test]
     fn helpers_can_quit_and_their_creds_are_taken_if_complete() {
         let actual = invoke_cascade(["last-pass", "custom-helper"], Action::get_for_url("http://github.com"))
@@ -12,6 +12,16 @@ fn encode_decode_roundtrip_works_only_for_serializing_fields() {
             url: Some("https://github.com/byron/gitoxide".into()),
             ..Default::default()
         },
+        Context {
+            protocol: Some("https".into()),
+            host: Some("github.com".into()),
+            password_expiry_utc: Some(1_700_000_000),
+            oauth_refresh_token: Some("refresh".into()),
+            wwwauth: vec!["Basic realm=\"example\"".into(), "Bearer".into()],
+            authtype: Some("Bearer".into()),
+            credential: Some("token".into()),
+            ..Default::default()
+        },
         Context::default(),
     ] {
         let mut buf = Vec::<u8>::new();
@@ -61,6 +71,21 @@ mod write_to {
 mod from_bytes {
     use gix_credentials::protocol::Context;
 
+    #[test]
+    fn empty_wwwauth_resets_previous_values_and_invalid_expiry_dates_are_ignored() {
+        let input = b"wwwauth[]=Basic
+wwwauth[]=
+wwwauth[]=Bearer
+password_expiry_utc=tomorrow";
+        assert_eq!(
+            Context::from_bytes(input).unwrap(),
+            Context {
+                wwwauth: vec!["Bearer".into()],
+                ..Default::default()
+            }
+        )
+    }
+
     #[test]
     fn empty_newlines_cause_skipping_remaining_input() {
         let input = b"protocol=https
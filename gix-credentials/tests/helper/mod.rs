@@ -1,3 +1,4 @@
+mod cache;
 mod cascade;
 mod context;
 mod invoke;
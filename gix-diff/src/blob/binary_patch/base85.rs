@@ -0,0 +1,49 @@
+//! The base85 encoding used by `git` in binary patches, which encodes 4 bytes as 5 characters.
+
+const ALPHABET: &[u8; 85] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz!#$%&()*+-;<=>?@^_`{|}~";
+
+/// Return the amount of characters needed to encode `len` bytes.
+pub fn encoded_len(len: usize) -> usize {
+    (len + 3) / 4 * 5
+}
+
+/// Append the encoding of `data` to `out`, padding the last group of bytes with zeroes.
+pub fn encode(data: &[u8], out: &mut Vec<u8>) {
+    for group in data.chunks(4) {
+        let mut acc = group
+            .iter()
+            .chain(std::iter::repeat(&0))
+            .take(4)
+            .fold(0u32, |acc, byte| (acc << 8) | u32::from(*byte));
+        let mut encoded = [0u8; 5];
+        for digit in encoded.iter_mut().rev() {
+            *digit = ALPHABET[(acc % 85) as usize];
+            acc /= 85;
+        }
+        out.extend_from_slice(&encoded);
+    }
+}
+
+fn decode_digit(digit: u8) -> Option<u32> {
+    ALPHABET.iter().position(|c| *c == digit).map(|pos| pos as u32)
+}
+
+/// Append the `len` bytes encoded in `encoded` to `out`, or return `None` if `encoded` isn't valid base85 or
+/// doesn't have the size needed for `len` bytes.
+pub fn decode(encoded: &[u8], len: usize, out: &mut Vec<u8>) -> Option<()> {
+    if encoded.len() != encoded_len(len) {
+        return None;
+    }
+    let mut remaining = len;
+    for group in encoded.chunks(5) {
+        let mut acc = 0u32;
+        for digit in group {
+            acc = acc.checked_mul(85)?.checked_add(decode_digit(*digit)?)?;
+        }
+        let bytes = acc.to_be_bytes();
+        let take = remaining.min(4);
+        out.extend_from_slice(&bytes[..take]);
+        remaining -= take;
+    }
+    Some(())
+}
@@ -0,0 +1,187 @@
+//! The delta format of `git`, which is the same in packs and binary patches and describes how to produce a new version of
+//! a file by copying ranges of the old version and inserting new data.
+use std::collections::HashMap;
+
+/// The size of the blocks of the old version that are indexed to find copies.
+const BLOCK_SIZE: usize = 16;
+/// The maximum amount of bytes a single copy instruction produces, as written by `git`.
+const MAX_COPY_SIZE: usize = 0x10000;
+/// The maximum amount of bytes a single insert instruction can carry.
+const MAX_INSERT_SIZE: usize = 0x7f;
+
+/// Produce a delta that turns `base` into `target`.
+pub fn encode(base: &[u8], target: &[u8]) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_size(&mut out, base.len());
+    write_size(&mut out, target.len());
+
+    let mut blocks = HashMap::<&[u8], usize>::new();
+    for (block_idx, block) in base.chunks_exact(BLOCK_SIZE).enumerate() {
+        let ofs = block_idx * BLOCK_SIZE;
+        if u32::try_from(ofs).is_err() {
+            break;
+        }
+        blocks.entry(block).or_insert(ofs);
+    }
+
+    let mut pos = 0;
+    let mut insert_start = 0;
+    while pos + BLOCK_SIZE <= target.len() {
+        let base_ofs = match blocks.get(&target[pos..][..BLOCK_SIZE]) {
+            Some(ofs) => *ofs,
+            None => {
+                pos += 1;
+                continue;
+            }
+        };
+        let (mut base_start, mut target_start) = (base_ofs, pos);
+        while target_start > insert_start && base_start > 0 && base[base_start - 1] == target[target_start - 1] {
+            base_start -= 1;
+            target_start -= 1;
+        }
+        let len = base[base_start..]
+            .iter()
+            .zip(&target[target_start..])
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        write_insert(&mut out, &target[insert_start..target_start]);
+        write_copy(&mut out, base_start, len);
+        pos = target_start + len;
+        insert_start = pos;
+    }
+    write_insert(&mut out, &target[insert_start..]);
+    out
+}
+
+fn write_size(out: &mut Vec<u8>, mut size: usize) {
+    loop {
+        let byte = (size & 0x7f) as u8;
+        size >>= 7;
+        if size == 0 {
+            out.push(byte);
+            break;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+fn write_insert(out: &mut Vec<u8>, data: &[u8]) {
+    for chunk in data.chunks(MAX_INSERT_SIZE) {
+        out.push(chunk.len() as u8);
+        out.extend_from_slice(chunk);
+    }
+}
+
+fn write_copy(out: &mut Vec<u8>, mut ofs: usize, mut len: usize) {
+    while len != 0 {
+        let size = len.min(MAX_COPY_SIZE);
+        let cmd_pos = out.len();
+        let mut cmd = 0x80u8;
+        out.push(cmd);
+        for (bit, byte) in (ofs as u32).to_le_bytes().into_iter().enumerate() {
+            if byte != 0 {
+                cmd |= 1 << bit;
+                out.push(byte);
+            }
+        }
+        // A size of 0 stands for the maximum size.
+        if size != MAX_COPY_SIZE {
+            for (bit, byte) in (size as u32).to_le_bytes().into_iter().take(3).enumerate() {
+                if byte != 0 {
+                    cmd |= 0x10 << bit;
+                    out.push(byte);
+                }
+            }
+        }
+        out[cmd_pos] = cmd;
+        ofs += size;
+        len -= size;
+    }
+}
+
+/// Return the size encoded at the beginning of `data` along with the remaining bytes, or `None` if it's truncated.
+fn read_size(data: &[u8]) -> Option<(u64, &[u8])> {
+    let mut size = 0u64;
+    for (idx, byte) in data.iter().enumerate() {
+        let shift = 7 * idx as u32;
+        size |= u64::from(byte & 0x7f).checked_shl(shift)?;
+        if byte & 0x80 == 0 {
+            return Some((size, &data[idx + 1..]));
+        }
+    }
+    None
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod apply {
+    /// The error returned by [`Hunk::apply()`](crate::blob::binary_patch::Hunk::apply()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The delta expects a base of {expected} bytes, but the old version has {actual} bytes")]
+        BaseSizeMismatch { expected: u64, actual: u64 },
+        #[error("The delta is truncated or contains an invalid instruction at byte {offset}")]
+        InvalidInstruction { offset: usize },
+        #[error("The delta was supposed to produce {expected} bytes, but produced {actual} bytes")]
+        ResultSizeMismatch { expected: u64, actual: u64 },
+    }
+}
+
+/// Apply `delta` to `base` and return the result.
+pub fn apply(base: &[u8], delta: &[u8]) -> Result<Vec<u8>, apply::Error> {
+    let invalid = |rest: &[u8]| apply::Error::InvalidInstruction {
+        offset: delta.len() - rest.len(),
+    };
+    let (base_size, rest) = read_size(delta).ok_or_else(|| invalid(&[]))?;
+    if base_size != base.len() as u64 {
+        return Err(apply::Error::BaseSizeMismatch {
+            expected: base_size,
+            actual: base.len() as u64,
+        });
+    }
+    let (result_size, mut rest) = read_size(rest).ok_or_else(|| invalid(&[]))?;
+
+    let mut out = Vec::with_capacity(result_size.min(base.len() as u64 + delta.len() as u64) as usize);
+    while let Some((&cmd, instruction)) = rest.split_first() {
+        match cmd {
+            0 => return Err(invalid(rest)),
+            1..=0x7f => {
+                let data = instruction.get(..cmd as usize).ok_or_else(|| invalid(rest))?;
+                out.extend_from_slice(data);
+                rest = &instruction[cmd as usize..];
+            }
+            _ => {
+                let mut args = instruction.iter();
+                let mut read_le = |bits: std::ops::Range<u8>| -> Option<usize> {
+                    let mut value = 0;
+                    for (shift, bit) in bits.enumerate() {
+                        if cmd & (1 << bit) != 0 {
+                            value |= usize::from(*args.next()?) << (8 * shift);
+                        }
+                    }
+                    Some(value)
+                };
+                let ofs = read_le(0..4).ok_or_else(|| invalid(rest))?;
+                let size = match read_le(4..7).ok_or_else(|| invalid(rest))? {
+                    0 => MAX_COPY_SIZE,
+                    size => size,
+                };
+                let data = ofs
+                    .checked_add(size)
+                    .and_then(|end| base.get(ofs..end))
+                    .ok_or_else(|| invalid(rest))?;
+                out.extend_from_slice(data);
+                rest = args.as_slice();
+            }
+        }
+    }
+    if out.len() as u64 != result_size {
+        return Err(apply::Error::ResultSizeMismatch {
+            expected: result_size,
+            actual: out.len() as u64,
+        });
+    }
+    Ok(out)
+}
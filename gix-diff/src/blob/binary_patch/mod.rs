@@ -0,0 +1,231 @@
+//! Facilities to produce and read binary patches as written by `git diff --binary` and understood by `git apply`.
+//!
+//! A binary patch starts with a `GIT binary patch` line and is followed by a hunk to turn the old version of a file into
+//! the new one, and usually a hunk to do the reverse. Each hunk either contains the entire content of the file it produces,
+//! or a delta to apply to the other version, compressed with zlib and encoded with base85 on lines of up to 52 bytes.
+use std::io::Write;
+
+use bstr::ByteSlice;
+
+mod base85;
+mod delta;
+pub use delta::apply;
+
+/// The line that introduces a binary patch.
+const HEADER: &[u8] = b"GIT binary patch\n";
+/// The maximum amount of bytes encoded on a single line.
+const MAX_BYTES_PER_LINE: usize = 52;
+
+/// The way the data of a [`Hunk`] produces the version of the file it leads to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Kind {
+    /// The data is the entire content of the file.
+    Literal,
+    /// The data is a delta to apply to the other version of the file.
+    Delta,
+}
+
+/// One of the two hunks of a [`BinaryPatch`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hunk {
+    /// Whether `data` is the resulting content, or a delta to apply.
+    pub kind: Kind,
+    /// The decompressed and decoded data of the hunk.
+    pub data: Vec<u8>,
+}
+
+impl Hunk {
+    /// Produce the version of the file this hunk leads to from the `other` version, which is only used if this is a delta.
+    pub fn apply(&self, other: &[u8]) -> Result<Vec<u8>, apply::Error> {
+        match self.kind {
+            Kind::Literal => Ok(self.data.clone()),
+            Kind::Delta => delta::apply(other, &self.data),
+        }
+    }
+}
+
+/// A binary patch as read by [`parse()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BinaryPatch {
+    /// The hunk to turn the old version of the file into the new one.
+    pub forward: Hunk,
+    /// The hunk to turn the new version of the file into the old one, which is always written by `git`, but may be missing
+    /// in patches produced by other tools.
+    pub reverse: Option<Hunk>,
+}
+
+/// Write a binary patch that turns `old` into `new` to `out`, along with the hunk to revert it, just like `git diff --binary`.
+///
+/// Each hunk contains a delta if both versions are non-empty and it's smaller than the entire file after compression.
+/// It's meant to follow a [`Header`](crate::blob::unified_diff::Header) written with
+/// [`Content::BinaryPatch`](crate::blob::unified_diff::Content::BinaryPatch).
+pub fn write_to(old: &[u8], new: &[u8], mut out: impl Write) -> std::io::Result<()> {
+    out.write_all(HEADER)?;
+    write_hunk(old, new, &mut out)?;
+    write_hunk(new, old, &mut out)
+}
+
+fn write_hunk(from: &[u8], to: &[u8], out: &mut dyn Write) -> std::io::Result<()> {
+    let literal = deflate(to)?;
+    let delta = if from.is_empty() || to.is_empty() {
+        None
+    } else {
+        let delta = delta::encode(from, to);
+        let compressed = deflate(&delta)?;
+        Some((delta.len(), compressed)).filter(|(_, compressed)| compressed.len() < literal.len())
+    };
+    let data = match delta {
+        Some((size, compressed)) => {
+            writeln!(out, "delta {size}")?;
+            compressed
+        }
+        None => {
+            writeln!(out, "literal {}", to.len())?;
+            literal
+        }
+    };
+
+    let mut line = Vec::with_capacity(1 + base85::encoded_len(MAX_BYTES_PER_LINE) + 1);
+    for chunk in data.chunks(MAX_BYTES_PER_LINE) {
+        line.clear();
+        line.push(match chunk.len() {
+            len @ 1..=26 => b'A' + len as u8 - 1,
+            len => b'a' + len as u8 - 27,
+        });
+        base85::encode(chunk, &mut line);
+        line.push(b'\n');
+        out.write_all(&line)?;
+    }
+    out.write_all(b"\n")
+}
+
+fn deflate(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
+    out.write_all(data)?;
+    out.flush()?;
+    Ok(out.into_inner())
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod parse {
+    use bstr::BString;
+
+    /// The error returned by [`parse()`](super::parse()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A binary patch must start with a 'GIT binary patch' line")]
+        MissingHeader,
+        #[error("Expected a line like 'literal <size>' or 'delta <size>', got '{line}'")]
+        InvalidHunkHeader { line: BString },
+        #[error("Line {line_number} of the binary patch isn't a valid line of encoded data")]
+        InvalidDataLine { line_number: usize },
+        #[error("The binary patch ended before the empty line that ends each hunk")]
+        UnexpectedEof,
+        #[error("Could not decompress the data of a hunk")]
+        Inflate(#[from] gix_features::zlib::inflate::Error),
+        #[error("A hunk was supposed to contain {expected} bytes, but contained at least {actual} bytes")]
+        SizeMismatch { expected: u64, actual: u64 },
+    }
+}
+
+/// Read the binary patch at the beginning of `input`, starting with its `GIT binary patch` line, and return it along with
+/// the amount of bytes it occupied, so that parsing can continue after it.
+pub fn parse(input: &[u8]) -> Result<(BinaryPatch, usize), parse::Error> {
+    if !input.starts_with(HEADER) {
+        return Err(parse::Error::MissingHeader);
+    }
+    let mut lines = Lines {
+        input,
+        pos: HEADER.len(),
+        line_number: 1,
+    };
+    let forward = parse_hunk(&mut lines)?.ok_or_else(|| match lines.peek_line() {
+        Some(line) => parse::Error::InvalidHunkHeader { line: line.into() },
+        None => parse::Error::UnexpectedEof,
+    })?;
+    let reverse = parse_hunk(&mut lines)?;
+    Ok((BinaryPatch { forward, reverse }, lines.pos))
+}
+
+struct Lines<'a> {
+    input: &'a [u8],
+    pos: usize,
+    line_number: usize,
+}
+
+impl<'a> Lines<'a> {
+    /// Return the next line without its line feed, without consuming it.
+    fn peek_line(&self) -> Option<&'a [u8]> {
+        let rest = &self.input[self.pos..];
+        if rest.is_empty() {
+            return None;
+        }
+        let line = rest.find_byte(b'\n').map_or(rest, |pos| &rest[..pos]);
+        Some(line)
+    }
+
+    fn next_line(&mut self) -> Option<&'a [u8]> {
+        let line = self.peek_line()?;
+        self.pos = (self.pos + line.len() + 1).min(self.input.len());
+        self.line_number += 1;
+        Some(line)
+    }
+}
+
+/// Parse the hunk that starts with the next line, or return `None` if there is no hunk header.
+fn parse_hunk(lines: &mut Lines<'_>) -> Result<Option<Hunk>, parse::Error> {
+    let (kind, size) = match lines.peek_line().and_then(parse_hunk_header) {
+        Some(header) => header,
+        None => return Ok(None),
+    };
+    lines.next_line();
+
+    let mut compressed = Vec::new();
+    loop {
+        let line = lines.next_line().ok_or(parse::Error::UnexpectedEof)?;
+        if line.is_empty() {
+            break;
+        }
+        let invalid = || parse::Error::InvalidDataLine {
+            line_number: lines.line_number,
+        };
+        let len = match line[0] {
+            b'A'..=b'Z' => line[0] - b'A' + 1,
+            b'a'..=b'z' => line[0] - b'a' + 27,
+            _ => return Err(invalid()),
+        };
+        base85::decode(&line[1..], len as usize, &mut compressed).ok_or_else(invalid)?;
+    }
+
+    Ok(Some(Hunk {
+        kind,
+        data: inflate(&compressed, size)?,
+    }))
+}
+
+fn parse_hunk_header(line: &[u8]) -> Option<(Kind, u64)> {
+    let (kind, size) = if let Some(size) = line.strip_prefix(b"literal ") {
+        (Kind::Literal, size)
+    } else {
+        (Kind::Delta, line.strip_prefix(b"delta ")?)
+    };
+    Some((kind, size.to_str().ok()?.parse().ok()?))
+}
+
+fn inflate(compressed: &[u8], size: u64) -> Result<Vec<u8>, parse::Error> {
+    let size_mismatch = |actual: usize| parse::Error::SizeMismatch {
+        expected: size,
+        actual: actual as u64,
+    };
+    let len = usize::try_from(size).map_err(|_| size_mismatch(0))?;
+    // One more byte than expected lets us detect if there is more data than announced.
+    let mut out = vec![0; len + 1];
+    let (status, _consumed, written) = gix_features::zlib::Inflate::default().once(compressed, &mut out)?;
+    if written != len || status != gix_features::zlib::Status::StreamEnd {
+        return Err(size_mismatch(written));
+    }
+    out.truncate(len);
+    Ok(out)
+}
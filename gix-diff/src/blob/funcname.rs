@@ -0,0 +1,159 @@
+//! Find the name of the function a hunk is in to show it in the hunk header, like `git` does with the built-in rules or
+//! the `diff.<driver>.xfuncname` configuration.
+use bstr::{BStr, ByteSlice};
+
+/// The maximum length of a function name in bytes, as longer names are truncated by `git` as well.
+const MAX_LEN: usize = 80;
+
+/// The `xfuncname` patterns of the diff drivers that are built into `git`, by driver name.
+///
+/// Note that these are adjusted to the syntax of the `regex` crate, which is why some brackets are escaped, and
+/// case-insensitive patterns are marked with `(?i)`.
+pub const BUILTIN: &[(&str, &str)] = &[
+    (
+        "bash",
+        r#"^[ \t]*(([a-zA-Z_][a-zA-Z0-9_]*[ \t]*\([ \t]*\))|(function[ \t]+[a-zA-Z_][a-zA-Z0-9_]*(([ \t]*\([ \t]*\))|([ \t]+)))[ \t]*(\{|\(\(?|\[\[))"#,
+    ),
+    (
+        "cpp",
+        "!^[ \\t]*[A-Za-z_][A-Za-z_0-9]*:[[:space:]]*($|/[/*])\n^((::[[:space:]]*)?[A-Za-z_].*)$",
+    ),
+    ("css", "!(?i)[:;][[:space:]]*$\n(?i)^[:\\[@.#]?[_a-z0-9].*$"),
+    (
+        "elixir",
+        r#"^[ \t]*((def(macro|module|impl|protocol|p)?|test)[ \t].*)$"#,
+    ),
+    (
+        "golang",
+        "^[ \\t]*(func[ \\t]*.*(\\{[ \\t]*)?)\n^[ \\t]*(type[ \\t].*(struct|interface)[ \\t]*(\\{[ \\t]*)?)",
+    ),
+    ("html", r#"^[ \t]*(<[Hh][1-6]([ \t].*)?>.*)$"#),
+    (
+        "java",
+        "!^[ \\t]*(catch|do|for|if|instanceof|new|return|switch|throw|while)\n\
+         ^[ \\t]*(([a-z-]+[ \\t]+)*(class|enum|interface|record)[ \\t]+.*)$\n\
+         ^[ \\t]*(([A-Za-z_<>&][\\]\\[?&<>.,A-Za-z_0-9]*[ \\t]+)+[A-Za-z_][A-Za-z_0-9]*[ \\t]*\\([^;]*)$",
+    ),
+    ("kotlin", r#"^[ \t]*(([a-z]+[ \t]+)*(fun|class|interface)[ \t]+.*)$"#),
+    ("markdown", r#"^ {0,3}#{1,6}[ \t].*"#),
+    (
+        "matlab",
+        r#"^[[:space:]]*((classdef|function)[[:space:]].*)$|^(%%%?|##)[[:space:]].*$"#,
+    ),
+    (
+        "php",
+        "^[\\t ]*(((public|protected|private|static|abstract|final)[\\t ]+)*function.*)$\n\
+         ^[\\t ]*((((final|abstract)[\\t ]+)?class|enum|interface|trait).*)$",
+    ),
+    ("python", r#"^[ \t]*((class|(async[ \t]+)?def)[ \t].*)$"#),
+    ("ruby", r#"^[ \t]*((class|module|def)[ \t].*)$"#),
+    (
+        "rust",
+        r#"^[\t ]*((pub(\([^\)]+\))?[\t ]+)?((async|const|unsafe|extern([\t ]+"[^"]+"))[\t ]+)?(struct|enum|union|mod|trait|fn|impl|macro_rules!)[< \t]+[^;]*)$"#,
+    ),
+    ("tex", r#"^(\\((sub)*section|chapter|part)\*{0,1}\{.*)$"#),
+];
+
+/// Return the `xfuncname` patterns of the diff driver named `name` that is built into `git`, if there is one.
+pub fn builtin(name: &BStr) -> Option<&'static str> {
+    BUILTIN
+        .iter()
+        .find_map(|(builtin_name, patterns)| (*name == **builtin_name).then_some(*patterns))
+}
+
+/// A regular expression that identifies lines with function names.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    /// If `true`, lines that match are not considered to contain a function name, even if later patterns would match.
+    pub negate: bool,
+    /// The expression to match lines against without their line terminator, with the function name being the first
+    /// capture group, or the whole match if it didn't participate.
+    pub regex: regex::bytes::Regex,
+}
+
+/// Determine which lines contain the name of a function, and what the name is.
+#[derive(Default, Debug, Clone)]
+pub enum Funcname {
+    /// Lines that start with a letter, `_` or `$` are function names, which is what `git` does without a pattern.
+    #[default]
+    Default,
+    /// Lines are matched against each pattern in order, and the first one that matches decides.
+    Patterns(Vec<Pattern>),
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod from_xfuncname {
+    /// The error returned by [`Funcname::from_xfuncname()`](super::Funcname::from_xfuncname()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The function name pattern '{pattern}' wasn't valid UTF-8")]
+        Utf8 { pattern: bstr::BString },
+        #[error("Invalid regex to look for hunk headers")]
+        Regex(#[from] regex::Error),
+    }
+}
+
+impl Funcname {
+    /// Parse `patterns` as read from `diff.<driver>.xfuncname`, with one regular expression per line, and lines starting
+    /// with `!` being negated.
+    pub fn from_xfuncname(patterns: &BStr) -> Result<Self, from_xfuncname::Error> {
+        let patterns = patterns.to_str().map_err(|_| from_xfuncname::Error::Utf8 {
+            pattern: patterns.to_owned(),
+        })?;
+        patterns
+            .split('\n')
+            .map(|line| {
+                let (negate, line) = match line.strip_prefix('!') {
+                    Some(line) => (true, line),
+                    None => (false, line),
+                };
+                Ok(Pattern {
+                    negate,
+                    regex: regex::bytes::RegexBuilder::new(line).unicode(false).build()?,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(Funcname::Patterns)
+    }
+
+    /// Return the function name contained in `line`, or `None` if it doesn't contain one.
+    ///
+    /// Like `git`, names are truncated to 80 bytes and trailing whitespace is removed.
+    pub fn find<'a>(&self, line: &'a [u8]) -> Option<&'a [u8]> {
+        let name = match self {
+            Funcname::Default => {
+                if !matches!(line.first(), Some(b) if b.is_ascii_alphabetic() || *b == b'_' || *b == b'$') {
+                    return None;
+                }
+                line
+            }
+            Funcname::Patterns(patterns) => {
+                let line = match line.strip_suffix(b"\n") {
+                    Some(line) => line.strip_suffix(b"\r").unwrap_or(line),
+                    None => line,
+                };
+                let (pattern, captures) = patterns
+                    .iter()
+                    .find_map(|pattern| pattern.regex.captures(line).map(|captures| (pattern, captures)))?;
+                if pattern.negate {
+                    return None;
+                }
+                let name = captures
+                    .get(1)
+                    .or_else(|| captures.get(0))
+                    .expect("the whole match always participates");
+                &line[name.range()]
+            }
+        };
+        let mut name = &name[..name.len().min(MAX_LEN)];
+        while let Some((last, rest)) = name.split_last() {
+            if !matches!(last, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r') {
+                break;
+            }
+            name = rest;
+        }
+        Some(name)
+    }
+}
@@ -3,6 +3,7 @@
 use std::{collections::HashMap, path::PathBuf};
 
 use bstr::BString;
+use imara_diff::intern::{InternedInput, Token};
 pub use imara_diff::*;
 
 ///
@@ -13,6 +14,127 @@ pub mod pipeline;
 #[allow(clippy::empty_docs)]
 pub mod platform;
 
+pub mod unified_diff;
+pub use unified_diff::UnifiedDiff;
+
+pub mod words;
+
+pub mod pickaxe;
+
+pub mod funcname;
+
+pub mod binary_patch;
+
+mod patience;
+
+pub(crate) mod whitespace;
+
+/// The algorithm to use when diffing, with all but [`Patience`](Algorithm::Patience) being implemented by `imara-diff`.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Hash)]
+pub enum Algorithm {
+    /// A variation of the patience diff algorithm that uses a histogram to find the least common subsequence, which
+    /// usually produces more readable diffs than Myers while being faster.
+    ///
+    /// See [`imara_diff::Algorithm::Histogram`] for details.
+    #[default]
+    Histogram,
+    /// The linear space variant of Myers `O((N+M)D)` algorithm with heuristics that keep the runtime reasonable,
+    /// and which is the default in `git`.
+    ///
+    /// See [`imara_diff::Algorithm::Myers`] for details.
+    Myers,
+    /// Same as [`Myers`](Algorithm::Myers), but without heuristics to guarantee a minimal diff, at the cost of
+    /// a significant slowdown in pathological cases.
+    MyersMinimal,
+    /// The patience diff algorithm as implemented by `git`, which anchors the diff on tokens that occur exactly once
+    /// on both sides and falls back to [`Myers`](Algorithm::Myers) in between if there are none.
+    Patience,
+}
+
+/// Compute the changes that turn `input.before` into `input.after` using `algorithm`, passing them to `sink` in order.
+///
+/// This is the same as [`imara_diff::diff()`], but supports all [algorithms](Algorithm).
+pub fn diff<S: Sink, T: Eq + std::hash::Hash>(algorithm: Algorithm, input: &InternedInput<T>, sink: S) -> S::Out {
+    diff_with_tokens(
+        algorithm,
+        &input.before,
+        &input.after,
+        input.interner.num_tokens(),
+        sink,
+    )
+}
+
+/// Compute the changes that turn `before` into `after` using `algorithm`, passing them to `sink` in order.
+/// All tokens must be smaller than `num_tokens`.
+///
+/// This is the same as [`imara_diff::diff_with_tokens()`], but supports all [algorithms](Algorithm).
+pub fn diff_with_tokens<S: Sink>(
+    algorithm: Algorithm,
+    before: &[Token],
+    after: &[Token],
+    num_tokens: u32,
+    sink: S,
+) -> S::Out {
+    let algorithm = match algorithm {
+        Algorithm::Histogram => imara_diff::Algorithm::Histogram,
+        Algorithm::Myers => imara_diff::Algorithm::Myers,
+        Algorithm::MyersMinimal => imara_diff::Algorithm::MyersMinimal,
+        Algorithm::Patience => return patience::diff(before, after, num_tokens, sink),
+    };
+    imara_diff::diff_with_tokens(algorithm, before, after, num_tokens, sink)
+}
+
+/// Ways to ignore changes in whitespace when diffing lines, like the respective options of `git diff` do.
+///
+/// Whitespace is what the C locale considers whitespace, which includes line terminators.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IgnoreWhitespace {
+    /// Ignore whitespace entirely when comparing lines, like `git diff -w|--ignore-all-space`.
+    pub all_space: bool,
+    /// Ignore changes in the amount of whitespace, so that any run of whitespace compares equal to any other, and whitespace
+    /// at the end of lines is ignored, like `git diff -b|--ignore-space-change`.
+    pub space_change: bool,
+    /// Ignore changes in whitespace at the end of lines, like `git diff --ignore-space-at-eol`.
+    pub space_at_eol: bool,
+    /// Ignore changes whose lines are all blank, i.e. empty or made of whitespace only, like `git diff --ignore-blank-lines`.
+    pub blank_lines: bool,
+}
+
+impl IgnoreWhitespace {
+    /// Return `true` if no whitespace is ignored.
+    pub fn is_empty(&self) -> bool {
+        *self == IgnoreWhitespace::default()
+    }
+}
+
+/// Compute the changes that turn `input.before` into `input.after` using `algorithm` like [`diff()`], but ignore the changes
+/// in whitespace as configured by `ignore`, passing the remaining changes to `sink` in order.
+///
+/// The tokens passed to `sink` still refer to the lines in `input`, so lines that are considered equal may still differ
+/// in whitespace. Changes whose lines are all blank are dropped entirely if [`IgnoreWhitespace::blank_lines`] is set,
+/// so the lines between changes may differ as well, which is why [`UnifiedDiff::ignore_blank_lines()`] has to be used
+/// to produce hunks instead.
+pub fn diff_ignoring_whitespace<S: Sink>(
+    algorithm: Algorithm,
+    input: &InternedInput<&[u8]>,
+    ignore: IgnoreWhitespace,
+    sink: S,
+) -> S::Out {
+    if ignore.is_empty() {
+        return diff(algorithm, input, sink);
+    }
+    let sink = whitespace::IgnoreBlankLines {
+        input,
+        enabled: ignore.blank_lines,
+        sink,
+    };
+    if !(ignore.all_space || ignore.space_change || ignore.space_at_eol) {
+        return diff(algorithm, input, sink);
+    }
+    let (before, after, num_tokens) = whitespace::normalize(input, ignore);
+    diff_with_tokens(algorithm, &before, &after, num_tokens, sink)
+}
+
 /// Information about the diff performed to detect similarity.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct DiffLineStats {
@@ -64,6 +186,16 @@ pub struct Driver {
     /// If `Some(false)`, it won't be considered binary, and the its data will not be sampled for the null-byte either.
     /// Leaving it to `None` means binary detection is automatic, and is based on the presence of the `0` byte in the first 8kB of the buffer.
     pub is_binary: Option<bool>,
+    /// The regular expression to find words with when diffing words instead of lines, as read from `diff.<driver>.wordRegex`.
+    ///
+    /// If unset, words are runs of non-whitespace characters.
+    pub word_regex: Option<BString>,
+    /// The regular expressions to find the lines with function names for hunk headers, one per line and negated if
+    /// prefixed with `!`, as read from `diff.<driver>.xfuncname` or as [built into `git`](funcname::BUILTIN) for some
+    /// drivers.
+    ///
+    /// If unset, lines starting with a letter, `_` or `$` are considered function names.
+    pub xfuncname: Option<BString>,
 }
 
 /// A conversion pipeline to take an object or path from what's stored in `git` to what can be diffed, while
@@ -0,0 +1,145 @@
+//! An implementation of the patience diff algorithm as described by [Bram Cohen](https://bramcohen.livejournal.com/73318.html)
+//! and implemented by `git`, which anchors the diff on tokens that occur exactly once on both sides.
+use std::{collections::HashMap, ops::Range};
+
+use imara_diff::{intern::Token, Sink};
+
+/// Compute the changes between `before` and `after`, whose tokens are all smaller than `num_tokens`, and pass them to `sink`.
+pub fn diff<S: Sink>(before: &[Token], after: &[Token], num_tokens: u32, mut sink: S) -> S::Out {
+    let mut changes = Vec::new();
+    diff_region(before, after, 0, 0, num_tokens, &mut changes);
+    for (before, after) in changes {
+        sink.process_change(before, after);
+    }
+    sink.finish()
+}
+
+type Change = (Range<u32>, Range<u32>);
+
+/// Diff `before` and `after`, which start at `before_ofs` and `after_ofs` in their respective input, and add all changes
+/// in order to `out`.
+fn diff_region(
+    mut before: &[Token],
+    mut after: &[Token],
+    mut before_ofs: u32,
+    mut after_ofs: u32,
+    num_tokens: u32,
+    out: &mut Vec<Change>,
+) {
+    let common_prefix = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+    before = &before[common_prefix..];
+    after = &after[common_prefix..];
+    before_ofs += common_prefix as u32;
+    after_ofs += common_prefix as u32;
+    let common_suffix = before
+        .iter()
+        .rev()
+        .zip(after.iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    before = &before[..before.len() - common_suffix];
+    after = &after[..after.len() - common_suffix];
+
+    if before.is_empty() || after.is_empty() {
+        if !(before.is_empty() && after.is_empty()) {
+            out.push((
+                before_ofs..before_ofs + before.len() as u32,
+                after_ofs..after_ofs + after.len() as u32,
+            ));
+        }
+        return;
+    }
+
+    let anchors = unique_common_subsequence(before, after);
+    if anchors.is_empty() {
+        // Like `git`, fall back to Myers if there are no unique tokens to anchor on.
+        imara_diff::diff_with_tokens(
+            imara_diff::Algorithm::Myers,
+            before,
+            after,
+            num_tokens,
+            |before: Range<u32>, after: Range<u32>| {
+                out.push((
+                    before.start + before_ofs..before.end + before_ofs,
+                    after.start + after_ofs..after.end + after_ofs,
+                ));
+            },
+        );
+        return;
+    }
+
+    let (mut before_pos, mut after_pos) = (0, 0);
+    for (before_anchor, after_anchor) in anchors {
+        diff_region(
+            &before[before_pos..before_anchor],
+            &after[after_pos..after_anchor],
+            before_ofs + before_pos as u32,
+            after_ofs + after_pos as u32,
+            num_tokens,
+            out,
+        );
+        before_pos = before_anchor + 1;
+        after_pos = after_anchor + 1;
+    }
+    diff_region(
+        &before[before_pos..],
+        &after[after_pos..],
+        before_ofs + before_pos as u32,
+        after_ofs + after_pos as u32,
+        num_tokens,
+        out,
+    );
+}
+
+/// Return the positions of the longest sequence of tokens that occur exactly once in both `before` and `after`,
+/// and in the same order.
+fn unique_common_subsequence(before: &[Token], after: &[Token]) -> Vec<(usize, usize)> {
+    #[derive(Default)]
+    struct Occurrences {
+        before: usize,
+        before_pos: usize,
+        after: usize,
+        after_pos: usize,
+    }
+    let mut occurrences = HashMap::<Token, Occurrences>::new();
+    for (pos, token) in before.iter().enumerate() {
+        let entry = occurrences.entry(*token).or_default();
+        entry.before += 1;
+        entry.before_pos = pos;
+    }
+    for (pos, token) in after.iter().enumerate() {
+        if let Some(entry) = occurrences.get_mut(token) {
+            entry.after += 1;
+            entry.after_pos = pos;
+        }
+    }
+    let mut unique: Vec<_> = occurrences
+        .into_values()
+        .filter(|o| o.before == 1 && o.after == 1)
+        .map(|o| (o.before_pos, o.after_pos))
+        .collect();
+    unique.sort_unstable();
+
+    // Patience sorting: find the longest increasing subsequence of positions in `after`, keeping track of
+    // the predecessor of each element to reconstruct it.
+    let mut stack_tops = Vec::<usize>::new();
+    let mut predecessors = vec![None; unique.len()];
+    for (idx, (_, after_pos)) in unique.iter().enumerate() {
+        let stack = stack_tops.partition_point(|top| unique[*top].1 < *after_pos);
+        predecessors[idx] = stack.checked_sub(1).map(|prev| stack_tops[prev]);
+        if stack == stack_tops.len() {
+            stack_tops.push(idx);
+        } else {
+            stack_tops[stack] = idx;
+        }
+    }
+
+    let mut out = Vec::with_capacity(stack_tops.len());
+    let mut next = stack_tops.last().copied();
+    while let Some(idx) = next {
+        out.push(unique[idx]);
+        next = predecessors[idx];
+    }
+    out.reverse();
+    out
+}
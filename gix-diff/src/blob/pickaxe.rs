@@ -0,0 +1,87 @@
+//! Facilities to find changes that add or remove a string, or that add or remove lines matching a regular expression,
+//! just like `git log -S` and `git log -G` do.
+use std::ops::Range;
+
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::blob::{intern::InternedInput, sources::byte_lines_with_terminator, Algorithm};
+
+/// Determine which changes are interesting when searching for them.
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// Match changes that alter the amount of occurrences of the string, i.e. that add or remove it, like `git log -S<string>`.
+    ///
+    /// Note that changes that merely move the string within a file don't match.
+    Occurrences(BString),
+    /// Match changes that alter the amount of matches of the regular expression, like `git log -S<regex> --pickaxe-regex`.
+    OccurrencesOfRegex(regex::bytes::Regex),
+    /// Match changes with added or removed lines that match the regular expression, like `git log -G<regex>`.
+    ///
+    /// Lines are matched without their line terminator.
+    ChangedLines(regex::bytes::Regex),
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod filter {
+    /// The error returned by [`Filter::occurrences_of_regex()`](super::Filter::occurrences_of_regex()) and
+    /// [`Filter::changed_lines()`](super::Filter::changed_lines()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The regex '{regex}' wasn't valid UTF-8")]
+        Utf8 { regex: bstr::BString },
+        #[error(transparent)]
+        Regex(#[from] regex::Error),
+    }
+}
+
+impl Filter {
+    /// Create a filter that matches changes which alter the amount of matches of `regex`, like `git log -S<regex> --pickaxe-regex`.
+    pub fn occurrences_of_regex(regex: &BStr) -> Result<Self, filter::Error> {
+        Ok(Filter::OccurrencesOfRegex(compile(regex)?))
+    }
+
+    /// Create a filter that matches changes with added or removed lines that match `regex`, like `git log -G<regex>`.
+    pub fn changed_lines(regex: &BStr) -> Result<Self, filter::Error> {
+        Ok(Filter::ChangedLines(compile(regex)?))
+    }
+
+    /// Return `true` if the change from `old` to `new` is interesting according to this filter, using `algorithm` to diff
+    /// lines if needed.
+    ///
+    /// Pass an empty slice for sides that don't exist, as is the case for additions and deletions.
+    pub fn matches(&self, algorithm: Algorithm, old: &[u8], new: &[u8]) -> bool {
+        match self {
+            Filter::Occurrences(needle) => {
+                let count = |haystack: &[u8]| haystack.find_iter(needle).count();
+                !needle.is_empty() && count(old) != count(new)
+            }
+            Filter::OccurrencesOfRegex(regex) => {
+                let count = |haystack: &[u8]| regex.find_iter(haystack).count();
+                count(old) != count(new)
+            }
+            Filter::ChangedLines(regex) => {
+                let input = InternedInput::new(byte_lines_with_terminator(old), byte_lines_with_terminator(new));
+                let mut found = false;
+                crate::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+                    found = found
+                        || input.before[before.start as usize..before.end as usize]
+                            .iter()
+                            .chain(&input.after[after.start as usize..after.end as usize])
+                            .any(|token| {
+                                regex.is_match(input.interner[*token].trim_end_with(|c| c == '\n' || c == '\r'))
+                            });
+                });
+                found
+            }
+        }
+    }
+}
+
+fn compile(regex: &BStr) -> Result<regex::bytes::Regex, filter::Error> {
+    let pattern = regex.to_str().map_err(|_| filter::Error::Utf8 {
+        regex: regex.to_owned(),
+    })?;
+    Ok(regex::bytes::Regex::new(pattern)?)
+}
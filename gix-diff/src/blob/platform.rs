@@ -185,17 +185,17 @@ pub mod set_resource {
 pub mod prepare_diff {
     use bstr::BStr;
 
-    use crate::blob::platform::Resource;
+    use crate::blob::{platform::Resource, Algorithm};
 
     /// The kind of operation that was performed during the [`diff`](super::Platform::prepare_diff()) operation.
     #[derive(Debug, Copy, Clone, Eq, PartialEq)]
     pub enum Operation<'a> {
-        /// The [internal diff algorithm](imara_diff::diff) should be called with the provided arguments.
+        /// The [internal diff algorithm](crate::blob::diff()) should be called with the provided arguments.
         /// This only happens if none of the resources are binary, and if there is no external diff program configured via git-attributes
         /// *or* [Options::skip_internal_diff_if_external_is_configured](super::Options::skip_internal_diff_if_external_is_configured)
         /// is `false`.
         ///
-        /// Use [`Outcome::interned_input()`] to easily obtain an interner for use with [`imara_diff::diff()`], or maintain one yourself
+        /// Use [`Outcome::interned_input()`] to easily obtain an interner for use with [`diff()`](crate::blob::diff()), or maintain one yourself
         /// for greater reuse.
         InternalDiff {
             /// The algorithm we determined should be used, which is one of (in order, first set one wins):
@@ -203,7 +203,7 @@ pub mod prepare_diff {
             /// * the driver's override
             /// * the platforms own configuration (typically from git-config)
             /// * the default algorithm
-            algorithm: imara_diff::Algorithm,
+            algorithm: Algorithm,
         },
         /// Run the external diff program according as configured in the `source`-resources driver.
         /// This only happens if [Options::skip_internal_diff_if_external_is_configured](super::Options::skip_internal_diff_if_external_is_configured)
@@ -227,6 +227,9 @@ pub mod prepare_diff {
         pub old: Resource<'a>,
         /// The new or destination of the diff operation.
         pub new: Resource<'a>,
+        /// The driver associated with the old or source of the diff operation, which is the one that determines how
+        /// the diff is performed, if there is one.
+        pub driver: Option<&'a crate::blob::Driver>,
     }
 
     impl<'a> Outcome<'a> {
@@ -308,6 +311,9 @@ pub struct Options {
     /// Otherwise, the internal diff is called despite the configured external diff, which is
     /// typically what callers expect by default.
     pub skip_internal_diff_if_external_is_configured: bool,
+    /// The changes in whitespace to ignore when diffing lines with [`diff_ignoring_whitespace()`](crate::blob::diff_ignoring_whitespace()),
+    /// which is also used when computing the similarity of files to find renames and copies.
+    pub ignore_whitespace: crate::blob::IgnoreWhitespace,
 }
 
 /// Lifecycle
@@ -509,6 +515,7 @@ impl Platform {
             operation: prepare_diff::Operation::SourceOrDestinationIsBinary,
             old: Resource::new(old_key, old),
             new: Resource::new(new_key, new),
+            driver: old.conversion.driver_index.map(|idx| &self.filter.drivers[idx]),
         };
 
         match (old.conversion.data, new.conversion.data) {
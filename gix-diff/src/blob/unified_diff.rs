@@ -0,0 +1,452 @@
+//! Facilities to produce the unified diff format, as used by `git diff` and understood by `git apply`.
+use std::{io::Write, ops::Range};
+
+use bstr::{BStr, ByteSlice};
+use imara_diff::{
+    intern::{InternedInput, Interner, Token},
+    Sink,
+};
+
+use crate::blob::funcname::Funcname;
+
+/// Defines the size of the context printed before and after each change.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq, Ord, PartialOrd)]
+pub struct ContextSize {
+    /// The amount of unchanged lines to print before and after each change, with `3` being the default just like in `git`.
+    ///
+    /// Changes that are separated by no more than twice this amount of lines end up in the same hunk.
+    pub symmetrical: u32,
+}
+
+impl Default for ContextSize {
+    fn default() -> Self {
+        ContextSize { symmetrical: 3 }
+    }
+}
+
+impl ContextSize {
+    /// Create a symmetrical context with `n` lines before and after each change.
+    pub fn symmetrical(n: u32) -> Self {
+        ContextSize { symmetrical: n }
+    }
+}
+
+/// A [`Sink`] that writes hunks in the unified diff format, starting with their `@@ -a,b +c,d @@` header.
+///
+/// It's meant to be used with an input of lines *including* their line terminator, as produced by
+/// [`Resource::intern_source()`](crate::blob::platform::Resource::intern_source()). Lines without terminator,
+/// which can only be the last line of a file, are followed by `\ No newline at end of file` like `git` does.
+///
+/// Note that the output only contains hunks, which makes it empty if the inputs are equal.
+/// Use [`Header`] to precede it with the information needed to turn it into a patch.
+pub struct UnifiedDiff<'a> {
+    before: &'a [Token],
+    after: &'a [Token],
+    interner: &'a Interner<&'a [u8]>,
+    context_size: ContextSize,
+    /// If set, find function names to show in hunk headers.
+    funcname: Option<&'a Funcname>,
+    /// The last function name that was found, which is shown until another one is found.
+    func_line: Vec<u8>,
+    /// The position in `before` until which lines were searched for function names already.
+    func_searched_until: u32,
+    /// If set, all changes are collected here to drop the ones whose lines are all blank once all changes are known.
+    blank_line_changes: Option<Vec<(Range<u32>, Range<u32>)>>,
+
+    /// The position in `before` up to which lines have been written, if we are in a hunk.
+    pos: u32,
+    /// The position in `after` that corresponds to `pos`.
+    after_pos: u32,
+    in_hunk: bool,
+    before_hunk_start: u32,
+    after_hunk_start: u32,
+    before_hunk_len: u32,
+    after_hunk_len: u32,
+
+    /// The lines of the current hunk, which can only be written once its header is known.
+    buffer: Vec<u8>,
+    out: Vec<u8>,
+}
+
+impl<'a> UnifiedDiff<'a> {
+    /// Create a new instance to write the hunks of the diff of `input` with `context_size` lines around each change.
+    pub fn new(input: &'a InternedInput<&'a [u8]>, context_size: ContextSize) -> Self {
+        UnifiedDiff {
+            before: &input.before,
+            after: &input.after,
+            interner: &input.interner,
+            context_size,
+            funcname: None,
+            func_line: Vec::new(),
+            func_searched_until: 0,
+            blank_line_changes: None,
+            pos: 0,
+            after_pos: 0,
+            in_hunk: false,
+            before_hunk_start: 0,
+            after_hunk_start: 0,
+            before_hunk_len: 0,
+            after_hunk_len: 0,
+            buffer: Vec::new(),
+            out: Vec::new(),
+        }
+    }
+
+    /// If `ignore` is `true`, drop changes whose lines are all blank unless they are close enough to other changes to be
+    /// shown in their context, like `git diff --ignore-blank-lines` does.
+    ///
+    /// Use this instead of [`IgnoreWhitespace::blank_lines`](crate::blob::IgnoreWhitespace::blank_lines), which drops such
+    /// changes unconditionally and would thus lead to incorrect hunks.
+    pub fn ignore_blank_lines(mut self, ignore: bool) -> Self {
+        self.blank_line_changes = ignore.then(Vec::new);
+        self
+    }
+
+    /// If set, show the name of the function each hunk is in after its header, as found by searching backwards from the
+    /// line before the hunk in the old version with `funcname`, like `git` does.
+    pub fn funcname(mut self, funcname: Option<&'a Funcname>) -> Self {
+        self.funcname = funcname;
+        self
+    }
+
+    fn write_lines(&mut self, prefix: u8, tokens: Range<u32>, side: &[Token]) {
+        for &token in &side[tokens.start as usize..tokens.end as usize] {
+            let line = self.interner[token];
+            self.buffer.push(prefix);
+            self.buffer.extend_from_slice(line);
+            if line.last() != Some(&b'\n') {
+                self.buffer.extend_from_slice(b"\n\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    /// Write unchanged lines up to `end` in `before`, taking them from `after` like `git` does, as they may still differ
+    /// if whitespace is ignored.
+    fn write_context(&mut self, end: u32) {
+        let len = end - self.pos;
+        self.write_lines(b' ', self.after_pos..self.after_pos + len, self.after);
+        self.before_hunk_len += len;
+        self.after_hunk_len += len;
+        self.pos = end;
+        self.after_pos += len;
+    }
+
+    fn write_change(&mut self, before: Range<u32>, after: Range<u32>) {
+        let context = self.context_size.symmetrical;
+        if self.in_hunk && before.start - self.pos > 2 * context {
+            self.flush();
+        }
+        if !self.in_hunk {
+            self.in_hunk = true;
+            self.pos = before.start.saturating_sub(context);
+            self.before_hunk_start = self.pos;
+            // Unchanged lines are the same on both sides, so the context before the change has the same size.
+            self.after_hunk_start = after.start - (before.start - self.pos);
+            self.after_pos = self.after_hunk_start;
+        }
+        self.write_context(before.start);
+        self.write_lines(b'-', before.clone(), self.before);
+        self.write_lines(b'+', after.clone(), self.after);
+        self.before_hunk_len += before.end - before.start;
+        self.after_hunk_len += after.end - after.start;
+        self.pos = before.end;
+        self.after_pos = after.end;
+    }
+
+    fn flush(&mut self) {
+        if !self.in_hunk {
+            return;
+        }
+        let end = (self.pos + self.context_size.symmetrical).min(self.before.len() as u32);
+        self.write_context(end);
+
+        self.out.extend_from_slice(b"@@ -");
+        write_range(&mut self.out, self.before_hunk_start, self.before_hunk_len);
+        self.out.extend_from_slice(b" +");
+        write_range(&mut self.out, self.after_hunk_start, self.after_hunk_len);
+        self.out.extend_from_slice(b" @@");
+        if let Some(funcname) = self.funcname {
+            // Only search the lines that weren't searched before, and keep showing the previous name if there is none.
+            let func_line = self.before[self.func_searched_until as usize..self.before_hunk_start as usize]
+                .iter()
+                .rev()
+                .find_map(|token| funcname.find(self.interner[*token]));
+            if let Some(func_line) = func_line {
+                self.func_line = func_line.to_owned();
+            }
+            self.func_searched_until = self.before_hunk_start;
+            if !self.func_line.is_empty() {
+                self.out.push(b' ');
+                self.out.extend_from_slice(&self.func_line);
+            }
+        }
+        self.out.push(b'\n');
+        self.out.append(&mut self.buffer);
+
+        self.in_hunk = false;
+        self.before_hunk_len = 0;
+        self.after_hunk_len = 0;
+    }
+}
+
+/// Return which of `changes` to keep if the ones that are `blank` are to be ignored, which is all that aren't `blank`,
+/// and all that would end up in their hunks given `context` lines around each change, just like `git` does.
+///
+/// Dropping the latter would make it impossible to write correct hunks, as the lines between changes are assumed
+/// to be unchanged.
+fn keep_changes(changes: &[(Range<u32>, Range<u32>)], blank: &[bool], context: u32) -> Vec<bool> {
+    let mut keep: Vec<bool> = blank.iter().map(|blank| !blank).collect();
+    let gap = |earlier: usize, later: usize| changes[later].0.start - changes[earlier].0.end;
+    loop {
+        let mut changed = false;
+        // Blank changes closer to kept ones than the context are shown anyway.
+        for idx in 0..changes.len() {
+            let close_to_kept = (idx > 0 && keep[idx - 1] && gap(idx - 1, idx) < context)
+                || (idx + 1 < changes.len() && keep[idx + 1] && gap(idx, idx + 1) < context);
+            if !keep[idx] && close_to_kept {
+                keep[idx] = true;
+                changed = true;
+            }
+        }
+        // Kept changes that end up in the same hunk take all changes between them with them.
+        let kept: Vec<usize> = (0..changes.len()).filter(|idx| keep[*idx]).collect();
+        for pair in kept.windows(2) {
+            let (earlier, later) = (pair[0], pair[1]);
+            if later - earlier > 1 && gap(earlier, later) <= 2 * context {
+                keep[earlier + 1..later].iter_mut().for_each(|keep| *keep = true);
+                changed = true;
+            }
+        }
+        if !changed {
+            return keep;
+        }
+    }
+}
+
+/// The line ranges of a hunk as given by its `@@ -<before> +<after> @@` header line.
+#[derive(Debug, Copy, Clone, Hash, PartialEq, Eq)]
+pub struct HunkHeader {
+    /// The one-based number of the first line in the old version of the file, or the line before the hunk if it has no old lines.
+    pub before_hunk_start: u32,
+    /// The amount of context and removed lines.
+    pub before_hunk_len: u32,
+    /// The one-based number of the first line in the new version of the file, or the line before the hunk if it has no new lines.
+    pub after_hunk_start: u32,
+    /// The amount of context and added lines.
+    pub after_hunk_len: u32,
+}
+
+impl HunkHeader {
+    /// Parse a line like `@@ -1,2 +1,3 @@ optional section heading`, where a missing length like in `@@ -1 +1 @@` is `1`,
+    /// or return `None` if it isn't a valid hunk header.
+    pub fn parse(line: &[u8]) -> Option<Self> {
+        let rest = line.strip_prefix(b"@@ -")?;
+        let (before, rest) = rest.split_once_str(" +")?;
+        let (after, _section_heading) = rest.split_once_str(" @@")?;
+        let range = |range: &[u8]| -> Option<(u32, u32)> {
+            let range = range.to_str().ok()?;
+            Some(match range.split_once(',') {
+                Some((start, len)) => (start.parse().ok()?, len.parse().ok()?),
+                None => (range.parse().ok()?, 1),
+            })
+        };
+        let (before_hunk_start, before_hunk_len) = range(before)?;
+        let (after_hunk_start, after_hunk_len) = range(after)?;
+        Some(HunkHeader {
+            before_hunk_start,
+            before_hunk_len,
+            after_hunk_start,
+            after_hunk_len,
+        })
+    }
+}
+
+/// Write a hunk range like `git` does, which omits the length if it's `1`, and refers to the line before the hunk if it's `0`.
+fn write_range(out: &mut Vec<u8>, start: u32, len: u32) {
+    match len {
+        0 => write!(out, "{start},0"),
+        1 => write!(out, "{}", start + 1),
+        _ => write!(out, "{},{len}", start + 1),
+    }
+    .expect("writing to a vec cannot fail");
+}
+
+impl Sink for UnifiedDiff<'_> {
+    type Out = Vec<u8>;
+
+    fn process_change(&mut self, before: Range<u32>, after: Range<u32>) {
+        match self.blank_line_changes.as_mut() {
+            Some(changes) => changes.push((before, after)),
+            None => self.write_change(before, after),
+        }
+    }
+
+    fn finish(mut self) -> Self::Out {
+        if let Some(changes) = self.blank_line_changes.take() {
+            let is_blank = |tokens: &[Token], range: &Range<u32>| {
+                super::whitespace::is_blank(self.interner, &tokens[range.start as usize..range.end as usize])
+            };
+            let blank: Vec<bool> = changes
+                .iter()
+                .map(|(before, after)| is_blank(self.before, before) && is_blank(self.after, after))
+                .collect();
+            let keep = keep_changes(&changes, &blank, self.context_size.symmetrical);
+            for ((before, after), _) in changes.into_iter().zip(keep).filter(|(_, keep)| *keep) {
+                self.write_change(before, after);
+            }
+        }
+        self.flush();
+        self.out
+    }
+}
+
+/// The state of a submodule on one side of a change, for use with [`submodule_hunks()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Subproject<'a> {
+    /// The commit the submodule is at.
+    pub commit: &'a gix_hash::oid,
+    /// If `true`, the worktree of the submodule has changes, which is noted with a `-dirty` suffix just like `git diff` does.
+    pub dirty: bool,
+}
+
+/// Produce the hunks for a change of a submodule from `old` to `new` with `context_size` lines of context, with each
+/// side written as `Subproject commit <id>` line just like `git` does, and `None` meaning that there is no submodule
+/// on that side.
+///
+/// Note that the output is empty if both sides are the same.
+pub fn submodule_hunks(old: Option<Subproject<'_>>, new: Option<Subproject<'_>>, context_size: ContextSize) -> Vec<u8> {
+    let line = |side: Option<Subproject<'_>>| {
+        side.map(|side| {
+            format!(
+                "Subproject commit {}{}\n",
+                side.commit,
+                if side.dirty { "-dirty" } else { "" }
+            )
+        })
+        .unwrap_or_default()
+    };
+    let (before, after) = (line(old), line(new));
+    let input = InternedInput::new(
+        crate::blob::sources::byte_lines_with_terminator(before.as_bytes()),
+        crate::blob::sources::byte_lines_with_terminator(after.as_bytes()),
+    );
+    crate::blob::diff(
+        crate::blob::Algorithm::Myers,
+        &input,
+        UnifiedDiff::new(&input, context_size),
+    )
+}
+
+/// One side of a change for use in a [`Header`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Side<'a> {
+    /// The path of the file relative to the root of the repository.
+    pub path: &'a BStr,
+    /// The mode of the file.
+    pub mode: gix_object::tree::EntryMode,
+    /// The id of the file's blob.
+    pub id: &'a gix_hash::oid,
+}
+
+/// Information about a rename or copy for use in a [`Header`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Rewrite {
+    /// If `true`, the file was copied, otherwise it was renamed.
+    pub copy: bool,
+    /// The similarity of the source and the destination in a range from `0.0` to `1.0`, with `1.0` meaning they are identical.
+    pub similarity: f32,
+}
+
+/// The kind of content that follows a [`Header`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Content {
+    /// No hunks follow, as is the case for pure renames, mode changes, or additions and deletions of empty files.
+    None,
+    /// Hunks follow, as produced by [`UnifiedDiff`].
+    Hunks,
+    /// One of the sides is binary, which is noted instead of producing hunks. Note that such changes can't be applied.
+    Binary,
+    /// One of the sides is binary, and a binary patch follows as written by [`binary_patch::write_to()`](crate::blob::binary_patch::write_to()).
+    ///
+    /// Note that `git` uses full object ids in the header of binary patches, so [`Header::hex_len`] should be set accordingly.
+    BinaryPatch,
+}
+
+/// The extended header that precedes the hunks of a single file in a patch, starting with `diff --git a/<path> b/<path>`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Header<'a> {
+    /// The previous version of the file, or `None` if it was added.
+    pub old: Option<Side<'a>>,
+    /// The current version of the file, or `None` if it was deleted.
+    pub new: Option<Side<'a>>,
+    /// Set if `new` is the result of renaming or copying `old`.
+    pub rewrite: Option<Rewrite>,
+    /// The amount of hexadecimal characters to use for the object ids in the `index` line, with `7` being the default in `git`.
+    pub hex_len: usize,
+}
+
+impl Header<'_> {
+    /// Write this header to `out`, followed by the lines that introduce the `content` that is expected to follow.
+    ///
+    /// Nothing will be written if both `old` and `new` are `None`.
+    pub fn write_to(&self, mut out: impl std::io::Write, content: Content) -> std::io::Result<()> {
+        let (old_path, new_path) = match (self.old, self.new) {
+            (Some(old), Some(new)) => (old.path, new.path),
+            (Some(side), None) | (None, Some(side)) => (side.path, side.path),
+            (None, None) => return Ok(()),
+        };
+        writeln!(out, "diff --git a/{old_path} b/{new_path}")?;
+        match (self.old, self.new) {
+            (None, Some(new)) => writeln!(out, "new file mode {}", mode_str(new.mode))?,
+            (Some(old), None) => writeln!(out, "deleted file mode {}", mode_str(old.mode))?,
+            (Some(old), Some(new)) if old.mode != new.mode => {
+                writeln!(out, "old mode {}", mode_str(old.mode))?;
+                writeln!(out, "new mode {}", mode_str(new.mode))?;
+            }
+            _ => {}
+        }
+        if let Some(rewrite) = self.rewrite {
+            let kind = if rewrite.copy { "copy" } else { "rename" };
+            writeln!(out, "similarity index {}%", (rewrite.similarity * 100.0) as u32)?;
+            writeln!(out, "{kind} from {old_path}")?;
+            writeln!(out, "{kind} to {new_path}")?;
+        }
+
+        let (old_id, new_id) = match (self.old, self.new) {
+            (Some(old), Some(new)) => (old.id.to_owned(), new.id.to_owned()),
+            (Some(old), None) => (old.id.to_owned(), old.id.kind().null()),
+            (None, Some(new)) => (new.id.kind().null(), new.id.to_owned()),
+            (None, None) => unreachable!("handled above"),
+        };
+        if old_id != new_id {
+            write!(
+                out,
+                "index {}..{}",
+                old_id.to_hex_with_len(self.hex_len),
+                new_id.to_hex_with_len(self.hex_len)
+            )?;
+            match (self.old, self.new) {
+                (Some(old), Some(new)) if old.mode == new.mode => writeln!(out, " {}", mode_str(new.mode))?,
+                _ => writeln!(out)?,
+            }
+        }
+
+        let old_label = self.old.map(|_| format!("a/{old_path}"));
+        let new_label = self.new.map(|_| format!("b/{new_path}"));
+        let old_label = old_label.as_deref().unwrap_or("/dev/null");
+        let new_label = new_label.as_deref().unwrap_or("/dev/null");
+        match content {
+            Content::None | Content::BinaryPatch => {}
+            Content::Hunks => {
+                writeln!(out, "--- {old_label}")?;
+                writeln!(out, "+++ {new_label}")?;
+            }
+            Content::Binary => writeln!(out, "Binary files {old_label} and {new_label} differ")?,
+        }
+        Ok(())
+    }
+}
+
+fn mode_str(mode: gix_object::tree::EntryMode) -> &'static BStr {
+    mode.kind().as_octal_str()
+}
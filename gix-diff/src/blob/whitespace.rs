@@ -0,0 +1,74 @@
+use std::ops::Range;
+
+use imara_diff::{
+    intern::{InternedInput, Interner, Token},
+    Sink,
+};
+
+use crate::blob::IgnoreWhitespace;
+
+/// Return the tokens of `input` with all lines that only differ in ignored whitespace mapped to the same token, along
+/// with the amount of tokens in use.
+pub(super) fn normalize(input: &InternedInput<&[u8]>, ignore: IgnoreWhitespace) -> (Vec<Token>, Vec<Token>, u32) {
+    let mut interner = Interner::<Vec<u8>>::new(input.interner.num_tokens() as usize);
+    let normalized: Vec<Token> = (0..input.interner.num_tokens())
+        .map(|token| interner.intern(normalize_line(input.interner[Token(token)], ignore)))
+        .collect();
+    let map = |tokens: &[Token]| -> Vec<Token> { tokens.iter().map(|token| normalized[token.0 as usize]).collect() };
+    (map(&input.before), map(&input.after), interner.num_tokens())
+}
+
+fn normalize_line(line: &[u8], ignore: IgnoreWhitespace) -> Vec<u8> {
+    if ignore.all_space {
+        return line.iter().copied().filter(|b| !is_space(*b)).collect();
+    }
+    let end = line.len() - line.iter().rev().take_while(|b| is_space(**b)).count();
+    let line = &line[..end];
+    if !ignore.space_change {
+        return line.to_owned();
+    }
+    let mut out = Vec::with_capacity(line.len());
+    for (idx, &b) in line.iter().enumerate() {
+        if !is_space(b) {
+            out.push(b);
+        } else if idx == 0 || !is_space(line[idx - 1]) {
+            out.push(b' ');
+        }
+    }
+    out
+}
+
+/// Return `true` if all lines of `tokens` are empty or consist of whitespace only.
+pub(super) fn is_blank(interner: &Interner<&[u8]>, tokens: &[Token]) -> bool {
+    tokens.iter().all(|token| interner[*token].iter().all(|b| is_space(*b)))
+}
+
+/// Whitespace like `isspace()` in the C locale, which is what `git` uses.
+pub(crate) fn is_space(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\x0b' | b'\x0c' | b'\r')
+}
+
+/// A sink that drops changes whose lines are all blank if `enabled`, and passes all other changes to `sink`.
+pub(super) struct IgnoreBlankLines<'a, 'data, S> {
+    pub input: &'a InternedInput<&'data [u8]>,
+    pub enabled: bool,
+    pub sink: S,
+}
+
+impl<S: Sink> Sink for IgnoreBlankLines<'_, '_, S> {
+    type Out = S::Out;
+
+    fn process_change(&mut self, before: Range<u32>, after: Range<u32>) {
+        let is_blank = |tokens: &[Token], range: &Range<u32>| {
+            is_blank(&self.input.interner, &tokens[range.start as usize..range.end as usize])
+        };
+        if self.enabled && is_blank(&self.input.before, &before) && is_blank(&self.input.after, &after) {
+            return;
+        }
+        self.sink.process_change(before, after)
+    }
+
+    fn finish(self) -> Self::Out {
+        self.sink.finish()
+    }
+}
@@ -0,0 +1,116 @@
+//! Facilities to diff words or characters instead of lines, for use in renderers that highlight changes within lines,
+//! similar to `git diff --word-diff`.
+use std::ops::Range;
+
+use bstr::{BStr, ByteSlice};
+
+use crate::blob::{intern::InternedInput, whitespace::is_space, Algorithm};
+
+/// Determine how text is split into the tokens that are compared with each other.
+#[derive(Debug, Clone)]
+pub enum Tokenizer {
+    /// Words are runs of non-whitespace characters, which is what `git` does by default.
+    Whitespace,
+    /// Words are the non-empty matches of the regular expression, with everything in between being ignored,
+    /// just like with `git diff --word-diff-regex=<regex>` or the `diff.<driver>.wordRegex` configuration.
+    Regex(regex::bytes::Regex),
+    /// Each character is a token, with each invalid UTF-8 sequence being a token of its own.
+    Characters,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tokenizer {
+    /// The error returned by [`Tokenizer::from_word_regex()`](super::Tokenizer::from_word_regex()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The word regex '{regex}' wasn't valid UTF-8")]
+        Utf8 { regex: bstr::BString },
+        #[error(transparent)]
+        Regex(#[from] regex::Error),
+    }
+}
+
+impl Tokenizer {
+    /// Create a tokenizer that considers all matches of `word_regex` as words, as read from `diff.<driver>.wordRegex`.
+    pub fn from_word_regex(word_regex: &BStr) -> Result<Self, tokenizer::Error> {
+        let regex = word_regex.to_str().map_err(|_| tokenizer::Error::Utf8 {
+            regex: word_regex.to_owned(),
+        })?;
+        Ok(Tokenizer::Regex(regex::bytes::Regex::new(regex)?))
+    }
+
+    /// Return the byte ranges of all tokens in `input`, in order.
+    pub fn tokens(&self, input: &[u8]) -> Vec<Range<usize>> {
+        match self {
+            Tokenizer::Whitespace => {
+                let mut out = Vec::new();
+                let mut start = None;
+                for (idx, byte) in input.iter().enumerate() {
+                    match (start, is_space(*byte)) {
+                        (None, false) => start = Some(idx),
+                        (Some(word_start), true) => {
+                            out.push(word_start..idx);
+                            start = None;
+                        }
+                        _ => {}
+                    }
+                }
+                out.extend(start.map(|word_start| word_start..input.len()));
+                out
+            }
+            Tokenizer::Regex(regex) => regex
+                .find_iter(input)
+                .map(|m| m.range())
+                .filter(|range| !range.is_empty())
+                .collect(),
+            Tokenizer::Characters => input.char_indices().map(|(start, end, _)| start..end).collect(),
+        }
+    }
+}
+
+/// A change between the tokens of two texts, as byte ranges into them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The range of the removed or replaced tokens in the old text.
+    ///
+    /// It's empty if tokens were only inserted, and then points to the position right after the token that precedes the insertion.
+    pub before: Range<usize>,
+    /// The range of the inserted or replacing tokens in the new text.
+    ///
+    /// It's empty if tokens were only removed, and then points to the position right after the token that precedes the removal.
+    pub after: Range<usize>,
+}
+
+/// Diff the tokens of `before` and `after` as produced by `tokenizer` using `algorithm`, and return the byte ranges of all changes.
+///
+/// Note that it's common to call this function with the lines of a hunk produced by a line-based diff, to highlight
+/// changes within them.
+pub fn diff(algorithm: Algorithm, before: &[u8], after: &[u8], tokenizer: &Tokenizer) -> Vec<Change> {
+    let before_tokens = tokenizer.tokens(before);
+    let after_tokens = tokenizer.tokens(after);
+    let mut input = InternedInput::default();
+    input.update_before(before_tokens.iter().map(|range| &before[range.clone()]));
+    input.update_after(after_tokens.iter().map(|range| &after[range.clone()]));
+
+    let mut out = Vec::new();
+    crate::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+        out.push(Change {
+            before: byte_range(&before_tokens, before),
+            after: byte_range(&after_tokens, after),
+        });
+    });
+    out
+}
+
+/// Convert the range of `tokens` into the byte range they span.
+fn byte_range(tokens: &[Range<usize>], range: Range<u32>) -> Range<usize> {
+    let (start, end) = (range.start as usize, range.end as usize);
+    if start == end {
+        let pos = start.checked_sub(1).map_or(0, |preceding| tokens[preceding].end);
+        pos..pos
+    } else {
+        tokens[start].start..tokens[end - 1].end
+    }
+}
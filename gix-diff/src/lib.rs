@@ -50,3 +50,14 @@ pub mod tree;
 ///
 #[cfg(feature = "blob")]
 pub mod blob;
+
+///
+#[cfg(feature = "blob")]
+pub mod range_diff;
+
+///
+#[cfg(feature = "blob")]
+pub mod patch_id;
+
+///
+pub mod stat;
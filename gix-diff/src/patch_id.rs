@@ -0,0 +1,100 @@
+//! Compute the id of a patch that is independent of the location of its hunks, the whitespace in its lines and the order of
+//! its files, just like `git patch-id --stable` does. It can be used to find commits which introduce the same change.
+use bstr::ByteSlice;
+
+use crate::blob::{unified_diff::HunkHeader, whitespace::is_space};
+
+/// Compute the id of `patch` in the unified diff format of `git diff`, using the hash function of `object_hash`,
+/// or return `None` if it doesn't contain any changes.
+///
+/// Everything before the first `diff` line, like a commit message, is ignored. Within the patch, `index` lines, line numbers
+/// of hunks and all whitespace are ignored, and each file is hashed separately to sum up the hashes of all files, so their
+/// order doesn't matter. Binary files are identified by the object ids in their `index` line, which should have full length.
+pub fn from_patch(patch: &[u8], object_hash: gix_hash::Kind) -> Option<gix_hash::ObjectId> {
+    let mut result = object_hash.null();
+    let mut hasher = gix_features::hash::hasher(object_hash);
+    let mut patch_len = 0;
+    let (mut before, mut after): (i64, i64) = (-1, -1);
+    let mut is_binary = false;
+    let (mut pre_id, mut post_id): (&[u8], &[u8]) = (&[], &[]);
+
+    for line in patch.lines_with_terminator() {
+        if line.starts_with(b"\\ ") && line.len() > 12 {
+            continue;
+        }
+        if patch_len == 0 && !line.starts_with(b"diff ") {
+            continue;
+        }
+
+        if before == -1 {
+            if line.starts_with(b"GIT binary patch") || line.starts_with(b"Binary files") {
+                is_binary = true;
+                before = 0;
+                hasher.update(pre_id);
+                hasher.update(post_id);
+                flush_one_file(&mut result, &mut hasher, object_hash);
+                continue;
+            } else if let Some(ids) = line.strip_prefix(b"index ") {
+                if let Some((pre, post)) = ids.split_once_str("..") {
+                    pre_id = pre;
+                    post_id = post
+                        .find_byte(b' ')
+                        .map_or_else(|| post.trim_end_with(|c| c == '\n' || c == '\r'), |pos| &post[..pos]);
+                }
+                continue;
+            } else if line.starts_with(b"--- ") {
+                (before, after) = (1, 1);
+            } else if !matches!(line.first(), Some(b) if b.is_ascii_alphabetic()) {
+                break;
+            }
+        }
+
+        if is_binary {
+            if line.starts_with(b"diff ") {
+                is_binary = false;
+                before = -1;
+            }
+            continue;
+        }
+
+        if before == 0 && after == 0 {
+            if line.starts_with(b"@@ -") {
+                if let Some(header) = HunkHeader::parse(line) {
+                    (before, after) = (header.before_hunk_len.into(), header.after_hunk_len.into());
+                }
+                continue;
+            }
+            if !line.starts_with(b"diff ") {
+                break;
+            }
+            flush_one_file(&mut result, &mut hasher, object_hash);
+            (before, after) = (-1, -1);
+        }
+
+        if matches!(line.first(), Some(b'-' | b' ')) {
+            before -= 1;
+        }
+        if matches!(line.first(), Some(b'+' | b' ')) {
+            after -= 1;
+        }
+
+        for chunk in line.split(|b| is_space(*b)) {
+            hasher.update(chunk);
+            patch_len += chunk.len();
+        }
+    }
+
+    flush_one_file(&mut result, &mut hasher, object_hash);
+    (patch_len != 0).then_some(result)
+}
+
+/// Add the hash computed by `hasher` to `result` as little-endian number, and reset `hasher` for the next file.
+fn flush_one_file(result: &mut gix_hash::ObjectId, hasher: &mut gix_features::hash::Sha1, object_hash: gix_hash::Kind) {
+    let hash = std::mem::replace(hasher, gix_features::hash::hasher(object_hash)).digest();
+    let mut carry = 0u16;
+    for (result, byte) in result.as_mut_slice().iter_mut().zip(hash) {
+        carry += u16::from(*result) + u16::from(byte);
+        *result = carry as u8;
+        carry >>= 8;
+    }
+}
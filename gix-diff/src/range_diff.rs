@@ -0,0 +1,240 @@
+//! Pair up the patches of two versions of a series of commits by their similarity, and compute how each pair differs,
+//! just like `git range-diff` does.
+use crate::blob::{intern::InternedInput, sources::byte_lines_with_terminator, unified_diff::ContextSize};
+
+/// Options for [`pair_patches()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The cost of considering a patch as added or removed instead of pairing it with a changed version of it,
+    /// as percentage of the size of the patch, with `60` being the default just like `git range-diff --creation-factor`.
+    ///
+    /// Higher values pair up more patches, even if they changed a lot.
+    pub creation_factor: u32,
+    /// The amount of unchanged lines to write around each change in the diff between paired patches.
+    pub context_size: ContextSize,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            creation_factor: 60,
+            context_size: ContextSize::default(),
+        }
+    }
+}
+
+/// The way a patch of the old series relates to a patch of the new series, as returned by [`pair_patches()`].
+///
+/// Patches are referred to by their index in the series they are in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Pair {
+    /// The patch at `old` doesn't have a counterpart in the new series.
+    Removed {
+        /// The index of the patch in the old series.
+        old: usize,
+    },
+    /// The patch at `new` doesn't have a counterpart in the old series.
+    Added {
+        /// The index of the patch in the new series.
+        new: usize,
+    },
+    /// The patches are the same.
+    Unchanged {
+        /// The index of the patch in the old series.
+        old: usize,
+        /// The index of the patch in the new series.
+        new: usize,
+    },
+    /// The patches correspond to each other, but differ.
+    Changed {
+        /// The index of the patch in the old series.
+        old: usize,
+        /// The index of the patch in the new series.
+        new: usize,
+        /// The hunks of the diff from the old patch to the new one, in the unified diff format.
+        interdiff: Vec<u8>,
+    },
+}
+
+/// Pair up each patch in `old` with the most similar patch in `new`, and return the pairs in the order in which
+/// `git range-diff` would show them, which is the order of `new` with removed patches shown as soon as all patches
+/// before them in `old` were shown.
+///
+/// Patches are paired such that the overall size of the differences between paired patches is minimal, with each
+/// unpaired patch costing the percentage of its size as configured by [`Options::creation_factor`].
+/// Patches that are the same are always paired, preferring those closest to the beginning of the series.
+pub fn pair_patches(old: &[impl AsRef<[u8]>], new: &[impl AsRef<[u8]>], options: Options) -> Vec<Pair> {
+    let (old, new): (Vec<&[u8]>, Vec<&[u8]>) = (
+        old.iter().map(AsRef::as_ref).collect(),
+        new.iter().map(AsRef::as_ref).collect(),
+    );
+    let mut old_match: Vec<Option<usize>> = vec![None; old.len()];
+    let mut new_match: Vec<Option<usize>> = vec![None; new.len()];
+    for (old_idx, old_patch) in old.iter().enumerate() {
+        if let Some(new_idx) = (0..new.len()).find(|idx| new_match[*idx].is_none() && new[*idx] == *old_patch) {
+            old_match[old_idx] = Some(new_idx);
+            new_match[new_idx] = Some(old_idx);
+        }
+    }
+
+    // Like `git`, find the cheapest assignment in a square matrix in which each patch can also be assigned to one of the
+    // rows or columns added to make it square, which represents it being unmatched.
+    const COST_MAX: i64 = 1 << 16;
+    let n = old.len() + new.len();
+    let unmatched_cost = |patch: &[u8], matched: bool| {
+        if matched {
+            COST_MAX
+        } else {
+            i64::from(count_lines(patch)) * i64::from(options.creation_factor) / 100
+        }
+    };
+    let mut cost = vec![0i64; n * n];
+    for (old_idx, old_patch) in old.iter().enumerate() {
+        for (new_idx, new_patch) in new.iter().enumerate() {
+            cost[old_idx * n + new_idx] = match (old_match[old_idx], new_match[new_idx]) {
+                (Some(matched), _) if matched == new_idx => 0,
+                (None, None) => i64::from(diff_size(old_patch, new_patch)),
+                _ => COST_MAX,
+            };
+        }
+        let unmatched = unmatched_cost(old_patch, old_match[old_idx].is_some());
+        for new_idx in new.len()..n {
+            cost[old_idx * n + new_idx] = unmatched;
+        }
+    }
+    for (new_idx, new_patch) in new.iter().enumerate() {
+        let unmatched = unmatched_cost(new_patch, new_match[new_idx].is_some());
+        for old_idx in old.len()..n {
+            cost[old_idx * n + new_idx] = unmatched;
+        }
+    }
+    new_match.iter_mut().for_each(|matched| *matched = None);
+    for (old_idx, new_idx) in assignment(&cost, n).into_iter().take(old.len()).enumerate() {
+        old_match[old_idx] = (new_idx < new.len()).then_some(new_idx);
+        if new_idx < new.len() {
+            new_match[new_idx] = Some(old_idx);
+        }
+    }
+
+    let mut out = Vec::with_capacity(n);
+    let mut shown = vec![false; old.len()];
+    let (mut old_idx, mut new_idx) = (0, 0);
+    while old_idx < old.len() || new_idx < new.len() {
+        if old_idx < old.len() && shown[old_idx] {
+            old_idx += 1;
+            continue;
+        }
+        if old_idx < old.len() && old_match[old_idx].is_none() {
+            out.push(Pair::Removed { old: old_idx });
+            old_idx += 1;
+            continue;
+        }
+        while new_idx < new.len() && new_match[new_idx].is_none() {
+            out.push(Pair::Added { new: new_idx });
+            new_idx += 1;
+        }
+        if let Some(matched) = new_match.get(new_idx).copied().flatten() {
+            out.push(if old[matched] == new[new_idx] {
+                Pair::Unchanged {
+                    old: matched,
+                    new: new_idx,
+                }
+            } else {
+                Pair::Changed {
+                    old: matched,
+                    new: new_idx,
+                    interdiff: interdiff(old[matched], new[new_idx], options.context_size),
+                }
+            });
+            shown[matched] = true;
+            new_idx += 1;
+        }
+    }
+    out
+}
+
+fn count_lines(patch: &[u8]) -> u32 {
+    byte_lines_with_terminator(patch).count() as u32
+}
+
+/// The amount of lines that differ between `old` and `new`.
+fn diff_size(old: &[u8], new: &[u8]) -> u32 {
+    let input = InternedInput::new(byte_lines_with_terminator(old), byte_lines_with_terminator(new));
+    let counter = crate::blob::diff(
+        crate::blob::Algorithm::Myers,
+        &input,
+        crate::blob::sink::Counter::default(),
+    );
+    counter.removals + counter.insertions
+}
+
+fn interdiff(old: &[u8], new: &[u8], context_size: ContextSize) -> Vec<u8> {
+    let input = InternedInput::new(byte_lines_with_terminator(old), byte_lines_with_terminator(new));
+    crate::blob::diff(
+        crate::blob::Algorithm::Myers,
+        &input,
+        crate::blob::UnifiedDiff::new(&input, context_size),
+    )
+}
+
+/// Solve the linear assignment problem for the `n` by `n` matrix `cost`, stored row by row, and return the column
+/// assigned to each row, using the Hungarian method.
+fn assignment(cost: &[i64], n: usize) -> Vec<usize> {
+    const INFINITY: i64 = i64::MAX / 4;
+    // Potentials of rows and columns, and the row assigned to each column, all one-based with `0` being a sentinel.
+    let mut row_potential = vec![0i64; n + 1];
+    let mut column_potential = vec![0i64; n + 1];
+    let mut row_of_column = vec![0usize; n + 1];
+    let mut previous_column = vec![0usize; n + 1];
+    for row in 1..=n {
+        row_of_column[0] = row;
+        let mut column = 0;
+        let mut min_slack = vec![INFINITY; n + 1];
+        let mut used = vec![false; n + 1];
+        loop {
+            used[column] = true;
+            let current_row = row_of_column[column];
+            let mut delta = INFINITY;
+            let mut next_column = 0;
+            for candidate in 1..=n {
+                if used[candidate] {
+                    continue;
+                }
+                let slack = cost[(current_row - 1) * n + candidate - 1]
+                    - row_potential[current_row]
+                    - column_potential[candidate];
+                if slack < min_slack[candidate] {
+                    min_slack[candidate] = slack;
+                    previous_column[candidate] = column;
+                }
+                if min_slack[candidate] < delta {
+                    delta = min_slack[candidate];
+                    next_column = candidate;
+                }
+            }
+            for candidate in 0..=n {
+                if used[candidate] {
+                    row_potential[row_of_column[candidate]] += delta;
+                    column_potential[candidate] -= delta;
+                } else {
+                    min_slack[candidate] -= delta;
+                }
+            }
+            column = next_column;
+            if row_of_column[column] == 0 {
+                break;
+            }
+        }
+        while column != 0 {
+            let previous = previous_column[column];
+            row_of_column[column] = row_of_column[previous];
+            column = previous;
+        }
+    }
+
+    let mut out = vec![0; n];
+    for (column, row) in row_of_column.into_iter().enumerate().skip(1) {
+        out[row - 1] = column - 1;
+    }
+    out
+}
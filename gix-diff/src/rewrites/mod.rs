@@ -23,6 +23,8 @@ pub enum CopySource {
     /// Find copies from the set of modified files, as well as all files known to the source (i.e. previous state of the tree).
     ///
     /// This can be an expensive operation as it scales exponentially with the total amount of files in the set.
+    /// To keep it tractable, copies among the modified files are searched first, and if searching all sources
+    /// would exceed the [limit](crate::Rewrites::limit), only exact copies are found among them.
     FromSetOfModifiedFilesAndAllSources,
 }
 
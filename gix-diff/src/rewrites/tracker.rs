@@ -306,7 +306,7 @@ impl<T: Change> Tracker<T> {
                 let (num_src, num_dst) =
                     estimate_involved_items(self.items.iter().map(|item| (item.emitted, item.change.kind())), kind);
                 let permutations = num_src * num_dst;
-                if permutations > self.rewrites.limit {
+                if exceeds_limit(num_src, num_dst, self.rewrites.limit) {
                     match kind {
                         visit::SourceKind::Rename => {
                             out.num_similarity_checks_skipped_for_rename_tracking_due_to_limit = permutations;
@@ -424,6 +424,12 @@ fn estimate_involved_items(
         })
 }
 
+/// Return `true` if the worst-case amount of similarity checks between `num_src` sources and `num_dst` destinations
+/// is too high for `limit`, which like in `git` is the amount of files on either side, allowing `limit * limit` checks.
+fn exceeds_limit(num_src: usize, num_dst: usize, limit: usize) -> bool {
+    num_src.saturating_mul(num_dst) > limit.saturating_mul(limit)
+}
+
 fn needs_exact_match(percentage: Option<f32>) -> bool {
     percentage.map_or(true, |p| p >= 1.0)
 }
@@ -505,15 +511,17 @@ fn find_match<'a, T: Change>(
                 ResourceKind::OldOrSource,
                 objects,
             )?;
+            let ignore_whitespace = diff_cache.options.ignore_whitespace;
             let prep = diff_cache.prepare_diff()?;
             stats.num_similarity_checks += 1;
             match prep.operation {
                 Operation::InternalDiff { algorithm } => {
                     let tokens =
                         crate::blob::intern::InternedInput::new(prep.old.intern_source(), prep.new.intern_source());
-                    let counts = crate::blob::diff(
+                    let counts = crate::blob::diff_ignoring_whitespace(
                         algorithm,
                         &tokens,
+                        ignore_whitespace,
                         crate::blob::sink::Counter::new(diff::Statistics {
                             removed_bytes: 0,
                             input: &tokens,
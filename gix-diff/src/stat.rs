@@ -0,0 +1,331 @@
+//! Aggregate the changes made to files into a *diffstat*, and write it just like `git diff --numstat` and
+//! `git diff --stat` do.
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// The amount of changes made to a single [`File`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Lines {
+    /// Both versions of the file are text, and lines were inserted and removed.
+    Text {
+        /// The amount of inserted lines.
+        insertions: u32,
+        /// The amount of removed lines.
+        removals: u32,
+    },
+    /// One of the versions of the file is binary, so only their sizes are known.
+    Binary {
+        /// The size of the previous version in bytes, or `0` if the file was added.
+        old_size: u64,
+        /// The size of the current version in bytes, or `0` if the file was deleted.
+        new_size: u64,
+    },
+}
+
+/// A changed file along with the amount of changes made to it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct File {
+    /// The location of the file before it was renamed or copied, or `None` if it wasn't.
+    pub source_location: Option<BString>,
+    /// The location of the file, relative to the root of the repository.
+    pub location: BString,
+    /// The amount of changes made to the file.
+    pub lines: Lines,
+}
+
+impl File {
+    /// Return the name `git` shows for this file, which is its location, or the source and destination of renames and
+    /// copies with their common leading directories and trailing components factored out, like `dir/{old => new}/file`.
+    pub fn display_name(&self) -> BString {
+        match self.source_location.as_ref() {
+            Some(source) => rename_name(source.as_bstr(), self.location.as_bstr()),
+            None => self.location.clone(),
+        }
+    }
+}
+
+/// The amount of changes of all files, as shown in the last line of `git diff --stat`.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Summary {
+    /// The amount of changed files, including binary ones.
+    pub files_changed: usize,
+    /// The amount of inserted lines in all text files.
+    pub insertions: u64,
+    /// The amount of removed lines in all text files.
+    pub removals: u64,
+}
+
+impl std::fmt::Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.files_changed == 0 {
+            return f.write_str("0 files changed");
+        }
+        let plural = |count: u64| if count == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} file{} changed",
+            self.files_changed,
+            plural(self.files_changed as u64)
+        )?;
+        // Like `git`, mention zero counts instead of leaving them out entirely.
+        if self.insertions != 0 || self.removals == 0 {
+            write!(f, ", {} insertion{}(+)", self.insertions, plural(self.insertions))?;
+        }
+        if self.removals != 0 || self.insertions == 0 {
+            write!(f, ", {} deletion{}(-)", self.removals, plural(self.removals))?;
+        }
+        Ok(())
+    }
+}
+
+/// Options for [`Stats::write_stat()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The maximum width of each line, defaulting to `80` just like `git` does if the output isn't a terminal.
+    pub width: usize,
+    /// If set, the maximum width of the file names, which are shortened from the front if they are wider.
+    pub name_width: Option<usize>,
+    /// If set, the maximum width of the graph of `+` and `-` signs.
+    pub graph_width: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            width: 80,
+            name_width: None,
+            graph_width: None,
+        }
+    }
+}
+
+/// The changes made to a set of files, as obtained from a tree diff.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// The changed files in the order in which they should be shown.
+    pub files: Vec<File>,
+}
+
+impl Stats {
+    /// Return the total amount of changes, with binary files counting as changed files, but not contributing any lines.
+    pub fn summary(&self) -> Summary {
+        self.files.iter().fold(
+            Summary {
+                files_changed: self.files.len(),
+                ..Default::default()
+            },
+            |mut summary, file| {
+                if let Lines::Text { insertions, removals } = file.lines {
+                    summary.insertions += u64::from(insertions);
+                    summary.removals += u64::from(removals);
+                }
+                summary
+            },
+        )
+    }
+
+    /// Write one line per file to `out` with the amount of inserted and removed lines separated by tabs, followed by the
+    /// [name](File::display_name()) of the file, exactly like `git diff --numstat` does.
+    ///
+    /// Binary files are written with `-` instead of the amount of lines.
+    pub fn write_numstat(&self, mut out: impl std::io::Write) -> std::io::Result<()> {
+        for file in &self.files {
+            match file.lines {
+                Lines::Text { insertions, removals } => write!(out, "{insertions}\t{removals}\t")?,
+                Lines::Binary { .. } => out.write_all(b"-\t-\t")?,
+            }
+            out.write_all(&file.display_name())?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Write one line per file to `out` with its name, the amount of changed lines and a graph of `+` and `-` signs
+    /// representing inserted and removed lines, followed by the [summary](Self::summary()), exactly like
+    /// `git diff --stat` does. The widths of the columns are determined by `options`.
+    ///
+    /// Binary files are written with their previous and current size instead. Nothing is written if there are no files.
+    pub fn write_stat(&self, mut out: impl std::io::Write, options: Options) -> std::io::Result<()> {
+        if self.files.is_empty() {
+            return Ok(());
+        }
+        let names: Vec<BString> = self.files.iter().map(File::display_name).collect();
+        let max_name_len = names
+            .iter()
+            .map(|name| width_of(name.as_bstr()))
+            .max()
+            .unwrap_or_default();
+        let mut max_change = 0;
+        let mut number_width = 0;
+        let mut bin_width = 0;
+        for file in &self.files {
+            match file.lines {
+                Lines::Text { insertions, removals } => {
+                    max_change = max_change.max(u64::from(insertions) + u64::from(removals));
+                }
+                Lines::Binary { old_size, new_size } => {
+                    // Room for "Bin <old_size> -> <new_size> bytes", with the changes of text files aligned with "Bin".
+                    bin_width = bin_width.max(14 + decimal_width(old_size) + decimal_width(new_size));
+                    number_width = 3;
+                }
+            }
+        }
+
+        // The layout is " <name> | <number> <graph>", which is why there are 6 columns in addition to the name,
+        // the number and the graph, with the graph getting at most 3/8th of the width if it has to be shortened.
+        let number_width = number_width.max(decimal_width(max_change));
+        let width = options.width.max(16 + 6 + number_width);
+        let max_change = max_change as usize;
+        let mut graph_width = if max_change + 4 > bin_width {
+            max_change
+        } else {
+            bin_width - 4
+        };
+        if let Some(max_graph_width) = options.graph_width.filter(|max| *max != 0 && *max < graph_width) {
+            graph_width = max_graph_width;
+        }
+        let mut name_width = match options.name_width {
+            Some(max_name_width) if max_name_width > 0 && max_name_width < max_name_len => max_name_width,
+            _ => max_name_len,
+        };
+        if name_width + number_width + 6 + graph_width > width {
+            let max_graph_width = (width * 3 / 8) as isize - number_width as isize - 6;
+            if graph_width as isize > max_graph_width {
+                graph_width = max_graph_width.max(6) as usize;
+            }
+            if let Some(max_graph_width) = options.graph_width.filter(|max| *max != 0 && graph_width > *max) {
+                graph_width = max_graph_width;
+            }
+            let available_name_width = width.saturating_sub(number_width + 6 + graph_width);
+            if name_width > available_name_width {
+                name_width = available_name_width;
+            } else {
+                graph_width = width - number_width - 6 - name_width;
+            }
+        }
+
+        for (file, name) in self.files.iter().zip(names.iter()) {
+            let (prefix, name) = shorten_name(name.as_bstr(), name_width);
+            let padding = name_width.saturating_sub(prefix.len() + width_of(name));
+            out.write_all(b" ")?;
+            out.write_all(prefix.as_bytes())?;
+            out.write_all(name)?;
+            write!(out, "{:padding$} | ", "")?;
+            let (insertions, removals) = match file.lines {
+                Lines::Binary { old_size, new_size } => {
+                    write!(out, "{:>number_width$}", "Bin")?;
+                    if old_size != 0 || new_size != 0 {
+                        write!(out, " {old_size} -> {new_size} bytes")?;
+                    }
+                    out.write_all(b"\n")?;
+                    continue;
+                }
+                Lines::Text { insertions, removals } => (insertions as usize, removals as usize),
+            };
+
+            let changes = insertions + removals;
+            let (mut plus, mut minus) = (insertions, removals);
+            if graph_width <= max_change {
+                let mut total = scale_linear(changes, graph_width, max_change);
+                if total < 2 && insertions != 0 && removals != 0 {
+                    total = 2;
+                }
+                if insertions < removals {
+                    plus = scale_linear(insertions, graph_width, max_change);
+                    minus = total - plus;
+                } else {
+                    minus = scale_linear(removals, graph_width, max_change);
+                    plus = total - minus;
+                }
+            }
+            write!(out, "{changes:>number_width$}")?;
+            if changes != 0 {
+                out.write_all(b" ")?;
+            }
+            out.write_all(&b"+".repeat(plus))?;
+            out.write_all(&b"-".repeat(minus))?;
+            out.write_all(b"\n")?;
+        }
+        writeln!(out, " {}", self.summary())
+    }
+}
+
+/// Scale `count` to `width` proportionally to `max`, but such that any change is represented by at least one column.
+fn scale_linear(count: usize, width: usize, max: usize) -> usize {
+    if count == 0 {
+        return 0;
+    }
+    1 + count * width.saturating_sub(1) / max
+}
+
+fn decimal_width(mut num: u64) -> usize {
+    let mut width = 1;
+    while num >= 10 {
+        num /= 10;
+        width += 1;
+    }
+    width
+}
+
+fn width_of(name: &BStr) -> usize {
+    name.chars().count()
+}
+
+/// Shorten `name` to fit into `width` by removing characters from the front, and return the prefix to indicate
+/// this along with the remainder, which starts at a directory boundary if possible.
+fn shorten_name(name: &BStr, width: usize) -> (&'static str, &BStr) {
+    let mut len = width_of(name);
+    if len <= width {
+        return ("", name);
+    }
+    let width = width.saturating_sub(3);
+    let mut rest = name;
+    while len > width {
+        let (_, end, _) = rest.char_indices().next().expect("more characters than the width");
+        rest = rest[end..].as_bstr();
+        len -= 1;
+    }
+    let rest = rest.find_byte(b'/').map_or(rest, |pos| rest[pos..].as_bstr());
+    ("...", rest)
+}
+
+/// Produce `prefix/{old => new}/suffix` from `old` and `new`, with `prefix` and `suffix` being the directories and the
+/// trailing path components they have in common.
+fn rename_name(old: &BStr, new: &BStr) -> BString {
+    let prefix_len = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter(|(_, (byte, _))| **byte == b'/')
+        .last()
+        .map_or(0, |(pos, _)| pos + 1);
+    // The suffix starts at a slash that may also be the last byte of the prefix, but must not overlap it otherwise.
+    let min_len = prefix_len.saturating_sub(1);
+    let suffix_len = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(old.len().min(new.len()).saturating_sub(min_len))
+        .take_while(|(a, b)| a == b)
+        .enumerate()
+        .filter(|(_, (byte, _))| **byte == b'/')
+        .last()
+        .map_or(0, |(pos, _)| pos + 1);
+
+    let old_mid = &old[prefix_len..old.len().saturating_sub(suffix_len).max(prefix_len)];
+    let new_mid = &new[prefix_len..new.len().saturating_sub(suffix_len).max(prefix_len)];
+    let mut out = BString::default();
+    let factored_out = prefix_len + suffix_len != 0;
+    if factored_out {
+        out.push_str(&old[..prefix_len]);
+        out.push_byte(b'{');
+    }
+    out.push_str(old_mid);
+    out.push_str(" => ");
+    out.push_str(new_mid);
+    if factored_out {
+        out.push_byte(b'}');
+        out.push_str(&old[old.len() - suffix_len..]);
+    }
+    out
+}
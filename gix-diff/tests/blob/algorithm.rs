@@ -0,0 +1,154 @@
+use gix_diff::blob::{intern::InternedInput, unified_diff::ContextSize, Algorithm, UnifiedDiff};
+
+const BEFORE: &str = r#"#include <stdio.h>
+
+// Frobs foo heartily
+int frobnitz(int foo)
+{
+    int i;
+    for(i = 0; i < 10; i++)
+    {
+        printf("Your answer is: ");
+        printf("%d\n", foo);
+    }
+}
+
+int fact(int n)
+{
+    if(n > 1)
+    {
+        return fact(n-1) * n;
+    }
+    return 1;
+}
+
+int main(int argc, char **argv)
+{
+    frobnitz(fact(10));
+}
+"#;
+
+const AFTER: &str = r#"#include <stdio.h>
+
+int fib(int n)
+{
+    if(n > 2)
+    {
+        return fib(n-1) + fib(n-2);
+    }
+    return 1;
+}
+
+// Frobs foo heartily
+int frobnitz(int foo)
+{
+    int i;
+    for(i = 0; i < 10; i++)
+    {
+        printf("%d\n", foo);
+    }
+}
+
+int main(int argc, char **argv)
+{
+    frobnitz(fib(10));
+}
+"#;
+
+/// The output of `git diff --patience`, without header.
+const EXPECTED_PATIENCE: &str = r#"@@ -1,26 +1,25 @@
+ #include <stdio.h>
+ 
++int fib(int n)
++{
++    if(n > 2)
++    {
++        return fib(n-1) + fib(n-2);
++    }
++    return 1;
++}
++
+ // Frobs foo heartily
+ int frobnitz(int foo)
+ {
+     int i;
+     for(i = 0; i < 10; i++)
+     {
+-        printf("Your answer is: ");
+         printf("%d\n", foo);
+     }
+ }
+ 
+-int fact(int n)
+-{
+-    if(n > 1)
+-    {
+-        return fact(n-1) * n;
+-    }
+-    return 1;
+-}
+-
+ int main(int argc, char **argv)
+ {
+-    frobnitz(fact(10));
++    frobnitz(fib(10));
+ }
+"#;
+
+#[test]
+fn patience_matches_git() {
+    assert_eq!(
+        unified_diff(Algorithm::Patience, BEFORE, AFTER),
+        EXPECTED_PATIENCE,
+        "this is exactly what `git diff --patience` produces"
+    );
+    assert_ne!(
+        unified_diff(Algorithm::Myers, BEFORE, AFTER),
+        unified_diff(Algorithm::Patience, BEFORE, AFTER),
+        "Myers interleaves the moved function with the modified one"
+    );
+}
+
+#[test]
+fn all_algorithms_produce_valid_changes() {
+    let cases = [
+        (BEFORE, AFTER),
+        ("", "a\nb\n"),
+        ("a\nb\n", ""),
+        ("a\na\na\n", "a\nb\na\n"),
+        ("x\ny\nz\n", "z\ny\nx\n"),
+    ];
+    for algorithm in [
+        Algorithm::Histogram,
+        Algorithm::Myers,
+        Algorithm::MyersMinimal,
+        Algorithm::Patience,
+    ] {
+        for (before, after) in cases {
+            let input = InternedInput::new(before, after);
+            let mut applied = Vec::<gix_diff::blob::intern::Token>::new();
+            let mut pos = 0;
+            gix_diff::blob::diff(
+                algorithm,
+                &input,
+                |removed: std::ops::Range<u32>, added: std::ops::Range<u32>| {
+                    assert!(removed.start >= pos, "changes are ordered and don't overlap");
+                    applied.extend(&input.before[pos as usize..removed.start as usize]);
+                    applied.extend(&input.after[added.start as usize..added.end as usize]);
+                    pos = removed.end;
+                },
+            );
+            applied.extend(&input.before[pos as usize..]);
+            assert_eq!(applied, input.after, "{algorithm:?}: {before:?} -> {after:?}");
+        }
+    }
+}
+
+fn unified_diff(algorithm: Algorithm, before: &str, after: &str) -> String {
+    let input = InternedInput::new(
+        gix_diff::blob::sources::byte_lines_with_terminator(before.as_bytes()),
+        gix_diff::blob::sources::byte_lines_with_terminator(after.as_bytes()),
+    );
+    let out = gix_diff::blob::diff(algorithm, &input, UnifiedDiff::new(&input, ContextSize::default()));
+    String::from_utf8(out).expect("valid UTF-8")
+}
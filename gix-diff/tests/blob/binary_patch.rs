@@ -0,0 +1,111 @@
+use gix_diff::blob::binary_patch::{parse, write_to, BinaryPatch, Hunk, Kind};
+use gix_object::bstr::ByteSlice;
+
+#[test]
+fn parse_patch_written_by_git() -> crate::Result {
+    let patch = "GIT binary patch\nliteral 3\nKcmYdfNCE%>hycU@\n\nliteral 0\nHcmV?d00001\n\ndiff --git a/next b/next\n";
+    let (parsed, consumed) = parse(patch.as_bytes())?;
+    assert_eq!(
+        parsed,
+        BinaryPatch {
+            forward: Hunk {
+                kind: Kind::Literal,
+                data: b"a\0b".to_vec()
+            },
+            reverse: Some(Hunk {
+                kind: Kind::Literal,
+                data: Vec::new()
+            })
+        }
+    );
+    assert_eq!(
+        &patch[consumed..],
+        "diff --git a/next b/next\n",
+        "parsing stops after the empty line that ends the last hunk"
+    );
+
+    let patch =
+        "GIT binary patch\ndelta 14\nVcmZ3(w1#Oy3JXJGQu4%%BLE})1vmfz\n\ndelta 14\nVcmZ3(w1#Oy3QJUsUem;kBLF4M1$Y1e\n\n";
+    let (parsed, consumed) = parse(patch.as_bytes())?;
+    assert_eq!(consumed, patch.len());
+    assert_eq!(parsed.forward.kind, Kind::Delta);
+    assert_eq!(parsed.forward.data.len(), 14);
+    assert!(
+        parsed.forward.apply(b"too small").is_err(),
+        "the delta is validated against the size of the base"
+    );
+    Ok(())
+}
+
+#[test]
+fn round_trip_literal() -> crate::Result {
+    let new = b"\0binary\xff".repeat(3);
+    let mut out = Vec::new();
+    write_to(b"", &new, &mut out)?;
+    let (patch, consumed) = parse(&out)?;
+    assert_eq!(consumed, out.len());
+    assert_eq!(
+        patch.forward,
+        Hunk {
+            kind: Kind::Literal,
+            data: new.clone()
+        }
+    );
+    assert_eq!(patch.forward.apply(b"")?, new);
+    assert_eq!(
+        patch.reverse.expect("always written").apply(&new)?,
+        b"",
+        "the reverse hunk produces the old version"
+    );
+    Ok(())
+}
+
+#[test]
+fn round_trip_delta() -> crate::Result {
+    let old: Vec<u8> = (0..20_000u32)
+        .map(|n| (n.wrapping_mul(2_654_435_761) >> 13) as u8)
+        .collect();
+    let mut new = old.clone();
+    new[1000..1004].copy_from_slice(b"\0abc");
+    new.splice(5000..5100, b"inserted".iter().copied());
+    new.extend_from_slice(b"tail");
+
+    let mut out = Vec::new();
+    write_to(&old, &new, &mut out)?;
+    assert!(
+        out.len() < 500,
+        "deltas are used as they are much smaller than the content: {}",
+        out.len()
+    );
+    assert!(out.lines().all(|line| line.len() <= 66), "lines hold up to 52 bytes");
+
+    let (patch, _) = parse(&out)?;
+    assert_eq!(patch.forward.kind, Kind::Delta);
+    assert_eq!(patch.forward.apply(&old)?, new);
+    let reverse = patch.reverse.expect("always written");
+    assert_eq!(reverse.kind, Kind::Delta);
+    assert_eq!(reverse.apply(&new)?, old);
+    Ok(())
+}
+
+#[test]
+fn invalid_patches() {
+    use gix_diff::blob::binary_patch::parse::Error;
+    assert!(matches!(parse(b"literal 0\n"), Err(Error::MissingHeader)));
+    assert!(matches!(
+        parse(b"GIT binary patch\nfoo 0\n"),
+        Err(Error::InvalidHunkHeader { .. })
+    ));
+    assert!(matches!(
+        parse(b"GIT binary patch\nliteral 0\nHcmV?d0000\n\n"),
+        Err(Error::InvalidDataLine { line_number: 3 })
+    ));
+    assert!(matches!(
+        parse(b"GIT binary patch\nliteral 1\nHcmV?d00001\n\n"),
+        Err(Error::SizeMismatch { expected: 1, actual: 0 })
+    ));
+    assert!(matches!(
+        parse(b"GIT binary patch\nliteral 0\nHcmV?d00001\n"),
+        Err(Error::UnexpectedEof)
+    ));
+}
@@ -0,0 +1,96 @@
+use gix_diff::blob::{
+    funcname::{Funcname, BUILTIN},
+    intern::InternedInput,
+    unified_diff::ContextSize,
+    Algorithm, UnifiedDiff,
+};
+use gix_object::bstr::ByteSlice;
+
+#[test]
+fn builtin_patterns_are_valid() {
+    for (name, patterns) in BUILTIN {
+        assert!(
+            Funcname::from_xfuncname(patterns.as_bytes().as_bstr()).is_ok(),
+            "{name} can be compiled"
+        );
+    }
+    assert!(gix_diff::blob::funcname::builtin("rust".into()).is_some());
+    assert!(gix_diff::blob::funcname::builtin("unknown".into()).is_none());
+}
+
+#[test]
+fn default_takes_lines_starting_like_identifiers() {
+    let funcname = Funcname::Default;
+    for (line, expected) in [
+        ("fn_like() {\n", Some("fn_like() {")),
+        ("_private\n", Some("_private")),
+        ("$var \t\r\n", Some("$var")),
+        ("    indented\n", None),
+        ("1 number\n", None),
+        ("\n", None),
+    ] {
+        assert_eq!(
+            funcname.find(line.as_bytes()).map(ByteSlice::as_bstr),
+            expected.map(Into::into)
+        );
+    }
+    let long = format!("{}\n", "x".repeat(100));
+    assert_eq!(
+        funcname.find(long.as_bytes()).map(<[u8]>::len),
+        Some(80),
+        "names are truncated like in git"
+    );
+}
+
+#[test]
+fn patterns_use_the_first_capture_group_and_can_be_negated() -> Result<(), Box<dyn std::error::Error>> {
+    let funcname = Funcname::from_xfuncname("!^skip\n^section ([a-z]+)\n^item.*".into())?;
+    for (line, expected) in [
+        ("section name\r\n", Some("name")),
+        ("item with trailing space  \n", Some("item with trailing space")),
+        ("skip section name\n", None),
+        ("other\n", None),
+    ] {
+        assert_eq!(
+            funcname.find(line.as_bytes()).map(ByteSlice::as_bstr),
+            expected.map(Into::into)
+        );
+    }
+    assert!(Funcname::from_xfuncname("(unclosed".into()).is_err());
+    Ok(())
+}
+
+#[test]
+fn hunk_headers_keep_the_previous_name_until_another_one_is_found() {
+    let before = "first() {\n1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\nsecond() {\n13\n14\n15\n16\n17\n18\n19\n20\n21\n22\n23\n24\n";
+    let after = before
+        .replace("\n6\n", "\nsix\n")
+        .replace("\n17\n", "\nseventeen\n")
+        .replace("\n24\n", "\ntwenty-four\n");
+    let input = InternedInput::new(before.as_bytes(), after.as_bytes());
+    let hunk_headers = |funcname: Option<&Funcname>| {
+        let hunks = gix_diff::blob::diff(
+            Algorithm::Myers,
+            &input,
+            UnifiedDiff::new(&input, ContextSize::symmetrical(1)).funcname(funcname),
+        );
+        hunks
+            .lines()
+            .filter(|line| line.starts_with(b"@@"))
+            .map(|line| line.as_bstr().to_string())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        hunk_headers(Some(&Funcname::Default)),
+        [
+            "@@ -6,3 +6,3 @@ first() {",
+            "@@ -18,3 +18,3 @@ second() {",
+            "@@ -25,2 +25,2 @@ second() {"
+        ]
+    );
+    assert_eq!(
+        hunk_headers(None),
+        ["@@ -6,3 +6,3 @@", "@@ -18,3 +18,3 @@", "@@ -25,2 +25,2 @@"],
+        "function names are only shown if enabled"
+    );
+}
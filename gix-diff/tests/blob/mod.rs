@@ -1,2 +1,9 @@
+mod algorithm;
+mod binary_patch;
+mod funcname;
+mod pickaxe;
 pub(crate) mod pipeline;
 mod platform;
+mod unified_diff;
+mod whitespace;
+mod words;
@@ -0,0 +1,51 @@
+use gix_diff::blob::{pickaxe::Filter, Algorithm};
+
+#[test]
+fn occurrences() {
+    let filter = Filter::Occurrences("fn main".into());
+    assert!(filter.matches(Algorithm::Myers, b"", b"fn main() {}\n"), "added");
+    assert!(filter.matches(Algorithm::Myers, b"fn main() {}\n", b""), "removed");
+    assert!(
+        !filter.matches(Algorithm::Myers, b"fn main() {}\n", b"// comment\nfn main() {}\n"),
+        "changes elsewhere don't matter"
+    );
+    assert!(
+        !filter.matches(Algorithm::Myers, b"a\nfn main() {}\n", b"fn main() {}\na\n"),
+        "moving the string doesn't change the amount of occurrences"
+    );
+    assert!(
+        filter.matches(Algorithm::Myers, b"fn main() {}\n", b"fn main() {}\nfn main() {}\n"),
+        "adding another occurrence is a change"
+    );
+    assert!(
+        !Filter::Occurrences("".into()).matches(Algorithm::Myers, b"", b"a"),
+        "empty strings never match"
+    );
+}
+
+#[test]
+fn occurrences_of_regex() -> crate::Result {
+    let filter = Filter::occurrences_of_regex("fn [a-z]+\\(".into())?;
+    assert!(filter.matches(Algorithm::Myers, b"fn a() {}\n", b"fn a() {}\nfn b() {}\n"));
+    assert!(
+        !filter.matches(Algorithm::Myers, b"fn a() {}\n", b"fn b() {}\n"),
+        "the amount stays the same"
+    );
+    assert!(Filter::occurrences_of_regex("(".into()).is_err());
+    Ok(())
+}
+
+#[test]
+fn changed_lines() -> crate::Result {
+    let filter = Filter::changed_lines("^fn [a-z]+\\(\\)$".into())?;
+    assert!(
+        filter.matches(Algorithm::Myers, b"fn a()\n", b"fn b()\n"),
+        "unlike with occurrences, changed lines match, and line terminators are ignored"
+    );
+    assert!(
+        !filter.matches(Algorithm::Myers, b"fn a()\nx\n", b"fn a()\ny\n"),
+        "unchanged lines don't matter"
+    );
+    assert!(filter.matches(Algorithm::Myers, b"", b"x\nfn a()"));
+    Ok(())
+}
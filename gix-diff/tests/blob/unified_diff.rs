@@ -0,0 +1,226 @@
+use gix_diff::blob::{
+    intern::InternedInput,
+    unified_diff::{submodule_hunks, Content, ContextSize, Header, HunkHeader, Rewrite, Side, Subproject},
+    Algorithm, UnifiedDiff,
+};
+use gix_object::{bstr::ByteSlice, tree::EntryKind};
+
+use crate::hex_to_id;
+
+#[test]
+fn hunk_headers_are_parsed() {
+    assert_eq!(
+        HunkHeader::parse(b"@@ -1,2 +3,4 @@ fn section_heading()"),
+        Some(HunkHeader {
+            before_hunk_start: 1,
+            before_hunk_len: 2,
+            after_hunk_start: 3,
+            after_hunk_len: 4,
+        })
+    );
+    assert_eq!(
+        HunkHeader::parse(b"@@ -5 +0,0 @@"),
+        Some(HunkHeader {
+            before_hunk_start: 5,
+            before_hunk_len: 1,
+            after_hunk_start: 0,
+            after_hunk_len: 0,
+        }),
+        "a missing length is one"
+    );
+    for invalid in [
+        "@@ -1,2 +3,4",
+        "@@ -1,2 3,4 @@",
+        "@@ -a +1 @@",
+        "@@ -1,-2 +1 @@",
+        "-1 +1 @@",
+    ] {
+        assert_eq!(HunkHeader::parse(invalid.as_bytes()), None, "{invalid}");
+    }
+}
+
+#[test]
+fn removed_modified_added() {
+    let a = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+    let b = "2\n3\n4\n5\nsix\n7\n8\n9\n10\neleven\ntwelve\n";
+
+    assert_eq!(
+        unified_diff(a, b, ContextSize::symmetrical(0)),
+        "@@ -1 +0,0 @@\n-1\n@@ -6 +5 @@\n-6\n+six\n@@ -10,0 +10,2 @@\n+eleven\n+twelve\n",
+        "without context, each change is its own hunk, and ranges of length 0 refer to the line before"
+    );
+    assert_eq!(
+        unified_diff(a, b, ContextSize::symmetrical(1)),
+        "@@ -1,2 +1 @@\n-1\n 2\n@@ -5,3 +4,3 @@\n 5\n-6\n+six\n 7\n@@ -10 +9,3 @@\n 10\n+eleven\n+twelve\n"
+    );
+    assert_eq!(
+        unified_diff(a, b, ContextSize::default()),
+        "@@ -1,10 +1,11 @@\n-1\n 2\n 3\n 4\n 5\n-6\n+six\n 7\n 8\n 9\n 10\n+eleven\n+twelve\n",
+        "changes separated by no more than twice the context end up in the same hunk"
+    );
+}
+
+#[test]
+fn hunks_are_separated_like_in_git() {
+    let a: String = (1..=20).map(|n| format!("{n}\n")).collect();
+    let b = a.replace("\n2\n", "\ntwo\n").replace("\n18\n", "\neighteen\n");
+    assert_eq!(
+        unified_diff(&a, &b, ContextSize::default()),
+        "@@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n@@ -15,6 +15,6 @@\n 15\n 16\n 17\n-18\n+eighteen\n 19\n 20\n",
+        "this is exactly what `git diff` produces"
+    );
+}
+
+#[test]
+fn missing_newline_at_end_of_file() {
+    assert_eq!(
+        unified_diff("a\nb\nc", "a\nb\nC", ContextSize::default()),
+        "@@ -1,3 +1,3 @@\n a\n b\n-c\n\\ No newline at end of file\n+C\n\\ No newline at end of file\n"
+    );
+    assert_eq!(
+        unified_diff("a", "a\n", ContextSize::default()),
+        "@@ -1 +1 @@\n-a\n\\ No newline at end of file\n+a\n"
+    );
+}
+
+#[test]
+fn added_and_removed_files() {
+    assert_eq!(
+        unified_diff("", "new\n", ContextSize::default()),
+        "@@ -0,0 +1 @@\n+new\n"
+    );
+    assert_eq!(
+        unified_diff("one\ntwo\n", "", ContextSize::default()),
+        "@@ -1,2 +0,0 @@\n-one\n-two\n"
+    );
+    assert_eq!(
+        unified_diff("same\n", "same\n", ContextSize::default()),
+        "",
+        "no changes means no hunks"
+    );
+}
+
+#[test]
+fn header() -> std::io::Result<()> {
+    let old_id = hex_to_id("0ff3bbb9c8bba2291654cd64067fa417ff54c508");
+    let new_id = hex_to_id("9bebd18e8f81e9d3aff7c2dba0a4c17fcf2e1b30");
+    let side = |path: &'static str, kind: EntryKind, id| Side {
+        path: path.into(),
+        mode: kind.into(),
+        id,
+    };
+    let header = |old, new, rewrite, content| -> std::io::Result<String> {
+        let mut out = Vec::new();
+        Header {
+            old,
+            new,
+            rewrite,
+            hex_len: 7,
+        }
+        .write_to(&mut out, content)?;
+        Ok(String::from_utf8(out).expect("valid UTF-8"))
+    };
+
+    assert_eq!(
+        header(
+            Some(side("long", EntryKind::Blob, &old_id)),
+            Some(side("long", EntryKind::Blob, &new_id)),
+            None,
+            Content::Hunks
+        )?,
+        "diff --git a/long b/long\nindex 0ff3bbb..9bebd18 100644\n--- a/long\n+++ b/long\n"
+    );
+    assert_eq!(
+        header(
+            None,
+            Some(side("added", EntryKind::Blob, &new_id)),
+            None,
+            Content::Hunks
+        )?,
+        "diff --git a/added b/added\nnew file mode 100644\nindex 0000000..9bebd18\n--- /dev/null\n+++ b/added\n"
+    );
+    assert_eq!(
+        header(None, Some(side("binf", EntryKind::Blob, &new_id)), None, Content::Binary)?,
+        "diff --git a/binf b/binf\nnew file mode 100644\nindex 0000000..9bebd18\nBinary files /dev/null and b/binf differ\n"
+    );
+    assert_eq!(
+        header(
+            Some(side("del", EntryKind::BlobExecutable, &old_id)),
+            None,
+            None,
+            Content::Hunks
+        )?,
+        "diff --git a/del b/del\ndeleted file mode 100755\nindex 0ff3bbb..0000000\n--- a/del\n+++ /dev/null\n"
+    );
+    assert_eq!(
+        header(
+            Some(side("mode", EntryKind::Blob, &old_id)),
+            Some(side("mode", EntryKind::BlobExecutable, &old_id)),
+            None,
+            Content::None
+        )?,
+        "diff --git a/mode b/mode\nold mode 100644\nnew mode 100755\n",
+        "without content change, there is no index line"
+    );
+    assert_eq!(
+        header(
+            Some(side("ren", EntryKind::Blob, &old_id)),
+            Some(side("ren2", EntryKind::Blob, &new_id)),
+            Some(Rewrite {
+                copy: false,
+                similarity: 0.79,
+            }),
+            Content::Hunks
+        )?,
+        "diff --git a/ren b/ren2\nsimilarity index 79%\nrename from ren\nrename to ren2\nindex 0ff3bbb..9bebd18 100644\n--- a/ren\n+++ b/ren2\n"
+    );
+    assert_eq!(
+        header(
+            Some(side("src", EntryKind::Blob, &old_id)),
+            Some(side("dst", EntryKind::BlobExecutable, &old_id)),
+            Some(Rewrite {
+                copy: true,
+                similarity: 1.0,
+            }),
+            Content::None
+        )?,
+        "diff --git a/src b/dst\nold mode 100644\nnew mode 100755\nsimilarity index 100%\ncopy from src\ncopy to dst\n"
+    );
+    Ok(())
+}
+
+#[test]
+fn submodules_are_represented_by_their_commit() {
+    let (old, new) = (
+        hex_to_id("0000000000000000000000000000000000000001"),
+        hex_to_id("0000000000000000000000000000000000000002"),
+    );
+    let side = |commit, dirty| Some(Subproject { commit, dirty });
+    assert_eq!(
+        submodule_hunks(side(&old, false), side(&new, false), ContextSize::default()).as_bstr(),
+        "@@ -1 +1 @@\n\
+         -Subproject commit 0000000000000000000000000000000000000001\n\
+         +Subproject commit 0000000000000000000000000000000000000002\n"
+    );
+    assert_eq!(
+        submodule_hunks(side(&old, false), side(&old, true), ContextSize::default()).as_bstr(),
+        "@@ -1 +1 @@\n\
+         -Subproject commit 0000000000000000000000000000000000000001\n\
+         +Subproject commit 0000000000000000000000000000000000000001-dirty\n",
+        "dirty submodules are marked"
+    );
+    assert_eq!(
+        submodule_hunks(None, side(&new, false), ContextSize::default()).as_bstr(),
+        "@@ -0,0 +1 @@\n+Subproject commit 0000000000000000000000000000000000000002\n"
+    );
+    assert!(submodule_hunks(side(&old, false), side(&old, false), ContextSize::default()).is_empty());
+}
+
+fn unified_diff(before: &str, after: &str, context_size: ContextSize) -> String {
+    let input = InternedInput::new(
+        gix_diff::blob::sources::byte_lines_with_terminator(before.as_bytes()),
+        gix_diff::blob::sources::byte_lines_with_terminator(after.as_bytes()),
+    );
+    let out = gix_diff::blob::diff(Algorithm::Myers, &input, UnifiedDiff::new(&input, context_size));
+    String::from_utf8(out).expect("valid UTF-8")
+}
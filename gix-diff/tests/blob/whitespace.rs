@@ -0,0 +1,147 @@
+use gix_diff::blob::{
+    intern::InternedInput, sink::Counter, unified_diff::ContextSize, Algorithm, IgnoreWhitespace, UnifiedDiff,
+};
+
+const BEFORE: &str = "a  b\nc \nd\n\ne\n";
+const AFTER: &str = "a b\nc\nd\ne\n\n";
+
+#[test]
+fn whitespace_changes_can_be_ignored_like_in_git() {
+    assert_eq!(
+        unified_diff(BEFORE, AFTER, IgnoreWhitespace::default()),
+        "@@ -1,5 +1,5 @@\n-a  b\n-c \n+a b\n+c\n d\n-\n e\n+\n"
+    );
+    let expected = "@@ -1,5 +1,5 @@\n a b\n c\n d\n-\n e\n+\n";
+    assert_eq!(
+        unified_diff(
+            BEFORE,
+            AFTER,
+            IgnoreWhitespace {
+                all_space: true,
+                ..Default::default()
+            }
+        ),
+        expected,
+        "context lines are taken from the new version"
+    );
+    assert_eq!(
+        unified_diff(
+            BEFORE,
+            AFTER,
+            IgnoreWhitespace {
+                space_change: true,
+                ..Default::default()
+            }
+        ),
+        expected
+    );
+    assert_eq!(
+        unified_diff(
+            BEFORE,
+            AFTER,
+            IgnoreWhitespace {
+                space_at_eol: true,
+                ..Default::default()
+            }
+        ),
+        "@@ -1,5 +1,5 @@\n-a  b\n+a b\n c\n d\n-\n e\n+\n"
+    );
+}
+
+#[test]
+fn space_change_still_sees_added_or_removed_whitespace() {
+    let space_change = IgnoreWhitespace {
+        space_change: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        unified_diff("a\tb\n", "ab\n", space_change),
+        "@@ -1 +1 @@\n-a\tb\n+ab\n"
+    );
+    assert_eq!(
+        unified_diff(
+            "a\tb\n",
+            "ab\n",
+            IgnoreWhitespace {
+                all_space: true,
+                ..Default::default()
+            }
+        ),
+        ""
+    );
+    assert_eq!(
+        unified_diff("a\n", "a", space_change),
+        "",
+        "line terminators are whitespace at the end of the line"
+    );
+}
+
+#[test]
+fn blank_lines_are_only_ignored_if_not_shown_as_context_anyway() {
+    let blank_lines = IgnoreWhitespace {
+        blank_lines: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        unified_diff(BEFORE, AFTER, blank_lines),
+        unified_diff(BEFORE, AFTER, IgnoreWhitespace::default()),
+        "the blank lines are close enough to the other changes to be shown"
+    );
+    assert_eq!(
+        unified_diff(
+            BEFORE,
+            AFTER,
+            IgnoreWhitespace {
+                all_space: true,
+                blank_lines: true,
+                ..Default::default()
+            }
+        ),
+        "",
+        "only blank lines remain, which are ignored"
+    );
+
+    let before: String = (1..=12).map(|n| format!("{n}\n")).collect();
+    let after = before.replace("\n2\n", "\ntwo\n").replace("\n10\n", "\n10\n\n");
+    assert_eq!(
+        unified_diff(&before, &after, blank_lines),
+        "@@ -1,5 +1,5 @@\n 1\n-2\n+two\n 3\n 4\n 5\n",
+        "blank lines far enough from other changes are dropped"
+    );
+}
+
+#[test]
+fn blank_lines_are_not_counted() {
+    let input = InternedInput::new(
+        gix_diff::blob::sources::byte_lines_with_terminator(BEFORE.as_bytes()),
+        gix_diff::blob::sources::byte_lines_with_terminator(AFTER.as_bytes()),
+    );
+    let counter = gix_diff::blob::diff_ignoring_whitespace(
+        Algorithm::Myers,
+        &input,
+        IgnoreWhitespace {
+            space_change: true,
+            blank_lines: true,
+            ..Default::default()
+        },
+        Counter::default(),
+    );
+    assert_eq!((counter.removals, counter.insertions), (0, 0));
+}
+
+fn unified_diff(before: &str, after: &str, ignore: IgnoreWhitespace) -> String {
+    let input = InternedInput::new(
+        gix_diff::blob::sources::byte_lines_with_terminator(before.as_bytes()),
+        gix_diff::blob::sources::byte_lines_with_terminator(after.as_bytes()),
+    );
+    let out = gix_diff::blob::diff_ignoring_whitespace(
+        Algorithm::Myers,
+        &input,
+        IgnoreWhitespace {
+            blank_lines: false,
+            ..ignore
+        },
+        UnifiedDiff::new(&input, ContextSize::default()).ignore_blank_lines(ignore.blank_lines),
+    );
+    String::from_utf8(out).expect("valid UTF-8")
+}
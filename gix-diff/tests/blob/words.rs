@@ -0,0 +1,98 @@
+use gix_diff::blob::{
+    words::{diff, Change, Tokenizer},
+    Algorithm,
+};
+
+#[test]
+fn whitespace_separated_words() {
+    let before = "the quick  brown fox\njumps";
+    let after = "the slow brown\tfox\njumps high";
+    assert_eq!(
+        changes(before, after, &Tokenizer::Whitespace),
+        vec![(Some("quick"), Some("slow")), (None, Some("high"))],
+        "changes in whitespace alone are ignored, just like in `git diff --word-diff`"
+    );
+    let changes = diff(
+        Algorithm::Myers,
+        before.as_bytes(),
+        after.as_bytes(),
+        &Tokenizer::Whitespace,
+    );
+    assert_eq!(
+        changes,
+        vec![
+            Change {
+                before: 4..9,
+                after: 4..8
+            },
+            Change {
+                before: 26..26,
+                after: 25..29
+            }
+        ],
+        "insertions are positioned right after the preceding word"
+    );
+}
+
+#[test]
+fn regex_words() -> Result<(), gix_diff::blob::words::tokenizer::Error> {
+    let tokenizer = Tokenizer::from_word_regex("[a-z]+|[^[:space:]]".into())?;
+    assert_eq!(
+        changes("foo(bar, baz);", "foo(bar,qux);", &tokenizer),
+        vec![(Some("baz"), Some("qux"))]
+    );
+    assert_eq!(
+        changes("a=b", "a = b", &tokenizer),
+        vec![],
+        "everything that doesn't match is ignored"
+    );
+    assert!(matches!(
+        Tokenizer::from_word_regex("(".into()),
+        Err(gix_diff::blob::words::tokenizer::Error::Regex(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn characters() {
+    assert_eq!(
+        changes("colour", "color", &Tokenizer::Characters),
+        vec![(Some("u"), None)]
+    );
+    assert_eq!(
+        diff(
+            Algorithm::Myers,
+            "ä".as_bytes(),
+            "äö".as_bytes(),
+            &Tokenizer::Characters
+        ),
+        vec![Change {
+            before: 2..2,
+            after: 2..4
+        }],
+        "ranges are in bytes and never split characters"
+    );
+}
+
+#[test]
+fn empty_inputs() {
+    assert_eq!(changes("", "", &Tokenizer::Whitespace), vec![]);
+    assert_eq!(
+        diff(Algorithm::Myers, b"", b"  new", &Tokenizer::Whitespace),
+        vec![Change {
+            before: 0..0,
+            after: 2..5
+        }]
+    );
+    assert_eq!(changes("old", "\n", &Tokenizer::Whitespace), vec![(Some("old"), None)]);
+}
+
+type Changes<'a> = Vec<(Option<&'a str>, Option<&'a str>)>;
+
+fn changes<'a>(before: &'a str, after: &'a str, tokenizer: &Tokenizer) -> Changes<'a> {
+    let non_empty = |s: &'a str| (!s.is_empty()).then_some(s);
+    diff(Algorithm::Histogram, before.as_bytes(), after.as_bytes(), tokenizer)
+        .into_iter()
+        .map(|change| (non_empty(&before[change.before]), non_empty(&after[change.after])))
+        .collect()
+}
@@ -5,7 +5,10 @@ fn hex_to_id(hex: &str) -> gix_hash::ObjectId {
 }
 
 mod blob;
+mod patch_id;
+mod range_diff;
 mod rewrites;
+mod stat;
 mod tree;
 
 mod util {
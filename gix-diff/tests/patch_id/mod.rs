@@ -0,0 +1,97 @@
+use gix_diff::patch_id::from_patch;
+
+use crate::hex_to_id;
+
+const MODIFICATION: &str = "diff --git a/a.txt b/a.txt
+index 3b18e51..bd5a4ba 100644
+--- a/a.txt
++++ b/a.txt
+@@ -1,3 +1,3 @@
+ one
+-two
++2
+ three
+";
+
+const ADDITION: &str = "diff --git a/b.txt b/b.txt
+new file mode 100644
+index 0000000..d00491f
+--- /dev/null
++++ b/b.txt
+@@ -0,0 +1 @@
++1
+\\ No newline at end of file
+";
+
+fn patch_id(patch: &str) -> Option<gix_hash::ObjectId> {
+    from_patch(patch.as_bytes(), gix_hash::Kind::Sha1)
+}
+
+#[test]
+fn single_file() {
+    let expected = Some(hex_to_id("44bd323efaee6ccde51dfbcd35d9566acfb0b616"));
+    assert_eq!(
+        patch_id(MODIFICATION),
+        expected,
+        "the value is produced by `git patch-id --stable`"
+    );
+    assert_eq!(
+        patch_id(
+            "commit message is ignored
+diff --git a/a.txt b/a.txt
+index 1111111..2222222 100644
+--- a/a.txt
++++ b/a.txt
+@@ -10,3 +10,3 @@ fn section
+ one
+-two
++ 2
+ three
+"
+        ),
+        expected,
+        "object ids, line numbers, hunk sections and whitespace don't matter"
+    );
+}
+
+#[test]
+fn multiple_files_in_any_order() {
+    let expected = Some(hex_to_id("9b77dc1a49b36f0f5bd2104659c468157b4c004a"));
+    assert_eq!(patch_id(&format!("{MODIFICATION}{ADDITION}")), expected);
+    assert_eq!(
+        patch_id(&format!("{ADDITION}{MODIFICATION}")),
+        expected,
+        "each file is hashed on its own, which is what makes the id stable"
+    );
+}
+
+#[test]
+fn binary_files_and_mode_changes() {
+    let patch = format!(
+        "diff --git a/bin b/bin
+new file mode 100644
+index 0000000000000000000000000000000000000000..f76dd238ade08917e6712764a16a22005a50573d
+GIT binary patch
+literal 1
+IcmZPo000310RR91
+
+literal 0
+HcmV?d00001
+
+diff --git a/x b/x
+old mode 100644
+new mode 100755
+{MODIFICATION}"
+    );
+    assert_eq!(
+        patch_id(&patch),
+        Some(hex_to_id("66d8ed44ddd298d716cf8abd7b6b20d443f49feb")),
+        "binary files are identified by their object ids"
+    );
+}
+
+#[test]
+fn no_changes() {
+    assert_eq!(patch_id(""), None);
+    assert_eq!(patch_id("no diff here\n"), None);
+}
@@ -0,0 +1,66 @@
+use gix_diff::range_diff::{pair_patches, Options, Pair};
+
+fn patch(subject: &str, lines: &[&str]) -> String {
+    let mut out = format!("    {subject}\n\ndiff --git a/f b/f\n--- a/f\n+++ b/f\n@@\n");
+    for line in lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out
+}
+
+#[test]
+fn equal_series_are_paired_in_order() {
+    let series = [patch("one", &["+1"]), patch("two", &["+2"]), patch("one", &["+1"])];
+    assert_eq!(
+        pair_patches(&series, &series, Options::default()),
+        [
+            Pair::Unchanged { old: 0, new: 0 },
+            Pair::Unchanged { old: 1, new: 1 },
+            Pair::Unchanged { old: 2, new: 2 },
+        ],
+        "equal patches are paired in order"
+    );
+}
+
+#[test]
+fn changed_added_and_removed_patches() {
+    let old = [
+        patch("one", &["+1", "+1", "+1", "+1"]),
+        patch("removed", &["-x", "-y", "-z", "+r", "+s", "+t"]),
+        patch("two", &["+2", "+2", "+2", "+2"]),
+    ];
+    let new = [
+        patch("added", &["+a", "+b", "+c", "+d", "+e"]),
+        patch("two", &["+2", "+2", "+2", "+two"]),
+        patch("one", &["+1", "+1", "+1", "+1"]),
+    ];
+    assert_eq!(
+        pair_patches(&old, &new, Options::default()),
+        [
+            Pair::Added { new: 0 },
+            Pair::Changed {
+                old: 2,
+                new: 1,
+                interdiff: b"@@ -7,4 +7,4 @@\n +2\n +2\n +2\n-+2\n++two\n".to_vec()
+            },
+            Pair::Unchanged { old: 0, new: 2 },
+            Pair::Removed { old: 1 },
+        ],
+        "similar patches are paired, and removed patches are shown once all old patches before them were shown"
+    );
+
+    let pairs = pair_patches(
+        &old,
+        &new,
+        Options {
+            creation_factor: 0,
+            ..Default::default()
+        },
+    );
+    assert!(
+        pairs.contains(&Pair::Removed { old: 2 }) && pairs.contains(&Pair::Added { new: 1 }),
+        "without creation cost, changed patches aren't paired: {pairs:?}"
+    );
+    assert!(pairs.contains(&Pair::Unchanged { old: 0, new: 2 }));
+}
@@ -0,0 +1,94 @@
+use gix_diff::stat::{File, Lines, Stats, Summary};
+
+fn renamed(source: &str, location: &str) -> File {
+    File {
+        source_location: Some(source.into()),
+        location: location.into(),
+        lines: Lines::Text {
+            insertions: 0,
+            removals: 0,
+        },
+    }
+}
+
+#[test]
+fn display_name_of_renames_factors_out_common_directories() {
+    for (source, location, expected) in [
+        ("a", "b", "a => b"),
+        ("dir/a.txt", "dir/b.txt", "dir/{a.txt => b.txt}"),
+        ("a/x/file", "b/x/file", "{a => b}/x/file"),
+        ("a/b/c", "a/c", "a/{b => }/c"),
+        ("a/c", "a/b/c", "a/{ => b}/c"),
+        ("src/old/deep/file", "src/new/deep/file", "src/{old => new}/deep/file"),
+    ] {
+        assert_eq!(renamed(source, location).display_name(), expected);
+    }
+}
+
+#[test]
+fn summary_mentions_zero_counts_only_if_both_are_zero() {
+    for (files_changed, insertions, removals, expected) in [
+        (0, 0, 0, "0 files changed"),
+        (1, 0, 0, "1 file changed, 0 insertions(+), 0 deletions(-)"),
+        (1, 1, 0, "1 file changed, 1 insertion(+)"),
+        (2, 0, 1, "2 files changed, 1 deletion(-)"),
+        (3, 2, 5, "3 files changed, 2 insertions(+), 5 deletions(-)"),
+    ] {
+        let summary = Summary {
+            files_changed,
+            insertions,
+            removals,
+        };
+        assert_eq!(summary.to_string(), expected);
+    }
+}
+
+#[test]
+fn binary_files_count_as_changed_files_without_lines() {
+    let stats = Stats {
+        files: vec![
+            File {
+                source_location: None,
+                location: "image".into(),
+                lines: Lines::Binary {
+                    old_size: 0,
+                    new_size: 1024,
+                },
+            },
+            File {
+                source_location: None,
+                location: "text".into(),
+                lines: Lines::Text {
+                    insertions: 3,
+                    removals: 1,
+                },
+            },
+        ],
+    };
+    assert_eq!(
+        stats.summary(),
+        Summary {
+            files_changed: 2,
+            insertions: 3,
+            removals: 1
+        }
+    );
+
+    let mut out = Vec::new();
+    stats.write_numstat(&mut out).unwrap();
+    assert_eq!(std::str::from_utf8(&out).unwrap(), "-\t-\timage\n3\t1\ttext\n");
+
+    let mut out = Vec::new();
+    stats.write_stat(&mut out, Default::default()).unwrap();
+    assert_eq!(
+        std::str::from_utf8(&out).unwrap(),
+        " image | Bin 0 -> 1024 bytes\n text  |   4 +++-\n 2 files changed, 3 insertions(+), 1 deletion(-)\n"
+    );
+}
+
+#[test]
+fn nothing_is_written_without_files() {
+    let mut out = Vec::new();
+    Stats::default().write_stat(&mut out, Default::default()).unwrap();
+    assert!(out.is_empty());
+}
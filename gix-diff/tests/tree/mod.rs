@@ -702,5 +702,45 @@ mod changes {
             );
             Ok(())
         }
+
+        #[test]
+        fn mode_change_with_same_content() -> crate::Result {
+            let id = hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+            let tree = |kind: EntryKind| -> crate::Result<Vec<u8>> {
+                let mut buf = Vec::new();
+                gix_object::WriteTo::write_to(
+                    &gix_object::Tree {
+                        entries: vec![gix_object::tree::Entry {
+                            mode: kind.into(),
+                            filename: "f".into(),
+                            oid: id,
+                        }],
+                    },
+                    &mut buf,
+                )?;
+                Ok(buf)
+            };
+            let (lhs, rhs) = (tree(EntryKind::Blob)?, tree(EntryKind::BlobExecutable)?);
+
+            let mut recorder = gix_diff::tree::Recorder::default();
+            gix_diff::tree::Changes::from(TreeRefIter::from_bytes(&lhs)).needed_to_obtain(
+                TreeRefIter::from_bytes(&rhs),
+                gix_diff::tree::State::default(),
+                gix_object::find::Never,
+                &mut recorder,
+            )?;
+            assert_eq!(
+                recorder.records,
+                vec![Modification {
+                    previous_entry_mode: EntryKind::Blob.into(),
+                    previous_oid: id,
+                    entry_mode: EntryKind::BlobExecutable.into(),
+                    oid: id,
+                    path: "f".into()
+                }],
+                "changes to the executable bit are modifications even if the content stays the same"
+            );
+            Ok(())
+        }
     }
 }
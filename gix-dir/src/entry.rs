@@ -22,6 +22,41 @@ pub enum Property {
     TrackedExcluded,
 }
 
+/// Information about an entry on disk, as obtained with `lstat` while the entry was classified.
+///
+/// It's only available if [`Options::emit_metadata`](crate::walk::Options::emit_metadata) is set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Metadata {
+    /// The size of the entry in bytes, as reported by the filesystem.
+    ///
+    /// For symlinks, this is the length of the path they point to, and for directories its meaning depends on the filesystem.
+    pub size: u64,
+    /// The time at which the entry was last modified, or `None` if this isn't supported on the current platform.
+    pub modified: Option<std::time::SystemTime>,
+    /// The id of the user owning the entry, or `None` on platforms without the concept, like Windows.
+    pub uid: Option<u32>,
+    /// The id of the group owning the entry, or `None` on platforms without the concept, like Windows.
+    pub gid: Option<u32>,
+}
+
+impl From<&std::fs::Metadata> for Metadata {
+    fn from(meta: &std::fs::Metadata) -> Self {
+        #[cfg(unix)]
+        let (uid, gid) = {
+            use std::os::unix::fs::MetadataExt;
+            (Some(meta.uid()), Some(meta.gid()))
+        };
+        #[cfg(not(unix))]
+        let (uid, gid) = (None, None);
+        Metadata {
+            size: meta.len(),
+            modified: meta.modified().ok(),
+            uid,
+            gid,
+        }
+    }
+}
+
 /// The kind of the entry, seated in their kinds available on disk.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd)]
 pub enum Kind {
@@ -116,6 +151,7 @@ impl EntryRef<'_> {
             disk_kind: self.disk_kind,
             index_kind: self.index_kind,
             pathspec_match: self.pathspec_match,
+            metadata: self.metadata,
         }
     }
 
@@ -128,6 +164,7 @@ impl EntryRef<'_> {
             disk_kind: self.disk_kind,
             index_kind: self.index_kind,
             pathspec_match: self.pathspec_match,
+            metadata: self.metadata,
         }
     }
 }
@@ -143,6 +180,7 @@ impl Entry {
             disk_kind: self.disk_kind,
             index_kind: self.index_kind,
             pathspec_match: self.pathspec_match,
+            metadata: self.metadata,
         }
     }
 }
@@ -39,6 +39,9 @@ pub struct EntryRef<'a> {
     /// Determines how the pathspec matched.
     /// Note that it can also be `Some(PathspecMatch::Excluded)` if a negative pathspec matched.
     pub pathspec_match: Option<entry::PathspecMatch>,
+    /// The size, modification time and ownership of the entry on disk, which is only set if
+    /// [`Options::emit_metadata`](walk::Options::emit_metadata) is `true` and the `disk_kind` is known.
+    pub metadata: Option<entry::Metadata>,
 }
 
 /// Just like [`EntryRef`], but with all fields owned (and thus without a lifetime to consider).
@@ -56,6 +59,8 @@ pub struct Entry {
     pub index_kind: Option<entry::Kind>,
     /// Indicate how the pathspec matches the entry. See more in [`EntryRef::pathspec_match`].
     pub pathspec_match: Option<entry::PathspecMatch>,
+    /// See [`EntryRef::metadata`] for details.
+    pub metadata: Option<entry::Metadata>,
 }
 
 ///
@@ -72,6 +72,8 @@ pub struct Outcome {
     pub index_kind: Option<entry::Kind>,
     /// If a pathspec matched, this is how it matched. Maybe `None` if computation didn't see the need to evaluate it.
     pub pathspec_match: Option<PathspecMatch>,
+    /// The metadata of the entry on disk, if it was requested and available.
+    pub metadata: Option<entry::Metadata>,
 }
 
 impl Outcome {
@@ -95,6 +97,7 @@ impl From<&Entry> for Outcome {
             disk_kind: e.disk_kind,
             index_kind: e.index_kind,
             pathspec_match: e.pathspec_match,
+            metadata: e.metadata,
         }
     }
 }
@@ -108,6 +111,7 @@ impl<'a> EntryRef<'a> {
             disk_kind: info.disk_kind,
             index_kind: info.index_kind,
             pathspec_match: info.pathspec_match,
+            metadata: info.metadata,
         }
     }
 }
@@ -122,7 +126,33 @@ impl<'a> EntryRef<'a> {
 ///
 /// Returns `(status, file_kind, pathspec_matches_how)` to identify the `status` on disk, along with a classification `file_kind`,
 /// and if `file_kind` is not a directory, the way the pathspec matched with `pathspec_matches_how`.
+///
+/// If [`Options::emit_metadata`] is set, the metadata of entries whose kind on disk is known is obtained as well.
 pub fn path(
+    path: &mut PathBuf,
+    rela_path: &mut BString,
+    filename_start_idx: usize,
+    disk_kind: Option<entry::Kind>,
+    on_demand_disk_kind: impl FnOnce() -> Option<entry::Kind>,
+    opts: Options,
+    ctx: &mut Context<'_>,
+) -> Result<Outcome, Error> {
+    let mut out = classify_path(
+        path,
+        rela_path,
+        filename_start_idx,
+        disk_kind,
+        on_demand_disk_kind,
+        opts,
+        ctx,
+    )?;
+    if opts.emit_metadata && out.disk_kind.is_some() {
+        out.metadata = path.symlink_metadata().ok().as_ref().map(Into::into);
+    }
+    Ok(out)
+}
+
+fn classify_path(
     path: &mut PathBuf,
     rela_path: &mut BString,
     filename_start_idx: usize,
@@ -145,6 +175,7 @@ pub fn path(
         disk_kind,
         index_kind: None,
         pathspec_match: None,
+        metadata: None,
     };
     if is_eq(rela_path[filename_start_idx..].as_bstr(), ".git", ignore_case) {
         out.pathspec_match = ctx
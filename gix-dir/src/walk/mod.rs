@@ -192,6 +192,11 @@ pub struct Options {
     ///
     /// In other words, for Git compatibility this flag should be `false`, the default, for `git2` compatibility it should be `true`.
     pub symlinks_to_directories_are_ignored_like_directories: bool,
+    /// If `true`, the [metadata](crate::entry::Metadata) of each entry whose kind on disk is known is obtained during
+    /// classification and emitted along with it, so consumers don't have to query it again.
+    ///
+    /// Note that this costs an additional `lstat` call per entry.
+    pub emit_metadata: bool,
 }
 
 /// All information that is required to perform a dirwalk, and classify paths properly.
@@ -257,12 +262,25 @@ pub struct Context<'a> {
     ///
     /// If the `traversal_root` is not in the `worktree_root` passed to [walk()](crate::walk()).
     pub explicit_traversal_root: Option<&'a std::path::Path>,
+    /// If not `None`, the untracked cache is used to avoid reading directories whose untracked entries are known and
+    /// provably unchanged, and it's updated with the entries of all directories that had to be read.
+    ///
+    /// It's only used if nothing but untracked entries are emitted with [`EmissionMode::Matching`], without pathspecs and
+    /// with `excludes`, and only for directories that contain tracked files.
+    /// As it's typically stored in the `index`, it should be [taken out](gix_index::State::set_untracked()) for the
+    /// duration of the walk and be returned afterwards.
+    ///
+    /// Note that the caller is responsible for [validating global excludes](gix_index::extension::UntrackedCache::set_global_excludes())
+    /// beforehand.
+    pub untracked_cache: Option<&'a mut gix_index::extension::UntrackedCache>,
 }
 
 /// Additional information collected as outcome of [`walk()`](function::walk()).
 #[derive(Default, Debug, Clone, Ord, PartialOrd, Eq, PartialEq)]
 pub struct Outcome {
     /// The amount of calls to read the directory contents.
+    ///
+    /// Directories whose entries are obtained from the [untracked cache](Context::untracked_cache) aren't counted.
     pub read_dir_calls: u32,
     /// The amount of returned entries provided to the callback. This number can be lower than `seen_entries`.
     pub returned_entries: usize,
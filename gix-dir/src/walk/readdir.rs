@@ -27,77 +27,84 @@ pub(super) fn recursive(
     if ctx.should_interrupt.map_or(false, |flag| flag.load(Ordering::Relaxed)) {
         return Err(Error::Interrupted);
     }
-    out.read_dir_calls += 1;
-    let entries = gix_fs::read_dir(current, opts.precompose_unicode).map_err(|err| Error::ReadDir {
-        path: current.to_owned(),
-        source: err,
-    })?;
+    let cache = untracked_cache::for_directory(current, current_bstr.as_bstr(), current_info, opts, ctx);
+    let cached_names = cache.as_ref().and_then(|cache| {
+        ctx.untracked_cache
+            .as_ref()?
+            .valid_directory(current_bstr.as_bstr(), &cache.stat, cache.exclude_file_oid)
+            .map(|dir| untracked_cache::names_to_visit(dir, ctx.untracked_cache.as_ref().expect("present")))
+    });
 
     let mut num_entries = 0;
     let mark = state.mark(may_collapse);
     let mut prevent_collapse = false;
-    for entry in entries {
-        let entry = entry.map_err(|err| Error::DirEntry {
-            parent_directory: current.to_owned(),
+    let mut recorded = cache.is_some().then(untracked_cache::Record::default);
+    if let Some(names) = cached_names {
+        for name in names {
+            let file_name = gix_path::from_bstr(name.as_bstr()).into_owned();
+            let disk_kind = match current.join(&file_name).symlink_metadata() {
+                Ok(meta) => entry::Kind::from(meta.file_type()),
+                Err(_) => continue,
+            };
+            num_entries += 1;
+            if let Some(action) = visit_entry(
+                file_name.as_os_str(),
+                || Some(disk_kind),
+                current,
+                current_bstr,
+                ctx,
+                opts,
+                delegate,
+                out,
+                state,
+                &mut prevent_collapse,
+                None,
+            )? {
+                return Ok((action, prevent_collapse));
+            }
+        }
+    } else {
+        out.read_dir_calls += 1;
+        let entries = gix_fs::read_dir(current, opts.precompose_unicode).map_err(|err| Error::ReadDir {
+            path: current.to_owned(),
             source: err,
         })?;
-        // Important to count right away, otherwise the directory could be seen as empty even though it's not.
-        // That is, this should be independent of the kind.
-        num_entries += 1;
 
-        let prev_len = current_bstr.len();
-        if prev_len != 0 {
-            current_bstr.push(b'/');
-        }
-        let file_name = entry.file_name();
-        current_bstr.extend_from_slice(
-            gix_path::try_os_str_into_bstr(Cow::Borrowed(file_name.as_ref()))
-                .expect("no illformed UTF-8")
-                .as_ref(),
-        );
-        current.push(file_name);
-
-        let info = classify::path(
-            current,
-            current_bstr,
-            if prev_len == 0 { 0 } else { prev_len + 1 },
-            None,
-            || entry.file_type().ok().map(Into::into),
-            opts,
-            ctx,
-        )?;
-
-        if can_recurse(
-            current_bstr.as_bstr(),
-            info,
-            opts.for_deletion,
-            false, /* is root */
-            delegate,
-        ) {
-            let subdir_may_collapse = state.may_collapse(current);
-            let (action, subdir_prevent_collapse) = recursive(
-                subdir_may_collapse,
+        for entry in entries {
+            let entry = entry.map_err(|err| Error::DirEntry {
+                parent_directory: current.to_owned(),
+                source: err,
+            })?;
+            // Important to count right away, otherwise the directory could be seen as empty even though it's not.
+            // That is, this should be independent of the kind.
+            num_entries += 1;
+            if let Some(action) = visit_entry(
+                entry.file_name().as_ref(),
+                || entry.file_type().ok().map(Into::into),
                 current,
                 current_bstr,
-                info,
                 ctx,
                 opts,
                 delegate,
                 out,
                 state,
-            )?;
-            prevent_collapse |= subdir_prevent_collapse;
-            if action != Action::Continue {
+                &mut prevent_collapse,
+                recorded.as_mut(),
+            )? {
                 return Ok((action, prevent_collapse));
             }
-        } else if !state.held_for_directory_collapse(current_bstr.as_bstr(), info, &opts) {
-            let action = emit_entry(Cow::Borrowed(current_bstr.as_bstr()), info, None, opts, out, delegate);
-            if action != Action::Continue {
-                return Ok((action, prevent_collapse));
+        }
+        if let Some((cache, recorded)) = cache.zip(recorded) {
+            if let Some(untracked_cache) = ctx.untracked_cache.as_mut() {
+                untracked_cache.set_directory(
+                    current_bstr.as_bstr(),
+                    cache.stat,
+                    cache.exclude_file_oid,
+                    recorded.untracked_entries,
+                    recorded.sub_directories.iter().map(|name| name.as_bstr()),
+                );
             }
         }
-        current_bstr.truncate(prev_len);
-        current.pop();
     }
 
     let res = mark.reduce_held_entries(
@@ -115,6 +122,83 @@ pub(super) fn recursive(
     Ok((res, prevent_collapse))
 }
 
+/// Classify the entry named `file_name` in `current` and recurse into it or emit it, returning `Some(action)`
+/// if the traversal should stop. If `record` is set, untracked entries and traversed tracked directories are
+/// recorded in it for use in the untracked cache.
+#[allow(clippy::too_many_arguments)]
+fn visit_entry(
+    file_name: &std::ffi::OsStr,
+    file_type: impl FnOnce() -> Option<entry::Kind>,
+    current: &mut PathBuf,
+    current_bstr: &mut BString,
+    ctx: &mut Context<'_>,
+    opts: Options,
+    delegate: &mut dyn Delegate,
+    out: &mut Outcome,
+    state: &mut State,
+    prevent_collapse: &mut bool,
+    record: Option<&mut untracked_cache::Record>,
+) -> Result<Option<Action>, Error> {
+    let prev_len = current_bstr.len();
+    if prev_len != 0 {
+        current_bstr.push(b'/');
+    }
+    let name_start = current_bstr.len();
+    current_bstr.extend_from_slice(
+        gix_path::try_os_str_into_bstr(Cow::Borrowed(file_name))
+            .expect("no illformed UTF-8")
+            .as_ref(),
+    );
+    current.push(file_name);
+
+    let info = classify::path(
+        current,
+        current_bstr,
+        if prev_len == 0 { 0 } else { prev_len + 1 },
+        None,
+        file_type,
+        opts,
+        ctx,
+    )?;
+
+    let is_recursed = can_recurse(
+        current_bstr.as_bstr(),
+        info,
+        opts.for_deletion,
+        false, /* is root */
+        delegate,
+    );
+    if let Some(record) = record {
+        record.push(current_bstr[name_start..].as_bstr(), info, is_recursed);
+    }
+    if is_recursed {
+        let subdir_may_collapse = state.may_collapse(current);
+        let (action, subdir_prevent_collapse) = recursive(
+            subdir_may_collapse,
+            current,
+            current_bstr,
+            info,
+            ctx,
+            opts,
+            delegate,
+            out,
+            state,
+        )?;
+        *prevent_collapse |= subdir_prevent_collapse;
+        if action != Action::Continue {
+            return Ok(Some(action));
+        }
+    } else if !state.held_for_directory_collapse(current_bstr.as_bstr(), info, &opts) {
+        let action = emit_entry(Cow::Borrowed(current_bstr.as_bstr()), info, None, opts, out, delegate);
+        if action != Action::Continue {
+            return Ok(Some(action));
+        }
+    }
+    current_bstr.truncate(prev_len);
+    current.pop();
+    Ok(None)
+}
+
 pub(super) struct State {
     /// The entries to hold back until it's clear what to do with them.
     pub on_hold: Vec<Entry>,
@@ -399,3 +483,106 @@ impl Options {
         self.emit_ignored == Some(CollapseDirectory) || self.emit_untracked == CollapseDirectory
     }
 }
+
+mod untracked_cache {
+    use bstr::{BStr, BString, ByteSlice};
+    use std::path::Path;
+
+    use crate::entry;
+    use crate::walk::{classify, Context, EmissionMode, Options};
+
+    /// Information about a directory that can be validated against the untracked cache, or recorded in it.
+    pub(super) struct Directory {
+        pub stat: gix_index::entry::Stat,
+        pub exclude_file_oid: Option<gix_index::hash::ObjectId>,
+    }
+
+    /// Return information about the directory at `dir` if the untracked cache can be used for it, which is only the case
+    /// if nothing but untracked files are emitted without further simplification, and if `dir` is tracked or the
+    /// worktree root, as identified by the empty `rela_dir`.
+    ///
+    /// Pathspecs and disabled excludes would make the cached entries incomplete, so they disable the cache as well.
+    pub(super) fn for_directory(
+        dir: &Path,
+        rela_dir: &BStr,
+        dir_info: classify::Outcome,
+        opts: Options,
+        ctx: &mut Context<'_>,
+    ) -> Option<Directory> {
+        let cache = ctx.untracked_cache.as_ref()?;
+        let is_applicable = (dir_info.status == entry::Status::Tracked || rela_dir.is_empty())
+            && ctx.excludes.is_some()
+            && ctx.pathspec.patterns().len() == 0
+            && opts.emit_untracked == EmissionMode::Matching
+            && opts.emit_ignored.is_none()
+            && !opts.emit_tracked
+            && !opts.emit_pruned
+            && opts.for_deletion.is_none();
+        if !is_applicable {
+            return None;
+        }
+        let stat = gix_index::fs::Metadata::from_path_no_follow(dir)
+            .ok()
+            .and_then(|meta| gix_index::entry::Stat::from_fs(&meta).ok())?;
+        if is_racy(&stat, ctx.index) {
+            return None;
+        }
+        let exclude_file = dir.join(gix_path::from_bstr(cache.exclude_filename_per_dir()));
+        let exclude_file_oid = match std::fs::read(exclude_file) {
+            Ok(data) => Some(gix_object::compute_hash(
+                ctx.index.object_hash(),
+                gix_object::Kind::Blob,
+                &data,
+            )),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => None,
+            Err(_) => return None,
+        };
+        Some(Directory { stat, exclude_file_oid })
+    }
+
+    /// A directory that was modified at or after the time the index was written could still change within the
+    /// same timestamp, so it's not trusted.
+    fn is_racy(stat: &gix_index::entry::Stat, index: &gix_index::State) -> bool {
+        let timestamp = index.timestamp();
+        i64::from(stat.mtime.secs) >= timestamp.unix_seconds()
+    }
+
+    /// The names of all entries in `dir` that have to be looked at instead of reading the directory.
+    pub(super) fn names_to_visit(
+        dir: &gix_index::extension::untracked_cache::Directory,
+        cache: &gix_index::extension::UntrackedCache,
+    ) -> Vec<BString> {
+        dir.untracked_entries
+            .iter()
+            .map(|name| name.strip_suffix(b"/").unwrap_or(name).as_bstr().to_owned())
+            .chain(
+                dir.sub_directories
+                    .iter()
+                    .map(|idx| cache.directories()[*idx].name.clone()),
+            )
+            .collect()
+    }
+
+    /// The entries of a directory that are recorded while reading it.
+    #[derive(Default)]
+    pub(super) struct Record {
+        pub untracked_entries: Vec<BString>,
+        pub sub_directories: Vec<BString>,
+    }
+
+    impl Record {
+        pub(super) fn push(&mut self, name: &BStr, info: classify::Outcome, is_recursed: bool) {
+            match info.status {
+                entry::Status::Untracked => {
+                    let mut name = name.to_owned();
+                    if matches!(info.disk_kind, Some(kind) if kind.is_dir()) {
+                        name.push(b'/');
+                    }
+                    self.untracked_entries.push(name);
+                }
+                entry::Status::Tracked if is_recursed => self.sub_directories.push(name.to_owned()),
+                _ => {}
+            }
+        }
+    }
+}
@@ -921,6 +921,39 @@ fn only_untracked() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn only_untracked_with_metadata() -> crate::Result {
+    let root = fixture("only-untracked");
+    let (_out, mut entries) = collect(&root, None, |keep, ctx| {
+        walk(
+            &root,
+            ctx,
+            walk::Options {
+                emit_untracked: CollapseDirectory,
+                emit_metadata: true,
+                ..options()
+            },
+            keep,
+        )
+    });
+    for (entry, _) in &mut entries {
+        let expected = std::fs::symlink_metadata(root.join(gix_path::from_bstr(entry.rela_path.as_ref())))?;
+        let metadata = entry.metadata.take().expect("metadata is emitted for every entry");
+        assert_eq!(metadata, entry::Metadata::from(&expected), "{}", entry.rela_path);
+    }
+    assert_eq!(
+        entries,
+        [
+            entry("a", Untracked, File),
+            entry("b", Untracked, File),
+            entry("c", Untracked, File),
+            entry("d", Untracked, Directory),
+        ],
+        "collapsed directories carry their own metadata"
+    );
+    Ok(())
+}
+
 #[test]
 fn only_untracked_explicit_pathspec_selection() -> crate::Result {
     let root = fixture("only-untracked");
@@ -4276,3 +4309,52 @@ fn type_mismatch_ignore_case_clash_file_is_dir() {
          If there was no special handling for this, it would have found the file (`d` in the index, icase), which would have been wrong."
     );
 }
+
+#[test]
+fn untracked_cache_avoids_reading_unchanged_tracked_directories() -> crate::Result {
+    let root = fixture("untracked-and-precious");
+    let mut cache = gix_index::extension::UntrackedCache::new("test", ".gitignore", 0);
+    // Pretend the index was written after all directories were last changed so they aren't considered racy.
+    let index_timestamp = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+
+    let walk_with_cache = |cache: &mut gix_index::extension::UntrackedCache, options: walk::Options| {
+        try_collect_filtered_opts_collect(
+            &root,
+            None,
+            |keep, ctx| walk(&root, ctx, options, keep),
+            None::<&str>,
+            Options {
+                untracked_cache: Some(cache),
+                index_timestamp: Some(index_timestamp),
+                ..Default::default()
+            },
+        )
+    };
+    let ((out, _root), entries) = walk_with_cache(&mut cache, options())?;
+    assert_eq!(out.read_dir_calls, 3, "all directories are read initially");
+    assert_eq!(entries, [entry("d/d/new", Untracked, File)]);
+    assert_eq!(
+        cache.directory("d".into()).expect("recorded").untracked_entries,
+        ["d/"],
+        "untracked directories are recorded with trailing slash"
+    );
+
+    let ((out, _root), cached_entries) = walk_with_cache(&mut cache, options())?;
+    assert_eq!(
+        out.read_dir_calls, 1,
+        "only the untracked directory is read as the tracked ones didn't change"
+    );
+    assert_eq!(cached_entries, entries, "the result is the same");
+
+    cache.invalidate("d/a".into());
+    let ((out, _root), invalidated_entries) = walk_with_cache(&mut cache, options())?;
+    assert_eq!(out.read_dir_calls, 3, "invalidated directories are read again");
+    assert_eq!(invalidated_entries, entries);
+
+    let ((out, _root), _entries) = walk_with_cache(&mut cache, options_emit_all())?;
+    assert_eq!(
+        out.read_dir_calls, 3,
+        "the cache isn't used if anything but untracked files are emitted"
+    );
+    Ok(())
+}
@@ -33,6 +33,7 @@ pub fn options_emit_all() -> walk::Options {
         emit_empty_directories: true,
         emit_collapsed: None,
         symlinks_to_directories_are_ignored_like_directories: false,
+        emit_metadata: false,
     }
 }
 
@@ -57,6 +58,7 @@ pub fn entry_nomatch(
             disk_kind: Some(disk_kind),
             index_kind: index_kind_from_status(status, disk_kind),
             pathspec_match: None,
+            metadata: None,
         },
         None,
     )
@@ -71,6 +73,7 @@ pub fn entry_nokind(rela_path: impl AsRef<BStr>, status: entry::Status) -> (Entr
             disk_kind: None,
             index_kind: None,
             pathspec_match: None,
+            metadata: None,
         },
         None,
     )
@@ -90,6 +93,7 @@ pub fn entryps(
             disk_kind: Some(disk_kind),
             index_kind: index_kind_from_status(status, disk_kind),
             pathspec_match: Some(pathspec_match),
+            metadata: None,
         },
         None,
     )
@@ -109,6 +113,7 @@ pub fn entry_dirstat(
             disk_kind: Some(disk_kind),
             index_kind: index_kind_from_status(status, disk_kind),
             pathspec_match: Some(entry::PathspecMatch::Always),
+            metadata: None,
         },
         Some(dir_status),
     )
@@ -130,6 +135,7 @@ pub fn entryps_dirstat(
             disk_kind: Some(disk_kind),
             index_kind: index_kind_from_status(status, disk_kind),
             pathspec_match: Some(pathspec_match),
+            metadata: None,
         },
         Some(dir_status),
     )
@@ -274,6 +280,8 @@ pub fn try_collect_filtered_opts(
         fresh_index,
         git_dir,
         should_interrupt,
+        untracked_cache,
+        index_timestamp,
     }: Options<'_>,
 ) -> Result<(walk::Outcome, PathBuf), walk::Error> {
     let git_dir = worktree_root.join(git_dir.unwrap_or(".git"));
@@ -282,7 +290,7 @@ pub fn try_collect_filtered_opts(
         |bytes| {
             gix_index::State::from_bytes(
                 &bytes,
-                std::time::UNIX_EPOCH.into(),
+                index_timestamp.unwrap_or(std::time::UNIX_EPOCH).into(),
                 gix_index::hash::Kind::Sha1,
                 Default::default(),
             )
@@ -349,6 +357,7 @@ pub fn try_collect_filtered_opts(
             objects: &gix_object::find::Never,
             explicit_traversal_root,
             should_interrupt,
+            untracked_cache,
         },
     )
 }
@@ -357,6 +366,9 @@ pub struct Options<'a> {
     pub fresh_index: bool,
     pub git_dir: Option<&'a str>,
     pub should_interrupt: Option<&'a AtomicBool>,
+    pub untracked_cache: Option<&'a mut gix_index::extension::UntrackedCache>,
+    /// The time at which the index is supposed to have been written, which affects what's considered racy.
+    pub index_timestamp: Option<std::time::SystemTime>,
 }
 
 impl<'a> Options<'a> {
@@ -374,6 +386,8 @@ impl<'a> Default for Options<'a> {
             fresh_index: true,
             git_dir: None,
             should_interrupt: None,
+            untracked_cache: None,
+            index_timestamp: None,
         }
     }
 }
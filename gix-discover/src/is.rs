@@ -165,25 +165,28 @@ pub(crate) fn git_with_metadata(
 
     {
         // Fast-path: avoid doing the complete search if HEAD is already not there.
-        // TODO(reftable): use a ref-store to lookup HEAD if ref-tables should be supported, or detect ref-tables beforehand.
         if !dot_git.join("HEAD").exists() {
             return Err(crate::is_git::Error::MissingHead);
         }
-        // We expect to be able to parse any ref-hash, so we shouldn't have to know the repos hash here.
-        // With ref-table, the has is probably stored as part of the ref-db itself, so we can handle it from there.
-        // In other words, it's important not to fail on detached heads here because we guessed the hash kind wrongly.
-        let object_hash_should_not_matter_here = gix_hash::Kind::Sha1;
-        let refs = gix_ref::file::Store::at(
-            dot_git.as_ref().into(),
-            gix_ref::store::WriteReflog::Normal,
-            object_hash_should_not_matter_here,
-            false,
-        );
-        let head = refs.find_loose("HEAD")?;
-        if head.name.as_bstr() != "HEAD" {
-            return Err(crate::is_git::Error::MisplacedHead {
-                name: head.name.into_inner(),
-            });
+        // With reftables, the `HEAD` file only exists for older versions of `git` to recognize the repository,
+        // and points to an invalid reference on purpose. The actual `HEAD` is stored in the reftable stack.
+        let is_reftable = dot_git.join("reftable").is_dir();
+        if !is_reftable {
+            // We expect to be able to parse any ref-hash, so we shouldn't have to know the repos hash here.
+            // In other words, it's important not to fail on detached heads here because we guessed the hash kind wrongly.
+            let object_hash_should_not_matter_here = gix_hash::Kind::Sha1;
+            let refs = gix_ref::file::Store::at(
+                dot_git.as_ref().into(),
+                gix_ref::store::WriteReflog::Normal,
+                object_hash_should_not_matter_here,
+                false,
+            );
+            let head = refs.find_loose("HEAD")?;
+            if head.name.as_bstr() != "HEAD" {
+                return Err(crate::is_git::Error::MisplacedHead {
+                    name: head.name.into_inner(),
+                });
+            }
         }
     }
 
@@ -0,0 +1,74 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// The implementation used to decompress and compress zlib streams.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// Use `flate2` with the implementation selected at compile time through one of the `zlib-*` feature toggles.
+    ///
+    /// It supports all operations, and it's always available.
+    Flate2,
+    /// Use `libdeflate` for decompressing and compressing whole buffers at once, which is significantly faster than `zlib`,
+    /// while operations that stream data fall back to [`Flate2`](Backend::Flate2).
+    ///
+    /// It's only available if the `zlib-libdeflate` feature is enabled.
+    Libdeflate,
+}
+
+/// The error returned by [`set_default_backend()`](super::set_default_backend()) and [`Inflate::with_backend()`](super::Inflate::with_backend()).
+#[derive(Debug, thiserror::Error)]
+#[error("The zlib backend {backend:?} isn't available as it wasn't enabled at compile time")]
+pub struct Error {
+    /// The backend that was requested.
+    pub backend: Backend,
+}
+
+impl Backend {
+    /// Return `true` if this backend was compiled in and can be used.
+    pub fn is_available(&self) -> bool {
+        match self {
+            Backend::Flate2 => true,
+            Backend::Libdeflate => cfg!(feature = "zlib-libdeflate"),
+        }
+    }
+
+    pub(crate) fn ensure_available(&self) -> Result<(), Error> {
+        if self.is_available() {
+            Ok(())
+        } else {
+            Err(Error { backend: *self })
+        }
+    }
+}
+
+const FLATE2: u8 = 0;
+const LIBDEFLATE: u8 = 1;
+
+static DEFAULT_BACKEND: AtomicU8 = AtomicU8::new(if cfg!(feature = "zlib-libdeflate") {
+    LIBDEFLATE
+} else {
+    FLATE2
+});
+
+/// Return the backend used by newly created decompressors and by [`compress()`](super::compress()).
+///
+/// It's [`Libdeflate`](Backend::Libdeflate) if the `zlib-libdeflate` feature is enabled, and [`Flate2`](Backend::Flate2) otherwise.
+pub fn default_backend() -> Backend {
+    match DEFAULT_BACKEND.load(Ordering::Relaxed) {
+        LIBDEFLATE => Backend::Libdeflate,
+        _ => Backend::Flate2,
+    }
+}
+
+/// Set the process-wide `backend` to use by newly created decompressors and by [`compress()`](super::compress()),
+/// or fail if it isn't available.
+pub fn set_default_backend(backend: Backend) -> Result<(), Error> {
+    backend.ensure_available()?;
+    DEFAULT_BACKEND.store(
+        match backend {
+            Backend::Flate2 => FLATE2,
+            Backend::Libdeflate => LIBDEFLATE,
+        },
+        Ordering::Relaxed,
+    );
+    Ok(())
+}
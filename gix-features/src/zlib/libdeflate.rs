@@ -0,0 +1,71 @@
+//! A minimal safe wrapper around the parts of `libdeflate` we need, as the `libdeflater` crate doesn't
+//! report the amount of consumed input bytes.
+#![allow(unsafe_code)]
+
+use std::{ffi::c_void, ptr::NonNull};
+
+use libdeflate_sys as sys;
+
+/// A `libdeflate` decompressor, which is expensive to create and thus should be reused.
+pub(crate) struct Decompressor(NonNull<sys::libdeflate_decompressor>);
+
+// SAFETY: the decompressor can be moved to other threads, it's just not safe to use it concurrently which `&mut self` prevents.
+unsafe impl Send for Decompressor {}
+
+impl Decompressor {
+    pub(crate) fn new() -> Self {
+        // SAFETY: a C function without preconditions.
+        let ptr = unsafe { sys::libdeflate_alloc_decompressor() };
+        Decompressor(NonNull::new(ptr).expect("libdeflate can allocate a decompressor"))
+    }
+
+    /// Decompress the zlib stream at the beginning of `input` into `out`, and return `(consumed_in, consumed_out)`,
+    /// or `None` if the stream is invalid or if `out` is too small to hold the entire decompressed stream.
+    pub(crate) fn zlib_decompress(&mut self, input: &[u8], out: &mut [u8]) -> Option<(usize, usize)> {
+        let (mut consumed_in, mut consumed_out) = (0, 0);
+        // SAFETY: all pointers are valid for the given lengths, and the decompressor is exclusively borrowed.
+        let res = unsafe {
+            sys::libdeflate_zlib_decompress_ex(
+                self.0.as_ptr(),
+                input.as_ptr() as *const c_void,
+                input.len(),
+                out.as_mut_ptr() as *mut c_void,
+                out.len(),
+                &mut consumed_in,
+                &mut consumed_out,
+            )
+        };
+        (res == sys::libdeflate_result_LIBDEFLATE_SUCCESS).then_some((consumed_in, consumed_out))
+    }
+}
+
+impl Drop for Decompressor {
+    fn drop(&mut self) {
+        // SAFETY: the pointer was allocated by libdeflate and isn't used afterwards.
+        unsafe { sys::libdeflate_free_decompressor(self.0.as_ptr()) }
+    }
+}
+
+/// Compress all of `data` into a zlib stream, with the same compression level `flate2` is configured with.
+pub(crate) fn zlib_compress(data: &[u8]) -> Vec<u8> {
+    const FAST_COMPRESSION_LEVEL: i32 = 1;
+    // SAFETY: a C function without preconditions.
+    let compressor = NonNull::new(unsafe { sys::libdeflate_alloc_compressor(FAST_COMPRESSION_LEVEL) })
+        .expect("libdeflate can allocate a compressor");
+    // SAFETY: the compressor is valid and only used here, and all pointers are valid for the given lengths.
+    let out = unsafe {
+        let mut out = vec![0u8; sys::libdeflate_zlib_compress_bound(compressor.as_ptr(), data.len())];
+        let len = sys::libdeflate_zlib_compress(
+            compressor.as_ptr(),
+            data.as_ptr() as *const c_void,
+            data.len(),
+            out.as_mut_ptr() as *mut c_void,
+            out.len(),
+        );
+        sys::libdeflate_free_compressor(compressor.as_ptr());
+        assert_ne!(len, 0, "the output buffer is large enough to hold the compressed data");
+        out.truncate(len);
+        out
+    };
+    out
+}
@@ -19,19 +19,50 @@ pub mod inflate {
 pub struct Inflate {
     /// The actual decompressor doing all the work.
     pub state: Decompress,
+    backend: Backend,
+    #[cfg(feature = "zlib-libdeflate")]
+    libdeflate: Option<libdeflate::Decompressor>,
 }
 
 impl Default for Inflate {
+    /// Create a new instance which uses the [default backend](default_backend()).
     fn default() -> Self {
         Inflate {
             state: Decompress::new(true),
+            backend: default_backend(),
+            #[cfg(feature = "zlib-libdeflate")]
+            libdeflate: None,
         }
     }
 }
 
 impl Inflate {
+    /// Create a new instance which uses `backend` for decompression, or fail if `backend` isn't available.
+    pub fn with_backend(backend: Backend) -> Result<Self, backend::Error> {
+        backend.ensure_available()?;
+        Ok(Inflate {
+            backend,
+            ..Default::default()
+        })
+    }
+
+    /// Return the backend used for decompression.
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
     /// Run the decompressor exactly once. Cannot be run multiple times
+    ///
+    /// If the [`Libdeflate`](Backend::Libdeflate) backend is used and `out` can hold the entire decompressed stream,
+    /// it will be decompressed in one go, falling back to the streaming decompressor otherwise.
     pub fn once(&mut self, input: &[u8], out: &mut [u8]) -> Result<(flate2::Status, usize, usize), inflate::Error> {
+        #[cfg(feature = "zlib-libdeflate")]
+        if self.backend == Backend::Libdeflate && self.state.total_in() == 0 {
+            let decompressor = self.libdeflate.get_or_insert_with(libdeflate::Decompressor::new);
+            if let Some((consumed_in, consumed_out)) = decompressor.zlib_decompress(input, out) {
+                return Ok((flate2::Status::StreamEnd, consumed_in, consumed_out));
+            }
+        }
         let before_in = self.state.total_in();
         let before_out = self.state.total_out();
         let status = self.state.decompress(input, out, flate2::FlushDecompress::None)?;
@@ -48,6 +79,29 @@ impl Inflate {
     }
 }
 
+/// Compress all of `data` into a new zlib stream using the [default backend](default_backend()).
+pub fn compress(data: &[u8]) -> std::io::Result<Vec<u8>> {
+    #[cfg(feature = "zlib-libdeflate")]
+    if default_backend() == Backend::Libdeflate {
+        return Ok(libdeflate::zlib_compress(data));
+    }
+    let mut out = stream::deflate::Write::new(Vec::new());
+    std::io::Write::write_all(&mut out, data)?;
+    std::io::Write::flush(&mut out)?;
+    Ok(out.into_inner())
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod backend;
+pub use backend::{default_backend, set_default_backend, Backend};
+
+#[cfg(feature = "zlib-libdeflate")]
+mod libdeflate;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod stream;
+
+#[cfg(test)]
+mod tests;
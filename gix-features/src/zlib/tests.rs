@@ -0,0 +1,61 @@
+mod backend {
+    use crate::zlib::{self, Backend, Inflate};
+
+    fn available_backends() -> impl Iterator<Item = Backend> {
+        [Backend::Flate2, Backend::Libdeflate]
+            .into_iter()
+            .filter(Backend::is_available)
+    }
+
+    #[test]
+    fn flate2_is_always_available() {
+        assert!(Backend::Flate2.is_available());
+        assert_eq!(
+            Backend::Libdeflate.is_available(),
+            cfg!(feature = "zlib-libdeflate"),
+            "libdeflate depends on the feature toggle"
+        );
+        assert_eq!(
+            Inflate::with_backend(Backend::Libdeflate).is_ok(),
+            Backend::Libdeflate.is_available()
+        );
+    }
+
+    #[test]
+    fn compress_and_decompress_in_one_go() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"hello world, hello world, hello world".repeat(10);
+        let compressed = zlib::compress(&data)?;
+        for backend in available_backends() {
+            let mut inflate = Inflate::with_backend(backend)?;
+            assert_eq!(inflate.backend(), backend);
+            let mut out = vec![0; data.len()];
+            let (status, consumed_in, consumed_out) = inflate.once(&compressed, &mut out)?;
+            assert_eq!(status, zlib::Status::StreamEnd);
+            assert_eq!(consumed_in, compressed.len());
+            assert_eq!(consumed_out, data.len());
+            assert_eq!(out, data);
+
+            let mut trailing_input = compressed.clone();
+            trailing_input.extend_from_slice(b"trailing bytes of the next entry");
+            inflate.reset();
+            let (_status, consumed_in, _consumed_out) = inflate.once(&trailing_input, &mut out)?;
+            assert_eq!(consumed_in, compressed.len(), "{backend:?}: the stream end is detected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn partial_output_falls_back_to_streaming() -> Result<(), Box<dyn std::error::Error>> {
+        let data = b"hello world".repeat(10);
+        let compressed = zlib::compress(&data)?;
+        for backend in available_backends() {
+            let mut inflate = Inflate::with_backend(backend)?;
+            let mut out = [0; 5];
+            let (status, _consumed_in, consumed_out) = inflate.once(&compressed, &mut out)?;
+            assert_eq!(status, zlib::Status::Ok, "{backend:?}");
+            assert_eq!(consumed_out, out.len());
+            assert_eq!(&out, b"hello");
+        }
+        Ok(())
+    }
+}
@@ -1,2 +1,147 @@
-#![deny(rust_2018_idioms)]
+//! Read and write the `.git/FETCH_HEAD` file, which lists the objects of the last fetch along with the remote references
+//! they were fetched from, and whether `git pull` should merge them.
+#![deny(rust_2018_idioms, missing_docs)]
 #![forbid(unsafe_code)]
+
+use bstr::{BStr, BString, ByteSlice};
+use gix_hash::ObjectId;
+
+/// The kind of reference an [`Entry`] of a `FETCH_HEAD` file was fetched from.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Kind {
+    /// A branch of the remote, from `refs/heads/`.
+    Branch,
+    /// A tag of the remote, from `refs/tags/`.
+    Tag,
+    /// A remote-tracking branch of the remote, from `refs/remotes/`.
+    RemoteTrackingBranch,
+    /// Any other reference, which is shown by its full name.
+    Other,
+}
+
+impl Kind {
+    /// Return the prefix `git` uses to describe references of this kind, or `None` if there is none.
+    pub fn as_str(&self) -> Option<&'static str> {
+        Some(match self {
+            Kind::Branch => "branch",
+            Kind::Tag => "tag",
+            Kind::RemoteTrackingBranch => "remote-tracking branch",
+            Kind::Other => return None,
+        })
+    }
+}
+
+/// The remote reference an [`Entry`] of a `FETCH_HEAD` file was fetched from.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Source {
+    /// The kind of the reference, which determines how `name` is shortened.
+    pub kind: Kind,
+    /// The name of the reference, like `main` for branches, `v1.0` for tags, or `refs/pull/1/head` for other references.
+    pub name: BString,
+}
+
+/// A single line of a `FETCH_HEAD` file, describing one object that was fetched.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Entry {
+    /// The id of the fetched object.
+    pub id: ObjectId,
+    /// If `true`, this object should not be merged by `git pull`, which is the case for all but the objects of
+    /// references that were explicitly requested or configured for merging.
+    pub not_for_merge: bool,
+    /// The remote reference the object was fetched from, or `None` if it was the remote's `HEAD`.
+    pub source: Option<Source>,
+    /// The URL of the remote, or the name of the remote if the URL isn't known.
+    pub url: BString,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`decode()`][super::decode()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("FETCH_HEAD line {line_number} could not be parsed: {line:?}")]
+        Line { line: BString, line_number: usize },
+    }
+}
+
+/// Parse all entries of a `FETCH_HEAD` file from `data`, ignoring empty lines.
+pub fn decode(data: &[u8]) -> Result<Vec<Entry>, decode::Error> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| {
+            Entry::from_line(line.as_bstr()).ok_or_else(|| decode::Error::Line {
+                line: line.into(),
+                line_number: idx + 1,
+            })
+        })
+        .collect()
+}
+
+impl Entry {
+    /// Parse a single `line` of a `FETCH_HEAD` file, without trailing newline.
+    fn from_line(line: &BStr) -> Option<Self> {
+        let mut tokens = line.splitn(3, |b| *b == b'\t');
+        let id = ObjectId::from_hex(tokens.next()?).ok()?;
+        let not_for_merge = match tokens.next()? {
+            b"" => false,
+            b"not-for-merge" => true,
+            _ => return None,
+        };
+        let description = tokens.next()?;
+
+        let (source, url) = match description.find(b"' of ") {
+            Some(end_of_name) if description.contains(&b'\'') => {
+                let start_of_name = description.find_byte(b'\'')?;
+                let kind = match description[..start_of_name].as_bstr().to_str().ok()? {
+                    "" => Kind::Other,
+                    "branch " => Kind::Branch,
+                    "tag " => Kind::Tag,
+                    "remote-tracking branch " => Kind::RemoteTrackingBranch,
+                    _ => return None,
+                };
+                let name = description.get(start_of_name + 1..end_of_name)?;
+                (
+                    Some(Source {
+                        kind,
+                        name: name.into(),
+                    }),
+                    &description[end_of_name + "' of ".len()..],
+                )
+            }
+            _ => (None, description),
+        };
+        Some(Entry {
+            id,
+            not_for_merge,
+            source,
+            url: url.into(),
+        })
+    }
+
+    /// Serialize this instance to `out` as a line of a `FETCH_HEAD` file, including the trailing newline.
+    pub fn write_to(&self, out: &mut dyn std::io::Write) -> std::io::Result<()> {
+        write!(out, "{}\t", self.id)?;
+        if self.not_for_merge {
+            out.write_all(b"not-for-merge")?;
+        }
+        out.write_all(b"\t")?;
+        if let Some(source) = &self.source {
+            if let Some(kind) = source.kind.as_str() {
+                write!(out, "{kind} ")?;
+            }
+            out.write_all(b"'")?;
+            out.write_all(&source.name)?;
+            out.write_all(b"' of ")?;
+        }
+        out.write_all(&self.url)?;
+        out.write_all(b"\n")
+    }
+}
@@ -0,0 +1,50 @@
+use gix_fetchhead::Kind;
+
+type Result<T = ()> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+#[test]
+fn descriptions_of_all_kinds_round_trip() -> Result {
+    let data = "\
+0000000000000000000000000000000000000001\t\tbranch 'main' of https://example.com/repo
+0000000000000000000000000000000000000002\tnot-for-merge\ttag 'v1.0' of https://example.com/repo
+0000000000000000000000000000000000000003\tnot-for-merge\tremote-tracking branch 'origin/main' of ../other
+0000000000000000000000000000000000000004\tnot-for-merge\t'refs/pull/1/head' of https://example.com/repo
+0000000000000000000000000000000000000005\t\thttps://example.com/repo
+";
+    let entries = gix_fetchhead::decode(data.as_bytes())?;
+    assert_eq!(
+        entries
+            .iter()
+            .map(|e| e.source.as_ref().map(|s| (s.kind, s.name.to_string())))
+            .collect::<Vec<_>>(),
+        [
+            Some((Kind::Branch, "main".into())),
+            Some((Kind::Tag, "v1.0".into())),
+            Some((Kind::RemoteTrackingBranch, "origin/main".into())),
+            Some((Kind::Other, "refs/pull/1/head".into())),
+            None
+        ]
+    );
+    assert_eq!(entries[2].url, "../other");
+    assert_eq!(
+        entries[4].url, "https://example.com/repo",
+        "HEAD is only described by its url"
+    );
+
+    let mut out = Vec::new();
+    for entry in &entries {
+        entry.write_to(&mut out)?;
+    }
+    assert_eq!(out, data.as_bytes(), "serialization is lossless");
+    Ok(())
+}
+
+#[test]
+fn invalid_lines_are_reported() {
+    let err = gix_fetchhead::decode(b"\n0000000000000000000000000000000000000001\tmerge\turl\n").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "FETCH_HEAD line 2 could not be parsed: \"0000000000000000000000000000000000000001\\tmerge\\turl\"",
+        "only an empty field or `not-for-merge` are valid merge markers"
+    );
+}
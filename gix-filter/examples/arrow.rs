@@ -33,6 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
             )?;
 
             let mut next_smudge_aborts = false;
+            let mut next_smudge_aborts_after_content = false;
             let mut next_smudge_fails_permanently = false; // a test validates that we don't actually hang
             let mut delayed = Vec::new();
             while let Some(mut request) = srv.next_request()? {
@@ -135,7 +136,12 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                             delayed.push(("smudge", pathname.expect("needed for delayed operation"), lines));
                         } else {
                             request.as_write().write_all(&lines)?;
-                            request.write_status(process::Status::Previous)?;
+                            request.write_status(if next_smudge_aborts_after_content {
+                                next_smudge_aborts_after_content = false;
+                                process::Status::abort()
+                            } else {
+                                process::Status::Previous
+                            })?;
                         }
                     }
                     "list_available_blobs" => {
@@ -165,6 +171,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                         request.write_status(process::Status::success())?;
                         next_smudge_aborts = true;
                     }
+                    "next-smudge-aborts-after-content" => {
+                        std::io::copy(&mut request.as_read(), &mut std::io::sink())?;
+                        request.write_status(process::Status::success())?;
+                        next_smudge_aborts_after_content = true;
+                    }
                     "next-invocation-returns-strange-status-and-smudge-fails-permanently" => {
                         std::io::copy(&mut request.as_read(), &mut std::io::sink())?;
                         request.write_status(process::Status::success())?;
@@ -77,10 +77,10 @@ impl State {
     ///
     /// ### Deviation
     ///
-    /// If a long running process returns the 'abort' status after receiving the data, it will be removed similar to how `git` does it.
-    /// However, it delivers an unsuccessful error status later, it will not be removed, but reports the error only.
+    /// If a long running process returns the 'abort' status after receiving the data or after sending the filtered result,
+    /// the capability will be removed similar to how `git` does it.
     /// If any other non-'error' status is received, the process will be stopped. But that doesn't happen if if such a status is received
-    /// after reading the filtered result.
+    /// after reading the filtered result, in which case the error is only reported.
     pub fn apply<'a>(
         &'a mut self,
         driver: &Driver,
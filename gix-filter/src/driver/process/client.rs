@@ -158,6 +158,7 @@ impl Client {
             input: out,
             capabilities,
             version: chosen_version,
+            last_command: None,
         })
     }
 
@@ -199,10 +200,14 @@ impl Client {
 
     /// Return a `Read` implementation that reads the server process output until the next flush package, and validates
     /// the status. If the status indicates failure, the last read will also fail.
+    ///
+    /// If the status is `abort`, the capability of the last invoked command is removed so it won't be used again, just like `git` does.
     pub fn as_read(&mut self) -> impl std::io::Read + '_ {
         self.out.reset_with(&[gix_packetline::PacketLineRef::Flush]);
         ReadProcessOutputAndStatus {
             inner: self.out.as_read(),
+            capabilities: &mut self.capabilities,
+            command: self.last_command.as_deref(),
         }
     }
 
@@ -219,6 +224,7 @@ impl Client {
         command: &str,
         meta: &mut dyn Iterator<Item = (&str, BString)>,
     ) -> Result<(), invoke::Error> {
+        self.last_command = Some(command.to_owned());
         self.input.write_all(format!("command={command}").as_bytes())?;
         let mut buf = BString::default();
         for (key, value) in meta {
@@ -257,6 +263,8 @@ fn read_status(read: &mut PacketlineReader<'_>) -> std::io::Result<process::Stat
 
 struct ReadProcessOutputAndStatus<'a> {
     inner: PacketlineReader<'a>,
+    capabilities: &'a mut Capabilities,
+    command: Option<&'a str>,
 }
 
 impl<'a> std::io::Read for ReadProcessOutputAndStatus<'a> {
@@ -268,6 +276,11 @@ impl<'a> std::io::Read for ReadProcessOutputAndStatus<'a> {
             if status.is_success() {
                 Ok(0)
             } else {
+                if status.is_abort() {
+                    if let Some(command) = self.command {
+                        self.capabilities.remove(command);
+                    }
+                }
                 Err(std::io::Error::new(
                     std::io::ErrorKind::Other,
                     format!(
@@ -11,6 +11,8 @@ pub struct Client {
     capabilities: Capabilities,
     /// The negotiated version of the protocol.
     version: usize,
+    /// The name of the last command we invoked, used to disable it if the process aborts it after sending its output.
+    last_command: Option<String>,
     /// A way to send packet-line encoded information to the process.
     input: gix_packetline::Writer<std::process::ChildStdin>,
     /// A way to read information sent to us by the process.
@@ -44,8 +44,10 @@ impl Configuration {
 impl Stats {
     /// Gather statistics from the given `bytes`.
     ///
-    /// Note that the entire buffer will be scanned.
+    /// Note that the entire buffer will be scanned. Just like `git`, a trailing *EOF* character (`\x1a`) isn't counted as
+    /// non-printable character.
     pub fn from_bytes(bytes: &[u8]) -> Self {
+        let ends_with_eof = bytes.last() == Some(&0x1a);
         let mut bytes = bytes.iter().peekable();
         let mut null = 0;
         let mut lone_cr = 0;
@@ -83,6 +85,9 @@ impl Stats {
                 printable += 1;
             }
         }
+        if ends_with_eof {
+            non_printable -= 1;
+        }
 
         Self {
             null,
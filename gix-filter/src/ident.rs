@@ -40,23 +40,52 @@ pub fn undo(src: &[u8], buf: &mut Vec<u8>) -> Result<bool, std::collections::Try
     Ok(initialized)
 }
 
-/// Substitute all occurrences of `$Id$` with `$Id: <hexsha-of-input>$` if present in `src` and write all changes to `buf`,
+/// Substitute all occurrences of `$Id$` with `$Id: <hexsha-of-input> $` if present in `src` and write all changes to `buf`,
 /// with `object_hash` being used accordingly. Return `true` if `buf` was written to or `false` if no change was made
 /// (as there was nothing to do).
 ///
-/// ### Deviation
-///
-/// `Git` also tries to cleanup 'stray' substituted `$Id: <hex>$`, but we don't do that, sticking exactly to what ought to be done.
-/// The respective code is up to 16 years old and one might assume that `git` by now handles checking and checkout filters correctly.
+/// Like `git`, identifiers that were already expanded, like `$Id: <hex> $` or `$Id:<anything>$`, are substituted as well,
+/// unless there is a newline before the closing dollar, or if they look like identifiers of other version control systems,
+/// i.e. `$Id: <with> <spaces>$`.
 pub fn apply(
     src: &[u8],
     object_hash: gix_hash::Kind,
     buf: &mut Vec<u8>,
 ) -> Result<bool, std::collections::TryReserveError> {
-    const HASH_LEN: usize = ": ".len() + gix_hash::Kind::longest().len_in_hex();
+    const HASH_LEN: usize = ": ".len() + gix_hash::Kind::longest().len_in_hex() + " $".len();
     let mut id = None;
     let mut ofs = 0;
-    while let Some(pos) = src[ofs..].find(b"$Id$") {
+    let mut cursor = 0;
+    while let Some(dollar) = src[cursor..].find_byte(b'$') {
+        let start = cursor + dollar + 1;
+        // Like `git`, we consume the matching prefix of `Id` along with the first mismatching byte.
+        let matched = src[start..].iter().zip(b"Id").take_while(|(a, b)| a == b).count();
+        cursor = (start + matched + 1).min(src.len());
+        if matched != 2 {
+            continue;
+        }
+        let end = match src.get(start + 2) {
+            Some(b'$') => start + 3,
+            Some(b':') => {
+                let value_start = start + 3;
+                let value_len = match src[value_start..].find_byteset(b"$\n") {
+                    Some(pos) => pos,
+                    None => break,
+                };
+                let closing = value_start + value_len;
+                cursor = closing + 1;
+                if src[closing] == b'\n' {
+                    continue;
+                }
+                let value = &src[value_start..closing];
+                let is_foreign = value.len() > 2 && value[0] == b' ' && value[1..value.len() - 1].contains(&b' ');
+                if is_foreign {
+                    continue;
+                }
+                closing + 1
+            }
+            _ => continue,
+        };
         let id = match id {
             None => {
                 let new_id = gix_object::compute_hash(object_hash, gix_object::Kind::Blob, src);
@@ -67,12 +96,13 @@ pub fn apply(
             Some(id) => id.to_owned(),
         };
 
-        buf.push_str(&src[ofs..][..pos + 3]);
-        buf.push_str(b": ");
+        buf.push_str(&src[ofs..start]);
+        buf.push_str(b"Id: ");
         id.write_hex_to(&mut *buf).expect("writes to memory always work");
-        buf.push(b'$');
+        buf.push_str(b" $");
 
-        ofs += pos + 4;
+        ofs = end;
+        cursor = end;
     }
     if id.is_some() {
         buf.push_str(&src[ofs..]);
@@ -0,0 +1,161 @@
+use std::io::Write;
+
+use bstr::{BStr, BString, ByteSlice};
+
+/// The `version` of pointer files we write, and the first one we accept when reading.
+pub const VERSION: &str = "https://git-lfs.github.com/spec/v1";
+/// Pointer files larger than this are never considered pointers, just like `git-lfs` does it.
+pub const MAX_POINTER_SIZE: usize = 1024;
+
+/// Versions used by early pre-release versions of `git-lfs`, which are still accepted when reading.
+const LEGACY_VERSIONS: [&str; 1] = ["https://hawser.github.com/spec/v1"];
+
+/// A parsed `git-lfs` pointer file, which is stored in `git` instead of the actual content of a file.
+///
+/// It's typically produced by the `clean` operation of the `lfs` filter driver, while the content itself
+/// is uploaded to a separate store from which it can be retrieved using the `oid`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pointer {
+    /// The hexadecimal SHA-256 digest of the actual content, without the `sha256:` prefix.
+    pub oid: BString,
+    /// The size of the actual content in bytes.
+    pub size: u64,
+    /// Additional `key value` pairs, like `ext-0-name sha256:<hex>` of extensions that were applied to the content,
+    /// in the order in which they were found.
+    pub extensions: Vec<(BString, BString)>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode {
+    use bstr::BString;
+
+    /// The error returned by [`Pointer::from_bytes()`](super::Pointer::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The buffer with {size} bytes is too large to be a pointer file")]
+        TooLarge { size: usize },
+        #[error("The first line must be 'version {}'", super::VERSION)]
+        Version,
+        #[error("The line {line:?} isn't a key-value pair separated by a single space")]
+        Line { line: BString },
+        #[error("The oid {oid:?} isn't a hexadecimal sha256 digest with 'sha256:' prefix")]
+        Oid { oid: BString },
+        #[error("The size {size:?} isn't a valid number")]
+        Size { size: BString },
+        #[error("The required key '{key}' is missing")]
+        MissingKey { key: &'static str },
+    }
+}
+
+/// Creation
+impl Pointer {
+    /// Create a new pointer to content with `size` in bytes whose SHA-256 digest is `oid` in hexadecimal form.
+    pub fn new(oid: impl Into<BString>, size: u64) -> Self {
+        Pointer {
+            oid: oid.into(),
+            size,
+            extensions: Vec::new(),
+        }
+    }
+
+    /// Parse `data` as pointer file, or fail if it isn't one.
+    ///
+    /// Use it to check if a blob is a pointer file. Note that `data` that is larger than [`MAX_POINTER_SIZE`] is rejected
+    /// early, so it's safe to pass the content of large blobs as well.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, decode::Error> {
+        if data.len() > MAX_POINTER_SIZE {
+            return Err(decode::Error::TooLarge { size: data.len() });
+        }
+        let mut lines = data.lines();
+        match lines.next().and_then(|line| line.strip_prefix(b"version ")) {
+            Some(version)
+                if version == VERSION.as_bytes() || LEGACY_VERSIONS.iter().any(|v| version == v.as_bytes()) => {}
+            _ => return Err(decode::Error::Version),
+        }
+
+        let mut oid = None;
+        let mut size = None;
+        let mut extensions = Vec::new();
+        for line in lines.filter(|line| !line.is_empty()) {
+            let (key, value) = line
+                .split_once_str(b" ")
+                .filter(|(key, value)| !key.is_empty() && !value.is_empty())
+                .ok_or_else(|| decode::Error::Line { line: line.into() })?;
+            match key {
+                b"oid" => {
+                    oid = Some(
+                        value
+                            .strip_prefix(b"sha256:")
+                            .filter(|hex| hex.len() == 64 && hex.iter().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')))
+                            .ok_or_else(|| decode::Error::Oid { oid: value.into() })?,
+                    )
+                }
+                b"size" => {
+                    size = Some(
+                        value
+                            .to_str()
+                            .ok()
+                            .and_then(|size| size.parse::<u64>().ok())
+                            .ok_or_else(|| decode::Error::Size { size: value.into() })?,
+                    )
+                }
+                _ => extensions.push((key.into(), value.into())),
+            }
+        }
+        Ok(Pointer {
+            oid: oid.ok_or(decode::Error::MissingKey { key: "oid" })?.into(),
+            size: size.ok_or(decode::Error::MissingKey { key: "size" })?,
+            extensions,
+        })
+    }
+}
+
+/// Serialization
+impl Pointer {
+    /// Write this instance as pointer file to `out`, with all keys but the `version` sorted alphabetically as
+    /// `git-lfs` expects it. The output is suitable for storage in `git` in place of the actual content.
+    pub fn write_to(&self, mut out: impl Write) -> std::io::Result<()> {
+        writeln!(out, "version {VERSION}")?;
+        let oid = format!("sha256:{}", self.oid);
+        let size = self.size.to_string();
+        let mut lines: Vec<(&[u8], &[u8])> = vec![(b"oid", oid.as_bytes()), (b"size", size.as_bytes())];
+        lines.extend(
+            self.extensions
+                .iter()
+                .map(|(key, value)| (key.as_slice(), value.as_slice())),
+        );
+        lines.sort_by(|a, b| a.0.cmp(b.0));
+        for (key, value) in lines {
+            out.write_all(key)?;
+            out.write_all(b" ")?;
+            out.write_all(value)?;
+            out.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+
+    /// Return this instance as pointer file, see [`write_to()`](Self::write_to()).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(128);
+        self.write_to(&mut buf).expect("writing to a vec never fails");
+        buf
+    }
+}
+
+/// A way to obtain the actual content of [pointer files](Pointer) from an LFS store, for use in the filter [pipeline](crate::Pipeline)
+/// via [`Options::lfs_resolver`](crate::pipeline::Options::lfs_resolver).
+///
+/// It's invoked whenever a pointer file is converted to the worktree, as done during checkout or when diffing worktree contents,
+/// unless an `lfs` filter driver is configured which then takes precedence.
+pub trait Resolve: Send + Sync {
+    /// Write the content referred to by `pointer` at `rela_path` to `out`, and return `true`, or return `false` if the content isn't
+    /// available so the pointer file is used as is.
+    fn resolve(
+        &self,
+        pointer: &Pointer,
+        rela_path: &BStr,
+        out: &mut Vec<u8>,
+    ) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>>;
+}
@@ -28,6 +28,9 @@ pub mod worktree;
 /// use filter programs to perform any kind of conversion.
 pub mod driver;
 
+/// parse and create `git-lfs` pointer files, and resolve them into their actual content.
+pub mod lfs;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod pipeline;
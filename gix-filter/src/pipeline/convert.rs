@@ -60,6 +60,11 @@ pub mod to_worktree {
         Configuration(#[from] super::configuration::Error),
         #[error("Could not allocate buffer")]
         OutOfMemory(#[from] std::collections::TryReserveError),
+        #[error("Could not obtain the content of the git-lfs pointer file at '{rela_path}'")]
+        LfsResolve {
+            rela_path: bstr::BString,
+            source: Box<dyn std::error::Error + Send + Sync + 'static>,
+        },
     }
 }
 
@@ -86,6 +91,7 @@ impl Pipeline {
             _attr_digest: _,
             encoding,
             apply_ident_filter,
+            is_lfs: _,
         } = Configuration::at_path(
             bstr_path.as_ref(),
             &self.options.drivers,
@@ -175,6 +181,9 @@ impl Pipeline {
     ///
     /// The reason `src` is a buffer is to indicate that `git` generally doesn't do well streaming data, so it should be small enough
     /// to be performant while being held in memory. This is typically the case, especially if `git-lfs` is used as intended.
+    ///
+    /// If `src` is a `git-lfs` pointer file with the `filter=lfs` attribute and there is no `lfs` driver, the
+    /// [resolver](crate::pipeline::Options::lfs_resolver) is used to obtain the actual content before all other filters are applied.
     pub fn convert_to_worktree<'input>(
         &mut self,
         src: &'input [u8],
@@ -188,6 +197,7 @@ impl Pipeline {
             _attr_digest: _,
             encoding,
             apply_ident_filter,
+            is_lfs,
         } = Configuration::at_path(
             rela_path,
             &self.options.drivers,
@@ -197,6 +207,26 @@ impl Pipeline {
         )?;
 
         let mut bufs = self.bufs.use_foreign_src(src);
+        if let Some(resolver) = self
+            .options
+            .lfs_resolver
+            .as_ref()
+            .filter(|_| is_lfs && driver.is_none())
+        {
+            let (src, dest) = bufs.src_and_dest();
+            if let Ok(pointer) = crate::lfs::Pointer::from_bytes(src) {
+                if resolver
+                    .resolve(&pointer, rela_path, dest)
+                    .map_err(|source| to_worktree::Error::LfsResolve {
+                        rela_path: rela_path.to_owned(),
+                        source,
+                    })?
+                {
+                    bufs.swap();
+                }
+            }
+        }
+
         let (src, dest) = bufs.src_and_dest();
         if apply_ident_filter && ident::apply(src, self.options.object_hash, dest)? {
             bufs.swap();
@@ -29,6 +29,9 @@ pub struct Options {
     pub encodings_with_roundtrip_check: Vec<&'static encoding_rs::Encoding>,
     /// The object hash to use when applying the `ident` filter.
     pub object_hash: gix_hash::Kind,
+    /// If set, it's used to obtain the content of `git-lfs` pointer files when converting them to the worktree,
+    /// for all paths with the `filter=lfs` attribute that have no `lfs` filter driver configured.
+    pub lfs_resolver: Option<std::sync::Arc<dyn crate::lfs::Resolve>>,
 }
 
 /// Context that typically doesn't change throughout the lifetime of a pipeline, for use with `process` filters.
@@ -20,6 +20,8 @@ pub(crate) struct Configuration<'a> {
     pub(crate) encoding: Option<&'static encoding_rs::Encoding>,
     /// Whether or not to apply the `ident` filter
     pub(crate) apply_ident_filter: bool,
+    /// Whether the `filter` attribute is set to `lfs`.
+    pub(crate) is_lfs: bool,
 }
 
 impl<'driver> Configuration<'driver> {
@@ -97,6 +99,7 @@ impl<'driver> Configuration<'driver> {
         let attrs: SmallVec<[_; crate::pipeline::ATTRS.len()]> = attrs.iter_selected().collect();
         let apply_ident_filter = attrs[1].assignment.state.is_set();
         let driver = extract_driver(drivers, &attrs[2]);
+        let is_lfs = matches!(attrs[2].assignment.state, StateRef::Value(name) if name.as_bstr() == "lfs");
         let encoding = extract_encoding(&attrs[5])?;
 
         let mut digest = extract_crlf(&attrs[4]);
@@ -132,6 +135,7 @@ impl<'driver> Configuration<'driver> {
             digest: digest.expect("always set by now"),
             encoding,
             apply_ident_filter,
+            is_lfs,
         })
     }
 }
@@ -178,6 +178,39 @@ pub(crate) mod apply {
         Ok(())
     }
 
+    #[serial]
+    #[test]
+    fn process_status_abort_after_content_disables_capability() -> crate::Result {
+        let mut state = gix_filter::driver::State::default();
+        let driver = driver_with_process();
+        let client = extract_client(state.maybe_launch_process(&driver, Operation::Clean, "does not matter".into())?);
+
+        assert!(client
+            .invoke("next-smudge-aborts-after-content", &mut None.into_iter(), &mut &b""[..])?
+            .is_success());
+        let mut filtered = state
+            .apply(&driver, &mut &b"hi\n"[..], Operation::Smudge, context_from_path("any"))?
+            .expect("the filter is active");
+        let mut buf = Vec::new();
+        let err = filtered
+            .read_to_end(&mut buf)
+            .expect_err("the abort status is sent after the content");
+        assert_eq!(err.to_string(), "Process indicated error after reading: abort");
+        drop(filtered);
+        assert!(
+            state
+                .apply(
+                    &driver,
+                    &mut std::io::empty(),
+                    Operation::Smudge,
+                    context_from_path("any")
+                )?
+                .is_none(),
+            "smudge is now disabled permanently"
+        );
+        Ok(())
+    }
+
     #[serial]
     #[test]
     fn process_status_strange_shuts_down_process() -> crate::Result {
@@ -18,6 +18,21 @@ mod stats {
             );
             assert!(stats.is_binary());
         }
+
+        #[test]
+        fn trailing_eof_character_is_not_counted_as_non_printable() {
+            let stats = eol::Stats::from_bytes(b"a\x1a\n\x1a");
+            assert_eq!(
+                stats,
+                eol::Stats {
+                    lone_lf: 1,
+                    printable: 1,
+                    non_printable: 1,
+                    ..Default::default()
+                },
+                "only the last EOF character is ignored, like in git"
+            );
+        }
     }
 }
 
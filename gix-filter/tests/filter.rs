@@ -1,6 +1,7 @@
 pub(crate) mod driver;
 pub(crate) mod eol;
 mod ident;
+mod lfs;
 mod pipeline;
 mod worktree;
 
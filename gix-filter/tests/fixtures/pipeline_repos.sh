@@ -16,3 +16,25 @@ EOF
 * filter=arrow
 EOF
 )
+
+(mkdir lfs && cd lfs
+  cat <<EOF > .gitattributes
+*.bin filter=lfs diff=lfs merge=lfs -text
+EOF
+)
+
+(mkdir eol-matrix && cd eol-matrix
+  cat <<EOF > .gitattributes
+text_* text
+binary_* -text
+auto_* text=auto
+lf_* eol=lf
+crlf_* eol=crlf
+textlf_* text eol=lf
+textcrlf_* text eol=crlf
+binarylf_* -text eol=lf
+binarycrlf_* -text eol=crlf
+autolf_* text=auto eol=lf
+autocrlf_* text=auto eol=crlf
+EOF
+)
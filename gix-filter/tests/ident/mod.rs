@@ -92,16 +92,57 @@ mod apply {
             ident::apply(B("$Id$"), gix_hash::Kind::Sha1, &mut buf)?,
             "a change happens"
         );
-        assert_eq!(buf.as_bstr(), "$Id: b3f5ebfb5843bc43ceecff6d4f26bb37c615beb1$");
+        assert_eq!(
+            buf.as_bstr(),
+            "$Id: b3f5ebfb5843bc43ceecff6d4f26bb37c615beb1 $",
+            "like git, the hash is followed by a space"
+        );
 
         assert!(ident::apply(B("$Id$ $Id$ foo"), gix_hash::Kind::Sha1, &mut buf)?);
         assert_eq!(
             buf.as_bstr(),
-            "$Id: e230cff7a9624f59eaa28bfb97602c3a03651a49$ $Id: e230cff7a9624f59eaa28bfb97602c3a03651a49$ foo"
+            "$Id: e230cff7a9624f59eaa28bfb97602c3a03651a49 $ $Id: e230cff7a9624f59eaa28bfb97602c3a03651a49 $ foo"
         );
         Ok(())
     }
 
+    #[test]
+    fn like_git() -> crate::Result {
+        let mut buf = Vec::new();
+        // Baselines were obtained by checking out blobs with these contents with `git` 2.39 and the `ident` attribute set.
+        for (input, expected) in [
+            ("$Id: stray $", Some("$Id: {id} $")),
+            ("$Id:$", Some("$Id: {id} $")),
+            ("$Id:x y$", Some("$Id: {id} $")),
+            ("$Id:a  $", Some("$Id: {id} $")),
+            ("$Id: a $", Some("$Id: {id} $")),
+            ("$Id: a b $", None),
+            ("$Id:   $", None),
+            ("$Id: a b $Id$", None),
+            ("$Id: a b $$Id$", Some("$Id: a b $$Id: {id} $")),
+            ("$Id: \n$Id$", Some("$Id: \n$Id: {id} $")),
+            ("$Id: \n$$Id$", None),
+            ("$Id:x", None),
+            ("$$Id$", None),
+            ("$$$Id$", Some("$$$Id: {id} $")),
+            ("$I$Id$", None),
+            ("$Ix$Id$", Some("$Ix$Id: {id} $")),
+            ("$Idx$Id$", Some("$Idx$Id: {id} $")),
+            ("$Id$$$Id$", Some("$Id: {id} $$$Id$")),
+        ] {
+            let changed = ident::apply(input.as_bytes(), gix_hash::Kind::Sha1, &mut buf)?;
+            match expected {
+                Some(expected) => {
+                    assert!(changed, "{input:?}");
+                    let id = gix_object::compute_hash(gix_hash::Kind::Sha1, gix_object::Kind::Blob, input.as_bytes());
+                    assert_eq!(buf.as_bstr(), expected.replace("{id}", &id.to_string()), "{input:?}");
+                }
+                None => assert!(!changed, "{input:?} is left as is"),
+            }
+        }
+        Ok(())
+    }
+
     #[test]
     fn round_trips() -> crate::Result {
         let mut buf = Vec::new();
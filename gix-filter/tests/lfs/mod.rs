@@ -0,0 +1,86 @@
+use bstr::ByteSlice;
+use gix_filter::lfs::{decode, Pointer};
+
+const OID: &str = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+
+#[test]
+fn pointer_round_trip() -> crate::Result {
+    let pointer = Pointer::new(OID, 12345);
+    let buf = pointer.to_bytes();
+    assert_eq!(
+        buf.as_bstr(),
+        format!("version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\nsize 12345\n"),
+        "this is the canonical format, with keys sorted"
+    );
+    assert_eq!(Pointer::from_bytes(&buf)?, pointer);
+    Ok(())
+}
+
+#[test]
+fn extensions_are_sorted_with_all_other_keys() -> crate::Result {
+    let mut pointer = Pointer::new(OID, 0);
+    pointer
+        .extensions
+        .push(("ext-0-foo".into(), format!("sha256:{OID}").into()));
+    let buf = pointer.to_bytes();
+    assert_eq!(
+        buf.as_bstr(),
+        format!("version https://git-lfs.github.com/spec/v1\next-0-foo sha256:{OID}\noid sha256:{OID}\nsize 0\n")
+    );
+    assert_eq!(Pointer::from_bytes(&buf)?, pointer);
+    Ok(())
+}
+
+#[test]
+fn legacy_versions_are_accepted() -> crate::Result {
+    let pointer = Pointer::from_bytes(
+        format!("version https://hawser.github.com/spec/v1\noid sha256:{OID}\nsize 5\n").as_bytes(),
+    )?;
+    assert_eq!(pointer, Pointer::new(OID, 5));
+    Ok(())
+}
+
+#[test]
+fn non_pointers_are_rejected() {
+    for (input, expected) in [
+        ("hello world\n".to_string(), "Version"),
+        (
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\n"),
+            "MissingKey",
+        ),
+        (
+            "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 1\n".into(),
+            "Oid",
+        ),
+        (
+            format!("version https://git-lfs.github.com/spec/v1\noid sha1:{OID}\nsize 1\n"),
+            "Oid",
+        ),
+        (
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\nsize -1\n"),
+            "Size",
+        ),
+        (
+            format!("version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\nsize\n"),
+            "Line",
+        ),
+        (
+            format!(
+                "version https://git-lfs.github.com/spec/v1\noid sha256:{OID}\nsize 1\n{}",
+                "x".repeat(1024)
+            ),
+            "TooLarge",
+        ),
+    ] {
+        let err = Pointer::from_bytes(input.as_bytes()).expect_err("invalid");
+        let actual = match err {
+            decode::Error::TooLarge { .. } => "TooLarge",
+            decode::Error::Version => "Version",
+            decode::Error::Line { .. } => "Line",
+            decode::Error::Oid { .. } => "Oid",
+            decode::Error::Size { .. } => "Size",
+            decode::Error::MissingKey { .. } => "MissingKey",
+        };
+        assert_eq!(actual, expected, "{input:?}");
+    }
+}
@@ -1,6 +1,6 @@
 use std::io::Read;
 
-use bstr::ByteSlice;
+use bstr::{BStr, ByteSlice};
 use gix_filter::pipeline::CrlfRoundTripCheck;
 
 use crate::{driver::apply::driver_with_process, pipeline::pipeline};
@@ -37,7 +37,7 @@ fn all_stages() -> gix_testtools::Result {
     out.read_to_end(&mut buf)?;
     assert_eq!(
         buf.as_bstr(),
-        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0$",
+        "➡a\r\n➡b\r\n➡$Id: 2188d1cdee2b93a80084b61af431a49d21bc7cc0 $",
         "the buffer shows that a lot of transformations were applied"
     );
     Ok(())
@@ -68,7 +68,7 @@ fn all_stages_no_filter() -> gix_testtools::Result {
     let buf = out.as_bytes().expect("no filter process");
     assert_eq!(
         buf.as_bstr(),
-        "$Id: a77d7acbc809ac8df987a769221c83137ba1b9f9$a\r\nb\r\n",
+        "$Id: a77d7acbc809ac8df987a769221c83137ba1b9f9 $a\r\nb\r\n",
         "the buffer shows that a lot of transformations were applied"
     );
     Ok(())
@@ -98,3 +98,77 @@ fn no_filter() -> gix_testtools::Result {
     assert_eq!(actual.as_ptr(), input.as_ptr(), "…which means it's exactly the same");
     Ok(())
 }
+
+#[test]
+fn lfs_pointers_are_resolved() -> gix_testtools::Result {
+    struct Store;
+    impl gix_filter::lfs::Resolve for Store {
+        fn resolve(
+            &self,
+            pointer: &gix_filter::lfs::Pointer,
+            rela_path: &BStr,
+            out: &mut Vec<u8>,
+        ) -> Result<bool, Box<dyn std::error::Error + Send + Sync + 'static>> {
+            if pointer.size == 0 {
+                return Ok(false);
+            }
+            out.extend_from_slice(format!("content of {rela_path} with {} bytes", pointer.size).as_bytes());
+            Ok(true)
+        }
+    }
+
+    let (mut cache, mut pipe) = pipeline("lfs", || {
+        (vec![], Vec::new(), CrlfRoundTripCheck::Skip, Default::default())
+    })?;
+    pipe.options_mut().lfs_resolver = Some(std::sync::Arc::new(Store));
+
+    let oid = "4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+    let pointer = gix_filter::lfs::Pointer::new(oid, 7).to_bytes();
+    let mut attributes = |path: &BStr, attrs: &mut gix_attributes::search::Outcome| {
+        cache
+            .at_entry(path, Some(false), &gix_object::find::Never)
+            .expect("cannot fail")
+            .matching_attributes(attrs);
+    };
+
+    let out = pipe.convert_to_worktree(
+        &pointer,
+        "large.bin".into(),
+        &mut attributes,
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    assert!(out.is_changed());
+    assert_eq!(
+        out.as_bytes().expect("no filter process").as_bstr(),
+        "content of large.bin with 7 bytes"
+    );
+    drop(out);
+
+    let out = pipe.convert_to_worktree(
+        &pointer,
+        "other.txt".into(),
+        &mut attributes,
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    assert!(!out.is_changed(), "only paths with `filter=lfs` are resolved");
+    drop(out);
+
+    let empty = gix_filter::lfs::Pointer::new(oid, 0).to_bytes();
+    let out = pipe.convert_to_worktree(
+        &empty,
+        "empty.bin".into(),
+        &mut attributes,
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    assert!(!out.is_changed(), "the resolver may decide to keep the pointer");
+    drop(out);
+
+    let out = pipe.convert_to_worktree(
+        b"not a pointer",
+        "file.bin".into(),
+        &mut attributes,
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    assert!(!out.is_changed(), "content that isn't a pointer is left alone");
+    Ok(())
+}
@@ -0,0 +1,465 @@
+//! End-of-line conversions for all combinations of `core.autocrlf`, `core.eol` and the `text`, `eol` and `-text` attributes,
+//! modelled after the matrix in `t0027-auto-crlf.sh` of the `git` test-suite.
+use std::{io::Read, path::Path};
+
+use bstr::{BStr, ByteSlice};
+use gix_filter::{
+    eol,
+    pipeline::{convert::to_git, CrlfRoundTripCheck},
+};
+
+use crate::pipeline::pipeline;
+
+/// Native line endings, used when neither `core.autocrlf` nor `core.eol` say otherwise.
+const NL: &str = if cfg!(windows) { "CRLF" } else { "LF" };
+
+fn content(name: &str) -> &'static [u8] {
+    match name {
+        "LF" => b"$Id: 0000000000000000000000000000000000000000 $\nLINEONE\nLINETWO\nLINETHREE",
+        "CRLF" => b"$Id: 0000000000000000000000000000000000000000 $\r\nLINEONE\r\nLINETWO\r\nLINETHREE",
+        "CRLF_mix_LF" => b"$Id: 0000000000000000000000000000000000000000 $\nLINEONE\r\nLINETWO\nLINETHREE",
+        "LF_mix_CR" => b"$Id: 0000000000000000000000000000000000000000 $\nLINEONE\nLINETWO\rLINETHREE",
+        "CRLF_mix_CR" => b"$Id: 0000000000000000000000000000000000000000 $\r\nLINEONE\r\nLINETWO\rLINETHREE",
+        "CRLF_nul" => b"$Id: 0000000000000000000000000000000000000000 $\r\nLINEONE\0\r\nLINETWO\r\nLINETHREE",
+        "LF_nul" => b"$Id: 0000000000000000000000000000000000000000 $\nLINEONE\0\nLINETWO\nLINETHREE",
+        unknown => unreachable!("unknown content {unknown}"),
+    }
+}
+
+fn auto_crlf(value: &str) -> eol::AutoCrlf {
+    match value {
+        "true" => eol::AutoCrlf::Enabled,
+        "input" => eol::AutoCrlf::Input,
+        "false" => eol::AutoCrlf::Disabled,
+        unknown => unreachable!("unknown core.autocrlf value {unknown}"),
+    }
+}
+
+fn core_eol(value: &str) -> Option<eol::Mode> {
+    match value {
+        "" | "native" => None,
+        "lf" => Some(eol::Mode::Lf),
+        "crlf" => Some(eol::Mode::CrLf),
+        unknown => unreachable!("unknown core.eol value {unknown}"),
+    }
+}
+
+struct Matrix {
+    cache: gix_worktree::Stack,
+    pipe: gix_filter::Pipeline,
+}
+
+impl Matrix {
+    fn new(crlf: &str, ceol: &str) -> gix_testtools::Result<Self> {
+        let (cache, pipe) = pipeline("eol-matrix", || {
+            (
+                vec![],
+                Vec::new(),
+                CrlfRoundTripCheck::Fail,
+                eol::Configuration {
+                    auto_crlf: auto_crlf(crlf),
+                    eol: core_eol(ceol),
+                },
+            )
+        })?;
+        Ok(Matrix { cache, pipe })
+    }
+
+    fn checkout(&mut self, attr: &str, name: &str) -> gix_testtools::Result<Vec<u8>> {
+        let cache = &mut self.cache;
+        let rela_path = format!("{attr}_{name}");
+        let mut out = self.pipe.convert_to_worktree(
+            content(name),
+            rela_path.as_str().into(),
+            &mut |path, attrs| {
+                cache
+                    .at_entry(path, Some(false), &gix_object::find::Never)
+                    .expect("cannot fail")
+                    .matching_attributes(attrs);
+            },
+            gix_filter::driver::apply::Delay::Forbid,
+        )?;
+        let mut buf = Vec::new();
+        out.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn checkin(&mut self, attr: &str, name: &str, index: Option<&str>) -> Result<Vec<u8>, to_git::Error> {
+        let cache = &mut self.cache;
+        let rela_path = format!("{attr}_{name}");
+        let mut out = self.pipe.convert_to_git(
+            content(name),
+            Path::new(&rela_path),
+            &mut |path, attrs| {
+                cache
+                    .at_entry(path, Some(false), &gix_object::find::Never)
+                    .expect("cannot fail")
+                    .matching_attributes(attrs);
+            },
+            &mut |buf| {
+                Ok(index.map(|name| {
+                    buf.clear();
+                    buf.extend_from_slice(content(name));
+                }))
+            },
+        )?;
+        let mut buf = Vec::new();
+        out.read_to_end(&mut buf)?;
+        Ok(buf)
+    }
+}
+
+fn assert_contents(actual: &[u8], expected: &str, context: std::fmt::Arguments<'_>) {
+    assert_eq!(
+        actual.as_bstr(),
+        BStr::new(content(expected)),
+        "{context}: expected content of {expected}"
+    );
+}
+
+fn checkout_files(
+    attr: &str,
+    crlf: &str,
+    ceol: &str,
+    [lf, crlf_out, crlf_mix_lf, lf_mix_cr, lf_nul]: [&str; 5],
+) -> gix_testtools::Result {
+    let mut matrix = Matrix::new(crlf, ceol)?;
+    for (name, expected) in [
+        ("LF", lf),
+        ("CRLF", crlf_out),
+        ("CRLF_mix_LF", crlf_mix_lf),
+        ("LF_mix_CR", lf_mix_cr),
+        ("LF_nul", lf_nul),
+    ] {
+        let actual = matrix.checkout(attr, name)?;
+        assert_contents(
+            &actual,
+            expected,
+            format_args!("checkout of {name} with attr={attr:?} core.autocrlf={crlf} core.eol={ceol:?}"),
+        );
+    }
+    Ok(())
+}
+
+fn check_files_in_repo(
+    attr: &str,
+    crlf: &str,
+    index: Option<&str>,
+    [lf, crlf_out, crlf_mix_lf, lf_mix_cr, crlf_nul]: [&str; 5],
+) -> gix_testtools::Result {
+    let mut matrix = Matrix::new(crlf, "")?;
+    for (name, expected) in [
+        ("LF", lf),
+        ("CRLF", crlf_out),
+        ("CRLF_mix_LF", crlf_mix_lf),
+        ("LF_mix_CR", lf_mix_cr),
+        ("CRLF_nul", crlf_nul),
+    ] {
+        matrix.pipe.options_mut().crlf_roundtrip_check = CrlfRoundTripCheck::Skip;
+        let actual = matrix.checkin(attr, name, index)?;
+        assert_contents(
+            &actual,
+            expected,
+            format_args!("check-in of {name} with attr={attr:?} core.autocrlf={crlf} index={index:?}"),
+        );
+    }
+    Ok(())
+}
+
+/// Each warning is `LF_CRLF` if `LF` would be replaced by `CRLF`, `CRLF_LF` if `CRLF` would be replaced by `LF`, or empty.
+fn commit_check_warn(
+    attr: &str,
+    crlf: &str,
+    [lf, crlf_out, crlf_mix_lf, lf_mix_cr, crlf_nul]: [&str; 5],
+) -> gix_testtools::Result {
+    let mut matrix = Matrix::new(crlf, "")?;
+    for (name, expected) in [
+        ("LF", lf),
+        ("CRLF", crlf_out),
+        ("CRLF_mix_LF", crlf_mix_lf),
+        ("LF_mix_CR", lf_mix_cr),
+        ("CRLF_nul", crlf_nul),
+    ] {
+        let actual = match matrix.checkin(attr, name, None) {
+            Ok(_) => "",
+            Err(to_git::Error::Eol(eol::convert_to_git::Error::RoundTrip { msg, .. })) => match msg {
+                "LF would be replaced by CRLF" => "LF_CRLF",
+                "CRLF would be replaced by LF" => "CRLF_LF",
+                unknown => unreachable!("unknown round-trip message {unknown}"),
+            },
+            Err(err) => return Err(err.into()),
+        };
+        assert_eq!(
+            actual, expected,
+            "round-trip warning when adding {name} with attr={attr:?} core.autocrlf={crlf}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn checkout_without_attributes() -> gix_testtools::Result {
+    for ceol in ["", "lf", "crlf", "native"] {
+        checkout_files(
+            "none",
+            "false",
+            ceol,
+            ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+        checkout_files(
+            "none",
+            "true",
+            ceol,
+            ["CRLF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+        checkout_files(
+            "none",
+            "input",
+            ceol,
+            ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn checkout_with_attributes_that_override_configuration() -> gix_testtools::Result {
+    for crlf in ["true", "false", "input"] {
+        for ceol in ["", "lf", "crlf", "native"] {
+            // -text overrides core.autocrlf and core.eol
+            checkout_files(
+                "binary",
+                crlf,
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+            checkout_files(
+                "binarylf",
+                crlf,
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+            checkout_files(
+                "binarycrlf",
+                crlf,
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+            // text and eol=crlf or eol=lf override core.autocrlf and core.eol
+            checkout_files(
+                "textlf",
+                crlf,
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+            checkout_files(
+                "textcrlf",
+                crlf,
+                ceol,
+                ["CRLF", "CRLF", "CRLF", "CRLF_mix_CR", "CRLF_nul"],
+            )?;
+            // eol=XXX implies text
+            checkout_files("lf", crlf, ceol, ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"])?;
+            checkout_files("crlf", crlf, ceol, ["CRLF", "CRLF", "CRLF", "CRLF_mix_CR", "CRLF_nul"])?;
+            // text=auto with eol=XXX only converts files that are safe to convert
+            checkout_files(
+                "autolf",
+                crlf,
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+            checkout_files(
+                "autocrlf",
+                crlf,
+                ceol,
+                ["CRLF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn checkout_with_text_attribute_uses_configuration() -> gix_testtools::Result {
+    for ceol in ["", "lf", "crlf", "native"] {
+        // core.autocrlf = true overrides core.eol
+        checkout_files(
+            "text",
+            "true",
+            ceol,
+            ["CRLF", "CRLF", "CRLF", "CRLF_mix_CR", "CRLF_nul"],
+        )?;
+        checkout_files(
+            "auto",
+            "true",
+            ceol,
+            ["CRLF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+        // core.autocrlf = input overrides core.eol
+        checkout_files(
+            "text",
+            "input",
+            ceol,
+            ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+        checkout_files(
+            "auto",
+            "input",
+            ceol,
+            ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+    }
+
+    // core.autocrlf = false uses core.eol
+    checkout_files(
+        "text",
+        "false",
+        "crlf",
+        ["CRLF", "CRLF", "CRLF", "CRLF_mix_CR", "CRLF_nul"],
+    )?;
+    checkout_files(
+        "text",
+        "false",
+        "lf",
+        ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+    )?;
+    checkout_files(
+        "auto",
+        "false",
+        "crlf",
+        ["CRLF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+    )?;
+    checkout_files(
+        "auto",
+        "false",
+        "lf",
+        ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+    )?;
+    for ceol in ["", "native"] {
+        if NL == "CRLF" {
+            checkout_files(
+                "text",
+                "false",
+                ceol,
+                ["CRLF", "CRLF", "CRLF", "CRLF_mix_CR", "CRLF_nul"],
+            )?;
+        } else {
+            checkout_files(
+                "text",
+                "false",
+                ceol,
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+            )?;
+        }
+        checkout_files(
+            "auto",
+            "false",
+            ceol,
+            [NL, "CRLF", "CRLF_mix_LF", "LF_mix_CR", "LF_nul"],
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn files_in_repo_after_adding_them() -> gix_testtools::Result {
+    check_files_in_repo(
+        "none",
+        "false",
+        None,
+        ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "CRLF_nul"],
+    )?;
+    for crlf in ["true", "input"] {
+        check_files_in_repo("none", crlf, None, ["LF", "LF", "LF", "LF_mix_CR", "CRLF_nul"])?;
+    }
+    for crlf in ["true", "false", "input"] {
+        for attr in ["binary", "binarylf", "binarycrlf"] {
+            check_files_in_repo(attr, crlf, None, ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "CRLF_nul"])?;
+        }
+        for attr in ["text", "textlf", "textcrlf", "lf", "crlf"] {
+            check_files_in_repo(attr, crlf, None, ["LF", "LF", "LF", "LF_mix_CR", "LF_nul"])?;
+        }
+        for attr in ["auto", "autolf", "autocrlf"] {
+            check_files_in_repo(attr, crlf, None, ["LF", "LF", "LF", "LF_mix_CR", "CRLF_nul"])?;
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn files_in_repo_keep_crlf_in_auto_mode_if_the_index_has_crlf() -> gix_testtools::Result {
+    for index in ["CRLF", "CRLF_mix_LF"] {
+        for crlf in ["true", "input"] {
+            check_files_in_repo(
+                "none",
+                crlf,
+                Some(index),
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "CRLF_nul"],
+            )?;
+        }
+        for crlf in ["true", "false", "input"] {
+            check_files_in_repo(
+                "auto",
+                crlf,
+                Some(index),
+                ["LF", "CRLF", "CRLF_mix_LF", "LF_mix_CR", "CRLF_nul"],
+            )?;
+            check_files_in_repo("text", crlf, Some(index), ["LF", "LF", "LF", "LF_mix_CR", "LF_nul"])?;
+        }
+    }
+
+    for index in ["LF", "CRLF_nul", "LF_mix_CR"] {
+        check_files_in_repo(
+            "auto",
+            "false",
+            Some(index),
+            ["LF", "LF", "LF", "LF_mix_CR", "CRLF_nul"],
+        )?;
+    }
+    Ok(())
+}
+
+#[test]
+fn round_trip_warnings_when_adding_files() -> gix_testtools::Result {
+    let (wilc, wicl, wamix) = if NL == "CRLF" {
+        ("LF_CRLF", "", "LF_CRLF")
+    } else {
+        ("", "CRLF_LF", "CRLF_LF")
+    };
+    commit_check_warn("none", "false", ["", "", "", "", ""])?;
+    commit_check_warn("none", "true", ["LF_CRLF", "", "LF_CRLF", "", ""])?;
+    commit_check_warn("none", "input", ["", "CRLF_LF", "CRLF_LF", "", ""])?;
+
+    commit_check_warn("auto", "false", [wilc, wicl, wamix, "", ""])?;
+    commit_check_warn("auto", "true", ["LF_CRLF", "", "LF_CRLF", "", ""])?;
+    commit_check_warn("auto", "input", ["", "CRLF_LF", "CRLF_LF", "", ""])?;
+
+    commit_check_warn("text", "false", [wilc, wicl, wamix, wilc, wicl])?;
+    commit_check_warn("text", "true", ["LF_CRLF", "", "LF_CRLF", "LF_CRLF", ""])?;
+    commit_check_warn("text", "input", ["", "CRLF_LF", "CRLF_LF", "", "CRLF_LF"])?;
+
+    for crlf in ["true", "false", "input"] {
+        commit_check_warn("binary", crlf, ["", "", "", "", ""])?;
+    }
+    Ok(())
+}
+
+#[test]
+fn trailing_eof_character_does_not_make_a_file_binary() -> gix_testtools::Result {
+    let mut matrix = Matrix::new("true", "")?;
+    let mut out = matrix.pipe.convert_to_worktree(
+        b"a\n\x1a",
+        "auto_eof".into(),
+        &mut |_, _| {},
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    let mut buf = Vec::new();
+    out.read_to_end(&mut buf)?;
+    assert_eq!(
+        buf.as_bstr(),
+        "a\r\n\x1a",
+        "with only one printable character, the trailing EOF would otherwise make it binary"
+    );
+    Ok(())
+}
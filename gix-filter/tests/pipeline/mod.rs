@@ -4,6 +4,7 @@ use gix_filter::eol;
 
 mod convert_to_git;
 mod convert_to_worktree;
+mod eol_matrix;
 
 #[test]
 fn default() -> crate::Result {
@@ -65,6 +66,7 @@ fn pipeline(
             encodings_with_roundtrip_check,
             crlf_roundtrip_check,
             object_hash: gix_hash::Kind::Sha1,
+            lfs_resolver: None,
         },
     );
     Ok((cache, pipe))
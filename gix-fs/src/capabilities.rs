@@ -1,5 +1,8 @@
-// TODO: tests
-use std::path::Path;
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 use crate::Capabilities;
 
@@ -55,6 +58,19 @@ impl Capabilities {
         }
     }
 
+    /// Like [`probe()`](Self::probe()), but determine all values by probing within `dir`, which can be any writable directory.
+    /// It's useful for directories of a worktree that may be located on a different filesystem than the repository itself.
+    ///
+    /// All errors are ignored and the respective value of `fallback` is used instead.
+    pub fn probe_dir(dir: &Path, fallback: Capabilities) -> Self {
+        Capabilities {
+            symlink: Self::probe_symlink(dir).unwrap_or(fallback.symlink),
+            ignore_case: Self::probe_ignore_case_by_creating_file(dir).unwrap_or(fallback.ignore_case),
+            precompose_unicode: Self::probe_precompose_unicode(dir).unwrap_or(fallback.precompose_unicode),
+            executable_bit: Self::probe_file_mode(dir).unwrap_or(fallback.executable_bit),
+        }
+    }
+
     #[cfg(unix)]
     fn probe_file_mode(root: &Path) -> std::io::Result<bool> {
         use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
@@ -86,6 +102,27 @@ impl Capabilities {
         })
     }
 
+    fn probe_ignore_case_by_creating_file(dir: &Path) -> std::io::Result<bool> {
+        let test_path = dir.join("_test_ignore_case");
+        std::fs::OpenOptions::new()
+            .create_new(true)
+            .write(true)
+            .open(&test_path)?;
+        let res = dir
+            .join("_TEST_IGNORE_CASE")
+            .symlink_metadata()
+            .map(|_| true)
+            .or_else(|err| {
+                if err.kind() == std::io::ErrorKind::NotFound {
+                    Ok(false)
+                } else {
+                    Err(err)
+                }
+            });
+        std::fs::remove_file(test_path)?;
+        res
+    }
+
     fn probe_precompose_unicode(root: &Path) -> std::io::Result<bool> {
         let precomposed = "ä";
         let decomposed = "a\u{308}";
@@ -120,3 +157,69 @@ impl Capabilities {
         res
     }
 }
+
+/// Capabilities of all filesystems mounted within a worktree, for use when a worktree spans multiple filesystems,
+/// like an `exFAT` drive mounted into one of its directories.
+///
+/// Each filesystem is identified by the device id of its mount point and probed by writing files into the first
+/// directory it is seen in, while the filesystem of the root is assumed to have the capabilities it was created with.
+/// On platforms without device ids, like Windows, the capabilities of the root are used everywhere.
+///
+/// Clones share the probed capabilities, which makes it usable from multiple threads.
+#[derive(Debug, Clone)]
+pub struct PerMountPoint {
+    root: Capabilities,
+    root_device: Option<u64>,
+    by_device: Arc<Mutex<HashMap<u64, Capabilities>>>,
+}
+
+impl PerMountPoint {
+    /// Create a new instance with `root` being the capabilities of the filesystem that `root_dir`, usually the worktree
+    /// directory, is located on.
+    ///
+    /// If the device of `root_dir` can't be determined, the capabilities of `root` are used for all paths.
+    pub fn new(root: Capabilities, root_dir: &Path) -> Self {
+        PerMountPoint {
+            root,
+            root_device: device_of(root_dir),
+            by_device: Default::default(),
+        }
+    }
+
+    /// Return the capabilities that `root_dir` was created with.
+    pub fn root(&self) -> Capabilities {
+        self.root
+    }
+
+    /// Return the capabilities of the filesystem that `dir` is located on, probing them in `dir` if the filesystem
+    /// wasn't seen before.
+    pub fn at(&self, dir: &Path) -> Capabilities {
+        match device_of(dir) {
+            Some(device) => self.at_device(device, dir),
+            None => self.root,
+        }
+    }
+
+    /// Like [`at()`](Self::at()), but use the known `device` id of `dir`, or of any file within it, to avoid
+    /// querying it again. `dir` is only accessed if the filesystem on `device` needs probing.
+    pub fn at_device(&self, device: u64, dir: &Path) -> Capabilities {
+        if self.root_device.map_or(true, |root_device| root_device == device) {
+            return self.root;
+        }
+        let mut by_device = self.by_device.lock().expect("no panics while probing");
+        *by_device
+            .entry(device)
+            .or_insert_with(|| Capabilities::probe_dir(dir, self.root))
+    }
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path).ok().map(|m| m.dev())
+}
+
+#[cfg(not(unix))]
+fn device_of(_path: &Path) -> Option<u64> {
+    None
+}
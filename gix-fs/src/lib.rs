@@ -30,7 +30,9 @@ pub struct Capabilities {
     /// out as files which contain the link as text.
     pub symlink: bool,
 }
-mod capabilities;
+///
+#[allow(clippy::empty_docs)]
+pub mod capabilities;
 
 mod snapshot;
 pub use snapshot::{FileSnapshot, SharedFileSnapshot, SharedFileSnapshotMut};
@@ -58,3 +58,21 @@ pub fn is_collision_error(err: &std::io::Error) -> bool {
 pub fn is_collision_error(err: &std::io::Error) -> bool {
     err.kind() == AlreadyExists || err.kind() == std::io::ErrorKind::PermissionDenied
 }
+
+#[cfg(not(windows))]
+/// Return true if `err` indicates that symlinks aren't supported by the filesystem that `link` was supposed to be created on,
+/// in which case the symlink can be stored as file containing the path to `original` instead.
+pub fn is_unsupported_error(err: &std::io::Error) -> bool {
+    // `EPERM` is what filesystems without symlink support, like FAT, return on Unix.
+    err.kind() == std::io::ErrorKind::Unsupported || err.raw_os_error() == Some(1)
+}
+
+#[cfg(windows)]
+/// Return true if `err` indicates that symlinks aren't supported by the filesystem that `link` was supposed to be created on,
+/// in which case the symlink can be stored as file containing the path to `original` instead.
+///
+/// This is typically the case if the developer mode isn't enabled, which is needed to create symlinks without elevated privileges.
+pub fn is_unsupported_error(err: &std::io::Error) -> bool {
+    const ERROR_PRIVILEGE_NOT_HELD: i32 = 1314;
+    err.kind() == std::io::ErrorKind::Unsupported || err.raw_os_error() == Some(ERROR_PRIVILEGE_NOT_HELD)
+}
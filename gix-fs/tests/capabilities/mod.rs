@@ -16,3 +16,42 @@ fn probe() {
         "there should be no left-over files after probing, found {entries:?}"
     );
 }
+
+#[test]
+fn probe_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let caps = gix_fs::Capabilities::probe_dir(dir.path(), Default::default());
+    assert_eq!(
+        std::fs::read_dir(dir.path()).unwrap().count(),
+        0,
+        "there should be no left-over files after probing"
+    );
+
+    std::fs::File::create(dir.path().join("config")).unwrap();
+    assert_eq!(
+        caps,
+        gix_fs::Capabilities::probe(dir.path()),
+        "the outcome is the same as when probing a git directory"
+    );
+}
+
+#[test]
+fn per_mount_point_uses_root_capabilities_on_the_same_filesystem() {
+    let dir = tempfile::tempdir().unwrap();
+    let sub_dir = dir.path().join("sub");
+    std::fs::create_dir(&sub_dir).unwrap();
+    let root = gix_fs::Capabilities {
+        precompose_unicode: true,
+        ignore_case: true,
+        executable_bit: false,
+        symlink: false,
+    };
+    let caps = gix_fs::capabilities::PerMountPoint::new(root, dir.path());
+    assert_eq!(
+        caps.at(&sub_dir),
+        root,
+        "no probing happens on the filesystem of the root"
+    );
+    assert_eq!(caps.root(), root);
+    assert_eq!(std::fs::read_dir(&sub_dir).unwrap().count(), 0);
+}
@@ -0,0 +1,110 @@
+use bstr::{BStr, ByteVec};
+
+use crate::{entry, extension, Entry, State};
+
+/// Conflicts
+impl State {
+    /// Return `true` if there is at least one entry that isn't in the [unconflicted](entry::Stage::Unconflicted) stage.
+    pub fn has_conflicts(&self) -> bool {
+        self.entries.iter().any(|e| e.stage_raw() != 0)
+    }
+
+    /// Return the entries of `path` at the [base](entry::Stage::Base), [ours](entry::Stage::Ours) and
+    /// [theirs](entry::Stage::Theirs) stages, in that order, or `None` if `path` isn't conflicted.
+    pub fn conflict(&self, path: &BStr) -> Option<[Option<&Entry>; 3]> {
+        let range = self.entry_range(path)?;
+        let entries = &self.entries[range];
+        entries.iter().any(|e| e.stage_raw() != 0).then(|| {
+            let mut out = [None, None, None];
+            for entry in entries {
+                if let Some(slot) = (entry.stage_raw() as usize)
+                    .checked_sub(1)
+                    .and_then(|idx| out.get_mut(idx))
+                {
+                    *slot = Some(entry);
+                }
+            }
+            out
+        })
+    }
+
+    /// Replace all entries of `path` with entries at the [base](entry::Stage::Base), [ours](entry::Stage::Ours) and
+    /// [theirs](entry::Stage::Theirs) stages as given by `(mode, id)` in `stages`, in that order, similar to what a merge
+    /// does if it can't resolve a conflict. Return `false` if all `stages` are `None`, which leaves `path` unchanged.
+    ///
+    /// The tree extension is invalidated along the way to `path`.
+    pub fn set_conflict(&mut self, path: &BStr, stages: [Option<(entry::Mode, gix_hash::ObjectId)>; 3]) -> bool {
+        if stages.iter().all(Option::is_none) {
+            return false;
+        }
+        self.remove_path(path);
+        let index = self.entry_index_by_path(path).expect_err("BUG: path was removed");
+        let path_start = self.path_backing.len();
+        self.path_backing.push_str(path);
+        let path = path_start..self.path_backing.len();
+        let entries = stages
+            .into_iter()
+            .zip(1u32..)
+            .filter_map(|(stage, stage_raw)| stage.map(|(mode, id)| (stage_raw, mode, id)))
+            .map(|(stage, mode, id)| Entry {
+                stat: entry::Stat::default(),
+                id,
+                flags: entry::Flags::from_bits_retain(stage << 12),
+                mode,
+                path: path.clone(),
+            })
+            .collect::<Vec<_>>();
+        self.entries.splice(index..index, entries);
+        true
+    }
+
+    /// Resolve the conflict of `path` by using its entry at `stage`, which is recorded in the resolve-undo extension.
+    /// Return `false` if `path` isn't conflicted or if it has no entry at `stage`.
+    ///
+    /// Note that the resulting entry has no `stat` information yet, it's expected to be checked out or refreshed.
+    pub fn resolve_conflict(&mut self, path: &BStr, stage: entry::Stage) -> bool {
+        let (mode, id) = match self
+            .conflict(path)
+            .and_then(|stages| (stage as usize).checked_sub(1).and_then(|idx| stages[idx]))
+        {
+            Some(entry) => (entry.mode, entry.id),
+            None => return false,
+        };
+        let idx = self.add_path(path, id, mode, entry::Stat::default());
+        self.entries[idx].flags.remove(entry::Flags::FSMONITOR_VALID);
+        true
+    }
+
+    /// Restore the conflicting stages of `path` as recorded in the resolve-undo extension when its conflict was resolved,
+    /// similar to `git update-index --unresolve` or `git checkout --merge`, and remove its record from the extension.
+    /// Return `false` if there was no such record, which leaves `path` unchanged.
+    pub fn unresolve(&mut self, path: &BStr) -> bool {
+        let paths = match self.resolve_undo.as_mut() {
+            Some(paths) => paths,
+            None => return false,
+        };
+        let resolved = match paths.iter().position(|p| p.name() == path) {
+            Some(pos) => paths.remove(pos),
+            None => return false,
+        };
+        if paths.is_empty() {
+            self.resolve_undo = None;
+        }
+        let stage = |stage: entry::Stage| resolved.stage(stage).map(|s| (s.mode(), s.id()));
+        self.set_conflict(
+            path,
+            [
+                stage(entry::Stage::Base),
+                stage(entry::Stage::Ours),
+                stage(entry::Stage::Theirs),
+            ],
+        )
+    }
+
+    /// Record `resolved` in the resolve-undo extension, replacing previous records of the same path.
+    pub(crate) fn record_resolve_undo(&mut self, resolved: extension::resolve_undo::ResolvePath) {
+        let paths = self.resolve_undo.get_or_insert_with(Default::default);
+        paths.retain(|p| p.name() != resolved.name());
+        paths.push(resolved);
+    }
+}
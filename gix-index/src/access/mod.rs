@@ -6,6 +6,7 @@ use filetime::FileTime;
 use crate::entry::{Stage, StageRaw};
 use crate::{entry, extension, AccelerateLookup, Entry, PathStorage, PathStorageRef, State, Version};
 
+mod conflict;
 // TODO: integrate this somehow, somewhere, depending on later usage.
 #[allow(dead_code)]
 mod sparse;
@@ -17,6 +18,14 @@ impl State {
         self.version
     }
 
+    /// Set the version to use when writing this state to disk.
+    ///
+    /// Note that [V2](Version::V2) and [V3](Version::V3) are chosen automatically when writing depending on whether
+    /// entries need extended flags, while [V4](Version::V4), which compresses paths, is always preserved.
+    pub fn set_version(&mut self, version: Version) {
+        self.version = version;
+    }
+
     /// Returns time at which the state was created, indicating its freshness compared to other files on disk.
     pub fn timestamp(&self) -> FileTime {
         self.timestamp
@@ -496,6 +505,110 @@ impl State {
         });
     }
 
+    /// Add an unconflicted entry for `path` with `id`, `mode` and `stat`, or update the one that exists, similar to `git add`,
+    /// and return its index in [`entries()`](Self::entries()).
+    ///
+    /// Entries of `path` in conflict stages are removed and recorded in the [resolve-undo](Self::resolve_undo()) extension.
+    /// Entries that can't coexist with `path`, i.e. files at its leading directories or entries inside of it as directory,
+    /// are removed as well. The tree extension is invalidated along the way to `path`.
+    /// As `stat` is expected to be fresh, the entry is marked [valid](entry::Flags::FSMONITOR_VALID) if there is a
+    /// [filesystem monitor](Self::fs_monitor()).
+    pub fn add_path(&mut self, path: &BStr, id: gix_hash::ObjectId, mode: entry::Mode, stat: entry::Stat) -> usize {
+        self.remove_directory_file_conflicts(path);
+        self.invalidate_tree_at(path);
+        if let Some(untracked) = self.untracked.as_mut() {
+            untracked.invalidate(path);
+        }
+        let index = match self.entry_range(path) {
+            Some(range) if range.len() == 1 && self.entries[range.start].stage_raw() == 0 => {
+                let entry = &mut self.entries[range.start];
+                entry.id = id;
+                entry.mode = mode;
+                entry.stat = stat;
+                entry.flags &= entry::Flags::ASSUME_VALID | entry::Flags::SKIP_WORKTREE | entry::Flags::EXTENDED;
+                if !entry.flags.contains(entry::Flags::SKIP_WORKTREE) {
+                    entry.flags.remove(entry::Flags::EXTENDED);
+                }
+                if self.fs_monitor.is_some() {
+                    entry.flags.insert(entry::Flags::FSMONITOR_VALID);
+                }
+                return range.start;
+            }
+            Some(range) => {
+                let stages = self
+                    .entries
+                    .drain(range.clone())
+                    .map(|e| (e.stage_raw(), e.mode, e.id))
+                    .collect::<Vec<_>>();
+                self.record_resolve_undo(extension::resolve_undo::ResolvePath::from_conflict(path, stages));
+                range.start
+            }
+            None => self.entry_index_by_path(path).expect_err("BUG: path isn't present"),
+        };
+
+        let path_start = self.path_backing.len();
+        self.path_backing.push_str(path);
+        self.entries.insert(
+            index,
+            Entry {
+                stat,
+                id,
+                flags: if self.fs_monitor.is_some() {
+                    entry::Flags::FSMONITOR_VALID
+                } else {
+                    entry::Flags::empty()
+                },
+                mode,
+                path: path_start..self.path_backing.len(),
+            },
+        );
+        index
+    }
+
+    /// Remove all entries of `path` in all stages, similar to `git rm --cached`, and return `true` if there was at least one.
+    ///
+    /// The tree extension is invalidated along the way to `path`.
+    pub fn remove_path(&mut self, path: &BStr) -> bool {
+        match self.entry_range(path) {
+            Some(range) => {
+                self.entries.drain(range);
+                self.invalidate_tree_at(path);
+                if let Some(untracked) = self.untracked.as_mut() {
+                    untracked.invalidate(path);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Remove entries that would conflict with a file at `path`, which are files at any of its leading directories, and
+    /// all entries within `path` as if it was a directory.
+    fn remove_directory_file_conflicts(&mut self, path: &BStr) {
+        let mut leading_dirs = path
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| **b == b'/')
+            .map(|(idx, _)| path[..idx].as_bstr())
+            .collect::<Vec<_>>();
+        leading_dirs.retain(|dir| self.entry_range(dir).is_some());
+        for dir in leading_dirs {
+            self.remove_path(dir);
+        }
+
+        let mut dir = path.to_owned();
+        dir.push(b'/');
+        if let Some(range) = self.prefixed_entries_range(dir.as_ref()) {
+            self.entries.drain(range);
+            self.invalidate_tree_at(path);
+        }
+    }
+
+    /// Mark all trees of the tree extension as invalid that contain `path`.
+    fn invalidate_tree_at(&mut self, path: &BStr) {
+        invalidate_tree(self.tree.as_mut(), path);
+    }
+
     /// Unconditionally sort entries as needed to perform lookups quickly.
     pub fn sort_entries(&mut self) {
         let path_backing = &self.path_backing;
@@ -519,6 +632,7 @@ impl State {
     /// Physically remove all entries for which `should_remove(idx, path, entry)` returns `true`, traversing them from first to last.
     ///
     /// Note that the memory used for the removed entries paths is not freed, as it's append-only.
+    /// The tree extension is invalidated along the way to each removed entry.
     ///
     /// ### Performance
     ///
@@ -527,15 +641,43 @@ impl State {
     pub fn remove_entries(&mut self, mut should_remove: impl FnMut(usize, &BStr, &mut Entry) -> bool) {
         let mut index = 0;
         let paths = &self.path_backing;
+        let tree = &mut self.tree;
         self.entries.retain_mut(|e| {
             let path = e.path_in(paths);
             let res = !should_remove(index, path, e);
+            if !res {
+                invalidate_tree(tree.as_mut(), path);
+            }
             index += 1;
             res
         });
     }
 }
 
+/// Mark all trees of `tree` as invalid that contain `path`.
+fn invalidate_tree(tree: Option<&mut extension::Tree>, path: &BStr) {
+    let mut tree = match tree {
+        Some(tree) => tree,
+        None => return,
+    };
+    tree.num_entries = None;
+    let mut components = path.split(|b| *b == b'/').peekable();
+    while let Some(component) = components.next() {
+        if components.peek().is_none() {
+            break;
+        }
+        tree = match tree
+            .children
+            .iter_mut()
+            .find(|child| child.name.as_slice() == component)
+        {
+            Some(child) => child,
+            None => break,
+        };
+        tree.num_entries = None;
+    }
+}
+
 /// Extensions
 impl State {
     /// Access the `tree` extension.
@@ -554,6 +696,16 @@ impl State {
     pub fn untracked(&self) -> Option<&extension::UntrackedCache> {
         self.untracked.as_ref()
     }
+    /// Obtain the untracked extension mutably, to update it after a directory walk.
+    pub fn untracked_mut(&mut self) -> Option<&mut extension::UntrackedCache> {
+        self.untracked.as_mut()
+    }
+    /// Set the untracked extension to `untracked`, or remove it if `None`, and return the previous one.
+    ///
+    /// This is useful to use the cache during a directory walk which also needs access to this index.
+    pub fn set_untracked(&mut self, untracked: Option<extension::UntrackedCache>) -> Option<extension::UntrackedCache> {
+        std::mem::replace(&mut self.untracked, untracked)
+    }
     /// Obtain the fsmonitor extension.
     pub fn fs_monitor(&self) -> Option<&extension::FsMonitor> {
         self.fs_monitor.as_ref()
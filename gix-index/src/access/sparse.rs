@@ -1,3 +1,9 @@
+use bstr::{BStr, BString, ByteSlice};
+use gix_object::FindExt;
+use gix_traverse::tree::breadthfirst;
+
+use crate::{entry, extension, init::from_tree::CollectEntries, Entry, State};
+
 /// Configuration related to sparse indexes.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Options {
@@ -57,3 +63,165 @@ pub enum Mode {
     /// index with all entries, non is excluded, `.git/info/sparse-checkout` file is not considered, a regular index.
     Disabled,
 }
+
+/// Sparse directories
+impl State {
+    /// Replace the [sparse directory entry](entry::Mode::DIR) at `dir`, with or without trailing slash, with entries for all
+    /// files of its tree, recursively, which are read from `objects`. Return `false` if there was no such entry.
+    ///
+    /// All new entries are marked with [`SKIP_WORKTREE`](entry::Flags::SKIP_WORKTREE), just like the directory was,
+    /// so this is what needs to be done before changing or reading individual entries below `dir`.
+    pub fn expand_sparse_directory(
+        &mut self,
+        dir: &BStr,
+        objects: impl gix_object::Find,
+    ) -> Result<bool, breadthfirst::Error> {
+        let mut dir_with_slash = dir.to_owned();
+        if !dir_with_slash.ends_with(b"/") {
+            dir_with_slash.push(b'/');
+        }
+        match self.entry_index_by_path_and_stage(dir_with_slash.as_ref(), entry::Stage::Unconflicted) {
+            Some(idx) if self.entries[idx].mode.is_sparse() => {
+                self.expand_sparse_directory_at(idx, &objects)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Expand all [sparse directory entries](entry::Mode::DIR) using `objects` to obtain a full index, and return the amount
+    /// of expanded directories. See [`expand_sparse_directory()`](Self::expand_sparse_directory()) for details.
+    ///
+    /// Afterwards, [`is_sparse()`](Self::is_sparse()) returns `false`.
+    pub fn expand_sparse_directories(&mut self, objects: impl gix_object::Find) -> Result<usize, breadthfirst::Error> {
+        let mut num_expanded = 0;
+        for idx in (0..self.entries.len()).rev() {
+            if self.entries[idx].mode.is_sparse() {
+                self.expand_sparse_directory_at(idx, &objects)?;
+                num_expanded += 1;
+            }
+        }
+        self.is_sparse = false;
+        Ok(num_expanded)
+    }
+
+    /// Replace all entries below `dir`, without trailing slash, with a single [sparse directory entry](entry::Mode::DIR)
+    /// and return `true`, or return `false` if that isn't possible.
+    ///
+    /// This is only possible if all entries are unconflicted and marked with [`SKIP_WORKTREE`](entry::Flags::SKIP_WORKTREE),
+    /// and if the tree extension has a valid tree for `dir`, which is used as the id of the new entry.
+    pub fn collapse_directory(&mut self, dir: &BStr) -> bool {
+        let mut dir_with_slash = dir.to_owned();
+        dir_with_slash.push(b'/');
+        let range = match self.prefixed_entries_range(dir_with_slash.as_ref()) {
+            Some(range) => range,
+            None => return false,
+        };
+        let tree = match self.tree.as_ref().and_then(|tree| find_tree(tree, dir)) {
+            Some(tree) => tree,
+            None => return false,
+        };
+        let is_collapsible = tree.num_entries == Some(range.len() as u32)
+            && self.entries[range.clone()].iter().all(|entry| {
+                entry.stage_raw() == 0 && entry.flags.contains(entry::Flags::SKIP_WORKTREE) && !entry.mode.is_sparse()
+            });
+        if !is_collapsible {
+            return false;
+        }
+
+        let id = tree.id;
+        let path_start = self.path_backing.len();
+        self.path_backing.extend_from_slice(&dir_with_slash);
+        self.entries.splice(
+            range,
+            Some(Entry {
+                stat: Default::default(),
+                id,
+                flags: entry::Flags::SKIP_WORKTREE | entry::Flags::EXTENDED,
+                mode: entry::Mode::DIR,
+                path: path_start..self.path_backing.len(),
+            }),
+        );
+        self.is_sparse = true;
+        true
+    }
+
+    /// Collapse all directories whose entries are all [skipped in the worktree](entry::Flags::SKIP_WORKTREE) into
+    /// [sparse directory entries](entry::Mode::DIR), as `git` does for a sparse index in cone mode, and return the
+    /// amount of collapsed directories.
+    ///
+    /// The tree extension is used to learn about directories and their trees, and only directories with a valid tree
+    /// can be collapsed. See [`collapse_directory()`](Self::collapse_directory()) for details.
+    pub fn collapse_skipped_directories(&mut self) -> usize {
+        let mut dirs = Vec::new();
+        if let Some(tree) = self.tree.as_ref() {
+            let mut path = BString::default();
+            collect_dirs(tree, &mut path, &mut dirs);
+        }
+
+        let mut num_collapsed = 0;
+        let mut last_collapsed: Option<BString> = None;
+        for dir in dirs {
+            if matches!(&last_collapsed, Some(parent) if dir.starts_with(parent) && dir.get(parent.len()) == Some(&b'/'))
+            {
+                continue;
+            }
+            if self.collapse_directory(dir.as_ref()) {
+                num_collapsed += 1;
+                last_collapsed = Some(dir);
+            }
+        }
+        num_collapsed
+    }
+
+    fn expand_sparse_directory_at(
+        &mut self,
+        idx: usize,
+        objects: &impl gix_object::Find,
+    ) -> Result<(), breadthfirst::Error> {
+        let dir_entry = &self.entries[idx];
+        let dir = dir_entry.path(self);
+        let dir = dir.strip_suffix(b"/").unwrap_or(dir).as_bstr().to_owned();
+        let mut buf = Vec::new();
+        let root = objects.find_tree_iter(&dir_entry.id, &mut buf)?;
+        let mut delegate = CollectEntries::new_at(dir);
+        breadthfirst(root, breadthfirst::State::default(), objects, &mut delegate)?;
+
+        let CollectEntries {
+            mut entries,
+            path_backing,
+            ..
+        } = delegate;
+        entries.sort_by(|a, b| Entry::cmp_filepaths(a.path_in(&path_backing), b.path_in(&path_backing)));
+
+        let path_offset = self.path_backing.len();
+        self.path_backing.extend_from_slice(&path_backing);
+        for entry in &mut entries {
+            entry.path = entry.path.start + path_offset..entry.path.end + path_offset;
+            entry.flags = entry::Flags::SKIP_WORKTREE | entry::Flags::EXTENDED;
+        }
+        self.entries.splice(idx..idx + 1, entries);
+        Ok(())
+    }
+}
+
+fn find_tree<'a>(mut tree: &'a extension::Tree, dir: &BStr) -> Option<&'a extension::Tree> {
+    for component in dir.split(|b| *b == b'/') {
+        tree = tree.children.iter().find(|child| child.name.as_slice() == component)?;
+    }
+    Some(tree)
+}
+
+/// Collect the paths of all sub-trees of `tree`, depth-first, so that parent directories come before their children.
+fn collect_dirs(tree: &extension::Tree, path: &mut BString, out: &mut Vec<BString>) {
+    for child in &tree.children {
+        let len = path.len();
+        if !path.is_empty() {
+            path.push(b'/');
+        }
+        path.extend_from_slice(&child.name);
+        out.push(path.clone());
+        collect_dirs(child, path, out);
+        path.truncate(len);
+    }
+}
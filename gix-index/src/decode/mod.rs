@@ -242,25 +242,27 @@ impl State {
         } = ext;
         is_sparse |= is_sparse_from_ext;
 
-        Ok((
-            State {
-                object_hash,
-                timestamp,
-                version,
-                entries,
-                path_backing,
-                is_sparse,
+        let mut state = State {
+            object_hash,
+            timestamp,
+            version,
+            entries,
+            path_backing,
+            is_sparse,
 
-                end_of_index_at_decode_time: end_of_index,
-                offset_table_at_decode_time: offset_table,
-                tree,
-                link,
-                resolve_undo,
-                untracked,
-                fs_monitor,
-            },
-            checksum,
-        ))
+            end_of_index_at_decode_time: end_of_index,
+            offset_table_at_decode_time: offset_table,
+            tree,
+            link,
+            resolve_undo,
+            untracked,
+            fs_monitor,
+        };
+        // The entries of split indices are only complete once the shared index was merged in.
+        if state.link.is_none() {
+            extension::fs_monitor::mark_valid_entries(&mut state);
+        }
+        Ok((state, checksum))
     }
 }
 
@@ -311,11 +313,11 @@ pub(crate) fn stat(data: &[u8]) -> Option<(entry::Stat, &[u8])> {
     let (size, data) = read_u32(data)?;
     Some((
         entry::Stat {
-            mtime: entry::stat::Time {
+            ctime: entry::stat::Time {
                 secs: ctime_secs,
                 nsecs: ctime_nsecs,
             },
-            ctime: entry::stat::Time {
+            mtime: entry::stat::Time {
                 secs: mtime_secs,
                 nsecs: mtime_nsecs,
             },
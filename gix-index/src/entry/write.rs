@@ -3,6 +3,13 @@ use crate::{entry, Entry, State};
 impl Entry {
     /// Serialize ourselves to `out` with path access via `state`, without padding.
     pub fn write_to(&self, mut out: impl std::io::Write, state: &State) -> std::io::Result<()> {
+        self.write_header_to(&mut out, state)?;
+        out.write_all(self.path(state))?;
+        out.write_all(b"\0")
+    }
+
+    /// Serialize everything but the path of this instance to `out`, as index V4 writes paths relative to the previous one.
+    pub(crate) fn write_header_to(&self, mut out: impl std::io::Write, state: &State) -> std::io::Result<()> {
         let stat = self.stat;
         out.write_all(&stat.ctime.secs.to_be_bytes())?;
         out.write_all(&stat.ctime.nsecs.to_be_bytes())?;
@@ -31,7 +38,6 @@ impl Entry {
                     .to_be_bytes(),
             )?;
         }
-        out.write_all(path)?;
-        out.write_all(b"\0")
+        Ok(())
     }
 }
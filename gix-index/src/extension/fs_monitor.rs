@@ -1,19 +1,49 @@
-use bstr::BString;
+use bstr::{BStr, BString, ByteSlice};
 
 use crate::{
+    entry,
     extension::{FsMonitor, Signature},
     util::{read_u32, read_u64, split_at_byte_exclusive},
+    State,
 };
 
-#[derive(Clone)]
-#[allow(dead_code)]
+/// The token identifying the point in time up to which the filesystem monitor reported changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Token {
-    V1 { nanos_since_1970: u64 },
-    V2 { token: BString },
+    /// The token of version 1 of the `fsmonitor` hook protocol, the time at which the hook was last queried.
+    V1 {
+        /// The amount of nanoseconds since the unix epoch.
+        nanos_since_1970: u64,
+    },
+    /// The opaque token as returned by version 2 of the `fsmonitor` hook protocol.
+    V2 {
+        /// The token itself.
+        token: BString,
+    },
 }
 
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::V1 { nanos_since_1970 } => write!(f, "{nanos_since_1970}"),
+            Token::V2 { token } => write!(f, "{token}"),
+        }
+    }
+}
+
+/// The changes reported by the filesystem monitor since a [token](Token).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Changes {
+    /// The filesystem monitor can't tell what changed, so every path has to be assumed changed.
+    All,
+    /// Only the given worktree-relative paths changed, which may also be directories with or without trailing slash.
+    Paths(Vec<BString>),
+}
+
+/// The signature of the fsmonitor extension.
 pub const SIGNATURE: Signature = *b"FSMN";
 
+/// Decode the fsmonitor extension from `data`, without its signature and size.
 pub fn decode(data: &[u8]) -> Option<FsMonitor> {
     let (version, data) = read_u32(data)?;
     let (token, data) = match version {
@@ -37,3 +67,160 @@ pub fn decode(data: &[u8]) -> Option<FsMonitor> {
 
     FsMonitor { token, entry_dirty }.into()
 }
+
+/// Lifecycle
+impl FsMonitor {
+    /// Create a new instance which is valid up to `token`, with all entries considered changed.
+    ///
+    /// Use [`State::update_fs_monitor()`] to mark entries as unchanged once the filesystem monitor was queried with `token`.
+    pub fn new(token: Token) -> Self {
+        FsMonitor {
+            token,
+            entry_dirty: crate::extension::link::bitmap_from_set_bits(None),
+        }
+    }
+}
+
+/// Access
+impl FsMonitor {
+    /// The token up to which all changes were reported by the filesystem monitor.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+}
+
+impl FsMonitor {
+    /// Return an instance whose bitmap marks all `entries` that aren't [valid](entry::Flags::FSMONITOR_VALID) as dirty,
+    /// or ourselves if that's already the case, to keep the bitmap as it was read.
+    pub(crate) fn for_entries(&self, entries: &[crate::Entry]) -> std::borrow::Cow<'_, FsMonitor> {
+        let dirty: Vec<_> = dirty_entry_indices(entries).collect();
+        let mut recorded = Vec::with_capacity(dirty.len());
+        self.entry_dirty.for_each_set_bit(|idx| {
+            recorded.push(idx);
+            Some(())
+        });
+        if recorded == dirty {
+            std::borrow::Cow::Borrowed(self)
+        } else {
+            std::borrow::Cow::Owned(FsMonitor {
+                token: self.token.clone(),
+                entry_dirty: crate::extension::link::bitmap_from_set_bits(dirty),
+            })
+        }
+    }
+
+    /// Serialize this instance to `out`.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> Result<(), std::io::Error> {
+        let mut entry_dirty = Vec::new();
+        self.entry_dirty.write_to(&mut entry_dirty)?;
+
+        let mut data = Vec::with_capacity(4 + 8 + 4 + entry_dirty.len());
+        match &self.token {
+            Token::V1 { nanos_since_1970 } => {
+                data.extend_from_slice(&1_u32.to_be_bytes());
+                data.extend_from_slice(&nanos_since_1970.to_be_bytes());
+            }
+            Token::V2 { token } => {
+                data.extend_from_slice(&2_u32.to_be_bytes());
+                data.extend_from_slice(token);
+                data.push(0);
+            }
+        }
+        data.extend_from_slice(&(u32::try_from(entry_dirty.len()).expect("less than 4GB bitmap")).to_be_bytes());
+        data.extend_from_slice(&entry_dirty);
+
+        out.write_all(&SIGNATURE)?;
+        out.write_all(&(u32::try_from(data.len()).expect("less than 4GB fsmonitor extension")).to_be_bytes())?;
+        out.write_all(&data)?;
+        Ok(())
+    }
+}
+
+/// Filesystem monitor
+impl State {
+    /// Set the filesystem monitor extension to `fs_monitor`, or remove it if `None`, and return the previous one.
+    ///
+    /// As the new instance doesn't know about the state of our entries, all of them are considered changed
+    /// until the next [update](Self::update_fs_monitor()).
+    pub fn set_fs_monitor(&mut self, fs_monitor: Option<FsMonitor>) -> Option<FsMonitor> {
+        for entry in &mut self.entries {
+            entry.flags.remove(entry::Flags::FSMONITOR_VALID);
+        }
+        std::mem::replace(&mut self.fs_monitor, fs_monitor)
+    }
+
+    /// Record that the filesystem monitor reported `changes` since our current token, and that it's now valid up to `token`.
+    ///
+    /// Entries at changed paths or below changed directories lose their [valid](entry::Flags::FSMONITOR_VALID) flag
+    /// so their `stat` information has to be checked, while all other entries keep their flag. Entries that are valid
+    /// don't have to be looked at on disk as they didn't change since their `stat` information was last recorded,
+    /// which is when they are marked valid, e.g. by [`add_path()`](Self::add_path()).
+    /// The directories leading to changed paths are also invalidated in the [untracked cache](Self::untracked()),
+    /// so that only those have to be read again during a directory walk.
+    ///
+    /// If there is no filesystem monitor extension yet, a new one is created.
+    /// Return the amount of entries that are now considered changed.
+    pub fn update_fs_monitor(&mut self, token: Token, changes: Changes) -> usize {
+        let fs_monitor = self.fs_monitor.get_or_insert_with(|| FsMonitor::new(token.clone()));
+        fs_monitor.token = token;
+        match changes {
+            Changes::All => {
+                for entry in &mut self.entries {
+                    entry.flags.remove(entry::Flags::FSMONITOR_VALID);
+                }
+                if let Some(untracked) = self.untracked.as_mut() {
+                    untracked.invalidate("".into());
+                }
+            }
+            Changes::Paths(paths) => {
+                for path in paths {
+                    let path = path.as_bstr();
+                    let dir = path.strip_suffix(b"/").unwrap_or(path).as_bstr();
+                    for entry in self.entries.iter_mut().filter(|entry| {
+                        let entry_path = entry.path_in(&self.path_backing);
+                        entry_path == dir || is_below(entry_path, dir)
+                    }) {
+                        entry.flags.remove(entry::Flags::FSMONITOR_VALID);
+                    }
+                    if let Some(untracked) = self.untracked.as_mut() {
+                        untracked.invalidate(path);
+                    }
+                }
+            }
+        }
+        let fs_monitor = self.fs_monitor.as_mut().expect("set above");
+        fs_monitor.entry_dirty = crate::extension::link::bitmap_from_set_bits(dirty_entry_indices(&self.entries));
+        dirty_entry_indices(&self.entries).count()
+    }
+}
+
+fn is_below(path: &BStr, dir: &BStr) -> bool {
+    path.len() > dir.len() && path.starts_with(dir) && path[dir.len()] == b'/'
+}
+
+/// Mark all entries that aren't dirty as per the extension as valid, as they didn't change up to the token of the extension.
+///
+/// Like `git`, entries past the end of the bitmap aren't dirty.
+pub(crate) fn mark_valid_entries(state: &mut State) {
+    let fs_monitor = match state.fs_monitor.as_ref() {
+        Some(fs_monitor) => fs_monitor,
+        None => return,
+    };
+    for entry in &mut state.entries {
+        entry.flags.insert(entry::Flags::FSMONITOR_VALID);
+    }
+    fs_monitor.entry_dirty.for_each_set_bit(|idx| {
+        if let Some(entry) = state.entries.get_mut(idx) {
+            entry.flags.remove(entry::Flags::FSMONITOR_VALID);
+        }
+        Some(())
+    });
+}
+
+fn dirty_entry_indices(entries: &[crate::Entry]) -> impl Iterator<Item = usize> + '_ {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| !entry.flags.contains(entry::Flags::FSMONITOR_VALID))
+        .map(|(idx, _)| idx)
+}
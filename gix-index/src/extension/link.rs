@@ -1,6 +1,10 @@
+use std::cmp::Ordering;
+
 use crate::{
+    entry,
     extension::{Link, Signature},
     util::split_at_pos,
+    Entry, State,
 };
 
 /// The signature of the link extension.
@@ -178,3 +182,166 @@ impl Link {
         Ok(())
     }
 }
+
+impl Link {
+    /// Serialize this instance to `out`, bitmaps included if present.
+    pub fn write_to(&self, mut out: impl std::io::Write) -> Result<(), std::io::Error> {
+        out.write_all(&SIGNATURE)?;
+        let mut data = Vec::with_capacity(self.shared_index_checksum.as_slice().len());
+        data.extend_from_slice(self.shared_index_checksum.as_slice());
+        if let Some(bitmaps) = &self.bitmaps {
+            bitmaps.delete.write_to(&mut data)?;
+            bitmaps.replace.write_to(&mut data)?;
+        }
+        out.write_all(&(data.len() as u32).to_be_bytes())?;
+        out.write_all(&data)
+    }
+}
+
+/// The entry flags that are stored on disk, as flags that are only used in memory don't make an entry differ from the shared one.
+const ON_DISK_FLAGS: entry::Flags = entry::Flags::STAGE_MASK
+    .union(entry::Flags::ASSUME_VALID)
+    .union(entry::Flags::INTENT_TO_ADD)
+    .union(entry::Flags::SKIP_WORKTREE);
+
+/// Return a split index with all entries of `state` that aren't in the `shared` index, or differ from the entries there,
+/// with a link extension referring to `shared` by its `shared_index_checksum`.
+///
+/// Return `None` if more than `max_percent_change` percent of the entries of `state` aren't in `shared`, which means
+/// it's time to write a new shared index.
+pub(crate) fn split(
+    state: &State,
+    shared: &State,
+    shared_index_checksum: gix_hash::ObjectId,
+    max_percent_change: u8,
+) -> Option<State> {
+    let mut delete = Vec::new();
+    let mut replace = Vec::new();
+    let mut replaced_entries = Vec::new();
+    let mut new_entries = Vec::new();
+
+    let mut ours = state
+        .entries()
+        .iter()
+        .filter(|entry| !entry.flags.contains(entry::Flags::REMOVE))
+        .peekable();
+    let mut theirs = shared.entries().iter().enumerate().peekable();
+    loop {
+        match (ours.peek(), theirs.peek()) {
+            (Some(our_entry), Some((shared_idx, shared_entry))) => {
+                match Entry::cmp_filepaths(our_entry.path(state), shared_entry.path(shared))
+                    .then_with(|| our_entry.stage().cmp(&shared_entry.stage()))
+                {
+                    Ordering::Less => new_entries.extend(ours.next()),
+                    Ordering::Greater => {
+                        delete.push(*shared_idx);
+                        theirs.next();
+                    }
+                    Ordering::Equal => {
+                        if our_entry.stat != shared_entry.stat
+                            || our_entry.id != shared_entry.id
+                            || our_entry.flags & ON_DISK_FLAGS != shared_entry.flags & ON_DISK_FLAGS
+                            || our_entry.mode != shared_entry.mode
+                        {
+                            replace.push(*shared_idx);
+                            replaced_entries.push(Entry {
+                                path: 0..0,
+                                ..(*our_entry).clone()
+                            });
+                        }
+                        ours.next();
+                        theirs.next();
+                    }
+                }
+            }
+            (Some(_), None) => new_entries.extend(ours.next()),
+            (None, Some((shared_idx, _))) => {
+                delete.push(*shared_idx);
+                theirs.next();
+            }
+            (None, None) => break,
+        }
+    }
+
+    let num_entries = state
+        .entries()
+        .iter()
+        .filter(|entry| !entry.flags.contains(entry::Flags::REMOVE))
+        .count();
+    if max_percent_change == 0 || new_entries.len() * 100 > usize::from(max_percent_change) * num_entries {
+        return None;
+    }
+
+    let mut path_backing = Vec::new();
+    let mut entries = replaced_entries;
+    entries.extend(new_entries.into_iter().map(|entry| {
+        let start = path_backing.len();
+        path_backing.extend_from_slice(entry.path(state));
+        Entry {
+            path: start..path_backing.len(),
+            ..entry.clone()
+        }
+    }));
+
+    Some(split_state(
+        state,
+        entries,
+        path_backing,
+        Bitmaps {
+            delete: bitmap_from_set_bits(delete),
+            replace: bitmap_from_set_bits(replace),
+        },
+        shared_index_checksum,
+    ))
+}
+
+/// Return a split index without entries that refers to all entries of the shared index with `shared_index_checksum`,
+/// which was just written from `state`.
+pub(crate) fn empty_split(state: &State, shared_index_checksum: gix_hash::ObjectId) -> State {
+    split_state(
+        state,
+        Vec::new(),
+        Vec::new(),
+        Bitmaps {
+            delete: bitmap_from_set_bits(None),
+            replace: bitmap_from_set_bits(None),
+        },
+        shared_index_checksum,
+    )
+}
+
+fn split_state(
+    state: &State,
+    entries: Vec<Entry>,
+    path_backing: crate::PathStorage,
+    bitmaps: Bitmaps,
+    shared_index_checksum: gix_hash::ObjectId,
+) -> State {
+    State {
+        object_hash: state.object_hash,
+        timestamp: state.timestamp,
+        version: state.version,
+        entries,
+        path_backing,
+        is_sparse: state.is_sparse,
+        end_of_index_at_decode_time: false,
+        offset_table_at_decode_time: false,
+        tree: state.tree.clone(),
+        link: Some(Link {
+            shared_index_checksum,
+            bitmaps: Some(bitmaps),
+        }),
+        resolve_undo: state.resolve_undo.clone(),
+        untracked: state.untracked.clone(),
+        fs_monitor: state
+            .fs_monitor
+            .as_ref()
+            .map(|fs_monitor| fs_monitor.for_entries(&state.entries).into_owned()),
+    }
+}
+
+/// Like `git`, make the bitmap only as long as needed to hold the last set bit.
+pub(crate) fn bitmap_from_set_bits(set_bits: impl IntoIterator<Item = usize>) -> gix_bitmap::ewah::Vec {
+    let set_bits: Vec<_> = set_bits.into_iter().collect();
+    gix_bitmap::ewah::Vec::from_set_bits(set_bits.last().map_or(0, |last| last + 1), set_bits)
+}
@@ -1,15 +1,17 @@
-use bstr::BString;
+use bstr::{BStr, BString};
 use gix_hash::ObjectId;
 
 use crate::{
+    entry,
     extension::Signature,
     util::{split_at_byte_exclusive, split_at_pos},
 };
 
+/// All paths whose conflicts were resolved, in the order in which they were recorded.
 pub type Paths = Vec<ResolvePath>;
 
-#[allow(dead_code)]
-#[derive(Clone)]
+/// The conflicting stages of a path before it was resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvePath {
     /// relative to the root of the repository, or what would be stored in the index
     name: BString,
@@ -18,15 +20,60 @@ pub struct ResolvePath {
     stages: [Option<Stage>; 3],
 }
 
-#[allow(dead_code)]
-#[derive(Clone, Copy)]
+/// A single conflicting stage of a [`ResolvePath`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Stage {
     mode: u32,
     id: ObjectId,
 }
 
+impl Stage {
+    /// The mode of the entry at this stage.
+    pub fn mode(&self) -> entry::Mode {
+        entry::Mode::from_bits_retain(self.mode)
+    }
+
+    /// The id of the object of the entry at this stage.
+    pub fn id(&self) -> ObjectId {
+        self.id
+    }
+}
+
+impl ResolvePath {
+    /// Create a new instance for `name` from its conflicting `stages` as `(stage, mode, id)`, with stages being 1 to 3.
+    pub(crate) fn from_conflict(
+        name: &BStr,
+        stages: impl IntoIterator<Item = (u32, crate::entry::Mode, ObjectId)>,
+    ) -> Self {
+        let mut out = ResolvePath {
+            name: name.to_owned(),
+            stages: [None, None, None],
+        };
+        for (stage, mode, id) in stages {
+            if let Some(slot) = (stage as usize).checked_sub(1).and_then(|idx| out.stages.get_mut(idx)) {
+                *slot = Some(Stage { mode: mode.bits(), id });
+            }
+        }
+        out
+    }
+
+    /// The repository-relative path that was in conflict.
+    pub fn name(&self) -> &BStr {
+        self.name.as_ref()
+    }
+
+    /// Return the conflicting entry at `stage`, or `None` if there was none or if `stage` is [unconflicted](entry::Stage::Unconflicted).
+    pub fn stage(&self, stage: entry::Stage) -> Option<&Stage> {
+        (stage as usize)
+            .checked_sub(1)
+            .and_then(|idx| self.stages[idx].as_ref())
+    }
+}
+
+/// The signature of the resolve-undo extension.
 pub const SIGNATURE: Signature = *b"REUC";
 
+/// Decode the resolve-undo extension from `data`, without its signature and size.
 pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     let hash_len = object_hash.len_in_bytes();
     let mut out = Vec::new();
@@ -62,3 +109,25 @@ pub fn decode(mut data: &[u8], object_hash: gix_hash::Kind) -> Option<Paths> {
     }
     out.into()
 }
+
+/// Serialize `paths` as resolve-undo extension to `out`.
+pub fn write_to(paths: &Paths, mut out: impl std::io::Write) -> Result<(), std::io::Error> {
+    let mut data = Vec::new();
+    for path in paths {
+        data.extend_from_slice(&path.name);
+        data.push(0);
+        for stage in &path.stages {
+            let mode = stage.map_or(0, |stage| stage.mode);
+            data.extend_from_slice(format!("{mode:o}").as_bytes());
+            data.push(0);
+        }
+        for stage in path.stages.iter().flatten() {
+            data.extend_from_slice(stage.id.as_bytes());
+        }
+    }
+
+    out.write_all(&SIGNATURE)?;
+    out.write_all(&(u32::try_from(data.len()).expect("less than 4GB resolve-undo extension")).to_be_bytes())?;
+    out.write_all(&data)?;
+    Ok(())
+}
@@ -7,6 +7,10 @@ pub const SIGNATURE: Signature = *b"TREE";
 #[allow(clippy::empty_docs)]
 pub mod verify;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod update;
+
 mod decode;
 pub use decode::decode;
 
@@ -0,0 +1,139 @@
+use bstr::{BStr, BString, ByteSlice};
+
+use crate::{entry, extension::Tree, Entry, PathStorage, State};
+
+/// The error returned by [`State::update_cache_tree()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot write a tree as '{path}' is conflicted")]
+    Conflict { path: BString },
+    #[error("The entry at '{path}' has an invalid mode: {mode:?}")]
+    InvalidMode { path: BString, mode: entry::Mode },
+    #[error("Could not write the tree object for directory '{path}'")]
+    WriteTree {
+        path: BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+/// Tree extension
+impl State {
+    /// Bring the tree extension up to date with our entries and return the id of the root tree,
+    /// creating the extension if it doesn't exist yet.
+    ///
+    /// Only trees that were invalidated since the extension was last updated are recomputed, and each of them is passed to
+    /// `write` to be stored in the object database, which returns its id. All other trees are reused as is,
+    /// so that writing the tree of a commit doesn't require rebuilding it from scratch.
+    ///
+    /// Entries marked as [intent-to-add](entry::Flags::INTENT_TO_ADD) or [to-be-removed](entry::Flags::REMOVE) are not
+    /// part of the resulting trees. Like `git`, all trees leading to intent-to-add entries are left invalid as they don't
+    /// match the index, and are thus recomputed on each call.
+    /// If there are conflicts, an error is returned and the extension is left unchanged.
+    pub fn update_cache_tree<E>(
+        &mut self,
+        mut write: impl FnMut(&gix_object::Tree) -> Result<gix_hash::ObjectId, E>,
+    ) -> Result<gix_hash::ObjectId, Error>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let _span = gix_features::trace::coarse!("gix_index::State::update_cache_tree()");
+        let tree = update(
+            "".into(),
+            &self.entries,
+            &self.path_backing,
+            self.tree.as_ref(),
+            &mut |tree, path| {
+                write(tree).map_err(|err| Error::WriteTree {
+                    path: path.to_owned(),
+                    source: err.into(),
+                })
+            },
+        )?;
+        let id = tree.id;
+        self.tree = Some(tree);
+        Ok(id)
+    }
+}
+
+/// Return the tree for the directory at `dir` which contains all `entries`, reusing `existing` if it's still valid.
+fn update(
+    dir: &BStr,
+    entries: &[Entry],
+    path_backing: &PathStorage,
+    existing: Option<&Tree>,
+    write: &mut dyn FnMut(&gix_object::Tree, &BStr) -> Result<gix_hash::ObjectId, Error>,
+) -> Result<Tree, Error> {
+    if let Some(existing) = existing.filter(|tree| tree.num_entries.is_some()) {
+        return Ok(existing.clone());
+    }
+    let name_start = if dir.is_empty() { 0 } else { dir.len() + 1 };
+    let mut tree_entries = Vec::new();
+    let mut children = Vec::new();
+    let mut is_valid = true;
+    let mut idx = 0;
+    while let Some(entry) = entries.get(idx) {
+        let path = entry.path_in(path_backing);
+        if entry.stage_raw() != 0 {
+            return Err(Error::Conflict { path: path.to_owned() });
+        }
+        match path[name_start..].find_byte(b'/') {
+            Some(pos) => {
+                let sub_dir = path[..name_start + pos].as_bstr();
+                let sub_dir_with_slash = &path[..name_start + pos + 1];
+                let end =
+                    idx + entries[idx..].partition_point(|e| e.path_in(path_backing).starts_with(sub_dir_with_slash));
+                let name = &sub_dir[name_start..];
+                let child = update(
+                    sub_dir,
+                    &entries[idx..end],
+                    path_backing,
+                    existing.and_then(|tree| tree.children.iter().find(|child| child.name.as_slice() == name)),
+                    write,
+                )?;
+                idx = end;
+                is_valid &= child.num_entries.is_some();
+                if child.id.is_empty_tree() {
+                    continue;
+                }
+                tree_entries.push(gix_object::tree::Entry {
+                    mode: gix_object::tree::EntryKind::Tree.into(),
+                    filename: name.into(),
+                    oid: child.id,
+                });
+                children.push(child);
+            }
+            None => {
+                idx += 1;
+                if entry.flags.contains(entry::Flags::INTENT_TO_ADD) {
+                    is_valid = false;
+                    continue;
+                }
+                if entry.flags.contains(entry::Flags::REMOVE) {
+                    continue;
+                }
+                let mode = entry.mode.to_tree_entry_mode().ok_or_else(|| Error::InvalidMode {
+                    path: path.to_owned(),
+                    mode: entry.mode,
+                })?;
+                tree_entries.push(gix_object::tree::Entry {
+                    mode,
+                    filename: path[name_start..].into(),
+                    oid: entry.id,
+                });
+            }
+        }
+    }
+    tree_entries.sort();
+    children.sort_by(|a: &Tree, b: &Tree| a.name.cmp(&b.name));
+    let id = write(&gix_object::Tree { entries: tree_entries }, dir)?;
+    Ok(Tree {
+        name: dir
+            .rfind_byte(b'/')
+            .map_or(dir.as_bytes(), |pos| &dir[pos + 1..])
+            .into(),
+        id,
+        num_entries: is_valid.then_some(entries.len() as u32),
+        children,
+    })
+}
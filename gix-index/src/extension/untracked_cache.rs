@@ -1,14 +1,14 @@
-use bstr::BString;
+use bstr::{BStr, BString, ByteSlice};
 use gix_hash::ObjectId;
 
 use crate::{
     entry,
     extension::{Signature, UntrackedCache},
-    util::{read_u32, split_at_byte_exclusive, split_at_pos, var_int},
+    util::{encode_var_int, read_u32, split_at_byte_exclusive, split_at_pos, var_int},
 };
 
 /// A structure to track filesystem stat information along with an object id, linking a worktree file with what's in our ODB.
-#[derive(Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct OidStat {
     /// The file system stat information
     pub stat: entry::Stat,
@@ -17,7 +17,7 @@ pub struct OidStat {
 }
 
 /// A directory with information about its untracked files, and its sub-directories
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct Directory {
     /// The directories name, or an empty string if this is the root directory.
     pub name: BString,
@@ -26,11 +26,11 @@ pub struct Directory {
     /// indices for sub-directories similar to this one.
     pub sub_directories: Vec<usize>,
 
-    /// The directories stat data, if available or valid // TODO: or is it the exclude file?
+    /// The directories stat data at the time its untracked entries were recorded, or `None` if they are invalid.
     pub stat: Option<entry::Stat>,
     /// The oid of a .gitignore file, if it exists
     pub exclude_file_oid: Option<ObjectId>,
-    /// TODO: figure out what this really does
+    /// If `true`, the directory was only checked for having untracked entries, which aren't all listed then.
     pub check_only: bool,
 }
 
@@ -47,17 +47,19 @@ pub fn decode(data: &[u8], object_hash: gix_hash::Kind) -> Option<UntrackedCache
     let (identifier, data) = split_at_pos(data, identifier_len.try_into().ok()?)?;
 
     let hash_len = object_hash.len_in_bytes();
-    let (info_exclude, data) = decode_oid_stat(data, hash_len)?;
-    let (excludes_file, data) = decode_oid_stat(data, hash_len)?;
+    let (info_exclude_stat, data) = crate::decode::stat(data)?;
+    let (excludes_file_stat, data) = crate::decode::stat(data)?;
     let (dir_flags, data) = read_u32(data)?;
+    let (info_exclude, data) = decode_oid_stat(info_exclude_stat, data, hash_len)?;
+    let (excludes_file, data) = decode_oid_stat(excludes_file_stat, data, hash_len)?;
     let (exclude_filename_per_dir, data) = split_at_byte_exclusive(data, 0)?;
 
     let (num_directory_blocks, data) = var_int(data)?;
 
     let mut res = UntrackedCache {
         identifier: identifier.into(),
-        info_exclude: (!info_exclude.id.is_null()).then_some(info_exclude),
-        excludes_file: (!excludes_file.id.is_null()).then_some(excludes_file),
+        info_exclude,
+        excludes_file,
         exclude_filename_per_dir: exclude_filename_per_dir.into(),
         dir_flags,
         directories: Vec::new(),
@@ -141,14 +143,306 @@ fn decode_directory_block<'a>(data: &'a [u8], directories: &mut Vec<Directory>)
     data.into()
 }
 
-fn decode_oid_stat(data: &[u8], hash_len: usize) -> Option<(OidStat, &[u8])> {
-    let (stat, data) = crate::decode::stat(data)?;
+/// Read the object id belonging to `stat`, and return both unless they are unset, which is when the file doesn't exist.
+fn decode_oid_stat(stat: entry::Stat, data: &[u8], hash_len: usize) -> Option<(Option<OidStat>, &[u8])> {
     let (hash, data) = split_at_pos(data, hash_len)?;
-    Some((
-        OidStat {
-            stat,
-            id: ObjectId::from_bytes_or_panic(hash),
-        },
-        data,
-    ))
+    let id = ObjectId::from_bytes_or_panic(hash);
+    let oid_stat = (!id.is_null() || stat != entry::Stat::default()).then_some(OidStat { stat, id });
+    Some((oid_stat, data))
+}
+
+impl UntrackedCache {
+    /// Serialize this instance to `out`, using null-ids of `object_hash` for exclude files that don't exist.
+    pub fn write_to(&self, mut out: impl std::io::Write, object_hash: gix_hash::Kind) -> Result<(), std::io::Error> {
+        let mut buf = [0u8; 10];
+        let mut data = Vec::new();
+        data.extend_from_slice(encode_var_int(self.identifier.len() as u64, &mut buf));
+        data.extend_from_slice(&self.identifier);
+        let null_stat = entry::Stat::default();
+        for oid_stat in [&self.info_exclude, &self.excludes_file] {
+            write_stat(
+                &mut data,
+                oid_stat.as_ref().map_or(&null_stat, |oid_stat| &oid_stat.stat),
+            );
+        }
+        data.extend_from_slice(&self.dir_flags.to_be_bytes());
+        for oid_stat in [&self.info_exclude, &self.excludes_file] {
+            data.extend_from_slice(
+                oid_stat
+                    .as_ref()
+                    .map_or(object_hash.null().as_bytes(), |oid_stat| oid_stat.id.as_bytes()),
+            );
+        }
+        data.extend_from_slice(&self.exclude_filename_per_dir);
+        data.push(0);
+
+        data.extend_from_slice(encode_var_int(self.directories.len() as u64, &mut buf));
+        if !self.directories.is_empty() {
+            let mut order = Vec::with_capacity(self.directories.len());
+            write_directory_block(&mut data, &self.directories, 0, &mut order);
+
+            let set_bits = |predicate: &dyn Fn(&Directory) -> bool| {
+                order
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, dir)| predicate(dir))
+                    .map(|(bit, _)| bit)
+                    .collect::<Vec<_>>()
+            };
+            for set_bits in [
+                set_bits(&|dir| dir.stat.is_some()),
+                set_bits(&|dir| dir.check_only),
+                set_bits(&|dir| dir.exclude_file_oid.is_some()),
+            ] {
+                // Like `git`, only track bits up to the last one that is set.
+                let num_bits = set_bits.last().map_or(0, |bit| bit + 1);
+                gix_bitmap::ewah::Vec::from_set_bits(num_bits, set_bits).write_to(&mut data)?;
+            }
+            for stat in order.iter().filter_map(|dir| dir.stat.as_ref()) {
+                write_stat(&mut data, stat);
+            }
+            for id in order.iter().filter_map(|dir| dir.exclude_file_oid.as_ref()) {
+                data.extend_from_slice(id.as_bytes());
+            }
+            data.push(0);
+        }
+
+        out.write_all(&SIGNATURE)?;
+        out.write_all(&(u32::try_from(data.len()).expect("less than 4GB untracked cache extension")).to_be_bytes())?;
+        out.write_all(&data)?;
+        Ok(())
+    }
+}
+
+/// Write the directory at `index` of `directories` and all of its sub-directories depth-first, recording the order in which
+/// they were written in `order` as it's the one in which bitmaps refer to them.
+fn write_directory_block<'a>(
+    out: &mut Vec<u8>,
+    directories: &'a [Directory],
+    index: usize,
+    order: &mut Vec<&'a Directory>,
+) {
+    let dir = &directories[index];
+    let mut buf = [0u8; 10];
+    out.extend_from_slice(encode_var_int(dir.untracked_entries.len() as u64, &mut buf));
+    out.extend_from_slice(encode_var_int(dir.sub_directories.len() as u64, &mut buf));
+    out.extend_from_slice(&dir.name);
+    out.push(0);
+    for name in &dir.untracked_entries {
+        out.extend_from_slice(name);
+        out.push(0);
+    }
+    order.push(dir);
+    for sub_dir_index in &dir.sub_directories {
+        write_directory_block(out, directories, *sub_dir_index, order);
+    }
+}
+
+fn write_stat(out: &mut Vec<u8>, stat: &entry::Stat) {
+    for field in [
+        stat.ctime.secs,
+        stat.ctime.nsecs,
+        stat.mtime.secs,
+        stat.mtime.nsecs,
+        stat.dev,
+        stat.ino,
+        stat.uid,
+        stat.gid,
+        stat.size,
+    ] {
+        out.extend_from_slice(&field.to_be_bytes());
+    }
+}
+
+/// Lifecycle
+impl UntrackedCache {
+    /// Create a new cache without any directories for the worktree identified by `identifier`, with `exclude_filename_per_dir`
+    /// being the name of per-directory exclude files, typically `.gitignore`, and with `dir_flags` describing how
+    /// untracked files are collected.
+    pub fn new(identifier: impl Into<BString>, exclude_filename_per_dir: impl Into<BString>, dir_flags: u32) -> Self {
+        UntrackedCache {
+            identifier: identifier.into(),
+            info_exclude: None,
+            excludes_file: None,
+            exclude_filename_per_dir: exclude_filename_per_dir.into(),
+            dir_flags,
+            directories: vec![invalid_directory(BString::default())],
+        }
+    }
+}
+
+/// Access
+impl UntrackedCache {
+    /// Something identifying the location and machine that this cache is for.
+    pub fn identifier(&self) -> &BStr {
+        self.identifier.as_ref()
+    }
+
+    /// The name of per-directory exclude files, typically `.gitignore`.
+    pub fn exclude_filename_per_dir(&self) -> &BStr {
+        self.exclude_filename_per_dir.as_ref()
+    }
+
+    /// Flags describing how untracked files were collected by `git`.
+    pub fn dir_flags(&self) -> u32 {
+        self.dir_flags
+    }
+
+    /// The stat and id of the `.git/info/exclude` file, if it existed.
+    pub fn info_exclude(&self) -> Option<&OidStat> {
+        self.info_exclude.as_ref()
+    }
+
+    /// The stat and id of the file configured with `core.excludesFile`, if it existed.
+    pub fn excludes_file(&self) -> Option<&OidStat> {
+        self.excludes_file.as_ref()
+    }
+
+    /// All directories, with the first one being the root directory if there are any.
+    pub fn directories(&self) -> &[Directory] {
+        &self.directories
+    }
+
+    /// Return the directory at the worktree-relative `rela_dir_path`, or the root directory if it's empty.
+    pub fn directory(&self, rela_dir_path: &BStr) -> Option<&Directory> {
+        self.directory_index(rela_dir_path).map(|idx| &self.directories[idx])
+    }
+
+    /// Return the directory at the worktree-relative `rela_dir_path` if its untracked entries can be used in place of reading
+    /// the directory, which is the case if it currently has `stat` and its exclude file has `exclude_file_oid`.
+    pub fn valid_directory(
+        &self,
+        rela_dir_path: &BStr,
+        stat: &entry::Stat,
+        exclude_file_oid: Option<ObjectId>,
+    ) -> Option<&Directory> {
+        self.directory(rela_dir_path).filter(|dir| {
+            dir.stat.as_ref() == Some(stat) && dir.exclude_file_oid == exclude_file_oid && !dir.check_only
+        })
+    }
+
+    fn directory_index(&self, rela_dir_path: &BStr) -> Option<usize> {
+        let mut idx = (!self.directories.is_empty()).then_some(0)?;
+        if rela_dir_path.is_empty() {
+            return Some(idx);
+        }
+        for component in rela_dir_path.split(|b| *b == b'/') {
+            idx = *self.directories[idx]
+                .sub_directories
+                .iter()
+                .find(|sub_idx| self.directories[**sub_idx].name == component)?;
+        }
+        Some(idx)
+    }
+}
+
+/// Mutation
+impl UntrackedCache {
+    /// Set the stat and id of the `.git/info/exclude` file as `info_exclude` and of the file configured with `core.excludesFile`
+    /// as `excludes_file`, each `None` if it doesn't exist, and return `true` if they are unchanged.
+    ///
+    /// Otherwise all directories are invalidated as the untracked files within them may have changed.
+    pub fn set_global_excludes(&mut self, info_exclude: Option<OidStat>, excludes_file: Option<OidStat>) -> bool {
+        let unchanged = self.info_exclude == info_exclude && self.excludes_file == excludes_file;
+        if !unchanged {
+            self.info_exclude = info_exclude;
+            self.excludes_file = excludes_file;
+            for dir in &mut self.directories {
+                dir.stat = None;
+            }
+        }
+        unchanged
+    }
+
+    /// Record that the directory at the worktree-relative `rela_dir_path` has the untracked files and directories in
+    /// `untracked_entries`, with the latter having a trailing slash, and that `sub_directories` are the names of all of its
+    /// directories that have to be traversed as they contain tracked files.
+    ///
+    /// `stat` and `exclude_file_oid` are the ones of the directory and its exclude file when the entries were collected.
+    /// Directories leading to `rela_dir_path` are created as needed, but remain invalid until their entries are recorded.
+    pub fn set_directory<'a>(
+        &mut self,
+        rela_dir_path: &BStr,
+        stat: entry::Stat,
+        exclude_file_oid: Option<ObjectId>,
+        untracked_entries: Vec<BString>,
+        sub_directories: impl IntoIterator<Item = &'a BStr>,
+    ) {
+        let idx = self.directory_index_or_insert(rela_dir_path);
+        let sub_directories = sub_directories
+            .into_iter()
+            .map(|name| self.sub_directory_index_or_insert(idx, name))
+            .collect();
+        let dir = &mut self.directories[idx];
+        dir.stat = Some(stat);
+        dir.exclude_file_oid = exclude_file_oid;
+        dir.untracked_entries = untracked_entries;
+        dir.sub_directories = sub_directories;
+        dir.check_only = false;
+    }
+
+    /// Invalidate all directories leading to the worktree-relative `rela_path`, similar to what `git` does when a file
+    /// is added to or removed from the index, which may change whether it's untracked or not without affecting the
+    /// directory it's in.
+    pub fn invalidate(&mut self, rela_path: &BStr) {
+        let mut idx = match (!self.directories.is_empty()).then_some(0) {
+            Some(idx) => idx,
+            None => return,
+        };
+        self.directories[idx].stat = None;
+        let mut components = rela_path.split(|b| *b == b'/').peekable();
+        while let Some(component) = components.next() {
+            if components.peek().is_none() {
+                break;
+            }
+            idx = match self.directories[idx]
+                .sub_directories
+                .iter()
+                .find(|sub_idx| self.directories[**sub_idx].name == component)
+            {
+                Some(sub_idx) => *sub_idx,
+                None => break,
+            };
+            self.directories[idx].stat = None;
+        }
+    }
+
+    fn directory_index_or_insert(&mut self, rela_dir_path: &BStr) -> usize {
+        if self.directories.is_empty() {
+            self.directories.push(invalid_directory(BString::default()));
+        }
+        let mut idx = 0;
+        if rela_dir_path.is_empty() {
+            return idx;
+        }
+        for component in rela_dir_path.split(|b| *b == b'/') {
+            idx = self.sub_directory_index_or_insert(idx, component.as_bstr());
+        }
+        idx
+    }
+
+    fn sub_directory_index_or_insert(&mut self, parent_idx: usize, name: &BStr) -> usize {
+        if let Some(idx) = self.directories[parent_idx]
+            .sub_directories
+            .iter()
+            .find(|sub_idx| self.directories[**sub_idx].name == name)
+        {
+            return *idx;
+        }
+        let idx = self.directories.len();
+        self.directories.push(invalid_directory(name.to_owned()));
+        self.directories[parent_idx].sub_directories.push(idx);
+        idx
+    }
+}
+
+fn invalid_directory(name: BString) -> Directory {
+    Directory {
+        name,
+        untracked_entries: Vec::new(),
+        sub_directories: Vec::new(),
+        stat: None,
+        exclude_file_oid: None,
+        check_only: false,
+    }
 }
@@ -96,9 +96,16 @@ impl File {
         };
 
         let (state, checksum) = State::from_bytes(&data, mtime, object_hash, options)?;
-        let mut file = File { state, path, checksum };
-        if let Some(mut link) = file.link.take() {
+        let mut file = File {
+            state,
+            path,
+            checksum,
+            shared_index_checksum: None,
+        };
+        if let Some(link) = file.link.take() {
+            file.shared_index_checksum = Some(link.shared_index_checksum);
             link.dissolve_into(&mut file, object_hash, skip_hash, options)?;
+            crate::extension::fs_monitor::mark_valid_entries(&mut file.state);
         }
 
         Ok(file)
@@ -112,6 +119,7 @@ impl File {
             state,
             path: path.into(),
             checksum: None,
+            shared_index_checksum: None,
         }
     }
 }
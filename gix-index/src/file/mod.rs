@@ -63,6 +63,14 @@ mod access {
         pub fn checksum(&self) -> Option<gix_hash::ObjectId> {
             self.checksum
         }
+
+        /// The checksum of the shared index if the index was read or written as split index, or `None` if all entries
+        /// are contained in the file at our [path](Self::path()).
+        ///
+        /// The shared index is located next to our path, in a file named `sharedindex.<checksum>`.
+        pub fn shared_index_checksum(&self) -> Option<gix_hash::ObjectId> {
+            self.shared_index_checksum
+        }
     }
 }
 
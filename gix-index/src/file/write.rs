@@ -1,6 +1,8 @@
+use std::path::Path;
+
 use gix_features::hash;
 
-use crate::{write, File, Version};
+use crate::{extension, write, File, State, Version};
 
 /// The error produced by [`File::write()`].
 #[derive(Debug, thiserror::Error)]
@@ -12,6 +14,12 @@ pub enum Error {
     AcquireLock(#[from] gix_lock::acquire::Error),
     #[error("Could not commit lock for index file")]
     CommitLock(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error("Could not read the shared index to write a split index")]
+    ReadSharedIndex(#[from] crate::file::init::Error),
+    #[error("Could not read the index that was changed on disk since it was read")]
+    ReadChangedIndex(#[source] crate::file::init::Error),
+    #[error("Could not reconcile the index with the one that was changed on disk since it was read")]
+    OnChange(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
 
 impl File {
@@ -19,22 +27,11 @@ impl File {
     /// to retain all information of this index.
     pub fn write_to(
         &self,
-        mut out: impl std::io::Write,
+        out: impl std::io::Write,
         options: write::Options,
     ) -> std::io::Result<(Version, gix_hash::ObjectId)> {
         let _span = gix_features::trace::detail!("gix_index::File::write_to()", skip_hash = options.skip_hash);
-        let (version, hash) = if options.skip_hash {
-            let out: &mut dyn std::io::Write = &mut out;
-            let version = self.state.write_to(out, options)?;
-            (version, self.state.object_hash.null())
-        } else {
-            let mut hasher = hash::Write::new(&mut out, self.state.object_hash);
-            let out: &mut dyn std::io::Write = &mut hasher;
-            let version = self.state.write_to(out, options)?;
-            (version, gix_hash::ObjectId::from(hasher.hash.digest()))
-        };
-        out.write_all(hash.as_slice())?;
-        Ok((version, hash))
+        write_state_to(&self.state, out, options)
     }
 
     /// Write ourselves to the path we were read from after acquiring a lock, using `options`.
@@ -42,17 +39,167 @@ impl File {
     /// Note that the hash produced will be stored which is why we need to be mutable.
     pub fn write(&mut self, options: write::Options) -> Result<(), Error> {
         let _span = gix_features::trace::detail!("gix_index::File::write()", path = ?self.path);
+        let lock = gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?;
+        self.write_locked(lock, options)
+    }
+
+    /// Like [`write()`](Self::write()), but after acquiring the lock, check if the index on disk changed since we read it
+    /// by comparing its checksum to [ours](File::checksum()), to avoid overwriting modifications made by others in the mean time.
+    ///
+    /// If it did change, `on_change(state, on_disk)` is called with our `state` and the index as currently `on_disk`, which
+    /// is empty if it doesn't exist anymore. It's expected to update `state` to include the changes of `on_disk`, or to
+    /// refresh it entirely, before it is written. If it fails, nothing is written and its error is returned.
+    /// As the lock is held the whole time, no other writer can change the index in the mean time.
+    ///
+    /// Note that indices written without checksum, for instance due to `index.skipHash`, can't be checked like this
+    /// and are considered unchanged if we were read from such an index, too.
+    pub fn write_checked<E>(
+        &mut self,
+        options: write::Options,
+        on_change: impl FnOnce(&mut State, File) -> Result<(), E>,
+    ) -> Result<(), Error>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let _span = gix_features::trace::detail!("gix_index::File::write_checked()", path = ?self.path);
+        let lock = gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?;
+        if on_disk_checksum(&self.path, self.state.object_hash)? != self.checksum.filter(|checksum| !checksum.is_null())
+        {
+            let on_disk = File::at_or_default(&self.path, self.state.object_hash, false, Default::default())
+                .map_err(Error::ReadChangedIndex)?;
+            on_change(&mut self.state, on_disk).map_err(|err| Error::OnChange(err.into()))?;
+        }
+        self.write_locked(lock, options)
+    }
+
+    fn write_locked(&mut self, lock: gix_lock::File, options: write::Options) -> Result<(), Error> {
+        let mut lock = std::io::BufWriter::with_capacity(64 * 1024, lock);
+        let (version, digest) = self.write_to(&mut lock, options)?;
+        match lock.into_inner() {
+            Ok(lock) => lock.commit()?,
+            Err(err) => return Err(err.into_error().into()),
+        };
+        self.state.version = version;
+        self.checksum = Some(digest);
+        self.shared_index_checksum = None;
+        Ok(())
+    }
+
+    /// Write ourselves to the path we were read from as split index after acquiring a lock, using `options`,
+    /// which is what `git` does if `core.splitIndex` is enabled.
+    ///
+    /// Only the entries that aren't in the [shared index](File::shared_index_checksum()) or that differ from it are written
+    /// to our path, along with a `link` extension referring to the shared index, so that large indices don't have to be
+    /// rewritten entirely on each change.
+    /// If there is no shared index yet, or if more than `max_percent_change` percent of our entries aren't in it,
+    /// all entries are written to a new shared index next to our path first. The latter is typically the value of
+    /// `splitIndex.maxPercentChange`, which defaults to 20.
+    ///
+    /// Note that shared indices that aren't used anymore are not removed.
+    pub fn write_split(&mut self, options: write::Options, max_percent_change: u8) -> Result<(), Error> {
+        let _span = gix_features::trace::detail!("gix_index::File::write_split()", path = ?self.path);
+        let dir = self
+            .path
+            .parent()
+            .expect("index files are always in a directory")
+            .to_owned();
+        let shared = match self.shared_index_checksum {
+            Some(checksum) => {
+                match File::at(
+                    shared_index_path(&dir, checksum),
+                    self.state.object_hash,
+                    false,
+                    crate::decode::Options {
+                        expected_checksum: Some(checksum),
+                        ..Default::default()
+                    },
+                ) {
+                    Ok(shared) => Some((shared, checksum)),
+                    Err(crate::file::init::Error::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => None,
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            None => None,
+        };
+
+        let split = match shared
+            .and_then(|(shared, checksum)| extension::link::split(&self.state, &shared, checksum, max_percent_change))
+        {
+            Some(split) => split,
+            None => {
+                let mut buf = Vec::new();
+                let (_version, checksum) = write_state_to(
+                    &self.state,
+                    &mut buf,
+                    write::Options {
+                        extensions: write::Extensions::None,
+                        skip_hash: false,
+                        thread_limit: None,
+                    },
+                )?;
+                let mut lock = gix_lock::File::acquire_to_update_resource(
+                    shared_index_path(&dir, checksum),
+                    gix_lock::acquire::Fail::Immediately,
+                    None,
+                )?;
+                std::io::Write::write_all(&mut lock, &buf)?;
+                lock.commit()?;
+                extension::link::empty_split(&self.state, checksum)
+            }
+        };
+
+        let shared_index_checksum = split.link().map(|link| link.shared_index_checksum);
         let mut lock = std::io::BufWriter::with_capacity(
             64 * 1024,
             gix_lock::File::acquire_to_update_resource(&self.path, gix_lock::acquire::Fail::Immediately, None)?,
         );
-        let (version, digest) = self.write_to(&mut lock, options)?;
+        let (version, digest) = write_state_to(&split, &mut lock, options)?;
         match lock.into_inner() {
             Ok(lock) => lock.commit()?,
             Err(err) => return Err(err.into_error().into()),
         };
         self.state.version = version;
         self.checksum = Some(digest);
+        self.shared_index_checksum = shared_index_checksum;
         Ok(())
     }
 }
+
+/// Return the checksum stored in the index file at `path`, or `None` if it doesn't exist or has no checksum.
+fn on_disk_checksum(path: &Path, object_hash: gix_hash::Kind) -> std::io::Result<Option<gix_hash::ObjectId>> {
+    use std::io::{Read, Seek, SeekFrom};
+    let mut file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let mut buf = [0u8; gix_hash::Kind::longest().len_in_bytes()];
+    let buf = &mut buf[..object_hash.len_in_bytes()];
+    file.seek(SeekFrom::End(-(buf.len() as i64)))?;
+    file.read_exact(buf)?;
+    let checksum = gix_hash::ObjectId::from_bytes_or_panic(buf);
+    Ok((!checksum.is_null()).then_some(checksum))
+}
+
+fn shared_index_path(dir: &Path, checksum: gix_hash::ObjectId) -> std::path::PathBuf {
+    dir.join(format!("sharedindex.{checksum}"))
+}
+
+fn write_state_to(
+    state: &State,
+    mut out: impl std::io::Write,
+    options: write::Options,
+) -> std::io::Result<(Version, gix_hash::ObjectId)> {
+    let (version, hash) = if options.skip_hash {
+        let out: &mut dyn std::io::Write = &mut out;
+        let version = state.write_to(out, options)?;
+        (version, state.object_hash.null())
+    } else {
+        let mut hasher = hash::Write::new(&mut out, state.object_hash);
+        let out: &mut dyn std::io::Write = &mut hasher;
+        let version = state.write_to(out, options)?;
+        (version, gix_hash::ObjectId::from(hasher.hash.digest()))
+    };
+    out.write_all(hash.as_slice())?;
+    Ok((version, hash))
+}
@@ -1,4 +1,6 @@
-mod from_tree {
+///
+#[allow(clippy::empty_docs)]
+pub mod from_tree {
     use std::collections::VecDeque;
 
     use bstr::{BStr, BString, ByteSlice, ByteVec};
@@ -7,9 +9,25 @@ mod from_tree {
 
     use crate::{
         entry::{Flags, Mode, Stat},
-        Entry, PathStorage, State, Version,
+        extension, Entry, PathStorage, State, Version,
     };
 
+    /// Options for use in [`State::from_tree_with_options()`].
+    #[derive(Debug, Default, Clone)]
+    pub struct Options {
+        /// If set, the tree is placed at this repository-relative directory, so all paths are prefixed with it,
+        /// similar to `git read-tree --prefix=<prefix>`.
+        pub prefix: Option<BString>,
+        /// If set, the repository-relative directories of a _cone mode_ sparse checkout, which are the ones that are
+        /// checked out recursively.
+        ///
+        /// All entries outside of these directories are marked with [`SKIP_WORKTREE`](Flags::SKIP_WORKTREE),
+        /// except for the ones directly inside the top-level directory or inside of any of the leading directories of a
+        /// cone directory, just like `git` does in cone mode.
+        /// Use [`State::collapse_skipped_directories()`] afterwards to obtain a sparse index.
+        pub sparse_cone_directories: Option<Vec<BString>>,
+    }
+
     /// Initialization
     impl State {
         /// Return a new and empty in-memory index assuming the given `object_hash`.
@@ -33,15 +51,36 @@ mod from_tree {
         /// Create an index [`State`] by traversing `tree` recursively, accessing sub-trees
         /// with `objects`.
         ///
-        /// **No extension data is currently produced**.
+        /// The `tree` extension is populated to match `tree`, but no other extension data is produced.
         pub fn from_tree<Find>(tree: &gix_hash::oid, objects: Find) -> Result<Self, breadthfirst::Error>
+        where
+            Find: gix_object::Find,
+        {
+            Self::from_tree_with_options(tree, objects, Default::default())
+        }
+
+        /// Like [`from_tree()`](Self::from_tree()), but allows to place the tree below a prefix directory and to
+        /// mark entries outside of a sparse checkout, as configured by `options`, similar to `git read-tree`.
+        ///
+        /// Note that with a prefix, only the part of the `tree` extension at the prefix directory is valid.
+        pub fn from_tree_with_options<Find>(
+            tree: &gix_hash::oid,
+            objects: Find,
+            Options {
+                prefix,
+                sparse_cone_directories,
+            }: Options,
+        ) -> Result<Self, breadthfirst::Error>
         where
             Find: gix_object::Find,
         {
             let _span = gix_features::trace::coarse!("gix_index::State::from_tree()");
+            let prefix = prefix
+                .map(|prefix| prefix.trim_end_with(|c| c == '/').as_bstr().to_owned())
+                .unwrap_or_default();
             let mut buf = Vec::new();
             let root = objects.find_tree_iter(tree, &mut buf)?;
-            let mut delegate = CollectEntries::new();
+            let mut delegate = CollectEntries::new_at(prefix.clone());
             breadthfirst(root, breadthfirst::State::default(), &objects, &mut delegate)?;
 
             let CollectEntries {
@@ -49,9 +88,23 @@ mod from_tree {
                 path_backing,
                 path: _,
                 path_deque: _,
+                mut trees,
             } = delegate;
 
             entries.sort_by(|a, b| Entry::cmp_filepaths(a.path_in(&path_backing), b.path_in(&path_backing)));
+            if let Some(cone_directories) = sparse_cone_directories {
+                for entry in &mut entries {
+                    if !is_in_cone(entry.path_in(&path_backing), &cone_directories) {
+                        entry.flags |= Flags::SKIP_WORKTREE | Flags::EXTENDED;
+                    }
+                }
+            }
+
+            trees.sort_by(|a, b| a.0.cmp(&b.0));
+            let cache_tree = cache_tree_at_prefix(
+                prefix.as_ref(),
+                tree_node(prefix.as_ref(), tree.to_owned(), &trees, &entries, &path_backing),
+            );
 
             Ok(State {
                 object_hash: tree.kind(),
@@ -60,7 +113,7 @@ mod from_tree {
                 entries,
                 path_backing,
                 is_sparse: false,
-                tree: None,
+                tree: Some(cache_tree),
                 link: None,
                 resolve_undo: None,
                 untracked: None,
@@ -71,20 +124,93 @@ mod from_tree {
         }
     }
 
-    struct CollectEntries {
-        entries: Vec<Entry>,
-        path_backing: PathStorage,
+    /// Return `true` if the file at `path` is checked out in a cone-mode sparse checkout of `cone_directories`.
+    fn is_in_cone(path: &BStr, cone_directories: &[BString]) -> bool {
+        let parent = path.rfind_byte(b'/').map_or(b"".as_bstr(), |pos| path[..pos].as_bstr());
+        parent.is_empty()
+            || cone_directories.iter().any(|dir| {
+                let dir = dir.trim_end_with(|c| c == '/');
+                is_below_or_at(parent, dir.as_bstr()) || is_below_or_at(dir.as_bstr(), parent)
+            })
+    }
+
+    fn is_below_or_at(path: &BStr, dir: &BStr) -> bool {
+        path.starts_with(dir) && (path.len() == dir.len() || path.get(dir.len()) == Some(&b'/'))
+    }
+
+    /// Produce the cache-tree node for the directory at `dir` with tree `id`, along with all of its sub-trees
+    /// from the sorted `trees`, counting the sorted `entries` below it.
+    fn tree_node(
+        dir: &BStr,
+        id: gix_hash::ObjectId,
+        trees: &[(BString, gix_hash::ObjectId)],
+        entries: &[Entry],
+        path_backing: &PathStorage,
+    ) -> extension::Tree {
+        let (entries, trees) = if dir.is_empty() {
+            (entries, trees)
+        } else {
+            let mut dir_with_slash = dir.to_owned();
+            dir_with_slash.push(b'/');
+            let dir_with_slash = dir_with_slash.as_bstr();
+            let entries = &entries[entries.partition_point(|e| e.path_in(path_backing) < dir_with_slash)..];
+            let entries = &entries[..entries.partition_point(|e| e.path_in(path_backing).starts_with(dir_with_slash))];
+            let trees = &trees[trees.partition_point(|(path, _)| path.as_bstr() < dir_with_slash)..];
+            let trees = &trees[..trees.partition_point(|(path, _)| path.starts_with(dir_with_slash))];
+            (entries, trees)
+        };
+        let name_start = if dir.is_empty() { 0 } else { dir.len() + 1 };
+        let children = trees
+            .iter()
+            .filter(|(path, _)| !path[name_start..].contains(&b'/'))
+            .map(|(path, id)| tree_node(path.as_bstr(), *id, trees, entries, path_backing))
+            .collect();
+        extension::Tree {
+            name: file_name(dir).into(),
+            id,
+            num_entries: Some(entries.len() as u32),
+            children,
+        }
+    }
+
+    /// Wrap `tree`, located at `prefix`, into invalid trees for each of the directories leading up to it.
+    fn cache_tree_at_prefix(prefix: &BStr, mut tree: extension::Tree) -> extension::Tree {
+        let mut dir = prefix;
+        while !dir.is_empty() {
+            let parent = dir.rfind_byte(b'/').map_or(b"".as_bstr(), |pos| dir[..pos].as_bstr());
+            tree = extension::Tree {
+                name: file_name(parent).into(),
+                id: tree.id.kind().null(),
+                num_entries: None,
+                children: vec![tree],
+            };
+            dir = parent;
+        }
+        tree
+    }
+
+    fn file_name(path: &BStr) -> &[u8] {
+        path.rfind_byte(b'/').map_or(path.as_bytes(), |pos| &path[pos + 1..])
+    }
+
+    pub(crate) struct CollectEntries {
+        pub(crate) entries: Vec<Entry>,
+        pub(crate) path_backing: PathStorage,
         path: BString,
         path_deque: VecDeque<BString>,
+        /// The repository-relative paths of all trees we encountered, along with their ids.
+        pub(crate) trees: Vec<(BString, gix_hash::ObjectId)>,
     }
 
     impl CollectEntries {
-        pub fn new() -> CollectEntries {
+        /// Collect entries of a tree that is located at the repository-relative `dir`, with all paths prefixed by it.
+        pub fn new_at(dir: BString) -> CollectEntries {
             CollectEntries {
                 entries: Vec::new(),
                 path_backing: Vec::new(),
-                path: BString::default(),
+                path: dir,
                 path_deque: VecDeque::new(),
+                trees: Vec::new(),
             }
         }
 
@@ -144,7 +270,8 @@ mod from_tree {
             }
         }
 
-        fn visit_tree(&mut self, _entry: &gix_object::tree::EntryRef<'_>) -> gix_traverse::tree::visit::Action {
+        fn visit_tree(&mut self, entry: &gix_object::tree::EntryRef<'_>) -> gix_traverse::tree::visit::Action {
+            self.trees.push((self.path.clone(), entry.oid.to_owned()));
             Action::Continue
         }
 
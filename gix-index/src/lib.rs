@@ -26,7 +26,9 @@ pub mod entry;
 
 mod access;
 
-mod init;
+///
+#[allow(clippy::empty_docs)]
+pub mod init;
 
 ///
 #[allow(clippy::empty_docs)]
@@ -40,6 +42,10 @@ pub mod verify;
 #[allow(clippy::empty_docs)]
 pub mod write;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod refresh;
+
 pub mod fs;
 
 /// All known versions of a git index file.
@@ -79,6 +85,9 @@ pub struct File {
     pub(crate) path: PathBuf,
     /// The checksum of all bytes prior to the checksum itself.
     pub(crate) checksum: Option<gix_hash::ObjectId>,
+    /// The checksum of the shared index whose entries are complemented by the entries of the index at `path`,
+    /// if it is a split index.
+    pub(crate) shared_index_checksum: Option<gix_hash::ObjectId>,
 }
 
 /// The type to use and store paths to all entries.
@@ -184,6 +193,22 @@ pub(crate) mod util {
         (num, data).into()
     }
 
+    /// Encode `n` into `buf` in the format understood by [`var_int()`], returning the bytes to write.
+    pub fn encode_var_int(mut n: u64, buf: &mut [u8; 10]) -> &[u8] {
+        let mut bytes_written = 1;
+        buf[buf.len() - 1] = n as u8 & 0b0111_1111;
+        for out in buf.iter_mut().rev().skip(1) {
+            n >>= 7;
+            if n == 0 {
+                break;
+            }
+            n -= 1;
+            *out = 0b1000_0000 | (n as u8 & 0b0111_1111);
+            bytes_written += 1;
+        }
+        &buf[buf.len() - bytes_written..]
+    }
+
     #[inline]
     pub fn read_u32(data: &[u8]) -> Option<(u32, &[u8])> {
         split_at_pos(data, 4).map(|(num, data)| (u32::from_be_bytes(num.try_into().unwrap()), data))
@@ -0,0 +1,229 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use bstr::{BStr, BString};
+use filetime::FileTime;
+
+use crate::{entry, Entry, State};
+
+/// The error returned by [`State::refresh()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The modification time of '{rela_path}' is before the unix epoch")]
+    Time {
+        rela_path: BString,
+        source: std::time::SystemTimeError,
+    },
+    #[error("Could not check if the content of '{rela_path}' was modified")]
+    IsModified {
+        rela_path: BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Options for use in [`State::refresh()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Capabilities of the file system which affect how the worktree is compared to our entries.
+    pub fs: gix_fs::Capabilities,
+    /// Options that control how stat comparisons are made when checking if a file is fresh.
+    pub stat: entry::stat::Options,
+    /// If set, don't use more than this amount of threads, with `Some(0)|None` meaning all logical cores.
+    pub thread_limit: Option<usize>,
+}
+
+/// The outcome of [`State::refresh()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of entries whose file in the worktree was checked.
+    pub entries_checked: usize,
+    /// The amount of entries whose stat information was updated as the content of their file didn't change.
+    pub entries_updated: usize,
+    /// The amount of entries whose stat information matched, but which were racy and had to have their content checked.
+    pub racy_clean: usize,
+    /// The amount of racy entries whose stat information matched even though their file was modified.
+    /// Their size was set to 0 to assure they are never considered unchanged by looking at stat information alone.
+    pub entries_smudged: usize,
+    /// The indices of all entries whose file was modified, removed or changed its type or executable bit, in order.
+    ///
+    /// This is similar to the paths that `git update-index --refresh` reports as *needs update*.
+    pub needs_update: Vec<usize>,
+}
+
+/// Refresh
+impl State {
+    /// Compare the stat information of all unconflicted entries with their files in the `worktree` and update it if
+    /// their content didn't change, using multiple threads as configured in `options`, similar to `git update-index --refresh`.
+    ///
+    /// The content of files whose stat information changed is checked with `is_modified(rela_path, entry, path)`, which
+    /// returns `true` if the file at `path` doesn't match `entry` anymore, usually by hashing it after converting it with
+    /// the filter pipeline.
+    ///
+    /// # Racy Git
+    ///
+    /// Files that are modified within the granularity of the filesystem timestamps after they were added to the index
+    /// have the same stat information despite the modification. Thus entries whose modification time isn't before our
+    /// [timestamp](State::timestamp()) are *racy* and always have their content checked. If it was modified, the size of
+    /// the entry is set to 0 (*smudged*) so that it's never considered unchanged again based on stat information alone.
+    /// Stat information that is updated here is racy itself and will be handled once the index is written and read again.
+    ///
+    /// Entries marked to [skip the worktree](entry::Flags::SKIP_WORKTREE), [assumed valid](entry::Flags::ASSUME_VALID),
+    /// [intended to be added](entry::Flags::INTENT_TO_ADD) or known to be unchanged by the
+    /// [filesystem monitor](entry::Flags::FSMONITOR_VALID) are skipped, as well as submodules and sparse directories.
+    pub fn refresh<E>(
+        &mut self,
+        worktree: &Path,
+        options: Options,
+        is_modified: impl Fn(&BStr, &Entry, &Path) -> Result<bool, E> + Send + Sync,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error>
+    where
+        E: std::error::Error + Send + Sync + 'static,
+    {
+        let _span = gix_features::trace::coarse!("gix_index::State::refresh()", num_entries = self.entries.len());
+        let (chunk_size, thread_limit, _) = gix_features::parallel::optimize_chunk_size_and_thread_limit(
+            500, // just like git
+            self.entries.len().into(),
+            options.thread_limit,
+            None,
+        );
+        let chunk_size = chunk_size.max(1);
+        let ctx = Context {
+            worktree,
+            path_backing: &self.path_backing,
+            timestamp: self.timestamp,
+            has_fs_monitor: self.fs_monitor.is_some(),
+            options,
+            is_modified: &is_modified,
+        };
+        let mut chunks: Vec<_> = self
+            .entries
+            .chunks_mut(chunk_size)
+            .enumerate()
+            .map(|(chunk_idx, entries)| (chunk_idx * chunk_size, entries))
+            .collect();
+        let outcomes = gix_features::parallel::in_parallel_with_slice(
+            &mut chunks,
+            thread_limit,
+            |_thread_id| Outcome::default(),
+            |(offset, entries), out, _threads_left, should_stop| {
+                for (idx, entry) in entries.iter_mut().enumerate() {
+                    if should_stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    ctx.refresh_entry(*offset + idx, entry, out)?;
+                }
+                Ok::<_, Error>(())
+            },
+            || (!should_interrupt.load(Ordering::Relaxed)).then(|| std::time::Duration::from_millis(50)),
+            std::convert::identity,
+        )?;
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+
+        let mut out = Outcome::default();
+        for outcome in outcomes {
+            out.entries_checked += outcome.entries_checked;
+            out.entries_updated += outcome.entries_updated;
+            out.racy_clean += outcome.racy_clean;
+            out.entries_smudged += outcome.entries_smudged;
+            out.needs_update.extend(outcome.needs_update);
+        }
+        out.needs_update.sort_unstable();
+        Ok(out)
+    }
+}
+
+struct Context<'a, F> {
+    worktree: &'a Path,
+    path_backing: &'a crate::PathStorageRef,
+    timestamp: FileTime,
+    has_fs_monitor: bool,
+    options: Options,
+    is_modified: &'a F,
+}
+
+impl<F, E> Context<'_, F>
+where
+    F: Fn(&BStr, &Entry, &Path) -> Result<bool, E>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    fn refresh_entry(&self, idx: usize, entry: &mut Entry, out: &mut Outcome) -> Result<(), Error> {
+        if entry.stage_raw() != 0
+            || entry.flags.intersects(
+                entry::Flags::SKIP_WORKTREE
+                    | entry::Flags::ASSUME_VALID
+                    | entry::Flags::INTENT_TO_ADD
+                    | entry::Flags::FSMONITOR_VALID
+                    | entry::Flags::REMOVE,
+            )
+            || entry.mode.is_submodule()
+            || entry.mode.is_sparse()
+        {
+            return Ok(());
+        }
+        out.entries_checked += 1;
+        let rela_path = entry.path_in(self.path_backing);
+        let path = self.worktree.join(gix_path::from_bstr(rela_path));
+        let metadata = match crate::fs::Metadata::from_path_no_follow(&path) {
+            Ok(metadata) => metadata,
+            Err(_) => {
+                out.needs_update.push(idx);
+                return Ok(());
+            }
+        };
+        if entry
+            .mode
+            .change_to_match_fs(&metadata, self.options.fs.symlink, self.options.fs.executable_bit)
+            .is_some()
+        {
+            out.needs_update.push(idx);
+            return Ok(());
+        }
+        let new_stat = entry::Stat::from_fs(&metadata).map_err(|source| Error::Time {
+            rela_path: rela_path.to_owned(),
+            source,
+        })?;
+        let stat_matches = new_stat.matches(&entry.stat, self.options.stat);
+        let is_racy = stat_matches && entry.stat.is_racy(self.timestamp, self.options.stat);
+        if stat_matches && !is_racy {
+            self.mark_valid(entry);
+            return Ok(());
+        }
+
+        let is_modified = (self.is_modified)(rela_path, entry, &path).map_err(|err| Error::IsModified {
+            rela_path: rela_path.to_owned(),
+            source: err.into(),
+        })?;
+        match (is_modified, is_racy) {
+            (true, true) => {
+                entry.stat.size = 0;
+                out.entries_smudged += 1;
+                out.needs_update.push(idx);
+            }
+            (true, false) => out.needs_update.push(idx),
+            (false, true) => {
+                out.racy_clean += 1;
+                self.mark_valid(entry);
+            }
+            (false, false) => {
+                entry.stat = new_stat;
+                out.entries_updated += 1;
+                self.mark_valid(entry);
+            }
+        }
+        Ok(())
+    }
+
+    fn mark_valid(&self, entry: &mut Entry) {
+        if self.has_fs_monitor {
+            entry.flags.insert(entry::Flags::FSMONITOR_VALID);
+        }
+    }
+}
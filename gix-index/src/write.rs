@@ -12,13 +12,19 @@ pub enum Extensions {
     ///
     /// # Note: mandatory extensions
     ///
-    /// Mandatory extensions, like `sdir` or other lower-case ones, may not be configured here as they need to be present
+    /// Mandatory extensions, like `sdir`, `link` or other lower-case ones, may not be configured here as they need to be present
     /// or absent depending on the state of the index itself and for it to be valid.
     Given {
         /// Write the tree-cache extension, if present.
         tree_cache: bool,
         /// Write the end-of-index-entry extension.
         end_of_index_entry: bool,
+        /// Write the resolve-undo extension, if present.
+        resolve_undo: bool,
+        /// Write the untracked cache extension, if present.
+        untracked_cache: bool,
+        /// Write the filesystem monitor extension, if present.
+        fs_monitor: bool,
     },
     /// Write no optional extension at all for what should be the smallest possible index
     None,
@@ -33,9 +39,15 @@ impl Extensions {
             Extensions::Given {
                 tree_cache,
                 end_of_index_entry,
+                resolve_undo,
+                untracked_cache,
+                fs_monitor,
             } => match signature {
                 extension::tree::SIGNATURE => tree_cache,
                 extension::end_of_index_entry::SIGNATURE => end_of_index_entry,
+                extension::resolve_undo::SIGNATURE => resolve_undo,
+                extension::untracked_cache::SIGNATURE => untracked_cache,
+                extension::fs_monitor::SIGNATURE => fs_monitor,
                 _ => &false,
             }
             .then(|| signature),
@@ -45,7 +57,8 @@ impl Extensions {
 
 /// The options for use when [writing an index][State::write_to()].
 ///
-/// Note that default options write either index V2 or V3 depending on the content of the entries.
+/// Note that default options write either index V2 or V3 depending on the content of the entries, unless the state
+/// is set to [V4](Version::V4), which is then preserved.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct Options {
     /// Configures which extensions to write.
@@ -56,8 +69,18 @@ pub struct Options {
     /// via [`File::write()`](crate::File::write()) and [`File::write_to()`](crate::File::write_to()).
     /// Note that
     pub skip_hash: bool,
+    /// The amount of threads that are expected to read the index, with `Some(0)|None` meaning all logical cores,
+    /// just like [`decode::Options::thread_limit`](crate::decode::Options::thread_limit).
+    ///
+    /// Large indices are split into blocks of entries that are recorded in the index-entry-offset-table extension
+    /// so that they can be decoded in parallel, but only if the end-of-index-entry extension is written as well.
+    /// `Some(1)` means such table is never written.
+    pub thread_limit: Option<usize>,
 }
 
+/// The minimum amount of entries in a block of the index-entry-offset-table, like in `git`.
+const MIN_ENTRIES_PER_BLOCK: usize = 10_000;
+
 impl State {
     /// Serialize this instance to `out` with [`options`][Options].
     pub fn write_to(
@@ -66,6 +89,7 @@ impl State {
         Options {
             extensions,
             skip_hash: _,
+            thread_limit,
         }: Options,
     ) -> std::io::Result<Version> {
         let _span = gix_features::trace::detail!("gix_index::State::write()");
@@ -85,9 +109,29 @@ impl State {
             .try_into()
             .expect("definitely not too many entries");
 
+        let num_blocks = if extensions
+            .should_write(extension::end_of_index_entry::SIGNATURE)
+            .is_some()
+        {
+            // The readers of the index may decode in parallel even if we can't, so don't rely on `gix_features::parallel`.
+            let num_threads = thread_limit
+                .filter(|limit| *limit != 0)
+                .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, Into::into));
+            // Leave one thread for loading extensions, which happens in parallel.
+            ((num_entries - removed_entries) as usize / MIN_ENTRIES_PER_BLOCK)
+                .min(num_threads.saturating_sub(1))
+                .max(1)
+        } else {
+            1
+        };
+        let entries_per_block = ((num_entries - removed_entries) as usize + num_blocks - 1) / num_blocks;
+
         let offset_to_entries = header(&mut write, version, num_entries - removed_entries)?;
-        let offset_to_extensions = entries(&mut write, self, offset_to_entries)?;
-        let (extension_toc, out) = self.write_extensions(write, offset_to_extensions, extensions)?;
+        let (offset_to_extensions, offset_table) =
+            entries(&mut write, self, version, offset_to_entries, entries_per_block)?;
+        let offset_table = (num_blocks > 1).then_some(offset_table);
+        let (extension_toc, out) =
+            self.write_extensions(write, offset_to_extensions, extensions, offset_table.as_deref())?;
 
         if num_entries > 0
             && extensions
@@ -106,17 +150,59 @@ impl State {
         mut write: CountBytes<T>,
         offset_to_extensions: u32,
         extensions: Extensions,
+        offset_table: Option<&[extension::index_entry_offset_table::Offset]>,
     ) -> std::io::Result<(Vec<(extension::Signature, u32)>, T)>
     where
         T: std::io::Write,
     {
         type WriteExtFn<'a> = &'a dyn Fn(&mut dyn std::io::Write) -> Option<std::io::Result<extension::Signature>>;
         let extensions: &[WriteExtFn<'_>] = &[
+            &|write| {
+                offset_table.map(|offsets| {
+                    extension::index_entry_offset_table::write_to(offsets, write)
+                        .map(|_| extension::index_entry_offset_table::SIGNATURE)
+                })
+            },
+            &|write| {
+                self.link()
+                    .map(|link| link.write_to(write).map(|_| extension::link::SIGNATURE))
+            },
             &|write| {
                 extensions
                     .should_write(extension::tree::SIGNATURE)
                     .and_then(|signature| self.tree().map(|tree| tree.write_to(write).map(|_| signature)))
             },
+            &|write| {
+                extensions
+                    .should_write(extension::resolve_undo::SIGNATURE)
+                    .and_then(|signature| {
+                        self.resolve_undo()
+                            .map(|paths| extension::resolve_undo::write_to(paths, write).map(|_| signature))
+                    })
+            },
+            &|write| {
+                extensions
+                    .should_write(extension::untracked_cache::SIGNATURE)
+                    .and_then(|signature| {
+                        self.untracked()
+                            .map(|untracked| untracked.write_to(write, self.object_hash).map(|_| signature))
+                    })
+            },
+            &|write| {
+                extensions
+                    .should_write(extension::fs_monitor::SIGNATURE)
+                    .and_then(|signature| {
+                        self.fs_monitor().map(|fs_monitor| {
+                            // Split indices carry the bitmap of the entries of the full index they were created from.
+                            if self.link().is_some() {
+                                fs_monitor.write_to(write)
+                            } else {
+                                fs_monitor.for_entries(&self.entries).write_to(write)
+                            }
+                            .map(|_| signature)
+                        })
+                    })
+            },
             &|write| {
                 self.is_sparse()
                     .then(|| extension::sparse::write_to(write).map(|_| extension::sparse::SIGNATURE))
@@ -139,6 +225,9 @@ impl State {
 
 impl State {
     fn detect_required_version(&self) -> Version {
+        if self.version == Version::V4 {
+            return Version::V4;
+        }
         self.entries
             .iter()
             .find_map(|e| e.flags.contains(entry::Flags::EXTENDED).then_some(Version::V3))
@@ -164,11 +253,24 @@ fn header<T: std::io::Write>(
     Ok(out.count)
 }
 
-fn entries<T: std::io::Write>(out: &mut CountBytes<T>, state: &State, header_size: u32) -> Result<u32, std::io::Error> {
+/// Write all entries of `state` and return the offset past them, along with the offsets to each block of `entries_per_block`
+/// entries.
+fn entries<T: std::io::Write>(
+    out: &mut CountBytes<T>,
+    state: &State,
+    version: Version,
+    header_size: u32,
+    entries_per_block: usize,
+) -> Result<(u32, Vec<extension::index_entry_offset_table::Offset>), std::io::Error> {
+    if version == Version::V4 {
+        return entries_with_delta_paths(out, state, entries_per_block);
+    }
+    let mut offsets = Offsets::new(entries_per_block);
     for entry in state.entries() {
         if entry.flags.contains(entry::Flags::REMOVE) {
             continue;
         }
+        offsets.add_entry(out.count);
         entry.write_to(&mut *out, state)?;
         match (out.count - header_size) % 8 {
             0 => {}
@@ -179,7 +281,74 @@ fn entries<T: std::io::Write>(out: &mut CountBytes<T>, state: &State, header_siz
         };
     }
 
-    Ok(out.count)
+    Ok((out.count, offsets.table))
+}
+
+/// Write all entries of `state` with their paths compressed against the path of the previous entry, without padding.
+/// The first entry of each block of `entries_per_block` entries strips the entire previous path and has its own written
+/// in full, so that the block can be decoded on its own.
+fn entries_with_delta_paths<T: std::io::Write>(
+    out: &mut CountBytes<T>,
+    state: &State,
+    entries_per_block: usize,
+) -> Result<(u32, Vec<extension::index_entry_offset_table::Offset>), std::io::Error> {
+    let mut offsets = Offsets::new(entries_per_block);
+    let mut prev_path: &[u8] = &[];
+    let mut buf = [0u8; 10];
+    for entry in state.entries() {
+        if entry.flags.contains(entry::Flags::REMOVE) {
+            continue;
+        }
+        let starts_block = offsets.add_entry(out.count);
+        let path = entry.path(state);
+        let common_prefix_len = if starts_block {
+            0
+        } else {
+            prev_path.iter().zip(path.iter()).take_while(|(a, b)| a == b).count()
+        };
+        entry.write_header_to(&mut *out, state)?;
+        out.write_all(crate::util::encode_var_int(
+            (prev_path.len() - common_prefix_len) as u64,
+            &mut buf,
+        ))?;
+        out.write_all(&path[common_prefix_len..])?;
+        out.write_all(b"\0")?;
+        prev_path = path;
+    }
+
+    Ok((out.count, offsets.table))
+}
+
+/// Keep track of the offsets to blocks of entries for the index-entry-offset-table.
+struct Offsets {
+    entries_per_block: usize,
+    table: Vec<extension::index_entry_offset_table::Offset>,
+}
+
+impl Offsets {
+    fn new(entries_per_block: usize) -> Self {
+        Offsets {
+            entries_per_block,
+            table: Vec::new(),
+        }
+    }
+
+    /// Record an entry at `offset` and return `true` if it starts a new block.
+    fn add_entry(&mut self, offset: u32) -> bool {
+        match self.table.last_mut() {
+            Some(block) if (block.num_entries as usize) < self.entries_per_block => {
+                block.num_entries += 1;
+                false
+            }
+            _ => {
+                self.table.push(extension::index_entry_offset_table::Offset {
+                    from_beginning_of_file: offset,
+                    num_entries: 1,
+                });
+                true
+            }
+        }
+    }
 }
 
 mod util {
@@ -214,6 +214,61 @@ fn remove_entries() {
     file.remove_entries(|_, _, _| unreachable!("should not be called"));
 }
 
+#[test]
+fn add_path() {
+    let mut file = Fixture::Generated("v4_more_files_IEOT").open();
+    let num_entries = file.entries().len();
+    let (id, mode, stat) = {
+        let entry = file.entry(0);
+        (entry.id, entry.mode, entry.stat)
+    };
+
+    let idx = file.add_path("e".into(), id, mode, stat);
+    assert_eq!(file.entry(idx).path(&file), "e");
+    assert_eq!(file.entries().len(), num_entries + 1, "new paths are inserted");
+    assert!(file.verify_entries().is_ok(), "entries remain sorted");
+
+    let new_id = gix_hash::ObjectId::empty_blob(gix_hash::Kind::Sha1);
+    let idx = file.add_path("a".into(), new_id, mode, stat);
+    assert_eq!(idx, 0);
+    assert_eq!(file.entry(idx).id, new_id, "existing entries are updated");
+    assert_eq!(file.entries().len(), num_entries + 1);
+
+    file.add_path("d".into(), id, mode, stat);
+    check_prefix(&file, "d", &["d"]);
+    file.add_path("x/y".into(), id, mode, stat);
+    check_prefix(&file, "x", &["x/y"]);
+    assert!(
+        file.verify_entries().is_ok(),
+        "entries that can't coexist with the new path are removed"
+    );
+}
+
+#[test]
+fn add_path_resolves_conflicts() {
+    let mut file = Fixture::Loose("conflicting-file").open();
+    assert!(file.resolve_undo().is_none());
+    let entry = file.entry(0).clone();
+
+    let idx = file.add_path("file".into(), entry.id, entry.mode, entry.stat);
+    assert_eq!(idx, 0);
+    assert_eq!(file.entries().len(), 1, "all stages are replaced by the new entry");
+    assert_eq!(file.entry(0).stage(), Stage::Unconflicted);
+    assert_eq!(
+        file.resolve_undo().map(Vec::len),
+        Some(1),
+        "the conflict is remembered to be able to undo the resolution"
+    );
+}
+
+#[test]
+fn remove_path() {
+    let mut file = Fixture::Loose("conflicting-file").open();
+    assert!(file.remove_path("file".into()), "all stages are removed");
+    assert_eq!(file.entries().len(), 0);
+    assert!(!file.remove_path("file".into()), "nothing left to remove");
+}
+
 #[test]
 fn sort_entries() {
     let mut file = Fixture::Generated("v4_more_files_IEOT").open();
@@ -311,3 +366,357 @@ fn check_prefix(index: &gix_index::State, prefix: &str, expected: &[&str]) {
         "{prefix:?}"
     );
 }
+
+#[test]
+fn expand_and_collapse_sparse_directories() -> crate::Result {
+    let full = Fixture::Generated("v3_skip_worktree").open();
+    let sparse = Fixture::Generated("v3_sparse_index").open();
+    let objects = gix::odb::at(sparse.path().parent().expect("in .git").join("objects"))?;
+    assert!(sparse.is_sparse());
+    assert_eq!(
+        sparse
+            .entries()
+            .iter()
+            .filter(|e| e.mode.is_sparse())
+            .map(|e| e.path(&sparse))
+            .collect::<Vec<_>>(),
+        ["c1/c3/", "d/"],
+        "the baseline only has sparse directories outside of the cone"
+    );
+
+    let mut expanded = sparse.clone();
+    assert!(
+        !expanded.expand_sparse_directory("a".into(), &objects)?,
+        "not a directory"
+    );
+    assert!(expanded.expand_sparse_directory("d".into(), &objects)?);
+    assert!(
+        expanded.is_sparse(),
+        "it's still sparse while there are sparse directories"
+    );
+    assert_eq!(expanded.expand_sparse_directories(&objects)?, 1, "only c1/c3/ was left");
+    assert!(!expanded.is_sparse());
+    expanded.verify_entries()?;
+    assert_eq!(
+        comparable_entries(&expanded),
+        comparable_entries(&full),
+        "expansion yields the same entries as a full index with skip-worktree entries"
+    );
+
+    let mut collapsed = full.clone();
+    assert!(
+        !collapsed.collapse_directory("c1".into()),
+        "c1 can't be collapsed as it has entries checked out in the worktree"
+    );
+    assert_eq!(collapsed.collapse_skipped_directories(), 2);
+    assert!(collapsed.is_sparse());
+    collapsed.verify_entries()?;
+    assert_eq!(comparable_entries(&collapsed), comparable_entries(&sparse));
+    Ok(())
+}
+
+fn comparable_entries(index: &gix_index::State) -> Vec<(BString, gix_hash::ObjectId, gix_index::entry::Mode, bool)> {
+    index
+        .entries()
+        .iter()
+        .map(|e| {
+            (
+                e.path(index).to_owned(),
+                e.id,
+                e.mode,
+                e.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn untracked_cache_maintenance() -> crate::Result {
+    use gix_index::extension::UntrackedCache;
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    let mut cache = UntrackedCache::new("id", ".gitignore", 0);
+    let stat = gix_index::entry::Stat {
+        size: 42,
+        ..Default::default()
+    };
+    cache.set_directory("".into(), stat, None, vec!["new".into()], Some("d".into()));
+    cache.set_directory("d".into(), stat, None, vec!["sub/".into()], None);
+    assert!(cache.valid_directory("".into(), &stat, None).is_some());
+    assert!(
+        cache.valid_directory("d".into(), &Default::default(), None).is_none(),
+        "the stat must match"
+    );
+    assert!(
+        cache
+            .valid_directory("d".into(), &stat, Some(gix_hash::Kind::Sha1.null()))
+            .is_none(),
+        "the exclude file must match"
+    );
+    assert_eq!(
+        cache.directory("d".into()).expect("present").untracked_entries,
+        ["sub/"]
+    );
+    state.set_untracked(Some(cache));
+
+    let mut buf = Vec::new();
+    gix_index::File::from_state(state.clone(), "unused").write_to(&mut buf, Default::default())?;
+    let (decoded, _) = gix_index::State::from_bytes(
+        &buf,
+        std::time::UNIX_EPOCH.into(),
+        gix_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    let decoded = decoded.untracked().expect("written and read back");
+    assert_eq!(decoded.identifier(), "id");
+    assert!(
+        decoded.valid_directory("d".into(), &stat, None).is_some(),
+        "directories survive a round-trip"
+    );
+
+    state.add_path(
+        "d/file".into(),
+        gix_hash::Kind::Sha1.null(),
+        gix_index::entry::Mode::FILE,
+        Default::default(),
+    );
+    let cache = state.untracked().expect("still present");
+    assert!(
+        cache.valid_directory("".into(), &stat, None).is_none(),
+        "adding paths invalidates all leading directories"
+    );
+    assert!(cache.valid_directory("d".into(), &stat, None).is_none());
+    Ok(())
+}
+
+#[test]
+fn fs_monitor_marks_unchanged_entries_as_valid() -> crate::Result {
+    use gix_index::{entry::Flags, extension::fs_monitor};
+    let mut file = Fixture::Loose("FSMN").open();
+    let valid_paths = |state: &gix_index::State| {
+        state
+            .entries()
+            .iter()
+            .filter(|e| e.flags.contains(Flags::FSMONITOR_VALID))
+            .map(|e| e.path(state).to_owned())
+            .collect::<Vec<_>>()
+    };
+    assert!(valid_paths(&file).is_empty(), "all entries are dirty in the fixture");
+    for entry in file.entries_mut() {
+        // pretend the entries were refreshed and found unchanged
+        entry.flags.insert(Flags::FSMONITOR_VALID);
+    }
+
+    let token = fs_monitor::Token::V2 { token: "next".into() };
+    let num_dirty = file.update_fs_monitor(
+        token.clone(),
+        fs_monitor::Changes::Paths(vec!["modified".into(), "dir1/".into()]),
+    );
+    assert_eq!(num_dirty, 3, "directories affect all entries below them");
+    assert_eq!(valid_paths(&file), ["dir2/modified", "dir2/tracked", "tracked"]);
+
+    let mut buf = Vec::new();
+    file.write_to(&mut buf, Default::default())?;
+    let (decoded, _) = gix_index::State::from_bytes(
+        &buf,
+        std::time::UNIX_EPOCH.into(),
+        gix_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    assert_eq!(decoded.fs_monitor().expect("written").token(), &token);
+    assert_eq!(
+        valid_paths(&decoded),
+        valid_paths(&file),
+        "the valid entries are restored from the written bitmap"
+    );
+
+    let idx = file.add_path(
+        "modified".into(),
+        gix_hash::Kind::Sha1.null(),
+        gix_index::entry::Mode::FILE,
+        Default::default(),
+    );
+    assert!(
+        file.entries()[idx].flags.contains(Flags::FSMONITOR_VALID),
+        "added entries have fresh stat information"
+    );
+    assert_eq!(file.update_fs_monitor(token, fs_monitor::Changes::All), 6);
+    assert!(file.set_fs_monitor(None).is_some());
+    assert!(file.fs_monitor().is_none());
+    Ok(())
+}
+
+#[test]
+fn conflicts_can_be_set_resolved_and_unresolved() -> crate::Result {
+    use gix_index::entry::Mode;
+    let id = |hex: &str| gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("valid");
+    let (base, ours, theirs) = (
+        id("0000000000000000000000000000000000000001"),
+        id("0000000000000000000000000000000000000002"),
+        id("0000000000000000000000000000000000000003"),
+    );
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    state.add_path("a".into(), base, Mode::FILE, Default::default());
+    state.add_path("b".into(), base, Mode::FILE, Default::default());
+    assert!(!state.has_conflicts());
+    assert!(state.conflict("a".into()).is_none());
+
+    assert!(!state.set_conflict("a".into(), [None, None, None]), "nothing to set");
+    assert!(state.set_conflict(
+        "a".into(),
+        [None, Some((Mode::FILE, ours)), Some((Mode::FILE_EXECUTABLE, theirs))]
+    ));
+    state.verify_entries()?;
+    assert!(state.has_conflicts());
+    let [b, o, t] = state.conflict("a".into()).expect("conflicted");
+    assert!(b.is_none(), "there was no base");
+    assert_eq!(o.map(|e| (e.id, e.stage())), Some((ours, Stage::Ours)));
+    assert_eq!(
+        t.map(|e| (e.mode, e.stage())),
+        Some((Mode::FILE_EXECUTABLE, Stage::Theirs))
+    );
+
+    assert!(
+        !state.resolve_conflict("a".into(), Stage::Base),
+        "there is no base to resolve with"
+    );
+    assert!(state.resolve_conflict("a".into(), Stage::Theirs));
+    assert!(!state.has_conflicts());
+    let entry = state.entry_by_path("a".into()).expect("present");
+    assert_eq!((entry.id, entry.mode), (theirs, Mode::FILE_EXECUTABLE));
+    let resolved = &state.resolve_undo().expect("recorded")[0];
+    assert_eq!(resolved.name(), "a");
+    assert!(resolved.stage(Stage::Base).is_none());
+    assert_eq!(
+        resolved.stage(Stage::Ours).map(|s| (s.id(), s.mode())),
+        Some((ours, Mode::FILE))
+    );
+
+    let mut buf = Vec::new();
+    gix_index::File::from_state(state.clone(), "unused").write_to(&mut buf, Default::default())?;
+    let (mut decoded, _) = gix_index::State::from_bytes(
+        &buf,
+        std::time::UNIX_EPOCH.into(),
+        gix_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    assert_eq!(
+        decoded.resolve_undo(),
+        state.resolve_undo(),
+        "round-trips through the extension"
+    );
+
+    assert!(!decoded.unresolve("b".into()), "b was never conflicted");
+    assert!(decoded.unresolve("a".into()));
+    decoded.verify_entries()?;
+    assert_eq!(
+        decoded
+            .entries()
+            .iter()
+            .map(|e| (e.path(&decoded).to_owned(), e.stage()))
+            .collect::<Vec<_>>(),
+        [
+            ("a".into(), Stage::Ours),
+            ("a".into(), Stage::Theirs),
+            ("b".into(), Stage::Unconflicted)
+        ],
+        "the conflict is restored"
+    );
+    assert!(decoded.resolve_undo().is_none(), "the record was consumed");
+    Ok(())
+}
+
+#[test]
+fn cache_tree_is_updated_incrementally() -> crate::Result {
+    use gix::objs::WriteTo;
+    fn write_trees(
+        count: &mut usize,
+    ) -> impl FnMut(&gix::objs::Tree) -> Result<gix_hash::ObjectId, std::io::Error> + '_ {
+        move |tree| {
+            *count += 1;
+            let mut buf = Vec::new();
+            tree.write_to(&mut buf)?;
+            Ok(gix::objs::compute_hash(
+                gix_hash::Kind::Sha1,
+                gix::objs::Kind::Tree,
+                &buf,
+            ))
+        }
+    }
+
+    let expected = Fixture::Generated("v2_more_files").open();
+    let mut state = gix_index::State::new(gix_hash::Kind::Sha1);
+    for entry in expected.entries() {
+        state.add_path(entry.path(&expected), entry.id, entry.mode, Default::default());
+    }
+    assert!(state.tree().is_none());
+
+    let mut count = 0;
+    let root = state.update_cache_tree(write_trees(&mut count))?;
+    assert_eq!(count, 2, "the root and 'd' were written");
+    assert_eq!(
+        state.tree(),
+        expected.tree(),
+        "the extension matches the one written by git"
+    );
+    assert_eq!(root, expected.tree().expect("present").id);
+
+    count = 0;
+    assert_eq!(state.update_cache_tree(write_trees(&mut count))?, root);
+    assert_eq!(count, 0, "nothing changed, so nothing needs to be written");
+
+    let id = gix_hash::ObjectId::empty_blob(gix_hash::Kind::Sha1);
+    state.add_path("d/e".into(), id, gix_index::entry::Mode::FILE, Default::default());
+    let tree = state.tree().expect("present");
+    assert_eq!(
+        (tree.num_entries, tree.children[0].num_entries),
+        (None, None),
+        "adding entries invalidates the trees leading to them"
+    );
+    count = 0;
+    let new_root = state.update_cache_tree(write_trees(&mut count))?;
+    assert_ne!(new_root, root);
+    assert_eq!(count, 2, "the root and 'd' were rewritten");
+    let tree = state.tree().expect("present");
+    assert_eq!((tree.num_entries, tree.children[0].num_entries), (Some(7), Some(4)));
+
+    state.remove_entries(|_, path, _| path.starts_with(b"d/"));
+    count = 0;
+    state.update_cache_tree(write_trees(&mut count))?;
+    assert_eq!(count, 1, "only the root was written");
+    let tree = state.tree().expect("present");
+    assert_eq!(tree.num_entries, Some(3));
+    assert!(tree.children.is_empty(), "'d' is gone");
+    let root = tree.id;
+
+    state.add_path("d/ita".into(), id, gix_index::entry::Mode::FILE, Default::default());
+    state
+        .entry_mut_by_path_and_stage("d/ita".into(), gix_index::entry::Stage::Unconflicted)
+        .expect("just added")
+        .flags
+        .insert(gix_index::entry::Flags::INTENT_TO_ADD);
+    count = 0;
+    assert_eq!(
+        state.update_cache_tree(write_trees(&mut count))?,
+        root,
+        "intent-to-add entries aren't part of the tree"
+    );
+    assert_eq!(
+        state.tree().expect("present").num_entries,
+        None,
+        "the trees leading to intent-to-add entries remain invalid"
+    );
+    count = 0;
+    state.update_cache_tree(write_trees(&mut count))?;
+    assert_eq!(count, 2, "the root and 'd' are recomputed each time");
+    state.remove_entries(|_, path, _| path == "d/ita");
+
+    state.set_conflict("b".into(), [None, Some((gix_index::entry::Mode::FILE, id)), None]);
+    assert!(
+        matches!(
+            state.update_cache_tree(write_trees(&mut count)),
+            Err(gix_index::extension::tree::update::Error::Conflict { path }) if path == "b"
+        ),
+        "conflicts can't be written as tree"
+    );
+    Ok(())
+}
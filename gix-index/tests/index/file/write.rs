@@ -15,6 +15,9 @@ fn roundtrips() -> crate::Result {
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: true,
+                resolve_undo: false,
+                untracked_cache: false,
+                fs_monitor: false,
             }),
         ),
         (Generated("V2_empty"), only_tree_ext()),
@@ -37,6 +40,108 @@ fn roundtrips() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn roundtrips_with_all_extensions() -> crate::Result {
+    for fixture in [Loose("REUC"), Loose("UNTR"), Loose("UNTR-with-oids"), Loose("FSMN")] {
+        let expected = fixture.open();
+        let expected_bytes = std::fs::read(fixture.to_path())?;
+        let mut out_bytes = Vec::new();
+
+        let options = options_with(write::Extensions::Given {
+            tree_cache: true,
+            end_of_index_entry: false,
+            resolve_undo: true,
+            untracked_cache: true,
+            fs_monitor: true,
+        });
+        let (actual_version, _digest) = expected.write_to(&mut out_bytes, options)?;
+        let (actual, _) = State::from_bytes(&out_bytes, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+
+        let name = fixture.to_name();
+        compare_states_against_baseline(&actual, actual_version, &expected, options, name);
+        compare_raw_bytes(&out_bytes, &expected_bytes, name);
+    }
+    Ok(())
+}
+
+#[test]
+fn v4_is_preserved_and_compresses_paths() -> crate::Result {
+    let mut index = Generated("v2_more_files").open();
+    let mut v2_bytes = Vec::new();
+    index.write_to(&mut v2_bytes, Default::default())?;
+
+    index.set_version(Version::V4);
+    let mut v4_bytes = Vec::new();
+    let (actual_version, _digest) = index.write_to(&mut v4_bytes, Default::default())?;
+    assert_eq!(actual_version, Version::V4, "the configured version is used");
+    assert!(
+        v4_bytes.len() < v2_bytes.len(),
+        "paths are compressed and entries aren't padded"
+    );
+
+    let (actual, _) = State::from_bytes(&v4_bytes, FileTime::now(), gix_hash::Kind::Sha1, Default::default())?;
+    compare_states_against_baseline(&actual, actual_version, &index, Default::default(), "v2_more_files");
+    Ok(())
+}
+
+#[test]
+fn large_indices_get_an_offset_table_for_threaded_decoding() -> crate::Result {
+    let mut state = State::new(gix_hash::Kind::Sha1);
+    let id = gix_hash::ObjectId::empty_blob(gix_hash::Kind::Sha1);
+    for idx in 0..25_000 {
+        state.dangerously_push_entry(
+            Default::default(),
+            id,
+            entry::Flags::empty(),
+            entry::Mode::FILE,
+            format!("dir{}/file{idx:05}", idx / 1000).as_str().into(),
+        );
+    }
+    state.sort_entries();
+
+    for version in [Version::V2, Version::V4] {
+        state.set_version(version);
+        let file = gix_index::File::from_state(state.clone(), "unused");
+        for (thread_limit, expect_offset_table) in [(Some(1), false), (Some(2), false), (Some(4), true)] {
+            let mut buf = Vec::new();
+            let (actual_version, _digest) = file.write_to(
+                &mut buf,
+                Options {
+                    thread_limit,
+                    ..Default::default()
+                },
+            )?;
+            assert_eq!(actual_version, version);
+            for decode_thread_limit in [Some(1), Some(4)] {
+                let (actual, _) = State::from_bytes(
+                    &buf,
+                    FileTime::now(),
+                    gix_hash::Kind::Sha1,
+                    gix_index::decode::Options {
+                        thread_limit: decode_thread_limit,
+                        ..Default::default()
+                    },
+                )?;
+                assert_eq!(
+                    actual.had_offset_table(),
+                    expect_offset_table,
+                    "the entries can only be split if there is more than one thread to decode them"
+                );
+                assert_eq!(actual.had_end_of_index_marker(), expect_offset_table);
+                assert!(
+                    actual
+                        .entries()
+                        .iter()
+                        .map(|e| (e.path(&actual), e.id, e.mode))
+                        .eq(state.entries().iter().map(|e| (e.path(&state), e.id, e.mode))),
+                    "{version:?}: entries decode the same, in parallel or not"
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn skip_hash() -> crate::Result {
     let tmp = gix_testtools::tempfile::TempDir::new()?;
@@ -48,6 +153,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: false,
+        thread_limit: None,
     })?;
 
     let actual = gix_index::File::at(
@@ -65,6 +171,7 @@ fn skip_hash() -> crate::Result {
     expected.write(Options {
         extensions: Default::default(),
         skip_hash: true,
+        thread_limit: None,
     })?;
 
     let actual = gix_index::File::at(
@@ -133,9 +240,7 @@ fn state_comparisons_with_various_extension_configurations() {
         Generated("v2_more_files"),
         Generated("v2_all_file_kinds"),
         Generated("v2_split_index"),
-        // TODO: this fails because git allows to configure the index version while gitoxide doesn't
-        //       the fixture artificially sets the version to V4 and gitoxide writes it back out as the lowest required version, V2
-        // Generated("v4_more_files_IEOT"),
+        Generated("v4_more_files_IEOT"),
         Generated("v3_skip_worktree"),
         Generated("v3_added_files"),
         Generated("v3_sparse_index_non_cone"),
@@ -149,10 +254,16 @@ fn state_comparisons_with_various_extension_configurations() {
             options_with(write::Extensions::Given {
                 tree_cache: true,
                 end_of_index_entry: false,
+                resolve_undo: false,
+                untracked_cache: false,
+                fs_monitor: false,
             }),
             options_with(write::Extensions::Given {
                 tree_cache: false,
                 end_of_index_entry: true,
+                resolve_undo: false,
+                untracked_cache: false,
+                fs_monitor: false,
             }),
         ] {
             let expected = fixture.open();
@@ -293,8 +404,12 @@ fn only_tree_ext() -> Options {
         extensions: write::Extensions::Given {
             end_of_index_entry: false,
             tree_cache: true,
+            resolve_undo: false,
+            untracked_cache: false,
+            fs_monitor: false,
         },
         skip_hash: false,
+        thread_limit: None,
     }
 }
 
@@ -302,5 +417,161 @@ fn options_with(extensions: write::Extensions) -> Options {
     Options {
         extensions,
         skip_hash: false,
+        thread_limit: None,
+    }
+}
+
+#[test]
+fn write_checked_detects_changes_on_disk() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let path = tmp.path().join("index");
+    let mut index = Generated("v2_more_files").open();
+    index.set_path(&path);
+    index.write(Default::default())?;
+
+    let id = gix_hash::ObjectId::empty_blob(gix_hash::Kind::Sha1);
+    let mut ours = gix_index::File::at(&path, gix_hash::Kind::Sha1, false, Default::default())?;
+    ours.add_path("ours".into(), id, entry::Mode::FILE, Default::default());
+    ours.write_checked(Default::default(), |_, _| -> Result<_, std::io::Error> {
+        unreachable!("the index on disk is unchanged")
+    })?;
+
+    let mut theirs = gix_index::File::at(&path, gix_hash::Kind::Sha1, false, Default::default())?;
+    theirs.add_path("theirs".into(), id, entry::Mode::FILE, Default::default());
+    theirs.write(Default::default())?;
+
+    ours.remove_path("a".into());
+    let err = ours
+        .write_checked(Default::default(), |_, _| {
+            Err(std::io::Error::new(std::io::ErrorKind::Other, "refuse"))
+        })
+        .unwrap_err();
+    assert!(matches!(err, gix_index::file::write::Error::OnChange(_)));
+    assert!(!path.with_extension("lock").exists(), "the lock was released");
+    let on_disk = gix_index::File::at(&path, gix_hash::Kind::Sha1, false, Default::default())?;
+    assert_eq!(on_disk.checksum(), theirs.checksum(), "nothing was written");
+
+    ours.write_checked(Default::default(), |state, on_disk| -> Result<_, std::io::Error> {
+        assert!(on_disk.entry_by_path("theirs".into()).is_some());
+        for entry in on_disk.entries() {
+            let path = entry.path(&on_disk);
+            if state.entry_by_path(path).is_none() && path != "a" {
+                state.add_path(path, entry.id, entry.mode, entry.stat);
+            }
+        }
+        Ok(())
+    })?;
+    let on_disk = gix_index::File::at(&path, gix_hash::Kind::Sha1, false, Default::default())?;
+    assert_eq!(on_disk.checksum(), ours.checksum());
+    assert_eq!(
+        on_disk.entries().iter().map(|e| e.path(&on_disk)).collect::<Vec<_>>(),
+        ["b", "c", "d/a", "d/b", "d/c", "ours", "theirs"],
+        "the changes of both writers are retained"
+    );
+    Ok(())
+}
+
+#[test]
+fn split_index() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let fixture_dir = Generated("v2_split_index")
+        .to_path()
+        .parent()
+        .expect("in .git")
+        .to_owned();
+    for entry in std::fs::read_dir(&fixture_dir)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        if name == "index" || name.to_string_lossy().starts_with("sharedindex.") {
+            std::fs::copy(entry.path(), tmp.path().join(name))?;
+        }
     }
+    let path = tmp.path().join("index");
+    let mut index = gix_index::File::at(&path, gix_hash::Kind::Sha1, false, Default::default())?;
+    let shared_index_checksum = index.shared_index_checksum().expect("the fixture is a split index");
+
+    let entry = index.entries()[0].clone();
+    index.dangerously_push_entry(entry.stat, entry.id, entry.flags, entry.mode, "b".into());
+    index.write_split(Default::default(), 100)?;
+    assert_eq!(
+        index.shared_index_checksum(),
+        Some(shared_index_checksum),
+        "the shared index is reused as not too many entries changed"
+    );
+    let split = split_state_at(&path)?;
+    assert_eq!(split.entries().len(), 1, "only the new entry is written");
+    assert_eq!(split.entries()[0].path(&split), "b");
+    assert_eq!(
+        split.link().expect("present").shared_index_checksum,
+        shared_index_checksum
+    );
+    assert_same_entries(&path, &index)?;
+
+    let in_memory_flags = gix_index::entry::Flags::FSMONITOR_VALID | gix_index::entry::Flags::UPTODATE;
+    index.entries_mut()[0].flags.insert(in_memory_flags);
+    index.write_split(Default::default(), 100)?;
+    assert_eq!(
+        split_state_at(&path)?.entries().len(),
+        1,
+        "flags that aren't stored on disk don't cause the shared entry to be replaced"
+    );
+    index.entries_mut()[0].flags.remove(in_memory_flags);
+
+    index.entries_mut()[0].id = gix_hash::Kind::Sha1.null();
+    index.write_split(Default::default(), 100)?;
+    let split = split_state_at(&path)?;
+    assert_eq!(
+        split.entries().iter().map(|e| e.path(&split)).collect::<Vec<_>>(),
+        ["", "b"],
+        "the changed entry replaces the shared one, and has no path"
+    );
+    assert_same_entries(&path, &index)?;
+
+    index.remove_entries(|_, path, _| path == "a");
+    index.write_split(Default::default(), 100)?;
+    let split = split_state_at(&path)?;
+    assert_eq!(split.entries().len(), 1, "the deletion is recorded in a bitmap");
+    assert_same_entries(&path, &index)?;
+
+    index.write_split(Default::default(), 20)?;
+    let new_shared_index_checksum = index.shared_index_checksum().expect("still split");
+    assert_ne!(
+        new_shared_index_checksum, shared_index_checksum,
+        "too many entries changed and a new shared index was written"
+    );
+    assert!(tmp
+        .path()
+        .join(format!("sharedindex.{new_shared_index_checksum}"))
+        .is_file());
+    assert_eq!(split_state_at(&path)?.entries().len(), 0, "all entries are shared");
+    assert_same_entries(&path, &index)?;
+
+    index.write(Default::default())?;
+    assert_eq!(index.shared_index_checksum(), None);
+    assert!(split_state_at(&path)?.link().is_none(), "a full index was written");
+    assert_same_entries(&path, &index)?;
+    Ok(())
+}
+
+fn split_state_at(path: &std::path::Path) -> crate::Result<State> {
+    let (state, _) = State::from_bytes(
+        &std::fs::read(path)?,
+        FileTime::now(),
+        gix_hash::Kind::Sha1,
+        Default::default(),
+    )?;
+    Ok(state)
+}
+
+fn assert_same_entries(path: &std::path::Path, expected: &State) -> crate::Result {
+    let actual = gix_index::File::at(path, gix_hash::Kind::Sha1, false, Default::default())?;
+    let comparable = |state: &State| {
+        state
+            .entries()
+            .iter()
+            .map(|e| (e.path(state).to_owned(), e.id, e.stat, e.flags, e.mode))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(comparable(&actual), comparable(expected));
+    Ok(())
 }
@@ -19,11 +19,69 @@ fn from_tree() -> crate::Result {
         let expected_state = repo.index()?;
         let actual_state = State::from_tree(&tree_id, &repo.objects)?;
 
-        compare_states(&actual_state, &expected_state, fixture)
+        compare_states(&actual_state, &expected_state, fixture);
+        actual_state.verify_extensions(true, &repo.objects)?;
+        assert_eq!(
+            actual_state.tree(),
+            expected_state.tree(),
+            "the tree extension matches the one git wrote in {fixture:?}"
+        );
     }
     Ok(())
 }
 
+#[test]
+fn from_tree_with_sparse_cone_directories() -> crate::Result {
+    let repo_dir = scripted_fixture_read_only_standalone("make_index/v3_skip_worktree.sh")?;
+    let repo = gix::open(&repo_dir)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let expected_state = repo.index()?;
+    let actual_state = State::from_tree_with_options(
+        &tree_id,
+        &repo.objects,
+        gix_index::init::from_tree::Options {
+            sparse_cone_directories: Some(vec!["c1/c2".into()]),
+            ..Default::default()
+        },
+    )?;
+    compare_states(&actual_state, &expected_state, "v3_skip_worktree");
+    Ok(())
+}
+
+#[test]
+fn from_tree_with_prefix() -> crate::Result {
+    let repo_dir = scripted_fixture_read_only_standalone("make_index/v2_more_files.sh")?;
+    let repo = gix::open(&repo_dir)?;
+    let tree_id = repo.head_commit()?.tree_id()?;
+
+    let state = State::from_tree_with_options(
+        &tree_id,
+        &repo.objects,
+        gix_index::init::from_tree::Options {
+            prefix: Some("p1/p2/".into()),
+            ..Default::default()
+        },
+    )?;
+    state.verify_entries()?;
+    assert_eq!(
+        state.entries().iter().map(|e| e.path(&state)).collect::<Vec<_>>(),
+        ["p1/p2/a", "p1/p2/b", "p1/p2/c", "p1/p2/d/a", "p1/p2/d/b", "p1/p2/d/c"]
+    );
+
+    let root = state.tree().expect("always set");
+    assert_eq!(root.num_entries, None, "trees leading to the prefix are unknown");
+    let p1 = &root.children[0];
+    assert_eq!((p1.name.as_slice(), p1.num_entries), (&b"p1"[..], None));
+    let p2 = &p1.children[0];
+    assert_eq!(p2.name.as_slice(), b"p2");
+    assert_eq!(p2.id, tree_id, "the tree itself is known");
+    assert_eq!(p2.num_entries, Some(6));
+    assert_eq!(p2.children.len(), 1, "the 'd' sub-tree");
+    assert_eq!(p2.children[0].num_entries, Some(3));
+    Ok(())
+}
+
 #[test]
 fn new() {
     let state = State::new(gix_hash::Kind::Sha1);
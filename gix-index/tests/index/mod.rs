@@ -7,6 +7,7 @@ mod entry;
 mod file;
 mod fs;
 mod init;
+mod refresh;
 
 pub fn hex_to_id(hex: &str) -> ObjectId {
     ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
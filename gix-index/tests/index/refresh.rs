@@ -0,0 +1,106 @@
+use std::{
+    path::Path,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+
+use bstr::BStr;
+use filetime::FileTime;
+use gix_index::{entry, refresh, Entry, State};
+
+fn blob_id(data: &[u8]) -> gix_hash::ObjectId {
+    gix::objs::compute_hash(gix_hash::Kind::Sha1, gix::objs::Kind::Blob, data)
+}
+
+#[test]
+fn stat_information_is_updated_and_racy_entries_are_smudged() -> crate::Result {
+    let tmp = gix_testtools::tempfile::TempDir::new()?;
+    let root = tmp.path();
+    std::fs::create_dir(root.join("d"))?;
+    let t0 = FileTime::from_unix_time(1_000_000_000, 0);
+    let mut state = State::new(gix_hash::Kind::Sha1);
+    for (path, content) in [("a", "a"), ("b", "b"), ("d/c", "c"), ("gone", "g")] {
+        let file = root.join(path);
+        std::fs::write(&file, content)?;
+        filetime::set_file_mtime(&file, t0)?;
+        let stat = entry::Stat::from_fs(&gix_index::fs::Metadata::from_path_no_follow(&file)?)?;
+        state.add_path(path.into(), blob_id(content.as_bytes()), entry::Mode::FILE, stat);
+    }
+    std::fs::remove_file(root.join("gone"))?;
+    state.set_timestamp(FileTime::from_unix_time(1_000_001_000, 0));
+
+    let content_checks = AtomicUsize::default();
+    let is_modified = |_rela_path: &BStr, entry: &Entry, path: &Path| -> std::io::Result<bool> {
+        content_checks.fetch_add(1, Ordering::Relaxed);
+        Ok(blob_id(&std::fs::read(path)?) != entry.id)
+    };
+    let options = refresh::Options {
+        stat: entry::stat::Options {
+            trust_ctime: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let should_interrupt = AtomicBool::default();
+
+    let out = state.refresh(root, options, is_modified, &should_interrupt)?;
+    assert_eq!(
+        out,
+        refresh::Outcome {
+            entries_checked: 4,
+            needs_update: vec![3],
+            ..Default::default()
+        },
+        "nothing changed, but 'gone' was removed"
+    );
+    assert_eq!(content_checks.load(Ordering::Relaxed), 0, "stat information matched");
+
+    filetime::set_file_mtime(root.join("a"), FileTime::from_unix_time(1_000_000_100, 0))?;
+    let out = state.refresh(root, options, is_modified, &should_interrupt)?;
+    assert_eq!(
+        (out.entries_updated, out.needs_update.as_slice()),
+        (1, &[3][..]),
+        "a was touched but is unchanged"
+    );
+    assert_eq!(state.entries()[0].stat.mtime.secs, 1_000_000_100);
+    assert_eq!(content_checks.swap(0, Ordering::Relaxed), 1);
+
+    std::fs::write(root.join("b"), "x")?;
+    filetime::set_file_mtime(root.join("b"), t0)?;
+    let out = state.refresh(root, options, is_modified, &should_interrupt)?;
+    assert_eq!(
+        out.needs_update,
+        [3],
+        "the modification of 'b' can't be seen as its stat information is unchanged, and it's not racy"
+    );
+
+    state.set_timestamp(t0);
+    let out = state.refresh(root, options, is_modified, &should_interrupt)?;
+    assert_eq!(
+        out,
+        refresh::Outcome {
+            entries_checked: 4,
+            entries_updated: 0,
+            racy_clean: 2,
+            entries_smudged: 1,
+            needs_update: vec![1, 3],
+        },
+        "entries at or after the index timestamp are racy and are checked by content, so the modification of 'b' is seen"
+    );
+    assert_eq!(content_checks.swap(0, Ordering::Relaxed), 3);
+    assert_eq!(state.entries()[1].stat.size, 0, "'b' was smudged");
+
+    state.set_timestamp(FileTime::from_unix_time(1_000_001_000, 0));
+    let out = state.refresh(root, options, is_modified, &should_interrupt)?;
+    assert_eq!(
+        (out.entries_smudged, out.needs_update.as_slice()),
+        (0, &[1, 3][..]),
+        "smudged entries are always checked by content, even if they are not racy anymore"
+    );
+
+    should_interrupt.store(true, Ordering::Relaxed);
+    assert!(matches!(
+        state.refresh(root, options, is_modified, &should_interrupt),
+        Err(refresh::Error::Interrupted)
+    ));
+    Ok(())
+}
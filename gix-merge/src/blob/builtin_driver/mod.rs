@@ -0,0 +1,5 @@
+//! The merge drivers that are built into `git`, and don't need an external program to run.
+
+///
+#[allow(clippy::empty_docs)]
+pub mod text;
@@ -0,0 +1,412 @@
+use std::ops::Range;
+
+use bstr::BStr;
+use gix_diff::blob::{
+    intern::{InternedInput, Interner, Token},
+    sources::byte_lines_with_terminator,
+};
+
+use crate::blob::{
+    builtin_driver::text::{Conflict, ConflictStyle, Labels, Options},
+    Resolution,
+};
+
+/// Merge `current` and `other` with `ancestor` as base line by line, and append the result to `out`, with
+/// `labels` written next to the conflict markers.
+///
+/// Changes made by only one side are taken as is, just like identical changes on both sides, while overlapping
+/// or adjacent changes are conflicts that are handled according to `options`.
+///
+/// The output is the same as the one of `git merge-file`.
+pub fn merge(
+    out: &mut Vec<u8>,
+    labels: Labels<'_>,
+    current: &[u8],
+    ancestor: &[u8],
+    other: &[u8],
+    options: Options,
+) -> Resolution {
+    let mut input = InternedInput::new(
+        byte_lines_with_terminator(ancestor),
+        byte_lines_with_terminator(current),
+    );
+    let mut hunks = collect_hunks(&input, options.diff_algorithm, Side::Current);
+    let current_tokens = std::mem::take(&mut input.after);
+    input.update_after(byte_lines_with_terminator(other));
+    hunks.extend(collect_hunks(&input, options.diff_algorithm, Side::Other));
+    hunks.sort_by_key(|hunk| hunk.before.start);
+
+    let tokens = Tokens {
+        ancestor: &input.before,
+        current: &current_tokens,
+        other: &input.after,
+    };
+    let mut regions = group_hunks(&hunks, &tokens);
+    let style = match options.conflict {
+        Conflict::Keep { style, .. } => style,
+        Conflict::ResolveWithOurs | Conflict::ResolveWithTheirs | Conflict::ResolveWithUnion => ConflictStyle::Merge,
+    };
+    match style {
+        ConflictStyle::Merge => {
+            regions = refine_conflicts(regions, &tokens, input.interner.num_tokens(), options.diff_algorithm);
+            regions = combine_close_conflicts(regions);
+        }
+        ConflictStyle::ZealousDiff3 => trim_conflicts(&mut regions, &tokens),
+        ConflictStyle::Diff3 => {}
+    }
+
+    let mut writer = Writer {
+        out,
+        interner: &input.interner,
+        tokens: &tokens,
+    };
+    let mut resolution = Resolution::Complete;
+    for region in &regions {
+        match region.kind {
+            Kind::Unchanged | Kind::Current => writer.write_lines(tokens.current, &region.current, None),
+            Kind::Other => writer.write_lines(tokens.other, &region.other, None),
+            Kind::Conflict => {
+                let eol = writer.conflict_eol(region);
+                match options.conflict {
+                    Conflict::Keep { style, marker_size } => {
+                        resolution = Resolution::Conflict;
+                        let marker_size = marker_size.get() as usize;
+                        writer.write_marker(b'<', marker_size, labels.current, eol);
+                        writer.write_lines(tokens.current, &region.current, Some(eol));
+                        if style != ConflictStyle::Merge {
+                            writer.write_marker(b'|', marker_size, labels.ancestor, eol);
+                            writer.write_lines(tokens.ancestor, &region.ancestor, Some(eol));
+                        }
+                        writer.write_marker(b'=', marker_size, None, eol);
+                        writer.write_lines(tokens.other, &region.other, Some(eol));
+                        writer.write_marker(b'>', marker_size, labels.other, eol);
+                    }
+                    Conflict::ResolveWithOurs => {
+                        writer.write_lines(tokens.current, &region.current, None);
+                    }
+                    Conflict::ResolveWithTheirs => {
+                        writer.write_lines(tokens.other, &region.other, None);
+                    }
+                    Conflict::ResolveWithUnion => {
+                        writer.write_lines(tokens.current, &region.current, Some(eol));
+                        writer.write_lines(tokens.other, &region.other, None);
+                    }
+                }
+                if resolution == Resolution::Complete {
+                    resolution = Resolution::CompleteWithAutoResolvedConflict;
+                }
+            }
+        }
+    }
+    resolution
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Side {
+    Current,
+    Other,
+}
+
+/// A change of one side compared to the ancestor, with `before` being the lines in the ancestor, and `after` the lines of `side`.
+struct Hunk {
+    before: Range<u32>,
+    after: Range<u32>,
+    side: Side,
+}
+
+fn collect_hunks(input: &InternedInput<&[u8]>, algorithm: gix_diff::blob::Algorithm, side: Side) -> Vec<Hunk> {
+    let mut hunks = Vec::new();
+    gix_diff::blob::diff(algorithm, input, |before: Range<u32>, after: Range<u32>| {
+        hunks.push(Hunk { before, after, side });
+    });
+    hunks
+}
+
+struct Tokens<'a> {
+    ancestor: &'a [Token],
+    current: &'a [Token],
+    other: &'a [Token],
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Kind {
+    /// The lines are the same in all versions.
+    Unchanged,
+    /// Only our side changed the lines, or both sides changed them in the same way.
+    Current,
+    /// Only their side changed the lines.
+    Other,
+    /// Both sides changed the lines differently.
+    Conflict,
+}
+
+/// A range of lines in each of the versions, which represent the same portion of the merged text.
+#[derive(Debug, Clone)]
+struct Region {
+    kind: Kind,
+    ancestor: Range<u32>,
+    current: Range<u32>,
+    other: Range<u32>,
+}
+
+/// Turn `hunks`, sorted by their position in the ancestor, into regions which are conflicting if hunks of both sides overlap
+/// or touch each other.
+fn group_hunks(hunks: &[Hunk], tokens: &Tokens<'_>) -> Vec<Region> {
+    let mut regions = Vec::new();
+    // The difference between the position of a line in the ancestor and the position of the same line in each side.
+    let (mut current_offset, mut other_offset) = (0i64, 0i64);
+    let at = |pos: u32, offset: i64| (pos as i64 + offset) as u32;
+    let mut ancestor_pos = 0;
+    let mut hunks = hunks.iter().peekable();
+    while let Some(first) = hunks.next() {
+        let start = first.before.start;
+        if start > ancestor_pos {
+            regions.push(Region {
+                kind: Kind::Unchanged,
+                ancestor: ancestor_pos..start,
+                current: at(ancestor_pos, current_offset)..at(start, current_offset),
+                other: at(ancestor_pos, other_offset)..at(start, other_offset),
+            });
+        }
+
+        let (current_start, other_start) = (at(start, current_offset), at(start, other_offset));
+        let mut end = first.before.end;
+        let (mut changed_current, mut changed_other) = (false, false);
+        let mut group = Some(first);
+        while let Some(hunk) = group {
+            end = end.max(hunk.before.end);
+            let offset = hunk.after.len() as i64 - hunk.before.len() as i64;
+            match hunk.side {
+                Side::Current => {
+                    changed_current = true;
+                    current_offset += offset;
+                }
+                Side::Other => {
+                    changed_other = true;
+                    other_offset += offset;
+                }
+            }
+            group = hunks.next_if(|next| next.before.start <= end);
+        }
+
+        let current = current_start..at(end, current_offset);
+        let other = other_start..at(end, other_offset);
+        let kind = match (changed_current, changed_other) {
+            (true, false) => Kind::Current,
+            (false, true) => Kind::Other,
+            _ if lines(tokens.current, &current) == lines(tokens.other, &other) => Kind::Current,
+            _ => Kind::Conflict,
+        };
+        regions.push(Region {
+            kind,
+            ancestor: start..end,
+            current,
+            other,
+        });
+        ancestor_pos = end;
+    }
+    if (ancestor_pos as usize) < tokens.ancestor.len() {
+        let end = tokens.ancestor.len() as u32;
+        regions.push(Region {
+            kind: Kind::Unchanged,
+            ancestor: ancestor_pos..end,
+            current: at(ancestor_pos, current_offset)..at(end, current_offset),
+            other: at(ancestor_pos, other_offset)..at(end, other_offset),
+        });
+    }
+    regions
+}
+
+fn lines<'a>(tokens: &'a [Token], range: &Range<u32>) -> &'a [Token] {
+    &tokens[range.start as usize..range.end as usize]
+}
+
+/// Diff our and their side of each conflict, and split it into smaller conflicts separated by the lines they have in common.
+///
+/// Conflicts where one side is empty are left alone.
+fn refine_conflicts(
+    regions: Vec<Region>,
+    tokens: &Tokens<'_>,
+    num_tokens: u32,
+    algorithm: gix_diff::blob::Algorithm,
+) -> Vec<Region> {
+    let mut out = Vec::with_capacity(regions.len());
+    for region in regions {
+        if region.kind != Kind::Conflict || region.current.is_empty() || region.other.is_empty() {
+            out.push(region);
+            continue;
+        }
+        let (current_start, other_start) = (region.current.start, region.other.start);
+        let mut changes = Vec::new();
+        gix_diff::blob::diff_with_tokens(
+            algorithm,
+            lines(tokens.current, &region.current),
+            lines(tokens.other, &region.other),
+            num_tokens,
+            |current: Range<u32>, other: Range<u32>| {
+                changes.push((
+                    current.start + current_start..current.end + current_start,
+                    other.start + other_start..other.end + other_start,
+                ));
+            },
+        );
+
+        let (mut current_pos, mut other_pos) = (current_start, other_start);
+        for (current, other) in changes {
+            if current.start > current_pos {
+                out.push(Region {
+                    kind: Kind::Unchanged,
+                    ancestor: region.ancestor.clone(),
+                    current: current_pos..current.start,
+                    other: other_pos..other.start,
+                });
+            }
+            current_pos = current.end;
+            other_pos = other.end;
+            out.push(Region {
+                kind: Kind::Conflict,
+                ancestor: region.ancestor.clone(),
+                current,
+                other,
+            });
+        }
+        if current_pos < region.current.end {
+            out.push(Region {
+                kind: Kind::Unchanged,
+                ancestor: region.ancestor.clone(),
+                current: current_pos..region.current.end,
+                other: other_pos..region.other.end,
+            });
+        }
+    }
+    out
+}
+
+/// Combine conflicts that are separated by no more than this amount of unchanged lines.
+const MAX_LINES_BETWEEN_COMBINED_CONFLICTS: usize = 3;
+
+/// Make conflicts easier to resolve by combining those that are close to each other, along with the lines between them.
+fn combine_close_conflicts(regions: Vec<Region>) -> Vec<Region> {
+    let mut out: Vec<Region> = Vec::with_capacity(regions.len());
+    for region in regions {
+        if region.kind == Kind::Conflict {
+            let unchanged_before = out.iter().rev().take_while(|r| r.kind == Kind::Unchanged).count();
+            let lines_between: usize = out[out.len() - unchanged_before..]
+                .iter()
+                .map(|r| r.current.len())
+                .sum();
+            let first_to_combine = out.len().checked_sub(unchanged_before + 1).filter(|idx| {
+                out[*idx].kind == Kind::Conflict && lines_between <= MAX_LINES_BETWEEN_COMBINED_CONFLICTS
+            });
+            if let Some(idx) = first_to_combine {
+                out.truncate(idx + 1);
+                let combined = &mut out[idx];
+                combined.ancestor = combined.ancestor.start.min(region.ancestor.start)..region.ancestor.end;
+                combined.current.end = region.current.end;
+                combined.other.end = region.other.end;
+                continue;
+            }
+        }
+        out.push(region);
+    }
+    out
+}
+
+/// Move lines at the beginning and the end of conflicts that are the same on our and their side out of the conflict,
+/// while keeping the ancestor as is.
+fn trim_conflicts(regions: &mut Vec<Region>, tokens: &Tokens<'_>) {
+    let mut out = Vec::with_capacity(regions.len());
+    for mut region in regions.drain(..) {
+        if region.kind != Kind::Conflict {
+            out.push(region);
+            continue;
+        }
+        let (current, other) = (
+            lines(tokens.current, &region.current),
+            lines(tokens.other, &region.other),
+        );
+        let prefix = current.iter().zip(other).take_while(|(a, b)| a == b).count() as u32;
+        let suffix = current[prefix as usize..]
+            .iter()
+            .rev()
+            .zip(other[prefix as usize..].iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count() as u32;
+
+        let unchanged = |current: Range<u32>, other: Range<u32>| Region {
+            kind: Kind::Unchanged,
+            ancestor: region.ancestor.clone(),
+            current,
+            other,
+        };
+        let (current_start, other_start) = (region.current.start, region.other.start);
+        let (current_end, other_end) = (region.current.end, region.other.end);
+        let leading = unchanged(current_start..current_start + prefix, other_start..other_start + prefix);
+        let trailing = unchanged(current_end - suffix..current_end, other_end - suffix..other_end);
+        region.current = leading.current.end..trailing.current.start;
+        region.other = leading.other.end..trailing.other.start;
+
+        out.extend(Some(leading).filter(|r| !r.current.is_empty()));
+        out.push(region);
+        out.extend(Some(trailing).filter(|r| !r.current.is_empty()));
+    }
+    *regions = out;
+}
+
+struct Writer<'a, 'data> {
+    out: &'a mut Vec<u8>,
+    interner: &'a Interner<&'data [u8]>,
+    tokens: &'a Tokens<'a>,
+}
+
+impl Writer<'_, '_> {
+    /// Write the lines of `tokens` in `range`, and if `eol` is set, make sure the output ends with a newline by appending `eol`.
+    fn write_lines(&mut self, tokens: &[Token], range: &Range<u32>, eol: Option<&[u8]>) {
+        let lines = lines(tokens, range);
+        for token in lines {
+            self.out.extend_from_slice(self.interner[*token]);
+        }
+        if let (Some(eol), Some(last)) = (eol, lines.last()) {
+            if !self.interner[*last].ends_with(b"\n") {
+                self.out.extend_from_slice(eol);
+            }
+        }
+    }
+
+    fn write_marker(&mut self, marker: u8, size: usize, label: Option<&BStr>, eol: &[u8]) {
+        self.out.extend(std::iter::repeat(marker).take(size));
+        if let Some(label) = label {
+            self.out.push(b' ');
+            self.out.extend_from_slice(label);
+        }
+        self.out.extend_from_slice(eol);
+    }
+
+    /// Determine the line ending of conflict markers like `git` does, by looking at the lines of each side that precede the
+    /// conflict, or their first line, and the first line of the ancestor. `CRLF` is used only if none of them ends with `LF`
+    /// alone, and the ancestor's line ends with `CRLF`.
+    fn conflict_eol(&self, region: &Region) -> &'static [u8] {
+        let is_crlf = |tokens: &[Token], pos: u32| self.is_crlf(tokens, pos.saturating_sub(1) as usize);
+        let current = is_crlf(self.tokens.current, region.current.start);
+        let other = is_crlf(self.tokens.other, region.other.start);
+        let ancestor = is_crlf(self.tokens.ancestor, 0);
+        if current != Some(false) && other != Some(false) && ancestor == Some(true) {
+            b"\r\n"
+        } else {
+            b"\n"
+        }
+    }
+
+    /// Return whether the line at `idx` ends with `CRLF`, or `None` if this can't be determined.
+    fn is_crlf(&self, tokens: &[Token], idx: usize) -> Option<bool> {
+        let ends_with_crlf = |idx: usize| self.interner[tokens[idx]].ends_with(b"\r\n");
+        let last = tokens.len().checked_sub(1)?;
+        if idx < last {
+            return Some(ends_with_crlf(idx));
+        }
+        if self.interner[tokens[last]].ends_with(b"\n") {
+            return Some(ends_with_crlf(last));
+        }
+        last.checked_sub(1).map(ends_with_crlf)
+    }
+}
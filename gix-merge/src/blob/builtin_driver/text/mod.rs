@@ -0,0 +1,98 @@
+//! The built-in `text` merge driver, which merges line by line and marks conflicting lines, just like `git merge-file` does.
+use std::num::NonZeroU8;
+
+use bstr::{BStr, ByteSlice};
+
+mod function;
+pub use function::merge;
+
+/// The way conflicting lines are presented in the merged text.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConflictStyle {
+    /// Only show the conflicting lines of our and their side, after minimizing conflicts by moving lines that are
+    /// the same on both sides out of them, and by combining conflicts that are only separated by a few lines.
+    ///
+    /// This is `merge.conflictStyle=merge`, the default of `git`.
+    #[default]
+    Merge,
+    /// Show the lines of our side, the ancestor and their side for each conflict, without any attempt to minimize it.
+    ///
+    /// This is `merge.conflictStyle=diff3`.
+    Diff3,
+    /// Like [`Diff3`](ConflictStyle::Diff3), but lines at the beginning and end of a conflict that are the same on our
+    /// and their side are moved out of it. The ancestor is always shown in full.
+    ///
+    /// This is `merge.conflictStyle=zdiff3`.
+    ZealousDiff3,
+}
+
+/// What to do with conflicting lines.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Conflict {
+    /// Keep both sides of the conflict, surrounded by conflict markers, and presented in the given `style`.
+    Keep {
+        /// How to present the conflict.
+        style: ConflictStyle,
+        /// The amount of characters in each conflict marker, as set by the `conflict-marker-size` attribute.
+        marker_size: NonZeroU8,
+    },
+    /// Resolve each conflict by choosing our side, like `git merge-file --ours`.
+    ResolveWithOurs,
+    /// Resolve each conflict by choosing their side, like `git merge-file --theirs`.
+    ResolveWithTheirs,
+    /// Resolve each conflict by writing our side followed by their side, like `git merge-file --union`.
+    ResolveWithUnion,
+}
+
+impl Conflict {
+    /// The amount of characters in each conflict marker, if nothing else is configured.
+    pub const DEFAULT_MARKER_SIZE: u8 = 7;
+
+    /// Parse the value of the `conflict-marker-size` attribute, and return `None` if it isn't a positive number
+    /// that fits into a byte, in which case the [default](Self::DEFAULT_MARKER_SIZE) should be used.
+    pub fn marker_size_from_attribute(value: &BStr) -> Option<NonZeroU8> {
+        value.to_str().ok()?.trim().parse().ok()
+    }
+}
+
+impl Default for Conflict {
+    fn default() -> Self {
+        Conflict::Keep {
+            style: Default::default(),
+            marker_size: Conflict::DEFAULT_MARKER_SIZE.try_into().expect("non-zero"),
+        }
+    }
+}
+
+/// Options for the built-in [text merge](merge()).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The algorithm to use when diffing the ancestor with our and their side.
+    pub diff_algorithm: gix_diff::blob::Algorithm,
+    /// What to do with conflicts.
+    pub conflict: Conflict,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            // This is what `git` uses for merging, independently of `diff.algorithm`.
+            diff_algorithm: gix_diff::blob::Algorithm::Myers,
+            conflict: Default::default(),
+        }
+    }
+}
+
+/// The labels to write next to the conflict markers, typically the names of the branches or commits that are merged.
+///
+/// Each marker stands alone if its label isn't set.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Labels<'a> {
+    /// The label of the ancestor, which is only shown with the [`Diff3`](ConflictStyle::Diff3) and
+    /// [`ZealousDiff3`](ConflictStyle::ZealousDiff3) styles.
+    pub ancestor: Option<&'a BStr>,
+    /// The label of our side, the version that is merged into.
+    pub current: Option<&'a BStr>,
+    /// The label of their side, the version that is merged.
+    pub other: Option<&'a BStr>,
+}
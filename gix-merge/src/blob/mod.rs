@@ -0,0 +1,107 @@
+//! Merge the content of blobs, with each version of the blob being a resource with the content of a file.
+use bstr::BString;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod builtin_driver;
+
+/// The way a merge of blobs was resolved.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// All changes could be merged without conflict.
+    Complete,
+    /// Conflicts were present, but were resolved automatically as configured, for instance by choosing one side.
+    CompleteWithAutoResolvedConflict,
+    /// Conflicts remain, and were marked in the output.
+    Conflict,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod platform;
+
+/// One of the merge drivers that are built into `git`, and which are used if no [custom driver](Driver) is configured
+/// under the name of the `merge` attribute.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BuiltinDriver {
+    /// Merge line by line with the [text driver](builtin_driver::text::merge()), which is the default, unless one of the
+    /// versions looks like binary data, in which case the [`Binary`](BuiltinDriver::Binary) driver is used instead.
+    #[default]
+    Text,
+    /// Don't merge at all, but keep our version and report a conflict, unless conflicts are configured to be resolved
+    /// with one side. This is used for the `-merge` attribute.
+    Binary,
+    /// Merge like [`Text`](BuiltinDriver::Text), but resolve conflicts by writing the conflicting lines of our side
+    /// followed by the ones of their side, without conflict markers.
+    Union,
+    /// Don't merge at all, but keep our version without reporting a conflict.
+    ///
+    /// Note that `git` doesn't know this driver, and would need `merge.ours.driver=true` to be configured to achieve
+    /// the same.
+    Ours,
+}
+
+impl BuiltinDriver {
+    /// All built-in drivers, in the order in which they are declared.
+    pub const ALL: [BuiltinDriver; 4] = [
+        BuiltinDriver::Text,
+        BuiltinDriver::Binary,
+        BuiltinDriver::Union,
+        BuiltinDriver::Ours,
+    ];
+
+    /// Return the name of the driver, as used as value of the `merge` attribute.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BuiltinDriver::Text => "text",
+            BuiltinDriver::Binary => "binary",
+            BuiltinDriver::Union => "union",
+            BuiltinDriver::Ours => "ours",
+        }
+    }
+
+    /// Return the built-in driver called `name`, or `None` if there is no such driver.
+    pub fn by_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|driver| driver.as_str() == name)
+    }
+}
+
+/// A merge driver as configured in a `[merge "name"]` section of the git configuration, which runs an external program
+/// to merge blobs whose `merge` attribute refers to it.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Driver {
+    /// The name of the driver, as referred to by `[merge "name"]` in the git configuration and by the `merge` attribute.
+    pub name: BString,
+    /// A human-readable name of the driver, as read from `merge.<driver>.name`.
+    pub display_name: Option<BString>,
+    /// The command to run for merging, as read from `merge.<driver>.driver`, which is invoked with a shell after
+    /// replacing these placeholders:
+    ///
+    /// * `%O` - the path to a temporary file with the ancestor's version.
+    /// * `%A` - the path to a temporary file with our version, which the command overwrites with the merged result.
+    /// * `%B` - the path to a temporary file with their version.
+    /// * `%L` - the size of the conflict markers.
+    /// * `%P` - the location of the merged file in the tree.
+    /// * `%S`, `%X`, `%Y` - the labels of the ancestor, our side and their side respectively.
+    ///
+    /// The command signals a clean merge by exiting with status 0, and leaving conflicts with any other status.
+    pub command: Option<BString>,
+    /// The name of the driver to use when merging the merge-bases into a virtual merge-base, as read from
+    /// `merge.<driver>.recursive`. If `None`, this driver is used for that as well.
+    pub recursive: Option<BString>,
+}
+
+/// A utility to merge blobs using the merge driver that is selected by the `merge` attribute of their location,
+/// which may be one of the [built-in drivers](BuiltinDriver) or a [configured driver](Driver) that runs an external program.
+pub struct Platform {
+    /// The configured drivers, sorted by name.
+    drivers: Vec<Driver>,
+    /// A way to access `.gitattributes`.
+    pub attr_stack: gix_worktree::Stack,
+    /// Pre-configured attributes to learn about the `merge` driver and the `conflict-marker-size`.
+    attrs: gix_worktree::attributes::search::Outcome,
+    /// The context to use when running the programs of configured drivers.
+    pub context: gix_command::Context,
+    /// Options to alter how blobs are merged.
+    pub options: platform::Options,
+}
@@ -0,0 +1,365 @@
+use std::{io::Write, num::NonZeroU8, path::PathBuf, process::Stdio};
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+
+use crate::blob::{
+    builtin_driver::text::{self, Conflict, Labels},
+    BuiltinDriver, Driver, Platform, Resolution,
+};
+
+/// Options for use in a [`Platform`].
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// The name of the driver to use for blobs whose `merge` attribute isn't specified, as read from `merge.default`.
+    /// If `None`, the [`Text`](BuiltinDriver::Text) driver is used.
+    pub default_driver: Option<BString>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge {
+    use bstr::BString;
+
+    /// Options for [merging blobs](crate::blob::Platform::merge()), which are typically the same for all blobs of a merge.
+    #[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct Options {
+        /// The options of the built-in text driver, which are also used by the `union` driver. The way conflicts are handled
+        /// also determines the version the `binary` driver chooses.
+        pub text: crate::blob::builtin_driver::text::Options,
+        /// If `true`, the merged blob is part of a virtual merge-base, which is the ancestor of another merge.
+        /// The `binary` driver then chooses the ancestor's version, and configured drivers are replaced by their
+        /// [`recursive`](crate::blob::Driver::recursive) driver, if set.
+        pub is_virtual_ancestor: bool,
+        /// The amount of characters to add to each conflict marker, so that markers of a virtual merge-base can be
+        /// told apart from the ones of the merge that uses it.
+        pub extra_marker_size: u8,
+    }
+
+    /// The error returned by [`Platform::merge()`](crate::blob::Platform::merge()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not obtain the attributes of '{rela_path}'")]
+        Attributes { rela_path: BString, source: std::io::Error },
+        #[error("The merge driver '{name}' doesn't have a command configured in 'merge.{name}.driver'")]
+        MissingDriverCommand { name: BString },
+        #[error("Could not write a version of '{rela_path}' to a tempfile for passing it to the merge driver")]
+        WriteTempfile { rela_path: BString, source: std::io::Error },
+        #[error("Could not run the merge driver '{cmd}' for '{rela_path}'")]
+        RunDriver {
+            rela_path: BString,
+            cmd: String,
+            source: std::io::Error,
+        },
+        #[error("Could not read the result of the merge driver for '{rela_path}'")]
+        ReadResult { rela_path: BString, source: std::io::Error },
+    }
+}
+
+/// The driver selected for a particular blob.
+#[derive(Debug, Copy, Clone)]
+enum Selection {
+    Builtin(BuiltinDriver),
+    /// The index into the configured drivers.
+    Configured(usize),
+}
+
+/// Lifecycle
+impl Platform {
+    /// Create a new instance that merges with the configured `drivers`, which are looked up by the `merge` attribute
+    /// as obtained from `attr_stack`. Their programs are run within `context`.
+    pub fn new(
+        mut drivers: Vec<Driver>,
+        attr_stack: gix_worktree::Stack,
+        context: gix_command::Context,
+        options: Options,
+    ) -> Self {
+        drivers.sort_by(|a, b| a.name.cmp(&b.name));
+        Platform {
+            drivers,
+            attr_stack,
+            attrs: {
+                let mut out = gix_worktree::attributes::search::Outcome::default();
+                out.initialize_with_selection(&Default::default(), ["merge", "conflict-marker-size"]);
+                out
+            },
+            context,
+            options,
+        }
+    }
+}
+
+/// Access
+impl Platform {
+    /// Return all drivers that this instance was initialized with, sorted by name.
+    pub fn drivers(&self) -> &[Driver] {
+        &self.drivers
+    }
+}
+
+/// Merging
+impl Platform {
+    /// Merge `current` and `other` with `ancestor` as base, which are the versions of the blob at `rela_path`, with the
+    /// driver selected by the `merge` attribute of `rela_path`, and write the result to `out` after clearing it.
+    /// `labels` are used in conflict markers, and `objects` is used to read `.gitattributes` files if needed.
+    ///
+    /// Just like `git`, the driver is selected like this:
+    ///
+    /// * `merge` uses the [`Text`](BuiltinDriver::Text) driver, while `-merge` uses the [`Binary`](BuiltinDriver::Binary) driver.
+    /// * `merge=name` uses the [configured driver](Driver) called `name`, or the built-in driver of that name,
+    ///   or the `Text` driver if there is neither.
+    /// * without `merge` attribute, the [default driver](Options::default_driver) is looked up the same way, and the `Text`
+    ///   driver is used if there is none.
+    ///
+    /// The `conflict-marker-size` attribute overrides the marker size configured in `options`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn merge(
+        &mut self,
+        out: &mut Vec<u8>,
+        rela_path: &BStr,
+        labels: Labels<'_>,
+        current: &[u8],
+        ancestor: &[u8],
+        other: &[u8],
+        objects: &dyn gix_object::Find,
+        options: merge::Options,
+    ) -> Result<Resolution, merge::Error> {
+        out.clear();
+        self.attr_stack
+            .at_entry(rela_path, Some(false), objects)
+            .map_err(|err| merge::Error::Attributes {
+                rela_path: rela_path.to_owned(),
+                source: err,
+            })?
+            .matching_attributes(&mut self.attrs);
+        let mut attrs = self.attrs.iter_selected();
+        let merge_attr = attrs.next().expect("pre-initialized with 'merge'").assignment.state;
+        let marker_size_attr = attrs
+            .next()
+            .expect("pre-initialized with 'conflict-marker-size'")
+            .assignment
+            .state;
+
+        let mut selection = match merge_attr {
+            gix_worktree::attributes::StateRef::Set => Selection::Builtin(BuiltinDriver::Text),
+            gix_worktree::attributes::StateRef::Unset => Selection::Builtin(BuiltinDriver::Binary),
+            gix_worktree::attributes::StateRef::Value(name) => self.select(name.as_bstr()),
+            gix_worktree::attributes::StateRef::Unspecified => match self.options.default_driver.as_ref() {
+                Some(name) => self.select(name.as_bstr()),
+                None => Selection::Builtin(BuiltinDriver::Text),
+            },
+        };
+        if let Selection::Configured(idx) = selection {
+            if let Some(recursive) = self.drivers[idx]
+                .recursive
+                .as_ref()
+                .filter(|_| options.is_virtual_ancestor)
+            {
+                selection = self.select(recursive.as_bstr());
+            }
+        }
+
+        let marker_size = marker_size_attr
+            .as_bstr()
+            .and_then(Conflict::marker_size_from_attribute)
+            .or(match options.text.conflict {
+                Conflict::Keep { marker_size, .. } => Some(marker_size),
+                _ => None,
+            })
+            .unwrap_or(Conflict::DEFAULT_MARKER_SIZE.try_into().expect("non-zero"))
+            .saturating_add(options.extra_marker_size);
+        let mut text_options = options.text;
+        if let Conflict::Keep {
+            marker_size: configured_size,
+            ..
+        } = &mut text_options.conflict
+        {
+            *configured_size = marker_size;
+        }
+
+        Ok(match selection {
+            Selection::Builtin(BuiltinDriver::Text) => {
+                if [current, ancestor, other].iter().any(|data| is_binary(data)) {
+                    binary(out, current, ancestor, other, options)
+                } else {
+                    text::merge(out, labels, current, ancestor, other, text_options)
+                }
+            }
+            Selection::Builtin(BuiltinDriver::Union) => text::merge(
+                out,
+                labels,
+                current,
+                ancestor,
+                other,
+                text::Options {
+                    conflict: Conflict::ResolveWithUnion,
+                    ..text_options
+                },
+            ),
+            Selection::Builtin(BuiltinDriver::Binary) => binary(out, current, ancestor, other, options),
+            Selection::Builtin(BuiltinDriver::Ours) => {
+                out.extend_from_slice(current);
+                Resolution::Complete
+            }
+            Selection::Configured(idx) => {
+                let driver = &self.drivers[idx];
+                let command = driver
+                    .command
+                    .as_ref()
+                    .ok_or_else(|| merge::Error::MissingDriverCommand {
+                        name: driver.name.clone(),
+                    })?;
+                run_driver(
+                    command.as_bstr(),
+                    self.context.clone(),
+                    out,
+                    rela_path,
+                    labels,
+                    current,
+                    ancestor,
+                    other,
+                    marker_size,
+                )?
+            }
+        })
+    }
+
+    /// Return the driver called `name`, preferring configured drivers over built-in ones, and falling back to the text driver.
+    fn select(&self, name: &BStr) -> Selection {
+        match self.drivers.binary_search_by(|d| d.name.as_bstr().cmp(name)) {
+            Ok(idx) => Selection::Configured(idx),
+            Err(_) => Selection::Builtin(
+                name.to_str()
+                    .ok()
+                    .and_then(BuiltinDriver::by_name)
+                    .unwrap_or(BuiltinDriver::Text),
+            ),
+        }
+    }
+}
+
+/// Write the version chosen by the `binary` driver to `out`, which is the one of the ancestor when merging a virtual merge-base,
+/// or the one of the side that conflicts are resolved with. Otherwise, our version is written and a conflict is reported.
+fn binary(out: &mut Vec<u8>, current: &[u8], ancestor: &[u8], other: &[u8], options: merge::Options) -> Resolution {
+    let (data, resolution) = if options.is_virtual_ancestor {
+        (ancestor, Resolution::Complete)
+    } else {
+        match options.text.conflict {
+            Conflict::ResolveWithOurs => (current, Resolution::CompleteWithAutoResolvedConflict),
+            Conflict::ResolveWithTheirs => (other, Resolution::CompleteWithAutoResolvedConflict),
+            Conflict::Keep { .. } | Conflict::ResolveWithUnion => (current, Resolution::Conflict),
+        }
+    };
+    out.extend_from_slice(data);
+    resolution
+}
+
+/// Run the `command` of a configured driver after writing all versions to tempfiles, and read the merged result into `out`.
+#[allow(clippy::too_many_arguments)]
+fn run_driver(
+    command: &BStr,
+    context: gix_command::Context,
+    out: &mut Vec<u8>,
+    rela_path: &BStr,
+    labels: Labels<'_>,
+    current: &[u8],
+    ancestor: &[u8],
+    other: &[u8],
+    marker_size: NonZeroU8,
+) -> Result<Resolution, merge::Error> {
+    let write_tempfile = |data: &[u8]| -> Result<_, merge::Error> {
+        let inner = || -> std::io::Result<_> {
+            let mut file = gix_tempfile::new(
+                std::env::temp_dir(),
+                gix_tempfile::ContainingDirectory::Exists,
+                gix_tempfile::AutoRemove::Tempfile,
+            )?;
+            file.write_all(data)?;
+            let path = file.with_mut(|f| f.path().to_owned())?;
+            Ok((file.close()?, path))
+        };
+        inner().map_err(|err| merge::Error::WriteTempfile {
+            rela_path: rela_path.to_owned(),
+            source: err,
+        })
+    };
+    // The tempfiles are removed when their handles are dropped.
+    let (_ancestor_file, ancestor_path) = write_tempfile(ancestor)?;
+    let (_current_file, current_path) = write_tempfile(current)?;
+    let (_other_file, other_path) = write_tempfile(other)?;
+
+    let path_to_bytes = |path: &PathBuf| gix_path::into_bstr(path.as_path()).into_owned();
+    let mut cmd = BString::default();
+    let mut rest = command.as_bytes();
+    while let Some(pos) = rest.find_byte(b'%') {
+        cmd.push_str(&rest[..pos]);
+        let value = match rest.get(pos + 1) {
+            Some(b'O') => path_to_bytes(&ancestor_path),
+            Some(b'A') => path_to_bytes(&current_path),
+            Some(b'B') => path_to_bytes(&other_path),
+            Some(b'P') => rela_path.to_owned(),
+            Some(b'S') => labels.ancestor.unwrap_or_default().to_owned(),
+            Some(b'X') => labels.current.unwrap_or_default().to_owned(),
+            Some(b'Y') => labels.other.unwrap_or_default().to_owned(),
+            Some(b'L') => {
+                cmd.push_str(marker_size.to_string());
+                rest = &rest[pos + 2..];
+                continue;
+            }
+            _ => {
+                cmd.push_byte(b'%');
+                rest = &rest[pos + 1..];
+                continue;
+            }
+        };
+        push_quoted(&mut cmd, value.as_ref());
+        rest = &rest[pos + 2..];
+    }
+    cmd.push_str(rest);
+
+    let status = std::process::Command::from(
+        gix_command::prepare(gix_path::from_bstring(cmd.clone()))
+            .with_shell()
+            .with_context(context)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit()),
+    )
+    .status()
+    .map_err(|err| merge::Error::RunDriver {
+        rela_path: rela_path.to_owned(),
+        cmd: cmd.to_string(),
+        source: err,
+    })?;
+    *out = std::fs::read(&current_path).map_err(|err| merge::Error::ReadResult {
+        rela_path: rela_path.to_owned(),
+        source: err,
+    })?;
+    Ok(if status.success() {
+        Resolution::Complete
+    } else {
+        Resolution::Conflict
+    })
+}
+
+/// Append `value` to `out` in single quotes so that the shell passes it as a single argument, just like `git` does.
+fn push_quoted(out: &mut BString, value: &BStr) {
+    out.push_byte(b'\'');
+    for byte in value.iter() {
+        match byte {
+            b'\'' | b'!' => {
+                out.push_str(b"'\\");
+                out.push_byte(*byte);
+                out.push_byte(b'\'');
+            }
+            _ => out.push_byte(*byte),
+        }
+    }
+    out.push_byte(b'\'');
+}
+
+/// Return `true` if `data` looks like binary data, just like `git` would determine it.
+fn is_binary(data: &[u8]) -> bool {
+    const FIRST_FEW_BYTES: usize = 8000;
+    data[..data.len().min(FIRST_FEW_BYTES)].contains(&0)
+}
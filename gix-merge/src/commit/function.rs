@@ -0,0 +1,178 @@
+use bstr::BStr;
+use gix_hash::ObjectId;
+use gix_object::FindExt;
+
+use crate::{
+    blob::builtin_driver::text::Labels,
+    commit::{merge::Error, Graph, Options, Outcome},
+};
+
+/// Merge `their_commit` into `our_commit` by [merging the trees](crate::tree::merge()) of both with the tree of their
+/// merge-base, which is found by traversing the commit `graph`, and write the merged tree using `write_object`.
+/// `labels` are used in conflict markers, but note that `labels.ancestor` is replaced by `merged common ancestors` if there
+/// is more than one merge-base, or by `empty tree` if there is none, just like `git` does.
+///
+/// `objects` provides access to all commits, trees and blobs, `diff_resource_cache` is used for finding renamed entries,
+/// and `blob_merge` is used for merging the content of files changed by both sides.
+///
+/// If there are multiple merge-bases, as is the case in criss-cross histories, they are merged one by one in the order
+/// of their age into a virtual merge-base, which is then used as ancestor. The merge-bases of these intermediate merges
+/// are again merged recursively if there are more than one. Conflicts in the virtual merge-base are kept as is, so that
+/// conflict markers can appear in the ancestor of the final merge. Larger conflict markers are used for these, as `git` does.
+#[allow(clippy::too_many_arguments)]
+pub fn merge<E>(
+    our_commit: ObjectId,
+    their_commit: ObjectId,
+    labels: Labels<'_>,
+    graph: &mut Graph<'_>,
+    objects: &impl gix_object::FindObjectOrHeader,
+    mut write_object: impl FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, E>,
+    diff_resource_cache: &mut gix_diff::blob::Platform,
+    blob_merge: &mut crate::blob::Platform,
+    options: Options,
+) -> Result<Outcome, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let _span = gix_trace::coarse!("gix_merge::commit::merge()", ?our_commit, ?their_commit);
+    let mut buf = Vec::new();
+    let merge_bases = gix_revision::merge_base(our_commit, &[their_commit], graph)?;
+    let (ancestor_tree, virtual_merge_base, labels) = match merge_bases.as_deref() {
+        None if options.allow_missing_merge_base => (
+            ObjectId::empty_tree(our_commit.kind()),
+            None,
+            Labels {
+                ancestor: Some(EMPTY_TREE.into()),
+                ..labels
+            },
+        ),
+        None => {
+            return Err(Error::NoMergeBase {
+                our_commit,
+                their_commit,
+            })
+        }
+        Some([base]) => (tree_of(base, objects, &mut buf)?, None, labels),
+        Some(bases) => {
+            let tree = virtual_merge_base(
+                bases,
+                1,
+                graph,
+                objects,
+                &mut write_object,
+                diff_resource_cache,
+                blob_merge,
+                &options,
+                &mut buf,
+            )?;
+            (
+                tree,
+                Some(tree),
+                Labels {
+                    ancestor: Some(MERGED_COMMON_ANCESTORS.into()),
+                    ..labels
+                },
+            )
+        }
+    };
+
+    let tree_merge = crate::tree::merge(
+        &ancestor_tree,
+        &tree_of(&our_commit, objects, &mut buf)?,
+        &tree_of(&their_commit, objects, &mut buf)?,
+        labels,
+        objects,
+        &mut write_object,
+        diff_resource_cache,
+        blob_merge,
+        options.tree_merge,
+    )?;
+    Ok(Outcome {
+        tree_merge,
+        merge_bases: merge_bases.unwrap_or_default(),
+        virtual_merge_base,
+    })
+}
+
+const MERGED_COMMON_ANCESTORS: &str = "merged common ancestors";
+const EMPTY_TREE: &str = "empty tree";
+
+/// Merge all `merge_bases` into one tree, from the oldest to the youngest, and return its id.
+/// `depth` is the level of recursion, starting at 1.
+#[allow(clippy::too_many_arguments)]
+fn virtual_merge_base<E>(
+    merge_bases: &[ObjectId],
+    depth: u8,
+    graph: &mut Graph<'_>,
+    objects: &impl gix_object::FindObjectOrHeader,
+    write_object: &mut impl FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, E>,
+    diff_resource_cache: &mut gix_diff::blob::Platform,
+    blob_merge: &mut crate::blob::Platform,
+    options: &Options,
+    buf: &mut Vec<u8>,
+) -> Result<ObjectId, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut tree_options = options.tree_merge;
+    tree_options.blob_merge.is_virtual_ancestor = true;
+    tree_options.blob_merge.extra_marker_size = depth.saturating_mul(2);
+
+    // Merge-bases are sorted from youngest to oldest.
+    let mut merge_bases = merge_bases.iter().rev();
+    let first = *merge_bases.next().expect("at least two merge-bases");
+    let mut tree = tree_of(&first, objects, buf)?;
+    // The commits that make up the virtual merge-base so far, which is what a virtual commit would have as ancestors.
+    let mut tips = vec![first];
+    for next in merge_bases {
+        let inner_merge_bases = gix_revision::merge_base(*next, &tips, graph)?;
+        let ancestor_hex;
+        let (ancestor_tree, ancestor_label): (_, &BStr) = match inner_merge_bases.as_deref() {
+            None => (ObjectId::empty_tree(next.kind()), EMPTY_TREE.into()),
+            Some([base]) => {
+                ancestor_hex = base.to_hex_with_len(7).to_string();
+                (tree_of(base, objects, buf)?, ancestor_hex.as_str().into())
+            }
+            Some(bases) => (
+                virtual_merge_base(
+                    bases,
+                    depth.saturating_add(1),
+                    graph,
+                    objects,
+                    write_object,
+                    diff_resource_cache,
+                    blob_merge,
+                    options,
+                    buf,
+                )?,
+                MERGED_COMMON_ANCESTORS.into(),
+            ),
+        };
+        tree = crate::tree::merge(
+            &ancestor_tree,
+            &tree,
+            &tree_of(next, objects, buf)?,
+            Labels {
+                ancestor: Some(ancestor_label),
+                current: Some("Temporary merge branch 1".into()),
+                other: Some("Temporary merge branch 2".into()),
+            },
+            objects,
+            &mut *write_object,
+            diff_resource_cache,
+            blob_merge,
+            tree_options,
+        )?
+        .tree;
+        tips.push(*next);
+    }
+    Ok(tree)
+}
+
+fn tree_of(
+    commit: &gix_hash::oid,
+    objects: &impl gix_object::FindObjectOrHeader,
+    buf: &mut Vec<u8>,
+) -> Result<ObjectId, Error> {
+    Ok(objects.find_commit(commit, buf)?.tree())
+}
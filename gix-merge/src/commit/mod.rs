@@ -0,0 +1,54 @@
+//! Merge commits like `git merge` does with the `ort` strategy, by merging their trees with the tree of their merge-base
+//! as ancestor. If there are multiple merge-bases, they are merged recursively into a virtual merge-base first.
+use gix_hash::ObjectId;
+
+mod function;
+pub use function::merge;
+
+/// The commit graph as needed to [merge commits](merge()).
+pub type Graph<'find> = gix_revwalk::Graph<'find, gix_revwalk::graph::Commit<gix_revision::merge_base::Flags>>;
+
+/// The result of [merging commits](merge()).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    /// The outcome of merging the trees of both commits with the tree of their merge-base.
+    pub tree_merge: crate::tree::Outcome,
+    /// All merge-bases of both commits, or an empty list if they don't share any history.
+    pub merge_bases: Vec<ObjectId>,
+    /// The id of the tree that was obtained by merging all [`merge_bases`](Self::merge_bases), if there was more
+    /// than one. It is the ancestor of the final tree merge.
+    pub virtual_merge_base: Option<ObjectId>,
+}
+
+/// Options for [merging commits](merge()).
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct Options {
+    /// If `true`, commits without a merge-base are merged as if the ancestor was an empty tree, similar to
+    /// `--allow-unrelated-histories`. Otherwise, this is an error.
+    pub allow_missing_merge_base: bool,
+    /// The options for merging trees, which are also used when merging the merge-bases.
+    pub tree_merge: crate::tree::Options,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge {
+    use gix_hash::ObjectId;
+
+    /// The error returned by [`merge()`](super::merge()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Commits {our_commit} and {their_commit} don't have a merge-base as their histories are unrelated")]
+        NoMergeBase {
+            our_commit: ObjectId,
+            their_commit: ObjectId,
+        },
+        #[error(transparent)]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+        #[error(transparent)]
+        FindExistingObject(#[from] gix_object::find::existing_object::Error),
+        #[error(transparent)]
+        MergeTree(#[from] crate::tree::merge::Error),
+    }
+}
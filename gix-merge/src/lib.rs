@@ -0,0 +1,16 @@
+//! Provide facilities to merge the content of blobs, trees and commits, as the foundation for
+//! `git merge`, `git cherry-pick` or `git rebase`.
+#![deny(rust_2018_idioms, missing_docs)]
+#![forbid(unsafe_code)]
+
+///
+#[allow(clippy::empty_docs)]
+pub mod blob;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod commit;
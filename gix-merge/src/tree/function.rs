@@ -0,0 +1,566 @@
+use std::collections::{BTreeMap, HashMap};
+
+use bstr::{BStr, BString, ByteSlice, ByteVec};
+use gix_diff::rewrites::tracker::ChangeKind;
+use gix_hash::ObjectId;
+use gix_object::{
+    tree::{EntryKind, EntryMode},
+    FindExt,
+};
+
+use crate::{
+    blob::{
+        builtin_driver::text::{self, Labels},
+        Resolution,
+    },
+    tree::{merge::Error, Conflict, Options, Outcome, Side},
+};
+
+/// Merge the changes `our_tree` and `their_tree` made to `ancestor_tree` and write the resulting tree along with all of its
+/// subtrees and merged blobs using `write_object`, with `labels` being used in conflict markers.
+///
+/// `objects` provides access to all trees and blobs, and `diff_resource_cache` is used for finding renamed entries if
+/// [`Options::rewrites`] is set.
+///
+/// Changes made by only one side are applied as is, while the content of files changed by both sides is merged with
+/// `blob_merge`, which uses the merge driver selected by their `merge` attribute. Unresolved [conflicts](Conflict) are resolved in a way that allows writing the merged tree, and are
+/// returned as part of the [`Outcome`] for the caller to handle.
+///
+/// ### Deviation
+///
+/// * renamed directories aren't detected, so files added by one side to a directory that the other side renamed remain
+///   in the old directory.
+/// * entries that are of a different kind on both sides, like a file and a symbolic link, are resolved by keeping our version,
+///   unless [their side is favored](Options::favor).
+#[allow(clippy::too_many_arguments)]
+pub fn merge<E>(
+    ancestor_tree: &gix_hash::oid,
+    our_tree: &gix_hash::oid,
+    their_tree: &gix_hash::oid,
+    labels: Labels<'_>,
+    objects: &impl gix_object::FindObjectOrHeader,
+    mut write_object: impl FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, E>,
+    diff_resource_cache: &mut gix_diff::blob::Platform,
+    blob_merge: &mut crate::blob::Platform,
+    options: Options,
+) -> Result<Outcome, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let mut buf = Vec::new();
+    let ancestor = flatten_tree(ancestor_tree, objects, &mut buf)?;
+    let ours = flatten_tree(our_tree, objects, &mut buf)?;
+    let theirs = flatten_tree(their_tree, objects, &mut buf)?;
+
+    let (our_renames, their_renames) = match options.rewrites {
+        Some(rewrites) => (
+            find_renames(&ancestor, &ours, rewrites, objects, diff_resource_cache)?,
+            find_renames(&ancestor, &theirs, rewrites, objects, diff_resource_cache)?,
+        ),
+        None => Default::default(),
+    };
+
+    let mut blob_options = options.blob_merge;
+    if let (Some(side), text::Conflict::Keep { .. }) = (options.favor, blob_options.text.conflict) {
+        blob_options.text.conflict = match side {
+            Side::Ours => text::Conflict::ResolveWithOurs,
+            Side::Theirs => text::Conflict::ResolveWithTheirs,
+        };
+    }
+    let mut merge = Merge {
+        labels,
+        objects,
+        write_object: &mut write_object,
+        blob_merge,
+        options: blob_options,
+        favor: options.favor,
+        buf,
+        conflicts: Vec::new(),
+        auto_resolved_conflicts: Vec::new(),
+    };
+
+    // Each slot is keyed by the location of its entries in the merged tree, which is where one side renamed them to.
+    let mut slots = BTreeMap::<BString, Slot>::new();
+    let mut rename_rename = Vec::new();
+    let mut target_location = HashMap::<&BStr, &BStr>::new();
+    for (location, entry) in &ancestor {
+        let location = location.as_bstr();
+        let target = match (our_renames.get(location), their_renames.get(location)) {
+            (Some(ours), Some(theirs)) if ours != theirs => {
+                rename_rename.push((location, ours.as_bstr(), theirs.as_bstr()));
+                continue;
+            }
+            (Some(renamed), _) | (None, Some(renamed)) => renamed.as_bstr(),
+            (None, None) => location,
+        };
+        target_location.insert(location, target);
+        slots.entry(target.to_owned()).or_default().ancestor = Some((location.to_owned(), *entry));
+    }
+    for (side, entries, renames) in [
+        (Side::Ours, &ours, &our_renames),
+        (Side::Theirs, &theirs, &their_renames),
+    ] {
+        let renamed_from: HashMap<_, _> = renames
+            .iter()
+            .map(|(source, destination)| (destination.as_bstr(), source.as_bstr()))
+            .collect();
+        let mut moved = Vec::new();
+        for (location, entry) in entries {
+            let location = location.as_bstr();
+            let origin = renamed_from
+                .get(location)
+                .copied()
+                .or_else(|| (ancestor.contains_key(location) && !renames.contains_key(location)).then_some(location));
+            let target = match origin {
+                Some(origin) => match target_location.get(origin) {
+                    Some(target) => *target,
+                    // The entry is part of a rename/rename conflict.
+                    None => continue,
+                },
+                None => location,
+            };
+            let renamed_from = origin.filter(|origin| *origin != location);
+            if target == location {
+                slots
+                    .entry(location.to_owned())
+                    .or_default()
+                    .set(side, *entry, renamed_from.map(ToOwned::to_owned));
+            } else {
+                moved.push((location, target, renamed_from, *entry));
+            }
+        }
+        for (location, target, renamed_from, entry) in moved {
+            let slot = slots.entry(target.to_owned()).or_default();
+            if slot.get(side).is_none() {
+                slot.set(side, entry, renamed_from.map(ToOwned::to_owned));
+            } else {
+                // The side already has an entry where the other side moved this one to, so keep it where it is.
+                slots.entry(location.to_owned()).or_default().set(side, entry, None);
+            }
+        }
+    }
+
+    let mut merged = BTreeMap::<BString, Entry>::new();
+    for (location, slot) in slots {
+        if let Some(entry) = merge.slot(location.as_ref(), slot)? {
+            merged.insert(location, entry);
+        }
+    }
+    for (ancestor_location, our_location, their_location) in rename_rename {
+        let entry = merge.entries(
+            our_location,
+            Some(ancestor[ancestor_location]),
+            ours[our_location],
+            theirs[their_location],
+            false,
+        )?;
+        if merge.favor != Some(Side::Theirs) {
+            merged.insert(our_location.to_owned(), entry);
+        }
+        if merge.favor != Some(Side::Ours) {
+            merged.insert(their_location.to_owned(), entry);
+        }
+        merge.record(Conflict::RenameRename {
+            ancestor_location: ancestor_location.to_owned(),
+            our_location: our_location.to_owned(),
+            their_location: their_location.to_owned(),
+        });
+    }
+    merge.move_files_out_of_the_way_of_directories(&mut merged, &ours);
+
+    let merged: Vec<_> = merged
+        .iter()
+        .map(|(location, entry)| (location.as_bstr(), *entry))
+        .collect();
+    let tree = merge.write_tree(&merged)?;
+    let (mut conflicts, mut auto_resolved_conflicts) = (merge.conflicts, merge.auto_resolved_conflicts);
+    conflicts.sort_by(|a, b| a.location().cmp(b.location()));
+    auto_resolved_conflicts.sort_by(|a, b| a.location().cmp(b.location()));
+    Ok(Outcome {
+        tree,
+        conflicts,
+        auto_resolved_conflicts,
+    })
+}
+
+fn content_conflict(location: &BStr, ancestor: Option<Entry>, ours: Entry, theirs: Entry) -> Conflict {
+    Conflict::Content {
+        location: location.to_owned(),
+        ancestor: ancestor.map(|entry| entry.id),
+        ours: ours.id,
+        theirs: theirs.id,
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Entry {
+    mode: EntryMode,
+    id: ObjectId,
+}
+
+/// Return all non-tree entries of the tree with `id`, recursively, keyed by their location.
+fn flatten_tree(
+    id: &gix_hash::oid,
+    objects: &impl gix_object::FindObjectOrHeader,
+    buf: &mut Vec<u8>,
+) -> Result<BTreeMap<BString, Entry>, Error> {
+    let mut out = BTreeMap::new();
+    let mut trees = vec![(BString::default(), id.to_owned())];
+    while let Some((prefix, id)) = trees.pop() {
+        if id.is_empty_tree() {
+            continue;
+        }
+        for entry in &objects.find_tree(&id, buf)?.entries {
+            let mut location = prefix.clone();
+            if !location.is_empty() {
+                location.push_byte(b'/');
+            }
+            location.push_str(entry.filename);
+            let (mode, id) = (EntryMode::from(entry.mode.kind()), entry.oid.to_owned());
+            if mode.is_tree() {
+                trees.push((location, id));
+            } else {
+                out.insert(location, Entry { mode, id });
+            }
+        }
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Clone)]
+struct Change {
+    id: ObjectId,
+    mode: EntryMode,
+    kind: ChangeKind,
+}
+
+impl gix_diff::rewrites::tracker::Change for Change {
+    fn id(&self) -> &gix_hash::oid {
+        &self.id
+    }
+
+    fn kind(&self) -> ChangeKind {
+        self.kind
+    }
+
+    fn entry_mode(&self) -> EntryMode {
+        self.mode
+    }
+
+    fn id_and_entry_mode(&self) -> (&gix_hash::oid, EntryMode) {
+        (&self.id, self.mode)
+    }
+}
+
+/// Find the entries of `ancestor` that were renamed in `side`, and return a map from their location in `ancestor` to
+/// their location in `side`.
+fn find_renames(
+    ancestor: &BTreeMap<BString, Entry>,
+    side: &BTreeMap<BString, Entry>,
+    rewrites: gix_diff::Rewrites,
+    objects: &impl gix_object::FindObjectOrHeader,
+    diff_resource_cache: &mut gix_diff::blob::Platform,
+) -> Result<HashMap<BString, BString>, Error> {
+    let mut tracker = gix_diff::rewrites::Tracker::new(gix_diff::Rewrites {
+        copies: None,
+        ..rewrites
+    });
+    let changes = ancestor
+        .iter()
+        .filter(|(location, _)| !side.contains_key(*location))
+        .map(|(location, entry)| (location, entry, ChangeKind::Deletion))
+        .chain(
+            side.iter()
+                .filter(|(location, _)| !ancestor.contains_key(*location))
+                .map(|(location, entry)| (location, entry, ChangeKind::Addition)),
+        );
+    let mut has_changes = false;
+    for (location, entry, kind) in changes {
+        has_changes = true;
+        let change = Change {
+            id: entry.id,
+            mode: entry.mode,
+            kind,
+        };
+        // Changes that can't be renamed are of no interest.
+        let _ = tracker.try_push_change(change, location.as_ref());
+    }
+
+    let mut renames = HashMap::new();
+    if has_changes {
+        tracker
+            .emit(
+                |destination, source| {
+                    if let Some(source) =
+                        source.filter(|source| source.kind == gix_diff::rewrites::tracker::visit::SourceKind::Rename)
+                    {
+                        renames.insert(source.location.to_owned(), destination.location.to_owned());
+                    }
+                    gix_diff::tree::visit::Action::Continue
+                },
+                diff_resource_cache,
+                objects,
+                |_push| Ok::<_, std::convert::Infallible>(()),
+            )
+            .map_err(|err| Error::TrackRewrites(Box::new(err)))?;
+    }
+    Ok(renames)
+}
+
+/// The versions of an entry on each side of the merge.
+#[derive(Default)]
+struct Slot {
+    /// The entry in the ancestor, along with its location there.
+    ancestor: Option<(BString, Entry)>,
+    ours: Option<Entry>,
+    theirs: Option<Entry>,
+    /// The location in the ancestor if one side renamed its entry, along with the side that did it.
+    renamed_from: Option<(BString, Side)>,
+}
+
+impl Slot {
+    fn get(&self, side: Side) -> Option<Entry> {
+        match side {
+            Side::Ours => self.ours,
+            Side::Theirs => self.theirs,
+        }
+    }
+
+    fn set(&mut self, side: Side, entry: Entry, renamed_from: Option<BString>) {
+        match side {
+            Side::Ours => self.ours = Some(entry),
+            Side::Theirs => self.theirs = Some(entry),
+        }
+        if let Some(location) = renamed_from {
+            self.renamed_from = Some((location, side));
+        }
+    }
+}
+
+struct Merge<'a, Find, E> {
+    labels: Labels<'a>,
+    objects: &'a Find,
+    write_object: &'a mut dyn FnMut(&dyn gix_object::WriteTo) -> Result<ObjectId, E>,
+    blob_merge: &'a mut crate::blob::Platform,
+    options: crate::blob::platform::merge::Options,
+    favor: Option<Side>,
+    buf: Vec<u8>,
+    conflicts: Vec<Conflict>,
+    auto_resolved_conflicts: Vec<Conflict>,
+}
+
+impl<Find, E> Merge<'_, Find, E>
+where
+    Find: gix_object::FindObjectOrHeader,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    /// Merge the versions in `slot` and return the entry to write at `location`, if any.
+    fn slot(&mut self, location: &BStr, slot: Slot) -> Result<Option<Entry>, Error> {
+        let ancestor = slot.ancestor.as_ref().map(|(_, entry)| *entry);
+        let (ours, theirs) = (slot.ours, slot.theirs);
+        if let Some((ancestor_location, renamed_by)) = slot.renamed_from {
+            let deleted_by = renamed_by.other();
+            let (renamed, deleted) = match renamed_by {
+                Side::Ours => (ours, theirs),
+                Side::Theirs => (theirs, ours),
+            };
+            if deleted.is_none() {
+                self.record(Conflict::RenameDelete {
+                    ancestor_location,
+                    location: location.to_owned(),
+                    deleted_by,
+                });
+                return Ok(renamed.filter(|_| self.favor != Some(deleted_by)));
+            }
+        }
+
+        Ok(match (ancestor, ours, theirs) {
+            _ if ours == theirs => ours,
+            _ if ancestor == ours => theirs,
+            _ if ancestor == theirs => ours,
+            (Some(_), Some(kept), None) | (Some(_), None, Some(kept)) => {
+                let deleted_by = if ours.is_none() { Side::Ours } else { Side::Theirs };
+                self.record(Conflict::ModifyDelete {
+                    location: location.to_owned(),
+                    deleted_by,
+                });
+                (self.favor != Some(deleted_by)).then_some(kept)
+            }
+            (ancestor, Some(ours), Some(theirs)) => Some(self.entries(location, ancestor, ours, theirs, true)?),
+            (_, None, None) | (None, _, None) | (None, None, _) => unreachable!("handled by the first cases"),
+        })
+    }
+
+    /// Merge the `ours` and `theirs` versions of the entry at `location` that both differ from `ancestor`, and
+    /// record a conflict if `record_conflict` is set and the content couldn't be merged.
+    fn entries(
+        &mut self,
+        location: &BStr,
+        ancestor: Option<Entry>,
+        ours: Entry,
+        theirs: Entry,
+        record_conflict: bool,
+    ) -> Result<Entry, Error> {
+        let is_file = |entry: &Entry| entry.mode.is_blob();
+        if !(is_file(&ours) && is_file(&theirs) && ancestor.as_ref().map_or(true, is_file)) {
+            if ours.id != theirs.id && record_conflict {
+                self.record(content_conflict(location, ancestor, ours, theirs));
+            }
+            return Ok(match self.favor {
+                Some(Side::Theirs) => theirs,
+                Some(Side::Ours) | None => ours,
+            });
+        }
+
+        let mode = match ancestor {
+            Some(ancestor) if ancestor.mode == ours.mode => theirs.mode,
+            _ => ours.mode,
+        };
+        let id = match ancestor {
+            _ if ours.id == theirs.id => ours.id,
+            Some(ancestor) if ancestor.id == ours.id => theirs.id,
+            Some(ancestor) if ancestor.id == theirs.id => ours.id,
+            _ => {
+                let (id, resolution) = self.blobs(location, ancestor.map(|entry| entry.id), ours.id, theirs.id)?;
+                if record_conflict {
+                    let conflict = content_conflict(location, ancestor, ours, theirs);
+                    match resolution {
+                        Resolution::Complete => {}
+                        Resolution::CompleteWithAutoResolvedConflict => self.auto_resolved_conflicts.push(conflict),
+                        Resolution::Conflict => self.conflicts.push(conflict),
+                    }
+                }
+                id
+            }
+        };
+        Ok(Entry { mode, id })
+    }
+
+    /// Record `conflict`, which is resolved automatically if a side is favored.
+    fn record(&mut self, conflict: Conflict) {
+        if self.favor.is_some() {
+            self.auto_resolved_conflicts.push(conflict);
+        } else {
+            self.conflicts.push(conflict);
+        }
+    }
+
+    /// Merge the blobs `ours` and `theirs` at `location` with `ancestor`, which is empty if `None`, and return the id
+    /// of the result.
+    fn blobs(
+        &mut self,
+        location: &BStr,
+        ancestor: Option<ObjectId>,
+        ours: ObjectId,
+        theirs: ObjectId,
+    ) -> Result<(ObjectId, Resolution), Error> {
+        let mut data = |id: Option<ObjectId>| -> Result<Vec<u8>, Error> {
+            Ok(match id {
+                Some(id) => self.objects.find_blob(&id, &mut self.buf)?.data.to_owned(),
+                None => Vec::new(),
+            })
+        };
+        let (ancestor, current, other) = (data(ancestor)?, data(Some(ours))?, data(Some(theirs))?);
+
+        let mut out = Vec::new();
+        let resolution = self.blob_merge.merge(
+            &mut out,
+            location,
+            self.labels,
+            &current,
+            &ancestor,
+            &other,
+            self.objects,
+            self.options,
+        )?;
+        let id = self.write(&gix_object::BlobRef { data: &out })?;
+        Ok((id, resolution))
+    }
+
+    /// Move each file that is in the way of a directory in `merged` to a new location next to it, and record a conflict.
+    fn move_files_out_of_the_way_of_directories(
+        &mut self,
+        merged: &mut BTreeMap<BString, Entry>,
+        ours: &BTreeMap<BString, Entry>,
+    ) {
+        let has_directory = |entries: &BTreeMap<BString, Entry>, location: &BString| {
+            let mut directory = location.clone();
+            directory.push_byte(b'/');
+            matches!(entries.range(directory.clone()..).next(), Some((next, _)) if next.starts_with(&directory))
+        };
+        let files_in_the_way: Vec<_> = merged
+            .keys()
+            .filter(|location| has_directory(merged, location))
+            .cloned()
+            .collect();
+        for location in files_in_the_way {
+            let entry = merged.remove(&location).expect("present");
+            let file_side = if has_directory(ours, &location) {
+                Side::Theirs
+            } else {
+                Side::Ours
+            };
+            let label = match file_side {
+                Side::Ours => self.labels.current.unwrap_or("ours".into()),
+                Side::Theirs => self.labels.other.unwrap_or("theirs".into()),
+            };
+            let mut file_location = location.clone();
+            file_location.push_byte(b'~');
+            file_location.extend(label.iter().map(|b| if *b == b'/' { b'_' } else { *b }));
+            let mut unique_location = file_location.clone();
+            let mut suffix = 0;
+            while merged.contains_key(&unique_location) {
+                unique_location = format!("{file_location}_{suffix}").into();
+                suffix += 1;
+            }
+            merged.insert(unique_location.clone(), entry);
+            // Favoring a side doesn't help here as there is no way to merge a file into a directory.
+            self.conflicts.push(Conflict::DirectoryFile {
+                location,
+                file_location: unique_location,
+                file_side,
+            });
+        }
+    }
+
+    /// Write the tree containing `entries`, which are sorted by their location relative to the tree, along with all subtrees.
+    fn write_tree(&mut self, entries: &[(&BStr, Entry)]) -> Result<ObjectId, Error> {
+        let mut tree = gix_object::Tree::empty();
+        let mut entries = entries;
+        while let Some(((location, entry), rest)) = entries.split_first() {
+            match location.find_byte(b'/') {
+                None => {
+                    tree.entries.push(gix_object::tree::Entry {
+                        mode: entry.mode,
+                        filename: (*location).to_owned(),
+                        oid: entry.id,
+                    });
+                    entries = rest;
+                }
+                Some(pos) => {
+                    let directory = &location[..=pos];
+                    let len = entries
+                        .iter()
+                        .take_while(|(location, _)| location.starts_with(directory))
+                        .count();
+                    let subtree: Vec<_> = entries[..len]
+                        .iter()
+                        .map(|(location, entry)| (location[directory.len()..].as_bstr(), *entry))
+                        .collect();
+                    tree.entries.push(gix_object::tree::Entry {
+                        mode: EntryKind::Tree.into(),
+                        filename: location[..pos].into(),
+                        oid: self.write_tree(&subtree)?,
+                    });
+                    entries = &entries[len..];
+                }
+            }
+        }
+        tree.entries.sort();
+        self.write(&tree)
+    }
+
+    fn write(&mut self, object: &dyn gix_object::WriteTo) -> Result<ObjectId, Error> {
+        (self.write_object)(object).map_err(|err| Error::WriteObject(Box::new(err)))
+    }
+}
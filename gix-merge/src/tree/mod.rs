@@ -0,0 +1,165 @@
+//! Merge trees like `git merge-tree --write-tree` does with the `ort` strategy, by merging the changes both sides made to
+//! a common ancestor tree, without any need for a worktree or an index.
+use bstr::{BStr, BString};
+use gix_hash::ObjectId;
+
+mod function;
+pub use function::merge;
+
+/// One of the two sides of a merge.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Side {
+    /// The side that is merged into, like `HEAD`.
+    Ours,
+    /// The side that is merged.
+    Theirs,
+}
+
+impl Side {
+    /// Return the side opposite to this one.
+    pub fn other(self) -> Side {
+        match self {
+            Side::Ours => Side::Theirs,
+            Side::Theirs => Side::Ours,
+        }
+    }
+}
+
+/// A conflict found while [merging trees](merge()), with all locations being relative to the root of the tree.
+///
+/// Each conflict was resolved in some way to be able to write the merged tree, but needs the attention of a user to
+/// judge the resolution, unless it was resolved automatically as configured with [`Options::favor`] or by the merge
+/// driver.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Conflict {
+    /// Both sides changed the entry at `location` differently, or added it with different content.
+    ///
+    /// Depending on the merge driver, the merged file contains conflict markers, or our version was kept if the
+    /// files are binary.
+    Content {
+        /// The location of the merged entry.
+        location: BString,
+        /// The id of the ancestor's version, or `None` if both sides added the entry.
+        ancestor: Option<ObjectId>,
+        /// The id of our version.
+        ours: ObjectId,
+        /// The id of their version.
+        theirs: ObjectId,
+    },
+    /// One side modified the entry at `location`, while the other side deleted it. The modified version was kept.
+    ModifyDelete {
+        /// The location of the kept entry.
+        location: BString,
+        /// The side that deleted the entry.
+        deleted_by: Side,
+    },
+    /// One side renamed the entry at `ancestor_location` to `location`, while the other side deleted it.
+    /// The renamed version was kept.
+    RenameDelete {
+        /// The location of the entry in the ancestor.
+        ancestor_location: BString,
+        /// The location of the kept entry.
+        location: BString,
+        /// The side that deleted the entry.
+        deleted_by: Side,
+    },
+    /// Both sides renamed the entry at `ancestor_location`, but to different locations. The versions of both sides were
+    /// merged, possibly with conflict markers, and the result was written to both locations.
+    RenameRename {
+        /// The location of the entry in the ancestor.
+        ancestor_location: BString,
+        /// The location we renamed the entry to.
+        our_location: BString,
+        /// The location they renamed the entry to.
+        their_location: BString,
+    },
+    /// One side has a file at `location` while the other side has a directory there.
+    /// The directory was kept, and the file was moved to `file_location`.
+    DirectoryFile {
+        /// The location of the directory.
+        location: BString,
+        /// The location the file was moved to.
+        file_location: BString,
+        /// The side that has the file.
+        file_side: Side,
+    },
+}
+
+impl Conflict {
+    /// Return the location of the conflicting entry in the merged tree, or one of them if there are multiple.
+    pub fn location(&self) -> &BStr {
+        match self {
+            Conflict::Content { location, .. }
+            | Conflict::ModifyDelete { location, .. }
+            | Conflict::RenameDelete { location, .. } => location.as_ref(),
+            Conflict::RenameRename { our_location, .. } => our_location.as_ref(),
+            Conflict::DirectoryFile { file_location, .. } => file_location.as_ref(),
+        }
+    }
+}
+
+/// The result of [merging trees](merge()).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Outcome {
+    /// The id of the merged tree, which was written to the object database.
+    pub tree: ObjectId,
+    /// All conflicts that were found and still need attention, in the order of the location of the entries they affect.
+    pub conflicts: Vec<Conflict>,
+    /// All conflicts that were resolved automatically, either by choosing the [favored side](Options::favor) or by a
+    /// merge driver that resolves conflicts, like the `union` driver. They are in the order of the location of the
+    /// entries they affect.
+    pub auto_resolved_conflicts: Vec<Conflict>,
+}
+
+impl Outcome {
+    /// Return `true` if there was at least one conflict that wasn't resolved automatically.
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+}
+
+/// Options for [merging trees](merge()).
+#[derive(Default, Debug, Copy, Clone, PartialEq)]
+pub struct Options {
+    /// If `Some(…)`, find entries that were renamed by either side so that changes to them can be merged.
+    ///
+    /// Note that copies aren't detected, even if configured.
+    pub rewrites: Option<gix_diff::Rewrites>,
+    /// The options for merging the content of files that were changed by both sides.
+    ///
+    /// Set [`text.conflict`](crate::blob::builtin_driver::text::Options::conflict) to
+    /// [`ResolveWithUnion`](crate::blob::builtin_driver::text::Conflict::ResolveWithUnion) to resolve conflicting
+    /// lines by keeping the ones of both sides.
+    pub blob_merge: crate::blob::platform::merge::Options,
+    /// If `Some(side)`, resolve conflicts automatically by choosing the version of `side`, similar to
+    /// `git merge -X ours` and `git merge -X theirs`, and report them as
+    /// [`auto_resolved_conflicts`](Outcome::auto_resolved_conflicts).
+    ///
+    /// * conflicting lines and binary files are resolved with `side`, unless [`blob_merge`](Self::blob_merge) is already
+    ///   configured to resolve conflicts in another way.
+    /// * entries deleted by one side and modified or renamed by the other are deleted if `side` deleted them.
+    /// * entries that both sides renamed differently are only written to the location `side` renamed them to.
+    /// * entries that are of a different kind on both sides, like a file and a symbolic link, are taken from `side`.
+    ///
+    /// Note that conflicts of files with directories, and of files whose configured merge driver failed, are never
+    /// resolved automatically.
+    pub favor: Option<Side>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge {
+    /// The error returned by [`merge()`](super::merge()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindExistingObject(#[from] gix_object::find::existing_object::Error),
+        #[error("Could not detect renames")]
+        TrackRewrites(#[source] Box<gix_diff::rewrites::tracker::emit::Error>),
+        #[error(transparent)]
+        MergeBlob(#[from] crate::blob::platform::merge::Error),
+        #[error("Could not write a merged object")]
+        WriteObject(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    }
+}
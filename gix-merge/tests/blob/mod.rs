@@ -0,0 +1,258 @@
+mod text {
+    use std::num::NonZeroU8;
+
+    use bstr::ByteSlice;
+    use gix_merge::blob::{
+        builtin_driver::text::{merge, Conflict, ConflictStyle, Labels, Options},
+        Resolution,
+    };
+
+    fn labels() -> Labels<'static> {
+        Labels {
+            ancestor: Some("base".into()),
+            current: Some("ours".into()),
+            other: Some("theirs".into()),
+        }
+    }
+
+    fn keep(style: ConflictStyle) -> Conflict {
+        Conflict::Keep {
+            style,
+            marker_size: 7.try_into().unwrap(),
+        }
+    }
+
+    fn run(current: &str, ancestor: &str, other: &str, labels: Labels<'_>, conflict: Conflict) -> (String, Resolution) {
+        let mut out = Vec::new();
+        let resolution = merge(
+            &mut out,
+            labels,
+            current.as_bytes(),
+            ancestor.as_bytes(),
+            other.as_bytes(),
+            Options {
+                conflict,
+                ..Default::default()
+            },
+        );
+        (out.to_str().expect("valid UTF-8").to_owned(), resolution)
+    }
+
+    #[test]
+    fn changes_of_both_sides_that_dont_touch_are_merged() {
+        let actual = run(
+            "a\nb\nc\nd\ne\n",
+            "a\nb\nc\nd\n",
+            "A\nb\nc\nd\n",
+            labels(),
+            Default::default(),
+        );
+        assert_eq!(actual, ("A\nb\nc\nd\ne\n".into(), Resolution::Complete));
+    }
+
+    #[test]
+    fn identical_changes_are_not_a_conflict() {
+        let actual = run("a\nB\nc\n", "a\nb\nc\n", "a\nB\nc\n", labels(), Default::default());
+        assert_eq!(actual, ("a\nB\nc\n".into(), Resolution::Complete));
+    }
+
+    #[test]
+    fn empty_inputs_merge_cleanly() {
+        assert_eq!(
+            run("", "", "", labels(), Default::default()),
+            ("".into(), Resolution::Complete)
+        );
+        assert_eq!(
+            run("a\n", "", "", labels(), Default::default()),
+            ("a\n".into(), Resolution::Complete)
+        );
+    }
+
+    const CURRENT: &str = "one\ntwo\nthree\nfour\nfive\n";
+    const ANCESTOR: &str = "one\n2\n3\n4\nfive\n";
+    const OTHER: &str = "one\ntwo\nTHREE\nfour\nfive\n";
+
+    #[test]
+    fn style_merge_moves_lines_that_are_the_same_on_both_sides_out_of_the_conflict() {
+        let actual = run(CURRENT, ANCESTOR, OTHER, labels(), keep(ConflictStyle::Merge));
+        assert_eq!(
+            actual,
+            (
+                r"one
+two
+<<<<<<< ours
+three
+=======
+THREE
+>>>>>>> theirs
+four
+five
+"
+                .into(),
+                Resolution::Conflict
+            )
+        );
+    }
+
+    #[test]
+    fn style_diff3_shows_the_unaltered_conflict_with_the_ancestor() {
+        let actual = run(CURRENT, ANCESTOR, OTHER, labels(), keep(ConflictStyle::Diff3));
+        assert_eq!(
+            actual,
+            (
+                r"one
+<<<<<<< ours
+two
+three
+four
+||||||| base
+2
+3
+4
+=======
+two
+THREE
+four
+>>>>>>> theirs
+five
+"
+                .into(),
+                Resolution::Conflict
+            )
+        );
+    }
+
+    #[test]
+    fn style_zealous_diff3_trims_the_conflict_but_shows_the_entire_ancestor() {
+        let actual = run(CURRENT, ANCESTOR, OTHER, labels(), keep(ConflictStyle::ZealousDiff3));
+        assert_eq!(
+            actual,
+            (
+                r"one
+two
+<<<<<<< ours
+three
+||||||| base
+2
+3
+4
+=======
+THREE
+>>>>>>> theirs
+four
+five
+"
+                .into(),
+                Resolution::Conflict
+            )
+        );
+    }
+
+    #[test]
+    fn conflicts_separated_by_up_to_three_lines_are_combined() {
+        let actual = run(
+            "1\nX\n3\n4\nY\n6\n7\n8\nZ\n10\n",
+            "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n",
+            "1\nx\n3\n4\ny\n6\n7\n8\nz\n10\n",
+            labels(),
+            Default::default(),
+        );
+        assert_eq!(
+            actual,
+            (
+                "1\n<<<<<<< ours\nX\n3\n4\nY\n6\n7\n8\nZ\n=======\nx\n3\n4\ny\n6\n7\n8\nz\n>>>>>>> theirs\n10\n".into(),
+                Resolution::Conflict
+            )
+        );
+
+        let actual = run(
+            "1\nX\n3\n4\n5\n6\nY\n",
+            "1\n2\n3\n4\n5\n6\n7\n",
+            "1\nx\n3\n4\n5\n6\ny\n",
+            Default::default(),
+            Default::default(),
+        );
+        assert_eq!(
+            actual,
+            (
+                "1\n<<<<<<<\nX\n=======\nx\n>>>>>>>\n3\n4\n5\n6\n<<<<<<<\nY\n=======\ny\n>>>>>>>\n".into(),
+                Resolution::Conflict
+            ),
+            "more lines in between keep conflicts separate, and markers without labels stand alone"
+        );
+    }
+
+    #[test]
+    fn marker_size_and_missing_newlines_at_the_end() {
+        let actual = run(
+            "a\nours",
+            "a\nb",
+            "a\ntheirs",
+            labels(),
+            Conflict::Keep {
+                style: ConflictStyle::Diff3,
+                marker_size: 3.try_into().unwrap(),
+            },
+        );
+        assert_eq!(
+            actual,
+            (
+                "a\n<<< ours\nours\n||| base\nb\n===\ntheirs\n>>> theirs\n".into(),
+                Resolution::Conflict
+            ),
+            "each side of the conflict is terminated with a newline so the markers start on their own line"
+        );
+    }
+
+    #[test]
+    fn conflict_markers_use_crlf_if_the_input_does() {
+        let actual = run(
+            "a\r\nX\r\nc\r\n",
+            "a\r\nb\r\nc\r\n",
+            "a\r\nY\r\nc\r\n",
+            labels(),
+            Default::default(),
+        );
+        assert_eq!(
+            actual,
+            (
+                "a\r\n<<<<<<< ours\r\nX\r\n=======\r\nY\r\n>>>>>>> theirs\r\nc\r\n".into(),
+                Resolution::Conflict
+            )
+        );
+    }
+
+    #[test]
+    fn conflicts_can_be_resolved_automatically() {
+        for (conflict, expected) in [
+            (Conflict::ResolveWithOurs, "a\nours"),
+            (Conflict::ResolveWithTheirs, "a\ntheirs"),
+            (Conflict::ResolveWithUnion, "a\nours\ntheirs"),
+        ] {
+            let actual = run("a\nours", "a\nb", "a\ntheirs", labels(), conflict);
+            assert_eq!(
+                actual,
+                (expected.into(), Resolution::CompleteWithAutoResolvedConflict),
+                "{conflict:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn marker_size_from_attribute() {
+        for (value, expected) in [
+            ("10", Some(10)),
+            (" 3 ", Some(3)),
+            ("0", None),
+            ("-1", None),
+            ("256", None),
+            ("x", None),
+        ] {
+            assert_eq!(
+                Conflict::marker_size_from_attribute(value.into()).map(NonZeroU8::get),
+                expected,
+                "{value}"
+            );
+        }
+    }
+}
@@ -140,6 +140,8 @@ pub struct Store {
     use_multi_pack_index: bool,
     /// The hash kind to use for some operations
     object_hash: gix_hash::Kind,
+    /// If set, packs are mapped in windows through this cache instead of being mapped as a whole.
+    pack_window_cache: Option<Arc<gix_pack::data::window::Cache>>,
 }
 
 /// Create a new cached handle to the object store with support for additional options.
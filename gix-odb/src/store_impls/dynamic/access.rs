@@ -12,6 +12,12 @@ impl Store {
         self.object_hash
     }
 
+    /// The cache through which pack data files are mapped in windows, if configured, which also provides
+    /// information about the amount of currently mapped bytes.
+    pub fn pack_window_cache(&self) -> Option<&gix_pack::data::window::Cache> {
+        self.pack_window_cache.as_deref()
+    }
+
     /// Whether or not we are allowed to use multi-pack indices
     pub fn use_multi_pack_index(&self) -> bool {
         self.use_multi_pack_index
@@ -0,0 +1,145 @@
+use std::{collections::HashSet, ops::Deref};
+
+use gix_pack::cache::DecodeEntry;
+
+use super::find::Error;
+use crate::store::{handle, load_index};
+
+impl<S> super::Handle<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Return, for each object id in `ids`, whether it exists in the object database.
+    ///
+    /// This is equivalent to calling [`contains()`](gix_pack::Find::contains()) for each id, but each index is probed with
+    /// all ids that weren't found yet in sorted order before moving on to the next one, loading more indices only as needed.
+    /// Loose object databases are probed last with the remaining ids.
+    pub fn contains_batch(&self, ids: &[gix_hash::ObjectId]) -> Vec<bool> {
+        let mut remaining: Vec<_> = (0..ids.len()).collect();
+        remaining.sort_by_key(|&idx| &ids[idx]);
+
+        let mut out = vec![false; ids.len()];
+        let mut snapshot = self.snapshot.borrow_mut();
+        // Like `contains()`, there is no way to communicate errors, so we treat objects as missing instead.
+        self.probe_indices(&mut snapshot, &mut remaining, |index, idx| {
+            out[idx] = index.contains(&ids[idx]);
+            out[idx]
+        })
+        .ok();
+        for lodb in snapshot.loose_dbs.iter() {
+            remaining.retain(|&idx| {
+                out[idx] = lodb.contains(&ids[idx]);
+                !out[idx]
+            });
+        }
+        out
+    }
+
+    /// Find each object in `ids` and call `on_object(index_into_ids, object)` with the object, or `None` if it couldn't be found.
+    ///
+    /// The location of all objects is determined by probing each index with all ids that weren't found yet before moving on to the
+    /// next one, loading more indices as needed. Then objects are decoded ordered by the pack they are in and by their offset in
+    /// that pack, not in the order of `ids`, followed by all other objects in the order of `ids`. This greatly improves the locality
+    /// of pack accesses, particularly when paired with a `pack_cache` which can then reuse delta-bases of objects that are close
+    /// to each other.
+    /// `buf` is used as buffer to hold each object while it's passed to `on_object`.
+    ///
+    /// The first error aborts the operation.
+    pub fn find_batch(
+        &self,
+        ids: &[gix_hash::ObjectId],
+        buf: &mut Vec<u8>,
+        pack_cache: &mut dyn DecodeEntry,
+        on_object: &mut dyn FnMut(usize, Option<gix_object::Data<'_>>),
+    ) -> Result<(), Error> {
+        let mut order = {
+            let replaced: Vec<_> = ids.iter().map(|id| self.replacement_for(id)).collect();
+            let mut remaining: Vec<_> = (0..ids.len()).collect();
+            remaining.sort_by_key(|&idx| replaced[idx]);
+
+            let mut locations = vec![None; ids.len()];
+            self.probe_indices(&mut self.snapshot.borrow_mut(), &mut remaining, |index, idx| {
+                index
+                    .lookup(replaced[idx])
+                    .map(|outcome| {
+                        locations[idx] = Some((
+                            outcome.object_index.pack_id.to_intrinsic_pack_id(),
+                            outcome.object_index.pack_offset,
+                        ));
+                    })
+                    .is_some()
+            })?;
+            locations
+                .into_iter()
+                .enumerate()
+                // `None` sorts first, but objects that aren't packed should come last.
+                .map(|(idx, location)| (location.is_none(), location, idx))
+                .collect::<Vec<_>>()
+        };
+        order.sort_unstable();
+
+        for (_, _, idx) in order {
+            let obj = {
+                let mut snapshot = self.snapshot.borrow_mut();
+                let mut inflate = self.inflate.borrow_mut();
+                self.try_find_cached_inner(&ids[idx], buf, &mut inflate, pack_cache, &mut snapshot, None)?
+                    .map(|(obj, _location)| obj)
+            };
+            on_object(idx, obj);
+        }
+        Ok(())
+    }
+
+    /// Call `probe(index, idx)` for each index in `snapshot` and each position `idx` in `remaining`, and remove all positions
+    /// for which it returns `true`, i.e. which were found in that index. Each index is probed only once, and more indices
+    /// are loaded into `snapshot` as long as there are `remaining` positions.
+    fn probe_indices(
+        &self,
+        snapshot: &mut load_index::Snapshot,
+        remaining: &mut Vec<usize>,
+        mut probe: impl FnMut(&mut handle::IndexLookup, usize) -> bool,
+    ) -> Result<(), load_index::Error> {
+        let mut probed = HashSet::new();
+        let mut generation = snapshot.marker.generation;
+        loop {
+            for index in snapshot.indices.iter_mut() {
+                if remaining.is_empty() {
+                    return Ok(());
+                }
+                if probed.insert(index.id) {
+                    remaining.retain(|&idx| !probe(index, idx));
+                }
+            }
+            if remaining.is_empty() {
+                return Ok(());
+            }
+            match self.store.load_one_index(self.refresh, snapshot.marker)? {
+                Some(new_snapshot) => {
+                    // Slots are only reused for other indices once the generation changes.
+                    if new_snapshot.marker.generation != generation {
+                        generation = new_snapshot.marker.generation;
+                        probed.clear();
+                    }
+                    *snapshot = new_snapshot;
+                    self.clear_cache();
+                }
+                None => return Ok(()),
+            }
+        }
+    }
+
+    /// Return the object `id` is replaced with, or `id` itself if replacements are ignored or if there is none.
+    fn replacement_for<'a>(&'a self, id: &'a gix_hash::oid) -> &'a gix_hash::oid {
+        if self.ignore_replacements {
+            return id;
+        }
+        match self
+            .store
+            .replacements
+            .binary_search_by(|(map_this, _)| map_this.as_ref().cmp(id))
+        {
+            Ok(pos) => self.store.replacements[pos].1.as_ref(),
+            Err(_) => id,
+        }
+    }
+}
@@ -83,7 +83,7 @@ impl<S> super::Handle<S>
 where
     S: Deref<Target = super::Store> + Clone,
 {
-    fn try_find_cached_inner<'a, 'b>(
+    pub(crate) fn try_find_cached_inner<'a, 'b>(
         &'b self,
         mut id: &'b gix_hash::oid,
         buffer: &'a mut Vec<u8>,
@@ -478,12 +478,12 @@ where
                                 possibly_pack.as_deref().expect("just put it in")
                             }
                         };
-                        return pack
-                            .entry_slice(location.entry_range(location.pack_offset))
-                            .map(|data| gix_pack::find::Entry {
-                                data: data.to_owned(),
+                        return pack.entry_data(location.entry_range(location.pack_offset)).map(|data| {
+                            gix_pack::find::Entry {
+                                data,
                                 version: pack.version(),
-                            });
+                            }
+                        });
                     }
                 }
             }
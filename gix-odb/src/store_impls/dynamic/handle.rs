@@ -352,6 +352,7 @@ impl TryFrom<&super::Store> for super::Store {
                 object_hash: Default::default(),
                 use_multi_pack_index: false,
                 current_dir: s.current_dir.clone().into(),
+                pack_windows: s.pack_window_cache().map(gix_pack::data::window::Cache::options),
             },
         )
     }
@@ -19,6 +19,11 @@ pub struct Options {
     /// The current directory of the process at the time of instantiation.
     /// If unset, it will be retrieved using `gix_fs::current_dir(false)`.
     pub current_dir: Option<std::path::PathBuf>,
+    /// If set, pack data files aren't mapped as a whole but in windows, limiting the amount of mapped bytes across all packs
+    /// of the store.
+    ///
+    /// This is useful on 32-bit platforms or in memory-constrained environments.
+    pub pack_windows: Option<gix_pack::data::window::Options>,
 }
 
 impl Default for Options {
@@ -28,6 +33,7 @@ impl Default for Options {
             object_hash: Default::default(),
             use_multi_pack_index: true,
             current_dir: None,
+            pack_windows: None,
         }
     }
 }
@@ -77,6 +83,7 @@ impl Store {
             object_hash,
             use_multi_pack_index,
             current_dir,
+            pack_windows,
         }: Options,
     ) -> std::io::Result<Self> {
         let _span = gix_features::trace::detail!("gix_odb::Store::at()");
@@ -124,6 +131,7 @@ impl Store {
             index: ArcSwap::new(Arc::new(SlotMapIndex::default())),
             use_multi_pack_index,
             object_hash,
+            pack_window_cache: pack_windows.map(|options| Arc::new(gix_pack::data::window::Cache::new(options))),
             num_handles_stable: Default::default(),
             num_handles_unstable: Default::default(),
             num_disk_state_consolidation: Default::default(),
@@ -148,10 +148,9 @@ fn maybe_sort_entries(index: &handle::IndexLookup, order: Ordering) -> Option<Ve
     Some(order)
 }
 
-impl Iterator for AllObjects {
-    type Item = Result<ObjectId, loose::iter::Error>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+impl AllObjects {
+    /// Like [`Iterator::next()`], but also return the location of the object.
+    fn next_with_location(&mut self) -> Option<Result<(ObjectId, Location), loose::iter::Error>> {
         match &mut self.state {
             State::Depleted => None,
             State::Pack {
@@ -162,13 +161,38 @@ impl Iterator for AllObjects {
                 num_objects,
             } => {
                 if *entry_index < *num_objects {
-                    let oid = match ordered_entries {
-                        Some(entries) => index.oid_at_index(entries[*entry_index as usize].entry_index),
-                        None => index.oid_at_index(*entry_index),
-                    }
-                    .to_owned();
+                    let index_entry = match ordered_entries {
+                        Some(entries) => entries[*entry_index as usize].entry_index,
+                        None => *entry_index,
+                    };
+                    let oid = index.oid_at_index(index_entry).to_owned();
+                    let (pack_id, pack_offset) = match &index.file {
+                        SingleOrMultiIndex::Single { index: file, .. } => (
+                            PackId {
+                                index: index.id,
+                                multipack_index: None,
+                            },
+                            file.pack_offset_at_index(index_entry),
+                        ),
+                        SingleOrMultiIndex::Multi { index: file, .. } => {
+                            let (pack_index, pack_offset) = file.pack_id_and_pack_offset_at_index(index_entry);
+                            (
+                                PackId {
+                                    index: index.id,
+                                    multipack_index: Some(pack_index),
+                                },
+                                pack_offset,
+                            )
+                        }
+                    };
                     *entry_index += 1;
-                    Some(Ok(oid))
+                    Some(Ok((
+                        oid,
+                        Location::Pack {
+                            pack_id: pack_id.to_intrinsic_pack_id(),
+                            pack_offset,
+                        },
+                    )))
                 } else {
                     match index_iter.next() {
                         Some(new_index) => {
@@ -185,17 +209,17 @@ impl Iterator for AllObjects {
                             }
                         }
                     }
-                    self.next()
+                    self.next_with_location()
                 }
             }
             State::Loose { iter, index } => match iter.next() {
-                Some(id) => Some(id),
+                Some(id) => Some(id.map(|id| (id, Location::Loose))),
                 None => {
                     *index += 1;
                     match self.loose_dbs.get(*index).map(loose::Store::iter) {
                         Some(new_iter) => {
                             *iter = new_iter;
-                            self.next()
+                            self.next_with_location()
                         }
                         None => {
                             self.state = State::Depleted;
@@ -206,12 +230,136 @@ impl Iterator for AllObjects {
             },
         }
     }
+}
+
+impl Iterator for AllObjects {
+    type Item = Result<ObjectId, loose::iter::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_location().map(|res| res.map(|(id, _location)| id))
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         (self.num_objects, None)
     }
 }
 
+/// The location of an object as returned by [`UniqueObjects`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum Location {
+    /// The object is stored in a pack.
+    Pack {
+        /// The intrinsic id of the pack, as used in [`gix_pack::data::entry::Location::pack_id`].
+        pack_id: gix_pack::data::Id,
+        /// The offset at which the object's entry starts in the pack.
+        pack_offset: gix_pack::data::Offset,
+    },
+    /// The object is stored as loose object.
+    Loose,
+}
+
+/// An object as returned by [`UniqueObjects`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct Object {
+    /// The id of the object.
+    pub id: ObjectId,
+    /// Where the object is stored.
+    pub location: Location,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod unique {
+    /// The error returned by [`UniqueObjects`][super::UniqueObjects].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        LooseIter(#[from] crate::loose::iter::Error),
+        #[error("Could not obtain the header of object {id} to determine its kind")]
+        Header {
+            id: gix_hash::ObjectId,
+            source: gix_object::find::Error,
+        },
+        #[error("Object {id} disappeared while determining its kind")]
+        Missing { id: gix_hash::ObjectId },
+    }
+}
+
+/// An iterator over all objects of an object store, each of which is returned only once along with its location,
+/// optionally limited to objects of certain kinds.
+///
+/// Objects in packs come first, in the order of [`Ordering`], followed by loose objects.
+/// If an object is stored multiple times, only its first occurrence is returned.
+///
+/// Note that deduplication requires to keep all seen object ids in memory.
+pub struct UniqueObjects<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    inner: AllObjects,
+    handle: super::Handle<S>,
+    kinds: Option<Vec<gix_object::Kind>>,
+    seen: gix_hashtable::HashSet<ObjectId>,
+}
+
+/// Builder
+impl<S> UniqueObjects<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    /// Set the ordering of the packed objects returned, trading off memory and latency for object query performance.
+    pub fn with_ordering(mut self, order: Ordering) -> Self {
+        self.inner = self.inner.with_ordering(order);
+        self
+    }
+
+    /// Only return objects of the given `kinds`, which requires reading the header of each object.
+    pub fn with_kinds(mut self, kinds: impl IntoIterator<Item = gix_object::Kind>) -> Self {
+        self.kinds = Some(kinds.into_iter().collect());
+        self
+    }
+}
+
+impl<S> UniqueObjects<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    fn is_wanted(&self, id: &ObjectId) -> Result<bool, unique::Error> {
+        let Some(kinds) = &self.kinds else {
+            return Ok(true);
+        };
+        let header = gix_object::FindHeader::try_header(&self.handle, id)
+            .map_err(|source| unique::Error::Header { id: *id, source })?
+            .ok_or(unique::Error::Missing { id: *id })?;
+        Ok(kinds.contains(&header.kind))
+    }
+}
+
+impl<S> Iterator for UniqueObjects<S>
+where
+    S: Deref<Target = super::Store> + Clone,
+{
+    type Item = Result<Object, unique::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (id, location) = match self.inner.next_with_location()? {
+                Ok(v) => v,
+                Err(err) => return Some(Err(err.into())),
+            };
+            if !self.seen.insert(id) {
+                continue;
+            }
+            match self.is_wanted(&id) {
+                Ok(true) => return Some(Ok(Object { id, location })),
+                Ok(false) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+}
+
 impl<S> super::Handle<S>
 where
     S: Deref<Target = super::Store> + Clone,
@@ -221,6 +369,19 @@ where
     pub fn iter(&self) -> Result<AllObjects, dynamic::load_index::Error> {
         AllObjects::new(self.store_ref())
     }
+
+    /// Return an iterator over all objects, each returned only once, first the ones in all packs of all linked databases
+    /// (via alternates), followed by all loose objects, along with their location.
+    ///
+    /// Use [`UniqueObjects::with_kinds()`] to only obtain objects of certain kinds.
+    pub fn iter_unique(&self) -> Result<UniqueObjects<S>, dynamic::load_index::Error> {
+        Ok(UniqueObjects {
+            inner: AllObjects::new(self.store_ref())?,
+            handle: self.clone(),
+            kinds: None,
+            seen: Default::default(),
+        })
+    }
 }
 
 impl dynamic::Store {
@@ -60,6 +60,8 @@ pub mod prefix;
 
 mod header;
 
+mod batch;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod iter;
@@ -57,6 +57,12 @@ pub mod find;
 pub mod iter;
 ///
 #[allow(clippy::empty_docs)]
+pub mod pack;
+///
+#[allow(clippy::empty_docs)]
+pub mod statistics;
+///
+#[allow(clippy::empty_docs)]
 pub mod verify;
 
 /// The type for an iterator over `Result<gix_hash::ObjectId, Error>)`
@@ -0,0 +1,164 @@
+use std::{
+    io::{Seek, SeekFrom},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use gix_features::progress::{Count, DynNestedProgress, Progress};
+
+use crate::store_impls::loose::{hash_path, Store};
+
+/// The error returned by [`Store::write_pack()`] and [`Store::auto_pack()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not list loose objects")]
+    Iter(#[from] crate::loose::iter::Error),
+    #[error("Could not read loose object")]
+    Find(#[from] crate::loose::find::Error),
+    #[error("Could not write the pack data of all loose objects")]
+    WritePackData(#[from] gix_pack::data::input::Error),
+    #[error("Could not write the pack with its index")]
+    WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error("An IO error occurred while creating the pack or deleting packed loose objects")]
+    Io(#[from] std::io::Error),
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+/// Configuration for [`Store::write_pack()`] and [`Store::auto_pack()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// If `true`, loose objects are deleted once they are contained in the new pack, similar to `git prune-packed`.
+    pub delete_packed_objects: bool,
+    /// The amount of threads to use when creating the pack index, or `None` to use all logical cores.
+    pub thread_limit: Option<usize>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            delete_packed_objects: true,
+            thread_limit: None,
+        }
+    }
+}
+
+/// The outcome of [`Store::write_pack()`] and [`Store::auto_pack()`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Outcome {
+    /// Information about the new pack and its index.
+    pub pack: gix_pack::bundle::write::Outcome,
+    /// The amount of loose objects that were deleted after they were packed.
+    pub num_deleted_objects: usize,
+}
+
+/// Packing
+impl Store {
+    /// Put all loose objects into a new pack in the `pack` directory next to the loose objects, along with its index,
+    /// and delete the loose objects afterwards if configured in `options`.
+    ///
+    /// Objects are stored as they are, without attempting to find deltas between them.
+    /// Returns `None` if there were no loose objects.
+    pub fn write_pack(
+        &self,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Option<Outcome>, Error> {
+        let mut ids = self.iter().collect::<Result<Vec<_>, _>>()?;
+        if ids.is_empty() {
+            return Ok(None);
+        }
+        ids.sort();
+
+        let pack_directory = self.path.join("pack");
+        std::fs::create_dir_all(&pack_directory)?;
+        let mut pack_data = tempfile::tempfile_in(&pack_directory)?;
+        {
+            let mut objects_progress = progress.add_child("packing loose objects".into());
+            objects_progress.init(Some(ids.len()), gix_features::progress::count("objects"));
+            let mut buf = Vec::new();
+            let mut find_error = None;
+            let entries = ids.iter().filter_map(|id| {
+                if find_error.is_some() || should_interrupt.load(Ordering::Relaxed) {
+                    return None;
+                }
+                objects_progress.inc();
+                match self.try_find(id, &mut buf) {
+                    // The pack offset isn't used when writing the data.
+                    Ok(Some(obj)) => Some(gix_pack::data::input::Entry::from_data_obj(&obj, 0)),
+                    // The object was removed in the meantime, which is fine as we are not supposed to pack it then.
+                    Ok(None) => None,
+                    Err(err) => {
+                        find_error = Some(err);
+                        None
+                    }
+                }
+            });
+            let mut bytes = gix_pack::data::input::EntriesToBytesIter::new(
+                entries,
+                &mut pack_data,
+                gix_pack::data::Version::V2,
+                self.object_hash,
+            );
+            for entry in bytes.by_ref() {
+                entry?;
+            }
+            drop(bytes);
+            if let Some(err) = find_error {
+                return Err(err.into());
+            }
+        }
+        if should_interrupt.load(Ordering::Relaxed) {
+            return Err(Error::Interrupted);
+        }
+
+        pack_data.seek(SeekFrom::Start(0))?;
+        let pack = gix_pack::Bundle::write_to_directory(
+            &mut std::io::BufReader::new(pack_data),
+            Some(&pack_directory),
+            progress,
+            should_interrupt,
+            None::<gix_object::find::Never>,
+            gix_pack::bundle::write::Options {
+                thread_limit: options.thread_limit,
+                object_hash: self.object_hash,
+                ..Default::default()
+            },
+        )?;
+
+        let mut num_deleted_objects = 0;
+        if options.delete_packed_objects {
+            for id in &ids {
+                match std::fs::remove_file(hash_path(id, self.path.clone())) {
+                    Ok(()) => num_deleted_objects += 1,
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            for first_byte in 0..=u8::MAX {
+                // Directories that aren't empty or don't exist are left alone, just like `git` does.
+                std::fs::remove_dir(self.path.join(format!("{first_byte:02x}"))).ok();
+            }
+        }
+        Ok(Some(Outcome {
+            pack,
+            num_deleted_objects,
+        }))
+    }
+
+    /// Call [`write_pack()`](Self::write_pack()) if [there are more than about `threshold` loose objects](Self::exceeds_auto_pack_threshold()),
+    /// similar to what `git gc --auto` does for loose objects with `gc.auto` set to `threshold`, or return `None` otherwise.
+    pub fn auto_pack(
+        &self,
+        threshold: usize,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: Options,
+    ) -> Result<Option<Outcome>, Error> {
+        if !self.exceeds_auto_pack_threshold(threshold)? {
+            return Ok(None);
+        }
+        self.write_pack(progress, should_interrupt, options)
+    }
+}
@@ -0,0 +1,93 @@
+use std::io;
+
+use crate::store_impls::loose::Store;
+
+/// Statistics about the loose objects in a single fan-out directory, like `objects/17`.
+#[derive(Default, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FanOut {
+    /// The amount of loose objects in the directory.
+    pub num_objects: usize,
+    /// The amount of bytes all loose objects in the directory occupy on disk, in their compressed form.
+    pub num_bytes: u64,
+}
+
+/// Statistics about all loose objects, as returned by [`Store::statistics()`].
+#[derive(Default, Debug, PartialEq, Eq, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Statistics {
+    /// Statistics for each of the 256 fan-out directories, indexed by the first byte of the object ids they contain.
+    pub fan_out: Vec<FanOut>,
+}
+
+impl Statistics {
+    /// The amount of loose objects in all fan-out directories.
+    pub fn num_objects(&self) -> usize {
+        self.fan_out.iter().map(|f| f.num_objects).sum()
+    }
+
+    /// The amount of bytes all loose objects occupy on disk.
+    pub fn num_bytes(&self) -> u64 {
+        self.fan_out.iter().map(|f| f.num_bytes).sum()
+    }
+}
+
+/// The fan-out directory `git` samples to estimate the amount of loose objects.
+const SAMPLE_FAN_OUT: u8 = 0x17;
+
+/// Statistics
+impl Store {
+    /// Count all loose objects and their sizes on disk, for each of the fan-out directories.
+    ///
+    /// Files that don't look like loose objects, like temporary files, are ignored.
+    pub fn statistics(&self) -> io::Result<Statistics> {
+        Ok(Statistics {
+            fan_out: (0..=u8::MAX)
+                .map(|first_byte| self.fan_out_statistics(first_byte))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    /// Count the loose objects and their sizes on disk in the fan-out directory for objects whose ids start with `first_byte`.
+    pub fn fan_out_statistics(&self, first_byte: u8) -> io::Result<FanOut> {
+        let mut out = FanOut::default();
+        let entries = match std::fs::read_dir(self.path.join(format!("{first_byte:02x}"))) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(out),
+            Err(err) => return Err(err),
+        };
+        let name_len = self.object_hash.len_in_hex() - 2;
+        for entry in entries {
+            let entry = entry?;
+            let name = entry.file_name();
+            let is_object = matches!(name.to_str(), Some(name) if name.len() == name_len && name.bytes().all(|b| b.is_ascii_hexdigit()));
+            if !is_object {
+                continue;
+            }
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                Err(err) => return Err(err),
+            };
+            if !metadata.is_file() {
+                continue;
+            }
+            out.num_objects += 1;
+            out.num_bytes += metadata.len();
+        }
+        Ok(out)
+    }
+
+    /// Return `true` if there are more than about `threshold` loose objects, using the same estimate as `git gc --auto`
+    /// with `gc.auto` set to `threshold`.
+    ///
+    /// Only a single fan-out directory is sampled, which makes this fast enough to be called after each write operation.
+    /// A `threshold` of 0 disables the check, and `false` is returned.
+    pub fn exceeds_auto_pack_threshold(&self, threshold: usize) -> io::Result<bool> {
+        if threshold == 0 {
+            return Ok(false);
+        }
+        let per_fan_out = (threshold + 255) / 256;
+        Ok(self.fan_out_statistics(SAMPLE_FAN_OUT)?.num_objects > per_fan_out)
+    }
+}
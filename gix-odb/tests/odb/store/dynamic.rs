@@ -1,7 +1,7 @@
 use std::process::Command;
 
 use gix_hash::ObjectId;
-use gix_object::{Exists, FindExt};
+use gix_object::{Exists, Find, FindExt};
 use gix_odb::{store, store::iter::Ordering, Header, Write};
 use gix_testtools::fixture_path_standalone;
 
@@ -506,6 +506,43 @@ fn lookup() {
     );
 }
 
+#[test]
+fn contains_and_find_batch() -> crate::Result {
+    let handle = db();
+    let missing = hex_to_id("aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa");
+    let ids = [
+        hex_to_id("dd25c539efbb0ab018caa4cda2d133285634e9b5"), // pack c043
+        missing,
+        hex_to_id("37d4e6c5c48ba0d245164c4e10d5f41140cab980"), // loose
+        hex_to_id("501b297447a8255d3533c6858bb692575cdefaa0"), // pack 11fd
+        hex_to_id("4dac9989f96bc5b5b1263b582c08f0c5f0b58542"), // pack a2bf
+    ];
+    assert_eq!(handle.contains_batch(&ids), [true, false, true, true, true]);
+    assert_eq!(handle.contains_batch(&[]), Vec::<bool>::new());
+
+    let mut seen = Vec::new();
+    let mut buf = Vec::new();
+    let fresh = db();
+    fresh.find_batch(&ids, &mut buf, &mut gix_odb::pack::cache::Never, &mut |idx, obj| {
+        let mut expected_buf = Vec::new();
+        let expected = handle.try_find(&ids[idx], &mut expected_buf).expect("no error");
+        assert_eq!(
+            obj.map(|o| (o.kind, o.data.to_owned())),
+            expected.map(|o| (o.kind, o.data.to_owned()))
+        );
+        seen.push(idx);
+    })?;
+    assert_eq!(seen.len(), ids.len(), "each id is reported exactly once");
+    assert_eq!(
+        seen[3..],
+        [1, 2],
+        "objects not in packs come last in the order they were given, even if indices had to be loaded first"
+    );
+    seen.sort_unstable();
+    assert_eq!(seen, [0, 1, 2, 3, 4]);
+    Ok(())
+}
+
 fn assert_all_indices_loaded(handle: &gix_odb::Handle, num_refreshes: usize, open_reachable_indices: usize) {
     assert_eq!(
         handle.store_ref().metrics(),
@@ -619,7 +656,12 @@ mod disambiguate_prefix {
 }
 
 mod iter {
-    use gix_odb::store::iter::Ordering;
+    use gix_object::Kind;
+    use gix_odb::{
+        pack::Find,
+        store::iter::{Location, Ordering},
+        HeaderExt,
+    };
 
     use crate::odb::{
         db,
@@ -651,6 +693,46 @@ mod iter {
         }
         Ok(())
     }
+
+    #[test]
+    fn unique_objects_with_location_and_kind_filter() -> crate::Result {
+        for (handle, _tmp) in [db_with_all_object_sources().map(|(a, b)| (a, Some(b)))?, (db(), None)] {
+            let mut expected: Vec<_> = handle.iter()?.map(Result::unwrap).collect();
+            expected.sort();
+            expected.dedup();
+
+            let objects: Vec<_> = handle.iter_unique()?.map(Result::unwrap).collect();
+            let mut ids: Vec<_> = objects.iter().map(|obj| obj.id).collect();
+            ids.sort();
+            assert_eq!(ids, expected, "each object is returned exactly once");
+
+            let mut buf = Vec::new();
+            for obj in &objects {
+                let (_, location) = handle
+                    .try_find_cached(&obj.id, &mut buf, &mut gix_odb::pack::cache::Never)?
+                    .expect("present");
+                match obj.location {
+                    Location::Loose => assert!(location.is_none(), "{} is loose", obj.id),
+                    Location::Pack { .. } => assert!(location.is_some(), "{} is packed", obj.id),
+                }
+            }
+
+            let mut num_by_kind = 0;
+            for kind in [Kind::Commit, Kind::Tree, Kind::Blob, Kind::Tag] {
+                for obj in handle
+                    .iter_unique()?
+                    .with_ordering(Ordering::PackAscendingOffsetThenLooseLexicographical)
+                    .with_kinds(Some(kind))
+                {
+                    let obj = obj?;
+                    assert_eq!(handle.header(obj.id)?.kind(), kind);
+                    num_by_kind += 1;
+                }
+            }
+            assert_eq!(num_by_kind, expected.len(), "all objects have one of the known kinds");
+        }
+        Ok(())
+    }
 }
 
 mod lookup_prefix {
@@ -794,6 +876,44 @@ fn iterate_over_a_bunch_of_loose_and_packed_objects() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn objects_can_be_read_through_pack_windows() -> crate::Result {
+    let window_options = gix_odb::pack::data::window::Options {
+        window_size: gix_odb::pack::data::window::MIN_WINDOW_SIZE,
+        max_mapped_bytes: 4 * gix_odb::pack::data::window::MIN_WINDOW_SIZE as u64,
+    };
+    let windowed = gix_odb::at_opts(
+        fixture_path_standalone("objects"),
+        Vec::new(),
+        gix_odb::store::init::Options {
+            pack_windows: Some(window_options),
+            ..Default::default()
+        },
+    )?;
+    let whole = db();
+    let (mut expected, mut actual) = (Vec::new(), Vec::new());
+    for id in whole.iter()? {
+        let id = id?;
+        let expected = whole.try_find(&id, &mut expected)?.expect("present");
+        let actual = windowed.try_find(&id, &mut actual)?.expect("present");
+        assert_eq!(actual.kind, expected.kind);
+        assert_eq!(actual.data, expected.data);
+    }
+
+    let cache = windowed
+        .store_ref()
+        .pack_window_cache()
+        .expect("configured to map packs in windows");
+    assert_eq!(cache.options(), window_options);
+    assert!(cache.num_windows() > 0, "packs were accessed through windows");
+    assert!(cache.mapped_bytes() <= window_options.max_mapped_bytes);
+    assert!(
+        whole.store_ref().pack_window_cache().is_none(),
+        "packs are mapped as a whole by default"
+    );
+    Ok(())
+}
+
 #[test]
 fn auto_refresh_with_and_without_id_stability() -> crate::Result {
     let tmp = gix_testtools::tempfile::TempDir::new()?;
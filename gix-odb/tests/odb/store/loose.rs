@@ -44,6 +44,127 @@ fn verify_integrity() {
     assert_eq!(outcome.num_objects, 7);
 }
 
+#[test]
+fn statistics() -> crate::Result {
+    let stats = ldb().statistics()?;
+    assert_eq!(stats.fan_out.len(), 256);
+    assert_eq!(stats.num_objects(), 7);
+    assert_eq!(stats.fan_out[0x37].num_objects, 1);
+    assert_eq!(stats.fan_out[0x17].num_objects, 0);
+    assert!(
+        stats.fan_out[0xa7].num_bytes > stats.fan_out[0x37].num_bytes,
+        "the big blob is larger"
+    );
+    assert_eq!(
+        stats.num_bytes(),
+        stats.fan_out.iter().map(|f| f.num_bytes).sum::<u64>()
+    );
+    assert_eq!(ldb().fan_out_statistics(0x37)?, stats.fan_out[0x37]);
+    Ok(())
+}
+
+mod pack {
+    use std::sync::atomic::AtomicBool;
+
+    use gix_features::progress;
+    use gix_object::Exists;
+    use gix_odb::{loose, Write};
+
+    use crate::store::loose::{locate_oid, object_ids};
+
+    fn db_with_all_objects() -> crate::Result<(gix_testtools::tempfile::TempDir, loose::Store)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let db = loose::Store::at(dir.path(), gix_hash::Kind::Sha1);
+        let mut buf = Vec::new();
+        for oid in object_ids() {
+            let obj = locate_oid(oid, &mut buf);
+            db.write_buf(obj.kind, obj.data)?;
+        }
+        Ok((dir, db))
+    }
+
+    #[test]
+    fn write_pack_and_delete_loose_objects() -> crate::Result {
+        let (dir, db) = db_with_all_objects()?;
+        let outcome = db
+            .write_pack(&mut progress::Discard, &AtomicBool::default(), Default::default())?
+            .expect("there are loose objects");
+        assert_eq!(outcome.pack.index.num_objects, 7);
+        assert_eq!(outcome.num_deleted_objects, 7);
+        assert_eq!(db.statistics()?.num_objects(), 0);
+        assert_eq!(
+            std::fs::read_dir(dir.path())?.count(),
+            1,
+            "only the pack directory is left as empty fan-out directories are removed"
+        );
+
+        let odb = gix_odb::at(dir.path())?;
+        for oid in object_ids() {
+            assert!(odb.exists(&oid), "{oid} is now packed");
+        }
+
+        assert!(
+            db.write_pack(&mut progress::Discard, &AtomicBool::default(), Default::default())?
+                .is_none(),
+            "nothing to do without loose objects"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_pack_and_keep_loose_objects() -> crate::Result {
+        let (_dir, db) = db_with_all_objects()?;
+        let outcome = db
+            .write_pack(
+                &mut progress::Discard,
+                &AtomicBool::default(),
+                loose::pack::Options {
+                    delete_packed_objects: false,
+                    ..Default::default()
+                },
+            )?
+            .expect("there are loose objects");
+        assert_eq!(outcome.pack.index.num_objects, 7);
+        assert_eq!(outcome.num_deleted_objects, 0);
+        assert_eq!(db.statistics()?.num_objects(), 7);
+        Ok(())
+    }
+
+    #[test]
+    fn auto_pack_respects_threshold() -> crate::Result {
+        let (_dir, db) = db_with_all_objects()?;
+        for threshold in [0, 1, 6700] {
+            assert!(!db.exceeds_auto_pack_threshold(threshold)?);
+            assert!(
+                db.auto_pack(
+                    threshold,
+                    &mut progress::Discard,
+                    &AtomicBool::default(),
+                    Default::default()
+                )?
+                .is_none(),
+                "there is no loose object in the sampled fan-out directory"
+            );
+        }
+
+        for content in ["a", "b", "c"]
+            .iter()
+            .flat_map(|a| (0..200).map(move |n| format!("{a}{n}")))
+        {
+            db.write_buf(gix_object::Kind::Blob, content.as_bytes())?;
+        }
+        let sampled = db.fan_out_statistics(0x17)?.num_objects;
+        assert!(sampled > 1, "enough objects to exceed a low threshold");
+        assert!(db.exceeds_auto_pack_threshold(1)?);
+        assert!(!db.exceeds_auto_pack_threshold(256 * sampled)?);
+        let outcome = db
+            .auto_pack(1, &mut progress::Discard, &AtomicBool::default(), Default::default())?
+            .expect("threshold exceeded");
+        assert_eq!(outcome.pack.index.num_objects, 7 + 600);
+        Ok(())
+    }
+}
+
 mod write {
     use gix_odb::{loose, Write};
 
@@ -10,14 +10,14 @@ pub struct Options {
     pub thread_limit: Option<usize>,
     /// Determine how much processing to spend on protecting against corruption or recovering from errors.
     pub iteration_mode: crate::data::input::Mode,
-    /// The version of pack index to write, should be [`crate::index::Version::default()`]
-    pub index_version: crate::index::Version,
+    /// The version of pack index to write, or `None` to select it with [`crate::index::Version::for_object_hash_and_count()`].
+    pub index_version: Option<crate::index::Version>,
     /// The kind of hash to use when writing the bundle.
     pub object_hash: gix_hash::Kind,
 }
 
 impl Default for Options {
-    /// Options which favor speed and correctness and write the most commonly supported index file.
+    /// Options which favor speed and correctness and select the index version automatically.
     fn default() -> Self {
         Options {
             thread_limit: None,
@@ -20,6 +20,9 @@ pub enum ResolvedBase {
     OutOfPack { kind: gix_object::Kind, end: usize },
 }
 
+/// The maximum size of an entry header, which is the entry type and size followed by the base offset or id of deltas.
+const MAX_ENTRY_HEADER_LEN: usize = 10 + 10 + gix_hash::Kind::longest().len_in_bytes();
+
 #[derive(Debug)]
 struct Delta {
     data: Range<usize>,
@@ -113,10 +116,10 @@ impl File {
     pub fn entry(&self, offset: data::Offset) -> data::Entry {
         self.assure_v2();
         let pack_offset: usize = offset.try_into().expect("offset representable by machine");
-        assert!(pack_offset <= self.data.len(), "offset out of bounds");
+        assert!(pack_offset <= self.data_len(), "offset out of bounds");
 
-        let object_data = &self.data[pack_offset..];
-        data::Entry::from_bytes(object_data, offset, self.hash_len)
+        let object_data = self.bytes_at(pack_offset, MAX_ENTRY_HEADER_LEN);
+        data::Entry::from_bytes(&object_data, offset, self.hash_len)
     }
 
     /// Decompress the object expected at the given data offset, sans pack header. This information is only
@@ -130,13 +133,8 @@ impl File {
         inflate: &mut zlib::Inflate,
         out: &mut [u8],
     ) -> Result<usize, zlib::inflate::Error> {
-        let offset: usize = data_offset.try_into().expect("offset representable by machine");
-        assert!(offset < self.data.len(), "entry offset out of bounds");
-
-        inflate.reset();
-        inflate
-            .once(&self.data[offset..], out)
-            .map(|(_status, consumed_in, _consumed_out)| consumed_in)
+        self.decompress_entry_from_data_offset_2(data_offset, inflate, out)
+            .map(|(consumed_in, _consumed_out)| consumed_in)
     }
 
     /// Like `decompress_entry_from_data_offset`, but returns consumed input and output.
@@ -147,12 +145,21 @@ impl File {
         out: &mut [u8],
     ) -> Result<(usize, usize), zlib::inflate::Error> {
         let offset: usize = data_offset.try_into().expect("offset representable by machine");
-        assert!(offset < self.data.len(), "entry offset out of bounds");
+        assert!(offset < self.data_len(), "entry offset out of bounds");
 
         inflate.reset();
-        inflate
-            .once(&self.data[offset..], out)
-            .map(|(_status, consumed_in, consumed_out)| (consumed_in, consumed_out))
+        let (mut consumed_in, mut consumed_out) = (0, 0);
+        loop {
+            let input = self.bytes_at(offset + consumed_in, 1);
+            let (status, read, written) = inflate.once(&input, &mut out[consumed_out..])?;
+            consumed_in += read;
+            consumed_out += written;
+            // Only windows can end before the compressed data does, so continue with the next one.
+            let window_exhausted = read == input.len() && offset + consumed_in < self.data_len();
+            if status == zlib::Status::StreamEnd || !window_exhausted {
+                break Ok((consumed_in, consumed_out));
+            }
+        }
     }
 
     /// Decode an entry, resolving delta's as needed, while growing the `out` vector if there is not enough
@@ -1,4 +1,4 @@
-use std::path::Path;
+use std::{path::Path, sync::Arc};
 
 use crate::data;
 
@@ -9,33 +9,57 @@ impl data::File {
     /// The `object_hash` is a way to read (and write) the same file format with different hashes, as the hash kind
     /// isn't stored within the file format itself.
     pub fn at(path: impl AsRef<Path>, object_hash: gix_hash::Kind) -> Result<data::File, data::header::decode::Error> {
-        Self::at_inner(path.as_ref(), object_hash)
+        Self::at_inner(path.as_ref(), object_hash, None)
     }
 
-    fn at_inner(path: &Path, object_hash: gix_hash::Kind) -> Result<data::File, data::header::decode::Error> {
+    /// Like [`at()`](Self::at()), but instead of mapping the whole file at once, map windows of it on demand using `cache`,
+    /// which may be shared among many packs to limit the amount of mapped bytes in total.
+    pub fn at_with_window_cache(
+        path: impl AsRef<Path>,
+        object_hash: gix_hash::Kind,
+        cache: Arc<data::window::Cache>,
+    ) -> Result<data::File, data::header::decode::Error> {
+        Self::at_inner(path.as_ref(), object_hash, Some(cache))
+    }
+
+    fn at_inner(
+        path: &Path,
+        object_hash: gix_hash::Kind,
+        cache: Option<Arc<data::window::Cache>>,
+    ) -> Result<data::File, data::header::decode::Error> {
         use crate::data::header::N32_SIZE;
         let hash_len = object_hash.len_in_bytes();
 
-        let data = crate::mmap::read_only(path).map_err(|e| data::header::decode::Error::Io {
+        let to_err = |e| data::header::decode::Error::Io {
             source: e,
             path: path.to_owned(),
-        })?;
-        let pack_len = data.len();
-        if pack_len < N32_SIZE * 3 + hash_len {
-            return Err(data::header::decode::Error::Corrupt(format!(
-                "Pack data of size {pack_len} is too small for even an empty pack with shortest hash"
-            )));
-        }
-        let (kind, num_objects) =
-            data::header::decode(&data[..12].try_into().expect("enough data after previous check"))?;
-        Ok(data::File {
+        };
+        let data = match cache {
+            Some(cache) => data::Data::Windowed(data::window::Windowed::at(path, cache).map_err(to_err)?),
+            None => data::Data::Mapped(crate::mmap::read_only(path).map_err(to_err)?),
+        };
+        let mut file = data::File {
             data,
             path: path.to_owned(),
             id: gix_features::hash::crc32(path.as_os_str().to_string_lossy().as_bytes()),
-            version: kind,
-            num_objects,
+            version: data::Version::V2,
+            num_objects: 0,
             hash_len,
             object_hash,
-        })
+        };
+        let pack_len = file.data_len();
+        if pack_len < N32_SIZE * 3 + hash_len {
+            return Err(data::header::decode::Error::Corrupt(format!(
+                "Pack data of size {pack_len} is too small for even an empty pack with shortest hash"
+            )));
+        }
+        let (kind, num_objects) = data::header::decode(
+            &file.bytes_at(0, 12)[..12]
+                .try_into()
+                .expect("enough data after previous check"),
+        )?;
+        file.version = kind;
+        file.num_objects = num_objects;
+        Ok(file)
     }
 }
@@ -15,7 +15,7 @@ pub mod checksum {
 impl File {
     /// The checksum in the trailer of this pack data file
     pub fn checksum(&self) -> gix_hash::ObjectId {
-        gix_hash::ObjectId::from_bytes_or_panic(&self.data[self.data.len() - self.hash_len..])
+        gix_hash::ObjectId::from_bytes_or_panic(&self.bytes_at(self.pack_end(), self.hash_len)[..self.hash_len])
     }
 
     /// Verifies that the checksum of the packfile over all bytes preceding it indeed matches the actual checksum,
@@ -31,9 +31,10 @@ impl File {
         progress: &mut dyn Progress,
         should_interrupt: &AtomicBool,
     ) -> Result<gix_hash::ObjectId, checksum::Error> {
-        crate::verify::checksum_on_disk_or_mmap(
+        crate::verify::checksum_on_disk_or_else(
             self.path(),
-            &self.data,
+            self.data_len(),
+            |len, consume| self.for_each_chunk(0..len, consume),
             self.checksum(),
             self.object_hash,
             progress,
@@ -1,5 +1,3 @@
-use std::io::Write;
-
 use crate::data::{entry::Header, input};
 
 impl input::Entry {
@@ -51,15 +49,5 @@ fn to_header(kind: gix_object::Kind) -> Header {
 }
 
 fn compress_data(obj: &gix_object::Data<'_>) -> Result<Vec<u8>, input::Error> {
-    let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
-    if let Err(err) = std::io::copy(&mut &*obj.data, &mut out) {
-        match err.kind() {
-            std::io::ErrorKind::Other => return Err(input::Error::Io(err)),
-            err => {
-                unreachable!("Should never see other errors than zlib, but got {:?}", err,)
-            }
-        }
-    };
-    out.flush().expect("zlib flush should never fail");
-    Ok(out.into_inner())
+    gix_features::zlib::compress(obj.data).map_err(input::Error::Io)
 }
@@ -39,6 +39,10 @@ pub mod init {
 #[allow(clippy::empty_docs)]
 pub mod entry;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod window;
+
 ///
 #[cfg(feature = "streaming-input")]
 pub mod input;
@@ -62,9 +66,17 @@ pub enum Version {
     V3,
 }
 
+/// The way the bytes of a pack data file are made available.
+enum Data {
+    /// The whole file is mapped at once.
+    Mapped(Mmap),
+    /// Portions of the file are mapped on demand.
+    Windowed(window::Windowed),
+}
+
 /// A pack data file
 pub struct File {
-    data: Mmap,
+    data: Data,
     path: std::path::PathBuf,
     /// A value to represent this pack uniquely when used with cache lookup, or a way to identify this pack by its location on disk.
     /// The same location on disk should yield the same id.
@@ -94,7 +106,10 @@ impl File {
     }
     /// The length of all mapped data, including the pack header and the pack trailer
     pub fn data_len(&self) -> usize {
-        self.data.len()
+        match &self.data {
+            Data::Mapped(data) => data.len(),
+            Data::Windowed(data) => data.len(),
+        }
     }
     /// The kind of hash we use internally.
     pub fn object_hash(&self) -> gix_hash::Kind {
@@ -102,7 +117,7 @@ impl File {
     }
     /// The position of the byte one past the last pack entry, or in other terms, the first byte of the trailing hash.
     pub fn pack_end(&self) -> usize {
-        self.data.len() - self.hash_len
+        self.data_len() - self.hash_len
     }
 
     /// The path to the pack data file on disk
@@ -111,10 +126,25 @@ impl File {
     }
 
     /// Returns the pack data at the given slice if its range is contained in the mapped pack data
+    ///
+    /// _Note_ that packs opened with a [window cache](window::Cache) will be mapped as a whole when this method is first called,
+    /// which counts towards the [mapped bytes](window::Cache::mapped_bytes()) of the cache until the pack is dropped.
+    /// Use [`entry_data()`](Self::entry_data()) to only map the required portion of the pack.
     pub fn entry_slice(&self, slice: EntryRange) -> Option<&[u8]> {
         let entry_end: usize = slice.end.try_into().expect("end of pack fits into usize");
         let entry_start = slice.start as usize;
-        self.data.get(entry_start..entry_end)
+        self.whole_data().get(entry_start..entry_end)
+    }
+
+    /// Returns a copy of the pack data at the given slice if its range is contained in the pack data.
+    pub fn entry_data(&self, slice: EntryRange) -> Option<Vec<u8>> {
+        let entry_end: usize = slice.end.try_into().expect("end of pack fits into usize");
+        let entry_start = slice.start as usize;
+        if entry_start > entry_end || entry_end > self.data_len() {
+            return None;
+        }
+        let len = entry_end - entry_start;
+        Some(self.bytes_at(entry_start, len)[..len].to_owned())
     }
 
     /// Returns the CRC32 of the pack data indicated by `pack_offset` and the `size` of the mapped data.
@@ -127,7 +157,67 @@ impl File {
     /// If `pack_offset` or `size` are pointing to a range outside of the mapped pack data.
     pub fn entry_crc32(&self, pack_offset: Offset, size: usize) -> u32 {
         let pack_offset: usize = pack_offset.try_into().expect("pack_size fits into usize");
-        gix_features::hash::crc32(&self.data[pack_offset..pack_offset + size])
+        let mut crc32 = 0;
+        self.for_each_chunk(pack_offset..pack_offset + size, &mut |chunk| {
+            crc32 = gix_features::hash::crc32_update(crc32, chunk);
+        });
+        crc32
+    }
+}
+
+/// Access to the underlying data
+impl File {
+    /// Return the bytes starting at `offset`, at least `min_len` of them unless the end of the pack is reached.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is out of bounds, or if a window couldn't be mapped.
+    pub(crate) fn bytes_at(&self, offset: usize, min_len: usize) -> window::Bytes<'_> {
+        match &self.data {
+            Data::Mapped(data) => window::Bytes::Borrowed(&data[offset..]),
+            Data::Windowed(data) => {
+                assert!(offset <= data.len(), "offset out of bounds");
+                data.bytes_at(offset, min_len)
+            }
+        }
+    }
+
+    /// Call `consume` with all bytes in `range`, in order, which may take multiple calls if the pack is accessed through windows.
+    ///
+    /// # Panics
+    ///
+    /// If `range` is out of bounds, or if a window couldn't be mapped.
+    pub(crate) fn for_each_chunk(&self, range: std::ops::Range<usize>, consume: &mut dyn FnMut(&[u8])) {
+        match &self.data {
+            Data::Mapped(data) => consume(&data[range]),
+            Data::Windowed(data) => {
+                assert!(range.end <= data.len(), "range out of bounds");
+                let mut offset = range.start;
+                while offset < range.end {
+                    let bytes = data.bytes_at(offset, 1);
+                    let chunk = &bytes[..bytes.len().min(range.end - offset)];
+                    consume(chunk);
+                    offset += chunk.len();
+                }
+            }
+        }
+    }
+
+    /// Return `true` if the pack is accessed through the windows of a [`window::Cache`].
+    pub(crate) fn is_windowed(&self) -> bool {
+        matches!(self.data, Data::Windowed(_))
+    }
+
+    /// Return all bytes of the pack, which maps it entirely if it is accessed through windows.
+    ///
+    /// # Panics
+    ///
+    /// If the pack couldn't be mapped.
+    pub(crate) fn whole_data(&self) -> &[u8] {
+        match &self.data {
+            Data::Mapped(data) => data,
+            Data::Windowed(data) => data.whole().expect("pack data file can be mapped entirely"),
+        }
     }
 }
 
@@ -220,6 +220,9 @@ mod expand {
                                     out = objects.dissolve(stats);
                                     &traverse_delegate.non_trees
                                 } else {
+                                    // Objects new compared to one parent are already seen when diffing against the next one,
+                                    // so they must be collected across all parents.
+                                    changes_delegate.clear();
                                     for commit_id in &parent_commit_ids {
                                         let parent_tree_id = {
                                             let (parent_commit_obj, location) = db.find(commit_id, buf2)?;
@@ -245,7 +248,6 @@ mod expand {
                                             gix_object::TreeRefIter::from_bytes(parent_tree_obj.data)
                                         };
 
-                                        changes_delegate.clear();
                                         let objects = CountingObjects::new(db);
                                         gix_diff::tree::Changes::from(Some(parent_tree))
                                             .needed_to_obtain(
@@ -1,5 +1,3 @@
-use std::io::Write;
-
 use gix_hash::ObjectId;
 
 use crate::{data, data::output, find};
@@ -132,17 +130,7 @@ impl output::Entry {
             id: count.id.to_owned(),
             kind: Kind::Base(obj.kind),
             decompressed_size: obj.data.len(),
-            compressed_data: {
-                let mut out = gix_features::zlib::stream::deflate::Write::new(Vec::new());
-                if let Err(err) = std::io::copy(&mut &*obj.data, &mut out) {
-                    match err.kind() {
-                        std::io::ErrorKind::Other => return Err(Error::ZlibDeflate(err)),
-                        err => unreachable!("Should never see other errors than zlib, but got {:?}", err,),
-                    }
-                };
-                out.flush()?;
-                out.into_inner()
-            },
+            compressed_data: gix_features::zlib::compress(obj.data).map_err(Error::ZlibDeflate)?,
         })
     }
 
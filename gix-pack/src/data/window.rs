@@ -0,0 +1,248 @@
+use std::{
+    ops::Deref,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use memmap2::Mmap;
+
+/// The smallest supported window size.
+pub const MIN_WINDOW_SIZE: usize = 4096;
+
+/// Configure how pack data files are mapped into memory if they are not mapped as a whole.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The amount of bytes to map at once when accessing a pack data file, similar to `core.packedGitWindowSize`.
+    ///
+    /// Windows start at multiples of this size, which is at least [`MIN_WINDOW_SIZE`].
+    /// They may be larger if a single access requires it.
+    pub window_size: usize,
+    /// The maximum amount of bytes to keep mapped across all pack data files using the same [`Cache`],
+    /// similar to `core.packedGitLimit`.
+    ///
+    /// Once reached, the least recently used windows are unmapped. Windows still in use by an ongoing operation
+    /// stay mapped until that operation finishes, so the limit can be exceeded temporarily.
+    pub max_mapped_bytes: u64,
+}
+
+impl Default for Options {
+    /// Use the same defaults as `git` does.
+    fn default() -> Self {
+        if cfg!(target_pointer_width = "32") {
+            Options {
+                window_size: 32 * 1024 * 1024,
+                max_mapped_bytes: 256 * 1024 * 1024,
+            }
+        } else {
+            Options {
+                window_size: 1024 * 1024 * 1024,
+                max_mapped_bytes: 32 * 1024 * 1024 * 1024 * 1024,
+            }
+        }
+    }
+}
+
+/// A cache of memory-mapped windows into pack data files, shared by all packs opened with
+/// [`data::File::at_with_window_cache()`][crate::data::File::at_with_window_cache()].
+///
+/// It bounds the address space used by pack data, which matters on 32-bit platforms or in memory-constrained
+/// environments where mapping entire multi-gigabyte packs isn't possible.
+pub struct Cache {
+    window_size: usize,
+    max_mapped_bytes: u64,
+    next_file_id: AtomicU64,
+    state: parking_lot::Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    /// All mapped windows, the least recently used one first.
+    windows: Vec<Window>,
+    mapped_bytes: u64,
+}
+
+struct Window {
+    file_id: u64,
+    start: usize,
+    map: Arc<Mmap>,
+}
+
+impl Window {
+    fn contains(&self, file_id: u64, start: usize, end: usize) -> bool {
+        self.file_id == file_id && self.start <= start && self.start + self.map.len() >= end
+    }
+}
+
+impl Cache {
+    /// Create a new instance configured with `options`.
+    pub fn new(
+        Options {
+            window_size,
+            max_mapped_bytes,
+        }: Options,
+    ) -> Self {
+        Cache {
+            window_size: window_size.max(MIN_WINDOW_SIZE),
+            max_mapped_bytes,
+            next_file_id: AtomicU64::new(0),
+            state: Default::default(),
+        }
+    }
+
+    /// Return the options in effect, with the window size adjusted to be at least [`MIN_WINDOW_SIZE`].
+    pub fn options(&self) -> Options {
+        Options {
+            window_size: self.window_size,
+            max_mapped_bytes: self.max_mapped_bytes,
+        }
+    }
+
+    /// The amount of bytes currently mapped through this cache, including packs that were mapped as a whole
+    /// as they were accessed through [`data::File::entry_slice()`](crate::data::File::entry_slice()).
+    pub fn mapped_bytes(&self) -> u64 {
+        self.state.lock().mapped_bytes
+    }
+
+    /// The amount of windows currently mapped through this cache.
+    pub fn num_windows(&self) -> usize {
+        self.state.lock().windows.len()
+    }
+
+    /// Return a window of `file` containing at least `start..end` along with the offset at which it starts,
+    /// mapping it if needed.
+    fn window(&self, file: &Windowed, start: usize, end: usize) -> std::io::Result<(Arc<Mmap>, usize)> {
+        let mut state = self.state.lock();
+        if let Some(pos) = state.windows.iter().rposition(|w| w.contains(file.id, start, end)) {
+            let window = state.windows.remove(pos);
+            let res = (window.map.clone(), window.start);
+            state.windows.push(window);
+            return Ok(res);
+        }
+
+        let window_start = start / self.window_size * self.window_size;
+        let window_len = self.window_size.max(end - window_start).min(file.len - window_start);
+        while state.mapped_bytes + window_len as u64 > self.max_mapped_bytes && !state.windows.is_empty() {
+            let evicted = state.windows.remove(0);
+            state.mapped_bytes -= evicted.map.len() as u64;
+        }
+        // SAFETY: we have to take the risk of somebody changing the file underneath. Git never writes into the same file.
+        #[allow(unsafe_code)]
+        let map = Arc::new(unsafe {
+            memmap2::MmapOptions::new()
+                .offset(window_start as u64)
+                .len(window_len)
+                .map_copy_read_only(&file.file)?
+        });
+        state.mapped_bytes += window_len as u64;
+        state.windows.push(Window {
+            file_id: file.id,
+            start: window_start,
+            map: map.clone(),
+        });
+        Ok((map, window_start))
+    }
+
+    /// Unmap all windows of the file with `file_id`, and stop accounting for `whole_len` bytes it mapped as a whole.
+    fn forget(&self, file_id: u64, whole_len: u64) {
+        let mut state = self.state.lock();
+        let mut unmapped = whole_len;
+        state.windows.retain(|w| {
+            let keep = w.file_id != file_id;
+            if !keep {
+                unmapped += w.map.len() as u64;
+            }
+            keep
+        });
+        state.mapped_bytes -= unmapped;
+    }
+}
+
+/// A pack data file which is accessed through windows of a [`Cache`].
+pub(crate) struct Windowed {
+    file: std::fs::File,
+    id: u64,
+    len: usize,
+    cache: Arc<Cache>,
+    /// A mapping of the entire file, only created on first use by methods that hand out slices of arbitrary size.
+    whole: once_cell::sync::OnceCell<Mmap>,
+}
+
+impl Windowed {
+    pub(crate) fn at(path: &Path, cache: Arc<Cache>) -> std::io::Result<Self> {
+        let file = std::fs::File::open(path)?;
+        let len = file.metadata()?.len().try_into().map_err(|_| {
+            std::io::Error::new(
+                std::io::ErrorKind::Other,
+                "pack data file is too large for the address space",
+            )
+        })?;
+        Ok(Windowed {
+            file,
+            id: cache.next_file_id.fetch_add(1, Ordering::Relaxed),
+            len,
+            cache,
+            whole: Default::default(),
+        })
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return the bytes starting at `offset`, which are at least `min_len` long unless the end of the file is reached.
+    ///
+    /// # Panics
+    ///
+    /// If the window can't be mapped.
+    pub(crate) fn bytes_at(&self, offset: usize, min_len: usize) -> Bytes<'static> {
+        let end = offset.saturating_add(min_len).min(self.len);
+        let (map, window_start) = self
+            .cache
+            .window(self, offset, end)
+            .expect("pack data window can be mapped");
+        Bytes::Window {
+            map,
+            start: offset - window_start,
+        }
+    }
+
+    /// Map the entire file once and return it, accounting for it in the mapped bytes of our cache.
+    pub(crate) fn whole(&self) -> std::io::Result<&[u8]> {
+        self.whole
+            .get_or_try_init(|| {
+                // SAFETY: we have to take the risk of somebody changing the file underneath. Git never writes into the same file.
+                #[allow(unsafe_code)]
+                let map = unsafe { memmap2::MmapOptions::new().map_copy_read_only(&self.file)? };
+                self.cache.state.lock().mapped_bytes += map.len() as u64;
+                Ok(map)
+            })
+            .map(|map| &map[..])
+    }
+}
+
+impl Drop for Windowed {
+    fn drop(&mut self) {
+        self.cache
+            .forget(self.id, self.whole.get().map_or(0, |map| map.len() as u64));
+    }
+}
+
+/// Bytes of a pack data file, either borrowed from a whole-file mapping or kept alive along with their window.
+pub(crate) enum Bytes<'a> {
+    Borrowed(&'a [u8]),
+    Window { map: Arc<Mmap>, start: usize },
+}
+
+impl Deref for Bytes<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        match self {
+            Bytes::Borrowed(data) => data,
+            Bytes::Window { map, start } => &map[*start..],
+        }
+    }
+}
@@ -68,6 +68,17 @@ impl index::File {
         }
     }
 
+    fn iter_v3(&self) -> impl Iterator<Item = Entry> + '_ {
+        match self.version {
+            index::Version::V3 => (0..self.num_objects).map(move |index| Entry {
+                oid: self.oid_at_index(index).to_owned(),
+                pack_offset: self.pack_offset_at_index(index),
+                crc32: self.crc32_at_index(index),
+            }),
+            _ => panic!("Cannot use iter_v3() on index of type {:?}", self.version),
+        }
+    }
+
     /// Returns the object hash at the given index in our list of (sorted) sha1 hashes.
     /// The index ranges from 0 to `self.num_objects()`
     ///
@@ -77,6 +88,7 @@ impl index::File {
     pub fn oid_at_index(&self, index: EntryIndex) -> &gix_hash::oid {
         let index = index as usize;
         let start = match self.version {
+            index::Version::V3 => self.v3().pack_order_oids + self.pack_order_index_v3(index) * self.hash_len,
             index::Version::V2 => V2_HEADER_SIZE + index * self.hash_len,
             index::Version::V1 => V1_HEADER_SIZE + index * (N32_SIZE + self.hash_len) + N32_SIZE,
         };
@@ -95,6 +107,15 @@ impl index::File {
                 let start = self.offset_pack_offset_v2() + index * N32_SIZE;
                 self.pack_offset_from_offset_v2(&self.data[start..][..N32_SIZE], self.offset_pack_offset64_v2())
             }
+            index::Version::V3 => {
+                let tables = self.v3();
+                let index = match tables.first_format {
+                    Some(first_format) => self.first_format_index_v3(index, first_format),
+                    None => index,
+                };
+                let start = tables.pack_offsets + index * N32_SIZE;
+                self.pack_offset_from_offset_v2(&self.data[start..][..N32_SIZE], tables.pack_offsets64)
+            }
             index::Version::V1 => {
                 let start = V1_HEADER_SIZE + index * (N32_SIZE + self.hash_len);
                 crate::read_u32(&self.data[start..][..N32_SIZE]) as u64
@@ -115,6 +136,10 @@ impl index::File {
                 let start = self.offset_crc32_v2() + index * N32_SIZE;
                 Some(crate::read_u32(&self.data[start..start + N32_SIZE]))
             }
+            index::Version::V3 => {
+                let start = self.v3().crc32 + self.pack_order_index_v3(index) * N32_SIZE;
+                Some(crate::read_u32(&self.data[start..start + N32_SIZE]))
+            }
             index::Version::V1 => None,
         }
     }
@@ -124,7 +149,44 @@ impl index::File {
     // NOTE: pretty much the same things as in `multi_index::File::lookup`, change things there
     //       as well.
     pub fn lookup(&self, id: impl AsRef<gix_hash::oid>) -> Option<EntryIndex> {
-        lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx))
+        match self.v3 {
+            Some(tables) => self.lookup_v3(id.as_ref(), tables),
+            None => lookup(id.as_ref(), &self.fan, &|idx| self.oid_at_index(idx)),
+        }
+    }
+
+    /// Bisect the table of shortened names, and only consult the full names stored in pack order to confirm a match.
+    fn lookup_v3(&self, id: &gix_hash::oid, tables: index::V3Tables) -> Option<EntryIndex> {
+        let short_name_at = |idx: EntryIndex| {
+            let start = tables.short_names + idx as usize * tables.short_name_len;
+            &self.data[start..][..tables.short_name_len]
+        };
+        let short_id = &id.as_bytes()[..tables.short_name_len];
+        let first_byte = id.first_byte() as usize;
+        let mut upper_bound = self.fan[first_byte];
+        let mut lower_bound = if first_byte != 0 { self.fan[first_byte - 1] } else { 0 };
+
+        while lower_bound < upper_bound {
+            let mid = (lower_bound + upper_bound) / 2;
+
+            use std::cmp::Ordering::*;
+            match short_id.cmp(short_name_at(mid)) {
+                Less => upper_bound = mid,
+                Equal => {
+                    // Shortened names may be ambiguous, so all neighbours with the same short name are candidates.
+                    let first = (0..mid)
+                        .rev()
+                        .take_while(|idx| short_name_at(*idx) == short_id)
+                        .last()
+                        .unwrap_or(mid);
+                    return (first..self.num_objects)
+                        .take_while(|idx| short_name_at(*idx) == short_id)
+                        .find(|idx| self.oid_at_index(*idx) == id);
+                }
+                Greater => lower_bound = mid + 1,
+            }
+        }
+        None
     }
 
     /// Given a `prefix`, find an object that matches it uniquely within this index and return `Some(Ok(entry_index))`.
@@ -154,6 +216,7 @@ impl index::File {
     /// An iterator over all [`Entries`][Entry] of this index file.
     pub fn iter<'a>(&'a self) -> Box<dyn Iterator<Item = Entry> + 'a> {
         match self.version {
+            index::Version::V3 => Box::new(self.iter_v3()),
             index::Version::V2 => Box::new(self.iter_v2()),
             index::Version::V1 => Box::new(self.iter_v1()),
         }
@@ -165,11 +228,14 @@ impl index::File {
     pub fn sorted_offsets(&self) -> Vec<data::Offset> {
         let mut ofs: Vec<_> = match self.version {
             index::Version::V1 => self.iter().map(|e| e.pack_offset).collect(),
-            index::Version::V2 => {
-                let offset32_start = &self.data[self.offset_pack_offset_v2()..];
+            index::Version::V2 | index::Version::V3 => {
+                let (offset32_start, pack_offset_64_start) = match self.v3 {
+                    Some(tables) => (tables.pack_offsets, tables.pack_offsets64),
+                    None => (self.offset_pack_offset_v2(), self.offset_pack_offset64_v2()),
+                };
+                let offset32_start = &self.data[offset32_start..];
                 let offsets32 = offset32_start.chunks_exact(N32_SIZE).take(self.num_objects as usize);
                 assert_eq!(self.num_objects as usize, offsets32.len());
-                let pack_offset_64_start = self.offset_pack_offset64_v2();
                 offsets32
                     .map(|offset| self.pack_offset_from_offset_v2(offset, pack_offset_64_start))
                     .collect()
@@ -194,9 +260,54 @@ impl index::File {
         self.offset_pack_offset_v2() + self.num_objects as usize * N32_SIZE
     }
 
+    #[inline]
+    fn v3(&self) -> &index::V3Tables {
+        self.v3.as_ref().expect("V3 tables are present in V3 files")
+    }
+
+    /// Return the position of the object at `index` in name order within the tables sorted in pack order.
+    #[inline]
+    fn pack_order_index_v3(&self, index: usize) -> usize {
+        let start = self.v3().name_to_pack_order + index * N32_SIZE;
+        crate::read_u32(&self.data[start..][..N32_SIZE]) as usize
+    }
+
+    /// Return the position of the object at `index` in name order within the tables of `first_format`, which are
+    /// also used to store the pack offsets of all formats.
+    fn first_format_index_v3(&self, index: usize, first_format: index::V3FirstFormatTables) -> usize {
+        let pack_order_index = self.pack_order_index_v3(index);
+        let name = &self.data[first_format.pack_order_oids + pack_order_index * first_format.hash_len..]
+            [..first_format.hash_len];
+        let short_name = &name[..first_format.short_name_len];
+        let short_name_at = |idx: usize| {
+            &self.data[first_format.short_names + idx * first_format.short_name_len..][..first_format.short_name_len]
+        };
+        let fan_at = |idx: usize| crate::read_u32(&self.data[first_format.fan + idx * N32_SIZE..][..N32_SIZE]) as usize;
+        let first_byte = name[0] as usize;
+        let mut lower_bound = if first_byte != 0 { fan_at(first_byte - 1) } else { 0 };
+        let upper_bound = fan_at(first_byte);
+        let mut end = upper_bound;
+        while lower_bound < end {
+            let mid = (lower_bound + end) / 2;
+            if short_name_at(mid) < short_name {
+                lower_bound = mid + 1;
+            } else {
+                end = mid;
+            }
+        }
+        // Shortened names may be ambiguous, so the candidate that refers to the same object in pack order is the one.
+        (lower_bound..upper_bound)
+            .take_while(|idx| short_name_at(*idx) == short_name)
+            .find(|idx| {
+                let start = first_format.name_to_pack_order + idx * N32_SIZE;
+                crate::read_u32(&self.data[start..][..N32_SIZE]) as usize == pack_order_index
+            })
+            .expect("all objects are present in the tables of each object format")
+    }
+
     #[inline]
     fn pack_offset_from_offset_v2(&self, offset: &[u8], pack64_offset: usize) -> data::Offset {
-        debug_assert_eq!(self.version, index::Version::V2);
+        debug_assert_ne!(self.version, index::Version::V1);
         let ofs32 = crate::read_u32(offset);
         if (ofs32 & N32_HIGH_BIT) == N32_HIGH_BIT {
             let from = pack64_offset + (ofs32 ^ N32_HIGH_BIT) as usize * N64_SIZE;
@@ -42,7 +42,7 @@ mod function {
     };
 
     use super::{fanout, HIGH_BIT, LARGE_OFFSET_THRESHOLD};
-    use crate::index::V2_SIGNATURE;
+    use crate::index::{v3_format_id, V2_SIGNATURE, V3_FORMAT_INFO_SIZE, V3_HEADER_START_SIZE, V3_SIGNATURE};
 
     struct Count<W> {
         bytes: u64,
@@ -78,19 +78,34 @@ mod function {
         progress: &mut dyn DynNestedProgress,
     ) -> io::Result<gix_hash::ObjectId> {
         use io::Write;
-        assert_eq!(kind, crate::index::Version::V2, "Can only write V2 packs right now");
+        assert_ne!(
+            kind,
+            crate::index::Version::V1,
+            "Can only write V2 and V3 indices right now"
+        );
         assert!(
             entries_sorted_by_oid.len() <= u32::MAX as usize,
             "a pack cannot have more than u32::MAX objects"
         );
+        let object_hash = pack_hash.kind();
 
         // Write header
         let mut out = Count::new(std::io::BufWriter::with_capacity(
             8 * 4096,
-            hash::Write::new(out, kind.hash()),
+            hash::Write::new(out, object_hash),
         ));
-        out.write_all(V2_SIGNATURE)?;
-        out.write_all(&(kind as u32).to_be_bytes())?;
+        let v3_layout = match kind {
+            crate::index::Version::V3 => {
+                let layout = V3Layout::new(&entries_sorted_by_oid, object_hash);
+                layout.write_header(&mut out, entries_sorted_by_oid.len() as u32, object_hash)?;
+                Some(layout)
+            }
+            _ => {
+                out.write_all(V2_SIGNATURE)?;
+                out.write_all(&(kind as u32).to_be_bytes())?;
+                None
+            }
+        };
 
         progress.init(Some(4), progress::steps());
         let start = std::time::Instant::now();
@@ -103,14 +118,38 @@ mod function {
 
         progress.inc();
         let _info = progress.add_child_with_id("writing ids".into(), gix_features::progress::UNKNOWN);
-        for entry in &entries_sorted_by_oid {
-            out.write_all(entry.data.id.as_slice())?;
+        match &v3_layout {
+            Some(layout) => {
+                for entry in &entries_sorted_by_oid {
+                    out.write_all(&entry.data.id.as_slice()[..layout.short_name_len])?;
+                }
+                for idx in &layout.pack_order {
+                    out.write_all(entries_sorted_by_oid[*idx as usize].data.id.as_slice())?;
+                }
+                for pack_order_idx in &layout.name_to_pack_order {
+                    out.write_all(&pack_order_idx.to_be_bytes())?;
+                }
+            }
+            None => {
+                for entry in &entries_sorted_by_oid {
+                    out.write_all(entry.data.id.as_slice())?;
+                }
+            }
         }
 
         progress.inc();
         let _info = progress.add_child_with_id("writing crc32".into(), gix_features::progress::UNKNOWN);
-        for entry in &entries_sorted_by_oid {
-            out.write_all(&entry.data.crc32.to_be_bytes())?;
+        match &v3_layout {
+            Some(layout) => {
+                for idx in &layout.pack_order {
+                    out.write_all(&entries_sorted_by_oid[*idx as usize].data.crc32.to_be_bytes())?;
+                }
+            }
+            None => {
+                for entry in &entries_sorted_by_oid {
+                    out.write_all(&entry.data.crc32.to_be_bytes())?;
+                }
+            }
         }
 
         progress.inc();
@@ -135,6 +174,12 @@ mod function {
             }
         }
 
+        if let Some(layout) = &v3_layout {
+            debug_assert_eq!(
+                out.bytes, layout.trailer_offset as u64,
+                "trailer location was precomputed"
+            );
+        }
         out.write_all(pack_hash.as_slice())?;
 
         let bytes_written_without_trailer = out.bytes;
@@ -146,13 +191,92 @@ mod function {
         progress.inc();
         progress.show_throughput_with(
             start,
-            (bytes_written_without_trailer + 20) as usize,
+            bytes_written_without_trailer as usize + object_hash.len_in_bytes(),
             progress::bytes().expect("unit always set"),
             progress::MessageLevel::Success,
         );
 
         Ok(index_hash)
     }
+
+    /// Information to write a V3 index file with tables for a single object format.
+    struct V3Layout {
+        /// The amount of bytes that suffice to tell all object names apart.
+        short_name_len: usize,
+        /// The indices of entries sorted by name, sorted by their pack offset.
+        pack_order: Vec<u32>,
+        /// For each entry in name order, its index in `pack_order`.
+        name_to_pack_order: Vec<u32>,
+        /// The offset of the trailer from the beginning of the file.
+        trailer_offset: u32,
+    }
+
+    impl V3Layout {
+        fn new(
+            entries_sorted_by_oid: &[crate::cache::delta::Item<crate::index::write::TreeEntry>],
+            object_hash: gix_hash::Kind,
+        ) -> Self {
+            let hash_len = object_hash.len_in_bytes();
+            let short_name_len = entries_sorted_by_oid
+                .windows(2)
+                .map(|w| {
+                    let (a, b) = (w[0].data.id.as_slice(), w[1].data.id.as_slice());
+                    a.iter().zip(b).take_while(|(a, b)| a == b).count() + 1
+                })
+                .max()
+                .unwrap_or(1)
+                .min(hash_len);
+
+            let mut pack_order: Vec<u32> = (0..entries_sorted_by_oid.len() as u32).collect();
+            pack_order.sort_by_key(|idx| entries_sorted_by_oid[*idx as usize].offset);
+            let mut name_to_pack_order = vec![0; pack_order.len()];
+            for (pack_order_idx, name_idx) in pack_order.iter().enumerate() {
+                name_to_pack_order[*name_idx as usize] = pack_order_idx as u32;
+            }
+
+            let num_objects = entries_sorted_by_oid.len();
+            let num_offsets64 = entries_sorted_by_oid
+                .iter()
+                .filter(|e| e.offset > LARGE_OFFSET_THRESHOLD)
+                .count();
+            let trailer_offset = Self::header_len()
+                + 256 * 4
+                + num_objects * (short_name_len + hash_len + 4 /* pack order */ + 4 /* crc32 */ + 4/* offset */)
+                + num_offsets64 * 8;
+            V3Layout {
+                short_name_len,
+                pack_order,
+                name_to_pack_order,
+                trailer_offset: trailer_offset
+                    .try_into()
+                    .expect("V3 index files are limited to 4GB in size"),
+            }
+        }
+
+        fn header_len() -> usize {
+            V3_HEADER_START_SIZE + V3_FORMAT_INFO_SIZE + 4 /* trailer offset */
+        }
+
+        fn write_header(
+            &self,
+            out: &mut dyn io::Write,
+            num_objects: u32,
+            object_hash: gix_hash::Kind,
+        ) -> io::Result<()> {
+            let header_len = Self::header_len() as u32;
+            out.write_all(V3_SIGNATURE)?;
+            out.write_all(&(crate::index::Version::V3 as u32).to_be_bytes())?;
+            out.write_all(&header_len.to_be_bytes())?;
+            out.write_all(&num_objects.to_be_bytes())?;
+            out.write_all(&1u32.to_be_bytes())?;
+
+            out.write_all(v3_format_id(object_hash))?;
+            out.write_all(&(self.short_name_len as u32).to_be_bytes())?;
+            out.write_all(&header_len.to_be_bytes())?;
+
+            out.write_all(&self.trailer_offset.to_be_bytes())
+        }
+    }
 }
 #[cfg(feature = "streaming-input")]
 pub(crate) use function::write_to;
@@ -1,6 +1,9 @@
 use std::{mem::size_of, path::Path};
 
-use crate::index::{self, Version, FAN_LEN, V2_SIGNATURE};
+use crate::index::{
+    self, v3_format_id, V3FirstFormatTables, V3Tables, Version, FAN_LEN, V2_SIGNATURE, V3_FORMAT_INFO_SIZE,
+    V3_HEADER_START_SIZE,
+};
 
 /// Returned by [`index::File::at()`].
 #[derive(thiserror::Error, Debug)]
@@ -15,6 +18,8 @@ pub enum Error {
     Corrupt { message: String },
     #[error("Unsupported index version: {version})")]
     UnsupportedVersion { version: u32 },
+    #[error("The V3 index file doesn't contain tables for objects hashed with {object_hash}")]
+    MissingObjectFormat { object_hash: gix_hash::Kind },
 }
 
 const N32_SIZE: usize = size_of::<u32>();
@@ -43,32 +48,31 @@ impl index::File {
                 message: format!("Pack index of size {idx_len} is too small for even an empty index"),
             });
         }
-        let (kind, fan, num_objects) = {
+        let (kind, fan, num_objects, v3) = {
             let (kind, d) = {
                 let (sig, d) = data.split_at(V2_SIGNATURE.len());
                 if sig == V2_SIGNATURE {
-                    (Version::V2, d)
-                } else {
-                    (Version::V1, &data[..])
-                }
-            };
-            let d = {
-                if let Version::V2 = kind {
                     let (vd, dr) = d.split_at(N32_SIZE);
-                    let version = crate::read_u32(vd);
-                    if version != Version::V2 as u32 {
-                        return Err(Error::UnsupportedVersion { version });
+                    match crate::read_u32(vd) {
+                        2 => (Version::V2, dr),
+                        3 => (Version::V3, dr),
+                        version => return Err(Error::UnsupportedVersion { version }),
                     }
-                    dr
                 } else {
-                    d
+                    (Version::V1, &data[..])
                 }
             };
-            let (fan, bytes_read) = read_fan(d);
-            let (_, _d) = d.split_at(bytes_read);
-            let num_objects = fan[FAN_LEN - 1];
-
-            (kind, fan, num_objects)
+            match kind {
+                Version::V1 | Version::V2 => {
+                    let (fan, _bytes_read) = read_fan(d);
+                    let num_objects = fan[FAN_LEN - 1];
+                    (kind, fan, num_objects, None)
+                }
+                Version::V3 => {
+                    let (fan, num_objects, tables) = read_v3_header(&data, object_hash)?;
+                    (kind, fan, num_objects, Some(tables))
+                }
+            }
         };
         Ok(index::File {
             data,
@@ -78,8 +82,108 @@ impl index::File {
             fan,
             hash_len,
             object_hash,
+            v3,
+        })
+    }
+}
+
+/// Parse the header of the V3 index file in `data`, and return the fan for the tables of `object_hash`
+/// along with the amount of objects and the location of all other tables.
+fn read_v3_header(data: &[u8], object_hash: gix_hash::Kind) -> Result<([u32; FAN_LEN], u32, V3Tables), Error> {
+    let corrupt = |message: &str| Error::Corrupt {
+        message: message.into(),
+    };
+    let u32_at = |ofs: usize| -> Result<u32, Error> {
+        data.get(ofs..ofs + N32_SIZE)
+            .map(crate::read_u32)
+            .ok_or_else(|| corrupt("V3 header is truncated"))
+    };
+    let header_len = u32_at(8)? as usize;
+    let num_objects = u32_at(12)?;
+    let num_formats = u32_at(16)? as usize;
+    if num_formats == 0 || V3_HEADER_START_SIZE + num_formats * V3_FORMAT_INFO_SIZE + N32_SIZE > header_len {
+        return Err(corrupt("V3 header length doesn't fit the amount of object formats"));
+    }
+    let trailer_ofs = u32_at(V3_HEADER_START_SIZE + num_formats * V3_FORMAT_INFO_SIZE)? as usize;
+    let hash_len = object_hash.len_in_bytes();
+    if trailer_ofs + hash_len * 2 != data.len() {
+        return Err(corrupt("V3 trailer offset doesn't match the file size"));
+    }
+
+    let format_info = |idx: usize| -> Result<(&[u8], usize, usize), Error> {
+        let ofs = V3_HEADER_START_SIZE + idx * V3_FORMAT_INFO_SIZE;
+        Ok((
+            &data[ofs..][..N32_SIZE],
+            u32_at(ofs + N32_SIZE)? as usize,
+            u32_at(ofs + N32_SIZE * 2)? as usize,
+        ))
+    };
+    let num_objects_usize = num_objects as usize;
+    // The CRC32 and offset tables are only stored with the first format, which can have a different hash length than ours.
+    let first_format = {
+        let (id, short_name_len, tables_ofs) = format_info(0)?;
+        let hash_len = match id {
+            b"sha1" => gix_hash::Kind::Sha1.len_in_bytes(),
+            _ => return Err(corrupt("The first object format of a V3 index is unknown")),
+        };
+        let short_names = tables_ofs + FAN_LEN * N32_SIZE;
+        let pack_order_oids = short_names + num_objects_usize * short_name_len;
+        V3FirstFormatTables {
+            fan: tables_ofs,
+            short_names,
+            short_name_len,
+            pack_order_oids,
+            name_to_pack_order: pack_order_oids + num_objects_usize * hash_len,
+            hash_len,
+        }
+    };
+    let crc32 = first_format.name_to_pack_order + num_objects_usize * N32_SIZE;
+    let pack_offsets = crc32 + num_objects_usize * N32_SIZE;
+    let pack_offsets64 = pack_offsets + num_objects_usize * N32_SIZE;
+    let (short_name_len, tables_ofs, is_first) = (0..num_formats)
+        .map(|idx| format_info(idx).map(|info| (idx, info)))
+        .collect::<Result<Vec<_>, _>>()?
+        .into_iter()
+        .find_map(|(idx, (id, short_name_len, tables_ofs))| {
+            (id == v3_format_id(object_hash)).then_some((short_name_len, tables_ofs, idx == 0))
         })
+        .ok_or(Error::MissingObjectFormat { object_hash })?;
+    if short_name_len == 0 || short_name_len > hash_len {
+        return Err(corrupt("V3 shortened object names have an invalid length"));
+    }
+
+    let fan_end = tables_ofs + FAN_LEN * N32_SIZE;
+    if fan_end > trailer_ofs {
+        return Err(corrupt("V3 fan-out table is out of bounds"));
+    }
+    let (fan, _) = read_fan(&data[tables_ofs..fan_end]);
+    if fan[FAN_LEN - 1] != num_objects {
+        return Err(corrupt(
+            "V3 fan-out table disagrees with the amount of objects in the header",
+        ));
+    }
+    let pack_order_oids = fan_end + num_objects_usize * short_name_len;
+    let name_to_pack_order = pack_order_oids + num_objects_usize * hash_len;
+    if pack_offsets64 > trailer_ofs
+        || name_to_pack_order + num_objects_usize * N32_SIZE > trailer_ofs
+        || first_format.short_names > trailer_ofs
+    {
+        return Err(corrupt("V3 tables are out of bounds"));
     }
+    Ok((
+        fan,
+        num_objects,
+        V3Tables {
+            short_names: fan_end,
+            short_name_len,
+            pack_order_oids,
+            name_to_pack_order,
+            crc32,
+            pack_offsets,
+            pack_offsets64,
+            first_format: (!is_first).then_some(first_format),
+        },
+    ))
 }
 
 fn read_fan(d: &[u8]) -> ([u32; FAN_LEN], usize) {
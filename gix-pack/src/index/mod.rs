@@ -83,13 +83,37 @@ pub enum Version {
     V1 = 1,
     #[default]
     V2 = 2,
+    V3 = 3,
 }
 
 impl Version {
     /// The kind of hash to produce to be compatible to this kind of index
     pub fn hash(&self) -> gix_hash::Kind {
         match self {
-            Version::V1 | Version::V2 => gix_hash::Kind::Sha1,
+            Version::V1 | Version::V2 | Version::V3 => gix_hash::Kind::Sha1,
+        }
+    }
+
+    /// The amount of objects from which on [`for_object_hash_and_count()`](Self::for_object_hash_and_count()) selects `V3`.
+    pub const MIN_OBJECTS_FOR_V3: u32 = 1 << 20;
+
+    /// Select the index version to write for `num_objects` objects hashed with `object_hash`.
+    ///
+    /// `V3` is used if the hash kind can't be represented by `V2`, or if there are at least [`MIN_OBJECTS_FOR_V3`](Self::MIN_OBJECTS_FOR_V3)
+    /// objects, as lookups in such large packs then bisect a compact table of shortened object names instead of full ones.
+    /// `V2` is used otherwise, and for packs with so many objects that their `V3` index could exceed the 4GB its 32 bit
+    /// table offsets can address.
+    ///
+    /// Note that `git` can't read `V3` index files, so `pack.indexVersion = 2` should be set to keep large packs readable by it.
+    pub fn for_object_hash_and_count(object_hash: gix_hash::Kind, num_objects: u32) -> Self {
+        let hash_len = object_hash.len_in_bytes();
+        let max_v3_objects = (u32::MAX as usize - V3_HEADER_START_SIZE - V3_FORMAT_INFO_SIZE - 4 - FAN_LEN * 4)
+            / (2 * hash_len + 4 /* pack order */ + 4 /* crc32 */ + 4 /* offset */ + 8/* 64 bit offset */);
+        let is_large = num_objects >= Self::MIN_OBJECTS_FOR_V3 && num_objects as usize <= max_v3_objects;
+        if object_hash != Version::V2.hash() || is_large {
+            Version::V3
+        } else {
+            Version::V2
         }
     }
 }
@@ -112,6 +136,47 @@ pub struct File {
     fan: [u32; FAN_LEN],
     hash_len: usize,
     object_hash: gix_hash::Kind,
+    /// The location of all tables in V3 files, which have a variable layout.
+    v3: Option<V3Tables>,
+}
+
+/// The byte offsets of all tables of the object format we read in a V3 index file.
+#[derive(Debug, Clone, Copy)]
+struct V3Tables {
+    /// Shortened object names in name order, each `short_name_len` bytes long.
+    short_names: usize,
+    /// The amount of bytes of each shortened object name.
+    short_name_len: usize,
+    /// Full object names in pack order.
+    pack_order_oids: usize,
+    /// A u32 for each object in name order, yielding its index in pack order.
+    name_to_pack_order: usize,
+    /// The CRC32 of each object in pack order, which is shared by all object formats.
+    crc32: usize,
+    /// 32 bit pack offsets in name order of the first object format, which are shared by all object formats.
+    pack_offsets: usize,
+    /// 64 bit pack offsets referred to by 32 bit offsets with the high bit set.
+    pack_offsets64: usize,
+    /// The tables of the first object format, if it's not the one we read, to translate our name order into its
+    /// name order for looking up pack offsets.
+    first_format: Option<V3FirstFormatTables>,
+}
+
+/// The byte offsets of the tables of the first object format in a V3 index file.
+#[derive(Debug, Clone, Copy)]
+struct V3FirstFormatTables {
+    /// The fan-out table.
+    fan: usize,
+    /// Shortened object names in name order, each `short_name_len` bytes long.
+    short_names: usize,
+    /// The amount of bytes of each shortened object name.
+    short_name_len: usize,
+    /// Full object names in pack order.
+    pack_order_oids: usize,
+    /// A u32 for each object in name order, yielding its index in pack order.
+    name_to_pack_order: usize,
+    /// The length of the full object names.
+    hash_len: usize,
 }
 
 /// Basic file information
@@ -135,6 +200,19 @@ impl File {
 }
 
 const V2_SIGNATURE: &[u8] = b"\xfftOc";
+/// V3 files use the same signature as V2, and are distinguished only by their version number.
+const V3_SIGNATURE: &[u8] = V2_SIGNATURE;
+/// The size of the fixed portion of a V3 header which precedes the per-format information.
+const V3_HEADER_START_SIZE: usize = 4 * 5;
+/// The size of the information stored for each object format in a V3 header.
+const V3_FORMAT_INFO_SIZE: usize = 4 * 3;
+
+/// Return the 4 byte identifier used in V3 index files to denote the object format `kind`.
+fn v3_format_id(kind: gix_hash::Kind) -> &'static [u8; 4] {
+    match kind {
+        gix_hash::Kind::Sha1 => b"sha1",
+    }
+}
 ///
 #[allow(clippy::empty_docs)]
 pub mod init;
@@ -69,6 +69,8 @@ impl index::File {
     /// * [`Algorithm::DeltaTreeLookup`] builds an index to avoid any unnecessary computation while resolving objects, avoiding
     ///   the need for a cache entirely, rendering `new_cache()` unused.
     ///   One could also call [`traverse_with_index()`][index::File::traverse_with_index()] directly.
+    ///   As it requires the whole `pack` to be mapped, packs opened with a [window cache](crate::data::window::Cache)
+    ///   are traversed with [`Algorithm::Lookup`] instead.
     /// * [`Algorithm::Lookup`] uses a cache created by `new_cache()` to avoid having to re-compute all bases of a delta-chain while
     ///   decoding objects.
     ///   One could also call [`traverse_with_lookup()`][index::File::traverse_with_lookup()] directly.
@@ -95,7 +97,14 @@ impl index::File {
         F: Fn() -> C + Send + Clone,
     {
         match traversal {
-            Algorithm::Lookup => self.traverse_with_lookup(
+            Algorithm::DeltaTreeLookup if !pack.is_windowed() => self.traverse_with_index(
+                pack,
+                processor,
+                progress,
+                should_interrupt,
+                with_index::Options { check, thread_limit },
+            ),
+            Algorithm::Lookup | Algorithm::DeltaTreeLookup => self.traverse_with_lookup(
                 processor,
                 pack,
                 progress,
@@ -106,13 +115,6 @@ impl index::File {
                     make_pack_lookup_cache,
                 },
             ),
-            Algorithm::DeltaTreeLookup => self.traverse_with_index(
-                pack,
-                processor,
-                progress,
-                should_interrupt,
-                with_index::Options { check, thread_limit },
-            ),
         }
     }
 
@@ -56,6 +56,9 @@ impl index::File {
     /// at the cost of memory.
     ///
     /// For more details, see the documentation on the [`traverse()`][index::File::traverse()] method.
+    ///
+    /// _Note_ that packs opened with a [window cache](crate::data::window::Cache) are mapped as a whole, just like
+    /// [`data::File::entry_slice()`](crate::data::File::entry_slice()) does.
     pub fn traverse_with_index<Processor, E>(
         &self,
         pack: &crate::data::File,
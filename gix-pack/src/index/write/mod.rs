@@ -66,7 +66,8 @@ impl crate::index::File {
     /// The resolver produced by `make_resolver` must resolve pack entries from the same pack data file that produced the
     /// `entries` iterator.
     ///
-    /// * `kind` is the version of pack index to produce, use [`crate::index::Version::default()`] if in doubt.
+    /// * `version` is the version of pack index to produce, or `None` to select it with
+    ///    [`crate::index::Version::for_object_hash_and_count()`] once the amount of objects is known.
     /// * `tread_limit` is used for a parallel tree traversal for obtaining object hashes with optimal performance.
     /// * `root_progress` is the top-level progress to stay informed about the progress of this potentially long-running
     ///    computation.
@@ -83,7 +84,7 @@ impl crate::index::File {
     /// the write operation to fail.
     #[allow(clippy::too_many_arguments)]
     pub fn write_data_iter_to_stream<F, F2, R>(
-        version: crate::index::Version,
+        version: Option<crate::index::Version>,
         make_resolver: F,
         entries: &mut dyn Iterator<Item = Result<crate::data::input::Entry, crate::data::input::Error>>,
         thread_limit: Option<usize>,
@@ -98,8 +99,8 @@ impl crate::index::File {
         R: Send + Sync,
         F2: for<'r> Fn(crate::data::EntryRange, &'r R) -> Option<&'r [u8]> + Send + Clone,
     {
-        if version != crate::index::Version::default() {
-            return Err(Error::Unsupported(version));
+        if version == Some(crate::index::Version::V1) {
+            return Err(Error::Unsupported(crate::index::Version::V1));
         }
         let mut num_objects: usize = 0;
         let mut last_seen_trailer = None;
@@ -172,6 +173,8 @@ impl crate::index::File {
         let num_objects: u32 = num_objects
             .try_into()
             .map_err(|_| Error::IteratorInvariantTooManyObjects(num_objects))?;
+        let version =
+            version.unwrap_or_else(|| crate::index::Version::for_object_hash_and_count(object_hash, num_objects));
 
         objects_progress.show_throughput(indexing_start);
         decompressed_progress.show_throughput(indexing_start);
@@ -193,7 +196,7 @@ impl crate::index::File {
                      decompressed: bytes,
                      ..
                  }| {
-                    modify_base(data, entry, bytes, version.hash());
+                    modify_base(data, entry, bytes, object_hash);
                     Ok::<_, Error>(())
                 },
                 traverse::Options {
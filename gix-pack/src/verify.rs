@@ -37,7 +37,29 @@ pub fn checksum_on_disk_or_mmap(
     progress: &mut dyn Progress,
     should_interrupt: &AtomicBool,
 ) -> Result<gix_hash::ObjectId, checksum::Error> {
-    let data_len_without_trailer = data.len() - object_hash.len_in_bytes();
+    checksum_on_disk_or_else(
+        data_path,
+        data.len(),
+        |len, consume| consume(&data[..len]),
+        expected,
+        object_hash,
+        progress,
+        should_interrupt,
+    )
+}
+
+/// Like [`checksum_on_disk_or_mmap()`], but only obtains the data of size `data_len` if the file can't be read from disk,
+/// by having `for_each_chunk` pass all of the given amount of leading bytes to the function it is called with.
+pub(crate) fn checksum_on_disk_or_else(
+    data_path: &Path,
+    data_len: usize,
+    for_each_chunk: impl FnOnce(usize, &mut dyn FnMut(&[u8])),
+    expected: gix_hash::ObjectId,
+    object_hash: gix_hash::Kind,
+    progress: &mut dyn Progress,
+    should_interrupt: &AtomicBool,
+) -> Result<gix_hash::ObjectId, checksum::Error> {
+    let data_len_without_trailer = data_len - object_hash.len_in_bytes();
     let actual = match gix_features::hash::bytes_of_file(
         data_path,
         data_len_without_trailer as u64,
@@ -50,7 +72,7 @@ pub fn checksum_on_disk_or_mmap(
         Err(_io_err) => {
             let start = std::time::Instant::now();
             let mut hasher = gix_features::hash::hasher(object_hash);
-            hasher.update(&data[..data_len_without_trailer]);
+            for_each_chunk(data_len_without_trailer, &mut |chunk| hasher.update(chunk));
             progress.inc_by(data_len_without_trailer);
             progress.show_throughput(start);
             gix_hash::ObjectId::from(hasher.digest())
@@ -0,0 +1,18 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+echo base > base
+git add base && git commit -q -m base
+
+git checkout -q -b side
+echo side > side
+git add side && git commit -q -m side
+
+git checkout -q main
+echo main > main
+git add main && git commit -q -m main
+
+git merge -q --no-ff -m merge side
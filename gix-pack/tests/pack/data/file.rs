@@ -219,3 +219,135 @@ mod decompress_entry {
         buf
     }
 }
+
+mod window_cache {
+    use std::sync::{atomic::AtomicBool, Arc};
+
+    use gix_features::progress;
+    use gix_pack::{cache, data::window};
+
+    use crate::{
+        fixture_path,
+        pack::{data::file::pack_at, INDEX_V2, PACK_FOR_INDEX_V2},
+    };
+
+    #[test]
+    fn decoding_through_small_windows_matches_whole_mapping() -> crate::Result {
+        let options = window::Options {
+            window_size: window::MIN_WINDOW_SIZE,
+            max_mapped_bytes: 2 * window::MIN_WINDOW_SIZE as u64,
+        };
+        let windows = Arc::new(window::Cache::new(options));
+        for (index_path, data_path) in [(INDEX_V2, PACK_FOR_INDEX_V2)] {
+            let index = gix_pack::index::File::at(fixture_path(index_path), gix_hash::Kind::Sha1)?;
+            let whole = pack_at(data_path);
+            let windowed = gix_pack::data::File::at_with_window_cache(
+                fixture_path(data_path),
+                gix_hash::Kind::Sha1,
+                windows.clone(),
+            )?;
+            assert!(
+                windowed.data_len() > options.max_mapped_bytes as usize,
+                "the pack must not fit into the limit"
+            );
+            assert_eq!(windowed.data_len(), whole.data_len());
+            assert_eq!(windowed.checksum(), whole.checksum());
+            assert_eq!(
+                windowed.verify_checksum(&mut progress::Discard, &AtomicBool::new(false))?,
+                whole.checksum()
+            );
+
+            let sorted_offsets = index.sorted_offsets();
+            for (idx, offset) in sorted_offsets.iter().enumerate() {
+                let entry = windowed.entry(*offset);
+                assert_eq!(entry, whole.entry(*offset));
+
+                let entry_end = sorted_offsets.get(idx + 1).copied().unwrap_or(whole.pack_end() as u64);
+                let size = (entry_end - offset) as usize;
+                assert_eq!(windowed.entry_crc32(*offset, size), whole.entry_crc32(*offset, size));
+
+                let (mut expected, mut actual) = (Vec::new(), Vec::new());
+                let resolve = |_: &gix_hash::oid, _: &mut Vec<u8>| None;
+                let expected_outcome = whole.decode_entry(
+                    entry.clone(),
+                    &mut expected,
+                    &mut Default::default(),
+                    &resolve,
+                    &mut cache::Never,
+                )?;
+                let actual_outcome =
+                    windowed.decode_entry(entry, &mut actual, &mut Default::default(), &resolve, &mut cache::Never)?;
+                assert_eq!(actual_outcome, expected_outcome);
+                assert_eq!(actual, expected);
+                assert!(
+                    windows.mapped_bytes() <= options.max_mapped_bytes,
+                    "windows are small enough for the limit to hold"
+                );
+            }
+
+            for (start, end) in sorted_offsets.iter().zip(sorted_offsets.iter().skip(1)) {
+                assert_eq!(
+                    windowed.entry_data(*start..*end).as_deref(),
+                    whole.entry_slice(*start..*end)
+                );
+            }
+            assert_eq!(windowed.entry_data(0..whole.data_len() as u64 + 1), None);
+            assert_ne!(windows.num_windows(), 0);
+            drop(windowed);
+            assert_eq!(windows.mapped_bytes(), 0, "windows are unmapped along with their pack");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn traversal_uses_windows_and_mapping_whole_packs_is_accounted_for() -> crate::Result {
+        let options = window::Options {
+            window_size: window::MIN_WINDOW_SIZE,
+            max_mapped_bytes: 2 * window::MIN_WINDOW_SIZE as u64,
+        };
+        let windows = Arc::new(window::Cache::new(options));
+        let index = gix_pack::index::File::at(fixture_path(INDEX_V2), gix_hash::Kind::Sha1)?;
+        let windowed = gix_pack::data::File::at_with_window_cache(
+            fixture_path(PACK_FOR_INDEX_V2),
+            gix_hash::Kind::Sha1,
+            windows.clone(),
+        )?;
+        for traversal in [
+            gix_pack::index::traverse::Algorithm::DeltaTreeLookup,
+            gix_pack::index::traverse::Algorithm::Lookup,
+        ] {
+            let outcome = index.verify_integrity(
+                Some(gix_pack::index::verify::PackContext {
+                    data: &windowed,
+                    options: gix_pack::index::verify::integrity::Options {
+                        verify_mode: Default::default(),
+                        traversal,
+                        make_pack_lookup_cache: || cache::Never,
+                        thread_limit: None,
+                    },
+                }),
+                &mut progress::Discard,
+                &AtomicBool::new(false),
+            )?;
+            assert_eq!(outcome.actual_index_checksum, index.index_checksum());
+            assert!(
+                windows.mapped_bytes() <= options.max_mapped_bytes,
+                "{traversal:?}: the pack isn't mapped as a whole when traversing it"
+            );
+        }
+
+        let data_len = windowed.data_len() as u64;
+        assert!(windowed.entry_slice(0..12).is_some());
+        assert!(
+            windows.mapped_bytes() >= data_len,
+            "mapping the whole pack is explicit, and accounted for"
+        );
+        drop(windowed);
+        assert_eq!(
+            windows.mapped_bytes(),
+            0,
+            "the whole mapping is released along with the pack"
+        );
+        Ok(())
+    }
+}
@@ -320,6 +320,43 @@ fn traversals() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn tree_additions_of_merge_commits_are_collected_across_all_parents() -> crate::Result {
+    let db = db(DbKind::MergeCommit)?;
+    let merge = hex_to_id("87060d3b3240e72a6e73444155f7acbacc526f80");
+    let (counts, stats) = output::count::objects(
+        db.clone(),
+        Box::new(std::iter::once(Ok(merge))),
+        &progress::Discard,
+        &AtomicBool::new(false),
+        count::objects::Options {
+            input_object_expansion: count::objects::ObjectExpansion::TreeAdditionsComparedToAncestor,
+            thread_limit: Some(1),
+            ..Default::default()
+        },
+    )?;
+
+    let mut buf = Vec::new();
+    let mut blobs = Vec::new();
+    for count in &counts {
+        let (obj, _location) = db.find(&count.id, &mut buf)?;
+        if obj.kind == gix_object::Kind::Blob {
+            blobs.push(obj.data.to_owned());
+        }
+    }
+    blobs.sort();
+    assert_eq!(
+        blobs,
+        [b"main\n".to_vec(), b"side\n".to_vec()],
+        "each blob is new compared to one of the parents, so both are needed"
+    );
+    assert_eq!(
+        stats.total_objects,
+        3 /* commits */ + 3 /* trees */ + 2, /* blobs */
+    );
+    Ok(())
+}
+
 #[test]
 fn empty_pack_is_allowed() {
     write_and_verify(
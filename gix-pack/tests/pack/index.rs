@@ -108,6 +108,124 @@ mod version {
         }
     }
 
+    mod v3 {
+        use std::sync::atomic::AtomicBool;
+
+        use gix_features::progress;
+        use gix_pack::{data::input, index};
+
+        use crate::{fixture_path, pack::V2_PACKS_AND_INDICES};
+
+        fn slice_map(entry: gix_pack::data::EntryRange, map: &memmap2::Mmap) -> Option<&[u8]> {
+            map.get(entry.start as usize..entry.end as usize)
+        }
+
+        fn write_index(
+            version: Option<index::Version>,
+            data_path: &str,
+        ) -> crate::Result<Result<(Vec<u8>, index::write::Outcome), index::write::Error>> {
+            let mut pack_iter = gix_pack::data::input::BytesToEntriesIter::new_from_header(
+                std::io::BufReader::new(std::fs::File::open(fixture_path(data_path))?),
+                input::Mode::Verify,
+                input::EntryDataMode::Crc32,
+                gix_hash::Kind::Sha1,
+            )?;
+            let pack_version = pack_iter.version();
+            let mut buf = Vec::new();
+            let res = index::File::write_data_iter_to_stream(
+                version,
+                || {
+                    let file = std::fs::File::open(fixture_path(data_path))?;
+                    let map = unsafe { memmap2::MmapOptions::new().map_copy_read_only(&file)? };
+                    Ok((slice_map, map))
+                },
+                &mut pack_iter,
+                None,
+                &mut progress::Discard,
+                &mut buf,
+                &AtomicBool::new(false),
+                gix_hash::Kind::Sha1,
+                pack_version,
+            );
+            Ok(res.map(|outcome| (buf, outcome)))
+        }
+
+        #[test]
+        fn write_and_lookup_matches_v2() -> crate::Result {
+            for (v2_index_path, data_path) in V2_PACKS_AND_INDICES {
+                let v2 = index::File::at(fixture_path(v2_index_path), gix_hash::Kind::Sha1)?;
+                let (buf, outcome) = write_index(Some(index::Version::V3), data_path)??;
+                let dir = gix_testtools::tempfile::tempdir()?;
+                let index_path = dir.path().join("pack.idx");
+                std::fs::write(&index_path, buf)?;
+                let v3 = index::File::at(&index_path, gix_hash::Kind::Sha1)?;
+
+                assert_eq!(outcome.index_version, index::Version::V3);
+                assert_eq!(v3.version(), index::Version::V3);
+                assert_eq!(v3.num_objects(), v2.num_objects());
+                assert_eq!(v3.pack_checksum(), v2.pack_checksum());
+                assert_eq!(
+                    v3.verify_checksum(&mut progress::Discard, &AtomicBool::new(false))?,
+                    outcome.index_hash,
+                    "the trailing checksum is valid"
+                );
+                assert_eq!(
+                    v3.iter().collect::<Vec<_>>(),
+                    v2.iter().collect::<Vec<_>>(),
+                    "entries are the same, including the CRC32 values"
+                );
+                assert_eq!(v3.sorted_offsets(), v2.sorted_offsets());
+
+                for entry in v2.iter() {
+                    let index = v3.lookup(entry.oid).expect("id present");
+                    assert_eq!(Some(index), v2.lookup(entry.oid));
+                    assert_eq!(v3.oid_at_index(index), entry.oid.as_ref());
+                    assert_eq!(v3.pack_offset_at_index(index), entry.pack_offset);
+                    assert_eq!(v3.crc32_at_index(index), entry.crc32);
+                    let prefix = gix_hash::Prefix::new(&entry.oid, 7)?;
+                    assert_eq!(v3.lookup_prefix(prefix, None), Some(Ok(index)));
+                }
+                assert_eq!(v3.lookup(gix_hash::Kind::Sha1.null()), None);
+            }
+            Ok(())
+        }
+
+        #[test]
+        fn automatic_version_selection() -> crate::Result {
+            let sha1 = gix_hash::Kind::Sha1;
+            assert_eq!(
+                index::Version::for_object_hash_and_count(sha1, index::Version::MIN_OBJECTS_FOR_V3 - 1),
+                index::Version::V2,
+                "V2 is preferred as it's understood by git"
+            );
+            assert_eq!(
+                index::Version::for_object_hash_and_count(sha1, index::Version::MIN_OBJECTS_FOR_V3),
+                index::Version::V3,
+                "large packs benefit from the shortened names of V3"
+            );
+            assert_eq!(
+                index::Version::for_object_hash_and_count(sha1, u32::MAX),
+                index::Version::V2,
+                "V3 files can't be larger than 4GB"
+            );
+
+            let (_buf, outcome) = write_index(None, V2_PACKS_AND_INDICES[0].1)??;
+            assert_eq!(
+                outcome.index_version,
+                index::Version::V2,
+                "the version is selected once the amount of objects is known"
+            );
+            Ok(())
+        }
+
+        #[test]
+        fn v1_cannot_be_written() -> crate::Result {
+            let err = write_index(Some(index::Version::V1), V2_PACKS_AND_INDICES[0].1)?.unwrap_err();
+            assert!(matches!(err, index::write::Error::Unsupported(index::Version::V1)));
+            Ok(())
+        }
+    }
+
     #[cfg(feature = "internal-testing-gix-features-parallel")]
     mod any {
         use std::{fs, io, sync::atomic::AtomicBool};
@@ -145,7 +263,7 @@ mod version {
                 let num_objects = pack_iter.len() as u32;
                 let pack_version = pack_iter.version();
                 let outcome = pack::index::File::write_data_iter_to_stream(
-                    desired_kind,
+                    Some(desired_kind),
                     || {
                         let file = std::fs::File::open(fixture_path(data_path))?;
                         let map = unsafe { memmap2::MmapOptions::map_copy_read_only(&file)? };
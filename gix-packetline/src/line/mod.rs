@@ -66,8 +66,7 @@ impl<'a> PacketLineRef<'a> {
 
 impl<'a> From<&'a [u8]> for TextRef<'a> {
     fn from(d: &'a [u8]) -> Self {
-        let d = if d[d.len() - 1] == b'\n' { &d[..d.len() - 1] } else { d };
-        TextRef(d)
+        TextRef(d.strip_suffix(b"\n").unwrap_or(d))
     }
 }
 
@@ -15,7 +15,9 @@ where
 {
     fn read_line_inner<'a>(reader: &mut T, buf: &'a mut [u8]) -> io::Result<Result<PacketLineRef<'a>, decode::Error>> {
         let (hex_bytes, data_bytes) = buf.split_at_mut(4);
-        reader.read_exact(hex_bytes)?;
+        reader
+            .read_exact(hex_bytes)
+            .map_err(|err| crate::read::describe_read_error(err, "before the next packet line was received"))?;
         let num_data_bytes = match decode::hex_prefix(hex_bytes) {
             Ok(decode::PacketLineOrWantedSize::Line(line)) => return Ok(Ok(line)),
             Ok(decode::PacketLineOrWantedSize::Wanted(additional_bytes)) => additional_bytes as usize,
@@ -23,7 +25,9 @@ where
         };
 
         let (data_bytes, _) = data_bytes.split_at_mut(num_data_bytes);
-        reader.read_exact(data_bytes)?;
+        reader
+            .read_exact(data_bytes)
+            .map_err(|err| crate::read::describe_read_error(err, "in the middle of a packet line"))?;
         match decode::to_data_line(data_bytes) {
             Ok(line) => Ok(Ok(line)),
             Err(err) => Ok(Err(err)),
@@ -18,6 +18,25 @@ type ExhaustiveOutcome<'a> = (
     Option<std::io::Result<Result<PacketLineRef<'a>, crate::decode::Error>>>, // actual method result
 );
 
+/// Turn `err` into an error that explains that the remote closed the connection unexpectedly `while_doing` something,
+/// or that it didn't send data in time if a read timeout was hit, or return it unchanged.
+#[cfg(any(feature = "blocking-io", feature = "async-io"))]
+fn describe_read_error(err: std::io::Error, while_doing: &str) -> std::io::Error {
+    use std::io::ErrorKind;
+    match err.kind() {
+        ErrorKind::UnexpectedEof => std::io::Error::new(
+            ErrorKind::UnexpectedEof,
+            format!("The remote closed the connection unexpectedly {while_doing}"),
+        ),
+        // Blocking sockets report a read timeout as `WouldBlock` on some platforms.
+        ErrorKind::TimedOut | ErrorKind::WouldBlock => std::io::Error::new(
+            ErrorKind::TimedOut,
+            format!("The remote didn't send any data within the read timeout {while_doing}"),
+        ),
+        _ => err,
+    }
+}
+
 mod error {
     use std::fmt::{Debug, Display, Formatter};
 
@@ -153,6 +153,9 @@ where
                                 break (U16_HEX_BYTES + ENCODED_BAND, d.len());
                             }
                             BandRef::Progress(d) => {
+                                if d.is_empty() {
+                                    continue;
+                                }
                                 let text = TextRef::from(d).0;
                                 match handle_progress(false, text) {
                                     ProgressAction::Continue => {}
@@ -165,6 +168,9 @@ where
                                 };
                             }
                             BandRef::Error(d) => {
+                                if d.is_empty() {
+                                    continue;
+                                }
                                 let text = TextRef::from(d).0;
                                 match handle_progress(true, text) {
                                     ProgressAction::Continue => {}
@@ -272,3 +272,41 @@ async fn handling_of_err_lines() {
         "An error can also be the reason, which is not distinguishable from an EOF"
     );
 }
+
+#[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+async fn empty_sideband_packets_are_keepalives_and_skipped() -> crate::Result {
+    let input = b"0005\x010005\x020005\x03000a\x01hello0005\x010000";
+    let mut rd = gix_packetline::StreamingPeekableIter::new(&input[..], &[PacketLineRef::Flush], false);
+    let mut seen_progress = 0;
+    let mut reader = rd.as_read_with_sidebands(|_is_err, _text| {
+        seen_progress += 1;
+        ProgressAction::Continue
+    });
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out).await?;
+    drop(reader);
+    assert_eq!(out.as_bstr(), "hello");
+    assert_eq!(seen_progress, 0, "empty progress and error packets aren't passed on");
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-io", async(feature = "async-io", async_std::test))]
+async fn truncated_packet_lines_are_an_unexpected_eof() {
+    for (input, expected) in [
+        (
+            &b"000a\x01hel"[..],
+            "The remote closed the connection unexpectedly in the middle of a packet line",
+        ),
+        (
+            &b"000a\x01hello00"[..],
+            "The remote closed the connection unexpectedly before the next packet line was received",
+        ),
+    ] {
+        let mut rd = gix_packetline::StreamingPeekableIter::new(input, &[PacketLineRef::Flush], false);
+        let mut reader = rd.as_read_with_sidebands(|_is_err, _text| ProgressAction::Continue);
+        let mut out = Vec::new();
+        let err = reader.read_to_end(&mut out).await.unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+        assert_eq!(err.to_string(), expected);
+    }
+}
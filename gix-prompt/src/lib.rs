@@ -10,6 +10,9 @@
 mod types;
 pub use types::{Error, Mode, Options};
 
+mod prompt;
+pub use prompt::{Prompt, Terminal};
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod unix;
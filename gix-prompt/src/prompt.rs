@@ -0,0 +1,38 @@
+use crate::{Error, Options};
+
+/// A way to ask the user for information, allowing applications to provide their own user interface instead of
+/// prompting in the terminal.
+///
+/// It's implemented for closures with the signature of [`ask()`](crate::ask()), and by [`Terminal`].
+pub trait Prompt {
+    /// Ask the user given a `prompt` like `"Password: "`, and return what they entered.
+    ///
+    /// `opts.mode` indicates if the input should be [hidden](crate::Mode::Hidden) as it's sensitive,
+    /// or if prompting is [disabled](crate::Mode::Disable) entirely.
+    fn ask(&self, prompt: &str, opts: &Options<'_>) -> Result<String, Error>;
+}
+
+/// A [`Prompt`] that calls the `askpass` program if configured, or asks in the terminal otherwise, just like [`ask()`](crate::ask()).
+#[derive(Default, Debug, Copy, Clone)]
+pub struct Terminal;
+
+impl Prompt for Terminal {
+    fn ask(&self, prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
+        crate::ask(prompt, opts)
+    }
+}
+
+impl<F> Prompt for F
+where
+    F: Fn(&str, &Options<'_>) -> Result<String, Error>,
+{
+    fn ask(&self, prompt: &str, opts: &Options<'_>) -> Result<String, Error> {
+        self(prompt, opts)
+    }
+}
+
+impl std::fmt::Debug for dyn Prompt + Send + Sync {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("dyn Prompt")
+    }
+}
@@ -1,5 +1,31 @@
 mod options;
 
+mod prompt {
+    use gix_prompt::{Error, Mode, Options, Prompt, Terminal};
+
+    #[test]
+    fn terminal_respects_disabled_mode() {
+        let err = Terminal
+            .ask(
+                "Password: ",
+                &Options {
+                    mode: Mode::Disable,
+                    askpass: None,
+                },
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::Disabled));
+    }
+
+    #[test]
+    fn closures_can_be_used_as_prompt() {
+        let prompt =
+            |message: &str, opts: &Options<'_>| -> Result<String, Error> { Ok(format!("{message}{:?}", opts.mode)) };
+        let prompt: &dyn Prompt = &prompt;
+        assert_eq!(prompt.ask("mode: ", &Options::default()).unwrap(), "mode: Hidden");
+    }
+}
+
 mod ask {
     use gix_testtools::bstr::ByteSlice;
 
@@ -18,6 +18,7 @@ pub struct Arguments {
     deepen_not: bool,
     deepen_relative: bool,
     ref_in_want: bool,
+    wait_for_done: bool,
     supports_include_tag: bool,
 
     features_for_first_want: Option<Vec<String>>,
@@ -33,7 +34,12 @@ impl Arguments {
     /// This can happen if callers assure that they won't add 'wants' if their 'have' is the same, i.e. if the remote has nothing
     /// new for them.
     pub fn is_empty(&self) -> bool {
-        self.haves.is_empty() && !self.args.iter().rev().any(|arg| arg.starts_with_str("want "))
+        self.haves.is_empty()
+            && !self
+                .args
+                .iter()
+                .rev()
+                .any(|arg| arg.starts_with_str("want ") || arg.starts_with_str("want-ref "))
     }
     /// Return true if ref filters is supported.
     pub fn can_use_filter(&self) -> bool {
@@ -76,6 +82,12 @@ impl Arguments {
     pub fn can_use_ref_in_want(&self) -> bool {
         self.ref_in_want
     }
+    /// Return true if the 'wait-for-done' capability is supported.
+    ///
+    /// This is only ever the case in protocol v2.
+    pub fn can_use_wait_for_done(&self) -> bool {
+        self.wait_for_done
+    }
     /// Return true if the 'include-tag' capability is supported.
     pub fn can_use_include_tag(&self) -> bool {
         self.supports_include_tag
@@ -171,6 +183,17 @@ impl Arguments {
         }
     }
 
+    /// Ask the server to not send a pack until we are done negotiating, even if it is ready to send one, so it will only
+    /// acknowledge `ready` instead.
+    ///
+    /// Needs to only be called once.
+    pub fn use_wait_for_done(&mut self) {
+        debug_assert!(self.wait_for_done, "'wait-for-done' feature required");
+        if self.wait_for_done {
+            self.args.push("wait-for-done".into());
+        }
+    }
+
     /// Add the given `feature`, unconditionally.
     ///
     /// Note that sending an unknown or unsupported feature may cause the remote to terminate
@@ -204,6 +227,7 @@ impl Arguments {
         let filter = has("filter");
         let shallow = has("shallow");
         let ref_in_want = has("ref-in-want");
+        let wait_for_done = version == gix_transport::Protocol::V2 && has("wait-for-done");
         let mut deepen_since = shallow;
         let mut deepen_not = shallow;
         let mut deepen_relative = shallow;
@@ -243,6 +267,7 @@ impl Arguments {
             deepen_not,
             deepen_relative,
             ref_in_want,
+            wait_for_done,
             deepen_since,
             features_for_first_want,
             trace,
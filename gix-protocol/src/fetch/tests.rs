@@ -288,7 +288,7 @@ mod arguments {
     mod v2 {
         use bstr::ByteSlice;
 
-        use crate::fetch::tests::arguments::{arguments_v2, id, transport};
+        use crate::fetch::tests::arguments::{arguments_v1, arguments_v2, id, transport};
 
         #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
         async fn include_tag() {
@@ -408,6 +408,42 @@ mod arguments {
 000eofs-delta
 001dwant-ref refs/heads/main
 0009done
+0000"
+                    .as_bstr()
+            )
+        }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn ref_in_want_only_is_not_empty() {
+            let mut arguments = arguments_v2(["ref-in-want"].iter().copied());
+            assert!(arguments.is_empty());
+            arguments.want_ref(b"refs/heads/main".as_bstr());
+            assert!(!arguments.is_empty(), "want-refs are wants as well");
+        }
+
+        #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+        async fn wait_for_done() {
+            let mut out = Vec::new();
+            let mut t = transport(&mut out, false);
+            let mut arguments = arguments_v2(["wait-for-done"].iter().copied());
+            assert!(arguments.can_use_wait_for_done());
+            assert!(
+                !arguments_v1(["wait-for-done"].iter().copied()).can_use_wait_for_done(),
+                "it's a V2 feature only"
+            );
+
+            arguments.use_wait_for_done();
+            arguments.want(id("7b333369de1221f9bfbbe03a3a13e9a09bc1c907"));
+            arguments.have(id("1111111111111111111111111111111111111111"));
+            arguments.send(&mut t, false).await.expect("sending to buffer to work");
+            assert_eq!(
+                out.as_bstr(),
+                b"0012command=fetch
+0001000ethin-pack
+000eofs-delta
+0012wait-for-done
+0032want 7b333369de1221f9bfbbe03a3a13e9a09bc1c907
+0032have 1111111111111111111111111111111111111111
 0000"
                     .as_bstr()
             )
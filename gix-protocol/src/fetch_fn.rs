@@ -71,6 +71,7 @@ where
     let crate::handshake::Outcome {
         server_protocol_version: protocol_version,
         refs,
+        v1_shallow_updates: _,
         capabilities,
     } = crate::fetch::handshake(
         &mut transport,
@@ -23,7 +23,7 @@ where
     T: client::Transport,
 {
     let _span = gix_features::trace::detail!("gix_protocol::handshake()", service = ?service, extra_parameters = ?extra_parameters);
-    let (server_protocol_version, refs, capabilities) = {
+    let (server_protocol_version, refs, v1_shallow_updates, capabilities) = {
         progress.init(None, progress::steps());
         progress.set_name("handshake".into());
         progress.step();
@@ -76,7 +76,7 @@ where
             });
         }
 
-        let parsed_refs = match refs {
+        let (refs, v1_shallow_updates) = match refs {
             Some(mut refs) => {
                 assert!(
                     matches!(
@@ -85,19 +85,20 @@ where
                     ),
                     "Only V(0|1) auto-responds with refs"
                 );
-                Some(
+                let (refs, shallow) =
                     refs::from_v1_refs_received_as_part_of_handshake_and_capabilities(&mut refs, capabilities.iter())
-                        .await?,
-                )
+                        .await?;
+                (Some(refs), (!shallow.is_empty()).then_some(shallow))
             }
-            None => None,
+            None => (None, None),
         };
-        (actual_protocol, parsed_refs, capabilities)
+        (actual_protocol, refs, v1_shallow_updates, capabilities)
     }; // this scope is needed, see https://github.com/rust-lang/rust/issues/76149
 
     Ok(Outcome {
         server_protocol_version,
         refs,
+        v1_shallow_updates,
         capabilities,
     })
 }
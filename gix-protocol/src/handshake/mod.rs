@@ -56,6 +56,9 @@ pub struct Outcome {
     pub server_protocol_version: gix_transport::Protocol,
     /// The references reported as part of the Protocol::V1 handshake, or `None` otherwise as V2 requires a separate request.
     pub refs: Option<Vec<Ref>>,
+    /// The shallow boundary of a shallow remote as reported as part of the Protocol::V1 handshake, or `None` if the remote isn't
+    /// shallow or if the protocol is V2, which reports it in the response to the `fetch` command instead.
+    pub v1_shallow_updates: Option<Vec<crate::fetch::response::ShallowUpdate>>,
     /// The server capabilities.
     pub capabilities: Capabilities,
 }
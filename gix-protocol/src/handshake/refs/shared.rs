@@ -1,6 +1,9 @@
 use bstr::{BStr, BString, ByteSlice};
 
-use crate::handshake::{refs::parse::Error, Ref};
+use crate::{
+    fetch::response::ShallowUpdate,
+    handshake::{refs::parse::Error, Ref},
+};
 
 impl From<InternalRef> for Ref {
     fn from(v: InternalRef) -> Self {
@@ -123,9 +126,15 @@ pub(crate) fn from_capabilities<'a>(
 pub(in crate::handshake::refs) fn parse_v1(
     num_initial_out_refs: usize,
     out_refs: &mut Vec<InternalRef>,
+    out_shallow: &mut Vec<ShallowUpdate>,
     line: &BStr,
 ) -> Result<(), Error> {
     let trimmed = line.trim_end();
+    if let Some(hex_hash) = trimmed.strip_prefix(b"shallow ") {
+        // shallow remotes list their shallow boundary after all refs.
+        out_shallow.push(ShallowUpdate::Shallow(gix_hash::ObjectId::from_hex(hex_hash)?));
+        return Ok(());
+    }
     let (hex_hash, path) = trimmed.split_at(
         trimmed
             .find(b" ")
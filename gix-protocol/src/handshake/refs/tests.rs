@@ -5,7 +5,10 @@ fn oid(hex: &str) -> gix_hash::ObjectId {
     gix_hash::ObjectId::from_hex(hex.as_bytes()).expect("40 bytes hex")
 }
 
-use crate::handshake::{refs, refs::shared::InternalRef, Ref};
+use crate::{
+    fetch::response::ShallowUpdate,
+    handshake::{refs, refs::shared::InternalRef, Ref},
+};
 
 #[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
 async fn extract_references_from_v2_refs() {
@@ -76,10 +79,12 @@ async fn extract_references_from_v1_refs() {
 73a6868963993a3328e7d8fe94e5a6ac5078a944 refs/heads/main
 8e472f9ccc7d745927426cbb2d9d077de545aa4e refs/pull/13/head
 dce0ea858eef7ff61ad345cc5cdac62203fb3c10 refs/tags/gix-commitgraph-v0.0.0
-21c9b7500cb144b3169a6537961ec2b9e865be81 refs/tags/gix-commitgraph-v0.0.0^{}"
+21c9b7500cb144b3169a6537961ec2b9e865be81 refs/tags/gix-commitgraph-v0.0.0^{}
+shallow 8e472f9ccc7d745927426cbb2d9d077de545aa4e
+shallow 21c9b7500cb144b3169a6537961ec2b9e865be81"
             .as_bytes(),
     );
-    let out = refs::from_v1_refs_received_as_part_of_handshake_and_capabilities(
+    let (out, shallow) = refs::from_v1_refs_received_as_part_of_handshake_and_capabilities(
         input,
         Capabilities::from_bytes(b"\0symref=HEAD:refs/heads/main symref=MISSING_NAMESPACE_TARGET:(null)")
             .expect("valid capabilities")
@@ -115,7 +120,15 @@ dce0ea858eef7ff61ad345cc5cdac62203fb3c10 refs/tags/gix-commitgraph-v0.0.0
                 object: oid("21c9b7500cb144b3169a6537961ec2b9e865be81")
             },
         ]
-    )
+    );
+    assert_eq!(
+        shallow,
+        vec![
+            ShallowUpdate::Shallow(oid("8e472f9ccc7d745927426cbb2d9d077de545aa4e")),
+            ShallowUpdate::Shallow(oid("21c9b7500cb144b3169a6537961ec2b9e865be81"))
+        ],
+        "shallow remotes send their boundary after the refs"
+    );
 }
 
 #[test]
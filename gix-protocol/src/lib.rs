@@ -1,4 +1,4 @@
-//! An abstraction over [fetching][fetch()] a pack from the server.
+//! An abstraction over [fetching][fetch()] a pack from the server, and [pushing][push()] one to it.
 //!
 //! This implementation hides the transport layer, statefulness and the protocol version to the [fetch delegate][fetch::Delegate],
 //! the actual client implementation.
@@ -61,6 +61,13 @@ pub mod ls_refs;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
 pub use ls_refs::function::ls_refs;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub mod push;
+#[cfg(any(feature = "blocking-client", feature = "async-client"))]
+pub use push::function::push;
+
 mod util;
 pub use util::agent;
 #[cfg(any(feature = "blocking-client", feature = "async-client"))]
@@ -0,0 +1,63 @@
+use bstr::{BStr, BString, ByteVec};
+
+use super::Update;
+
+/// The error returned by a [signer](Sign) of a push [`Certificate`].
+pub type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+/// A way to sign push [certificates](Certificate), typically by calling out to `gpg`.
+pub trait Sign {
+    /// Return a detached and ASCII-armored signature of `payload` that ends with a newline.
+    fn sign(&mut self, payload: &[u8]) -> Result<BString, Error>;
+}
+
+impl<F> Sign for F
+where
+    F: FnMut(&[u8]) -> Result<BString, Error>,
+{
+    fn sign(&mut self, payload: &[u8]) -> Result<BString, Error> {
+        self(payload)
+    }
+}
+
+/// The information needed to send a signed push certificate along with the reference updates in [`push()`](crate::push()),
+/// which allows the server to verify who requested them.
+///
+/// It's only sent if the server advertises the `push-cert` capability along with the nonce to sign.
+pub struct Certificate<'a> {
+    /// The identity of the signer, typically the signing key or the committer like `Name <email>`.
+    pub pusher: BString,
+    /// The time at which the push is performed.
+    pub time: gix_date::Time,
+    /// The url of the remote we push to, without credentials.
+    pub pushee: Option<BString>,
+    /// If `true`, fail if the server doesn't support signed pushes instead of pushing without certificate.
+    pub required: bool,
+    /// The implementation to sign the certificate with.
+    pub signer: &'a mut dyn Sign,
+}
+
+impl Certificate<'_> {
+    /// Produce the certificate for `updates` and `push_options` with the `nonce` advertised by the server,
+    /// and append its signature.
+    pub fn to_signed(&mut self, nonce: &BStr, updates: &[Update], push_options: &[BString]) -> Result<BString, Error> {
+        let mut out = BString::from("certificate version 0.1\n");
+        out.push_str(format!("pusher {} ", self.pusher));
+        out.push_str(self.time.to_bstring());
+        out.push(b'\n');
+        if let Some(pushee) = &self.pushee {
+            out.push_str(format!("pushee {pushee}\n"));
+        }
+        out.push_str(format!("nonce {nonce}\n"));
+        for push_option in push_options {
+            out.push_str(format!("push-option {push_option}\n"));
+        }
+        out.push(b'\n');
+        for update in updates {
+            out.push_str(format!("{} {} {}\n", update.old, update.new, update.name));
+        }
+        let signature = self.signer.sign(&out)?;
+        out.push_str(signature);
+        Ok(out)
+    }
+}
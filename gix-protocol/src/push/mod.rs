@@ -0,0 +1,334 @@
+use bstr::BString;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod report;
+pub use report::Report;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod certificate;
+pub use certificate::{Certificate, Sign};
+
+/// A reference update to request from the server when pushing.
+#[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Update {
+    /// The full name of the reference on the remote, like `refs/heads/main`.
+    pub name: BString,
+    /// The value the remote reference is expected to have, or the null id if it is expected to not exist.
+    pub old: gix_hash::ObjectId,
+    /// The value to set the remote reference to, or the null id to delete it.
+    pub new: gix_hash::ObjectId,
+}
+
+impl Update {
+    /// Create an update that sets the remote reference `name` to `new`, expecting it to have the value it has in the
+    /// `advertised` references of the server, or to not exist if it wasn't advertised.
+    pub fn from_advertised(
+        name: impl Into<BString>,
+        new: gix_hash::ObjectId,
+        advertised: &[crate::handshake::Ref],
+    ) -> Self {
+        let name = name.into();
+        let old = advertised
+            .iter()
+            .map(crate::handshake::Ref::unpack)
+            .find_map(|(advertised_name, target, _peeled)| (advertised_name == name).then_some(target).flatten())
+            .map_or_else(|| new.kind().null(), ToOwned::to_owned);
+        Update { name, old, new }
+    }
+
+    /// Return `true` if this update deletes the remote reference.
+    pub fn is_delete(&self) -> bool {
+        self.new.is_null()
+    }
+}
+
+/// Options for use in [`push()`](crate::push()).
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+pub struct Options {
+    /// If `true`, ask the server to either update all references or none of them, which requires the `atomic` capability.
+    pub atomic: bool,
+    /// Strings to pass to the hooks of the server, which requires the `push-options` capability.
+    pub push_options: Vec<BString>,
+}
+
+/// The data of the pack to send along with the reference updates in [`push()`](crate::push()).
+#[cfg(feature = "blocking-client")]
+pub type PackData<'a> = dyn std::io::Read + 'a;
+/// The data of the pack to send along with the reference updates in [`push()`](crate::push()).
+#[cfg(feature = "async-client")]
+pub type PackData<'a> = dyn futures_io::AsyncRead + Unpin + 'a;
+
+mod error {
+    /// The error returned by [`push()`](crate::push()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        Transport(#[from] gix_transport::client::Error),
+        #[error("The server doesn't support the '{feature}' capability, which is needed to {description}")]
+        MissingServerFeature {
+            feature: &'static str,
+            description: &'static str,
+        },
+        #[error("The status report of the server could not be decoded")]
+        DecodeReport(#[from] gix_transport::packetline::decode::Error),
+        #[error("The status report of the server ended unexpectedly")]
+        UnexpectedEndOfReport,
+        #[error(transparent)]
+        Report(#[from] super::report::Error),
+        #[error("Could not sign the push certificate")]
+        SignCertificate(#[source] super::certificate::Error),
+    }
+
+    impl gix_transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Io(err) => err.is_spurious(),
+                Error::Transport(err) => err.is_spurious(),
+                _ => false,
+            }
+        }
+    }
+}
+pub use error::Error;
+
+pub(crate) mod function {
+    use bstr::{BStr, BString, ByteSlice, ByteVec};
+    #[cfg(feature = "async-client")]
+    use futures_lite::io::{AsyncReadExt, AsyncWriteExt};
+    use gix_features::progress::NestedProgress;
+    use gix_transport::{
+        client,
+        client::{Capabilities, MessageKind, WriteMode},
+        packetline::{decode, PacketLineRef},
+    };
+    use maybe_async::maybe_async;
+    #[cfg(feature = "blocking-client")]
+    use std::io::{Read, Write};
+
+    use super::{Certificate, Error, Options, PackData, Report, Update};
+    use crate::indicate_end_of_interaction;
+
+    /// Send `updates` of references to the server on the other side of `transport` after a prior handshake for the
+    /// `receive-pack` service that yielded the server `capabilities`, along with the `pack` that contains all objects
+    /// the server needs to perform them, and return the status report of the server.
+    ///
+    /// The `pack` may be thin unless the server advertises `no-thin`, and may only contain offset deltas if the server
+    /// advertises `ofs-delta`. It is only sent if there is at least one update that doesn't delete a reference, as no pack
+    /// is expected by the server otherwise.
+    ///
+    /// * `options` control how the updates are to be performed.
+    /// * `certificate`, if set, is used to sign the updates if the server advertises `push-cert`.
+    /// * `agent` is the name of the git client to present as `agent`, like `"my-app (v2.0)"`.
+    /// * `progress` is used to show progress messages sent by the server.
+    /// * If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+    ///
+    /// If there are no `updates`, the server is informed that the interaction ended and an empty report is returned.
+    /// The same is the case if the server doesn't advertise `report-status`.
+    #[allow(clippy::too_many_arguments)]
+    #[maybe_async]
+    pub async fn push<T, P>(
+        mut transport: T,
+        capabilities: &Capabilities,
+        updates: &[Update],
+        pack: Option<&mut PackData<'_>>,
+        options: &Options,
+        certificate: Option<Certificate<'_>>,
+        agent: impl Into<String>,
+        progress: &mut P,
+        trace: bool,
+    ) -> Result<Report, Error>
+    where
+        T: client::Transport,
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let _span = gix_features::trace::coarse!("gix_protocol::push()", updates = ?updates);
+        if updates.is_empty() {
+            indicate_end_of_interaction(transport, trace).await?;
+            return Ok(Report::default());
+        }
+
+        let (features, report_status, side_band) = select_features(capabilities, updates, options, agent.into())?;
+        let signed_certificate = match select_certificate_nonce(capabilities, certificate.as_ref())? {
+            Some(nonce) => Some(
+                certificate
+                    .expect("present if there is a nonce")
+                    .to_signed(nonce, updates, &options.push_options)
+                    .map_err(Error::SignCertificate)?,
+            ),
+            None => None,
+        };
+        let mut writer = transport.request(WriteMode::Binary, MessageKind::Flush, trace)?;
+        match signed_certificate {
+            Some(signed_certificate) => {
+                // The updates are part of the certificate, and the features are sent with its first line instead.
+                let mut line = BString::from("push-cert");
+                line.push(0);
+                line.push_str(features.join(" "));
+                writer.write_all(&line).await?;
+                for line in signed_certificate.lines_with_terminator() {
+                    writer.write_all(line).await?;
+                }
+                writer.write_all(b"push-cert-end\n").await?;
+            }
+            None => {
+                for (idx, update) in updates.iter().enumerate() {
+                    let mut line = BString::from(format!("{} {} ", update.old, update.new));
+                    line.push_str(&update.name);
+                    if idx == 0 {
+                        line.push(0);
+                        line.push_str(features.join(" "));
+                    }
+                    writer.write_all(&line).await?;
+                }
+            }
+        }
+        writer.write_message(MessageKind::Flush).await?;
+        if !options.push_options.is_empty() {
+            for push_option in &options.push_options {
+                writer.write_all(push_option).await?;
+            }
+            writer.write_message(MessageKind::Flush).await?;
+        }
+
+        let (mut writer, mut reader) = writer.into_parts();
+        match pack {
+            Some(pack) if updates.iter().any(|update| !update.is_delete()) => {
+                progress.set_name("send pack".into());
+                copy(pack, &mut writer).await?;
+            }
+            _ => {}
+        }
+        writer.flush().await?;
+        drop(writer);
+
+        if !report_status {
+            return Ok(Report::default());
+        }
+        if side_band {
+            reader.set_progress_handler(Some(Box::new({
+                let mut remote_progress = progress.add_child("remote");
+                move |is_err: bool, data: &[u8]| {
+                    crate::RemoteProgress::translate_to_progress(is_err, data, &mut remote_progress);
+                    gix_transport::packetline::read::ProgressAction::Continue
+                }
+            }) as gix_transport::client::HandleProgress<'_>));
+        }
+        let mut response = Vec::new();
+        reader.read_to_end(&mut response).await?;
+        let lines = if side_band {
+            packet_lines(&response)?
+        } else {
+            response.lines().map(ByteSlice::as_bstr).collect()
+        };
+        Ok(Report::from_lines(lines)?)
+    }
+
+    /// Return the features to send along with the first update, along with flags telling whether we expect a status report
+    /// and if it's sent in side-band mode.
+    fn select_features(
+        capabilities: &Capabilities,
+        updates: &[Update],
+        options: &Options,
+        agent: String,
+    ) -> Result<(Vec<String>, bool, bool), Error> {
+        let mut features = Vec::new();
+        let report_status = if capabilities.contains("report-status-v2") {
+            features.push("report-status-v2".into());
+            true
+        } else if capabilities.contains("report-status") {
+            features.push("report-status".into());
+            true
+        } else {
+            false
+        };
+        let side_band = capabilities.contains("side-band-64k");
+        if side_band {
+            features.push("side-band-64k".into());
+        }
+        if options.atomic {
+            if !capabilities.contains("atomic") {
+                return Err(Error::MissingServerFeature {
+                    feature: "atomic",
+                    description: "update all references atomically",
+                });
+            }
+            features.push("atomic".into());
+        }
+        if !options.push_options.is_empty() {
+            if !capabilities.contains("push-options") {
+                return Err(Error::MissingServerFeature {
+                    feature: "push-options",
+                    description: "send push options",
+                });
+            }
+            features.push("push-options".into());
+        }
+        if updates.iter().any(Update::is_delete) && !capabilities.contains("delete-refs") {
+            return Err(Error::MissingServerFeature {
+                feature: "delete-refs",
+                description: "delete references",
+            });
+        }
+        if let Some(object_format) = capabilities.capability("object-format").and_then(|c| c.value()) {
+            features.push(format!("object-format={object_format}"));
+        }
+        if capabilities.contains("agent") {
+            features.push(format!("agent={}", crate::agent(agent)));
+        }
+        Ok((features, report_status, side_band))
+    }
+
+    /// Return the nonce to sign if the `certificate` should be sent, or fail if it's required but the server doesn't support it.
+    fn select_certificate_nonce<'a>(
+        capabilities: &'a Capabilities,
+        certificate: Option<&Certificate<'_>>,
+    ) -> Result<Option<&'a BStr>, Error> {
+        let Some(certificate) = certificate else {
+            return Ok(None);
+        };
+        match capabilities.capability("push-cert").and_then(|c| c.value()) {
+            Some(nonce) => Ok(Some(nonce)),
+            None if certificate.required => Err(Error::MissingServerFeature {
+                feature: "push-cert",
+                description: "sign the push",
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Decode the packet lines that were sent in side-band mode up to the first flush packet.
+    fn packet_lines(mut data: &[u8]) -> Result<Vec<&BStr>, Error> {
+        let mut lines = Vec::new();
+        while !data.is_empty() {
+            match decode::streaming(data)? {
+                decode::Stream::Complete { line, bytes_consumed } => {
+                    match line {
+                        PacketLineRef::Data(line) => lines.push(line.strip_suffix(b"\n").unwrap_or(line).as_bstr()),
+                        PacketLineRef::Flush => return Ok(lines),
+                        PacketLineRef::Delimiter | PacketLineRef::ResponseEnd => {}
+                    }
+                    data = &data[bytes_consumed..];
+                }
+                decode::Stream::Incomplete { .. } => break,
+            }
+        }
+        Err(Error::UnexpectedEndOfReport)
+    }
+
+    #[cfg(feature = "blocking-client")]
+    fn copy(pack: &mut PackData<'_>, out: &mut impl std::io::Write) -> std::io::Result<u64> {
+        std::io::copy(pack, out)
+    }
+
+    #[cfg(feature = "async-client")]
+    async fn copy(pack: &mut PackData<'_>, out: &mut (impl futures_io::AsyncWrite + Unpin)) -> std::io::Result<u64> {
+        futures_lite::io::copy(pack, out).await
+    }
+}
@@ -0,0 +1,152 @@
+use bstr::{BStr, BString, ByteSlice};
+
+/// The status of a single reference as reported by the server after a push.
+#[derive(PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RefStatus {
+    /// The name of the reference as it was sent to the server, like `refs/heads/main` or `refs/for/main`.
+    pub name: BString,
+    /// The reason for the reference not being updated, or `None` if it was updated successfully.
+    pub error: Option<BString>,
+    /// The name of the reference that was actually updated, if it differs from `name`.
+    ///
+    /// This is only set with `report-status-v2`, typically when a `proc-receive` hook rewrote the update.
+    pub refname: Option<BString>,
+    /// The previous value of the reference that was actually updated, if provided with `report-status-v2`.
+    pub old_id: Option<gix_hash::ObjectId>,
+    /// The new value of the reference that was actually updated, if provided with `report-status-v2`.
+    pub new_id: Option<gix_hash::ObjectId>,
+    /// If `true`, the update was forced, as reported with `report-status-v2`.
+    pub forced_update: bool,
+}
+
+/// The parsed `report-status` or `report-status-v2` response of a server after a push.
+#[derive(Default, PartialEq, Eq, Debug, Hash, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Report {
+    /// The reason the server couldn't unpack the pack we sent, or `None` if it was unpacked successfully.
+    pub unpack_error: Option<BString>,
+    /// The status of each reference, in the order they were reported.
+    ///
+    /// Note that with `report-status-v2`, a reference may be reported more than once.
+    pub refs: Vec<RefStatus>,
+}
+
+/// The error returned by [`Report::from_lines()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Expected the report to start with an 'unpack' line, got {line:?}")]
+    MissingUnpack { line: Option<BString> },
+    #[error("Could not parse report line {line:?}")]
+    UnknownLine { line: BString },
+    #[error("The report option {line:?} wasn't preceded by an 'ok' line")]
+    OptionWithoutRef { line: BString },
+    #[error("The object id in report option {line:?} could not be decoded")]
+    InvalidId {
+        line: BString,
+        source: gix_hash::decode::Error,
+    },
+}
+
+impl Report {
+    /// Parse the report from its `lines` without their trailing newline, as sent by the server after the pack was received.
+    ///
+    /// Both `report-status` and `report-status-v2` are supported, the latter adding `option` lines.
+    pub fn from_lines<'a>(lines: impl IntoIterator<Item = &'a BStr>) -> Result<Self, Error> {
+        let mut lines = lines.into_iter();
+        let unpack_error = match lines.next() {
+            Some(line) if line == "unpack ok" => None,
+            Some(line) => match line.strip_prefix(b"unpack ") {
+                Some(error) => Some(error.as_bstr().to_owned()),
+                None => {
+                    return Err(Error::MissingUnpack {
+                        line: Some(line.into()),
+                    })
+                }
+            },
+            None => return Err(Error::MissingUnpack { line: None }),
+        };
+        let mut refs = Vec::<RefStatus>::new();
+        for line in lines {
+            let unknown_line = || Error::UnknownLine { line: line.into() };
+            if let Some(name) = line.strip_prefix(b"ok ") {
+                refs.push(RefStatus::new(name.as_bstr(), None));
+            } else if let Some(rest) = line.strip_prefix(b"ng ") {
+                let (name, error) = rest.split_once_str(b" ").ok_or_else(unknown_line)?;
+                refs.push(RefStatus::new(name.as_bstr(), Some(error.as_bstr())));
+            } else if let Some(option) = line.strip_prefix(b"option ") {
+                let status = refs
+                    .last_mut()
+                    .filter(|status| status.error.is_none())
+                    .ok_or_else(|| Error::OptionWithoutRef { line: line.into() })?;
+                let (key, value) = option.split_once_str(b" ").unwrap_or((option, b""));
+                let parse_id = || {
+                    gix_hash::ObjectId::from_hex(value).map_err(|err| Error::InvalidId {
+                        line: line.into(),
+                        source: err,
+                    })
+                };
+                match key {
+                    b"refname" => status.refname = Some(value.into()),
+                    b"old-oid" => status.old_id = Some(parse_id()?),
+                    b"new-oid" => status.new_id = Some(parse_id()?),
+                    b"forced-update" => status.forced_update = true,
+                    _ => return Err(unknown_line()),
+                }
+            } else {
+                return Err(unknown_line());
+            }
+        }
+        Ok(Report { unpack_error, refs })
+    }
+
+    /// Produce the lines of this report without their trailing newline, as sent by a server after receiving the pack.
+    ///
+    /// It's the inverse of [`from_lines()`](Self::from_lines()), with `option` lines only produced if needed.
+    pub fn to_lines(&self) -> Vec<BString> {
+        let mut lines = vec![match &self.unpack_error {
+            None => BString::from("unpack ok"),
+            Some(error) => format!("unpack {error}").into(),
+        }];
+        for status in &self.refs {
+            let Some(error) = &status.error else {
+                lines.push(format!("ok {}", status.name).into());
+                if let Some(refname) = &status.refname {
+                    lines.push(format!("option refname {refname}").into());
+                }
+                if let Some(id) = status.old_id {
+                    lines.push(format!("option old-oid {id}").into());
+                }
+                if let Some(id) = status.new_id {
+                    lines.push(format!("option new-oid {id}").into());
+                }
+                if status.forced_update {
+                    lines.push("option forced-update".into());
+                }
+                continue;
+            };
+            lines.push(format!("ng {} {error}", status.name).into());
+        }
+        lines
+    }
+
+    /// Return `true` if the pack was unpacked and all references were updated successfully.
+    pub fn is_success(&self) -> bool {
+        self.unpack_error.is_none() && self.refs.iter().all(|status| status.error.is_none())
+    }
+}
+
+impl RefStatus {
+    /// Create the status of the reference `name`, which failed to be updated if there is an `error`.
+    pub fn new(name: &BStr, error: Option<&BStr>) -> Self {
+        RefStatus {
+            name: name.to_owned(),
+            error: error.map(ToOwned::to_owned),
+            refname: None,
+            old_id: None,
+            new_id: None,
+            forced_update: false,
+        }
+    }
+}
@@ -0,0 +1,418 @@
+use bstr::{BString, ByteSlice};
+use gix_features::progress;
+use gix_protocol::{
+    handshake,
+    push::{self, report::RefStatus, Report, Update},
+};
+use gix_transport::{client::git::ConnectMode, Protocol, Service};
+
+use crate::fetch::{helper_unused, oid, transport};
+
+mod report {
+    use bstr::ByteSlice;
+    use gix_protocol::push::{report::RefStatus, Report};
+
+    use crate::fetch::oid;
+
+    fn parse(input: &str) -> Result<Report, gix_protocol::push::report::Error> {
+        Report::from_lines(input.lines().map(|line| line.as_bytes().as_bstr()))
+    }
+
+    #[test]
+    fn successful_and_rejected_updates() -> crate::Result {
+        let report = parse("unpack ok\nok refs/heads/main\nng refs/heads/other non-fast-forward")?;
+        assert_eq!(report.unpack_error, None);
+        assert_eq!(
+            report.refs,
+            vec![
+                RefStatus {
+                    name: "refs/heads/main".into(),
+                    error: None,
+                    refname: None,
+                    old_id: None,
+                    new_id: None,
+                    forced_update: false,
+                },
+                RefStatus {
+                    name: "refs/heads/other".into(),
+                    error: Some("non-fast-forward".into()),
+                    refname: None,
+                    old_id: None,
+                    new_id: None,
+                    forced_update: false,
+                }
+            ]
+        );
+        assert!(!report.is_success());
+        Ok(())
+    }
+
+    #[test]
+    fn unpack_failure() -> crate::Result {
+        let report = parse("unpack index-pack abnormal exit\nng refs/heads/main unpacker error")?;
+        assert_eq!(
+            report.unpack_error.as_ref().map(|e| e.as_bstr()),
+            Some("index-pack abnormal exit".into())
+        );
+        assert_eq!(report.refs.len(), 1);
+        assert!(!report.is_success());
+        Ok(())
+    }
+
+    #[test]
+    fn v2_options_apply_to_the_previous_update() -> crate::Result {
+        let old = "808e50d724f604f69ab93c6da2919c014667bedb";
+        let new = "31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7";
+        let report = parse(&format!(
+            "unpack ok\nok refs/for/main\noption refname refs/changes/1\noption old-oid {old}\noption new-oid {new}\noption forced-update\nok refs/heads/main"
+        ))?;
+        assert_eq!(
+            report.refs[0],
+            RefStatus {
+                name: "refs/for/main".into(),
+                error: None,
+                refname: Some("refs/changes/1".into()),
+                old_id: Some(oid(old)),
+                new_id: Some(oid(new)),
+                forced_update: true,
+            }
+        );
+        assert_eq!(report.refs[1].refname, None, "options don't affect other updates");
+        assert!(report.is_success());
+        Ok(())
+    }
+
+    #[test]
+    fn lines_round_trip() -> crate::Result {
+        let old = "808e50d724f604f69ab93c6da2919c014667bedb";
+        for input in [
+            "unpack ok\nok refs/heads/main\nng refs/heads/other non-fast-forward".to_string(),
+            "unpack index-pack abnormal exit\nng refs/heads/main unpacker error".into(),
+            format!("unpack ok\nok refs/for/main\noption refname refs/changes/1\noption old-oid {old}\noption forced-update"),
+        ] {
+            let lines = parse(&input)?.to_lines();
+            assert_eq!(lines.join(&b'\n').as_bstr(), input);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_reports() {
+        assert!(parse("").is_err(), "the unpack line is mandatory");
+        assert!(parse("ok refs/heads/main").is_err(), "the unpack line must come first");
+        assert!(parse("unpack ok\nwhat refs/heads/main").is_err());
+        assert!(
+            parse("unpack ok\nng refs/heads/main reason\noption forced-update").is_err(),
+            "options can only follow successful updates"
+        );
+        assert!(parse("unpack ok\nok refs/heads/main\noption old-oid 1234").is_err());
+    }
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn updates_with_pack_and_side_band_report() -> crate::Result {
+    let mut transport = transport(Vec::new(), "v1/push.response", Protocol::V1, ConnectMode::Daemon);
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    assert_eq!(outcome.refs.as_ref().map(Vec::len), Some(2));
+    assert!(outcome.capabilities.contains("report-status-v2"));
+
+    let advertised = outcome.refs.as_deref().expect("refs in V1");
+    let updates = [
+        Update::from_advertised(
+            "refs/heads/main",
+            oid("31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7"),
+            advertised,
+        ),
+        Update::from_advertised(
+            "refs/heads/other",
+            oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+            advertised,
+        ),
+    ];
+    assert_eq!(updates[0].old, oid("808e50d724f604f69ab93c6da2919c014667bedb"));
+    assert!(
+        Update::from_advertised("refs/heads/new", updates[0].new, advertised)
+            .old
+            .is_null(),
+        "refs that aren't advertised are expected to not exist yet"
+    );
+    let mut pack: &[u8] = b"PACK-bytes-are-sent-verbatim";
+    let report = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &updates,
+        Some(&mut pack),
+        &push::Options {
+            atomic: true,
+            push_options: vec!["ci.skip".into()],
+        },
+        None,
+        "agent",
+        &mut progress::Discard,
+        false,
+    )
+    .await?;
+
+    assert_eq!(
+        report,
+        Report {
+            unpack_error: None,
+            refs: vec![
+                RefStatus {
+                    name: "refs/heads/main".into(),
+                    error: None,
+                    refname: Some("refs/heads/main".into()),
+                    old_id: None,
+                    new_id: None,
+                    forced_update: true,
+                },
+                RefStatus {
+                    name: "refs/heads/other".into(),
+                    error: Some("non-fast-forward".into()),
+                    refname: None,
+                    old_id: None,
+                    new_id: None,
+                    forced_update: false,
+                }
+            ]
+        }
+    );
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        b"0025git-receive-pack does/not/matter\0\
+00bb808e50d724f604f69ab93c6da2919c014667bedb 31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7 refs/heads/main\0report-status-v2 side-band-64k atomic push-options object-format=sha1 agent=git/agent\
+006631d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7 808e50d724f604f69ab93c6da2919c014667bedb refs/heads/other\
+0000000bci.skip0000PACK-bytes-are-sent-verbatim"
+            .as_bstr()
+    );
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn deletions_without_side_band_send_no_pack() -> crate::Result {
+    let mut transport = transport(
+        Vec::new(),
+        "v1/push-no-side-band.response",
+        Protocol::V1,
+        ConnectMode::Daemon,
+    );
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    assert!(matches!(outcome.refs.as_deref(), Some([handshake::Ref::Direct { .. }])));
+
+    let updates = [Update {
+        name: "refs/heads/main".into(),
+        old: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+        new: gix_hash::Kind::Sha1.null(),
+    }];
+    let mut pack: &[u8] = b"never sent";
+    let report = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &updates,
+        Some(&mut pack),
+        &push::Options::default(),
+        None,
+        "agent",
+        &mut progress::Discard,
+        false,
+    )
+    .await?;
+    assert!(report.is_success());
+    assert_eq!(report.refs.len(), 1);
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        b"0025git-receive-pack does/not/matter\0\
+0083808e50d724f604f69ab93c6da2919c014667bedb 0000000000000000000000000000000000000000 refs/heads/main\0report-status agent=git/agent\
+0000"
+            .as_bstr()
+    );
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn signed_updates_are_sent_as_part_of_the_certificate() -> crate::Result {
+    let mut transport = transport(Vec::new(), "v1/push-cert.response", Protocol::V1, ConnectMode::Daemon);
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+
+    let updates = [Update::from_advertised(
+        "refs/heads/main",
+        oid("31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7"),
+        outcome.refs.as_deref().expect("refs in V1"),
+    )];
+    let mut signed_payload = None;
+    let mut signer = |payload: &[u8]| -> Result<BString, push::certificate::Error> {
+        signed_payload = Some(payload.as_bstr().to_owned());
+        Ok("-----BEGIN PGP SIGNATURE-----\n\nc2lnbmF0dXJl\n-----END PGP SIGNATURE-----\n".into())
+    };
+    let mut pack: &[u8] = b"PACK";
+    let report = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &updates,
+        Some(&mut pack),
+        &push::Options::default(),
+        Some(push::Certificate {
+            pusher: "Jane <jane@example.com>".into(),
+            time: gix_date::Time::new(1700000000, 3600),
+            pushee: Some("https://example.com/repo".into()),
+            required: true,
+            signer: &mut signer,
+        }),
+        "agent",
+        &mut progress::Discard,
+        false,
+    )
+    .await?;
+    assert!(report.is_success());
+    assert_eq!(
+        signed_payload.expect("signer was called"),
+        "certificate version 0.1\n\
+pusher Jane <jane@example.com> 1700000000 +0100\n\
+pushee https://example.com/repo\n\
+nonce 1700000000-6d9e7ab4\n\
+\n\
+808e50d724f604f69ab93c6da2919c014667bedb 31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7 refs/heads/main\n",
+        "the nonce advertised by the server is signed along with the updates"
+    );
+    assert_eq!(
+        transport.into_inner().1.as_bstr(),
+        b"0025git-receive-pack does/not/matter\0\
+002bpush-cert\0report-status agent=git/agent\
+001ccertificate version 0.1\n\
+0034pusher Jane <jane@example.com> 1700000000 +0100\n\
+0024pushee https://example.com/repo\n\
+001enonce 1700000000-6d9e7ab4\n\
+0005\n\
+0066808e50d724f604f69ab93c6da2919c014667bedb 31d23cac0b8ed4f78c7dcc1b3ec2ce7c8b7c35b7 refs/heads/main\n\
+0022-----BEGIN PGP SIGNATURE-----\n\
+0005\n\
+0011c2lnbmF0dXJl\n\
+0020-----END PGP SIGNATURE-----\n\
+0012push-cert-end\n\
+0000PACK"
+            .as_bstr(),
+        "updates aren't sent separately when they are part of the certificate"
+    );
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn signing_is_optional_unless_required() -> crate::Result {
+    for required in [false, true] {
+        let mut transport = transport(
+            Vec::new(),
+            "v1/push-no-side-band.response",
+            Protocol::V1,
+            ConnectMode::Daemon,
+        );
+        let outcome = gix_protocol::handshake(
+            &mut transport,
+            Service::ReceivePack,
+            helper_unused,
+            Vec::new(),
+            &mut progress::Discard,
+        )
+        .await?;
+        let mut signer = |_payload: &[u8]| -> Result<BString, push::certificate::Error> {
+            unreachable!("the server doesn't support signed pushes")
+        };
+        let res = gix_protocol::push(
+            &mut transport,
+            &outcome.capabilities,
+            &[Update {
+                name: "refs/heads/main".into(),
+                old: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+                new: gix_hash::Kind::Sha1.null(),
+            }],
+            None,
+            &push::Options::default(),
+            Some(push::Certificate {
+                pusher: "Jane <jane@example.com>".into(),
+                time: gix_date::Time::new(1700000000, 0),
+                pushee: None,
+                required,
+                signer: &mut signer,
+            }),
+            "agent",
+            &mut progress::Discard,
+            false,
+        )
+        .await;
+        if required {
+            assert!(matches!(
+                res.unwrap_err(),
+                push::Error::MissingServerFeature {
+                    feature: "push-cert",
+                    ..
+                }
+            ));
+        } else {
+            assert!(res?.is_success(), "the push is performed without certificate");
+        }
+    }
+    Ok(())
+}
+
+#[maybe_async::test(feature = "blocking-client", async(feature = "async-client", async_std::test))]
+async fn missing_server_features_are_detected_early() -> crate::Result {
+    let mut transport = transport(
+        Vec::new(),
+        "v1/push-no-side-band.response",
+        Protocol::V1,
+        ConnectMode::Daemon,
+    );
+    let outcome = gix_protocol::handshake(
+        &mut transport,
+        Service::ReceivePack,
+        helper_unused,
+        Vec::new(),
+        &mut progress::Discard,
+    )
+    .await?;
+    let err = gix_protocol::push(
+        &mut transport,
+        &outcome.capabilities,
+        &[Update {
+            name: "refs/heads/main".into(),
+            old: gix_hash::Kind::Sha1.null(),
+            new: oid("808e50d724f604f69ab93c6da2919c014667bedb"),
+        }],
+        None,
+        &push::Options {
+            atomic: true,
+            push_options: Vec::new(),
+        },
+        None,
+        "agent",
+        &mut progress::Discard,
+        false,
+    )
+    .await
+    .unwrap_err();
+    assert!(matches!(
+        err,
+        push::Error::MissingServerFeature { feature: "atomic", .. }
+    ));
+    Ok(())
+}
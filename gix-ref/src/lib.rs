@@ -14,6 +14,8 @@
 //!     * one reference maps to a file on disk
 //!   * **packed**
 //!     * references are stored in a single human-readable file, along with their targets if they are symbolic.
+//! * **[reftable][reftable::Stack]**
+//!   * references and their logs are stored in a stack of binary tables, with newer tables overriding older ones.
 //!
 //! ## Feature Flags
 #![cfg_attr(
@@ -25,13 +27,14 @@
 
 use std::borrow::Cow;
 
+pub use gix_fetchhead as fetch_head;
 use gix_hash::{oid, ObjectId};
 pub use gix_object::bstr;
 use gix_object::bstr::{BStr, BString};
 
 #[path = "store/mod.rs"]
 mod store_impl;
-pub use store_impl::{file, packed};
+pub use store_impl::{file, packed, reftable};
 
 mod fullname;
 ///
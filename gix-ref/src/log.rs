@@ -1,5 +1,7 @@
+use std::borrow::Cow;
+
 use gix_hash::ObjectId;
-use gix_object::bstr::BString;
+use gix_object::bstr::{BStr, BString, ByteSlice};
 
 /// A parsed ref log line that can be changed
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
@@ -14,3 +16,25 @@ pub struct Line {
     /// The message providing details about the operation performed in this log line.
     pub message: BString,
 }
+
+/// Normalize `message` for use in a reflog the way git does, by dropping leading and trailing whitespace and by
+/// collapsing all other runs of whitespace, including newlines, into a single space.
+///
+/// This assures that any message can be stored in a single line of a reflog.
+pub fn normalize_message(message: &BStr) -> Cow<'_, BStr> {
+    let is_normalized = !message.starts_with(b" ")
+        && !message.ends_with(b" ")
+        && !message.contains_str("  ")
+        && message.iter().all(|b| !b.is_ascii_whitespace() || *b == b' ');
+    if is_normalized {
+        return Cow::Borrowed(message);
+    }
+    let mut out = BString::default();
+    for word in message.fields_with(|c| c.is_ascii_whitespace()) {
+        if !out.is_empty() {
+            out.push(b' ');
+        }
+        out.extend_from_slice(word);
+    }
+    Cow::Owned(out)
+}
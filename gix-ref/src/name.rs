@@ -81,6 +81,29 @@ impl PartialNameRef {
         buf.extend_from_slice(&self.0);
         FullNameRef::new_unchecked(buf.as_bstr())
     }
+
+    /// Return all full names this name may refer to, in the order in which they are tried when
+    /// [finding](crate::file::Store::find()) it.
+    ///
+    /// If it already looks like a full name, like `refs/heads/main` or `HEAD`, it's the only candidate.
+    /// Otherwise, these are `refs/<name>`, `refs/tags/<name>`, `refs/heads/<name>`, `refs/remotes/<name>` and
+    /// `refs/remotes/<name>/HEAD`, just like described in the [git documentation][git-lookup-docs].
+    ///
+    /// [git-lookup-docs]: https://github.com/git/git/blob/5d5b1473453400224ebb126bf3947e0a3276bdf5/Documentation/revisions.txt#L34-L46
+    pub fn candidates(&self) -> Vec<FullName> {
+        if self.looks_like_full_name() {
+            return vec![FullName(self.0.to_owned())];
+        }
+        let mut buf = BString::default();
+        let mut candidates: Vec<_> = ["", "tags", "heads", "remotes"]
+            .iter()
+            .map(|inbetween| self.construct_full_name_ref(inbetween, &mut buf).to_owned())
+            .collect();
+        let mut remote_head = candidates.last().expect("remote candidate").0.clone();
+        remote_head.push_str("/HEAD");
+        candidates.push(FullName(remote_head));
+        candidates
+    }
 }
 
 impl PartialNameRef {
@@ -0,0 +1,125 @@
+use gix_object::bstr::{BStr, BString, ByteSlice};
+
+use crate::{
+    file::iter::{loose_then_packed, LooseThenPacked, Platform},
+    FullNameRef, Reference,
+};
+
+/// A set of patterns to select references by their full name, to be used with [`Platform::matching()`].
+///
+/// A reference is selected if it matches any of the included patterns, or if there are none, and if it isn't excluded.
+/// Patterns match like they do in `git for-each-ref`, i.e. a name matches if the pattern is a prefix of it that ends
+/// at a `/`, or if the pattern matches it as glob whose wildcards don't match `/`.
+///
+/// Exclusions are evaluated in order with the last matching one winning, and exclusions starting with `!` re-include
+/// the references they match, just like `transfer.hideRefs` does.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct Filter {
+    include: Vec<BString>,
+    exclude: Vec<BString>,
+}
+
+impl Filter {
+    /// Select all references matching `pattern`, like `refs/heads/*` or `refs/tags`.
+    pub fn include(mut self, pattern: impl Into<BString>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Don't select references matching `pattern`, like `refs/heads/tmp/*`, or select them again if it starts with `!`.
+    pub fn exclude(mut self, pattern: impl Into<BString>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Return `true` if the reference `name` is selected by this filter.
+    pub fn is_match(&self, name: &FullNameRef) -> bool {
+        let name = name.as_bstr();
+        (self.include.is_empty() || self.include.iter().any(|pattern| matches(pattern.as_bstr(), name)))
+            && !self
+                .exclude
+                .iter()
+                .rev()
+                .find_map(|pattern| match pattern.strip_prefix(b"!") {
+                    Some(pattern) => matches(pattern.as_bstr(), name).then_some(false),
+                    None => matches(pattern.as_bstr(), name).then_some(true),
+                })
+                .unwrap_or(false)
+    }
+
+    /// Return the directories to iterate to see all references that may be included, sorted and with none of them
+    /// contained in another one, or `None` if all references have to be iterated.
+    fn iteration_prefixes(&self) -> Option<Vec<&BStr>> {
+        let mut prefixes = Vec::with_capacity(self.include.len());
+        for pattern in &self.include {
+            let literal = pattern
+                .find_byteset(b"*?[\\")
+                .map_or(pattern.as_bstr(), |pos| pattern[..pos].as_bstr());
+            let dir = literal.rfind_byte(b'/').map(|pos| literal[..=pos].as_bstr())?;
+            prefixes.push(dir);
+        }
+        if prefixes.is_empty() {
+            return None;
+        }
+        prefixes.sort();
+        prefixes.dedup_by(|dir, parent| dir.starts_with(parent));
+        Some(prefixes)
+    }
+}
+
+fn matches(pattern: &BStr, name: &BStr) -> bool {
+    match name.strip_prefix(pattern.as_bytes()) {
+        Some(rest) if rest.is_empty() || rest[0] == b'/' || pattern.ends_with(b"/") => true,
+        _ => gix_glob::wildmatch(pattern, name, gix_glob::wildmatch::Mode::NO_MATCH_SLASH_LITERAL),
+    }
+}
+
+/// An iterator over all references selected by a [`Filter`], sorted by name, as returned by [`Platform::matching()`].
+///
+/// All errors are returned verbatim as the names of the failing references aren't known.
+pub struct Matching<'p, 's> {
+    filter: Filter,
+    current: Option<LooseThenPacked<'p, 's>>,
+    remaining: std::vec::IntoIter<LooseThenPacked<'p, 's>>,
+}
+
+impl<'p, 's> Iterator for Matching<'p, 's> {
+    type Item = Result<Reference, loose_then_packed::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.current.as_mut()?.next() {
+                Some(Ok(reference)) => {
+                    if self.filter.is_match(reference.name.as_ref()) {
+                        return Some(Ok(reference));
+                    }
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                None => self.current = self.remaining.next(),
+            }
+        }
+    }
+}
+
+impl<'s> Platform<'s> {
+    /// Return an iterator over all references selected by `filter`, loose or packed, sorted by their name.
+    ///
+    /// Only the directories that can contain included references are traversed, and their packed references are
+    /// found by binary search, so that references in other hierarchies, like `refs/pull/` on a server, don't have
+    /// to be read at all.
+    pub fn matching(&self, filter: Filter) -> std::io::Result<Matching<'_, '_>> {
+        let mut iters = match filter.iteration_prefixes() {
+            Some(prefixes) => prefixes
+                .into_iter()
+                .map(|prefix| self.prefixed(&gix_path::from_bstr(prefix)))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![self.all()?],
+        }
+        .into_iter();
+        Ok(Matching {
+            current: iters.next(),
+            remaining: iters,
+            filter,
+        })
+    }
+}
@@ -9,9 +9,18 @@ pub use error::Error;
 use crate::{
     file,
     store_impl::{file::loose, packed},
-    BStr, BString, FullNameRef, PartialName, PartialNameRef, Reference,
+    BStr, FullName, FullNameRef, PartialName, PartialNameRef, Reference,
 };
 
+/// A full name a partial name may refer to, as returned by [`file::Store::find_candidates()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// The full name of the candidate, like `refs/heads/main` for the partial name `main`.
+    pub name: FullName,
+    /// The reference with `name`, or `None` if it doesn't exist.
+    pub reference: Option<Reference>,
+}
+
 /// ### Finding References - notes about precomposed unicode.
 ///
 /// Generally, ref names and the target of symbolic refs are stored as-is if [`Self::precompose_unicode`] is `false`.
@@ -70,11 +79,49 @@ impl file::Store {
         self.find_one_with_verified_input(partial.try_into()?, packed)
     }
 
+    /// Look up all full names `partial` may refer to, in the order in which [`find()`](file::Store::find()) tries them,
+    /// and return each of them along with the reference it names, if it exists.
+    ///
+    /// The first candidate with a reference is the one `find()` would return, and if there is more than one,
+    /// `partial` is ambiguous.
+    pub fn find_candidates<'a, Name, E>(&self, partial: Name) -> Result<Vec<Candidate>, Error>
+    where
+        Name: TryInto<&'a PartialNameRef, Error = E>,
+        Error: From<E>,
+    {
+        let packed = self.assure_packed_refs_uptodate()?;
+        let mut candidates = Vec::new();
+        self.find_candidates_inner(
+            partial.try_into()?,
+            packed.as_ref().map(|b| &***b),
+            &mut |name, reference| {
+                candidates.push(Candidate { name, reference });
+                true
+            },
+        )?;
+        Ok(candidates)
+    }
+
     pub(crate) fn find_one_with_verified_input(
         &self,
         partial_name: &PartialNameRef,
         packed: Option<&packed::Buffer>,
     ) -> Result<Option<Reference>, Error> {
+        let mut out = None;
+        self.find_candidates_inner(partial_name, packed, &mut |_name, reference| {
+            out = reference;
+            out.is_none()
+        })?;
+        Ok(out)
+    }
+
+    /// Call `cb(full_name, reference)` for each candidate of `partial_name` until it returns `false`.
+    fn find_candidates_inner(
+        &self,
+        partial_name: &PartialNameRef,
+        packed: Option<&packed::Buffer>,
+        cb: &mut dyn FnMut(FullName, Option<Reference>) -> bool,
+    ) -> Result<(), Error> {
         fn decompose_if(mut r: Reference, input_changed_to_precomposed: bool) -> Reference {
             if input_changed_to_precomposed {
                 use gix_object::bstr::ByteSlice;
@@ -90,8 +137,7 @@ impl file::Store {
             }
             r
         }
-        let mut buf = BString::default();
-        let mut precomposed_partial_name_storage = packed.filter(|_| self.precompose_unicode).and_then(|_| {
+        let precomposed_partial_name = packed.filter(|_| self.precompose_unicode).and_then(|_| {
             use gix_object::bstr::ByteSlice;
             let precomposed = partial_name.0.to_str().ok()?;
             let precomposed = gix_utils::str::precompose(precomposed.into());
@@ -100,53 +146,42 @@ impl file::Store {
                 Cow::Borrowed(_) => None,
             }
         });
-        let precomposed_partial_name = precomposed_partial_name_storage
+        let lookup_candidates = precomposed_partial_name
             .as_ref()
-            .map(std::convert::AsRef::as_ref);
-        for inbetween in &["", "tags", "heads", "remotes"] {
-            match self.find_inner(inbetween, partial_name, precomposed_partial_name, packed, &mut buf) {
-                Ok(Some(r)) => return Ok(Some(decompose_if(r, precomposed_partial_name.is_some()))),
-                Ok(None) => {
-                    continue;
-                }
-                Err(err) => return Err(err),
-            }
-        }
-        if partial_name.as_bstr() != "HEAD" {
-            if let Some(mut precomposed) = precomposed_partial_name_storage {
-                precomposed = precomposed.join("HEAD".into()).expect("HEAD is valid name");
-                precomposed_partial_name_storage = Some(precomposed);
+            .map_or(partial_name, std::convert::AsRef::as_ref)
+            .candidates();
+        for (name, lookup_name) in partial_name.candidates().into_iter().zip(lookup_candidates) {
+            let reference = self
+                .find_inner(lookup_name.as_ref(), packed)?
+                .map(|r| decompose_if(r, precomposed_partial_name.is_some()));
+            if !cb(name, reference) {
+                break;
             }
-            self.find_inner(
-                "remotes",
-                partial_name
-                    .to_owned()
-                    .join("HEAD".into())
-                    .expect("HEAD is valid name")
-                    .as_ref(),
-                precomposed_partial_name_storage
-                    .as_ref()
-                    .map(std::convert::AsRef::as_ref),
-                None,
-                &mut buf,
-            )
-            .map(|res| res.map(|r| decompose_if(r, precomposed_partial_name_storage.is_some())))
-        } else {
-            Ok(None)
         }
+        Ok(())
     }
 
-    fn find_inner(
-        &self,
-        inbetween: &str,
-        partial_name: &PartialNameRef,
-        precomposed_partial_name: Option<&PartialNameRef>,
-        packed: Option<&packed::Buffer>,
-        path_buf: &mut BString,
-    ) -> Result<Option<Reference>, Error> {
-        let full_name = precomposed_partial_name
-            .unwrap_or(partial_name)
-            .construct_full_name_ref(inbetween, path_buf);
+    fn find_inner(&self, full_name: &FullNameRef, packed: Option<&packed::Buffer>) -> Result<Option<Reference>, Error> {
+        if let Some((stack, name)) = self.reftable_stack_for(full_name)? {
+            let name_backing;
+            let lookup_name = match &self.namespace {
+                Some(namespace) => {
+                    name_backing = namespace.to_owned().into_namespaced_name(name);
+                    name_backing.as_ref()
+                }
+                None => name,
+            };
+            if let Some(mut r) = stack.find(lookup_name)? {
+                if let Some(namespace) = &self.namespace {
+                    r.strip_namespace(namespace);
+                }
+                return Ok(Some(r));
+            }
+            // Only pseudo-refs like `FETCH_HEAD` are stored in files next to reftables.
+            if name.as_bstr() == "HEAD" || name.as_bstr().starts_with(b"refs/") {
+                return Ok(None);
+            }
+        }
         let content_buf = self.ref_contents(full_name).map_err(|err| Error::ReadFileContents {
             source: err,
             path: self.reference_path(full_name),
@@ -378,6 +413,8 @@ mod error {
         PackedRef(#[from] packed::find::Error),
         #[error("Could not open the packed refs buffer when trying to find references.")]
         PackedOpen(#[from] packed::buffer::open::Error),
+        #[error("Could not read the reftable stack when trying to find references")]
+        Reftable(#[from] crate::store_impl::reftable::stack::Error),
     }
 
     impl From<Infallible> for Error {
@@ -0,0 +1,44 @@
+use crate::{file, FullName, Reference, Target};
+
+/// The error returned by [`file::ReferenceExt::follow_chain()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Find(#[from] file::find::Error),
+    #[error("Aborting due to reference cycle at {name:?}")]
+    Cycle { name: FullName },
+    #[error("Refusing to follow more than {max_depth} levels of indirection")]
+    DepthLimitExceeded { max_depth: usize },
+}
+
+/// The outcome of [`file::ReferenceExt::follow_chain()`], describing each reference visited when following symbolic references.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// All references in the order they were visited, starting with the reference the chain was followed from.
+    ///
+    /// All but the last one are symbolic references, each pointing to the one after it.
+    pub hops: Vec<Reference>,
+    /// The name of the reference the last hop points to if it doesn't exist, like an unborn branch `HEAD` points to.
+    pub missing: Option<FullName>,
+}
+
+impl Outcome {
+    /// Return the last reference that exists in the chain.
+    pub fn last(&self) -> &Reference {
+        self.hops.last().expect("the first hop is always present")
+    }
+
+    /// Return the object the chain ultimately points to, or `None` if its end is missing.
+    pub fn id(&self) -> Option<&gix_hash::oid> {
+        match &self.last().target {
+            Target::Peeled(id) => Some(id),
+            Target::Symbolic(_) => None,
+        }
+    }
+
+    /// Return the amount of symbolic references that were followed.
+    pub fn depth(&self) -> usize {
+        self.hops.len() - 1 + usize::from(self.missing.is_some())
+    }
+}
@@ -0,0 +1,103 @@
+use std::io::Write;
+
+use gix_hash::oid;
+use gix_object::bstr::ByteSlice;
+
+use crate::{file, store_impl::file::log::LineRef, FullNameRef};
+
+/// The error returned by [`file::Store::expire_reflog()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The lock for the reflog of {full_name:?} could not be obtained")]
+    LockAcquire {
+        source: gix_lock::acquire::Error,
+        full_name: crate::FullName,
+    },
+    #[error("The reflog could not be read or written")]
+    Io(#[from] std::io::Error),
+    #[error("The reflog could not be committed")]
+    Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+}
+
+/// Options for use in [`file::Store::expire_reflog()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Remove all entries that were created before this time, or keep them if `None`.
+    ///
+    /// This corresponds to `gc.reflogExpire`.
+    pub expire: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// Remove all entries that were created before this time if one of their objects isn't reachable anymore,
+    /// or keep them if `None`.
+    ///
+    /// This corresponds to `gc.reflogExpireUnreachable`.
+    pub expire_unreachable: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// How to deal with the reflog lock if it's already held.
+    pub lock_fail_mode: gix_lock::acquire::Fail,
+}
+
+/// The outcome of [`file::Store::expire_reflog()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of entries that were kept.
+    pub kept: usize,
+    /// The amount of entries that were removed.
+    pub expired: usize,
+}
+
+impl file::Store {
+    /// Rewrite the reflog of the reference `name` under lock to remove all entries that expired according to `options`,
+    /// using `is_reachable(id)` to determine if an object that was recorded in an entry is still reachable, which is only
+    /// called for entries that are older than [`Options::expire_unreachable`]. Null ids are always considered reachable.
+    ///
+    /// Lines that can't be parsed are kept as is. If there is no reflog, nothing is done.
+    pub fn expire_reflog(
+        &self,
+        name: &FullNameRef,
+        options: Options,
+        is_reachable: &mut dyn FnMut(&oid) -> bool,
+    ) -> Result<Outcome, Error> {
+        let path = self.reflog_path(name);
+        if !path.is_file() {
+            return Ok(Outcome::default());
+        }
+        let mut lock =
+            gix_lock::File::acquire_to_update_resource(&path, options.lock_fail_mode, None).map_err(|source| {
+                Error::LockAcquire {
+                    source,
+                    full_name: name.to_owned(),
+                }
+            })?;
+        let log = match std::fs::read(&path) {
+            Ok(log) => log,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Outcome::default()),
+            Err(err) => return Err(err.into()),
+        };
+
+        let mut out = Outcome::default();
+        for line in log.lines_with_terminator() {
+            let is_expired = match LineRef::from_bytes(line.trim_end_with(|c| c == '\n')) {
+                Ok(entry) => {
+                    let time = entry.signature.time.seconds;
+                    matches!(options.expire, Some(expire) if time < expire)
+                        || (matches!(options.expire_unreachable, Some(expire) if time < expire)
+                            && [entry.previous_oid(), entry.new_oid()]
+                                .iter()
+                                .any(|id| !id.is_null() && !is_reachable(id)))
+                }
+                Err(_) => false,
+            };
+            if is_expired {
+                out.expired += 1;
+            } else {
+                out.kept += 1;
+                lock.write_all(line)?;
+            }
+        }
+        if out.expired == 0 {
+            return Ok(out);
+        }
+        lock.commit()?;
+        Ok(out)
+    }
+}
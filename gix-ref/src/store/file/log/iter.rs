@@ -91,7 +91,7 @@ pub struct Platform<'a, 's> {
 
 impl<'a, 's> Platform<'a, 's> {
     /// Return a forward iterator over all log-lines, most recent to oldest.
-    pub fn rev(&mut self) -> std::io::Result<Option<log::iter::Reverse<'_, std::fs::File>>> {
+    pub fn rev(&mut self) -> std::io::Result<Option<log::iter::Reverse<'_, ReverseSource>>> {
         self.buf.clear();
         self.buf.resize(512, 0);
         self.store
@@ -106,6 +106,33 @@ impl<'a, 's> Platform<'a, 's> {
     }
 }
 
+/// The data a reflog is read from by a [`Reverse`] iterator.
+#[derive(Debug)]
+pub enum ReverseSource {
+    /// The file of a loose reflog.
+    File(std::fs::File),
+    /// The serialized lines of a reflog read from reftables.
+    Buffer(std::io::Cursor<Vec<u8>>),
+}
+
+impl std::io::Read for ReverseSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            ReverseSource::File(file) => file.read(buf),
+            ReverseSource::Buffer(buffer) => buffer.read(buf),
+        }
+    }
+}
+
+impl std::io::Seek for ReverseSource {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        match self {
+            ReverseSource::File(file) => file.seek(pos),
+            ReverseSource::Buffer(buffer) => buffer.seek(pos),
+        }
+    }
+}
+
 /// An iterator yielding parsed lines in a file in reverse, most recent to oldest.
 pub struct Reverse<'a, F> {
     buf: &'a mut [u8],
@@ -7,6 +7,10 @@ pub use super::loose::reflog::{create_or_update, Error};
 pub mod iter;
 mod line;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod expire;
+
 /// A parsed ref log line.
 #[derive(PartialEq, Eq, Debug, Hash, Ord, PartialOrd, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -7,6 +7,7 @@ pub(crate) fn must_be_io_err(err: loose::reflog::Error) -> std::io::Error {
     match err {
         loose::reflog::Error::Io(err) => err,
         loose::reflog::Error::RefnameValidation(_) => unreachable!("we are called from a valid ref"),
+        loose::reflog::Error::Reftable(err) => std::io::Error::new(std::io::ErrorKind::Other, err),
     }
 }
 
@@ -29,7 +30,7 @@ impl Reference {
         &self,
         store: &file::Store,
         buf: &'b mut [u8],
-    ) -> std::io::Result<Option<log::iter::Reverse<'b, std::fs::File>>> {
+    ) -> std::io::Result<Option<log::iter::Reverse<'b, log::iter::ReverseSource>>> {
         store.reflog_iter_rev(self.name.as_ref(), buf).map_err(must_be_io_err)
     }
 
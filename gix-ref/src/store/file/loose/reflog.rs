@@ -16,7 +16,13 @@ impl file::Store {
         Name: TryInto<&'a FullNameRef, Error = E>,
         crate::name::Error: From<E>,
     {
-        Ok(self.reflog_path(name.try_into()?).is_file())
+        let name = name.try_into()?;
+        if self.is_reftable() {
+            return Ok(self
+                .reftable_reflog(name)
+                .is_ok_and(|lines| lines.is_some_and(|lines| !lines.is_empty())));
+        }
+        Ok(self.reflog_path(name).is_file())
     }
 
     /// Return a reflog reverse iterator for the given fully qualified `name`, reading chunks from the back into the fixed buffer `buf`.
@@ -27,18 +33,27 @@ impl file::Store {
         &self,
         name: Name,
         buf: &'b mut [u8],
-    ) -> Result<Option<log::iter::Reverse<'b, std::fs::File>>, Error>
+    ) -> Result<Option<log::iter::Reverse<'b, log::iter::ReverseSource>>, Error>
     where
         Name: TryInto<&'a FullNameRef, Error = E>,
         crate::name::Error: From<E>,
     {
         let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+        if let Some(lines) = self.reftable_reflog(name)? {
+            if lines.is_empty() {
+                return Ok(None);
+            }
+            let mut log = Vec::new();
+            file::reftable::write_reflog(lines, &mut log)?;
+            let source = log::iter::ReverseSource::Buffer(std::io::Cursor::new(log));
+            return Ok(Some(log::iter::reverse(source, buf)?));
+        }
         let path = self.reflog_path(name);
         if path.is_dir() {
             return Ok(None);
         }
         match std::fs::File::open(&path) {
-            Ok(file) => Ok(Some(log::iter::reverse(file, buf)?)),
+            Ok(file) => Ok(Some(log::iter::reverse(log::iter::ReverseSource::File(file), buf)?)),
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
             Err(err) => Err(err.into()),
         }
@@ -58,6 +73,14 @@ impl file::Store {
         crate::name::Error: From<E>,
     {
         let name: &FullNameRef = name.try_into().map_err(|err| Error::RefnameValidation(err.into()))?;
+        if let Some(lines) = self.reftable_reflog(name)? {
+            if lines.is_empty() {
+                return Ok(None);
+            }
+            buf.clear();
+            file::reftable::write_reflog(lines, buf)?;
+            return Ok(Some(log::iter::forward(buf)));
+        }
         let path = self.reflog_path(name);
         match std::fs::File::open(&path) {
             Ok(mut file) => {
@@ -153,6 +176,7 @@ pub mod create_or_update {
 
                     if let Some(mut file) = file_for_appending {
                         let committer = committer.ok_or(Error::MissingCommitter)?;
+                        let message = crate::log::normalize_message(message);
                         write!(file, "{} {} ", previous_oid.unwrap_or_else(|| new.kind().null()), new)
                             .and_then(|_| committer.write_to(&mut file))
                             .and_then(|_| {
@@ -240,6 +264,8 @@ mod error {
         RefnameValidation(#[from] crate::name::Error),
         #[error("The reflog file could not read")]
         Io(#[from] std::io::Error),
+        #[error("The reflog could not be read from the reftable stack")]
+        Reftable(#[from] crate::store_impl::reftable::stack::Error),
     }
 }
 pub use error::Error;
@@ -151,3 +151,41 @@ fn missing_reflog_creates_it_even_if_similarly_named_empty_dir_exists_and_append
     }
     Ok(())
 }
+
+#[test]
+fn messages_are_normalized_to_a_single_line() -> Result {
+    let (_keep, store) = empty_store(WriteReflog::Normal)?;
+    let full_name: &FullNameRef = "refs/heads/main".try_into()?;
+    let new = hex_to_id("28ce6a8b26aa170e1de65536fe8abe1832bd3242");
+    let committer = Signature {
+        name: "committer".into(),
+        email: "committer@example.com".into(),
+        time: Time {
+            seconds: 1234,
+            offset: 1800,
+            sign: Sign::Plus,
+        },
+    };
+    store.reflog_create_or_append(
+        full_name,
+        None,
+        &new,
+        committer.to_ref().into(),
+        b"  commit: subject\n\nbody \t with  spaces\n".as_bstr(),
+        false,
+    )?;
+
+    let mut buf = Vec::new();
+    let lines = reflog_lines(&store, "refs/heads/main", &mut buf)?;
+    assert_eq!(lines.len(), 1, "newlines don't break the log");
+    assert_eq!(lines[0].message, "commit: subject body with spaces");
+
+    assert!(
+        matches!(
+            crate::log::normalize_message(b"already normal".as_bstr()),
+            Cow::Borrowed(_)
+        ),
+        "normalized messages aren't copied"
+    );
+    Ok(())
+}
@@ -38,6 +38,8 @@ pub struct Store {
     /// It's updated only in one spot, which is prior to reading it based on file stamps.
     /// Doing it like this has the benefit of being able to hand snapshots out to people without blocking others from updating it.
     packed: packed::modifiable::MutableSharedBuffer,
+    /// The reftable stacks to read references from instead of loose references and the packed buffer, if set.
+    reftable: Option<reftable::modifiable::Stacks>,
 }
 
 mod access {
@@ -94,6 +96,7 @@ pub(in crate::store_impl::file) fn path_to_name<'a>(path: impl Into<Cow<'a, Path
     gix_path::to_unix_separators_on_windows(path)
 }
 
+mod filter;
 ///
 #[allow(clippy::empty_docs)]
 pub mod loose;
@@ -102,7 +105,10 @@ mod overlay_iter;
 ///
 #[allow(clippy::empty_docs)]
 pub mod iter {
-    pub use super::overlay_iter::{LooseThenPacked, Platform};
+    pub use super::{
+        filter::{Filter, Matching},
+        overlay_iter::{LooseThenPacked, Platform},
+    };
 
     ///
     #[allow(clippy::empty_docs)]
@@ -119,6 +125,10 @@ pub mod log;
 #[allow(clippy::empty_docs)]
 pub mod find;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod follow_chain;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod transaction;
@@ -127,5 +137,18 @@ pub mod transaction;
 #[allow(clippy::empty_docs)]
 pub mod packed;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod pack_refs;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod watch;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod pseudo_ref;
+
 mod raw_ext;
+mod reftable;
 pub use raw_ext::ReferenceExt;
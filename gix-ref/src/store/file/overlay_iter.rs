@@ -24,6 +24,8 @@ pub struct LooseThenPacked<'p, 's> {
     iter_git_dir: Peekable<SortedLoosePaths>,
     #[allow(dead_code)]
     iter_common_dir: Option<Peekable<SortedLoosePaths>>,
+    /// All references read from reftables, which are used instead of loose and packed references if set.
+    iter_reftable: Option<std::vec::IntoIter<Reference>>,
     buf: Vec<u8>,
 }
 
@@ -149,11 +151,21 @@ impl<'p, 's> Iterator for LooseThenPacked<'p, 's> {
                 None => git_dir.peek().map(|r| (r, IterKind::Git)),
             }
         }
+        if let Some(reftable_iter) = self.iter_reftable.as_mut() {
+            return reftable_iter.next().map(|r| Ok(self.strip_namespace(r)));
+        }
         match self.iter_packed.as_mut() {
-            Some(packed_iter) => match (
-                peek_loose(&mut self.iter_git_dir, self.iter_common_dir.as_mut()),
-                packed_iter.peek(),
-            ) {
+            Some(packed_iter) => match (peek_loose(&mut self.iter_git_dir, self.iter_common_dir.as_mut()), {
+                // Worktree-private references are never packed, as they are looked up in their worktree only.
+                while let Some(Ok(packed)) = packed_iter.peek() {
+                    if matches!(packed.name.category(), Some(cat) if cat.is_worktree_private()) {
+                        packed_iter.next();
+                    } else {
+                        break;
+                    }
+                }
+                packed_iter.peek()
+            }) {
                 (None, None) => None,
                 (None, Some(_)) | (Some(_), Some(Err(_))) => {
                     let res = packed_iter.next().expect("peeked value exists");
@@ -410,6 +422,17 @@ impl file::Store {
         common_dir_info: Option<IterInfo<'_>>,
         packed: Option<&'p packed::Buffer>,
     ) -> std::io::Result<LooseThenPacked<'p, 's>> {
+        let iter_reftable = self
+            .is_reftable()
+            .then(|| {
+                let prefix = git_dir_info
+                    .prefix()
+                    .map_or_else(|| "refs/".into(), |prefix| path_to_name(prefix).into_owned());
+                self.reftable_references(&prefix)
+            })
+            .transpose()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+            .map(Vec::into_iter);
         Ok(LooseThenPacked {
             git_dir: self.git_dir(),
             common_dir: self.common_dir(),
@@ -426,6 +449,7 @@ impl file::Store {
             },
             iter_git_dir: git_dir_info.into_iter(),
             iter_common_dir: common_dir_info.map(IterInfo::into_iter),
+            iter_reftable,
             buf: Vec::new(),
             namespace: self.namespace.as_ref(),
         })
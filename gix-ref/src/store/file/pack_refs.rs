@@ -0,0 +1,181 @@
+use crate::{
+    store_impl::{file, file::loose, packed},
+    transaction::{Change, LogChange, PreviousValue, RefEdit},
+    FullName, Target,
+};
+
+/// The error returned by [`file::Store::pack_refs()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The packed-refs file could not be opened")]
+    PackedOpen(#[from] packed::buffer::open::Error),
+    #[error("Loose references could not be iterated")]
+    Iter(#[from] std::io::Error),
+    #[error("A loose reference could not be read")]
+    LooseIter(#[from] file::iter::loose_then_packed::Error),
+    #[error("A lock could not be obtained for reference {full_name:?}")]
+    LockAcquire {
+        source: gix_lock::acquire::Error,
+        full_name: FullName,
+    },
+    #[error("The lock for the packed-refs file could not be obtained")]
+    PackedTransactionAcquire(#[from] file::packed::transaction::Error),
+    #[error("Could not prepare writing the packed-refs file")]
+    PackedTransactionPrepare(#[from] packed::transaction::prepare::Error),
+    #[error("Could not write the packed-refs file")]
+    PackedTransactionCommit(#[from] packed::transaction::commit::Error),
+    #[error("The loose reference {full_name:?} could not be deleted")]
+    DeleteReference {
+        source: std::io::Error,
+        full_name: FullName,
+    },
+}
+
+/// Options for use in [`file::Store::pack_refs()`].
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// If `true`, pack all references. Otherwise, only tags and references that are already packed are packed,
+    /// just like `git pack-refs` does without `--all`.
+    pub all: bool,
+    /// If `true`, delete the loose references after packing them.
+    pub prune: bool,
+    /// How to deal with locks of loose references that are already held.
+    pub lock_fail_mode: gix_lock::acquire::Fail,
+    /// How to deal with the lock of the `packed-refs` file if it is already held.
+    pub packed_refs_lock_fail_mode: gix_lock::acquire::Fail,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            all: false,
+            prune: true,
+            lock_fail_mode: gix_lock::acquire::Fail::Immediately,
+            packed_refs_lock_fail_mode: gix_lock::acquire::Fail::Immediately,
+        }
+    }
+}
+
+/// The outcome of [`file::Store::pack_refs()`].
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Outcome {
+    /// The amount of loose references that were written into the `packed-refs` file.
+    pub packed_refs: usize,
+    /// The amount of loose references that were deleted after packing them.
+    pub pruned_refs: usize,
+}
+
+impl file::Store {
+    /// Move all loose references that aren't symbolic and which are shared among all worktrees into the `packed-refs` file,
+    /// while using `objects` to peel them, and delete their loose files if `options.prune` is set.
+    ///
+    /// All loose references to pack are locked first and re-read under their lock so concurrent changes aren't lost,
+    /// with the locks being held until the loose files have been deleted.
+    ///
+    /// Note that namespaces are ignored, this operation always applies to the whole store.
+    pub fn pack_refs(&self, objects: &dyn gix_object::Find, options: Options) -> Result<Outcome, Error> {
+        let packed = self.assure_packed_refs_uptodate()?;
+        let mut candidates = Vec::new();
+        for reference in self.loose_iter()? {
+            // Like git, leave broken references alone.
+            let reference = match reference {
+                Ok(reference) => reference,
+                Err(file::iter::loose_then_packed::Error::ReferenceCreation { .. }) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            let name = reference.name.as_ref();
+            let is_shared = name.as_bstr().starts_with(b"refs/")
+                && !matches!(name.category(), Some(category) if category.is_worktree_private());
+            let is_eligible = options.all
+                || name.category() == Some(crate::Category::Tag)
+                || matches!(&packed, Some(packed) if packed.try_find(name).ok().flatten().is_some());
+            if is_shared && is_eligible && matches!(reference.target, Target::Peeled(_)) {
+                candidates.push(reference.name);
+            }
+        }
+
+        let mut locks = Vec::with_capacity(candidates.len());
+        let mut edits = Vec::with_capacity(candidates.len());
+        for name in candidates {
+            let path = self.common_dir_resolved().join(name.to_path());
+            let lock = gix_lock::Marker::acquire_to_hold_resource(
+                &path,
+                options.lock_fail_mode,
+                Some(self.common_dir_resolved().to_owned()),
+            )
+            .map_err(|source| Error::LockAcquire {
+                source,
+                full_name: name.clone(),
+            })?;
+            // Re-read the reference under lock, it might have changed or disappeared in the meantime.
+            let Some(Target::Peeled(id)) = std::fs::read(&path)
+                .ok()
+                .and_then(|buf| loose::Reference::try_from_path(name.clone(), &buf).ok())
+                .map(|r| r.target)
+            else {
+                continue;
+            };
+            edits.push(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::Any,
+                    new: Target::Peeled(id),
+                },
+                name: name.clone(),
+                deref: false,
+            });
+            locks.push((name, path, lock));
+        }
+        if edits.is_empty() {
+            return Ok(Outcome::default());
+        }
+
+        let packed_refs_lock = gix_lock::File::acquire_to_update_resource(
+            self.packed_refs_path(),
+            options.packed_refs_lock_fail_mode,
+            None,
+        )
+        .map_err(file::packed::transaction::Error::from)?;
+        let packed_refs_transaction = packed::Transaction::new_from_pack_and_lock(
+            self.assure_packed_refs_uptodate()?,
+            packed_refs_lock,
+            self.precompose_unicode,
+            None,
+        );
+        let mut outcome = Outcome {
+            packed_refs: edits.len(),
+            pruned_refs: 0,
+        };
+        packed_refs_transaction
+            .prepare(&mut edits.into_iter(), objects)?
+            .commit()?;
+        self.force_refresh_packed_buffer().ok();
+
+        if options.prune {
+            for (full_name, path, lock) in locks {
+                // Like git, keep `refs/` and its immediate subdirectories.
+                let boundary = self.common_dir_resolved().join(
+                    full_name
+                        .as_bstr()
+                        .splitn(3, |b| *b == b'/')
+                        .take(2)
+                        .map(gix_path::from_byte_slice)
+                        .collect::<std::path::PathBuf>(),
+                );
+                if let Err(err) = std::fs::remove_file(&path) {
+                    if err.kind() != std::io::ErrorKind::NotFound {
+                        return Err(Error::DeleteReference { source: err, full_name });
+                    }
+                } else {
+                    outcome.pruned_refs += 1;
+                }
+                drop(lock);
+                if let Some(parent) = path.parent() {
+                    gix_tempfile::remove_dir::empty_upward_until_boundary(parent, &boundary).ok();
+                }
+            }
+        }
+        Ok(outcome)
+    }
+}
@@ -0,0 +1,156 @@
+use std::{io::Write, path::PathBuf};
+
+use gix_hash::ObjectId;
+use gix_object::bstr::{BString, ByteSlice};
+
+use crate::{fetch_head, file};
+
+/// The error returned by the methods to read and write pseudo-references of [`file::Store`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The pseudo-reference at {path:?} could not be read or written")]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error(transparent)]
+    DecodeFetchHead(#[from] fetch_head::decode::Error),
+    #[error("{name} line {line_number} is not a valid object id: {line:?}")]
+    DecodeObjectId {
+        name: &'static str,
+        line: BString,
+        line_number: usize,
+    },
+    #[error("The lock for {path:?} could not be obtained")]
+    LockAcquire {
+        source: gix_lock::acquire::Error,
+        path: PathBuf,
+    },
+    #[error("The pseudo-reference could not be committed")]
+    Commit(#[from] gix_lock::commit::Error<gix_lock::File>),
+}
+
+/// The name of the file recording what was fetched last.
+const FETCH_HEAD: &str = "FETCH_HEAD";
+/// The name of the file recording the commits to merge into `HEAD` while a merge is in progress.
+const MERGE_HEAD: &str = "MERGE_HEAD";
+/// The name of the file recording the commit that is picked while a cherry-pick is in progress.
+const CHERRY_PICK_HEAD: &str = "CHERRY_PICK_HEAD";
+
+/// Reading and writing of pseudo-references which record the state of fetches, merges and cherry-picks, and which live
+/// in the `git_dir()` of the current worktree.
+///
+/// Pseudo-references that hold a single object can also be read with [`find()`](file::Store::find()) as the store
+/// treats them like regular references.
+impl file::Store {
+    /// Read and parse all entries of the `FETCH_HEAD` file, or return `None` if it doesn't exist.
+    pub fn fetch_head(&self) -> Result<Option<Vec<fetch_head::Entry>>, Error> {
+        self.read_pseudo_ref(FETCH_HEAD)?
+            .map(|data| fetch_head::decode(&data).map_err(Into::into))
+            .transpose()
+    }
+
+    /// Write `entries` to the `FETCH_HEAD` file under lock, replacing all existing entries, or adding to them if `append`
+    /// is `true` just like `git fetch --append` does.
+    pub fn write_fetch_head(
+        &self,
+        entries: &[fetch_head::Entry],
+        append: bool,
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<(), Error> {
+        let mut buf = Vec::new();
+        for entry in entries {
+            entry.write_to(&mut buf).expect("writing to memory can't fail");
+        }
+        self.write_pseudo_ref(FETCH_HEAD, append, &buf, lock_fail_mode)
+    }
+
+    /// Read the ids of all commits listed in the `MERGE_HEAD` file in order, or return `None` if no merge is in progress.
+    pub fn merge_heads(&self) -> Result<Option<Vec<ObjectId>>, Error> {
+        self.read_pseudo_ref(MERGE_HEAD)?
+            .map(|data| decode_object_ids(MERGE_HEAD, &data))
+            .transpose()
+    }
+
+    /// Write `ids` to the `MERGE_HEAD` file under lock, one per line, to record the commits to merge into `HEAD`.
+    pub fn write_merge_heads(&self, ids: &[ObjectId], lock_fail_mode: gix_lock::acquire::Fail) -> Result<(), Error> {
+        let buf: String = ids.iter().map(|id| format!("{id}\n")).collect();
+        self.write_pseudo_ref(MERGE_HEAD, false, buf.as_bytes(), lock_fail_mode)
+    }
+
+    /// Read the id of the commit in the `CHERRY_PICK_HEAD` file, or return `None` if no cherry-pick is in progress.
+    pub fn cherry_pick_head(&self) -> Result<Option<ObjectId>, Error> {
+        Ok(self
+            .read_pseudo_ref(CHERRY_PICK_HEAD)?
+            .map(|data| decode_object_ids(CHERRY_PICK_HEAD, &data))
+            .transpose()?
+            .and_then(|ids| ids.into_iter().next()))
+    }
+
+    /// Write `id` to the `CHERRY_PICK_HEAD` file under lock to record the commit that is currently picked.
+    pub fn write_cherry_pick_head(
+        &self,
+        id: &gix_hash::oid,
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<(), Error> {
+        self.write_pseudo_ref(CHERRY_PICK_HEAD, false, format!("{id}\n").as_bytes(), lock_fail_mode)
+    }
+
+    /// Delete the pseudo-reference file `name`, like `MERGE_HEAD`, `CHERRY_PICK_HEAD` or `FETCH_HEAD`, returning `true`
+    /// if it existed.
+    pub fn remove_pseudo_ref(&self, name: &str) -> Result<bool, Error> {
+        let path = self.git_dir.join(name);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(source) => Err(Error::Io { source, path }),
+        }
+    }
+
+    fn read_pseudo_ref(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let path = self.git_dir.join(name);
+        match std::fs::read(&path) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(source) => Err(Error::Io { source, path }),
+        }
+    }
+
+    /// Write `data` to the pseudo-reference file `name` under lock, after its current content if `append` is `true`.
+    fn write_pseudo_ref(
+        &self,
+        name: &str,
+        append: bool,
+        data: &[u8],
+        lock_fail_mode: gix_lock::acquire::Fail,
+    ) -> Result<(), Error> {
+        let path = self.git_dir.join(name);
+        let mut lock = gix_lock::File::acquire_to_update_resource(&path, lock_fail_mode, None).map_err(|source| {
+            Error::LockAcquire {
+                source,
+                path: path.clone(),
+            }
+        })?;
+        let existing = if append { self.read_pseudo_ref(name)? } else { None };
+        existing
+            .iter()
+            .try_for_each(|existing| lock.write_all(existing))
+            .and_then(|_| lock.write_all(data))
+            .map_err(|source| Error::Io { source, path })?;
+        lock.commit()?;
+        Ok(())
+    }
+}
+
+/// Parse one object id per non-empty line of `data`, which was read from the pseudo-reference `name`.
+fn decode_object_ids(name: &'static str, data: &[u8]) -> Result<Vec<ObjectId>, Error> {
+    data.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| {
+            ObjectId::from_hex(line).map_err(|_| Error::DecodeObjectId {
+                name,
+                line: line.into(),
+                line_number: idx + 1,
+            })
+        })
+        .collect()
+}
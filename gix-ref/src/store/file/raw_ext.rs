@@ -53,6 +53,17 @@ pub trait ReferenceExt: Sealed {
         store: &file::Store,
         packed: Option<&packed::Buffer>,
     ) -> Option<Result<Reference, file::find::existing::Error>>;
+
+    /// Follow this reference through all symbolic references it might point to, up to `max_depth` levels of indirection,
+    /// and return every reference that was visited on the way, starting with this one.
+    ///
+    /// Unlike [`ReferenceExt::peel_to_id_in_place()`], a missing reference at the end of the chain isn't an error,
+    /// but is recorded in the outcome instead, and objects aren't peeled.
+    fn follow_chain(
+        &self,
+        store: &file::Store,
+        max_depth: usize,
+    ) -> Result<file::follow_chain::Outcome, file::follow_chain::Error>;
 }
 
 impl ReferenceExt for Reference {
@@ -142,6 +153,40 @@ impl ReferenceExt for Reference {
         }
     }
 
+    fn follow_chain(
+        &self,
+        store: &file::Store,
+        max_depth: usize,
+    ) -> Result<file::follow_chain::Outcome, file::follow_chain::Error> {
+        let packed = store
+            .assure_packed_refs_uptodate()
+            .map_err(file::find::Error::PackedOpen)?;
+        let mut hops = vec![Reference {
+            name: self.name.clone(),
+            target: self.target.clone(),
+            peeled: None,
+        }];
+        let mut missing = None;
+        while let Target::Symbolic(next_name) = &hops.last().expect("at least one").target {
+            if hops.iter().any(|hop| &hop.name == next_name) {
+                return Err(file::follow_chain::Error::Cycle {
+                    name: next_name.clone(),
+                });
+            }
+            if hops.len() > max_depth {
+                return Err(file::follow_chain::Error::DepthLimitExceeded { max_depth });
+            }
+            match store.try_find_packed(next_name.as_ref(), packed.as_ref().map(|b| &***b))? {
+                Some(next) => hops.push(next),
+                None => {
+                    missing = Some(next_name.clone());
+                    break;
+                }
+            }
+        }
+        Ok(file::follow_chain::Outcome { hops, missing })
+    }
+
     fn follow(&self, store: &file::Store) -> Option<Result<Reference, file::find::existing::Error>> {
         let packed = match store
             .assure_packed_refs_uptodate()
@@ -0,0 +1,160 @@
+use std::path::Path;
+
+use gix_features::threading::OwnShared;
+
+use crate::{
+    store_impl::{file, reftable},
+    FullNameRef, Reference,
+};
+
+/// An up-to-date snapshot of a reftable stack.
+pub(crate) type SharedStackSnapshot = gix_fs::SharedFileSnapshot<reftable::Stack>;
+
+pub(crate) mod modifiable {
+    use gix_features::threading::OwnShared;
+
+    use crate::store_impl::reftable;
+
+    pub(crate) type MutableSharedStack = OwnShared<gix_fs::SharedFileSnapshotMut<reftable::Stack>>;
+
+    /// The cached stacks of the repository, and of the worktree the store is for, if it's a linked worktree.
+    #[derive(Debug, Clone)]
+    pub(crate) struct Stacks {
+        pub common: MutableSharedStack,
+        pub worktree: MutableSharedStack,
+    }
+
+    impl Default for Stacks {
+        fn default() -> Self {
+            Stacks {
+                common: gix_fs::SharedFileSnapshotMut::new().into(),
+                worktree: gix_fs::SharedFileSnapshotMut::new().into(),
+            }
+        }
+    }
+}
+
+/// Reftables
+impl file::Store {
+    /// Read references and reflogs from the reftable stacks in the `reftable` directories of the repository instead of
+    /// from loose references and `packed-refs`, as needed if `extensions.refStorage = reftable` is configured.
+    ///
+    /// Pseudo-refs that aren't stored in reftables, like `FETCH_HEAD`, are still read from files.
+    /// Note that transactions fail once reftables are used, as this store can't write them.
+    pub fn use_reftable(&mut self) {
+        self.reftable = Some(Default::default());
+    }
+
+    /// Return `true` if references are read from reftables.
+    pub fn is_reftable(&self) -> bool {
+        self.reftable.is_some()
+    }
+
+    /// Return an up-to-date snapshot of the reftable stack holding the reference with the full `name`, along with the
+    /// name to look up in the stack, or `None` if reftables aren't used or the stack doesn't exist.
+    pub(crate) fn reftable_stack_for<'a>(
+        &self,
+        name: &'a FullNameRef,
+    ) -> Result<Option<(SharedStackSnapshot, &'a FullNameRef)>, reftable::stack::Error> {
+        // Worktree-private references are always stored in the stack of their worktree, just like reflogs.
+        let is_reflog = true;
+        let (base, name) = self.to_base_dir_and_relative_name(name, is_reflog);
+        Ok(self.reftable_stack_at(&base)?.map(|stack| (stack, name)))
+    }
+
+    /// Return an up-to-date snapshot of the reftable stack of the repository, or `None` if reftables aren't used or
+    /// the stack doesn't exist.
+    pub(crate) fn reftable_common_stack(&self) -> Result<Option<SharedStackSnapshot>, reftable::stack::Error> {
+        self.reftable_stack_at(self.common_dir_resolved())
+    }
+
+    /// Return an up-to-date snapshot of the reftable stack of this linked worktree, or `None` if reftables aren't used,
+    /// if this isn't a linked worktree, or if the stack doesn't exist.
+    pub(crate) fn reftable_worktree_stack(&self) -> Result<Option<SharedStackSnapshot>, reftable::stack::Error> {
+        match self.common_dir {
+            Some(_) => self.reftable_stack_at(&self.git_dir),
+            None => Ok(None),
+        }
+    }
+
+    /// Return all references in the reftables of this store that start with the full, namespaced `prefix`, sorted by name.
+    ///
+    /// Worktree-private references are taken from the stack of this linked worktree, and all others from the stack of
+    /// the repository.
+    pub(crate) fn reftable_references(&self, prefix: &[u8]) -> Result<Vec<Reference>, reftable::stack::Error> {
+        let is_private = |r: &Reference| r.name.category().is_some_and(|cat| cat.is_worktree_private());
+        let mut refs = Vec::new();
+        if let Some(common) = self.reftable_common_stack()? {
+            let is_linked_worktree = self.common_dir.is_some();
+            refs.extend(
+                common
+                    .references()?
+                    .into_iter()
+                    .filter(|r| !(is_linked_worktree && is_private(r))),
+            );
+        }
+        if let Some(worktree) = self.reftable_worktree_stack()? {
+            refs.extend(worktree.references()?.into_iter().filter(is_private));
+            refs.sort_by(|a, b| a.name.cmp(&b.name));
+        }
+        refs.retain(|r| r.name.as_bstr().starts_with(prefix));
+        Ok(refs)
+    }
+
+    /// Return the reflog of the reference with the full `name` from the reftable stack holding it, from the oldest to
+    /// the newest entry, or `None` if reftables aren't used.
+    pub(crate) fn reftable_reflog(
+        &self,
+        name: &FullNameRef,
+    ) -> Result<Option<Vec<crate::log::Line>>, reftable::stack::Error> {
+        if !self.is_reftable() {
+            return Ok(None);
+        }
+        let Some((stack, name)) = self.reftable_stack_for(name)? else {
+            return Ok(Some(Vec::new()));
+        };
+        let lines = match &self.namespace {
+            Some(namespace) => stack.reflog(namespace.to_owned().into_namespaced_name(name).as_ref())?,
+            None => stack.reflog(name)?,
+        };
+        Ok(Some(lines))
+    }
+
+    fn reftable_stack_at(&self, base: &Path) -> Result<Option<SharedStackSnapshot>, reftable::stack::Error> {
+        let Some(stacks) = self.reftable.as_ref() else {
+            return Ok(None);
+        };
+        let stack_dir = base.join("reftable");
+        let list_path = stack_dir.join("tables.list");
+        let open = || match reftable::Stack::at(&stack_dir) {
+            Ok(stack) => Ok(Some(stack)),
+            Err(reftable::stack::Error::Io { source, path })
+                if source.kind() == std::io::ErrorKind::NotFound && path == list_path =>
+            {
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        };
+        let cached = if base == self.common_dir_resolved() {
+            &stacks.common
+        } else if base == self.git_dir {
+            &stacks.worktree
+        } else {
+            // Stacks of other worktrees are rarely needed, so we don't keep them.
+            return Ok(open()?.map(|stack| OwnShared::new(gix_fs::FileSnapshot::new(stack))));
+        };
+        cached.recent_snapshot(|| list_path.metadata().and_then(|m| m.modified()).ok(), open)
+    }
+}
+
+/// Serialize reflog `lines` read from reftables into `out` just like they would be stored in loose reflogs.
+pub(crate) fn write_reflog(lines: Vec<crate::log::Line>, out: &mut Vec<u8>) -> std::io::Result<()> {
+    for mut line in lines {
+        // Messages in reftables end with a newline, which loose reflogs don't store as part of the message.
+        if line.message.last() == Some(&b'\n') {
+            line.message.pop();
+        }
+        line.write_to(out)?;
+    }
+    Ok(())
+}
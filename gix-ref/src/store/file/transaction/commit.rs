@@ -1,4 +1,5 @@
 use crate::{
+    file::{self, ReferenceExt},
     store_impl::file::{transaction::PackedRefs, Transaction},
     transaction::{Change, LogChange, RefEdit, RefLog},
     Target,
@@ -9,15 +10,16 @@ impl<'s, 'p> Transaction<'s, 'p> {
     /// state of the affected refs in the ref store in that instant. Please note that the obtained edits may have been
     /// adjusted to contain more dependent edits or additional information.
     /// `committer` is used in the reflog and only if the reflog is actually written, which is why it is optional. Please note
-    /// that if `None` is passed and the reflog needs to be written, the operation will be aborted late and a few refs may have been
-    /// successfully committed already, making clear the non-atomic nature of multi-file edits.
+    /// that if `None` is passed and the reflog needs to be written, the operation will be aborted before any reference was changed,
+    /// but after reflogs of other references may have been written already.
     ///
-    /// On error the transaction may have been performed partially, depending on the nature of the error, and no attempt to roll back
-    /// partial changes is made.
+    /// As all reflogs are written before the first reference is changed, most errors leave all references untouched. However,
+    /// if moving an updated reference into place fails, the transaction may have been performed partially, and no attempt to
+    /// roll back partial changes is made.
     ///
     /// In this stage, we perform the following operations:
     ///
-    /// * update the ref log
+    /// * update the ref logs
     /// * move updated refs into place
     /// * delete reflogs and empty parent directories
     /// * delete packed refs
@@ -36,63 +38,77 @@ impl<'s, 'p> Transaction<'s, 'p> {
             PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(_)
         );
 
+        // Write all reflogs first so failures, like a missing committer, are detected before any reference was changed.
+        for change in &updates {
+            assert!(!change.update.deref, "Deref mode is turned into splits and turned off");
+            let Change::Update { log, new, expected } = &change.update.change else {
+                continue;
+            };
+            let log_update = match new {
+                Target::Symbolic(new_target) => {
+                    // Special HACK: symrefs have no OID which the reflog needs, unless the ref is new and we can obtain
+                    // a peeled id identified by the expectation of what could be there, as is the case when cloning.
+                    match expected {
+                        PreviousValue::ExistingMustMatch(Target::Peeled(oid)) => {
+                            Some((Some(gix_hash::ObjectId::null(oid.kind())), oid.to_owned(), false))
+                        }
+                        // Otherwise, log the move to the referent's id like git does when switching branches, which is
+                        // only possible if the referent exists.
+                        _ => resolve_to_id(self.store, new_target.as_ref()).map(|new_oid| {
+                            let previous = match expected {
+                                PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
+                                PreviousValue::MustExistAndMatch(Target::Symbolic(name)) => {
+                                    resolve_to_id(self.store, name.as_ref())
+                                }
+                                _ => None,
+                            };
+                            let is_move = !matches!(
+                                expected,
+                                PreviousValue::MustExistAndMatch(Target::Symbolic(name)) if name == new_target
+                            );
+                            (previous, new_oid, is_move)
+                        }),
+                    }
+                }
+                Target::Peeled(new_oid) => {
+                    let previous = match expected {
+                        // Here, this means that the ref already existed, and that it will receive (even transitively)
+                        // the given value
+                        PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
+                        _ => None,
+                    }
+                    .or(change.leaf_referent_previous_oid);
+                    Some((previous, new_oid.to_owned(), false))
+                }
+            };
+            if let Some((previous, new_oid, is_symbolic_move)) = log_update {
+                let do_update = is_symbolic_move || previous != Some(new_oid);
+                if do_update {
+                    self.store.reflog_create_or_append(
+                        change.update.name.as_ref(),
+                        previous,
+                        &new_oid,
+                        committer,
+                        log.message.as_ref(),
+                        log.force_create_reflog,
+                    )?;
+                }
+            }
+        }
+
         // Perform updates first so live commits remain referenced
         for change in &mut updates {
-            assert!(!change.update.deref, "Deref mode is turned into splits and turned off");
             match &change.update.change {
-                // reflog first, then reference
-                Change::Update { log, new, expected } => {
+                Change::Update { log, new, .. } => {
                     let lock = change.lock.take();
-                    let (update_ref, update_reflog) = match log.mode {
-                        RefLog::Only => (false, true),
-                        RefLog::AndReference => (true, true),
-                    };
-                    if update_reflog {
-                        let log_update = match new {
-                            Target::Symbolic(_) => {
-                                // Special HACK: no reflog for symref changes as there is no OID involved which the reflog needs.
-                                // Unless, the ref is new and we can obtain a peeled id
-                                // identified by the expectation of what could be there, as is the case when cloning.
-                                match expected {
-                                    PreviousValue::ExistingMustMatch(Target::Peeled(oid)) => {
-                                        Some((Some(gix_hash::ObjectId::null(oid.kind())), oid))
-                                    }
-                                    _ => None,
-                                }
-                            }
-                            Target::Peeled(new_oid) => {
-                                let previous = match expected {
-                                    // Here, this means that the ref already existed, and that it will receive (even transitively)
-                                    // the given value
-                                    PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(oid.to_owned()),
-                                    _ => None,
-                                }
-                                .or(change.leaf_referent_previous_oid);
-                                Some((previous, new_oid))
-                            }
-                        };
-                        if let Some((previous, new_oid)) = log_update {
-                            let do_update = previous.as_ref().map_or(true, |previous| previous != new_oid);
-                            if do_update {
-                                self.store.reflog_create_or_append(
-                                    change.update.name.as_ref(),
-                                    previous,
-                                    new_oid,
-                                    committer,
-                                    log.message.as_ref(),
-                                    log.force_create_reflog,
-                                )?;
-                            }
-                        }
-                    }
-                    // Don't do anything else while keeping the lock after potentially updating the reflog.
+                    // Don't do anything else while keeping the lock after having updated the reflog.
                     // We delay deletion of the reference and dropping the lock to after the packed-refs were
                     // safely written.
                     if delete_loose_refs && matches!(new, Target::Peeled(_)) {
                         change.lock = lock;
                         continue;
                     }
-                    if update_ref {
+                    if log.mode == RefLog::AndReference {
                         if let Some(Err(err)) = lock.map(gix_lock::Marker::commit) {
                             // TODO: when Kind::IsADirectory becomes stable, use that.
                             let err = if err.instance.resource_path().is_dir() {
@@ -202,3 +218,15 @@ mod error {
 pub use error::Error;
 
 use crate::transaction::PreviousValue;
+
+/// Return the id the reference `name` ultimately points to, or `None` if it or its referents don't exist.
+fn resolve_to_id(store: &file::Store, name: &crate::FullNameRef) -> Option<gix_hash::ObjectId> {
+    /// The maximum amount of symbolic references to follow, just like git.
+    const MAX_DEPTH: usize = 5;
+    let reference = store.try_find(name).ok()??;
+    reference
+        .follow_chain(store, MAX_DEPTH)
+        .ok()?
+        .id()
+        .map(ToOwned::to_owned)
+}
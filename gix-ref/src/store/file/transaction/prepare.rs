@@ -45,12 +45,27 @@ impl<'s, 'p> Transaction<'s, 'p> {
                 other => Err(other),
             })
             .and_then(|maybe_loose| match (maybe_loose, packed) {
-                (None, Some(packed)) => packed
-                    .try_find(change.update.name.as_ref())
-                    .map(|opt| opt.map(Into::into))
-                    .map_err(Error::from),
+                (None, Some(packed)) => {
+                    let name = match &store.namespace {
+                        Some(namespace) => namespace.clone().into_namespaced_name(change.update.name.as_ref()),
+                        None => change.update.name.clone(),
+                    };
+                    packed
+                        .try_find(name.as_ref())
+                        .map(|opt| opt.map(Into::into))
+                        .map_err(Error::from)
+                }
                 (None, None) => Ok(None),
                 (maybe_loose, _) => Ok(maybe_loose),
+            })
+            .map(|maybe_ref| {
+                maybe_ref.map(|mut r: Reference| {
+                    // Existing values are compared to and reported as edits, which are never namespaced.
+                    if let Some(namespace) = &store.namespace {
+                        r.strip_namespace(namespace);
+                    }
+                    r
+                })
             });
         let lock = match &mut change.update.change {
             Change::Delete { expected, .. } => {
@@ -185,7 +200,13 @@ impl<'s, 'p> Transaction<'s, 'p> {
 
                     lock.with_mut(|file| match new {
                         Target::Peeled(oid) => write!(file, "{oid}"),
-                        Target::Symbolic(name) => writeln!(file, "ref: {}", name.0),
+                        Target::Symbolic(name) => match &store.namespace {
+                            // Like git, symbolic references within a namespace point to references in the same namespace.
+                            Some(namespace) => {
+                                writeln!(file, "ref: {}", namespace.clone().into_namespaced_name(name.as_ref()).0)
+                            }
+                            None => writeln!(file, "ref: {}", name.0),
+                        },
                     })?;
                     Some(lock.close()?)
                 } else {
@@ -225,6 +246,9 @@ impl<'s, 'p> Transaction<'s, 'p> {
     ) -> Result<Self, Error> {
         assert!(self.updates.is_none(), "BUG: Must not call prepare(…) multiple times");
         let store = self.store;
+        if store.is_reftable() {
+            return Err(Error::Reftable);
+        }
         let mut updates: Vec<_> = edits
             .map(|update| Edit {
                 update,
@@ -407,6 +431,16 @@ impl<'s, 'p> Transaction<'s, 'p> {
         Ok(self)
     }
 
+    /// Return the edits of this transaction after it was [prepared](Transaction::prepare()), or nothing if it wasn't prepared yet.
+    ///
+    /// While the transaction is prepared, all affected references are locked and their expected previous values were verified,
+    /// so these edits can be inspected to decide whether to [commit](Transaction::commit()) or [roll back](Transaction::rollback()).
+    /// Note that they have been altered compared to what was initially provided as they have been split and know about their
+    /// current state on disk.
+    pub fn prepared_edits(&self) -> impl Iterator<Item = &RefEdit> + '_ {
+        self.updates.iter().flatten().map(|edit| &edit.update)
+    }
+
     /// Rollback all intermediate state and return the `RefEdits` as we know them thus far.
     ///
     /// Note that they have been altered compared to what was initially provided as they have
@@ -488,6 +522,8 @@ mod error {
         },
         #[error("Could not read reference")]
         ReferenceDecode(#[from] file::loose::reference::decode::Error),
+        #[error("References can't be changed yet if they are stored in reftables")]
+        Reftable,
     }
 }
 
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+
+use crate::{file, packed, FullName, Target};
+
+/// The error returned by [`file::Store::watch()`] and [`Watcher::changes()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The packed-refs file could not be opened")]
+    PackedOpen(#[from] packed::buffer::open::Error),
+    #[error("Loose references could not be iterated")]
+    Iter(#[from] std::io::Error),
+    #[error("A reference could not be read")]
+    Reference(#[from] file::iter::loose_then_packed::Error),
+    #[error("The HEAD reference could not be read")]
+    Head(#[from] file::find::Error),
+    #[cfg(feature = "notify")]
+    #[error("Filesystem notifications could not be set up or received")]
+    Notify(#[from] notify::Error),
+}
+
+/// A change to a reference as observed by [`Watcher::changes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The reference `name` didn't exist before and now points to `target`.
+    Created {
+        /// The name of the new reference.
+        name: FullName,
+        /// The target the reference points to.
+        target: Target,
+    },
+    /// The reference `name` was changed to point from `previous` to `current`.
+    Updated {
+        /// The name of the changed reference.
+        name: FullName,
+        /// The target the reference pointed to before.
+        previous: Target,
+        /// The target the reference points to now.
+        current: Target,
+    },
+    /// The reference `name` pointing to `previous` doesn't exist anymore.
+    Deleted {
+        /// The name of the deleted reference.
+        name: FullName,
+        /// The target the reference pointed to before its deletion.
+        previous: Target,
+    },
+}
+
+impl Change {
+    /// Return the name of the reference that changed.
+    pub fn name(&self) -> &FullName {
+        match self {
+            Change::Created { name, .. } | Change::Updated { name, .. } | Change::Deleted { name, .. } => name,
+        }
+    }
+}
+
+/// A watcher for changes to all references of a store, as seen through loose references and the `packed-refs` file.
+///
+/// It remembers the state of all references and reports the difference to their current state with each call to
+/// [`changes()`](Watcher::changes()), which is typically done after a filesystem notification was received for the
+/// `refs/` directory, `HEAD` or `packed-refs`, or periodically.
+/// With the `notify` feature, a `Notifier` does this on native filesystem notifications.
+///
+/// As references are compared by value, rewrites of the `packed-refs` file only produce changes if they alter the
+/// value of a reference, so packing references isn't observable.
+#[derive(Debug, Clone)]
+pub struct Watcher {
+    refs: BTreeMap<FullName, Target>,
+}
+
+impl file::Store {
+    /// Create a [`Watcher`] initialized with the current state of all references in this store, to learn about
+    /// changes to them later.
+    pub fn watch(&self) -> Result<Watcher, Error> {
+        Ok(Watcher {
+            refs: self.current_refs()?,
+        })
+    }
+
+    /// Return the targets of `HEAD` and all references, ignoring broken ones just like iteration does.
+    fn current_refs(&self) -> Result<BTreeMap<FullName, Target>, Error> {
+        let mut refs = BTreeMap::new();
+        if let Some(head) = self.try_find("HEAD")? {
+            refs.insert(head.name, head.target);
+        }
+        for reference in self.iter()?.all()? {
+            let reference = match reference {
+                Ok(reference) => reference,
+                Err(file::iter::loose_then_packed::Error::ReferenceCreation { .. }) => continue,
+                Err(err) => return Err(err.into()),
+            };
+            refs.insert(reference.name, reference.target);
+        }
+        Ok(refs)
+    }
+}
+
+impl Watcher {
+    /// Compare the references in `store` with the ones seen last time and return all changes, sorted by reference name,
+    /// while remembering the current state for the next call.
+    pub fn changes(&mut self, store: &file::Store) -> Result<Vec<Change>, Error> {
+        let current = store.current_refs()?;
+        let mut changes = Vec::new();
+        let mut previous = std::mem::take(&mut self.refs).into_iter().peekable();
+        let mut current_iter = current.iter().peekable();
+        loop {
+            let is_deletion = match (previous.peek(), current_iter.peek()) {
+                (None, None) => break,
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                (Some((previous_name, _)), Some((current_name, _))) => {
+                    if previous_name == *current_name {
+                        let (name, previous) = previous.next().expect("peeked");
+                        let (_, current) = current_iter.next().expect("peeked");
+                        if previous != *current {
+                            changes.push(Change::Updated {
+                                name,
+                                previous,
+                                current: current.clone(),
+                            });
+                        }
+                        continue;
+                    }
+                    previous_name < *current_name
+                }
+            };
+            if is_deletion {
+                let (name, previous) = previous.next().expect("peeked");
+                changes.push(Change::Deleted { name, previous });
+            } else {
+                let (name, target) = current_iter.next().expect("peeked");
+                changes.push(Change::Created {
+                    name: name.clone(),
+                    target: target.clone(),
+                });
+            }
+        }
+        self.refs = current;
+        Ok(changes)
+    }
+}
+
+#[cfg(feature = "notify")]
+mod notifier {
+    use std::{
+        path::Path,
+        sync::mpsc,
+        time::{Duration, Instant},
+    };
+
+    use notify::Watcher as _;
+
+    use super::{Change, Error, Watcher};
+    use crate::file;
+
+    /// A [`Watcher`] driven by native filesystem notifications for `HEAD`, `packed-refs` and the `refs/` directories,
+    /// so changes to references can be awaited instead of polled for.
+    pub struct Notifier {
+        store: file::Store,
+        watcher: Watcher,
+        events: mpsc::Receiver<notify::Result<notify::Event>>,
+        /// Keeps the native watches alive, dropping it stops them.
+        _backend: notify::RecommendedWatcher,
+    }
+
+    impl std::fmt::Debug for Notifier {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("Notifier")
+                .field("store", &self.store)
+                .field("watcher", &self.watcher)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl file::Store {
+        /// Create a [`Notifier`] that watches the files and directories holding references of this store and
+        /// is initialized with their current state, to await changes to them.
+        pub fn watch_notified(&self) -> Result<Notifier, Error> {
+            let (tx, events) = mpsc::channel();
+            let mut backend = notify::recommended_watcher(move |event| {
+                tx.send(event).ok();
+            })?;
+            let common_dir = self.common_dir_resolved();
+            backend.watch(self.git_dir(), notify::RecursiveMode::NonRecursive)?;
+            if common_dir != self.git_dir() {
+                backend.watch(common_dir, notify::RecursiveMode::NonRecursive)?;
+                watch_refs_dir(&mut backend, &self.git_dir().join("refs"))?;
+            }
+            watch_refs_dir(&mut backend, &common_dir.join("refs"))?;
+
+            // Only look at references once notifications are in place to not miss changes in between.
+            let watcher = self.watch()?;
+            Ok(Notifier {
+                store: self.clone(),
+                watcher,
+                events,
+                _backend: backend,
+            })
+        }
+    }
+
+    fn watch_refs_dir(backend: &mut notify::RecommendedWatcher, dir: &Path) -> Result<(), Error> {
+        if dir.is_dir() {
+            backend.watch(dir, notify::RecursiveMode::Recursive)?;
+        }
+        Ok(())
+    }
+
+    impl Notifier {
+        /// Block until filesystem notifications indicate that references changed and return the non-empty list of
+        /// changes, just like [`Watcher::changes()`].
+        pub fn wait(&mut self) -> Result<Vec<Change>, Error> {
+            loop {
+                let event = self
+                    .events
+                    .recv()
+                    .expect("the sender is owned by the backend we keep alive");
+                if let Some(changes) = self.changes_after(event)? {
+                    return Ok(changes);
+                }
+            }
+        }
+
+        /// Like [`wait()`](Self::wait()), but return `None` if no reference changed within `timeout`.
+        pub fn wait_timeout(&mut self, timeout: Duration) -> Result<Option<Vec<Change>>, Error> {
+            let deadline = Instant::now() + timeout;
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                let event = match self.events.recv_timeout(remaining) {
+                    Ok(event) => event,
+                    Err(mpsc::RecvTimeoutError::Timeout) => return Ok(None),
+                    Err(mpsc::RecvTimeoutError::Disconnected) => {
+                        unreachable!("the sender is owned by the backend we keep alive")
+                    }
+                };
+                if let Some(changes) = self.changes_after(event)? {
+                    return Ok(Some(changes));
+                }
+            }
+        }
+
+        /// Process `event` along with all other pending ones and return the resulting reference changes, if there are any.
+        fn changes_after(&mut self, event: notify::Result<notify::Event>) -> Result<Option<Vec<Change>>, Error> {
+            let mut needs_check = is_relevant(event?);
+            for event in self.events.try_iter() {
+                needs_check |= is_relevant(event?);
+            }
+            if !needs_check {
+                return Ok(None);
+            }
+            let changes = self.watcher.changes(&self.store)?;
+            Ok((!changes.is_empty()).then_some(changes))
+        }
+    }
+
+    /// Reading files doesn't alter them, and everything else may have changed a reference.
+    fn is_relevant(event: notify::Event) -> bool {
+        !matches!(event.kind, notify::EventKind::Access(_))
+    }
+}
+#[cfg(feature = "notify")]
+pub use notifier::Notifier;
@@ -148,9 +148,18 @@ pub(crate) fn transform_full_name_for_lookup(name: &FullNameRef) -> Option<&Full
         Some((c, sn)) => {
             use crate::Category::*;
             Some(match c {
-                MainRef | LinkedRef { .. } => FullNameRef::new_unchecked(sn),
-                Tag | RemoteBranch | LocalBranch | Bisect | Rewritten | Note => name,
-                MainPseudoRef | PseudoRef | LinkedPseudoRef { .. } | WorktreePrivate => return None,
+                MainRef | LinkedRef { .. } => {
+                    let sn = FullNameRef::new_unchecked(sn);
+                    if matches!(sn.category(), Some(c) if c.is_worktree_private()) {
+                        return None;
+                    }
+                    sn
+                }
+                Tag | RemoteBranch | LocalBranch | Note => name,
+                // Like in `git`, worktree-private references are never packed as they would be shared with all worktrees.
+                MainPseudoRef | PseudoRef | LinkedPseudoRef { .. } | WorktreePrivate | Bisect | Rewritten => {
+                    return None
+                }
             })
         }
         None => Some(name),
@@ -0,0 +1,265 @@
+use gix_hash::ObjectId;
+use gix_object::bstr::BString;
+
+use crate::store_impl::reftable::{LogRecord, RefRecord, RefValue};
+
+/// The error returned when decoding a [`Table`](super::Table) fails.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The table doesn't start with the 'REFT' magic")]
+    Magic,
+    #[error("Table version {version} is not supported")]
+    UnsupportedVersion { version: u8 },
+    #[error("The hash id {hash_id:#010x} is not supported")]
+    UnsupportedHash { hash_id: u32 },
+    #[error("The footer doesn't match the header of the table")]
+    FooterMismatch,
+    #[error("The footer checksum was {actual:#010x}, but should have been {expected:#010x}")]
+    FooterChecksum { actual: u32, expected: u32 },
+    #[error("The table ended unexpectedly")]
+    Truncated,
+    #[error("A block at offset {offset} is malformed")]
+    Block { offset: usize },
+    #[error("A record is malformed")]
+    Record,
+    #[error("Could not decompress the log block at offset {offset}")]
+    Inflate {
+        offset: usize,
+        source: gix_features::zlib::inflate::Error,
+    },
+}
+
+/// A block of records, along with the key of the previously decoded record to decode the prefix-compressed keys.
+pub(crate) struct Block<'a> {
+    data: std::borrow::Cow<'a, [u8]>,
+    /// The position of the next record.
+    pos: usize,
+    /// The position past the last record, where the restart offsets begin.
+    end: usize,
+    last_key: Vec<u8>,
+    /// The position at which the next block starts.
+    pub(crate) next_block: usize,
+}
+
+impl<'a> Block<'a> {
+    /// Parse the block at `start` in `data`, with the block header being located at `start + header_offset`,
+    /// or return `None` if it's not of `block_type`. `end` is the position past the last block.
+    pub(crate) fn at(
+        data: &'a [u8],
+        start: usize,
+        header_offset: usize,
+        end: usize,
+        block_type: u8,
+    ) -> Result<Option<Self>, Error> {
+        if start >= end {
+            return Ok(None);
+        }
+        let header = data
+            .get(start + header_offset..start + header_offset + 4)
+            .ok_or(Error::Truncated)?;
+        if header[0] != block_type {
+            return Ok(None);
+        }
+        let block_len = be24(&header[1..]);
+        let records_start = header_offset + 4;
+        if block_len < records_start + 2 {
+            return Err(Error::Block { offset: start });
+        }
+        let (block, records_start, next_block) = if block_type == super::BLOCK_TYPE_LOG {
+            let compressed_start = start + records_start;
+            let mut out = vec![0; block_len - records_start];
+            let compressed = data.get(compressed_start..end).ok_or(Error::Truncated)?;
+            let consumed = inflate(compressed, &mut out)
+                .map_err(|source| Error::Inflate { offset: start, source })?
+                .ok_or(Error::Block { offset: start })?;
+            (std::borrow::Cow::Owned(out), 0, compressed_start + consumed)
+        } else {
+            let block = data.get(start..start + block_len).ok_or(Error::Truncated)?;
+            (std::borrow::Cow::Borrowed(block), records_start, start + block_len)
+        };
+
+        let num_restarts = be16(&block[block.len() - 2..]);
+        let records_end = (block.len() - 2)
+            .checked_sub(num_restarts * 3)
+            .filter(|records_end| *records_end >= records_start)
+            .ok_or(Error::Block { offset: start })?;
+        // Blocks may be padded with zeroes to align them to the block size.
+        let padding = data.get(next_block.min(end)..end).ok_or(Error::Truncated)?;
+        let next_block = next_block + padding.iter().take_while(|b| **b == 0).count();
+        Ok(Some(Block {
+            data: block,
+            pos: records_start,
+            end: records_end,
+            last_key: Vec::new(),
+            next_block,
+        }))
+    }
+
+    /// Decode the next key, returning the 3 bits of extra information stored along with it, or `None` if there is no more record.
+    fn next_key(&mut self) -> Result<Option<u8>, Error> {
+        if self.pos >= self.end {
+            return Ok(None);
+        }
+        let prefix_len = self.varint()? as usize;
+        let suffix_len_and_type = self.varint()?;
+        let suffix_len = (suffix_len_and_type >> 3) as usize;
+        if prefix_len > self.last_key.len() {
+            return Err(Error::Record);
+        }
+        self.last_key.truncate(prefix_len);
+        let suffix = self.range(suffix_len)?;
+        self.last_key.extend_from_slice(&self.data[suffix]);
+        Ok(Some((suffix_len_and_type & 0b111) as u8))
+    }
+
+    fn varint(&mut self) -> Result<u64, Error> {
+        let (value, consumed) = varint(&self.data[self.pos..self.end]).ok_or(Error::Record)?;
+        self.pos += consumed;
+        Ok(value)
+    }
+
+    fn range(&mut self, len: usize) -> Result<std::ops::Range<usize>, Error> {
+        let range = self.pos..self.pos + len;
+        if range.end > self.end {
+            return Err(Error::Record);
+        }
+        self.pos = range.end;
+        Ok(range)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], Error> {
+        let range = self.range(len)?;
+        Ok(&self.data[range])
+    }
+
+    fn object_id(&mut self, object_hash: gix_hash::Kind) -> Result<ObjectId, Error> {
+        Ok(ObjectId::from_bytes_or_panic(self.take(object_hash.len_in_bytes())?))
+    }
+
+    fn string(&mut self) -> Result<BString, Error> {
+        let len = self.varint()? as usize;
+        Ok(self.take(len)?.into())
+    }
+
+    /// Decode the next ref record, with `min_update_index` being the base for the stored update index.
+    pub(crate) fn next_ref(
+        &mut self,
+        min_update_index: u64,
+        object_hash: gix_hash::Kind,
+    ) -> Result<Option<RefRecord>, Error> {
+        let Some(value_type) = self.next_key()? else {
+            return Ok(None);
+        };
+        let name = self.last_key.clone().into();
+        let update_index = min_update_index + self.varint()?;
+        let value = match value_type {
+            0 => RefValue::Deletion,
+            1 => RefValue::Object(self.object_id(object_hash)?),
+            2 => RefValue::Peeled {
+                target: self.object_id(object_hash)?,
+                peeled: self.object_id(object_hash)?,
+            },
+            3 => RefValue::Symbolic(self.string()?),
+            _ => return Err(Error::Record),
+        };
+        Ok(Some(RefRecord {
+            name,
+            update_index,
+            value,
+        }))
+    }
+
+    /// Decode the next log record.
+    pub(crate) fn next_log(&mut self, object_hash: gix_hash::Kind) -> Result<Option<LogRecord>, Error> {
+        let Some(log_type) = self.next_key()? else {
+            return Ok(None);
+        };
+        let (name, reversed_update_index) = match self.last_key.len().checked_sub(9) {
+            Some(name_len) if self.last_key[name_len] == 0 => (
+                self.last_key[..name_len].into(),
+                u64::from_be_bytes(self.last_key[name_len + 1..].try_into().expect("8 bytes")),
+            ),
+            _ => return Err(Error::Record),
+        };
+        let line = match log_type {
+            0 => None,
+            1 => {
+                let previous_oid = self.object_id(object_hash)?;
+                let new_oid = self.object_id(object_hash)?;
+                let name = self.string()?;
+                let email = self.string()?;
+                let seconds = self.varint()?;
+                let tz_offset = i16::from_be_bytes(self.take(2)?.try_into().expect("2 bytes"));
+                let message = self.string()?;
+                Some(crate::log::Line {
+                    previous_oid,
+                    new_oid,
+                    signature: gix_actor::Signature {
+                        name,
+                        email,
+                        time: gix_date::Time::new(
+                            seconds as gix_date::SecondsSinceUnixEpoch,
+                            offset_from_hhmm(tz_offset),
+                        ),
+                    },
+                    message,
+                })
+            }
+            _ => return Err(Error::Record),
+        };
+        Ok(Some(LogRecord {
+            name,
+            update_index: u64::MAX - reversed_update_index,
+            line,
+        }))
+    }
+}
+
+/// Decompress the zlib stream at the beginning of `input` into `out`, which must be filled entirely, and return the amount of
+/// consumed bytes or `None` if the stream didn't match the size of `out`.
+fn inflate(input: &[u8], out: &mut [u8]) -> Result<Option<usize>, gix_features::zlib::inflate::Error> {
+    let mut inflate = gix_features::zlib::Inflate::default();
+    let (mut consumed_in, mut consumed_out) = (0, 0);
+    loop {
+        let (status, num_in, num_out) = inflate.once(&input[consumed_in..], &mut out[consumed_out..])?;
+        consumed_in += num_in;
+        consumed_out += num_out;
+        match status {
+            gix_features::zlib::Status::StreamEnd => break,
+            _ if num_in == 0 && num_out == 0 => return Ok(None),
+            _ => {}
+        }
+    }
+    Ok((consumed_out == out.len()).then_some(consumed_in))
+}
+
+/// Decode a variable-length integer as used by reftables, and return it along with the amount of bytes it consumed.
+pub(crate) fn varint(input: &[u8]) -> Option<(u64, usize)> {
+    let mut bytes = input.iter();
+    let mut byte = *bytes.next()?;
+    let mut value = u64::from(byte & 0x7f);
+    let mut consumed = 1;
+    while byte & 0x80 != 0 {
+        byte = *bytes.next()?;
+        value = value.checked_add(1)?.checked_mul(128)? | u64::from(byte & 0x7f);
+        consumed += 1;
+    }
+    Some((value, consumed))
+}
+
+pub(crate) fn be16(bytes: &[u8]) -> usize {
+    u16::from_be_bytes([bytes[0], bytes[1]]) as usize
+}
+
+pub(crate) fn be24(bytes: &[u8]) -> usize {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize
+}
+
+/// Convert `tz_offset` as stored by `git`, which is the `±HHMM` of the signature as decimal number, into seconds.
+/// For example, `+0130` is stored as `130`.
+fn offset_from_hhmm(tz_offset: i16) -> gix_date::OffsetInSeconds {
+    let hhmm = gix_date::OffsetInSeconds::from(tz_offset);
+    let minutes = hhmm.abs() / 100 * 60 + hhmm.abs() % 100;
+    hhmm.signum() * minutes * 60
+}
@@ -0,0 +1,364 @@
+use std::io::Write;
+
+use gix_object::bstr::BString;
+
+use crate::store_impl::reftable::{LogRecord, RefRecord, RefValue, BLOCK_TYPE_LOG, BLOCK_TYPE_REF, MAGIC};
+
+/// The error returned by [`table()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The block size must be between {min} and {max} bytes, got {block_size}")]
+    BlockSize { block_size: u32, min: u32, max: u32 },
+    #[error("The record of {name:?} doesn't fit into a block of {block_size} bytes")]
+    RecordTooLarge { name: BString, block_size: u32 },
+    #[error("The update index {update_index} of the ref {name:?} is outside of the range {min_update_index}..={max_update_index} of the table")]
+    UpdateIndex {
+        name: BString,
+        update_index: u64,
+        min_update_index: u64,
+        max_update_index: u64,
+    },
+}
+
+/// Options for use in [`table()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// The size of ref blocks, which are padded to align with it. Log blocks are compressed and thus not aligned.
+    pub block_size: u32,
+    /// The amount of records after which a record with a full key is written, to allow binary searches within blocks.
+    pub restart_interval: u16,
+}
+
+impl Default for Options {
+    /// Use the same defaults as `git`.
+    fn default() -> Self {
+        Options {
+            block_size: 4096,
+            restart_interval: 16,
+        }
+    }
+}
+
+/// The size of the header of version 1 tables.
+const HEADER_LEN: usize = 24;
+
+/// Encode a table containing the ref records in `refs` and the log records in `logs`, which will be sorted as needed,
+/// and return its bytes. The update indices of all ref records must be within `min_update_index..=max_update_index`,
+/// while log records may refer to older updates, which is needed to delete them.
+///
+/// Only ref and log blocks are written, without any indices.
+pub fn table(
+    mut refs: Vec<RefRecord>,
+    mut logs: Vec<LogRecord>,
+    min_update_index: u64,
+    max_update_index: u64,
+    object_hash: gix_hash::Kind,
+    options: Options,
+) -> Result<Vec<u8>, Error> {
+    const MAX_BLOCK_SIZE: u32 = (1 << 24) - 1;
+    let min_block_size = (HEADER_LEN + 4 + 2) as u32;
+    if !(min_block_size..=MAX_BLOCK_SIZE).contains(&options.block_size) {
+        return Err(Error::BlockSize {
+            block_size: options.block_size,
+            min: min_block_size,
+            max: MAX_BLOCK_SIZE,
+        });
+    }
+    let check_update_index = |name: &BString, update_index: u64| {
+        if (min_update_index..=max_update_index).contains(&update_index) {
+            Ok(())
+        } else {
+            Err(Error::UpdateIndex {
+                name: name.clone(),
+                update_index,
+                min_update_index,
+                max_update_index,
+            })
+        }
+    };
+
+    // SHA1 tables are written as version 1 for compatibility, which is the only hash we support.
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    header.extend_from_slice(MAGIC);
+    header.push(1);
+    header.extend_from_slice(&options.block_size.to_be_bytes()[1..]);
+    header.extend_from_slice(&min_update_index.to_be_bytes());
+    header.extend_from_slice(&max_update_index.to_be_bytes());
+    debug_assert_eq!(header.len(), HEADER_LEN);
+
+    let mut out = Writer {
+        data: Vec::new(),
+        pending_padding: 0,
+        options,
+    };
+
+    refs.sort_by(|a, b| a.name.cmp(&b.name));
+    let records = refs
+        .into_iter()
+        .map(|record| {
+            check_update_index(&record.name, record.update_index)?;
+            let mut value = Vec::new();
+            put_varint(&mut value, record.update_index - min_update_index);
+            let value_type = match record.value {
+                RefValue::Deletion => 0,
+                RefValue::Object(id) => {
+                    value.extend_from_slice(id.as_bytes());
+                    1
+                }
+                RefValue::Peeled { target, peeled } => {
+                    value.extend_from_slice(target.as_bytes());
+                    value.extend_from_slice(peeled.as_bytes());
+                    2
+                }
+                RefValue::Symbolic(target) => {
+                    put_varint(&mut value, target.len() as u64);
+                    value.extend_from_slice(&target);
+                    3
+                }
+            };
+            Ok(Record {
+                key: record.name.into(),
+                value_type,
+                value,
+            })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    out.write_section(BLOCK_TYPE_REF, records)?;
+
+    logs.sort_by(|a, b| a.name.cmp(&b.name).then(b.update_index.cmp(&a.update_index)));
+    let records = logs
+        .into_iter()
+        .map(|record| {
+            let mut key: Vec<u8> = record.name.into();
+            key.push(0);
+            key.extend_from_slice(&(u64::MAX - record.update_index).to_be_bytes());
+            let mut value = Vec::new();
+            let value_type = match record.line {
+                None => 0,
+                Some(line) => {
+                    debug_assert_eq!(
+                        line.new_oid.kind(),
+                        object_hash,
+                        "all object ids must be of the same kind"
+                    );
+                    value.extend_from_slice(line.previous_oid.as_bytes());
+                    value.extend_from_slice(line.new_oid.as_bytes());
+                    for field in [&line.signature.name, &line.signature.email] {
+                        put_varint(&mut value, field.len() as u64);
+                        value.extend_from_slice(field);
+                    }
+                    put_varint(&mut value, line.signature.time.seconds.max(0) as u64);
+                    value.extend_from_slice(&offset_to_hhmm(line.signature.time.offset).to_be_bytes());
+                    put_varint(&mut value, line.message.len() as u64);
+                    value.extend_from_slice(&line.message);
+                    1
+                }
+            };
+            Ok(Record { key, value_type, value })
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    let log_position = out.write_section(BLOCK_TYPE_LOG, records)?;
+
+    let mut data = out.data;
+    if data.is_empty() {
+        data.extend_from_slice(&header);
+    } else {
+        data[..HEADER_LEN].copy_from_slice(&header);
+    }
+    let footer_start = data.len();
+    data.extend_from_slice(&header);
+    for position in [0, 0, 0, log_position.unwrap_or(0) as u64, 0] {
+        data.extend_from_slice(&position.to_be_bytes());
+    }
+    let checksum = gix_features::hash::crc32(&data[footer_start..]);
+    data.extend_from_slice(&checksum.to_be_bytes());
+    Ok(data)
+}
+
+struct Record {
+    key: Vec<u8>,
+    value_type: u8,
+    value: Vec<u8>,
+}
+
+struct Writer {
+    data: Vec<u8>,
+    /// The amount of zeroes to write before the next block, to align the previous one to the block size.
+    pending_padding: usize,
+    options: Options,
+}
+
+impl Writer {
+    /// Write all `records` into as many blocks of `block_type` as needed, and return the position of the first block.
+    fn write_section(&mut self, block_type: u8, records: Vec<Record>) -> Result<Option<usize>, Error> {
+        let mut first_block = None;
+        let mut block: Option<BlockWriter> = None;
+        for record in records {
+            let current = block.get_or_insert_with(|| {
+                let header_offset = if self.data.is_empty() { HEADER_LEN } else { 0 };
+                BlockWriter::new(block_type, header_offset, self.options.restart_interval)
+            });
+            if current.num_records != 0 && current.len_with(&record) > self.options.block_size as usize {
+                let full = block.take().expect("present");
+                let position = self.write_block(full);
+                first_block.get_or_insert(position);
+                block = Some(BlockWriter::new(block_type, 0, self.options.restart_interval));
+            }
+            let current = block.as_mut().expect("present");
+            // Log records may exceed the block size as their blocks aren't aligned.
+            if block_type != BLOCK_TYPE_LOG && current.len_with(&record) > self.options.block_size as usize {
+                return Err(Error::RecordTooLarge {
+                    name: record_name(&record, block_type),
+                    block_size: self.options.block_size,
+                });
+            }
+            current.push(record);
+        }
+        if let Some(block) = block {
+            let position = self.write_block(block);
+            first_block.get_or_insert(position);
+        }
+        Ok(first_block)
+    }
+
+    fn write_block(&mut self, block: BlockWriter) -> usize {
+        let block_type = block.block_type;
+        let block = block.finish();
+        self.data.resize(self.data.len() + self.pending_padding, 0);
+        let position = self.data.len();
+        self.data.extend_from_slice(&block);
+        self.pending_padding = if block_type == BLOCK_TYPE_LOG {
+            0
+        } else {
+            self.options.block_size as usize - block.len()
+        };
+        position
+    }
+}
+
+fn record_name(record: &Record, block_type: u8) -> BString {
+    let name = if block_type == BLOCK_TYPE_LOG {
+        &record.key[..record.key.len().saturating_sub(9)]
+    } else {
+        &record.key
+    };
+    name.into()
+}
+
+struct BlockWriter {
+    block_type: u8,
+    header_offset: usize,
+    data: Vec<u8>,
+    restarts: Vec<usize>,
+    last_key: Vec<u8>,
+    num_records: usize,
+    restart_interval: usize,
+}
+
+impl BlockWriter {
+    fn new(block_type: u8, header_offset: usize, restart_interval: u16) -> Self {
+        let mut data = vec![0; header_offset];
+        data.extend_from_slice(&[block_type, 0, 0, 0]);
+        BlockWriter {
+            block_type,
+            header_offset,
+            data,
+            restarts: Vec::new(),
+            last_key: Vec::new(),
+            num_records: 0,
+            restart_interval: usize::from(restart_interval.max(1)),
+        }
+    }
+
+    fn is_restart(&self) -> bool {
+        self.num_records % self.restart_interval == 0
+    }
+
+    fn prefix_len(&self, key: &[u8]) -> usize {
+        if self.is_restart() {
+            0
+        } else {
+            self.last_key.iter().zip(key).take_while(|(a, b)| a == b).count()
+        }
+    }
+
+    /// Return the size of the block, including its restart table, if `record` was added to it.
+    fn len_with(&self, record: &Record) -> usize {
+        let prefix_len = self.prefix_len(&record.key);
+        let suffix_len = record.key.len() - prefix_len;
+        let num_restarts = self.restarts.len() + usize::from(self.is_restart());
+        self.data.len()
+            + varint_len(prefix_len as u64)
+            + varint_len(((suffix_len as u64) << 3) | u64::from(record.value_type))
+            + suffix_len
+            + record.value.len()
+            + num_restarts * 3
+            + 2
+    }
+
+    fn push(&mut self, record: Record) {
+        let prefix_len = self.prefix_len(&record.key);
+        if self.is_restart() {
+            self.restarts.push(self.data.len());
+        }
+        let suffix = &record.key[prefix_len..];
+        put_varint(&mut self.data, prefix_len as u64);
+        put_varint(
+            &mut self.data,
+            ((suffix.len() as u64) << 3) | u64::from(record.value_type),
+        );
+        self.data.extend_from_slice(suffix);
+        self.data.extend_from_slice(&record.value);
+        self.last_key = record.key;
+        self.num_records += 1;
+    }
+
+    /// Write the restart table and the block length, and compress log blocks.
+    fn finish(mut self) -> Vec<u8> {
+        for restart in &self.restarts {
+            self.data.extend_from_slice(&(*restart as u32).to_be_bytes()[1..]);
+        }
+        self.data.extend_from_slice(&(self.restarts.len() as u16).to_be_bytes());
+        let block_len = (self.data.len() as u32).to_be_bytes();
+        self.data[self.header_offset + 1..self.header_offset + 4].copy_from_slice(&block_len[1..]);
+        if self.block_type != BLOCK_TYPE_LOG {
+            return self.data;
+        }
+        let records_start = self.header_offset + 4;
+        let mut compressed = gix_features::zlib::stream::deflate::Write::new(self.data[..records_start].to_vec());
+        compressed
+            .write_all(&self.data[records_start..])
+            .and_then(|_| compressed.flush())
+            .expect("writing to memory doesn't fail");
+        compressed.into_inner()
+    }
+}
+
+/// Append `value` to `out` as variable-length integer as used by reftables.
+pub(crate) fn put_varint(out: &mut Vec<u8>, mut value: u64) {
+    let mut buf = [0u8; 10];
+    let mut pos = buf.len() - 1;
+    buf[pos] = (value & 0x7f) as u8;
+    value >>= 7;
+    while value != 0 {
+        value -= 1;
+        pos -= 1;
+        buf[pos] = 0x80 | (value & 0x7f) as u8;
+        value >>= 7;
+    }
+    out.extend_from_slice(&buf[pos..]);
+}
+
+fn varint_len(value: u64) -> usize {
+    let mut buf = Vec::with_capacity(10);
+    put_varint(&mut buf, value);
+    buf.len()
+}
+
+/// Convert `offset` in seconds into the `±HHMM` of a signature as decimal number, which is how `git` stores it.
+/// For example, `+0130` is stored as `130`.
+fn offset_to_hhmm(offset: gix_date::OffsetInSeconds) -> i16 {
+    let minutes = offset.abs() / 60;
+    (offset.signum() * (minutes / 60 * 100 + minutes % 60)) as i16
+}
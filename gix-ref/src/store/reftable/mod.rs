@@ -0,0 +1,109 @@
+use std::path::PathBuf;
+
+use gix_hash::ObjectId;
+use gix_object::bstr::BString;
+
+/// The magic bytes at the beginning of each table, and of its footer.
+const MAGIC: &[u8; 4] = b"REFT";
+/// The `hash_id` of SHA1 in version 2 tables.
+const HASH_ID_SHA1: u32 = u32::from_be_bytes(*b"sha1");
+
+/// The block type of blocks containing ref records.
+const BLOCK_TYPE_REF: u8 = b'r';
+/// The block type of blocks containing log records.
+const BLOCK_TYPE_LOG: u8 = b'g';
+
+/// The header of a [`Table`], which is repeated in its footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Header {
+    /// The version of the table, either 1 or 2.
+    pub version: u8,
+    /// The size of blocks that were written, which may be 0 if blocks are unaligned.
+    pub block_size: u32,
+    /// The smallest update index of all records in the table.
+    pub min_update_index: u64,
+    /// The largest update index of all records in the table.
+    pub max_update_index: u64,
+    /// The kind of hash used for all object ids in the table.
+    pub object_hash: gix_hash::Kind,
+}
+
+/// A single reftable file, which is fully loaded into memory.
+///
+/// Note that only ref and log blocks are read, while indices are ignored as all lookups are linear.
+#[derive(Debug, Clone)]
+pub struct Table {
+    data: Vec<u8>,
+    header: Header,
+    header_len: usize,
+    footer_len: usize,
+    /// The position of the first log block, if there are any.
+    log_position: Option<usize>,
+}
+
+/// A stack of [tables](Table) as listed in the `tables.list` file of a `reftable` directory, with newer tables overriding
+/// the records of older ones.
+#[derive(Debug, Clone)]
+pub struct Stack {
+    path: PathBuf,
+    /// The file names of all tables, from oldest to newest.
+    names: Vec<String>,
+    /// All tables, from oldest to newest.
+    tables: Vec<Table>,
+}
+
+/// The value of a [`RefRecord`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub enum RefValue {
+    /// The reference was deleted, hiding records of the same name in older tables.
+    Deletion,
+    /// The reference points to an object.
+    Object(ObjectId),
+    /// The reference points to an object which is an annotated tag, along with the object the tag ultimately points to.
+    Peeled {
+        /// The object the reference points to.
+        target: ObjectId,
+        /// The peeled object, which is never a tag.
+        peeled: ObjectId,
+    },
+    /// The reference points to another reference with the given name.
+    Symbolic(BString),
+}
+
+/// A record of a reference as stored in a [`Table`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct RefRecord {
+    /// The full name of the reference.
+    pub name: BString,
+    /// The update index at which the reference was last changed.
+    pub update_index: u64,
+    /// The value of the reference.
+    pub value: RefValue,
+}
+
+/// A record of a change to a reference as stored in a [`Table`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Ord, PartialOrd)]
+pub struct LogRecord {
+    /// The full name of the reference.
+    pub name: BString,
+    /// The update index of the change, which is unique for each change of a reference.
+    pub update_index: u64,
+    /// The change itself, or `None` if this record deletes the log entry with the same name and update index in older tables.
+    pub line: Option<crate::log::Line>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod decode;
+///
+#[allow(clippy::empty_docs)]
+pub mod encode;
+///
+#[allow(clippy::empty_docs)]
+pub mod stack;
+///
+#[allow(clippy::empty_docs)]
+pub mod table;
+///
+#[allow(clippy::empty_docs)]
+pub mod transaction;
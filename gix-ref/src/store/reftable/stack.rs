@@ -0,0 +1,160 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use gix_object::bstr::{BString, ByteSlice};
+
+use crate::{
+    store_impl::reftable::{decode, RefRecord, RefValue, Stack, Table},
+    FullName, FullNameRef, Reference, Target,
+};
+
+/// The error returned by [`Stack`] and [`Table`] operations that access the file system.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not read reftable file at '{}'", path.display())]
+    Io { source: std::io::Error, path: PathBuf },
+    #[error("Could not decode reftable at '{}'", path.display())]
+    Decode { source: decode::Error, path: PathBuf },
+    #[error("The table name {name:?} in 'tables.list' is invalid")]
+    TableName { name: BString },
+    #[error("The reference name {name:?} is invalid")]
+    RefName {
+        name: BString,
+        source: gix_validate::reference::name::Error,
+    },
+}
+
+/// Initialization
+impl Stack {
+    /// Open the stack in the `reftable` directory at `path`, typically `.git/reftable`, and read all tables listed
+    /// in its `tables.list` file.
+    pub fn at(path: impl Into<PathBuf>) -> Result<Self, Error> {
+        let path = path.into();
+        let list_path = path.join("tables.list");
+        let list = std::fs::read(&list_path).map_err(|source| Error::Io {
+            source,
+            path: list_path.clone(),
+        })?;
+        let names = list
+            .lines()
+            .filter(|name| !name.is_empty())
+            .map(|name| match name.to_str() {
+                Ok(name) if !name.contains(['/', '\\']) && name != "." && name != ".." => Ok(name.to_owned()),
+                _ => Err(Error::TableName { name: name.into() }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let tables = names
+            .iter()
+            .map(|name| Table::at(&path.join(name)))
+            .collect::<Result<_, _>>()?;
+        Ok(Stack { path, names, tables })
+    }
+}
+
+/// Access
+impl Stack {
+    /// Return the path to the `reftable` directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Return all tables of the stack, from oldest to newest.
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
+    /// Find the reference with the full `name`, considering that newer tables override older ones.
+    ///
+    /// Returns `None` if no table contains the reference, or if it was deleted.
+    pub fn find(&self, name: &FullNameRef) -> Result<Option<Reference>, Error> {
+        for table in self.tables.iter().rev() {
+            let record = table.find(name.as_bstr()).map_err(|source| self.decode_error(source))?;
+            if let Some(record) = record {
+                return to_reference(record);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Return all references, sorted by name, merging all tables so that newer tables override older ones.
+    pub fn references(&self) -> Result<Vec<Reference>, Error> {
+        let mut refs = BTreeMap::new();
+        for table in &self.tables {
+            for record in table.refs() {
+                let record = record.map_err(|source| self.decode_error(source))?;
+                refs.insert(record.name.clone(), record);
+            }
+        }
+        refs.into_values()
+            .filter_map(|record| to_reference(record).transpose())
+            .collect()
+    }
+
+    /// Return the reflog of the reference with the full `name`, from the oldest to the newest entry, merging all tables
+    /// so that newer tables override older ones.
+    pub fn reflog(&self, name: &FullNameRef) -> Result<Vec<crate::log::Line>, Error> {
+        let mut lines = BTreeMap::new();
+        for table in &self.tables {
+            for record in table.logs() {
+                let record = record.map_err(|source| self.decode_error(source))?;
+                if record.name.as_bstr() == name.as_bstr() {
+                    lines.insert(record.update_index, record.line);
+                }
+            }
+        }
+        Ok(lines.into_values().flatten().collect())
+    }
+
+    /// Return the names of all references that have a reflog, sorted by name.
+    pub fn reflog_names(&self) -> Result<Vec<BString>, Error> {
+        let mut logs = BTreeMap::new();
+        for table in &self.tables {
+            for record in table.logs() {
+                let record = record.map_err(|source| self.decode_error(source))?;
+                logs.insert((record.name, record.update_index), record.line.is_some());
+            }
+        }
+        let mut names: Vec<BString> = logs
+            .into_iter()
+            .filter_map(|((name, _), exists)| exists.then_some(name))
+            .collect();
+        names.dedup();
+        Ok(names)
+    }
+
+    fn decode_error(&self, source: decode::Error) -> Error {
+        Error::Decode {
+            source,
+            path: self.path.clone(),
+        }
+    }
+}
+
+fn to_reference(record: RefRecord) -> Result<Option<Reference>, Error> {
+    let name = full_name(record.name)?;
+    Ok(Some(match record.value {
+        RefValue::Deletion => return Ok(None),
+        RefValue::Object(id) => Reference {
+            name,
+            target: Target::Peeled(id),
+            peeled: None,
+        },
+        RefValue::Peeled { target, peeled } => Reference {
+            name,
+            target: Target::Peeled(target),
+            peeled: Some(peeled),
+        },
+        RefValue::Symbolic(target) => Reference {
+            target: Target::Symbolic(full_name(target)?),
+            name,
+            peeled: None,
+        },
+    }))
+}
+
+fn full_name(name: BString) -> Result<FullName, Error> {
+    FullName::try_from(name.as_bstr()).map_err(|source| Error::RefName { name, source })
+}
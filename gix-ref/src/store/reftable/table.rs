@@ -0,0 +1,207 @@
+use gix_object::bstr::BStr;
+
+use crate::store_impl::reftable::{
+    decode::{self, be24, Block},
+    Header, LogRecord, RefRecord, Table, BLOCK_TYPE_LOG, BLOCK_TYPE_REF, HASH_ID_SHA1, MAGIC,
+};
+
+/// Initialization
+impl Table {
+    /// Decode the table contained in `data`, validating its header and footer.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, decode::Error> {
+        if data.get(..4) != Some(MAGIC.as_slice()) {
+            return Err(decode::Error::Magic);
+        }
+        let version = *data.get(4).ok_or(decode::Error::Truncated)?;
+        let (header_len, footer_len) = match version {
+            1 => (24, 68),
+            2 => (28, 72),
+            _ => return Err(decode::Error::UnsupportedVersion { version }),
+        };
+        if data.len() < header_len + footer_len {
+            return Err(decode::Error::Truncated);
+        }
+        let object_hash = if version == 1 {
+            gix_hash::Kind::Sha1
+        } else {
+            match be32(&data[24..]) {
+                HASH_ID_SHA1 => gix_hash::Kind::Sha1,
+                hash_id => return Err(decode::Error::UnsupportedHash { hash_id }),
+            }
+        };
+        let header = Header {
+            version,
+            block_size: be24(&data[5..]) as u32,
+            min_update_index: be64(&data[8..]),
+            max_update_index: be64(&data[16..]),
+            object_hash,
+        };
+
+        let footer = &data[data.len() - footer_len..];
+        if footer[..header_len] != data[..header_len] {
+            return Err(decode::Error::FooterMismatch);
+        }
+        let expected = be32(&footer[footer_len - 4..]);
+        let actual = gix_features::hash::crc32(&footer[..footer_len - 4]);
+        if actual != expected {
+            return Err(decode::Error::FooterChecksum { actual, expected });
+        }
+        let log_position = be64(&footer[header_len + 24..]) as usize;
+        let first_block_type = data.get(header_len).copied();
+        let log_position = if log_position > 0 {
+            Some(log_position)
+        } else {
+            (first_block_type == Some(BLOCK_TYPE_LOG) && data.len() > header_len + footer_len).then_some(0)
+        };
+
+        Ok(Table {
+            data,
+            header,
+            header_len,
+            footer_len,
+            log_position,
+        })
+    }
+
+    /// Read the table at `path` into memory and decode it.
+    pub fn at(path: &std::path::Path) -> Result<Self, crate::reftable::stack::Error> {
+        let data = std::fs::read(path).map_err(|source| crate::reftable::stack::Error::Io {
+            source,
+            path: path.to_owned(),
+        })?;
+        Table::from_bytes(data).map_err(|source| crate::reftable::stack::Error::Decode {
+            source,
+            path: path.to_owned(),
+        })
+    }
+}
+
+/// Access
+impl Table {
+    /// Return the header of this table.
+    pub fn header(&self) -> &Header {
+        &self.header
+    }
+
+    /// Return an iterator over all ref records in the table, sorted by name.
+    pub fn refs(&self) -> Refs<'_> {
+        Refs {
+            blocks: Blocks::new(self, 0, BLOCK_TYPE_REF),
+        }
+    }
+
+    /// Return an iterator over all log records in the table, sorted by name, and by update index in descending order.
+    pub fn logs(&self) -> Logs<'_> {
+        Logs {
+            blocks: Blocks::new(self, self.log_position.unwrap_or(self.data.len()), BLOCK_TYPE_LOG),
+        }
+    }
+
+    /// Find the ref record with the given full `name`, which may be a [deletion](crate::reftable::RefValue::Deletion).
+    pub fn find(&self, name: &BStr) -> Result<Option<RefRecord>, decode::Error> {
+        for record in self.refs() {
+            let record = record?;
+            match record.name.as_slice().cmp(name) {
+                std::cmp::Ordering::Less => continue,
+                std::cmp::Ordering::Equal => return Ok(Some(record)),
+                std::cmp::Ordering::Greater => break,
+            }
+        }
+        Ok(None)
+    }
+}
+
+struct Blocks<'a> {
+    table: &'a Table,
+    next_block: usize,
+    block_type: u8,
+    current: Option<Block<'a>>,
+    is_done: bool,
+}
+
+impl<'a> Blocks<'a> {
+    fn new(table: &'a Table, start: usize, block_type: u8) -> Self {
+        Blocks {
+            table,
+            next_block: start,
+            block_type,
+            current: None,
+            is_done: false,
+        }
+    }
+
+    /// Return the next record decoded by `decode()`, moving on to the next block once the current one is exhausted.
+    fn next_record<T>(
+        &mut self,
+        mut decode: impl FnMut(&mut Block<'a>, &Header) -> Result<Option<T>, decode::Error>,
+    ) -> Option<Result<T, decode::Error>> {
+        while !self.is_done {
+            if let Some(block) = self.current.as_mut() {
+                match decode(block, &self.table.header) {
+                    Ok(Some(record)) => return Some(Ok(record)),
+                    Ok(None) => {
+                        self.next_block = block.next_block;
+                        self.current = None;
+                    }
+                    Err(err) => {
+                        self.is_done = true;
+                        return Some(Err(err));
+                    }
+                }
+            }
+            let table = self.table;
+            let header_offset = if self.next_block == 0 { table.header_len } else { 0 };
+            match Block::at(
+                &table.data,
+                self.next_block,
+                header_offset,
+                table.data.len() - table.footer_len,
+                self.block_type,
+            ) {
+                Ok(Some(block)) => self.current = Some(block),
+                Ok(None) => self.is_done = true,
+                Err(err) => {
+                    self.is_done = true;
+                    return Some(Err(err));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// An iterator over all [ref records](RefRecord) of a [`Table`].
+pub struct Refs<'a> {
+    blocks: Blocks<'a>,
+}
+
+impl<'a> Iterator for Refs<'a> {
+    type Item = Result<RefRecord, decode::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.blocks
+            .next_record(|block, header| block.next_ref(header.min_update_index, header.object_hash))
+    }
+}
+
+/// An iterator over all [log records](LogRecord) of a [`Table`].
+pub struct Logs<'a> {
+    blocks: Blocks<'a>,
+}
+
+impl<'a> Iterator for Logs<'a> {
+    type Item = Result<LogRecord, decode::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.blocks
+            .next_record(|block, header| block.next_log(header.object_hash))
+    }
+}
+
+fn be32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().expect("4 bytes"))
+}
+
+fn be64(bytes: &[u8]) -> u64 {
+    u64::from_be_bytes(bytes[..8].try_into().expect("8 bytes"))
+}
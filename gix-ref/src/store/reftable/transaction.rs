@@ -0,0 +1,431 @@
+use std::{collections::BTreeMap, io::Write};
+
+use gix_hash::ObjectId;
+use gix_object::bstr::BString;
+
+use crate::{
+    store_impl::reftable::{encode, stack, LogRecord, RefRecord, RefValue, Stack, Table},
+    transaction::{Change, PreviousValue, RefEdit, RefEditsExt, RefLog},
+    FullNameRef, Target,
+};
+
+/// The error returned by [`Stack::commit()`] and [`Stack::compact()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The lock for 'tables.list' could not be obtained")]
+    LockAcquire(#[from] gix_lock::acquire::Error),
+    #[error("The lock for 'tables.list' could not be committed")]
+    LockCommit(#[from] gix_lock::commit::Error<gix_lock::File>),
+    #[error("Could not reload the stack after locking it")]
+    Reload(#[from] stack::Error),
+    #[error("Edit preprocessing failed with an error")]
+    PreprocessingFailed(#[source] std::io::Error),
+    #[error("The reference {full_name:?} for deletion did not exist")]
+    DeleteReferenceMustExist { full_name: BString },
+    #[error("Reference {full_name:?} was not supposed to exist when writing it with value {new:?}, but actual content was {actual:?}")]
+    MustNotExist {
+        full_name: BString,
+        actual: Target,
+        new: Target,
+    },
+    #[error("Reference {full_name:?} was supposed to exist with value {expected}, but didn't.")]
+    MustExist { full_name: BString, expected: Target },
+    #[error("The reference {full_name:?} should have content {expected}, actual content was {actual}")]
+    ReferenceOutOfDate {
+        full_name: BString,
+        expected: Target,
+        actual: Target,
+    },
+    #[error("Could not encode a new table")]
+    Encode(#[from] encode::Error),
+    #[error("Could not write a new table")]
+    Io(#[from] std::io::Error),
+}
+
+/// Options for use in [`Stack::commit()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// Control how new tables are encoded.
+    pub encode: encode::Options,
+    /// If `true`, after adding a table to the stack, merge the newest tables into one as long as the table below them
+    /// isn't at least twice as large as all of them together. This keeps the amount of tables logarithmic to the amount of changes.
+    pub auto_compaction: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            encode: Default::default(),
+            auto_compaction: true,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Edit {
+    update: RefEdit,
+    /// Set if this update is coming from a symbolic reference.
+    parent_index: Option<usize>,
+    /// For symbolic refs, this is the previous id to put into the reflog instead of our own previous value.
+    leaf_referent_previous_oid: Option<ObjectId>,
+}
+
+impl std::borrow::Borrow<RefEdit> for Edit {
+    fn borrow(&self) -> &RefEdit {
+        &self.update
+    }
+}
+
+impl std::borrow::BorrowMut<RefEdit> for Edit {
+    fn borrow_mut(&mut self) -> &mut RefEdit {
+        &mut self.update
+    }
+}
+
+/// Mutation
+impl Stack {
+    /// Apply all `edits` by adding a single table to the stack, while holding the lock on `tables.list` which is acquired
+    /// according to `lock_fail_mode`. Before applying them, the stack is reloaded to see the latest changes made by other writers.
+    ///
+    /// Reflog entries are written if `committer` is set, for all edits that change the object a reference points to.
+    /// Just like in [file transactions](crate::file::Transaction), the returned edits have their `expected` values
+    /// set to the previous value of each reference, if there was one.
+    ///
+    /// Tables which are no longer listed after auto-compaction are removed on a best-effort basis.
+    pub fn commit(
+        &mut self,
+        edits: impl IntoIterator<Item = RefEdit>,
+        committer: Option<gix_actor::SignatureRef<'_>>,
+        lock_fail_mode: gix_lock::acquire::Fail,
+        options: Options,
+    ) -> Result<Vec<RefEdit>, Error> {
+        let lock = self.lock_and_reload(lock_fail_mode)?;
+        let mut updates: Vec<_> = edits
+            .into_iter()
+            .map(|update| Edit {
+                update,
+                parent_index: None,
+                leaf_referent_previous_oid: None,
+            })
+            .collect();
+        updates
+            .pre_process(
+                &mut |name| {
+                    let name: &FullNameRef = name.as_bstr().try_into().ok()?;
+                    self.find(name).ok().flatten().map(|r| r.target)
+                },
+                &mut |idx, update| Edit {
+                    update,
+                    parent_index: Some(idx),
+                    leaf_referent_previous_oid: None,
+                },
+            )
+            .map_err(Error::PreprocessingFailed)?;
+
+        for idx in 0..updates.len() {
+            let existing = self.find(updates[idx].update.name.as_ref())?.map(|r| r.target);
+            check_and_fill_expected(&mut updates[idx].update, existing)?;
+            if let (Some(crate::TargetRef::Peeled(oid)), Some(parent_idx)) =
+                (updates[idx].update.change.previous_value(), updates[idx].parent_index)
+            {
+                let oid = oid.to_owned();
+                let mut parent_idx_cursor = Some(parent_idx);
+                while let Some(parent) = parent_idx_cursor.take().map(|idx| &mut updates[idx]) {
+                    parent_idx_cursor = parent.parent_index;
+                    parent.leaf_referent_previous_oid = Some(oid);
+                }
+            }
+        }
+
+        let update_index = self.tables.last().map_or(1, |t| t.header.max_update_index + 1);
+        let object_hash = self
+            .tables
+            .last()
+            .map_or(gix_hash::Kind::Sha1, |t| t.header.object_hash);
+        let mut refs = Vec::new();
+        let mut logs = Vec::new();
+        let mut obsolete = Vec::new();
+        for edit in &updates {
+            self.records_for_edit(edit, update_index, committer, &mut refs, &mut logs)?;
+        }
+
+        if !refs.is_empty() || !logs.is_empty() {
+            let data = encode::table(refs, logs, update_index, update_index, object_hash, options.encode)?;
+            self.add_table(data)?;
+            if options.auto_compaction {
+                let sizes: Vec<_> = self.tables.iter().map(|t| t.data.len()).collect();
+                let mut first = sizes.len() - 1;
+                let mut newer_size = sizes[first];
+                while first > 0 && sizes[first - 1] < 2 * newer_size {
+                    first -= 1;
+                    newer_size += sizes[first];
+                }
+                obsolete = self.compact_from(first, object_hash, options.encode)?;
+            }
+        }
+        self.commit_list(lock, obsolete)?;
+        Ok(updates.into_iter().map(|edit| edit.update).collect())
+    }
+
+    /// Merge all tables of the stack into a single one while holding the lock on `tables.list`, which is acquired according to
+    /// `lock_fail_mode`, dropping all deletions along the way.
+    pub fn compact(&mut self, lock_fail_mode: gix_lock::acquire::Fail, options: encode::Options) -> Result<(), Error> {
+        let lock = self.lock_and_reload(lock_fail_mode)?;
+        let object_hash = self
+            .tables
+            .last()
+            .map_or(gix_hash::Kind::Sha1, |t| t.header.object_hash);
+        let obsolete = self.compact_from(0, object_hash, options)?;
+        self.commit_list(lock, obsolete)
+    }
+
+    fn lock_and_reload(&mut self, lock_fail_mode: gix_lock::acquire::Fail) -> Result<gix_lock::File, Error> {
+        let lock = gix_lock::File::acquire_to_update_resource(self.path.join("tables.list"), lock_fail_mode, None)?;
+        *self = Stack::at(self.path.clone())?;
+        Ok(lock)
+    }
+
+    /// Write the names of all tables into `lock` and commit it, then remove the `obsolete` tables unless they are still listed.
+    fn commit_list(&self, mut lock: gix_lock::File, obsolete: Vec<String>) -> Result<(), Error> {
+        for name in &self.names {
+            writeln!(lock, "{name}")?;
+        }
+        lock.commit()?;
+        for name in obsolete.iter().filter(|name| !self.names.contains(name)) {
+            std::fs::remove_file(self.path.join(name)).ok();
+        }
+        Ok(())
+    }
+
+    /// Write the table in `data` into the `reftable` directory and append it to the stack.
+    fn add_table(&mut self, data: Vec<u8>) -> Result<(), Error> {
+        let table = Table::from_bytes(data).expect("we just encoded the table");
+        let name = format!(
+            "0x{:012x}-0x{:012x}-{:08x}.ref",
+            table.header.min_update_index,
+            table.header.max_update_index,
+            gix_features::hash::crc32(&table.data)
+        );
+        let mut file = gix_lock::File::acquire_to_update_resource(
+            self.path.join(&name),
+            gix_lock::acquire::Fail::Immediately,
+            None,
+        )?;
+        file.write_all(&table.data)?;
+        file.commit()?;
+        self.tables.push(table);
+        self.names.push(name);
+        Ok(())
+    }
+
+    /// Replace all tables from index `first` to the newest one with a single table, if there are at least two of them,
+    /// and return the names of the replaced tables.
+    fn compact_from(
+        &mut self,
+        first: usize,
+        object_hash: gix_hash::Kind,
+        options: encode::Options,
+    ) -> Result<Vec<String>, Error> {
+        let is_full_compaction = first == 0;
+        if self.tables.len() - first < 2 && !(is_full_compaction && self.tables.len() == 1) {
+            return Ok(Vec::new());
+        }
+        let mut refs = BTreeMap::new();
+        let mut logs = BTreeMap::new();
+        for table in &self.tables[first..] {
+            for record in table.refs() {
+                let record = record.map_err(|source| self.table_error(source))?;
+                refs.insert(record.name.clone(), record);
+            }
+            for record in table.logs() {
+                let record = record.map_err(|source| self.table_error(source))?;
+                logs.insert((record.name.clone(), record.update_index), record);
+            }
+        }
+        // Deletions only need to hide records of older tables, so they can be dropped if there are none.
+        let refs = refs
+            .into_values()
+            .filter(|r| !(is_full_compaction && r.value == RefValue::Deletion))
+            .collect();
+        let logs = logs
+            .into_values()
+            .filter(|r| !(is_full_compaction && r.line.is_none()))
+            .collect();
+        let min_update_index = self.tables[first].header.min_update_index;
+        let max_update_index = self.tables.last().expect("at least one table").header.max_update_index;
+        let data = encode::table(refs, logs, min_update_index, max_update_index, object_hash, options)?;
+
+        self.tables.truncate(first);
+        let obsolete = self.names.drain(first..).collect();
+        self.add_table(data)?;
+        Ok(obsolete)
+    }
+
+    fn table_error(&self, source: crate::reftable::decode::Error) -> Error {
+        Error::Reload(stack::Error::Decode {
+            source,
+            path: self.path.clone(),
+        })
+    }
+
+    /// Produce the records to write for `edit` at `update_index`.
+    fn records_for_edit(
+        &self,
+        edit: &Edit,
+        update_index: u64,
+        committer: Option<gix_actor::SignatureRef<'_>>,
+        refs: &mut Vec<RefRecord>,
+        logs: &mut Vec<LogRecord>,
+    ) -> Result<(), Error> {
+        let name = edit.update.name.as_bstr().to_owned();
+        match &edit.update.change {
+            Change::Update { log, expected, new } => {
+                if log.mode == RefLog::AndReference {
+                    refs.push(RefRecord {
+                        name: name.clone(),
+                        update_index,
+                        value: match new {
+                            Target::Peeled(id) => RefValue::Object(*id),
+                            Target::Symbolic(target) => RefValue::Symbolic(target.as_bstr().to_owned()),
+                        },
+                    });
+                }
+                let (Some(committer), Target::Peeled(new_oid)) = (committer, new) else {
+                    return Ok(());
+                };
+                let previous_oid = match expected {
+                    PreviousValue::MustExistAndMatch(Target::Peeled(oid)) => Some(*oid),
+                    _ => None,
+                }
+                .or(edit.leaf_referent_previous_oid);
+                if previous_oid.as_ref() == Some(new_oid) {
+                    return Ok(());
+                }
+                let mut message = crate::log::normalize_message(log.message.as_ref()).into_owned();
+                if !message.is_empty() {
+                    message.push(b'\n');
+                }
+                logs.push(LogRecord {
+                    name,
+                    update_index,
+                    line: Some(crate::log::Line {
+                        previous_oid: previous_oid.unwrap_or_else(|| new_oid.kind().null()),
+                        new_oid: *new_oid,
+                        signature: committer.to_owned(),
+                        message,
+                    }),
+                });
+            }
+            Change::Delete { log, .. } => {
+                if *log == RefLog::AndReference {
+                    refs.push(RefRecord {
+                        name: name.clone(),
+                        update_index,
+                        value: RefValue::Deletion,
+                    });
+                }
+                let mut update_indices = std::collections::BTreeSet::new();
+                for table in &self.tables {
+                    for record in table.logs() {
+                        let record = record.map_err(|source| self.table_error(source))?;
+                        if record.name == name && record.line.is_some() {
+                            update_indices.insert(record.update_index);
+                        }
+                    }
+                }
+                logs.extend(update_indices.into_iter().map(|update_index| LogRecord {
+                    name: name.clone(),
+                    update_index,
+                    line: None,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Check the expectations of `edit` against the `existing` value of the reference, and remember the latter.
+fn check_and_fill_expected(edit: &mut RefEdit, existing: Option<Target>) -> Result<(), Error> {
+    let full_name = || edit.name.as_bstr().to_owned();
+    match &mut edit.change {
+        Change::Delete { expected, .. } => {
+            match (&expected, &existing) {
+                (PreviousValue::MustNotExist, _) => {
+                    panic!("BUG: MustNotExist constraint makes no sense if references are to be deleted")
+                }
+                (PreviousValue::ExistingMustMatch(_) | PreviousValue::Any, None)
+                | (PreviousValue::MustExist | PreviousValue::Any, Some(_)) => {}
+                (PreviousValue::MustExist | PreviousValue::MustExistAndMatch(_), None) => {
+                    return Err(Error::DeleteReferenceMustExist { full_name: full_name() })
+                }
+                (
+                    PreviousValue::MustExistAndMatch(previous) | PreviousValue::ExistingMustMatch(previous),
+                    Some(existing),
+                ) => {
+                    if previous != existing {
+                        return Err(Error::ReferenceOutOfDate {
+                            full_name: full_name(),
+                            expected: previous.clone(),
+                            actual: existing.clone(),
+                        });
+                    }
+                }
+            }
+            if let Some(existing) = existing {
+                *expected = PreviousValue::MustExistAndMatch(existing);
+            }
+        }
+        Change::Update { expected, new, .. } => {
+            match (&expected, &existing) {
+                (PreviousValue::Any, _)
+                | (PreviousValue::MustExist, Some(_))
+                | (PreviousValue::MustNotExist | PreviousValue::ExistingMustMatch(_), None) => {}
+                (PreviousValue::MustExist, None) => {
+                    return Err(Error::MustExist {
+                        full_name: full_name(),
+                        expected: Target::Peeled(new_kind(new).null()),
+                    });
+                }
+                (PreviousValue::MustNotExist, Some(existing)) => {
+                    if existing != new {
+                        return Err(Error::MustNotExist {
+                            full_name: full_name(),
+                            actual: existing.clone(),
+                            new: new.clone(),
+                        });
+                    }
+                }
+                (
+                    PreviousValue::MustExistAndMatch(previous) | PreviousValue::ExistingMustMatch(previous),
+                    Some(existing),
+                ) => {
+                    if previous != existing {
+                        return Err(Error::ReferenceOutOfDate {
+                            full_name: full_name(),
+                            expected: previous.clone(),
+                            actual: existing.clone(),
+                        });
+                    }
+                }
+                (PreviousValue::MustExistAndMatch(previous), None) => {
+                    return Err(Error::MustExist {
+                        full_name: full_name(),
+                        expected: previous.clone(),
+                    });
+                }
+            }
+            if let Some(existing) = existing {
+                *expected = PreviousValue::MustExistAndMatch(existing);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn new_kind(target: &Target) -> gix_hash::Kind {
+    match target {
+        Target::Peeled(id) => id.kind(),
+        Target::Symbolic(_) => gix_hash::Kind::Sha1,
+    }
+}
@@ -24,7 +24,8 @@ pub struct LogChange {
     /// If set, create a reflog even though it would otherwise not be the case as prohibited by general rules.
     /// Note that ref-log writing might be prohibited in the entire repository which is when this flag has no effect either.
     pub force_create_reflog: bool,
-    /// The message to put into the reference log. It must be a single line, hence newlines are forbidden.
+    /// The message to put into the reference log. It will be [normalized](crate::log::normalize_message()) to fit on a single line,
+    /// hence newlines are replaced with spaces.
     /// The string can be empty to indicate there should be no message at all.
     pub message: BString,
 }
@@ -159,6 +159,81 @@ mod peel {
     }
 }
 
+mod follow_chain {
+    use gix_ref::{file::ReferenceExt, Reference, Target};
+
+    use crate::{file, hex_to_id};
+
+    #[test]
+    fn multi_hop() -> crate::Result {
+        let store = file::store()?;
+        let r: Reference = store.find_loose("multi-link")?.into();
+        let outcome = r.follow_chain(&store, 3)?;
+        assert_eq!(
+            outcome.hops.iter().map(|r| r.name.as_bstr()).collect::<Vec<_>>(),
+            [
+                "refs/multi-link",
+                "refs/heads/multi-link-target1",
+                "refs/tags/multi-link-target2",
+                "refs/remotes/origin/multi-link-target3"
+            ],
+            "every hop is recorded, starting with the reference itself"
+        );
+        assert_eq!(outcome.depth(), 3);
+        assert_eq!(outcome.missing, None);
+        assert_eq!(
+            outcome.id(),
+            Some(hex_to_id("134385f6d781b7e97062102c6a483440bfda2a03").as_ref())
+        );
+
+        assert!(matches!(
+            r.follow_chain(&store, 2).unwrap_err(),
+            gix_ref::file::follow_chain::Error::DepthLimitExceeded { max_depth: 2 }
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn peeled_references_have_no_depth() -> crate::Result {
+        let store = file::store()?;
+        let r: Reference = store.find_loose("main")?.into();
+        let outcome = r.follow_chain(&store, 0)?;
+        assert_eq!(outcome.hops, [r]);
+        assert_eq!(outcome.depth(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn missing_end() -> crate::Result {
+        let store = file::store()?;
+        let r = Reference {
+            name: "refs/heads/symbolic".try_into()?,
+            target: Target::Symbolic("refs/heads/unborn".try_into()?),
+            peeled: None,
+        };
+        let outcome = r.follow_chain(&store, 1)?;
+        assert_eq!(outcome.hops, [r]);
+        assert_eq!(
+            outcome.missing.as_ref().map(gix_ref::FullName::as_bstr),
+            Some("refs/heads/unborn".into())
+        );
+        assert_eq!(outcome.id(), None);
+        assert_eq!(outcome.depth(), 1, "following to the missing reference counts as well");
+        Ok(())
+    }
+
+    #[test]
+    fn cycle() -> crate::Result {
+        let store = file::store()?;
+        let r: Reference = store.find_loose("loop-a")?.into();
+        assert!(matches!(
+            r.follow_chain(&store, 10).unwrap_err(),
+            gix_ref::file::follow_chain::Error::Cycle { .. }
+        ));
+        Ok(())
+    }
+}
+
 mod parse {
     mod invalid {
         use gix_ref::file::loose::Reference;
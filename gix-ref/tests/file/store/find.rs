@@ -170,3 +170,67 @@ mod loose {
         Ok(())
     }
 }
+
+mod candidates {
+    use gix_ref::PartialNameRef;
+
+    use crate::file::{store, store_with_packed_refs};
+
+    fn names(partial: &str) -> crate::Result<Vec<String>> {
+        let partial: &PartialNameRef = partial.try_into()?;
+        Ok(partial
+            .candidates()
+            .iter()
+            .map(|name| name.as_bstr().to_string())
+            .collect())
+    }
+
+    #[test]
+    fn partial_names_expand_in_lookup_order() -> crate::Result {
+        assert_eq!(
+            names("origin")?,
+            [
+                "refs/origin",
+                "refs/tags/origin",
+                "refs/heads/origin",
+                "refs/remotes/origin",
+                "refs/remotes/origin/HEAD"
+            ]
+        );
+        assert_eq!(
+            names("refs/heads/main")?,
+            ["refs/heads/main"],
+            "full names are used as is"
+        );
+        assert_eq!(names("HEAD")?, ["HEAD"], "and so are pseudo-refs");
+        Ok(())
+    }
+
+    #[test]
+    fn matches_and_ambiguity_are_reported() -> crate::Result {
+        for store in [store()?, store_with_packed_refs()?] {
+            let candidates = store.find_candidates("d1")?;
+            assert_eq!(candidates.len(), 5);
+            let existing: Vec<_> = candidates
+                .iter()
+                .filter_map(|c| c.reference.as_ref().map(|r| r.name.as_bstr().to_string()))
+                .collect();
+            assert_eq!(
+                existing,
+                ["refs/d1", "refs/heads/d1"],
+                "the first match is what `find()` returns, the others make it ambiguous"
+            );
+            assert_eq!(store.find("d1")?.name.as_bstr(), "refs/d1");
+
+            let candidates = store.find_candidates("origin")?;
+            assert_eq!(
+                candidates
+                    .iter()
+                    .position(|c| c.reference.is_some())
+                    .map(|idx| candidates[idx].name.as_bstr().to_string()),
+                Some("refs/remotes/origin/HEAD".into())
+            );
+        }
+        Ok(())
+    }
+}
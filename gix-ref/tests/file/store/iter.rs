@@ -461,3 +461,74 @@ fn overlay_partial_prefix_iter() -> crate::Result {
     assert_eq!(ref_names, vec![(b"refs/heads/main".as_bstr().to_owned(), Peeled(c1)),]);
     Ok(())
 }
+
+mod matching {
+    use gix_ref::file::iter::Filter;
+
+    use crate::file::store_at;
+
+    fn matching_names(filter: Filter) -> crate::Result<Vec<String>> {
+        let store = store_at("make_packed_ref_repository_for_overlay.sh")?;
+        Ok(store
+            .iter()?
+            .matching(filter)?
+            .map(|r| r.map(|r| r.name.as_bstr().to_string()))
+            .collect::<Result<Vec<_>, _>>()?)
+    }
+
+    #[test]
+    fn multiple_includes_with_exclusions() -> crate::Result {
+        assert_eq!(
+            matching_names(
+                Filter::default()
+                    .include("refs/tags/*")
+                    .include("refs/heads/*")
+                    .exclude("refs/heads/newer*")
+            )?,
+            ["refs/heads/main", "refs/tags/tag-object"],
+            "results are sorted, even though includes aren't"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn includes_match_as_prefix_and_globs_do_not_match_slashes() -> crate::Result {
+        assert_eq!(
+            matching_names(Filter::default().include("refs/remotes/origin"))?,
+            ["refs/remotes/origin/HEAD", "refs/remotes/origin/main"]
+        );
+        assert_eq!(
+            matching_names(Filter::default().include("refs/remotes/*"))?,
+            Vec::<String>::new(),
+            "there is nothing directly in `refs/remotes/`"
+        );
+        assert_eq!(
+            matching_names(
+                Filter::default()
+                    .include("refs/remotes/origin/")
+                    .include("refs/remotes/*/main")
+            )?,
+            ["refs/remotes/origin/HEAD", "refs/remotes/origin/main"],
+            "overlapping includes don't yield references twice"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn exclusions_can_be_negated_with_the_last_match_winning() -> crate::Result {
+        let filter = Filter::default()
+            .exclude("refs/remotes")
+            .exclude("!refs/remotes/origin/main")
+            .exclude("refs/tags/tag-object");
+        assert_eq!(
+            matching_names(filter)?,
+            [
+                "refs/heads/main",
+                "refs/heads/newer-as-loose",
+                "refs/remotes/origin/main"
+            ],
+            "without includes everything is included, like with `transfer.hideRefs`"
+        );
+        Ok(())
+    }
+}
@@ -9,7 +9,10 @@ use gix_ref::Target;
 mod access;
 mod find;
 mod iter;
+mod pack_refs;
+mod pseudo_ref;
 mod reflog;
+mod watch;
 
 #[test]
 fn precompose_unicode_journey() -> crate::Result {
@@ -0,0 +1,94 @@
+use gix_ref::file::pack_refs::{Options, Outcome};
+
+use crate::file::store_writable;
+
+fn all_refs(store: &gix_ref::file::Store) -> crate::Result<Vec<(String, gix_ref::Target)>> {
+    Ok(store
+        .iter()?
+        .all()?
+        .filter_map(Result::ok)
+        .map(|r| (r.name.as_bstr().to_string(), r.target))
+        .collect())
+}
+
+fn loose_names(store: &gix_ref::file::Store) -> crate::Result<Vec<String>> {
+    Ok(store
+        .loose_iter()?
+        .filter_map(Result::ok)
+        .map(|r| r.name.as_bstr().to_string())
+        .collect())
+}
+
+#[test]
+fn tags_only_by_default_then_all() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    let refs_before = all_refs(&store)?;
+
+    let outcome = store.pack_refs(&odb, Options::default())?;
+    assert_eq!(
+        outcome,
+        Outcome {
+            packed_refs: 2,
+            pruned_refs: 2
+        },
+        "only the tags are packed, symbolic tags are ignored"
+    );
+    assert!(!store.git_dir().join("refs/tags/t1").exists());
+    assert!(
+        store.git_dir().join("refs/tags").is_dir(),
+        "directories directly below refs/ are kept"
+    );
+    let packed = store.open_packed_buffer()?.expect("created");
+    let tag = packed.find("refs/tags/dt1")?;
+    assert!(tag.object.is_some(), "annotated tags are peeled");
+    assert!(packed.find("refs/tags/t1")?.object.is_none());
+    assert_eq!(all_refs(&store)?, refs_before, "packing doesn't change any value");
+
+    let outcome = store.pack_refs(
+        &odb,
+        Options {
+            all: true,
+            prune: false,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome,
+        Outcome {
+            packed_refs: 6,
+            pruned_refs: 0
+        },
+        "broken and symbolic refs are skipped"
+    );
+    assert!(store.git_dir().join("refs/heads/main").is_file(), "nothing was pruned");
+    assert_eq!(all_refs(&store)?, refs_before);
+
+    let outcome = store.pack_refs(&odb, Options::default())?;
+    assert_eq!(
+        outcome,
+        Outcome {
+            packed_refs: 6,
+            pruned_refs: 6
+        },
+        "refs that are already packed are packed again"
+    );
+    assert_eq!(all_refs(&store)?, refs_before);
+    assert_eq!(
+        loose_names(&store)?,
+        [
+            "refs/heads/multi-link-target1",
+            "refs/loop-a",
+            "refs/loop-b",
+            "refs/multi-link",
+            "refs/remotes/origin/HEAD",
+            "refs/tags/multi-link-target2"
+        ],
+        "only symbolic refs remain loose"
+    );
+    assert!(
+        store.git_dir().join("refs/broken").is_file(),
+        "broken refs are left alone"
+    );
+    Ok(())
+}
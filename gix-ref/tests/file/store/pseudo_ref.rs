@@ -0,0 +1,108 @@
+use gix_lock::acquire::Fail;
+use gix_ref::fetch_head::{Entry, Kind, Source};
+
+use crate::{
+    file::{store, store_writable},
+    hex_to_id,
+};
+
+#[test]
+fn fetch_head_is_parsed_with_its_annotations() -> crate::Result {
+    let entries = store()?.fetch_head()?.expect("FETCH_HEAD is present");
+    assert_eq!(entries.len(), 10);
+    assert_eq!(
+        entries[0],
+        Entry {
+            id: hex_to_id("9064ea31fae4dc59a56bdd3a06c0ddc990ee689e"),
+            not_for_merge: false,
+            source: Some(Source {
+                kind: Kind::Branch,
+                name: "main".into(),
+            }),
+            url: "https://github.com/Byron/gitoxide".into(),
+        }
+    );
+    assert!(
+        entries[1..].iter().all(|e| e.not_for_merge),
+        "only the first branch is for merging"
+    );
+    assert_eq!(store()?.merge_heads()?, None, "no merge is in progress");
+    Ok(())
+}
+
+#[test]
+fn fetch_head_can_be_replaced_or_appended_to() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let entry = Entry {
+        id: hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+        not_for_merge: false,
+        source: Some(Source {
+            kind: Kind::Tag,
+            name: "t1".into(),
+        }),
+        url: "origin".into(),
+    };
+
+    store.write_fetch_head(std::slice::from_ref(&entry), true, Fail::Immediately)?;
+    let entries = store.fetch_head()?.expect("present");
+    assert_eq!(entries.len(), 11, "appending keeps existing entries");
+    assert_eq!(entries.last(), Some(&entry));
+
+    store.write_fetch_head(std::slice::from_ref(&entry), false, Fail::Immediately)?;
+    assert_eq!(store.fetch_head()?, Some(vec![entry]), "otherwise entries are replaced");
+
+    assert!(store.remove_pseudo_ref("FETCH_HEAD")?);
+    assert_eq!(store.fetch_head()?, None);
+    assert!(!store.remove_pseudo_ref("FETCH_HEAD")?, "it's fine if it doesn't exist");
+    Ok(())
+}
+
+#[test]
+fn merge_heads_can_be_written_and_read() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let ids = [
+        hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391"),
+        hex_to_id("9064ea31fae4dc59a56bdd3a06c0ddc990ee689e"),
+    ];
+    store.write_merge_heads(&ids, Fail::Immediately)?;
+    assert_eq!(store.merge_heads()?.as_deref(), Some(&ids[..]));
+    assert_eq!(
+        store.find("MERGE_HEAD")?.target.id(),
+        ids[0],
+        "like git, the first merge head can be read as reference"
+    );
+
+    std::fs::write(store.git_dir().join("MERGE_HEAD"), "invalid\n")?;
+    assert_eq!(
+        store.merge_heads().unwrap_err().to_string(),
+        "MERGE_HEAD line 1 is not a valid object id: \"invalid\""
+    );
+
+    assert!(store.remove_pseudo_ref("MERGE_HEAD")?);
+    assert_eq!(store.merge_heads()?, None);
+    Ok(())
+}
+
+#[test]
+fn cherry_pick_head_can_be_written_and_read() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    assert_eq!(store.cherry_pick_head()?, None, "no cherry-pick is in progress");
+
+    let id = hex_to_id("9064ea31fae4dc59a56bdd3a06c0ddc990ee689e");
+    store.write_cherry_pick_head(&id, Fail::Immediately)?;
+    assert_eq!(store.cherry_pick_head()?, Some(id));
+    assert_eq!(
+        std::fs::read(store.git_dir().join("CHERRY_PICK_HEAD"))?,
+        b"9064ea31fae4dc59a56bdd3a06c0ddc990ee689e\n",
+        "it's written just like git does"
+    );
+    assert_eq!(
+        store.find("CHERRY_PICK_HEAD")?.target.id(),
+        id,
+        "it can also be read as reference"
+    );
+
+    assert!(store.remove_pseudo_ref("CHERRY_PICK_HEAD")?);
+    assert_eq!(store.cherry_pick_head()?, None);
+    Ok(())
+}
@@ -66,3 +66,73 @@ mod iter_rev {
         Ok(())
     }
 }
+
+mod expire {
+    use gix_ref::file::log::expire::{Options, Outcome};
+
+    use crate::{file::transaction::prepare_and_commit::empty_store, hex_to_id};
+
+    const A: &str = "1111111111111111111111111111111111111111";
+    const B: &str = "2222222222222222222222222222222222222222";
+    const NULL: &str = "0000000000000000000000000000000000000000";
+
+    fn line(previous: &str, new: &str, time: u32) -> String {
+        format!("{previous} {new} name <email> {time} +0000\tmessage at {time}\n")
+    }
+
+    #[test]
+    fn old_and_unreachable_entries_are_removed() -> crate::Result {
+        let (_keep, store) = empty_store()?;
+        let log_path = store.git_dir().join("logs/refs/heads/main");
+        std::fs::create_dir_all(log_path.parent().expect("parent"))?;
+        std::fs::write(
+            &log_path,
+            [
+                line(NULL, A, 100),
+                line(A, B, 200),
+                "not a valid line\n".into(),
+                line(B, A, 300),
+                line(A, A, 400),
+            ]
+            .concat(),
+        )?;
+
+        let name = "refs/heads/main".try_into()?;
+        let mut unreachable = vec![hex_to_id(B)];
+        let options = Options {
+            expire: Some(150),
+            expire_unreachable: Some(350),
+            lock_fail_mode: gix_lock::acquire::Fail::Immediately,
+        };
+        let outcome = store.expire_reflog(name, options, &mut |id| {
+            assert_ne!(id, hex_to_id(NULL), "null ids are never checked");
+            !unreachable.iter().any(|u| *u == id)
+        })?;
+        assert_eq!(
+            outcome,
+            Outcome { kept: 2, expired: 3 },
+            "the first entry is too old, and the next two refer to an unreachable object"
+        );
+        assert_eq!(
+            std::fs::read_to_string(&log_path)?,
+            ["not a valid line\n".into(), line(A, A, 400)].concat(),
+            "unparsable lines are kept"
+        );
+
+        unreachable.clear();
+        let outcome = store.expire_reflog(
+            name,
+            Options {
+                expire: None,
+                expire_unreachable: None,
+                ..options
+            },
+            &mut |_| unreachable!("no unreachable expiry"),
+        )?;
+        assert_eq!(outcome, Outcome { kept: 2, expired: 0 });
+
+        let outcome = store.expire_reflog("refs/heads/missing".try_into()?, options, &mut |_| true)?;
+        assert_eq!(outcome, Outcome::default(), "missing reflogs are fine");
+        Ok(())
+    }
+}
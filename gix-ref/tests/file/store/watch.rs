@@ -0,0 +1,158 @@
+use gix_lock::acquire::Fail;
+use gix_ref::{
+    file::watch::Change,
+    transaction::{Change as EditChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+
+use crate::{
+    file::{
+        store_writable,
+        transaction::prepare_and_commit::{committer, create_at},
+    },
+    hex_to_id,
+};
+
+#[test]
+fn created_updated_and_deleted_references_are_reported() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let mut watcher = store.watch()?;
+    assert_eq!(watcher.changes(&store)?, [], "nothing changed yet");
+
+    let main = store.find("refs/heads/main")?.target;
+    let new_id = hex_to_id("e69de29bb2d1d6434b8b29ae775ad8c2e48c5391");
+    store
+        .transaction()
+        .prepare(
+            [
+                create_at("refs/heads/new"),
+                RefEdit {
+                    change: EditChange::Update {
+                        log: Default::default(),
+                        expected: PreviousValue::MustExist,
+                        new: Target::Peeled(new_id),
+                    },
+                    name: "refs/heads/main".try_into()?,
+                    deref: false,
+                },
+                RefEdit {
+                    change: EditChange::Delete {
+                        expected: PreviousValue::MustExist,
+                        log: RefLog::AndReference,
+                    },
+                    name: "refs/heads/d1".try_into()?,
+                    deref: false,
+                },
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    assert_eq!(
+        watcher.changes(&store)?,
+        [
+            Change::Deleted {
+                name: "refs/heads/d1".try_into()?,
+                previous: main.clone(),
+            },
+            Change::Updated {
+                name: "refs/heads/main".try_into()?,
+                previous: main,
+                current: Target::Peeled(new_id),
+            },
+            Change::Created {
+                name: "refs/heads/new".try_into()?,
+                target: Target::Peeled(new_id),
+            },
+        ],
+        "changes are sorted by name, and HEAD is symbolic and thus unchanged"
+    );
+    assert_eq!(watcher.changes(&store)?, [], "changes are only reported once");
+    Ok(())
+}
+
+#[test]
+fn packing_references_is_not_a_change_but_rewriting_packed_refs_is() -> crate::Result {
+    let (_keep, store) = store_writable("make_ref_repository.sh")?;
+    let odb = gix_odb::at(store.git_dir().join("objects"))?;
+    let mut watcher = store.watch()?;
+
+    store.pack_refs(
+        &odb,
+        gix_ref::file::pack_refs::Options {
+            all: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        watcher.changes(&store)?,
+        [],
+        "references moved into packed-refs keep their values"
+    );
+
+    let packed_refs = store.git_dir().join("packed-refs");
+    let content = std::fs::read_to_string(&packed_refs)?;
+    let (kept, removed): (Vec<_>, Vec<_>) = content.lines().partition(|line| !line.ends_with(" refs/heads/d1"));
+    assert_eq!(removed.len(), 1, "the fixture has a packed d1 branch now");
+    std::fs::write(&packed_refs, kept.join("\n") + "\n")?;
+
+    let changes = watcher.changes(&store)?;
+    assert_eq!(changes.len(), 1);
+    assert!(
+        matches!(&changes[0], Change::Deleted { name, .. } if name.as_bstr() == "refs/heads/d1"),
+        "rewriting packed-refs is observed"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "notify")]
+mod notifier {
+    use std::time::Duration;
+
+    use gix_lock::acquire::Fail;
+    use gix_ref::file::watch::Change;
+
+    use crate::file::{
+        store_writable,
+        transaction::prepare_and_commit::{committer, create_at},
+    };
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn reference_changes_and_packed_refs_rewrites_are_delivered() -> crate::Result {
+        let (_keep, store) = store_writable("make_ref_repository.sh")?;
+        let mut notifier = store.watch_notified()?;
+        assert_eq!(
+            notifier.wait_timeout(Duration::from_millis(100))?,
+            None,
+            "nothing changed yet"
+        );
+
+        store
+            .transaction()
+            .prepare(Some(create_at("refs/heads/new")), Fail::Immediately, Fail::Immediately)?
+            .commit(committer().to_ref())?;
+        let changes = notifier.wait_timeout(TIMEOUT)?.expect("the creation is noticed");
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(&changes[0], Change::Created { name, .. } if name.as_bstr() == "refs/heads/new"),
+            "a new loose reference is observed"
+        );
+
+        let id = store.find("refs/heads/main")?.target.into_id();
+        std::fs::write(
+            store.git_dir().join("packed-refs"),
+            format!("# pack-refs with: peeled fully-peeled sorted \n{id} refs/heads/packed\n"),
+        )?;
+
+        let changes = notifier.wait()?;
+        assert_eq!(changes.len(), 1);
+        assert!(
+            matches!(&changes[0], Change::Created { name, .. } if name.as_bstr() == "refs/heads/packed"),
+            "writing packed-refs is observed"
+        );
+        Ok(())
+    }
+}
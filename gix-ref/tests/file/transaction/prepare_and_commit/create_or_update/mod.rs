@@ -265,6 +265,63 @@ fn namespaced_updates_or_deletions_are_transparent_and_not_observable() -> crate
     Ok(())
 }
 
+#[test]
+fn namespaced_symbolic_references_point_into_the_namespace() -> crate::Result {
+    let (dir, mut store) = empty_store()?;
+    store.namespace = gix_ref::namespace::expand("foo")?.into();
+    store
+        .transaction()
+        .prepare(
+            [
+                create_at("refs/heads/hello"),
+                create_symbolic_at("HEAD", "refs/heads/hello"),
+            ],
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+
+    assert_eq!(
+        std::fs::read(dir.path().join("refs/namespaces/foo/HEAD"))?.as_bstr(),
+        "ref: refs/namespaces/foo/refs/heads/hello\n",
+        "like git, the target is written with the namespace"
+    );
+    let head = store.find("HEAD")?;
+    assert_eq!(
+        head.target,
+        Target::Symbolic("refs/heads/hello".try_into()?),
+        "but is read without it"
+    );
+    assert_eq!(
+        head.follow(&store).expect("symbolic")?.name.as_bstr(),
+        "refs/heads/hello",
+        "and can be followed within the namespace"
+    );
+
+    let edits = store
+        .transaction()
+        .prepare(
+            Some(RefEdit {
+                change: Change::Update {
+                    log: LogChange::default(),
+                    expected: PreviousValue::MustExistAndMatch(Target::Symbolic("refs/heads/hello".try_into()?)),
+                    new: Target::Symbolic("refs/heads/main".try_into()?),
+                },
+                name: "HEAD".try_into()?,
+                deref: false,
+            }),
+            Fail::Immediately,
+            Fail::Immediately,
+        )?
+        .commit(committer().to_ref())?;
+    assert_eq!(
+        edits[0].change.previous_value(),
+        Some(Target::Symbolic("refs/heads/hello".try_into()?).to_ref()),
+        "previous values are verified and reported without namespace"
+    );
+    Ok(())
+}
+
 #[test]
 fn reference_with_must_exist_constraint_must_exist_already_with_any_value() -> crate::Result {
     let (_keep, store) = store_writable("make_repo_for_reflog.sh")?;
@@ -381,6 +438,34 @@ fn cancellation_after_preparation_leaves_no_change() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn missing_committer_fails_before_any_reference_is_changed() -> crate::Result {
+    let (_dir, store) = empty_store()?;
+
+    let tx = store.transaction().prepare(
+        [create_at("refs/heads/a"), create_at("refs/heads/b")],
+        Fail::Immediately,
+        Fail::Immediately,
+    )?;
+    assert_eq!(
+        tx.prepared_edits()
+            .map(|edit| edit.name.as_bstr().to_owned())
+            .collect::<Vec<_>>(),
+        ["refs/heads/a", "refs/heads/b"],
+        "prepared edits can be inspected before committing"
+    );
+
+    let err = tx.commit(None).unwrap_err();
+    assert!(matches!(err, transaction::commit::Error::CreateOrUpdateRefLog(_)));
+    for name in ["refs/heads/a", "refs/heads/b"] {
+        assert!(
+            store.try_find_loose(name)?.is_none(),
+            "{name}: the reflog is written first, so no reference was changed"
+        );
+    }
+    Ok(())
+}
+
 #[test]
 fn symbolic_reference_writes_reflog_if_previous_value_is_set() -> crate::Result {
     let (_keep, store) = empty_store()?;
@@ -676,6 +676,59 @@ mod writable {
     }
 }
 
+#[test]
+fn worktree_private_references_are_never_read_from_packed_refs() -> crate::Result {
+    let (store, _odb, _tmp) = worktree_store(false, "w1", Mode::Write)?;
+    let main = gix_ref::file::Store::at(
+        store.common_dir_resolved().to_owned(),
+        Default::default(),
+        Default::default(),
+        false,
+    );
+    let id = "134385f6d781b7e97062102c6a483440bfda2a03";
+    let mut packed_refs = String::from("# pack-refs with: peeled fully-peeled sorted \n");
+    for name in [
+        "refs/bisect/packed",
+        "refs/heads/packed",
+        "refs/rewritten/packed",
+        "refs/worktree/packed",
+    ] {
+        packed_refs.push_str(&format!("{id} {name}\n"));
+    }
+    std::fs::write(store.common_dir_resolved().join("packed-refs"), packed_refs)?;
+
+    for name in [
+        "refs/bisect/packed",
+        "refs/rewritten/packed",
+        "refs/worktree/packed",
+        "worktrees/w1/refs/bisect/packed",
+        "main-worktree/refs/worktree/packed",
+    ] {
+        assert!(
+            store.try_find(name)?.is_none(),
+            "{name}: private references are only looked up in their worktree"
+        );
+    }
+    assert!(store.try_find("refs/heads/packed")?.is_some(), "shared ones are found");
+    assert!(store.try_find("main-worktree/refs/heads/packed")?.is_some());
+
+    let names = |store: &gix_ref::file::Store| -> crate::Result<Vec<String>> {
+        Ok(store
+            .iter()?
+            .all()?
+            .map(|r| r.map(|r| r.name.to_string()))
+            .filter(|name| !matches!(name, Ok(name) if !name.ends_with("/packed")))
+            .collect::<Result<_, _>>()?)
+    };
+    assert_eq!(
+        names(&store)?,
+        ["refs/heads/packed"],
+        "iteration skips packed private references as well"
+    );
+    assert_eq!(names(&main)?, ["refs/heads/packed"]);
+    Ok(())
+}
+
 fn assert_reflog(store: &gix_ref::file::Store, a: Reference, b: Reference) {
     let mut arl = a.log_iter(store);
     let arl = arl.all().unwrap();
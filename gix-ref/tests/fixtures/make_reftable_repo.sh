@@ -0,0 +1,10 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q --ref-format=reftable
+
+git checkout -q -b main
+GIT_COMMITTER_DATE="1700000000 +0130" git commit -q --allow-empty -m c1
+GIT_COMMITTER_DATE="1700000001 -0500" git commit -q --allow-empty -m c2
+GIT_COMMITTER_DATE="1700000002 +0545" git branch feature
+GIT_COMMITTER_DATE="1700000003 -0930" git tag -m "annotated" v1
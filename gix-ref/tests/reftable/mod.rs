@@ -0,0 +1,725 @@
+use gix_ref::{
+    bstr::{BString, ByteSlice},
+    reftable::{RefValue, Stack, Table},
+    FullNameRef, Target,
+};
+use gix_testtools::fixture_path_standalone;
+
+use crate::hex_to_id;
+
+fn stack() -> crate::Result<Stack> {
+    Ok(Stack::at(fixture_path_standalone("reftable"))?)
+}
+
+fn name(name: &str) -> &FullNameRef {
+    name.try_into().expect("valid name")
+}
+
+fn id(byte: char) -> gix_hash::ObjectId {
+    hex_to_id(&byte.to_string().repeat(40))
+}
+
+/// Return the raw date and message of each reflog entry of `name` in `stack`, oldest first.
+fn reflog(stack: &Stack, ref_name: &str) -> crate::Result<Vec<String>> {
+    Ok(stack
+        .reflog(name(ref_name))?
+        .iter()
+        .map(|line| {
+            format!(
+                "{} {}",
+                line.signature.time.to_bstring(),
+                line.message.trim_end().as_bstr()
+            )
+        })
+        .collect())
+}
+
+/// Like [`reflog()`], but as `git` sees the reflog of `name` in the repository at `dir`.
+fn git_reflog(dir: &std::path::Path, ref_name: &str) -> crate::Result<Vec<String>> {
+    let out = std::process::Command::new("git")
+        .args(["reflog", "show", "--date=raw", "--format=%gD %gs", ref_name])
+        .current_dir(dir)
+        .output()?;
+    assert!(out.status.success(), "{}", out.stderr.as_bstr());
+    Ok(out
+        .stdout
+        .lines()
+        .rev()
+        .map(|line| {
+            let (_selector, date_and_message) = line.split_once_str("@{").expect("selector with date");
+            date_and_message.replacen("} ", " ", 1).to_str_lossy().into_owned()
+        })
+        .collect())
+}
+
+mod table {
+    use super::*;
+
+    #[test]
+    fn refs_and_logs_of_a_single_table() -> crate::Result {
+        let stack = stack()?;
+        let table = &stack.tables()[0];
+        let header = table.header();
+        assert_eq!(header.version, 1);
+        assert_eq!(header.block_size, 256);
+        assert_eq!((header.min_update_index, header.max_update_index), (1, 2));
+        assert_eq!(header.object_hash, gix_hash::Kind::Sha1);
+
+        let refs = table.refs().collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(refs.len(), 24, "the records span multiple blocks");
+        assert!(
+            refs.windows(2).all(|w| w[0].name < w[1].name),
+            "records are sorted by name"
+        );
+        assert_eq!(refs[0].name, "HEAD");
+        assert_eq!(refs[0].value, RefValue::Symbolic("refs/heads/main".into()));
+        let tag = refs.last().expect("present");
+        assert_eq!(tag.name, "refs/tags/v1");
+        assert_eq!(tag.update_index, 2, "it's relative to the minimal update index");
+        assert_eq!(
+            tag.value,
+            RefValue::Peeled {
+                target: id('4'),
+                peeled: id('1')
+            }
+        );
+
+        let logs = table
+            .logs()
+            .map(|r| r.map(|r| (r.name, r.update_index)))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            logs,
+            [
+                ("HEAD".into(), 1),
+                ("refs/heads/feature".into(), 1),
+                ("refs/heads/main".into(), 2),
+                ("refs/heads/main".into(), 1)
+            ],
+            "sorted by name and newest first"
+        );
+
+        assert_eq!(
+            table.find("refs/heads/branch-13".into())?.expect("present").value,
+            RefValue::Object(id('1'))
+        );
+        assert!(table.find("refs/heads/branch-20".into())?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn tables_without_refs() -> crate::Result {
+        let stack = stack()?;
+        let table = &stack.tables()[2];
+        assert_eq!(table.header().version, 2);
+        assert_eq!(table.refs().count(), 0);
+        let logs = table.logs().collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].update_index, 4);
+        Ok(())
+    }
+
+    #[test]
+    fn decode_errors() -> crate::Result {
+        let path = fixture_path_standalone("reftable/0x000000000003-0x000000000003-00000002.ref");
+        let data = std::fs::read(path)?;
+        assert!(Table::from_bytes(data.clone()).is_ok());
+
+        let mut corrupt = data.clone();
+        *corrupt.last_mut().expect("not empty") ^= 1;
+        assert!(matches!(
+            Table::from_bytes(corrupt),
+            Err(gix_ref::reftable::decode::Error::FooterChecksum { .. })
+        ));
+
+        let mut corrupt = data.clone();
+        corrupt[0] = b'X';
+        assert!(matches!(
+            Table::from_bytes(corrupt),
+            Err(gix_ref::reftable::decode::Error::Magic)
+        ));
+
+        let mut corrupt = data.clone();
+        corrupt[4] = 3;
+        assert!(matches!(
+            Table::from_bytes(corrupt),
+            Err(gix_ref::reftable::decode::Error::UnsupportedVersion { version: 3 })
+        ));
+
+        assert!(matches!(
+            Table::from_bytes(data[..50].to_vec()),
+            Err(gix_ref::reftable::decode::Error::Truncated)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_log_block() -> crate::Result {
+        let path = fixture_path_standalone("reftable/0x000000000003-0x000000000003-00000002.ref");
+        let data = std::fs::read(path)?;
+        let (footer_len, log_position) = (68, 122);
+        assert_eq!(data[log_position], b'g', "the log block starts here");
+
+        // Keep only part of the header of the log block, so its compressed records would start past the end of all blocks.
+        let mut truncated = data[..log_position + 2].to_vec();
+        truncated.extend_from_slice(&data[data.len() - footer_len..]);
+        let table = Table::from_bytes(truncated)?;
+        assert_eq!(table.refs().filter(Result::is_ok).count(), 3, "refs are still readable");
+        assert!(matches!(
+            table.logs().collect::<Result<Vec<_>, _>>(),
+            Err(gix_ref::reftable::decode::Error::Truncated)
+        ));
+        Ok(())
+    }
+}
+
+mod stack {
+    use super::*;
+
+    #[test]
+    fn references_are_merged_with_newer_tables_taking_precedence() -> crate::Result {
+        let stack = stack()?;
+        assert_eq!(stack.tables().len(), 3);
+        let refs = stack.references()?;
+        assert_eq!(refs.len(), 24, "one ref was added, and one was deleted");
+        assert!(
+            refs.iter().all(|r| r.name.as_bstr() != "refs/heads/feature"),
+            "deletions hide references in older tables"
+        );
+
+        let main = refs
+            .iter()
+            .find(|r| r.name.as_bstr() == "refs/heads/main")
+            .expect("present");
+        assert_eq!(main.target, Target::Peeled(id('3')), "the newer table wins");
+        assert_eq!(refs[0].target, Target::Symbolic(name("refs/heads/main").to_owned()));
+        let tag = refs.last().expect("present");
+        assert_eq!(tag.target, Target::Peeled(id('4')));
+        assert_eq!(tag.peeled, Some(id('1')));
+        Ok(())
+    }
+
+    #[test]
+    fn find() -> crate::Result {
+        let stack = stack()?;
+        assert_eq!(
+            stack.find(name("refs/heads/main"))?.expect("present").target,
+            Target::Peeled(id('3'))
+        );
+        assert_eq!(
+            stack.find(name("refs/heads/branch-00"))?.expect("present").target,
+            Target::Peeled(id('1')),
+            "found in older tables"
+        );
+        assert_eq!(
+            stack.find(name("HEAD"))?.expect("present").target,
+            Target::Symbolic(name("refs/heads/main").to_owned())
+        );
+        assert!(stack.find(name("refs/heads/feature"))?.is_none(), "deleted");
+        assert!(stack.find(name("refs/heads/missing"))?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn reflogs() -> crate::Result {
+        let stack = stack()?;
+        let lines = stack.reflog(name("refs/heads/main"))?;
+        assert_eq!(
+            lines
+                .iter()
+                .map(|l| (l.previous_oid, l.new_oid, l.message.to_str().expect("ascii")))
+                .collect::<Vec<_>>(),
+            [
+                (gix_hash::Kind::Sha1.null(), id('1'), "commit (initial): c1\n"),
+                (id('1'), id('1'), "reset: moving to HEAD\n"),
+                (id('1'), id('3'), "commit: c2\n"),
+                (id('3'), id('3'), "reset: moving to HEAD\n"),
+            ],
+            "oldest first, from all tables"
+        );
+        let signature = &lines[1].signature;
+        assert_eq!(signature.name, "A U Thor");
+        assert_eq!(signature.email, "author@example.com");
+        assert_eq!(signature.time, gix_date::Time::new(1700000002, -5 * 3600));
+        assert_eq!(lines[0].signature.time.offset, 90 * 60, "`+0130` is stored as `130`");
+
+        assert!(
+            stack.reflog(name("refs/heads/feature"))?.is_empty(),
+            "log records can be deleted as well"
+        );
+        assert_eq!(stack.reflog_names()?, [BString::from("HEAD"), "refs/heads/main".into()]);
+        Ok(())
+    }
+
+    #[test]
+    fn reflogs_written_by_git() -> crate::Result {
+        if gix_testtools::should_skip_as_git_version_is_smaller_than(2, 45, 0) {
+            return Ok(());
+        }
+        let dir = gix_testtools::scripted_fixture_read_only_standalone("make_reftable_repo.sh")?;
+        let stack = Stack::at(dir.join(".git").join("reftable"))?;
+        for ref_name in ["HEAD", "refs/heads/main", "refs/heads/feature"] {
+            assert_eq!(reflog(&stack, ref_name)?, git_reflog(&dir, ref_name)?, "{ref_name}");
+        }
+        let offsets = stack
+            .reflog(name("refs/heads/main"))?
+            .iter()
+            .map(|line| line.signature.time.offset)
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, [90 * 60, -5 * 3600], "`git` stores `+0130` as `130`");
+        assert!(stack.find(name("refs/tags/v1"))?.is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn missing_stack() {
+        assert!(matches!(
+            Stack::at(fixture_path_standalone("does-not-exist")),
+            Err(gix_ref::reftable::stack::Error::Io { .. })
+        ));
+    }
+}
+
+mod file_store {
+    use gix_ref::{file::ReferenceExt, store::WriteReflog};
+
+    use super::*;
+
+    fn store() -> crate::Result<(gix_testtools::tempfile::TempDir, gix_ref::file::Store)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        let stack_dir = dir.path().join("reftable");
+        std::fs::create_dir(&stack_dir)?;
+        for entry in std::fs::read_dir(fixture_path_standalone("reftable"))? {
+            let entry = entry?;
+            std::fs::copy(entry.path(), stack_dir.join(entry.file_name()))?;
+        }
+        // `git` writes this to keep older versions of `git` from failing to detect the repository.
+        std::fs::write(dir.path().join("HEAD"), "ref: refs/heads/.invalid\n")?;
+        let mut store = gix_ref::file::Store::at(dir.path().into(), WriteReflog::Normal, gix_hash::Kind::Sha1, false);
+        store.use_reftable();
+        Ok((dir, store))
+    }
+
+    #[test]
+    fn find_and_iterate() -> crate::Result {
+        let (dir, store) = store()?;
+        assert!(store.is_reftable());
+        assert_eq!(
+            store.find("HEAD")?.target,
+            Target::Symbolic(name("refs/heads/main").to_owned()),
+            "the `HEAD` file is ignored"
+        );
+        assert_eq!(store.find("main")?.target, Target::Peeled(id('3')));
+        assert!(store.try_find("feature")?.is_none(), "deleted");
+
+        let refs = store.iter()?.all()?.collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(refs.len(), 23, "all references but `HEAD`");
+        assert!(refs.windows(2).all(|w| w[0].name < w[1].name), "sorted by name");
+        let tags = store
+            .iter()?
+            .prefixed("refs/tags/".as_ref())?
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].peeled, Some(id('1')));
+
+        std::fs::write(dir.path().join("MERGE_HEAD"), format!("{}\n", id('2')))?;
+        assert_eq!(
+            store.find("MERGE_HEAD")?.target,
+            Target::Peeled(id('2')),
+            "pseudo-refs are still stored in files"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn reflogs() -> crate::Result {
+        let (_dir, store) = store()?;
+        let main = store.find("main")?;
+        assert!(main.log_exists(&store));
+        let messages = main
+            .log_iter(&store)
+            .all()?
+            .expect("log exists")
+            .map(|line| line.map(|line| line.message.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            messages,
+            [
+                "commit (initial): c1",
+                "reset: moving to HEAD",
+                "commit: c2",
+                "reset: moving to HEAD"
+            ]
+        );
+        let newest_first = main
+            .log_iter(&store)
+            .rev()?
+            .expect("log exists")
+            .map(|line| line.map(|line| line.message.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(newest_first, messages.into_iter().rev().collect::<Vec<_>>());
+
+        assert!(
+            !store.reflog_exists("refs/heads/feature")?,
+            "deleted along with the reference"
+        );
+        let mut buf = Vec::new();
+        assert!(store.reflog_iter("refs/heads/feature", &mut buf)?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn transactions_are_refused() -> crate::Result {
+        let (_dir, store) = store()?;
+        let err = store
+            .transaction()
+            .prepare(
+                None,
+                gix_lock::acquire::Fail::Immediately,
+                gix_lock::acquire::Fail::Immediately,
+            )
+            .expect_err("writing reftables through the file store isn't supported");
+        assert!(matches!(err, gix_ref::file::transaction::prepare::Error::Reftable));
+        Ok(())
+    }
+}
+
+mod transaction {
+    use gix_lock::acquire::Fail;
+    use gix_ref::{
+        reftable::transaction::{Error, Options},
+        transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    };
+
+    use super::*;
+
+    fn empty_stack() -> crate::Result<(gix_testtools::tempfile::TempDir, Stack)> {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        std::fs::write(dir.path().join("tables.list"), "")?;
+        let stack = Stack::at(dir.path())?;
+        Ok((dir, stack))
+    }
+
+    fn committer() -> gix_actor::Signature {
+        gix_actor::Signature {
+            name: "committer".into(),
+            email: "committer@example.com".into(),
+            time: gix_date::Time::new(1234, 1800),
+        }
+    }
+
+    fn update(name: &str, new: Target, expected: PreviousValue, deref: bool) -> RefEdit {
+        RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    message: "an update".into(),
+                    ..Default::default()
+                },
+                expected,
+                new,
+            },
+            name: name.try_into().expect("valid"),
+            deref,
+        }
+    }
+
+    fn num_table_files(dir: &std::path::Path) -> crate::Result<usize> {
+        Ok(std::fs::read_dir(dir)?
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .map_or(true, |e| e.path().extension() == Some("ref".as_ref()))
+            })
+            .count())
+    }
+
+    #[test]
+    fn updates_through_symbolic_refs_and_reflogs() -> crate::Result {
+        let (dir, mut stack) = empty_stack()?;
+        let committer = committer();
+        let options = Options {
+            auto_compaction: false,
+            ..Default::default()
+        };
+        let edits = stack.commit(
+            [
+                update(
+                    "refs/heads/main",
+                    Target::Peeled(id('1')),
+                    PreviousValue::MustNotExist,
+                    false,
+                ),
+                update(
+                    "HEAD",
+                    Target::Symbolic(name("refs/heads/main").to_owned()),
+                    PreviousValue::Any,
+                    false,
+                ),
+            ],
+            Some(committer.to_ref()),
+            Fail::Immediately,
+            options,
+        )?;
+        assert_eq!(edits.len(), 2);
+        assert_eq!(stack.tables().len(), 1);
+
+        let edits = stack.commit(
+            Some(update(
+                "HEAD",
+                Target::Peeled(id('2')),
+                PreviousValue::MustExistAndMatch(Target::Peeled(id('1'))),
+                true,
+            )),
+            Some(committer.to_ref()),
+            Fail::Immediately,
+            options,
+        )?;
+        assert_eq!(edits.len(), 2, "HEAD was split into an update of its referent");
+        assert_eq!(
+            edits[1].change.previous_value(),
+            Some(gix_ref::TargetRef::Peeled(&id('1'))),
+            "the previous value is returned"
+        );
+
+        let stack = Stack::at(dir.path())?;
+        assert_eq!(stack.tables().len(), 2);
+        assert_eq!(stack.tables()[1].header().min_update_index, 2);
+        assert_eq!(
+            stack.find(name("HEAD"))?.expect("present").target,
+            Target::Symbolic(name("refs/heads/main").to_owned()),
+            "symbolic refs stay untouched when dereferenced"
+        );
+        assert_eq!(
+            stack.find(name("refs/heads/main"))?.expect("present").target,
+            Target::Peeled(id('2'))
+        );
+
+        let log = stack.reflog(name("refs/heads/main"))?;
+        assert_eq!(
+            log.iter().map(|l| (l.previous_oid, l.new_oid)).collect::<Vec<_>>(),
+            [(gix_hash::Kind::Sha1.null(), id('1')), (id('1'), id('2'))]
+        );
+        assert_eq!(log[0].signature, committer);
+        assert_eq!(log[0].message, "an update\n");
+        let log = stack.reflog(name("HEAD"))?;
+        assert_eq!(
+            log.iter().map(|l| (l.previous_oid, l.new_oid)).collect::<Vec<_>>(),
+            [(id('1'), id('2'))],
+            "symbolic refs get a reflog for changes of their referent only"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn deletions_remove_refs_and_their_logs() -> crate::Result {
+        let (dir, mut stack) = empty_stack()?;
+        let committer = committer();
+        stack.commit(
+            Some(update(
+                "refs/heads/a",
+                Target::Peeled(id('1')),
+                PreviousValue::Any,
+                false,
+            )),
+            Some(committer.to_ref()),
+            Fail::Immediately,
+            Default::default(),
+        )?;
+        let edits = stack.commit(
+            Some(RefEdit {
+                change: Change::Delete {
+                    expected: PreviousValue::MustExist,
+                    log: RefLog::AndReference,
+                },
+                name: "refs/heads/a".try_into()?,
+                deref: false,
+            }),
+            Some(committer.to_ref()),
+            Fail::Immediately,
+            Options {
+                auto_compaction: false,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            edits[0].change.previous_value(),
+            Some(gix_ref::TargetRef::Peeled(&id('1')))
+        );
+        assert!(stack.find(name("refs/heads/a"))?.is_none());
+        assert!(stack.reflog(name("refs/heads/a"))?.is_empty());
+        assert!(stack.reflog_names()?.is_empty());
+
+        stack.compact(Fail::Immediately, Default::default())?;
+        assert_eq!(stack.tables().len(), 1);
+        assert_eq!(stack.tables()[0].refs().count(), 0, "deletions were dropped");
+        assert_eq!(stack.tables()[0].logs().count(), 0);
+        assert_eq!(num_table_files(dir.path())?, 1, "compacted tables are removed");
+        Ok(())
+    }
+
+    #[test]
+    fn preconditions_are_checked_before_writing() -> crate::Result {
+        let (dir, mut stack) = empty_stack()?;
+        stack.commit(
+            Some(update(
+                "refs/heads/a",
+                Target::Peeled(id('1')),
+                PreviousValue::Any,
+                false,
+            )),
+            None,
+            Fail::Immediately,
+            Default::default(),
+        )?;
+
+        let err = stack
+            .commit(
+                Some(update(
+                    "refs/heads/a",
+                    Target::Peeled(id('2')),
+                    PreviousValue::MustNotExist,
+                    false,
+                )),
+                None,
+                Fail::Immediately,
+                Default::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::MustNotExist { .. }));
+        let err = stack
+            .commit(
+                Some(update(
+                    "refs/heads/a",
+                    Target::Peeled(id('2')),
+                    PreviousValue::MustExistAndMatch(Target::Peeled(id('3'))),
+                    false,
+                )),
+                None,
+                Fail::Immediately,
+                Default::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::ReferenceOutOfDate { .. }));
+        let err = stack
+            .commit(
+                Some(update(
+                    "refs/heads/b",
+                    Target::Peeled(id('2')),
+                    PreviousValue::MustExist,
+                    false,
+                )),
+                None,
+                Fail::Immediately,
+                Default::default(),
+            )
+            .unwrap_err();
+        assert!(matches!(err, Error::MustExist { .. }));
+
+        let stack = Stack::at(dir.path())?;
+        assert_eq!(stack.tables().len(), 1, "nothing was written");
+        assert_eq!(num_table_files(dir.path())?, 1);
+        assert!(!dir.path().join("tables.list.lock").exists(), "the lock was released");
+        Ok(())
+    }
+
+    #[test]
+    fn auto_compaction_keeps_the_amount_of_tables_small() -> crate::Result {
+        let (dir, mut stack) = empty_stack()?;
+        let committer = committer();
+        for round in 0..20u8 {
+            stack.commit(
+                Some(update(
+                    &format!("refs/heads/branch-{round:02}"),
+                    Target::Peeled(id('1')),
+                    PreviousValue::MustNotExist,
+                    false,
+                )),
+                Some(committer.to_ref()),
+                Fail::Immediately,
+                Default::default(),
+            )?;
+            assert!(stack.tables().len() <= 5, "tables are merged as they are added");
+        }
+        let stack = Stack::at(dir.path())?;
+        assert_eq!(num_table_files(dir.path())?, stack.tables().len());
+        assert_eq!(stack.references()?.len(), 20);
+        assert_eq!(stack.reflog_names()?.len(), 20);
+        assert_eq!(stack.tables().last().expect("present").header().max_update_index, 20);
+        Ok(())
+    }
+
+    #[test]
+    fn tables_written_alongside_those_of_git_are_readable_by_git() -> crate::Result {
+        if gix_testtools::should_skip_as_git_version_is_smaller_than(2, 45, 0) {
+            return Ok(());
+        }
+        let dir = gix_testtools::scripted_fixture_writable_standalone("make_reftable_repo.sh")?;
+        let mut stack = Stack::at(dir.path().join(".git").join("reftable"))?;
+        let expected = ["HEAD", "refs/heads/main", "refs/heads/feature"]
+            .into_iter()
+            .map(|ref_name| git_reflog(dir.path(), ref_name))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        stack.compact(Fail::Immediately, Default::default())?;
+        for (ref_name, expected) in ["HEAD", "refs/heads/main", "refs/heads/feature"].iter().zip(expected) {
+            assert_eq!(
+                git_reflog(dir.path(), ref_name)?,
+                expected,
+                "{ref_name}: `git` sees the same timezones after we rewrote its tables"
+            );
+        }
+
+        let main = stack.find(name("refs/heads/main"))?.expect("present");
+        let mut committer = committer();
+        committer.time = gix_date::Time::new(1700000010, -(9 * 3600 + 30 * 60));
+        stack.commit(
+            Some(update(
+                "refs/heads/new",
+                main.target.clone(),
+                PreviousValue::MustNotExist,
+                false,
+            )),
+            Some(committer.to_ref()),
+            Fail::Immediately,
+            Default::default(),
+        )?;
+        assert_eq!(
+            git_reflog(dir.path(), "refs/heads/new")?,
+            ["1700000010 -0930 an update"]
+        );
+        assert_eq!(reflog(&stack, "refs/heads/new")?, ["1700000010 -0930 an update"]);
+        Ok(())
+    }
+
+    #[test]
+    fn full_compaction_preserves_all_refs_and_logs() -> crate::Result {
+        let dir = gix_testtools::tempfile::tempdir()?;
+        for entry in std::fs::read_dir(fixture_path_standalone("reftable"))? {
+            let entry = entry?;
+            std::fs::copy(entry.path(), dir.path().join(entry.file_name()))?;
+        }
+        let mut stack = Stack::at(dir.path())?;
+        let refs = stack.references()?;
+        let log = stack.reflog(name("refs/heads/main"))?;
+
+        stack.compact(
+            Fail::Immediately,
+            gix_ref::reftable::encode::Options {
+                block_size: 128,
+                restart_interval: 3,
+            },
+        )?;
+        let stack = Stack::at(dir.path())?;
+        assert_eq!(stack.tables().len(), 1);
+        let header = stack.tables()[0].header();
+        assert_eq!((header.min_update_index, header.max_update_index), (1, 4));
+        assert_eq!(stack.references()?, refs);
+        assert_eq!(stack.reflog(name("refs/heads/main"))?, log);
+        assert_eq!(
+            stack.tables()[0].find("refs/heads/feature".into())?,
+            None,
+            "deletions are dropped"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,231 @@
+bitflags::bitflags! {
+    /// The flags used in the graph for [bisecting](crate::bisect()).
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    pub struct Flags: u8 {
+        /// The commit is reachable from the bad commit.
+        const BAD = 1 << 0;
+        /// The commit is reachable from one of the good commits.
+        const GOOD = 1 << 1;
+    }
+}
+
+/// The error returned by the [`bisect()`][function::bisect()] function.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+    #[error("The bad commit {bad} is reachable from a good commit, so there is nothing to bisect")]
+    BadIsGood { bad: gix_hash::ObjectId },
+}
+
+/// The result of [bisecting](crate::bisect()).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// The commit at `id` should be tested next to be marked as good or bad.
+    Next {
+        /// The id of the commit to test.
+        id: gix_hash::ObjectId,
+        /// The amount of commits that are left to test after this one, as reported by `git bisect`.
+        remaining: usize,
+        /// The estimated amount of steps needed to find the first bad commit after this one.
+        steps: usize,
+    },
+    /// The bad commit is the first bad commit, as all of its parents are known to be good.
+    FirstBad(gix_hash::ObjectId),
+    /// All commits that are left to test were skipped, so the first bad commit is any of the given `candidates`,
+    /// which includes the bad commit.
+    OnlySkippedLeft {
+        /// The commits that might be the first bad commit, from the oldest to the youngest.
+        candidates: Vec<gix_hash::ObjectId>,
+    },
+}
+
+pub(crate) mod function {
+    use gix_hash::ObjectId;
+    use gix_revwalk::graph;
+
+    use super::{Error, Flags, Outcome};
+    use crate::{
+        merge_base::function::{priority, Priority},
+        Graph, PriorityQueue,
+    };
+
+    /// Given the `bad` commit and the `good` ones, traverse the commit `graph` to find the commit that splits the commits
+    /// which are reachable from `bad` but not from `good` into halves, and return it as the next commit to test, like
+    /// `git bisect` does. Commits in `skip` are never chosen.
+    ///
+    /// Generation numbers from the commit-graph of `graph` are used to visit commits in topological order if available,
+    /// and commit times are used otherwise.
+    ///
+    /// ### Deviation
+    ///
+    /// * `git` checks if merge-bases of `bad` and `good` commits were tested, which is left to the caller.
+    /// * If the best commit to test was skipped, `git` chooses one at a pseudo-random distance from it, while we choose
+    ///   the best commit of the ones that weren't skipped.
+    pub fn bisect(
+        bad: ObjectId,
+        good: &[ObjectId],
+        skip: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Outcome, Error> {
+        let _span = gix_trace::coarse!("gix_revision::bisect()", ?bad, ?good, ?skip);
+        let candidates = candidates(bad, good, graph)?;
+        if candidates.is_empty() {
+            return Err(Error::BadIsGood { bad });
+        }
+        if candidates.len() == 1 {
+            return Ok(Outcome::FirstBad(bad));
+        }
+
+        let weights = weights(&candidates, graph);
+        let all = candidates.len();
+        let best = candidates
+            .iter()
+            .zip(&weights)
+            .filter(|(id, _)| **id != bad && !skip.contains(id))
+            .fold(None, |best: Option<(ObjectId, usize)>, (id, weight)| {
+                let distance = |weight: usize| weight.min(all - weight);
+                match best {
+                    Some((_, best_weight)) if distance(best_weight) >= distance(*weight) => best,
+                    _ => Some((*id, *weight)),
+                }
+            });
+        Ok(match best {
+            Some((id, weight)) => Outcome::Next {
+                id,
+                remaining: all - weight - 1,
+                steps: estimate_steps(all),
+            },
+            None => Outcome::OnlySkippedLeft { candidates },
+        })
+    }
+
+    /// Return all commits that are reachable from `bad` but not from `good`, ordered by their commit time from the
+    /// oldest to the youngest so ties are resolved just like in `git`.
+    fn candidates(
+        bad: ObjectId,
+        good: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        let mut seen = Vec::new();
+        for (id, flag) in Some((bad, Flags::BAD))
+            .into_iter()
+            .chain(good.iter().map(|id| (*id, Flags::GOOD)))
+        {
+            let commit = graph.try_lookup_or_insert_commit(id, |flags| {
+                if flags.is_empty() {
+                    seen.push(id);
+                }
+                *flags |= flag;
+            })?;
+            if commit.is_some() {
+                queue.insert(priority(graph, &id), id);
+            }
+        }
+
+        while queue.iter_unordered().any(|id| !graph[id].data.contains(Flags::GOOD)) {
+            let id = queue.pop_value().expect("not empty");
+            let commit = graph.get(&id).expect("queued commits are in the graph");
+            let flags = commit.data;
+            for parent_id in commit.parents.clone() {
+                let mut was_modified = false;
+                let parent = graph.try_lookup_or_insert_commit(parent_id, |parent_flags| {
+                    if parent_flags.is_empty() {
+                        seen.push(parent_id);
+                    }
+                    if !parent_flags.contains(flags) {
+                        *parent_flags |= flags;
+                        was_modified = true;
+                    }
+                })?;
+                if parent.is_some() && was_modified {
+                    queue.insert(priority(graph, &parent_id), parent_id);
+                }
+            }
+        }
+
+        let mut candidates: Vec<_> = seen.into_iter().filter(|id| graph[id].data == Flags::BAD).collect();
+        candidates.sort_by_key(|id| graph[id].commit_time);
+        Ok(candidates)
+    }
+
+    /// Return the amount of `candidates` that are reachable from each candidate, including itself.
+    fn weights(candidates: &[ObjectId], graph: &Graph<'_, graph::Commit<Flags>>) -> Vec<usize> {
+        let index: gix_hashtable::HashMap<ObjectId, usize> =
+            candidates.iter().enumerate().map(|(idx, id)| (*id, idx)).collect();
+        let parents: Vec<Vec<usize>> = candidates
+            .iter()
+            .map(|id| {
+                graph[id]
+                    .parents
+                    .iter()
+                    .filter_map(|parent_id| index.get(parent_id).copied())
+                    .collect()
+            })
+            .collect();
+
+        // Visit parents before their children, which allows to derive the weight of commits with a single parent from it.
+        let mut order = Vec::with_capacity(candidates.len());
+        let mut is_visited = vec![false; candidates.len()];
+        for root in 0..candidates.len() {
+            let mut stack = vec![(root, false)];
+            while let Some((idx, parents_are_done)) = stack.pop() {
+                if parents_are_done {
+                    order.push(idx);
+                    continue;
+                }
+                if std::mem::replace(&mut is_visited[idx], true) {
+                    continue;
+                }
+                stack.push((idx, true));
+                stack.extend(
+                    parents[idx]
+                        .iter()
+                        .filter(|idx| !is_visited[**idx])
+                        .map(|idx| (*idx, false)),
+                );
+            }
+        }
+
+        let mut weights = vec![0; candidates.len()];
+        let mut visited_by = vec![usize::MAX; candidates.len()];
+        for merge in order {
+            weights[merge] = match parents[merge].as_slice() {
+                [] => 1,
+                [parent] => weights[*parent] + 1,
+                _ => {
+                    // The ancestries of parents of merges overlap, so they have to be counted.
+                    let mut count = 0;
+                    let mut stack = vec![merge];
+                    while let Some(idx) = stack.pop() {
+                        if std::mem::replace(&mut visited_by[idx], merge) == merge {
+                            continue;
+                        }
+                        count += 1;
+                        stack.extend(parents[idx].iter().copied());
+                    }
+                    count
+                }
+            };
+        }
+        weights
+    }
+
+    /// Estimate the amount of steps needed to find the first bad commit among `all` candidates, just like `git` does.
+    fn estimate_steps(all: usize) -> usize {
+        if all < 3 {
+            return 0;
+        }
+        let n = (usize::BITS - 1 - all.leading_zeros()) as usize;
+        let e = 1 << n;
+        let x = all - e;
+        if e < 3 * x {
+            n
+        } else {
+            n - 1
+        }
+    }
+}
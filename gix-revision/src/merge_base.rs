@@ -0,0 +1,305 @@
+bitflags::bitflags! {
+    /// The flags used in the graph for finding [merge bases](crate::merge_base()).
+    #[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+    pub struct Flags: u8 {
+        /// The commit is reachable from the first commit.
+        const COMMIT1 = 1 << 0;
+        /// The commit is reachable from one of the other commits.
+        const COMMIT2 = 1 << 1;
+        /// The commit and all of its ancestors are known to be irrelevant for the result.
+        const STALE = 1 << 2;
+        /// The commit was added to the result list already.
+        const RESULT = 1 << 3;
+    }
+}
+
+/// The error returned by the [`merge_base()`][function::merge_base()] function.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A commit could not be inserted into the graph")]
+    InsertCommit(#[from] gix_revwalk::graph::try_lookup_or_insert_default::Error),
+}
+
+pub use function::{ahead_behind, independent, is_ancestor, octopus};
+
+pub(crate) mod function {
+    use gix_hash::ObjectId;
+    use gix_revwalk::graph;
+
+    use super::{Error, Flags};
+    use crate::{Graph, PriorityQueue};
+
+    /// Given a commit at `first` id, traverse the commit `graph` and return all possible merge-base between it and `others`,
+    /// sorted from best to worst. Returns `None` if there is no merge-base as `first` and `others` don't share history.
+    /// If `others` is empty, `Some(first)` is returned.
+    ///
+    /// Note that this function doesn't do any work if `first` is contained in `others`, which is when `first` will be returned
+    /// as only merge-base right away. This is even the case if some commits of `others` are disjoint.
+    ///
+    /// With more than one commit in `others`, the merge-bases are the ones of `first` and a hypothetical merge of all `others`,
+    /// just like `git merge-base` would see it.
+    pub fn merge_base(
+        first: ObjectId,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Option<Vec<ObjectId>>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base()", ?first, ?others);
+        if others.is_empty() || others.contains(&first) {
+            return Ok(Some(vec![first]));
+        }
+
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        let bases = paint_down_to_common(first, others, graph)?;
+        let bases: Vec<_> = bases
+            .into_iter()
+            .filter(|id| matches!(graph.get(id), Some(commit) if !commit.data.contains(Flags::STALE)))
+            .collect();
+        let bases = remove_redundant(&bases, graph)?;
+        Ok((!bases.is_empty()).then_some(bases))
+    }
+
+    /// Find the best merge-bases of all `commits` as if they were merged all at once in an octopus merge, like
+    /// `git merge-base --octopus --all` does, sorted from best to worst. Returns `None` if there is no merge-base as
+    /// not all `commits` share history, or if there are no `commits`.
+    ///
+    /// This is done by finding the merge-bases of the first two commits, and then the ones between these and each of the
+    /// following commits.
+    pub fn octopus(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Option<Vec<ObjectId>>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::octopus()", ?commits);
+        let Some((first, rest)) = commits.split_first() else {
+            return Ok(None);
+        };
+        let mut bases = vec![*first];
+        for commit in rest {
+            let mut next_bases = Vec::new();
+            for base in &bases {
+                next_bases.extend(merge_base(*commit, &[*base], graph)?.unwrap_or_default());
+            }
+            bases = next_bases;
+        }
+        let bases = independent(&bases, graph)?;
+        Ok((!bases.is_empty()).then_some(bases))
+    }
+
+    /// Reduce `commits` to those which can't be reached from any other commit in `commits`, like
+    /// `git merge-base --independent` does, while retaining their order. Duplicates are removed as well.
+    pub fn independent(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::independent()", ?commits);
+        let mut unique = Vec::with_capacity(commits.len());
+        for id in commits {
+            if !unique.contains(id) {
+                unique.push(*id);
+            }
+        }
+        remove_redundant(&unique, graph)
+    }
+
+    /// Count the commits reachable from `local` but not from `upstream`, and the ones reachable from `upstream` but not
+    /// from `local`, and return them as `(ahead, behind)` just like `git rev-list --left-right --count local...upstream` would.
+    ///
+    /// The traversal stops as soon as only commits reachable from both are left, so histories aren't walked entirely.
+    /// Generation numbers from the commit-graph of `graph` are used to visit commits in topological order if available,
+    /// preferring corrected commit dates, and commit times are used otherwise, which can yield incorrect results if clocks
+    /// were skewed.
+    pub fn ahead_behind(
+        local: ObjectId,
+        upstream: ObjectId,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<(usize, usize), Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::ahead_behind()", ?local, ?upstream);
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        let mut seen = Vec::new();
+        for (id, flag) in [(local, Flags::COMMIT1), (upstream, Flags::COMMIT2)] {
+            let commit = graph.try_lookup_or_insert_commit(id, |flags| {
+                if flags.is_empty() {
+                    seen.push(id);
+                }
+                *flags |= flag;
+            })?;
+            if commit.is_some() {
+                queue.insert(priority(graph, &id), id);
+            }
+        }
+
+        let both = Flags::COMMIT1 | Flags::COMMIT2;
+        while queue.iter_unordered().any(|id| graph[id].data != both) {
+            let id = queue.pop_value().expect("not empty");
+            let commit = graph.get(&id).expect("queued commits are in the graph");
+            let flags = commit.data;
+            for parent_id in commit.parents.clone() {
+                let mut was_modified = false;
+                let parent = graph.try_lookup_or_insert_commit(parent_id, |parent_flags| {
+                    if parent_flags.is_empty() {
+                        seen.push(parent_id);
+                    }
+                    if !parent_flags.contains(flags) {
+                        *parent_flags |= flags;
+                        was_modified = true;
+                    }
+                })?;
+                if parent.is_some() && was_modified {
+                    queue.insert(priority(graph, &parent_id), parent_id);
+                }
+            }
+        }
+
+        Ok(seen.iter().fold((0, 0), |(ahead, behind), id| match graph[id].data {
+            Flags::COMMIT1 => (ahead + 1, behind),
+            Flags::COMMIT2 => (ahead, behind + 1),
+            _ => (ahead, behind),
+        }))
+    }
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, or is `descendant`, which is what's needed to answer
+    /// `git tag --contains` or `git merge-base --is-ancestor` style queries.
+    ///
+    /// Generation numbers from the commit-graph of `graph` are used to avoid traversing past commits which are older than
+    /// `ancestor`, which is correct even if clocks were skewed. Without them, the history of `descendant` may be walked
+    /// entirely.
+    pub fn is_ancestor(
+        ancestor: ObjectId,
+        descendant: ObjectId,
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<bool, Error> {
+        let _span = gix_trace::coarse!("gix_revision::merge_base::is_ancestor()", ?ancestor, ?descendant);
+        if ancestor == descendant {
+            return Ok(true);
+        }
+        graph.clear_commit_data(|flags| *flags = Flags::empty());
+        if graph.try_lookup_or_insert_commit(ancestor, |_| {})?.is_none() {
+            return Ok(false);
+        }
+        let cutoff = priority(graph, &ancestor).0;
+
+        let mut queue = vec![descendant];
+        while let Some(id) = queue.pop() {
+            let mut was_seen = false;
+            let commit = graph.try_lookup_or_insert_commit(id, |flags| {
+                was_seen = flags.contains(Flags::COMMIT2);
+                *flags |= Flags::COMMIT2;
+            })?;
+            if commit.is_none() || was_seen || priority(graph, &id).0 < cutoff {
+                continue;
+            }
+            for parent_id in graph[&id].parents.clone() {
+                if parent_id == ancestor {
+                    return Ok(true);
+                }
+                queue.push(parent_id);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Commits are ordered by their generation, preferring corrected commit dates over topological levels, and then by
+    /// their commit time.
+    pub(crate) type Priority = (gix_date::SecondsSinceUnixEpoch, gix_date::SecondsSinceUnixEpoch);
+
+    /// Return the priority of the commit named `id` in `graph` to visit commits from the youngest to the oldest.
+    ///
+    /// Commits that aren't in the commit-graph are newer than all commits in it, and are ordered by time among each other.
+    pub(crate) fn priority<T>(graph: &Graph<'_, graph::Commit<T>>, id: &gix_hash::oid) -> Priority {
+        let commit = &graph[id];
+        let generation = graph
+            .corrected_commit_date(id)
+            .or(commit.generation.map(Into::into))
+            .unwrap_or(gix_date::SecondsSinceUnixEpoch::MAX);
+        (generation, commit.commit_time)
+    }
+
+    /// Remove all those commits from `commits` if they are in the history of another commit in `commits`.
+    /// That way, we return only the topologically most recent commits in `commits`.
+    fn remove_redundant(
+        commits: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        if commits.len() < 2 {
+            return Ok(commits.to_vec());
+        }
+        let mut redundant = vec![false; commits.len()];
+        for (idx, id) in commits.iter().enumerate() {
+            if redundant[idx] {
+                continue;
+            }
+            let (other_indices, others): (Vec<_>, Vec<_>) = commits
+                .iter()
+                .enumerate()
+                .filter(|(other_idx, _)| *other_idx != idx && !redundant[*other_idx])
+                .map(|(other_idx, other)| (other_idx, *other))
+                .unzip();
+
+            graph.clear_commit_data(|flags| *flags = Flags::empty());
+            paint_down_to_common(*id, &others, graph)?;
+            if graph[id].data.contains(Flags::COMMIT2) {
+                redundant[idx] = true;
+            }
+            for (other_idx, other) in other_indices.into_iter().zip(others) {
+                if graph[&other].data.contains(Flags::COMMIT1) {
+                    redundant[other_idx] = true;
+                }
+            }
+        }
+        Ok(commits
+            .iter()
+            .zip(redundant)
+            .filter_map(|(id, is_redundant)| (!is_redundant).then_some(*id))
+            .collect())
+    }
+
+    /// Mark all commits reachable from `first` and from `others` with their respective flags, and return all commits
+    /// that are reachable from both, in the order in which they were found, while not traversing past them.
+    ///
+    /// Commits are visited from the youngest to the oldest, using generation numbers if available just like `git` does,
+    /// which keeps the result correct even if clocks were skewed.
+    fn paint_down_to_common(
+        first: ObjectId,
+        others: &[ObjectId],
+        graph: &mut Graph<'_, graph::Commit<Flags>>,
+    ) -> Result<Vec<ObjectId>, Error> {
+        let mut queue = PriorityQueue::<Priority, ObjectId>::new();
+        for (id, flag) in Some((first, Flags::COMMIT1))
+            .into_iter()
+            .chain(others.iter().map(|id| (*id, Flags::COMMIT2)))
+        {
+            if graph.try_lookup_or_insert_commit(id, |flags| *flags |= flag)?.is_some() {
+                queue.insert(priority(graph, &id), id);
+            }
+        }
+
+        let mut out = Vec::new();
+        while queue.iter_unordered().any(|id| !graph[id].data.contains(Flags::STALE)) {
+            let id = queue.pop_value().expect("not empty");
+            let commit = graph.get_mut(&id).expect("queued commits are in the graph");
+            let mut flags = commit.data & (Flags::COMMIT1 | Flags::COMMIT2 | Flags::STALE);
+            if flags == Flags::COMMIT1 | Flags::COMMIT2 {
+                if !commit.data.contains(Flags::RESULT) {
+                    commit.data |= Flags::RESULT;
+                    out.push(id);
+                }
+                flags |= Flags::STALE;
+            }
+
+            for parent_id in commit.parents.clone() {
+                let mut was_modified = false;
+                let parent = graph.try_lookup_or_insert_commit(parent_id, |parent_flags| {
+                    if !parent_flags.contains(flags) {
+                        *parent_flags |= flags;
+                        was_modified = true;
+                    }
+                })?;
+                if parent.is_some() && was_modified {
+                    queue.insert(priority(graph, &parent_id), parent_id);
+                }
+            }
+        }
+        Ok(out)
+    }
+}
@@ -0,0 +1,91 @@
+use gix_revision::bisect::{self, Outcome};
+
+use crate::hex_to_id;
+
+#[test]
+fn next_commit_matches_git() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let baseline = std::fs::read_to_string(root.join("bisect.baseline"))?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&odb.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&odb, cache);
+        for block in baseline.split("\n\n").filter(|block| !block.trim().is_empty()) {
+            let mut lines = block.lines();
+            let names = lines.next().expect("names");
+            let mut ids = lines
+                .next()
+                .expect("ids")
+                .split(' ')
+                .filter(|id| !id.is_empty())
+                .map(hex_to_id);
+            let bad = ids.next().expect("bad");
+            let good: Vec<_> = ids.collect();
+            let mut tokens = lines.next().expect("next commit").split(' ');
+            let id = hex_to_id(tokens.next().expect("id"));
+            let remaining = tokens.next().expect("remaining").parse()?;
+            let steps = tokens.next().expect("steps").parse()?;
+            let expected = if id == bad && remaining == 0 {
+                Outcome::FirstBad(bad)
+            } else {
+                Outcome::Next { id, remaining, steps }
+            };
+            assert_eq!(
+                gix_revision::bisect(bad, &good, &[], &mut graph)?,
+                expected,
+                "{names} (commitgraph: {use_commitgraph})"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn skipped_commits_are_not_chosen() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&odb, None);
+    let (n, h, a) = (tag(&root, "N")?, tag(&root, "H")?, tag(&root, "A")?);
+
+    let outcome = gix_revision::bisect(n, &[a], &[h], &mut graph)?;
+    assert_eq!(
+        outcome,
+        Outcome::Next {
+            id: tag(&root, "G")?,
+            remaining: 7,
+            steps: 3
+        },
+        "the best commit is skipped, so the oldest of the next best ones is chosen, which is G and not X5"
+    );
+
+    let (m, x5) = (tag(&root, "M")?, tag(&root, "X5")?);
+    let outcome = gix_revision::bisect(n, &[h, x5], &[m], &mut graph)?;
+    assert_eq!(
+        outcome,
+        Outcome::OnlySkippedLeft { candidates: vec![m, n] },
+        "the bad commit is always a candidate as well"
+    );
+    Ok(())
+}
+
+#[test]
+fn bad_commits_reachable_from_good_ones_are_an_error() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&odb, None);
+    let (h, n) = (tag(&root, "H")?, tag(&root, "N")?);
+    let err = gix_revision::bisect(h, &[n], &[], &mut graph).unwrap_err();
+    assert!(matches!(err, bisect::Error::BadIsGood { bad } if bad == h));
+    Ok(())
+}
+
+fn tag(root: &std::path::Path, name: &str) -> crate::Result<gix_hash::ObjectId> {
+    let hex = std::fs::read_to_string(root.join(".git/refs/tags").join(name))?;
+    Ok(hex_to_id(hex.trim()))
+}
+
+fn fixture_path() -> std::path::PathBuf {
+    gix_testtools::scripted_fixture_read_only("make_bisect_repo.sh").unwrap()
+}
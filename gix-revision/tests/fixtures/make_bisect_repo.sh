@@ -0,0 +1,82 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?first argument is the commit message}
+  tick
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge() {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick
+  git merge -q --no-ff -m "$message" "$@"
+  git tag "$message"
+}
+
+# Write the names of the bad and all good revisions, their ids, and the commit `git` would test next along with
+# the amount of revisions left to test and the estimated amount of steps, as seen by `git rev-list --bisect-vars`.
+function baseline() {
+  local bad=${1:?first argument is the bad revision}
+  shift
+  echo "$bad $*"
+  echo $(git rev-parse "$bad" "$@")
+  (
+    eval "$(git rev-list --bisect-vars "$bad" $(for rev in "$@"; do echo "^$rev"; done))"
+    echo "$bisect_rev $bisect_nr $bisect_steps"
+  )
+  echo
+}
+
+git init -q
+git checkout -q -b main
+
+#   A - B - C - D - E - F - G - H - M - N
+#        \                         /
+#         X1 - X2 - X3 - X4 - X5 -
+commit A
+commit B
+commit C
+commit D
+commit E
+git checkout -q -b side B
+commit X1
+commit X2
+commit X3
+git checkout -q main
+commit F
+commit G
+git checkout -q side
+commit X4
+commit X5
+git checkout -q main
+commit H
+merge M X5
+commit N
+
+git commit-graph write --no-progress --reachable
+
+{
+  baseline N A
+  baseline N B
+  baseline H A
+  baseline N E X3
+  baseline N X5
+  baseline M H X5
+  baseline B A
+  baseline X5
+} > bisect.baseline
@@ -0,0 +1,100 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?first argument is the commit message}
+  local date=${2:-}
+  tick
+  if [ -n "$date" ]; then
+    GIT_COMMITTER_DATE="$date"
+    GIT_AUTHOR_DATE="$date"
+  fi
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge() {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick
+  git merge -q --no-ff -m "$message" "$@"
+  git tag "$message"
+}
+
+# Like in `make_merge_base_repos.sh`, write the names and ids of all given revisions, followed by all merge-bases.
+function baseline() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git merge-base --all "$@" || :
+  echo
+}
+
+# Like `baseline`, but for the amount of commits only reachable from the first and only from the second revision.
+function baseline_ahead_behind() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git rev-list --left-right --count "$1...$2"
+  echo
+}
+
+# Like `baseline`, but for whether the first revision is an ancestor of the second one.
+function baseline_is_ancestor() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  if git merge-base --is-ancestor "$1" "$2"; then echo true; else echo false; fi
+  echo
+}
+
+git init -q
+git checkout -q -b main
+git config commitGraph.generationVersion 2
+
+#   A - B - C - D
+#        \       \
+#         E - F - M
+#
+# C was committed with a clock that was way behind, so it seems older than all other commits.
+commit A
+commit B
+commit C "@100000 +0000"
+commit D
+git checkout -q -b other B
+commit E
+commit F
+merge M D
+
+git commit-graph write --no-progress --reachable
+
+{
+  baseline D F
+  baseline F D
+  baseline M D
+  baseline C E
+} > merge_base.baseline
+
+{
+  baseline_ahead_behind D F
+  baseline_ahead_behind M C
+  baseline_ahead_behind C E
+} > ahead_behind.baseline
+
+{
+  baseline_is_ancestor C M
+  baseline_is_ancestor C F
+  baseline_is_ancestor A D
+  baseline_is_ancestor D F
+  baseline_is_ancestor E M
+  baseline_is_ancestor M M
+} > is_ancestor.baseline
@@ -0,0 +1,133 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?first argument is the commit message}
+  tick
+  git commit -q --allow-empty -m "$message"
+  git tag "$message"
+}
+
+function merge() {
+  local message=${1:?first argument is the commit message}
+  shift
+  tick
+  git merge -q --no-ff -m "$message" "$@"
+  git tag "$message"
+}
+
+# Write the names and the ids of all given revisions into a line each, followed by all merge-bases as seen by `git merge-base --all`, and
+# an empty line. The first revision is the one to find merge-bases for, the others are the ones to find them with.
+function baseline() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git merge-base --all "$@" || :
+  echo
+}
+
+# Like `baseline`, but for the result of `git merge-base --octopus --all` of all given revisions.
+function baseline_octopus() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git merge-base --octopus --all "$@" || :
+  echo
+}
+
+# Like `baseline`, but for the result of `git merge-base --independent` of all given revisions.
+function baseline_independent() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git merge-base --independent "$@" || :
+  echo
+}
+
+# Like `baseline`, but for the amount of commits only reachable from the first and only from the second revision.
+function baseline_ahead_behind() {
+  echo "$*"
+  echo $(git rev-parse "$@")
+  git rev-list --left-right --count "$1...$2"
+  echo
+}
+
+git init -q
+git checkout -q -b main
+
+#   A - B - C --- M2
+#    \     \     /
+#     \     \   /
+#      \     \ /
+#       \     X
+#        \   / \
+#         \ /   \
+#          D --- M1
+#           \
+#            E
+commit A
+commit B
+commit C
+git checkout -q -b other A
+commit D
+commit E
+git checkout -q -b criss-cross D
+merge M1 C
+git checkout -q main
+merge M2 D
+
+git checkout -q --orphan unrelated
+commit U
+
+git commit-graph write --no-progress --reachable
+
+{
+  baseline C E
+  baseline E C
+  baseline B C
+  baseline C A
+  baseline M1 M2
+  baseline M2 M1
+  baseline M1 E C
+  baseline E M1 M2
+  baseline U C
+  baseline C C
+} > merge_base.baseline
+
+{
+  baseline_octopus C E D
+  baseline_octopus M1 M2 E
+  baseline_octopus M1 M2 C
+  baseline_octopus B C A
+  baseline_octopus C E U
+  baseline_octopus C
+} > octopus.baseline
+
+{
+  baseline_independent C B A
+  baseline_independent M1 M2 E
+  baseline_independent E D C
+  baseline_independent C C B
+  baseline_independent U C A
+  baseline_independent A
+} > independent.baseline
+
+{
+  baseline_ahead_behind C E
+  baseline_ahead_behind E C
+  baseline_ahead_behind M1 M2
+  baseline_ahead_behind M2 E
+  baseline_ahead_behind C A
+  baseline_ahead_behind A C
+  baseline_ahead_behind U C
+  baseline_ahead_behind C C
+} > ahead_behind.baseline
@@ -0,0 +1,236 @@
+use gix_hash::ObjectId;
+use gix_revision::merge_base;
+
+use crate::hex_to_id;
+
+#[test]
+fn validate() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&odb.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&odb, cache);
+        for baseline in parse_baseline(&std::fs::read_to_string(root.join("merge_base.baseline"))?) {
+            let actual = merge_base(baseline.first, &baseline.others, &mut graph)?;
+            assert_eq!(
+                actual, baseline.bases,
+                "{} (commitgraph: {use_commitgraph})",
+                baseline.names
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn octopus() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&odb, None);
+    for baseline in parse_baseline(&std::fs::read_to_string(root.join("octopus.baseline"))?) {
+        let actual = merge_base::octopus(&baseline.commits(), &mut graph)?;
+        assert_eq!(actual, baseline.bases, "{}", baseline.names);
+    }
+    assert_eq!(merge_base::octopus(&[], &mut graph)?, None, "there is nothing to merge");
+    Ok(())
+}
+
+#[test]
+fn independent() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&odb, None);
+    for baseline in parse_baseline(&std::fs::read_to_string(root.join("independent.baseline"))?) {
+        let actual = merge_base::independent(&baseline.commits(), &mut graph)?;
+        assert_eq!(Some(actual), baseline.bases, "{}", baseline.names);
+    }
+    Ok(())
+}
+
+#[test]
+fn ahead_behind() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let baseline = std::fs::read_to_string(root.join("ahead_behind.baseline"))?;
+    for use_commitgraph in [false, true] {
+        let cache = use_commitgraph
+            .then(|| gix_commitgraph::Graph::from_info_dir(&odb.store_ref().path().join("info")).ok())
+            .flatten();
+        let mut graph = gix_revision::Graph::new(&odb, cache);
+        for block in baseline.split("\n\n").filter(|block| !block.trim().is_empty()) {
+            let mut lines = block.lines();
+            let names = lines.next().expect("names");
+            let mut ids = lines.next().expect("ids").split(' ').map(hex_to_id);
+            let (local, upstream) = (ids.next().expect("local"), ids.next().expect("upstream"));
+            let (ahead, behind) = lines.next().expect("counts").split_once('\t').expect("two counts");
+            let expected = (ahead.parse()?, behind.parse()?);
+            assert_eq!(
+                merge_base::ahead_behind(local, upstream, &mut graph)?,
+                expected,
+                "{names} (commitgraph: {use_commitgraph})"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn no_others_yields_first() -> crate::Result {
+    let root = fixture_path();
+    let odb = gix_odb::at(root.join(".git/objects"))?;
+    let mut graph = gix_revision::Graph::new(&odb, None);
+    let first = ObjectId::null(gix_hash::Kind::Sha1);
+    assert_eq!(
+        merge_base(first, &[], &mut graph)?,
+        Some(vec![first]),
+        "the commit isn't even looked at"
+    );
+    Ok(())
+}
+
+mod clock_skew {
+    use gix_revision::merge_base;
+
+    use super::parse_baseline;
+    use crate::hex_to_id;
+
+    #[test]
+    fn merge_base_ahead_behind_and_is_ancestor() -> crate::Result {
+        let root = fixture_path();
+        let odb = gix_odb::at(root.join(".git/objects"))?;
+        let merge_bases = parse_baseline(&std::fs::read_to_string(root.join("merge_base.baseline"))?);
+        let ahead_behind = std::fs::read_to_string(root.join("ahead_behind.baseline"))?;
+        let is_ancestor = std::fs::read_to_string(root.join("is_ancestor.baseline"))?;
+        for use_commitgraph in [false, true] {
+            let cache = use_commitgraph
+                .then(|| gix_commitgraph::Graph::from_info_dir(&odb.store_ref().path().join("info")).ok())
+                .flatten();
+            let mut graph = gix_revision::Graph::new(&odb, cache);
+            for baseline in &merge_bases {
+                let actual = merge_base(baseline.first, &baseline.others, &mut graph)?;
+                assert_eq!(
+                    actual, baseline.bases,
+                    "{} (commitgraph: {use_commitgraph})",
+                    baseline.names
+                );
+            }
+
+            // Without generation numbers, the skewed commit C seems older than all others, which stops the traversal
+            // too early. `git rev-list` avoids this with a heuristic that keeps going for a while, which we don't have.
+            if use_commitgraph {
+                for (names, (first, second), expected) in blocks(&ahead_behind) {
+                    let (ahead, behind) = expected.split_once('\t').expect("two counts");
+                    assert_eq!(
+                        merge_base::ahead_behind(first, second, &mut graph)?,
+                        (ahead.parse()?, behind.parse()?),
+                        "{names}"
+                    );
+                }
+            }
+
+            for (names, (ancestor, descendant), expected) in blocks(&is_ancestor) {
+                assert_eq!(
+                    merge_base::is_ancestor(ancestor, descendant, &mut graph)?,
+                    expected.parse::<bool>()?,
+                    "{names} (commitgraph: {use_commitgraph})"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn corrected_commit_dates_are_used_if_available() -> crate::Result {
+        let root = fixture_path();
+        let odb = gix_odb::at(root.join(".git/objects"))?;
+        let cache = gix_commitgraph::Graph::from_info_dir(&odb.store_ref().path().join("info"))?;
+        assert!(cache.has_corrected_commit_dates());
+        let mut graph = gix_revision::Graph::new(&odb, Some(cache));
+
+        let (_, (skewed, _), _) = blocks(&std::fs::read_to_string(root.join("is_ancestor.baseline"))?)
+            .next()
+            .expect("C M is first");
+        let commit = graph
+            .try_lookup_or_insert_commit(skewed, |_: &mut merge_base::Flags| {})?
+            .expect("present")
+            .clone();
+        assert_eq!(
+            commit.commit_time, 100000,
+            "the time as seen by the clock of the committer"
+        );
+        let parent = graph
+            .try_lookup_or_insert_commit(commit.parents[0], |_| {})?
+            .expect("present")
+            .clone();
+        assert_eq!(
+            graph.corrected_commit_date(&skewed),
+            Some(parent.commit_time + 1),
+            "the corrected commit date is one more than the one of its parent"
+        );
+        assert_eq!(
+            graph.corrected_commit_date(&commit.parents[0]),
+            Some(parent.commit_time)
+        );
+        Ok(())
+    }
+
+    fn fixture_path() -> std::path::PathBuf {
+        gix_testtools::scripted_fixture_read_only("make_clock_skew_repo.sh").unwrap()
+    }
+
+    /// Parse blocks of names, the ids of two commits and an expected value from a baseline.
+    fn blocks(baseline: &str) -> impl Iterator<Item = (&str, (gix_hash::ObjectId, gix_hash::ObjectId), &str)> {
+        baseline
+            .split("\n\n")
+            .filter(|block| !block.trim().is_empty())
+            .map(|block| {
+                let mut lines = block.lines();
+                let names = lines.next().expect("names");
+                let mut ids = lines.next().expect("ids").split(' ').map(hex_to_id);
+                let ids = (ids.next().expect("first"), ids.next().expect("second"));
+                (names, ids, lines.next().expect("expected value"))
+            })
+    }
+}
+
+fn fixture_path() -> std::path::PathBuf {
+    gix_testtools::scripted_fixture_read_only("make_merge_base_repos.sh").unwrap()
+}
+
+struct Baseline {
+    names: String,
+    first: ObjectId,
+    others: Vec<ObjectId>,
+    bases: Option<Vec<ObjectId>>,
+}
+
+impl Baseline {
+    fn commits(&self) -> Vec<ObjectId> {
+        Some(self.first)
+            .into_iter()
+            .chain(self.others.iter().copied())
+            .collect()
+    }
+}
+
+fn parse_baseline(baseline: &str) -> Vec<Baseline> {
+    baseline
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let mut lines = block.lines();
+            let names = lines.next().expect("names").to_owned();
+            let mut ids = lines.next().expect("ids").split(' ').map(hex_to_id);
+            let first = ids.next().expect("first");
+            let bases: Vec<_> = lines.map(hex_to_id).collect();
+            Baseline {
+                names,
+                first,
+                others: ids.collect(),
+                bases: (!bases.is_empty()).then_some(bases),
+            }
+        })
+        .collect()
+}
@@ -33,6 +33,15 @@ impl<'graph> LazyCommit<'graph> {
         }
     }
 
+    /// Returns the corrected commit date of the commit, also known as generation number v2, if it is backed by a commit
+    /// graph whose files all store it.
+    pub fn corrected_commit_date(&self) -> Option<SecondsSinceUnixEpoch> {
+        match &self.backing {
+            Either::Left(_) => None,
+            Either::Right((cache, pos)) => corrected_commit_date(cache, &cache.commit_at(*pos)),
+        }
+    }
+
     /// Convert ourselves into an owned version, which effectively detaches us from the underlying graph.
     /// Use `new_data()` to provide the `data` field for the owned `Commit`.
     pub fn to_owned<T>(&self, new_data: impl FnOnce() -> T) -> Result<Commit<T>, to_owned::Error> {
@@ -88,6 +97,16 @@ impl<'graph> LazyCommit<'graph> {
     }
 }
 
+pub(crate) fn corrected_commit_date(
+    cache: &gix_commitgraph::Graph,
+    commit: &gix_commitgraph::file::Commit<'_>,
+) -> Option<SecondsSinceUnixEpoch> {
+    if !cache.has_corrected_commit_dates() {
+        return None;
+    }
+    commit.corrected_commit_date()?.try_into().ok()
+}
+
 /// An iterator over the parents of a commit.
 pub struct Parents<'graph> {
     backing: Either<
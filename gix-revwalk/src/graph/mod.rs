@@ -196,6 +196,12 @@ impl<'find, T> Graph<'find, Commit<T>> {
         };
         Ok(self.map.get_mut(&id))
     }
+
+    /// Call `clear_data()` on the data of all commits in the graph, making it possible to traverse it again without having
+    /// to look up the commits that were seen already.
+    pub fn clear_commit_data(&mut self, mut clear_data: impl FnMut(&mut T)) {
+        self.map.values_mut().for_each(|commit| clear_data(&mut commit.data));
+    }
 }
 
 /// commit access
@@ -267,6 +273,15 @@ impl<'find, T> Graph<'find, T> {
         self.try_lookup(id)?
             .ok_or(gix_object::find::existing_iter::Error::NotFound { oid: id.to_owned() })
     }
+
+    /// Return the corrected commit date of the commit named `id`, also known as generation number v2, if it is in the
+    /// commit-graph and all of its files store it.
+    ///
+    /// Prefer it over the generation of a commit to cut traversals short, as it is usually more effective.
+    pub fn corrected_commit_date(&self, id: &gix_hash::oid) -> Option<SecondsSinceUnixEpoch> {
+        let cache = self.cache.as_ref()?;
+        commit::corrected_commit_date(cache, &cache.commit_by_id(id)?)
+    }
 }
 
 fn try_lookup<'graph>(
@@ -311,6 +326,8 @@ pub struct Commit<T> {
     /// The time at which the commit was created.
     pub commit_time: SecondsSinceUnixEpoch,
     /// The generation of the commit, if available.
+    ///
+    /// Note that the corrected commit date is obtained with [`Graph::corrected_commit_date()`] instead, to keep commits small.
     pub generation: Option<u32>,
     /// Any kind of data to associate with this commit.
     pub data: T,
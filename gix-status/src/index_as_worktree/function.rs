@@ -105,6 +105,9 @@ where
     progress.init(entries.len().into(), gix_features::progress::count("files"));
     let count = progress.counter();
 
+    let fs_per_mount_point = options
+        .fs_per_mount_point
+        .then(|| gix_fs::capabilities::PerMountPoint::new(options.fs, worktree));
     let new_state = {
         let options = &options;
         let (skipped_by_pathspec, skipped_by_entry_flags) = (&skipped_by_pathspec, &skipped_by_entry_flags);
@@ -122,6 +125,7 @@ where
                     path_backing,
                     filter,
                     options,
+                    fs_per_mount_point,
 
                     skipped_by_pathspec,
                     skipped_by_entry_flags,
@@ -228,6 +232,8 @@ struct State<'a, 'b> {
     filter: gix_filter::Pipeline,
     path_backing: &'b gix_index::PathStorageRef,
     options: &'a Options,
+    /// If set, the capabilities of the filesystem each entry is located on, in case it's not the one of the worktree root.
+    fs_per_mount_point: Option<gix_fs::capabilities::PerMountPoint>,
 
     skipped_by_pathspec: &'a AtomicUsize,
     skipped_by_entry_flags: &'a AtomicUsize,
@@ -388,15 +394,23 @@ impl<'index> State<'_, 'index> {
             return Ok(Some(EntryStatus::IntentToAdd));
         }
         let new_stat = gix_index::entry::Stat::from_fs(&metadata)?;
-        let executable_bit_changed =
-            match entry
-                .mode
-                .change_to_match_fs(&metadata, self.options.fs.symlink, self.options.fs.executable_bit)
-            {
-                Some(gix_index::entry::mode::Change::Type { .. }) => return Ok(Some(Change::Type.into())),
-                Some(gix_index::entry::mode::Change::ExecutableBit) => true,
-                None => false,
-            };
+        let fs = match (&self.fs_per_mount_point, worktree_path.parent()) {
+            (Some(fs), Some(dir)) => {
+                // Capabilities can only be restricted, as they may also have been turned off by configuration.
+                let fs = fs.at_device(metadata.dev(), dir);
+                gix_fs::Capabilities {
+                    symlink: self.options.fs.symlink && fs.symlink,
+                    executable_bit: self.options.fs.executable_bit && fs.executable_bit,
+                    ..self.options.fs
+                }
+            }
+            _ => self.options.fs,
+        };
+        let executable_bit_changed = match entry.mode.change_to_match_fs(&metadata, fs.symlink, fs.executable_bit) {
+            Some(gix_index::entry::mode::Change::Type { .. }) => return Ok(Some(Change::Type.into())),
+            Some(gix_index::entry::mode::Change::ExecutableBit) => true,
+            None => false,
+        };
 
         // Here we implement racy-git. See racy-git.txt in the git documentation for a detailed documentation.
         //
@@ -432,7 +446,7 @@ impl<'index> State<'_, 'index> {
             file_len: metadata.len(),
             filter: &mut self.filter,
             attr_stack: &mut self.attr_stack,
-            options: self.options,
+            fs,
             id: &entry.id,
             objects,
             worktree_reads: self.worktree_reads,
@@ -500,7 +514,7 @@ where
     entry: &'a gix_index::Entry,
     filter: &'a mut gix_filter::Pipeline,
     attr_stack: &'a mut gix_worktree::Stack,
-    options: &'a Options,
+    fs: gix_fs::Capabilities,
     id: &'a gix_hash::oid,
     objects: Find,
     worktree_bytes: &'a AtomicU64,
@@ -528,7 +542,7 @@ where
         //
         let is_symlink = self.entry.mode == gix_index::entry::Mode::SYMLINK;
         // TODO: what to do about precompose unicode and ignore_case for symlinks
-        let out = if is_symlink && self.options.fs.symlink {
+        let out = if is_symlink && self.fs.symlink {
             // conversion to bstr can never fail because symlinks are only used
             // on unix (by git) so no reason to use the try version here
             let symlink_path = gix_path::into_bstr(std::fs::read_link(self.path)?);
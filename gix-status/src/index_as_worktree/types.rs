@@ -25,6 +25,13 @@ pub enum Error {
 pub struct Options {
     /// Capabilities of the file system which affect the status computation.
     pub fs: gix_fs::Capabilities,
+    /// If true, default false, `fs` is assumed to only describe the filesystem the worktree root is located on, and
+    /// the capabilities of other filesystems mounted within it are [probed](gix_fs::capabilities::PerMountPoint) by writing
+    /// files into the first directory they are seen in.
+    ///
+    /// This allows, for instance, to not see changes to the executable bit of files on an `exFAT` drive mounted within
+    /// the worktree.
+    pub fs_per_mount_point: bool,
     /// If set, don't use more than this amount of threads.
     /// Otherwise, usually use as many threads as there are logical cores.
     /// A value of 0 is interpreted as no-limit
@@ -105,6 +105,7 @@ pub(super) mod function {
                                         excludes: excludes.as_mut(),
                                         objects: &objects,
                                         explicit_traversal_root: Some(worktree),
+                                        untracked_cache: None,
                                     },
                                     options,
                                     &mut collect,
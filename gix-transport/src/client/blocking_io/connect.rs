@@ -7,7 +7,7 @@ pub(crate) mod function {
     ///
     /// This includes connections to
     /// [local repositories][crate::client::file::connect()],
-    /// [repositories over ssh][crate::client::ssh::connect()],
+    /// [repositories over ssh][crate::client::ssh::connect()], or [in-process][crate::client::ssh::native] if configured,
     /// [git daemons][crate::client::git::connect()],
     /// and if compiled in connections to [git repositories over https][crate::client::http::connect()].
     ///
@@ -32,10 +32,25 @@ pub(crate) mod function {
                         .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
                 )
             }
-            gix_url::Scheme::Ssh => Box::new({
-                crate::client::blocking_io::ssh::connect(url, options.version, options.ssh, options.trace)
+            gix_url::Scheme::Ssh => match options.ssh.native {
+                #[cfg(feature = "ssh-client-native")]
+                Some(native_options) => Box::new(
+                    crate::client::blocking_io::ssh::native::connect(
+                        url,
+                        options.version,
+                        native_options,
+                        options.trace,
+                    )
                     .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
-            }),
+                    .with_read_timeout(options.read_timeout),
+                ),
+                #[cfg(not(feature = "ssh-client-native"))]
+                Some(_) => return Err(Error::CompiledWithoutNativeSsh),
+                None => Box::new({
+                    crate::client::blocking_io::ssh::connect(url, options.version, options.ssh, options.trace)
+                        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
+                }),
+            },
             gix_url::Scheme::Git => {
                 if url.user().is_some() {
                     return Err(Error::UnsupportedUrlTokens {
@@ -45,14 +60,20 @@ pub(crate) mod function {
                 }
                 Box::new({
                     let path = std::mem::take(&mut url.path);
-                    crate::client::git::connect(
+                    let connection = crate::client::git::connect(
                         url.host().expect("host is present in url"),
                         path,
                         options.version,
                         url.port,
                         options.trace,
                     )
-                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?
+                    .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+                    // The writer is a clone of the reading stream, and both share the same socket and thus its options.
+                    connection
+                        .writer
+                        .set_read_timeout(options.read_timeout)
+                        .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+                    connection
                 })
             }
             #[cfg(not(any(feature = "http-client-curl", feature = "http-client-reqwest")))]
@@ -156,6 +156,8 @@ pub fn new() -> (
                     proxy_authenticate,
                     verbose,
                     ssl_ca_info,
+                    ssl_cert,
+                    ssl_key,
                     ssl_version,
                     ssl_verify,
                     http_version,
@@ -177,6 +179,12 @@ pub fn new() -> (
             if let Some(ca_info) = ssl_ca_info {
                 handle.cainfo(ca_info)?;
             }
+            if let Some(cert) = ssl_cert {
+                handle.ssl_cert(cert)?;
+            }
+            if let Some(key) = ssl_key {
+                handle.ssl_key(key)?;
+            }
 
             if let Some(ref mut curl_options) = backend.as_ref().and_then(|backend| backend.lock().ok()) {
                 if let Some(opts) = curl_options.downcast_mut::<super::Options>() {
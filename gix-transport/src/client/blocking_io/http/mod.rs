@@ -178,7 +178,18 @@ pub struct Options {
     /// If enabled, emit additional information about connections and possibly the data received or written.
     pub verbose: bool,
     /// If set, use this path to point to a file with CA certificates to verify peers.
+    ///
+    /// Refers to `http.sslCAInfo`.
     pub ssl_ca_info: Option<PathBuf>,
+    /// If set, the path to a file with the PEM encoded client certificate to present to the server, which also needs
+    /// `ssl_key` to be set.
+    ///
+    /// Refers to `http.sslCert`.
+    pub ssl_cert: Option<PathBuf>,
+    /// If set, the path to a file with the PEM encoded private key of the client certificate in `ssl_cert`.
+    ///
+    /// Refers to `http.sslKey`.
+    pub ssl_key: Option<PathBuf>,
     /// The SSL version or version range to use, or `None` to let the TLS backend determine which versions are acceptable.
     pub ssl_version: Option<SslVersionRangeInclusive>,
     /// Controls whether to perform SSL identity verification or not. Turning this off is not recommended and can lead to
@@ -206,6 +217,8 @@ impl Default for Options {
             connect_timeout: None,
             verbose: false,
             ssl_ca_info: None,
+            ssl_cert: None,
+            ssl_key: None,
             ssl_version: None,
             ssl_verify: true,
             http_version: None,
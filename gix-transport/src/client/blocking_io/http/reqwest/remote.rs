@@ -1,6 +1,7 @@
 use std::{
     any::Any,
     io::{Read, Write},
+    path::PathBuf,
     str::FromStr,
     sync::{atomic, Arc},
 };
@@ -21,6 +22,10 @@ pub enum Error {
     ConfigureRequest(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
     #[error(transparent)]
     Redirect(#[from] redirect::Error),
+    #[error("Could not obtain or store credentials for the proxy")]
+    ProxyAuthenticate(#[from] Box<gix_credentials::protocol::Error>),
+    #[error("Could not read the certificate or key at \"{}\"", path.display())]
+    ReadTlsFile { path: PathBuf, source: std::io::Error },
 }
 
 impl crate::IsSpuriousError for Error {
@@ -42,40 +47,7 @@ impl Default for Remote {
             let mut follow = None;
             let mut redirected_base_url = None::<String>;
             let allow_redirects = Arc::new(atomic::AtomicBool::new(false));
-
-            // We may error while configuring, which is expected as part of the internal protocol. The error will be
-            // received and the sender of the request might restart us.
-            let client = reqwest::blocking::ClientBuilder::new()
-                .connect_timeout(std::time::Duration::from_secs(20))
-                .http1_title_case_headers()
-                .redirect(reqwest::redirect::Policy::custom({
-                    let allow_redirects = allow_redirects.clone();
-                    move |attempt| {
-                        if allow_redirects.load(atomic::Ordering::Relaxed) {
-                            let curr_url = attempt.url();
-                            let prev_urls = attempt.previous();
-
-                            match prev_urls.first() {
-                                Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
-                                    // git does not want to be redirected to a different host.
-                                    attempt.stop()
-                                }
-                                _ => {
-                                    // emulate default git behaviour which relies on curl default behaviour apparently.
-                                    const CURL_DEFAULT_REDIRS: usize = 50;
-                                    if prev_urls.len() >= CURL_DEFAULT_REDIRS {
-                                        attempt.error("too many redirects")
-                                    } else {
-                                        attempt.follow()
-                                    }
-                                }
-                            }
-                        } else {
-                            attempt.stop()
-                        }
-                    }
-                }))
-                .build()?;
+            let mut client = None::<(reqwest::blocking::Client, ClientSettings)>;
 
             for Request {
                 url,
@@ -86,6 +58,19 @@ impl Default for Remote {
             } in req_recv
             {
                 let effective_url = redirect::swap_tails(redirected_base_url.as_deref(), &base_url, url.clone());
+                // We may error while configuring, which is expected as part of the internal protocol. The error will be
+                // received and the sender of the request might restart us.
+                let client_settings = ClientSettings::from(&config);
+                let mut proxy_auth_action = None;
+                if client
+                    .as_ref()
+                    .map_or(true, |(_, settings)| *settings != client_settings)
+                {
+                    let (new_client, auth_action) = new_client(&config, allow_redirects.clone())?;
+                    proxy_auth_action = auth_action;
+                    client = Some((new_client, client_settings));
+                }
+                let client = &client.as_ref().expect("set above").0;
                 let mut req_builder = if upload_body_kind.is_some() {
                     client.post(&effective_url)
                 } else {
@@ -139,8 +124,17 @@ impl Default for Remote {
                     .execute(req)
                     .and_then(reqwest::blocking::Response::error_for_status)
                 {
-                    Ok(res) => res,
+                    Ok(res) => {
+                        if let Some((action, authenticate)) = proxy_auth_action {
+                            authenticate.lock().expect("no panics in other threads")(action.store())
+                                .map_err(Box::new)?;
+                        }
+                        res
+                    }
                     Err(err) => {
+                        if let Some((action, authenticate)) = proxy_auth_action {
+                            authenticate.lock().expect("no panics in other threads")(action.erase()).ok();
+                        }
                         let (kind, err) = match err.status() {
                             Some(status) => {
                                 let kind = if status == reqwest::StatusCode::UNAUTHORIZED {
@@ -202,6 +196,172 @@ impl Default for Remote {
     }
 }
 
+/// The parts of the configuration that affect the client as a whole, and which require a new client if they change.
+#[derive(PartialEq, Eq)]
+struct ClientSettings {
+    proxy: Option<String>,
+    no_proxy: Option<String>,
+    has_proxy_authentication: bool,
+    ssl_ca_info: Option<PathBuf>,
+    ssl_cert: Option<PathBuf>,
+    ssl_key: Option<PathBuf>,
+    ssl_version: Option<http::options::SslVersionRangeInclusive>,
+    ssl_verify: bool,
+}
+
+impl From<&http::Options> for ClientSettings {
+    fn from(options: &http::Options) -> Self {
+        ClientSettings {
+            proxy: options.proxy.clone(),
+            no_proxy: options.no_proxy.clone(),
+            has_proxy_authentication: options.proxy_authenticate.is_some(),
+            ssl_ca_info: options.ssl_ca_info.clone(),
+            ssl_cert: options.ssl_cert.clone(),
+            ssl_key: options.ssl_key.clone(),
+            ssl_version: options.ssl_version,
+            ssl_verify: options.ssl_verify,
+        }
+    }
+}
+
+type ProxyAuthAction = (
+    gix_credentials::helper::NextAction,
+    Arc<std::sync::Mutex<http::options::AuthenticateFn>>,
+);
+
+/// Create a new client which uses the proxy and TLS settings as configured in `config`, along with the action to perform
+/// once it's known if the credentials for the proxy, if any, were accepted.
+///
+/// Note that `reqwest` would pick up proxies from the environment by itself, which we prevent as the caller is expected to
+/// pass the proxy configuration in `config`, possibly after reading it from the environment.
+fn new_client(
+    config: &http::Options,
+    allow_redirects: Arc<atomic::AtomicBool>,
+) -> Result<(reqwest::blocking::Client, Option<ProxyAuthAction>), Error> {
+    let mut builder = reqwest::blocking::ClientBuilder::new()
+        .connect_timeout(std::time::Duration::from_secs(20))
+        .http1_title_case_headers()
+        .no_proxy()
+        .redirect(reqwest::redirect::Policy::custom({
+            move |attempt| {
+                if allow_redirects.load(atomic::Ordering::Relaxed) {
+                    let curr_url = attempt.url();
+                    let prev_urls = attempt.previous();
+
+                    match prev_urls.first() {
+                        Some(prev_url) if prev_url.host_str() != curr_url.host_str() => {
+                            // git does not want to be redirected to a different host.
+                            attempt.stop()
+                        }
+                        _ => {
+                            // emulate default git behaviour which relies on curl default behaviour apparently.
+                            const CURL_DEFAULT_REDIRS: usize = 50;
+                            if prev_urls.len() >= CURL_DEFAULT_REDIRS {
+                                attempt.error("too many redirects")
+                            } else {
+                                attempt.follow()
+                            }
+                        }
+                    }
+                } else {
+                    attempt.stop()
+                }
+            }
+        }));
+
+    let mut proxy_auth_action = None;
+    if let Some(proxy_url) = config.proxy.as_deref().filter(|url| !url.is_empty()) {
+        let disable_all_proxies = config.no_proxy.as_deref() == Some("*");
+        if !disable_all_proxies {
+            // Like curl, we assume http if no scheme is given.
+            let mut proxy = if proxy_url.contains("://") {
+                reqwest::Proxy::all(proxy_url)
+            } else {
+                reqwest::Proxy::all(format!("http://{proxy_url}"))
+            }?
+            .no_proxy(config.no_proxy.as_deref().and_then(reqwest::NoProxy::from_string));
+            if let Some((obtain_creds_action, authenticate)) = &config.proxy_authenticate {
+                let creds = authenticate.lock().expect("no panics in other threads")(obtain_creds_action.clone())
+                    .map_err(Box::new)?
+                    .expect("action to fetch credentials");
+                proxy = proxy.basic_auth(&creds.identity.username, &creds.identity.password);
+                proxy_auth_action = Some((creds.next, authenticate.clone()));
+            }
+            builder = builder.proxy(proxy);
+        }
+    }
+    builder = configure_tls(builder, config)?;
+    Ok((builder.build()?, proxy_auth_action))
+}
+
+/// Apply the TLS settings of `config` to `builder`.
+#[cfg(any(
+    feature = "http-client-reqwest-rust-tls",
+    feature = "http-client-reqwest-rust-tls-trust-dns",
+    feature = "http-client-reqwest-native-tls"
+))]
+fn configure_tls(
+    mut builder: reqwest::blocking::ClientBuilder,
+    config: &http::Options,
+) -> Result<reqwest::blocking::ClientBuilder, Error> {
+    use http::options::SslVersion;
+    use reqwest::tls;
+
+    fn read(path: &std::path::Path) -> Result<Vec<u8>, Error> {
+        std::fs::read(path).map_err(|source| Error::ReadTlsFile {
+            path: path.to_owned(),
+            source,
+        })
+    }
+    fn to_tls_version(version: SslVersion) -> Option<tls::Version> {
+        Some(match version {
+            SslVersion::Default | SslVersion::SslV2 | SslVersion::SslV3 => return None,
+            SslVersion::TlsV1 | SslVersion::TlsV1_0 => tls::Version::TLS_1_0,
+            SslVersion::TlsV1_1 => tls::Version::TLS_1_1,
+            SslVersion::TlsV1_2 => tls::Version::TLS_1_2,
+            SslVersion::TlsV1_3 => tls::Version::TLS_1_3,
+        })
+    }
+
+    if let Some(path) = &config.ssl_ca_info {
+        for certificate in reqwest::Certificate::from_pem_bundle(&read(path)?)? {
+            builder = builder.add_root_certificate(certificate);
+        }
+    }
+    if let (Some(cert), Some(key)) = (&config.ssl_cert, &config.ssl_key) {
+        let (cert, key) = (read(cert)?, read(key)?);
+        #[cfg(feature = "http-client-reqwest-native-tls")]
+        let identity = reqwest::Identity::from_pkcs8_pem(&cert, &key)?;
+        #[cfg(not(feature = "http-client-reqwest-native-tls"))]
+        let identity = reqwest::Identity::from_pem(&[key, cert].concat())?;
+        builder = builder.identity(identity);
+    }
+    if let Some(version) = config.ssl_version {
+        let (min, max) = version.min_max();
+        if let Some(min) = to_tls_version(min) {
+            builder = builder.min_tls_version(min);
+        }
+        if let Some(max) = to_tls_version(max) {
+            builder = builder.max_tls_version(max);
+        }
+    }
+    Ok(builder.danger_accept_invalid_certs(!config.ssl_verify))
+}
+
+/// Without a TLS implementation, there is nothing to configure.
+#[cfg(not(any(
+    feature = "http-client-reqwest-rust-tls",
+    feature = "http-client-reqwest-rust-tls-trust-dns",
+    feature = "http-client-reqwest-native-tls"
+)))]
+#[allow(clippy::unnecessary_wraps)]
+fn configure_tls(
+    builder: reqwest::blocking::ClientBuilder,
+    _config: &http::Options,
+) -> Result<reqwest::blocking::ClientBuilder, Error> {
+    Ok(builder)
+}
+
 /// utilities
 impl Remote {
     fn restore_thread_after_failure(&mut self) -> http::Error {
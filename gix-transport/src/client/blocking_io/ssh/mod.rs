@@ -33,6 +33,9 @@ pub enum ProgramKind {
 
 mod program_kind;
 
+/// An ssh transport that runs in-process, without spawning an `ssh` program.
+pub mod native;
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod invocation {
@@ -75,6 +78,11 @@ pub mod connect {
         /// when invoking the program.
         /// If unset, the `program` basename determines the variant, or an invocation of the `command` itself.
         pub kind: Option<ProgramKind>,
+        /// If set, [`connect()`][crate::connect()] will use the ssh implementation built into this crate
+        /// with the given options instead of spawning `command`.
+        ///
+        /// This requires the `ssh-client-native` feature to be enabled, or connections will fail.
+        pub native: Option<super::native::Options>,
     }
 
     impl Options {
@@ -0,0 +1,399 @@
+use std::{
+    any::Any,
+    borrow::Cow,
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+};
+
+use bstr::{BStr, BString, ByteSlice};
+
+use super::{HostKeyCheck, Options};
+use crate::{
+    client::{self, git, MessageKind, RequestWriter, SetServiceResponse, WriteMode},
+    Protocol, Service,
+};
+
+/// The error used in [`connect()`] and when performing the handshake with a [`Transport`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("The scheme in \"{}\" is not usable for an ssh connection", .0.to_bstring())]
+    UnsupportedScheme(gix_url::Url),
+    #[error("Could not connect to {host}:{port}")]
+    Connect {
+        host: String,
+        port: u16,
+        source: std::io::Error,
+    },
+    #[error("The ssh session could not be established")]
+    Session(#[from] ssh2::Error),
+    #[error("The host '{host}' did not present a host key")]
+    MissingHostKey { host: String },
+    #[error("The host key of '{host}' is unknown, and strict host key checking is enabled")]
+    UnknownHostKey { host: String },
+    #[error("The host key of '{host}' differs from the known one, which could mean that someone is intercepting the connection")]
+    HostKeyMismatch { host: String },
+    #[error("Could not add the host key of '{host}' to the known hosts file at \"{}\"", path.display())]
+    WriteKnownHosts {
+        host: String,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Could not determine the name of the user to authenticate as on '{host}'")]
+    MissingUser { host: String },
+    #[error("Could not authenticate as '{user}' on '{host}' using the ssh agent, identity files or password")]
+    Authenticate { user: String, host: String },
+}
+
+impl crate::IsSpuriousError for Error {
+    fn is_spurious(&self) -> bool {
+        match self {
+            Error::Connect { source, .. } => source.is_spurious(),
+            _ => false,
+        }
+    }
+}
+
+/// A transport which establishes an ssh connection in-process, without spawning an `ssh` program, to invoke the
+/// requested service on the remote host.
+///
+/// It can only be instantiated using [`connect()`].
+pub struct Transport {
+    desired_version: Protocol,
+    url: gix_url::Url,
+    path: BString,
+    options: Options,
+    password: Option<String>,
+    connection: Option<git::Connection<ReadStdoutFailOnError, ssh2::Stream>>,
+    session: Option<ssh2::Session>,
+    read_timeout: Option<std::time::Duration>,
+    trace: bool,
+}
+
+/// Prepare a connection to the host in `url` to obtain data from the repository at its path using the ssh implementation built
+/// into this crate, configured with `options`.
+///
+/// The connection is established and authenticated only once the handshake is performed.
+/// The `desired_version` is the preferred protocol version when establishing the connection, but note that it can be
+/// downgraded by servers not supporting it.
+/// If `trace` is `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
+#[allow(clippy::result_large_err)]
+pub fn connect(
+    url: gix_url::Url,
+    desired_version: Protocol,
+    options: Options,
+    trace: bool,
+) -> Result<Transport, Error> {
+    if url.scheme != gix_url::Scheme::Ssh || url.host().is_none() {
+        return Err(Error::UnsupportedScheme(url));
+    }
+    let path = gix_url::expand_path::for_shell(url.path.clone());
+    Ok(Transport {
+        desired_version,
+        url,
+        path,
+        options,
+        password: None,
+        connection: None,
+        session: None,
+        read_timeout: None,
+        trace,
+    })
+}
+
+impl client::TransportWithoutIO for Transport {
+    fn set_identity(&mut self, identity: gix_sec::identity::Account) -> Result<(), client::Error> {
+        self.url
+            .set_user((!identity.username.is_empty()).then_some(identity.username));
+        self.password = (!identity.password.is_empty()).then_some(identity.password);
+        Ok(())
+    }
+
+    fn request(
+        &mut self,
+        write_mode: WriteMode,
+        on_into_read: MessageKind,
+        trace: bool,
+    ) -> Result<RequestWriter<'_>, client::Error> {
+        self.connection
+            .as_mut()
+            .expect("handshake() to have been called first")
+            .request(write_mode, on_into_read, trace)
+    }
+
+    fn to_url(&self) -> Cow<'_, BStr> {
+        Cow::Owned(self.url.to_bstring())
+    }
+
+    fn connection_persists_across_multiple_requests(&self) -> bool {
+        true
+    }
+
+    fn configure(&mut self, _config: &dyn Any) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(())
+    }
+}
+
+impl client::Transport for Transport {
+    fn handshake<'a>(
+        &mut self,
+        service: Service,
+        extra_parameters: &'a [(&'a str, Option<&'a str>)],
+    ) -> Result<SetServiceResponse<'_>, client::Error> {
+        if self.path.trim().first() == Some(&b'-') {
+            return Err(client::Error::AmbiguousPath {
+                path: self.path.clone(),
+            });
+        }
+        let session = self
+            .open_session()
+            .map_err(|err| client::Error::NativeSsh(err.into()))?;
+        let mut channel = session
+            .channel_session()
+            .map_err(|err| client::Error::NativeSsh(Error::from(err).into()))?;
+        if self.desired_version != Protocol::V1 {
+            // Servers only accept this if configured with `AcceptEnv GIT_PROTOCOL`, and otherwise we fall back to V1.
+            channel
+                .setenv("GIT_PROTOCOL", &format!("version={}", self.desired_version as usize))
+                .ok();
+        }
+        let command = format!(
+            "{} {}",
+            service.as_str(),
+            gix_quote::single(self.path.as_ref()).to_str_lossy()
+        );
+        gix_features::trace::debug!(command = ?command, "gix_transport::ssh::native");
+        channel
+            .exec(&command)
+            .map_err(|err| client::Error::NativeSsh(Error::from(err).into()))?;
+
+        let writer = channel.stream(0);
+        self.connection = Some(git::Connection::new_for_spawned_process(
+            ReadStdoutFailOnError { channel },
+            writer,
+            self.desired_version,
+            self.path.clone(),
+            self.trace,
+        ));
+        self.session = Some(session);
+        self.connection
+            .as_mut()
+            .expect("connection to be there right after setting it")
+            .handshake(service, extra_parameters)
+    }
+}
+
+impl Transport {
+    /// Fail with a timeout error if the remote doesn't send data for longer than `timeout`, or wait forever if `None`.
+    pub fn with_read_timeout(mut self, timeout: Option<std::time::Duration>) -> Self {
+        self.read_timeout = timeout;
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn open_session(&self) -> Result<ssh2::Session, Error> {
+        let host = self.url.host().expect("validated in connect()");
+        let port = self.url.port.unwrap_or(22);
+        let stream = (host, port)
+            .to_socket_addrs()
+            .and_then(|mut addrs| {
+                let addr = addrs.next().ok_or_else(|| {
+                    std::io::Error::new(std::io::ErrorKind::NotFound, "host name did not resolve to any address")
+                })?;
+                TcpStream::connect_timeout(&addr, std::time::Duration::from_secs(20))
+            })
+            .map_err(|source| Error::Connect {
+                host: host.into(),
+                port,
+                source,
+            })?;
+
+        let mut session = ssh2::Session::new()?;
+        if let Some(timeout) = self.read_timeout {
+            session.set_timeout(timeout.as_millis().try_into().unwrap_or(u32::MAX));
+        }
+        session.set_tcp_stream(stream);
+        session.handshake()?;
+        check_host_key(&session, host, port, &self.options)?;
+
+        let user = self
+            .url
+            .user()
+            .map(ToOwned::to_owned)
+            .or_else(|| std::env::var("USER").ok())
+            .or_else(|| std::env::var("USERNAME").ok())
+            .ok_or_else(|| Error::MissingUser { host: host.into() })?;
+        authenticate(&session, &user, self.password.as_deref(), &self.options);
+        if !session.authenticated() {
+            return Err(Error::Authenticate {
+                user,
+                host: host.into(),
+            });
+        }
+        Ok(session)
+    }
+}
+
+/// Try all authentication methods in order until one succeeds, ignoring errors of the ones that didn't.
+fn authenticate(session: &ssh2::Session, user: &str, password: Option<&str>, options: &Options) {
+    if options.use_agent && session.userauth_agent(user).is_ok() {
+        return;
+    }
+    for identity_file in identity_files(options).iter().filter(|path| path.is_file()) {
+        if session.userauth_pubkey_file(user, None, identity_file, None).is_ok() {
+            return;
+        }
+    }
+    if let Some(password) = password {
+        if session.userauth_password(user, password).is_ok() {
+            return;
+        }
+    }
+    if let Some(prompt) = options.prompt.as_deref() {
+        session
+            .userauth_keyboard_interactive(user, &mut KeyboardInteractive { prompt })
+            .ok();
+    }
+}
+
+/// Answer the challenges of keyboard-interactive authentication by prompting the user.
+struct KeyboardInteractive<'a> {
+    prompt: &'a (dyn gix_prompt::Prompt + Send + Sync),
+}
+
+impl ssh2::KeyboardInteractivePrompt for KeyboardInteractive<'_> {
+    fn prompt<'a>(&mut self, _username: &str, instructions: &str, prompts: &[ssh2::Prompt<'a>]) -> Vec<String> {
+        let mut instructions = Some(instructions.trim()).filter(|instructions| !instructions.is_empty());
+        prompts
+            .iter()
+            .map_while(|challenge| {
+                let message = match instructions.take() {
+                    Some(instructions) => format!("{instructions}\n{}", challenge.text),
+                    None => challenge.text.clone().into_owned(),
+                };
+                let opts = gix_prompt::Options {
+                    askpass: None,
+                    mode: if challenge.echo {
+                        gix_prompt::Mode::Visible
+                    } else {
+                        gix_prompt::Mode::Hidden
+                    },
+                };
+                self.prompt.ask(&message, &opts).ok()
+            })
+            .collect()
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn check_host_key(session: &ssh2::Session, host: &str, port: u16, options: &Options) -> Result<(), Error> {
+    if options.host_key_check == HostKeyCheck::Disabled {
+        return Ok(());
+    }
+    let (key, key_type) = session
+        .host_key()
+        .ok_or_else(|| Error::MissingHostKey { host: host.into() })?;
+    let known_hosts_files = known_hosts_files(options);
+    let mut known_hosts = session.known_hosts()?;
+    for path in known_hosts_files.iter().filter(|path| path.is_file()) {
+        known_hosts.read_file(path, ssh2::KnownHostFileKind::OpenSSH)?;
+    }
+    match known_hosts.check_port(host, port, key) {
+        ssh2::CheckResult::Match => Ok(()),
+        ssh2::CheckResult::Mismatch => Err(Error::HostKeyMismatch { host: host.into() }),
+        ssh2::CheckResult::NotFound if options.host_key_check == HostKeyCheck::AcceptNew => {
+            let Some(path) = known_hosts_files.first() else {
+                return Err(Error::UnknownHostKey { host: host.into() });
+            };
+            let name = if port == 22 {
+                host.to_owned()
+            } else {
+                format!("[{host}]:{port}")
+            };
+            // Use a new set of known hosts so only the new entry is written.
+            let mut new_known_hosts = session.known_hosts()?;
+            new_known_hosts.add(&name, key, "", key_type.into())?;
+            let line = new_known_hosts
+                .hosts()?
+                .first()
+                .map(|new_host| new_known_hosts.write_string(new_host, ssh2::KnownHostFileKind::OpenSSH))
+                .transpose()?
+                .expect("the host was just added");
+            append_line(path, &line).map_err(|source| Error::WriteKnownHosts {
+                host: host.into(),
+                path: path.to_owned(),
+                source,
+            })
+        }
+        ssh2::CheckResult::NotFound | ssh2::CheckResult::Failure => Err(Error::UnknownHostKey { host: host.into() }),
+    }
+}
+
+fn append_line(path: &Path, line: &str) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.trim_end().as_bytes())?;
+    file.write_all(b"\n")
+}
+
+fn known_hosts_files(options: &Options) -> Vec<PathBuf> {
+    if options.known_hosts_files.is_empty() {
+        gix_path::env::home_dir()
+            .map(|home| home.join(".ssh").join("known_hosts"))
+            .into_iter()
+            .collect()
+    } else {
+        options.known_hosts_files.clone()
+    }
+}
+
+fn identity_files(options: &Options) -> Vec<PathBuf> {
+    if options.identity_files.is_empty() {
+        gix_path::env::home_dir()
+            .map(|home| {
+                ["id_ed25519", "id_ecdsa", "id_rsa"]
+                    .iter()
+                    .map(|name| home.join(".ssh").join(name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        options.identity_files.clone()
+    }
+}
+
+/// Read from the standard output of the remote command, turning what it wrote to standard error into an error
+/// if it ended unexpectedly.
+struct ReadStdoutFailOnError {
+    channel: ssh2::Channel,
+}
+
+impl Read for ReadStdoutFailOnError {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.channel.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            let mut stderr = Vec::new();
+            self.channel.stderr().read_to_end(&mut stderr).ok();
+            let message = stderr.trim();
+            if !message.is_empty() {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    message.to_str_lossy().into_owned(),
+                ));
+            }
+            self.channel.wait_close().ok();
+            if let Ok(status) = self.channel.exit_status() {
+                if status != 0 {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("The remote command exited with status {status}"),
+                    ));
+                }
+            }
+        }
+        Ok(n)
+    }
+}
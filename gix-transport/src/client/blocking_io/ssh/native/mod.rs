@@ -0,0 +1,56 @@
+use std::path::PathBuf;
+
+/// How to verify the key presented by the host we connect to, similar to the `StrictHostKeyChecking` option of `ssh`.
+#[derive(Default, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum HostKeyCheck {
+    /// Only connect to hosts whose key is known and matches, which is like `StrictHostKeyChecking=yes`.
+    #[default]
+    Strict,
+    /// Add the keys of unknown hosts to the known hosts file, but refuse to connect if a known host presents a different key,
+    /// which is like `StrictHostKeyChecking=accept-new`.
+    AcceptNew,
+    /// Don't verify host keys at all, which makes connections vulnerable to man-in-the-middle attacks.
+    /// This is like `StrictHostKeyChecking=no`, but doesn't add any keys to the known hosts file.
+    Disabled,
+}
+
+/// The options for use when connecting with the native ssh transport.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Determine how to verify the key of the host.
+    pub host_key_check: HostKeyCheck,
+    /// The files in the format of OpenSSH to read known host keys from. Files that don't exist are ignored.
+    /// Newly accepted host keys are written to the first file, see [`HostKeyCheck::AcceptNew`].
+    ///
+    /// If empty, `~/.ssh/known_hosts` is used.
+    pub known_hosts_files: Vec<PathBuf>,
+    /// The private key files to try for authentication, in order, after trying the ssh agent.
+    /// Files that don't exist are ignored.
+    ///
+    /// If empty, `~/.ssh/id_ed25519`, `~/.ssh/id_ecdsa` and `~/.ssh/id_rsa` are tried.
+    pub identity_files: Vec<PathBuf>,
+    /// If `true`, the default, try to authenticate with the keys provided by a running ssh agent first.
+    pub use_agent: bool,
+    /// If set, try keyboard-interactive authentication after all other methods failed, and answer the challenges of the
+    /// server like one-time passwords of a second factor by asking the user with this prompt.
+    #[cfg(feature = "ssh-client-native")]
+    pub prompt: Option<std::sync::Arc<dyn gix_prompt::Prompt + Send + Sync>>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            host_key_check: Default::default(),
+            known_hosts_files: Vec::new(),
+            identity_files: Vec::new(),
+            use_agent: true,
+            #[cfg(feature = "ssh-client-native")]
+            prompt: None,
+        }
+    }
+}
+
+#[cfg(feature = "ssh-client-native")]
+mod function;
+#[cfg(feature = "ssh-client-native")]
+pub use function::{connect, Error, Transport};
@@ -306,3 +306,45 @@ mod program_kind {
         }
     }
 }
+
+#[cfg(feature = "ssh-client-native")]
+mod native {
+    use crate::{
+        client::{ssh::native, Transport, TransportWithoutIO},
+        Protocol, Service,
+    };
+
+    #[test]
+    fn connect_only_accepts_ssh_urls_with_host() {
+        for url in ["file:///path/to/repo", "https://example.com/repo"] {
+            let url = gix_url::parse(url.into()).expect("valid url");
+            assert!(matches!(
+                native::connect(url, Protocol::V2, Default::default(), false),
+                Err(native::Error::UnsupportedScheme(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn connection_is_established_lazily_and_failures_are_reported() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("binding works");
+        let port = listener.local_addr().expect("bound").port();
+        drop(listener);
+
+        let url = gix_url::parse(format!("ssh://user@127.0.0.1:{port}/~/repo").as_str().into()).expect("valid url");
+        let mut transport =
+            native::connect(url, Protocol::V2, Default::default(), false).expect("no connection is made yet");
+        assert_eq!(
+            transport.to_url().as_ref(),
+            format!("ssh://user@127.0.0.1:{port}/~/repo")
+        );
+        let err = transport
+            .handshake(Service::UploadPack, &[])
+            .err()
+            .expect("nothing listens on the port");
+        assert!(
+            matches!(&err, crate::client::Error::NativeSsh(err) if matches!(**err, native::Error::Connect { .. })),
+            "{err:?}"
+        );
+    }
+}
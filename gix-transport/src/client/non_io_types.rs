@@ -40,6 +40,11 @@ pub(crate) mod connect {
         #[cfg(feature = "blocking-client")]
         /// Options to use if the scheme of the URL is `ssh`.
         pub ssh: crate::client::ssh::connect::Options,
+        #[cfg(feature = "blocking-client")]
+        /// The longest time to wait for data from the remote before failing with a timeout error, or `None` to wait forever.
+        ///
+        /// This affects connections to `git://` daemons and those made with the [native ssh transport](crate::client::ssh::native).
+        pub read_timeout: Option<std::time::Duration>,
         /// If `true`, all packetlines received or sent will be passed to the facilities of the `gix-trace` crate.
         pub trace: bool,
     }
@@ -66,6 +71,9 @@ pub(crate) mod connect {
             "'{0}' is not compiled in. Compile with the 'http-client-curl' or 'http-client-reqwest' cargo feature"
         )]
         CompiledWithoutHttp(gix_url::Scheme),
+        #[cfg(not(feature = "ssh-client-native"))]
+        #[error("The native ssh transport is not compiled in. Compile with the 'ssh-client-native' cargo feature")]
+        CompiledWithoutNativeSsh,
     }
 
     // TODO: maybe fix this workaround: want `IsSpuriousError`  in `Connection(…)`
@@ -107,6 +115,10 @@ mod error {
     type HttpError = std::convert::Infallible;
     #[cfg(not(feature = "blocking-client"))]
     type SshInvocationError = std::convert::Infallible;
+    #[cfg(feature = "ssh-client-native")]
+    type NativeSshError = ssh::native::Error;
+    #[cfg(not(feature = "ssh-client-native"))]
+    type NativeSshError = std::convert::Infallible;
 
     /// The error used in most methods of the [`client`][crate::client] module
     #[derive(thiserror::Error, Debug)]
@@ -140,6 +152,8 @@ mod error {
         Http(#[from] HttpError),
         #[error(transparent)]
         SshInvocation(SshInvocationError),
+        #[error(transparent)]
+        NativeSsh(Box<NativeSshError>),
         #[error("The repository path '{path}' could be mistaken for a command-line argument")]
         AmbiguousPath { path: BString },
     }
@@ -149,6 +163,8 @@ mod error {
             match self {
                 Error::Io(err) => err.is_spurious(),
                 Error::Http(err) => err.is_spurious(),
+                #[cfg(feature = "ssh-client-native")]
+                Error::NativeSsh(err) => err.is_spurious(),
                 _ => false,
             }
         }
@@ -62,6 +62,31 @@ fn http_identity_is_picked_up_from_url() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn http_proxy_receives_requests_with_absolute_urls() -> crate::Result {
+    let server = mock::serve_once("v1/http-handshake.response");
+    let mut client = gix_transport::client::http::connect(
+        "http://example.com/path/not/important/due/to/mock".try_into()?,
+        Protocol::V1,
+        false,
+    );
+    client
+        .configure(&http::Options {
+            proxy: Some(format!("{}:{}", server.addr.ip(), server.addr.port())),
+            ..Default::default()
+        })
+        .map_err(|err| err as Box<dyn std::error::Error>)?;
+    client.handshake(Service::UploadPack, &[])?;
+    let received = server.received_as_string();
+    let request_line = received.lines().next().expect("at least one line");
+    assert_eq!(
+        request_line,
+        "GET http://example.com/path/not/important/due/to/mock/info/refs?service=git-upload-pack HTTP/1.1",
+        "the proxy is asked for the complete URL"
+    );
+    Ok(())
+}
+
 // based on a test in cargo
 #[test]
 fn http_will_use_pipelining() {
@@ -443,3 +443,38 @@ async fn handshake_v2_and_request_inner() -> crate::Result {
     );
     Ok(())
 }
+
+#[test]
+#[cfg(feature = "blocking-client")]
+fn daemon_that_does_not_respond_fails_after_read_timeout() -> crate::Result {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0")?;
+    let port = listener.local_addr()?.port();
+    let server = std::thread::spawn(move || -> std::io::Result<()> {
+        let (mut stream, _addr) = listener.accept()?;
+        // read the request, but never answer it.
+        let mut buf = Vec::new();
+        std::io::Read::read_to_end(&mut stream, &mut buf)?;
+        Ok(())
+    });
+
+    let mut transport = gix_transport::connect(
+        format!("git://127.0.0.1:{port}/repo.git").as_str(),
+        gix_transport::client::connect::Options {
+            version: Protocol::V1,
+            read_timeout: Some(std::time::Duration::from_millis(50)),
+            ..Default::default()
+        },
+    )?;
+    let err = transport.handshake(Service::UploadPack, &[]).map(|_| ()).unwrap_err();
+    let client::Error::Io(err) = err else {
+        panic!("expected an IO error, got {err:?}")
+    };
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+    assert_eq!(
+        err.to_string(),
+        "The remote didn't send any data within the read timeout before the next packet line was received"
+    );
+    drop(transport);
+    server.join().expect("no panic")?;
+    Ok(())
+}
@@ -65,6 +65,9 @@ pub struct Info {
     /// The time at which the commit was created. It will only be `Some(_)` if the chosen traversal was
     /// taking dates into consideration.
     pub commit_time: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// If `true`, this commit is hidden and was only returned as it's the parent of a commit that wasn't, which marks
+    /// the boundary of the traversal.
+    pub is_boundary: bool,
 }
 
 enum Either<'buf, 'cache> {
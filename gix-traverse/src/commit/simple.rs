@@ -70,6 +70,12 @@ pub(super) struct State {
     seen: HashSet<ObjectId>,
     parents_buf: Vec<u8>,
     parent_ids: SmallVec<[(ObjectId, SecondsSinceUnixEpoch); 2]>,
+    /// The tips whose ancestry is hidden, which are traversed before the first commit is returned.
+    hidden_tips: Vec<ObjectId>,
+    /// All hidden commits that are also reachable from the tips, which is where the traversal stops.
+    hidden: HashSet<ObjectId>,
+    /// If `Some`, the hidden parents of returned commits, which are returned last.
+    boundary: Option<VecDeque<ObjectId>>,
 }
 
 ///
@@ -77,7 +83,10 @@ pub(super) struct State {
 mod init {
     use gix_date::SecondsSinceUnixEpoch;
     use gix_hash::{oid, ObjectId};
+    use gix_hashtable::HashSet;
     use gix_object::{CommitRefIter, FindExt};
+    use smallvec::SmallVec;
+    use std::collections::VecDeque;
 
     use super::{
         super::{simple::Sorting, Either, Info, ParentIds, Parents, Simple},
@@ -93,6 +102,9 @@ mod init {
                 seen: Default::default(),
                 parents_buf: vec![],
                 parent_ids: Default::default(),
+                hidden_tips: Vec::new(),
+                hidden: Default::default(),
+                boundary: None,
             }
         }
     }
@@ -103,6 +115,11 @@ mod init {
             self.queue.clear();
             self.buf.clear();
             self.seen.clear();
+            self.hidden_tips.clear();
+            self.hidden.clear();
+            if let Some(boundary) = self.boundary.as_mut() {
+                boundary.clear();
+            }
         }
     }
 
@@ -148,6 +165,27 @@ mod init {
             self
         }
 
+        /// Hide the given `tips` along with all of their ancestors, so that the traversal stops at them and doesn't return them,
+        /// just like `git rev-list <tips> ^<hidden>` does.
+        ///
+        /// Hidden commits are only traversed until it's clear which of the commits reachable from our tips are hidden, which
+        /// happens before the first commit is returned. Generation numbers of the [commit-graph](Self::commit_graph()) are used to
+        /// do so correctly if available, and commit times otherwise, which can lead to hidden commits being returned if clocks
+        /// were skewed, just like in `git`.
+        pub fn hide(mut self, tips: impl IntoIterator<Item = impl Into<ObjectId>>) -> Self {
+            self.state.hidden_tips.extend(tips.into_iter().map(Into::into));
+            self
+        }
+
+        /// If `toggle` is `true`, return the hidden parents of all returned commits after all other commits were returned,
+        /// with [`Info::is_boundary`] set, like `git rev-list --boundary` does.
+        ///
+        /// This is useful to know where the traversal stopped when [hiding commits](Self::hide()).
+        pub fn boundary(mut self, toggle: bool) -> Self {
+            self.state.boundary = toggle.then(VecDeque::new);
+            self
+        }
+
         /// Set the commitgraph as `cache` to greatly accelerate any traversal.
         ///
         /// The cache will be used if possible, but we will fall-back without error to using the object
@@ -250,6 +288,11 @@ mod init {
         type Item = Result<Info, Error>;
 
         fn next(&mut self) -> Option<Self::Item> {
+            if !self.state.hidden_tips.is_empty() {
+                if let Err(err) = self.find_hidden() {
+                    return Some(Err(err));
+                }
+            }
             if matches!(self.parents, Parents::First) {
                 self.next_by_topology()
             } else {
@@ -261,6 +304,7 @@ mod init {
                     }
                 }
             }
+            .or_else(|| self.next_boundary())
         }
     }
 
@@ -298,7 +342,10 @@ mod init {
                     for (id, parent_commit_time) in state.parent_ids.drain(..) {
                         parents.push(id);
                         let was_inserted = state.seen.insert(id);
-                        if !(was_inserted && (self.predicate)(&id)) {
+                        if !(was_inserted
+                            && is_visible(&state.hidden, &mut state.boundary, &id)
+                            && (self.predicate)(&id))
+                        {
                             continue;
                         }
 
@@ -315,7 +362,10 @@ mod init {
                             Ok(gix_object::commit::ref_iter::Token::Parent { id }) => {
                                 parents.push(id);
                                 let was_inserted = state.seen.insert(id);
-                                if !(was_inserted && (self.predicate)(&id)) {
+                                if !(was_inserted
+                                    && is_visible(&state.hidden, &mut state.boundary, &id)
+                                    && (self.predicate)(&id))
+                                {
                                     continue;
                                 }
 
@@ -340,6 +390,7 @@ mod init {
                 id: oid,
                 parent_ids: parents,
                 commit_time: Some(commit_time),
+                is_boundary: false,
             }))
         }
     }
@@ -365,7 +416,8 @@ mod init {
                     for (id, _commit_time) in state.parent_ids.drain(..) {
                         parents.push(id);
                         let was_inserted = state.seen.insert(id);
-                        if was_inserted && (self.predicate)(&id) {
+                        if was_inserted && is_visible(&state.hidden, &mut state.boundary, &id) && (self.predicate)(&id)
+                        {
                             state.next.push_back(id);
                         }
                         if matches!(self.parents, Parents::First) {
@@ -380,7 +432,10 @@ mod init {
                             Ok(gix_object::commit::ref_iter::Token::Parent { id }) => {
                                 parents.push(id);
                                 let was_inserted = state.seen.insert(id);
-                                if was_inserted && (self.predicate)(&id) {
+                                if was_inserted
+                                    && is_visible(&state.hidden, &mut state.boundary, &id)
+                                    && (self.predicate)(&id)
+                                {
                                     state.next.push_back(id);
                                 }
                                 if matches!(self.parents, Parents::First) {
@@ -398,9 +453,147 @@ mod init {
                 id: oid,
                 parent_ids: parents,
                 commit_time: None,
+                is_boundary: false,
             }))
         }
     }
+
+    /// Utilities
+    impl<Find, Predicate> Simple<Find, Predicate>
+    where
+        Find: gix_object::Find,
+        Predicate: FnMut(&oid) -> bool,
+    {
+        /// Traverse the hidden tips and our tips at the same time, from the youngest to the oldest commit, and mark all
+        /// ancestors of hidden commits as hidden, until only hidden commits are left to be traversed. Then drop all tips
+        /// that turned out to be hidden.
+        fn find_hidden(&mut self) -> Result<(), Error> {
+            let state = &mut self.state;
+            let cache = self.cache.as_ref();
+            let mut is_hidden_by_id = gix_hashtable::HashMap::<ObjectId, bool>::default();
+            let mut parents_by_id = gix_hashtable::HashMap::<ObjectId, SmallVec<[ObjectId; 2]>>::default();
+            let mut queue = gix_revwalk::PriorityQueue::<GenAndCommitTime, ObjectId>::new();
+
+            let tips: Vec<_> = state.next.iter().chain(state.queue.iter_unordered()).copied().collect();
+            let hidden_tips = std::mem::take(&mut state.hidden_tips);
+            for (id, is_hidden) in tips
+                .into_iter()
+                .map(|id| (id, false))
+                .chain(hidden_tips.into_iter().map(|id| (id, true)))
+            {
+                let (key, parents) = lookup(cache, &self.objects, &id, &mut state.parents_buf)?;
+                *is_hidden_by_id.entry(id).or_default() |= is_hidden;
+                parents_by_id.insert(id, parents);
+                queue.insert(key, id);
+            }
+
+            while queue.iter_unordered().any(|id| !is_hidden_by_id[id]) {
+                let id = queue.pop_value().expect("non-empty");
+                let is_hidden = is_hidden_by_id[&id];
+                let parents = parents_by_id.get(&id).cloned().unwrap_or_default();
+                for (idx, parent_id) in parents.into_iter().enumerate() {
+                    // Hidden commits hide all of their parents, while the ones we see follow only the parents we traverse.
+                    if !is_hidden && idx > 0 && matches!(self.parents, Parents::First) {
+                        break;
+                    }
+                    match is_hidden_by_id.get_mut(&parent_id) {
+                        Some(parent_is_hidden) if *parent_is_hidden || !is_hidden => continue,
+                        Some(parent_is_hidden) => *parent_is_hidden = true,
+                        None => {
+                            is_hidden_by_id.insert(parent_id, is_hidden);
+                        }
+                    }
+                    if !parents_by_id.contains_key(&parent_id) {
+                        match lookup(cache, &self.objects, &parent_id, &mut state.parents_buf) {
+                            Ok((key, parents)) => {
+                                parents_by_id.insert(parent_id, parents);
+                                queue.insert(key, parent_id);
+                            }
+                            // Parents of shallow commits aren't present.
+                            Err(Error::Find(gix_object::find::existing_iter::Error::NotFound { .. })) => {}
+                            Err(err) => return Err(err),
+                        }
+                    } else {
+                        let (key, _) = lookup(cache, &self.objects, &parent_id, &mut state.parents_buf)?;
+                        queue.insert(key, parent_id);
+                    }
+                }
+            }
+
+            state.hidden = is_hidden_by_id
+                .into_iter()
+                .filter_map(|(id, is_hidden)| is_hidden.then_some(id))
+                .collect();
+            let hidden = &state.hidden;
+            state.next.retain(|id| !hidden.contains(id));
+            state.queue = std::mem::replace(&mut state.queue, gix_revwalk::PriorityQueue::new())
+                .into_iter_unordered()
+                .filter(|(_time, id)| !hidden.contains(id))
+                .collect();
+            Ok(())
+        }
+
+        fn next_boundary(&mut self) -> Option<Result<Info, Error>> {
+            let state = &mut self.state;
+            let id = state.boundary.as_mut()?.pop_front()?;
+            Some(
+                lookup(self.cache.as_ref(), &self.objects, &id, &mut state.buf).map(|((_, commit_time), parents)| {
+                    Info {
+                        id,
+                        parent_ids: parents.into_iter().collect(),
+                        commit_time: (!matches!(self.sorting, Sorting::BreadthFirst)).then_some(commit_time),
+                        is_boundary: true,
+                    }
+                }),
+            )
+        }
+    }
+
+    /// Return `true` if `id` isn't `hidden`, or remember it as `boundary` commit otherwise.
+    fn is_visible(hidden: &HashSet<ObjectId>, boundary: &mut Option<VecDeque<ObjectId>>, id: &oid) -> bool {
+        if !hidden.contains(id) {
+            return true;
+        }
+        if let Some(boundary) = boundary.as_mut() {
+            boundary.push_back(id.to_owned());
+        }
+        false
+    }
+
+    /// The generation and commit time of a commit, which is the order in which we find hidden commits.
+    type GenAndCommitTime = (u32, SecondsSinceUnixEpoch);
+
+    /// Return the generation and commit time of the commit with `id`, along with its parents.
+    ///
+    /// Commits that aren't in the commit-graph have the highest possible generation, as they are younger than all commits in it.
+    fn lookup(
+        cache: Option<&gix_commitgraph::Graph>,
+        objects: &impl gix_object::Find,
+        id: &oid,
+        buf: &mut Vec<u8>,
+    ) -> Result<(GenAndCommitTime, SmallVec<[ObjectId; 2]>), Error> {
+        if let Some(commit) = cache.and_then(|cache| cache.commit_by_id(id)) {
+            let mut parent_ids = SmallVec::new();
+            for pos in commit.iter_parents() {
+                match pos {
+                    Ok(pos) => parent_ids.push(cache.expect("present").id_at(pos).to_owned()),
+                    // Fall back to the object database if the cache is corrupt.
+                    Err(_) => return lookup(None, objects, id, buf),
+                }
+            }
+            return Ok((
+                (
+                    commit.generation(),
+                    commit.committer_timestamp() as SecondsSinceUnixEpoch,
+                ),
+                parent_ids,
+            ));
+        }
+        let commit = objects.find_commit_iter(id, buf)?;
+        let parent_ids = commit.parent_ids().collect();
+        let commit_time = commit.committer()?.time.seconds;
+        Ok(((u32::MAX, commit_time), parent_ids))
+    }
 }
 
 fn collect_parents(
@@ -158,12 +158,14 @@ where
             let (_, time) = gen_and_commit_time(commit)?;
             let parent_ids = w.collect_all_parents(id)?.into_iter().map(|e| e.0).collect();
 
+            let queue_time = w.queue_time(id, time)?;
             w.topo_queue.push(
-                time,
+                queue_time,
                 Info {
                     id: *id,
                     parent_ids,
                     commit_time: Some(time),
+                    is_boundary: false,
                 },
             );
         }
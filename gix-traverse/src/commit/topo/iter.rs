@@ -1,6 +1,7 @@
 use crate::commit::topo::{Error, Sorting, WalkFlags};
 use crate::commit::{find, Either, Info, Parents, Topo};
 use gix_hash::{oid, ObjectId};
+use gix_object::FindExt;
 use gix_revwalk::PriorityQueue;
 use smallvec::SmallVec;
 
@@ -13,6 +14,7 @@ pub(in crate::commit) type GenAndCommitTime = (u32, i64);
 #[derive(Debug)]
 pub(in crate::commit) enum Queue {
     Date(PriorityQueue<i64, Info>),
+    AuthorDate(PriorityQueue<i64, Info>),
     Topo(Vec<(i64, Info)>),
 }
 
@@ -20,20 +22,22 @@ impl Queue {
     pub(super) fn new(s: Sorting) -> Self {
         match s {
             Sorting::DateOrder => Self::Date(PriorityQueue::new()),
+            Sorting::AuthorDateOrder => Self::AuthorDate(PriorityQueue::new()),
             Sorting::TopoOrder => Self::Topo(vec![]),
         }
     }
 
-    pub(super) fn push(&mut self, commit_time: i64, info: Info) {
+    /// Queue `info`, ordered by `time`, which is the author time when sorting by author date and the commit time otherwise.
+    pub(super) fn push(&mut self, time: i64, info: Info) {
         match self {
-            Self::Date(q) => q.insert(commit_time, info),
-            Self::Topo(q) => q.push((commit_time, info)),
+            Self::Date(q) | Self::AuthorDate(q) => q.insert(time, info),
+            Self::Topo(q) => q.push((time, info)),
         }
     }
 
     fn pop(&mut self) -> Option<Info> {
         match self {
-            Self::Date(q) => q.pop().map(|(_, info)| info),
+            Self::Date(q) | Self::AuthorDate(q) => q.pop().map(|(_, info)| info),
             Self::Topo(q) => q.pop().map(|(_, info)| info),
         }
     }
@@ -49,6 +53,14 @@ impl<Find, Predicate> Topo<Find, Predicate>
 where
     Find: gix_object::Find,
 {
+    /// Return the time by which `id` is ordered in the topo-queue, which is its `commit_time` unless sorting by author date.
+    pub(super) fn queue_time(&mut self, id: &oid, commit_time: i64) -> Result<i64, Error> {
+        if !matches!(self.topo_queue, Queue::AuthorDate(_)) {
+            return Ok(commit_time);
+        }
+        Ok(self.find.find_commit_iter(id, &mut self.buf)?.author()?.time.seconds)
+    }
+
     pub(super) fn compute_indegrees_to_depth(&mut self, gen_cutoff: u32) -> Result<(), Error> {
         while let Some(((gen, _), _)) = self.indegree_queue.peek() {
             if *gen >= gen_cutoff {
@@ -129,12 +141,14 @@ where
             }
 
             let parent_ids = self.collect_all_parents(&pid)?.into_iter().map(|e| e.0).collect();
+            let time = self.queue_time(&pid, parent_commit_time)?;
             self.topo_queue.push(
-                parent_commit_time,
+                time,
                 Info {
                     id: pid,
                     parent_ids,
                     commit_time: Some(parent_commit_time),
+                    is_boundary: false,
                 },
             );
         }
@@ -62,6 +62,12 @@ pub enum Sorting {
     ///
     /// In the *sample history* the order would be `8, 6, 5, 3, 7, 4, 2, 1`
     TopoOrder,
+    /// Show no parents before all of its children are shown, but otherwise show
+    /// commits in the author timestamp order, like `git log --author-date-order`.
+    ///
+    /// Note that this needs to read the author of each commit from the object database,
+    /// as it isn't part of the commit-graph.
+    AuthorDateOrder,
 }
 
 mod init;
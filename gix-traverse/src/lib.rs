@@ -4,5 +4,7 @@
 
 pub mod commit;
 
+pub mod objects;
+
 /// Tree traversal
 pub mod tree;
@@ -0,0 +1,320 @@
+//! Traverse all objects reachable from commits, like `git rev-list --objects` does, while optionally leaving out trees and
+//! blobs according to a [`Filter`] as used for partial clones.
+use std::fmt::{Display, Formatter};
+
+use gix_hash::ObjectId;
+use gix_hashtable::HashMap;
+use gix_object::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    FindExt,
+};
+
+/// A filter to leave out trees and blobs, as specified with `--filter=<spec>` when cloning partially.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Filter {
+    /// Leave out all blobs, as specified with `blob:none`.
+    BlobNone,
+    /// Leave out all blobs whose size in bytes is at least the given one, as specified with `blob:limit=<n>[kmg]`.
+    BlobLimit(u64),
+    /// Leave out all trees and blobs whose depth from the root tree is at least the given one, as specified with
+    /// `tree:<depth>`. The root tree has a depth of 0, and the entries in it have a depth of 1.
+    TreeDepth(u64),
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod filter {
+    use gix_object::bstr::BString;
+
+    /// The error returned by [`Filter::from_bytes()`](super::Filter::from_bytes()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("The filter specification \"{spec}\" is not supported")]
+        Unsupported { spec: BString },
+        #[error("The filter specification \"{spec}\" does not contain a valid number")]
+        InvalidNumber { spec: BString },
+    }
+}
+
+impl Filter {
+    /// Parse `spec` as given to `--filter=<spec>`, like `blob:none`, `blob:limit=1m` or `tree:0`.
+    pub fn from_bytes(spec: &BStr) -> Result<Self, filter::Error> {
+        let invalid_number = || filter::Error::InvalidNumber { spec: spec.into() };
+        if spec == "blob:none" {
+            Ok(Filter::BlobNone)
+        } else if let Some(limit) = spec.strip_prefix(b"blob:limit=") {
+            parse_size(limit).map(Filter::BlobLimit).ok_or_else(invalid_number)
+        } else if let Some(depth) = spec.strip_prefix(b"tree:") {
+            depth
+                .to_str()
+                .ok()
+                .and_then(|depth| depth.parse().ok())
+                .map(Filter::TreeDepth)
+                .ok_or_else(invalid_number)
+        } else {
+            Err(filter::Error::Unsupported { spec: spec.into() })
+        }
+    }
+}
+
+impl Display for Filter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Filter::BlobNone => f.write_str("blob:none"),
+            Filter::BlobLimit(limit) => write!(f, "blob:limit={limit}"),
+            Filter::TreeDepth(depth) => write!(f, "tree:{depth}"),
+        }
+    }
+}
+
+/// Parse a size in bytes with an optional unit suffix of `k`, `m` or `g`, just like `git` does.
+fn parse_size(input: &[u8]) -> Option<u64> {
+    let (number, factor) = match input.last()? {
+        b'k' | b'K' => (&input[..input.len() - 1], 1 << 10),
+        b'm' | b'M' => (&input[..input.len() - 1], 1 << 20),
+        b'g' | b'G' => (&input[..input.len() - 1], 1 << 30),
+        _ => (input, 1),
+    };
+    number.to_str().ok()?.parse::<u64>().ok()?.checked_mul(factor)
+}
+
+/// An object returned by [`Objects`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Item {
+    /// The id of the object.
+    pub id: ObjectId,
+    /// The kind of the object.
+    pub kind: gix_object::Kind,
+    /// The slash-separated path of the tree or blob relative to the root tree of the commit it was first seen in,
+    /// which is empty for commits and root trees.
+    pub path: BString,
+}
+
+/// The error returned by [`Objects`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    Commits(#[from] crate::commit::simple::Error),
+    #[error(transparent)]
+    Find(#[from] gix_object::find::existing_iter::Error),
+    #[error(transparent)]
+    FindHeader(#[from] gix_object::find::existing::Error),
+    #[error(transparent)]
+    Decode(#[from] gix_object::decode::Error),
+}
+
+/// An iterator over all commits returned by a commit traversal, followed by all trees and blobs reachable from them,
+/// like `git rev-list --objects` does.
+///
+/// Trees and blobs that are reachable from [boundary](crate::commit::Info::is_boundary) commits are left out, just like
+/// the boundary commits themselves, which is why the commit traversal should be configured to return them.
+///
+/// ### Deviation
+///
+/// When [filtering by depth](Filter::TreeDepth), `git` returns trees once more if they are seen again closer to the
+/// root tree, while we return each object only once.
+pub struct Objects<Find, Commits> {
+    objects: Find,
+    commits: Option<Commits>,
+    filter: Option<Filter>,
+    /// The root trees of returned commits, to be traversed once all commits are known.
+    trees: Vec<ObjectId>,
+    /// The root trees of boundary commits, whose objects are not to be returned.
+    uninteresting_trees: Vec<ObjectId>,
+    /// Trees and blobs that were returned or are uninteresting, along with the smallest depth at which they were seen.
+    seen: HashMap<ObjectId, u64>,
+    /// Trees and blobs left to visit, with the next one to visit last.
+    stack: Vec<Entry>,
+    buf: Vec<u8>,
+}
+
+/// A tree or blob to visit.
+struct Entry {
+    id: ObjectId,
+    is_tree: bool,
+    path: BString,
+    depth: u64,
+}
+
+impl<Find, Commits> Objects<Find, Commits>
+where
+    Find: gix_object::Find + gix_object::FindHeader,
+    Commits: Iterator<Item = Result<crate::commit::Info, crate::commit::simple::Error>>,
+{
+    /// Create a new instance to return all `commits` that aren't boundary commits, followed by all trees and blobs
+    /// reachable from them that pass `filter`, and which aren't reachable from boundary commits, with `objects` used
+    /// for lookups.
+    ///
+    /// Note that all commits are traversed before the first tree is returned.
+    pub fn new(commits: Commits, objects: Find, filter: Option<Filter>) -> Self {
+        Objects {
+            objects,
+            commits: Some(commits),
+            filter,
+            trees: Vec::new(),
+            uninteresting_trees: Vec::new(),
+            seen: HashMap::default(),
+            stack: Vec::new(),
+            buf: Vec::new(),
+        }
+    }
+
+    /// Return the next commit which isn't a boundary commit, or `None` if there is none.
+    fn next_commit(&mut self) -> Option<Result<Item, Error>> {
+        let commits = self.commits.as_mut()?;
+        for res in commits {
+            let info = match res {
+                Ok(info) => info,
+                Err(err) => return Some(Err(err.into())),
+            };
+            let tree_id = match self
+                .objects
+                .find_commit_iter(&info.id, &mut self.buf)
+                .map_err(Error::from)
+                .and_then(|mut commit| commit.tree_id().map_err(Error::from))
+            {
+                Ok(tree_id) => tree_id,
+                Err(err) => return Some(Err(err)),
+            };
+            if info.is_boundary {
+                self.uninteresting_trees.push(tree_id);
+                continue;
+            }
+            self.trees.push(tree_id);
+            return Some(Ok(Item {
+                id: info.id,
+                kind: gix_object::Kind::Commit,
+                path: BString::default(),
+            }));
+        }
+        self.commits = None;
+        None
+    }
+
+    /// Mark all trees and blobs reachable from the root trees of boundary commits as seen, and prepare the
+    /// root trees of returned commits for traversal.
+    fn prepare_trees(&mut self) -> Result<(), Error> {
+        let mut stack = std::mem::take(&mut self.uninteresting_trees);
+        while let Some(tree_id) = stack.pop() {
+            if self.seen.insert(tree_id, 0).is_some() {
+                continue;
+            }
+            for entry in self.objects.find_tree_iter(&tree_id, &mut self.buf)? {
+                let entry = entry?;
+                if entry.mode.is_tree() {
+                    stack.push(entry.oid.to_owned());
+                } else if !entry.mode.is_commit() {
+                    self.seen.insert(entry.oid.to_owned(), 0);
+                }
+            }
+        }
+        self.stack = std::mem::take(&mut self.trees)
+            .into_iter()
+            .rev()
+            .map(|id| Entry {
+                id,
+                is_tree: true,
+                path: BString::default(),
+                depth: 0,
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn blob_size(&self, id: &gix_hash::oid) -> Result<u64, Error> {
+        let header = self
+            .objects
+            .try_header(id)
+            .map_err(gix_object::find::existing::Error::Find)?
+            .ok_or_else(|| gix_object::find::existing::Error::NotFound { oid: id.to_owned() })?;
+        Ok(header.size)
+    }
+
+    /// Visit `entry` and return it as item if it should be returned.
+    fn visit(
+        &mut self,
+        Entry {
+            id,
+            is_tree,
+            path,
+            depth,
+        }: Entry,
+    ) -> Result<Option<Item>, Error> {
+        let seen_at_depth = self.seen.get(&id).copied();
+        let is_depth_limited = matches!(self.filter, Some(Filter::TreeDepth(_)));
+        if matches!(seen_at_depth, Some(seen_depth) if !is_tree || !is_depth_limited || seen_depth <= depth) {
+            return Ok(None);
+        }
+        let is_filtered = match self.filter {
+            Some(Filter::TreeDepth(max_depth)) => depth >= max_depth,
+            Some(Filter::BlobNone) => !is_tree,
+            Some(Filter::BlobLimit(limit)) => !is_tree && self.blob_size(&id)? >= limit,
+            None => false,
+        };
+        if is_filtered {
+            return Ok(None);
+        }
+        self.seen.insert(id, depth);
+
+        if is_tree {
+            // Trees seen before are only traversed again to find entries that were too deep to be returned back then.
+            let first_child = self.stack.len();
+            for entry in self.objects.find_tree_iter(&id, &mut self.buf)? {
+                let entry = entry?;
+                if entry.mode.is_commit() {
+                    continue;
+                }
+                let mut child_path = path.clone();
+                if !child_path.is_empty() {
+                    child_path.push_byte(b'/');
+                }
+                child_path.push_str(entry.filename);
+                self.stack.push(Entry {
+                    id: entry.oid.to_owned(),
+                    is_tree: entry.mode.is_tree(),
+                    path: child_path,
+                    depth: depth + 1,
+                });
+            }
+            self.stack[first_child..].reverse();
+        }
+        Ok(seen_at_depth.is_none().then_some(Item {
+            id,
+            kind: if is_tree {
+                gix_object::Kind::Tree
+            } else {
+                gix_object::Kind::Blob
+            },
+            path,
+        }))
+    }
+}
+
+impl<Find, Commits> Iterator for Objects<Find, Commits>
+where
+    Find: gix_object::Find + gix_object::FindHeader,
+    Commits: Iterator<Item = Result<crate::commit::Info, crate::commit::simple::Error>>,
+{
+    type Item = Result<Item, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.commits.is_some() {
+            if let Some(res) = self.next_commit() {
+                return Some(res);
+            }
+            if let Err(err) = self.prepare_trees() {
+                return Some(Err(err));
+            }
+        }
+        while let Some(entry) = self.stack.pop() {
+            match self.visit(entry) {
+                Ok(Some(item)) => return Some(Ok(item)),
+                Ok(None) => continue,
+                Err(err) => return Some(Err(err)),
+            }
+        }
+        None
+    }
+}
@@ -420,3 +420,146 @@ mod adjusted_dates {
         .check()
     }
 }
+
+mod hidden {
+    use gix_hash::ObjectId;
+    use gix_traverse::commit::{simple::Sorting, Parents, Simple};
+
+    use crate::hex_to_id;
+
+    /// Return the ids of all commits along with their boundary flag, making sure they are the same with all sort modes
+    /// and with or without the commit-graph.
+    fn traverse(
+        tips: &[&str],
+        hidden: &[&str],
+        parents: Parents,
+        boundary: bool,
+    ) -> crate::Result<Vec<(ObjectId, bool)>> {
+        let dir = gix_testtools::scripted_fixture_read_only_standalone("make_traversal_repo_for_commits_same_date.sh")?;
+        let store = gix_odb::at(dir.join(".git").join("objects"))?;
+        let mut out = None;
+        for use_commitgraph in [false, true] {
+            for sorting in [Sorting::BreadthFirst, Sorting::ByCommitTimeNewestFirst] {
+                let commits = Simple::new(tips.iter().copied().map(hex_to_id), &store)
+                    .sorting(sorting)?
+                    .parents(parents)
+                    .hide(hidden.iter().copied().map(hex_to_id))
+                    .boundary(boundary)
+                    .commit_graph(
+                        use_commitgraph
+                            .then(|| gix_commitgraph::at(store.store_ref().path().join("info")))
+                            .transpose()?,
+                    )
+                    .map(|res| res.map(|info| (info.id, info.is_boundary)))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if let Some(previous) = out.as_ref() {
+                    assert_eq!(
+                        &commits, previous,
+                        "sorting = {sorting:?}, commit-graph = {use_commitgraph}"
+                    );
+                }
+                out = Some(commits);
+            }
+        }
+        Ok(out.expect("at least one traversal"))
+    }
+
+    fn ids(hex: &[&str], is_boundary: bool) -> Vec<(ObjectId, bool)> {
+        hex.iter().map(|hex| (hex_to_id(hex), is_boundary)).collect()
+    }
+
+    #[test]
+    fn ancestors_of_hidden_commits_are_not_returned() -> crate::Result {
+        let commits = traverse(
+            &["01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"], /* m1b1 */
+            &["9556057aee5abb06912922e9f26c46386a816822"], /* c4 */
+            Parents::All,
+            false,
+        )?;
+        assert_eq!(
+            commits,
+            ids(
+                &[
+                    "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b", /* m1b1 */
+                    "efd9a841189668f1bab5b8ebade9cd0a1b139a37", /* c5 */
+                    "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353", /* b1c2 */
+                    "9152eeee2328073cf23dcf8e90c949170b711659", /* b1c1 */
+                ],
+                false
+            ),
+            "this is `git rev-list m1b1 ^c4`"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn boundary_commits_are_returned_last() -> crate::Result {
+        let commits = traverse(
+            &["01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"], /* m1b1 */
+            &["ce2e8ffaa9608a26f7b21afc1db89cadb54fd353"], /* b1c2 */
+            Parents::All,
+            true,
+        )?;
+        let mut expected = ids(
+            &[
+                "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b", /* m1b1 */
+                "efd9a841189668f1bab5b8ebade9cd0a1b139a37", /* c5 */
+            ],
+            false,
+        );
+        expected.extend(ids(
+            &[
+                "ce2e8ffaa9608a26f7b21afc1db89cadb54fd353", /* b1c2 */
+                "9556057aee5abb06912922e9f26c46386a816822", /* c4 */
+            ],
+            true,
+        ));
+        assert_eq!(
+            commits, expected,
+            "c4 is hidden as it's reachable from b1c2, and both are parents of returned commits. \
+             `git` returns the same boundary commits, but ordered by date"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn first_parent_only_ignores_hidden_side_branches() -> crate::Result {
+        let commits = traverse(
+            &["01ec18a3ebf2855708ad3c9d244306bc1fae3e9b"], /* m1b1 */
+            &["9152eeee2328073cf23dcf8e90c949170b711659"], /* b1c1 */
+            Parents::First,
+            true,
+        )?;
+        let mut expected = ids(
+            &[
+                "01ec18a3ebf2855708ad3c9d244306bc1fae3e9b", /* m1b1 */
+                "efd9a841189668f1bab5b8ebade9cd0a1b139a37", /* c5 */
+            ],
+            false,
+        );
+        // Deviation: `git` also returns b1c2 as boundary, as it's a hidden parent of m1b1, but we never look at second parents.
+        expected.extend(ids(&["9556057aee5abb06912922e9f26c46386a816822" /* c4 */], true));
+        assert_eq!(
+            commits, expected,
+            "hidden commits still hide all of their ancestors, even though only the first parent is followed"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_tips_are_not_returned() -> crate::Result {
+        let commits = traverse(
+            &[
+                "efd9a841189668f1bab5b8ebade9cd0a1b139a37", /* c5 */
+                "9152eeee2328073cf23dcf8e90c949170b711659", /* b1c1 */
+            ],
+            &["ce2e8ffaa9608a26f7b21afc1db89cadb54fd353"], /* b1c2 */
+            Parents::All,
+            true,
+        )?;
+        let mut expected = ids(&["efd9a841189668f1bab5b8ebade9cd0a1b139a37" /* c5 */], false);
+        expected.extend(ids(&["9556057aee5abb06912922e9f26c46386a816822" /* c4 */], true));
+        assert_eq!(commits, expected);
+        Ok(())
+    }
+}
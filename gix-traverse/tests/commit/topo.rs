@@ -370,3 +370,31 @@ mod date_order {
         .check()
     }
 }
+
+mod author_date_order {
+    use gix_hash::ObjectId;
+    use gix_object::bstr::ByteSlice;
+    use gix_traverse::commit::topo;
+
+    use crate::hex_to_id;
+
+    #[test]
+    fn matches_git() -> crate::Result {
+        let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_for_author_date_order.sh")?;
+        let store = gix_odb::at(dir.join(".git").join("objects"))?;
+        for (sorting, name) in [
+            (topo::Sorting::DateOrder, "date-order"),
+            (topo::Sorting::AuthorDateOrder, "author-date-order"),
+        ] {
+            let baseline = std::fs::read(dir.join(format!("{name}.baseline")))?;
+            let expected: Vec<_> = baseline.lines().map(|hex| hex_to_id(hex.to_str().unwrap())).collect();
+            let actual = topo::Builder::from_iters(&store, Some(expected[0]), None::<Vec<ObjectId>>)
+                .sorting(sorting)
+                .build()?
+                .map(|res| res.map(|info| info.id))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(actual, expected, "{name}");
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+#!/bin/bash
+set -eu -o pipefail
+
+# Times are offsets to a fixed date, chosen such that the order of author dates differs from the one of commit dates.
+function commit_at() {
+  local message=${1:?first argument is the commit message}
+  GIT_AUTHOR_DATE="$((1600000000 + ${2:?second argument is the author time})) +0000" \
+  GIT_COMMITTER_DATE="$((1600000000 + ${3:?third argument is the commit time})) +0000" \
+    git commit -q --allow-empty -m "$message"
+}
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+commit_at c1 1000 1000
+
+git checkout -q -b branch
+commit_at b1 1100 1400
+commit_at b2 1500 1500
+
+git checkout -q main
+commit_at c2 1300 1200
+commit_at c3 1200 1300
+
+GIT_AUTHOR_DATE="1600001600 +0000" GIT_COMMITTER_DATE="1600001600 +0000" git merge -q branch -m merge
+
+git rev-list --date-order HEAD > date-order.baseline
+git rev-list --author-date-order HEAD > author-date-order.baseline
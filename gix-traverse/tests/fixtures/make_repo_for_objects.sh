@@ -0,0 +1,44 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git checkout -q -b main
+
+mkdir -p dir/sub
+echo a > a
+head -c 2000 /dev/zero > big
+echo b > dir/b
+echo c > dir/sub/c
+git add .
+git commit -q -m c1
+git tag c1
+
+echo changed > dir/sub/c
+echo d > dir/d
+# `same` shares its tree with `dir/sub`, but is seen at a shallower depth later.
+mkdir same && cp dir/sub/c same/c
+git add .
+git commit -q -m c2
+
+echo changed > a
+git add .
+git commit -q -m c3
+
+git rev-parse main >main.id
+git rev-parse c1 >c1.id
+
+function baseline() {
+  local name=${1:?name}
+  shift
+  git rev-list --objects "$@" | sed 's/ $//' >"$name.baseline"
+}
+
+baseline all main
+baseline range main ^c1
+baseline blob-none --filter=blob:none main
+baseline blob-limit --filter=blob:limit=1k main
+baseline tree-0 --filter=tree:0 main
+baseline tree-1 --filter=tree:1 main
+baseline tree-2 --filter=tree:2 main
+baseline tree-3 --filter=tree:3 main
+baseline range-tree-2 --filter=tree:2 main ^c1
@@ -0,0 +1,107 @@
+use gix_hash::ObjectId;
+use gix_traverse::{
+    commit,
+    objects::{Filter, Objects},
+};
+
+fn id_in(dir: &std::path::Path, name: &str) -> crate::Result<ObjectId> {
+    let hex = std::fs::read_to_string(dir.join(name))?;
+    Ok(ObjectId::from_hex(hex.trim().as_bytes())?)
+}
+
+#[test]
+fn objects_and_filters_match_git() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_repo_for_objects.sh")?;
+    let db = gix_odb::at(dir.join(".git").join("objects"))?;
+    let main = id_in(&dir, "main.id")?;
+    let c1 = id_in(&dir, "c1.id")?;
+    for (name, hidden, filter) in [
+        ("all", None, None),
+        ("range", Some(c1), None),
+        ("blob-none", None, Some("blob:none")),
+        ("blob-limit", None, Some("blob:limit=1k")),
+        ("tree-0", None, Some("tree:0")),
+        ("tree-1", None, Some("tree:1")),
+        ("tree-2", None, Some("tree:2")),
+        ("tree-3", None, Some("tree:3")),
+        ("range-tree-2", Some(c1), Some("tree:2")),
+    ] {
+        let commits = commit::Simple::new(Some(main), &db)
+            .sorting(commit::simple::Sorting::ByCommitTimeNewestFirst)?
+            .hide(hidden)
+            .boundary(true);
+        let filter = filter.map(|spec| Filter::from_bytes(spec.into())).transpose()?;
+        let actual = Objects::new(commits, &db, filter)
+            .map(|item| {
+                item.map(|item| {
+                    if item.path.is_empty() {
+                        item.id.to_string()
+                    } else {
+                        format!("{} {}", item.id, item.path)
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let baseline = std::fs::read_to_string(dir.join(format!("{name}.baseline")))?;
+        let mut seen = std::collections::HashSet::new();
+        // `git` returns trees once more if they are seen again closer to the root tree while filtering by depth.
+        let expected: Vec<_> = baseline
+            .lines()
+            .filter(|line| seen.insert(line.split(' ').next().expect("id")))
+            .collect();
+        assert_eq!(actual, expected, "{name}");
+    }
+    Ok(())
+}
+
+#[test]
+fn filter_specs_roundtrip() -> crate::Result {
+    for (spec, expected, canonical) in [
+        ("blob:none", Filter::BlobNone, "blob:none"),
+        ("blob:limit=0", Filter::BlobLimit(0), "blob:limit=0"),
+        ("blob:limit=10", Filter::BlobLimit(10), "blob:limit=10"),
+        ("blob:limit=2k", Filter::BlobLimit(2048), "blob:limit=2048"),
+        ("blob:limit=1m", Filter::BlobLimit(1024 * 1024), "blob:limit=1048576"),
+        (
+            "blob:limit=1G",
+            Filter::BlobLimit(1024 * 1024 * 1024),
+            "blob:limit=1073741824",
+        ),
+        ("tree:0", Filter::TreeDepth(0), "tree:0"),
+        ("tree:3", Filter::TreeDepth(3), "tree:3"),
+    ] {
+        let filter = Filter::from_bytes(spec.into())?;
+        assert_eq!(filter, expected, "{spec}");
+        assert_eq!(filter.to_string(), canonical);
+    }
+    Ok(())
+}
+
+#[test]
+fn invalid_filter_specs() {
+    for spec in ["blob:limit=", "blob:limit=k", "blob:limit=-1", "tree:", "tree:x"] {
+        assert!(
+            matches!(
+                Filter::from_bytes(spec.into()),
+                Err(gix_traverse::objects::filter::Error::InvalidNumber { .. })
+            ),
+            "{spec}"
+        );
+    }
+    for spec in [
+        "",
+        "blob",
+        "sparse:oid=HEAD:filter",
+        "combine:blob:none+tree:0",
+        "object:type=blob",
+    ] {
+        assert!(
+            matches!(
+                Filter::from_bytes(spec.into()),
+                Err(gix_traverse::objects::filter::Error::Unsupported { .. })
+            ),
+            "{spec}"
+        );
+    }
+}
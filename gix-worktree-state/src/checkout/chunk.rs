@@ -76,6 +76,10 @@ pub struct Context<Find: Clone> {
     pub filters: gix_filter::Pipeline,
     pub buf: Vec<u8>,
     pub options: Options,
+    /// The amount of symlinks that had to be written as files as the filesystem doesn't support them.
+    pub symlinks_written_as_files: usize,
+    /// If set, the capabilities of the filesystem each entry is written to, in case it's not the one of the worktree root.
+    pub fs_per_mount_point: Option<gix_fs::capabilities::PerMountPoint>,
 }
 
 #[derive(Clone, Copy)]
@@ -177,7 +181,6 @@ where
     // We process each key and do as the filter process tells us, while collecting data about the overall progress.
     let keys: BTreeSet<_> = delayed_filter_results.iter().map(|d| d.key.clone()).collect();
     let mut unknown_paths = Vec::new();
-    let mut rela_path_as_path = Default::default();
     for key in keys {
         loop {
             let rela_paths = ctx.filters.driver_state_mut().list_delayed_paths(&key)?;
@@ -205,8 +208,9 @@ where
                         gix_filter::driver::Operation::Smudge,
                     )?,
                 );
+                let dest = std::mem::take(&mut delayed.validated_file_path); // mark it as seen, relevant for `unprocessed_paths`
                 let (file, set_executable_after_creation) = match entry::open_file(
-                    &std::mem::take(&mut delayed.validated_file_path), // mark it as seen, relevant for `unprocessed_paths`
+                    &dest,
                     destination_is_initially_empty,
                     overwrite_existing,
                     delayed.needs_executable_bit,
@@ -229,10 +233,7 @@ where
                 entry::finalize_entry(
                     delayed.entry,
                     write.inner.into_inner().map_err(std::io::IntoInnerError::into_error)?,
-                    set_executable_after_creation.then(|| {
-                        rela_path_as_path = gix_path::from_bstr(delayed.entry_path);
-                        rela_path_as_path.as_ref()
-                    }),
+                    set_executable_after_creation.then_some(dest.as_path()),
                 )?;
                 delayed_files += 1;
                 files.fetch_add(1, Ordering::Relaxed);
@@ -292,6 +293,8 @@ pub fn checkout_entry_handle_result<'entry, Find>(
         filters,
         buf,
         options,
+        symlinks_written_as_files,
+        fs_per_mount_point,
     }: &mut Context<Find>,
 ) -> Result<entry::Outcome<'entry>, checkout::Error>
 where
@@ -305,6 +308,8 @@ where
             path_cache,
             filters,
             buf,
+            symlinks_written_as_files,
+            fs_per_mount_point: fs_per_mount_point.as_ref(),
         },
         *options,
     );
@@ -361,6 +366,7 @@ fn is_collision(
     collisions.push(checkout::Collision {
         path: entry_path.into(),
         error_kind: err.kind(),
+        colliding_path: None,
     });
     files.fetch_add(1, Ordering::Relaxed);
     true
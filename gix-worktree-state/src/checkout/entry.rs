@@ -16,13 +16,15 @@ pub struct Context<'a, Find> {
     pub path_cache: &'a mut Stack,
     pub filters: &'a mut gix_filter::Pipeline,
     pub buf: &'a mut Vec<u8>,
+    pub symlinks_written_as_files: &'a mut usize,
+    pub fs_per_mount_point: Option<&'a gix_fs::capabilities::PerMountPoint>,
 }
 
 /// A delayed result of a long-running filter process, which is made available as stream.
 pub struct DelayedFilteredStream<'a> {
     /// The key identifying the driver program
     pub key: gix_filter::driver::Key,
-    /// If the file is going to be an executable.
+    /// If the filesystem supports the executable bit, which then has to be set according to the mode of the entry.
     pub needs_executable_bit: bool,
     /// The validated path on disk at which the file should be placed.
     pub validated_file_path: PathBuf,
@@ -61,6 +63,8 @@ pub fn checkout<'entry, Find>(
         filters,
         path_cache,
         buf,
+        symlinks_written_as_files,
+        fs_per_mount_point,
     }: Context<'_, Find>,
     crate::checkout::chunk::Options {
         fs: gix_fs::Capabilities {
@@ -83,6 +87,14 @@ where
     let is_dir = Some(entry.mode == gix_index::entry::Mode::COMMIT || entry.mode == gix_index::entry::Mode::DIR);
     let path_cache = path_cache.at_path(dest_relative, is_dir, &*objects)?;
     let dest = path_cache.path();
+    let (symlink, executable_bit) = match fs_per_mount_point.zip(dest.parent()) {
+        Some((fs, dir)) => {
+            // Capabilities can only be restricted, as they may also have been turned off by configuration.
+            let fs = fs.at(dir);
+            (symlink && fs.symlink, executable_bit && fs.executable_bit)
+        }
+        None => (symlink, executable_bit),
+    };
 
     let object_size = match entry.mode {
         gix_index::entry::Mode::FILE | gix_index::entry::Mode::FILE_EXECUTABLE => {
@@ -127,7 +139,7 @@ where
                 ToWorktreeOutcome::Process(MaybeDelayed::Delayed(key)) => {
                     return Ok(Outcome::Delayed(DelayedFilteredStream {
                         key,
-                        needs_executable_bit: false,
+                        needs_executable_bit: executable_bit,
                         validated_file_path: dest.to_owned(),
                         entry,
                         entry_path,
@@ -149,11 +161,23 @@ where
             let symlink_destination = gix_path::try_from_byte_slice(obj.data)
                 .map_err(|_| crate::checkout::Error::IllformedUtf8 { path: obj.data.into() })?;
 
+            let mut write_file = !symlink;
             if symlink {
-                try_op_or_unlink(dest, overwrite_existing, |p| {
+                match try_op_or_unlink(dest, overwrite_existing, |p| {
                     gix_fs::symlink::create(symlink_destination, p)
-                })?;
-            } else {
+                }) {
+                    Ok(()) => {}
+                    Err(err) if gix_fs::symlink::is_unsupported_error(&err) => {
+                        gix_features::trace::warn!(
+                            "Writing symlink at '{entry_path}' as file as the filesystem doesn't support symlinks: {err}"
+                        );
+                        *symlinks_written_as_files += 1;
+                        write_file = true;
+                    }
+                    Err(err) => return Err(err.into()),
+                }
+            }
+            if write_file {
                 let mut file = try_op_or_unlink(dest, overwrite_existing, |p| {
                     open_options(p, destination_is_initially_empty, overwrite_existing).open(dest)
                 })?;
@@ -238,6 +262,8 @@ fn open_options(path: &Path, destination_is_initially_empty: bool, overwrite_exi
     options
         .create_new(destination_is_initially_empty && !overwrite_existing)
         .create(!destination_is_initially_empty || overwrite_existing)
+        // Existing files may be longer than what we are going to write.
+        .truncate(!destination_is_initially_empty || overwrite_existing)
         .write(true);
     options
 }
@@ -262,13 +288,18 @@ pub(crate) fn open_file(
     } else {
         needs_executable_bit
     };
+    // Files that may exist already may have the executable bit set even though they shouldn't.
+    #[cfg(unix)]
+    let set_executable_after_creation = set_executable_after_creation
+        || (fs_supports_executable_bit && !needs_executable_bit && !destination_is_initially_empty);
     //  not supported on windows
     #[cfg(windows)]
     let set_executable_after_creation = needs_executable_bit;
     try_op_or_unlink(path, overwrite_existing, |p| options.open(p)).map(|f| (f, set_executable_after_creation))
 }
 
-/// Close `file` and store its stats in `entry`, possibly setting `file` executable depending on `set_executable_after_creation`.
+/// Close `file` and store its stats in `entry`, possibly setting or clearing the executable bit of `file` according to the mode
+/// of `entry` depending on `set_executable_after_creation`.
 #[cfg_attr(windows, allow(unused_variables))]
 pub(crate) fn finalize_entry(
     entry: &mut gix_index::Entry,
@@ -280,8 +311,15 @@ pub(crate) fn finalize_entry(
     if let Some(path) = set_executable_after_creation {
         use std::os::unix::fs::PermissionsExt;
         let mut perm = std::fs::symlink_metadata(path)?.permissions();
-        perm.set_mode(0o777);
-        std::fs::set_permissions(path, perm)?;
+        let mode = if entry.mode == gix_index::entry::Mode::FILE_EXECUTABLE {
+            0o777
+        } else {
+            perm.mode() & !0o111
+        };
+        if perm.mode() & 0o777 != mode {
+            perm.set_mode(mode);
+            std::fs::set_permissions(path, perm)?;
+        }
     }
     // NOTE: we don't call `file.sync_all()` here knowing that some filesystems don't handle this well.
     //       revisit this once there is a bug to fix.
@@ -1,6 +1,11 @@
-use std::sync::atomic::AtomicBool;
+use std::{
+    collections::{hash_map, HashMap},
+    sync::atomic::{AtomicBool, Ordering},
+};
 
+use bstr::{BStr, ByteSlice};
 use gix_features::{interrupt, parallel::in_parallel_with_finalize};
+use gix_index::entry::{Flags, Mode};
 use gix_worktree::{stack, Stack};
 
 use crate::checkout::chunk;
@@ -58,6 +63,23 @@ where
         None,
     );
 
+    let case_collisions = if options.fs.ignore_case {
+        case_insensitive_collisions(index, paths)
+    } else {
+        Vec::new()
+    };
+    let mut is_colliding = Vec::new();
+    for (idx, _) in &case_collisions {
+        if is_colliding.is_empty() {
+            is_colliding.resize(index.entries().len(), false);
+        }
+        is_colliding[*idx] = true;
+    }
+    num_files.fetch_add(case_collisions.len(), Ordering::Relaxed);
+
+    let fs_per_mount_point = options
+        .fs_per_mount_point
+        .then(|| gix_fs::capabilities::PerMountPoint::new(options.fs, &dir));
     let mut ctx = chunk::Context {
         buf: Vec::new(),
         options: (&options).into(),
@@ -70,6 +92,8 @@ where
         ),
         filters: options.filters,
         objects,
+        symlinks_written_as_files: 0,
+        fs_per_mount_point,
     };
 
     let chunk::Outcome {
@@ -81,7 +105,14 @@ where
         delayed_paths_unknown,
         delayed_paths_unprocessed,
     } = if num_threads == 1 {
-        let entries_with_paths = interrupt::Iter::new(index.entries_mut_with_paths_in(paths), should_interrupt);
+        let entries_with_paths = interrupt::Iter::new(
+            index
+                .entries_mut_with_paths_in(paths)
+                .enumerate()
+                .filter(|(idx, _)| !is_colliding.get(*idx).copied().unwrap_or_default())
+                .map(|(_, entry_and_path)| entry_and_path),
+            should_interrupt,
+        );
         let mut delayed_filter_results = Vec::new();
         let mut out = chunk::process(
             entries_with_paths,
@@ -93,7 +124,14 @@ where
         chunk::process_delayed_filter_results(delayed_filter_results, &num_files, &num_bytes, &mut out, &mut ctx)?;
         out
     } else {
-        let entries_with_paths = interrupt::Iter::new(index.entries_mut_with_paths_in(paths), should_interrupt);
+        let entries_with_paths = interrupt::Iter::new(
+            index
+                .entries_mut_with_paths_in(paths)
+                .enumerate()
+                .filter(|(idx, _)| !is_colliding.get(*idx).copied().unwrap_or_default())
+                .map(|(_, entry_and_path)| entry_and_path),
+            should_interrupt,
+        );
         in_parallel_with_finalize(
             gix_features::iter::Chunks {
                 inner: entries_with_paths,
@@ -137,8 +175,17 @@ where
         .as_bytes()
         .expect("only symlinks are delayed here, they are never filtered (or delayed again)")
             as u64;
+        if ctx.symlinks_written_as_files != 0 {
+            // Don't try to create symlinks again once we know the filesystem doesn't support them.
+            ctx.options.fs.symlink = false;
+        }
     }
 
+    let collisions = {
+        let mut all: Vec<_> = case_collisions.into_iter().map(|(_, collision)| collision).collect();
+        all.append(&mut collisions);
+        all
+    };
     Ok(crate::checkout::Outcome {
         files_updated,
         collisions,
@@ -146,5 +193,62 @@ where
         bytes_written,
         delayed_paths_unknown,
         delayed_paths_unprocessed,
+        symlinks_written_as_files: ctx.symlinks_written_as_files,
     })
 }
+
+/// Return the indices of all entries in `index` whose path would collide with the path of a previous entry, or with one of
+/// its leading directories, on a case-insensitive filesystem, along with the respective collision.
+///
+/// Similar to how the checkout works, files are considered before symlinks so they take precedence. Note that only
+/// ASCII characters are folded, just like when matching paths case-insensitively.
+fn case_insensitive_collisions(
+    index: &gix_index::State,
+    paths: &gix_index::PathStorageRef,
+) -> Vec<(usize, crate::checkout::Collision)> {
+    let entries = index.entries().iter().enumerate();
+    let files_then_symlinks = entries
+        .clone()
+        .filter(|(_, entry)| entry.mode != Mode::SYMLINK)
+        .chain(entries.filter(|(_, entry)| entry.mode == Mode::SYMLINK))
+        .filter(|(_, entry)| !entry.flags.contains(Flags::SKIP_WORKTREE));
+
+    // folded path => (path, is_dir)
+    let mut seen = HashMap::<Vec<u8>, (&BStr, bool)>::new();
+    let mut out = Vec::new();
+    for (idx, entry) in files_then_symlinks {
+        let path = entry.path_in(paths);
+        let leading_dirs = path.find_iter("/").map(|pos| path[..pos].as_bstr());
+        let colliding_path = leading_dirs
+            .clone()
+            .find_map(|dir| match seen.get(dir.to_ascii_lowercase().as_slice()) {
+                Some((other, is_dir)) if *other != dir || !is_dir => Some(*other),
+                _ => None,
+            })
+            .or_else(|| seen.get(path.to_ascii_lowercase().as_slice()).map(|(other, _)| *other));
+        match colliding_path {
+            Some(other) => {
+                gix_features::trace::error!(
+                    "{path}: collides with {other} on a case-insensitive filesystem and won't be checked out"
+                );
+                out.push((
+                    idx,
+                    crate::checkout::Collision {
+                        path: path.to_owned(),
+                        error_kind: std::io::ErrorKind::AlreadyExists,
+                        colliding_path: Some(other.to_owned()),
+                    },
+                ));
+            }
+            None => {
+                for dir in leading_dirs {
+                    if let hash_map::Entry::Vacant(e) = seen.entry(dir.to_ascii_lowercase()) {
+                        e.insert((dir, true));
+                    }
+                }
+                seen.insert(path.to_ascii_lowercase(), (path, false));
+            }
+        }
+    }
+    out
+}
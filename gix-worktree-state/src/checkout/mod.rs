@@ -8,6 +8,11 @@ pub struct Collision {
     pub path: BString,
     /// The io error we encountered when checking out `path`.
     pub error_kind: std::io::ErrorKind,
+    /// The path of the entry which was checked out instead, or which is a leading directory of it, if `path` was
+    /// known to collide with it on a [case-insensitive](gix_fs::Capabilities::ignore_case) filesystem before writing it.
+    ///
+    /// It's `None` if the collision was detected while writing `path`, in which case it's unknown what it collided with.
+    pub colliding_path: Option<BString>,
 }
 
 /// A path that encountered an IO error.
@@ -34,13 +39,28 @@ pub struct Outcome {
     pub delayed_paths_unknown: Vec<BString>,
     /// All paths that were left unprocessed, because they were never listed by the process even though we passed them.
     pub delayed_paths_unprocessed: Vec<BString>,
+    /// The amount of symlinks that were written as files containing their target path, even though
+    /// [symlinks were enabled](gix_fs::Capabilities::symlink), as the filesystem turned out not to support them.
+    ///
+    /// If non-zero, `core.symlinks` should be set to `false` so these files aren't seen as type-changes later.
+    pub symlinks_written_as_files: usize,
 }
 
 /// Options to further configure the checkout operation.
 #[derive(Clone, Default)]
 pub struct Options {
     /// capabilities of the file system
+    ///
+    /// If [`ignore_case`](gix_fs::Capabilities::ignore_case) is set, entries whose paths would collide with the path of another
+    /// entry or its leading directories are not written, but reported as [collisions](Outcome::collisions) instead.
     pub fs: gix_fs::Capabilities,
+    /// If true, default false, `fs` is assumed to only describe the filesystem the checkout directory is located on, and
+    /// the capabilities of other filesystems mounted within it are [probed](gix_fs::capabilities::PerMountPoint) by writing
+    /// files into the first directory they are seen in, before writing files or symlinks to them.
+    ///
+    /// This allows, for instance, to write symlinks as files and to not set the executable bit on an `exFAT` drive mounted
+    /// within the checkout directory.
+    pub fs_per_mount_point: bool,
     /// If set, don't use more than this amount of threads.
     /// Otherwise, usually use as many threads as there are logical cores.
     /// A value of 0 is interpreted as no-limit
@@ -99,3 +119,7 @@ pub enum Error {
 mod chunk;
 mod entry;
 pub(crate) mod function;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod remove_deleted;
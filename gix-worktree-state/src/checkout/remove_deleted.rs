@@ -0,0 +1,167 @@
+use std::path::{Path, PathBuf};
+
+use bstr::{BStr, BString};
+use gix_index::{entry, Entry};
+
+use crate::checkout::ErrorRecord;
+
+/// The outcome of [`remove_deleted()`](crate::remove_deleted()).
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The amount of files and symlinks that were removed.
+    pub files_removed: usize,
+    /// The paths of files that were kept as they were modified, or changed their type.
+    pub modified: Vec<BString>,
+    /// Other errors that happened while removing files, if `keep_going` was set.
+    pub errors: Vec<ErrorRecord>,
+}
+
+/// The error returned by [`remove_deleted()`](crate::remove_deleted()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not convert path to UTF8: {}", .path)]
+    IllformedUtf8 { path: BString },
+    #[error("The clock was off when reading file related metadata")]
+    Time(#[from] std::time::SystemTimeError),
+    #[error("IO error while removing a file or reading its metadata")]
+    Io(#[from] std::io::Error),
+    #[error("Could not check if the content of '{rela_path}' was modified")]
+    IsModified {
+        rela_path: BString,
+        source: Box<dyn std::error::Error + Send + Sync + 'static>,
+    },
+}
+
+/// Remove the files and symlinks of all entries in `previous` that aren't in `index` anymore from `dir`, along with the
+/// directories that become empty, similar to what `git checkout` does when switching to another tree.
+/// It's typically called before [`checkout()`](crate::checkout()) with `index` and [`overwrite_existing`](crate::checkout::Options::overwrite_existing)
+/// enabled.
+///
+/// Files are only removed if they are unchanged compared to `previous`, unless `overwrite_existing` is set in `options`.
+/// If their stat information doesn't match, or if they are racy, `is_modified(rela_path, entry, path)` is called to learn if
+/// the file at `path` still has the content of `entry`, usually by hashing it after converting it with the filter pipeline.
+/// Modified files are kept and reported in the outcome.
+///
+/// Entries that are marked to [skip the worktree](entry::Flags::SKIP_WORKTREE), sparse directories and submodules
+/// are ignored, as are files that are only reachable through a symlink.
+pub fn remove_deleted<E>(
+    previous: &gix_index::State,
+    index: &gix_index::State,
+    dir: &Path,
+    options: &crate::checkout::Options,
+    mut is_modified: impl FnMut(&BStr, &Entry, &Path) -> Result<bool, E>,
+) -> Result<Outcome, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let _span = gix_features::trace::coarse!("gix_worktree_state::remove_deleted()");
+    let lookup = index.prepare_icase_backing();
+    let mut out = Outcome::default();
+    let mut prev_path = None;
+    for entry in previous.entries() {
+        let rela_path = entry.path(previous);
+        if prev_path == Some(rela_path) {
+            continue;
+        }
+        prev_path = Some(rela_path);
+        if entry.flags.contains(entry::Flags::SKIP_WORKTREE)
+            || entry.mode.is_sparse()
+            || entry.mode.is_submodule()
+            || index
+                .entry_by_path_icase(rela_path, options.fs.ignore_case, &lookup)
+                .is_some()
+        {
+            continue;
+        }
+        match remove_entry(previous, entry, rela_path, dir, options, &mut is_modified) {
+            Ok(Removal::Removed) => out.files_removed += 1,
+            Ok(Removal::Modified) => out.modified.push(rela_path.to_owned()),
+            Ok(Removal::Missing) => {}
+            Err(Error::Io(err)) if options.keep_going => out.errors.push(ErrorRecord {
+                path: rela_path.to_owned(),
+                error: err.into(),
+            }),
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(out)
+}
+
+enum Removal {
+    Removed,
+    Modified,
+    Missing,
+}
+
+fn remove_entry<E>(
+    previous: &gix_index::State,
+    entry: &Entry,
+    rela_path: &BStr,
+    dir: &Path,
+    options: &crate::checkout::Options,
+    is_modified: &mut impl FnMut(&BStr, &Entry, &Path) -> Result<bool, E>,
+) -> Result<Removal, Error>
+where
+    E: std::error::Error + Send + Sync + 'static,
+{
+    let rela_path_on_disk = gix_path::try_from_bstr(rela_path).map_err(|_| Error::IllformedUtf8 {
+        path: rela_path.to_owned(),
+    })?;
+    let mut path = PathBuf::from(dir);
+    let mut components = rela_path_on_disk.components().peekable();
+    while let Some(component) = components.next() {
+        path.push(component);
+        if components.peek().is_none() {
+            break;
+        }
+        match std::fs::symlink_metadata(&path) {
+            Ok(meta) if meta.is_dir() => {}
+            Ok(_) => return Ok(Removal::Missing),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Removal::Missing),
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Removal::Missing),
+        Err(err) => return Err(err.into()),
+    };
+    if !options.overwrite_existing {
+        if metadata.is_dir()
+            || entry
+                .mode
+                .change_to_match_fs(&metadata, options.fs.symlink, false)
+                .is_some()
+        {
+            return Ok(Removal::Modified);
+        }
+        let stat = entry::Stat::from_fs(&metadata)?;
+        if (!stat.matches(&entry.stat, options.stat_options)
+            || entry.stat.is_racy(previous.timestamp(), options.stat_options))
+            && is_modified(rela_path, entry, &path).map_err(|err| Error::IsModified {
+                rela_path: rela_path.to_owned(),
+                source: err.into(),
+            })?
+        {
+            return Ok(Removal::Modified);
+        }
+    }
+
+    if metadata.is_dir() {
+        std::fs::remove_dir_all(&path)?;
+    } else if metadata.is_symlink() {
+        gix_fs::symlink::remove(&path)?;
+    } else {
+        std::fs::remove_file(&path)?;
+    }
+    let mut parent = path.parent();
+    while let Some(dir_to_remove) = parent.filter(|parent| *parent != dir) {
+        if std::fs::remove_dir(dir_to_remove).is_err() {
+            break;
+        }
+        parent = dir_to_remove.parent();
+    }
+    Ok(Removal::Removed)
+}
@@ -4,4 +4,4 @@
 ///
 #[allow(clippy::empty_docs)]
 pub mod checkout;
-pub use checkout::function::checkout;
+pub use checkout::{function::checkout, remove_deleted::remove_deleted};
@@ -78,23 +78,7 @@ fn accidental_writes_through_symlinks_are_prevented_if_overwriting_is_forbidden(
             stripped_prefix(&source_tree, &source_files),
             stripped_prefix(&destination, &worktree_files),
         );
-        if multi_threaded() {
-            assert_eq!(outcome.collisions.len(), 2);
-        } else {
-            assert_eq!(
-                outcome.collisions,
-                vec![
-                    Collision {
-                        path: "FAKE-DIR".into(),
-                        error_kind: AlreadyExists
-                    },
-                    Collision {
-                        path: "FAKE-FILE".into(),
-                        error_kind: AlreadyExists
-                    }
-                ]
-            );
-        }
+        assert_eq!(outcome.collisions, dangerous_symlink_collisions());
     } else {
         let expected = ["A-dir/a", "A-file", "FAKE-DIR", "FAKE-FILE", "fake-dir/b", "fake-file"];
         assert_eq!(stripped_prefix(&source_tree, &source_files), paths(expected));
@@ -115,20 +99,14 @@ fn writes_through_symlinks_are_prevented_even_if_overwriting_is_allowed() {
     let worktree_files = dir_structure(&destination);
 
     if opts.fs.ignore_case {
-        assert_eq!(
-            stripped_prefix(&source_tree, &source_files),
-            paths(["A-dir/a", "A-file", "fake-dir/b", "fake-file"]),
-        );
+        let expected = paths(["A-dir/a", "A-file", "fake-dir/b", "fake-file"]);
+        assert_eq!(stripped_prefix(&source_tree, &source_files), expected);
         assert_eq!(
             stripped_prefix(&destination, &worktree_files),
-            paths([
-                if cfg!(windows) { "A-dir\\a" } else { "A-dir/a" },
-                "A-file",
-                "FAKE-DIR",
-                if cfg!(windows) { "fake-file" } else { "FAKE-FILE" }
-            ]),
+            expected,
+            "symlinks that would collide with files or directories are never written"
         );
-        assert!(outcome.collisions.is_empty());
+        assert_eq!(outcome.collisions, dangerous_symlink_collisions());
     } else {
         let expected = ["A-dir/a", "A-file", "FAKE-DIR", "FAKE-FILE", "fake-dir/b", "fake-file"];
         assert_eq!(stripped_prefix(&source_tree, &source_files), paths(expected));
@@ -245,6 +223,113 @@ fn overwriting_files_and_lone_directories_works() -> crate::Result {
     Ok(())
 }
 
+#[test]
+#[cfg(unix)]
+fn executable_bits_are_removed_from_existing_files_if_they_are_not_executable() -> crate::Result {
+    let mut opts = opts_from_probe();
+    if !opts.fs.executable_bit {
+        return Ok(());
+    }
+    opts.overwrite_existing = true;
+    opts.destination_is_initially_empty = false;
+    let (_source, destination, _index, outcome) = checkout_index_in_tmp_dir_opts(
+        opts,
+        "make_mixed_without_submodules",
+        |_| true,
+        |d| {
+            use std::os::unix::fs::PermissionsExt;
+            let empty = d.join("empty");
+            std::fs::write(&empty, b"")?;
+            std::fs::set_permissions(empty, std::fs::Permissions::from_mode(0o755))
+        },
+    )?;
+    assert!(outcome.collisions.is_empty());
+
+    let meta = std::fs::symlink_metadata(destination.path().join("empty"))?;
+    assert_eq!(
+        meta.mode() & 0o111,
+        0,
+        "the executable bit was removed as the entry isn't executable"
+    );
+    let meta = std::fs::symlink_metadata(destination.path().join("executable"))?;
+    assert_eq!(meta.mode() & 0o700, 0o700, "executables keep their executable bit");
+    Ok(())
+}
+
+#[test]
+fn existing_files_are_truncated_when_overwritten() -> crate::Result {
+    let mut opts = opts_from_probe();
+    opts.overwrite_existing = true;
+    opts.destination_is_initially_empty = false;
+    let (_source, destination, _index, outcome) = checkout_index_in_tmp_dir_opts(
+        opts,
+        "make_mixed_without_submodules",
+        |_| true,
+        |d| std::fs::write(d.join("executable"), b"longer previous content"),
+    )?;
+    assert!(outcome.collisions.is_empty());
+    assert_eq!(
+        std::fs::read(destination.path().join("executable"))?.as_bstr(),
+        "content",
+        "no trailing bytes of the previous content remain"
+    );
+    Ok(())
+}
+
+#[test]
+fn files_of_deleted_entries_are_removed_unless_they_are_modified() -> crate::Result {
+    let opts = opts_from_probe();
+    let (_source, destination, previous, _outcome) =
+        checkout_index_in_tmp_dir(opts.clone(), "make_mixed_without_submodules")?;
+    std::fs::write(destination.path().join("empty"), b"modified")?;
+
+    let mut index = gix_index::State::clone(&previous);
+    index.remove_entries(|_, path, _| path == "empty" || path.starts_with(b"dir/"));
+    let mut checked = Vec::new();
+    let outcome = gix_worktree_state::remove_deleted(
+        &previous,
+        &index,
+        destination.path(),
+        &opts,
+        |rela_path, _entry, _path| {
+            checked.push(rela_path.to_owned());
+            Ok::<_, std::io::Error>(rela_path == "empty")
+        },
+    )?;
+
+    assert_eq!(outcome.files_removed, 2, "'dir/content' and 'dir/sub-dir/symlink'");
+    assert_eq!(outcome.modified, ["empty"], "modified files are kept");
+    assert!(outcome.errors.is_empty());
+    assert!(
+        checked.iter().any(|p| p == "empty"),
+        "files with changed stat information have their content checked"
+    );
+    assert_eq!(
+        stripped_prefix(&destination, &dir_structure(&destination)),
+        paths(["empty", "executable"])
+    );
+    assert!(
+        !destination.path().join("dir").exists(),
+        "directories that became empty are removed as well"
+    );
+
+    let mut opts = opts;
+    opts.overwrite_existing = true;
+    let outcome = gix_worktree_state::remove_deleted(
+        &previous,
+        &index,
+        destination.path(),
+        &opts,
+        |_, _, _| -> Result<bool, std::io::Error> { unreachable!("files are removed without checking") },
+    )?;
+    assert_eq!(
+        outcome.files_removed, 1,
+        "modified files are removed if overwriting is allowed"
+    );
+    assert!(outcome.modified.is_empty());
+    Ok(())
+}
+
 #[test]
 fn symlinks_become_files_if_disabled() -> crate::Result {
     let mut opts = opts_from_probe();
@@ -377,58 +462,70 @@ fn collisions_are_detected_on_a_case_insensitive_filesystem_even_with_delayed_fi
     );
 
     let dest_files = dir_structure(&destination);
-    if multi_threaded() {
-        assert!(
-            (4..=6).contains(&dest_files.len()),
-            "due to the clash happening at nearly any time, and keep-going is false, we get a variance of files"
-        );
-    } else {
-        assert_eq!(
-            stripped_prefix(&destination, &dest_files),
-            paths(["D/B", "D/C", "FILE_X", "X", "link-to-X"]),
-            "we checkout files in order and generally handle collision detection differently, hence the difference"
-        );
-    }
+    assert_eq!(
+        stripped_prefix(&destination, &dest_files),
+        paths(["D/B", "D/C", "FILE_X", "X", "link-to-X"]),
+        "files are preferred over symlinks, and otherwise the first entry in the index wins"
+    );
+    assert_eq!(
+        outcome.collisions,
+        ignorecase_collisions(),
+        "these files couldn't be checked out"
+    );
+}
 
-    let error_kind = ErrorKind::AlreadyExists;
-    #[cfg(windows)]
-    let error_kind_dir = ErrorKind::PermissionDenied;
-    #[cfg(not(windows))]
-    let error_kind_dir = error_kind;
+#[test]
+fn case_collisions_are_detected_upfront_if_the_filesystem_is_configured_to_ignore_case() {
+    let mut opts = opts_from_probe();
+    opts.fs.ignore_case = true;
+    let (_source_tree, destination, index, outcome) =
+        checkout_index_in_tmp_dir(opts, "make_ignorecase_collisions").unwrap();
 
-    if multi_threaded() {
-        assert!(
-            (5..=6).contains(&outcome.collisions.len()),
-            "can only assert on number as it's racily creating files so unclear which one clashes, and due to keep-going = false there is variance"
-        );
-    } else {
-        assert_eq!(
-            outcome.collisions,
-            vec![
-                Collision {
-                    path: "d".into(),
-                    error_kind: error_kind_dir,
-                },
-                Collision {
-                    path: "FILE_x".into(),
-                    error_kind,
-                },
-                Collision {
-                    path: "file_X".into(),
-                    error_kind,
-                },
-                Collision {
-                    path: "file_x".into(),
-                    error_kind,
-                },
-                Collision {
-                    path: "x".into(),
-                    error_kind,
-                },
-            ],
-            "these files couldn't be checked out"
-        );
-    }
+    let dest_files = dir_structure(&destination);
+    assert_eq!(
+        stripped_prefix(&destination, &dest_files),
+        paths(["D/B", "D/C", "FILE_X", "X", "link-to-X"]),
+        "colliding entries are skipped, even if the filesystem could hold them, so the result is deterministic"
+    );
+    assert_eq!(outcome.collisions, ignorecase_collisions());
+    assert!(outcome.errors.is_empty());
+    assert_eq!(
+        outcome.files_updated,
+        index.entries().len() - outcome.collisions.len() - 1,
+        "colliding entries are not written (minus 1 to account for the symlink, which isn't counted)"
+    );
+}
+
+fn ignorecase_collisions() -> Vec<Collision> {
+    [
+        ("FILE_x", "FILE_X"),
+        ("d", "D"),
+        ("file_X", "FILE_X"),
+        ("file_x", "FILE_X"),
+        ("x", "X"),
+    ]
+    .into_iter()
+    .map(|(path, colliding_path)| Collision {
+        path: path.into(),
+        error_kind: ErrorKind::AlreadyExists,
+        colliding_path: Some(colliding_path.into()),
+    })
+    .collect()
+}
+
+fn dangerous_symlink_collisions() -> Vec<Collision> {
+    vec![
+        Collision {
+            path: "FAKE-DIR".into(),
+            error_kind: AlreadyExists,
+            colliding_path: Some("fake-dir".into()),
+        },
+        Collision {
+            path: "FAKE-FILE".into(),
+            error_kind: AlreadyExists,
+            colliding_path: Some("fake-file".into()),
+        },
+    ]
 }
 
 fn multi_threaded() -> bool {
@@ -0,0 +1,245 @@
+//! Support for the `export-subst` attribute, which expands `$Format:<format>$` placeholders in files with information
+//! about the commit that is streamed, similar to `git archive`.
+use gix_object::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// Information about the commit whose tree is streamed, for use when expanding `$Format:<format>$` placeholders.
+#[derive(Debug, Clone)]
+pub struct Context {
+    /// The id of the commit.
+    pub id: gix_hash::ObjectId,
+    /// The decoded commit itself.
+    pub commit: gix_object::Commit,
+    /// The abbreviated hash of the commit, as used by `%h`.
+    pub short_id: BString,
+    /// The abbreviated hash of the tree of the commit, as used by `%t`.
+    pub short_tree_id: BString,
+    /// The abbreviated hashes of the parents of the commit, in order, as used by `%p`.
+    pub short_parent_ids: Vec<BString>,
+}
+
+impl Context {
+    /// Create a new instance for the commit with `id` and its decoded `commit`, with all hashes abbreviated to 7 hex characters.
+    ///
+    /// Note that `git` makes sure that abbreviated hashes are unique in the repository, which is why the `short_*` fields
+    /// should be adjusted if this is known to be insufficient.
+    pub fn new(id: gix_hash::ObjectId, commit: gix_object::Commit) -> Self {
+        const DEFAULT_HEX_LEN: usize = 7;
+        let short = |id: &gix_hash::oid| BString::from(id.to_hex_with_len(DEFAULT_HEX_LEN).to_string());
+        Context {
+            short_id: short(&id),
+            short_tree_id: short(&commit.tree),
+            short_parent_ids: commit.parents.iter().map(|id| short(id)).collect(),
+            id,
+            commit,
+        }
+    }
+}
+
+/// Expand all `$Format:<format>$` placeholders in `src` with information from `ctx` and write the result to `buf`,
+/// returning `true` if `buf` was written to, or `false` if `src` didn't contain any placeholder.
+///
+/// The following placeholders of `git log --format` are supported in `<format>`, and all others are written as is:
+///
+/// * `%H`, `%h`, `%T`, `%t`, `%P` and `%p` for the full or abbreviated hashes of the commit, its tree and its parents.
+/// * `%an`, `%aN`, `%ae`, `%aE`, `%al`, `%aL` for the author name, email and the local part of the email, and their
+///   committer counterparts starting with `%c`. Note that the mailmap isn't respected.
+/// * `%ad`, `%aD`, `%at`, `%ai`, `%aI` and `%as` for the author date in various formats, and their committer counterparts.
+/// * `%s`, `%b` and `%B` for the subject, the body and the raw message.
+/// * `%n`, `%%` and `%x<hex>` for newlines, the percent sign and bytes in hexadecimal notation.
+/// * `%+<placeholder>`, `%-<placeholder>` and `% <placeholder>` to add a newline or space before non-empty expansions,
+///   or to remove preceding newlines if the expansion is empty.
+pub fn expand(ctx: &Context, src: &[u8], buf: &mut Vec<u8>) -> bool {
+    const START: &[u8] = b"$Format:";
+    let mut ofs = 0;
+    let mut changed = false;
+    while let Some(start) = src[ofs..].find(START).map(|pos| ofs + pos) {
+        let format_start = start + START.len();
+        let end = match src[format_start..].find_byte(b'$') {
+            Some(pos) => format_start + pos,
+            None => break,
+        };
+        if !changed {
+            buf.clear();
+            changed = true;
+        }
+        buf.extend_from_slice(&src[ofs..start]);
+        format(ctx, src[format_start..end].as_bstr(), buf);
+        ofs = end + 1;
+    }
+    if changed {
+        buf.extend_from_slice(&src[ofs..]);
+    }
+    changed
+}
+
+fn format(ctx: &Context, fmt: &BStr, out: &mut Vec<u8>) {
+    let mut rest: &[u8] = fmt;
+    let mut expansion = Vec::new();
+    while let Some(pos) = rest.find_byte(b'%') {
+        out.extend_from_slice(&rest[..pos]);
+        rest = &rest[pos + 1..];
+        let magic = match rest.first() {
+            Some(b @ (b'+' | b'-' | b' ')) if rest.len() > 1 => Some(*b),
+            _ => None,
+        };
+        let placeholder = if magic.is_some() { &rest[1..] } else { rest };
+        expansion.clear();
+        match expand_placeholder(ctx, placeholder, &mut expansion) {
+            Some(consumed) => {
+                match magic {
+                    Some(b'+') if !expansion.is_empty() => out.push(b'\n'),
+                    Some(b' ') if !expansion.is_empty() => out.push(b' '),
+                    Some(b'-') if expansion.is_empty() => {
+                        while out.last() == Some(&b'\n') {
+                            out.pop();
+                        }
+                    }
+                    _ => {}
+                }
+                out.extend_from_slice(&expansion);
+                rest = &placeholder[consumed..];
+            }
+            None => out.push(b'%'),
+        }
+    }
+    out.extend_from_slice(rest);
+}
+
+/// Write the expansion of the placeholder at the beginning of `input` (with the `%` removed) to `out`, and return the
+/// amount of bytes it consumed, or `None` if it isn't supported.
+fn expand_placeholder(ctx: &Context, input: &[u8], out: &mut Vec<u8>) -> Option<usize> {
+    let commit = &ctx.commit;
+    let consumed = match *input.first()? {
+        b'n' => {
+            out.push(b'\n');
+            1
+        }
+        b'%' => {
+            out.push(b'%');
+            1
+        }
+        b'x' => {
+            let hex = input.get(1..3)?;
+            let byte = u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?;
+            out.push(byte);
+            3
+        }
+        b'H' => {
+            out.push_str(ctx.id.to_string());
+            1
+        }
+        b'h' => {
+            out.push_str(&ctx.short_id);
+            1
+        }
+        b'T' => {
+            out.push_str(commit.tree.to_string());
+            1
+        }
+        b't' => {
+            out.push_str(&ctx.short_tree_id);
+            1
+        }
+        b'P' => {
+            join(out, commit.parents.iter().map(|id| BString::from(id.to_string())));
+            1
+        }
+        b'p' => {
+            join(out, ctx.short_parent_ids.iter().cloned());
+            1
+        }
+        b'a' => {
+            signature(&commit.author, *input.get(1)?, out)?;
+            2
+        }
+        b'c' => {
+            signature(&commit.committer, *input.get(1)?, out)?;
+            2
+        }
+        b's' => {
+            subject(commit.message.as_ref(), out);
+            1
+        }
+        b'b' => {
+            out.push_str(body(commit.message.as_ref()));
+            1
+        }
+        b'B' => {
+            out.push_str(&commit.message);
+            1
+        }
+        _ => return None,
+    };
+    Some(consumed)
+}
+
+fn join(out: &mut Vec<u8>, items: impl Iterator<Item = BString>) {
+    for (idx, item) in items.enumerate() {
+        if idx != 0 {
+            out.push(b' ');
+        }
+        out.push_str(item);
+    }
+}
+
+fn signature(signature: &gix_actor::Signature, kind: u8, out: &mut Vec<u8>) -> Option<()> {
+    use gix_object::date::time::format;
+    let time = &signature.time;
+    match kind {
+        b'n' | b'N' => out.push_str(&signature.name),
+        b'e' | b'E' => out.push_str(&signature.email),
+        b'l' | b'L' => out.push_str(signature.email.split_str("@").next().unwrap_or_default()),
+        b'd' => out.push_str(time.format(format::DEFAULT)),
+        b'D' => out.push_str(time.format(format::GIT_RFC2822)),
+        b't' => out.push_str(time.format(format::UNIX)),
+        b'i' => out.push_str(time.format(format::ISO8601)),
+        b'I' => out.push_str(time.format(format::ISO8601_STRICT)),
+        b's' => out.push_str(time.format(format::SHORT)),
+        _ => return None,
+    }
+    Some(())
+}
+
+/// Return `line` without trailing whitespace, or `None` if it's blank.
+fn non_blank(line: &[u8]) -> Option<&[u8]> {
+    let line = line.trim_end();
+    (!line.is_empty()).then_some(line)
+}
+
+/// Skip all leading blank lines in `message`.
+fn skip_blank_lines(mut message: &[u8]) -> &[u8] {
+    while !message.is_empty() {
+        let line_end = message.find_byte(b'\n').map_or(message.len(), |pos| pos + 1);
+        if non_blank(&message[..line_end]).is_some() {
+            break;
+        }
+        message = &message[line_end..];
+    }
+    message
+}
+
+/// Write the first paragraph of `message` to `out`, with its lines joined by a space, and return what follows it.
+fn subject<'a>(message: &'a [u8], out: &mut Vec<u8>) -> &'a [u8] {
+    let mut message = skip_blank_lines(message);
+    let mut first = true;
+    while !message.is_empty() {
+        let line_end = message.find_byte(b'\n').map_or(message.len(), |pos| pos + 1);
+        let line = &message[..line_end];
+        message = &message[line_end..];
+        match non_blank(line) {
+            Some(line) => {
+                if !first {
+                    out.push(b' ');
+                }
+                out.extend_from_slice(line);
+                first = false;
+            }
+            None => break,
+        }
+    }
+    message
+}
+
+fn body(message: &[u8]) -> &[u8] {
+    skip_blank_lines(subject(message, &mut Vec::new()))
+}
@@ -31,7 +31,7 @@ use crate::{entry, entry::Error, protocol, AdditionalEntry, SharedErrorSlot, Str
 ///
 /// ### Limitations
 ///
-/// * `export-subst` is not support, as it requires the entire formatting engine of `git log`.
+/// * `export-subst` is not supported as there is no commit to obtain information from, use [`from_commit()`] instead.
 pub fn from_tree<Find, E>(
     tree: gix_hash::ObjectId,
     objects: Find,
@@ -40,6 +40,41 @@ pub fn from_tree<Find, E>(
         + Send
         + 'static,
 ) -> Stream
+where
+    Find: gix_object::Find + Clone + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    spawn(tree, None, objects, pipeline, attributes)
+}
+
+/// Like [`from_tree()`], but streams the tree of the commit described by `commit`, which is also used to expand
+/// `$Format:<format>$` placeholders in files with the `export-subst` attribute, just like `git archive` does.
+///
+/// See [`export_subst::expand()`](crate::export_subst::expand()) for the supported placeholders.
+pub fn from_commit<Find, E>(
+    commit: crate::export_subst::Context,
+    objects: Find,
+    pipeline: gix_filter::Pipeline,
+    attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
+        + Send
+        + 'static,
+) -> Stream
+where
+    Find: gix_object::Find + Clone + Send + 'static,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    spawn(commit.commit.tree, Some(commit), objects, pipeline, attributes)
+}
+
+fn spawn<Find, E>(
+    tree: gix_hash::ObjectId,
+    export_subst: Option<crate::export_subst::Context>,
+    objects: Find,
+    pipeline: gix_filter::Pipeline,
+    attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
+        + Send
+        + 'static,
+) -> Stream
 where
     Find: gix_object::Find + Clone + Send + 'static,
     E: std::error::Error + Send + Sync + 'static,
@@ -50,6 +85,7 @@ where
         move || {
             if let Err(err) = run(
                 tree,
+                export_subst,
                 objects,
                 pipeline,
                 attributes,
@@ -75,8 +111,10 @@ where
     stream
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run<Find, E>(
     tree: gix_hash::ObjectId,
+    export_subst: Option<crate::export_subst::Context>,
     objects: Find,
     mut pipeline: gix_filter::Pipeline,
     mut attributes: impl FnMut(&BStr, gix_object::tree::EntryMode, &mut gix_attributes::search::Outcome) -> Result<(), E>
@@ -97,7 +135,7 @@ where
     }
 
     let mut attrs = gix_attributes::search::Outcome::default();
-    attrs.initialize_with_selection(&Default::default(), Some("export-ignore"));
+    attrs.initialize_with_selection(&Default::default(), ["export-ignore", "export-subst"]);
     let mut dlg = traverse::Delegate {
         out,
         err,
@@ -113,6 +151,8 @@ where
         path_deque: Default::default(),
         path: Default::default(),
         buf: Vec::with_capacity(1024),
+        export_subst,
+        subst_buf: Vec::new(),
     };
     gix_traverse::tree::breadthfirst(
         tree_iter,
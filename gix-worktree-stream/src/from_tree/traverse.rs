@@ -1,4 +1,7 @@
-use std::{collections::VecDeque, io::Write};
+use std::{
+    collections::VecDeque,
+    io::{Read, Write},
+};
 
 use gix_filter::{driver::apply::MaybeDelayed, pipeline::convert::ToWorktreeOutcome};
 use gix_object::{
@@ -22,6 +25,8 @@ where
     pub(crate) fetch_attributes: AttributesFn,
     pub(crate) objects: Find,
     pub(crate) buf: Vec<u8>,
+    pub(crate) export_subst: Option<crate::export_subst::Context>,
+    pub(crate) subst_buf: Vec<u8>,
 }
 
 impl<AttributesFn, Find> Delegate<'_, AttributesFn, Find>
@@ -49,7 +54,17 @@ where
         self.attrs
             .iter_selected()
             .next()
-            .expect("initialized with one attr")
+            .expect("initialized with two attrs")
+            .assignment
+            .state
+    }
+
+    /// Return the state of the `export-subst` attribute.
+    fn subst_state(&self) -> gix_attributes::StateRef<'_> {
+        self.attrs
+            .iter_selected()
+            .nth(1)
+            .expect("initialized with two attrs")
             .assignment
             .state
     }
@@ -62,6 +77,10 @@ where
         if self.ignore_state().is_set() {
             return Ok(Action::Continue);
         }
+        let export_subst = self
+            .export_subst
+            .as_ref()
+            .filter(|_| entry.mode.is_blob() && self.subst_state().is_set());
         self.objects.find(entry.oid, &mut self.buf)?;
 
         self.pipeline.driver_context_mut().blob = Some(entry.oid.into());
@@ -76,6 +95,34 @@ where
 
         // Our pipe writer always writes the whole amount.
         #[allow(clippy::unused_io_amount)]
+        if let Some(ctx) = export_subst {
+            let mut content = Vec::new();
+            let content: &[u8] = match converted {
+                ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => buf,
+                ToWorktreeOutcome::Process(MaybeDelayed::Immediate(mut read)) => {
+                    read.read_to_end(&mut content)?;
+                    &content
+                }
+                ToWorktreeOutcome::Process(MaybeDelayed::Delayed(_)) => {
+                    unreachable!("we forbade it")
+                }
+            };
+            let content = if crate::export_subst::expand(ctx, content, &mut self.subst_buf) {
+                &self.subst_buf
+            } else {
+                content
+            };
+            protocol::write_entry_header_and_path(
+                self.path.as_ref(),
+                entry.oid,
+                entry.mode,
+                Some(content.len()),
+                self.out,
+            )?;
+            self.out.write(content)?;
+            return Ok(Action::Continue);
+        }
+        #[allow(clippy::unused_io_amount)]
         match converted {
             ToWorktreeOutcome::Unchanged(buf) | ToWorktreeOutcome::Buffer(buf) => {
                 protocol::write_entry_header_and_path(
@@ -30,8 +30,12 @@ pub struct Stream {
 pub mod entry;
 pub(crate) mod protocol;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod export_subst;
+
 mod from_tree;
-pub use from_tree::from_tree;
+pub use from_tree::{from_commit, from_tree};
 
 pub(crate) type SharedErrorSlot = Arc<parking_lot::Mutex<Option<entry::Error>>>;
 
@@ -0,0 +1,33 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init
+
+echo "subst export-subst" > .gitattributes
+echo 'no $Format:%H$ expansion without attribute' > plain
+git add .
+git commit -m "first"
+
+cat <<'CONTENT' > subst
+commit $Format:%H %h$, tree $Format:%T %t$, parents $Format:%P %p$
+author $Format:%an <%ae> %al %aN %aE %aL$
+committer $Format:%cn <%ce> %cl$
+dates $Format:%ad|%aD|%at|%ai|%aI|%as$
+dates $Format:%cd|%cD|%ct|%ci|%cI|%cs$
+subject $Format:%s$
+body $Format:%b$
+raw $Format:%B$
+magic $Format:a%+s|b%-b|c% h|d%+b$
+escapes $Format:%%|%x41|%n|%Z|%x|%$
+multiple $Format:%h$$Format:%t$ and unterminated $Format:%H
+CONTENT
+git add subst
+git commit -m "second subject
+spanning lines
+
+the body
+
+with paragraphs"
+
+git rev-parse @ > head.hex
+git archive HEAD subst plain | tar -xf - -O subst > expected-subst
@@ -235,8 +235,49 @@ mod from_tree {
         Ok(())
     }
 
+    #[test]
+    fn export_subst_expands_placeholders_like_git_archive() -> gix_testtools::Result {
+        let (dir, head_commit, odb, mut cache) = fixture("export_subst.sh")?;
+        let mut buf = Vec::new();
+        let commit = gix_object::FindExt::find_commit(&odb, &head_commit, &mut buf)?.into();
+        let mut stream = gix_worktree_stream::from_commit(
+            gix_worktree_stream::export_subst::Context::new(head_commit, commit),
+            odb.clone(),
+            Default::default(),
+            move |rela_path, mode, attrs| {
+                cache
+                    .at_entry(rela_path, mode.is_tree().into(), &odb)
+                    .map(|entry| entry.matching_attributes(attrs))
+                    .map(|_| ())
+            },
+        );
+
+        let mut contents = Vec::new();
+        while let Some(mut entry) = stream.next_entry()? {
+            let mut buf = Vec::new();
+            entry.read_to_end(&mut buf)?;
+            contents.push((entry.relative_path().to_owned(), buf));
+        }
+        assert_eq!(
+            contents,
+            [
+                (".gitattributes".into(), b"subst export-subst\n".to_vec()),
+                ("plain".into(), b"no $Format:%H$ expansion without attribute\n".to_vec()),
+                ("subst".into(), std::fs::read(dir.join("expected-subst"))?),
+            ],
+            "only files with the `export-subst` attribute are expanded, exactly like `git archive` does"
+        );
+        Ok(())
+    }
+
     fn basic() -> gix_testtools::Result<(PathBuf, gix_hash::ObjectId, gix_odb::HandleArc, gix_worktree::Stack)> {
-        let dir = gix_testtools::scripted_fixture_read_only("basic.sh")?;
+        fixture("basic.sh")
+    }
+
+    fn fixture(
+        script: &str,
+    ) -> gix_testtools::Result<(PathBuf, gix_hash::ObjectId, gix_odb::HandleArc, gix_worktree::Stack)> {
+        let dir = gix_testtools::scripted_fixture_read_only(script)?;
 
         let head = {
             let hex = std::fs::read(dir.join("head.hex"))?;
@@ -19,6 +19,8 @@ pub struct Statistics {
     pub pattern_files: usize,
     /// Amount of pattern files we tried to find on disk.
     pub tried_pattern_files: usize,
+    /// Amount of pattern lists that were taken from a [shared cache](crate::stack::state::SharedCache) instead.
+    pub shared_cache_hits: usize,
 }
 
 /// Decide where to read `.gitattributes` files from.
@@ -78,6 +80,7 @@ impl Attributes {
             info_attributes,
             source,
             collection,
+            shared_cache: None,
         }
     }
 }
@@ -106,61 +109,81 @@ impl Attributes {
         let read_macros_as_dir_is_root = root == dir;
 
         let mut added = false;
-        match self.source {
-            Source::IdMapping | Source::IdMappingThenWorktree => {
-                if let Ok(idx) = attr_file_in_index {
-                    let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
-                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-                    let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
-                    self.stack.add_patterns_buffer(
-                        blob.data,
-                        attr_path,
-                        Some(Path::new("")),
-                        &mut self.collection,
-                        read_macros_as_dir_is_root,
-                    );
+        let shared_cache = self.shared_cache.clone();
+        match shared_cache
+            .as_ref()
+            .and_then(|cache| cache.attribute_list(attr_path_relative.as_ref()))
+        {
+            Some(list) => {
+                stats.shared_cache_hits += 1;
+                if let Some(list) = list {
+                    self.stack.add_pattern_list(list, &mut self.collection);
                     added = true;
-                    stats.patterns_buffers += 1;
-                }
-                if !added && matches!(self.source, Source::IdMappingThenWorktree) {
-                    added = self.stack.add_patterns_file(
-                        dir.join(".gitattributes"),
-                        no_follow_symlinks,
-                        Some(root),
-                        buf,
-                        &mut self.collection,
-                        read_macros_as_dir_is_root,
-                    )?;
-                    stats.pattern_files += usize::from(added);
-                    stats.tried_pattern_files += 1;
                 }
             }
-            Source::WorktreeThenIdMapping => {
-                added = self.stack.add_patterns_file(
-                    dir.join(".gitattributes"),
-                    no_follow_symlinks,
-                    Some(root),
-                    buf,
-                    &mut self.collection,
-                    read_macros_as_dir_is_root,
-                )?;
-                stats.pattern_files += usize::from(added);
-                stats.tried_pattern_files += 1;
-                if let Some(idx) = attr_file_in_index.ok().filter(|_| !added) {
-                    let blob = objects
-                        .find_blob(&id_mappings[idx].1, buf)
-                        .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
-                    let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
-                    self.stack.add_patterns_buffer(
-                        blob.data,
-                        attr_path,
-                        Some(Path::new("")),
-                        &mut self.collection,
-                        read_macros_as_dir_is_root,
-                    );
-                    added = true;
-                    stats.patterns_buffers += 1;
+            None => {
+                let cache_key = shared_cache.is_some().then(|| attr_path_relative.clone().into_owned());
+                match self.source {
+                    Source::IdMapping | Source::IdMappingThenWorktree => {
+                        if let Ok(idx) = attr_file_in_index {
+                            let blob = objects
+                                .find_blob(&id_mappings[idx].1, buf)
+                                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                            let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
+                            self.stack.add_patterns_buffer(
+                                blob.data,
+                                attr_path,
+                                Some(Path::new("")),
+                                &mut self.collection,
+                                read_macros_as_dir_is_root,
+                            );
+                            added = true;
+                            stats.patterns_buffers += 1;
+                        }
+                        if !added && matches!(self.source, Source::IdMappingThenWorktree) {
+                            added = self.stack.add_patterns_file(
+                                dir.join(".gitattributes"),
+                                no_follow_symlinks,
+                                Some(root),
+                                buf,
+                                &mut self.collection,
+                                read_macros_as_dir_is_root,
+                            )?;
+                            stats.pattern_files += usize::from(added);
+                            stats.tried_pattern_files += 1;
+                        }
+                    }
+                    Source::WorktreeThenIdMapping => {
+                        added = self.stack.add_patterns_file(
+                            dir.join(".gitattributes"),
+                            no_follow_symlinks,
+                            Some(root),
+                            buf,
+                            &mut self.collection,
+                            read_macros_as_dir_is_root,
+                        )?;
+                        stats.pattern_files += usize::from(added);
+                        stats.tried_pattern_files += 1;
+                        if let Some(idx) = attr_file_in_index.ok().filter(|_| !added) {
+                            let blob = objects
+                                .find_blob(&id_mappings[idx].1, buf)
+                                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+                            let attr_path = gix_path::from_bstring(attr_path_relative.into_owned());
+                            self.stack.add_patterns_buffer(
+                                blob.data,
+                                attr_path,
+                                Some(Path::new("")),
+                                &mut self.collection,
+                                read_macros_as_dir_is_root,
+                            );
+                            added = true;
+                            stats.patterns_buffers += 1;
+                        }
+                    }
+                }
+                if let Some((cache, key)) = shared_cache.zip(cache_key) {
+                    let list = added.then(|| self.stack.last_pattern_list().expect("just added").clone());
+                    cache.insert_attribute_list(key, list);
                 }
             }
         }
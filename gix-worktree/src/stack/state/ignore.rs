@@ -47,6 +47,8 @@ pub struct Statistics {
     pub pattern_files: usize,
     /// Amount of pattern files we tried to find on disk.
     pub tried_pattern_files: usize,
+    /// Amount of pattern lists that were taken from a [shared cache](crate::stack::state::SharedCache) instead.
+    pub shared_cache_hits: usize,
 }
 
 impl Ignore {
@@ -69,6 +71,7 @@ impl Ignore {
             exclude_file_name_for_directories: exclude_file_name_for_directories
                 .map_or_else(|| ".gitignore".into(), ToOwned::to_owned),
             source,
+            shared_cache: None,
         }
     }
 }
@@ -173,6 +176,19 @@ impl Ignore {
             .push(self.matching_exclude_pattern_no_dir(rela_dir, Some(true), case));
 
         let ignore_path_relative = gix_path::join_bstr_unix_pathsep(rela_dir, ".gitignore");
+        let shared_cache = self.shared_cache.clone();
+        if let Some(list) = shared_cache
+            .as_ref()
+            .and_then(|cache| cache.ignore_list(ignore_path_relative.as_ref()))
+        {
+            self.stack.patterns.push(list);
+            stats.shared_cache_hits += 1;
+            return Ok(());
+        }
+        let cache_key = shared_cache
+            .is_some()
+            .then(|| ignore_path_relative.clone().into_owned());
+
         let ignore_file_in_index = id_mappings.binary_search_by(|t| t.0.as_bstr().cmp(ignore_path_relative.as_ref()));
         match self.source {
             Source::IdMapping => {
@@ -222,6 +238,9 @@ impl Ignore {
                 }
             }
         }
+        if let Some((cache, key)) = shared_cache.zip(cache_key) {
+            cache.insert_ignore_list(key, self.stack.patterns.last().expect("just pushed").clone());
+        }
         Ok(())
     }
 }
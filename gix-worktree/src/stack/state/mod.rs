@@ -1,4 +1,9 @@
-use bstr::{BString, ByteSlice};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use bstr::{BStr, BString, ByteSlice};
 use gix_glob::pattern::Case;
 
 use crate::{stack::State, PathIdMapping};
@@ -25,6 +30,8 @@ pub struct Attributes {
     collection: gix_attributes::search::MetadataCollection,
     /// Where to read `.gitattributes` data from.
     source: attributes::Source,
+    /// If set, pattern lists of directories are taken from here, or put here once they were read.
+    shared_cache: Option<SharedCache>,
 }
 
 /// State related to the exclusion of files, supporting static overrides and globals, along with a stack of dynamically read
@@ -47,6 +54,61 @@ pub struct Ignore {
     pub(crate) exclude_file_name_for_directories: BString,
     /// Where to read ignore files from
     source: ignore::Source,
+    /// If set, pattern lists of directories are taken from here, or put here once they were read.
+    shared_cache: Option<SharedCache>,
+}
+
+type IgnoreList = gix_glob::search::pattern::List<gix_ignore::search::Ignore>;
+#[cfg(feature = "attributes")]
+type AttributeList = gix_glob::search::pattern::List<gix_attributes::search::Attributes>;
+
+/// A cache for the parsed `.gitignore` and `.gitattributes` files of directories, keyed by their path relative to the worktree root.
+///
+/// It's meant to be shared among [`Stack`](crate::Stack)s that are used for the same worktree, like the ones of multiple threads
+/// during a `status` operation, so that each file only has to be read and parsed once. Clones are cheap and share the same cache.
+///
+/// Note that all stacks sharing a cache must be configured to read their files from the same [sources](ignore::Source), and that
+/// changes to these files won't be picked up once they are cached.
+#[derive(Default, Clone)]
+pub struct SharedCache {
+    ignore: Arc<RwLock<HashMap<BString, IgnoreList>>>,
+    #[cfg(feature = "attributes")]
+    attributes: Arc<RwLock<HashMap<BString, Option<AttributeList>>>>,
+}
+
+impl SharedCache {
+    fn ignore_list(&self, rela_path: &BStr) -> Option<IgnoreList> {
+        self.ignore
+            .read()
+            .expect("no panic while holding lock")
+            .get(rela_path)
+            .cloned()
+    }
+
+    fn insert_ignore_list(&self, rela_path: BString, list: IgnoreList) {
+        self.ignore
+            .write()
+            .expect("no panic while holding lock")
+            .insert(rela_path, list);
+    }
+
+    /// Return `Some(None)` if it's cached that there is no attributes file at `rela_path`.
+    #[cfg(feature = "attributes")]
+    fn attribute_list(&self, rela_path: &BStr) -> Option<Option<AttributeList>> {
+        self.attributes
+            .read()
+            .expect("no panic while holding lock")
+            .get(rela_path)
+            .cloned()
+    }
+
+    #[cfg(feature = "attributes")]
+    fn insert_attribute_list(&self, rela_path: BString, list: Option<AttributeList>) {
+        self.attributes
+            .write()
+            .expect("no panic while holding lock")
+            .insert(rela_path, list);
+    }
 }
 
 ///
@@ -159,6 +221,23 @@ impl State {
             .collect()
     }
 
+    /// Use `cache` to obtain the parsed pattern files of directories, or to store them once they were read, or stop using a cache
+    /// if `None`.
+    pub fn set_shared_cache(&mut self, cache: Option<SharedCache>) {
+        match self {
+            State::IgnoreStack(ignore) => ignore.shared_cache = cache,
+            #[cfg(feature = "attributes")]
+            State::AttributesAndIgnoreStack { attributes, ignore } => {
+                attributes.shared_cache = cache.clone();
+                ignore.shared_cache = cache;
+            }
+            #[cfg(feature = "attributes")]
+            State::AttributesStack(attributes) | State::CreateDirectoryAndAttributesStack { attributes, .. } => {
+                attributes.shared_cache = cache
+            }
+        }
+    }
+
     pub(crate) fn ignore_or_panic(&self) -> &Ignore {
         match self {
             State::IgnoreStack(v) => v,
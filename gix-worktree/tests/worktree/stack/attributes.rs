@@ -26,9 +26,10 @@ fn baseline() -> crate::Result {
     );
 
     let mut cache = gix_worktree::Stack::new(&base, state, case, buf, vec![]);
+    assert_matches_baseline(&mut cache, &base)?;
 
-    let mut actual = cache.attribute_matches();
-    let input = std::fs::read(base.join("baseline"))?;
+    let mut actual = cache.selected_attribute_matches(["info", "test"]);
+    let input = std::fs::read(base.join("baseline.selected"))?;
     for (rela_path, expected) in (baseline::Expectations { lines: input.lines() }) {
         let entry = cache.at_entry(rela_path, None, &gix_object::find::Never)?;
         let has_match = entry.matching_attributes(&mut actual);
@@ -39,17 +40,60 @@ fn baseline() -> crate::Result {
             "matches are reported when git reports them, too"
         );
         assert_references(&actual);
-        assert_eq!(actual.iter_selected().count(), 0, "no selection made yet");
-        let actual: Vec<_> = actual
-            .iter()
-            .filter_map(|m| (!m.assignment.state.is_unspecified()).then_some(m.assignment))
-            .collect();
+        let actual: Vec<_> = actual.iter_selected().map(|m| m.assignment).collect();
         assert_eq!(actual, expected, "we have the same matches: {rela_path:?}");
         assert_eq!(has_match, !actual.is_empty());
     }
 
-    let mut actual = cache.selected_attribute_matches(["info", "test"]);
-    let input = std::fs::read(base.join("baseline.selected"))?;
+    Ok(())
+}
+
+#[test]
+fn shared_cache_is_used_instead_of_reading_files_again() -> crate::Result {
+    let case = probe_case()?;
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_attributes_baseline.sh")?;
+    let base = dir.join("basics");
+    let git_dir = base.join(".git");
+
+    let mut buf = Vec::new();
+    let mut collection = gix_attributes::search::MetadataCollection::default();
+    let mut state = gix_worktree::stack::State::for_checkout(
+        false,
+        state::Attributes::new(
+            gix_attributes::Search::new_globals([base.join("user.attributes")], &mut buf, &mut collection)?,
+            Some(git_dir.join("info").join("attributes")),
+            gix_worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
+            collection,
+        ),
+    );
+    state.set_shared_cache(Some(Default::default()));
+
+    let mut first = gix_worktree::Stack::new(&base, state, case, buf, vec![]);
+    let mut second = first.clone();
+    assert_matches_baseline(&mut first, &base)?;
+    let stats = first.take_statistics().attributes;
+    assert!(stats.pattern_files > 1);
+    assert!(
+        stats.shared_cache_hits > 0,
+        "directories that are entered again are taken from the cache as well"
+    );
+
+    assert_matches_baseline(&mut second, &base)?;
+    let stats = second.take_statistics().attributes;
+    assert!(
+        stats.shared_cache_hits > 0,
+        "the attribute names of cached lists are added to the collection of each stack, so matches are the same"
+    );
+    assert_eq!(
+        stats.pattern_files, 1,
+        "only `info/attributes` is read as it's never cached, all directories are taken from the cache"
+    );
+    Ok(())
+}
+
+fn assert_matches_baseline(cache: &mut gix_worktree::Stack, base: &std::path::Path) -> crate::Result {
+    let mut actual = cache.attribute_matches();
+    let input = std::fs::read(base.join("baseline"))?;
     for (rela_path, expected) in (baseline::Expectations { lines: input.lines() }) {
         let entry = cache.at_entry(rela_path, None, &gix_object::find::Never)?;
         let has_match = entry.matching_attributes(&mut actual);
@@ -60,11 +104,14 @@ fn baseline() -> crate::Result {
             "matches are reported when git reports them, too"
         );
         assert_references(&actual);
-        let actual: Vec<_> = actual.iter_selected().map(|m| m.assignment).collect();
+        assert_eq!(actual.iter_selected().count(), 0, "no selection made yet");
+        let actual: Vec<_> = actual
+            .iter()
+            .filter_map(|m| (!m.assignment.state.is_unspecified()).then_some(m.assignment))
+            .collect();
         assert_eq!(actual, expected, "we have the same matches: {rela_path:?}");
         assert_eq!(has_match, !actual.is_empty());
     }
-
     Ok(())
 }
 
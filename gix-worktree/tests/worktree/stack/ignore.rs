@@ -166,3 +166,64 @@ fn check_against_baseline() -> crate::Result {
     }
     Ok(())
 }
+
+#[test]
+fn shared_cache_is_used_instead_of_reading_files_again() -> crate::Result {
+    let dir = gix_testtools::scripted_fixture_read_only_standalone("make_ignore_and_attributes_setup.sh")?;
+    let worktree_dir = dir.join("repo");
+    let git_dir = worktree_dir.join(".git");
+    let mut buf = Vec::new();
+    let case = probe_case()?;
+    let mut index = gix_index::File::at(git_dir.join("index"), gix_hash::Kind::Sha1, false, Default::default())?;
+    let odb = gix_odb::at(git_dir.join("objects"))?;
+    let mut state = gix_worktree::stack::State::for_add(
+        Default::default(),
+        gix_worktree::stack::state::Ignore::new(
+            Default::default(),
+            gix_ignore::Search::from_git_dir(&git_dir, None, &mut buf)?,
+            None,
+            Source::WorktreeThenIdMappingIfNotSkipped,
+        ),
+    );
+    state.set_shared_cache(Some(Default::default()));
+    let paths_storage = index.take_path_backing();
+    let id_mappings = state.id_mappings_from_index(&index, &paths_storage, case);
+    let mut first = Stack::new(&worktree_dir, state, case, buf, id_mappings);
+    let mut second = first.clone();
+
+    let baseline = std::fs::read(git_dir.parent().unwrap().join("git-check-ignore.baseline"))?;
+    for (relative_entry, _) in (IgnoreExpectations {
+        lines: baseline.lines(),
+    }) {
+        let is_dir = worktree_dir
+            .join(gix_path::from_byte_slice(relative_entry))
+            .metadata()
+            .ok()
+            .map(|m| m.is_dir());
+        let expected = first.at_entry(relative_entry, is_dir, &odb)?.excluded_kind();
+        let actual = second.at_entry(relative_entry, is_dir, &odb)?.excluded_kind();
+        assert_eq!(actual, expected, "{relative_entry}");
+    }
+
+    let first = first.take_statistics().ignore;
+    let second = second.take_statistics().ignore;
+    assert!(first.pattern_files > 0 && first.patterns_buffers > 0);
+    assert!(
+        first.shared_cache_hits > 0,
+        "directories that are entered again are taken from the cache as well"
+    );
+    assert_eq!(
+        (
+            second.pattern_files,
+            second.patterns_buffers,
+            second.tried_pattern_files
+        ),
+        (0, 0, 0),
+        "everything comes from the cache"
+    );
+    assert_eq!(
+        second.shared_cache_hits,
+        first.shared_cache_hits + first.tried_pattern_files
+    );
+    Ok(())
+}
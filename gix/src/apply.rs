@@ -0,0 +1,69 @@
+//! Types and errors for applying patches to the worktree, the index or trees, similar to `git apply`.
+pub use gix_apply as plumbing;
+
+/// Where [`Repository::apply_patches()`](crate::Repository::apply_patches()) reads files from and writes the patched files to.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Location {
+    /// Read from and write to the worktree, leaving the index unchanged, like `git apply`.
+    #[default]
+    Worktree,
+    /// Read from and write to the index, leaving the worktree unchanged, like `git apply --cached`.
+    Index,
+    /// Read from the worktree, and write to both the worktree and the index, like `git apply --index`.
+    WorktreeAndIndex,
+}
+
+/// Options for [`Repository::apply_patches()`](crate::Repository::apply_patches()).
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Where to read files from and write patched files to.
+    pub location: Location,
+    /// If `true`, only check that all patches apply without writing anything, like `git apply --check`.
+    pub check: bool,
+    /// Control how hunks are matched.
+    pub apply: gix_apply::Options,
+}
+
+/// The outcome of [`Repository::apply_patches()`](crate::Repository::apply_patches()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// All files that were changed by the patches, or would have been changed if only checking, sorted by location.
+    pub changes: Vec<gix_apply::Change>,
+}
+
+/// The error returned by [`Repository::apply_patches()`](crate::Repository::apply_patches()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A working tree is required to apply patches to it")]
+    MissingWorkDir,
+    #[error("The patch refers to '{location}', which isn't a valid location within the working tree")]
+    InvalidLocation { location: crate::bstr::BString },
+    #[error("The file at '{location}' is beyond a symbolic link")]
+    BeyondSymlink { location: crate::bstr::BString },
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    FilesystemOptions(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    Apply(#[from] gix_apply::apply::Error),
+    #[error("Could not write {path:?} to the working tree")]
+    Io {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error(transparent)]
+    ConvertToWorktree(#[from] crate::filter::pipeline::convert_to_worktree::Error),
+    #[error("The patched submodule at '{location}' doesn't refer to a commit")]
+    InvalidSubmodule { location: crate::bstr::BString },
+    #[error(transparent)]
+    WriteBlob(#[from] crate::object::write::Error),
+    #[error("The modification time of a file is before the unix epoch")]
+    Stat(#[from] std::time::SystemTimeError),
+    #[error(transparent)]
+    IndexThreads(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
@@ -0,0 +1,62 @@
+//! Types and errors for fetching from bundle files, similar to `git fetch <bundle>`, and for writing them.
+pub use gix_bundle as plumbing;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub mod fetch {
+    use crate::remote;
+
+    /// The outcome of [`Repository::fetch_bundle()`](crate::Repository::fetch_bundle()).
+    #[derive(Debug, Clone)]
+    pub struct Outcome {
+        /// The header of the bundle, with all of its references and prerequisites.
+        pub header: gix_bundle::Header,
+        /// Information about the pack and index that were written into the object database.
+        pub write_pack_bundle: gix_pack::bundle::write::Outcome,
+        /// The mappings between references in the bundle and local references as produced by the ref-specs.
+        pub mappings: Vec<remote::fetch::Mapping>,
+        /// Information about how each of the `mappings` was applied to the local references.
+        pub update_refs: remote::fetch::refs::update::Outcome,
+    }
+
+    /// The error returned by [`Repository::fetch_bundle()`](crate::Repository::fetch_bundle()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Open(#[from] gix_bundle::file::init::Error),
+        #[error("The bundle requires {} object(s) that the repository doesn't have, like {}", .ids.len(), .ids[0])]
+        MissingPrerequisites { ids: Vec<gix_hash::ObjectId> },
+        #[error(transparent)]
+        Configuration(#[from] Box<remote::fetch::Error>),
+        #[error(transparent)]
+        UnbundlePack(#[from] gix_bundle::file::write_pack::Error),
+        #[error(transparent)]
+        MatchRefs(#[from] gix_refspec::match_group::validate::Error),
+        #[error(transparent)]
+        UpdateRefs(#[from] remote::fetch::refs::update::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod write {
+    /// The error returned by [`Repository::write_bundle()`](crate::Repository::write_bundle()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        RevParse(#[from] crate::revision::spec::parse::Error),
+        #[error(transparent)]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+        #[error(transparent)]
+        FindCommit(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        NotACommit(#[from] crate::object::try_into::Error),
+        #[error("Could not share the object database for generating the pack")]
+        ShareObjectDatabase(#[source] std::io::Error),
+        #[error(transparent)]
+        Write(#[from] gix_bundle::write::Error),
+    }
+}
@@ -35,6 +35,15 @@ impl PrepareFetch {
         self
     }
 
+    /// Make this clone a partial one which leaves out the trees and blobs that don't pass `filter`.
+    ///
+    /// The remote is configured as promisor remote, so future fetches from it use the same `filter`.
+    /// Note that objects that are left out are never fetched on demand, so a checkout needs all blobs it touches to be present.
+    pub fn with_filter(mut self, filter: impl Into<Option<gix_traverse::objects::Filter>>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
     /// Apply the given configuration `values` right before readying the actual fetch from the remote.
     /// The configuration is marked with [source API](gix_config::Source::Api), and will not be written back, it's
     /// retained only in memory.
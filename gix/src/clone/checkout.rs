@@ -32,6 +32,14 @@ pub mod main_worktree {
         FindHead(#[from] crate::reference::find::existing::Error),
         #[error("The HEAD reference could not be located")]
         PeelHeadToId(#[from] crate::head::peel::Error),
+        #[error(transparent)]
+        LoadConfig(#[from] gix_config::file::init::from_paths::Error),
+        #[error(transparent)]
+        SetConfig(#[from] gix_config::file::set_raw_value::Error),
+        #[error(transparent)]
+        ApplyConfig(#[from] crate::config::Error),
+        #[error("Could not write configuration file at \"{}\"", path.display())]
+        WriteConfig { source: std::io::Error, path: PathBuf },
     }
 
     /// The progress ids used in [`PrepareCheckout::main_worktree()`].
@@ -126,9 +134,33 @@ pub mod main_worktree {
             bytes.show_throughput(start);
 
             index.write(Default::default())?;
-            Ok((self.repo.take().expect("still present"), outcome))
+            let mut repo = self.repo.take().expect("still present");
+            if outcome.symlinks_written_as_files != 0 {
+                // Remember that this worktree can't have symlinks, so they are compared as files from now on.
+                disable_symlinks(&mut repo)?;
+            }
+            Ok((repo, outcome))
         }
     }
+
+    fn disable_symlinks(repo: &mut Repository) -> Result<(), Error> {
+        let config_path = repo.git_dir().join("config");
+        let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)?;
+        config.set_raw_value("core", None, "symlinks", "false")?;
+        let mut buf = Vec::new();
+        config
+            .write_to(&mut buf)
+            .and_then(|_| std::fs::write(&config_path, buf))
+            .map_err(|source| Error::WriteConfig {
+                source,
+                path: config_path,
+            })?;
+
+        let mut snapshot = repo.config_snapshot_mut();
+        snapshot.set_raw_value("core", None, "symlinks", "false")?;
+        snapshot.commit()?;
+        Ok(())
+    }
 }
 
 /// Access
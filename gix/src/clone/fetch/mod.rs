@@ -28,6 +28,8 @@ pub enum Error {
     SaveConfig(#[from] crate::remote::save::AsError),
     #[error("Failed to write repository configuration to disk")]
     SaveConfigIo(#[from] std::io::Error),
+    #[error("Failed to configure the remote as promisor remote of a partial clone")]
+    PartialCloneConfig(#[from] gix_config::file::set_raw_value::Error),
     #[error("The remote HEAD points to a reference named {head_ref_name:?} which is invalid.")]
     InvalidHeadRef {
         source: gix_validate::reference::name::Error,
@@ -96,6 +98,10 @@ impl PrepareFetch {
                 .unwrap_or_else(|| "origin".into()),
         };
 
+        if self.filter.is_some() {
+            util::set_repository_format_version_for_extensions(repo)?;
+        }
+
         let mut remote = repo
             .remote_at(self.url.clone())?
             .with_refspecs(
@@ -110,7 +116,7 @@ impl PrepareFetch {
             clone_fetch_tags = remote::fetch::Tags::All.into();
         }
 
-        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone())?;
+        let config = util::write_remote_to_local_config_file(&mut remote, remote_name.clone(), self.filter)?;
 
         // Now we are free to apply remote configuration we don't want to be written to disk.
         if let Some(fetch_tags) = clone_fetch_tags {
@@ -154,6 +160,8 @@ impl PrepareFetch {
                 message: reflog_message.clone(),
             })
             .with_shallow(self.shallow.clone())
+            .with_filter(self.filter)
+            .with_write_fetch_head(false)
             .receive_inner(progress, should_interrupt)
             .await?;
 
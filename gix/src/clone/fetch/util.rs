@@ -20,14 +20,46 @@ enum WriteMode {
 pub fn write_remote_to_local_config_file(
     remote: &mut crate::Remote<'_>,
     remote_name: BString,
+    filter: Option<gix_traverse::objects::Filter>,
 ) -> Result<gix_config::File<'static>, Error> {
     let mut config = gix_config::File::new(local_config_meta(remote.repo));
-    remote.save_as_to(remote_name, &mut config)?;
+    remote.save_as_to(remote_name.clone(), &mut config)?;
+    if let Some(filter) = filter {
+        use crate::config::tree::{Extensions, Key, Remote};
+        let name = Some(remote_name.as_bstr());
+        config.set_raw_value("remote", name, Remote::PROMISOR.name(), "true")?;
+        config.set_raw_value(
+            "remote",
+            name,
+            Remote::PARTIAL_CLONE_FILTER.name(),
+            filter.to_string().as_str(),
+        )?;
+        config.set_raw_value(
+            "extensions",
+            None,
+            Extensions::PARTIAL_CLONE.name(),
+            remote_name.as_bstr(),
+        )?;
+    }
 
     write_to_local_config(&config, WriteMode::Append)?;
     Ok(config)
 }
 
+/// Set `core.repositoryFormatVersion` to `1` in the local configuration file, as `git` ignores `extensions.*` otherwise,
+/// like `extensions.partialClone` which is written by [`write_remote_to_local_config_file()`] for partial clones.
+#[allow(clippy::result_large_err)]
+pub fn set_repository_format_version_for_extensions(repo: &mut Repository) -> Result<(), Error> {
+    use crate::config::tree::{Core, Key};
+    let mut config = repo.config_snapshot_mut();
+    config.set_raw_value_filter("core", None, Core::REPOSITORY_FORMAT_VERSION.name(), "1", &mut |meta| {
+        meta.source == gix_config::Source::Local
+    })?;
+    write_to_local_config(&config, WriteMode::Overwrite)?;
+    config.commit().expect("configuration we set is valid");
+    Ok(())
+}
+
 fn local_config_meta(repo: &Repository) -> gix_config::file::Metadata {
     let meta = repo.config.resolved.meta().clone();
     assert_eq!(
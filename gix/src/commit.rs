@@ -31,7 +31,11 @@ pub mod describe {
     use gix_hash::ObjectId;
     use gix_hashtable::HashMap;
 
-    use crate::{bstr::BStr, ext::ObjectIdExt, Repository};
+    use crate::{
+        bstr::{BStr, BString, ByteSlice},
+        ext::ObjectIdExt,
+        Repository,
+    };
 
     /// The result of [`try_resolve()`][Platform::try_resolve()].
     pub struct Resolution<'repo> {
@@ -100,7 +104,20 @@ pub mod describe {
     }
 
     impl SelectRef {
-        fn names(&self, repo: &Repository) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
+        /// Return the names of all selected references, along with the commit they point to, as long as they
+        /// match any of the `patterns`, if there are any, but none of the `exclude_patterns`.
+        fn names(
+            &self,
+            repo: &Repository,
+            patterns: &[BString],
+            exclude_patterns: &[BString],
+        ) -> Result<HashMap<ObjectId, Cow<'static, BStr>>, Error> {
+            let is_selected = |name: &BStr| {
+                let matches = |pattern: &BString| {
+                    gix_glob::wildmatch(pattern.as_bstr(), name, gix_glob::wildmatch::Mode::empty())
+                };
+                !exclude_patterns.iter().any(matches) && (patterns.is_empty() || patterns.iter().any(matches))
+            };
             let platform = repo.references()?;
 
             Ok(match self {
@@ -129,6 +146,7 @@ pub mod describe {
                         )
                             .into()
                     })
+                    .filter(|(_, _, _, name): &(_, _, _, Cow<'_, BStr>)| is_selected(name.as_ref()))
                     .collect();
                     // By priority, then by time ascending, then lexicographically.
                     // More recent entries overwrite older ones due to collection into hashmap.
@@ -154,6 +172,7 @@ pub mod describe {
                             let commit_id = tag.target_id().ok()?.object().ok()?.try_into_commit().ok()?.id;
                             Some((commit_id, tag_time, Cow::<BStr>::from(r.name().shorten().to_owned())))
                         })
+                        .filter(|(_, _, name)| is_selected(name.as_ref()))
                         .collect();
                     // Sort by time ascending, then lexicographically.
                     // More recent entries overwrite older ones due to collection into hashmap.
@@ -177,6 +196,11 @@ pub mod describe {
         pub(crate) first_parent: bool,
         pub(crate) id_as_fallback: bool,
         pub(crate) max_candidates: usize,
+        pub(crate) patterns: Vec<BString>,
+        pub(crate) exclude_patterns: Vec<BString>,
+        pub(crate) long: bool,
+        #[cfg(feature = "status")]
+        pub(crate) dirty_suffix: Option<String>,
     }
 
     impl<'repo> Platform<'repo> {
@@ -198,6 +222,39 @@ pub mod describe {
             self
         }
 
+        /// Only consider names that match the glob `pattern`, like `git describe --match` does.
+        /// If called multiple times, names matching any of the patterns are considered.
+        ///
+        /// Names are matched in their shortened form, i.e. without `refs/tags/`, `refs/heads/` or `refs/remotes/`.
+        pub fn match_pattern(mut self, pattern: impl Into<BString>) -> Self {
+            self.patterns.push(pattern.into());
+            self
+        }
+
+        /// Do not consider names that match the glob `pattern`, like `git describe --exclude` does, even if they
+        /// [match](Self::match_pattern()) otherwise. If called multiple times, names matching any of the patterns are excluded.
+        pub fn exclude_pattern(mut self, pattern: impl Into<BString>) -> Self {
+            self.exclude_patterns.push(pattern.into());
+            self
+        }
+
+        /// If true, always produce the long format with depth and abbreviated id when formatting, even if the commit
+        /// is named directly, like `git describe --long` does.
+        pub fn long(mut self, long: bool) -> Self {
+            self.long = long;
+            self
+        }
+
+        /// If `Some(suffix)`, append `suffix` to the formatted name if the worktree is [dirty](crate::Repository::is_dirty()),
+        /// like `git describe --dirty=<suffix>` does.
+        ///
+        /// Note that obtaining the dirty-state of the repository can be expensive.
+        #[cfg(feature = "status")]
+        pub fn dirty_suffix(mut self, suffix: impl Into<Option<String>>) -> Self {
+            self.dirty_suffix = suffix.into();
+            self
+        }
+
         /// If true, even if no candidate is available a format will always be produced.
         pub fn id_as_fallback(mut self, use_fallback: bool) -> Self {
             self.id_as_fallback = use_fallback;
@@ -207,7 +264,15 @@ pub mod describe {
         /// Try to find a name for the configured commit id using all prior configuration, returning `Some(describe::Format)`
         /// if one was found, or `None` if that wasn't the case.
         pub fn try_format(&self) -> Result<Option<gix_revision::describe::Format<'static>>, Error> {
-            self.try_resolve()?.map(Resolution::format).transpose()
+            let Some(resolution) = self.try_resolve()? else {
+                return Ok(None);
+            };
+            #[cfg(feature = "status")]
+            let mut format = resolution.format_with_dirty_suffix(self.dirty_suffix.clone())?;
+            #[cfg(not(feature = "status"))]
+            let mut format = resolution.format()?;
+            format.long(self.long);
+            Ok(Some(format))
         }
 
         /// Try to find a name for the configured commit id using all prior configuration, returning `Some(Outcome)`
@@ -228,7 +293,7 @@ pub mod describe {
                 &self.id,
                 &mut graph,
                 gix_revision::describe::Options {
-                    name_by_oid: self.select.names(self.repo)?,
+                    name_by_oid: self.select.names(self.repo, &self.patterns, &self.exclude_patterns)?,
                     fallback_to_oid: self.id_as_fallback,
                     first_parent: self.first_parent,
                     max_candidates: self.max_candidates,
@@ -9,7 +9,7 @@ use crate::{
     config::{
         boolean,
         cache::util::{ApplyLeniency, ApplyLeniencyDefaultValue},
-        tree::{Core, Key},
+        tree::{CommitGraph, Core, Key},
         Cache,
     },
     remote,
@@ -20,7 +20,7 @@ use crate::{
 impl Cache {
     #[cfg(feature = "blob-diff")]
     pub(crate) fn diff_algorithm(&self) -> Result<gix_diff::blob::Algorithm, config::diff::algorithm::Error> {
-        use crate::config::{cache::util::ApplyLeniencyDefault, diff::algorithm::Error};
+        use crate::config::cache::util::ApplyLeniencyDefault;
         self.diff_algorithm
             .get_or_try_init(|| {
                 let name = self
@@ -29,10 +29,6 @@ impl Cache {
                     .unwrap_or_else(|| Cow::Borrowed("myers".into()));
                 config::tree::Diff::ALGORITHM
                     .try_into_algorithm(name)
-                    .or_else(|err| match err {
-                        Error::Unimplemented { .. } if self.lenient_config => Ok(gix_diff::blob::Algorithm::Histogram),
-                        err => Err(err),
-                    })
                     .with_lenient_default(self.lenient_config)
             })
             .copied()
@@ -80,15 +76,15 @@ impl Cache {
             if let Some(textconv) = section.value(config::tree::Diff::DRIVER_TEXTCONV.name) {
                 driver.binary_to_text_command = textconv.into_owned().into();
             }
+            if let Some(word_regex) = section.value(config::tree::Diff::DRIVER_WORD_REGEX.name) {
+                driver.word_regex = word_regex.into_owned().into();
+            }
+            if let Some(xfuncname) = section.value(config::tree::Diff::DRIVER_XFUNCNAME.name) {
+                driver.xfuncname = xfuncname.into_owned().into();
+            }
             if let Some(algorithm) = section.value("algorithm") {
                 driver.algorithm = config::tree::Diff::DRIVER_ALGORITHM
                     .try_into_algorithm(algorithm)
-                    .or_else(|err| match err {
-                        config::diff::algorithm::Error::Unimplemented { .. } if self.lenient_config => {
-                            Ok(gix_diff::blob::Algorithm::Histogram)
-                        }
-                        err => Err(err),
-                    })
                     .with_lenient_default(self.lenient_config)
                     .map_err(|err| config::diff::drivers::Error {
                         name: driver.name.clone(),
@@ -98,9 +94,61 @@ impl Cache {
                     .into();
             }
         }
+        // Like `git`, provide the function name patterns of built-in drivers unless they are configured.
+        for (name, xfuncname) in gix_diff::blob::funcname::BUILTIN {
+            let driver = match out.iter_mut().find(|d| d.name == *name) {
+                Some(existing) => existing,
+                None => {
+                    out.push(gix_diff::blob::Driver {
+                        name: (*name).into(),
+                        ..Default::default()
+                    });
+                    out.last_mut().expect("just pushed")
+                }
+            };
+            driver.xfuncname.get_or_insert_with(|| (*xfuncname).into());
+        }
         Ok(out)
     }
 
+    #[cfg(feature = "merge")]
+    pub(crate) fn merge_drivers(&self) -> Vec<gix_merge::blob::Driver> {
+        let mut out = Vec::<gix_merge::blob::Driver>::new();
+        for section in self
+            .resolved
+            .sections_by_name("merge")
+            .into_iter()
+            .flatten()
+            .filter(|s| (self.filter_config_section)(s.meta()))
+        {
+            let Some(name) = section.header().subsection_name().filter(|n| !n.is_empty()) else {
+                continue;
+            };
+
+            let driver = match out.iter_mut().find(|d| d.name == name) {
+                Some(existing) => existing,
+                None => {
+                    out.push(gix_merge::blob::Driver {
+                        name: name.into(),
+                        ..Default::default()
+                    });
+                    out.last_mut().expect("just pushed")
+                }
+            };
+
+            if let Some(display_name) = section.value(config::tree::Merge::DRIVER_NAME.name) {
+                driver.display_name = display_name.into_owned().into();
+            }
+            if let Some(command) = section.value(config::tree::Merge::DRIVER_COMMAND.name) {
+                driver.command = command.into_owned().into();
+            }
+            if let Some(recursive) = section.value(config::tree::Merge::DRIVER_RECURSIVE.name) {
+                driver.recursive = recursive.into_owned().into();
+            }
+        }
+        out
+    }
+
     #[cfg(feature = "blob-diff")]
     pub(crate) fn diff_pipeline_options(
         &self,
@@ -183,6 +231,18 @@ impl Cache {
             })
     }
 
+    /// Returns `true` if the changed-path Bloom filters of the commit-graph may be used to accelerate path-limited traversals.
+    pub(crate) fn may_read_changed_paths(&self) -> Result<bool, config::boolean::Error> {
+        const DEFAULT: bool = true;
+        self.resolved
+            .boolean_by_key("commitGraph.readChangedPaths")
+            .map_or(Ok(DEFAULT), |res| {
+                CommitGraph::READ_CHANGED_PATHS
+                    .enrich_error(res)
+                    .with_lenient_default_value(self.lenient_config, DEFAULT)
+            })
+    }
+
     /// Returns (file-timeout, pack-refs timeout)
     pub(crate) fn lock_timeout(
         &self,
@@ -245,6 +305,17 @@ impl Cache {
         })
     }
 
+    #[cfg(any(feature = "worktree-mutation", feature = "status"))]
+    pub(crate) fn fs_per_mount_point(&self) -> Result<bool, boolean::Error> {
+        use crate::config::tree::gitoxide;
+        boolean(
+            self,
+            "gitoxide.core.probeFilesystemPerMountPoint",
+            &gitoxide::Core::PROBE_FILESYSTEM_PER_MOUNT_POINT,
+            false,
+        )
+    }
+
     #[cfg(feature = "index")]
     pub(crate) fn stat_options(&self) -> Result<gix_index::entry::stat::Options, config::stat_options::Error> {
         use crate::config::tree::gitoxide;
@@ -306,6 +377,7 @@ impl Cache {
                 .assemble_attribute_globals(git_dir, attributes_source, self.attributes)?
                 .0,
             fs: capabilities,
+            fs_per_mount_point: self.fs_per_mount_point()?,
             thread_limit,
             destination_is_initially_empty: false,
             overwrite_existing: false,
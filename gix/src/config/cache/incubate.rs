@@ -15,6 +15,7 @@ pub(crate) struct StageOne {
     pub object_hash: gix_hash::Kind,
     pub reflog: Option<gix_ref::store::WriteReflog>,
     pub precompose_unicode: bool,
+    pub use_reftable: bool,
 }
 
 /// Initialization
@@ -53,6 +54,15 @@ impl StageOne {
             })
             .transpose()?
             .unwrap_or(gix_hash::Kind::Sha1);
+        let use_reftable = (repo_format_version == 1)
+            .then(|| {
+                config
+                    .string("extensions", None, "refStorage")
+                    .map(|storage| Extensions::REF_STORAGE.try_into_is_reftable(storage))
+            })
+            .flatten()
+            .transpose()?
+            .unwrap_or_default();
 
         let extension_worktree = util::config_bool(
             &config,
@@ -89,6 +99,7 @@ impl StageOne {
             object_hash,
             reflog,
             precompose_unicode,
+            use_reftable,
         })
     }
 }
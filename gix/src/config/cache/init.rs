@@ -28,6 +28,7 @@ impl Cache {
             object_hash,
             reflog: _,
             precompose_unicode: _,
+            use_reftable: _,
         }: StageOne,
         git_dir: &std::path::Path,
         branch_name: Option<&gix_ref::FullNameRef>,
@@ -157,10 +158,12 @@ impl Cache {
         let object_kind_hint = util::disambiguate_hint(&config, lenient_config)?;
         let (static_pack_cache_limit_bytes, pack_cache_bytes, object_cache_bytes) =
             util::parse_object_caches(&config, lenient_config, filter_config_section)?;
+        let pack_windows = util::parse_pack_windows(&config, lenient_config, filter_config_section)?;
         // NOTE: When adding a new initial cache, consider adjusting `reread_values_and_clear_caches()` as well.
         Ok(Cache {
             resolved: config.into(),
             use_multi_pack_index,
+            pack_windows,
             object_hash,
             #[cfg(feature = "revision")]
             object_kind_hint,
@@ -337,6 +340,14 @@ fn apply_environment_overrides(
                     let key = &Http::SSL_CA_INFO;
                     (env(key), key.name)
                 },
+                {
+                    let key = &Http::SSL_CERT;
+                    (env(key), key.name)
+                },
+                {
+                    let key = &Http::SSL_KEY;
+                    (env(key), key.name)
+                },
                 {
                     let key = &Http::SSL_VERSION;
                     (env(key), key.name)
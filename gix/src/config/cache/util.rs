@@ -103,6 +103,35 @@ pub(crate) fn parse_object_caches(
     Ok((static_pack_cache_limit, pack_cache_bytes, object_cache_bytes))
 }
 
+/// Return window options for pack data files if `core.packedGitWindowSize` or `core.packedGitLimit` are set,
+/// using git's defaults for the one that isn't.
+pub(crate) fn parse_pack_windows(
+    config: &gix_config::File<'static>,
+    lenient: bool,
+    mut filter_config_section: fn(&gix_config::file::Metadata) -> bool,
+) -> Result<Option<gix_pack::data::window::Options>, Error> {
+    let window_size = config
+        .integer_filter_by_key("core.packedGitWindowSize", &mut filter_config_section)
+        .map(|res| Core::PACKED_GIT_WINDOW_SIZE.try_into_usize(res))
+        .transpose()
+        .with_leniency(lenient)?;
+    let max_mapped_bytes = config
+        .integer_filter_by_key("core.packedGitLimit", &mut filter_config_section)
+        .map(|res| Core::PACKED_GIT_LIMIT.try_into_u64(res))
+        .transpose()
+        .with_leniency(lenient)?;
+    if window_size.is_none() && max_mapped_bytes.is_none() {
+        return Ok(None);
+    }
+    let defaults = gix_pack::data::window::Options::default();
+    Ok(Some(gix_pack::data::window::Options {
+        window_size: window_size
+            .unwrap_or(defaults.window_size)
+            .max(gix_pack::data::window::MIN_WINDOW_SIZE),
+        max_mapped_bytes: max_mapped_bytes.unwrap_or(defaults.max_mapped_bytes),
+    }))
+}
+
 pub(crate) fn parse_core_abbrev(
     config: &gix_config::File<'static>,
     object_hash: gix_hash::Kind,
@@ -7,6 +7,11 @@ pub(crate) mod cache;
 mod snapshot;
 #[cfg(feature = "credentials")]
 pub use snapshot::credential_helpers;
+#[cfg(any(
+    feature = "blocking-http-transport-reqwest",
+    feature = "blocking-http-transport-curl"
+))]
+pub(crate) mod url_match;
 
 ///
 #[allow(clippy::empty_docs)]
@@ -122,8 +127,6 @@ pub mod diff {
         pub enum Error {
             #[error("Unknown diff algorithm named '{name}'")]
             Unknown { name: BString },
-            #[error("The '{name}' algorithm is not yet implemented")]
-            Unimplemented { name: BString },
         }
     }
 
@@ -270,8 +273,17 @@ pub mod ssh_connect_options {
     /// The error produced when obtaining ssh connection configuration.
     #[derive(Debug, thiserror::Error)]
     #[allow(missing_docs)]
-    #[error(transparent)]
-    pub struct Error(#[from] super::key::GenericErrorWithValue);
+    pub enum Error {
+        #[error(transparent)]
+        Key(#[from] super::key::GenericErrorWithValue),
+        #[error(transparent)]
+        Boolean(#[from] super::boolean::Error),
+        #[error("Could not interpolate path at key {key:?}")]
+        InterpolatePath {
+            source: gix_config::path::interpolate::Error,
+            key: &'static str,
+        },
+    }
 }
 
 ///
@@ -593,6 +605,9 @@ pub(crate) struct Cache {
     pub object_hash: gix_hash::Kind,
     /// If true, multi-pack indices, whether present or not, may be used by the object database.
     pub use_multi_pack_index: bool,
+    /// How to map pack data files in windows, as configured by `core.packedGitWindowSize` and `core.packedGitLimit`,
+    /// or `None` to map them as a whole.
+    pub pack_windows: Option<gix_pack::data::window::Options>,
     /// The representation of `core.logallrefupdates`, or `None` if the variable wasn't set.
     pub reflog: Option<gix_ref::store::WriteReflog>,
     /// The representation of `gitoxide.core.refsNamespace`, or `None` if the variable wasn't set.
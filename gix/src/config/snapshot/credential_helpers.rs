@@ -167,6 +167,8 @@ impl Snapshot<'_> {
                     .transpose()
                     .with_leniency(self.repo.options.lenient_config)?
                     .unwrap_or(true),
+                cache: None,
+                prompt: None,
             },
             gix_credentials::helper::Action::get_for_url(url.to_bstring()),
             prompt_options,
@@ -174,7 +176,8 @@ impl Snapshot<'_> {
     }
 }
 
-fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
+/// Return `true` if `host` matches `pattern`, which may use wildcards to match each dot-separated component.
+pub(crate) fn host_matches(pattern: Option<&str>, host: Option<&str>) -> bool {
     match (pattern, host) {
         (Some(pattern), Some(host)) => {
             let lfields = pattern.split('.');
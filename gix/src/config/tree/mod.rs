@@ -18,12 +18,17 @@ pub(crate) mod root {
     impl Tree {
         /// The `author` section.
         pub const AUTHOR: sections::Author = sections::Author;
+        /// The `blame` section.
+        #[cfg(feature = "blame")]
+        pub const BLAME: sections::Blame = sections::Blame;
         /// The `branch` section.
         pub const BRANCH: sections::Branch = sections::Branch;
         /// The `checkout` section.
         pub const CHECKOUT: sections::Checkout = sections::Checkout;
         /// The `clone` section.
         pub const CLONE: sections::Clone = sections::Clone;
+        /// The `commitGraph` section.
+        pub const COMMIT_GRAPH: sections::CommitGraph = sections::CommitGraph;
         /// The `committer` section.
         pub const COMMITTER: sections::Committer = sections::Committer;
         /// The `core` section.
@@ -35,10 +40,16 @@ pub(crate) mod root {
         pub const DIFF: sections::Diff = sections::Diff;
         /// The `extensions` section.
         pub const EXTENSIONS: sections::Extensions = sections::Extensions;
+        /// The `feature` section.
+        pub const FEATURE: sections::Feature = sections::Feature;
         /// The `fetch` section.
         pub const FETCH: sections::Fetch = sections::Fetch;
+        /// The `gc` section.
+        pub const GC: sections::Gc = sections::Gc;
         /// The `gitoxide` section.
         pub const GITOXIDE: sections::Gitoxide = sections::Gitoxide;
+        /// The `gpg` section.
+        pub const GPG: sections::Gpg = sections::Gpg;
         /// The `http` section.
         pub const HTTP: sections::Http = sections::Http;
         /// The `index` section.
@@ -47,16 +58,23 @@ pub(crate) mod root {
         pub const INIT: sections::Init = sections::Init;
         /// The `mailmap` section.
         pub const MAILMAP: sections::Mailmap = sections::Mailmap;
+        /// The `merge` section.
+        #[cfg(feature = "merge")]
+        pub const MERGE: sections::Merge = sections::Merge;
         /// The `pack` section.
         pub const PACK: sections::Pack = sections::Pack;
         /// The `protocol` section.
         pub const PROTOCOL: sections::Protocol = sections::Protocol;
         /// The `push` section.
         pub const PUSH: sections::Push = sections::Push;
+        /// The `receive` section.
+        pub const RECEIVE: sections::Receive = sections::Receive;
         /// The `remote` section.
         pub const REMOTE: sections::Remote = sections::Remote;
         /// The `safe` section.
         pub const SAFE: sections::Safe = sections::Safe;
+        /// The `splitIndex` section.
+        pub const SPLIT_INDEX: sections::SplitIndex = sections::SplitIndex;
         /// The `ssh` section.
         pub const SSH: sections::Ssh = sections::Ssh;
         /// The `status` section.
@@ -71,26 +89,36 @@ pub(crate) mod root {
         pub fn sections(&self) -> &[&dyn Section] {
             &[
                 &Self::AUTHOR,
+                #[cfg(feature = "blame")]
+                &Self::BLAME,
                 &Self::BRANCH,
                 &Self::CHECKOUT,
                 &Self::CLONE,
+                &Self::COMMIT_GRAPH,
                 &Self::COMMITTER,
                 &Self::CORE,
                 &Self::CREDENTIAL,
                 #[cfg(feature = "blob-diff")]
                 &Self::DIFF,
                 &Self::EXTENSIONS,
+                &Self::FEATURE,
                 &Self::FETCH,
+                &Self::GC,
                 &Self::GITOXIDE,
+                &Self::GPG,
                 &Self::HTTP,
                 &Self::INDEX,
                 &Self::INIT,
                 &Self::MAILMAP,
+                #[cfg(feature = "merge")]
+                &Self::MERGE,
                 &Self::PACK,
                 &Self::PROTOCOL,
                 &Self::PUSH,
+                &Self::RECEIVE,
                 &Self::REMOTE,
                 &Self::SAFE,
+                &Self::SPLIT_INDEX,
                 &Self::SSH,
                 #[cfg(feature = "status")]
                 &Self::STATUS,
@@ -103,12 +131,16 @@ pub(crate) mod root {
 
 mod sections;
 pub use sections::{
-    branch, checkout, core, credential, extensions, fetch, gitoxide, http, index, protocol, push, remote, ssh, Author,
-    Branch, Checkout, Clone, Committer, Core, Credential, Extensions, Fetch, Gitoxide, Http, Index, Init, Mailmap,
-    Pack, Protocol, Push, Remote, Safe, Ssh, Url, User,
+    branch, checkout, core, credential, extensions, fetch, gc, gitoxide, http, index, protocol, push, remote, ssh,
+    Author, Branch, Checkout, Clone, CommitGraph, Committer, Core, Credential, Extensions, Feature, Fetch, Gc,
+    Gitoxide, Gpg, Http, Index, Init, Mailmap, Pack, Protocol, Push, Receive, Remote, Safe, SplitIndex, Ssh, Url, User,
 };
+#[cfg(feature = "blame")]
+pub use sections::Blame;
 #[cfg(feature = "blob-diff")]
 pub use sections::{diff, Diff};
+#[cfg(feature = "merge")]
+pub use sections::{merge, Merge};
 #[cfg(feature = "status")]
 pub use sections::{status, Status};
 
@@ -0,0 +1,20 @@
+use crate::config::{
+    tree::{keys, Blame, Key, Section},
+    Tree,
+};
+
+impl Blame {
+    /// The `blame.ignoreRevsFile` key, which may be given multiple times.
+    pub const IGNORE_REVS_FILE: keys::Path = keys::Path::new_path("ignoreRevsFile", &Tree::BLAME)
+        .with_note("An empty value resets the list of files configured before it");
+}
+
+impl Section for Blame {
+    fn name(&self) -> &str {
+        "blame"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::IGNORE_REVS_FILE]
+    }
+}
@@ -0,0 +1,20 @@
+use crate::{
+    config,
+    config::tree::{keys, CommitGraph, Key, Section},
+};
+
+impl CommitGraph {
+    /// The `commitGraph.readChangedPaths` key.
+    pub const READ_CHANGED_PATHS: keys::Boolean =
+        keys::Boolean::new_boolean("readChangedPaths", &config::Tree::COMMIT_GRAPH);
+}
+
+impl Section for CommitGraph {
+    fn name(&self) -> &str {
+        "commitGraph"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::READ_CHANGED_PATHS]
+    }
+}
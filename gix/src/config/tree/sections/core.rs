@@ -26,6 +26,12 @@ impl Core {
     pub const EDITOR: keys::Program = keys::Program::new_program("editor", &config::Tree::CORE);
     /// The `core.fileMode` key.
     pub const FILE_MODE: keys::Boolean = keys::Boolean::new_boolean("fileMode", &config::Tree::CORE);
+    /// The `core.fsmonitor` key.
+    pub const FSMONITOR: keys::Executable = keys::Executable::new_executable("fsmonitor", &config::Tree::CORE)
+        .with_note("only hooks are supported, a boolean to use the builtin filesystem monitor daemon is ignored");
+    /// The `core.fsmonitorHookVersion` key.
+    pub const FSMONITOR_HOOK_VERSION: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("fsmonitorHookVersion", &config::Tree::CORE);
     /// The `core.ignoreCase` key.
     pub const IGNORE_CASE: keys::Boolean = keys::Boolean::new_boolean("ignoreCase", &config::Tree::CORE);
     /// The `core.filesRefLockTimeout` key.
@@ -34,6 +40,14 @@ impl Core {
     /// The `core.packedRefsTimeout` key.
     pub const PACKED_REFS_TIMEOUT: keys::LockTimeout =
         keys::LockTimeout::new_lock_timeout("packedRefsTimeout", &config::Tree::CORE);
+    /// The `core.packedGitWindowSize` key.
+    pub const PACKED_GIT_WINDOW_SIZE: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("packedGitWindowSize", &config::Tree::CORE)
+            .with_note("if neither this nor `core.packedGitLimit` is set, pack data files are mapped as a whole");
+    /// The `core.packedGitLimit` key.
+    pub const PACKED_GIT_LIMIT: keys::UnsignedInteger =
+        keys::UnsignedInteger::new_unsigned_integer("packedGitLimit", &config::Tree::CORE)
+            .with_note("if neither this nor `core.packedGitWindowSize` is set, pack data files are mapped as a whole");
     /// The `core.multiPackIndex` key.
     pub const MULTIPACK_INDEX: keys::Boolean = keys::Boolean::new_boolean("multiPackIndex", &config::Tree::CORE);
     /// The `core.logAllRefUpdates` key.
@@ -47,6 +61,13 @@ impl Core {
     /// The `core.repositoryFormatVersion` key.
     pub const REPOSITORY_FORMAT_VERSION: keys::UnsignedInteger =
         keys::UnsignedInteger::new_unsigned_integer("repositoryFormatVersion", &config::Tree::CORE);
+    /// The `core.sparseCheckout` key.
+    pub const SPARSE_CHECKOUT: keys::Boolean = keys::Boolean::new_boolean("sparseCheckout", &config::Tree::CORE);
+    /// The `core.sparseCheckoutCone` key.
+    pub const SPARSE_CHECKOUT_CONE: keys::Boolean =
+        keys::Boolean::new_boolean("sparseCheckoutCone", &config::Tree::CORE);
+    /// The `core.splitIndex` key.
+    pub const SPLIT_INDEX: keys::Boolean = keys::Boolean::new_boolean("splitIndex", &config::Tree::CORE);
     /// The `core.symlinks` key.
     pub const SYMLINKS: keys::Boolean = keys::Boolean::new_boolean("symlinks", &config::Tree::CORE);
     /// The `core.trustCTime` key.
@@ -65,6 +86,8 @@ impl Core {
     pub const ATTRIBUTES_FILE: keys::Path =
         keys::Path::new_path("attributesFile", &config::Tree::CORE)
             .with_deviation("for checkout - it's already queried but needs building of attributes group, and of course support during checkout");
+    /// The `core.hooksPath` key.
+    pub const HOOKS_PATH: keys::Path = keys::Path::new_path("hooksPath", &config::Tree::CORE);
     /// The `core.sshCommand` key.
     pub const SSH_COMMAND: keys::Executable = keys::Executable::new_executable("sshCommand", &config::Tree::CORE)
         .with_environment_override("GIT_SSH_COMMAND");
@@ -106,19 +129,27 @@ impl Section for Core {
             &Self::DISAMBIGUATE,
             &Self::EDITOR,
             &Self::FILE_MODE,
+            &Self::FSMONITOR,
+            &Self::FSMONITOR_HOOK_VERSION,
             &Self::IGNORE_CASE,
             &Self::FILES_REF_LOCK_TIMEOUT,
             &Self::PACKED_REFS_TIMEOUT,
+            &Self::PACKED_GIT_WINDOW_SIZE,
+            &Self::PACKED_GIT_LIMIT,
             &Self::MULTIPACK_INDEX,
             &Self::LOG_ALL_REF_UPDATES,
             &Self::PRECOMPOSE_UNICODE,
             &Self::REPOSITORY_FORMAT_VERSION,
+            &Self::SPARSE_CHECKOUT,
+            &Self::SPARSE_CHECKOUT_CONE,
+            &Self::SPLIT_INDEX,
             &Self::SYMLINKS,
             &Self::TRUST_C_TIME,
             &Self::WORKTREE,
             &Self::ASKPASS,
             &Self::EXCLUDES_FILE,
             &Self::ATTRIBUTES_FILE,
+            &Self::HOOKS_PATH,
             &Self::SSH_COMMAND,
             &Self::USE_REPLACE_REFS,
             &Self::COMMIT_GRAPH,
@@ -5,8 +5,8 @@ use crate::{
 
 impl Diff {
     /// The `diff.algorithm` key.
-    pub const ALGORITHM: Algorithm = Algorithm::new_with_validate("algorithm", &config::Tree::DIFF, validate::Algorithm)
-                                        .with_deviation("'patience' diff is not implemented and can default to 'histogram' if lenient config is used, and defaults to histogram if unset for fastest and best results");
+    pub const ALGORITHM: Algorithm =
+        Algorithm::new_with_validate("algorithm", &config::Tree::DIFF, validate::Algorithm);
     /// The `diff.renameLimit` key.
     pub const RENAME_LIMIT: keys::UnsignedInteger = keys::UnsignedInteger::new_unsigned_integer(
         "renameLimit",
@@ -31,6 +31,12 @@ impl Diff {
     /// The `diff.<driver>.binary` key.
     pub const DRIVER_BINARY: Binary = Binary::new_with_validate("binary", &config::Tree::DIFF, validate::Binary)
         .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
+    /// The `diff.<driver>.wordRegex` key.
+    pub const DRIVER_WORD_REGEX: keys::String = keys::String::new_string("wordRegex", &config::Tree::DIFF)
+        .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
+    /// The `diff.<driver>.xfuncname` key.
+    pub const DRIVER_XFUNCNAME: keys::String = keys::String::new_string("xfuncname", &config::Tree::DIFF)
+        .with_subsection_requirement(Some(SubSectionRequirement::Parameter("driver")));
 
     /// The `diff.external` key.
     pub const EXTERNAL: keys::Program =
@@ -51,6 +57,8 @@ impl Section for Diff {
             &Self::DRIVER_TEXTCONV,
             &Self::DRIVER_ALGORITHM,
             &Self::DRIVER_BINARY,
+            &Self::DRIVER_WORD_REGEX,
+            &Self::DRIVER_XFUNCNAME,
             &Self::EXTERNAL,
         ]
     }
@@ -70,7 +78,6 @@ mod algorithm {
 
     use crate::{
         bstr::BStr,
-        config,
         config::{diff::algorithm::Error, tree::sections::diff::Algorithm},
     };
 
@@ -84,9 +91,7 @@ mod algorithm {
             } else if name.eq_ignore_ascii_case(b"histogram") {
                 gix_diff::blob::Algorithm::Histogram
             } else if name.eq_ignore_ascii_case(b"patience") {
-                return Err(config::diff::algorithm::Error::Unimplemented {
-                    name: name.into_owned(),
-                });
+                gix_diff::blob::Algorithm::Patience
             } else {
                 return Err(Error::Unknown {
                     name: name.into_owned(),
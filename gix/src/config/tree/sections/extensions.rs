@@ -6,16 +6,26 @@ use crate::{
 impl Extensions {
     /// The `extensions.worktreeConfig` key.
     pub const WORKTREE_CONFIG: keys::Boolean = keys::Boolean::new_boolean("worktreeConfig", &config::Tree::EXTENSIONS);
+    /// The `extensions.partialClone` key, naming the remote to fetch missing objects from.
+    pub const PARTIAL_CLONE: keys::RemoteName =
+        keys::RemoteName::new_remote_name("partialClone", &config::Tree::EXTENSIONS);
     /// The `extensions.objectFormat` key.
     pub const OBJECT_FORMAT: ObjectFormat =
         ObjectFormat::new_with_validate("objectFormat", &config::Tree::EXTENSIONS, validate::ObjectFormat).with_note(
             "Support for SHA256 is prepared but not fully implemented yet. For now we abort when encountered",
         );
+    /// The `extensions.refStorage` key.
+    pub const REF_STORAGE: RefStorage =
+        RefStorage::new_with_validate("refStorage", &config::Tree::EXTENSIONS, validate::RefStorage)
+            .with_note("References stored in reftables can be read, but not changed yet");
 }
 
 /// The `core.checkStat` key.
 pub type ObjectFormat = keys::Any<validate::ObjectFormat>;
 
+/// The `extensions.refStorage` key.
+pub type RefStorage = keys::Any<validate::RefStorage>;
+
 mod object_format {
     use std::borrow::Cow;
 
@@ -35,13 +45,40 @@ mod object_format {
     }
 }
 
+mod ref_storage {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::sections::extensions::RefStorage};
+
+    impl RefStorage {
+        /// Return `true` if `value` configures references to be stored in reftables, or `false` if they are stored in files.
+        pub fn try_into_is_reftable(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<bool, config::key::GenericErrorWithValue> {
+            if value.as_ref() == "files" {
+                Ok(false)
+            } else if value.as_ref() == "reftable" {
+                Ok(true)
+            } else {
+                Err(config::key::GenericErrorWithValue::from_value(self, value.into_owned()))
+            }
+        }
+    }
+}
+
 impl Section for Extensions {
     fn name(&self) -> &str {
         "extensions"
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::OBJECT_FORMAT, &Self::WORKTREE_CONFIG]
+        &[
+            &Self::OBJECT_FORMAT,
+            &Self::REF_STORAGE,
+            &Self::WORKTREE_CONFIG,
+            &Self::PARTIAL_CLONE,
+        ]
     }
 }
 
@@ -56,4 +93,13 @@ mod validate {
             Ok(())
         }
     }
+
+    pub struct RefStorage;
+
+    impl keys::Validate for RefStorage {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Extensions::REF_STORAGE.try_into_is_reftable(value.into())?;
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,20 @@
+use crate::{
+    config,
+    config::tree::{keys, Feature, Key, Section},
+};
+
+impl Feature {
+    /// The `feature.experimental` key.
+    pub const EXPERIMENTAL: keys::Boolean = keys::Boolean::new_boolean("experimental", &config::Tree::FEATURE)
+        .with_note("only affects the default of `fetch.negotiationAlgorithm`, which becomes `skipping`");
+}
+
+impl Section for Feature {
+    fn name(&self) -> &str {
+        "feature"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::EXPERIMENTAL]
+    }
+}
@@ -14,6 +14,12 @@ impl Fetch {
     #[cfg(feature = "attributes")]
     pub const RECURSE_SUBMODULES: RecurseSubmodules =
         RecurseSubmodules::new_with_validate("recurseSubmodules", &config::Tree::FETCH, validate::RecurseSubmodules);
+    /// The `fetch.prune` key.
+    pub const PRUNE: keys::Boolean = keys::Boolean::new_boolean("prune", &config::Tree::FETCH);
+    /// The `fetch.pruneTags` key.
+    pub const PRUNE_TAGS: keys::Boolean = keys::Boolean::new_boolean("pruneTags", &config::Tree::FETCH);
+    /// The `fetch.writeFetchHead` key.
+    pub const WRITE_FETCH_HEAD: keys::Boolean = keys::Boolean::new_boolean("writeFetchHead", &config::Tree::FETCH);
 }
 
 impl Section for Fetch {
@@ -26,6 +32,9 @@ impl Section for Fetch {
             &Self::NEGOTIATION_ALGORITHM,
             #[cfg(feature = "attributes")]
             &Self::RECURSE_SUBMODULES,
+            &Self::PRUNE,
+            &Self::PRUNE_TAGS,
+            &Self::WRITE_FETCH_HEAD,
         ]
     }
 }
@@ -0,0 +1,83 @@
+use crate::{
+    config,
+    config::tree::{keys, Gc, Key, Section},
+};
+
+impl Gc {
+    /// The `gc.reflogExpire` key.
+    pub const REFLOG_EXPIRE: ReflogExpire =
+        ReflogExpire::new_with_validate("reflogExpire", &config::Tree::GC, validate::ReflogExpire)
+            .with_note("per-reference settings in `gc.<pattern>.reflogExpire` are not supported");
+    /// The `gc.reflogExpireUnreachable` key.
+    pub const REFLOG_EXPIRE_UNREACHABLE: ReflogExpire =
+        ReflogExpire::new_with_validate("reflogExpireUnreachable", &config::Tree::GC, validate::ReflogExpire)
+            .with_note("per-reference settings in `gc.<pattern>.reflogExpireUnreachable` are not supported");
+}
+
+/// The `gc.reflogExpire` and `gc.reflogExpireUnreachable` keys.
+pub type ReflogExpire = keys::Any<validate::ReflogExpire>;
+
+mod reflog_expire {
+    use std::borrow::Cow;
+
+    use crate::{
+        bstr::{BStr, ByteSlice},
+        config,
+        config::tree::gc::ReflogExpire,
+    };
+
+    impl ReflogExpire {
+        /// Parse `value` into the time before which reflog entries expire, with `now` as reference time for relative dates,
+        /// or return `None` if entries never expire.
+        ///
+        /// Like git, `never` and `false` disable expiry, while `now` and `all` expire all entries. Otherwise, dates like
+        /// `90 days`, `90.days.ago` or absolute dates are supported.
+        pub fn try_into_expiry_date(
+            &'static self,
+            value: Cow<'_, BStr>,
+            now: std::time::SystemTime,
+        ) -> Result<Option<gix_date::SecondsSinceUnixEpoch>, config::time::Error> {
+            let make_err = |err| config::time::Error::from_value(self, value.clone().into_owned()).with_source(err);
+            let date = value.to_str().map_err(|_| {
+                make_err(gix_date::parse::Error::InvalidDateString {
+                    input: value.to_string(),
+                })
+            })?;
+            Ok(match date {
+                "never" | "false" => None,
+                "now" | "all" => Some(gix_date::SecondsSinceUnixEpoch::MAX),
+                _ => {
+                    let mut date = date.replace('.', " ");
+                    if date.split_whitespace().count() == 2 {
+                        date.push_str(" ago");
+                    }
+                    Some(gix_date::parse(&date, Some(now)).map_err(make_err)?.seconds)
+                }
+            })
+        }
+    }
+}
+
+impl Section for Gc {
+    fn name(&self) -> &str {
+        "gc"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::REFLOG_EXPIRE, &Self::REFLOG_EXPIRE_UNREACHABLE]
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod validate {
+    use crate::{bstr::BStr, config::tree::keys};
+
+    pub struct ReflogExpire;
+    impl keys::Validate for ReflogExpire {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn std::error::Error + Send + Sync + 'static>> {
+            super::Gc::REFLOG_EXPIRE.try_into_expiry_date(value.into(), std::time::SystemTime::now())?;
+            Ok(())
+        }
+    }
+}
@@ -36,6 +36,12 @@ impl Gitoxide {
     /// The `gitoxide.tracePacket` Key.
     pub const TRACE_PACKET: keys::Boolean = keys::Boolean::new_boolean("tracePacket", &config::Tree::GITOXIDE)
         .with_environment_override("GIT_TRACE_PACKET");
+    /// The `gitoxide.readTimeout` Key.
+    pub const READ_TIMEOUT: keys::DurationInMilliseconds = keys::DurationInMilliseconds::new_duration(
+        "readTimeout",
+        &config::Tree::GITOXIDE,
+    )
+    .with_note("entirely new, and in milliseconds, to describe how long to wait for data from `git://` daemons or the native ssh transport until the connection is aborted");
 }
 
 impl Section for Gitoxide {
@@ -44,7 +50,7 @@ impl Section for Gitoxide {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::USER_AGENT, &Self::TRACE_PACKET]
+        &[&Self::USER_AGENT, &Self::TRACE_PACKET, &Self::READ_TIMEOUT]
     }
 
     fn sub_sections(&self) -> &[&dyn Section] {
@@ -116,6 +122,13 @@ mod subsections {
         pub const FILTER_PROCESS_DELAY: keys::Boolean =
             keys::Boolean::new_boolean("filterProcessDelay", &Gitoxide::CORE);
 
+        /// The `gitoxide.core.probeFilesystemPerMountPoint` key (default `false`).
+        ///
+        /// If `true`, the capabilities of filesystems mounted within the worktree are probed when checking out files
+        /// or when computing the status, instead of assuming that `core.symlinks` and `core.fileMode` apply to them as well.
+        pub const PROBE_FILESYSTEM_PER_MOUNT_POINT: keys::Boolean =
+            keys::Boolean::new_boolean("probeFilesystemPerMountPoint", &Gitoxide::CORE);
+
         /// The `gitoxide.core.externalCommandStderr` key (default `true`).
         ///
         /// If `true`, the default, `stderr` of worktree filter programs, or any other git-context bearing command
@@ -143,6 +156,7 @@ mod subsections {
                 &Self::USE_STDEV,
                 &Self::SHALLOW_FILE,
                 &Self::FILTER_PROCESS_DELAY,
+                &Self::PROBE_FILESYSTEM_PER_MOUNT_POINT,
                 &Self::EXTERNAL_COMMAND_STDERR,
                 &Self::REFS_NAMESPACE,
             ]
@@ -183,12 +197,12 @@ mod subsections {
         /// The `gitoxide.http.sslVersionMin` key.
         pub const SSL_VERSION_MIN: http::SslVersion =
             http::SslVersion::new_ssl_version("sslVersionMin", &Gitoxide::HTTP).with_note(
-                "entirely new to set the lower bound for the allowed ssl version range. Overwrites the min bound of `http.sslVersion` if set. Min and Max must be set to become effective.",
+                "entirely new to set the lower bound for the allowed ssl version range. Overwrites the min bound of `http.sslVersion` if set.",
             );
         /// The `gitoxide.http.sslVersionMax` key.
         pub const SSL_VERSION_MAX: http::SslVersion =
             http::SslVersion::new_ssl_version("sslVersionMax", &Gitoxide::HTTP).with_note(
-                "entirely new to set the upper bound for the allowed ssl version range. Overwrites the max bound of `http.sslVersion` if set. Min and Max must be set to become effective.",
+                "entirely new to set the upper bound for the allowed ssl version range. Overwrites the max bound of `http.sslVersion` if set.",
             );
         /// The `gitoxide.http.sslNoVerify` key.
         ///
@@ -345,6 +359,58 @@ mod subsections {
             keys::Executable::new_executable("commandWithoutShellFallback", &Gitoxide::SSH)
                 .with_environment_override("GIT_SSH")
                 .with_note("is always executed without shell and treated as fallback");
+        /// The `gitoxide.ssh.native` key (default `false`).
+        ///
+        /// If `true`, `ssh://` connections are made with the ssh implementation built into the transport, instead of
+        /// spawning an `ssh` program. This requires the `blocking-ssh-transport-native` cargo feature.
+        pub const NATIVE: keys::Boolean = keys::Boolean::new_boolean("native", &Gitoxide::SSH)
+            .with_note("entirely new, useful in environments where spawning processes isn't allowed");
+        /// The `gitoxide.ssh.strictHostKeyChecking` key.
+        pub const STRICT_HOST_KEY_CHECKING: StrictHostKeyChecking = StrictHostKeyChecking::new_with_validate(
+            "strictHostKeyChecking",
+            &Gitoxide::SSH,
+            super::validate::StrictHostKeyChecking,
+        )
+        .with_note("like the ssh option of the same name, one of `yes` (default), `accept-new` or `no`, used by the native ssh implementation");
+        /// The `gitoxide.ssh.knownHostsFile` key.
+        pub const KNOWN_HOSTS_FILE: keys::Path = keys::Path::new_path("knownHostsFile", &Gitoxide::SSH)
+            .with_note("used by the native ssh implementation, defaults to `~/.ssh/known_hosts`");
+        /// The `gitoxide.ssh.identityFile` key, which may be specified multiple times.
+        pub const IDENTITY_FILE: keys::Path = keys::Path::new_path("identityFile", &Gitoxide::SSH).with_note(
+            "used by the native ssh implementation, defaults to `~/.ssh/id_ed25519`, `~/.ssh/id_ecdsa` and `~/.ssh/id_rsa`",
+        );
+        /// The `gitoxide.ssh.useAgent` key (default `true`).
+        pub const USE_AGENT: keys::Boolean = keys::Boolean::new_boolean("useAgent", &Gitoxide::SSH).with_note(
+            "used by the native ssh implementation to authenticate with the keys of a running ssh agent first",
+        );
+    }
+
+    /// The `gitoxide.ssh.strictHostKeyChecking` key.
+    pub type StrictHostKeyChecking = keys::Any<super::validate::StrictHostKeyChecking>;
+
+    #[cfg(feature = "blocking-network-client")]
+    impl StrictHostKeyChecking {
+        /// Parse `value` into the way host keys are checked by the native ssh implementation.
+        pub fn try_into_host_key_check(
+            &'static self,
+            value: std::borrow::Cow<'_, crate::bstr::BStr>,
+        ) -> Result<gix_protocol::transport::client::ssh::native::HostKeyCheck, crate::config::key::GenericErrorWithValue>
+        {
+            use gix_protocol::transport::client::ssh::native::HostKeyCheck;
+
+            use crate::bstr::ByteSlice;
+            Ok(match value.as_ref().as_bytes() {
+                b"yes" | b"true" => HostKeyCheck::Strict,
+                b"accept-new" => HostKeyCheck::AcceptNew,
+                b"no" | b"off" | b"false" => HostKeyCheck::Disabled,
+                _ => {
+                    return Err(crate::config::key::GenericErrorWithValue::from_value(
+                        self,
+                        value.into_owned(),
+                    ))
+                }
+            })
+        }
     }
 
     impl Section for Ssh {
@@ -353,7 +419,14 @@ mod subsections {
         }
 
         fn keys(&self) -> &[&dyn Key] {
-            &[&Self::COMMAND_WITHOUT_SHELL_FALLBACK]
+            &[
+                &Self::COMMAND_WITHOUT_SHELL_FALLBACK,
+                &Self::NATIVE,
+                &Self::STRICT_HOST_KEY_CHECKING,
+                &Self::KNOWN_HOSTS_FILE,
+                &Self::IDENTITY_FILE,
+                &Self::USE_AGENT,
+            ]
         }
 
         fn parent(&self) -> Option<&dyn Section> {
@@ -551,4 +624,13 @@ pub mod validate {
             Ok(())
         }
     }
+
+    pub struct StrictHostKeyChecking;
+    impl Validate for StrictHostKeyChecking {
+        fn validate(&self, _value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            #[cfg(feature = "blocking-network-client")]
+            super::Ssh::STRICT_HOST_KEY_CHECKING.try_into_host_key_check(_value.into())?;
+            Ok(())
+        }
+    }
 }
@@ -0,0 +1,19 @@
+use crate::{
+    config,
+    config::tree::{keys, Gpg, Key, Section},
+};
+
+impl Gpg {
+    /// The `gpg.program` key
+    pub const PROGRAM: keys::Program = keys::Program::new_program("program", &config::Tree::GPG);
+}
+
+impl Section for Gpg {
+    fn name(&self) -> &str {
+        "gpg"
+    }
+
+    fn keys(&self) -> &[&dyn Key] {
+        &[&Self::PROGRAM]
+    }
+}
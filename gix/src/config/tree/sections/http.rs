@@ -48,9 +48,22 @@ impl Http {
     /// The `http.sslCAInfo` key.
     pub const SSL_CA_INFO: keys::Path =
         keys::Path::new_path("sslCAInfo", &config::Tree::HTTP).with_environment_override("GIT_SSL_CAINFO");
+    /// The `http.sslCert` key.
+    pub const SSL_CERT: keys::Path = keys::Path::new_path("sslCert", &config::Tree::HTTP)
+        .with_environment_override("GIT_SSL_CERT")
+        .with_deviation("only PEM encoded certificates are supported, and only if `http.sslKey` is set as well");
+    /// The `http.sslKey` key.
+    pub const SSL_KEY: keys::Path = keys::Path::new_path("sslKey", &config::Tree::HTTP)
+        .with_environment_override("GIT_SSL_KEY")
+        .with_deviation("only unencrypted PEM encoded keys are supported, as `http.sslCertPasswordProtected` isn't");
     /// The `http.schannelCheckRevoke` key.
     pub const SCHANNEL_CHECK_REVOKE: keys::Boolean =
         keys::Boolean::new_boolean("schannelCheckRevoke", &config::Tree::HTTP);
+    /// The `http.uploadPack` key.
+    pub const UPLOAD_PACK: keys::Boolean = keys::Boolean::new_boolean("uploadPack", &config::Tree::HTTP);
+    /// The `http.receivePack` key.
+    pub const RECEIVE_PACK: keys::Boolean = keys::Boolean::new_boolean("receivePack", &config::Tree::HTTP)
+        .with_deviation("defaults to false as the authentication of clients can't be determined");
 }
 
 impl Section for Http {
@@ -72,7 +85,11 @@ impl Section for Http {
             &Self::LOW_SPEED_LIMIT,
             &Self::SCHANNEL_USE_SSL_CA_INFO,
             &Self::SSL_CA_INFO,
+            &Self::SSL_CERT,
+            &Self::SSL_KEY,
             &Self::SCHANNEL_CHECK_REVOKE,
+            &Self::UPLOAD_PACK,
+            &Self::RECEIVE_PACK,
         ]
     }
 }
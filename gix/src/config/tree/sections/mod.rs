@@ -5,6 +5,13 @@
 pub struct Author;
 mod author;
 
+/// The `blame` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "blame")]
+pub struct Blame;
+#[cfg(feature = "blame")]
+mod blame;
+
 /// The `branch` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Branch;
@@ -20,6 +27,11 @@ pub mod checkout;
 pub struct Clone;
 mod clone;
 
+/// The `commitGraph` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct CommitGraph;
+mod commit_graph;
+
 /// The `committer` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Committer;
@@ -47,16 +59,31 @@ pub mod diff;
 pub struct Extensions;
 pub mod extensions;
 
+/// The `feature` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Feature;
+mod feature;
+
 /// The `fetch` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Fetch;
 pub mod fetch;
 
+/// The `gc` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Gc;
+pub mod gc;
+
 /// The `gitoxide` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Gitoxide;
 pub mod gitoxide;
 
+/// The `gpg` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Gpg;
+mod gpg;
+
 /// The `http` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Http;
@@ -76,6 +103,13 @@ mod init;
 pub struct Mailmap;
 mod mailmap;
 
+/// The `merge` top-level section.
+#[derive(Copy, Clone, Default)]
+#[cfg(feature = "merge")]
+pub struct Merge;
+#[cfg(feature = "merge")]
+pub mod merge;
+
 /// The `pack` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Pack;
@@ -91,6 +125,11 @@ pub mod protocol;
 pub struct Push;
 pub mod push;
 
+/// The `receive` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct Receive;
+mod receive;
+
 /// The `remote` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Remote;
@@ -101,6 +140,11 @@ pub mod remote;
 pub struct Safe;
 mod safe;
 
+/// The `splitIndex` top-level section.
+#[derive(Copy, Clone, Default)]
+pub struct SplitIndex;
+mod split_index;
+
 /// The `ssh` top-level section.
 #[derive(Copy, Clone, Default)]
 pub struct Ssh;
@@ -10,6 +10,9 @@ impl Pack {
             .with_deviation("Leaving this key unspecified uses all available cores, instead of 1");
 
     /// The `pack.indexVersion` key.
+    ///
+    /// If unset, the version is chosen by [`gix_pack::index::Version::for_object_hash_and_count()`] based on the kind
+    /// of object hash and the amount of objects in the pack.
     pub const INDEX_VERSION: IndexVersion =
         IndexVersion::new_with_validate("indexVersion", &config::Tree::PACK, validate::IndexVersion);
 }
@@ -30,6 +33,7 @@ mod index_version {
             Ok(match value {
                 1 => gix_pack::index::Version::V1,
                 2 => gix_pack::index::Version::V2,
+                3 => gix_pack::index::Version::V3,
                 _ => return Err(config::key::GenericError::from(self)),
             })
         }
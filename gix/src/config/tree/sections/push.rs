@@ -6,6 +6,10 @@ use crate::{
 impl Push {
     /// The `push.default` key
     pub const DEFAULT: Default = Default::new_with_validate("default", &config::Tree::PUSH, validate::Default);
+    /// The `push.pushOption` key
+    pub const PUSH_OPTION: keys::Any = keys::Any::new("pushOption", &config::Tree::PUSH);
+    /// The `push.gpgSign` key
+    pub const GPG_SIGN: GpgSign = GpgSign::new_with_validate("gpgSign", &config::Tree::PUSH, validate::GpgSign);
 }
 
 impl Section for Push {
@@ -14,13 +18,16 @@ impl Section for Push {
     }
 
     fn keys(&self) -> &[&dyn Key] {
-        &[&Self::DEFAULT]
+        &[&Self::DEFAULT, &Self::PUSH_OPTION, &Self::GPG_SIGN]
     }
 }
 
 /// The `remote.<name>.tagOpt` key type.
 pub type Default = keys::Any<validate::Default>;
 
+/// The `push.gpgSign` key type.
+pub type GpgSign = keys::Any<validate::GpgSign>;
+
 mod default {
     use std::borrow::Cow;
 
@@ -49,8 +56,35 @@ mod default {
     }
 }
 
+mod gpg_sign {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::push::GpgSign, push};
+
+    impl GpgSign {
+        /// Try to interpret `value` as `push.gpgSign`, which is a boolean or `if-asked`.
+        pub fn try_into_gpg_sign(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<push::GpgSign, config::key::GenericErrorWithValue> {
+            if value.as_ref() == "if-asked" {
+                return Ok(push::GpgSign::IfAsked);
+            }
+            let value = gix_config::Boolean::try_from(value.as_ref()).map_err(|err| {
+                config::key::GenericErrorWithValue::from_value(self, value.into_owned()).with_source(err)
+            })?;
+            Ok(if value.into() {
+                push::GpgSign::Yes
+            } else {
+                push::GpgSign::No
+            })
+        }
+    }
+}
+
 mod validate {
     pub struct Default;
+    pub struct GpgSign;
     use std::{borrow::Cow, error::Error};
 
     use crate::{bstr::BStr, config::tree::keys::Validate};
@@ -61,4 +95,11 @@ mod validate {
             Ok(())
         }
     }
+
+    impl Validate for GpgSign {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Push::GPG_SIGN.try_into_gpg_sign(Cow::Borrowed(value))?;
+            Ok(())
+        }
+    }
 }
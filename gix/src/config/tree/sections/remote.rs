@@ -31,6 +31,26 @@ impl Remote {
         http::ProxyAuthMethod::new_proxy_auth_method("proxyAuthMethod", &config::Tree::REMOTE)
             .with_subsection_requirement(NAME_PARAMETER)
             .with_deviation("implemented like git, but never actually tried");
+    /// The `remote.<name>.sshCommand` key.
+    pub const SSH_COMMAND: keys::Executable = keys::Executable::new_executable("sshCommand", &config::Tree::REMOTE)
+        .with_subsection_requirement(NAME_PARAMETER)
+        .with_note("entirely new, like `core.sshCommand` but only for connections to this remote, which also disables `gitoxide.ssh.native` for it");
+    /// The `remote.<name>.promisor` key.
+    pub const PROMISOR: keys::Boolean =
+        keys::Boolean::new_boolean("promisor", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.partialCloneFilter` key.
+    pub const PARTIAL_CLONE_FILTER: PartialCloneFilter = PartialCloneFilter::new_with_validate(
+        "partialCloneFilter",
+        &config::Tree::REMOTE,
+        validate::PartialCloneFilter,
+    )
+    .with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.prune` key, overriding `fetch.prune`.
+    pub const PRUNE: keys::Boolean =
+        keys::Boolean::new_boolean("prune", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
+    /// The `remote.<name>.pruneTags` key, overriding `fetch.pruneTags`.
+    pub const PRUNE_TAGS: keys::Boolean =
+        keys::Boolean::new_boolean("pruneTags", &config::Tree::REMOTE).with_subsection_requirement(NAME_PARAMETER);
 }
 
 impl Section for Remote {
@@ -48,6 +68,11 @@ impl Section for Remote {
             &Self::PUSH,
             &Self::PROXY,
             &Self::PROXY_AUTH_METHOD,
+            &Self::SSH_COMMAND,
+            &Self::PROMISOR,
+            &Self::PARTIAL_CLONE_FILTER,
+            &Self::PRUNE,
+            &Self::PRUNE_TAGS,
         ]
     }
 }
@@ -86,6 +111,30 @@ mod tag_opts {
     }
 }
 
+/// The `remote.<name>.partialCloneFilter` key type.
+pub type PartialCloneFilter = keys::Any<validate::PartialCloneFilter>;
+
+mod partial_clone_filter {
+    use std::borrow::Cow;
+
+    use crate::{bstr::BStr, config, config::tree::remote::PartialCloneFilter};
+
+    impl PartialCloneFilter {
+        /// Try to interpret `value` as filter specification, like `blob:none`.
+        pub fn try_into_filter(
+            &'static self,
+            value: Cow<'_, BStr>,
+        ) -> Result<
+            gix_traverse::objects::Filter,
+            config::key::GenericErrorWithValue<gix_traverse::objects::filter::Error>,
+        > {
+            gix_traverse::objects::Filter::from_bytes(value.as_ref()).map_err(|err| {
+                config::key::GenericErrorWithValue::from_value(self, value.into_owned()).with_source(err)
+            })
+        }
+    }
+}
+
 pub mod validate {
     use std::{borrow::Cow, error::Error};
 
@@ -98,4 +147,12 @@ pub mod validate {
             Ok(())
         }
     }
+
+    pub struct PartialCloneFilter;
+    impl Validate for PartialCloneFilter {
+        fn validate(&self, value: &BStr) -> Result<(), Box<dyn Error + Send + Sync + 'static>> {
+            super::Remote::PARTIAL_CLONE_FILTER.try_into_filter(Cow::Borrowed(value))?;
+            Ok(())
+        }
+    }
 }
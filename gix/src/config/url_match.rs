@@ -0,0 +1,96 @@
+//! Match urls against the `<url>` of sections like `http.<url>`, similar to how `git` does it.
+use std::borrow::Cow;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config::credential_helpers::host_matches,
+};
+
+/// How specifically a url was matched by the `<url>` of a section, with greater values being more specific.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Specificity {
+    /// If `true`, the value is from an environment variable, which is always used in favor of configuration files.
+    from_environment: bool,
+    /// The length of the host in the pattern, so wildcards are less specific than the actual host name.
+    host: usize,
+    /// The length of the path in the pattern plus one, with `0` meaning no path was given.
+    path: usize,
+    /// If `true`, the pattern contained the matching user.
+    user: bool,
+}
+
+/// Return how specifically `url` is matched by `pattern` as used in sections like `http.<url>`, or `None` if it doesn't match.
+///
+/// Schemes must be equal, the host may contain `*` to match any subdomain, ports are equal if they are equal after applying
+/// defaults, the path in `pattern` must be a prefix of the path of `url` at a `/` boundary and if the `pattern` has a user,
+/// it must be the user in `url` as well.
+fn specificity(pattern: &gix_url::Url, url: &gix_url::Url) -> Option<Specificity> {
+    if pattern.scheme != url.scheme
+        || !host_matches(pattern.host(), url.host())
+        || pattern.port_or_default() != url.port_or_default()
+    {
+        return None;
+    }
+    let user = match pattern.user() {
+        Some(user) if Some(user) != url.user() => return None,
+        Some(_) => true,
+        None => false,
+    };
+    Some(Specificity {
+        host: pattern.host().map_or(0, str::len),
+        path: path_prefix_len(pattern.path.as_ref(), url.path.as_ref())?,
+        user,
+        from_environment: false,
+    })
+}
+
+/// Return the value of `<section>.<url>.<key>` with the `<url>` that matches `url` most specifically, or of `<section>.<key>`
+/// if there is no such `<url>`.
+/// Among values of equal specificity, the last one wins.
+///
+/// Values of environment variables are used in favor of all others, just like `git` does.
+/// Sections with invalid urls are ignored.
+pub(crate) fn value<'a>(
+    config: &'a gix_config::File<'static>,
+    section_name: &'a str,
+    key: &str,
+    url: &gix_url::Url,
+    filter: fn(&gix_config::file::Metadata) -> bool,
+) -> Option<Cow<'a, BStr>> {
+    let mut best = None::<(Specificity, Cow<'a, BStr>)>;
+    for section in config
+        .sections_by_name(section_name)?
+        .filter(|section| filter(section.meta()))
+    {
+        let Some(value) = section.value(key) else {
+            continue;
+        };
+        let specificity = match section.header().subsection_name() {
+            Some(pattern) => match gix_url::parse(pattern)
+                .ok()
+                .and_then(|pattern| specificity(&pattern, url))
+            {
+                Some(specificity) => specificity,
+                None => continue,
+            },
+            None => Specificity {
+                from_environment: section.meta().source == gix_config::Source::EnvOverride,
+                ..Default::default()
+            },
+        };
+        if best.as_ref().map_or(true, |(best, _)| specificity >= *best) {
+            best = Some((specificity, value));
+        }
+    }
+    best.map(|(_, value)| value)
+}
+
+/// Return the length of the match of `prefix` in `path` plus one, if `prefix` matches `path` up to a `/`.
+/// An empty or root `prefix` matches every path.
+fn path_prefix_len(prefix: &BStr, path: &BStr) -> Option<usize> {
+    if prefix.is_empty() || prefix == "/" {
+        return (path.is_empty() || path.starts_with(b"/")).then_some(1);
+    }
+    let prefix = prefix.strip_suffix(b"/").unwrap_or(prefix.as_bytes());
+    (path.starts_with(prefix) && matches!(path.get(prefix.len()), None | Some(b'/'))).then_some(prefix.len() + 1)
+}
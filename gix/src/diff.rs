@@ -120,6 +120,7 @@ mod utils {
             gix_diff::blob::platform::Options {
                 algorithm: Some(diff_algo),
                 skip_internal_diff_if_external_is_configured: false,
+                ignore_whitespace: Default::default(),
             },
             gix_diff::blob::Pipeline::new(
                 roots,
@@ -18,6 +18,7 @@ impl Options {
             emit_collapsed: None,
             empty_patterns_match_prefix: false,
             symlinks_to_directories_are_ignored_like_directories: false,
+            emit_metadata: false,
         }
     }
 }
@@ -38,6 +39,7 @@ impl From<Options> for gix_dir::walk::Options {
             emit_collapsed: v.emit_collapsed,
             symlinks_to_directories_are_ignored_like_directories: v
                 .symlinks_to_directories_are_ignored_like_directories,
+            emit_metadata: v.emit_metadata,
         }
     }
 }
@@ -183,4 +185,18 @@ impl Options {
         self.symlinks_to_directories_are_ignored_like_directories = value;
         self
     }
+
+    /// If `toggle` is `true`, the [metadata](gix_dir::entry::Metadata) of each entry, like its size, modification time
+    /// and ownership, is obtained during the walk and provided along with the entry.
+    /// This costs an additional `lstat` call per entry.
+    pub fn emit_metadata(mut self, toggle: bool) -> Self {
+        self.emit_metadata = toggle;
+        self
+    }
+
+    /// Like [`emit_metadata()`](Self::emit_metadata), but only requires a mutably borrowed instance.
+    pub fn set_emit_metadata(&mut self, toggle: bool) -> &mut Self {
+        self.emit_metadata = toggle;
+        self
+    }
 }
@@ -131,4 +131,58 @@ pub mod collate {
             }
         }
     }
+
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod push {
+        /// An error which combines all possible errors when opening a repository, finding remotes and using them to push.
+        ///
+        /// It can be used to detect if the push failed spuriously and thus can be retried.
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error<E: std::error::Error + Send + Sync + 'static = std::convert::Infallible> {
+            #[error(transparent)]
+            Open(#[from] crate::open::Error),
+            #[error(transparent)]
+            FindExistingReference(#[from] crate::reference::find::existing::Error),
+            #[error(transparent)]
+            RemoteInit(#[from] crate::remote::init::Error),
+            #[error(transparent)]
+            FindExistingRemote(#[from] crate::remote::find::existing::Error),
+            #[error(transparent)]
+            #[cfg(feature = "credentials")]
+            CredentialHelperConfig(#[from] crate::config::credential_helpers::Error),
+            #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+            #[error(transparent)]
+            Connect(#[from] crate::remote::connect::Error),
+            #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+            #[error(transparent)]
+            PreparePush(#[from] crate::remote::push::prepare::Error),
+            #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+            #[error(transparent)]
+            Push(#[from] crate::remote::push::Error),
+            #[error(transparent)]
+            Other(E),
+        }
+
+        #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
+        impl<E> crate::protocol::transport::IsSpuriousError for Error<E>
+        where
+            E: std::error::Error + Send + Sync + 'static,
+        {
+            fn is_spurious(&self) -> bool {
+                match self {
+                    Error::Open(_)
+                    | Error::CredentialHelperConfig(_)
+                    | Error::RemoteInit(_)
+                    | Error::FindExistingReference(_)
+                    | Error::FindExistingRemote(_)
+                    | Error::Other(_) => false,
+                    Error::Connect(err) => err.is_spurious(),
+                    Error::PreparePush(err) => err.is_spurious(),
+                    Error::Push(err) => err.is_spurious(),
+                }
+            }
+        }
+    }
 }
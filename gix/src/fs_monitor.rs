@@ -0,0 +1,109 @@
+//! A client for the `fsmonitor` hook as configured in `core.fsmonitor`, to learn which worktree paths changed since a token.
+//!
+//! The hook typically is a script which asks a filesystem watcher like `watchman` for all changes since the given token.
+use std::path::{Path, PathBuf};
+
+use crate::bstr::{BString, ByteSlice};
+pub use gix_index::extension::fs_monitor::{Changes, Token};
+
+/// The error returned by [`query_hook()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not launch fsmonitor hook at {path:?}")]
+    Spawn { source: std::io::Error, path: PathBuf },
+    #[error("The fsmonitor hook at {path:?} failed with {status}")]
+    Failed {
+        status: std::process::ExitStatus,
+        path: PathBuf,
+    },
+    #[error("The fsmonitor hook at {path:?} didn't provide a token")]
+    MissingToken { path: PathBuf },
+    #[error("A token of version 2 can't be used with version 1 of the fsmonitor hook protocol")]
+    TokenVersionMismatch,
+}
+
+/// The version of the protocol to use when talking to the `fsmonitor` hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// The hook receives the time of the last query and reports all paths that changed since then.
+    V1,
+    /// The hook receives an opaque token and reports a new token along with all paths that changed since the given one.
+    V2,
+}
+
+/// The information provided by the `fsmonitor` hook.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    /// The token to use in the next query, which all returned `changes` are relative to.
+    pub token: Token,
+    /// All paths that changed since the token that was passed to the hook.
+    pub changes: Changes,
+}
+
+/// Run the `fsmonitor` `hook` in `worktree` using the protocol `version` to learn which paths changed since `token`.
+///
+/// If the hook can't tell what changed, for instance because it doesn't know `token`, it may report the
+/// worktree root, which is returned as [`Changes::All`].
+pub fn query_hook(hook: &Path, worktree: &Path, version: Version, token: &Token) -> Result<Response, Error> {
+    let (version_arg, token_arg, new_token) = match (version, token) {
+        (Version::V1, Token::V1 { nanos_since_1970 }) => (
+            "1",
+            nanos_since_1970.to_string(),
+            Some(Token::V1 {
+                nanos_since_1970: now_in_nanos(),
+            }),
+        ),
+        (Version::V1, Token::V2 { .. }) => return Err(Error::TokenVersionMismatch),
+        (Version::V2, token) => ("2", token.to_string(), None),
+    };
+    let out = std::process::Command::new(hook)
+        .arg(version_arg)
+        .arg(token_arg)
+        .current_dir(worktree)
+        .stdin(std::process::Stdio::null())
+        .stderr(std::process::Stdio::inherit())
+        .output()
+        .map_err(|source| Error::Spawn {
+            source,
+            path: hook.to_owned(),
+        })?;
+    if !out.status.success() {
+        return Err(Error::Failed {
+            status: out.status,
+            path: hook.to_owned(),
+        });
+    }
+
+    let mut fields = out.stdout.split(|b| *b == 0);
+    let token = match new_token {
+        Some(token) => token,
+        None => Token::V2 {
+            token: fields
+                .next()
+                .filter(|token| !token.is_empty())
+                .ok_or_else(|| Error::MissingToken { path: hook.to_owned() })?
+                .into(),
+        },
+    };
+    let mut paths = Vec::new();
+    for path in fields.filter(|path| !path.is_empty()) {
+        if path == b"/" {
+            return Ok(Response {
+                token,
+                changes: Changes::All,
+            });
+        }
+        paths.push(BString::from(path.trim_end_with(|c| c == '\n')));
+    }
+    Ok(Response {
+        token,
+        changes: Changes::Paths(paths),
+    })
+}
+
+fn now_in_nanos() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos().try_into().unwrap_or(u64::MAX))
+}
@@ -95,6 +95,8 @@
 pub use gix_actor as actor;
 #[cfg(feature = "attributes")]
 pub use gix_attributes as attrs;
+#[cfg(feature = "blame")]
+pub use gix_blame as blame;
 #[cfg(feature = "command")]
 pub use gix_command as command;
 pub use gix_commitgraph as commitgraph;
@@ -120,6 +122,8 @@ pub use gix_ignore as ignore;
 #[cfg(feature = "index")]
 pub use gix_index as index;
 pub use gix_lock as lock;
+#[cfg(feature = "merge")]
+pub use gix_merge as merge;
 #[cfg(feature = "credentials")]
 pub use gix_negotiate as negotiate;
 pub use gix_object as objs;
@@ -181,6 +185,10 @@ pub mod commit;
 #[cfg(feature = "dirwalk")]
 #[allow(clippy::empty_docs)]
 pub mod dirwalk;
+///
+#[cfg(feature = "index")]
+#[allow(clippy::empty_docs)]
+pub mod fs_monitor;
 pub mod head;
 pub mod id;
 pub mod object;
@@ -188,6 +196,8 @@ pub mod object;
 pub mod pathspec;
 pub mod reference;
 pub mod repository;
+#[cfg(feature = "blocking-network-client")]
+pub mod server;
 #[cfg(feature = "attributes")]
 pub mod submodule;
 pub mod tag;
@@ -328,6 +338,26 @@ pub mod remote;
 #[allow(clippy::empty_docs)]
 pub mod init;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "dirwalk")]
+pub mod stage;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "worktree-mutation")]
+pub mod sparse;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "apply")]
+pub mod apply;
+
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "bundle")]
+pub mod bundle;
+
 /// Not to be confused with 'status'.
 pub mod state;
 
@@ -155,9 +155,38 @@ pub mod diff {
         }
     }
 
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod words {
+        /// The error returned by [Platform::words()](super::Platform::words()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+            #[error("The word regex of the diff driver could not be used")]
+            WordRegex(#[from] gix_diff::blob::words::tokenizer::Error),
+        }
+    }
+
+    ///
+    #[allow(clippy::empty_docs)]
+    pub mod unified_diff {
+        /// The error returned by [Platform::unified_diff()](super::Platform::unified_diff()).
+        #[derive(Debug, thiserror::Error)]
+        #[allow(missing_docs)]
+        pub enum Error {
+            #[error(transparent)]
+            PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+            #[error("The function name patterns of the diff driver could not be used")]
+            Funcname(#[from] gix_diff::blob::funcname::from_xfuncname::Error),
+        }
+    }
+
     impl<'a> Platform<'a> {
         /// Perform a diff on lines between the old and the new version of a blob, passing each hunk of lines to `process_hunk`.
         /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        /// Changes in whitespace are ignored as configured in the [options](gix_diff::blob::platform::Options::ignore_whitespace) of the `resource_cache`.
         /// Note that `process_hunk` is not called if one of the involved resources are binary, but that can be determined
         /// by introspecting the outcome.
         // TODO: more tests (only tested insertion right now)
@@ -171,6 +200,7 @@ pub mod diff {
         {
             self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
 
+            let ignore_whitespace = self.resource_cache.options.ignore_whitespace;
             let prep = self.resource_cache.prepare_diff()?;
             match prep.operation {
                 Operation::InternalDiff { algorithm } => {
@@ -178,7 +208,7 @@ pub mod diff {
                     let mut err = None;
                     let mut lines = Vec::new();
 
-                    gix_diff::blob::diff(algorithm, &input, |before: Range<u32>, after: Range<u32>| {
+                    let process = |before: Range<u32>, after: Range<u32>| {
                         if err.is_some() {
                             return;
                         }
@@ -207,7 +237,8 @@ pub mod diff {
                             })
                             .err();
                         }
-                    });
+                    };
+                    gix_diff::blob::diff_ignoring_whitespace(algorithm, &input, ignore_whitespace, process);
 
                     if let Some(err) = err {
                         return Err(lines::Error::ProcessHunk(err));
@@ -221,18 +252,131 @@ pub mod diff {
             Ok(prep)
         }
 
+        /// Produce the hunks of the diff between the old and the new version of a blob in the unified diff format, with
+        /// `context_size` unchanged lines around each change, or `None` if one of the involved resources is binary.
+        /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        /// Changes in whitespace are ignored as configured in the [options](gix_diff::blob::platform::Options::ignore_whitespace) of the `resource_cache`.
+        /// Hunk headers show the name of the function they are in, as found with the `diff.<driver>.xfuncname` patterns
+        /// or the patterns built into `git` for the diff driver, or by taking lines starting with a letter, `_` or `$` otherwise.
+        ///
+        /// Note that the output is empty if there is no difference, and that it lacks the header which turns it into a patch.
+        pub fn unified_diff(
+            &mut self,
+            context_size: gix_diff::blob::unified_diff::ContextSize,
+        ) -> Result<Option<Vec<u8>>, unified_diff::Error> {
+            use gix_diff::blob::funcname::Funcname;
+            self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
+
+            let ignore_whitespace = self.resource_cache.options.ignore_whitespace;
+            let prep = self.resource_cache.prepare_diff()?;
+            match prep.operation {
+                Operation::InternalDiff { algorithm } => {
+                    let funcname = match prep.driver.and_then(|driver| driver.xfuncname.as_ref()) {
+                        Some(patterns) => Funcname::from_xfuncname(patterns.as_ref())?,
+                        None => Funcname::Default,
+                    };
+                    let input = prep.interned_input();
+                    let hunks = gix_diff::blob::diff_ignoring_whitespace(
+                        algorithm,
+                        &input,
+                        gix_diff::blob::IgnoreWhitespace {
+                            blank_lines: false,
+                            ..ignore_whitespace
+                        },
+                        gix_diff::blob::UnifiedDiff::new(&input, context_size)
+                            .ignore_blank_lines(ignore_whitespace.blank_lines)
+                            .funcname(Some(&funcname)),
+                    );
+                    Ok(Some(hunks))
+                }
+                Operation::ExternalCommand { .. } => {
+                    unreachable!("we disabled that")
+                }
+                Operation::SourceOrDestinationIsBinary => Ok(None),
+            }
+        }
+
+        /// Perform a diff on words between the old and the new version of a blob, and return the byte ranges of all changes,
+        /// or `None` if one of the involved resources is binary.
+        ///
+        /// Words are found using `tokenizer`, or if `None`, by the `diff.<driver>.wordRegex` configuration of the diff driver.
+        /// Without either, words are runs of non-whitespace characters, just like in `git diff --word-diff`.
+        /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        pub fn words(
+            &mut self,
+            tokenizer: Option<&gix_diff::blob::words::Tokenizer>,
+        ) -> Result<Option<Vec<gix_diff::blob::words::Change>>, words::Error> {
+            use gix_diff::blob::words::Tokenizer;
+            self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
+
+            let prep = self.resource_cache.prepare_diff()?;
+            match prep.operation {
+                Operation::InternalDiff { algorithm } => {
+                    let configured;
+                    let tokenizer = match (tokenizer, prep.driver.and_then(|driver| driver.word_regex.as_ref())) {
+                        (Some(tokenizer), _) => tokenizer,
+                        (None, Some(word_regex)) => {
+                            configured = Tokenizer::from_word_regex(word_regex.as_ref())?;
+                            &configured
+                        }
+                        (None, None) => &Tokenizer::Whitespace,
+                    };
+                    Ok(Some(gix_diff::blob::words::diff(
+                        algorithm,
+                        prep.old.data.as_slice().unwrap_or_default(),
+                        prep.new.data.as_slice().unwrap_or_default(),
+                        tokenizer,
+                    )))
+                }
+                Operation::ExternalCommand { .. } => {
+                    unreachable!("we disabled that")
+                }
+                Operation::SourceOrDestinationIsBinary => Ok(None),
+            }
+        }
+
+        /// Return `true` if the change between the old and the new version of a blob is interesting according to `filter`,
+        /// like `git log -S` or `git log -G` would determine, or `false` if one of the involved resources is binary.
+        /// The diffing algorithm is determined by the `diff.algorithm` configuration, or individual diff drivers.
+        pub fn pickaxe(
+            &mut self,
+            filter: &gix_diff::blob::pickaxe::Filter,
+        ) -> Result<bool, gix_diff::blob::platform::prepare_diff::Error> {
+            self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
+
+            let prep = self.resource_cache.prepare_diff()?;
+            match prep.operation {
+                Operation::InternalDiff { algorithm } => Ok(filter.matches(
+                    algorithm,
+                    prep.old.data.as_slice().unwrap_or_default(),
+                    prep.new.data.as_slice().unwrap_or_default(),
+                )),
+                Operation::ExternalCommand { .. } => {
+                    unreachable!("we disabled that")
+                }
+                Operation::SourceOrDestinationIsBinary => Ok(false),
+            }
+        }
+
         /// Count the amount of removed and inserted lines efficiently.
+        /// Changes in whitespace are ignored as configured in the [options](gix_diff::blob::platform::Options::ignore_whitespace) of the `resource_cache`.
         /// Note that nothing will happen if one of the inputs is binary, and `None` will be returned.
         pub fn line_counts(
             &mut self,
         ) -> Result<Option<gix_diff::blob::sink::Counter<()>>, gix_diff::blob::platform::prepare_diff::Error> {
             self.resource_cache.options.skip_internal_diff_if_external_is_configured = false;
 
+            let ignore_whitespace = self.resource_cache.options.ignore_whitespace;
             let prep = self.resource_cache.prepare_diff()?;
             match prep.operation {
                 Operation::InternalDiff { algorithm } => {
                     let tokens = prep.interned_input();
-                    let counter = gix_diff::blob::diff(algorithm, &tokens, gix_diff::blob::sink::Counter::default());
+                    let counter = gix_diff::blob::diff_ignoring_whitespace(
+                        algorithm,
+                        &tokens,
+                        ignore_whitespace,
+                        gix_diff::blob::sink::Counter::default(),
+                    );
                     Ok(Some(counter))
                 }
                 Operation::ExternalCommand { .. } => {
@@ -154,6 +154,11 @@ impl<'repo> Commit<'repo> {
             first_parent: false,
             id_as_fallback: false,
             max_candidates: 10,
+            patterns: Vec::new(),
+            exclude_patterns: Vec::new(),
+            long: false,
+            #[cfg(feature = "status")]
+            dirty_suffix: None,
         }
     }
 
@@ -67,7 +67,115 @@ pub enum Event<'a, 'old, 'new> {
     },
 }
 
+/// A change to a submodule, as returned by [`Change::submodule()`](super::Change::submodule()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Submodule {
+    /// The commit the submodule was at, or `None` if there was no submodule before.
+    pub previous_commit: Option<gix_hash::ObjectId>,
+    /// The commit the submodule is at now, or `None` if there is no submodule anymore.
+    pub commit: Option<gix_hash::ObjectId>,
+    /// `Some(true)` if `commit` is present in the repository of the submodule, which means it can be checked out,
+    /// or `None` if the submodule repository isn't available or if there is no `commit`.
+    pub commit_is_present: Option<bool>,
+    /// `Some(true)` if the worktree of the submodule has changes, including a checked out commit that differs from the
+    /// one in the index of the super-project, or `None` if that couldn't be determined as there is no worktree of the
+    /// submodule.
+    ///
+    /// It's always `None` without the `status` feature.
+    pub is_dirty: Option<bool>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod submodule {
+    /// The error returned by [`Change::submodule()`](super::super::Change::submodule()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Modules(#[from] crate::submodule::modules::Error),
+        #[error(transparent)]
+        Path(#[from] gix_submodule::config::path::Error),
+        #[error(transparent)]
+        Open(#[from] crate::submodule::open::Error),
+        #[error(transparent)]
+        Ignore(#[from] gix_submodule::config::Error),
+        #[cfg(feature = "status")]
+        #[error(transparent)]
+        Status(#[from] crate::submodule::status::Error),
+    }
+}
+
 impl<'a, 'old, 'new> super::Change<'a, 'old, 'new> {
+    /// Return information about the submodule that changed, or `None` if neither side of the change is a submodule.
+    ///
+    /// The submodule is looked up in the repository of the new side of the change by its [location](super::Change::location),
+    /// so [path tracking](super::Platform::track_path()) should be enabled, to learn if its new commit is present and
+    /// if its worktree is dirty.
+    pub fn submodule(&self) -> Result<Option<Submodule>, submodule::Error> {
+        let (previous, current, repo) = match self.event {
+            Event::Addition { entry_mode, id } => (None, Some((entry_mode, id.inner)), id.repo),
+            Event::Deletion { entry_mode, id } => (Some((entry_mode, id.inner)), None, id.repo),
+            Event::Modification {
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            } => (
+                Some((previous_entry_mode, previous_id.inner)),
+                Some((entry_mode, id.inner)),
+                id.repo,
+            ),
+            Event::Rewrite {
+                source_entry_mode,
+                source_id,
+                entry_mode,
+                id,
+                ..
+            } => (
+                Some((source_entry_mode, source_id.inner)),
+                Some((entry_mode, id.inner)),
+                id.repo,
+            ),
+        };
+        let commit_of = |side: Option<(gix_object::tree::EntryMode, gix_hash::ObjectId)>| {
+            side.filter(|(mode, _)| mode.is_commit()).map(|(_, id)| id)
+        };
+        let mut out = Submodule {
+            previous_commit: commit_of(previous),
+            commit: commit_of(current),
+            commit_is_present: None,
+            is_dirty: None,
+        };
+        if out.previous_commit.is_none() && out.commit.is_none() {
+            return Ok(None);
+        }
+
+        let Some(submodules) = repo.submodules()? else {
+            return Ok(Some(out));
+        };
+        let mut found = None;
+        for submodule in submodules {
+            if submodule.path()? == self.location {
+                found = Some(submodule);
+                break;
+            }
+        }
+        let Some(submodule) = found else {
+            return Ok(Some(out));
+        };
+        if let (Some(commit), Some(submodule_repo)) = (out.commit, submodule.open()?) {
+            out.commit_is_present = Some(submodule_repo.has_object(commit));
+        }
+        #[cfg(feature = "status")]
+        {
+            out.is_dirty = submodule
+                .status(submodule.ignore()?.unwrap_or_default(), true)?
+                .is_dirty();
+        }
+        Ok(Some(out))
+    }
+
     /// Produce a platform for performing a line-diff no matter whether the underlying [Event] is an addition, modification,
     /// deletion or rewrite.
     /// Use `resource_cache` to store the diffable data and possibly reuse previously stored data.
@@ -85,6 +193,252 @@ impl<'a, 'old, 'new> super::Change<'a, 'old, 'new> {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod write_patch {
+    /// Options for use in [`Change::write_patch()`](super::super::Change::write_patch()).
+    #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+    pub struct Options {
+        /// The amount of unchanged lines to write around each change.
+        pub context_size: gix_diff::blob::unified_diff::ContextSize,
+        /// If `true`, write binary patches for binary files like `git diff --binary` does, instead of only noting that they differ.
+        pub binary: bool,
+        /// If `true`, mark the new commit of submodules with a [dirty](super::Submodule::is_dirty) worktree with a `-dirty`
+        /// suffix, like `git diff` does when comparing with the worktree.
+        pub mark_dirty_submodules: bool,
+    }
+
+    /// The error returned by [`Change::write_patch()`](super::super::Change::write_patch()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        SetResource(#[from] Box<crate::object::blob::diff::init::Error>),
+        #[error(transparent)]
+        PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+        #[error(transparent)]
+        UnifiedDiff(#[from] crate::object::blob::diff::unified_diff::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        Submodule(#[from] Box<super::submodule::Error>),
+        #[error("Could not write patch")]
+        Io(#[from] std::io::Error),
+    }
+}
+
+impl<'a, 'old, 'new> super::Change<'a, 'old, 'new> {
+    /// Write this change as patch in the unified diff format to `out`, just like `git diff` would, configured by `options`.
+    /// Use `resource_cache` to store the diffable data and possibly reuse previously stored data, see [`diff()`](Self::diff())
+    /// for details. To produce patches that `git apply` accepts, it should be configured to use the data as stored in `git`, i.e.
+    /// with [`Mode::ToGit`](gix_diff::blob::pipeline::Mode::ToGit).
+    ///
+    /// Note that nothing is written for trees, so [path tracking](super::Platform::track_path()) should be enabled for
+    /// the paths in the patch to be complete. Submodules are written like `git` does, as `Subproject commit <id>` lines,
+    /// see [`submodule()`](Self::submodule()) for details on how dirty submodules are found.
+    /// Binary files are written as binary patches with the content as stored in `git` if [`binary`](write_patch::Options::binary)
+    /// is set, and with full object ids just like `git` does.
+    pub fn write_patch(
+        &self,
+        resource_cache: &mut gix_diff::blob::Platform,
+        write_patch::Options {
+            context_size,
+            binary,
+            mark_dirty_submodules,
+        }: write_patch::Options,
+        mut out: impl std::io::Write,
+    ) -> Result<(), write_patch::Error> {
+        use gix_diff::blob::unified_diff::{Content, Header, Rewrite, Side, Subproject};
+
+        let (old, new, rewrite, repo) = match self.event {
+            Event::Addition { entry_mode, id } => (None, Some((self.location, entry_mode, id.inner)), None, id.repo),
+            Event::Deletion { entry_mode, id } => (Some((self.location, entry_mode, id.inner)), None, None, id.repo),
+            Event::Modification {
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            } => (
+                Some((self.location, previous_entry_mode, previous_id.inner)),
+                Some((self.location, entry_mode, id.inner)),
+                None,
+                id.repo,
+            ),
+            Event::Rewrite {
+                source_location,
+                source_entry_mode,
+                source_id,
+                diff,
+                entry_mode,
+                id,
+                copy,
+            } => (
+                Some((source_location, source_entry_mode, source_id.inner)),
+                Some((self.location, entry_mode, id.inner)),
+                Some(Rewrite {
+                    copy,
+                    similarity: diff.map_or(1.0, |stats| stats.similarity),
+                }),
+                id.repo,
+            ),
+        };
+        if old.into_iter().chain(new).all(|(_, mode, _)| mode.is_tree()) {
+            return Ok(());
+        }
+
+        let is_submodule = old.into_iter().chain(new).any(|(_, mode, _)| mode.is_commit());
+        let hunks = if is_submodule {
+            let is_dirty = if mark_dirty_submodules {
+                self.submodule()
+                    .map_err(Box::new)?
+                    .and_then(|submodule| submodule.is_dirty)
+            } else {
+                None
+            };
+            fn subproject<'b>(
+                side: &'b Option<(&BStr, gix_object::tree::EntryMode, gix_hash::ObjectId)>,
+                dirty: bool,
+            ) -> Option<Subproject<'b>> {
+                side.as_ref()
+                    .filter(|(_, mode, _)| mode.is_commit())
+                    .map(|(_, _, id)| Subproject { commit: id, dirty })
+            }
+            Some(gix_diff::blob::unified_diff::submodule_hunks(
+                subproject(&old, false),
+                subproject(&new, is_dirty == Some(true)),
+                context_size,
+            ))
+        } else {
+            self.diff(resource_cache)
+                .map_err(Box::new)?
+                .unified_diff(context_size)?
+        };
+
+        fn to_side<'b>((path, mode, id): &'b (&'b BStr, gix_object::tree::EntryMode, gix_hash::ObjectId)) -> Side<'b> {
+            Side { path, mode: *mode, id }
+        }
+        let same_content = matches!((old, new), (Some((_, _, old_id)), Some((_, _, new_id))) if old_id == new_id);
+        let content = match &hunks {
+            Some(hunks) if hunks.is_empty() => Content::None,
+            Some(_) => Content::Hunks,
+            None if same_content => Content::None,
+            None if binary => Content::BinaryPatch,
+            None => Content::Binary,
+        };
+        Header {
+            old: old.as_ref().map(to_side),
+            new: new.as_ref().map(to_side),
+            rewrite,
+            hex_len: match content {
+                Content::BinaryPatch => repo.object_hash().len_in_hex(),
+                _ => repo.config.hex_len.unwrap_or(7),
+            },
+        }
+        .write_to(&mut out, content)?;
+        match (hunks, content) {
+            (Some(hunks), _) => out.write_all(&hunks)?,
+            (None, Content::BinaryPatch) => {
+                let data = |side: Option<(&BStr, gix_object::tree::EntryMode, gix_hash::ObjectId)>| {
+                    side.map_or(Ok(Vec::new()), |(_, _, id)| {
+                        repo.find_object(id).map(|mut object| std::mem::take(&mut object.data))
+                    })
+                };
+                gix_diff::blob::binary_patch::write_to(&data(old)?, &data(new)?, &mut out)?;
+            }
+            (None, _) => {}
+        }
+        Ok(())
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod stat {
+    /// The error returned by [`Change::stat()`](super::super::Change::stat()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        SetResource(#[from] Box<crate::object::blob::diff::init::Error>),
+        #[error(transparent)]
+        PrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
+        #[error(transparent)]
+        FindHeader(#[from] crate::object::find::existing::Error),
+    }
+}
+
+impl<'a, 'old, 'new> super::Change<'a, 'old, 'new> {
+    /// Count the lines this change inserted and removed, just like `git diff --numstat` does, or return `None` if this
+    /// is a change to a tree.
+    /// Use `resource_cache` to store the diffable data and possibly reuse previously stored data, see [`diff()`](Self::diff())
+    /// for details, along with the whitespace changes to ignore.
+    ///
+    /// Submodules count as a single line per commit, just like in patches, while binary files are represented by
+    /// the sizes of their objects.
+    pub fn stat(
+        &self,
+        resource_cache: &mut gix_diff::blob::Platform,
+    ) -> Result<Option<gix_diff::stat::File>, stat::Error> {
+        use gix_diff::stat::{File, Lines};
+
+        let (previous, current, source_location) = match self.event {
+            Event::Addition { entry_mode, id } => (None, Some((entry_mode, id)), None),
+            Event::Deletion { entry_mode, id } => (Some((entry_mode, id)), None, None),
+            Event::Modification {
+                previous_entry_mode,
+                previous_id,
+                entry_mode,
+                id,
+            } => (Some((previous_entry_mode, previous_id)), Some((entry_mode, id)), None),
+            Event::Rewrite {
+                source_location,
+                source_entry_mode,
+                source_id,
+                entry_mode,
+                id,
+                ..
+            } => (
+                Some((source_entry_mode, source_id)),
+                Some((entry_mode, id)),
+                Some(source_location.to_owned()),
+            ),
+        };
+        if previous.into_iter().chain(current).all(|(mode, _)| mode.is_tree()) {
+            return Ok(None);
+        }
+
+        let is_commit =
+            |side: Option<(gix_object::tree::EntryMode, Id<'_>)>| matches!(side, Some((mode, _)) if mode.is_commit());
+        let lines = if is_commit(previous) || is_commit(current) {
+            Lines::Text {
+                insertions: is_commit(current).into(),
+                removals: is_commit(previous).into(),
+            }
+        } else {
+            match self.diff(resource_cache).map_err(Box::new)?.line_counts()? {
+                Some(counts) => Lines::Text {
+                    insertions: counts.insertions,
+                    removals: counts.removals,
+                },
+                None => {
+                    let size = |side: Option<(gix_object::tree::EntryMode, Id<'_>)>| {
+                        side.map_or(Ok(0), |(_, id)| id.header().map(|header| header.size()))
+                    };
+                    Lines::Binary {
+                        old_size: size(previous)?,
+                        new_size: size(current)?,
+                    }
+                }
+            }
+        };
+        Ok(Some(File {
+            source_location,
+            location: self.location.to_owned(),
+            lines,
+        }))
+    }
+}
+
 impl<'a, 'old, 'new> Event<'a, 'old, 'new> {
     /// Return the current mode of this instance.
     pub fn entry_mode(&self) -> gix_object::tree::EntryMode {
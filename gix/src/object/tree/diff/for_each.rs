@@ -21,6 +21,10 @@ pub enum Error {
     ResourceCache(#[from] crate::repository::diff::resource_cache::Error),
     #[error("Failure during rename tracking")]
     RenameTracking(#[from] tracker::emit::Error),
+    #[error("Could not obtain a blob for the pickaxe search")]
+    PickaxeSetResource(#[from] crate::object::blob::diff::init::Error),
+    #[error("Could not prepare the diff for the pickaxe search")]
+    PickaxePrepareDiff(#[from] gix_diff::blob::platform::prepare_diff::Error),
 }
 
 ///
@@ -79,40 +83,90 @@ impl<'a, 'old> Platform<'a, 'old> {
         E: Into<Box<dyn std::error::Error + Sync + Send + 'static>>,
     {
         let repo = self.lhs.repo;
+        let mut pickaxe = match self.pickaxe.clone() {
+            Some(filter) => Some((
+                filter,
+                repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?,
+            )),
+            None => None,
+        };
+        let mut pickaxe_err = None;
+        let mut for_each = for_each;
+        let visit = |change: Change<'_, 'old, 'new>| -> Result<Action, E> {
+            if let Some((filter, cache)) = pickaxe.as_mut() {
+                match pickaxe_matches(&change, filter, cache) {
+                    Ok(true) => {}
+                    Ok(false) => return Ok(Action::Continue),
+                    Err(err) => {
+                        pickaxe_err = Some(err);
+                        return Ok(Action::Cancel);
+                    }
+                }
+            }
+            for_each(change)
+        };
         let mut delegate = Delegate {
             src_tree: self.lhs,
             other_repo: other.repo,
             recorder: gix_diff::tree::Recorder::default().track_location(self.tracking),
-            visit: for_each,
+            visit,
             location: self.tracking,
             tracked: self.rewrites.map(rewrites::Tracker::new),
             err: None,
         };
-        match gix_diff::tree::Changes::from(TreeRefIter::from_bytes(&self.lhs.data)).needed_to_obtain(
+        let res = match gix_diff::tree::Changes::from(TreeRefIter::from_bytes(&self.lhs.data)).needed_to_obtain(
             TreeRefIter::from_bytes(&other.data),
             &mut self.state,
             &repo.objects,
             &mut delegate,
         ) {
-            Ok(()) => {
-                let outcome = Outcome {
-                    rewrites: delegate.process_tracked_changes(resource_cache)?,
-                };
-                match delegate.err {
+            Ok(()) => match delegate.process_tracked_changes(resource_cache, self.ignore_whitespace) {
+                Ok(rewrites) => match delegate.err {
                     Some(err) => Err(Error::ForEach(err.into())),
-                    None => Ok(outcome),
-                }
-            }
+                    None => Ok(Outcome { rewrites }),
+                },
+                Err(err) => Err(err),
+            },
             Err(gix_diff::tree::changes::Error::Cancelled) => delegate
                 .err
                 .map_or(Err(Error::Diff(gix_diff::tree::changes::Error::Cancelled)), |err| {
                     Err(Error::ForEach(err.into()))
                 }),
             Err(err) => Err(err.into()),
+        };
+        match pickaxe_err {
+            Some(err) => Err(err),
+            None => res,
         }
     }
 }
 
+/// Return `true` if `change` is interesting according to `filter`, which is never the case for changes that involve
+/// anything but blobs and symlinks.
+fn pickaxe_matches(
+    change: &Change<'_, '_, '_>,
+    filter: &gix_diff::blob::pickaxe::Filter,
+    resource_cache: &mut gix_diff::blob::Platform,
+) -> Result<bool, Error> {
+    let previous_entry_mode = match change.event {
+        change::Event::Modification {
+            previous_entry_mode, ..
+        } => Some(previous_entry_mode),
+        change::Event::Rewrite { source_entry_mode, .. } => Some(source_entry_mode),
+        change::Event::Addition { .. } | change::Event::Deletion { .. } => None,
+    };
+    if !previous_entry_mode
+        .into_iter()
+        .chain(Some(change.event.entry_mode()))
+        .all(|mode| mode.is_blob_or_symlink())
+    {
+        return Ok(false);
+    }
+    let matches = change.diff(resource_cache)?.pickaxe(filter)?;
+    resource_cache.clear_resource_cache();
+    Ok(matches)
+}
+
 struct Delegate<'a, 'old, 'new, VisitFn, E> {
     src_tree: &'a Tree<'old>,
     other_repo: &'new Repository,
@@ -172,6 +226,7 @@ where
     fn process_tracked_changes(
         &mut self,
         diff_cache: Option<&mut gix_diff::blob::Platform>,
+        ignore_whitespace: Option<gix_diff::blob::IgnoreWhitespace>,
     ) -> Result<Option<rewrites::Outcome>, Error> {
         let tracked = match self.tracked.as_mut() {
             Some(t) => t,
@@ -187,6 +242,9 @@ where
                 &mut storage
             }
         };
+        if let Some(ignore) = ignore_whitespace {
+            diff_cache.options.ignore_whitespace = ignore;
+        }
 
         let outcome = tracked.emit(
             |dest, source| match source {
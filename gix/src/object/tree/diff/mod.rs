@@ -46,6 +46,8 @@ impl<'repo> Tree<'repo> {
             lhs: self,
             tracking: None,
             rewrites: self.repo.config.diff_renames()?.unwrap_or_default().into(),
+            ignore_whitespace: None,
+            pickaxe: None,
         })
     }
 }
@@ -57,6 +59,8 @@ pub struct Platform<'a, 'repo> {
     lhs: &'a Tree<'repo>,
     tracking: Option<Location>,
     rewrites: Option<Rewrites>,
+    ignore_whitespace: Option<gix_diff::blob::IgnoreWhitespace>,
+    pickaxe: Option<gix_diff::blob::pickaxe::Filter>,
 }
 
 /// Configuration
@@ -84,8 +88,34 @@ impl<'a, 'repo> Platform<'a, 'repo> {
         self.rewrites = renames;
         self
     }
+
+    /// Ignore changes in whitespace as configured by `ignore` when computing the similarity of files to track rewrites.
+    ///
+    /// This overrides the [configuration](gix_diff::blob::platform::Options::ignore_whitespace) of the resource cache used
+    /// for rewrite tracking, which also affects later diffs performed with a cache passed to
+    /// [`for_each_to_obtain_tree_with_cache()`](Self::for_each_to_obtain_tree_with_cache()).
+    pub fn ignore_whitespace(&mut self, ignore: gix_diff::blob::IgnoreWhitespace) -> &mut Self {
+        self.ignore_whitespace = Some(ignore);
+        self
+    }
+
+    /// Only pass changes to blobs and symlinks to the callback that are interesting according to `filter`, like
+    /// `git log -S` or `git log -G` would, or pass `None` to see all changes, which is the default.
+    ///
+    /// Changes to binary files never match, and renames and copies are matched by the change between their source
+    /// and their destination.
+    /// Note that the blobs are obtained with a resource cache of their own that converts them with
+    /// [`gix_diff::blob::pipeline::Mode::ToGit`].
+    pub fn pickaxe(&mut self, filter: Option<gix_diff::blob::pickaxe::Filter>) -> &mut Self {
+        self.pickaxe = filter;
+        self
+    }
 }
 
 ///
 #[allow(clippy::empty_docs)]
 pub mod for_each;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod stats;
@@ -0,0 +1,46 @@
+use gix_diff::tree::recorder::Location;
+
+use super::{Action, Platform};
+use crate::Tree;
+
+/// The error returned by [`Platform::stats()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    ResourceCache(#[from] crate::repository::diff::resource_cache::Error),
+    #[error(transparent)]
+    ForEach(#[from] super::for_each::Error),
+}
+
+impl<'a, 'old> Platform<'a, 'old> {
+    /// Count the lines inserted and removed in each file that changed between the source of the diff and `other`, to
+    /// produce the data for `git diff --numstat` and `git diff --stat`, with files sorted by their location.
+    ///
+    /// Paths are always tracked for this, and the blobs are obtained with a resource cache that converts them with
+    /// [`gix_diff::blob::pipeline::Mode::ToGit`], ignoring whitespace as [configured](Self::ignore_whitespace()).
+    /// See [`Change::stat()`](super::Change::stat()) for details on how changes are counted.
+    pub fn stats(&mut self, other: &Tree<'_>) -> Result<gix_diff::stat::Stats, Error> {
+        let mut resource_cache = self
+            .lhs
+            .repo
+            .diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        if let Some(ignore) = self.ignore_whitespace {
+            resource_cache.options.ignore_whitespace = ignore;
+        }
+        let tracking = self.tracking.replace(Location::Path);
+        let mut stats = gix_diff::stat::Stats::default();
+        let res = self.for_each_to_obtain_tree(other, |change| -> Result<_, super::change::stat::Error> {
+            if let Some(file) = change.stat(&mut resource_cache)? {
+                stats.files.push(file);
+            }
+            resource_cache.clear_resource_cache();
+            Ok(Action::Continue)
+        });
+        self.tracking = tracking;
+        res?;
+        // Rewrites are emitted last, but `git` shows them at their destination.
+        stats.files.sort_by(|a, b| a.location.cmp(&b.location));
+        Ok(stats)
+    }
+}
@@ -227,6 +227,9 @@ impl ThreadSafeRepository {
                 None => crate::RefStore::at(git_dir.to_owned(), reflog, object_hash, repo_config.precompose_unicode),
             }
         };
+        if repo_config.use_reftable {
+            refs.use_reftable();
+        }
         let head = refs.find("HEAD").ok();
         let git_install_dir = crate::path::install_dir().ok();
         let home = gix_path::env::home_dir().and_then(|home| env.home.check_opt(home));
@@ -328,6 +331,7 @@ impl ThreadSafeRepository {
                     object_hash: config.object_hash,
                     use_multi_pack_index: config.use_multi_pack_index,
                     current_dir: current_dir.to_owned().into(),
+                    pack_windows: config.pack_windows,
                 },
             )?),
             common_dir,
@@ -19,3 +19,15 @@ pub enum Default {
     /// Push *all* branches to their similarly named counterpart on the remote.
     Matching,
 }
+
+/// All possible values of `push.gpgSign`.
+#[derive(Default, Copy, Clone, PartialOrd, PartialEq, Ord, Eq, Hash, Debug)]
+pub enum GpgSign {
+    /// Never sign pushes.
+    #[default]
+    No,
+    /// Sign pushes if the server supports it, and push without signature otherwise.
+    IfAsked,
+    /// Sign pushes, and fail if the server doesn't support it.
+    Yes,
+}
@@ -21,6 +21,32 @@ pub mod edit {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod expire_reflog {
+    use crate::config;
+
+    /// The error returned by [`Reference::expire_reflog()`](crate::Reference::expire_reflog()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ExpiryConfiguration(#[from] config::time::Error),
+        #[error("Could not interpret core.filesRefLockTimeout or core.packedRefsTimeout, it must be the number in milliseconds to wait for locks or negative to wait forever")]
+        LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
+        #[error(transparent)]
+        PeelToId(#[from] super::peel::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        WalkSetup(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        Walk(#[from] gix_traverse::commit::simple::Error),
+        #[error(transparent)]
+        Expire(#[from] gix_ref::file::log::expire::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod peel {
@@ -78,6 +104,46 @@ pub mod head_tree_id {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod head_detach {
+    /// The error returned by [`Repository::head_detach(…)`](crate::Repository::head_detach()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Head(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod head_attach {
+    /// The error returned by [`Repository::head_attach(…)`](crate::Repository::head_attach()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        NameValidation(#[from] gix_validate::reference::name::Error),
+        #[error("HEAD can only be attached to local branches, but got {name:?}")]
+        NotABranch { name: gix_ref::FullName },
+        #[error("The branch {name:?} to attach HEAD to doesn't exist")]
+        BranchNotFound { name: gix_ref::FullName },
+        #[error(transparent)]
+        FindBranch(#[from] crate::reference::find::Error),
+        #[error(transparent)]
+        Head(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        Edit(#[from] crate::reference::edit::Error),
+    }
+}
+
 ///
 #[allow(clippy::empty_docs)]
 pub mod find {
@@ -5,6 +5,9 @@ use gix_ref::file::ReferenceExt;
 
 use crate::{
     bstr::{BStr, BString, ByteVec},
+    config,
+    config::{cache::util::ApplyLeniency, tree::Key},
+    reference::expire_reflog,
     Reference,
 };
 
@@ -18,6 +21,46 @@ impl<'repo> Reference<'repo> {
     pub fn log_exists(&self) -> bool {
         self.inner.log_exists(&self.repo.refs)
     }
+
+    /// Remove all entries from the reflog of this reference that are older than `gc.reflogExpire` (90 days by default),
+    /// along with entries older than `gc.reflogExpireUnreachable` (30 days by default) whose commits aren't reachable
+    /// from the commit this reference currently points to.
+    ///
+    /// Note that unlike `git reflog expire`, reachability is only determined from this reference, even for `HEAD`.
+    pub fn expire_reflog(&self) -> Result<gix_ref::file::log::expire::Outcome, expire_reflog::Error> {
+        let now = std::time::SystemTime::now();
+        let config = &self.repo.config;
+        let expiry_date = |key: &'static config::tree::gc::ReflogExpire, default: &'static str| {
+            let value = config
+                .resolved
+                .string(key.section().name(), None, key.name())
+                .unwrap_or_else(|| BString::from(default).into());
+            key.try_into_expiry_date(value, now)
+                .with_leniency(config.lenient_config)
+        };
+        let expire = expiry_date(&config::tree::Gc::REFLOG_EXPIRE, "90 days")?;
+        let expire_unreachable = expiry_date(&config::tree::Gc::REFLOG_EXPIRE_UNREACHABLE, "30 days")?;
+
+        let mut reachable = gix_hashtable::HashSet::default();
+        if expire_unreachable.is_some() {
+            let tip = self.clone().into_fully_peeled_id()?;
+            if tip.object()?.kind == gix_object::Kind::Commit {
+                for info in crate::revision::walk::Platform::new(Some(tip.detach()), self.repo).all()? {
+                    reachable.insert(info?.id);
+                }
+            }
+        }
+        let (lock_fail_mode, _) = config.lock_timeout()?;
+        Ok(self.repo.refs.expire_reflog(
+            self.name(),
+            gix_ref::file::log::expire::Options {
+                expire,
+                expire_unreachable,
+                lock_fail_mode,
+            },
+            &mut |id| reachable.contains(id),
+        )?)
+    }
 }
 
 /// Generate a message typical for git commit logs based on the given `operation`, commit `message` and `num_parents` of the commit.
@@ -11,12 +11,14 @@ pub mod iter;
 pub mod remote;
 
 mod errors;
-pub use errors::{edit, find, head_commit, head_id, head_tree_id, peel};
+pub use errors::{edit, expire_reflog, find, head_attach, head_commit, head_detach, head_id, head_tree_id, peel};
 
 use crate::ext::ObjectIdExt;
 
 pub mod log;
 
+pub mod transaction;
+
 pub use gix_ref::{Category, Kind};
 
 /// Access
@@ -90,6 +92,17 @@ impl<'repo> Reference<'repo> {
             })
         })
     }
+
+    /// Follow this reference through all symbolic references it might point to, up to `max_depth` levels of indirection,
+    /// and return every reference visited on the way, starting with this one.
+    ///
+    /// A reference missing at the end of the chain, like an unborn branch, is recorded in the outcome instead of failing.
+    pub fn follow_chain(
+        &self,
+        max_depth: usize,
+    ) -> Result<gix_ref::file::follow_chain::Outcome, gix_ref::file::follow_chain::Error> {
+        self.inner.follow_chain(&self.repo.refs, max_depth)
+    }
 }
 
 mod edits;
@@ -0,0 +1,37 @@
+//!
+#![allow(clippy::empty_docs)]
+use gix_ref::transaction::RefEdit;
+
+use crate::reference;
+
+/// A set of reference edits whose references are all locked and whose expected previous values were verified,
+/// as obtained by [`Repository::prepare_reference_edits()`](crate::Repository::prepare_reference_edits()).
+///
+/// It must be [committed](Prepared::commit()) to apply the edits, or [rolled back](Prepared::rollback()) to release
+/// all locks without changing any reference, which also happens when it's dropped.
+pub struct Prepared<'repo> {
+    pub(crate) inner: gix_ref::file::Transaction<'repo, 'repo>,
+    pub(crate) repo: &'repo crate::Repository,
+}
+
+impl<'repo> Prepared<'repo> {
+    /// Return the edits to be performed, which have been split as needed and know about the current state of
+    /// their references on disk, so they can be inspected before committing.
+    pub fn edits(&self) -> impl Iterator<Item = &RefEdit> + '_ {
+        self.inner.prepared_edits()
+    }
+
+    /// Apply all edits and write reference logs as the currently configured committer.
+    ///
+    /// Returns all performed edits, whose previous values are the ones seen in storage after the references were locked.
+    pub fn commit(self) -> Result<Vec<RefEdit>, reference::edit::Error> {
+        self.inner
+            .commit(self.repo.committer().transpose()?)
+            .map_err(Into::into)
+    }
+
+    /// Release all locks without changing any reference and return the edits as they were prepared.
+    pub fn rollback(self) -> Vec<RefEdit> {
+        self.inner.rollback()
+    }
+}
@@ -27,22 +27,66 @@ impl<'repo> Remote<'repo> {
         self.fetch_tags
     }
 
+    /// Return `true` if remote-tracking references that don't exist on the remote anymore are deleted when fetching.
+    ///
+    /// It's configured by `remote.<name>.prune` or `fetch.prune`.
+    pub fn prune(&self) -> bool {
+        self.prune
+    }
+
+    /// Return `true` if local tags that don't exist on the remote anymore are deleted when fetching with [pruning](Self::prune())
+    /// enabled, which also fetches all tags.
+    ///
+    /// It's configured by `remote.<name>.pruneTags` or `fetch.pruneTags`.
+    pub fn prune_tags(&self) -> bool {
+        self.prune_tags
+    }
+
+    /// Return how tags are actually handled when fetching, as pruning tags implies fetching all of them.
+    #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+    pub(crate) fn effective_fetch_tags(&self) -> remote::fetch::Tags {
+        if self.prune && self.prune_tags {
+            remote::fetch::Tags::All
+        } else {
+            self.fetch_tags
+        }
+    }
+
     /// Return the url used for the given `direction` with rewrites from `url.<base>.insteadOf|pushInsteadOf`, unless the instance
     /// was created with one of the `_without_url_rewrite()` methods.
     /// For pushing, this is the `remote.<name>.pushUrl` or the `remote.<name>.url` used for fetching, and for fetching it's
     /// the `remote.<name>.url`.
+    /// If there is no push url, `pushInsteadOf` rules are applied to the fetch url to obtain the push url, and if none matches,
+    /// the push url is the same as the fetch url.
     /// Note that it's possible to only have the push url set, in which case there will be no way to fetch from the remote as
     /// the push-url isn't used for that.
     pub fn url(&self, direction: remote::Direction) -> Option<&gix_url::Url> {
         match direction {
-            remote::Direction::Fetch => self.url_alias.as_ref().or(self.url.as_ref()),
+            remote::Direction::Fetch => self.url_alias.as_ref().map(|(url, _)| url).or(self.url.as_ref()),
             remote::Direction::Push => self
                 .push_url_alias
                 .as_ref()
+                .map(|(url, _)| url)
                 .or(self.push_url.as_ref())
                 .or_else(|| self.url(remote::Direction::Fetch)),
         }
     }
+
+    /// Return the rewrite from `url.<base>.insteadOf|pushInsteadOf` that was applied to obtain the [url](Self::url())
+    /// for the given `direction`, or `None` if it wasn't rewritten.
+    ///
+    /// Note that the push url may also be rewritten by an `insteadOf` rule, as it's the fetch url if no push url is set
+    /// and no `pushInsteadOf` rule matched it.
+    pub fn url_replacement(&self, direction: remote::Direction) -> Option<&remote::url::Replacement> {
+        match direction {
+            remote::Direction::Fetch => self.url_alias.as_ref().map(|(_, replacement)| replacement),
+            remote::Direction::Push => match &self.push_url_alias {
+                Some((_, replacement)) => Some(replacement),
+                None if self.push_url.is_some() => None,
+                None => self.url_replacement(remote::Direction::Fetch),
+            },
+        }
+    }
 }
 
 /// Modification
@@ -59,14 +103,17 @@ impl Remote<'_> {
             }
             Err(err) => err.into(),
         };
-        let push_url_err =
-            match remote::init::rewrite_url(&self.repo.config, self.push_url.as_ref(), remote::Direction::Push) {
-                Ok(url) => {
-                    self.push_url_alias = url;
-                    None
-                }
-                Err(err) => err.into(),
-            };
+        let push_url_err = match remote::init::rewrite_url(
+            &self.repo.config,
+            self.push_url.as_ref().or(self.url.as_ref()),
+            remote::Direction::Push,
+        ) {
+            Ok(url) => {
+                self.push_url_alias = url;
+                None
+            }
+            Err(err) => err.into(),
+        };
         url_err.or(push_url_err).map(Err::<&mut Self, _>).transpose()?;
         Ok(self)
     }
@@ -33,6 +33,20 @@ impl Remote<'_> {
         self
     }
 
+    /// If `toggle` is `true`, delete remote-tracking references that don't exist on the remote anymore when fetching,
+    /// like `git fetch --prune`.
+    pub fn with_prune(mut self, toggle: bool) -> Self {
+        self.prune = toggle;
+        self
+    }
+
+    /// If `toggle` is `true`, also fetch all tags and delete local tags that don't exist on the remote anymore when
+    /// [pruning](Self::with_prune()), like `git fetch --prune --prune-tags`.
+    pub fn with_prune_tags(mut self, toggle: bool) -> Self {
+        self.prune_tags = toggle;
+        self
+    }
+
     fn push_url_inner(
         mut self,
         push_url: gix_url::Url,
@@ -15,6 +15,8 @@ mod error {
     pub enum Error {
         #[error("Could not obtain options for connecting via ssh")]
         SshOptions(#[from] config::ssh_connect_options::Error),
+        #[error("Could not obtain the read timeout for the connection")]
+        ReadTimeout(#[from] config::duration::Error),
         #[error("Could not obtain the current directory")]
         CurrentDir(#[from] std::io::Error),
         #[error("Could not access remote repository at \"{}\"", directory.display())]
@@ -91,9 +93,11 @@ impl<'repo> Remote<'repo> {
                 version,
                 #[cfg(feature = "blocking-network-client")]
                 ssh: scheme_is_ssh
-                    .then(|| self.repo.ssh_connect_options())
+                    .then(|| self.ssh_connect_options())
                     .transpose()?
                     .unwrap_or_default(),
+                #[cfg(feature = "blocking-network-client")]
+                read_timeout: self.read_timeout()?,
                 trace: self.repo.config.trace_packet(),
             },
         )
@@ -101,6 +105,50 @@ impl<'repo> Remote<'repo> {
         Ok(self.to_connection_with_transport(transport))
     }
 
+    /// Obtain options for use when connecting to this remote via `ssh`, which are the
+    /// [options of the repository](crate::Repository::ssh_connect_options()) unless `remote.<name>.sshCommand` is set,
+    /// in which case this command is used instead.
+    #[cfg(feature = "blocking-network-client")]
+    pub fn ssh_connect_options(
+        &self,
+    ) -> Result<gix_protocol::transport::client::ssh::connect::Options, crate::config::ssh_connect_options::Error> {
+        let mut opts = self.repo.ssh_connect_options()?;
+        let remote_command = self.name().and_then(|name| {
+            self.repo.config.resolved.string_filter(
+                "remote",
+                Some(name.as_bstr()),
+                crate::config::tree::Remote::SSH_COMMAND.name,
+                &mut self.repo.filter_config_section(),
+            )
+        });
+        if let Some(command) = remote_command {
+            opts.command = Some(gix_path::from_bstr(command).into_owned().into());
+            opts.disallow_shell = false;
+            opts.native = None;
+        }
+        Ok(opts)
+    }
+
+    /// Return the read timeout configured in `gitoxide.readTimeout`, if any.
+    #[cfg(feature = "blocking-network-client")]
+    fn read_timeout(&self) -> Result<Option<std::time::Duration>, Error> {
+        use crate::config::{
+            cache::util::ApplyLeniency,
+            tree::{Gitoxide, Key},
+        };
+
+        let key = "gitoxide.readTimeout";
+        debug_assert_eq!(key, Gitoxide::READ_TIMEOUT.logical_name());
+        Ok(self
+            .repo
+            .config
+            .resolved
+            .integer_filter_by_key(key, &mut self.repo.filter_config_section())
+            .map(|value| Gitoxide::READ_TIMEOUT.try_into_duration(value))
+            .transpose()
+            .with_leniency(self.repo.config.lenient_config)?)
+    }
+
     /// Produce the sanitized URL and protocol version to use as obtained by querying the repository configuration.
     ///
     /// This can be useful when using custom transports to allow additional configuration.
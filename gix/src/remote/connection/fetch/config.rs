@@ -1,6 +1,11 @@
 use super::Error;
 use crate::{
-    config::{cache::util::ApplyLeniency, tree::Pack},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Feature, Fetch, Key, Pack, Remote},
+    },
+    remote,
+    remote::fetch::negotiate::Algorithm,
     Repository,
 };
 
@@ -14,13 +19,92 @@ pub fn index_threads(repo: &Repository) -> Result<Option<usize>, Error> {
         .with_leniency(repo.options.lenient_config)?)
 }
 
-pub fn pack_index_version(repo: &Repository) -> Result<gix_pack::index::Version, Error> {
+/// Return the index version configured in `pack.indexVersion`, or `None` to select it automatically.
+pub fn pack_index_version(repo: &Repository) -> Result<Option<gix_pack::index::Version>, Error> {
     Ok(repo
         .config
         .resolved
         .integer("pack", None, Pack::INDEX_VERSION.name)
         .map(|value| Pack::INDEX_VERSION.try_into_index_version(value))
         .transpose()
-        .with_leniency(repo.options.lenient_config)?
-        .unwrap_or(gix_pack::index::Version::V2))
+        .with_leniency(repo.options.lenient_config)?)
+}
+
+/// Return the negotiation algorithm configured in `fetch.negotiationAlgorithm`, which like in `git` defaults to `skipping`
+/// if `feature.experimental` is enabled, and to `consecutive` otherwise.
+pub fn negotiation_algorithm(repo: &Repository) -> Result<Algorithm, Error> {
+    let config = &repo.config.resolved;
+    let algorithm = config
+        .string_by_key(Fetch::NEGOTIATION_ALGORITHM.logical_name().as_str())
+        .filter(|name| name.as_ref() != "default")
+        .map(|name| Fetch::NEGOTIATION_ALGORITHM.try_into_negotiation_algorithm(name))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)?;
+    if let Some(algorithm) = algorithm {
+        return Ok(algorithm);
+    }
+    let experimental = config
+        .boolean_by_key(Feature::EXPERIMENTAL.logical_name().as_str())
+        .map(|value| Feature::EXPERIMENTAL.enrich_error(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::ExperimentalFeatureConfig)?
+        .unwrap_or_default();
+    Ok(if experimental {
+        Algorithm::Skipping
+    } else {
+        Algorithm::Consecutive
+    })
+}
+
+/// Return `true` if `FETCH_HEAD` should be written after fetching, as configured by `fetch.writeFetchHead` which defaults to `true`.
+pub fn write_fetch_head(repo: &Repository) -> Result<bool, Error> {
+    Ok(repo
+        .config
+        .resolved
+        .boolean_filter_by_key(
+            Fetch::WRITE_FETCH_HEAD.logical_name().as_str(),
+            &mut repo.filter_config_section(),
+        )
+        .map(|value| Fetch::WRITE_FETCH_HEAD.enrich_error(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::WriteFetchHeadConfig)?
+        .unwrap_or(true))
+}
+
+/// Return the filter configured for the remote with `name` if it is a promisor remote, as set up by partial clones.
+pub fn partial_clone_filter(
+    repo: &Repository,
+    name: Option<&remote::Name<'_>>,
+) -> Result<Option<gix_traverse::objects::Filter>, Error> {
+    let Some(name) = name.map(remote::Name::as_bstr) else {
+        return Ok(None);
+    };
+    let config = &repo.config.resolved;
+    let is_promisor = config
+        .boolean_filter(
+            "remote",
+            Some(name),
+            Remote::PROMISOR.name,
+            &mut repo.filter_config_section(),
+        )
+        .map(|value| Remote::PROMISOR.enrich_error(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)
+        .map_err(Error::PromisorConfig)?
+        .unwrap_or_default();
+    if !is_promisor {
+        return Ok(None);
+    }
+    Ok(config
+        .string_filter(
+            "remote",
+            Some(name),
+            Remote::PARTIAL_CLONE_FILTER.name,
+            &mut repo.filter_config_section(),
+        )
+        .map(|value| Remote::PARTIAL_CLONE_FILTER.try_into_filter(value))
+        .transpose()
+        .with_leniency(repo.options.lenient_config)?)
 }
@@ -45,6 +45,23 @@ pub enum Error {
     RejectShallowRemote,
     #[error(transparent)]
     NegotiationAlgorithmConfig(#[from] config::key::GenericErrorWithValue),
+    #[error("Could not obtain configuration to learn if experimental features are enabled")]
+    ExperimentalFeatureConfig(#[source] config::boolean::Error),
+    #[error("Could not obtain configuration to learn if the remote is a promisor remote")]
+    PromisorConfig(#[source] config::boolean::Error),
+    #[error(transparent)]
+    PartialCloneFilterConfig(#[from] config::key::GenericErrorWithValue<gix_traverse::objects::filter::Error>),
+    #[error("Could not obtain configuration to learn if FETCH_HEAD should be written")]
+    WriteFetchHeadConfig(#[source] config::boolean::Error),
+    #[error("Could not interpret core.filesRefLockTimeout, it must be the number in milliseconds to wait for locks or negative to wait forever")]
+    LockTimeoutConfiguration(#[from] config::lock_timeout::Error),
+    #[error("Failed to write the FETCH_HEAD file")]
+    WriteFetchHead(#[from] gix_ref::file::pseudo_ref::Error),
+    #[error("Failed to write .promisor file at \"{}\"", path.display())]
+    WritePackPromisorFile {
+        path: std::path::PathBuf,
+        source: std::io::Error,
+    },
 }
 
 impl gix_protocol::transport::IsSpuriousError for Error {
@@ -0,0 +1,123 @@
+use gix_ref::fetch_head::{Entry, Kind, Source};
+
+use super::Error;
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    remote,
+    remote::fetch::{self, refs::update::Mode},
+    Repository,
+};
+
+/// Write the `FETCH_HEAD` file of `repo` to list every object received from `remote` according to `mappings` and their `updates`,
+/// in the format used by `git fetch`.
+///
+/// Like in `git`, the remote references configured to be merged into the current branch are marked for merging and listed first.
+/// Without such configuration, what's fetched by the first of the `refspecs` is marked for merging if it doesn't contain a glob.
+pub(crate) fn write(
+    repo: &Repository,
+    remote: &crate::Remote<'_>,
+    mappings: &[fetch::Mapping],
+    updates: &[fetch::refs::Update],
+    refspecs: &[gix_refspec::RefSpec],
+) -> Result<(), Error> {
+    // Only set if the current branch has merge configuration, and `Some` only if it's for `remote`.
+    let merge_ref = repo.head_name().ok().flatten().and_then(|head| {
+        let merge_ref = repo
+            .branch_remote_ref_name(head.as_ref(), remote::Direction::Fetch)?
+            .ok()?
+            .as_bstr()
+            .to_owned();
+        let is_our_remote = repo
+            .branch_remote_name(head.shorten(), remote::Direction::Fetch)
+            .zip(remote.name())
+            .is_some_and(|(branch_remote, remote)| branch_remote.as_bstr() == remote.as_bstr());
+        Some(is_our_remote.then_some(merge_ref))
+    });
+    let first_spec_is_exact = refspecs
+        .first()
+        .and_then(|spec| spec.to_ref().remote().map(|remote| !remote.contains(&b'*')))
+        .unwrap_or(false);
+    let is_for_merge = |mapping: &fetch::Mapping| match &merge_ref {
+        Some(merge_ref) => merge_ref.is_some() && mapping.remote.as_name() == merge_ref.as_ref().map(AsRef::as_ref),
+        None => first_spec_is_exact && mapping.spec_index == fetch::SpecIndex::ExplicitInRemote(0),
+    };
+    let url = remote
+        .url(remote::Direction::Fetch)
+        .map(display_url)
+        .unwrap_or_default();
+
+    let mut for_merge = Vec::new();
+    let mut not_for_merge = Vec::new();
+    for (mapping, update) in mappings.iter().zip(updates) {
+        if matches!(
+            update.mode,
+            Mode::ImplicitTagNotSentByRemote | Mode::RejectedSourceObjectNotFound { .. }
+        ) {
+            continue;
+        }
+        let Some(id) = mapping.remote.as_id() else {
+            continue;
+        };
+        let not_for_merge_entry = !is_for_merge(mapping);
+        let source = match mapping.remote.as_name() {
+            Some(name) => source(name),
+            None => Some(Source {
+                kind: Kind::Other,
+                name: id.to_string().into(),
+            }),
+        };
+        let entry = Entry {
+            id: id.to_owned(),
+            not_for_merge: not_for_merge_entry,
+            source,
+            url: url.clone(),
+        };
+        if not_for_merge_entry {
+            not_for_merge.push(entry);
+        } else {
+            for_merge.push(entry);
+        }
+    }
+    for_merge.extend(not_for_merge);
+
+    let (lock_fail_mode, _) = repo.config.lock_timeout()?;
+    repo.refs.write_fetch_head(&for_merge, false, lock_fail_mode)?;
+    Ok(())
+}
+
+/// Return the source of the remote reference `name` as recorded by `git`, or `None` for the remote's `HEAD`.
+fn source(name: &BStr) -> Option<Source> {
+    if name == "HEAD" {
+        return None;
+    }
+    let (kind, name) = [
+        ("refs/heads/", Kind::Branch),
+        ("refs/tags/", Kind::Tag),
+        ("refs/remotes/", Kind::RemoteTrackingBranch),
+    ]
+    .iter()
+    .find_map(|(prefix, kind)| name.strip_prefix(prefix.as_bytes()).map(|name| (*kind, name.as_bstr())))
+    .unwrap_or((Kind::Other, name));
+    Some(Source {
+        kind,
+        name: name.to_owned(),
+    })
+}
+
+/// Produce the url as shown in `FETCH_HEAD`, without credentials and without trailing slashes or `.git` suffix.
+fn display_url(url: &gix_url::Url) -> BString {
+    let mut url = url.clone();
+    url.set_password(None);
+    if url.scheme != gix_url::Scheme::Ssh {
+        url.set_user(None);
+    }
+    let mut url = url.to_bstring();
+    while url.last() == Some(&b'/') {
+        url.pop();
+    }
+    if url.ends_with(b".git") {
+        let len = url.len() - ".git".len();
+        url.truncate(len);
+    }
+    url
+}
@@ -195,6 +195,9 @@ where
             reflog_message: None,
             write_packed_refs: WritePackedRefs::Never,
             shallow: Default::default(),
+            filter: None,
+            negotiation_algorithm: None,
+            write_fetch_head: None,
         })
     }
 }
@@ -209,7 +212,8 @@ where
     }
 }
 
-mod config;
+pub(crate) mod config;
+mod fetch_head;
 mod receive_pack;
 ///
 #[path = "update_refs/mod.rs"]
@@ -226,6 +230,9 @@ where
     reflog_message: Option<RefLogMessage>,
     write_packed_refs: WritePackedRefs,
     shallow: remote::fetch::Shallow,
+    filter: Option<gix_traverse::objects::Filter>,
+    negotiation_algorithm: Option<remote::fetch::negotiate::Algorithm>,
+    write_fetch_head: Option<bool>,
 }
 
 /// Builder
@@ -267,6 +274,32 @@ where
         self.shallow = shallow;
         self
     }
+
+    /// Ask the remote to leave out the trees and blobs that don't pass `filter`, turning the received pack into a
+    /// promisor pack of a partial clone.
+    ///
+    /// If unset, the filter configured in `remote.<name>.partialCloneFilter` is used if the remote is a promisor remote
+    /// according to `remote.<name>.promisor`.
+    pub fn with_filter(mut self, filter: impl Into<Option<gix_traverse::objects::Filter>>) -> Self {
+        self.filter = filter.into();
+        self
+    }
+
+    /// Use `algorithm` to negotiate which objects the server should send, instead of the one configured in
+    /// `fetch.negotiationAlgorithm`.
+    pub fn with_negotiation_algorithm(mut self, algorithm: remote::fetch::negotiate::Algorithm) -> Self {
+        self.negotiation_algorithm = Some(algorithm);
+        self
+    }
+
+    /// If `enabled`, write the `FETCH_HEAD` file to list all objects that were fetched, instead of following the value of
+    /// `fetch.writeFetchHead`, which defaults to `true`.
+    ///
+    /// Note that it's never written in [dry-run](Self::with_dry_run()) mode.
+    pub fn with_write_fetch_head(mut self, enabled: bool) -> Self {
+        self.write_fetch_head = Some(enabled);
+        self
+    }
 }
 
 impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
@@ -252,6 +252,46 @@ pub(crate) fn add_wants(
     }
 }
 
+/// Update the remote side of all `mappings` that were requested by name using `want-ref` with the object ids the server
+/// reported in `wanted_refs`, as the remote references may have changed since they were listed.
+pub(crate) fn apply_wanted_refs(
+    mappings: &mut [fetch::Mapping],
+    wanted_refs: &[gix_protocol::fetch::response::WantedRef],
+) {
+    use gix_protocol::handshake::Ref;
+    for wanted in wanted_refs {
+        for mapping in mappings.iter_mut() {
+            let fetch::Source::Ref(remote) = &mut mapping.remote else {
+                continue;
+            };
+            match remote {
+                Ref::Direct { full_ref_name, object } if *full_ref_name == wanted.path => *object = wanted.id,
+                Ref::Peeled { full_ref_name, tag, .. } if *full_ref_name == wanted.path => {
+                    if *tag != wanted.id {
+                        // We don't know what the new tag points to, but only need the tag itself to update references.
+                        *remote = Ref::Direct {
+                            full_ref_name: std::mem::take(full_ref_name),
+                            object: wanted.id,
+                        };
+                    }
+                }
+                Ref::Symbolic {
+                    full_ref_name,
+                    tag,
+                    object,
+                    ..
+                } if *full_ref_name == wanted.path => {
+                    if tag.map_or(*object, |tag| tag) != wanted.id {
+                        *tag = None;
+                        *object = wanted.id;
+                    }
+                }
+                Ref::Direct { .. } | Ref::Peeled { .. } | Ref::Symbolic { .. } | Ref::Unborn { .. } => {}
+            }
+        }
+    }
+}
+
 /// Remove all commits that are more recent than the cut-off, which is the commit time of the oldest common commit we have with the server.
 fn mark_recent_complete_commits(
     queue: &mut Queue,
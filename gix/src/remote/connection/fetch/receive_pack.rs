@@ -10,18 +10,12 @@ use gix_protocol::{
 };
 
 use crate::{
-    config::{
-        cache::util::ApplyLeniency,
-        tree::{Clone, Fetch, Key},
-    },
+    config::tree::Clone,
     remote,
     remote::{
-        connection::fetch::config,
+        connection::fetch::{config, fetch_head},
         fetch,
-        fetch::{
-            negotiate, negotiate::Algorithm, outcome, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage,
-            Shallow, Status,
-        },
+        fetch::{negotiate, outcome, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Shallow, Status},
     },
     Repository,
 };
@@ -36,8 +30,12 @@ where
     ///
     /// ### Negotiation
     ///
-    /// "fetch.negotiationAlgorithm" describes algorithms `git` uses currently, with the default being `consecutive` and `skipping` being
-    /// experimented with. We currently implement something we could call 'naive' which works for now.
+    /// The algorithm to use is configured with `fetch.negotiationAlgorithm`, with `consecutive` being the default unless
+    /// `feature.experimental` is set, which makes `skipping` the default. The latter needs fewer round-trips when there are
+    /// many local commits the server doesn't know, at the cost of possibly receiving more objects than needed.
+    /// `noop` sends no local commits at all, which makes for a single round but may lead to receiving objects we already have.
+    ///
+    /// Use [`with_negotiation_algorithm()`](Self::with_negotiation_algorithm()) to override the configured algorithm.
     ///
     /// ### Pack `.keep` files
     ///
@@ -105,7 +103,7 @@ where
         gix_protocol::fetch::Response::check_required_features(protocol_version, &fetch_features)?;
         let sideband_all = fetch_features.iter().any(|(n, _)| *n == "sideband-all");
         let mut arguments = gix_protocol::fetch::Arguments::new(protocol_version, fetch_features, con.trace);
-        if matches!(con.remote.fetch_tags, crate::remote::fetch::Tags::Included) {
+        if matches!(con.remote.effective_fetch_tags(), crate::remote::fetch::Tags::Included) {
             if !arguments.can_use_include_tag() {
                 return Err(Error::MissingServerFeature {
                     feature: "include-tag",
@@ -117,6 +115,19 @@ where
             arguments.use_include_tag();
         }
         let (shallow_commits, mut shallow_lock) = add_shallow_args(&mut arguments, &self.shallow, repo)?;
+        let filter = match self.filter {
+            Some(filter) => Some(filter),
+            None => config::partial_clone_filter(repo, con.remote.name())?,
+        };
+        if let Some(filter) = filter {
+            if !arguments.can_use_filter() {
+                return Err(Error::MissingServerFeature {
+                    feature: "filter",
+                    description: "partial clones need the server to leave out objects that don't pass the filter",
+                });
+            }
+            arguments.filter(&filter.to_string());
+        }
 
         if self.ref_map.object_hash != repo.object_hash() {
             return Err(Error::IncompatibleObjectHash {
@@ -129,15 +140,11 @@ where
             "negotiate",
             protocol_version = self.ref_map.handshake.server_protocol_version as usize
         );
-        let mut negotiator = repo
-            .config
-            .resolved
-            .string_by_key(Fetch::NEGOTIATION_ALGORITHM.logical_name().as_str())
-            .map(|n| Fetch::NEGOTIATION_ALGORITHM.try_into_negotiation_algorithm(n))
-            .transpose()
-            .with_leniency(repo.config.lenient_config)?
-            .unwrap_or(Algorithm::Consecutive)
-            .into_negotiator();
+        let mut negotiator = match self.negotiation_algorithm {
+            Some(algorithm) => algorithm,
+            None => config::negotiation_algorithm(repo)?,
+        }
+        .into_negotiator();
         let graph_repo = {
             let mut r = repo.clone();
             // assure that checking for unknown server refs doesn't trigger ODB refreshes.
@@ -153,7 +160,7 @@ where
             &mut graph,
             &self.ref_map,
             &self.shallow,
-            negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
+            negotiate::make_refmapping_ignore_predicate(con.remote.effective_fetch_tags(), &self.ref_map),
         )?;
         let mut previous_response = None::<gix_protocol::fetch::Response>;
         let (mut write_pack_bundle, negotiate) = match &action {
@@ -172,8 +179,11 @@ where
                     &self.ref_map,
                     remote_ref_target_known,
                     &self.shallow,
-                    negotiate::make_refmapping_ignore_predicate(con.remote.fetch_tags, &self.ref_map),
+                    negotiate::make_refmapping_ignore_predicate(con.remote.effective_fetch_tags(), &self.ref_map),
                 );
+                if arguments.can_use_wait_for_done() {
+                    arguments.use_wait_for_done();
+                }
                 let mut rounds = Vec::new();
                 let is_stateless =
                     arguments.is_stateless(!con.transport.connection_persists_across_multiple_requests());
@@ -186,10 +196,16 @@ where
                     progress.step();
                     progress.set_name(format!("negotiate (round {})", rounds.len() + 1));
 
+                    // The server tells us it's ready if it would send a pack, but waits for us to be done if we asked it to.
+                    let server_is_ready = previous_response.as_ref().is_some_and(|response| {
+                        response
+                            .acknowledgements()
+                            .contains(&gix_protocol::fetch::response::Acknowledgement::Ready)
+                    });
                     let is_done = match negotiate::one_round(
                         negotiator.deref_mut(),
                         &mut graph,
-                        haves_to_send,
+                        if server_is_ready { 0 } else { haves_to_send },
                         &mut arguments,
                         previous_response.as_ref(),
                         common.as_mut(),
@@ -206,7 +222,8 @@ where
                                 haves_to_send,
                                 previous_response_had_at_least_one_in_common: ack_seen,
                             });
-                            let is_done = haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
+                            let is_done =
+                                server_is_ready || haves_sent != haves_to_send || (seen_ack && in_vain >= 256);
                             haves_to_send = gix_negotiate::window_size(is_stateless, Some(haves_to_send));
                             is_done
                         }
@@ -244,7 +261,17 @@ where
                 drop(negotiate_span);
 
                 let previous_response = previous_response.expect("knowledge of a pack means a response was received");
-                if !previous_response.shallow_updates().is_empty() && shallow_lock.is_none() {
+                negotiate::apply_wanted_refs(&mut self.ref_map.mappings, previous_response.wanted_refs());
+                let shallow_updates: Vec<_> = self
+                    .ref_map
+                    .handshake
+                    .v1_shallow_updates
+                    .iter()
+                    .flatten()
+                    .chain(previous_response.shallow_updates())
+                    .copied()
+                    .collect();
+                if !shallow_updates.is_empty() && shallow_lock.is_none() {
                     let reject_shallow_remote = repo
                         .config
                         .resolved
@@ -298,6 +325,12 @@ where
                     {
                         reader = rd;
                     }
+                    if filter.is_some() {
+                        if let Some(path) = res.data_path.as_ref().map(|path| path.with_extension("promisor")) {
+                            std::fs::write(&path, b"")
+                                .map_err(|err| Error::WritePackPromisorFile { path, source: err })?;
+                        }
+                    }
                     Some(res)
                 } else {
                     None
@@ -311,15 +344,34 @@ where
                 }
 
                 if let Some(shallow_lock) = shallow_lock {
-                    if !previous_response.shallow_updates().is_empty() {
-                        crate::shallow::write(shallow_lock, shallow_commits, previous_response.shallow_updates())?;
+                    if !shallow_updates.is_empty() {
+                        crate::shallow::write(shallow_lock, shallow_commits, &shallow_updates)?;
                     }
                 }
                 (write_pack_bundle, Some(outcome::Negotiate { graph, rounds }))
             }
         };
 
-        let update_refs = refs::update(
+        let prune_specs = if con.remote.prune {
+            let mut specs = con.remote.refspecs(remote::Direction::Fetch).to_vec();
+            if let Some(tag_spec) = con.remote.prune_tags.then(|| fetch::Tags::All.to_refspec()).flatten() {
+                specs.push(tag_spec.to_owned());
+            }
+            specs
+        } else {
+            Vec::new()
+        };
+        let pruned = refs::prune(
+            repo,
+            &self.ref_map.mappings,
+            &self.ref_map.remote_refs,
+            &prune_specs,
+            con.remote.refspecs(remote::Direction::Fetch),
+            &self.ref_map.extra_refspecs,
+            self.dry_run,
+            self.write_packed_refs,
+        )?;
+        let mut update_refs = refs::update(
             repo,
             self.reflog_message
                 .take()
@@ -327,10 +379,25 @@ where
             &self.ref_map.mappings,
             con.remote.refspecs(remote::Direction::Fetch),
             &self.ref_map.extra_refspecs,
-            con.remote.fetch_tags,
+            con.remote.effective_fetch_tags(),
             self.dry_run,
             self.write_packed_refs,
         )?;
+        update_refs.pruned = pruned;
+
+        let write_fetch_head = match self.write_fetch_head {
+            Some(enabled) => enabled,
+            None => config::write_fetch_head(repo)?,
+        };
+        if write_fetch_head && matches!(self.dry_run, fetch::DryRun::No) {
+            fetch_head::write(
+                repo,
+                con.remote,
+                &self.ref_map.mappings,
+                &update_refs.updates,
+                con.remote.refspecs(remote::Direction::Fetch),
+            )?;
+        }
 
         if let Some(bundle) = write_pack_bundle.as_mut() {
             if !update_refs.edits.is_empty() || bundle.index.num_objects == 0 {
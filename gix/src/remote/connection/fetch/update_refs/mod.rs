@@ -1,5 +1,8 @@
 #![allow(clippy::result_large_err)]
-use std::{collections::BTreeMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::PathBuf,
+};
 
 use gix_object::Exists;
 use gix_ref::{
@@ -8,6 +11,7 @@ use gix_ref::{
 };
 
 use crate::{
+    bstr::{BStr, BString, ByteSlice},
     ext::ObjectIdExt,
     remote::{
         fetch,
@@ -338,7 +342,163 @@ pub(crate) fn update(
         fetch::DryRun::Yes => edits,
     };
 
-    Ok(update::Outcome { edits, updates })
+    Ok(update::Outcome {
+        edits,
+        updates,
+        pruned: Vec::new(),
+    })
+}
+
+/// Delete all local references that are the destination of one of `prune_specs`, but whose source doesn't exist in `remote_refs` anymore,
+/// and produce the edits that were performed.
+/// If `dry_run` is true, the edits are produced but not applied.
+///
+/// We will never delete a reference…
+///
+/// * …that is the destination of one of the `mappings`
+/// * …whose source on the remote is excluded by a negative refspec in `refspecs` or `extra_refspecs`
+/// * …that is symbolic, like `refs/remotes/origin/HEAD`
+/// * …that is checked out in a worktree
+///
+/// This is what `git fetch --prune` does before updating references, which avoids conflicts with new references whose names
+/// start like the name of a deleted one.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn prune(
+    repo: &Repository,
+    mappings: &[fetch::Mapping],
+    remote_refs: &[gix_protocol::handshake::Ref],
+    prune_specs: &[gix_refspec::RefSpec],
+    refspecs: &[gix_refspec::RefSpec],
+    extra_refspecs: &[gix_refspec::RefSpec],
+    dry_run: fetch::DryRun,
+    write_packed_refs: fetch::WritePackedRefs,
+) -> Result<Vec<RefEdit>, update::Error> {
+    if prune_specs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let _span = gix_trace::detail!("prune_refs()", specs = prune_specs.len());
+    let mapped_locals: BTreeSet<&BStr> = mappings
+        .iter()
+        .filter_map(|m| m.local.as_ref().map(AsRef::as_ref))
+        .collect();
+    let remote_names: BTreeSet<&BStr> = remote_refs.iter().map(|r| r.unpack().0).collect();
+    let checked_out_branches = worktree_branches(repo)?;
+
+    let mut candidates = Vec::new();
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(update::Error::ReadReference)?;
+        let Some(id) = reference.target().try_id().map(ToOwned::to_owned) else {
+            continue;
+        };
+        let local = reference.name().as_bstr();
+        if mapped_locals.contains(local) || checked_out_branches.contains_key(reference.name()) {
+            continue;
+        }
+        if let Some(remote_name) = prune_specs
+            .iter()
+            .find_map(|spec| remote_name_for_local(spec.to_ref(), local))
+            .filter(|remote_name| !remote_names.contains(remote_name.as_bstr()))
+        {
+            candidates.push((reference.name().to_owned(), id, remote_name));
+        }
+    }
+    if candidates.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // Only prune what the refspecs would actually map if the source still existed, which is how negative refspecs are respected.
+    let null = repo.object_hash().null();
+    let mut is_covered = vec![false; candidates.len()];
+    let group = gix_refspec::MatchGroup::from_fetch_specs(
+        refspecs
+            .iter()
+            .chain(extra_refspecs)
+            .chain(prune_specs)
+            .map(gix_refspec::RefSpec::to_ref),
+    );
+    for mapping in group
+        .match_remotes(
+            candidates
+                .iter()
+                .map(|(_, _, remote_name)| gix_refspec::match_group::Item {
+                    full_ref_name: remote_name.as_bstr(),
+                    target: &null,
+                    object: None,
+                }),
+        )
+        .mappings
+    {
+        if let Some(idx) = mapping.item_index {
+            if mapping.rhs.as_deref() == Some(candidates[idx].0.as_bstr()) {
+                is_covered[idx] = true;
+            }
+        }
+    }
+
+    let edits: Vec<_> = candidates
+        .into_iter()
+        .zip(is_covered)
+        .filter_map(|((name, id, _), is_covered)| is_covered.then_some((name, id)))
+        .map(|(name, id)| RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                log: RefLog::AndReference,
+            },
+            name,
+            deref: false,
+        })
+        .collect();
+    if edits.is_empty() || matches!(dry_run, fetch::DryRun::Yes) {
+        return Ok(edits);
+    }
+
+    let _span = gix_trace::detail!("apply", edits = edits.len());
+    let (file_lock_fail, packed_refs_lock_fail) = repo
+        .config
+        .lock_timeout()
+        .map_err(crate::reference::edit::Error::from)?;
+    Ok(repo
+        .refs
+        .transaction()
+        .packed_refs(match write_packed_refs {
+            fetch::WritePackedRefs::Only => {
+                gix_ref::file::transaction::PackedRefs::DeletionsAndNonSymbolicUpdatesRemoveLooseSourceReference(
+                    Box::new(&repo.objects),
+                )
+            }
+            fetch::WritePackedRefs::Never => gix_ref::file::transaction::PackedRefs::DeletionsOnly,
+        })
+        .prepare(edits, file_lock_fail, packed_refs_lock_fail)
+        .map_err(crate::reference::edit::Error::from)?
+        .commit(
+            repo.committer()
+                .transpose()
+                .map_err(|err| update::Error::EditReferences(crate::reference::edit::Error::ParseCommitterTime(err)))?,
+        )
+        .map_err(crate::reference::edit::Error::from)?)
+}
+
+/// Map the `local` reference name back to the name of its source on the remote according to the fetch `spec`,
+/// if `local` is its destination.
+fn remote_name_for_local(spec: gix_refspec::RefSpecRef<'_>, local: &BStr) -> Option<BString> {
+    let (remote, dst) = (spec.remote()?, spec.local()?);
+    match (dst.find_byte(b'*'), remote.find_byte(b'*')) {
+        (Some(dst_glob), Some(remote_glob)) => {
+            let (dst_prefix, dst_suffix) = (&dst[..dst_glob], &dst[dst_glob + 1..]);
+            if local.len() < dst_prefix.len() + dst_suffix.len()
+                || !local.starts_with(dst_prefix)
+                || !local.ends_with(dst_suffix)
+            {
+                return None;
+            }
+            let mut name = BString::from(&remote[..remote_glob]);
+            name.extend_from_slice(&local[dst_prefix.len()..local.len() - dst_suffix.len()]);
+            name.extend_from_slice(&remote[remote_glob + 1..]);
+            Some(name)
+        }
+        (None, None) => (dst == local && remote.starts_with(b"refs/")).then(|| remote.to_owned()),
+        _ => None,
+    }
 }
 
 /// Figure out if target of `edit` points to a reference that doesn't exist in `repo` and won't exist as it's not in any of `edits`.
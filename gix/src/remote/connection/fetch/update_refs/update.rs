@@ -23,6 +23,12 @@ mod error {
         PeelToId(#[from] crate::reference::peel::Error),
         #[error("Failed to follow a symbolic reference to assure worktree isn't affected")]
         FollowSymref(#[from] gix_ref::file::find::existing::Error),
+        #[error("Failed to list local references to find the ones to prune")]
+        IterReferences(#[from] crate::reference::iter::Error),
+        #[error("Failed to list local references to find the ones to prune")]
+        IterReferencesInit(#[from] crate::reference::iter::init::Error),
+        #[error("Failed to read a local reference while finding the ones to prune")]
+        ReadReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
     }
 }
 
@@ -37,6 +43,11 @@ pub struct Outcome {
     /// Use [`iter_mapping_updates()`][Self::iter_mapping_updates()] to recombine the update information with ref-edits and their
     /// mapping.
     pub updates: Vec<super::Update>,
+    /// The edits that deleted local references as their counterpart on the remote doesn't exist anymore,
+    /// if [pruning](crate::Remote::prune()) is enabled.
+    ///
+    /// These don't correspond to any mapping.
+    pub pruned: Vec<gix_ref::transaction::RefEdit>,
 }
 
 /// Describe the way a ref was updated, with particular focus on how the (peeled) target commit was affected.
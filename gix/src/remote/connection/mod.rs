@@ -29,3 +29,7 @@ pub mod ref_map;
 ///
 #[allow(clippy::empty_docs)]
 pub mod fetch;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod push;
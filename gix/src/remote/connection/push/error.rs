@@ -0,0 +1,40 @@
+/// The error returned by [`send()`](super::Prepare::send()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Cannot perform a meaningful push operation without any configured push ref-specs")]
+    MissingRefSpecs,
+    #[error("The remote reference {name:?} would be updated from more than one source")]
+    MultipleSources { name: crate::bstr::BString },
+    #[error("Failed to list local references to match them against the push ref-specs")]
+    IterReferences(#[from] crate::reference::iter::Error),
+    #[error("Failed to list local references to match them against the push ref-specs")]
+    IterReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error("Failed to read a local reference to match it against the push ref-specs")]
+    ReadReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("Could not find an object to push")]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error("Could not traverse the commits to push")]
+    TraverseCommits(#[from] gix_traverse::commit::simple::Error),
+    #[error("Could not count the objects to push")]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error("Could not write the pack to send")]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error("Could not share the object database for generating the pack")]
+    ShareObjectDatabase(#[source] std::io::Error),
+    #[error("The value of push.gpgSign is invalid")]
+    GpgSign(#[from] crate::config::key::GenericErrorWithValue),
+    #[error("Neither user.signingKey nor the committer are configured to identify the pusher of a signed push")]
+    MissingPusher,
+    #[error(transparent)]
+    Push(#[from] gix_protocol::push::Error),
+}
+
+impl gix_protocol::transport::IsSpuriousError for Error {
+    fn is_spurious(&self) -> bool {
+        match self {
+            Error::Push(err) => err.is_spurious(),
+            _ => false,
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use std::{
+    ffi::OsString,
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    config::tree::{Gpg as GpgSection, Key, User},
+    Repository,
+};
+
+/// The error returned when signing a push certificate with [`Gpg`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not launch {program:?} to sign the push certificate")]
+    Launch { source: std::io::Error, program: OsString },
+    #[error("Could not communicate with {program:?} to sign the push certificate")]
+    Io { source: std::io::Error, program: OsString },
+    #[error("{program:?} failed to sign the push certificate: {stderr}")]
+    Failed { program: OsString, stderr: BString },
+}
+
+/// A signer for push certificates which calls `gpg` to produce a detached and ASCII-armored signature,
+/// just like `git push --signed` does.
+#[derive(Debug, Clone)]
+pub struct Gpg {
+    /// The program to launch, `gpg` by default.
+    pub program: OsString,
+    /// The key to sign with, or `None` to let the program choose based on the `committer`.
+    pub key: Option<BString>,
+    /// The identity to sign as if no `key` is set, like `Name <email>`.
+    pub committer: Option<BString>,
+}
+
+impl Gpg {
+    /// Configure the signer from `gpg.program`, `user.signingKey` and the committer of `repo`.
+    pub fn from_repo(repo: &Repository) -> Self {
+        let config = repo.config_snapshot();
+        Gpg {
+            program: config
+                .trusted_program(GpgSection::PROGRAM.logical_name().as_str())
+                .map_or_else(|| "gpg".into(), std::borrow::Cow::into_owned),
+            key: signing_key(repo),
+            committer: committer(repo),
+        }
+    }
+}
+
+impl gix_protocol::push::Sign for Gpg {
+    fn sign(&mut self, payload: &[u8]) -> Result<BString, gix_protocol::push::certificate::Error> {
+        let program = self.program.clone();
+        let mut cmd = Command::new(&program);
+        let key = self.key.as_ref().or(self.committer.as_ref()).map(|key| key.as_bstr());
+        cmd.args(["--status-fd=2", "-bsau"])
+            .arg(gix_path::from_bstr(key.unwrap_or_default()).as_os_str())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        let mut child = cmd.spawn().map_err(|source| Error::Launch {
+            source,
+            program: program.clone(),
+        })?;
+        child
+            .stdin
+            .take()
+            .expect("configured")
+            .write_all(payload)
+            .map_err(|source| Error::Io {
+                source,
+                program: program.clone(),
+            })?;
+        let out = child.wait_with_output().map_err(|source| Error::Io {
+            source,
+            program: program.clone(),
+        })?;
+        if !out.status.success() || out.stderr.find(b"[GNUPG:] SIG_CREATED ").is_none() {
+            return Err(Error::Failed {
+                program,
+                stderr: out.stderr.trim_end().into(),
+            }
+            .into());
+        }
+        Ok(out.stdout.into())
+    }
+}
+
+/// Return the value of `user.signingKey`, if set.
+pub(crate) fn signing_key(repo: &Repository) -> Option<BString> {
+    repo.config
+        .resolved
+        .string_by_key(User::SIGNING_KEY.logical_name().as_str())
+        .map(std::borrow::Cow::into_owned)
+}
+
+/// Return the committer of `repo` as `Name <email>`, if configured.
+pub(crate) fn committer(repo: &Repository) -> Option<BString> {
+    let committer = repo.committer()?.ok()?;
+    Some(format!("{} <{}>", committer.name, committer.email).into())
+}
@@ -0,0 +1,339 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_features::progress::NestedProgress;
+use gix_protocol::transport::client::Transport;
+
+use crate::{
+    bstr::BString,
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{Key, Push},
+    },
+    remote::{Connection, Direction},
+    Progress,
+};
+
+mod error;
+pub use error::Error;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod gpg;
+pub use gpg::Gpg;
+
+mod pack;
+mod updates;
+
+/// The reason for an update to be rejected before it was sent to the remote.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RejectReason {
+    /// The remote reference isn't an ancestor of the object to set it to, and the refspec doesn't allow forcing the update.
+    NonFastForward,
+    /// The object the remote reference currently points to isn't present locally, so it can't be determined if the update
+    /// is a fast-forward. Fetching from the remote first typically resolves this.
+    FetchFirst,
+    /// The remote tag already exists and the refspec doesn't allow forcing the update.
+    TagAlreadyExists,
+}
+
+/// An update that wasn't sent to the remote as it was rejected locally.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// The update as it would have been sent.
+    pub update: gix_protocol::push::Update,
+    /// The reason for the rejection.
+    pub reason: RejectReason,
+}
+
+/// The outcome of sending reference updates via [`Prepare::send()`].
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    /// The reference updates that were sent to the remote, which is empty if there was nothing to do.
+    ///
+    /// Remote references that already have their desired value aren't updated.
+    pub updates: Vec<gix_protocol::push::Update>,
+    /// The updates that were rejected locally and thus not sent to the remote.
+    ///
+    /// In [atomic mode](Prepare::with_atomic()), a single rejection prevents all updates from being sent.
+    pub rejected: Vec<Rejection>,
+    /// The status of each update as reported by the remote.
+    pub report: gix_protocol::push::Report,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod prepare {
+    /// The error returned by [`prepare_push()`][super::Connection::prepare_push()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Handshake(#[from] crate::remote::ref_map::Error),
+    }
+
+    impl gix_protocol::transport::IsSpuriousError for Error {
+        fn is_spurious(&self) -> bool {
+            match self {
+                Error::Handshake(err) => err.is_spurious(),
+            }
+        }
+    }
+}
+
+impl<'remote, 'repo, T> Connection<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// Perform a handshake with the remote to obtain the references it advertises for receiving a push, and prepare
+    /// sending updates to them based on the [push refspecs](crate::Remote::refspecs()) of our remote.
+    ///
+    /// Note that at this point, the `transport` should already be configured using the [`transport_mut()`][Self::transport_mut()]
+    /// method, as it will be consumed here.
+    ///
+    /// # Async Experimental
+    ///
+    /// Just like [fetching](Self::prepare_fetch()), generating the pack to send is inherently blocking and isn't offloaded
+    /// to a thread, making [`send()`](Prepare::send()) block the executor.
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn prepare_push(
+        mut self,
+        mut progress: impl Progress,
+    ) -> Result<Prepare<'remote, 'repo, T>, prepare::Error> {
+        let handshake = self
+            .handshake(
+                gix_protocol::transport::Service::ReceivePack,
+                Direction::Push,
+                Vec::new(),
+                &mut progress,
+            )
+            .await?;
+        Ok(Prepare {
+            con: Some(self),
+            handshake,
+            atomic: false,
+            push_options: None,
+            gpg_sign: None,
+            signer: None,
+        })
+    }
+}
+
+/// A structure to hold the result of the handshake with the remote and configure the upcoming push operation.
+pub struct Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    con: Option<Connection<'remote, 'repo, T>>,
+    handshake: gix_protocol::handshake::Outcome,
+    atomic: bool,
+    push_options: Option<Vec<BString>>,
+    gpg_sign: Option<crate::push::GpgSign>,
+    signer: Option<Box<dyn gix_protocol::push::Sign>>,
+}
+
+impl<'remote, 'repo, T> Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// Return the outcome of the handshake with the remote, with the capabilities it advertised for receiving a push.
+    pub fn handshake(&self) -> &gix_protocol::handshake::Outcome {
+        &self.handshake
+    }
+
+    /// Return the references advertised by the remote, which are expected to be updated.
+    pub fn remote_refs(&self) -> &[gix_protocol::handshake::Ref] {
+        self.handshake.refs.as_deref().unwrap_or_default()
+    }
+}
+
+/// Builder
+impl<'remote, 'repo, T> Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// If `enabled`, ask the remote to update either all references or none of them, which fails if it doesn't support
+    /// the `atomic` capability.
+    ///
+    /// Then none of the updates are sent if any of them is [rejected locally](Outcome::rejected) as well.
+    pub fn with_atomic(mut self, enabled: bool) -> Self {
+        self.atomic = enabled;
+        self
+    }
+
+    /// Send `options` to the remote for use by its hooks, instead of the ones configured in `push.pushOption`,
+    /// which fails if it doesn't support the `push-options` capability.
+    pub fn with_push_options(mut self, options: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+        self.push_options = Some(options.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Determine if the updates should be sent as part of a signed push certificate, instead of what's configured
+    /// in `push.gpgSign`.
+    ///
+    /// With [`GpgSign::Yes`](crate::push::GpgSign::Yes), sending fails if the remote doesn't support signed pushes.
+    pub fn with_gpg_sign(mut self, mode: crate::push::GpgSign) -> Self {
+        self.gpg_sign = Some(mode);
+        self
+    }
+
+    /// Use `signer` to sign push certificates instead of a [`Gpg`] signer configured from the repository.
+    pub fn with_signer(mut self, signer: impl gix_protocol::push::Sign + 'static) -> Self {
+        self.signer = Some(Box::new(signer));
+        self
+    }
+}
+
+impl<'remote, 'repo, T> Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    /// Send the updates derived from the push refspecs of our remote, along with a pack of all objects the remote needs
+    /// to perform them, and return the status it reports for each of them.
+    ///
+    /// Updates that aren't fast-forwards are rejected unless their refspec allows forcing them, and the remote
+    /// decides which of the remaining updates to perform.
+    ///
+    /// `progress` is used to show the progress of generating the pack and messages sent by the remote,
+    /// and `should_interrupt` can stop generating the pack.
+    ///
+    /// If configured [with `push.gpgSign`](Prepare::with_gpg_sign()), the updates are sent as part of a push certificate
+    /// signed by the pusher, which is `user.signingKey` or the committer.
+    ///
+    /// Note that the pack is generated in memory before it's sent, and that no local remote-tracking references are updated.
+    #[gix_protocol::maybe_async::maybe_async]
+    pub async fn send<P>(mut self, mut progress: P, should_interrupt: &AtomicBool) -> Result<Outcome, Error>
+    where
+        P: NestedProgress,
+        P::SubProgress: 'static,
+    {
+        let con = self.con.as_ref().expect("send() can only be called once");
+        let repo = con.remote.repo;
+        let refspecs = con.remote.refspecs(Direction::Push);
+        if refspecs.is_empty() {
+            return Err(Error::MissingRefSpecs);
+        }
+        let remote_refs = self.handshake.refs.as_deref().unwrap_or_default();
+        let (mut updates, rejected) = updates::compute(repo, refspecs, remote_refs)?;
+        if self.atomic && !rejected.is_empty() {
+            updates.clear();
+        }
+        let options = gix_protocol::push::Options {
+            atomic: self.atomic,
+            push_options: match self.push_options.take() {
+                Some(options) => options,
+                None => configured_push_options(repo),
+            },
+        };
+        let pack = if updates.iter().any(|update| !update.is_delete()) {
+            Some(pack::generate(
+                repo,
+                &updates,
+                remote_refs,
+                &mut progress,
+                should_interrupt,
+            )?)
+        } else {
+            None
+        };
+
+        let gpg_sign = match self.gpg_sign {
+            Some(mode) => mode,
+            None => repo
+                .config
+                .resolved
+                .string_by_key(Push::GPG_SIGN.logical_name().as_str())
+                .map(|value| Push::GPG_SIGN.try_into_gpg_sign(value))
+                .transpose()
+                .with_leniency(repo.config.lenient_config)?
+                .unwrap_or_default(),
+        };
+        let mut default_signer = None;
+        let certificate = match gpg_sign {
+            crate::push::GpgSign::No => None,
+            mode => Some(gix_protocol::push::Certificate {
+                pusher: gpg::signing_key(repo)
+                    .or_else(|| gpg::committer(repo))
+                    .ok_or(Error::MissingPusher)?,
+                time: gix_date::Time::now_local_or_utc(),
+                pushee: con.remote.url(Direction::Push).map(|url| {
+                    let mut url = url.clone();
+                    url.set_user(None);
+                    url.set_password(None);
+                    url.to_bstring()
+                }),
+                required: mode == crate::push::GpgSign::Yes,
+                signer: match self.signer.as_mut() {
+                    Some(signer) => signer.as_mut(),
+                    None => default_signer.insert(Gpg::from_repo(repo)),
+                },
+            }),
+        };
+
+        let mut con = self.con.take().expect("present");
+        let mut pack_data = pack.as_deref().unwrap_or_default();
+        let agent = repo.config.user_agent_tuple().1.unwrap_or_default();
+        let report = gix_protocol::push(
+            &mut con.transport,
+            &self.handshake.capabilities,
+            &updates,
+            pack.is_some()
+                .then_some(&mut pack_data as &mut gix_protocol::push::PackData<'_>),
+            &options,
+            certificate,
+            agent,
+            &mut progress,
+            con.trace,
+        )
+        .await?;
+        Ok(Outcome {
+            updates,
+            rejected,
+            report,
+        })
+    }
+}
+
+/// Obtain the push options from `push.pushOption`, where an empty value clears all previous ones.
+fn configured_push_options(repo: &crate::Repository) -> Vec<BString> {
+    let mut out = Vec::new();
+    for value in repo
+        .config
+        .resolved
+        .strings_by_key(Push::PUSH_OPTION.logical_name().as_str())
+        .unwrap_or_default()
+    {
+        if value.is_empty() {
+            out.clear();
+        } else {
+            out.push(value.into_owned());
+        }
+    }
+    out
+}
+
+impl<'remote, 'repo, T> Drop for Prepare<'remote, 'repo, T>
+where
+    T: Transport,
+{
+    fn drop(&mut self) {
+        if let Some(mut con) = self.con.take() {
+            #[cfg(feature = "async-network-client")]
+            {
+                // TODO: this should be an async drop once the feature is available.
+                //       Right now we block the executor by forcing this communication, but that only
+                //       happens if the user didn't actually try to send anything, which consumes the
+                //       connection in an async context.
+                gix_protocol::futures_lite::future::block_on(gix_protocol::indicate_end_of_interaction(
+                    &mut con.transport,
+                    con.trace,
+                ))
+                .ok();
+            }
+            #[cfg(not(feature = "async-network-client"))]
+            {
+                gix_protocol::indicate_end_of_interaction(&mut con.transport, con.trace).ok();
+            }
+        }
+    }
+}
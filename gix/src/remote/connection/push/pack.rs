@@ -0,0 +1,91 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_features::{parallel::InOrderIter, progress, progress::NestedProgress};
+use gix_pack::data::output;
+use gix_protocol::{handshake, push::Update};
+
+use super::Error;
+use crate::{Count, Progress, Repository};
+
+/// Generate a pack with all objects needed to perform `updates`, leaving out the history of everything the remote
+/// advertised in `remote_refs` that we have locally.
+pub(crate) fn generate<P>(
+    repo: &Repository,
+    updates: &[Update],
+    remote_refs: &[handshake::Ref],
+    progress: &mut P,
+    should_interrupt: &AtomicBool,
+) -> Result<Vec<u8>, Error>
+where
+    P: NestedProgress,
+    P::SubProgress: 'static,
+{
+    let mut commits = Vec::new();
+    let mut others = Vec::new();
+    for update in updates.iter().filter(|update| !update.is_delete()) {
+        let object = repo.find_object(update.new)?;
+        if object.kind != gix_object::Kind::Commit {
+            others.push(object.id);
+        }
+        let object = object.peel_tags_to_end()?;
+        if object.kind == gix_object::Kind::Commit {
+            commits.push(object.id);
+        } else if object.id != update.new {
+            others.push(object.id);
+        }
+    }
+    let hidden: Vec<_> = remote_refs
+        .iter()
+        .map(handshake::Ref::unpack)
+        .filter_map(|(_name, target, peeled)| peeled.or(target))
+        .filter_map(|id| repo.find_object(id).ok()?.peel_tags_to_end().ok())
+        .filter(|object| object.kind == gix_object::Kind::Commit)
+        .map(|object| object.id)
+        .collect();
+
+    let mut db = repo.objects.clone().into_arc().map_err(Error::ShareObjectDatabase)?;
+    db.prevent_pack_unload();
+    db.ignore_replacements = true;
+    let counts = {
+        let mut counting = progress.add_child("counting");
+        counting.init(None, progress::count("objects"));
+        let mut input = others.into_iter().map(Ok).chain(
+            gix_traverse::commit::Simple::new(commits, &repo.objects)
+                .hide(hidden)
+                .map(|info| info.map(|info| info.id).map_err(|err| Box::new(err) as Box<_>)),
+        );
+        let (counts, _outcome) = output::count::objects_unthreaded(
+            &db,
+            &mut input,
+            &counting,
+            should_interrupt,
+            output::count::objects::ObjectExpansion::TreeAdditionsComparedToAncestor,
+        )?;
+        counts
+    };
+
+    let num_objects = counts.len();
+    let entries = output::entry::iter_from_counts(
+        counts,
+        db,
+        Box::new(progress.add_child("creating entries")),
+        output::entry::iter_from_counts::Options {
+            mode: output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+            allow_thin_pack: false,
+            ..Default::default()
+        },
+    );
+    let mut pack = Vec::new();
+    let mut writing = progress.add_child("writing");
+    writing.init(None, progress::bytes());
+    for written in output::bytes::FromEntriesIter::new(
+        InOrderIter::from(entries),
+        &mut pack,
+        num_objects as u32,
+        gix_pack::data::Version::V2,
+        repo.object_hash(),
+    ) {
+        writing.inc_by(written? as usize);
+    }
+    Ok(pack)
+}
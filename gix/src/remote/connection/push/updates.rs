@@ -0,0 +1,167 @@
+use gix_hash::ObjectId;
+use gix_protocol::{handshake, push::Update};
+use gix_refspec::{instruction::Push, match_group::SourceRef, RefSpec};
+
+use super::{Error, RejectReason, Rejection};
+use crate::{
+    bstr::{BStr, BString, ByteSlice},
+    ext::ObjectIdExt,
+    Repository,
+};
+
+/// Compute the updates of `remote_refs` as requested by the push `refspecs`, along with the ones that are rejected as
+/// they aren't allowed to be forced. Updates that wouldn't change anything are omitted.
+pub(crate) fn compute(
+    repo: &Repository,
+    refspecs: &[RefSpec],
+    remote_refs: &[handshake::Ref],
+) -> Result<(Vec<Update>, Vec<Rejection>), Error> {
+    let mut local_refs = Vec::new();
+    if let Ok(head_id) = repo.head_id() {
+        local_refs.push((BString::from("HEAD"), head_id.detach()));
+    }
+    for reference in repo.references()?.all()? {
+        let reference = reference.map_err(Error::ReadReference)?;
+        if let Some(id) = reference.target().try_id() {
+            local_refs.push((reference.name().as_bstr().to_owned(), id.to_owned()));
+        }
+    }
+
+    // (destination, new value, force)
+    let mut candidates = Vec::<(BString, ObjectId, bool)>::new();
+    let group = gix_refspec::MatchGroup::from_push_specs(refspecs.iter().map(RefSpec::to_ref));
+    let outcome = group.match_remotes(local_refs.iter().map(|(name, id)| gix_refspec::match_group::Item {
+        full_ref_name: name.as_ref(),
+        target: id,
+        object: None,
+    }));
+    let head_name = repo.head_name().ok().flatten();
+    for mapping in &outcome.mappings {
+        let (source, new) = match mapping.lhs {
+            SourceRef::FullName(name) => (Some(name), local_refs[mapping.item_index.expect("set for all names")].1),
+            SourceRef::ObjectId(id) => (None, id),
+        };
+        // Without destination, the reference with the same name as the source is updated.
+        let destination = match (mapping.rhs.as_deref().or(source), head_name.as_ref()) {
+            (Some(destination), Some(head_name)) if destination == "HEAD" => head_name.as_bstr(),
+            (Some(destination), _) => destination,
+            (None, _) => continue,
+        };
+        candidates.push((
+            expand_destination(destination, source, remote_refs),
+            new,
+            refspecs[mapping.spec_index].allow_non_fast_forward(),
+        ));
+    }
+
+    for spec in refspecs {
+        match spec.to_ref().instruction() {
+            gix_refspec::Instruction::Push(Push::Delete { ref_or_pattern }) => {
+                for (name, _, _) in remote_refs.iter().map(handshake::Ref::unpack) {
+                    if matches_deletion(ref_or_pattern, name) {
+                        candidates.push((name.to_owned(), repo.object_hash().null(), true));
+                    }
+                }
+            }
+            gix_refspec::Instruction::Push(Push::AllMatchingBranches { allow_non_fast_forward }) => {
+                for (name, id) in &local_refs {
+                    let is_advertised = remote_refs
+                        .iter()
+                        .any(|remote_ref| remote_ref.unpack().0 == name.as_bstr());
+                    if name.starts_with(b"refs/heads/") && is_advertised {
+                        candidates.push((name.clone(), *id, allow_non_fast_forward));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut updates = Vec::<Update>::new();
+    let mut rejected = Vec::new();
+    for (name, new, force) in candidates {
+        if let Some(existing) = updates.iter().find(|update| update.name == name) {
+            if existing.new == new {
+                continue;
+            }
+            return Err(Error::MultipleSources { name });
+        }
+        let update = Update::from_advertised(name, new, remote_refs);
+        if update.old == update.new {
+            continue;
+        }
+        match (!force && !update.old.is_null() && !update.is_delete())
+            .then(|| reject_reason(repo, &update))
+            .flatten()
+        {
+            Some(reason) => rejected.push(Rejection { update, reason }),
+            None => updates.push(update),
+        }
+    }
+    Ok((updates, rejected))
+}
+
+/// Return the reason for rejecting `update` if it's not a fast-forward, assuming it's not forced.
+fn reject_reason(repo: &Repository, update: &Update) -> Option<RejectReason> {
+    if update.name.starts_with(b"refs/tags/") {
+        return Some(RejectReason::TagAlreadyExists);
+    }
+    let old_is_commit = repo
+        .find_object(update.old)
+        .is_ok_and(|obj| obj.kind == gix_object::Kind::Commit);
+    if !old_is_commit {
+        return Some(if repo.has_object(update.old) {
+            RejectReason::NonFastForward
+        } else {
+            RejectReason::FetchFirst
+        });
+    }
+    // Commit times can't be used to cut the traversal short as they may not be monotonic.
+    let is_fast_forward = update
+        .new
+        .ancestors(&repo.objects)
+        .any(|info| info.is_ok_and(|info| info.id == update.old));
+    (!is_fast_forward).then_some(RejectReason::NonFastForward)
+}
+
+/// Turn the possibly partial `destination` into a full reference name, preferring the names advertised in `remote_refs`
+/// and falling back to the category of the local `source` reference.
+fn expand_destination(destination: &BStr, source: Option<&BStr>, remote_refs: &[handshake::Ref]) -> BString {
+    if destination.starts_with(b"refs/") {
+        return destination.to_owned();
+    }
+    let candidates = [
+        format!("refs/{destination}"),
+        format!("refs/heads/{destination}"),
+        format!("refs/tags/{destination}"),
+    ];
+    candidates
+        .iter()
+        .find(|candidate| {
+            remote_refs
+                .iter()
+                .any(|remote_ref| remote_ref.unpack().0 == candidate.as_str())
+        })
+        .cloned()
+        .unwrap_or_else(|| match source {
+            Some(source) if source.starts_with(b"refs/tags/") => format!("refs/tags/{destination}"),
+            _ => format!("refs/heads/{destination}"),
+        })
+        .into()
+}
+
+/// Return `true` if the advertised reference `name` should be deleted by a refspec with `ref_or_pattern` as destination.
+fn matches_deletion(ref_or_pattern: &BStr, name: &BStr) -> bool {
+    match ref_or_pattern.find_byte(b'*') {
+        Some(pos) => {
+            let (prefix, suffix) = (&ref_or_pattern[..pos], &ref_or_pattern[pos + 1..]);
+            name.len() >= prefix.len() + suffix.len() && name.starts_with(prefix) && name.ends_with(suffix)
+        }
+        None => {
+            name == ref_or_pattern
+                || ["refs/", "refs/heads/", "refs/tags/"]
+                    .iter()
+                    .any(|prefix| name.strip_prefix(prefix.as_bytes()) == Some(ref_or_pattern.as_bytes()))
+        }
+    }
+}
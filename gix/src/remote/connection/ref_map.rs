@@ -115,7 +115,12 @@ where
         let _span = gix_trace::coarse!("remote::Connection::ref_map()");
         let null = gix_hash::ObjectId::null(gix_hash::Kind::Sha1); // OK to hardcode Sha1, it's not supposed to match, ever.
 
-        if let Some(tag_spec) = self.remote.fetch_tags.to_refspec().map(|spec| spec.to_owned()) {
+        if let Some(tag_spec) = self
+            .remote
+            .effective_fetch_tags()
+            .to_refspec()
+            .map(|spec| spec.to_owned())
+        {
             if !extra_refspecs.contains(&tag_spec) {
                 extra_refspecs.push(tag_spec);
             }
@@ -188,35 +193,14 @@ where
         mut progress: impl Progress,
     ) -> Result<HandshakeWithRefs, Error> {
         let _span = gix_trace::coarse!("remote::Connection::fetch_refs()");
-        let mut credentials_storage;
-        let url = self.transport.to_url();
-        let authenticate = match self.authenticate.as_mut() {
-            Some(f) => f,
-            None => {
-                let url = self.remote.url(Direction::Fetch).map_or_else(
-                    || gix_url::parse(url.as_ref()).expect("valid URL to be provided by transport"),
-                    ToOwned::to_owned,
-                );
-                credentials_storage = self.configured_credentials(url)?;
-                &mut credentials_storage
-            }
-        };
-
-        if self.transport_options.is_none() {
-            self.transport_options = self
-                .remote
-                .repo
-                .transport_options(url.as_ref(), self.remote.name().map(crate::remote::Name::as_bstr))
-                .map_err(|err| Error::GatherTransportConfig {
-                    source: err,
-                    url: url.into_owned(),
-                })?;
-        }
-        if let Some(config) = self.transport_options.as_ref() {
-            self.transport.configure(&**config)?;
-        }
-        let mut outcome =
-            gix_protocol::fetch::handshake(&mut self.transport, authenticate, extra_parameters, &mut progress).await?;
+        let mut outcome = self
+            .handshake(
+                gix_protocol::transport::Service::UploadPack,
+                Direction::Fetch,
+                extra_parameters,
+                &mut progress,
+            )
+            .await?;
         let refs = match outcome.refs.take() {
             Some(refs) => refs,
             None => {
@@ -250,6 +234,47 @@ where
         };
         Ok(HandshakeWithRefs { outcome, refs })
     }
+
+    /// Configure the transport and perform the handshake for `service`, authenticating with the credentials configured for
+    /// the url used in `direction` unless [overridden](Self::with_credentials()).
+    #[allow(clippy::result_large_err)]
+    #[gix_protocol::maybe_async::maybe_async]
+    pub(crate) async fn handshake(
+        &mut self,
+        service: gix_protocol::transport::Service,
+        direction: Direction,
+        extra_parameters: Vec<(String, Option<String>)>,
+        progress: &mut impl Progress,
+    ) -> Result<gix_protocol::handshake::Outcome, Error> {
+        let mut credentials_storage;
+        let url = self.transport.to_url();
+        let authenticate = match self.authenticate.as_mut() {
+            Some(f) => f,
+            None => {
+                let url = self.remote.url(direction).map_or_else(
+                    || gix_url::parse(url.as_ref()).expect("valid URL to be provided by transport"),
+                    ToOwned::to_owned,
+                );
+                credentials_storage = self.configured_credentials(url)?;
+                &mut credentials_storage
+            }
+        };
+
+        if self.transport_options.is_none() {
+            self.transport_options = self
+                .remote
+                .repo
+                .transport_options(url.as_ref(), self.remote.name().map(crate::remote::Name::as_bstr))
+                .map_err(|err| Error::GatherTransportConfig {
+                    source: err,
+                    url: url.into_owned(),
+                })?;
+        }
+        if let Some(config) = self.transport_options.as_ref() {
+            self.transport.configure(&**config)?;
+        }
+        Ok(gix_protocol::handshake(&mut self.transport, service, authenticate, extra_parameters, progress).await?)
+    }
 }
 
 /// Assume sha1 if server says nothing, otherwise configure anything beyond sha1 in the local repo configuration
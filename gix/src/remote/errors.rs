@@ -9,6 +9,8 @@ pub mod find {
     pub enum Error {
         #[error("The value for 'remote.<name>.tagOpt` is invalid and must either be '--tags' or '--no-tags'")]
         TagOpt(#[from] config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        Boolean(#[from] config::boolean::Error),
         #[error("{kind} ref-spec under `remote.{remote_name}` was invalid")]
         RefSpec {
             kind: &'static str,
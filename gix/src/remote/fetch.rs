@@ -8,10 +8,12 @@ pub mod negotiate {
     pub use super::super::connection::fetch::negotiate::Error;
     #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
     pub(crate) use super::super::connection::fetch::negotiate::{
-        add_wants, make_refmapping_ignore_predicate, mark_complete_and_common_ref, one_round, Action,
+        add_wants, apply_wanted_refs, make_refmapping_ignore_predicate, mark_complete_and_common_ref, one_round, Action,
     };
 }
 
+#[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+pub(crate) use super::connection::fetch::config;
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
 pub use super::connection::fetch::{
     outcome, prepare, refs, Error, Outcome, Prepare, ProgressId, RefLogMessage, Status,
@@ -52,6 +52,8 @@ impl<'repo> Remote<'repo> {
             fetch_specs,
             push_specs,
             fetch_tags,
+            prune: false,
+            prune_tags: false,
             repo,
         })
     }
@@ -77,49 +79,59 @@ impl<'repo> Remote<'repo> {
         should_rewrite_urls: bool,
         repo: &'repo Repository,
     ) -> Result<Self, Error> {
-        let (url_alias, _) = should_rewrite_urls
-            .then(|| rewrite_urls(&repo.config, Some(&url), None))
-            .unwrap_or(Ok((None, None)))?;
+        let (url_alias, push_url_alias) = if should_rewrite_urls {
+            rewrite_urls(&repo.config, Some(&url), None)
+        } else {
+            Ok((None, None))
+        }?;
         Ok(Remote {
             name: None,
             url: Some(url),
             url_alias,
             push_url: None,
-            push_url_alias: None,
+            push_url_alias,
             fetch_specs: Vec::new(),
             push_specs: Vec::new(),
             fetch_tags: Default::default(),
+            prune: false,
+            prune_tags: false,
             repo,
         })
     }
 }
 
+/// A rewritten url along with the rewrite that was applied to it.
+pub(crate) type Alias = (gix_url::Url, remote::url::Replacement);
+
 pub(crate) fn rewrite_url(
     config: &config::Cache,
     url: Option<&gix_url::Url>,
     direction: remote::Direction,
-) -> Result<Option<gix_url::Url>, Error> {
+) -> Result<Option<Alias>, Error> {
     url.and_then(|url| config.url_rewrite().longest(url, direction))
-        .map(|url| {
-            gix_url::parse(url.as_ref()).map_err(|err| Error::RewrittenUrlInvalid {
-                kind: match direction {
-                    remote::Direction::Fetch => "fetch",
-                    remote::Direction::Push => "push",
-                },
-                source: err,
-                rewritten_url: url,
-            })
+        .map(|(url, replacement)| {
+            gix_url::parse(url.as_ref())
+                .map(|url| (url, replacement))
+                .map_err(|err| Error::RewrittenUrlInvalid {
+                    kind: match direction {
+                        remote::Direction::Fetch => "fetch",
+                        remote::Direction::Push => "push",
+                    },
+                    source: err,
+                    rewritten_url: url,
+                })
         })
         .transpose()
 }
 
+/// Rewrite `url` for fetching, and `push_url` for pushing, or `url` if there is no `push_url` as it's used for pushing then.
 pub(crate) fn rewrite_urls(
     config: &config::Cache,
     url: Option<&gix_url::Url>,
     push_url: Option<&gix_url::Url>,
-) -> Result<(Option<gix_url::Url>, Option<gix_url::Url>), Error> {
+) -> Result<(Option<Alias>, Option<Alias>), Error> {
     let url_alias = rewrite_url(config, url, remote::Direction::Fetch)?;
-    let push_url_alias = rewrite_url(config, push_url, remote::Direction::Push)?;
+    let push_url_alias = rewrite_url(config, push_url.or(url), remote::Direction::Push)?;
 
     Ok((url_alias, push_url_alias))
 }
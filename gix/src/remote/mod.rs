@@ -55,7 +55,11 @@ pub mod connect;
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
 mod connection;
 #[cfg(any(feature = "async-network-client", feature = "blocking-network-client"))]
-pub use connection::{ref_map, AuthenticateFn, Connection};
+pub use connection::{push, ref_map, AuthenticateFn, Connection};
+
+///
+#[cfg(feature = "blocking-network-client")]
+pub mod retry;
 
 ///
 #[allow(clippy::empty_docs)]
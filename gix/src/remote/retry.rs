@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use gix_protocol::transport::{client::Transport, IsSpuriousError};
+
+use crate::{
+    remote::{connect, Connection, Direction},
+    Remote,
+};
+
+/// A policy to control how often and after which delay an operation is retried if it failed
+/// [spuriously](IsSpuriousError::is_spurious()), like when a connection couldn't be established, timed out,
+/// or the server responded with a `5xx` status code.
+///
+/// All other errors are returned right away as retrying them can't change the outcome.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Policy {
+    /// The maximum amount of times the operation is attempted, including the first attempt.
+    ///
+    /// `0` and `1` are equivalent and don't retry at all.
+    pub max_attempts: usize,
+    /// The delay before the first retry, which doubles with each following retry.
+    pub initial_delay: Duration,
+    /// The delay between attempts will never be longer than this.
+    pub max_delay: Duration,
+}
+
+impl Default for Policy {
+    /// Try up to 3 times, waiting a second before the first retry and two seconds before the second one.
+    fn default() -> Self {
+        Policy {
+            max_attempts: 3,
+            initial_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Policy {
+    /// Return the delays to wait for before each retry, which is one less than the amount of attempts.
+    pub fn delays(&self) -> impl Iterator<Item = Duration> {
+        let max_delay = self.max_delay;
+        std::iter::successors(Some(self.initial_delay.min(max_delay)), move |delay| {
+            Some(delay.saturating_mul(2).min(max_delay))
+        })
+        .take(self.max_attempts.saturating_sub(1))
+    }
+
+    /// Call `op` with the number of the current attempt, starting at `1`, until it succeeds, fails with an error that
+    /// isn't spurious, or until no attempts are left. Before each retry, wait for the next of our [delays](Self::delays()).
+    ///
+    /// The last result of `op` is returned.
+    pub fn run<T, E>(&self, mut op: impl FnMut(usize) -> Result<T, E>) -> Result<T, E>
+    where
+        E: IsSpuriousError,
+    {
+        let mut delays = self.delays();
+        let mut attempt = 1;
+        loop {
+            match op(attempt) {
+                Err(err) if err.is_spurious() => match delays.next() {
+                    Some(delay) => {
+                        gix_trace::debug!("attempt {attempt} failed spuriously, retrying in {delay:?}");
+                        std::thread::sleep(delay);
+                        attempt += 1;
+                    }
+                    None => return Err(err),
+                },
+                res => return res,
+            }
+        }
+    }
+}
+
+/// Retrying operations on connections.
+impl<'repo> Remote<'repo> {
+    /// Connect to the url suitable for `direction` and pass the connection to `op`, which typically performs a
+    /// whole fetch or push with it. If connecting or `op` fail [spuriously](IsSpuriousError::is_spurious()), a new
+    /// connection is established and passed to `op` again, as long as `policy` permits it.
+    ///
+    /// Errors like [`gix::env::collate::fetch::Error`](crate::env::collate::fetch::Error) and
+    /// [`gix::env::collate::push::Error`](crate::env::collate::push::Error) are well suited for use in `op`.
+    ///
+    /// Note that a push is also retried if the remote received the pack, but its report was lost.
+    /// As the next attempt performs a new handshake, updates that were already applied won't be sent again.
+    pub fn connect_with_retry<T, E>(
+        &self,
+        direction: Direction,
+        policy: &Policy,
+        mut op: impl FnMut(Connection<'_, 'repo, Box<dyn Transport + Send>>) -> Result<T, E>,
+    ) -> Result<T, E>
+    where
+        E: IsSpuriousError + From<connect::Error>,
+    {
+        policy.run(|_attempt| op(self.connect(direction)?))
+    }
+}
@@ -94,6 +94,12 @@ impl Remote<'_> {
                 .into(),
             );
         }
+        if self.prune {
+            section.push(as_key(config::tree::Remote::PRUNE.name), Some("true".into()));
+        }
+        if self.prune_tags {
+            section.push(as_key(config::tree::Remote::PRUNE_TAGS.name), Some("true".into()));
+        }
         for (key, spec) in self
             .fetch_specs
             .iter()
@@ -1,11 +1,23 @@
 use gix_features::threading::OwnShared;
 
 use crate::{
-    bstr::{BStr, BString, ByteVec},
+    bstr::{BString, ByteVec},
     config,
     remote::Direction,
 };
 
+/// A rewrite of a remote url that was applied as configured by `url.<base>.insteadOf` or `url.<base>.pushInsteadOf`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Replacement {
+    /// The kind of rule that was applied, with [`Fetch`](Direction::Fetch) for `insteadOf` and [`Push`](Direction::Push)
+    /// for `pushInsteadOf`.
+    pub direction: Direction,
+    /// The prefix of the original url that was replaced, i.e. the value of `insteadOf` or `pushInsteadOf`.
+    pub find: BString,
+    /// The value the prefix was replaced with, i.e. the `<base>` of the `url` section.
+    pub with: BString,
+}
+
 #[derive(Debug, Clone)]
 struct Replace {
     find: BString,
@@ -66,35 +78,31 @@ impl Rewrite {
         }
     }
 
-    pub fn longest(&self, url: &gix_url::Url, direction: Direction) -> Option<BString> {
-        if self.replacements_for(direction).is_empty() {
-            None
-        } else {
-            let mut url = url.to_bstring();
-            self.rewrite_url_in_place(&mut url, direction).then_some(url)
-        }
-    }
-
-    /// Rewrite the given `url` of `direction` and return `true` if a replacement happened.
+    /// Rewrite the given `url` of `direction` with the replacement that matches the longest prefix of it, and return the
+    /// rewritten url along with the replacement, or `None` if there was no match.
     ///
     /// Note that the result must still be checked for validity, it might not be a valid URL as we do a syntax-unaware replacement.
-    pub fn rewrite_url_in_place(&self, url: &mut BString, direction: Direction) -> bool {
-        self.replacements_for(direction)
+    pub fn longest(&self, url: &gix_url::Url, direction: Direction) -> Option<(BString, Replacement)> {
+        let replacements = self.replacements_for(direction);
+        if replacements.is_empty() {
+            return None;
+        }
+        let mut url = url.to_bstring();
+        let replace = replacements
             .iter()
-            .fold(None::<(usize, &BStr)>, |mut acc, replace| {
-                if url.starts_with(replace.find.as_ref()) {
-                    let (bytes_matched, prev_rewrite_with) =
-                        acc.get_or_insert((replace.find.len(), replace.with.as_slice().into()));
-                    if *bytes_matched < replace.find.len() {
-                        *bytes_matched = replace.find.len();
-                        *prev_rewrite_with = replace.with.as_slice().into();
-                    }
-                };
-                acc
-            })
-            .map(|(bytes_matched, replace_with)| {
-                url.replace_range(..bytes_matched, replace_with);
-            })
-            .is_some()
+            .filter(|replace| url.starts_with(replace.find.as_ref()))
+            .fold(None::<&Replace>, |longest, replace| match longest {
+                Some(longest) if longest.find.len() >= replace.find.len() => Some(longest),
+                _ => Some(replace),
+            })?;
+        url.replace_range(..replace.find.len(), replace.with.as_slice());
+        Some((
+            url,
+            Replacement {
+                direction,
+                find: replace.find.clone(),
+                with: replace.with.as_ref().clone(),
+            },
+        ))
     }
 }
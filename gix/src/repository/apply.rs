@@ -0,0 +1,351 @@
+use std::{
+    collections::BTreeSet,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use gix_apply::Entry;
+use gix_hash::ObjectId;
+use gix_index::entry::{Mode, Stage};
+use gix_object::{tree::EntryKind, FindExt};
+
+use crate::{
+    apply,
+    apply::Location,
+    bstr::{BStr, ByteSlice},
+    config::cache::util::ApplyLeniencyDefault,
+    ext::ObjectIdExt,
+    filter, Repository,
+};
+
+impl Repository {
+    /// Apply `patches` as [parsed](gix_apply::patch::parse()) from the output of `git diff` or `git format-patch` in order,
+    /// reading files from and writing them to the [location](apply::Location) in `options`, similar to `git apply`,
+    /// and return the changed files.
+    ///
+    /// Nothing is written unless all patches apply, and nothing is written at all if [`check`](apply::Options::check)
+    /// is set. Files in the worktree are converted to their git representation with the filters configured for them
+    /// before patching, and converted back to their worktree representation when written.
+    ///
+    /// Patches that refer to paths outside of the worktree or within a `.git` directory are rejected, as are those that
+    /// would write through a symbolic link in the worktree.
+    ///
+    /// Note that patched submodules are updated in the index, but never checked out.
+    pub fn apply_patches(
+        &self,
+        patches: &[gix_apply::Patch],
+        options: apply::Options,
+    ) -> Result<apply::Outcome, apply::Error> {
+        let _span = gix_trace::coarse!("gix::apply_patches", patches = patches.len());
+        let workdir = match options.location {
+            Location::Index => None,
+            Location::Worktree | Location::WorktreeAndIndex => {
+                Some(self.work_dir().ok_or(apply::Error::MissingWorkDir)?)
+            }
+        };
+        for location in patches
+            .iter()
+            .flat_map(|patch| patch.old_path.iter().chain(patch.new_path.iter()))
+        {
+            let location = location.as_bstr();
+            if !gix_apply::patch::is_valid_location(location) {
+                return Err(apply::Error::InvalidLocation {
+                    location: location.to_owned(),
+                });
+            }
+            if let Some(workdir) = workdir {
+                if is_beyond_symlink(workdir, location)? {
+                    return Err(apply::Error::BeyondSymlink {
+                        location: location.to_owned(),
+                    });
+                }
+            }
+        }
+        let mut index = (**self.index_or_empty()?).clone();
+        let (mut pipeline, _) = self.filter_pipeline(None)?;
+        let fs_caps = self.filesystem_options()?;
+
+        let changes = match workdir {
+            Some(workdir) => gix_apply::apply(
+                patches,
+                |rela_path| read_from_worktree(workdir, rela_path, &index, &mut pipeline, fs_caps),
+                options.apply,
+            )?,
+            None => {
+                let mut buf = Vec::new();
+                gix_apply::apply(
+                    patches,
+                    |rela_path| -> Result<_, gix_object::find::existing_object::Error> {
+                        let Some(entry) = index.entry_by_path_and_stage(rela_path, Stage::Unconflicted) else {
+                            return Ok(None);
+                        };
+                        let mode = match entry.mode {
+                            Mode::FILE => EntryKind::Blob,
+                            Mode::FILE_EXECUTABLE => EntryKind::BlobExecutable,
+                            Mode::SYMLINK => EntryKind::Link,
+                            Mode::COMMIT => return Ok(Some(submodule_entry(entry.id))),
+                            _ => return Ok(None),
+                        };
+                        let data = self.objects.find_blob(&entry.id, &mut buf)?.data.to_owned();
+                        Ok(Some(Entry { mode, data }))
+                    },
+                    options.apply,
+                )?
+            }
+        };
+        if workdir.is_some() {
+            // Symbolic links created by the patches must not be written through either.
+            let links: BTreeSet<_> = changes
+                .iter()
+                .filter(|change| matches!(&change.entry, Some(entry) if entry.mode == EntryKind::Link))
+                .map(|change| change.location.as_bstr())
+                .collect();
+            if let Some(change) = changes.iter().find(|change| {
+                let location = change.location.as_bstr();
+                location
+                    .find_iter("/")
+                    .any(|pos| links.contains(location[..pos].as_bstr()))
+            }) {
+                return Err(apply::Error::BeyondSymlink {
+                    location: change.location.clone(),
+                });
+            }
+        }
+        if options.check || changes.is_empty() {
+            return Ok(apply::Outcome { changes });
+        }
+
+        if let Some(workdir) = workdir {
+            for change in &changes {
+                write_to_worktree(workdir, change, &mut pipeline, fs_caps)?;
+            }
+        }
+        if options.location != Location::Worktree {
+            for change in &changes {
+                let location = change.location.as_bstr();
+                let Some(entry) = change.entry.as_ref() else {
+                    index.remove_path(location);
+                    continue;
+                };
+                let (id, mode) = match entry.mode {
+                    EntryKind::Commit => (
+                        submodule_commit(&entry.data).ok_or_else(|| apply::Error::InvalidSubmodule {
+                            location: change.location.clone(),
+                        })?,
+                        Mode::COMMIT,
+                    ),
+                    kind => {
+                        let mode = match kind {
+                            EntryKind::BlobExecutable => Mode::FILE_EXECUTABLE,
+                            EntryKind::Link => Mode::SYMLINK,
+                            _ => Mode::FILE,
+                        };
+                        (self.write_blob(&entry.data)?.detach(), mode)
+                    }
+                };
+                let stat = match workdir {
+                    Some(workdir) if mode != Mode::COMMIT => {
+                        let path = workdir.join(gix_path::from_bstr(location));
+                        let metadata = gix_index::fs::Metadata::from_path_no_follow(&path)
+                            .map_err(|source| apply::Error::Io { source, path })?;
+                        gix_index::entry::Stat::from_fs(&metadata)?
+                    }
+                    _ => Default::default(),
+                };
+                index.add_path(location, id, mode, stat);
+            }
+
+            let skip_hash = self
+                .config
+                .resolved
+                .boolean("index", None, "skipHash")
+                .map(|res| crate::config::tree::Index::SKIP_HASH.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or_default();
+            index.write(gix_index::write::Options {
+                extensions: Default::default(),
+                skip_hash,
+                thread_limit: self.index_thread_limit()?,
+            })?;
+        }
+        Ok(apply::Outcome { changes })
+    }
+
+    /// Apply `patches` as [parsed](gix_apply::patch::parse()) from the output of `git diff` or `git format-patch` in order
+    /// to `tree`, and write the resulting tree along with all changed blobs to the object database, using `options` to
+    /// control how hunks are matched.
+    ///
+    /// Neither the index nor the worktree are touched, and no filters are applied.
+    pub fn apply_patches_to_tree(
+        &self,
+        tree: impl Into<ObjectId>,
+        patches: &[gix_apply::Patch],
+        options: gix_apply::Options,
+    ) -> Result<crate::Id<'_>, gix_apply::tree::apply::Error> {
+        gix_apply::tree::apply(
+            &tree.into(),
+            patches,
+            &self.objects,
+            |object| self.write_object_dyn(object),
+            options,
+        )
+        .map(|id| id.attach(self))
+    }
+}
+
+fn read_from_worktree(
+    workdir: &Path,
+    rela_path: &BStr,
+    index: &gix_index::State,
+    pipeline: &mut filter::Pipeline<'_>,
+    fs_caps: gix_fs::Capabilities,
+) -> std::io::Result<Option<Entry>> {
+    let path = workdir.join(gix_path::from_bstr(rela_path));
+    let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+    let previous = index
+        .entry_by_path_and_stage(rela_path, Stage::Unconflicted)
+        .map(|entry| (entry.id, entry.mode));
+    if metadata.is_symlink() && fs_caps.symlink {
+        let target = std::fs::read_link(&path)?;
+        return Ok(Some(Entry {
+            mode: EntryKind::Link,
+            data: gix_path::into_bstr(target).into_owned().into(),
+        }));
+    }
+    if metadata.is_file() {
+        let mode = match previous {
+            // Without support for symlinks, they are checked out as files containing their target.
+            Some((_, Mode::SYMLINK)) if !fs_caps.symlink => EntryKind::Link,
+            _ if fs_caps.executable_bit => {
+                if metadata.is_executable() {
+                    EntryKind::BlobExecutable
+                } else {
+                    EntryKind::Blob
+                }
+            }
+            Some((_, Mode::FILE_EXECUTABLE)) => EntryKind::BlobExecutable,
+            _ => EntryKind::Blob,
+        };
+        let mut data = Vec::with_capacity(metadata.len() as usize);
+        if mode == EntryKind::Link {
+            std::fs::File::open(&path)?.read_to_end(&mut data)?;
+        } else {
+            pipeline
+                .convert_to_git(std::fs::File::open(&path)?, &gix_path::from_bstr(rela_path), index)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+                .read_to_end(&mut data)?;
+        }
+        return Ok(Some(Entry { mode, data }));
+    }
+    match previous {
+        Some((id, Mode::COMMIT)) => Ok(Some(submodule_entry(id))),
+        _ => Err(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!("{path:?} is neither a file nor a symlink"),
+        )),
+    }
+}
+
+fn write_to_worktree(
+    workdir: &Path,
+    change: &gix_apply::Change,
+    pipeline: &mut filter::Pipeline<'_>,
+    fs_caps: gix_fs::Capabilities,
+) -> Result<(), apply::Error> {
+    let path = workdir.join(gix_path::from_bstr(change.location.as_bstr()));
+    let io_err = |path: &Path| {
+        let path = path.to_owned();
+        move |source| apply::Error::Io { source, path }
+    };
+    let Some(entry) = change.entry.as_ref() else {
+        match std::fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(io_err(&path)(err)),
+        }
+        remove_empty_parent_directories(workdir, &path);
+        return Ok(());
+    };
+    if entry.mode == EntryKind::Commit {
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(io_err(parent))?;
+    }
+    // Replace what's there instead of writing through it, as it may be a symlink.
+    match std::fs::symlink_metadata(&path) {
+        Ok(metadata) if !metadata.is_dir() => std::fs::remove_file(&path).map_err(io_err(&path))?,
+        _ => {}
+    }
+    if entry.mode == EntryKind::Link && fs_caps.symlink {
+        let target = gix_path::try_from_byte_slice(&entry.data)
+            .map_err(|err| io_err(&path)(std::io::Error::new(std::io::ErrorKind::InvalidData, err)))?;
+        return gix_fs::symlink::create(target, &path).map_err(io_err(&path));
+    }
+
+    let mut data = pipeline.convert_to_worktree(
+        &entry.data,
+        change.location.as_bstr(),
+        gix_filter::driver::apply::Delay::Forbid,
+    )?;
+    let mut file = std::fs::File::create(&path).map_err(io_err(&path))?;
+    std::io::copy(&mut data, &mut file).map_err(io_err(&path))?;
+    #[cfg(unix)]
+    if fs_caps.executable_bit && entry.mode == EntryKind::BlobExecutable {
+        use std::os::unix::fs::PermissionsExt;
+        let mut permissions = file.metadata().map_err(io_err(&path))?.permissions();
+        permissions.set_mode(permissions.mode() | 0o111);
+        file.set_permissions(permissions).map_err(io_err(&path))?;
+    }
+    Ok(())
+}
+
+/// Return `true` if one of the leading directories of `rela_path` in `workdir` is a symbolic link, which would make
+/// reading or writing it affect files outside of the worktree.
+fn is_beyond_symlink(workdir: &Path, rela_path: &BStr) -> Result<bool, apply::Error> {
+    let Some((dirs, _file_name)) = rela_path.rsplit_once_str("/") else {
+        return Ok(false);
+    };
+    let mut path = workdir.to_owned();
+    for component in dirs.split_str("/") {
+        path.push(gix_path::from_bstr(component.as_bstr()));
+        match std::fs::symlink_metadata(&path) {
+            Ok(metadata) if metadata.is_symlink() => return Ok(true),
+            Ok(_) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => break,
+            Err(source) => return Err(apply::Error::Io { source, path }),
+        }
+    }
+    Ok(false)
+}
+
+/// Remove the parent directories of `path` as long as they are empty, but never `workdir` itself.
+fn remove_empty_parent_directories(workdir: &Path, path: &Path) {
+    let mut dir: Option<PathBuf> = path.parent().map(ToOwned::to_owned);
+    while let Some(current) = dir.filter(|dir| dir.starts_with(workdir) && dir != workdir) {
+        if std::fs::remove_dir(&current).is_err() {
+            break;
+        }
+        dir = current.parent().map(ToOwned::to_owned);
+    }
+}
+
+/// Represent the submodule at `id` just like `git` does in patches.
+fn submodule_entry(id: ObjectId) -> Entry {
+    Entry {
+        mode: EntryKind::Commit,
+        data: format!("Subproject commit {id}\n").into_bytes(),
+    }
+}
+
+/// Parse the content of a patched submodule, which is `Subproject commit <id>`.
+fn submodule_commit(data: &[u8]) -> Option<ObjectId> {
+    let hex = data.strip_prefix(b"Subproject commit ")?.trim_end();
+    ObjectId::from_hex(hex).ok()
+}
@@ -0,0 +1,313 @@
+use std::io::Write;
+
+use gix_hash::ObjectId;
+use gix_ref::transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog};
+
+use crate::{
+    bstr::{BString, ByteSlice},
+    Repository,
+};
+
+pub use gix_revision::bisect::Outcome;
+
+/// The verdict on a commit that was tested while bisecting.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// The commit doesn't have the property that is searched for, like a bug, which means none of its ancestors has it.
+    Good,
+    /// The commit has the property that is searched for, which is the case for all of its descendants as well.
+    Bad,
+    /// The commit can't be tested, and should thus be avoided.
+    Skip,
+}
+
+impl Mark {
+    /// Return the term that `git` uses for this mark.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Mark::Good => "good",
+            Mark::Bad => "bad",
+            Mark::Skip => "skip",
+        }
+    }
+}
+
+/// The state of a bisection as stored in the `refs/bisect/` references of a repository.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct State {
+    /// The bad commit, if one was marked yet.
+    pub bad: Option<ObjectId>,
+    /// All commits which were marked as good.
+    pub good: Vec<ObjectId>,
+    /// All commits which were skipped.
+    pub skip: Vec<ObjectId>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod start {
+    /// The error returned by [Repository::bisect_start()](crate::Repository::bisect_start()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A bisection is in progress already and needs to be reset first")]
+        InProgress,
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error("Could not write the bisection state")]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod mark {
+    /// The error returned by [Repository::bisect_mark()](crate::Repository::bisect_mark()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("No bisection is in progress")]
+        NotStarted,
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        EditReference(#[from] crate::reference::edit::Error),
+        #[error("Could not write the bisection log")]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod state {
+    /// The error returned by [Repository::bisect_state()](crate::Repository::bisect_state()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        IterInit(#[from] crate::reference::iter::init::Error),
+        #[error(transparent)]
+        Iter(#[from] crate::reference::iter::Error),
+        #[error(transparent)]
+        IterItem(#[from] Box<dyn std::error::Error + Send + Sync + 'static>),
+        #[error(transparent)]
+        Peel(#[from] crate::reference::peel::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod next {
+    /// The error returned by [Repository::bisect_next()](crate::Repository::bisect_next()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("No bisection is in progress")]
+        NotStarted,
+        #[error("At least one good and one bad commit are needed to bisect")]
+        MissingGoodOrBad,
+        #[error(transparent)]
+        State(#[from] super::state::Error),
+        #[error(transparent)]
+        Bisect(#[from] gix_revision::bisect::Error),
+        #[error(transparent)]
+        DescribeCommit(#[from] super::mark::Error),
+        #[error("Could not write the bisection log")]
+        Io(#[from] std::io::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod reset {
+    /// The error returned by [Repository::bisect_reset()](crate::Repository::bisect_reset()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        State(#[from] super::state::Error),
+        #[error(transparent)]
+        EditReferences(#[from] crate::reference::edit::Error),
+        #[error("Could not remove the bisection state")]
+        Io(#[from] std::io::Error),
+    }
+}
+
+/// The files in the git directory that `git bisect` uses to keep its state, besides the `refs/bisect/` references.
+const STATE_FILES: &[&str] = &[
+    "BISECT_LOG",
+    "BISECT_START",
+    "BISECT_TERMS",
+    "BISECT_EXPECTED_REV",
+    "BISECT_ANCESTORS_OK",
+    "BISECT_NAMES",
+    "BISECT_RUN",
+    "BISECT_FIRST_PARENT",
+];
+
+/// Bisection, to find the commit which introduced a change by testing commits in between a good and a bad one,
+/// with state that is compatible with `git bisect`.
+///
+/// Note that commits are never checked out, which is left to the caller.
+impl Repository {
+    /// Start a bisection by remembering the current `HEAD` to return to when [resetting](Self::bisect_reset()), like
+    /// `git bisect start` does.
+    ///
+    /// Fails if a bisection is in progress already, as `git` would silently discard its state instead.
+    pub fn bisect_start(&self) -> Result<(), start::Error> {
+        let start = self.path().join("BISECT_START");
+        if start.is_file() {
+            return Err(start::Error::InProgress);
+        }
+        let head = self.head()?;
+        let mut head_name: BString = match (head.referent_name(), head.id()) {
+            (Some(name), _) => name.shorten().to_owned(),
+            (None, Some(id)) => id.to_string().into(),
+            (None, None) => "HEAD".into(),
+        };
+        head_name.push(b'\n');
+        std::fs::write(start, head_name)?;
+        std::fs::write(self.path().join("BISECT_TERMS"), "bad\ngood\n")?;
+        std::fs::write(self.path().join("BISECT_LOG"), "git bisect start\n")?;
+        Ok(())
+    }
+
+    /// Mark the commit with `id` according to `mark` by writing its `refs/bisect/` reference, and record it in the
+    /// bisection log, like `git bisect good|bad|skip <id>` does.
+    ///
+    /// Marking another commit as bad replaces the previous bad commit.
+    pub fn bisect_mark(&self, id: impl Into<ObjectId>, mark: Mark) -> Result<(), mark::Error> {
+        if !self.path().join("BISECT_START").is_file() {
+            return Err(mark::Error::NotStarted);
+        }
+        let id = id.into();
+        let comment = self.bisect_log_comment(mark.as_str(), id)?;
+        let name = match mark {
+            Mark::Bad => "refs/bisect/bad".to_owned(),
+            Mark::Good | Mark::Skip => format!("refs/bisect/{}-{id}", mark.as_str()),
+        };
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    message: format!("bisect: {}", mark.as_str()).into(),
+                    ..Default::default()
+                },
+                expected: PreviousValue::Any,
+                new: gix_ref::Target::Peeled(id),
+            },
+            name: name.try_into().expect("valid reference name"),
+            deref: false,
+        })?;
+        self.append_to_bisect_log(format!("{comment}git bisect {} {id}\n", mark.as_str()).as_bytes())?;
+        Ok(())
+    }
+
+    /// Return the state of the bisection in progress, or `None` if there is none.
+    pub fn bisect_state(&self) -> Result<Option<State>, state::Error> {
+        if !self.path().join("BISECT_START").is_file() {
+            return Ok(None);
+        }
+        let mut state = State::default();
+        for reference in self.references()?.prefixed("refs/bisect/")? {
+            let mut reference = reference?;
+            let id = reference.peel_to_id_in_place()?.detach();
+            let name = reference.name().as_bstr();
+            let name = name.strip_prefix(b"refs/bisect/").unwrap_or(name);
+            if name == b"bad" {
+                state.bad = Some(id);
+            } else if name.starts_with(b"good-") {
+                state.good.push(id);
+            } else if name.starts_with(b"skip-") {
+                state.skip.push(id);
+            }
+        }
+        Ok(Some(state))
+    }
+
+    /// Find the next commit to test in the bisection in progress, by choosing the one which splits the commits that
+    /// are reachable from the bad commit but not from any good commit in halves, and which wasn't skipped.
+    ///
+    /// If the first bad commit was found, or if only skipped commits are left to test, this is recorded in the bisection
+    /// log just like `git bisect` does it.
+    pub fn bisect_next(&self) -> Result<Outcome, next::Error> {
+        let state = self.bisect_state()?.ok_or(next::Error::NotStarted)?;
+        let bad = match state.bad {
+            Some(bad) if !state.good.is_empty() => bad,
+            _ => return Err(next::Error::MissingGoodOrBad),
+        };
+        let outcome = gix_revision::bisect(bad, &state.good, &state.skip, &mut self.revision_graph())?;
+        let log = match &outcome {
+            Outcome::Next { .. } => None,
+            Outcome::FirstBad(id) => Some(self.bisect_log_comment("first bad commit", *id)?),
+            Outcome::OnlySkippedLeft { candidates } => {
+                let mut log = String::from("# only skipped commits left to test\n");
+                for id in candidates {
+                    log.push_str(&self.bisect_log_comment("possible first bad commit", *id)?);
+                }
+                Some(log)
+            }
+        };
+        if let Some(log) = log {
+            let previous_log = std::fs::read(self.path().join("BISECT_LOG"))?;
+            if !previous_log.ends_with(log.as_bytes()) {
+                self.append_to_bisect_log(log.as_bytes())?;
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Stop the bisection in progress by removing all of its references and files, and return the name of the branch or
+    /// the id of the commit that `HEAD` pointed to when it [was started](Self::bisect_start()), like `git bisect reset`
+    /// does, or `None` if there was no bisection in progress.
+    ///
+    /// Note that `HEAD` isn't changed, as checking it out is left to the caller.
+    pub fn bisect_reset(&self) -> Result<Option<BString>, reset::Error> {
+        let start = match std::fs::read(self.path().join("BISECT_START")) {
+            Ok(start) => start,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let names = self
+            .references()
+            .map_err(state::Error::from)?
+            .prefixed("refs/bisect/")
+            .map_err(state::Error::from)?
+            .map(|reference| reference.map(|reference| reference.name().to_owned()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(state::Error::from)?;
+        self.edit_references(names.into_iter().map(|name| RefEdit {
+            change: Change::Delete {
+                expected: PreviousValue::Any,
+                log: RefLog::AndReference,
+            },
+            name,
+            deref: false,
+        }))?;
+        for file in STATE_FILES {
+            match std::fs::remove_file(self.path().join(file)) {
+                Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            }
+        }
+        Ok(Some(start.trim_end().into()))
+    }
+
+    /// Return a comment line for the bisection log, which describes the commit with `id` with `label`.
+    fn bisect_log_comment(&self, label: &str, id: ObjectId) -> Result<String, mark::Error> {
+        let commit = self.find_object(id)?.try_into_commit()?;
+        let summary = commit.message()?.summary();
+        Ok(format!("# {label}: [{id}] {summary}\n"))
+    }
+
+    fn append_to_bisect_log(&self, text: &[u8]) -> Result<(), std::io::Error> {
+        std::fs::OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(self.path().join("BISECT_LOG"))?
+            .write_all(text)
+    }
+}
@@ -0,0 +1,113 @@
+use gix_hash::ObjectId;
+
+use crate::{
+    bstr::{BStr, ByteSlice},
+    config::tree::{Blame, Key},
+    Repository,
+};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod blame_options {
+    use crate::bstr::BString;
+
+    /// The error returned by [Repository::blame_options()](crate::Repository::blame_options()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not interpolate path of '{key}'")]
+        InterpolatePath {
+            source: gix_config::path::interpolate::Error,
+            key: &'static str,
+        },
+        #[error("Could not read the list of commits to ignore from '{}'", path.display())]
+        ReadIgnoreRevsFile {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+        #[error("Line {line_number} of '{}' should contain a full object id, but was '{line}'", path.display())]
+        InvalidIgnoreRev {
+            line: BString,
+            line_number: usize,
+            path: std::path::PathBuf,
+        },
+    }
+}
+
+impl Repository {
+    /// Return the options to use with [`blame_file()`](Self::blame_file()), which ignore the commits listed in the files
+    /// configured with `blame.ignoreRevsFile`.
+    ///
+    /// Relative paths are relative to the work tree, or to the `.git` directory of bare repositories.
+    /// Each line of these files lists a full object id, and comments start with `#`.
+    pub fn blame_options(&self) -> Result<gix_blame::Options, blame_options::Error> {
+        let key = "blame.ignoreRevsFile";
+        debug_assert_eq!(key, Blame::IGNORE_REVS_FILE.logical_name());
+
+        let install_dir = crate::path::install_dir().ok();
+        let home = self.config.home_dir();
+        let ctx = crate::config::cache::interpolate_context(install_dir.as_deref(), home.as_deref());
+        let mut paths = Vec::new();
+        for path in self
+            .config
+            .resolved
+            .strings_filter_by_key(key, &mut self.filter_config_section())
+            .unwrap_or_default()
+        {
+            if path.is_empty() {
+                paths.clear();
+                continue;
+            }
+            let path = gix_config::Path::from(path)
+                .interpolate(ctx)
+                .map_err(|err| blame_options::Error::InterpolatePath { source: err, key })?;
+            paths.push(self.work_dir().unwrap_or(self.git_dir()).join(path));
+        }
+
+        let mut options = gix_blame::Options::default();
+        for path in paths {
+            let content = std::fs::read(&path).map_err(|err| blame_options::Error::ReadIgnoreRevsFile {
+                source: err,
+                path: path.clone(),
+            })?;
+            for (line_number, line) in content.lines().enumerate() {
+                let id = line.split_str("#").next().unwrap_or_default().trim();
+                if id.is_empty() {
+                    continue;
+                }
+                let id = ObjectId::from_hex(id).map_err(|_| blame_options::Error::InvalidIgnoreRev {
+                    line: line.into(),
+                    line_number: line_number + 1,
+                    path: path.clone(),
+                })?;
+                options.ignore_revs.insert(id);
+            }
+        }
+        Ok(options)
+    }
+
+    /// Find the commits that introduced each line of the file at `file_path` as of the commit `suspect`, similar to
+    /// `git blame <suspect> -- <file_path>`.
+    ///
+    /// Use [`blame_options()`](Self::blame_options()) to obtain `options` which respect the configuration of the repository.
+    pub fn blame_file(
+        &self,
+        file_path: &BStr,
+        suspect: impl Into<ObjectId>,
+        options: gix_blame::Options,
+    ) -> Result<gix_blame::Outcome, gix_blame::Error> {
+        gix_blame::file(&self.objects, suspect.into(), file_path, options)
+    }
+
+    /// Like [`blame_file()`](Self::blame_file()), but call `on_entry` with each entry as soon as its lines are attributed to a
+    /// commit, similar to `git blame --incremental <suspect> -- <file_path>`.
+    pub fn blame_file_incremental(
+        &self,
+        file_path: &BStr,
+        suspect: impl Into<ObjectId>,
+        options: gix_blame::Options,
+        on_entry: impl FnMut(gix_blame::BlameEntry),
+    ) -> Result<gix_blame::incremental::Outcome, gix_blame::Error> {
+        gix_blame::incremental(&self.objects, suspect.into(), file_path, options, on_entry)
+    }
+}
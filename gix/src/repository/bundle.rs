@@ -0,0 +1,186 @@
+use std::sync::atomic::AtomicBool;
+
+use gix_features::progress::DynNestedProgress;
+
+use crate::{bstr::BStr, bundle, Repository};
+
+impl Repository {
+    /// Fetch from the bundle file at `path`, similar to `git fetch <bundle> <refspecs>…`, by writing its pack into the
+    /// object database and updating local references according to the fetch `refspecs` that match the references of the bundle.
+    ///
+    /// The same rules as for fetches from remotes apply to reference updates, so non-fast-forward updates are rejected
+    /// unless forced by the respective ref-spec.
+    /// To clone from a bundle, [initialize](crate::init()) a repository and fetch into it with a ref-spec like
+    /// `+refs/heads/*:refs/remotes/origin/*`.
+    ///
+    /// All [prerequisites](gix_bundle::Header::prerequisites) of the bundle must already be present, or nothing is written.
+    #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
+    pub fn fetch_bundle(
+        &self,
+        path: &std::path::Path,
+        refspecs: &[gix_refspec::RefSpec],
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<bundle::fetch::Outcome, bundle::fetch::Error> {
+        use crate::remote::{
+            self,
+            fetch::{refs, DryRun, RefLogMessage, SpecIndex, Tags, WritePackedRefs},
+        };
+        let _span = gix_trace::coarse!("gix::fetch_bundle()", path = ?path);
+        let bundle = gix_bundle::File::at(path)?;
+        let missing = bundle.missing_prerequisites(&self.objects);
+        if !missing.is_empty() {
+            return Err(bundle::fetch::Error::MissingPrerequisites {
+                ids: missing.into_iter().map(|prerequisite| prerequisite.id).collect(),
+            });
+        }
+
+        let options = gix_pack::bundle::write::Options {
+            thread_limit: remote::fetch::config::index_threads(self).map_err(Box::new)?,
+            index_version: remote::fetch::config::pack_index_version(self).map_err(Box::new)?,
+            iteration_mode: gix_pack::data::input::Mode::Verify,
+            object_hash: self.object_hash(),
+        };
+        let write_pack_bundle = bundle.write_pack_to_directory(
+            Some(&self.objects.store_ref().path().join("pack")),
+            progress,
+            should_interrupt,
+            Some(self.objects.clone()),
+            options,
+        )?;
+
+        let refs: Vec<_> = bundle
+            .header()
+            .refs
+            .iter()
+            .map(|r| gix_protocol::handshake::Ref::Direct {
+                full_ref_name: r.name.clone(),
+                object: r.target,
+            })
+            .collect();
+        let group = gix_refspec::MatchGroup::from_fetch_specs(refspecs.iter().map(gix_refspec::RefSpec::to_ref));
+        let (outcome, _fixes) = group
+            .match_remotes(refs.iter().map(|r| {
+                let (full_ref_name, target, object) = r.unpack();
+                gix_refspec::match_group::Item {
+                    full_ref_name,
+                    target: target.expect("direct refs always have a target"),
+                    object,
+                }
+            }))
+            .validated()?;
+        let mappings: Vec<_> = outcome
+            .mappings
+            .into_iter()
+            .filter_map(|m| {
+                Some(remote::fetch::Mapping {
+                    remote: remote::fetch::Source::Ref(refs[m.item_index?].clone()),
+                    local: m.rhs.map(std::borrow::Cow::into_owned),
+                    spec_index: SpecIndex::ExplicitInRemote(m.spec_index),
+                })
+            })
+            .collect();
+
+        let update_refs = refs::update(
+            self,
+            RefLogMessage::Prefixed { action: "fetch".into() },
+            &mappings,
+            refspecs,
+            &[],
+            Tags::None,
+            DryRun::No,
+            WritePackedRefs::Never,
+        )?;
+        Ok(bundle::fetch::Outcome {
+            header: bundle.header().clone(),
+            write_pack_bundle,
+            mappings,
+            update_refs,
+        })
+    }
+
+    /// Write a bundle with all references and objects selected by the revision `specs` to `out`, similar to
+    /// `git bundle create <file> <specs>…`.
+    ///
+    /// Each of the `specs` can be a reference like `main`, an exclusion like `^v1`, or a range like `v1..main` or `v1...main`.
+    /// Only revisions that are named by a reference end up as references in the bundle, while everything that is excluded
+    /// determines the prerequisites of the bundle.
+    /// An error is returned if no reference remains to be written, as the bundle would be empty.
+    ///
+    /// Replacement objects are ignored, and see [`gix_bundle::write_to()`] for details on all other parameters.
+    pub fn write_bundle<'a>(
+        &self,
+        out: &mut dyn std::io::Write,
+        specs: impl IntoIterator<Item = &'a BStr>,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        options: gix_bundle::write::Options,
+    ) -> Result<gix_bundle::write::Outcome, bundle::write::Error> {
+        let _span = gix_trace::coarse!("gix::write_bundle()");
+        let mut refs = Vec::new();
+        let mut excluded = Vec::new();
+        for spec in specs {
+            let spec = self.rev_parse(spec)?;
+            let (first, second) = (spec.first_reference().cloned(), spec.second_reference().cloned());
+            match spec.detach() {
+                gix_revision::Spec::Include(id) => refs.extend(self.bundle_ref(first, id)),
+                gix_revision::Spec::Exclude(id) => excluded.push(id),
+                gix_revision::Spec::Range { from, to } => {
+                    excluded.push(from);
+                    refs.extend(self.bundle_ref(second, to));
+                }
+                gix_revision::Spec::Merge { theirs, ours } => {
+                    excluded.extend(
+                        self.merge_bases_many(theirs, Some(ours))?
+                            .into_iter()
+                            .map(crate::Id::detach),
+                    );
+                    refs.extend(self.bundle_ref(first, theirs));
+                    refs.extend(self.bundle_ref(second, ours));
+                }
+                gix_revision::Spec::ExcludeParents(id) => {
+                    excluded.extend(
+                        self.find_object(id)?
+                            .try_into_commit()?
+                            .parent_ids()
+                            .map(crate::Id::detach),
+                    );
+                    refs.extend(self.bundle_ref(first, id));
+                }
+                gix_revision::Spec::IncludeOnlyParents(_) => {}
+            }
+        }
+
+        let mut objects = self
+            .objects
+            .clone()
+            .into_arc()
+            .map_err(bundle::write::Error::ShareObjectDatabase)?;
+        objects.prevent_pack_unload();
+        objects.ignore_replacements = true;
+        Ok(gix_bundle::write_to(
+            out,
+            refs,
+            &excluded,
+            objects,
+            progress,
+            should_interrupt,
+            options,
+        )?)
+    }
+
+    /// Return the bundle reference for `reference` if it was used to name `id`, following symbolic references.
+    fn bundle_ref(&self, reference: Option<gix_ref::Reference>, id: gix_hash::ObjectId) -> Option<gix_bundle::Ref> {
+        let reference = reference?;
+        let name = reference.name.as_bstr().to_owned();
+        let mut reference = crate::Reference::from_ref(reference, self);
+        loop {
+            match reference.try_id() {
+                Some(target) => {
+                    return (target == id).then_some(gix_bundle::Ref { name, target: id });
+                }
+                None => reference = reference.follow()?.ok()?,
+            }
+        }
+    }
+}
@@ -49,7 +49,7 @@ impl crate::Repository {
     ) -> Result<gix_protocol::transport::client::ssh::connect::Options, config::ssh_connect_options::Error> {
         use crate::config::{
             cache::util::ApplyLeniency,
-            tree::{gitoxide, Core, Ssh},
+            tree::{gitoxide, Core, Key, Ssh},
         };
 
         let config = &self.config.resolved;
@@ -67,6 +67,7 @@ impl crate::Repository {
                 )
             })
             .map(|cmd| gix_path::from_bstr(cmd).into_owned().into());
+        let lenient = self.options.lenient_config;
         let opts = gix_protocol::transport::client::ssh::connect::Options {
             disallow_shell: fallback_active,
             command: ssh_command,
@@ -74,11 +75,76 @@ impl crate::Repository {
                 .string_filter_by_key("ssh.variant", &mut trusted)
                 .and_then(|variant| Ssh::VARIANT.try_into_variant(variant).transpose())
                 .transpose()
-                .with_leniency(self.options.lenient_config)?,
+                .with_leniency(lenient)?,
+            native: {
+                let key = &gitoxide::Ssh::NATIVE;
+                config
+                    .boolean_filter_by_key(key.logical_name().as_str(), &mut trusted)
+                    .map(|value| key.enrich_error(value))
+                    .transpose()
+                    .with_leniency(lenient)?
+                    .unwrap_or_default()
+                    .then(|| self.native_ssh_connect_options())
+                    .transpose()?
+            },
         };
         Ok(opts)
     }
 
+    #[cfg(feature = "blocking-network-client")]
+    fn native_ssh_connect_options(
+        &self,
+    ) -> Result<gix_protocol::transport::client::ssh::native::Options, config::ssh_connect_options::Error> {
+        use crate::config::{
+            cache::util::ApplyLeniency,
+            tree::{gitoxide, Key},
+        };
+
+        let config = &self.config.resolved;
+        let mut trusted = self.filter_config_section();
+        let lenient = self.options.lenient_config;
+        let install_dir = crate::path::install_dir().ok();
+        let home = self.config.home_dir();
+        let ctx = config::cache::interpolate_context(install_dir.as_deref(), home.as_deref());
+        let interpolate = |key: &'static str, path: gix_config::Path<'_>| {
+            path.interpolate(ctx)
+                .map(std::borrow::Cow::into_owned)
+                .map_err(|err| config::ssh_connect_options::Error::InterpolatePath { source: err, key })
+        };
+
+        let mut opts = gix_protocol::transport::client::ssh::native::Options::default();
+        if let Some(check) = config
+            .string_filter_by_key(
+                gitoxide::Ssh::STRICT_HOST_KEY_CHECKING.logical_name().as_str(),
+                &mut trusted,
+            )
+            .map(|value| gitoxide::Ssh::STRICT_HOST_KEY_CHECKING.try_into_host_key_check(value))
+            .transpose()
+            .with_leniency(lenient)?
+        {
+            opts.host_key_check = check;
+        }
+        let key = "gitoxide.ssh.knownHostsFile";
+        debug_assert_eq!(key, gitoxide::Ssh::KNOWN_HOSTS_FILE.logical_name());
+        if let Some(path) = config.path_filter_by_key(key, &mut trusted) {
+            opts.known_hosts_files.push(interpolate(key, path)?);
+        }
+        let key = "gitoxide.ssh.identityFile";
+        debug_assert_eq!(key, gitoxide::Ssh::IDENTITY_FILE.logical_name());
+        for path in config.strings_filter_by_key(key, &mut trusted).unwrap_or_default() {
+            opts.identity_files.push(interpolate(key, path.into())?);
+        }
+        if let Some(use_agent) = config
+            .boolean_filter_by_key(gitoxide::Ssh::USE_AGENT.logical_name().as_str(), &mut trusted)
+            .map(|value| gitoxide::Ssh::USE_AGENT.enrich_error(value))
+            .transpose()
+            .with_leniency(lenient)?
+        {
+            opts.use_agent = use_agent;
+        }
+        Ok(opts)
+    }
+
     /// Return the context to be passed to any spawned program that is supposed to interact with the repository, like
     /// hooks or filters.
     #[cfg(feature = "attributes")]
@@ -101,19 +101,11 @@ impl crate::Repository {
                     }
 
                     fn ssl_version(
-                        config: &gix_config::File<'static>,
-                        key_str: &'static str,
+                        value: Option<Cow<'_, BStr>>,
                         key: &'static config::tree::http::SslVersion,
-                        mut filter: fn(&gix_config::file::Metadata) -> bool,
                         lenient: bool,
                     ) -> Result<Option<SslVersion>, config::transport::Error> {
-                        debug_assert_eq!(
-                            key_str,
-                            key.logical_name(),
-                            "BUG: hardcoded and generated key names must match"
-                        );
-                        config
-                            .string_filter_by_key(key_str, &mut filter)
+                        value
                             .filter(|v| !v.is_empty())
                             .map(|v| {
                                 key.try_into_ssl_version(v)
@@ -351,57 +343,64 @@ impl crate::Repository {
                             .unwrap_or(true)
                     };
 
-                    if may_use_cainfo {
-                        let key = "http.sslCAInfo";
-                        debug_assert_eq!(key, config::tree::Http::SSL_CA_INFO.logical_name());
-                        opts.ssl_ca_info = config
-                            .path_filter_by_key(key, &mut trusted_only)
+                    // TLS settings may be specific to the url, as configured in `http.<url>.<key>`.
+                    let http_value =
+                        |key: &str| config::url_match::value(config, "http", key, &url, self.filter_config_section());
+                    let ssl_path = |key: &'static config::tree::keys::Path,
+                                    key_str: &'static str|
+                     -> Result<_, config::transport::Error> {
+                        debug_assert_eq!(key_str, key.logical_name());
+                        http_value(key.name)
                             .map(|p| {
                                 use crate::config::cache::interpolate_context;
-                                p.interpolate(interpolate_context(
-                                    self.install_dir().ok().as_deref(),
-                                    self.config.home_dir().as_deref(),
-                                ))
-                                .map(std::borrow::Cow::into_owned)
+                                gix_config::Path::from(p)
+                                    .interpolate(interpolate_context(
+                                        self.install_dir().ok().as_deref(),
+                                        self.config.home_dir().as_deref(),
+                                    ))
+                                    .map(std::borrow::Cow::into_owned)
                             })
                             .transpose()
                             .with_leniency(lenient)
-                            .map_err(|err| config::transport::Error::InterpolatePath { source: err, key })?;
+                            .map_err(|err| config::transport::Error::InterpolatePath {
+                                source: err,
+                                key: key_str,
+                            })
+                    };
+                    if may_use_cainfo {
+                        opts.ssl_ca_info = ssl_path(&config::tree::Http::SSL_CA_INFO, "http.sslCAInfo")?;
                     }
+                    opts.ssl_cert = ssl_path(&config::tree::Http::SSL_CERT, "http.sslCert")?;
+                    opts.ssl_key = ssl_path(&config::tree::Http::SSL_KEY, "http.sslKey")?;
 
                     {
                         opts.ssl_version = ssl_version(
-                            config,
-                            "http.sslVersion",
+                            http_value(config::tree::Http::SSL_VERSION.name),
                             &config::tree::Http::SSL_VERSION,
-                            trusted_only,
                             lenient,
                         )?
                         .map(|v| SslVersionRangeInclusive { min: v, max: v });
-                        let min_max = ssl_version(
-                            config,
-                            "gitoxide.http.sslVersionMin",
+                        let min = ssl_version(
+                            config.string_filter_by_key("gitoxide.http.sslVersionMin", &mut trusted_only),
                             &gitoxide::Http::SSL_VERSION_MIN,
-                            trusted_only,
                             lenient,
-                        )
-                        .and_then(|min| {
-                            ssl_version(
-                                config,
-                                "gitoxide.http.sslVersionMax",
-                                &gitoxide::Http::SSL_VERSION_MAX,
-                                trusted_only,
-                                lenient,
-                            )
-                            .map(|max| min.and_then(|min| max.map(|max| (min, max))))
-                        })?;
-                        if let Some((min, max)) = min_max {
+                        )?;
+                        let max = ssl_version(
+                            config.string_filter_by_key("gitoxide.http.sslVersionMax", &mut trusted_only),
+                            &gitoxide::Http::SSL_VERSION_MAX,
+                            lenient,
+                        )?;
+                        if min.is_some() || max.is_some() {
                             let v = opts.ssl_version.get_or_insert(SslVersionRangeInclusive {
-                                min: SslVersion::TlsV1_3,
+                                min: SslVersion::Default,
                                 max: SslVersion::TlsV1_3,
                             });
-                            v.min = min;
-                            v.max = max;
+                            if let Some(min) = min {
+                                v.min = min;
+                            }
+                            if let Some(max) = max {
+                                v.max = max;
+                            }
                         }
                     }
 
@@ -418,10 +417,9 @@ impl crate::Repository {
                         if ssl_no_verify {
                             opts.ssl_verify = false;
                         } else {
-                            let key = "http.sslVerify";
-                            opts.ssl_verify = config
-                                .boolean_filter_by_key(key, &mut trusted_only)
-                                .map(|value| config::tree::Http::SSL_VERIFY.enrich_error(value))
+                            let key = &config::tree::Http::SSL_VERIFY;
+                            opts.ssl_verify = http_value(key.name)
+                                .map(|value| key.enrich_error(gix_config::Boolean::try_from(value).map(|b| b.0)))
                                 .transpose()
                                 .with_leniency(lenient)
                                 .map_err(config::transport::http::Error::from)?
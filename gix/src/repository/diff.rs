@@ -1,4 +1,6 @@
-use crate::Repository;
+use gix_hash::ObjectId;
+
+use crate::{bstr::ByteSlice, Repository};
 
 ///
 #[allow(clippy::empty_docs)]
@@ -16,6 +18,99 @@ pub mod resource_cache {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+pub mod range_diff {
+    use gix_hash::ObjectId;
+
+    /// The outcome of [Repository::range_diff()](super::Repository::range_diff()).
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Outcome {
+        /// The commits of the old series that were compared, in order and without merge commits.
+        pub old: Vec<ObjectId>,
+        /// The commits of the new series that were compared, in order and without merge commits.
+        pub new: Vec<ObjectId>,
+        /// The way the commits of both series relate to each other, with indices referring to `old` and `new`,
+        /// in the order in which `git range-diff` shows them.
+        pub pairs: Vec<gix_diff::range_diff::Pair>,
+    }
+
+    /// The error returned by [Repository::range_diff()](super::Repository::range_diff()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error(transparent)]
+        CommitTree(#[from] crate::object::commit::Error),
+        #[error(transparent)]
+        ResourceCache(#[from] super::resource_cache::Error),
+        #[error(transparent)]
+        Rewrites(#[from] crate::diff::new_rewrites::Error),
+        #[error(transparent)]
+        Diff(#[from] crate::object::tree::diff::for_each::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod patch_id {
+    /// The error returned by [Repository::patch_id()](super::Repository::patch_id()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        ObjectKind(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        CommitTree(#[from] crate::object::commit::Error),
+        #[error(transparent)]
+        ResourceCache(#[from] super::resource_cache::Error),
+        #[error(transparent)]
+        Rewrites(#[from] crate::diff::new_rewrites::Error),
+        #[error(transparent)]
+        Diff(#[from] crate::object::tree::diff::for_each::Error),
+    }
+}
+
+///
+#[cfg(feature = "revision")]
+#[allow(clippy::empty_docs)]
+pub mod cherry {
+    use gix_hash::ObjectId;
+
+    /// A commit as returned by [Repository::cherry()](super::Repository::cherry()).
+    #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+    pub struct Commit {
+        /// The id of the commit which isn't reachable from the upstream commit.
+        pub id: ObjectId,
+        /// The id of a commit only reachable from the upstream commit which introduces the same change, as determined
+        /// by their [patch-ids](super::Repository::patch_id()).
+        ///
+        /// If `Some`, the commit was applied upstream already, which is marked with `-` by `git cherry`.
+        pub upstream_equivalent: Option<ObjectId>,
+    }
+
+    /// The error returned by [Repository::cherry()](super::Repository::cherry()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Walk(#[from] crate::revision::walk::Error),
+        #[error(transparent)]
+        Traverse(#[from] gix_traverse::commit::simple::Error),
+        #[error(transparent)]
+        ResourceCache(#[from] super::resource_cache::Error),
+        #[error(transparent)]
+        PatchId(#[from] super::patch_id::Error),
+    }
+}
+
 /// Diff-utilities
 impl Repository {
     /// Create a resource cache for diffable objects, and configured with everything it needs to know to perform diffs
@@ -49,4 +144,184 @@ impl Repository {
             worktree_roots,
         )?)
     }
+
+    /// Pair up the commits of the `old` and `new` version of a series of commits by the similarity of their patches,
+    /// and compute how paired patches differ, just like `git range-diff` does, using `options` for configuration.
+    ///
+    /// Commits are expected in the order in which they were applied, oldest first, like `git rev-list --reverse <base>..<tip>`
+    /// produces them, and merge commits are skipped.
+    /// The patch of each commit consists of its message and its diff to its first parent, in which `index` lines and
+    /// the line numbers of hunk headers are omitted so that the same change in another place compares equal.
+    pub fn range_diff(
+        &self,
+        old: impl IntoIterator<Item = impl Into<ObjectId>>,
+        new: impl IntoIterator<Item = impl Into<ObjectId>>,
+        options: gix_diff::range_diff::Options,
+    ) -> Result<range_diff::Outcome, range_diff::Error> {
+        let mut resource_cache = self.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        let mut series = |commits: Vec<ObjectId>| -> Result<(Vec<ObjectId>, Vec<Vec<u8>>), range_diff::Error> {
+            let mut ids = Vec::with_capacity(commits.len());
+            let mut patches = Vec::with_capacity(commits.len());
+            for id in commits {
+                if let Some(patch) = self.range_diff_patch(id, &mut resource_cache)? {
+                    ids.push(id);
+                    patches.push(patch);
+                }
+            }
+            Ok((ids, patches))
+        };
+        let (old, old_patches) = series(old.into_iter().map(Into::into).collect())?;
+        let (new, new_patches) = series(new.into_iter().map(Into::into).collect())?;
+        Ok(range_diff::Outcome {
+            pairs: gix_diff::range_diff::pair_patches(&old_patches, &new_patches, options),
+            old,
+            new,
+        })
+    }
+
+    /// Compute the patch-id of the commit with `id` from its diff to its first parent, just like
+    /// `git show <id> | git patch-id --stable` does, or return `None` if it's a merge commit or if it doesn't change anything.
+    ///
+    /// Commits with the same patch-id introduce the same change, even if it was applied to another place in the file, with
+    /// different whitespace, or to a different version of the file.
+    /// Renames aren't tracked, and binary files are identified by their object ids.
+    pub fn patch_id(&self, id: impl Into<ObjectId>) -> Result<Option<ObjectId>, patch_id::Error> {
+        let mut resource_cache = self.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        self.patch_id_inner(id.into(), &mut resource_cache)
+    }
+
+    /// Find the commits reachable from `head` but not from `upstream`, and determine which of them were applied to `upstream`
+    /// already by comparing their [patch-ids](Self::patch_id()) to the ones of commits reachable from `upstream` but not from
+    /// `head`, like `git cherry <upstream> <head>` does.
+    ///
+    /// The commits are returned from the oldest to the newest by commit time, and merge commits are skipped.
+    /// Commits that don't change anything are never considered to be applied.
+    #[cfg(feature = "revision")]
+    pub fn cherry(
+        &self,
+        upstream: impl Into<ObjectId>,
+        head: impl Into<ObjectId>,
+    ) -> Result<Vec<cherry::Commit>, cherry::Error> {
+        let (upstream, head) = (upstream.into(), head.into());
+        let mut resource_cache = self.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        let mut patch_ids = |tip: ObjectId, hidden: ObjectId| -> Result<Vec<_>, cherry::Error> {
+            let mut out = Vec::new();
+            for info in self
+                .rev_walk(Some(tip))
+                .hide(Some(hidden))
+                .sorting(gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst)
+                .all()?
+            {
+                let info = info?;
+                if info.parent_ids().count() > 1 {
+                    continue;
+                }
+                out.push((info.id, self.patch_id_inner(info.id, &mut resource_cache)?));
+            }
+            Ok(out)
+        };
+
+        let upstream_patch_ids: gix_hashtable::HashMap<_, _> = patch_ids(upstream, head)?
+            .into_iter()
+            .filter_map(|(id, patch_id)| patch_id.map(|patch_id| (patch_id, id)))
+            .collect();
+        Ok(patch_ids(head, upstream)?
+            .into_iter()
+            .rev()
+            .map(|(id, patch_id)| cherry::Commit {
+                id,
+                upstream_equivalent: patch_id.and_then(|patch_id| upstream_patch_ids.get(&patch_id).copied()),
+            })
+            .collect())
+    }
+
+    fn patch_id_inner(
+        &self,
+        id: ObjectId,
+        resource_cache: &mut gix_diff::blob::Platform,
+    ) -> Result<Option<ObjectId>, patch_id::Error> {
+        let commit = self.find_object(id)?.try_into_commit()?;
+        let parents: Vec<_> = commit.parent_ids().collect();
+        if parents.len() > 1 {
+            return Ok(None);
+        }
+        let tree = commit.tree()?;
+        let parent_tree = match parents.first() {
+            Some(parent) => parent.object()?.try_into_commit()?.tree()?,
+            None => self.empty_tree(),
+        };
+        let mut patch = Vec::new();
+        parent_tree
+            .changes()?
+            .track_path()
+            .track_rewrites(None)
+            .for_each_to_obtain_tree(
+                &tree,
+                |change| -> Result<_, crate::object::tree::diff::change::write_patch::Error> {
+                    change.write_patch(
+                        resource_cache,
+                        crate::object::tree::diff::change::write_patch::Options {
+                            binary: true,
+                            ..Default::default()
+                        },
+                        &mut patch,
+                    )?;
+                    Ok(crate::object::tree::diff::Action::Continue)
+                },
+            )?;
+        resource_cache.clear_resource_cache();
+        Ok(gix_diff::patch_id::from_patch(&patch, self.object_hash()))
+    }
+
+    /// Produce the patch of the commit with `id` for comparison in a range-diff, or `None` if it's a merge commit.
+    fn range_diff_patch(
+        &self,
+        id: ObjectId,
+        resource_cache: &mut gix_diff::blob::Platform,
+    ) -> Result<Option<Vec<u8>>, range_diff::Error> {
+        let commit = self.find_object(id)?.try_into_commit()?;
+        let parents: Vec<_> = commit.parent_ids().collect();
+        if parents.len() > 1 {
+            return Ok(None);
+        }
+        let mut out = Vec::new();
+        for line in commit.message_raw()?.lines() {
+            out.extend_from_slice(b"    ");
+            out.extend_from_slice(line);
+            out.push(b'\n');
+        }
+        out.push(b'\n');
+
+        let tree = commit.tree()?;
+        let parent_tree = match parents.first() {
+            Some(parent) => parent.object()?.try_into_commit()?.tree()?,
+            None => self.empty_tree(),
+        };
+        let mut patch = Vec::new();
+        parent_tree.changes()?.track_path().for_each_to_obtain_tree(
+            &tree,
+            |change| -> Result<_, crate::object::tree::diff::change::write_patch::Error> {
+                change.write_patch(resource_cache, Default::default(), &mut patch)?;
+                Ok(crate::object::tree::diff::Action::Continue)
+            },
+        )?;
+        resource_cache.clear_resource_cache();
+
+        for line in patch.lines_with_terminator() {
+            if line.starts_with(b"index ") {
+                continue;
+            }
+            match line
+                .strip_prefix(b"@@ ")
+                .and_then(|rest| rest.find(b" @@").map(|pos| &rest[pos + 3..]))
+            {
+                Some(section) => {
+                    out.extend_from_slice(b"@@");
+                    out.extend_from_slice(section);
+                }
+                None => out.extend_from_slice(line),
+            }
+        }
+        Ok(Some(out))
+    }
 }
@@ -7,13 +7,7 @@ impl crate::Repository {
     /// It will use the `index.threads` configuration key to learn how many threads to use.
     /// Note that it may fail if there is no index.
     pub fn open_index(&self) -> Result<gix_index::File, worktree::open_index::Error> {
-        let thread_limit = self
-            .config
-            .resolved
-            .string("index", None, "threads")
-            .map(|value| crate::config::tree::Index::THREADS.try_into_index_threads(value))
-            .transpose()
-            .with_lenient_default(self.config.lenient_config)?;
+        let thread_limit = self.index_thread_limit()?;
         let skip_hash = self
             .config
             .resolved
@@ -23,7 +17,7 @@ impl crate::Repository {
             .with_lenient_default(self.config.lenient_config)?
             .unwrap_or_default();
 
-        let index = gix_index::File::at(
+        let mut index = gix_index::File::at(
             self.index_path(),
             self.object_hash(),
             skip_hash,
@@ -33,10 +27,79 @@ impl crate::Repository {
                 expected_checksum: None,
             },
         )?;
+        self.refresh_fs_monitor(&mut index);
 
         Ok(index)
     }
 
+    /// Return the amount of threads to use when reading the index, or to prepare the index for when writing it,
+    /// as configured by `index.threads`.
+    pub(crate) fn index_thread_limit(&self) -> Result<Option<usize>, crate::config::key::GenericErrorWithValue> {
+        self.config
+            .resolved
+            .string("index", None, "threads")
+            .map(|value| crate::config::tree::Index::THREADS.try_into_index_threads(value))
+            .transpose()
+            .with_lenient_default(self.config.lenient_config)
+    }
+
+    /// Like `git`, ask the hook configured in `core.fsmonitor` which paths changed since the token stored in the index,
+    /// so that only those entries have to be checked on disk, or forget all filesystem monitor information if there
+    /// is no such hook.
+    ///
+    /// Failures are not fatal, but cause all entries to be considered changed.
+    fn refresh_fs_monitor(&self, index: &mut gix_index::File) {
+        use crate::fs_monitor::{Changes, Token, Version};
+        let hook = self
+            .config_snapshot()
+            .trusted_program("core.fsmonitor")
+            .filter(|value| {
+                gix_path::os_str_into_bstr(value).map_or(true, |value| gix_config::Boolean::try_from(value).is_err())
+            })
+            .map(|value| std::path::PathBuf::from(value.into_owned()));
+        let (hook, worktree) = match (hook, self.work_dir()) {
+            (Some(hook), Some(worktree)) => (hook, worktree),
+            _ => {
+                if index.fs_monitor().is_some() {
+                    index.set_fs_monitor(None);
+                }
+                return;
+            }
+        };
+        let versions: &[Version] = match self.config.resolved.integer("core", None, "fsmonitorHookVersion") {
+            Some(Ok(1)) => &[Version::V1],
+            Some(Ok(2)) => &[Version::V2],
+            _ => &[Version::V2, Version::V1],
+        };
+
+        let previous_token = index.fs_monitor().map(|fs_monitor| fs_monitor.token().clone());
+        for version in versions {
+            let token = match (version, &previous_token) {
+                (_, Some(token)) => token.clone(),
+                (Version::V1, None) => Token::V1 { nanos_since_1970: 0 },
+                (Version::V2, None) => Token::V2 {
+                    token: "builtin:fake".into(),
+                },
+            };
+            match crate::fs_monitor::query_hook(&hook, worktree, *version, &token) {
+                Ok(response) => {
+                    let changes = if previous_token.is_some() {
+                        response.changes
+                    } else {
+                        Changes::All
+                    };
+                    index.update_fs_monitor(response.token, changes);
+                    return;
+                }
+                Err(_err) => {
+                    gix_trace::warn!("fsmonitor hook failed with protocol {version:?}: {_err}");
+                }
+            }
+        }
+        let token = previous_token.unwrap_or(Token::V1 { nanos_since_1970: 0 });
+        index.update_fs_monitor(token, Changes::All);
+    }
+
     /// Return a shared worktree index which is updated automatically if the in-memory snapshot has become stale as the underlying file
     /// on disk has changed.
     ///
@@ -0,0 +1,238 @@
+use gix_hash::ObjectId;
+use gix_merge::blob::builtin_driver::text::Labels;
+
+use crate::{
+    config::{cache::util::ApplyLeniency, tree::Merge},
+    diff::rename::Tracking,
+    Repository,
+};
+
+///
+#[allow(clippy::empty_docs)]
+pub mod tree_merge_options {
+    /// The error returned by [Repository::tree_merge_options()](crate::Repository::tree_merge_options()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ConflictStyle(#[from] crate::config::key::GenericErrorWithValue),
+        #[error(transparent)]
+        Renames(#[from] crate::config::key::GenericError),
+        #[error(transparent)]
+        RenameLimit(#[from] crate::config::unsigned_integer::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge_resource_cache {
+    /// The error returned by [Repository::merge_resource_cache()](crate::Repository::merge_resource_cache()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        AttributeStack(#[from] crate::config::attribute_stack::Error),
+        #[error(transparent)]
+        ResourceCache(#[from] crate::diff::resource_cache::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod blob_merge_platform {
+    /// The error returned by [Repository::blob_merge_platform()](crate::Repository::blob_merge_platform()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        AttributeStack(#[from] crate::config::attribute_stack::Error),
+        #[error(transparent)]
+        CommandContext(#[from] crate::config::command_context::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge_trees {
+    /// The error returned by [Repository::merge_trees()](crate::Repository::merge_trees()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ResourceCache(#[from] super::merge_resource_cache::Error),
+        #[error(transparent)]
+        BlobMergePlatform(#[from] super::blob_merge_platform::Error),
+        #[error(transparent)]
+        Merge(#[from] gix_merge::tree::merge::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod merge_commits {
+    /// The error returned by [Repository::merge_commits()](crate::Repository::merge_commits()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        ResourceCache(#[from] super::merge_resource_cache::Error),
+        #[error(transparent)]
+        BlobMergePlatform(#[from] super::blob_merge_platform::Error),
+        #[error(transparent)]
+        Merge(#[from] gix_merge::commit::merge::Error),
+    }
+}
+
+/// Merge-utilities
+impl Repository {
+    /// Return options for [merging trees](Self::merge_trees()) as configured by `merge.conflictStyle`, `merge.renames`
+    /// and `merge.renameLimit`, the latter two falling back to `diff.renames` and `diff.renameLimit` respectively.
+    ///
+    /// Just like `git`, renames are detected unless this is turned off explicitly. Note that copies are never detected.
+    pub fn tree_merge_options(&self) -> Result<gix_merge::tree::Options, tree_merge_options::Error> {
+        let config = &self.config.resolved;
+        let lenient = self.config.lenient_config;
+
+        let mut options = gix_merge::tree::Options::default();
+        if let Some(style) = config
+            .string_by_key("merge.conflictStyle")
+            .map(|value| Merge::CONFLICT_STYLE.try_into_conflict_style(value))
+            .transpose()
+            .with_leniency(lenient)?
+        {
+            options.blob_merge.text.conflict = gix_merge::blob::builtin_driver::text::Conflict::Keep {
+                style,
+                marker_size: gix_merge::blob::builtin_driver::text::Conflict::DEFAULT_MARKER_SIZE
+                    .try_into()
+                    .expect("non-zero"),
+            };
+        }
+
+        let renames = match config.boolean_by_key("merge.renames") {
+            Some(value) => Some(Merge::RENAMES.try_into_renames(value)),
+            None => config
+                .boolean_by_key("diff.renames")
+                .map(|value| crate::config::tree::Diff::RENAMES.try_into_renames(value)),
+        }
+        .transpose()
+        .with_leniency(lenient)?
+        .unwrap_or(Tracking::Renames);
+        if renames != Tracking::Disabled {
+            let limit = config
+                .integer_by_key("merge.renameLimit")
+                .map(|value| Merge::RENAME_LIMIT.try_into_usize(value))
+                .or_else(|| {
+                    config
+                        .integer_by_key("diff.renameLimit")
+                        .map(|value| crate::config::tree::Diff::RENAME_LIMIT.try_into_usize(value))
+                })
+                .transpose()
+                .with_leniency(lenient)?;
+            let default = gix_diff::Rewrites::default();
+            options.rewrites = Some(gix_diff::Rewrites {
+                copies: None,
+                limit: limit.unwrap_or(default.limit),
+                ..default
+            });
+        }
+        Ok(options)
+    }
+
+    /// Merge the changes `our_tree` and `their_tree` made to `ancestor_tree`, and write the merged tree along with all
+    /// merged blobs to the object database, using `labels` in conflict markers.
+    ///
+    /// Use [`tree_merge_options()`](Self::tree_merge_options()) to obtain `options` as configured.
+    /// Conflicts are resolved in a way that allows writing the merged tree, and are returned as part of the outcome.
+    /// Files changed by both sides are merged with the driver selected by their `merge` attribute, see
+    /// [`blob_merge_platform()`](Self::blob_merge_platform()).
+    /// Neither the index nor the worktree are touched, and `.gitattributes` are read from the current index, if present.
+    pub fn merge_trees(
+        &self,
+        ancestor_tree: impl Into<ObjectId>,
+        our_tree: impl Into<ObjectId>,
+        their_tree: impl Into<ObjectId>,
+        labels: Labels<'_>,
+        options: gix_merge::tree::Options,
+    ) -> Result<gix_merge::tree::Outcome, merge_trees::Error> {
+        let mut diff_cache = self.merge_resource_cache()?;
+        let mut blob_merge = self.blob_merge_platform()?;
+        Ok(gix_merge::tree::merge(
+            &ancestor_tree.into(),
+            &our_tree.into(),
+            &their_tree.into(),
+            labels,
+            &self.objects,
+            |object| self.write_object_dyn(object),
+            &mut diff_cache,
+            &mut blob_merge,
+            options,
+        )?)
+    }
+
+    /// Merge `their_commit` into `our_commit` by merging their trees with the tree of their merge-base, and write the merged
+    /// tree along with all merged blobs to the object database, using `labels` in conflict markers.
+    ///
+    /// If there are multiple merge-bases, as is the case in criss-cross histories, these are merged recursively into a virtual
+    /// merge-base first, just like `git` does.
+    /// Use [`tree_merge_options()`](Self::tree_merge_options()) to obtain the tree-merge `options` as configured.
+    /// Neither the index nor the worktree are touched, and `.gitattributes` are read from the current index, if present.
+    pub fn merge_commits(
+        &self,
+        our_commit: impl Into<ObjectId>,
+        their_commit: impl Into<ObjectId>,
+        labels: Labels<'_>,
+        options: gix_merge::commit::Options,
+    ) -> Result<gix_merge::commit::Outcome, merge_commits::Error> {
+        let mut diff_cache = self.merge_resource_cache()?;
+        let mut blob_merge = self.blob_merge_platform()?;
+        Ok(gix_merge::commit::merge(
+            our_commit.into(),
+            their_commit.into(),
+            labels,
+            &mut self.revision_graph(),
+            &self.objects,
+            |object| self.write_object_dyn(object),
+            &mut diff_cache,
+            &mut blob_merge,
+            options,
+        )?)
+    }
+
+    /// Create a resource cache for finding renamed entries while merging trees, configured just like `git` would.
+    ///
+    /// Resources are always read from the object database, and `.gitattributes` are read from the current index, if present.
+    pub fn merge_resource_cache(&self) -> Result<gix_diff::blob::Platform, merge_resource_cache::Error> {
+        let index = self.index_or_empty()?;
+        Ok(crate::diff::resource_cache(
+            self,
+            gix_diff::blob::pipeline::Mode::ToGit,
+            self.attributes_only(&index, gix_worktree::stack::state::attributes::Source::IdMapping)?
+                .inner,
+            Default::default(),
+        )?)
+    }
+
+    /// Create a platform for merging the content of files with the merge driver selected by their `merge` attribute,
+    /// configured just like `git` would, with `merge.<driver>.driver` programs and `merge.default`.
+    ///
+    /// `.gitattributes` are read from the current index, if present.
+    pub fn blob_merge_platform(&self) -> Result<gix_merge::blob::Platform, blob_merge_platform::Error> {
+        let index = self.index_or_empty()?;
+        let default_driver = self
+            .config
+            .resolved
+            .string_by_key("merge.default")
+            .map(std::borrow::Cow::into_owned);
+        Ok(gix_merge::blob::Platform::new(
+            self.config.merge_drivers(),
+            self.attributes_only(&index, gix_worktree::stack::state::attributes::Source::IdMapping)?
+                .inner,
+            self.command_context()?,
+            gix_merge::blob::platform::Options { default_driver },
+        ))
+    }
+}
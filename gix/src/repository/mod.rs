@@ -36,8 +36,18 @@ impl crate::Repository {
     }
 }
 
+#[cfg(feature = "apply")]
+mod apply;
 #[cfg(any(feature = "attributes", feature = "excludes"))]
 pub mod attributes;
+///
+#[cfg(feature = "revision")]
+pub mod bisect;
+///
+#[cfg(feature = "blame")]
+pub mod blame;
+#[cfg(feature = "bundle")]
+mod bundle;
 mod cache;
 mod config;
 ///
@@ -59,6 +69,9 @@ mod kind;
 mod location;
 #[cfg(feature = "mailmap")]
 mod mailmap;
+///
+#[cfg(feature = "merge")]
+pub mod merge;
 mod object;
 #[cfg(feature = "attributes")]
 mod pathspec;
@@ -67,6 +80,10 @@ mod remote;
 #[cfg(feature = "revision")]
 mod revision;
 mod shallow;
+#[cfg(feature = "worktree-mutation")]
+mod sparse;
+#[cfg(feature = "dirwalk")]
+mod stage;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
@@ -121,6 +138,23 @@ pub mod pathspec_defaults_ignore_case {
     }
 }
 
+///
+#[cfg(feature = "revision")]
+pub mod merge_base {
+    /// The error returned by [Repository::merge_base()](crate::Repository::merge_base()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+        #[error("No merge-base found between {first} and {second}")]
+        NotFound {
+            first: gix_hash::ObjectId,
+            second: gix_hash::ObjectId,
+        },
+    }
+}
+
 ///
 #[cfg(feature = "index")]
 pub mod index_or_load_from_head {
@@ -156,7 +190,11 @@ pub mod worktree_stream {
         FilterPipeline(#[from] crate::filter::pipeline::options::Error),
         #[error(transparent)]
         CommandContext(#[from] crate::config::command_context::Error),
-        #[error("Needed {id} to be a tree to turn into a workspace stream, got {actual}")]
+        #[error(transparent)]
+        NotACommit(#[from] crate::object::try_into::Error),
+        #[error(transparent)]
+        DecodeCommit(#[from] gix_object::decode::Error),
+        #[error("Needed {id} to be a tree or commit to turn into a workspace stream, got {actual}")]
         NotATree {
             id: gix_hash::ObjectId,
             actual: gix_object::Kind,
@@ -116,7 +116,7 @@ impl crate::Repository {
         }
     }
 
-    fn shared_empty_buf(&self) -> std::cell::RefMut<'_, Vec<u8>> {
+    pub(crate) fn shared_empty_buf(&self) -> std::cell::RefMut<'_, Vec<u8>> {
         let mut bufs = self.bufs.borrow_mut();
         if bufs.last().is_none() {
             bufs.push(Vec::with_capacity(512));
@@ -139,7 +139,19 @@ impl crate::Repository {
         self.write_object_inner(&buf, object.kind())
     }
 
-    fn write_object_inner(&self, buf: &[u8], kind: gix_object::Kind) -> Result<Id<'_>, object::write::Error> {
+    /// Like [`write_object()`](Self::write_object()), but for use as callback to write objects of any kind.
+    #[cfg(any(feature = "merge", feature = "apply"))]
+    pub(crate) fn write_object_dyn(&self, object: &dyn gix_object::WriteTo) -> Result<ObjectId, object::write::Error> {
+        let mut buf = self.shared_empty_buf();
+        object.write_to(buf.deref_mut()).expect("write to memory works");
+        self.write_object_inner(&buf, object.kind()).map(Id::detach)
+    }
+
+    pub(crate) fn write_object_inner(
+        &self,
+        buf: &[u8],
+        kind: gix_object::Kind,
+    ) -> Result<Id<'_>, object::write::Error> {
         let oid = gix_object::compute_hash(self.object_hash(), kind, buf);
         if self.objects.exists(&oid) {
             return Ok(oid.attach(self));
@@ -127,6 +127,65 @@ impl crate::Repository {
         .attach(self))
     }
 
+    /// Create or update the symbolic reference `name`, like `HEAD` or `refs/remotes/origin/HEAD`, to point to the reference `target`,
+    /// adhering to `constraint` and writing `log_message` into the reflog if the target exists.
+    ///
+    /// The newly created Reference is returned.
+    pub fn symbolic_reference<Name, Referent, E, E2>(
+        &self,
+        name: Name,
+        target: Referent,
+        constraint: PreviousValue,
+        log_message: impl Into<BString>,
+    ) -> Result<Reference<'_>, reference::edit::Error>
+    where
+        Name: TryInto<FullName, Error = E>,
+        gix_validate::reference::name::Error: From<E>,
+        Referent: TryInto<FullName, Error = E2>,
+        gix_validate::reference::name::Error: From<E2>,
+    {
+        self.symbolic_reference_inner(
+            name.try_into().map_err(gix_validate::reference::name::Error::from)?,
+            target.try_into().map_err(gix_validate::reference::name::Error::from)?,
+            constraint,
+            log_message.into(),
+        )
+    }
+
+    fn symbolic_reference_inner(
+        &self,
+        name: FullName,
+        target: FullName,
+        constraint: PreviousValue,
+        log_message: BString,
+    ) -> Result<Reference<'_>, reference::edit::Error> {
+        let mut edits = self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: log_message,
+                },
+                expected: constraint,
+                new: Target::Symbolic(target.clone()),
+            },
+            name,
+            deref: false,
+        })?;
+        assert_eq!(
+            edits.len(),
+            1,
+            "only one reference can be created, splits aren't possible"
+        );
+
+        Ok(gix_ref::Reference {
+            name: edits.pop().expect("exactly one edit").name,
+            target: Target::Symbolic(target),
+            peeled: None,
+        }
+        .attach(self))
+    }
+
     /// Edit a single reference as described in `edit`, and write reference logs as `log_committer`.
     ///
     /// One or more `RefEdit`s  are returned - symbolic reference splits can cause more edits to be performed. All edits have the previous
@@ -153,6 +212,25 @@ impl crate::Repository {
             .map_err(Into::into)
     }
 
+    /// Prepare the given `edits` by locking all affected references and verifying their expected previous values,
+    /// without changing any of them yet.
+    ///
+    /// The returned transaction can be inspected and then committed or rolled back as a whole, which is useful to
+    /// apply many edits atomically, as done when receiving a push, or when renaming a branch and moving `HEAD` with it.
+    pub fn prepare_reference_edits(
+        &self,
+        edits: impl IntoIterator<Item = RefEdit>,
+    ) -> Result<reference::transaction::Prepared<'_>, reference::edit::Error> {
+        let (file_lock_fail, packed_refs_lock_fail) = self.config.lock_timeout()?;
+        Ok(reference::transaction::Prepared {
+            inner: self
+                .refs
+                .transaction()
+                .prepare(edits, file_lock_fail, packed_refs_lock_fail)?,
+            repo: self,
+        })
+    }
+
     /// Return the repository head, an abstraction to help dealing with the `HEAD` reference.
     ///
     /// The `HEAD` reference can be in various states, for more information, the documentation of [`Head`][crate::Head].
@@ -172,6 +250,73 @@ impl crate::Repository {
         .attach(self))
     }
 
+    /// Detach `HEAD` from its branch and let it point to the commit that `commit` peels to, just like `git checkout --detach`
+    /// does, and write a reflog entry for `HEAD` that describes the move.
+    ///
+    /// The new `HEAD` is returned. Note that neither the index nor the worktree are changed.
+    pub fn head_detach(&self, commit: impl Into<ObjectId>) -> Result<crate::Head<'_>, reference::head_detach::Error> {
+        let id = self
+            .find_object(commit.into())?
+            .peel_to_kind(gix_object::Kind::Commit)?
+            .id;
+        let message = format!("checkout: moving from {} to {id}", checkout_name(&self.head()?));
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Peeled(id),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(crate::head::Kind::Detached {
+            target: id,
+            peeled: None,
+        }
+        .attach(self))
+    }
+
+    /// Attach `HEAD` to the existing local `branch`, like `refs/heads/main`, just like `git switch` does,
+    /// and write a reflog entry for `HEAD` that describes the move.
+    ///
+    /// The new `HEAD` is returned. Note that neither the index nor the worktree are changed.
+    pub fn head_attach<Name, E>(&self, branch: Name) -> Result<crate::Head<'_>, reference::head_attach::Error>
+    where
+        Name: TryInto<FullName, Error = E>,
+        gix_validate::reference::name::Error: From<E>,
+    {
+        let name = branch.try_into().map_err(gix_validate::reference::name::Error::from)?;
+        if name.category() != Some(gix_ref::Category::LocalBranch) {
+            return Err(reference::head_attach::Error::NotABranch { name });
+        }
+        let branch = self
+            .try_find_reference(name.as_ref())?
+            .ok_or_else(|| reference::head_attach::Error::BranchNotFound { name: name.clone() })?;
+        let message = format!(
+            "checkout: moving from {} to {}",
+            checkout_name(&self.head()?),
+            name.shorten()
+        );
+        self.edit_reference(RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: message.into(),
+                },
+                expected: PreviousValue::Any,
+                new: Target::Symbolic(name),
+            },
+            name: "HEAD".try_into().expect("valid"),
+            deref: false,
+        })?;
+        Ok(crate::head::Kind::Symbolic(branch.detach()).attach(self))
+    }
+
     /// Resolve the `HEAD` reference, follow and peel its target and obtain its object id,
     /// following symbolic references and tags until a commit is found.
     ///
@@ -261,3 +406,11 @@ impl crate::Repository {
         }
     }
 }
+
+/// Return the name `git checkout` uses for `head` in its reflog messages.
+fn checkout_name(head: &crate::Head<'_>) -> String {
+    match head.referent_name() {
+        Some(name) => name.shorten().to_string(),
+        None => head.id().expect("detached heads have an id").to_string(),
+    }
+}
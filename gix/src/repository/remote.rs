@@ -182,6 +182,23 @@ impl crate::Repository {
             Some(Err(err)) => return Some(Err(err)),
             None => Default::default(),
         };
+        let mut config_boolean = |remote_key: &'static config::tree::keys::Boolean,
+                                  fetch_key: &'static config::tree::keys::Boolean| {
+            match config.boolean_filter("remote", Some(name_or_url), remote_key.name, &mut filter) {
+                Some(value) => remote_key.enrich_error(value),
+                None => config
+                    .boolean_filter("fetch", None, fetch_key.name, &mut filter)
+                    .map_or(Ok(false), |value| fetch_key.enrich_error(value)),
+            }
+        };
+        let prune = match config_boolean(&config::tree::Remote::PRUNE, &config::tree::Fetch::PRUNE) {
+            Ok(v) => v,
+            Err(err) => return Some(Err(err.into())),
+        };
+        let prune_tags = match config_boolean(&config::tree::Remote::PRUNE_TAGS, &config::tree::Fetch::PRUNE_TAGS) {
+            Ok(v) => v,
+            Err(err) => return Some(Err(err.into())),
+        };
 
         match (url, fetch_specs, push_url, push_specs) {
             (None, None, None, None) => None,
@@ -219,6 +236,7 @@ impl crate::Repository {
                         fetch_tags,
                         self,
                     )
+                    .map(|remote| remote.with_prune(prune).with_prune_tags(prune_tags))
                     .map_err(Into::into),
                 )
             }
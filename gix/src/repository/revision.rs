@@ -1,6 +1,8 @@
 use gix_macros::momo;
 
-use crate::{bstr::BStr, revision, Id};
+use gix_hash::ObjectId;
+
+use crate::{bstr::BStr, ext::ObjectIdExt, revision, Id};
 
 /// Methods for resolving revisions by spec or working with the commit graph.
 impl crate::Repository {
@@ -45,4 +47,93 @@ impl crate::Repository {
     ) -> revision::walk::Platform<'_> {
         revision::walk::Platform::new(tips, self)
     }
+
+    /// Obtain the best merge-base between commit `one` and `two`, or fail if there is none, like `git merge-base` does.
+    #[doc(alias = "git2")]
+    pub fn merge_base(
+        &self,
+        one: impl Into<ObjectId>,
+        two: impl Into<ObjectId>,
+    ) -> Result<Id<'_>, crate::repository::merge_base::Error> {
+        let (first, second) = (one.into(), two.into());
+        let bases = gix_revision::merge_base(first, &[second], &mut self.revision_graph())?
+            .ok_or(crate::repository::merge_base::Error::NotFound { first, second })?;
+        Ok(bases[0].attach(self))
+    }
+
+    /// Obtain all merge-bases between commit `one` and `others`, sorted from best to worst, like `git merge-base --all` does.
+    /// The returned list is empty if there is no merge-base.
+    ///
+    /// With more than one commit in `others`, the merge-bases are the ones of `one` and a hypothetical merge of all `others`.
+    #[doc(alias = "merge_bases", alias = "git2")]
+    pub fn merge_bases_many(
+        &self,
+        one: impl Into<ObjectId>,
+        others: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<Id<'_>>, gix_revision::merge_base::Error> {
+        let others: Vec<_> = others.into_iter().map(Into::into).collect();
+        Ok(
+            gix_revision::merge_base(one.into(), &others, &mut self.revision_graph())?
+                .unwrap_or_default()
+                .into_iter()
+                .map(|id| id.attach(self))
+                .collect(),
+        )
+    }
+
+    /// Obtain all merge-bases of `commits` as if they were merged in an octopus merge, sorted from best to worst, like
+    /// `git merge-base --octopus --all` does. The returned list is empty if there is no merge-base.
+    #[doc(alias = "merge_base_octopus", alias = "git2")]
+    pub fn merge_bases_octopus(
+        &self,
+        commits: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<Id<'_>>, gix_revision::merge_base::Error> {
+        let commits: Vec<_> = commits.into_iter().map(Into::into).collect();
+        Ok(gix_revision::merge_base::octopus(&commits, &mut self.revision_graph())?
+            .unwrap_or_default()
+            .into_iter()
+            .map(|id| id.attach(self))
+            .collect())
+    }
+
+    /// Reduce `commits` to those that aren't reachable from any of the other `commits` in their original order, like
+    /// `git merge-base --independent` does.
+    pub fn independent_commits(
+        &self,
+        commits: impl IntoIterator<Item = impl Into<ObjectId>>,
+    ) -> Result<Vec<Id<'_>>, gix_revision::merge_base::Error> {
+        let commits: Vec<_> = commits.into_iter().map(Into::into).collect();
+        Ok(
+            gix_revision::merge_base::independent(&commits, &mut self.revision_graph())?
+                .into_iter()
+                .map(|id| id.attach(self))
+                .collect(),
+        )
+    }
+
+    /// Count the commits reachable from `local` but not from `upstream`, and the ones reachable from `upstream` but not
+    /// from `local`, and return them as `(ahead, behind)`, like `git rev-list --left-right --count local...upstream` does.
+    ///
+    /// This is what's needed to show how far a branch diverged from its upstream branch, and the traversal stops as soon as
+    /// only commits reachable from both are left. It uses the commit-graph if present to visit commits in topological order.
+    pub fn ahead_behind(
+        &self,
+        local: impl Into<ObjectId>,
+        upstream: impl Into<ObjectId>,
+    ) -> Result<(usize, usize), gix_revision::merge_base::Error> {
+        gix_revision::merge_base::ahead_behind(local.into(), upstream.into(), &mut self.revision_graph())
+    }
+
+    /// Return `true` if `ancestor` is reachable from `descendant`, or if both are the same, like
+    /// `git merge-base --is-ancestor ancestor descendant` does.
+    ///
+    /// This is what's needed to find the tags or branches that contain a commit, and the traversal doesn't go past commits
+    /// which are older than `ancestor` according to the generation numbers of the commit-graph, if present.
+    pub fn is_ancestor(
+        &self,
+        ancestor: impl Into<ObjectId>,
+        descendant: impl Into<ObjectId>,
+    ) -> Result<bool, gix_revision::merge_base::Error> {
+        gix_revision::merge_base::is_ancestor(ancestor.into(), descendant.into(), &mut self.revision_graph())
+    }
 }
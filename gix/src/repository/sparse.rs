@@ -0,0 +1,202 @@
+use std::{io::Read, path::Path, sync::atomic::AtomicBool};
+
+use gix_index::entry::{Flags, Stage};
+
+use crate::{bstr::ByteSlice, config::cache::util::ApplyLeniencyDefault, sparse, Repository};
+
+impl Repository {
+    /// Return the patterns of the sparse checkout as read from `$GIT_DIR/info/sparse-checkout`, or `None` if `core.sparseCheckout`
+    /// isn't enabled. They are interpreted in cone mode if `core.sparseCheckoutCone` is set.
+    ///
+    /// A missing file is treated like an empty one, which excludes all files.
+    pub fn sparse_checkout_patterns(&self) -> Result<Option<sparse::Patterns>, sparse::patterns::Error> {
+        let config = &self.config.resolved;
+        let enabled = config
+            .boolean("core", None, "sparseCheckout")
+            .map(|res| crate::config::tree::Core::SPARSE_CHECKOUT.enrich_error(res))
+            .transpose()
+            .with_lenient_default(self.config.lenient_config)?
+            .unwrap_or_default();
+        if !enabled {
+            return Ok(None);
+        }
+        let cone = config
+            .boolean("core", None, "sparseCheckoutCone")
+            .map(|res| crate::config::tree::Core::SPARSE_CHECKOUT_CONE.enrich_error(res))
+            .transpose()
+            .with_lenient_default(self.config.lenient_config)?
+            .unwrap_or_default();
+        let path = self.sparse_checkout_path();
+        let data = match std::fs::read(&path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(source) => return Err(sparse::patterns::Error::Io { source, path }),
+        };
+        Ok(Some(sparse::Patterns::from_bytes(&data, cone)))
+    }
+
+    /// Change the worktree to contain only the files included by `patterns` and write them to `$GIT_DIR/info/sparse-checkout`,
+    /// similar to `git sparse-checkout set`. To add to the existing patterns like `git sparse-checkout add`, obtain them with
+    /// [`sparse_checkout_patterns()`](Self::sparse_checkout_patterns()) and [add](sparse::Patterns::add()) to them first.
+    ///
+    /// All unconflicted entries that aren't included anymore are marked to [skip the worktree](Flags::SKIP_WORKTREE)
+    /// and their files are removed, unless they are modified in which case they are kept along with their entry.
+    /// Entries that are included again have their skip-worktree bit cleared and their files checked out.
+    /// Submodules are left unchanged.
+    ///
+    /// Note that `core.sparseCheckout` and `core.sparseCheckoutCone` aren't set here, and that a sparse index is written
+    /// with all of its sparse directories expanded.
+    pub fn sparse_checkout_set(
+        &self,
+        patterns: &sparse::Patterns,
+        should_interrupt: &AtomicBool,
+    ) -> Result<sparse::Outcome, sparse::Error> {
+        let _span = gix_trace::coarse!("gix::sparse_checkout_set");
+        let workdir = self.work_dir().ok_or(sparse::Error::MissingWorkDir)?;
+        // Always read the index from disk as the shared one may be stale if it was written within the same filesystem tick.
+        let mut index = match self.open_index() {
+            Ok(index) => index,
+            Err(crate::worktree::open_index::Error::IndexFile(gix_index::file::init::Error::Io(err)))
+                if err.kind() == std::io::ErrorKind::NotFound =>
+            {
+                gix_index::File::from_state(gix_index::State::new(self.object_hash()), self.index_path())
+            }
+            Err(err) => return Err(err.into()),
+        };
+        index.expand_sparse_directories(&self.objects)?;
+        let previous = gix_index::State::clone(&index);
+
+        let mut out = sparse::Outcome::default();
+        let mut to_checkout = vec![false; index.entries().len()];
+        for ((entry, rela_path), checkout) in index.entries_mut_with_paths().zip(to_checkout.iter_mut()) {
+            if entry.stage() != Stage::Unconflicted || entry.mode.is_submodule() {
+                continue;
+            }
+            match (
+                entry.flags.contains(Flags::SKIP_WORKTREE),
+                patterns.is_included(rela_path),
+            ) {
+                (true, true) => {
+                    set_skip_worktree(entry, false);
+                    *checkout = true;
+                    out.entries_included += 1;
+                }
+                (false, false) => {
+                    set_skip_worktree(entry, true);
+                    out.entries_excluded += 1;
+                }
+                _ => {}
+            }
+        }
+
+        let mut options = self.config.checkout_options(
+            self,
+            gix_worktree::stack::state::attributes::Source::IdMappingThenWorktree,
+        )?;
+        if out.entries_excluded != 0 {
+            let mut remaining = gix_index::State::clone(&index);
+            remaining.remove_entries(|idx, _, entry| {
+                entry.flags.contains(Flags::SKIP_WORKTREE)
+                    && !previous.entries()[idx].flags.contains(Flags::SKIP_WORKTREE)
+            });
+            let (mut pipeline, _) = self.filter_pipeline(None)?;
+            let removed = gix_worktree_state::remove_deleted(
+                &previous,
+                &remaining,
+                workdir,
+                &options,
+                |rela_path, entry, path| -> Result<bool, std::io::Error> {
+                    let mut buf = Vec::new();
+                    if entry.mode == gix_index::entry::Mode::SYMLINK {
+                        buf.extend_from_slice(gix_path::into_bstr(std::fs::read_link(path)?).as_bytes());
+                    } else {
+                        pipeline
+                            .convert_to_git(std::fs::File::open(path)?, &gix_path::from_bstr(rela_path), &previous)
+                            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?
+                            .read_to_end(&mut buf)?;
+                    }
+                    Ok(gix_object::compute_hash(self.object_hash(), gix_object::Kind::Blob, &buf) != entry.id)
+                },
+            )?;
+            out.files_removed = removed.files_removed;
+            for rela_path in &removed.modified {
+                if let Some(entry) = index.entry_mut_by_path_and_stage(rela_path.as_ref(), Stage::Unconflicted) {
+                    set_skip_worktree(entry, false);
+                    out.entries_excluded -= 1;
+                }
+            }
+            out.modified = removed.modified;
+        }
+
+        if out.entries_included != 0 {
+            let mut checkout_index = gix_index::State::clone(&index);
+            for (entry, checkout) in checkout_index.entries_mut().iter_mut().zip(to_checkout.iter()) {
+                if !checkout {
+                    entry.flags.insert(Flags::SKIP_WORKTREE);
+                }
+            }
+            options.destination_is_initially_empty = false;
+            out.checkout = gix_worktree_state::checkout(
+                &mut checkout_index,
+                workdir,
+                self.objects.clone().into_arc().map_err(sparse::Error::OpenArcOdb)?,
+                &gix_features::progress::Discard,
+                &gix_features::progress::Discard,
+                should_interrupt,
+                options,
+            )?;
+            for ((entry, checked_out), checkout) in index
+                .entries_mut()
+                .iter_mut()
+                .zip(checkout_index.entries())
+                .zip(to_checkout.iter())
+            {
+                if *checkout {
+                    entry.stat = checked_out.stat;
+                }
+            }
+        }
+
+        let path = self.sparse_checkout_path();
+        write_patterns(&path, patterns).map_err(|source| sparse::Error::WritePatterns { source, path })?;
+
+        let skip_hash = self
+            .config
+            .resolved
+            .boolean("index", None, "skipHash")
+            .map(|res| crate::config::tree::Index::SKIP_HASH.enrich_error(res))
+            .transpose()
+            .with_lenient_default(self.config.lenient_config)?
+            .unwrap_or_default();
+        index.write(gix_index::write::Options {
+            extensions: Default::default(),
+            skip_hash,
+            thread_limit: self.index_thread_limit()?,
+        })?;
+        Ok(out)
+    }
+
+    fn sparse_checkout_path(&self) -> std::path::PathBuf {
+        // Like in `git`, this file is specific to each worktree.
+        self.git_dir().join("info").join("sparse-checkout")
+    }
+}
+
+fn write_patterns(path: &Path, patterns: &sparse::Patterns) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, patterns.to_bytes())
+}
+
+fn set_skip_worktree(entry: &mut gix_index::Entry, skip: bool) {
+    if skip {
+        entry.flags.insert(Flags::SKIP_WORKTREE | Flags::EXTENDED);
+    } else {
+        entry.flags.remove(Flags::SKIP_WORKTREE);
+        // Without any other extended flag, the entry can be stored in the more compact format again.
+        if !entry.flags.contains(Flags::INTENT_TO_ADD) {
+            entry.flags.remove(Flags::EXTENDED);
+        }
+    }
+}
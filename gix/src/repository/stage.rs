@@ -0,0 +1,185 @@
+use std::{io::Read, sync::atomic::AtomicBool};
+
+use gix_index::entry::{Mode, Stage};
+
+use crate::{
+    bstr::{BStr, BString},
+    config::cache::util::ApplyLeniencyDefault,
+    filter, stage, Repository,
+};
+
+impl Repository {
+    /// Stage the worktree file at the repository-relative `rela_path` into `index`, similar to `git add <rela_path>`,
+    /// and return how its entry changed.
+    ///
+    /// Files are converted to their git representation with `pipeline` and written to the object database, while symlinks
+    /// are stored as blob of their target if `core.symlinks` is enabled. If `rela_path` doesn't exist in the worktree,
+    /// its entries are removed from `index`. Conflicts are resolved by replacing all stages with the staged version.
+    ///
+    /// Note that submodules are left unchanged, and that `index` isn't written back to disk.
+    pub fn stage_path(
+        &self,
+        index: &mut gix_index::State,
+        pipeline: &mut filter::Pipeline<'_>,
+        rela_path: &BStr,
+    ) -> Result<stage::Change, stage::path::Error> {
+        let workdir = self.work_dir().ok_or(stage::path::Error::MissingWorkDir)?;
+        let path = workdir.join(gix_path::from_bstr(rela_path));
+        let metadata = match gix_index::fs::Metadata::from_path_no_follow(&path) {
+            Ok(metadata) => metadata,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(if index.remove_path(rela_path) {
+                    stage::Change::Removed
+                } else {
+                    stage::Change::Unchanged
+                });
+            }
+            Err(source) => return Err(stage::path::Error::Io { source, path }),
+        };
+
+        let previous = index.entry_range(rela_path).map(|range| match &index.entries()[range] {
+            [entry] if entry.stage() == Stage::Unconflicted => Some((entry.id, entry.mode)),
+            _ => None,
+        });
+        let fs_caps = self.filesystem_options()?;
+        let (id, mode) = if metadata.is_symlink() && fs_caps.symlink {
+            let target = std::fs::read_link(&path).map_err(|source| stage::path::Error::Io {
+                source,
+                path: path.clone(),
+            })?;
+            let id = self.write_blob(gix_path::into_bstr(target).as_ref())?;
+            (id.detach(), Mode::SYMLINK)
+        } else if metadata.is_file() {
+            let mode = if fs_caps.executable_bit {
+                if metadata.is_executable() {
+                    Mode::FILE_EXECUTABLE
+                } else {
+                    Mode::FILE
+                }
+            } else {
+                match previous {
+                    Some(Some((_, Mode::FILE_EXECUTABLE))) => Mode::FILE_EXECUTABLE,
+                    _ => Mode::FILE,
+                }
+            };
+            let file = std::fs::File::open(&path).map_err(|source| stage::path::Error::Io {
+                source,
+                path: path.clone(),
+            })?;
+            let mut buf = Vec::with_capacity(metadata.len() as usize);
+            pipeline
+                .convert_to_git(file, &gix_path::from_bstr(rela_path), index)?
+                .read_to_end(&mut buf)
+                .map_err(|source| stage::path::Error::Io {
+                    source,
+                    path: path.clone(),
+                })?;
+            (self.write_blob(&buf)?.detach(), mode)
+        } else if matches!(previous, Some(Some((_, mode))) if mode.is_submodule()) {
+            return Ok(stage::Change::Unchanged);
+        } else {
+            return Err(stage::path::Error::UnsupportedKind { path });
+        };
+
+        let stat = gix_index::entry::Stat::from_fs(&metadata)?;
+        index.add_path(rela_path, id, mode, stat);
+        Ok(match previous {
+            Some(Some(previous)) if previous == (id, mode) => stage::Change::Unchanged,
+            Some(_) => stage::Change::Modified,
+            None => stage::Change::Added,
+        })
+    }
+
+    /// Stage all tracked and untracked files matching `patterns` and write the index, similar to `git add --all <patterns>`,
+    /// and return the paths whose entries changed. Empty `patterns` match all files in the working tree.
+    ///
+    /// Ignored files are never added, and tracked files that were deleted from the worktree are removed from the index.
+    /// See [`stage_path()`](Self::stage_path()) for details on how each path is staged.
+    pub fn stage(&self, patterns: impl IntoIterator<Item = impl AsRef<BStr>>) -> Result<stage::Outcome, stage::Error> {
+        let _span = gix_trace::coarse!("gix::stage");
+        let mut index = (**self.index_or_empty()?).clone();
+        let (mut pipeline, _) = self.filter_pipeline(None)?;
+
+        let mut collect = gix_dir::walk::delegate::Collect::default();
+        let options = self
+            .dirwalk_options()?
+            .emit_untracked(gix_dir::walk::EmissionMode::Matching);
+        let mut pathspec = self
+            .dirwalk(&index, patterns, &AtomicBool::default(), options, &mut collect)?
+            .pathspec;
+
+        let mut paths: Vec<BString> = collect
+            .unorded_entries
+            .into_iter()
+            .filter(|(entry, _)| {
+                entry.status == gix_dir::entry::Status::Untracked
+                    && matches!(
+                        entry.disk_kind,
+                        Some(gix_dir::entry::Kind::File | gix_dir::entry::Kind::Symlink)
+                    )
+            })
+            .map(|(entry, _)| entry.rela_path)
+            .collect();
+        paths.extend(
+            pathspec
+                .index_entries_with_paths(&index)
+                .into_iter()
+                .flatten()
+                .filter(|(_, entry)| {
+                    !entry.mode.is_submodule()
+                        && !entry.mode.is_sparse()
+                        && !entry.flags.contains(gix_index::entry::Flags::SKIP_WORKTREE)
+                })
+                .map(|(path, _)| path.to_owned()),
+        );
+        paths.sort();
+        paths.dedup();
+
+        let mut out = stage::Outcome::default();
+        for path in paths {
+            match self.stage_path(&mut index, &mut pipeline, path.as_ref())? {
+                stage::Change::Unchanged => {}
+                change => out.changes.push((path, change)),
+            }
+        }
+
+        if !out.changes.is_empty() {
+            let skip_hash = self
+                .config
+                .resolved
+                .boolean("index", None, "skipHash")
+                .map(|res| crate::config::tree::Index::SKIP_HASH.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or_default();
+            let options = gix_index::write::Options {
+                extensions: Default::default(),
+                skip_hash,
+                thread_limit: self.index_thread_limit()?,
+            };
+            // Like `git`, keep a split index split unless told otherwise.
+            let split_index = self
+                .config
+                .resolved
+                .boolean("core", None, "splitIndex")
+                .map(|res| crate::config::tree::Core::SPLIT_INDEX.enrich_error(res))
+                .transpose()
+                .with_lenient_default(self.config.lenient_config)?
+                .unwrap_or(index.shared_index_checksum().is_some());
+            if split_index {
+                let max_percent_change = self
+                    .config
+                    .resolved
+                    .integer("splitIndex", None, "maxPercentChange")
+                    .map(|value| crate::config::tree::SplitIndex::MAX_PERCENT_CHANGE.try_into_u64(value))
+                    .transpose()
+                    .with_lenient_default(self.config.lenient_config)?
+                    .map_or(20, |value| value.min(100) as u8);
+                index.write_split(options, max_percent_change)?;
+            } else {
+                index.write(options)?;
+            }
+        }
+        Ok(out)
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{worktree, Worktree};
+use crate::{bstr::BString, worktree, Worktree};
 
 /// Interact with individual worktrees and their information.
 impl crate::Repository {
@@ -25,6 +25,163 @@ impl crate::Repository {
         res.sort_by(|a, b| a.git_dir.cmp(&b.git_dir));
         Ok(res)
     }
+
+    /// Remove the administrative files of all linked worktrees that are [prunable](worktree::Proxy::prunable()), similar to
+    /// `git worktree prune`, and return their ids along with the reason for pruning them, sorted by id.
+    ///
+    /// Locked worktrees are never pruned. Note that `gc.worktreePruneExpire` isn't respected, so worktrees are pruned
+    /// as soon as they are prunable.
+    pub fn prune_worktrees(&self) -> std::io::Result<Vec<(BString, worktree::proxy::Prunable)>> {
+        let mut res = Vec::new();
+        let worktrees_dir = self.common_dir().join("worktrees");
+        let iter = match std::fs::read_dir(&worktrees_dir) {
+            Ok(iter) => iter,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(res),
+            Err(err) => return Err(err),
+        };
+        for entry in iter {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let proxy = worktree::Proxy::new(self, entry.path());
+            if let Some(reason) = proxy.prunable() {
+                std::fs::remove_dir_all(proxy.git_dir())?;
+                res.push((proxy.id().to_owned(), reason));
+            }
+        }
+        res.sort_by(|a, b| a.0.cmp(&b.0));
+        // Like `git`, don't leave an empty directory behind.
+        std::fs::remove_dir(&worktrees_dir).ok();
+        Ok(res)
+    }
+
+    /// Create a new linked worktree at `path` as configured by `options` and return it as repository, similar to `git worktree add`.
+    ///
+    /// This writes its administrative files to `$GIT_COMMON_DIR/worktrees/<id>`, where `<id>` is derived from the last component of `path`,
+    /// including its private `HEAD`, and the `.git` file pointing to them. Unless disabled in `options`, the files of the commit `HEAD`
+    /// points to are checked out along with creating the index.
+    ///
+    /// `path` must not exist or be an empty directory, and unless `options.force` is set, a branch to check out must not be
+    /// checked out in another worktree.
+    #[cfg(feature = "worktree-mutation")]
+    pub fn worktree_add(
+        &self,
+        path: impl AsRef<std::path::Path>,
+        options: worktree::add::Options,
+    ) -> Result<crate::Repository, worktree::add::Error> {
+        use worktree::add::{Error, Head};
+        let path = path.as_ref();
+        let path = if path.is_relative() {
+            std::env::current_dir()?.join(path)
+        } else {
+            path.to_owned()
+        };
+        if path.exists() && (!path.is_dir() || std::fs::read_dir(&path)?.next().is_some()) {
+            return Err(Error::PathExists { path });
+        }
+
+        let (head, commit_id) = match &options.head {
+            Head::Detached(id) => (format!("{id}\n"), *id),
+            Head::Branch(name) => {
+                if !options.force {
+                    if let Some(path) = self.worktree_with_branch_checked_out(name.as_ref()) {
+                        return Err(Error::BranchCheckedOut {
+                            name: name.clone(),
+                            path,
+                        });
+                    }
+                }
+                let id = self.find_reference(name.as_ref())?.peel_to_id_in_place()?.detach();
+                (format!("ref: {}\n", name.as_bstr()), id)
+            }
+            Head::NewBranch { name, target } => {
+                self.reference(
+                    name.clone(),
+                    *target,
+                    gix_ref::transaction::PreviousValue::MustNotExist,
+                    format!("branch: Created from {target}"),
+                )?;
+                (format!("ref: {}\n", name.as_bstr()), *target)
+            }
+        };
+
+        let worktrees_dir = self.common_dir().join("worktrees");
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .filter(|name| !name.is_empty() && !name.starts_with('.'))
+            .unwrap_or_else(|| "worktree".into());
+        let mut id = name.clone();
+        let mut counter = 0;
+        while worktrees_dir.join(&id).exists() {
+            counter += 1;
+            id = format!("{name}{counter}");
+        }
+        let git_dir = worktrees_dir.join(id);
+        std::fs::create_dir_all(&git_dir)?;
+        std::fs::create_dir_all(&path)?;
+
+        let mut gitdir = gix_path::into_bstr(path.join(".git")).into_owned();
+        gitdir.push(b'\n');
+        std::fs::write(git_dir.join("gitdir"), gitdir)?;
+        std::fs::write(git_dir.join("commondir"), "../..\n")?;
+        std::fs::write(git_dir.join("HEAD"), head)?;
+        if let Some(reason) = &options.lock {
+            std::fs::write(git_dir.join("locked"), reason)?;
+        }
+        let mut dot_git = BString::from("gitdir: ");
+        dot_git.extend_from_slice(&gix_path::into_bstr(git_dir.as_path()));
+        dot_git.push(b'\n');
+        std::fs::write(path.join(".git"), dot_git)?;
+
+        let repo = worktree::Proxy::new(self, git_dir).into_repo()?;
+        if options.checkout {
+            let tree = repo.find_object(commit_id)?.try_into_commit()?.tree_id()?.detach();
+            let mut index = repo
+                .index_from_tree(&tree)
+                .map_err(|source| Error::IndexFromTree { id: tree, source })?;
+            let mut opts = repo
+                .config
+                .checkout_options(&repo, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+            opts.destination_is_initially_empty = true;
+            gix_worktree_state::checkout(
+                &mut index,
+                &path,
+                repo.objects.clone().into_arc()?,
+                &gix_features::progress::Discard,
+                &gix_features::progress::Discard,
+                &Default::default(),
+                opts,
+            )?;
+            index.write(Default::default())?;
+        }
+        Ok(repo)
+    }
+
+    /// Return the base of the worktree that has the branch `name` checked out, if there is one.
+    #[cfg(feature = "worktree-mutation")]
+    fn worktree_with_branch_checked_out(&self, name: &gix_ref::FullNameRef) -> Option<std::path::PathBuf> {
+        use crate::bstr::ByteSlice;
+        let head_points_to_name = |git_dir: &std::path::Path| {
+            let head = match std::fs::read(git_dir.join("HEAD")) {
+                Ok(head) => head,
+                Err(_) => return false,
+            };
+            matches!(head.strip_prefix(b"ref: "), Some(target) if target.trim() == name.as_bstr())
+        };
+        if !self.config.is_bare && head_points_to_name(self.common_dir()) {
+            if let Some(base) = self.common_dir().parent() {
+                return Some(base.to_owned());
+            }
+        }
+        self.worktrees()
+            .ok()?
+            .into_iter()
+            .find(|proxy| head_points_to_name(proxy.git_dir()))
+            .and_then(|proxy| proxy.base().ok())
+    }
+
     /// Return the repository owning the main worktree, typically from a linked worktree.
     ///
     /// Note that it might be the one that is currently open if this repository doesn't point to a linked worktree.
@@ -51,11 +208,14 @@ impl crate::Repository {
         self.config.is_bare && self.work_dir().is_none()
     }
 
-    /// If `id` points to a tree, produce a stream that yields one worktree entry after the other. The index of the tree at `id`
-    /// is returned as well as it is an intermediate byproduct that might be useful to callers.
+    /// If `id` points to a tree or a commit, produce a stream that yields one worktree entry after the other.
+    /// The index of the tree at `id` (or the tree of the commit at `id`) is returned as well as it is an intermediate
+    /// byproduct that might be useful to callers.
     ///
     /// The entries will look exactly like they would if one would check them out, with filters applied.
     /// The `export-ignore` attribute is used to skip blobs or directories to which it applies.
+    /// If `id` is a commit, `$Format:<format>$` placeholders in blobs with the `export-subst` attribute are expanded
+    /// with information about the commit, just like `git archive` does.
     #[cfg(feature = "worktree-stream")]
     #[gix_macros::momo]
     pub fn worktree_stream(
@@ -65,12 +225,23 @@ impl crate::Repository {
         use gix_odb::HeaderExt;
         let id = id.into();
         let header = self.objects.header(id)?;
-        if !header.kind().is_tree() {
-            return Err(crate::repository::worktree_stream::Error::NotATree {
-                id,
-                actual: header.kind(),
-            });
-        }
+        let (id, export_subst) = match header.kind() {
+            gix_object::Kind::Tree => (id, None),
+            gix_object::Kind::Commit => {
+                use crate::ext::ObjectIdExt;
+                let commit: gix_object::Commit = self.find_object(id)?.try_into_commit()?.decode()?.into();
+                let short = |id: gix_hash::ObjectId| id.attach(self).shorten_or_id().to_string().into();
+                let ctx = gix_worktree_stream::export_subst::Context {
+                    id,
+                    short_id: short(id),
+                    short_tree_id: short(commit.tree),
+                    short_parent_ids: commit.parents.iter().copied().map(short).collect(),
+                    commit,
+                };
+                (ctx.commit.tree, Some(ctx))
+            }
+            actual => return Err(crate::repository::worktree_stream::Error::NotATree { id, actual }),
+        };
 
         // TODO(perf): potential performance improvements could be to use the index at `HEAD` if possible (`index_from_head_tree…()`)
         // TODO(perf): when loading a non-HEAD tree, we effectively traverse the tree twice. This is usually fast though, and sharing
@@ -81,16 +252,21 @@ impl crate::Repository {
             .detach();
         let pipeline = gix_filter::Pipeline::new(self.command_context()?, crate::filter::Pipeline::options(self)?);
         let objects = self.objects.clone().into_arc().expect("TBD error handling");
-        let stream = gix_worktree_stream::from_tree(
-            id,
-            objects.clone(),
-            pipeline,
-            move |path, mode, attrs| -> std::io::Result<()> {
+        let attributes = {
+            let objects = objects.clone();
+            move |path: &crate::bstr::BStr,
+                  mode: gix_object::tree::EntryMode,
+                  attrs: &mut gix_attributes::search::Outcome|
+                  -> std::io::Result<()> {
                 let entry = cache.at_entry(path, Some(mode.is_tree()), &objects)?;
                 entry.matching_attributes(attrs);
                 Ok(())
-            },
-        );
+            }
+        };
+        let stream = match export_subst {
+            Some(ctx) => gix_worktree_stream::from_commit(ctx, objects, pipeline, attributes),
+            None => gix_worktree_stream::from_tree(id, objects, pipeline, attributes),
+        };
         Ok((stream, index))
     }
 
@@ -5,6 +5,22 @@ use crate::{ext::ReferenceExt, revision::Spec, Id, Reference};
 #[allow(clippy::empty_docs)]
 pub mod parse;
 
+///
+#[allow(clippy::empty_docs)]
+pub mod rev_walk {
+    /// The error returned by [`Spec::rev_walk()`][crate::revision::Spec::rev_walk()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        FindObject(#[from] crate::object::find::existing::Error),
+        #[error(transparent)]
+        PeelToCommit(#[from] crate::object::peel::to_kind::Error),
+        #[error("Could not find the merge-base of a symmetric difference")]
+        MergeBase(#[from] gix_revision::merge_base::Error),
+    }
+}
+
 mod impls {
     use std::ops::{Deref, DerefMut};
 
@@ -47,6 +63,45 @@ impl<'repo> Spec<'repo> {
     }
 }
 
+/// Traversal
+impl<'repo> Spec<'repo> {
+    /// Create a platform to traverse all commits that are selected by this specification, as `git log <spec>` would.
+    ///
+    /// * `a` includes `a` and its ancestors, while `^a` includes nothing as all of these are hidden.
+    /// * `a..b` includes `b` and its ancestors, but hides `a` and its ancestors.
+    /// * `a...b` includes `a`, `b` and their ancestors, but hides their merge-bases and all of their ancestors.
+    /// * `a^@` includes the parents of `a` and their ancestors, but not `a` itself.
+    /// * `a^!` includes only `a` as all of its parents are hidden.
+    ///
+    /// All objects are peeled to commits, so `v1.0..main` works as expected with `v1.0` being an annotated tag.
+    pub fn rev_walk(&self) -> Result<crate::revision::walk::Platform<'repo>, rev_walk::Error> {
+        let repo = self.repo;
+        let commit = |id: gix_hash::ObjectId| -> Result<crate::Commit<'repo>, rev_walk::Error> {
+            Ok(repo
+                .find_object(id)?
+                .peel_to_kind(gix_object::Kind::Commit)?
+                .into_commit())
+        };
+        let parents = |id| -> Result<Vec<gix_hash::ObjectId>, rev_walk::Error> {
+            Ok(commit(id)?.parent_ids().map(crate::Id::detach).collect())
+        };
+        let (tips, hidden) = match self.inner {
+            gix_revision::Spec::Include(id) => (vec![commit(id)?.id], Vec::new()),
+            gix_revision::Spec::Exclude(id) => (Vec::new(), vec![commit(id)?.id]),
+            gix_revision::Spec::Range { from, to } => (vec![commit(to)?.id], vec![commit(from)?.id]),
+            gix_revision::Spec::Merge { theirs, ours } => {
+                let (theirs, ours) = (commit(theirs)?.id, commit(ours)?.id);
+                let merge_bases =
+                    gix_revision::merge_base(theirs, &[ours], &mut repo.revision_graph())?.unwrap_or_default();
+                (vec![theirs, ours], merge_bases)
+            }
+            gix_revision::Spec::IncludeOnlyParents(id) => (parents(id)?, Vec::new()),
+            gix_revision::Spec::ExcludeParents(id) => (vec![commit(id)?.id], parents(id)?),
+        };
+        Ok(repo.rev_walk(tips).hide(hidden))
+    }
+}
+
 /// Access
 impl<'repo> Spec<'repo> {
     /// Detach the `Repository` from this instance, leaving only plain data that can be moved freely and serialized.
@@ -1,7 +1,11 @@
 use gix_hash::ObjectId;
 use gix_object::FindExt;
 
-use crate::{ext::ObjectIdExt, revision, Repository};
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    ext::ObjectIdExt,
+    revision, Repository,
+};
 
 /// The error returned by [`Platform::all()`].
 #[derive(Debug, thiserror::Error)]
@@ -13,6 +17,9 @@ pub enum Error {
     ShallowCommits(#[from] crate::shallow::open::Error),
     #[error(transparent)]
     ConfigBoolean(#[from] crate::config::boolean::Error),
+    #[cfg(feature = "revparse-regex")]
+    #[error("Invalid author or committer pattern")]
+    InvalidRegex(#[from] regex::Error),
 }
 
 /// Information about a commit that we obtained naturally as part of the iteration.
@@ -25,6 +32,8 @@ pub struct Info<'repo> {
     /// The time at which the commit was created. It will only be `Some(_)` if the chosen traversal was
     /// taking dates into consideration.
     pub commit_time: Option<gix_date::SecondsSinceUnixEpoch>,
+    /// If `true`, this commit is hidden and only returned as [boundary](Platform::boundary()) of the traversal.
+    pub is_boundary: bool,
 
     repo: &'repo Repository,
 }
@@ -67,6 +76,7 @@ impl<'repo> Info<'repo> {
             id: info.id,
             parent_ids: info.parent_ids,
             commit_time: info.commit_time,
+            is_boundary: info.is_boundary,
             repo,
         }
     }
@@ -78,6 +88,7 @@ impl<'repo> Info<'repo> {
             id: self.id,
             parent_ids: self.parent_ids,
             commit_time: self.commit_time,
+            is_boundary: self.is_boundary,
         }
     }
 }
@@ -91,10 +102,25 @@ impl<'repo> Info<'repo> {
 pub struct Platform<'repo> {
     pub(crate) repo: &'repo Repository,
     pub(crate) tips: Vec<ObjectId>,
+    pub(crate) hidden: Vec<ObjectId>,
+    pub(crate) boundary: bool,
+    pub(crate) ancestry_path: bool,
     pub(crate) sorting: gix_traverse::commit::simple::Sorting,
+    pub(crate) topo_sorting: Option<gix_traverse::commit::topo::Sorting>,
     pub(crate) parents: gix_traverse::commit::Parents,
     pub(crate) use_commit_graph: Option<bool>,
     pub(crate) commit_graph: Option<gix_commitgraph::Graph>,
+    pub(crate) filter: Filter,
+}
+
+/// Criteria that commits have to match to be returned, without affecting which commits are traversed.
+#[derive(Default, Clone)]
+pub(crate) struct Filter {
+    since: Option<gix_date::SecondsSinceUnixEpoch>,
+    until: Option<gix_date::SecondsSinceUnixEpoch>,
+    authors: Vec<BString>,
+    committers: Vec<BString>,
+    paths: Vec<BString>,
 }
 
 impl<'repo> Platform<'repo> {
@@ -102,10 +128,15 @@ impl<'repo> Platform<'repo> {
         revision::walk::Platform {
             repo,
             tips: tips.into_iter().map(Into::into).collect(),
+            hidden: Vec::new(),
+            boundary: false,
+            ancestry_path: false,
             sorting: Default::default(),
+            topo_sorting: None,
             parents: Default::default(),
             use_commit_graph: None,
             commit_graph: None,
+            filter: Default::default(),
         }
     }
 }
@@ -118,6 +149,52 @@ impl<'repo> Platform<'repo> {
         self
     }
 
+    /// Return commits such that no parent is returned before all of its children, and order them by `sorting` otherwise,
+    /// like `git log --topo-order`, `--date-order` and `--author-date-order` do. Set it to `None` to return commits
+    /// in the order of the traversal (the default).
+    ///
+    /// Note that all commits are traversed before the first one is returned, ordered by [commit time](Self::sorting())
+    /// unless configured otherwise, which also applies if only the [first parent](Self::first_parent_only()) is followed.
+    pub fn topo_sorting(mut self, sorting: impl Into<Option<gix_traverse::commit::topo::Sorting>>) -> Self {
+        self.topo_sorting = sorting.into();
+        self
+    }
+
+    /// Do not return commits that are reachable from any of `ids`, including the commits themselves, like `^<rev>` on the
+    /// command-line of `git log` does. If called multiple times, commits reachable from any of the `ids` are hidden.
+    ///
+    /// ### Performance
+    ///
+    /// Commits reachable from `ids` are traversed before the first commit is returned, but only until it's clear which
+    /// of the commits reachable from the tips are hidden.
+    pub fn hide(mut self, ids: impl IntoIterator<Item = impl Into<ObjectId>>) -> Self {
+        self.hidden.extend(ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// If `toggle` is `true`, return the [hidden](Self::hide()) parents of returned commits after all other commits,
+    /// with [`Info::is_boundary`] set, like `git log --boundary` does.
+    ///
+    /// Boundary commits are never filtered.
+    pub fn boundary(mut self, toggle: bool) -> Self {
+        self.boundary = toggle;
+        self
+    }
+
+    /// If `toggle` is `true`, only return commits that are descendants of any of the [hidden](Self::hide()) commits,
+    /// which are ancestors of the tips by definition, like `git log --ancestry-path` does. This is useful to see how a
+    /// commit made its way into another one. Nothing changes if no commit is hidden.
+    ///
+    /// [Boundary](Self::boundary()) commits are the parents of returned commits that weren't returned, hidden or not.
+    ///
+    /// ### Performance
+    ///
+    /// All commits are traversed before the first one is returned.
+    pub fn ancestry_path(mut self, toggle: bool) -> Self {
+        self.ancestry_path = toggle;
+        self
+    }
+
     /// Only traverse the first parent of the commit graph.
     pub fn first_parent_only(mut self) -> Self {
         self.parents = gix_traverse::commit::Parents::First;
@@ -145,6 +222,66 @@ impl<'repo> Platform<'repo> {
     }
 }
 
+/// Filters, which only affect which commits are returned, but not which commits are traversed.
+///
+/// Commits are read from the object database if any filter is set, which makes the traversal slower.
+impl<'repo> Platform<'repo> {
+    /// Only return commits whose committer time is at or after `time` in seconds since the unix epoch, like
+    /// `git log --since-as-filter`.
+    ///
+    /// Note that unlike `git log --since`, the traversal doesn't stop at the first commit that is older, which is
+    /// what [`Sorting::ByCommitTimeNewestFirstCutoffOlderThan`](gix_traverse::commit::simple::Sorting) can be used for.
+    pub fn since(mut self, time: gix_date::SecondsSinceUnixEpoch) -> Self {
+        self.filter.since = Some(time);
+        self
+    }
+
+    /// Only return commits whose committer time is at or before `time` in seconds since the unix epoch, like `git log --until`.
+    pub fn until(mut self, time: gix_date::SecondsSinceUnixEpoch) -> Self {
+        self.filter.until = Some(time);
+        self
+    }
+
+    /// Only return commits whose author matches `pattern` when formatted as `Name <email>`, like `git log --author`.
+    /// If called multiple times, commits matching any of the patterns are returned.
+    ///
+    /// The pattern is a regular expression if the `revparse-regex` feature is enabled, and is searched verbatim otherwise.
+    pub fn author(mut self, pattern: impl Into<BString>) -> Self {
+        self.filter.authors.push(pattern.into());
+        self
+    }
+
+    /// Only return commits whose committer matches `pattern` when formatted as `Name <email>`, like `git log --committer`.
+    /// If called multiple times, commits matching any of the patterns are returned.
+    ///
+    /// The pattern is a regular expression if the `revparse-regex` feature is enabled, and is searched verbatim otherwise.
+    pub fn committer(mut self, pattern: impl Into<BString>) -> Self {
+        self.filter.committers.push(pattern.into());
+        self
+    }
+
+    /// Only return commits that changed any of the entries at `paths`, like `git log -- <paths>`.
+    ///
+    /// A commit is considered to change a path if the entry there differs from the one in each of its parents, which
+    /// means that merges are only returned if they don't take the entry from one of their parents as is.
+    /// Root commits are returned if one of the paths exists in their tree.
+    ///
+    /// ### Performance
+    ///
+    /// If the commit-graph was written with changed-path Bloom filters, these are used to skip comparing trees for commits
+    /// that definitely didn't change any of the paths, unless `commitGraph.readChangedPaths` is `false`.
+    ///
+    /// ### Deviation
+    ///
+    /// * paths are slash-separated locations relative to the root of the repository, and are matched literally without
+    ///   any pathspec magic, with directories matching all entries within them.
+    /// * history isn't simplified, so commits of side-branches are returned even if a merge discarded their changes.
+    pub fn paths(mut self, paths: impl IntoIterator<Item = impl Into<BString>>) -> Self {
+        self.filter.paths.extend(paths.into_iter().map(Into::into));
+        self
+    }
+}
+
 /// Produce the iterator
 impl<'repo> Platform<'repo> {
     /// For each commit, let `filter` return `true` if it and its parents should be included in the traversal, or `false`
@@ -158,52 +295,69 @@ impl<'repo> Platform<'repo> {
         let Platform {
             repo,
             tips,
+            hidden,
+            boundary,
+            ancestry_path,
             sorting,
+            topo_sorting,
             parents,
             use_commit_graph,
             commit_graph,
+            filter: output_filter,
         } = self;
+        let sorting = match (topo_sorting, sorting) {
+            // Like `git`, order commits by date before sorting them topologically.
+            (Some(_), gix_traverse::commit::simple::Sorting::BreadthFirst) => {
+                gix_traverse::commit::simple::Sorting::ByCommitTimeNewestFirst
+            }
+            (_, sorting) => sorting,
+        };
+        let may_use_commit_graph = use_commit_graph.map_or_else(|| repo.config.may_use_commit_graph(), Ok)?;
+        let bottoms = if ancestry_path { hidden.clone() } else { Vec::new() };
+        let mut inner: Box<dyn Iterator<Item = _> + 'repo> = Box::new(
+            gix_traverse::commit::Simple::filtered(tips, &repo.objects, {
+                let mut shallow_filter = shallow_filter(repo)?;
+                move |id| filter(id) && shallow_filter(id)
+            })
+            .sorting(sorting)?
+            .parents(parents)
+            .hide(hidden)
+            .boundary(boundary)
+            .commit_graph(commit_graph.or(may_use_commit_graph.then(|| repo.commit_graph().ok()).flatten())),
+        );
+        if topo_sorting.is_some() || !bottoms.is_empty() {
+            let (mut boundary_commits, mut commits): (Vec<_>, Vec<_>) = inner
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .partition(|info| info.is_boundary);
+            if !bottoms.is_empty() {
+                (commits, boundary_commits) = limit_to_ancestry(commits, boundary_commits, &bottoms, boundary);
+            }
+            if let Some(topo_sorting) = topo_sorting {
+                commits = sort_topologically(commits, topo_sorting, &repo.objects)?;
+            }
+            inner = Box::new(commits.into_iter().chain(boundary_commits).map(Ok));
+        }
+        if output_filter.is_empty() {
+            return Ok(revision::Walk { repo, inner });
+        }
+
+        let changed_paths =
+            (!output_filter.paths.is_empty() && may_use_commit_graph && repo.config.may_read_changed_paths()?)
+                .then(|| repo.commit_graph().ok())
+                .flatten();
+        let mut matcher = output_filter.into_matcher(changed_paths)?;
         Ok(revision::Walk {
             repo,
-            inner: Box::new(
-                gix_traverse::commit::Simple::filtered(tips, &repo.objects, {
-                    // Note that specific shallow handling for commit-graphs isn't needed as these contain
-                    // all information there is, and exclude shallow parents to be structurally consistent.
-                    let shallow_commits = repo.shallow_commits()?;
-                    let mut grafted_parents_to_skip = Vec::new();
-                    let mut buf = Vec::new();
-                    move |id| {
-                        if !filter(id) {
-                            return false;
-                        }
-                        match shallow_commits.as_ref() {
-                            Some(commits) => {
-                                let id = id.to_owned();
-                                if let Ok(idx) = grafted_parents_to_skip.binary_search(&id) {
-                                    grafted_parents_to_skip.remove(idx);
-                                    return false;
-                                };
-                                if commits.binary_search(&id).is_ok() {
-                                    if let Ok(commit) = repo.objects.find_commit_iter(&id, &mut buf) {
-                                        grafted_parents_to_skip.extend(commit.parent_ids());
-                                        grafted_parents_to_skip.sort();
-                                    }
-                                };
-                                true
-                            }
-                            None => true,
-                        }
-                    }
-                })
-                .sorting(sorting)?
-                .parents(parents)
-                .commit_graph(
-                    commit_graph.or(use_commit_graph
-                        .map_or_else(|| self.repo.config.may_use_commit_graph(), Ok)?
-                        .then(|| self.repo.commit_graph().ok())
-                        .flatten()),
-                ),
-            ),
+            inner: Box::new(inner.filter_map(move |res| match res {
+                Ok(info) if info.is_boundary => Some(Ok(info)),
+                Ok(info) => match matcher.matches(&info, &repo.objects) {
+                    Ok(true) => Some(Ok(info)),
+                    Ok(false) => None,
+                    Err(err) => Some(Err(err)),
+                },
+                Err(err) => Some(Err(err)),
+            })),
         })
     }
     /// Return an iterator to traverse all commits reachable as configured by the [Platform].
@@ -215,6 +369,413 @@ impl<'repo> Platform<'repo> {
     pub fn all(self) -> Result<revision::Walk<'repo>, Error> {
         self.selected(|_| true)
     }
+
+    /// Return an iterator over all commits that [`all()`](Self::all()) would return, followed by all trees and blobs
+    /// reachable from them which aren't reachable from [boundary](Self::boundary()) commits, like `git rev-list --objects`
+    /// does. Trees and blobs that don't pass `filter` are left out, as needed for partial clones.
+    ///
+    /// Boundary commits aren't returned.
+    pub fn objects(
+        self,
+        filter: Option<gix_traverse::objects::Filter>,
+    ) -> Result<revision::walk::Objects<'repo>, Error> {
+        let repo = self.repo;
+        let walk = self.boundary(true).all()?;
+        Ok(revision::walk::Objects {
+            inner: gix_traverse::objects::Objects::new(walk.inner, &repo.objects, filter),
+        })
+    }
+}
+
+/// Return a predicate which excludes the parents of shallow commits, as these aren't present in the object database.
+///
+/// Note that specific shallow handling for commit-graphs isn't needed as these contain
+/// all information there is, and exclude shallow parents to be structurally consistent.
+fn shallow_filter(repo: &Repository) -> Result<impl FnMut(&gix_hash::oid) -> bool + '_, Error> {
+    let shallow_commits = repo.shallow_commits()?;
+    let mut grafted_parents_to_skip = Vec::new();
+    let mut buf = Vec::new();
+    Ok(move |id: &gix_hash::oid| match shallow_commits.as_ref() {
+        Some(commits) => {
+            let id = id.to_owned();
+            if let Ok(idx) = grafted_parents_to_skip.binary_search(&id) {
+                grafted_parents_to_skip.remove(idx);
+                return false;
+            };
+            if commits.binary_search(&id).is_ok() {
+                if let Ok(commit) = repo.objects.find_commit_iter(&id, &mut buf) {
+                    grafted_parents_to_skip.extend(commit.parent_ids());
+                    grafted_parents_to_skip.sort();
+                }
+            };
+            true
+        }
+        None => true,
+    })
+}
+
+/// Retain only those of `commits` that are descendants of any of the `bottoms`, just like `limit_to_ancestry()` in `git`
+/// does, and return them along with the parents of retained commits that are either part of `boundary_commits` or weren't
+/// retained, as new boundary ordered by commit time like `git` would, if `boundary` is `true`.
+fn limit_to_ancestry(
+    commits: Vec<gix_traverse::commit::Info>,
+    boundary_commits: Vec<gix_traverse::commit::Info>,
+    bottoms: &[ObjectId],
+    boundary: bool,
+) -> (Vec<gix_traverse::commit::Info>, Vec<gix_traverse::commit::Info>) {
+    let index: gix_hashtable::HashMap<ObjectId, usize> = commits
+        .iter()
+        .enumerate()
+        .map(|(idx, commit)| (commit.id, idx))
+        .collect();
+    let mut is_descendant = vec![false; commits.len()];
+    // Parents tend to come after their children, so visiting commits in reverse should make repetitions rare.
+    let mut made_progress = true;
+    while made_progress {
+        made_progress = false;
+        for (idx, commit) in commits.iter().enumerate().rev() {
+            if is_descendant[idx] {
+                continue;
+            }
+            if commit.parent_ids.iter().any(|parent_id| {
+                bottoms.contains(parent_id) || index.get(parent_id).is_some_and(|&idx| is_descendant[idx])
+            }) {
+                is_descendant[idx] = true;
+                made_progress = true;
+            }
+        }
+    }
+
+    let (retained, excluded): (Vec<_>, Vec<_>) = commits
+        .into_iter()
+        .zip(is_descendant)
+        .partition(|(_, is_descendant)| *is_descendant);
+    let retained: Vec<_> = retained.into_iter().map(|(commit, _)| commit).collect();
+    if !boundary {
+        return (retained, boundary_commits);
+    }
+    let mut candidates: gix_hashtable::HashMap<ObjectId, gix_traverse::commit::Info> = excluded
+        .into_iter()
+        .map(|(commit, _)| commit)
+        .chain(boundary_commits)
+        .map(|commit| (commit.id, commit))
+        .collect();
+    let mut boundary_commits: Vec<_> = retained
+        .iter()
+        .flat_map(|commit| commit.parent_ids.iter())
+        .filter_map(|parent_id| candidates.remove(parent_id))
+        .map(|mut commit| {
+            commit.is_boundary = true;
+            commit
+        })
+        .collect();
+    boundary_commits.sort_by_key(|commit| std::cmp::Reverse(commit.commit_time));
+    (retained, boundary_commits)
+}
+
+/// Sort `commits` such that no parent comes before any of its children, and by `sorting` otherwise, just like
+/// `sort_in_topological_order()` in `git` does. Parents that aren't part of `commits` are ignored.
+fn sort_topologically(
+    commits: Vec<gix_traverse::commit::Info>,
+    sorting: gix_traverse::commit::topo::Sorting,
+    objects: &impl gix_object::Find,
+) -> Result<Vec<gix_traverse::commit::Info>, gix_traverse::commit::simple::Error> {
+    use gix_traverse::commit::topo::Sorting;
+    use std::{cmp::Reverse, collections::BinaryHeap};
+
+    /// Commits are popped from a stack for `TopoOrder`, and newest first otherwise, in the order of queueing if the time is equal.
+    enum Queue {
+        Stack(Vec<usize>),
+        Time {
+            times: Vec<gix_date::SecondsSinceUnixEpoch>,
+            heap: BinaryHeap<(gix_date::SecondsSinceUnixEpoch, Reverse<usize>, usize)>,
+            count: usize,
+        },
+    }
+    impl Queue {
+        fn push(&mut self, idx: usize) {
+            match self {
+                Queue::Stack(stack) => stack.push(idx),
+                Queue::Time { times, heap, count } => {
+                    heap.push((times[idx], Reverse(*count), idx));
+                    *count += 1;
+                }
+            }
+        }
+
+        fn pop(&mut self) -> Option<usize> {
+            match self {
+                Queue::Stack(stack) => stack.pop(),
+                Queue::Time { heap, .. } => heap.pop().map(|(_, _, idx)| idx),
+            }
+        }
+    }
+
+    let index: gix_hashtable::HashMap<ObjectId, usize> = commits
+        .iter()
+        .enumerate()
+        .map(|(idx, commit)| (commit.id, idx))
+        .collect();
+    let mut indegrees = vec![1_usize; commits.len()];
+    for parent_id in commits.iter().flat_map(|commit| commit.parent_ids.iter()) {
+        if let Some(&idx) = index.get(parent_id) {
+            indegrees[idx] += 1;
+        }
+    }
+
+    let mut queue = match sorting {
+        Sorting::TopoOrder => Queue::Stack(Vec::new()),
+        Sorting::DateOrder | Sorting::AuthorDateOrder => {
+            let mut buf = Vec::new();
+            let times = commits
+                .iter()
+                .map(|commit| match (sorting, commit.commit_time) {
+                    (Sorting::DateOrder, Some(time)) => Ok(time),
+                    _ => {
+                        let commit = objects.find_commit_iter(&commit.id, &mut buf)?;
+                        let signature = if matches!(sorting, Sorting::AuthorDateOrder) {
+                            commit.author()?
+                        } else {
+                            commit.committer()?
+                        };
+                        Ok(signature.time.seconds)
+                    }
+                })
+                .collect::<Result<_, gix_traverse::commit::simple::Error>>()?;
+            Queue::Time {
+                times,
+                heap: BinaryHeap::new(),
+                count: 0,
+            }
+        }
+    };
+    for (idx, _) in indegrees.iter().enumerate().filter(|(_, indegree)| **indegree == 1) {
+        queue.push(idx);
+    }
+    if let Queue::Stack(stack) = &mut queue {
+        // The first tip is supposed to be returned first.
+        stack.reverse();
+    }
+
+    let mut order = Vec::with_capacity(commits.len());
+    while let Some(idx) = queue.pop() {
+        for parent_id in &commits[idx].parent_ids {
+            if let Some(&parent_idx) = index.get(parent_id) {
+                indegrees[parent_idx] -= 1;
+                if indegrees[parent_idx] == 1 {
+                    queue.push(parent_idx);
+                }
+            }
+        }
+        order.push(idx);
+    }
+
+    let mut commits: Vec<_> = commits.into_iter().map(Some).collect();
+    Ok(order.into_iter().filter_map(|idx| commits[idx].take()).collect())
+}
+
+impl Filter {
+    fn is_empty(&self) -> bool {
+        self.since.is_none()
+            && self.until.is_none()
+            && self.authors.is_empty()
+            && self.committers.is_empty()
+            && self.paths.is_empty()
+    }
+
+    fn into_matcher(self, commit_graph: Option<gix_commitgraph::Graph>) -> Result<Matcher, Error> {
+        let compile = |patterns: Vec<BString>| patterns.into_iter().map(Pattern::new).collect::<Result<Vec<_>, _>>();
+        // Bloom filters contain paths without leading or trailing slashes, and can't tell anything about the root.
+        let bloom_filter_paths = self
+            .paths
+            .iter()
+            .map(|path| {
+                let path: BString = path
+                    .split_str("/")
+                    .filter(|component| !component.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(&b"/"[..])
+                    .into();
+                (!path.is_empty()).then_some(path)
+            })
+            .collect::<Option<Vec<_>>>()
+            .unwrap_or_default();
+        Ok(Matcher {
+            since: self.since,
+            until: self.until,
+            authors: compile(self.authors)?,
+            committers: compile(self.committers)?,
+            paths: self.paths,
+            bloom_filter_paths,
+            commit_graph,
+            buf: Vec::new(),
+            tree_buf: Vec::new(),
+            signature: BString::default(),
+        })
+    }
+}
+
+/// A pattern to match signatures against.
+enum Pattern {
+    Text(BString),
+    #[cfg(feature = "revparse-regex")]
+    Regex(regex::bytes::Regex),
+}
+
+impl Pattern {
+    fn new(pattern: BString) -> Result<Self, Error> {
+        #[cfg(feature = "revparse-regex")]
+        {
+            let regex = regex::bytes::Regex::new(pattern.to_str_lossy().as_ref())?;
+            if regex::escape(regex.as_str()) != pattern {
+                return Ok(Pattern::Regex(regex));
+            }
+        }
+        Ok(Pattern::Text(pattern))
+    }
+
+    fn is_match(&self, haystack: &BStr) -> bool {
+        match self {
+            Pattern::Text(text) => haystack.contains_str(text),
+            #[cfg(feature = "revparse-regex")]
+            Pattern::Regex(regex) => regex.is_match(haystack),
+        }
+    }
+}
+
+/// The state needed to apply a [`Filter`] to each commit of a traversal.
+struct Matcher {
+    since: Option<gix_date::SecondsSinceUnixEpoch>,
+    until: Option<gix_date::SecondsSinceUnixEpoch>,
+    authors: Vec<Pattern>,
+    committers: Vec<Pattern>,
+    paths: Vec<BString>,
+    /// Our `paths` in the form used in changed-path Bloom filters, or empty if these can't be used.
+    bloom_filter_paths: Vec<BString>,
+    commit_graph: Option<gix_commitgraph::Graph>,
+    buf: Vec<u8>,
+    tree_buf: Vec<u8>,
+    signature: BString,
+}
+
+impl Matcher {
+    /// Return `true` if the commit described by `info` passes all filters.
+    fn matches(
+        &mut self,
+        info: &gix_traverse::commit::Info,
+        objects: &impl gix_object::Find,
+    ) -> Result<bool, gix_traverse::commit::simple::Error> {
+        if self.is_unchanged_according_to_bloom_filter(info) {
+            return Ok(false);
+        }
+        let mut commit = objects.find_commit_iter(&info.id, &mut self.buf)?;
+        let tree = commit.tree_id()?;
+        let committer = commit.committer()?;
+        let time = committer.time.seconds;
+        if matches!(self.since, Some(since) if time < since) || matches!(self.until, Some(until) if time > until) {
+            return Ok(false);
+        }
+        let signature = &mut self.signature;
+        let mut matches_any = |patterns: &[Pattern], name: &BStr, email: &BStr| {
+            signature.clear();
+            signature.push_str(name);
+            signature.push_str(" <");
+            signature.push_str(email);
+            signature.push_byte(b'>');
+            patterns.is_empty() || patterns.iter().any(|pattern| pattern.is_match(signature.as_bstr()))
+        };
+        if !matches_any(&self.committers, committer.name, committer.email) {
+            return Ok(false);
+        }
+        let author = commit.author()?;
+        if !matches_any(&self.authors, author.name, author.email) {
+            return Ok(false);
+        }
+
+        if self.paths.is_empty() {
+            return Ok(true);
+        }
+        let entries = self.entries(tree, objects)?;
+        if info.parent_ids.is_empty() {
+            return Ok(entries.iter().any(Option::is_some));
+        }
+        for parent_id in &info.parent_ids {
+            let parent_tree = objects.find_commit_iter(parent_id, &mut self.buf)?.tree_id()?;
+            if self.entries(parent_tree, objects)? == entries {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Return `true` if the changed-path Bloom filter of the commit described by `info` proves that none of our paths
+    /// changed in comparison to its first parent, which makes comparing trees unnecessary.
+    fn is_unchanged_according_to_bloom_filter(&self, info: &gix_traverse::commit::Info) -> bool {
+        if self.bloom_filter_paths.is_empty() || info.parent_ids.is_empty() {
+            return false;
+        }
+        let Some(filter) = self
+            .commit_graph
+            .as_ref()
+            .and_then(|graph| graph.commit_by_id(info.id))
+            .and_then(|commit| commit.changed_paths())
+        else {
+            return false;
+        };
+        !self
+            .bloom_filter_paths
+            .iter()
+            .any(|path| filter.maybe_contains_path(path.as_bstr()))
+    }
+
+    /// Return the ids and modes of the entries at all of our paths in `tree`, or `None` for each path that doesn't exist.
+    fn entries(
+        &mut self,
+        tree: ObjectId,
+        objects: &impl gix_object::Find,
+    ) -> Result<Vec<Option<(ObjectId, gix_object::tree::EntryMode)>>, gix_traverse::commit::simple::Error> {
+        let mut out = Vec::with_capacity(self.paths.len());
+        for path in &self.paths {
+            let mut entry = Some((
+                tree,
+                gix_object::tree::EntryMode::from(gix_object::tree::EntryKind::Tree),
+            ));
+            for component in path.split_str("/").filter(|component| !component.is_empty()) {
+                entry = match entry {
+                    Some((id, mode)) if mode.is_tree() => {
+                        let mut found = None;
+                        for tree_entry in objects.find_tree_iter(&id, &mut self.tree_buf)? {
+                            let tree_entry = tree_entry?;
+                            if tree_entry.filename == component {
+                                found = Some((tree_entry.oid.to_owned(), tree_entry.mode));
+                                break;
+                            }
+                        }
+                        found
+                    }
+                    _ => None,
+                };
+            }
+            out.push(entry);
+        }
+        Ok(out)
+    }
+}
+
+/// The iterator returned by [`Platform::objects()`].
+pub struct Objects<'repo> {
+    inner: gix_traverse::objects::Objects<
+        &'repo crate::OdbHandle,
+        Box<dyn Iterator<Item = Result<gix_traverse::commit::Info, gix_traverse::commit::simple::Error>> + 'repo>,
+    >,
+}
+
+impl<'repo> Iterator for Objects<'repo> {
+    type Item = Result<gix_traverse::objects::Item, gix_traverse::objects::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
 }
 
 pub(crate) mod iter {
@@ -0,0 +1,239 @@
+//! Handlers for the endpoints of the smart HTTP protocol, which serve the [`UploadPack`] and [`ReceivePack`] services
+//! through any HTTP server or framework that can implement the [`Request`] and [`Response`] traits.
+use std::{io, sync::atomic::AtomicBool};
+
+use gix_features::progress::DynNestedProgress;
+use gix_protocol::transport::{packetline::encode, Service};
+
+use super::{receive_pack, upload_pack, ReceivePack, UploadPack};
+use crate::{
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{keys, Http, Key},
+    },
+    Repository,
+};
+
+/// The request to handle, as received by an HTTP server.
+pub trait Request {
+    /// The method of the request, like `GET` or `POST`.
+    fn method(&self) -> &str;
+    /// The path of the request relative to the URL of the repository, like `info/refs` or `/git-upload-pack`.
+    fn path(&self) -> &str;
+    /// The query of the request without the leading `?`, if there is one.
+    fn query(&self) -> Option<&str>;
+    /// The value of the header `name`, which is matched case-insensitively, if present.
+    fn header(&self, name: &str) -> Option<&str>;
+    /// The body of the request.
+    fn body(&mut self) -> &mut dyn io::Read;
+}
+
+/// The response to a [`Request`], which is sent by an HTTP server.
+///
+/// The status and all headers are set before the body is obtained.
+pub trait Response {
+    /// Set the status code of the response, like `200`.
+    fn set_status(&mut self, status: u16);
+    /// Set the header `name` to `value`.
+    fn set_header(&mut self, name: &str, value: &str);
+    /// The body of the response.
+    fn body(&mut self) -> &mut dyn io::Write;
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod handle {
+    /// The error returned by [`Handler::handle()`](super::Handler::handle()).
+    ///
+    /// Note that requests which can't be served are answered with an error status instead.
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Could not communicate with the client")]
+        Io(#[from] std::io::Error),
+        #[error(transparent)]
+        UploadPack(#[from] crate::server::upload_pack::Error),
+        #[error(transparent)]
+        ReceivePackInit(#[from] crate::server::receive_pack::init::Error),
+        #[error(transparent)]
+        ReceivePack(#[from] crate::server::receive_pack::Error),
+    }
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init {
+    /// The error returned by [`Handler::new()`](super::Handler::new()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Config(#[from] crate::config::boolean::Error),
+    }
+}
+
+/// The outcome of [handling](Handler::handle()) a request.
+#[derive(Debug, Clone)]
+pub enum Outcome {
+    /// The references were advertised for use with the given service.
+    Advertised(Service),
+    /// Objects were negotiated with the client, and possibly sent to it.
+    Uploaded(upload_pack::Outcome),
+    /// Reference updates were received from the client.
+    Received(receive_pack::Outcome),
+    /// The request couldn't be served and was answered with the given error `status`, like `404` for unknown paths
+    /// or `403` for disabled services.
+    Rejected {
+        /// The status code of the response.
+        status: u16,
+    },
+}
+
+/// Serve the smart HTTP protocol for a repository, similar to `git http-backend`.
+///
+/// It handles `GET info/refs?service=<service>` to advertise references, along with `POST git-upload-pack` and
+/// `POST git-receive-pack` to run the respective service. The dumb HTTP protocol isn't supported.
+pub struct Handler<'repo> {
+    repo: &'repo Repository,
+    upload_pack: bool,
+    receive_pack: bool,
+}
+
+/// Lifecycle
+impl<'repo> Handler<'repo> {
+    /// Prepare serving `repo`, with the services enabled by `http.uploadPack` and `http.receivePack`.
+    ///
+    /// Note that pushing is disabled by default as we can't know if the client was authenticated.
+    pub fn new(repo: &'repo Repository) -> Result<Self, init::Error> {
+        let boolean = |key: &'static keys::Boolean, default: bool| -> Result<bool, init::Error> {
+            Ok(repo
+                .config
+                .resolved
+                .boolean_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+                .map(|value| key.enrich_error(value))
+                .transpose()
+                .with_leniency(repo.config.lenient_config)?
+                .unwrap_or(default))
+        };
+        Ok(Handler {
+            repo,
+            upload_pack: boolean(&Http::UPLOAD_PACK, true)?,
+            receive_pack: boolean(&Http::RECEIVE_PACK, false)?,
+        })
+    }
+}
+
+/// Builder
+impl Handler<'_> {
+    /// If `enabled`, allow clients to fetch from the repository.
+    pub fn with_upload_pack(mut self, enabled: bool) -> Self {
+        self.upload_pack = enabled;
+        self
+    }
+
+    /// If `enabled`, allow clients to push to the repository, which should only be done once they were authenticated.
+    pub fn with_receive_pack(mut self, enabled: bool) -> Self {
+        self.receive_pack = enabled;
+        self
+    }
+}
+
+impl Handler<'_> {
+    /// Answer `request` by writing to `response`, using `progress` to show the progress of sending or receiving packs
+    /// and `should_interrupt` to stop it.
+    pub fn handle(
+        &self,
+        request: &mut dyn Request,
+        response: &mut dyn Response,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, handle::Error> {
+        let path = request.path().trim_start_matches('/');
+        let (method, service) = match path {
+            "info/refs" => {
+                let service = request
+                    .query()
+                    .unwrap_or_default()
+                    .split('&')
+                    .find_map(|parameter| parameter.strip_prefix("service="));
+                match service {
+                    Some(service) => ("GET", parse_service(service)),
+                    None => return reject(response, 404, "The dumb HTTP protocol is not supported"),
+                }
+            }
+            "git-upload-pack" => ("POST", Some(Service::UploadPack)),
+            "git-receive-pack" => ("POST", Some(Service::ReceivePack)),
+            _ => return reject(response, 404, "Not Found"),
+        };
+        if request.method() != method {
+            response.set_header("Allow", method);
+            return reject(response, 405, "Method Not Allowed");
+        }
+        let service = match service {
+            Some(Service::UploadPack) if self.upload_pack => Service::UploadPack,
+            Some(Service::ReceivePack) if self.receive_pack => Service::ReceivePack,
+            _ => return reject(response, 403, "Unsupported service"),
+        };
+
+        if method == "GET" {
+            respond(response, service, "advertisement");
+            let out = response.body();
+            encode::text_to_write(format!("# service={}", service.as_str()).as_bytes(), &mut *out)?;
+            encode::flush_to_write(&mut *out)?;
+            match service {
+                Service::UploadPack => UploadPack::new(self.repo).advertise_refs(out)?,
+                Service::ReceivePack => ReceivePack::new(self.repo)?.advertise_refs(out)?,
+            }
+            return Ok(Outcome::Advertised(service));
+        }
+
+        if request.header("Content-Type") != Some(&format!("application/x-{}-request", service.as_str())) {
+            return reject(response, 415, "Unsupported Media Type");
+        }
+        let is_gzip = match request.header("Content-Encoding") {
+            None | Some("identity") => false,
+            Some("gzip" | "x-gzip") => true,
+            Some(_) => return reject(response, 415, "Unsupported Content-Encoding"),
+        };
+        let mut gzip_body;
+        let input: &mut dyn io::Read = if is_gzip {
+            // `git` sends large negotiations compressed.
+            gzip_body = flate2::read::MultiGzDecoder::new(request.body());
+            &mut gzip_body
+        } else {
+            request.body()
+        };
+        respond(response, service, "result");
+        let out = response.body();
+        Ok(match service {
+            Service::UploadPack => {
+                Outcome::Uploaded(UploadPack::new(self.repo).upload(input, out, progress, should_interrupt)?)
+            }
+            Service::ReceivePack => {
+                Outcome::Received(ReceivePack::new(self.repo)?.receive(input, out, progress, should_interrupt)?)
+            }
+        })
+    }
+}
+
+fn parse_service(name: &str) -> Option<Service> {
+    [Service::UploadPack, Service::ReceivePack]
+        .into_iter()
+        .find(|service| service.as_str() == name)
+}
+
+/// Prepare a successful response of `service` with a body of the given `kind`.
+fn respond(response: &mut dyn Response, service: Service, kind: &str) {
+    response.set_status(200);
+    response.set_header("Content-Type", &format!("application/x-{}-{kind}", service.as_str()));
+    response.set_header("Expires", "Fri, 01 Jan 1980 00:00:00 GMT");
+    response.set_header("Pragma", "no-cache");
+    response.set_header("Cache-Control", "no-cache, max-age=0, must-revalidate");
+}
+
+fn reject(response: &mut dyn Response, status: u16, message: &str) -> Result<Outcome, handle::Error> {
+    response.set_status(status);
+    response.set_header("Content-Type", "text/plain");
+    writeln!(response.body(), "{message}")?;
+    Ok(Outcome::Rejected { status })
+}
@@ -0,0 +1,15 @@
+//! Server-side implementations of the services `git` clients connect to, using blocking IO.
+pub mod http;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod receive_pack;
+pub use receive_pack::ReceivePack;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod upload_pack;
+pub use upload_pack::UploadPack;
+
+/// The largest amount of data that fits into a single side-band packet line.
+const MAX_BAND_DATA_LEN: usize = 65515;
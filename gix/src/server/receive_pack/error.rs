@@ -0,0 +1,39 @@
+/// The error returned by [`ReceivePack`](super::ReceivePack) when serving a client.
+///
+/// Note that problems with the pack sent by the client or with individual reference updates are reported to the client
+/// instead.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not communicate with the client")]
+    Io(#[from] std::io::Error),
+    #[error("Could not decode a packet line sent by the client")]
+    DecodePacketLine(#[from] gix_protocol::transport::packetline::decode::Error),
+    #[error("Could not parse the command {line:?} sent by the client")]
+    InvalidCommand { line: crate::bstr::BString },
+    #[error("Failed to list references to advertise them")]
+    IterReferences(#[from] crate::reference::iter::Error),
+    #[error("Failed to list references to advertise them")]
+    IterReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error("Failed to read a reference to advertise it")]
+    ReadReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("Could not resolve the object database directory")]
+    ObjectsDirectory(#[source] gix_path::realpath::Error),
+    #[error("Could not create the quarantine directory for the received objects")]
+    Quarantine(#[source] std::io::Error),
+    #[error(transparent)]
+    WritePack(#[from] gix_pack::bundle::write::Error),
+    #[error("Could not open the index of the received pack")]
+    OpenIndex(#[from] gix_pack::index::init::Error),
+    #[error(transparent)]
+    FindObject(#[from] gix_object::find::existing::Error),
+    #[error("Could not decode a received object")]
+    DecodeObject(#[from] gix_object::decode::Error),
+    #[error("Could not interpolate the value of core.hooksPath")]
+    HooksPath(#[source] gix_config::path::interpolate::Error),
+    #[error("Could not run the hook at {path:?}")]
+    SpawnHook {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+}
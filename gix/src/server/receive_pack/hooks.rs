@@ -0,0 +1,88 @@
+use std::{
+    ffi::OsString,
+    io::Write,
+    path::PathBuf,
+    process::{Command, Stdio},
+};
+
+use super::Error;
+use crate::{
+    bstr::BString,
+    config::tree::{Core, Key},
+    Repository,
+};
+
+/// The result of running a hook.
+pub(crate) struct Output {
+    /// `true` if the hook exited successfully.
+    pub success: bool,
+    /// Everything the hook wrote to stdout, followed by what it wrote to stderr.
+    pub messages: Vec<u8>,
+}
+
+/// Run the hook `name` of `repo` with `args` and `env`, passing `stdin` as its input, or return `None` if there is no such hook.
+pub(crate) fn run(
+    repo: &Repository,
+    name: &str,
+    args: &[&BString],
+    stdin: &[u8],
+    env: &[(OsString, OsString)],
+) -> Result<Option<Output>, Error> {
+    let Some(path) = find(repo, name)? else {
+        return Ok(None);
+    };
+    let mut child = Command::new(&path)
+        .args(args.iter().map(|arg| gix_path::from_bstr(arg.as_ref()).into_owned()))
+        .current_dir(repo.git_dir())
+        .env("GIT_DIR", ".")
+        .envs(env.iter().map(|(key, value)| (key, value)))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|source| Error::SpawnHook {
+            source,
+            path: path.clone(),
+        })?;
+    let mut hook_stdin = child.stdin.take().expect("configured");
+    let output = std::thread::scope(|scope| {
+        // Hooks don't have to read their input, so failing to write it is fine.
+        scope.spawn(move || hook_stdin.write_all(stdin).ok());
+        child.wait_with_output()
+    })
+    .map_err(|source| Error::SpawnHook { source, path })?;
+    let mut messages = output.stdout;
+    messages.extend_from_slice(&output.stderr);
+    Ok(Some(Output {
+        success: output.status.success(),
+        messages,
+    }))
+}
+
+/// Return the path to the executable hook `name` in the `core.hooksPath` directory, or in the `hooks` directory of `repo`.
+fn find(repo: &Repository, name: &str) -> Result<Option<PathBuf>, Error> {
+    let hooks_dir = match repo
+        .config_snapshot()
+        .trusted_path(Core::HOOKS_PATH.logical_name().as_str())
+        .transpose()
+        .map_err(Error::HooksPath)?
+    {
+        Some(path) if path.is_relative() => repo.work_dir().unwrap_or(repo.git_dir()).join(path),
+        Some(path) => path.into_owned(),
+        None => repo.common_dir().join("hooks"),
+    };
+    let path = hooks_dir.join(name);
+    Ok(is_executable(&path).then_some(path))
+}
+
+#[cfg(unix)]
+fn is_executable(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &std::path::Path) -> bool {
+    path.is_file()
+}
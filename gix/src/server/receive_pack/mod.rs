@@ -0,0 +1,476 @@
+use std::{ffi::OsString, io, sync::atomic::AtomicBool};
+
+use gix_features::progress::DynNestedProgress;
+use gix_hash::ObjectId;
+use gix_protocol::{
+    push::{report::RefStatus, Report, Update},
+    transport::packetline::{encode, Channel, PacketLineRef, StreamingPeekableIter},
+};
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    FullName, Target,
+};
+
+use super::MAX_BAND_DATA_LEN;
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    config::{
+        cache::util::ApplyLeniency,
+        tree::{keys, Key, Receive},
+    },
+    ext::ObjectIdExt,
+    Repository,
+};
+
+mod error;
+pub use error::Error;
+
+mod hooks;
+mod quarantine;
+use quarantine::Quarantine;
+
+///
+#[allow(clippy::empty_docs)]
+pub mod init {
+    /// The error returned by [`ReceivePack::new()`](super::ReceivePack::new()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Config(#[from] crate::config::boolean::Error),
+    }
+}
+
+/// The server side of a push, which receives reference updates along with a pack of the objects they need, similar to
+/// `git receive-pack`.
+///
+/// Only protocol V1 is supported as it's the only one used for pushing. Bidirectional connections, like the ones made
+/// via `ssh://` or `git://`, are handled by [`serve()`](Self::serve()), while stateless ones, like smart HTTP, first
+/// [advertise references](Self::advertise_refs()) in one request and [receive updates](Self::receive()) in another.
+///
+/// The received pack is kept in a quarantine directory until all objects needed by the updates are known to be present
+/// and the `pre-receive` hook accepted them. Problems with individual updates are reported to the client in
+/// `report-status` format.
+pub struct ReceivePack<'repo> {
+    repo: &'repo Repository,
+    run_hooks: bool,
+    advertise_push_options: bool,
+    deny_deletes: bool,
+    deny_non_fast_forwards: bool,
+}
+
+/// The outcome of [receiving](ReceivePack::receive()) updates from a client.
+#[derive(Default, Debug, Clone)]
+pub struct Outcome {
+    /// The reference updates requested by the client, which is empty if it had nothing to push.
+    pub updates: Vec<Update>,
+    /// The options the client sent for use by hooks.
+    pub push_options: Vec<BString>,
+    /// The status of each update as it was reported to the client.
+    pub report: Report,
+}
+
+/// Lifecycle
+impl<'repo> ReceivePack<'repo> {
+    /// Prepare receiving pushes into `repo`, configured by `receive.advertisePushOptions`, `receive.denyDeletes`
+    /// and `receive.denyNonFastForwards`.
+    pub fn new(repo: &'repo Repository) -> Result<Self, init::Error> {
+        let boolean = |key: &'static keys::Boolean| -> Result<bool, init::Error> {
+            Ok(repo
+                .config
+                .resolved
+                .boolean_filter_by_key(key.logical_name().as_str(), &mut repo.filter_config_section())
+                .map(|value| key.enrich_error(value))
+                .transpose()
+                .with_leniency(repo.config.lenient_config)?
+                .unwrap_or_default())
+        };
+        Ok(ReceivePack {
+            repo,
+            run_hooks: true,
+            advertise_push_options: boolean(&Receive::ADVERTISE_PUSH_OPTIONS)?,
+            deny_deletes: boolean(&Receive::DENY_DELETES)?,
+            deny_non_fast_forwards: boolean(&Receive::DENY_NON_FAST_FORWARDS)?,
+        })
+    }
+}
+
+/// Builder
+impl ReceivePack<'_> {
+    /// If `enabled`, run the `pre-receive`, `update` and `post-receive` hooks of the repository, which is the default.
+    pub fn with_hooks(mut self, enabled: bool) -> Self {
+        self.run_hooks = enabled;
+        self
+    }
+}
+
+impl ReceivePack<'_> {
+    /// Advertise all references to the client along with our capabilities, and then [receive](Self::receive()) its updates.
+    pub fn serve(
+        &self,
+        input: &mut dyn io::Read,
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        self.advertise_refs(out)?;
+        self.receive(input, out, progress, should_interrupt)
+    }
+
+    /// Write all references of the repository along with our capabilities to `out`, for the client to decide
+    /// which updates to send.
+    pub fn advertise_refs(&self, out: &mut dyn io::Write) -> Result<(), Error> {
+        let mut lines = Vec::<BString>::new();
+        for reference in self.repo.references()?.all()? {
+            let reference = reference.map_err(Error::ReadReference)?;
+            if let Some(id) = reference.target().try_id() {
+                let mut line = BString::from(format!("{id} "));
+                line.push_str(reference.name().as_bstr());
+                lines.push(line);
+            }
+        }
+        if lines.is_empty() {
+            lines.push(format!("{} capabilities^{{}}", self.repo.object_hash().null()).into());
+        }
+        lines[0].push(0);
+        lines[0].push_str(self.capabilities());
+        for line in lines {
+            encode::text_to_write(&line, &mut *out)?;
+        }
+        encode::flush_to_write(&mut *out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Read the reference updates from `input` along with the pack containing their objects, apply them and write the
+    /// status of each of them to `out` if the client asked for it with the `report-status` or `report-status-v2` capability.
+    ///
+    /// `progress` is used to show the progress of receiving the pack, and `should_interrupt` can stop it.
+    /// If the client had nothing to push, nothing is written.
+    pub fn receive(
+        &self,
+        input: &mut dyn io::Read,
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        let repo = self.repo;
+        let mut reader = StreamingPeekableIter::new(input, &[PacketLineRef::Flush], repo.config.trace_packet());
+        let mut updates = Vec::new();
+        let mut capabilities = Vec::<BString>::new();
+        while let Some(line) = reader.read_line() {
+            let line = line??;
+            let line = line.as_bstr().ok_or_else(|| Error::InvalidCommand {
+                line: line.as_slice().unwrap_or_default().into(),
+            })?;
+            let line = line.strip_suffix(b"\n").unwrap_or(line).as_bstr();
+            let command = match line.split_once_str(b"\0") {
+                Some((command, features)) => {
+                    if updates.is_empty() {
+                        capabilities = features.fields().map(|feature| feature.as_bstr().to_owned()).collect();
+                    }
+                    command.as_bstr()
+                }
+                None => line,
+            };
+            updates.push(
+                parse_command(command, repo.object_hash())
+                    .ok_or_else(|| Error::InvalidCommand { line: line.into() })?,
+            );
+        }
+        if updates.is_empty() {
+            return Ok(Outcome::default());
+        }
+        let has_capability = |name: &str| capabilities.iter().any(|capability| capability == name);
+        let mut push_options = Vec::new();
+        if has_capability("push-options") {
+            reader.reset();
+            while let Some(line) = reader.read_line() {
+                if let Some(option) = line??.as_bstr() {
+                    push_options.push(option.strip_suffix(b"\n").unwrap_or(option).as_bstr().to_owned());
+                }
+            }
+        }
+        let input = reader.into_inner();
+        let side_band = has_capability("side-band-64k");
+        let atomic = has_capability("atomic");
+        let report_status = has_capability("report-status") || has_capability("report-status-v2");
+
+        let mut errors = vec![None::<&'static str>; updates.len()];
+        let quarantine = if updates.iter().any(|update| !update.is_delete()) {
+            match Quarantine::receive(repo, input, progress, should_interrupt) {
+                Ok(quarantine) => Some(quarantine),
+                Err(err) => {
+                    let report = Report {
+                        unpack_error: Some(err.to_string().into()),
+                        refs: updates
+                            .iter()
+                            .map(|update| RefStatus::new(update.name.as_ref(), Some("unpacker error".into())))
+                            .collect(),
+                    };
+                    write_report(&report, report_status, side_band, out)?;
+                    return Ok(Outcome {
+                        updates,
+                        push_options,
+                        report,
+                    });
+                }
+            }
+        } else {
+            None
+        };
+        if let Some(quarantine) = &quarantine {
+            let is_connected = quarantine.is_connected(repo)?;
+            for (update, error) in updates.iter().zip(errors.iter_mut()) {
+                if !update.is_delete() && (!is_connected || !quarantine.exists(repo, &update.new)) {
+                    *error = Some("missing necessary objects");
+                }
+            }
+        }
+        fail_remaining_if_atomic(atomic, &mut errors);
+
+        let mut hook_env = Vec::<(OsString, OsString)>::new();
+        if has_capability("push-options") {
+            hook_env.push(("GIT_PUSH_OPTION_COUNT".into(), push_options.len().to_string().into()));
+            for (index, option) in push_options.iter().enumerate() {
+                hook_env.push((
+                    format!("GIT_PUSH_OPTION_{index}").into(),
+                    gix_path::from_bstr(option.as_bstr()).into_owned().into(),
+                ));
+            }
+        }
+        if self.run_hooks && errors.iter().any(Option::is_none) {
+            let mut env = hook_env.clone();
+            env.extend(quarantine.as_ref().map(Quarantine::env).unwrap_or_default());
+            if let Some(output) = hooks::run(repo, "pre-receive", &[], &hook_input(&updates, &errors), &env)? {
+                write_messages(&output.messages, side_band, out)?;
+                if !output.success {
+                    fail_remaining(&mut errors, "pre-receive hook declined");
+                }
+            }
+        }
+        if let Some(quarantine) = quarantine {
+            if errors.iter().any(Option::is_none) && quarantine.migrate().is_err() {
+                fail_remaining(&mut errors, "unable to migrate objects to permanent storage");
+            }
+        }
+
+        let head_name = (!repo.is_bare()).then(|| repo.head_name().ok().flatten()).flatten();
+        let mut edits = Vec::new();
+        for (index, update) in updates.iter().enumerate() {
+            if errors[index].is_some() {
+                continue;
+            }
+            let name = match FullName::try_from(update.name.clone()) {
+                Ok(name) if name.as_bstr().starts_with(b"refs/") => name,
+                _ => {
+                    errors[index] = Some("funny refname");
+                    continue;
+                }
+            };
+            if let Some(reason) = self.rejection(update, &name, head_name.as_ref()) {
+                errors[index] = Some(reason);
+                continue;
+            }
+            if self.run_hooks {
+                let args = [
+                    &update.name,
+                    &update.old.to_string().into(),
+                    &update.new.to_string().into(),
+                ];
+                if let Some(output) = hooks::run(repo, "update", &args, &[], &hook_env)? {
+                    write_messages(&output.messages, side_band, out)?;
+                    if !output.success {
+                        errors[index] = Some("hook declined");
+                        continue;
+                    }
+                }
+            }
+            edits.push((index, edit(update, name)));
+        }
+        if atomic {
+            fail_remaining_if_atomic(atomic, &mut errors);
+            if errors.iter().all(Option::is_none)
+                && repo
+                    .prepare_reference_edits(edits.into_iter().map(|(_, edit)| edit))
+                    .and_then(crate::reference::transaction::Prepared::commit)
+                    .is_err()
+            {
+                fail_remaining(&mut errors, "atomic transaction failed");
+            }
+        } else {
+            for (index, edit) in edits {
+                if repo.edit_reference(edit).is_err() {
+                    errors[index] = Some("failed to update ref");
+                }
+            }
+        }
+
+        if self.run_hooks && errors.iter().any(Option::is_none) {
+            if let Some(output) = hooks::run(repo, "post-receive", &[], &hook_input(&updates, &errors), &hook_env)? {
+                write_messages(&output.messages, side_band, out)?;
+            }
+        }
+        let report = Report {
+            unpack_error: None,
+            refs: updates
+                .iter()
+                .zip(errors)
+                .map(|(update, error)| RefStatus::new(update.name.as_ref(), error.map(Into::into)))
+                .collect(),
+        };
+        write_report(&report, report_status, side_band, out)?;
+        Ok(Outcome {
+            updates,
+            push_options,
+            report,
+        })
+    }
+
+    fn capabilities(&self) -> String {
+        let mut capabilities = vec![
+            "report-status",
+            "report-status-v2",
+            "delete-refs",
+            "side-band-64k",
+            "quiet",
+            "atomic",
+            "ofs-delta",
+        ];
+        if self.advertise_push_options {
+            capabilities.push("push-options");
+        }
+        let object_format = match self.repo.object_hash() {
+            gix_hash::Kind::Sha1 => "sha1",
+        };
+        let (agent_key, agent) = self.repo.config.user_agent_tuple();
+        format!(
+            "{} object-format={object_format} {agent_key}={}",
+            capabilities.join(" "),
+            agent.unwrap_or_default()
+        )
+    }
+
+    /// Return the reason for rejecting `update` of the reference `name` due to our configuration, if any.
+    fn rejection(&self, update: &Update, name: &FullName, head_name: Option<&FullName>) -> Option<&'static str> {
+        if head_name == Some(name) {
+            return Some(if update.is_delete() {
+                "deletion of the current branch prohibited"
+            } else {
+                "branch is currently checked out"
+            });
+        }
+        if update.is_delete() && self.deny_deletes {
+            return Some("deletion prohibited");
+        }
+        let is_branch_update =
+            !update.is_delete() && !update.old.is_null() && name.as_bstr().starts_with(b"refs/heads/");
+        // Commit times can't be used to cut the traversal short as they may not be monotonic.
+        if is_branch_update
+            && self.deny_non_fast_forwards
+            && !update
+                .new
+                .ancestors(&self.repo.objects)
+                .any(|info| info.is_ok_and(|info| info.id == update.old))
+        {
+            return Some("non-fast-forward");
+        }
+        None
+    }
+}
+
+/// Parse a command like `<old> <new> <name>`, expecting object ids of `kind`.
+fn parse_command(command: &BStr, kind: gix_hash::Kind) -> Option<Update> {
+    let mut tokens = command.splitn(3, |byte| *byte == b' ');
+    let old = ObjectId::from_hex(tokens.next()?).ok()?;
+    let new = ObjectId::from_hex(tokens.next()?).ok()?;
+    let name = tokens.next()?.as_bstr().to_owned();
+    (old.kind() == kind && new.kind() == kind).then_some(Update { name, old, new })
+}
+
+fn edit(update: &Update, name: FullName) -> RefEdit {
+    let expected = |if_null: PreviousValue| {
+        if update.old.is_null() {
+            if_null
+        } else {
+            PreviousValue::MustExistAndMatch(Target::Peeled(update.old))
+        }
+    };
+    RefEdit {
+        change: if update.is_delete() {
+            Change::Delete {
+                expected: expected(PreviousValue::Any),
+                log: RefLog::AndReference,
+            }
+        } else {
+            Change::Update {
+                log: LogChange {
+                    mode: RefLog::AndReference,
+                    force_create_reflog: false,
+                    message: "push".into(),
+                },
+                expected: expected(PreviousValue::MustNotExist),
+                new: Target::Peeled(update.new),
+            }
+        },
+        name,
+        deref: false,
+    }
+}
+
+/// The input of the `pre-receive` and `post-receive` hooks, one line per update that didn't fail yet.
+fn hook_input(updates: &[Update], errors: &[Option<&'static str>]) -> Vec<u8> {
+    let mut input = Vec::new();
+    for (update, _) in updates.iter().zip(errors).filter(|(_, error)| error.is_none()) {
+        input.push_str(format!("{} {} ", update.old, update.new));
+        input.push_str(&update.name);
+        input.push(b'\n');
+    }
+    input
+}
+
+fn fail_remaining(errors: &mut [Option<&'static str>], reason: &'static str) {
+    for error in errors.iter_mut().filter(|error| error.is_none()) {
+        *error = Some(reason);
+    }
+}
+
+/// In atomic mode, fail all updates if one of them failed.
+fn fail_remaining_if_atomic(atomic: bool, errors: &mut [Option<&'static str>]) {
+    if atomic && errors.iter().any(Option::is_some) {
+        fail_remaining(errors, "atomic push failure");
+    }
+}
+
+/// Forward the messages of hooks to the client, which is only possible in side-band mode.
+fn write_messages(messages: &[u8], side_band: bool, out: &mut dyn io::Write) -> io::Result<()> {
+    if !side_band {
+        return Ok(());
+    }
+    for chunk in messages.chunks(MAX_BAND_DATA_LEN) {
+        encode::band_to_write(Channel::Progress, chunk, &mut *out)?;
+    }
+    out.flush()
+}
+
+/// Write `report` to `out` if the client asked for it with `report_status`, and end the side-band if `side_band` is set.
+fn write_report(report: &Report, report_status: bool, side_band: bool, out: &mut dyn io::Write) -> io::Result<()> {
+    if report_status {
+        let mut buf = Vec::new();
+        for line in report.to_lines() {
+            encode::text_to_write(&line, &mut buf)?;
+        }
+        encode::flush_to_write(&mut buf)?;
+        if side_band {
+            for chunk in buf.chunks(MAX_BAND_DATA_LEN) {
+                encode::band_to_write(Channel::Data, chunk, &mut *out)?;
+            }
+        } else {
+            out.write_all(&buf)?;
+        }
+    }
+    if side_band {
+        encode::flush_to_write(&mut *out)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,148 @@
+use std::{ffi::OsString, path::PathBuf, sync::atomic::AtomicBool};
+
+use gix_features::progress::DynNestedProgress;
+use gix_hash::ObjectId;
+use gix_object::{Exists, FindExt};
+
+use super::Error;
+use crate::Repository;
+
+/// A directory next to the object database of the repository which receives the pack sent by the client,
+/// keeping its objects invisible to others until they are known to be connected and accepted by the hooks.
+///
+/// The directory is removed when dropped, unless its objects were [migrated](Quarantine::migrate()) first.
+pub(crate) struct Quarantine {
+    /// The directory that is laid out like an object database.
+    dir: PathBuf,
+    /// The objects database of the repository the quarantine belongs to.
+    objects_dir: PathBuf,
+    /// The object database containing only the received objects.
+    objects: gix_odb::Handle,
+    /// The received pack and its index, once written.
+    pack: Option<gix_pack::bundle::write::Outcome>,
+}
+
+impl Quarantine {
+    /// Read a pack from `input` and write it into a new quarantine directory of `repo`, resolving it with objects of `repo`
+    /// if it's thin.
+    pub fn receive(
+        repo: &Repository,
+        input: &mut dyn std::io::Read,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Self, Error> {
+        let objects_dir = gix_path::realpath(repo.objects.store_ref().path()).map_err(Error::ObjectsDirectory)?;
+        let dir = objects_dir.join(format!(
+            "incoming-{}-{}",
+            std::process::id(),
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |duration| duration.as_nanos())
+        ));
+        std::fs::create_dir_all(dir.join("pack")).map_err(Error::Quarantine)?;
+        let mut quarantine = Quarantine {
+            objects: gix_odb::at_opts(
+                &dir,
+                None,
+                gix_odb::store::init::Options {
+                    object_hash: repo.object_hash(),
+                    ..Default::default()
+                },
+            )
+            .map_err(Error::Quarantine)?,
+            dir,
+            objects_dir,
+            pack: None,
+        };
+        quarantine.pack = Some(gix_pack::Bundle::write_to_directory(
+            &mut std::io::BufReader::new(input),
+            Some(&quarantine.dir.join("pack")),
+            progress,
+            should_interrupt,
+            Some(repo.objects.clone()),
+            gix_pack::bundle::write::Options {
+                thread_limit: None,
+                index_version: None,
+                iteration_mode: gix_pack::data::input::Mode::Verify,
+                object_hash: repo.object_hash(),
+            },
+        )?);
+        Ok(quarantine)
+    }
+
+    /// Return the environment variables that make the quarantined objects available to `git` programs, like hooks.
+    pub fn env(&self) -> Vec<(OsString, OsString)> {
+        vec![
+            ("GIT_QUARANTINE_PATH".into(), self.dir.clone().into()),
+            ("GIT_OBJECT_DIRECTORY".into(), self.dir.clone().into()),
+            (
+                "GIT_ALTERNATE_OBJECT_DIRECTORIES".into(),
+                self.objects_dir.clone().into(),
+            ),
+        ]
+    }
+
+    /// Return `true` if `id` is in the quarantine or in the object database of `repo`.
+    pub fn exists(&self, repo: &Repository, id: &gix_hash::oid) -> bool {
+        self.objects.exists(id) || repo.objects.exists(id)
+    }
+
+    /// Return `true` if all objects referenced by the received objects are either received as well or present in `repo`,
+    /// assuming that objects in `repo` are already connected.
+    pub fn is_connected(&self, repo: &Repository) -> Result<bool, Error> {
+        let Some(index_path) = self.pack.as_ref().and_then(|pack| pack.index_path.as_deref()) else {
+            return Ok(true);
+        };
+        let index = gix_pack::index::File::at(index_path, repo.object_hash())?;
+        let mut buf = Vec::new();
+        let mut referenced = Vec::<ObjectId>::new();
+        for entry in index.iter() {
+            let object = self.objects.find(&entry.oid, &mut buf)?;
+            referenced.clear();
+            match object.kind {
+                gix_object::Kind::Commit => {
+                    let mut commit = gix_object::CommitRefIter::from_bytes(object.data);
+                    referenced.push(commit.tree_id()?);
+                    referenced.extend(commit.parent_ids());
+                }
+                gix_object::Kind::Tree => {
+                    for entry in gix_object::TreeRefIter::from_bytes(object.data) {
+                        let entry = entry?;
+                        // Submodule commits are never expected to be present.
+                        if !entry.mode.is_commit() {
+                            referenced.push(entry.oid.to_owned());
+                        }
+                    }
+                }
+                gix_object::Kind::Tag => {
+                    referenced.push(gix_object::TagRefIter::from_bytes(object.data).target_id()?);
+                }
+                gix_object::Kind::Blob => {}
+            }
+            if !referenced.iter().all(|id| self.exists(repo, id)) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Move the received pack into the object database of the repository, making its objects visible to everyone.
+    pub fn migrate(self) -> std::io::Result<()> {
+        let Some(pack) = &self.pack else {
+            return Ok(());
+        };
+        let pack_dir = self.objects_dir.join("pack");
+        std::fs::create_dir_all(&pack_dir)?;
+        // The index goes last so the pack is complete once it's discovered.
+        for path in [&pack.data_path, &pack.index_path].into_iter().flatten() {
+            std::fs::rename(path, pack_dir.join(path.file_name().expect("pack files have a name")))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for Quarantine {
+    fn drop(&mut self) {
+        std::fs::remove_dir_all(&self.dir).ok();
+    }
+}
@@ -0,0 +1,29 @@
+/// The error returned by [`UploadPack`](super::UploadPack) when serving a client.
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Could not communicate with the client")]
+    Io(#[from] std::io::Error),
+    #[error("Could not decode a packet line sent by the client")]
+    DecodePacketLine(#[from] gix_protocol::transport::packetline::decode::Error),
+    #[error("Could not parse the line {line:?} sent by the client")]
+    InvalidLine { line: crate::bstr::BString },
+    #[error("The client wants {id}, which isn't the tip of an advertised reference")]
+    NotOurRef { id: gix_hash::ObjectId },
+    #[error("Could not read HEAD to advertise it")]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error("Failed to list references to advertise them")]
+    IterReferences(#[from] crate::reference::iter::Error),
+    #[error("Failed to list references to advertise them")]
+    IterReferencesInit(#[from] crate::reference::iter::init::Error),
+    #[error("Failed to read a reference to advertise it")]
+    ReadReference(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
+    #[error("Could not find an object to send")]
+    FindObject(#[from] crate::object::find::existing::Error),
+    #[error("Could not count the objects to send")]
+    CountObjects(#[from] gix_pack::data::output::count::objects::Error),
+    #[error("Could not write the pack to send")]
+    WritePack(#[from] gix_pack::data::output::bytes::Error<gix_pack::data::output::entry::iter_from_counts::Error>),
+    #[error("Could not share the object database for generating the pack")]
+    ShareObjectDatabase(#[source] std::io::Error),
+}
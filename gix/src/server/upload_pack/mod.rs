@@ -0,0 +1,368 @@
+use std::{io, io::Write, sync::atomic::AtomicBool};
+
+use gix_features::{
+    parallel::InOrderIter,
+    progress::{self, Count, DynNestedProgress, Progress},
+};
+use gix_hash::ObjectId;
+use gix_pack::data::output;
+use gix_protocol::transport::packetline::{encode, Channel, PacketLineRef, StreamingPeekableIter};
+
+use super::MAX_BAND_DATA_LEN;
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    Repository,
+};
+
+mod error;
+pub use error::Error;
+
+/// The name and target of all advertised references, along with the name of the branch `HEAD` points to.
+type Refs = (Vec<(BString, ObjectId)>, Option<gix_ref::FullName>);
+
+/// The server side of a fetch or clone, which sends a pack with all objects the client wants but doesn't have yet,
+/// similar to `git upload-pack`.
+///
+/// Only protocol V1 is supported. Bidirectional connections, like the ones made via `ssh://` or `git://`, are handled by
+/// [`serve()`](Self::serve()), while stateless ones, like smart HTTP, first [advertise references](Self::advertise_refs())
+/// in one request and then [negotiate](Self::upload()) in as many requests as the client needs.
+///
+/// Negotiation uses `multi_ack_detailed` if the client supports it, but never tells the client that it's `ready` before
+/// it ran out of objects to offer. Shallow clones, filters and thin packs aren't supported.
+pub struct UploadPack<'repo> {
+    repo: &'repo Repository,
+}
+
+/// The outcome of [serving](UploadPack::serve()) or [uploading](UploadPack::upload()) to a client.
+#[derive(Default, Debug, Clone)]
+pub struct Outcome {
+    /// The objects the client wants, which is empty if it was up to date.
+    pub wants: Vec<ObjectId>,
+    /// The objects the client has which we have as well, in the order they were offered.
+    pub common: Vec<ObjectId>,
+    /// The amount of objects in the pack sent to the client, or `None` if the negotiation didn't finish yet as the client
+    /// of a stateless connection is expected to send another request.
+    pub num_objects: Option<usize>,
+}
+
+/// Lifecycle
+impl<'repo> UploadPack<'repo> {
+    /// Prepare sending objects of `repo` to clients.
+    pub fn new(repo: &'repo Repository) -> Self {
+        UploadPack { repo }
+    }
+}
+
+impl UploadPack<'_> {
+    /// Advertise all references to the client along with our capabilities, negotiate the objects to send and send them.
+    pub fn serve(
+        &self,
+        input: &mut dyn io::Read,
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        self.advertise_refs(out)?;
+        self.negotiate(input, out, progress, should_interrupt, false)
+    }
+
+    /// Write `HEAD` and all references of the repository along with our capabilities to `out`, for the client to
+    /// decide which objects it wants.
+    pub fn advertise_refs(&self, out: &mut dyn io::Write) -> Result<(), Error> {
+        let (refs, head_name) = self.refs()?;
+        let mut lines: Vec<BString> = refs
+            .into_iter()
+            .map(|(name, id)| {
+                let mut line = BString::from(format!("{id} "));
+                line.push_str(name);
+                line
+            })
+            .collect();
+        if lines.is_empty() {
+            lines.push(format!("{} capabilities^{{}}", self.repo.object_hash().null()).into());
+        }
+        lines[0].push(0);
+        lines[0].push_str(self.capabilities(head_name.as_ref().map(gix_ref::FullName::as_bstr)));
+        for line in lines {
+            encode::text_to_write(&line, &mut *out)?;
+        }
+        encode::flush_to_write(&mut *out)?;
+        out.flush()?;
+        Ok(())
+    }
+
+    /// Handle a single request of a stateless connection by reading the objects the client wants and has from `input`,
+    /// and writing our acknowledgements to `out`.
+    ///
+    /// If the client indicated that it's done, the pack is written to `out` as well, using `progress` to show the progress
+    /// of generating it and `should_interrupt` to stop it. Otherwise, the client is expected to send another request.
+    pub fn upload(
+        &self,
+        input: &mut dyn io::Read,
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<Outcome, Error> {
+        self.negotiate(input, out, progress, should_interrupt, true)
+    }
+
+    fn negotiate(
+        &self,
+        input: &mut dyn io::Read,
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+        stateless: bool,
+    ) -> Result<Outcome, Error> {
+        let repo = self.repo;
+        let mut reader = StreamingPeekableIter::new(input, &[PacketLineRef::Flush], repo.config.trace_packet());
+        let mut wants = Vec::new();
+        let mut capabilities = Vec::<BString>::new();
+        while let Some(line) = reader.read_line() {
+            let line = text(line??)?;
+            let (want, features) = line
+                .strip_prefix(b"want ")
+                .map(|want| want.split_once_str(b" ").unwrap_or((want, &[])))
+                .ok_or_else(|| Error::InvalidLine { line: line.into() })?;
+            if wants.is_empty() {
+                capabilities = features.fields().map(|feature| feature.as_bstr().to_owned()).collect();
+            }
+            wants.push(parse_id(want, repo.object_hash()).ok_or_else(|| Error::InvalidLine { line: line.into() })?);
+        }
+        if wants.is_empty() {
+            return Ok(Outcome::default());
+        }
+        let (refs, _) = self.refs()?;
+        if let Some(id) = wants.iter().find(|id| !refs.iter().any(|(_, tip)| tip == *id)) {
+            encode::error_to_write(format!("upload-pack: not our ref {id}").as_bytes(), &mut *out)?;
+            out.flush()?;
+            return Err(Error::NotOurRef { id: *id });
+        }
+        let has_capability = |name: &str| capabilities.iter().any(|capability| capability == name);
+        let multi_ack = has_capability("multi_ack_detailed");
+        let side_band = has_capability("side-band-64k");
+
+        let mut common = Vec::new();
+        loop {
+            reader.reset();
+            let mut done = false;
+            while let Some(line) = reader.read_line() {
+                let line = text(line??)?;
+                if line == "done" {
+                    done = true;
+                    break;
+                }
+                let id = line
+                    .strip_prefix(b"have ")
+                    .and_then(|have| parse_id(have, repo.object_hash()))
+                    .ok_or_else(|| Error::InvalidLine { line: line.into() })?;
+                if !common.contains(&id) && repo.has_object(id) {
+                    common.push(id);
+                    if multi_ack {
+                        encode::text_to_write(format!("ACK {id} common").as_bytes(), &mut *out)?;
+                    } else if common.len() == 1 {
+                        encode::text_to_write(format!("ACK {id}").as_bytes(), &mut *out)?;
+                    }
+                }
+            }
+            if done {
+                break;
+            }
+            if multi_ack || common.is_empty() {
+                encode::text_to_write(b"NAK", &mut *out)?;
+            }
+            out.flush()?;
+            if stateless {
+                return Ok(Outcome {
+                    wants,
+                    common,
+                    num_objects: None,
+                });
+            }
+        }
+        match common.last() {
+            None => {
+                encode::text_to_write(b"NAK", &mut *out)?;
+            }
+            Some(id) if multi_ack => {
+                encode::text_to_write(format!("ACK {id}").as_bytes(), &mut *out)?;
+            }
+            Some(_) => {}
+        }
+
+        let num_objects = if side_band {
+            let mut band = io::BufWriter::with_capacity(MAX_BAND_DATA_LEN, DataBand(&mut *out));
+            let num_objects = self.write_pack(&wants, &common, &mut band, progress, should_interrupt)?;
+            band.flush()?;
+            drop(band);
+            encode::flush_to_write(&mut *out)?;
+            num_objects
+        } else {
+            self.write_pack(&wants, &common, &mut *out, progress, should_interrupt)?
+        };
+        out.flush()?;
+        Ok(Outcome {
+            wants,
+            common,
+            num_objects: Some(num_objects),
+        })
+    }
+
+    /// Return the name and target of `HEAD` and all direct references, along with the peeled objects of annotated tags,
+    /// and the name of the branch `HEAD` points to.
+    fn refs(&self) -> Result<Refs, Error> {
+        let repo = self.repo;
+        let mut refs = Vec::new();
+        let head = repo.head()?;
+        let head_name = match head.kind {
+            crate::head::Kind::Symbolic(branch) => {
+                if let Some(id) = branch.target.try_id() {
+                    refs.push(("HEAD".into(), id.to_owned()));
+                }
+                Some(branch.name)
+            }
+            crate::head::Kind::Detached { target, .. } => {
+                refs.push(("HEAD".into(), target));
+                None
+            }
+            crate::head::Kind::Unborn(_) => None,
+        };
+        for reference in repo.references()?.all()? {
+            let reference = reference.map_err(Error::ReadReference)?;
+            let Some(id) = reference.target().try_id().map(ToOwned::to_owned) else {
+                continue;
+            };
+            let name = reference.name().as_bstr().to_owned();
+            refs.push((name.clone(), id));
+            let object = repo.find_object(id)?;
+            if object.kind == gix_object::Kind::Tag {
+                let mut peeled_name = name;
+                peeled_name.push_str("^{}");
+                refs.push((peeled_name, object.peel_tags_to_end()?.id));
+            }
+        }
+        Ok((refs, head_name))
+    }
+
+    fn capabilities(&self, head_name: Option<&BStr>) -> String {
+        let mut capabilities = vec![
+            "multi_ack_detailed".to_owned(),
+            "side-band-64k".into(),
+            "ofs-delta".into(),
+        ];
+        if let Some(name) = head_name {
+            capabilities.push(format!("symref=HEAD:{name}"));
+        }
+        let object_format = match self.repo.object_hash() {
+            gix_hash::Kind::Sha1 => "sha1",
+        };
+        capabilities.push(format!("object-format={object_format}"));
+        let (agent_key, agent) = self.repo.config.user_agent_tuple();
+        capabilities.push(format!("{agent_key}={}", agent.unwrap_or_default()));
+        capabilities.join(" ")
+    }
+
+    /// Write a pack with all objects reachable from `wants` that aren't reachable from `common` to `out`, and return the
+    /// amount of objects in it.
+    fn write_pack(
+        &self,
+        wants: &[ObjectId],
+        common: &[ObjectId],
+        out: &mut dyn io::Write,
+        progress: &mut dyn DynNestedProgress,
+        should_interrupt: &AtomicBool,
+    ) -> Result<usize, Error> {
+        let repo = self.repo;
+        let mut commits = Vec::new();
+        let mut others = Vec::new();
+        for want in wants {
+            let object = repo.find_object(*want)?;
+            if object.kind != gix_object::Kind::Commit {
+                others.push(object.id);
+            }
+            let object = object.peel_tags_to_end()?;
+            if object.kind == gix_object::Kind::Commit {
+                commits.push(object.id);
+            } else if object.id != *want {
+                others.push(object.id);
+            }
+        }
+        let hidden: Vec<_> = common
+            .iter()
+            .filter_map(|id| repo.find_object(*id).ok()?.peel_tags_to_end().ok())
+            .filter(|object| object.kind == gix_object::Kind::Commit)
+            .map(|object| object.id)
+            .collect();
+
+        let mut db = repo.objects.clone().into_arc().map_err(Error::ShareObjectDatabase)?;
+        db.prevent_pack_unload();
+        db.ignore_replacements = true;
+        let counts = {
+            let mut counting = progress.add_child("counting".into());
+            counting.init(None, progress::count("objects"));
+            let mut input = others.into_iter().map(Ok).chain(
+                gix_traverse::commit::Simple::new(commits, &repo.objects)
+                    .hide(hidden)
+                    .map(|info| info.map(|info| info.id).map_err(|err| Box::new(err) as Box<_>)),
+            );
+            let (counts, _outcome) = output::count::objects_unthreaded(
+                &db,
+                &mut input,
+                &counting,
+                should_interrupt,
+                output::count::objects::ObjectExpansion::TreeAdditionsComparedToAncestor,
+            )?;
+            counts
+        };
+
+        let num_objects = counts.len();
+        let entries = output::entry::iter_from_counts(
+            counts,
+            db,
+            Box::new(progress.add_child("creating entries".into())),
+            output::entry::iter_from_counts::Options {
+                mode: output::entry::iter_from_counts::Mode::PackCopyAndBaseObjects,
+                allow_thin_pack: false,
+                ..Default::default()
+            },
+        );
+        let mut writing = progress.add_child("writing".into());
+        writing.init(None, progress::bytes());
+        for written in output::bytes::FromEntriesIter::new(
+            InOrderIter::from(entries),
+            out,
+            num_objects as u32,
+            gix_pack::data::Version::V2,
+            repo.object_hash(),
+        ) {
+            writing.inc_by(written? as usize);
+        }
+        Ok(num_objects)
+    }
+}
+
+/// Write all data as packet lines on the data channel of the side-band.
+struct DataBand<'a>(&'a mut dyn io::Write);
+
+impl io::Write for DataBand<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(MAX_BAND_DATA_LEN);
+        encode::band_to_write(Channel::Data, &buf[..len], &mut *self.0)?;
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+fn text(line: PacketLineRef<'_>) -> Result<&BStr, Error> {
+    let line = line.as_bstr().ok_or_else(|| Error::InvalidLine {
+        line: line.as_slice().unwrap_or_default().into(),
+    })?;
+    Ok(line.strip_suffix(b"\n").unwrap_or(line).as_bstr())
+}
+
+fn parse_id(hex: &[u8], kind: gix_hash::Kind) -> Option<ObjectId> {
+    ObjectId::from_hex(hex).ok().filter(|id| id.kind() == kind)
+}
@@ -0,0 +1,314 @@
+//! Types and errors for changing which files are present in the worktree with sparse checkouts, similar to `git sparse-checkout`.
+use std::collections::BTreeSet;
+
+use crate::bstr::{BStr, BString, ByteSlice, ByteVec};
+
+/// The patterns of a sparse checkout which define the entries to have in the worktree, as stored in `$GIT_DIR/info/sparse-checkout`.
+///
+/// In *cone mode*, all files in the root of the repository are included, along with all files of the configured directories,
+/// recursively, and the files directly contained in their parent directories. Otherwise, patterns are matched like those in
+/// `.gitignore` files, but a match *includes* the path instead of ignoring it.
+#[derive(Debug, Clone)]
+pub struct Patterns {
+    kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Cone {
+        /// If `true`, everything is included as there was only the `/*` pattern.
+        full: bool,
+        /// Directories whose entries are included recursively, without trailing slash.
+        recursive: BTreeSet<BString>,
+        /// Directories whose files are included, but not their subdirectories, without trailing slash.
+        parents: BTreeSet<BString>,
+    },
+    NonCone {
+        /// The patterns as they would be written to the file.
+        lines: Vec<BString>,
+        list: gix_glob::search::pattern::List<gix_ignore::search::Ignore>,
+    },
+}
+
+/// Instantiation
+impl Patterns {
+    /// Parse `data` as read from `$GIT_DIR/info/sparse-checkout`, in cone mode if `cone` is `true`.
+    ///
+    /// Like `git`, we fall back to non-cone mode if any of the patterns doesn't conform to what cone mode expects,
+    /// which can be checked with [`is_cone()`](Self::is_cone()).
+    pub fn from_bytes(data: &[u8], cone: bool) -> Self {
+        let lines: Vec<BString> = data
+            .lines()
+            .map(ByteSlice::trim_end)
+            .filter(|line| !line.is_empty() && !line.starts_with(b"#"))
+            .map(Into::into)
+            .collect();
+        if cone {
+            if let Some(kind) = parse_cone(&lines) {
+                return Patterns { kind };
+            }
+        }
+        Patterns::non_cone(lines)
+    }
+
+    /// Create cone-mode patterns that include all files of `directories`, recursively, similar to `git sparse-checkout set --cone`.
+    /// Leading and trailing slashes of each directory are ignored.
+    pub fn from_directories(directories: impl IntoIterator<Item = impl AsRef<BStr>>) -> Self {
+        let mut out = Patterns {
+            kind: Kind::Cone {
+                full: false,
+                recursive: Default::default(),
+                parents: Default::default(),
+            },
+        };
+        out.add(directories);
+        out
+    }
+
+    fn non_cone(lines: Vec<BString>) -> Self {
+        let list = gix_glob::search::pattern::List::from_bytes(
+            &join_lines(lines.iter().map(AsRef::as_ref)),
+            "info/sparse-checkout".into(),
+            None,
+        );
+        Patterns {
+            kind: Kind::NonCone { lines, list },
+        }
+    }
+}
+
+/// Access and Mutation
+impl Patterns {
+    /// Return `true` if these patterns are interpreted in cone mode.
+    pub fn is_cone(&self) -> bool {
+        matches!(self.kind, Kind::Cone { .. })
+    }
+
+    /// Add `items` to the patterns, similar to `git sparse-checkout add`.
+    ///
+    /// In cone mode, `items` are directories to include recursively, otherwise they are patterns that are appended.
+    pub fn add(&mut self, items: impl IntoIterator<Item = impl AsRef<BStr>>) {
+        match &mut self.kind {
+            Kind::Cone {
+                full,
+                recursive,
+                parents,
+            } => {
+                for dir in items {
+                    let dir = dir.as_ref().trim_with(|c| c == '/');
+                    if dir.is_empty() {
+                        *full = true;
+                        continue;
+                    }
+                    recursive.insert(dir.as_bstr().to_owned());
+                }
+                parents.clear();
+                let recursive_dirs: Vec<_> = recursive.iter().cloned().collect();
+                for dir in recursive_dirs {
+                    if ancestors(dir.as_ref()).any(|parent| recursive.contains(parent)) {
+                        recursive.remove(&dir);
+                        continue;
+                    }
+                    parents.extend(ancestors(dir.as_ref()).map(ToOwned::to_owned));
+                }
+            }
+            Kind::NonCone { lines, .. } => {
+                let mut lines = std::mem::take(lines);
+                lines.extend(items.into_iter().map(|item| item.as_ref().to_owned()));
+                *self = Patterns::non_cone(lines);
+            }
+        }
+    }
+
+    /// Return `true` if the file at the repository-relative `rela_path` should be present in the worktree.
+    pub fn is_included(&self, rela_path: &BStr) -> bool {
+        match &self.kind {
+            Kind::Cone {
+                full,
+                recursive,
+                parents,
+            } => {
+                let parent = match rela_path.rfind_byte(b'/') {
+                    Some(pos) => rela_path[..pos].as_bstr(),
+                    None => return true,
+                };
+                *full
+                    || parents.contains(parent)
+                    || std::iter::once(parent)
+                        .chain(ancestors(parent))
+                        .any(|dir| recursive.contains(dir))
+            }
+            Kind::NonCone { list, .. } => {
+                // The first match of the path itself or any of its leading directories decides.
+                let mut path = rela_path;
+                let mut is_dir = false;
+                loop {
+                    let basename_pos = path.rfind_byte(b'/').map(|pos| pos + 1);
+                    if let Some(m) = gix_ignore::search::pattern_matching_relative_path(
+                        list,
+                        path,
+                        basename_pos,
+                        Some(is_dir),
+                        gix_glob::pattern::Case::Sensitive,
+                    ) {
+                        return !m.pattern.is_negative();
+                    }
+                    match basename_pos {
+                        Some(pos) => {
+                            path = path[..pos - 1].as_bstr();
+                            is_dir = true;
+                        }
+                        None => return false,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Serialization
+impl Patterns {
+    /// Return the patterns in the format of `$GIT_DIR/info/sparse-checkout`.
+    ///
+    /// In cone mode, the patterns are generated from the included directories just like `git` does it.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        match &self.kind {
+            Kind::Cone {
+                full,
+                recursive,
+                parents,
+            } => {
+                let mut lines: Vec<BString> = vec!["/*".into()];
+                if !*full {
+                    lines.push("!/*/".into());
+                    for parent in parents {
+                        lines.push(format!("/{parent}/").into());
+                        lines.push(format!("!/{parent}/*/").into());
+                    }
+                    lines.extend(recursive.iter().map(|dir| BString::from(format!("/{dir}/"))));
+                }
+                join_lines(lines.iter().map(AsRef::as_ref))
+            }
+            Kind::NonCone { lines, .. } => join_lines(lines.iter().map(AsRef::as_ref)),
+        }
+    }
+}
+
+/// Parse `lines` in cone mode, or return `None` if they don't conform to it.
+fn parse_cone(lines: &[BString]) -> Option<Kind> {
+    let mut has_root = false;
+    let mut has_root_exclusion = false;
+    let mut recursive = BTreeSet::new();
+    let mut parents = BTreeSet::new();
+    for line in lines {
+        let line = line.as_bstr();
+        if line == "/*" {
+            has_root = true;
+        } else if line == "!/*/" {
+            has_root_exclusion = true;
+        } else if let Some(dir) = line.strip_prefix(b"!/").and_then(|dir| dir.strip_suffix(b"/*/")) {
+            let dir = cone_directory(dir)?;
+            if !recursive.remove(dir) {
+                return None;
+            }
+            parents.insert(dir.to_owned());
+        } else if let Some(dir) = line.strip_prefix(b"/").and_then(|dir| dir.strip_suffix(b"/")) {
+            recursive.insert(cone_directory(dir)?.to_owned());
+        } else {
+            return None;
+        }
+    }
+    if !has_root {
+        return None;
+    }
+    Some(Kind::Cone {
+        full: !has_root_exclusion,
+        recursive,
+        parents,
+    })
+}
+
+fn cone_directory(dir: &[u8]) -> Option<&BStr> {
+    (!dir.is_empty() && !dir.iter().any(|b| matches!(b, b'*' | b'?' | b'[' | b'\\'))).then(|| dir.as_bstr())
+}
+
+/// Return all leading directories of `dir`, from the innermost to the outermost one.
+fn ancestors(dir: &BStr) -> impl Iterator<Item = &BStr> {
+    dir.char_indices()
+        .rev()
+        .filter(|(_, _, c)| *c == '/')
+        .map(move |(start, _, _)| dir[..start].as_bstr())
+}
+
+fn join_lines<'a>(lines: impl Iterator<Item = &'a BStr>) -> Vec<u8> {
+    let mut out = Vec::new();
+    for line in lines {
+        out.push_str(line);
+        out.push(b'\n');
+    }
+    out
+}
+
+/// The outcome of [`Repository::sparse_checkout_set()`](crate::Repository::sparse_checkout_set()).
+#[derive(Debug, Default)]
+pub struct Outcome {
+    /// The amount of entries that are now part of the worktree, after they were skipped before.
+    pub entries_included: usize,
+    /// The amount of entries that are now skipped, after they were part of the worktree before.
+    pub entries_excluded: usize,
+    /// The amount of files that were removed from the worktree as their entry is now skipped.
+    pub files_removed: usize,
+    /// The paths of files that would have been removed, but were kept along with their entry as they were modified.
+    pub modified: Vec<BString>,
+    /// The outcome of checking out the files of newly included entries.
+    pub checkout: gix_worktree_state::checkout::Outcome,
+}
+
+/// The error returned by [`Repository::sparse_checkout_patterns()`](crate::Repository::sparse_checkout_patterns()).
+pub mod patterns {
+    /// The error returned by [`Repository::sparse_checkout_patterns()`](crate::Repository::sparse_checkout_patterns()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error(transparent)]
+        Config(#[from] crate::config::boolean::Error),
+        #[error("Could not read sparse checkout patterns from {path:?}")]
+        Io {
+            source: std::io::Error,
+            path: std::path::PathBuf,
+        },
+    }
+}
+
+/// The error returned by [`Repository::sparse_checkout_set()`](crate::Repository::sparse_checkout_set()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("A working tree is required to change the sparse checkout")]
+    MissingWorkDir,
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error("Could not expand the sparse directories of the index")]
+    ExpandSparseIndex(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    RemoveFiles(#[from] gix_worktree_state::checkout::remove_deleted::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error("Failed to reopen object database as Arc (only if thread-safety wasn't compiled in)")]
+    OpenArcOdb(#[source] std::io::Error),
+    #[error(transparent)]
+    Config(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    IndexThreads(#[from] crate::config::key::GenericErrorWithValue),
+    #[error("Could not write sparse checkout patterns to {path:?}")]
+    WritePatterns {
+        source: std::io::Error,
+        path: std::path::PathBuf,
+    },
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
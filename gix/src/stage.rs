@@ -0,0 +1,70 @@
+//! Types and errors for adding worktree files to the index, similar to `git add`.
+use crate::bstr::BString;
+
+/// How the index entry of a path was affected by staging it with [`Repository::stage_path()`](crate::Repository::stage_path()).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// The path wasn't tracked yet and a new entry was added for it.
+    Added,
+    /// The entry of the path changed its object or mode, or its conflict was resolved.
+    Modified,
+    /// The path doesn't exist in the worktree anymore and its entries were removed.
+    Removed,
+    /// The path was already staged as is, and only its filesystem information was refreshed, if it exists.
+    Unchanged,
+}
+
+/// The outcome of [`Repository::stage()`](crate::Repository::stage()).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Outcome {
+    /// All paths whose index entries changed, along with the kind of change, sorted by path.
+    pub changes: Vec<(BString, Change)>,
+}
+
+///
+#[allow(clippy::empty_docs)]
+pub mod path {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Repository::stage_path()`](crate::Repository::stage_path()).
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("A working tree is required to stage paths")]
+        MissingWorkDir,
+        #[error("Could not read {path:?} from the working tree")]
+        Io { source: std::io::Error, path: PathBuf },
+        #[error("Cannot stage {path:?} as it's neither a file nor a symlink")]
+        UnsupportedKind { path: PathBuf },
+        #[error(transparent)]
+        FilesystemOptions(#[from] crate::config::boolean::Error),
+        #[error(transparent)]
+        ConvertToGit(#[from] crate::filter::pipeline::convert_to_git::Error),
+        #[error(transparent)]
+        WriteBlob(#[from] crate::object::write::Error),
+        #[error("The modification time of a file is before the unix epoch")]
+        Stat(#[from] std::time::SystemTimeError),
+    }
+}
+
+/// The error returned by [`Repository::stage()`](crate::Repository::stage()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    FilterPipeline(#[from] crate::repository::filter::pipeline::Error),
+    #[error(transparent)]
+    Config(#[from] crate::config::boolean::Error),
+    #[error(transparent)]
+    MaxPercentChange(#[from] crate::config::unsigned_integer::Error),
+    #[error(transparent)]
+    IndexThreads(#[from] crate::config::key::GenericErrorWithValue),
+    #[error(transparent)]
+    Dirwalk(#[from] crate::dirwalk::Error),
+    #[error(transparent)]
+    StagePath(#[from] path::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
@@ -104,12 +104,17 @@ impl Repository {
     {
         let _span = gix_trace::coarse!("gix::index_worktree_status");
         let workdir = self.work_dir().ok_or(Error::MissingWorkDir)?;
-        let attrs_and_excludes = self.attributes(
+        let mut attrs_and_excludes = self.attributes(
             index,
             crate::worktree::stack::state::attributes::Source::WorktreeThenIdMapping,
             crate::worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
             None,
         )?;
+        // All copies of the stack, one per thread, will share the files they read and parse.
+        attrs_and_excludes
+            .inner
+            .state_mut()
+            .set_shared_cache(Some(Default::default()));
         let pathspec = crate::Pathspec::new(
             self,
             options
@@ -158,6 +163,7 @@ impl Repository {
                 object_hash: self.object_hash(),
                 tracked_file_modifications: gix_status::index_as_worktree::Options {
                     fs: fs_caps,
+                    fs_per_mount_point: self.config.fs_per_mount_point()?,
                     thread_limit: options.thread_limit,
                     stat: self.stat_options()?,
                 },
@@ -329,6 +335,7 @@ pub mod iter {
         /// The index that was used for the operation.
         pub index: crate::worktree::IndexPersistedOrInMemory,
         skip_hash: bool,
+        thread_limit: Option<usize>,
         changes: Option<Vec<(usize, iter::ApplyChange)>>,
     }
 
@@ -351,6 +358,7 @@ pub mod iter {
                 IndexPersistedOrInMemory::InMemory(index) => index.clone(),
             };
 
+            let has_fs_monitor = index.fs_monitor().is_some();
             let entries = index.entries_mut();
             for (entry_index, change) in changes {
                 let entry = &mut entries[entry_index];
@@ -360,6 +368,9 @@ pub mod iter {
                     }
                     ApplyChange::NewStat(new_stat) => {
                         entry.stat = new_stat;
+                        if has_fs_monitor {
+                            entry.flags.insert(gix_index::entry::Flags::FSMONITOR_VALID);
+                        }
                     }
                 }
             }
@@ -367,6 +378,7 @@ pub mod iter {
             Some(index.write(crate::index::write::Options {
                 extensions: Default::default(),
                 skip_hash: self.skip_hash,
+                thread_limit: self.thread_limit,
             }))
         }
     }
@@ -629,6 +641,10 @@ pub mod iter {
                 .transpose()
                 .with_lenient_default(self.repo.config.lenient_config)?
                 .unwrap_or_default();
+            let thread_limit = self
+                .repo
+                .index_thread_limit()
+                .map_err(crate::worktree::open_index::Error::from)?;
             let should_interrupt = self.should_interrupt.clone().unwrap_or_default();
             let submodule = BuiltinSubmoduleStatus::new(self.repo.clone().into_sync(), self.submodules)?;
             #[cfg(feature = "parallel")]
@@ -660,6 +676,7 @@ pub mod iter {
                                 index,
                                 changes: None,
                                 skip_hash,
+                                thread_limit,
                             })
                         }
                     })
@@ -695,6 +712,7 @@ pub mod iter {
                     index,
                     changes: None,
                     skip_hash,
+                    thread_limit,
                 };
                 let mut iter = super::Iter {
                     items: Vec::new().into_iter(),
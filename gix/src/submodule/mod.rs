@@ -452,6 +452,17 @@ pub mod status {
 #[cfg(feature = "status")]
 pub use status::types::Status;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(all(
+    feature = "blocking-network-client",
+    feature = "worktree-mutation",
+    feature = "status",
+    feature = "revision",
+    feature = "merge"
+))]
+pub mod update;
+
 /// A summary of the state of all parts forming a submodule, which allows to answer various questions about it.
 ///
 /// Note that expensive questions about its presence in the `HEAD` or the `index` are left to the caller.
@@ -0,0 +1,605 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::AtomicBool,
+};
+
+use gix_merge::blob::builtin_driver::text::Labels;
+use gix_ref::{
+    transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+    Target,
+};
+use gix_submodule::config::Update;
+
+use crate::{
+    bstr::{BStr, BString, ByteSlice, ByteVec},
+    Repository, Submodule,
+};
+
+/// Options for use in [`Submodule::apply_update()`] and [`Repository::update_submodules()`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// If `true`, also update submodules that aren't active yet, and initialize them by writing their url to the configuration
+    /// of the superproject, similar to `git submodule update --init`.
+    pub init: bool,
+    /// If `true`, also update the submodules of each updated submodule, similar to `git submodule update --recursive`.
+    pub recursive: bool,
+    /// If `true`, check out the recorded commit even if the submodule worktree has modifications which are then lost.
+    pub force: bool,
+}
+
+/// What happened to a submodule during an update.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// The submodule isn't active and wasn't initialized, it has no commit recorded in the index of the superproject,
+    /// or its `update` strategy is `none`.
+    Skipped,
+    /// The submodule repository was cloned and the recorded commit was checked out on a detached `HEAD`.
+    Cloned,
+    /// The submodule already was at the recorded commit.
+    UpToDate,
+    /// The recorded commit was checked out on a detached `HEAD`, with `previous` being the commit `HEAD` pointed to before.
+    CheckedOut {
+        /// The commit that was checked out before, if there was one.
+        previous: Option<gix_hash::ObjectId>,
+    },
+    /// With the `rebase` or `merge` strategy, the commit `HEAD` pointed to was an ancestor of the recorded commit,
+    /// so `HEAD` or the branch it points to was fast-forwarded.
+    FastForwarded {
+        /// The commit that was checked out before.
+        previous: gix_hash::ObjectId,
+    },
+    /// With the `merge` strategy, the recorded commit was merged into the commit `HEAD` pointed to, and the branch `HEAD`
+    /// points to was moved to the merge commit.
+    Merged {
+        /// The commit that was checked out before.
+        previous: gix_hash::ObjectId,
+        /// The merge commit that is checked out now.
+        merge_commit: gix_hash::ObjectId,
+    },
+    /// With the `rebase` strategy, the commits that were only reachable from `HEAD` were replayed onto the recorded commit,
+    /// and the branch `HEAD` points to was moved to the last of them.
+    Rebased {
+        /// The commit that was checked out before.
+        previous: gix_hash::ObjectId,
+        /// The last of the replayed commits, or the recorded commit if all of them were already contained in it.
+        head: gix_hash::ObjectId,
+    },
+}
+
+/// The outcome of [`Submodule::apply_update()`] and [`Repository::update_submodules()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Outcome {
+    /// The path of each submodule relative to the repository the update was started in, along with what happened to it,
+    /// in the order in which they were updated. Each submodule is followed by its own submodules if the update was recursive.
+    pub submodules: Vec<(BString, Action)>,
+}
+
+/// The error returned by [`Submodule::apply_update()`] and [`Repository::update_submodules()`].
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Update of submodule at '{path}' with a custom command isn't supported")]
+    UnsupportedCommand { path: BString },
+    #[error("Merging the recorded commit {id} into the submodule at '{path}' caused conflicts")]
+    MergeConflict { id: gix_hash::ObjectId, path: BString },
+    #[error("Rebasing commit {commit} of the submodule at '{path}' onto {onto} caused conflicts")]
+    RebaseConflict {
+        commit: gix_hash::ObjectId,
+        onto: gix_hash::ObjectId,
+        path: BString,
+    },
+    #[error("An author and a committer must be configured to merge or rebase the submodule at '{path}'")]
+    IdentityMissing { path: BString },
+    #[error("Refusing to update the submodule at '{path}' as it has local modifications")]
+    Dirty { path: BString },
+    #[error(transparent)]
+    Modules(#[from] crate::submodule::modules::Error),
+    #[error(transparent)]
+    IsActive(#[from] crate::submodule::is_active::Error),
+    #[error(transparent)]
+    Path(#[from] gix_submodule::config::path::Error),
+    #[error(transparent)]
+    Url(#[from] gix_submodule::config::url::Error),
+    #[error(transparent)]
+    Update(#[from] gix_submodule::config::update::Error),
+    #[error(transparent)]
+    IndexId(#[from] crate::submodule::index_id::Error),
+    #[error(transparent)]
+    Open(#[from] crate::submodule::open::Error),
+    #[error(transparent)]
+    Clone(#[from] crate::clone::Error),
+    #[error(transparent)]
+    CloneFetch(#[from] crate::clone::fetch::Error),
+    #[error(transparent)]
+    FindRemote(#[from] crate::remote::find::existing::Error),
+    #[error(transparent)]
+    Connect(#[from] crate::remote::connect::Error),
+    #[error(transparent)]
+    PrepareFetch(#[from] crate::remote::fetch::prepare::Error),
+    #[error(transparent)]
+    Fetch(#[from] crate::remote::fetch::Error),
+    #[error("The commit {id} recorded for the submodule at '{path}' couldn't be obtained from its remote")]
+    MissingCommit { id: gix_hash::ObjectId, path: BString },
+    #[error(transparent)]
+    LoadConfig(#[from] gix_config::file::init::from_paths::Error),
+    #[error(transparent)]
+    SetConfig(#[from] gix_config::file::set_raw_value::Error),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    IsDirty(#[from] crate::status::is_dirty::Error),
+    #[error(transparent)]
+    Traverse(#[from] gix_traverse::commit::simple::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    NotACommit(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error(transparent)]
+    IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+    #[error(transparent)]
+    OpenIndex(#[from] crate::worktree::open_index::Error),
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    RemoveFiles(#[from] gix_worktree_state::checkout::remove_deleted::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+    #[error(transparent)]
+    FindHead(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    EditHead(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    Walk(#[from] crate::revision::walk::Error),
+    #[error(transparent)]
+    TreeMergeOptions(#[from] crate::repository::merge::tree_merge_options::Error),
+    #[error(transparent)]
+    MergeCommits(#[from] crate::repository::merge::merge_commits::Error),
+    #[error(transparent)]
+    MergeTrees(#[from] crate::repository::merge::merge_trees::Error),
+    #[error(transparent)]
+    Time(#[from] crate::config::time::Error),
+    #[error(transparent)]
+    WriteObject(#[from] crate::object::write::Error),
+}
+
+impl Repository {
+    /// Update all submodules of this repository to the commits recorded in its index, similar to `git submodule update`,
+    /// and return what happened to each of them.
+    ///
+    /// See [`Submodule::apply_update()`] for details.
+    pub fn update_submodules(&self, options: Options, should_interrupt: &AtomicBool) -> Result<Outcome, Error> {
+        let mut out = Outcome::default();
+        if let Some(submodules) = self.submodules()? {
+            for submodule in submodules {
+                submodule.update_inner(options, should_interrupt, "".into(), &mut out)?;
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl Submodule<'_> {
+    /// Bring this submodule to the commit recorded in the index of the superproject, similar to `git submodule update`,
+    /// and return what happened to it, along with its own submodules if `options.recursive` is set.
+    ///
+    /// If its repository doesn't exist yet, it's cloned into `$GIT_DIR/modules/<name>` of the superproject, from the `url` configured
+    /// for the submodule, which may be relative to the url of the default remote of the superproject. Missing commits are fetched
+    /// from the default remote of the submodule.
+    ///
+    /// With the `checkout` strategy, the recorded commit is checked out on a detached `HEAD`, which fails if the submodule worktree has
+    /// modifications, unless `options.force` is set. With the `rebase` or `merge` strategy, the branch `HEAD` points to is
+    /// fast-forwarded to the recorded commit if possible. Otherwise, with `merge`, the recorded commit is merged into it, and with
+    /// `rebase`, the commits that are only reachable from `HEAD` are replayed onto the recorded commit, skipping merge commits.
+    /// Both fail without changing anything if there are conflicts, and both need an author and committer to be configured in the
+    /// submodule repository. Custom commands are not supported, and submodules with the `none` strategy are skipped.
+    pub fn apply_update(&self, options: Options, should_interrupt: &AtomicBool) -> Result<Outcome, Error> {
+        let mut out = Outcome::default();
+        self.update_inner(options, should_interrupt, "".into(), &mut out)?;
+        Ok(out)
+    }
+
+    fn update_inner(
+        &self,
+        options: Options,
+        should_interrupt: &AtomicBool,
+        prefix: &BStr,
+        out: &mut Outcome,
+    ) -> Result<(), Error> {
+        let mut path = prefix.to_owned();
+        path.push_str(self.path()?.as_ref());
+        let (action, repo) = self.update_one(options, should_interrupt, path.as_ref())?;
+        out.submodules.push((path.clone(), action));
+        if let Some(repo) = repo.filter(|_| options.recursive) {
+            if let Some(submodules) = repo.submodules()? {
+                path.push(b'/');
+                for submodule in submodules {
+                    submodule.update_inner(options, should_interrupt, path.as_ref(), out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn update_one(
+        &self,
+        options: Options,
+        should_interrupt: &AtomicBool,
+        path: &BStr,
+    ) -> Result<(Action, Option<Repository>), Error> {
+        if !options.init && !self.is_active()? {
+            return Ok((Action::Skipped, None));
+        }
+        let strategy = self.update()?.unwrap_or_default();
+        match strategy {
+            Update::None => return Ok((Action::Skipped, None)),
+            Update::Command(_) => return Err(Error::UnsupportedCommand { path: path.to_owned() }),
+            Update::Checkout | Update::Rebase | Update::Merge => {}
+        }
+        let id = match self.index_id()? {
+            Some(id) => id,
+            None => return Ok((Action::Skipped, None)),
+        };
+
+        let url = self.resolved_url()?;
+        if options.init {
+            self.initialize(&url)?;
+        }
+        let (repo, cloned) = match self.open()? {
+            Some(repo) => (repo, false),
+            None => (self.clone_repository(url, should_interrupt)?, true),
+        };
+        if !repo.has_object(id) {
+            let remote = repo
+                .find_default_remote(crate::remote::Direction::Fetch)
+                .ok_or_else(|| Error::MissingCommit {
+                    id,
+                    path: path.to_owned(),
+                })??;
+            remote
+                .connect(crate::remote::Direction::Fetch)?
+                .prepare_fetch(gix_features::progress::Discard, Default::default())?
+                .receive(gix_features::progress::Discard, should_interrupt)?;
+            if !repo.has_object(id) {
+                return Err(Error::MissingCommit {
+                    id,
+                    path: path.to_owned(),
+                });
+            }
+        }
+
+        let work_dir = self.work_dir()?;
+        let has_checkout = work_dir.join(gix_discover::DOT_GIT_DIR).exists();
+        let previous = if cloned {
+            None
+        } else {
+            repo.head_id().ok().map(crate::Id::detach)
+        };
+        let action = match previous {
+            Some(previous) if has_checkout && previous == id => Action::UpToDate,
+            Some(previous)
+                if has_checkout
+                    && matches!(strategy, Update::Rebase | Update::Merge)
+                    && !repo.head()?.is_detached() =>
+            {
+                if is_ancestor(&repo, id, previous)? {
+                    Action::UpToDate
+                } else if is_ancestor(&repo, previous, id)? {
+                    switch_to(&repo, id, false, options.force, path)?;
+                    Action::FastForwarded { previous }
+                } else if strategy == Update::Merge {
+                    let merge_commit = merge(&repo, previous, id, path)?;
+                    switch_to(&repo, merge_commit, false, options.force, path)?;
+                    Action::Merged { previous, merge_commit }
+                } else {
+                    let head = rebase(&repo, previous, id, path)?;
+                    switch_to(&repo, head, false, options.force, path)?;
+                    Action::Rebased { previous, head }
+                }
+            }
+            _ => {
+                if !has_checkout {
+                    write_dot_git_file(&repo, &work_dir)?;
+                }
+                switch_to(&repo, id, true, options.force || !has_checkout, path)?;
+                if cloned {
+                    Action::Cloned
+                } else {
+                    Action::CheckedOut { previous }
+                }
+            }
+        };
+        Ok((action, Some(repo)))
+    }
+
+    /// Return our url, resolved against the url of the superproject's default remote if it's relative.
+    fn resolved_url(&self) -> Result<gix_url::Url, Error> {
+        let url = self.url()?;
+        let is_relative =
+            url.scheme == gix_url::Scheme::File && (url.path.starts_with_str("./") || url.path.starts_with_str("../"));
+        if !is_relative {
+            return Ok(url);
+        }
+        let repo = self.state.repo;
+        let base = repo
+            .find_default_remote(crate::remote::Direction::Fetch)
+            .and_then(Result::ok)
+            .and_then(|remote| remote.url(crate::remote::Direction::Fetch).cloned());
+        let mut base = match base {
+            Some(base) => base,
+            None => gix_url::Url::from_bytes(
+                gix_path::into_bstr(repo.work_dir().unwrap_or_else(|| repo.git_dir())).as_ref(),
+            )
+            .map_err(|source| gix_submodule::config::url::Error::Parse {
+                submodule: self.name().to_owned(),
+                source,
+            })?,
+        };
+        let mut path = base.path.clone();
+        while path.ends_with(b"/") {
+            path.pop();
+        }
+        let mut relative = url.path.as_bstr();
+        loop {
+            if let Some(rest) = relative.strip_prefix(b"./") {
+                relative = rest.as_bstr();
+            } else if let Some(rest) = relative.strip_prefix(b"../") {
+                relative = rest.as_bstr();
+                let parent_len = path.rfind_byte(b'/').unwrap_or(0);
+                path.truncate(parent_len);
+            } else {
+                break;
+            }
+        }
+        path.push(b'/');
+        path.push_str(relative);
+        base.path = path;
+        Ok(base)
+    }
+
+    /// Write `url` and mark this submodule active in the local configuration of the superproject, like `git submodule init` would.
+    fn initialize(&self, url: &gix_url::Url) -> Result<(), Error> {
+        let config_path = self.state.repo.common_dir().join("config");
+        let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)?;
+        let url = url.to_bstring();
+        config.set_raw_value("submodule", Some(self.name()), "url", url.as_bstr())?;
+        config.set_raw_value("submodule", Some(self.name()), "active", "true")?;
+        let mut buf = Vec::new();
+        config.write_to(&mut buf)?;
+        std::fs::write(config_path, buf)?;
+        Ok(())
+    }
+
+    /// Clone the repository at `url` into our git directory, configured to use our worktree.
+    fn clone_repository(&self, url: gix_url::Url, should_interrupt: &AtomicBool) -> Result<Repository, Error> {
+        let git_dir = self.git_dir();
+        std::fs::create_dir_all(&git_dir)?;
+        let (repo, _outcome) = crate::clone::PrepareFetch::new(
+            url,
+            &git_dir,
+            crate::create::Kind::Bare,
+            Default::default(),
+            self.state.repo.options.clone(),
+        )?
+        .fetch_only(gix_features::progress::Discard, should_interrupt)?;
+        drop(repo);
+
+        let work_dir = self.work_dir()?;
+        std::fs::create_dir_all(&work_dir)?;
+        let config_path = git_dir.join("config");
+        let mut config = gix_config::File::from_path_no_includes(config_path.clone(), gix_config::Source::Local)?;
+        config.set_raw_value("core", None, "bare", "false")?;
+        let worktree = gix_path::into_bstr(relative_path(&git_dir, &work_dir)?).into_owned();
+        config.set_raw_value("core", None, "worktree", worktree.as_bstr())?;
+        let mut buf = Vec::new();
+        config.write_to(&mut buf)?;
+        std::fs::write(config_path, buf)?;
+
+        Ok(self.open()?.expect("just cloned"))
+    }
+}
+
+fn write_dot_git_file(repo: &Repository, work_dir: &Path) -> Result<(), Error> {
+    std::fs::create_dir_all(work_dir)?;
+    let mut content = BString::from("gitdir: ");
+    content.push_str(gix_path::into_bstr(relative_path(work_dir, repo.git_dir())?).as_ref());
+    content.push(b'\n');
+    std::fs::write(work_dir.join(gix_discover::DOT_GIT_DIR), content)?;
+    Ok(())
+}
+
+/// Check out `id` in `repo`, either on a detached `HEAD` if `detach` is `true`, or by moving the branch `HEAD` points to.
+fn switch_to(repo: &Repository, id: gix_hash::ObjectId, detach: bool, force: bool, path: &BStr) -> Result<(), Error> {
+    if !force && repo.is_dirty()? {
+        return Err(Error::Dirty { path: path.to_owned() });
+    }
+    let work_dir = repo.work_dir().expect("submodules have a worktree");
+    let tree = repo.find_object(id)?.try_into_commit()?.tree_id()?.detach();
+    let mut index = repo.index_from_tree(&tree)?;
+    // Read the index from disk as the shared one may be stale if it was written within the same filesystem tick.
+    let previous: gix_index::State = match repo.open_index() {
+        Ok(previous) => previous.into(),
+        Err(crate::worktree::open_index::Error::IndexFile(gix_index::file::init::Error::Io(err)))
+            if err.kind() == std::io::ErrorKind::NotFound =>
+        {
+            gix_index::State::new(repo.object_hash())
+        }
+        Err(err) => return Err(err.into()),
+    };
+    let mut opts = repo
+        .config
+        .checkout_options(repo, gix_worktree::stack::state::attributes::Source::IdMapping)?;
+    opts.overwrite_existing = true;
+    gix_worktree_state::remove_deleted(&previous, &index, work_dir, &opts, |_, _, _| {
+        Ok::<_, std::io::Error>(false)
+    })?;
+    gix_worktree_state::checkout(
+        &mut index,
+        work_dir,
+        repo.objects.clone().into_arc()?,
+        &gix_features::progress::Discard,
+        &gix_features::progress::Discard,
+        &AtomicBool::default(),
+        opts,
+    )?;
+    index.write(Default::default())?;
+
+    repo.edit_reference(RefEdit {
+        change: Change::Update {
+            log: LogChange {
+                mode: RefLog::AndReference,
+                force_create_reflog: false,
+                message: format!("submodule update: checkout {id}").into(),
+            },
+            expected: PreviousValue::Any,
+            new: Target::Peeled(id),
+        },
+        name: "HEAD".try_into().expect("valid"),
+        deref: !detach,
+    })?;
+    Ok(())
+}
+
+/// Merge `id` into `head` and write a merge commit like `git merge` would, without touching the worktree, and return its id.
+fn merge(
+    repo: &Repository,
+    head: gix_hash::ObjectId,
+    id: gix_hash::ObjectId,
+    path: &BStr,
+) -> Result<gix_hash::ObjectId, Error> {
+    let their_label = id.to_string();
+    let outcome = repo.merge_commits(
+        head,
+        id,
+        Labels {
+            ancestor: None,
+            current: Some("HEAD".into()),
+            other: Some(their_label.as_str().into()),
+        },
+        gix_merge::commit::Options {
+            allow_missing_merge_base: false,
+            tree_merge: repo.tree_merge_options()?,
+        },
+    )?;
+    if outcome.tree_merge.has_conflicts() {
+        return Err(Error::MergeConflict {
+            id,
+            path: path.to_owned(),
+        });
+    }
+    let (author, committer) = identity(repo, path)?;
+    let commit = gix_object::Commit {
+        tree: outcome.tree_merge.tree,
+        parents: [head, id].into_iter().collect(),
+        author,
+        committer,
+        encoding: None,
+        message: format!("Merge commit '{id}'\n").into(),
+        extra_headers: Vec::new(),
+    };
+    Ok(repo.write_object(&commit)?.detach())
+}
+
+/// Replay all non-merge commits that are reachable from `head` but not from `onto` onto `onto`, oldest first, like `git rebase`
+/// would, without touching the worktree, and return the id of the last written commit.
+///
+/// Commits whose changes are already contained in the commit they are replayed onto are dropped.
+fn rebase(
+    repo: &Repository,
+    head: gix_hash::ObjectId,
+    mut onto: gix_hash::ObjectId,
+    path: &BStr,
+) -> Result<gix_hash::ObjectId, Error> {
+    let mut to_replay = Vec::new();
+    for info in repo
+        .rev_walk(Some(head))
+        .hide(Some(onto))
+        .topo_sorting(gix_traverse::commit::topo::Sorting::TopoOrder)
+        .all()?
+    {
+        let info = info?;
+        if info.parent_ids.len() == 1 {
+            to_replay.push((info.id, info.parent_ids[0]));
+        }
+    }
+
+    let options = repo.tree_merge_options()?;
+    let (_, committer) = identity(repo, path)?;
+    let tree_of = |id: gix_hash::ObjectId| -> Result<gix_hash::ObjectId, Error> {
+        Ok(repo.find_object(id)?.try_into_commit()?.tree_id()?.detach())
+    };
+    let mut onto_tree = tree_of(onto)?;
+    for (id, parent) in to_replay.into_iter().rev() {
+        let commit = repo.find_object(id)?.try_into_commit()?;
+        let their_label = format!("{id} ({})", commit.message()?.summary());
+        let outcome = repo.merge_trees(
+            tree_of(parent)?,
+            onto_tree,
+            commit.tree_id()?,
+            Labels {
+                ancestor: Some(format!("parent of {id}").as_str().into()),
+                current: Some("HEAD".into()),
+                other: Some(their_label.as_str().into()),
+            },
+            options,
+        )?;
+        if outcome.has_conflicts() {
+            return Err(Error::RebaseConflict {
+                commit: id,
+                onto,
+                path: path.to_owned(),
+            });
+        }
+        if outcome.tree == onto_tree {
+            continue;
+        }
+        let commit = commit.decode()?;
+        let replayed = gix_object::Commit {
+            tree: outcome.tree,
+            parents: [onto].into_iter().collect(),
+            author: commit.author.into(),
+            committer: committer.clone(),
+            encoding: commit.encoding.map(ToOwned::to_owned),
+            message: commit.message.to_owned(),
+            extra_headers: Vec::new(),
+        };
+        onto = repo.write_object(&replayed)?.detach();
+        onto_tree = outcome.tree;
+    }
+    Ok(onto)
+}
+
+/// Return the author and committer as configured in `repo`, for writing commits in the submodule at `path`.
+fn identity(repo: &Repository, path: &BStr) -> Result<(gix_actor::Signature, gix_actor::Signature), Error> {
+    let missing = || Error::IdentityMissing { path: path.to_owned() };
+    let author = repo.author().ok_or_else(missing)??.into();
+    let committer = repo.committer().ok_or_else(missing)??.into();
+    Ok((author, committer))
+}
+
+/// Return `true` if `ancestor` is reachable from `descendant`.
+fn is_ancestor(repo: &Repository, ancestor: gix_hash::ObjectId, descendant: gix_hash::ObjectId) -> Result<bool, Error> {
+    for info in gix_traverse::commit::Simple::new(Some(descendant), &repo.objects) {
+        if info?.id == ancestor {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Return the path to `to` relative to the directory `from`, with both paths being made absolute first.
+fn relative_path(from: &Path, to: &Path) -> std::io::Result<PathBuf> {
+    let from = gix_path::realpath(from).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+    let to = gix_path::realpath(to).map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))?;
+    let common = from
+        .components()
+        .zip(to.components())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut out: PathBuf = from.components().skip(common).map(|_| "..").collect();
+    out.extend(to.components().skip(common));
+    Ok(out)
+}
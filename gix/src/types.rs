@@ -203,22 +203,23 @@ pub struct Remote<'repo> {
     /// The url of the host to talk to, after application of replacements. If it is unset, the `push_url` must be set.
     /// and fetches aren't possible.
     pub(crate) url: Option<gix_url::Url>,
-    /// The rewritten `url`, if it was rewritten.
-    pub(crate) url_alias: Option<gix_url::Url>,
+    /// The rewritten `url` along with the rewrite that was applied, if it was rewritten.
+    pub(crate) url_alias: Option<(gix_url::Url, remote::url::Replacement)>,
     /// The url to use for pushing specifically.
     pub(crate) push_url: Option<gix_url::Url>,
-    /// The rewritten `push_url`, if it was rewritten.
-    pub(crate) push_url_alias: Option<gix_url::Url>,
+    /// The rewritten `push_url`, or the `url` rewritten for pushing if there is no `push_url`, along with the rewrite
+    /// that was applied, if it was rewritten.
+    pub(crate) push_url_alias: Option<(gix_url::Url, remote::url::Replacement)>,
     /// Refspecs for use when fetching.
     pub(crate) fetch_specs: Vec<gix_refspec::RefSpec>,
     /// Refspecs for use when pushing.
     pub(crate) push_specs: Vec<gix_refspec::RefSpec>,
     /// Tell us what to do with tags when fetched.
     pub(crate) fetch_tags: remote::fetch::Tags,
-    // /// Delete local tracking branches that don't exist on the remote anymore.
-    // pub(crate) prune: bool,
-    // /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
-    // pub(crate) prune_tags: bool,
+    /// Delete local tracking branches that don't exist on the remote anymore.
+    pub(crate) prune: bool,
+    /// Delete tags that don't exist on the remote anymore, equivalent to pruning the refspec `refs/tags/*:refs/tags/*`.
+    pub(crate) prune_tags: bool,
     pub(crate) repo: &'repo Repository,
 }
 
@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use crate::bstr::BString;
+
+/// What the `HEAD` of a new linked worktree should point to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Head {
+    /// Detach `HEAD` at the given commit.
+    Detached(gix_hash::ObjectId),
+    /// Point `HEAD` to the existing branch with the given full name, like `refs/heads/main`.
+    ///
+    /// Unless [forced](Options::force), the branch must not be checked out in any other worktree.
+    Branch(gix_ref::FullName),
+    /// Create a new branch with the given full name which points to `target`, and point `HEAD` to it.
+    NewBranch {
+        /// The full name of the branch to create, like `refs/heads/feature`.
+        name: gix_ref::FullName,
+        /// The commit the new branch should point to.
+        target: gix_hash::ObjectId,
+    },
+}
+
+/// Options for use in [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// What `HEAD` of the new worktree should point to.
+    pub head: Head,
+    /// If `Some(reason)`, lock the new worktree right away, with `reason` possibly being empty.
+    pub lock: Option<BString>,
+    /// If `true`, check out the files of the commit `HEAD` points to, along with creating the index.
+    /// Otherwise, the worktree is left empty and without index, similar to `git worktree add --no-checkout`.
+    pub checkout: bool,
+    /// If `true`, allow checking out a branch that is already checked out in another worktree.
+    pub force: bool,
+}
+
+impl Options {
+    /// Create options to add a worktree with its `HEAD` set to `head` and with files checked out.
+    pub fn new(head: Head) -> Self {
+        Options {
+            head,
+            lock: None,
+            checkout: true,
+            force: false,
+        }
+    }
+}
+
+/// The error returned by [`Repository::worktree_add()`](crate::Repository::worktree_add()).
+#[derive(Debug, thiserror::Error)]
+#[allow(missing_docs)]
+pub enum Error {
+    #[error("Refusing to create a worktree at '{}' as it already exists and isn't an empty directory", path.display())]
+    PathExists { path: PathBuf },
+    #[error("Branch '{}' is already checked out at '{}'", name.as_bstr(), path.display())]
+    BranchCheckedOut { name: gix_ref::FullName, path: PathBuf },
+    #[error("Could not write administrative files of the new worktree")]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    FindReference(#[from] crate::reference::find::existing::Error),
+    #[error(transparent)]
+    PeelReference(#[from] crate::reference::peel::Error),
+    #[error(transparent)]
+    CreateBranch(#[from] crate::reference::edit::Error),
+    #[error(transparent)]
+    OpenWorktree(#[from] crate::worktree::proxy::into_repo::Error),
+    #[error(transparent)]
+    FindCommit(#[from] crate::object::find::existing::Error),
+    #[error(transparent)]
+    NotACommit(#[from] crate::object::try_into::Error),
+    #[error(transparent)]
+    DecodeCommit(#[from] gix_object::decode::Error),
+    #[error("Could not create index from tree at {id}")]
+    IndexFromTree {
+        id: gix_hash::ObjectId,
+        source: gix_traverse::tree::breadthfirst::Error,
+    },
+    #[error(transparent)]
+    CheckoutOptions(#[from] crate::config::checkout_options::Error),
+    #[error(transparent)]
+    Checkout(#[from] gix_worktree_state::checkout::Error),
+    #[error(transparent)]
+    WriteIndex(#[from] gix_index::file::write::Error),
+}
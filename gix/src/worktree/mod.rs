@@ -111,6 +111,11 @@ pub(crate) fn id(git_dir: &std::path::Path, has_common_dir: bool) -> Option<&BSt
 #[allow(clippy::empty_docs)]
 pub mod proxy;
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "worktree-mutation")]
+pub mod add;
+
 ///
 #[cfg(feature = "index")]
 pub mod open_index {
@@ -24,6 +24,50 @@ pub mod into_repo {
     }
 }
 
+///
+#[allow(clippy::empty_docs)]
+#[cfg(feature = "status")]
+pub mod remove {
+    use std::path::PathBuf;
+
+    /// The error returned by [`Proxy::remove()`][super::Proxy::remove()].
+    #[derive(Debug, thiserror::Error)]
+    #[allow(missing_docs)]
+    pub enum Error {
+        #[error("Refusing to remove the locked worktree at '{}'", .base.display())]
+        Locked { base: PathBuf },
+        #[error("Refusing to remove the worktree at '{}' as it contains staged, modified or untracked files", .base.display())]
+        Dirty { base: PathBuf },
+        #[error(transparent)]
+        IntoRepo(#[from] super::into_repo::Error),
+        #[error(transparent)]
+        StatusPlatform(#[from] crate::status::Error),
+        #[error(transparent)]
+        StatusIterator(#[from] crate::status::index_worktree::iter::Error),
+        #[error(transparent)]
+        OpenIndex(#[from] crate::worktree::open_index::Error),
+        #[error(transparent)]
+        FindHead(#[from] crate::reference::find::existing::Error),
+        #[error(transparent)]
+        HeadTreeId(#[from] crate::reference::head_tree_id::Error),
+        #[error(transparent)]
+        IndexFromTree(#[from] gix_traverse::tree::breadthfirst::Error),
+        #[error(transparent)]
+        Io(#[from] std::io::Error),
+    }
+}
+
+/// The reason for a linked worktree to be prunable, i.e. for its administrative files to be removable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prunable {
+    /// The `gitdir` file in the administrative directory is missing.
+    GitDirFileMissing,
+    /// The `gitdir` file couldn't be read or doesn't contain a path.
+    GitDirFileInvalid,
+    /// The `gitdir` file points to a location that doesn't exist anymore, typically as the worktree was deleted.
+    GitDirFileLocationMissing,
+}
+
 impl<'repo> Proxy<'repo> {
     pub(crate) fn new(parent: &'repo Repository, git_dir: impl Into<PathBuf>) -> Self {
         Proxy {
@@ -99,3 +143,94 @@ impl<'repo> Proxy<'repo> {
         Ok(repo.into())
     }
 }
+
+/// Mutation
+impl<'repo> Proxy<'repo> {
+    /// Lock this worktree with `reason`, which may be empty, to prevent it from being pruned, moved or deleted,
+    /// like `git worktree lock`. Return `false` if it was already locked, in which case the reason isn't changed.
+    pub fn lock(&self, reason: &BStr) -> std::io::Result<bool> {
+        if self.is_locked() {
+            return Ok(false);
+        }
+        std::fs::write(self.git_dir.join("locked"), reason)?;
+        Ok(true)
+    }
+
+    /// Unlock this worktree, like `git worktree unlock`, and return `false` if it wasn't locked.
+    pub fn unlock(&self) -> std::io::Result<bool> {
+        match std::fs::remove_file(self.git_dir.join("locked")) {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Return the reason for why the administrative files of this worktree can be pruned, or `None` if they
+    /// are still in use or if the worktree is [locked](Self::is_locked()), similar to what `git worktree list` shows.
+    pub fn prunable(&self) -> Option<Prunable> {
+        if self.is_locked() {
+            return None;
+        }
+        let gitdir_file = self.git_dir.join("gitdir");
+        if !gitdir_file.is_file() {
+            return Some(Prunable::GitDirFileMissing);
+        }
+        match gix_discover::path::from_plain_file(&gitdir_file) {
+            Some(Ok(dot_git)) if dot_git.exists() => None,
+            Some(Ok(_)) => Some(Prunable::GitDirFileLocationMissing),
+            Some(Err(_)) | None => Some(Prunable::GitDirFileInvalid),
+        }
+    }
+
+    /// Remove the checkout of this worktree along with its administrative files, similar to `git worktree remove`.
+    ///
+    /// Unless `force` is `true`, locked worktrees, as well as those with staged, modified or untracked files, are not removed.
+    /// Worktrees whose checkout is missing are always removed.
+    #[cfg(feature = "status")]
+    pub fn remove(self, force: bool) -> Result<(), remove::Error> {
+        let base = self.base().ok().filter(|base| base.is_dir());
+        if !force {
+            if self.is_locked() {
+                return Err(remove::Error::Locked {
+                    base: base.unwrap_or_default(),
+                });
+            }
+            if let Some(base) = &base {
+                let repo = self.clone().into_repo()?;
+                let is_dirty = has_staged_changes(&repo)?
+                    || repo
+                        .status(gix_features::progress::Discard)?
+                        .into_index_worktree_iter(Vec::new())?
+                        .next()
+                        .is_some();
+                if is_dirty {
+                    return Err(remove::Error::Dirty { base: base.clone() });
+                }
+            }
+        }
+        if let Some(base) = base {
+            std::fs::remove_dir_all(base)?;
+        }
+        std::fs::remove_dir_all(&self.git_dir)?;
+        Ok(())
+    }
+}
+
+/// Return `true` if the index of `repo` differs from the tree of its `HEAD` commit, which is empty if `HEAD` is unborn.
+#[cfg(feature = "status")]
+fn has_staged_changes(repo: &Repository) -> Result<bool, remove::Error> {
+    let index = repo.index_or_empty()?;
+    let head_index = if repo.head()?.is_unborn() {
+        gix_index::State::new(repo.object_hash())
+    } else {
+        gix_index::State::from_tree(&repo.head_tree_id()?, &repo.objects)?
+    };
+    let entries = |state: &gix_index::State| -> Vec<_> {
+        state
+            .entries()
+            .iter()
+            .map(|entry| (entry.path(state).to_owned(), entry.stage(), entry.mode, entry.id))
+            .collect()
+    };
+    Ok(entries(&index) != entries(&head_index))
+}
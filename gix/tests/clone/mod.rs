@@ -4,6 +4,7 @@ use crate::{remote, util::restricted};
 mod blocking_io {
     use std::{borrow::Cow, sync::atomic::AtomicBool};
 
+    use gix::traverse::objects::Filter;
     use gix::{
         bstr::BString,
         config::tree::{Clone, Core, Init, Key},
@@ -116,8 +117,8 @@ mod blocking_io {
             vec![
                 hex_to_id("2d9d136fb0765f2e24c44a0f91984318d580d03b"),
                 hex_to_id("dfd0954dabef3b64f458321ef15571cc1a46d552"),
-                hex_to_id("dfd0954dabef3b64f458321ef15571cc1a46d552"),
-            ]
+            ],
+            "each shallow commit is only written once"
         );
         assert_eq!(
             repo.config_snapshot().boolean("my.marker"),
@@ -134,6 +135,77 @@ mod blocking_io {
         Ok(())
     }
 
+    #[test]
+    fn partial_with_blob_filter() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let (repo, out) = gix::prepare_clone_bare(remote::repo("base.allow-filter").path(), tmp.path())?
+            .with_filter(Filter::BlobNone)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())?;
+
+        let config = repo.config_snapshot();
+        assert_eq!(config.boolean("remote.origin.promisor"), Some(true));
+        assert_eq!(
+            config.string("remote.origin.partialCloneFilter").as_deref(),
+            Some("blob:none".into())
+        );
+        assert_eq!(
+            config.string("extensions.partialClone").as_deref(),
+            Some("origin".into()),
+            "the remote to fetch missing objects from is recorded"
+        );
+        assert_eq!(
+            config.integer("core.repositoryFormatVersion"),
+            Some(1),
+            "extensions are only honored by git in repositories of version 1"
+        );
+        let reopened = gix::open_opts(repo.git_dir(), crate::restricted())?;
+        assert_eq!(
+            reopened.config_snapshot().integer("core.repositoryFormatVersion"),
+            Some(1),
+            "the version is changed on disk as well"
+        );
+
+        let pack = match out.status {
+            gix::remote::fetch::Status::Change { write_pack_bundle, .. } => write_pack_bundle,
+            _ => unreachable!("a pack is received"),
+        };
+        assert!(
+            pack.data_path.expect("written").with_extension("promisor").is_file(),
+            "the pack is marked as promisor pack"
+        );
+
+        let tree = repo.head_commit()?.tree()?;
+        let blob = tree
+            .iter()
+            .map(|entry| entry.map(|e| (e.mode(), e.object_id())))
+            .find(|entry| matches!(entry, Ok((mode, _)) if mode.is_blob()))
+            .expect("tree has a blob")?
+            .1;
+        assert!(repo.has_object(tree.id), "trees are received");
+        assert!(!repo.has_object(blob), "blobs are left out");
+        Ok(())
+    }
+
+    #[test]
+    fn partial_needs_server_support() -> crate::Result {
+        let tmp = gix_testtools::tempfile::TempDir::new()?;
+        let err = gix::prepare_clone_bare(remote::repo("base").path(), tmp.path())?
+            .with_filter(Filter::BlobNone)
+            .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+            .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                gix::clone::fetch::Error::Fetch(gix::remote::fetch::Error::MissingServerFeature {
+                    feature: "filter",
+                    ..
+                })
+            ),
+            "uploadpack.allowFilter isn't set on the remote"
+        );
+        Ok(())
+    }
+
     #[test]
     fn from_non_shallow_then_deepen_then_deepen_since_to_unshallow() -> crate::Result {
         let tmp = gix_testtools::tempfile::TempDir::new()?;
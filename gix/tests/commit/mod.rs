@@ -44,6 +44,26 @@ mod describe {
             assert_eq!(actual, "main");
             Ok(())
         }
+
+        #[test]
+        fn dirty_suffix_can_be_configured_upfront() -> crate::Result {
+            let repo = named_subrepo_opts(
+                "make_submodules.sh",
+                "submodule-head-changed",
+                gix::open::Options::isolated(),
+            )?;
+
+            let actual = repo
+                .head_commit()?
+                .describe()
+                .names(SelectRef::AllRefs)
+                .dirty_suffix("dirty".to_owned())
+                .try_format()?
+                .expect("a name was found")
+                .to_string();
+            assert_eq!(actual, "main-dirty");
+            Ok(())
+        }
     }
 
     #[test]
@@ -85,4 +105,82 @@ mod describe {
         }
         Ok(())
     }
+
+    #[test]
+    fn names_can_be_matched_and_excluded_with_patterns() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let commit = repo.head_commit()?;
+        let format = |describe: gix::commit::describe::Platform<'_>| -> crate::Result<Option<String>> {
+            Ok(describe.try_format()?.map(|f| f.to_string()))
+        };
+
+        assert_eq!(
+            format(commit.describe().names(AllTags).match_pattern("v2*"))?.as_deref(),
+            Some("v2"),
+            "annotated tags have priority"
+        );
+        assert_eq!(
+            format(
+                commit
+                    .describe()
+                    .names(AllTags)
+                    .match_pattern("v2*")
+                    .exclude_pattern("v2")
+            )?
+            .as_deref(),
+            Some("v2.5"),
+            "exclusions win over matches"
+        );
+        assert_eq!(
+            format(commit.describe().exclude_pattern("v4").exclude_pattern("v5"))?.as_deref(),
+            Some("v2")
+        );
+        assert_eq!(
+            format(commit.describe().match_pattern("does-not-exist"))?,
+            None,
+            "without matching names there is no result"
+        );
+
+        let actual = format(commit.describe().names(AllTags).match_pattern("l*"))?.expect("found");
+        assert!(
+            actual.starts_with("l0-2-g"),
+            "names that don't match are skipped in favor of ones further away: {actual}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn long_format_is_used_even_for_exact_matches() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let commit = repo.head_commit()?;
+        let actual = commit.describe().long(true).format()?.to_string();
+        assert!(actual.starts_with("v4-0-g"), "{actual}");
+        Ok(())
+    }
+
+    #[test]
+    fn zero_candidates_only_allow_exact_matches() -> crate::Result {
+        let repo = named_repo("make_commit_describe_multiple_tags.sh")?;
+        let commit = repo.head_commit()?;
+        assert_eq!(
+            commit
+                .describe()
+                .max_candidates(0)
+                .try_format()?
+                .map(|f| f.to_string())
+                .as_deref(),
+            Some("v4")
+        );
+        assert_eq!(
+            commit
+                .describe()
+                .names(AllTags)
+                .match_pattern("l*")
+                .max_candidates(0)
+                .try_format()?
+                .map(|f| f.to_string()),
+            None
+        );
+        Ok(())
+    }
 }
@@ -176,6 +176,35 @@ mod status {
     }
 }
 
+#[cfg(feature = "merge")]
+mod merge {
+    use crate::config::tree::bcow;
+    use gix::config::tree::{Key, Merge};
+    use gix::merge::blob::builtin_driver::text::ConflictStyle;
+
+    #[test]
+    fn conflict_style() -> crate::Result {
+        for (actual, expected) in [
+            ("merge", ConflictStyle::Merge),
+            ("diff3", ConflictStyle::Diff3),
+            ("zdiff3", ConflictStyle::ZealousDiff3),
+        ] {
+            assert_eq!(Merge::CONFLICT_STYLE.try_into_conflict_style(bcow(actual))?, expected);
+            assert!(Merge::CONFLICT_STYLE.validate(actual.into()).is_ok());
+        }
+
+        assert_eq!(
+            Merge::CONFLICT_STYLE
+                .try_into_conflict_style(bcow("DIFF3"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"merge.conflictStyle=DIFF3\" was invalid",
+            "case-sensitive comparisons"
+        );
+        Ok(())
+    }
+}
+
 mod push {
     use crate::config::tree::bcow;
     use gix::config::tree::Push;
@@ -201,6 +230,25 @@ mod push {
         );
         Ok(())
     }
+
+    #[test]
+    fn gpg_sign() -> crate::Result {
+        for (actual, expected) in [
+            ("if-asked", push::GpgSign::IfAsked),
+            ("true", push::GpgSign::Yes),
+            ("yes", push::GpgSign::Yes),
+            ("false", push::GpgSign::No),
+            ("off", push::GpgSign::No),
+        ] {
+            assert_eq!(Push::GPG_SIGN.try_into_gpg_sign(bcow(actual))?, expected);
+        }
+
+        assert_eq!(
+            Push::GPG_SIGN.try_into_gpg_sign(bcow("maybe")).unwrap_err().to_string(),
+            "The key \"push.gpgSign=maybe\" was invalid"
+        );
+        Ok(())
+    }
 }
 
 mod fetch {
@@ -219,7 +267,7 @@ mod fetch {
             ("noop", Algorithm::Noop),
             ("consecutive", Algorithm::Consecutive),
             ("skipping", Algorithm::Skipping),
-            ("default", Algorithm::Consecutive), // actually, default can be Skipping of `feature.experimental` is true, but we don't deal with that yet until we implement `skipping`
+            ("default", Algorithm::Consecutive), // when fetching, `feature.experimental` makes this `Skipping`
         ] {
             assert_eq!(
                 Fetch::NEGOTIATION_ALGORITHM.try_into_negotiation_algorithm(bcow(actual))?,
@@ -346,17 +394,11 @@ mod diff {
             ("Default", Algorithm::Myers),
             ("minimal", Algorithm::MyersMinimal),
             ("histogram", Algorithm::Histogram),
+            ("patience", Algorithm::Patience),
         ] {
             assert_eq!(Diff::ALGORITHM.try_into_algorithm(bcow(actual))?, expected);
             assert!(Diff::ALGORITHM.validate(actual.into()).is_ok());
         }
-        assert_eq!(
-            Diff::ALGORITHM
-                .try_into_algorithm(bcow("patience"))
-                .unwrap_err()
-                .to_string(),
-            "The 'patience' algorithm is not yet implemented"
-        );
         assert_eq!(
             Diff::ALGORITHM.try_into_algorithm(bcow("foo")).unwrap_err().to_string(),
             "Unknown diff algorithm named 'foo'"
@@ -626,6 +668,43 @@ mod core {
     }
 }
 
+mod gc {
+    use gix::config::tree::{Gc, Key};
+
+    use crate::config::tree::bcow;
+
+    #[test]
+    fn reflog_expire() -> crate::Result {
+        let now = std::time::UNIX_EPOCH + std::time::Duration::from_secs(100 * 24 * 60 * 60);
+        for value in ["never", "false"] {
+            assert_eq!(Gc::REFLOG_EXPIRE.try_into_expiry_date(bcow(value), now)?, None);
+        }
+        for value in ["now", "all"] {
+            assert_eq!(
+                Gc::REFLOG_EXPIRE_UNREACHABLE.try_into_expiry_date(bcow(value), now)?,
+                Some(gix_date::SecondsSinceUnixEpoch::MAX)
+            );
+        }
+        for value in ["90 days", "90.days.ago", "90 days ago"] {
+            assert_eq!(
+                Gc::REFLOG_EXPIRE.try_into_expiry_date(bcow(value), now)?,
+                Some(10 * 24 * 60 * 60),
+                "{value}"
+            );
+            assert!(Gc::REFLOG_EXPIRE.validate(value.into()).is_ok());
+        }
+        assert_eq!(
+            Gc::REFLOG_EXPIRE
+                .try_into_expiry_date(bcow("invalid"), now)
+                .unwrap_err()
+                .to_string(),
+            "The date format at key \"gc.reflogExpire=invalid\" was invalid"
+        );
+        assert!(Gc::REFLOG_EXPIRE_UNREACHABLE.validate("invalid".into()).is_err());
+        Ok(())
+    }
+}
+
 mod index {
     use gix::config::tree::{Index, Key};
 
@@ -678,6 +757,22 @@ mod extensions {
         assert!(Extensions::OBJECT_FORMAT.validate("invalid".into()).is_err());
         Ok(())
     }
+
+    #[test]
+    fn ref_storage() -> crate::Result {
+        assert!(!Extensions::REF_STORAGE.try_into_is_reftable(bcow("files"))?);
+        assert!(Extensions::REF_STORAGE.try_into_is_reftable(bcow("reftable"))?);
+        assert_eq!(
+            Extensions::REF_STORAGE
+                .try_into_is_reftable(bcow("invalid"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"extensions.refStorage=invalid\" was invalid"
+        );
+        assert!(Extensions::REF_STORAGE.validate("reftable".into()).is_ok());
+        assert!(Extensions::REF_STORAGE.validate("invalid".into()).is_err());
+        Ok(())
+    }
 }
 
 mod checkout {
@@ -715,14 +810,19 @@ mod pack {
             gix_pack::index::Version::V2
         );
         assert!(Pack::INDEX_VERSION.validate("2".into()).is_ok());
+        assert_eq!(
+            Pack::INDEX_VERSION.try_into_index_version(Ok(3))?,
+            gix_pack::index::Version::V3
+        );
+        assert!(Pack::INDEX_VERSION.validate("3".into()).is_ok());
         assert_eq!(
             Pack::INDEX_VERSION
-                .try_into_index_version(Ok(3))
+                .try_into_index_version(Ok(4))
                 .unwrap_err()
                 .to_string(),
             "The value of key \"pack.indexVersion\" was invalid"
         );
-        assert!(Pack::INDEX_VERSION.validate("3".into()).is_err());
+        assert!(Pack::INDEX_VERSION.validate("4".into()).is_err());
         assert!(Pack::INDEX_VERSION.validate("-1".into()).is_err());
         Ok(())
     }
@@ -803,6 +903,18 @@ mod protocol {
 }
 
 mod gitoxide {
+    #[test]
+    fn read_timeout() -> crate::Result {
+        use gix::config::tree::{Gitoxide, Key};
+
+        assert_eq!(
+            Gitoxide::READ_TIMEOUT.validated_assignment_fmt(&std::time::Duration::from_millis(500).as_millis())?,
+            "gitoxide.readTimeout=500"
+        );
+        assert!(Gitoxide::READ_TIMEOUT.validate("soon".into()).is_err());
+        Ok(())
+    }
+
     mod http {
         use std::time::Duration;
 
@@ -817,6 +929,30 @@ mod gitoxide {
             Ok(())
         }
     }
+    #[cfg(feature = "blocking-network-client")]
+    mod ssh {
+        use gix::config::tree::{gitoxide, Key};
+        use gix_protocol::transport::client::ssh::native::HostKeyCheck;
+
+        use crate::config::tree::bcow;
+
+        #[test]
+        fn strict_host_key_checking() -> crate::Result {
+            for (actual, expected) in [
+                ("yes", HostKeyCheck::Strict),
+                ("accept-new", HostKeyCheck::AcceptNew),
+                ("no", HostKeyCheck::Disabled),
+                ("off", HostKeyCheck::Disabled),
+            ] {
+                assert_eq!(
+                    gitoxide::Ssh::STRICT_HOST_KEY_CHECKING.try_into_host_key_check(bcow(actual))?,
+                    expected
+                );
+            }
+            assert!(gitoxide::Ssh::STRICT_HOST_KEY_CHECKING.validate("ask".into()).is_err());
+            Ok(())
+        }
+    }
     mod allow {
         use gix::config::tree::{gitoxide, Key};
 
@@ -1069,6 +1205,24 @@ mod remote {
         Ok(())
     }
 
+    #[test]
+    fn partial_clone_filter() -> crate::Result {
+        assert_eq!(
+            Remote::PARTIAL_CLONE_FILTER.try_into_filter(bcow("blob:limit=1k"))?,
+            gix::traverse::objects::Filter::BlobLimit(1024)
+        );
+        assert!(Remote::PARTIAL_CLONE_FILTER.validate("tree:0".into()).is_ok());
+        assert_eq!(
+            Remote::PARTIAL_CLONE_FILTER
+                .try_into_filter(bcow("sparse:oid=main"))
+                .unwrap_err()
+                .to_string(),
+            "The key \"remote.<name>.partialCloneFilter=sparse:oid=main\" was invalid"
+        );
+        assert!(Remote::PARTIAL_CLONE_FILTER.validate("blob:limit=x".into()).is_err());
+        Ok(())
+    }
+
     #[test]
     fn url_and_push_url() {
         assert!(Remote::URL.try_into_url(bcow("http://example.org")).is_ok());
@@ -13,28 +13,44 @@ fn resource_cache() -> crate::Result {
             .detach(),
         Default::default(),
     )?;
+    let (builtin, configured): (Vec<_>, Vec<_>) = cache
+        .filter
+        .drivers()
+        .iter()
+        .partition(|driver| gix_diff::blob::funcname::builtin(driver.name.as_ref()).is_some());
     assert_eq!(
-        cache.filter.drivers(),
-        &[
-            Driver {
+        configured,
+        [
+            &Driver {
                 name: "all-but-binary".into(),
                 command: Some("command".into()),
                 algorithm: Some(Algorithm::Histogram),
                 binary_to_text_command: Some("textconv".into()),
-                is_binary: None
+                is_binary: None,
+                word_regex: None,
+                xfuncname: None,
             },
-            Driver {
+            &Driver {
                 name: "binary-false".into(),
                 is_binary: Some(false),
                 ..Default::default()
             },
-            Driver {
+            &Driver {
                 name: "binary-true".into(),
                 is_binary: Some(true),
                 ..Default::default()
             }
         ]
     );
+    assert_eq!(
+        builtin.len(),
+        gix_diff::blob::funcname::BUILTIN.len(),
+        "built-in drivers are always available"
+    );
+    assert!(
+        builtin.iter().all(|driver| driver.xfuncname.is_some()),
+        "they come with the function name patterns of git"
+    );
     assert_eq!(cache.options.algorithm, Some(Algorithm::Histogram));
     assert!(
         !cache.options.skip_internal_diff_if_external_is_configured,
@@ -0,0 +1,36 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+git init -q
+git checkout -q -b main
+
+# The value in the file `value` was first greater than 6 in c7, which is the commit to find.
+for value in $(seq 1 10); do
+  echo "$value" > value
+  git add value
+  tick
+  git commit -q -m "c$value"
+  git tag "c$value"
+done
+
+git bisect start c10 c1 >/dev/null
+git bisect run sh -c 'test $(cat value) -le 6' | grep 'is the first bad commit' | cut -d' ' -f1 > first-bad.baseline
+git bisect reset >/dev/null 2>&1
+
+git clone -q --no-local . with-git-bisect
+(cd with-git-bisect
+  git bisect start origin/main "$(git rev-parse c1)" >/dev/null
+  git bisect skip "$(git rev-parse c5)" >/dev/null
+)
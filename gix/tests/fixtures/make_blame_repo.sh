@@ -0,0 +1,21 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+printf 'a\nb\nc\n' > file
+git add file && git commit -q -m c1
+
+printf 'a\nB\nc\n' > file
+git commit -q -am c2
+
+printf 'a;\nB;\nc\nd\n' > file
+git commit -q -am c3-formatting
+
+{
+  echo "# formatting changes"
+  git rev-parse HEAD
+} > .git-blame-ignore-revs
+
+printf 'a;\nB;\nC\nd\n' > file
+git commit -q -am c4
+git config blame.ignoreRevsFile .git-blame-ignore-revs
@@ -0,0 +1,15 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+
+git init -q source
+(cd source
+  git commit -q --allow-empty -m c1
+  git commit -q --allow-empty -m c2
+  git tag v1
+  echo content > file && git add file && git commit -q -m c3
+
+  git bundle create -q ../all.bundle --all
+  git bundle create -q ../incremental.bundle v1..main
+)
@@ -0,0 +1,61 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  local message=${1:?message}
+  git add -A
+  tick
+  git commit -qm "$message"
+  git tag "$message"
+}
+
+git init -q
+git checkout -q -b main
+seq 1 20 > a
+echo b > b
+commit base
+
+# The branch with the commits to upstream.
+git checkout -q -b head
+sed -i.bak 's/^2$/two/' a && rm a.bak
+commit h1
+sed -i.bak 's/^18$/eighteen/' a && rm a.bak
+commit h2
+echo c > c
+commit h3
+chmod +x b
+commit h4
+printf 'bin\0ary' > bin
+commit h5
+git checkout -q -b feature h3
+echo d > d
+commit side
+git checkout -q head
+tick
+git merge -q --no-ff -m merge feature
+git tag merge
+
+# Upstream moved on, and picked some of the commits, but in a different context.
+git checkout -q main
+{ echo 0; echo 00; } > new-a && cat a >> new-a && mv new-a a
+commit u1
+git cherry-pick h2 h3 h5 >/dev/null
+git tag upstream
+
+git cherry upstream head > cherry.baseline
+
+for commit in h1 h2 h3 h4 h5 side; do
+  echo "$commit $(git show --binary --full-index "$commit" | git patch-id --stable | cut -d ' ' -f 1)"
+done > patch-id.baseline
@@ -0,0 +1,50 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config diff.custom.xfuncname '!^skip
+^section ([a-z]+)'
+
+cat > .gitattributes <<'ATTRIBUTES'
+*.rs diff=rust
+*.py diff=python
+*.sh diff=bash
+*.go diff=golang
+*.java diff=java
+*.cpp diff=cpp
+*.css diff=css
+*.md diff=markdown
+*.custom diff=custom
+ATTRIBUTES
+
+# Write a file with `header` followed by ten lines, `middle` and another twenty lines, so changes at the end are far
+# from both.
+function write() {
+  local file=${1:?file} header=${2:?header} middle=${3:?middle}
+  {
+    echo "$header"
+    seq 1 10 | sed 's/^/    line /'
+    echo "$middle"
+    seq 11 30 | sed 's/^/    line /'
+  } > "$file"
+}
+
+write plain "fn_like_line() {" "second_function() {"
+write lib.rs "pub(crate) async fn first() {" "impl<T> Trait for Type<T> {"
+write script.py "class Foo:" "    async def method(self):"
+write script.sh "function first {" "second () {"
+write main.go "func main() {" "type Thing struct {"
+write Main.java "public class Main {" "    public static void main(String[] args) {"
+write main.cpp "int main(int argc, char **argv)" "label:"
+write style.css "BODY {" "  color: red;"
+write README.md "# Title" "## Section"
+write file.custom "section first" "skip this"
+git add .
+git commit -qm "initial"
+
+for file in plain lib.rs script.py script.sh main.go Main.java main.cpp style.css README.md file.custom; do
+  sed -i 's/line 2$/changed 2/; s/line 18$/changed 18/; s/line 28$/changed 28/' "$file"
+done
+git commit -qam "change the lines"
+
+git diff HEAD~1 HEAD > funcname.baseline
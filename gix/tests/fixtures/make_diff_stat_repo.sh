@@ -0,0 +1,34 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q
+git config diff.renames true
+
+mkdir -p src/deeply/nested/directory docs
+seq 1 20 > modified
+seq 1 100 > mostly-removed
+seq 1 30 > src/deeply/nested/directory/renamed-with-changes
+printf 'binary\0content' > binary
+echo "unchanged" > docs/unchanged
+seq 1 5 > deleted
+git add .
+git commit -qm "initial"
+
+sed -i 's/^5$/five/' modified
+echo 21 >> modified
+seq 1 3 > mostly-removed
+mkdir src/deeply/nested/other
+git mv src/deeply/nested/directory/renamed-with-changes src/deeply/nested/other/renamed-with-changes
+echo 31 >> src/deeply/nested/other/renamed-with-changes
+printf 'binary\0content that is longer' > binary
+git rm -q deleted
+seq 1 500 > "a-file-with-a-very-long-name-that-needs-to-be-shortened-to-fit-into-the-line"
+echo "added" > docs/added
+git mv docs/unchanged docs/renamed
+git add .
+git commit -qm "changes"
+
+git diff --numstat HEAD~1 HEAD > numstat.baseline
+git diff --stat HEAD~1 HEAD > stat.baseline
+git diff --stat=60 HEAD~1 HEAD > stat-60.baseline
+git diff --stat --stat-name-width=20 --stat-graph-width=10 HEAD~1 HEAD > stat-name-20-graph-10.baseline
@@ -0,0 +1,80 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+# Set the line at position $1 of `file` to $2 and commit it with message $3.
+function change_line() {
+  local line=${1:?line number} content=${2:?content} message=${3:?message}
+  sed -i.bak "${line}s/.*/${content}/" file && rm file.bak
+  tick
+  git commit -qam "$message"
+}
+
+function merge() {
+  tick
+  git merge -q --no-ff -m "$@"
+}
+
+# Record the tree `git merge-tree` produces when merging the given branches.
+function baseline() {
+  local ours=${1:?ours} theirs=${2:?theirs}
+  echo "$ours $theirs $(git merge-tree --write-tree --no-messages "$ours" "$theirs" | head -n 1)"
+}
+
+git init -q
+git checkout -q -b main
+seq 1 10 > file
+git add file
+tick
+git commit -qm base
+
+# A criss-cross history with the merge-bases `a1` and `b1` of `a` and `b`, which merge cleanly.
+git checkout -q -b a main
+change_line 1 one a1
+git branch a1
+git checkout -q -b b main
+change_line 10 ten b1
+git branch b1
+merge "b: merge a1" a1
+change_line 8 eight b2
+git checkout -q a
+merge "a: merge b1" b1
+change_line 3 three a2
+
+# A criss-cross history with the merge-bases `c1` and `d1` of `c` and `d`, which conflict with each other.
+# Both sides resolve the conflict differently, so the virtual merge-base has conflict markers.
+git checkout -q -b c main
+change_line 5 c1 c1
+git branch c1
+git checkout -q -b d main
+change_line 5 d1 d1
+git branch d1
+merge "d: merge c1" -X ours c1
+git checkout -q c
+merge "c: merge d1" -X ours d1
+change_line 1 c2 c2
+
+git checkout -q --orphan unrelated
+git rm -qf file
+echo unrelated > unrelated
+git add unrelated
+tick
+git commit -qm unrelated
+
+{
+  baseline a b
+  baseline b a
+  baseline c d
+  baseline d c
+} > merge-tree.baseline
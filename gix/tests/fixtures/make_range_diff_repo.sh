@@ -0,0 +1,44 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+# Write the numbers from $2 to $3 into file $1, and commit it with message $4.
+function write_and_commit() {
+  local file=${1:?file} from=${2:?from} to=${3:?to} message=${4:?message}
+  seq "$from" "$to" > "$file"
+  git add "$file"
+  tick
+  git commit -qm "$message"
+}
+
+git init -q
+git checkout -q -b main
+write_and_commit base 1 3 base
+git branch base
+
+# The old version of the series.
+git checkout -q -b old base
+write_and_commit a 1 10 "add a"
+write_and_commit b 1 20 "add b"
+write_and_commit c 1 30 "add c"
+
+# The new version of the series, based on another commit, which drops `b`, changes `c` and adds `d`.
+git checkout -q main
+write_and_commit other 1 5 other
+git checkout -q -b new main
+write_and_commit a 1 10 "add a"
+write_and_commit c 2 31 "add c"
+write_and_commit d 100 140 "add d"
+
+git range-diff --no-color -s base..old main..new > range-diff.baseline
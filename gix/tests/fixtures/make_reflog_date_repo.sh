@@ -0,0 +1,59 @@
+#!/bin/bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function baseline () {
+  local spec=${1:?first argument is the spec to test}
+  {
+    echo "$spec"
+    git rev-parse -q --verify "$spec" 2>/dev/null || echo $?
+  } >> baseline.git
+}
+
+git init -q
+git checkout -q -b main
+
+for value in $(seq 1 4); do
+  tick
+  git commit -q --allow-empty -m "c$value"
+  git tag "c$value"
+done
+
+tick
+git checkout -q -b other c2
+tick
+git commit -q --allow-empty -m "o1"
+tick
+git checkout -q main
+tick
+git reset -q --hard c3
+
+# the first entry of `main` was written at 1112911993, and every other one a minute later
+baseline "main@{1112911993 -0700}"
+baseline "main@{1112912000 -0700}"
+baseline "main@{1112912053 -0700}"
+baseline "main@{1112912113 -0700}"
+baseline "main@{2005-04-07 15:21:33 -0700}"
+baseline "main@{2005-04-07 22:22:33 +0000}"
+baseline "main@{1000000000 +0000}"
+baseline "main@{now}"
+baseline "main@{yesterday}"
+baseline "main@{1.week.ago}"
+baseline "HEAD@{1112912113 -0700}"
+baseline "HEAD@{1112912233 -0700}"
+baseline "HEAD@{1112912293 -0700}"
+baseline "HEAD@{1000000000 +0000}"
+baseline "other@{1112912233 -0700}"
+baseline "other@{1112912173 -0700}"
+baseline "@{-1}"
@@ -376,3 +376,8 @@ git init empty-core-askpass
 (cd empty-core-askpass
   echo "    askpass =" >> .git/config
 )
+
+git clone --bare base base.allow-filter
+(cd base.allow-filter
+  git config uploadpack.allowFilter true
+)
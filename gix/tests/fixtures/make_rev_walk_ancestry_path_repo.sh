@@ -0,0 +1,55 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+function tick () {
+  if test -z "${tick+set}"
+  then
+    tick=1112911993
+  else
+    tick=$(($tick + 60))
+  fi
+  GIT_COMMITTER_DATE="$tick -0700"
+  GIT_AUTHOR_DATE="$tick -0700"
+  export GIT_COMMITTER_DATE GIT_AUTHOR_DATE
+}
+
+function commit() {
+  tick
+  git commit -q --allow-empty -m "${1:?first argument is the commit message}"
+  git tag "$1"
+}
+
+function merge() {
+  tick
+  git merge -q --no-ff "${1:?first argument is the branch to merge}" -m "${2:?second argument is the commit message}"
+  git tag "$2"
+}
+
+# x1 and x2 branch off before b and c, and y1 branches off after b but before c.
+git init -q
+git checkout -q -b main
+commit a
+commit b
+git checkout -q -b x a
+commit x1
+commit x2
+git checkout -q -b y main
+commit y1
+git checkout -q main
+commit c
+merge x m1
+commit d
+merge y m2
+commit e
+
+function baseline() {
+  local name=${1:?name}
+  shift
+  git log --format=%m%s "$@" >"$name.baseline"
+}
+
+baseline ancestry-path-from-b --date-order --ancestry-path b..e
+baseline ancestry-path-from-c --date-order --ancestry-path c..e
+baseline ancestry-path-from-c-and-y1 --date-order --ancestry-path e ^c ^y1
+baseline ancestry-path-from-c-with-boundary --date-order --ancestry-path --boundary c..e
+baseline ancestry-path-from-x1 --date-order --ancestry-path x1..e
@@ -0,0 +1,61 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+git init -q
+git config merge.ff false
+
+function commit_at() {
+  local day=${1:?day}
+  local author=${2:?author}
+  local committer=${3:?committer}
+  local message=${4:?message}
+  GIT_AUTHOR_NAME=$author GIT_AUTHOR_EMAIL="$author@example.com" GIT_AUTHOR_DATE="2000-01-0$day 00:00:00 +0000" \
+  GIT_COMMITTER_NAME=$committer GIT_COMMITTER_EMAIL="$committer@example.com" GIT_COMMITTER_DATE="2000-01-0$day 00:00:00 +0000" \
+    git commit -q -m "$message" "${@:5}"
+}
+
+git checkout -q -b main
+mkdir a
+echo 1 >a/file && echo b >b
+git add .
+commit_at 1 Alice Alice c1
+
+echo 2 >a/file
+commit_at 2 Bob Bob c2 -a
+
+git checkout -q -b side
+echo B >b
+commit_at 3 Alice Carol s1 -a
+
+git checkout -q main
+echo c >c
+git add c
+commit_at 4 Bob Bob c3
+
+GIT_AUTHOR_DATE="2000-01-05 00:00:00 +0000" GIT_COMMITTER_DATE="2000-01-05 00:00:00 +0000" \
+  git merge -q side -m m1
+
+chmod +x a/file
+commit_at 6 Alice Bob c4 -a
+
+git commit-graph write --no-progress --reachable --changed-paths
+
+function baseline() {
+  local name=${1:?name}
+  shift
+  git log --format=%s "$@" >"$name.baseline"
+}
+
+baseline since --since-as-filter="2000-01-03 00:00:00 +0000"
+baseline until --until="2000-01-04 00:00:00 +0000"
+baseline since-until --since-as-filter="2000-01-02 00:00:00 +0000" --until="2000-01-05 00:00:00 +0000"
+baseline author --author=Alice
+baseline author-regex --author='^B.*example'
+baseline authors --author=Bob --author=Alice@
+baseline committer --committer=Carol
+baseline author-committer --author=Alice --committer=Bob
+baseline path-a -- a
+baseline path-b -- b
+baseline path-file-c -- a/file c
+baseline path-missing -- missing
+baseline first-parent-b --first-parent -- b
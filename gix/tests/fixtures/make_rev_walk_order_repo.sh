@@ -0,0 +1,45 @@
+#!/usr/bin/env bash
+set -eu -o pipefail
+
+# Times are offsets to a fixed date, chosen such that ordering by topology, commit date and author date differs.
+function commit_at() {
+  local message=${1:?first argument is the commit message}
+  GIT_AUTHOR_DATE="$((1600000000 + ${2:?second argument is the author time})) +0000" \
+  GIT_COMMITTER_DATE="$((1600000000 + ${3:?third argument is the commit time})) +0000" \
+    git commit -q --allow-empty -m "$message"
+}
+
+git init -q
+git config merge.ff false
+
+git checkout -q -b main
+commit_at c1 1000 1000
+
+git checkout -q -b branch
+commit_at b1 1100 1150
+commit_at b2 1150 1350
+
+git checkout -q main
+commit_at c2 1300 1200
+commit_at c3 1200 1300
+
+GIT_AUTHOR_DATE="1600001600 +0000" GIT_COMMITTER_DATE="1600001600 +0000" git merge -q branch -m merge
+commit_at c4 1700 1700
+
+function baseline() {
+  local name=${1:?name}
+  shift
+  git log --format=%s "$@" >"$name.baseline"
+}
+
+baseline topo-order --topo-order
+baseline date-order --date-order
+baseline author-date-order --author-date-order
+baseline first-parent-topo-order --first-parent --topo-order
+baseline topo-order-since --topo-order --since-as-filter=@1600001200
+
+baseline range --date-order branch..main
+baseline symmetric-difference --date-order main~2...branch
+baseline only-parents --date-order main~1^@
+baseline exclude-parents --date-order main~1^!
+baseline exclude --date-order ^main
@@ -0,0 +1,51 @@
+#!/bin/bash
+set -eu -o pipefail
+
+git init -q module1
+(cd module1
+  touch this
+  mkdir subdir
+  touch subdir/that
+  git add .
+  git commit -q -m c1
+  echo hello >> this
+  git commit -q -am c2
+  touch untracked
+)
+
+git init -q super
+(cd super
+  git submodule add ../module1 m1
+  git submodule add ../module1 dir/m1
+  git commit -q -m "add submodules"
+)
+
+git clone -q super super-clone
+(cd super-clone
+  git submodule init m1
+)
+
+function diverged_submodule() {
+  local name=${1:?first argument is the repository name}
+  local strategy=${2:?second argument is the update strategy}
+  local local_change=${3:?third argument is the file to change locally}
+
+  git clone -q super "$name"
+  (cd "$name"
+    git submodule update -q --init m1
+    git config submodule.m1.update "$strategy"
+    (cd m1
+      git config user.name "Submodule Author"
+      git config user.email "author@example.com"
+      git checkout -q -b local @~1
+      echo "local" >> "$local_change"
+      git add "$local_change"
+      git commit -q -m "local change"
+    )
+  )
+}
+
+diverged_submodule super-merge merge other
+diverged_submodule super-rebase rebase other
+diverged_submodule super-merge-conflict merge this
+diverged_submodule super-rebase-conflict rebase this
@@ -44,6 +44,8 @@ mod with_overrides {
             .set("GIX_EXTERNAL_COMMAND_STDERR", "filter-stderr")
             .set("GIT_SSL_CAINFO", "./env.pem")
             .set("GIT_SSL_VERSION", "tlsv1.3")
+            .set("GIT_SSL_CERT", "./cert-env.pem")
+            .set("GIT_SSL_KEY", "./key-env.pem")
             .set("GIT_SSH_VARIANT", "ssh-variant-env")
             .set("GIT_SSH_COMMAND", "ssh-command-env")
             .set("GIT_SSH", "ssh-command-fallback-env")
@@ -236,6 +238,8 @@ mod with_overrides {
         );
         for (key, expected) in [
             ("gitoxide.http.sslNoVerify", "true"),
+            ("http.sslCert", "./cert-env.pem"),
+            ("http.sslKey", "./key-env.pem"),
             ("gitoxide.http.verbose", "true"),
             ("gitoxide.allow.protocolFromUser", "file-allowed"),
             ("core.useReplaceRefs", "no-replace"),
@@ -16,6 +16,8 @@ mod remote;
 mod repository;
 #[cfg(feature = "revision")]
 mod revision;
+#[cfg(feature = "blocking-network-client")]
+mod server;
 #[cfg(feature = "status")]
 mod status;
 #[cfg(feature = "attributes")]
@@ -47,8 +47,312 @@ fn changes_against_tree_modified() -> crate::Result {
                 Ok::<_, Infallible>(())
             })
             .expect("infallible");
+            assert_eq!(
+                diff.words(None).expect("no diff error").expect("no binary blobs"),
+                vec![gix_diff::blob::words::Change {
+                    before: 1..1,
+                    after: 2..4
+                }],
+                "the inserted word is placed after the last word of the previous version"
+            );
+            Ok(Default::default())
+        })?;
+    Ok(())
+}
+
+#[test]
+fn changes_as_patch() -> crate::Result {
+    let repo = named_repo("make_diff_repo.sh")?;
+    let from = tree_named(&repo, "@^{/r4-symlinks}~1");
+    let to = tree_named(&repo, ":/r4-symlinks");
+    let mut cache = repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+    let mut patch = Vec::new();
+    from.changes()?.track_path().for_each_to_obtain_tree(
+        &to,
+        |change| -> Result<_, gix::object::tree::diff::change::write_patch::Error> {
+            change.write_patch(&mut cache, Default::default(), &mut patch)?;
+            Ok(Default::default())
+        },
+    )?;
+    assert_eq!(
+        patch.as_bstr(),
+        "diff --git a/link-1 b/renamed-link-1\n\
+         similarity index 100%\n\
+         rename from link-1\n\
+         rename to renamed-link-1\n\
+         diff --git a/dir/link-2 b/dir/link-2\n\
+         deleted file mode 120000\n\
+         index dc320d6..0000000\n\
+         --- a/dir/link-2\n\
+         +++ /dev/null\n\
+         @@ -1 +0,0 @@\n\
+         -../lt2\n\
+         \\ No newline at end of file\n\
+         diff --git a/no-link b/no-link\n\
+         deleted file mode 100644\n\
+         index 07ec3cb..0000000\n\
+         --- a/no-link\n\
+         +++ /dev/null\n\
+         @@ -1 +0,0 @@\n\
+         -lt1\n\
+         diff --git a/z-link-2 b/z-link-2\n\
+         new file mode 120000\n\
+         index 0000000..9d59f75\n\
+         --- /dev/null\n\
+         +++ b/z-link-2\n\
+         @@ -0,0 +1 @@\n\
+         +lt1\n\
+         \\ No newline at end of file\n",
+        "this is what `git show` produces as well, but in a different order"
+    );
+    Ok(())
+}
+
+#[test]
+fn hunk_headers_show_function_names() -> crate::Result {
+    let repo = named_repo("make_diff_funcname_repo.sh")?;
+    let from = tree_named(&repo, "@~1");
+    let to = tree_named(&repo, "@");
+    let mut cache = repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+    let mut patch = Vec::new();
+    from.changes()?.track_path().for_each_to_obtain_tree(
+        &to,
+        |change| -> Result<_, gix::object::tree::diff::change::write_patch::Error> {
+            change.write_patch(&mut cache, Default::default(), &mut patch)?;
+            Ok(Default::default())
+        },
+    )?;
+    let baseline = std::fs::read(repo.work_dir().expect("non-bare").join("funcname.baseline"))?;
+    assert_eq!(
+        patch.as_bstr(),
+        baseline.as_bstr(),
+        "built-in and configured drivers are used, and lines starting like identifiers otherwise"
+    );
+    Ok(())
+}
+
+#[test]
+fn whitespace_changes_can_be_ignored() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_diff_repo.sh")?;
+    let before: String = (1..=10).map(|n| format!("    line {n}\n")).collect();
+    let after = before.replace("    ", "\t").replace("line 10", "line ten");
+    let (from, to) = (
+        write_tree(&repo, &[("a", &before)])?,
+        write_tree(&repo, &[("b", &after)])?,
+    );
+
+    let patch = |ignore: Option<gix::diff::blob::IgnoreWhitespace>| -> crate::Result<BString> {
+        let mut cache = repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+        let mut patch = Vec::new();
+        let mut changes = from.changes()?;
+        changes.track_path();
+        if let Some(ignore) = ignore {
+            changes.ignore_whitespace(ignore);
+            cache.options.ignore_whitespace = ignore;
+        }
+        changes.for_each_to_obtain_tree(
+            &to,
+            |change| -> Result<_, gix::object::tree::diff::change::write_patch::Error> {
+                change.write_patch(&mut cache, Default::default(), &mut patch)?;
+                Ok(Default::default())
+            },
+        )?;
+        Ok(patch.into())
+    };
+    assert!(
+        patch(None)?.starts_with(b"diff --git a/a b/a\ndeleted file mode 100644\n"),
+        "the files are too different to be considered a rename"
+    );
+    assert_eq!(
+        patch(Some(gix::diff::blob::IgnoreWhitespace {
+            all_space: true,
+            ..Default::default()
+        }))?,
+        "diff --git a/a b/b\n\
+         similarity index 89%\n\
+         rename from a\n\
+         rename to b\n\
+         index 3ef592a..bbc6b9c 100644\n\
+         --- a/a\n\
+         +++ b/b\n\
+         @@ -7,4 +7,4 @@\n \
+         \tline 7\n \
+         \tline 8\n \
+         \tline 9\n\
+         -    line 10\n\
+         +\tline ten\n",
+        "the files are similar when ignoring whitespace, which also affects the hunks"
+    );
+    Ok(())
+}
+
+#[test]
+fn pickaxe() -> crate::Result {
+    use gix::diff::blob::pickaxe::Filter;
+
+    let (repo, _tmp) = crate::repo_rw("make_diff_repo.sh")?;
+    let from = write_tree(&repo, &[("a", "fn main() {}\n"), ("b", "b\n"), ("c", "c\n")])?;
+    let to = write_tree(
+        &repo,
+        &[
+            ("a", "// comment\nfn main() {}\n"),
+            ("b", "fn main() {}\n"),
+            ("d", "d\n"),
+        ],
+    )?;
+    let changes = |filter: Filter| -> crate::Result<Vec<(char, BString)>> {
+        let mut out = Vec::new();
+        from.changes()?
+            .track_path()
+            .track_rewrites(None)
+            .pickaxe(Some(filter))
+            .for_each_to_obtain_tree(&to, |change| -> Result<_, Infallible> {
+                out.push(store(&change));
+                Ok(Default::default())
+            })?;
+        Ok(out)
+    };
+    assert_eq!(
+        changes(Filter::Occurrences("fn main".into()))?,
+        vec![modified("b")],
+        "only `b` changes the amount of occurrences"
+    );
+    assert_eq!(changes(Filter::changed_lines("^// ".into())?)?, vec![modified("a")]);
+    assert_eq!(
+        changes(Filter::changed_lines("^[cd]$".into())?)?,
+        vec![deleted("c"), added("d")]
+    );
+    Ok(())
+}
+
+fn write_tree<'repo>(repo: &'repo gix::Repository, files: &[(&str, &str)]) -> crate::Result<gix::Tree<'repo>> {
+    let mut tree = gix::objs::Tree::empty();
+    for (name, content) in files {
+        tree.entries.push(gix::objs::tree::Entry {
+            mode: EntryKind::Blob.into(),
+            filename: (*name).into(),
+            oid: repo.write_blob(content)?.detach(),
+        });
+    }
+    Ok(repo.write_object(tree)?.object()?.into_tree())
+}
+
+#[test]
+#[cfg(feature = "status")]
+fn submodule_changes() -> crate::Result {
+    let repo = crate::submodule::repo("modified-and-untracked")?;
+    let commit = repo.rev_parse_single("HEAD:m1")?.detach();
+    let mut cache = repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+    let (mut submodules, mut patch) = (Vec::new(), Vec::new());
+    repo.empty_tree().changes()?.track_path().for_each_to_obtain_tree(
+        &tree_named(&repo, "HEAD"),
+        |change| -> Result<_, gix::object::tree::diff::change::write_patch::Error> {
+            if change.location == "m1" {
+                change.write_patch(
+                    &mut cache,
+                    gix::object::tree::diff::change::write_patch::Options {
+                        mark_dirty_submodules: true,
+                        ..Default::default()
+                    },
+                    &mut patch,
+                )?;
+            }
+            submodules.extend(change.submodule().map_err(Box::new)?);
             Ok(Default::default())
+        },
+    )?;
+    assert_eq!(
+        submodules,
+        vec![gix::object::tree::diff::change::Submodule {
+            previous_commit: None,
+            commit: Some(commit),
+            commit_is_present: Some(true),
+            is_dirty: Some(true),
+        }],
+        "only the submodule is represented, and its worktree has changes"
+    );
+    assert_eq!(
+        patch.as_bstr(),
+        format!(
+            "diff --git a/m1 b/m1\n\
+             new file mode 160000\n\
+             index 0000000..{short}\n\
+             --- /dev/null\n\
+             +++ b/m1\n\
+             @@ -0,0 +1 @@\n\
+             +Subproject commit {commit}-dirty\n",
+            short = commit.to_hex_with_len(7)
+        ),
+        "dirty submodules are marked if desired"
+    );
+    Ok(())
+}
+
+#[test]
+fn changes_as_binary_patch() -> crate::Result {
+    let (repo, _tmp) = crate::util::basic_rw_repo()?;
+    let old_data = b"binary\0data\n".repeat(100);
+    let mut new_data = old_data.clone();
+    new_data.extend_from_slice(b"more\0");
+    let tree_with_blob = |data: &[u8]| -> crate::Result<gix::Tree<'_>> {
+        let id = repo.write_object(gix::objs::Tree {
+            entries: vec![gix::objs::tree::Entry {
+                mode: EntryKind::Blob.into(),
+                filename: "bin".into(),
+                oid: repo.write_blob(data)?.detach(),
+            }],
         })?;
+        Ok(id.object()?.into_tree())
+    };
+    let (from, to) = (tree_with_blob(&old_data)?, tree_with_blob(&new_data)?);
+
+    let mut cache = repo.diff_resource_cache(gix_diff::blob::pipeline::Mode::ToGit, Default::default())?;
+    let mut write_patch = |binary: bool| -> crate::Result<Vec<u8>> {
+        let mut patch = Vec::new();
+        from.changes()?.track_path().for_each_to_obtain_tree(
+            &to,
+            |change| -> Result<_, gix::object::tree::diff::change::write_patch::Error> {
+                change.write_patch(
+                    &mut cache,
+                    gix::object::tree::diff::change::write_patch::Options {
+                        binary,
+                        ..Default::default()
+                    },
+                    &mut patch,
+                )?;
+                Ok(Default::default())
+            },
+        )?;
+        Ok(patch)
+    };
+
+    let (old_id, new_id) = (
+        gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &old_data),
+        gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &new_data),
+    );
+    assert_eq!(
+        write_patch(false)?.as_bstr(),
+        format!(
+            "diff --git a/bin b/bin\nindex {}..{} 100644\nBinary files a/bin and b/bin differ\n",
+            old_id.to_hex_with_len(7),
+            new_id.to_hex_with_len(7)
+        ),
+        "by default, binary files are only noted"
+    );
+
+    let patch = write_patch(true)?;
+    let header = format!("diff --git a/bin b/bin\nindex {old_id}..{new_id} 100644\n");
+    assert!(
+        patch.starts_with(header.as_bytes()),
+        "binary patches use full object ids like `git`"
+    );
+    let (binary_patch, consumed) = gix_diff::blob::binary_patch::parse(&patch[header.len()..])?;
+    assert_eq!(header.len() + consumed, patch.len());
+    assert_eq!(binary_patch.forward.apply(&old_data)?, new_data);
+    assert_eq!(
+        binary_patch.reverse.expect("always written").apply(&new_data)?,
+        old_data
+    );
     Ok(())
 }
 
@@ -91,6 +395,47 @@ fn changes_against_tree_with_filename_tracking() -> crate::Result {
     Ok(())
 }
 
+#[test]
+fn stats_match_git() -> crate::Result {
+    let repo = named_repo("make_diff_stat_repo.sh")?;
+    let from = tree_named(&repo, "@~1");
+    let to = tree_named(&repo, "@");
+    let stats = from.changes()?.stats(&to)?;
+    let baseline = |name: &str| std::fs::read(repo.work_dir().expect("non-bare").join(name));
+
+    let mut out = Vec::new();
+    stats.write_numstat(&mut out)?;
+    assert_eq!(out.as_bstr(), baseline("numstat.baseline")?.as_bstr());
+
+    for (options, baseline_name) in [
+        (gix::diff::stat::Options::default(), "stat.baseline"),
+        (
+            gix::diff::stat::Options {
+                width: 60,
+                ..Default::default()
+            },
+            "stat-60.baseline",
+        ),
+        (
+            gix::diff::stat::Options {
+                name_width: Some(20),
+                graph_width: Some(10),
+                ..Default::default()
+            },
+            "stat-name-20-graph-10.baseline",
+        ),
+    ] {
+        let mut out = Vec::new();
+        stats.write_stat(&mut out, options)?;
+        assert_eq!(out.as_bstr(), baseline(baseline_name)?.as_bstr(), "{baseline_name}");
+    }
+    assert_eq!(
+        stats.summary().to_string(),
+        "8 files changed, 504 insertions(+), 103 deletions(-)"
+    );
+    Ok(())
+}
+
 fn tree_named(repo: &gix::Repository, rev_spec: impl AsRef<str>) -> gix::Tree {
     repo.rev_parse_single(rev_spec.as_ref())
         .unwrap()
@@ -563,7 +908,7 @@ mod track_rewrites {
                         source: CopySource::FromSetOfModifiedFilesAndAllSources,
                         ..Default::default()
                     }),
-                    limit: 2, // similarity checks can't be made that way
+                    limit: 1, // similarity checks can't be made that way
                     ..Default::default()
                 }
                 .into(),
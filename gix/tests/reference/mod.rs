@@ -101,4 +101,47 @@ fn set_target_id() {
         .starts_with("Reference \"refs/heads/main\" was supposed to exist"));
 }
 
+#[test]
+fn expire_reflog() -> crate::Result {
+    use gix::config::tree::Gc;
+    let (mut repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+    let main = repo.find_reference("refs/heads/main")?;
+    let num_entries = main.log_iter().all()?.expect("present").count();
+    assert_eq!(num_entries, 2, "one entry per commit");
+
+    {
+        let mut config = repo.config_snapshot_mut();
+        config.set_value(&Gc::REFLOG_EXPIRE, "never")?;
+        config.set_value(&Gc::REFLOG_EXPIRE_UNREACHABLE, "never")?;
+    }
+    let main = repo.find_reference("refs/heads/main")?;
+    let outcome = main.expire_reflog()?;
+    assert_eq!(
+        (outcome.kept, outcome.expired),
+        (2, 0),
+        "nothing expires if configured like that"
+    );
+
+    repo.config_snapshot_mut()
+        .set_value(&Gc::REFLOG_EXPIRE_UNREACHABLE, "now")?;
+    let outcome = repo.find_reference("refs/heads/main")?.expire_reflog()?;
+    assert_eq!(
+        (outcome.kept, outcome.expired),
+        (2, 0),
+        "all commits in the log are reachable from the tip"
+    );
+
+    repo.config_snapshot_mut()
+        .set_value(&Gc::REFLOG_EXPIRE, "90.days.ago")?;
+    let main = repo.find_reference("refs/heads/main")?;
+    let outcome = main.expire_reflog()?;
+    assert_eq!(
+        (outcome.kept, outcome.expired),
+        (0, 2),
+        "the fixture's entries are from the year 2000 and expire"
+    );
+    assert_eq!(main.log_iter().all()?.expect("still present").count(), 0);
+    Ok(())
+}
+
 mod remote;
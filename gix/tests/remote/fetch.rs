@@ -11,7 +11,7 @@ mod shallow {
 }
 
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client-async-std"))]
-mod blocking_and_async_io {
+pub(crate) mod blocking_and_async_io {
     use std::sync::atomic::AtomicBool;
 
     use gix::remote::{fetch, fetch::Status, Direction::Fetch};
@@ -109,6 +109,85 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[test]
+    #[cfg(feature = "blocking-network-client")]
+    fn fetch_with_prune_deletes_refs_that_are_gone_on_the_remote_unless_excluded() -> crate::Result {
+        use gix::refs::transaction::PreviousValue;
+
+        let (repo, tmp) = repo_rw("two-origins");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        for name in ["refs/heads/a", "refs/heads/b"] {
+            remote_repo.find_reference(name)?.delete()?;
+        }
+        repo.tag_reference("local-only", repo.head_id()?, PreviousValue::MustNotExist)?;
+
+        let fetch_and_prune = |remote: gix::Remote<'_>| -> crate::Result<Vec<String>> {
+            let outcome = remote
+                .connect(Fetch)?
+                .prepare_fetch(gix::progress::Discard, Default::default())?
+                .receive(gix::progress::Discard, &AtomicBool::default())?;
+            let (Status::Change { update_refs, .. } | Status::NoPackReceived { update_refs, .. }) = outcome.status;
+            Ok(update_refs
+                .pruned
+                .iter()
+                .map(|edit| edit.name.as_bstr().to_string())
+                .collect())
+        };
+
+        let remote = repo
+            .find_remote("origin")?
+            .with_prune(true)
+            .with_refspecs(Some("^refs/heads/b"), Fetch)?;
+        assert_eq!(
+            fetch_and_prune(remote)?,
+            ["refs/remotes/origin/a"],
+            "b is excluded by a negative refspec, and tags aren't pruned by default"
+        );
+        assert!(repo.try_find_reference("refs/remotes/origin/a")?.is_none());
+        assert!(repo.try_find_reference("refs/remotes/origin/b")?.is_some());
+        assert!(repo.try_find_reference("refs/tags/local-only")?.is_some());
+
+        let remote = repo.find_remote("origin")?.with_prune(true).with_prune_tags(true);
+        assert_eq!(
+            fetch_and_prune(remote)?,
+            ["refs/remotes/origin/b", "refs/tags/local-only"]
+        );
+        assert!(
+            repo.try_find_reference("refs/remotes/origin/HEAD")?.is_some(),
+            "symbolic refs are never pruned"
+        );
+        assert!(
+            repo.try_find_reference("refs/tags/b-tag")?.is_some(),
+            "pruning tags also fetches all of them"
+        );
+
+        let fetch_head = std::fs::read_to_string(repo.git_dir().join("FETCH_HEAD"))?;
+        let url = tmp.path().join("base");
+        let url = gix::path::realpath(url)?;
+        let mut lines = fetch_head.lines();
+        assert_eq!(
+            lines.next().expect("at least one line"),
+            format!(
+                "{}\t\tbranch 'main' of {}",
+                remote_repo.find_reference("refs/heads/main")?.id(),
+                url.display()
+            ),
+            "the configured upstream branch is for merging, and comes first"
+        );
+        assert!(lines.all(|line| line.contains("\tnot-for-merge\t")));
+        assert!(fetch_head.contains("\tnot-for-merge\ttag 'b-tag' of "));
+
+        let remote = repo.find_remote("origin")?;
+        std::fs::remove_file(repo.git_dir().join("FETCH_HEAD"))?;
+        remote
+            .connect(Fetch)?
+            .prepare_fetch(gix::progress::Discard, Default::default())?
+            .with_write_fetch_head(false)
+            .receive(gix::progress::Discard, &AtomicBool::default())?;
+        assert!(!repo.git_dir().join("FETCH_HEAD").exists());
+        Ok(())
+    }
+
     #[test]
     #[cfg(feature = "blocking-network-client")]
     fn fetch_with_alternates_adds_tips_from_alternates() -> crate::Result<()> {
@@ -238,6 +317,68 @@ mod blocking_and_async_io {
         Ok(())
     }
 
+    #[maybe_async::test(
+        feature = "blocking-network-client",
+        async(feature = "async-network-client-async-std", async_std::test)
+    )]
+    async fn fetch_negotiation_algorithm_defaults_to_skipping_with_experimental_features_or_can_be_overridden(
+    ) -> crate::Result {
+        for (experimental, algorithm_override, expected_negotiation_rounds) in
+            [(true, None, 2), (false, Some(gix::negotiate::Algorithm::Noop), 1)]
+        {
+            let (mut client_repo, _tmp) = {
+                let client_repo = remote::repo("multi_round/client");
+                let daemon = spawn_git_daemon_if_async(client_repo.work_dir().expect("non-bare"))?;
+                let tmp = TempDir::new()?;
+                let repo = gix::prepare_clone_bare(
+                    daemon.as_ref().map_or_else(
+                        || client_repo.git_dir().to_owned(),
+                        |d| std::path::PathBuf::from(format!("{}/", d.url)),
+                    ),
+                    tmp.path(),
+                )?
+                .fetch_only(gix::progress::Discard, &std::sync::atomic::AtomicBool::default())
+                .await?
+                .0;
+                (repo, tmp)
+            };
+
+            {
+                let mut config = client_repo.config_snapshot_mut();
+                config.set_value(&gix::config::tree::Fetch::NEGOTIATION_ALGORITHM, "default")?;
+                config.set_value(
+                    &gix::config::tree::Feature::EXPERIMENTAL,
+                    experimental.to_string().as_str(),
+                )?;
+            }
+            let server_repo = remote::repo("multi_round/server");
+            let daemon = spawn_git_daemon_if_async(server_repo.work_dir().expect("non-bare"))?;
+            let remote = into_daemon_remote_if_async(
+                client_repo.remote_at(server_repo.work_dir().expect("non-bare"))?,
+                daemon.as_ref(),
+                None,
+            );
+            let remote = remote.with_refspecs(Some("refs/heads/*:refs/remotes/origin/*"), Fetch)?;
+            let mut prepare = remote
+                .connect(Fetch)
+                .await?
+                .prepare_fetch(gix::progress::Discard, Default::default())
+                .await?;
+            if let Some(algorithm) = algorithm_override {
+                prepare = prepare.with_negotiation_algorithm(algorithm);
+            }
+            let changes = prepare.receive(gix::progress::Discard, &AtomicBool::default()).await?;
+
+            match changes.status {
+                Status::Change { negotiate, .. } => {
+                    assert_eq!(negotiate.rounds.len(), expected_negotiation_rounds);
+                }
+                _ => unreachable!("We expect a pack for sure"),
+            }
+        }
+        Ok(())
+    }
+
     #[maybe_async::test(
         feature = "blocking-network-client",
         async(feature = "async-network-client-async-std", async_std::test)
@@ -343,11 +484,6 @@ mod blocking_and_async_io {
             gix::protocol::transport::Protocol::V2,
         ] {
             for (shallow_args, expected) in [(None, [1, 7, 7]), (Some("--depth=2"), [1, 2, 2])] {
-                if version == gix::protocol::transport::Protocol::V1 && shallow_args.is_some() {
-                    // TODO: We cannot yet handle shallow mode for V1 as it will send shallow-info as part of the handshake :/.
-                    //       It's probably not the most important thing to be able to clone from a shallow remote anyway.
-                    continue;
-                }
                 for (fetch_tags, expected_ref_count) in [fetch::Tags::None, fetch::Tags::Included, fetch::Tags::All]
                     .into_iter()
                     .zip(expected)
@@ -66,7 +66,9 @@ pub(crate) fn into_daemon_remote_if_async<'repo, 'a>(
 
 mod connect;
 pub(crate) mod fetch;
+mod push;
 mod ref_map;
+mod retry;
 mod save;
 mod name {
 
@@ -0,0 +1,161 @@
+#[cfg(all(feature = "blocking-network-client", unix))]
+mod blocking_io {
+    use std::{os::unix::fs::PermissionsExt, path::Path, sync::atomic::AtomicBool};
+
+    use gix::{
+        bstr::BString,
+        config::tree::User,
+        remote::{push::RejectReason, Direction::Push},
+    };
+
+    use crate::remote::fetch::blocking_and_async_io::repo_rw;
+
+    /// Let the bare repository at `remote_dir` accept push options and record them in its `push-options` file.
+    fn record_push_options(remote_dir: &Path) -> crate::Result {
+        let mut config = std::fs::read_to_string(remote_dir.join("config"))?;
+        config.push_str("[receive]\n\tadvertisePushOptions = true\n");
+        std::fs::write(remote_dir.join("config"), config)?;
+
+        let hook = remote_dir.join("hooks").join("pre-receive");
+        std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+        std::fs::write(
+            &hook,
+            "#!/bin/sh\ni=0\nwhile [ $i -lt \"${GIT_PUSH_OPTION_COUNT:-0}\" ]; do\n  eval \"echo \\$GIT_PUSH_OPTION_$i\"\n  i=$((i + 1))\ndone > push-options\n",
+        )?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    #[test]
+    fn atomic_push_with_push_options() -> crate::Result {
+        let (repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        record_push_options(&remote_dir)?;
+
+        let remote = repo
+            .find_remote("origin")?
+            .with_refspecs(["refs/heads/main", "v1.0"], Push)?;
+        let outcome = remote
+            .connect(Push)?
+            .prepare_push(gix::progress::Discard)?
+            .with_atomic(true)
+            .with_push_options(["ci.skip", "environment=staging"])
+            .send(gix::progress::Discard, &AtomicBool::default())?;
+
+        assert_eq!(outcome.rejected, []);
+        assert_eq!(
+            outcome
+                .updates
+                .iter()
+                .map(|update| update.name.to_string())
+                .collect::<Vec<_>>(),
+            ["refs/heads/main", "refs/tags/v1.0"],
+            "partial destinations are expanded according to the category of their source"
+        );
+        assert_eq!(outcome.report.unpack_error, None);
+        assert!(outcome.report.refs.iter().all(|status| status.error.is_none()));
+
+        let remote_repo = gix::open_opts(&remote_dir, crate::restricted())?;
+        for name in ["refs/heads/main", "refs/tags/v1.0"] {
+            let id = repo.find_reference(name)?.id().detach();
+            assert_eq!(remote_repo.find_reference(name)?.id(), id);
+            assert!(remote_repo.has_object(id), "the pack with all new objects was received");
+        }
+        assert_eq!(
+            std::fs::read_to_string(remote_dir.join("push-options"))?,
+            "ci.skip\nenvironment=staging\n",
+            "push options are passed to the hooks of the remote"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn non_fast_forward_updates_are_rejected_unless_forced() -> crate::Result {
+        let (repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        let main = repo.find_reference("refs/heads/main")?.id().detach();
+        let previous_main = repo
+            .find_object(main)?
+            .into_commit()
+            .parent_ids()
+            .next()
+            .expect("one parent")
+            .detach();
+
+        let push = |specs: &[String], atomic: bool| -> crate::Result<gix::remote::push::Outcome> {
+            Ok(repo
+                .find_remote("origin")?
+                .with_refspecs(specs.iter().map(String::as_str), Push)?
+                .connect(Push)?
+                .prepare_push(gix::progress::Discard)?
+                .with_atomic(atomic)
+                .send(gix::progress::Discard, &AtomicBool::default())?)
+        };
+        let outcome = push(&["main".into()], false)?;
+        assert_eq!(outcome.updates.len(), 1, "a fast-forward");
+
+        let outcome = push(&[format!("{previous_main}:refs/heads/main"), "v1.0".into()], true)?;
+        assert_eq!(outcome.rejected.len(), 1);
+        assert_eq!(outcome.rejected[0].reason, RejectReason::NonFastForward);
+        assert_eq!(outcome.updates, [], "a single rejection prevents an atomic push");
+
+        let remote_repo = gix::open_opts(&remote_dir, crate::restricted())?;
+        assert_eq!(remote_repo.find_reference("refs/heads/main")?.id(), main);
+        assert!(remote_repo.try_find_reference("refs/tags/v1.0")?.is_none());
+
+        let outcome = push(&[format!("+{previous_main}:refs/heads/main"), "v1.0".into()], true)?;
+        assert_eq!(outcome.rejected, []);
+        assert_eq!(outcome.updates.len(), 2);
+        assert_eq!(remote_repo.find_reference("refs/heads/main")?.id(), previous_main);
+
+        let outcome = push(&[":v1.0".into()], false)?;
+        assert!(outcome.updates[0].is_delete());
+        assert!(remote_repo.try_find_reference("refs/tags/v1.0")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn signed_push_sends_a_certificate_with_the_updates() -> crate::Result {
+        let (mut repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        let mut config = std::fs::read_to_string(remote_dir.join("config"))?;
+        config.push_str("[receive]\n\tcertNonceSeed = seed\n");
+        std::fs::write(remote_dir.join("config"), config)?;
+        let hook = remote_dir.join("hooks").join("pre-receive");
+        std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+        std::fs::write(
+            &hook,
+            "#!/bin/sh\necho $GIT_PUSH_CERT_NONCE_STATUS > nonce-status\ngit cat-file blob $GIT_PUSH_CERT > push-cert\n",
+        )?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+        repo.config_snapshot_mut()
+            .set_value(&User::SIGNING_KEY, "Signer <signer@example.com>")?;
+
+        let signature = "-----BEGIN PGP SIGNATURE-----\n\nfake\n-----END PGP SIGNATURE-----\n";
+        let outcome = repo
+            .find_remote("origin")?
+            .with_refspecs(["refs/heads/main"], Push)?
+            .connect(Push)?
+            .prepare_push(gix::progress::Discard)?
+            .with_gpg_sign(gix::push::GpgSign::Yes)
+            .with_signer(move |payload: &[u8]| {
+                assert!(payload.starts_with(b"certificate version 0.1\npusher Signer <signer@example.com> "));
+                Ok(BString::from(signature))
+            })
+            .send(gix::progress::Discard, &AtomicBool::default())?;
+        assert_eq!(outcome.updates.len(), 1);
+        assert_eq!(outcome.report.unpack_error, None);
+        assert!(outcome.report.refs.iter().all(|status| status.error.is_none()));
+
+        assert_eq!(
+            std::fs::read_to_string(remote_dir.join("nonce-status"))?,
+            "OK\n",
+            "the certificate is signed with the nonce the remote asked for"
+        );
+        let cert = std::fs::read_to_string(remote_dir.join("push-cert"))?;
+        let update = &outcome.updates[0];
+        assert!(cert.contains(&format!("\n\n{} {} refs/heads/main\n", update.old, update.new)));
+        assert!(cert.ends_with(signature));
+        Ok(())
+    }
+}
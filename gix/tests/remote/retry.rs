@@ -0,0 +1,88 @@
+#[cfg(feature = "blocking-network-client")]
+mod blocking_io {
+    use std::time::Duration;
+
+    use gix::remote::{retry::Policy, Direction::Fetch};
+    use gix_protocol::transport::IsSpuriousError;
+
+    fn policy(max_attempts: usize) -> Policy {
+        Policy {
+            max_attempts,
+            initial_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(3),
+        }
+    }
+
+    #[test]
+    fn delays_double_until_they_reach_the_maximum() {
+        assert_eq!(
+            policy(5).delays().collect::<Vec<_>>(),
+            [1, 2, 3, 3].map(Duration::from_millis),
+            "one less than the amount of attempts"
+        );
+        assert_eq!(policy(0).delays().count(), 0);
+        assert_eq!(
+            Policy::default().delays().collect::<Vec<_>>(),
+            [1, 2].map(Duration::from_secs)
+        );
+    }
+
+    #[test]
+    fn spurious_failures_are_retried_until_no_attempts_are_left() {
+        let repo = gix::open_opts(crate::remote::repo_path("base"), crate::restricted()).unwrap();
+        // Nothing listens on the first port, so connecting is refused, which is assumed to be temporary.
+        let remote = repo.remote_at("git://127.0.0.1:1/repo.git").unwrap();
+        let mut attempts = Vec::new();
+        let err = policy(3)
+            .run(|attempt| {
+                attempts.push(attempt);
+                remote.connect(Fetch).map(|_| ())
+            })
+            .unwrap_err();
+        assert!(err.is_spurious());
+        assert_eq!(attempts, [1, 2, 3]);
+
+        let err = remote
+            .connect_with_retry(
+                Fetch,
+                &policy(2),
+                |_connection| -> Result<(), gix::env::collate::fetch::Error> {
+                    unreachable!("there is never a connection to operate on")
+                },
+            )
+            .unwrap_err();
+        assert!(err.is_spurious());
+    }
+
+    #[test]
+    fn other_failures_and_successes_are_not_retried() {
+        let repo = gix::open_opts(crate::remote::repo_path("base"), crate::restricted()).unwrap();
+        let remote = repo.remote_at("does-not-exist").unwrap();
+        let mut attempts = 0;
+        let err = remote
+            .connect_with_retry(
+                Fetch,
+                &policy(3),
+                |_connection| -> Result<(), gix::env::collate::fetch::Error> {
+                    attempts += 1;
+                    Ok(())
+                },
+            )
+            .unwrap_err();
+        assert!(!err.is_spurious(), "the remote repository doesn't exist");
+        assert_eq!(attempts, 0, "connecting failed for good");
+
+        let repo = crate::remote::repo("clone");
+        let remote = repo.find_remote("origin").unwrap();
+        let mut attempts = 0;
+        let outcome = remote
+            .connect_with_retry(Fetch, &policy(3), |connection| {
+                attempts += 1;
+                let prepare = connection.prepare_fetch(gix::progress::Discard, Default::default())?;
+                Ok::<_, gix::env::collate::fetch::Error>(prepare.ref_map().remote_refs.len())
+            })
+            .unwrap();
+        assert!(outcome > 0);
+        assert_eq!(attempts, 1);
+    }
+}
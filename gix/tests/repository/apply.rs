@@ -0,0 +1,286 @@
+use gix::{
+    apply::{Location, Options},
+    bstr::ByteSlice,
+};
+
+fn parse(patch: &str) -> Vec<gix::apply::plumbing::Patch> {
+    gix::apply::plumbing::patch::parse(patch.as_bytes(), Default::default()).expect("valid patch")
+}
+
+const PATCH: &str = "diff --git a/a b/a
+--- a/a
++++ b/a
+@@ -1,2 +1,2 @@
+ 1
+-2
++two
+diff --git a/dir/b b/dir/b
+deleted file mode 100644
+--- a/dir/b
++++ /dev/null
+@@ -1 +0,0 @@
+-b
+diff --git a/new/c b/new/c
+new file mode 100755
+--- /dev/null
++++ b/new/c
+@@ -0,0 +1 @@
++c
+";
+
+#[test]
+fn to_worktree() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "1\n2\n")?;
+    std::fs::create_dir(workdir.join("dir"))?;
+    std::fs::write(workdir.join("dir").join("b"), "b\n")?;
+    let patches = parse(PATCH);
+
+    let outcome = repo.apply_patches(
+        &patches,
+        Options {
+            check: true,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        outcome
+            .changes
+            .iter()
+            .map(|change| change.location.to_str_lossy().into_owned())
+            .collect::<Vec<_>>(),
+        ["a", "dir/b", "new/c"]
+    );
+    assert_eq!(
+        std::fs::read(workdir.join("a"))?.as_bstr(),
+        "1\n2\n",
+        "nothing is written when checking"
+    );
+
+    repo.apply_patches(&patches, Options::default())?;
+    assert_eq!(std::fs::read(workdir.join("a"))?.as_bstr(), "1\ntwo\n");
+    assert!(
+        !workdir.join("dir").exists(),
+        "deleted files are removed along with their empty parent directories"
+    );
+    assert_eq!(std::fs::read(workdir.join("new").join("c"))?.as_bstr(), "c\n");
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        assert_ne!(
+            std::fs::metadata(workdir.join("new").join("c"))?.permissions().mode() & 0o111,
+            0,
+            "the executable bit is set"
+        );
+    }
+    assert!(repo.index_or_empty()?.entries().is_empty(), "the index isn't touched");
+
+    let err = repo.apply_patches(&patches, Options::default()).unwrap_err();
+    assert!(
+        matches!(err, gix::apply::Error::Apply(_)),
+        "patches that don't apply anymore fail: {err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn to_index() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let options = Options {
+        location: Location::Index,
+        ..Default::default()
+    };
+    repo.apply_patches(&parse("--- /dev/null\n+++ b/a\n@@ -0,0 +1,2 @@\n+1\n+2\n"), options)?;
+    let outcome = repo.apply_patches(&parse("--- a/a\n+++ b/a\n@@ -1,2 +1,2 @@\n 1\n-2\n+two\n"), options)?;
+    assert_eq!(outcome.changes.len(), 1);
+
+    let index = repo.open_index()?;
+    let entry = index.entry_by_path("a".into()).expect("added");
+    assert_eq!(entry.mode, gix::index::entry::Mode::FILE);
+    assert_eq!(repo.find_object(entry.id)?.data.as_bstr(), "1\ntwo\n");
+    assert!(!workdir.join("a").exists(), "the worktree isn't touched");
+    Ok(())
+}
+
+#[test]
+fn to_worktree_and_index() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "1\n2\n")?;
+    std::fs::create_dir(workdir.join("dir"))?;
+    std::fs::write(workdir.join("dir").join("b"), "b\n")?;
+
+    repo.apply_patches(
+        &parse(PATCH),
+        Options {
+            location: Location::WorktreeAndIndex,
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(std::fs::read(workdir.join("a"))?.as_bstr(), "1\ntwo\n");
+    let index = repo.open_index()?;
+    assert_eq!(
+        index.entries().iter().map(|e| e.path(&index)).collect::<Vec<_>>(),
+        ["a", "new/c"]
+    );
+    let entry = index.entry_by_path("new/c".into()).expect("added");
+    assert_eq!(entry.mode, gix::index::entry::Mode::FILE_EXECUTABLE);
+    assert_eq!(repo.find_object(entry.id)?.data.as_bstr(), "c\n");
+    Ok(())
+}
+
+#[test]
+fn to_tree() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let mut dir = gix::objs::Tree::empty();
+    dir.entries.push(gix::objs::tree::Entry {
+        mode: gix::objs::tree::EntryKind::Blob.into(),
+        filename: "b".into(),
+        oid: repo.write_blob("b\n")?.detach(),
+    });
+    let mut tree = gix::objs::Tree::empty();
+    tree.entries.push(gix::objs::tree::Entry {
+        mode: gix::objs::tree::EntryKind::Blob.into(),
+        filename: "a".into(),
+        oid: repo.write_blob("1\n2\n")?.detach(),
+    });
+    tree.entries.push(gix::objs::tree::Entry {
+        mode: gix::objs::tree::EntryKind::Tree.into(),
+        filename: "dir".into(),
+        oid: repo.write_object(&dir)?.detach(),
+    });
+    let tree = repo.write_object(&tree)?;
+
+    let id = repo.apply_patches_to_tree(tree, &parse(PATCH), Default::default())?;
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    repo.find_object(id)?
+        .into_tree()
+        .traverse()
+        .breadthfirst(&mut recorder)?;
+    let files: Vec<_> = recorder
+        .records
+        .into_iter()
+        .filter(|entry| !entry.mode.is_tree())
+        .map(|entry| -> crate::Result<_> {
+            Ok((
+                entry.filepath.to_string(),
+                entry.mode.kind(),
+                repo.find_object(entry.oid)?.data.to_str_lossy().into_owned(),
+            ))
+        })
+        .collect::<Result<_, _>>()?;
+    assert_eq!(
+        files,
+        [
+            (
+                "a".to_string(),
+                gix::objs::tree::EntryKind::Blob,
+                "1\ntwo\n".to_string()
+            ),
+            (
+                "new/c".to_string(),
+                gix::objs::tree::EntryKind::BlobExecutable,
+                "c\n".to_string()
+            ),
+        ],
+        "empty directories disappear"
+    );
+    Ok(())
+}
+
+#[test]
+fn invalid_locations_are_rejected() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    for location in ["../x", "/x", "dir//x", ".git/config", "dir/.git/config"] {
+        // Parsing rejects these already, but patches may also be created by hand.
+        let patches = [gix::apply::plumbing::Patch {
+            old_path: None,
+            new_path: Some(location.into()),
+            old_mode: None,
+            new_mode: Some(gix::objs::tree::EntryKind::Blob),
+            rewrite: None,
+            content: gix::apply::plumbing::patch::Content::Hunks(Vec::new()),
+        }];
+        for location_kind in [Location::Worktree, Location::Index] {
+            let err = repo
+                .apply_patches(
+                    &patches,
+                    Options {
+                        location: location_kind,
+                        ..Default::default()
+                    },
+                )
+                .unwrap_err();
+            assert!(
+                matches!(&err, gix::apply::Error::InvalidLocation { location: actual } if actual == location),
+                "{location:?} must be rejected, got {err:?}"
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn writing_beyond_symlinks_is_rejected() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    let outside = gix_testtools::tempfile::TempDir::new()?;
+    std::fs::write(outside.path().join("existing"), "a\n")?;
+    std::os::unix::fs::symlink(outside.path(), workdir.join("link"))?;
+
+    for patch in [
+        "--- /dev/null\n+++ b/link/new\n@@ -0,0 +1 @@\n+a\n",
+        "--- a/link/existing\n+++ b/link/existing\n@@ -1 +1 @@\n-a\n+b\n",
+        "--- a/link/existing\n+++ /dev/null\n@@ -1 +0,0 @@\n-a\n",
+    ] {
+        let err = repo.apply_patches(&parse(patch), Options::default()).unwrap_err();
+        assert!(
+            matches!(&err, gix::apply::Error::BeyondSymlink { location } if location.starts_with(b"link/")),
+            "symlinked directories in the worktree are never followed: {err:?}"
+        );
+    }
+    assert!(!outside.path().join("new").exists());
+    assert_eq!(std::fs::read(outside.path().join("existing"))?.as_bstr(), "a\n");
+
+    let patch = parse(&format!(
+        "diff --git a/dir b/dir
+new file mode 120000
+--- /dev/null
++++ b/dir
+@@ -0,0 +1 @@
++{}
+\\ No newline at end of file
+diff --git a/dir/new b/dir/new
+new file mode 100644
+--- /dev/null
++++ b/dir/new
+@@ -0,0 +1 @@
++a
+",
+        outside.path().display()
+    ));
+    for check in [true, false] {
+        let err = repo
+            .apply_patches(
+                &patch,
+                Options {
+                    check,
+                    ..Default::default()
+                },
+            )
+            .unwrap_err();
+        assert!(
+            matches!(&err, gix::apply::Error::BeyondSymlink { location } if location == "dir/new"),
+            "symlinks created by the patch aren't followed either: {err:?}"
+        );
+    }
+    assert!(
+        std::fs::symlink_metadata(workdir.join("dir")).is_err(),
+        "nothing is written"
+    );
+    assert!(!outside.path().join("new").exists());
+    Ok(())
+}
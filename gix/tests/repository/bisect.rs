@@ -0,0 +1,131 @@
+use gix::repository::bisect::{self, Mark, Outcome};
+
+use crate::util::{named_subrepo_opts, repo_rw, restricted};
+
+fn value_at(repo: &gix::Repository, id: gix::ObjectId) -> crate::Result<u32> {
+    let tree = repo.find_object(id)?.try_into_commit()?.tree()?;
+    let value = tree.find_entry("value").expect("present").object()?;
+    Ok(std::str::from_utf8(&value.data)?.trim().parse()?)
+}
+
+#[test]
+fn session_finds_first_bad_commit_like_git() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_bisect_session_repo.sh")?;
+    let expected = repo.rev_parse_single("c7")?.detach();
+    assert_eq!(
+        std::fs::read_to_string(repo.work_dir().expect("non-bare").join("first-bad.baseline"))?.trim(),
+        expected.to_string(),
+        "git found the same commit"
+    );
+    let (c1, c10) = (repo.rev_parse_single("c1")?, repo.rev_parse_single("c10")?);
+
+    assert!(matches!(
+        repo.bisect_mark(c10, Mark::Bad),
+        Err(bisect::mark::Error::NotStarted)
+    ));
+    assert_eq!(repo.bisect_state()?, None);
+    assert_eq!(repo.bisect_reset()?, None, "there is nothing to reset");
+
+    repo.bisect_start()?;
+    assert!(matches!(repo.bisect_start(), Err(bisect::start::Error::InProgress)));
+    assert_eq!(repo.state(), Some(gix::state::InProgress::Bisect));
+
+    repo.bisect_mark(c10, Mark::Bad)?;
+    assert!(matches!(repo.bisect_next(), Err(bisect::next::Error::MissingGoodOrBad)));
+    repo.bisect_mark(c1, Mark::Good)?;
+
+    let mut tested = Vec::new();
+    let first_bad = loop {
+        match repo.bisect_next()? {
+            Outcome::Next { id, .. } => {
+                tested.push(id);
+                let mark = if value_at(&repo, id)? > 6 {
+                    Mark::Bad
+                } else {
+                    Mark::Good
+                };
+                repo.bisect_mark(id, mark)?;
+            }
+            Outcome::FirstBad(id) => break id,
+            Outcome::OnlySkippedLeft { .. } => unreachable!("nothing was skipped"),
+        }
+    };
+    assert_eq!(first_bad, expected);
+    assert_eq!(tested.len(), 3, "it takes as many steps as it takes `git`");
+    assert_eq!(
+        repo.bisect_next()?,
+        Outcome::FirstBad(first_bad),
+        "asking again is fine"
+    );
+
+    let state = repo.bisect_state()?.expect("in progress");
+    assert_eq!(state.bad, Some(first_bad));
+    assert_eq!(state.good.len(), 3, "c1 and the two commits that were tested as good");
+    assert!(state.skip.is_empty());
+
+    let log = std::fs::read_to_string(repo.path().join("BISECT_LOG"))?;
+    assert!(log.starts_with(&format!(
+        "git bisect start\n# bad: [{c10}] c10\ngit bisect bad {c10}\n# good: [{c1}] c1\ngit bisect good {c1}\n"
+    )));
+    assert!(
+        log.ends_with(&format!("# first bad commit: [{first_bad}] c7\n")),
+        "the first bad commit is only logged once: {log}"
+    );
+
+    assert_eq!(repo.bisect_reset()?, Some("main".into()), "the branch to return to");
+    assert_eq!(repo.bisect_state()?, None);
+    assert_eq!(repo.state(), None);
+    assert_eq!(repo.references()?.prefixed("refs/bisect/")?.count(), 0);
+    Ok(())
+}
+
+#[test]
+fn skipped_commits_are_avoided() -> crate::Result {
+    let (repo, _tmp) = repo_rw("make_bisect_session_repo.sh")?;
+    repo.bisect_start()?;
+    repo.bisect_mark(repo.rev_parse_single("c7")?, Mark::Bad)?;
+    repo.bisect_mark(repo.rev_parse_single("c5")?, Mark::Good)?;
+    let c6 = repo.rev_parse_single("c6")?.detach();
+    assert_eq!(
+        repo.bisect_next()?,
+        Outcome::Next {
+            id: c6,
+            remaining: 0,
+            steps: 0
+        }
+    );
+
+    repo.bisect_mark(c6, Mark::Skip)?;
+    let c7 = repo.rev_parse_single("c7")?.detach();
+    assert_eq!(
+        repo.bisect_next()?,
+        Outcome::OnlySkippedLeft {
+            candidates: vec![c6, c7]
+        }
+    );
+    let log = std::fs::read_to_string(repo.path().join("BISECT_LOG"))?;
+    assert!(log.ends_with(&format!(
+        "# only skipped commits left to test\n# possible first bad commit: [{c6}] c6\n# possible first bad commit: [{c7}] c7\n"
+    )));
+    Ok(())
+}
+
+#[test]
+fn state_written_by_git_can_be_continued() -> crate::Result {
+    let repo = named_subrepo_opts("make_bisect_session_repo.sh", "with-git-bisect", restricted())?;
+    let state = repo.bisect_state()?.expect("git started bisecting");
+    assert_eq!(state.bad, Some(repo.rev_parse_single("c10")?.detach()));
+    assert_eq!(state.good, [repo.rev_parse_single("c1")?.detach()]);
+    assert_eq!(state.skip, [repo.rev_parse_single("c5")?.detach()]);
+
+    let Outcome::Next { id, remaining, steps } = repo.bisect_next()? else {
+        unreachable!("there is more to test")
+    };
+    assert_eq!(
+        id,
+        repo.rev_parse_single("c6")?.detach(),
+        "c5 would split the commits into halves as well, but was skipped"
+    );
+    assert_eq!((remaining, steps), (3, 2));
+    Ok(())
+}
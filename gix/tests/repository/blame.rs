@@ -0,0 +1,79 @@
+use gix::{bstr::ByteSlice, ObjectId, Repository};
+
+/// Return the ids of `HEAD` and all of its first-parent ancestors, from the oldest to the newest.
+fn first_parent_ids(repo: &Repository) -> crate::Result<Vec<ObjectId>> {
+    let mut ids = vec![repo.head_id()?.detach()];
+    while let Some(parent) = repo
+        .find_object(*ids.last().expect("non-empty"))?
+        .into_commit()
+        .parent_ids()
+        .next()
+    {
+        ids.push(parent.detach());
+    }
+    ids.reverse();
+    Ok(ids)
+}
+
+/// Return the commit that introduced each line of `file_path` as of `HEAD`.
+fn commit_per_line(repo: &Repository, file_path: &str, options: gix::blame::Options) -> crate::Result<Vec<ObjectId>> {
+    let outcome = repo.blame_file(file_path.into(), repo.head_id()?, options)?;
+    assert_eq!(
+        outcome.entries.iter().map(|e| e.len as usize).sum::<usize>(),
+        outcome.blob.lines().count(),
+        "all lines are accounted for"
+    );
+    Ok(outcome
+        .entries
+        .iter()
+        .flat_map(|entry| std::iter::repeat(entry.commit_id).take(entry.len as usize))
+        .collect())
+}
+
+#[test]
+fn file_without_ignored_revisions() -> crate::Result {
+    let repo = crate::named_repo("make_blame_repo.sh")?;
+    let [_c1, _c2, c3, c4]: [ObjectId; 4] = first_parent_ids(&repo)?.try_into().expect("four commits");
+    assert_eq!(
+        commit_per_line(&repo, "file", Default::default())?,
+        [c3, c3, c4, c3],
+        "the formatting commit takes the blame for all lines it touched"
+    );
+    Ok(())
+}
+
+#[test]
+fn file_with_configured_ignore_revs_file() -> crate::Result {
+    let repo = crate::named_repo("make_blame_repo.sh")?;
+    let [c1, c2, c3, c4]: [ObjectId; 4] = first_parent_ids(&repo)?.try_into().expect("four commits");
+    let options = repo.blame_options()?;
+    assert_eq!(
+        options.ignore_revs.iter().copied().collect::<Vec<_>>(),
+        [c3],
+        "the ignored commit is read from the file configured in `blame.ignoreRevsFile`, skipping comments"
+    );
+    assert_eq!(
+        commit_per_line(&repo, "file", options)?,
+        [c1, c2, c4, c3],
+        "changed lines are passed to the parent of the ignored commit, unless they were added by it"
+    );
+    Ok(())
+}
+
+#[test]
+fn file_incremental() -> crate::Result {
+    let repo = crate::named_repo("make_blame_repo.sh")?;
+    let mut entries = Vec::new();
+    let outcome = repo.blame_file_incremental("file".into(), repo.head_id()?, repo.blame_options()?, |entry| {
+        entries.push(entry)
+    })?;
+    entries.sort();
+    assert_eq!(
+        entries,
+        repo.blame_file("file".into(), repo.head_id()?, repo.blame_options()?)?
+            .entries,
+        "entries are emitted as they are found, and are the same as the ones returned all at once"
+    );
+    assert_eq!(outcome.statistics.commits_traversed, 4);
+    Ok(())
+}
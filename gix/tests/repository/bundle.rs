@@ -0,0 +1,157 @@
+use std::sync::atomic::AtomicBool;
+
+use gix::remote::fetch::refs::update::Mode;
+
+fn fetch_spec(spec: &str) -> gix::refspec::RefSpec {
+    gix::refspec::parse(spec.into(), gix::refspec::parse::Operation::Fetch)
+        .expect("valid")
+        .to_owned()
+}
+
+#[test]
+fn fetch_all_into_empty_repository() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_bundle_repo.sh")?;
+    let source = gix::open_opts(repo.work_dir().expect("non-bare").join("source"), crate::restricted())?;
+    let bundle_path = repo.work_dir().expect("non-bare").join("all.bundle");
+
+    let outcome = repo.fetch_bundle(
+        &bundle_path,
+        &[
+            fetch_spec("+refs/heads/*:refs/remotes/origin/*"),
+            fetch_spec("refs/tags/*:refs/tags/*"),
+        ],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert!(outcome.header.prerequisites.is_empty());
+    assert_eq!(outcome.mappings.len(), 2, "main and v1");
+    assert!(
+        outcome
+            .update_refs
+            .updates
+            .iter()
+            .all(|update| update.mode == Mode::New),
+        "{:?}",
+        outcome.update_refs.updates
+    );
+
+    assert_eq!(
+        repo.find_reference("refs/remotes/origin/main")?.id(),
+        source.head_id()?.detach(),
+        "the branch was created"
+    );
+    assert_eq!(
+        repo.find_reference("v1")?.id(),
+        source.find_reference("v1")?.id().detach(),
+        "tags are fetched as well"
+    );
+    assert!(
+        repo.head_commit().is_err(),
+        "HEAD isn't changed, just like with any other fetch"
+    );
+    let tree = repo
+        .find_reference("refs/remotes/origin/main")?
+        .peel_to_id_in_place()?
+        .object()?
+        .peel_to_tree()?;
+    assert!(tree.find_entry("file").is_some(), "the objects are available");
+    Ok(())
+}
+
+#[test]
+fn missing_prerequisites_prevent_fetching() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_bundle_repo.sh")?;
+    let bundle_path = repo.work_dir().expect("non-bare").join("incremental.bundle");
+
+    let err = repo
+        .fetch_bundle(
+            &bundle_path,
+            &[fetch_spec("+refs/heads/*:refs/remotes/origin/*")],
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+        )
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        gix::bundle::fetch::Error::MissingPrerequisites { ref ids } if ids.len() == 1
+    ));
+    assert!(repo.try_find_reference("refs/remotes/origin/main")?.is_none());
+
+    repo.fetch_bundle(
+        &repo.work_dir().expect("non-bare").join("all.bundle"),
+        &[fetch_spec("refs/tags/v1:refs/tags/v1")],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    let outcome = repo.fetch_bundle(
+        &bundle_path,
+        &[fetch_spec("+refs/heads/*:refs/remotes/origin/*")],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(outcome.header.prerequisites.len(), 1);
+    assert_eq!(outcome.update_refs.updates.len(), 1);
+    assert_eq!(outcome.update_refs.updates[0].mode, Mode::New);
+    Ok(())
+}
+
+#[test]
+fn write_revision_range() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_bundle_repo.sh")?;
+    let source = gix::open_opts(repo.work_dir().expect("non-bare").join("source"), crate::restricted())?;
+    let bundle_path = repo.work_dir().expect("non-bare").join("written.bundle");
+
+    let outcome = source.write_bundle(
+        &mut std::fs::File::create(&bundle_path)?,
+        ["v1..main".into()],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+        Default::default(),
+    )?;
+    assert_eq!(outcome.header.refs.len(), 1);
+    assert_eq!(outcome.header.refs[0].name, "refs/heads/main");
+    assert_eq!(outcome.header.refs[0].target, source.head_id()?.detach());
+    assert_eq!(outcome.header.prerequisites.len(), 1);
+    assert_eq!(
+        outcome.header.prerequisites[0].id,
+        source.find_reference("v1")?.id().detach()
+    );
+    assert_eq!(outcome.num_objects, 3, "one commit, one tree and one blob");
+
+    repo.fetch_bundle(
+        &repo.work_dir().expect("non-bare").join("all.bundle"),
+        &[fetch_spec("refs/tags/v1:refs/tags/v1")],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    let outcome = repo.fetch_bundle(
+        &bundle_path,
+        &[fetch_spec("+refs/heads/*:refs/remotes/origin/*")],
+        &mut gix_features::progress::Discard,
+        &AtomicBool::default(),
+    )?;
+    assert_eq!(outcome.update_refs.updates.len(), 1);
+    assert_eq!(
+        repo.find_reference("refs/remotes/origin/main")?.id(),
+        source.head_id()?.detach(),
+        "the written bundle can be fetched"
+    );
+
+    let err = source
+        .write_bundle(
+            &mut Vec::new(),
+            ["main..v1".into()],
+            &mut gix_features::progress::Discard,
+            &AtomicBool::default(),
+            Default::default(),
+        )
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            gix::bundle::write::Error::Write(gix::bundle::plumbing::write::Error::Empty)
+        ),
+        "{err:?}"
+    );
+    Ok(())
+}
@@ -6,7 +6,7 @@ mod remote;
 mod ssh_options {
     use std::ffi::OsStr;
 
-    use crate::repository::config::repo;
+    use crate::repository::config::{repo, repo_opts};
 
     #[test]
     fn with_command_and_variant() -> crate::Result {
@@ -33,6 +33,57 @@ mod ssh_options {
         );
         Ok(())
     }
+
+    #[test]
+    fn native_is_unset_by_default() -> crate::Result {
+        let repo = repo("ssh-all-options");
+        assert!(repo.ssh_connect_options()?.native.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn with_native_options() -> crate::Result {
+        use gix::protocol::transport::client::ssh::native::HostKeyCheck;
+
+        let repo = repo_opts("ssh-all-options", |opts| {
+            opts.strict_config(true).config_overrides([
+                "gitoxide.ssh.native=true",
+                "gitoxide.ssh.strictHostKeyChecking=accept-new",
+                "gitoxide.ssh.knownHostsFile=/known/hosts",
+                "gitoxide.ssh.identityFile=/first/key",
+                "gitoxide.ssh.identityFile=/second/key",
+                "gitoxide.ssh.useAgent=false",
+            ])
+        });
+        let native = repo.ssh_connect_options()?.native.expect("configured");
+        assert_eq!(native.host_key_check, HostKeyCheck::AcceptNew);
+        assert_eq!(native.known_hosts_files, [std::path::PathBuf::from("/known/hosts")]);
+        assert_eq!(
+            native.identity_files,
+            [
+                std::path::PathBuf::from("/first/key"),
+                std::path::PathBuf::from("/second/key")
+            ]
+        );
+        assert!(!native.use_agent);
+        Ok(())
+    }
+
+    #[test]
+    fn remote_ssh_command_overrides_repository_options() -> crate::Result {
+        let repo = repo_opts("ssh-all-options", |opts| {
+            opts.strict_config(true).config_overrides([
+                "gitoxide.ssh.native=true",
+                "remote.origin.url=ssh://host/repo",
+                "remote.origin.sshCommand=ssh -o Option=remote",
+            ])
+        });
+        let opts = repo.find_remote("origin")?.ssh_connect_options()?;
+        assert_eq!(opts.command.as_deref(), Some(OsStr::new("ssh -o Option=remote")));
+        assert!(opts.native.is_none(), "the remote wants to use a program");
+        assert!(!opts.disallow_shell);
+        Ok(())
+    }
 }
 
 #[cfg(any(feature = "blocking-network-client", feature = "async-network-client"))]
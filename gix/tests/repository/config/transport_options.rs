@@ -54,6 +54,8 @@ mod http {
             connect_timeout,
             verbose,
             ssl_ca_info,
+            ssl_cert,
+            ssl_key,
             ssl_version,
             ssl_verify,
             http_version,
@@ -78,6 +80,8 @@ mod http {
         assert_eq!(no_proxy, None);
         assert!(!verbose, "verbose is disabled by default");
         assert_eq!(ssl_ca_info.as_deref(), Some(std::path::Path::new("./CA.pem")));
+        assert_eq!(ssl_cert, None, "client certificates aren't configured by default");
+        assert_eq!(ssl_key, None);
         #[cfg(feature = "blocking-http-transport-reqwest")]
         {
             assert!(
@@ -155,6 +159,91 @@ mod http {
         assert!(opts.ssl_version.is_none(), "empty strings reset what was there");
     }
 
+    #[test]
+    fn http_ssl_version_min_or_max_apply_on_their_own() {
+        let repo = repo_opts("http-config", |opts| {
+            opts.config_overrides(["http.sslVersion=", "gitoxide.http.sslVersionMin=tlsv1.2"])
+        });
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert_eq!(
+            opts.ssl_version,
+            Some(SslVersionRangeInclusive {
+                min: SslVersion::TlsV1_2,
+                max: SslVersion::TlsV1_3
+            })
+        );
+
+        let repo = repo_opts("http-config", |opts| {
+            opts.config_overrides(["gitoxide.http.sslVersionMax=tlsv1.2"])
+        });
+        let opts = http_options(&repo, None, "https://example.com/does/not/matter");
+        assert_eq!(
+            opts.ssl_version,
+            Some(SslVersionRangeInclusive {
+                min: SslVersion::SslV2,
+                max: SslVersion::TlsV1_2
+            }),
+            "the minimum of `http.sslVersion` is kept"
+        );
+    }
+
+    #[test]
+    fn http_ssl_settings_per_url() {
+        let repo = repo_opts("http-config", |opts| {
+            opts.config_overrides([
+                "http.sslCert=./default-cert.pem",
+                "http.sslKey=./key.pem",
+                "http.https://example.com.sslVerify=false",
+                "http.https://example.com.sslCert=./host-cert.pem",
+                "http.https://example.com/org/.sslCert=./org-cert.pem",
+                "http.https://*.example.com.sslVersion=tlsv1.3",
+                "http.https://other.example.com.sslCAInfo=./other-CA.pem",
+            ])
+        });
+
+        let opts = http_options(&repo, None, "https://example.com/org/repo");
+        assert!(!opts.ssl_verify, "the host matches");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./org-cert.pem")),
+            "the longest matching path wins"
+        );
+        assert_eq!(
+            opts.ssl_key.as_deref(),
+            Some(std::path::Path::new("./key.pem")),
+            "values without url apply to all urls"
+        );
+
+        let opts = http_options(&repo, None, "https://example.com/organisation/repo");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./host-cert.pem")),
+            "paths only match at component boundaries"
+        );
+
+        let opts = http_options(&repo, None, "https://other.example.com/org/repo");
+        assert!(opts.ssl_verify, "this host doesn't match");
+        assert_eq!(
+            opts.ssl_cert.as_deref(),
+            Some(std::path::Path::new("./default-cert.pem"))
+        );
+        assert_eq!(
+            opts.ssl_ca_info.as_deref(),
+            Some(std::path::Path::new("./other-CA.pem"))
+        );
+        assert_eq!(
+            opts.ssl_version,
+            Some(SslVersionRangeInclusive {
+                min: SslVersion::TlsV1_3,
+                max: SslVersion::TlsV1_3
+            }),
+            "wildcards match subdomains"
+        );
+
+        let opts = http_options(&repo, None, "http://example.com/org/repo");
+        assert!(opts.ssl_verify, "the scheme has to match as well");
+    }
+
     #[test]
     fn http_verbose() {
         let repo = repo("http-verbose");
@@ -0,0 +1,118 @@
+use gix::{bstr::ByteSlice, diff::range_diff::Pair, ObjectId};
+
+#[test]
+fn range_diff_matches_git() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_range_diff_repo.sh")?;
+    let series = |tip: &str| -> crate::Result<Vec<ObjectId>> {
+        (0..3)
+            .rev()
+            .map(|generation| Ok(repo.rev_parse_single(format!("{tip}~{generation}").as_str())?.detach()))
+            .collect()
+    };
+    let outcome = repo.range_diff(series("old")?, series("new")?, Default::default())?;
+
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("range-diff.baseline"))?;
+    let index = |token: &str| token.trim_end_matches(':').parse::<usize>().ok().map(|pos| pos - 1);
+    let expected: Vec<_> = baseline
+        .lines()
+        .map(|line| {
+            let tokens: Vec<_> = line.split_whitespace().collect();
+            let (old, new) = (index(tokens[0]), index(tokens[3]));
+            match tokens[2] {
+                "=" => (Some(old.expect("old")), Some(new.expect("new")), false),
+                "!" => (old, new, true),
+                _ => (old, new, false),
+            }
+        })
+        .collect();
+    let actual: Vec<_> = outcome
+        .pairs
+        .iter()
+        .map(|pair| match pair {
+            Pair::Removed { old } => (Some(*old), None, false),
+            Pair::Added { new } => (None, Some(*new), false),
+            Pair::Unchanged { old, new } => (Some(*old), Some(*new), false),
+            Pair::Changed { old, new, .. } => (Some(*old), Some(*new), true),
+        })
+        .collect();
+    assert_eq!(
+        actual, expected,
+        "the pairs and their order are the same as the ones of `git`"
+    );
+
+    let Pair::Changed { interdiff, .. } = &outcome.pairs[2] else {
+        panic!("the third pair is changed")
+    };
+    assert!(
+        interdiff.contains_str("\n-+1\n") && interdiff.contains_str("\n++31\n"),
+        "the interdiff shows how the patches differ: {}",
+        interdiff.as_bstr()
+    );
+    Ok(())
+}
+
+#[test]
+fn patch_id_matches_git() -> crate::Result {
+    let repo = crate::named_repo("make_cherry_repo.sh")?;
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("patch-id.baseline"))?;
+    for line in baseline.lines() {
+        let (name, expected) = line.split_once(' ').expect("name and patch-id");
+        let id = repo.rev_parse_single(name)?;
+        assert_eq!(
+            repo.patch_id(id)?,
+            Some(ObjectId::from_hex(expected.as_bytes())?),
+            "{name}: the same as `git show --binary --full-index {name} | git patch-id --stable`"
+        );
+    }
+    let cherry_picked = repo.rev_parse_single("upstream~1")?;
+    assert_eq!(
+        repo.patch_id(cherry_picked)?,
+        repo.patch_id(repo.rev_parse_single("h3")?)?,
+        "cherry-picked commits have the same patch-id"
+    );
+    assert_eq!(
+        repo.patch_id(repo.rev_parse_single("merge")?)?,
+        None,
+        "merges have none"
+    );
+    Ok(())
+}
+
+#[cfg(feature = "revision")]
+#[test]
+fn cherry_matches_git() -> crate::Result {
+    let repo = crate::named_repo("make_cherry_repo.sh")?;
+    let actual = repo.cherry(repo.rev_parse_single("upstream")?, repo.rev_parse_single("head")?)?;
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("cherry.baseline"))?;
+    let expected: Vec<_> = baseline
+        .lines()
+        .map(|line| {
+            let (mark, id) = line.split_once(' ').expect("mark and id");
+            Ok((mark == "-", ObjectId::from_hex(id.as_bytes())?))
+        })
+        .collect::<crate::Result<_>>()?;
+    assert_eq!(
+        actual
+            .iter()
+            .map(|commit| (commit.upstream_equivalent.is_some(), commit.id))
+            .collect::<Vec<_>>(),
+        expected,
+        "the same commits are marked as applied, in the same order"
+    );
+
+    let first_applied = actual
+        .iter()
+        .find(|commit| commit.upstream_equivalent.is_some())
+        .map(|commit| commit.id);
+    assert_eq!(
+        first_applied,
+        Some(repo.rev_parse_single("h2")?.detach()),
+        "the first applied commit is h2"
+    );
+    assert_eq!(
+        actual[1].upstream_equivalent,
+        Some(repo.rev_parse_single("upstream~2")?.detach()),
+        "h2 was picked right after u1"
+    );
+    Ok(())
+}
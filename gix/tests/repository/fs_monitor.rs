@@ -0,0 +1,62 @@
+use gix::index::entry::Flags;
+
+#[test]
+fn hook_restricts_valid_entries_to_unchanged_paths() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "a\n")?;
+    std::fs::write(workdir.join("b"), "b\n")?;
+    repo.stage(None::<&str>)?;
+    assert!(repo.open_index()?.fs_monitor().is_none(), "no hook is configured");
+
+    let hook = repo.git_dir().join("fsmonitor-hook");
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\necho \"$@\" >> .git/fsmonitor-args\nprintf 'next-token\\0a\\0'\n",
+    )?;
+    std::fs::set_permissions(&hook, std::os::unix::fs::PermissionsExt::from_mode(0o755))?;
+    let mut config = std::fs::OpenOptions::new()
+        .append(true)
+        .open(repo.git_dir().join("config"))?;
+    std::io::Write::write_all(
+        &mut config,
+        format!("[core]\n\tfsmonitor = {}\n\tfsmonitorHookVersion = 2\n", hook.display()).as_bytes(),
+    )?;
+    let repo = gix::open_opts(repo.git_dir(), crate::restricted())?;
+
+    let index = repo.open_index()?;
+    let fs_monitor = index.fs_monitor().expect("created after querying the hook");
+    assert_eq!(
+        fs_monitor.token(),
+        &gix::fs_monitor::Token::V2 {
+            token: "next-token".into()
+        }
+    );
+    assert!(
+        index
+            .entries()
+            .iter()
+            .all(|e| !e.flags.contains(Flags::FSMONITOR_VALID)),
+        "without a previous token, all entries have to be checked"
+    );
+
+    std::fs::write(workdir.join("c"), "c\n")?;
+    repo.stage(None::<&str>)?;
+    let index = repo.open_index()?;
+    assert_eq!(
+        index
+            .entries()
+            .iter()
+            .filter(|e| e.flags.contains(Flags::FSMONITOR_VALID))
+            .map(|e| e.path(&index))
+            .collect::<Vec<_>>(),
+        ["b", "c"],
+        "staged entries are valid unless the hook reports them as changed"
+    );
+    assert_eq!(
+        std::fs::read_to_string(repo.git_dir().join("fsmonitor-args"))?,
+        "2 builtin:fake\n2 builtin:fake\n2 next-token\n",
+        "the token stored in the index is passed to the hook"
+    );
+    Ok(())
+}
@@ -0,0 +1,535 @@
+use gix::{
+    bstr::ByteSlice,
+    merge::{
+        blob::builtin_driver::text::Labels,
+        tree::{Conflict, Side},
+    },
+    ObjectId, Repository,
+};
+
+#[test]
+fn changes_of_both_sides_are_merged() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let ancestor = write_tree(&repo, &[("a", "1\n2\n3\n4\n5\n"), ("b", "b\n"), ("dir/c", "c\n")])?;
+    let ours = write_tree(&repo, &[("a", "one\n2\n3\n4\n5\n"), ("dir/c", "c\n"), ("dir/d", "d\n")])?;
+    let theirs = write_tree(
+        &repo,
+        &[
+            ("a", "1\n2\n3\n4\nfive\n"),
+            ("b", "b\n"),
+            ("dir/c", "C\n"),
+            ("e", "e\n"),
+        ],
+    )?;
+
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[
+            ("a", "one\n2\n3\n4\nfive\n"),
+            ("dir/c", "C\n"),
+            ("dir/d", "d\n"),
+            ("e", "e\n")
+        ]),
+        "additions, deletions and modifications of both sides are combined"
+    );
+    Ok(())
+}
+
+#[test]
+fn conflicting_changes_are_marked_and_reported() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let ancestor = write_tree(&repo, &[("a", "a\n"), ("b", "b\n")])?;
+    let ours = write_tree(&repo, &[("a", "ours\n")])?;
+    let theirs = write_tree(&repo, &[("a", "theirs\n"), ("b", "B\n")])?;
+
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[
+            ("a", "<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\n"),
+            ("b", "B\n")
+        ]),
+        "the modified version is kept if the other side deleted the file"
+    );
+    assert_eq!(
+        outcome.conflicts,
+        [
+            Conflict::Content {
+                location: "a".into(),
+                ancestor: Some(blob_id(&repo, "a\n")),
+                ours: blob_id(&repo, "ours\n"),
+                theirs: blob_id(&repo, "theirs\n"),
+            },
+            Conflict::ModifyDelete {
+                location: "b".into(),
+                deleted_by: Side::Ours,
+            }
+        ]
+    );
+    Ok(())
+}
+
+const CONTENT: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+const MODIFIED_CONTENT: &str = "1\n2\n3\n4\n5\n6\n7\n8\n9\nten\n";
+
+#[test]
+fn changes_follow_renames() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let ancestor = write_tree(&repo, &[("a", CONTENT)])?;
+    let ours = write_tree(&repo, &[("dir/renamed", CONTENT)])?;
+    let theirs = write_tree(&repo, &[("a", MODIFIED_CONTENT)])?;
+
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+    assert!(!outcome.has_conflicts());
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[("dir/renamed", MODIFIED_CONTENT)])
+    );
+
+    let mut options = repo.tree_merge_options()?;
+    options.rewrites = None;
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), options)?;
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[("a", MODIFIED_CONTENT), ("dir/renamed", CONTENT)]),
+        "without rename tracking, the rename looks like a deletion and an addition"
+    );
+    assert_eq!(
+        outcome.conflicts,
+        [Conflict::ModifyDelete {
+            location: "a".into(),
+            deleted_by: Side::Ours
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn renames_conflicting_with_deletions_and_other_renames() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let other_content = "a\nb\nc\nd\ne\nf\ng\nh\n";
+    let ancestor = write_tree(&repo, &[("a", CONTENT), ("b", other_content)])?;
+    let ours = write_tree(&repo, &[("a-ours", CONTENT), ("b-ours", other_content)])?;
+    let theirs = write_tree(&repo, &[("a-theirs", MODIFIED_CONTENT)])?;
+
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[
+            ("a-ours", MODIFIED_CONTENT),
+            ("a-theirs", MODIFIED_CONTENT),
+            ("b-ours", other_content)
+        ]),
+        "the merged content is written to both locations of a rename/rename conflict"
+    );
+    assert_eq!(
+        outcome.conflicts,
+        [
+            Conflict::RenameRename {
+                ancestor_location: "a".into(),
+                our_location: "a-ours".into(),
+                their_location: "a-theirs".into(),
+            },
+            Conflict::RenameDelete {
+                ancestor_location: "b".into(),
+                location: "b-ours".into(),
+                deleted_by: Side::Theirs,
+            }
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn files_are_moved_out_of_the_way_of_directories() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let ancestor = write_tree(&repo, &[("a", "a\n")])?;
+    let ours = write_tree(&repo, &[("a", "a\n"), ("b", "file\n")])?;
+    let theirs = write_tree(&repo, &[("a", "a\n"), ("b/c", "c\n")])?;
+
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+    assert_eq!(
+        files(&repo, outcome.tree)?,
+        expected(&[("a", "a\n"), ("b/c", "c\n"), ("b~ours", "file\n")])
+    );
+    assert_eq!(
+        outcome.conflicts,
+        [Conflict::DirectoryFile {
+            location: "b".into(),
+            file_location: "b~ours".into(),
+            file_side: Side::Ours,
+        }]
+    );
+    Ok(())
+}
+
+#[test]
+fn conflicts_are_resolved_with_the_favored_side() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let other_content = "a\nb\nc\nd\ne\nf\ng\nh\n";
+    let ancestor = write_tree(
+        &repo,
+        &[
+            ("conflict", "1\n2\n3\n"),
+            ("modify-delete", "m\n"),
+            ("renamed", other_content),
+        ],
+    )?;
+    let ours = write_tree(&repo, &[("conflict", "1\nours\n3\n"), ("renamed-ours", other_content)])?;
+    let theirs = write_tree(
+        &repo,
+        &[
+            ("conflict", "1\ntheirs\n3\n"),
+            ("modify-delete", "M\n"),
+            ("renamed-theirs", other_content),
+        ],
+    )?;
+
+    for (favor, expected_files) in [
+        (
+            Side::Ours,
+            expected(&[("conflict", "1\nours\n3\n"), ("renamed-ours", other_content)]),
+        ),
+        (
+            Side::Theirs,
+            expected(&[
+                ("conflict", "1\ntheirs\n3\n"),
+                ("modify-delete", "M\n"),
+                ("renamed-theirs", other_content),
+            ]),
+        ),
+    ] {
+        let mut options = repo.tree_merge_options()?;
+        options.favor = Some(favor);
+        let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), options)?;
+        assert!(!outcome.has_conflicts(), "{favor:?}");
+        assert_eq!(files(&repo, outcome.tree)?, expected_files, "{favor:?}");
+        assert_eq!(
+            conflict_locations(&outcome.auto_resolved_conflicts),
+            ["conflict", "modify-delete", "renamed-ours"],
+            "all conflicts are still reported, but as resolved"
+        );
+    }
+
+    let mut options = repo.tree_merge_options()?;
+    options.favor = Some(Side::Theirs);
+    options.blob_merge.text.conflict = gix::merge::blob::builtin_driver::text::Conflict::ResolveWithUnion;
+    let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), options)?;
+    assert_eq!(
+        files(&repo, outcome.tree)?[0],
+        ("conflict".to_string(), "1\nours\ntheirs\n3\n".to_string()),
+        "lines are merged with the union of both sides if configured, while other conflicts favor a side"
+    );
+    assert!(!outcome.has_conflicts());
+    Ok(())
+}
+
+#[test]
+fn tree_merge_options_from_configuration() -> crate::Result {
+    let (mut repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let options = repo.tree_merge_options()?;
+    assert!(options.rewrites.is_some(), "renames are tracked by default");
+
+    let mut config = repo.config_snapshot_mut();
+    config.set_raw_value("diff", None, "renames", "false")?;
+    config.set_raw_value("merge", None, "renameLimit", "42")?;
+    config.set_raw_value("merge", None, "conflictStyle", "zdiff3")?;
+    drop(config);
+    let options = repo.tree_merge_options()?;
+    assert_eq!(options.rewrites, None, "merge.renames defaults to diff.renames");
+    assert_eq!(
+        options.blob_merge.text.conflict,
+        gix::merge::blob::builtin_driver::text::Conflict::Keep {
+            style: gix::merge::blob::builtin_driver::text::ConflictStyle::ZealousDiff3,
+            marker_size: 7.try_into().expect("non-zero"),
+        }
+    );
+
+    repo.config_snapshot_mut()
+        .set_raw_value("merge", None, "renames", "true")?;
+    let rewrites = repo
+        .tree_merge_options()?
+        .rewrites
+        .expect("merge.renames overrides diff.renames");
+    assert_eq!(rewrites.limit, 42);
+    assert_eq!(rewrites.copies, None, "copies are never tracked");
+    Ok(())
+}
+
+mod drivers {
+    use super::{conflict_locations, expected, files, labels, write_tree};
+
+    fn set_attributes(repo: &gix::Repository, attributes: &str) -> crate::Result {
+        let info = repo.git_dir().join("info");
+        std::fs::create_dir_all(&info)?;
+        std::fs::write(info.join("attributes"), attributes)?;
+        Ok(())
+    }
+
+    fn conflicting_trees(
+        repo: &gix::Repository,
+        locations: &[&str],
+    ) -> crate::Result<(gix::ObjectId, gix::ObjectId, gix::ObjectId)> {
+        let versions = |content: &'static str| -> Vec<(&str, &str)> {
+            locations.iter().map(|location| (*location, content)).collect()
+        };
+        Ok((
+            write_tree(repo, &versions("base\n"))?,
+            write_tree(repo, &versions("ours\n"))?,
+            write_tree(repo, &versions("theirs\n"))?,
+        ))
+    }
+
+    #[test]
+    fn builtin_drivers_are_selected_by_attribute() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+        set_attributes(
+            &repo,
+            "union merge=union\nours merge=ours\nunset -merge\nbinary merge=binary\ntext merge\nunknown merge=unknown\nsmall-markers conflict-marker-size=3\n",
+        )?;
+        let (ancestor, ours, theirs) = conflicting_trees(
+            &repo,
+            &["binary", "ours", "small-markers", "text", "union", "unknown", "unset"],
+        )?;
+
+        let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+        let markers = "<<<<<<< ours\nours\n=======\ntheirs\n>>>>>>> theirs\n";
+        assert_eq!(
+            files(&repo, outcome.tree)?,
+            expected(&[
+                ("binary", "ours\n"),
+                ("ours", "ours\n"),
+                ("small-markers", "<<< ours\nours\n===\ntheirs\n>>> theirs\n"),
+                ("text", markers),
+                ("union", "ours\ntheirs\n"),
+                ("unknown", markers),
+                ("unset", "ours\n"),
+            ]),
+            "unknown drivers fall back to the text driver"
+        );
+        assert_eq!(
+            conflict_locations(&outcome.conflicts),
+            ["binary", "small-markers", "text", "unknown", "unset"],
+            "union and ours resolve all conflicts"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn configured_drivers_run_their_command() -> crate::Result {
+        let (mut repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+        set_attributes(
+            &repo,
+            "append merge=append\nfail merge=fail\nbuiltin merge=union\ndir/file merge=append conflict-marker-size=9\n",
+        )?;
+        let mut config = repo.config_snapshot_mut();
+        config.set_raw_value(
+            "merge",
+            Some("append".into()),
+            "driver",
+            "cat %B >> %A && echo %L %P %S %X %Y >> %A",
+        )?;
+        config.set_raw_value("merge", Some("fail".into()), "driver", "echo failed > %A; false")?;
+        config.set_raw_value("merge", Some("union".into()), "driver", "cat %O > %A")?;
+        config.set_raw_value("merge", None, "default", "append")?;
+        drop(config);
+
+        let (ancestor, ours, theirs) = conflicting_trees(&repo, &["append", "builtin", "dir/file", "fail", "other"])?;
+        let outcome = repo.merge_trees(ancestor, ours, theirs, labels(), repo.tree_merge_options()?)?;
+        assert_eq!(
+            files(&repo, outcome.tree)?,
+            expected(&[
+                ("append", "ours\ntheirs\n7 append base ours theirs\n"),
+                ("builtin", "base\n"),
+                ("dir/file", "ours\ntheirs\n9 dir/file base ours theirs\n"),
+                ("fail", "failed\n"),
+                ("other", "ours\ntheirs\n7 other base ours theirs\n"),
+            ]),
+            "configured drivers take precedence over built-in ones, and merge.default applies without attribute"
+        );
+        assert_eq!(
+            conflict_locations(&outcome.conflicts),
+            ["fail"],
+            "a non-zero exit status signals a conflict"
+        );
+        Ok(())
+    }
+}
+
+mod commits {
+    use gix::merge::blob::builtin_driver::text::Labels;
+
+    use super::files;
+
+    fn options(repo: &gix::Repository) -> crate::Result<gix::merge::commit::Options> {
+        Ok(gix::merge::commit::Options {
+            allow_missing_merge_base: false,
+            tree_merge: repo.tree_merge_options()?,
+        })
+    }
+
+    #[test]
+    fn merged_trees_match_git() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_commits_repo.sh")?;
+        let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("merge-tree.baseline"))?;
+        for line in baseline.lines() {
+            let mut tokens = line.split(' ');
+            let (ours, theirs, expected) = (
+                tokens.next().expect("ours"),
+                tokens.next().expect("theirs"),
+                gix::ObjectId::from_hex(tokens.next().expect("tree").as_bytes())?,
+            );
+            let outcome = repo.merge_commits(
+                repo.rev_parse_single(ours)?,
+                repo.rev_parse_single(theirs)?,
+                Labels {
+                    ancestor: None,
+                    current: Some(ours.into()),
+                    other: Some(theirs.into()),
+                },
+                options(&repo)?,
+            )?;
+            assert_eq!(outcome.merge_bases.len(), 2, "{line}: all cases are criss-cross merges");
+            assert!(outcome.virtual_merge_base.is_some());
+            assert_eq!(outcome.tree_merge.tree, expected, "{line}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn conflicts_of_virtual_merge_base_are_kept() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_commits_repo.sh")?;
+        let labels = Labels {
+            ancestor: None,
+            current: Some("c".into()),
+            other: Some("d".into()),
+        };
+        let outcome = repo.merge_commits(
+            repo.rev_parse_single("c")?,
+            repo.rev_parse_single("d")?,
+            labels,
+            options(&repo)?,
+        )?;
+        assert_eq!(
+            files(&repo, outcome.virtual_merge_base.expect("criss-cross"))?,
+            [(
+                "file".to_string(),
+                "1\n2\n3\n4\n<<<<<<<<< Temporary merge branch 1\nc1\n=========\nd1\n>>>>>>>>> Temporary merge branch 2\n6\n7\n8\n9\n10\n"
+                    .to_string()
+            )],
+            "conflict markers in virtual merge-bases are larger"
+        );
+        assert_eq!(
+            outcome.tree_merge.conflicts.len(),
+            1,
+            "both sides resolved the conflict of the merge-bases differently"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn unrelated_histories_need_to_be_allowed() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_merge_commits_repo.sh")?;
+        let (ours, theirs) = (repo.rev_parse_single("main")?, repo.rev_parse_single("unrelated")?);
+        let err = repo
+            .merge_commits(ours, theirs, Default::default(), options(&repo)?)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            gix::repository::merge::merge_commits::Error::Merge(gix::merge::commit::merge::Error::NoMergeBase { .. })
+        ));
+
+        let outcome = repo.merge_commits(
+            ours,
+            theirs,
+            Default::default(),
+            gix::merge::commit::Options {
+                allow_missing_merge_base: true,
+                ..options(&repo)?
+            },
+        )?;
+        assert!(outcome.merge_bases.is_empty());
+        assert!(!outcome.tree_merge.has_conflicts());
+        assert_eq!(
+            files(&repo, outcome.tree_merge.tree)?
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect::<Vec<_>>(),
+            ["file", "unrelated"],
+            "the empty tree is used as ancestor"
+        );
+        Ok(())
+    }
+}
+
+fn conflict_locations(conflicts: &[Conflict]) -> Vec<String> {
+    conflicts
+        .iter()
+        .map(|conflict| conflict.location().to_string())
+        .collect()
+}
+
+fn labels() -> Labels<'static> {
+    Labels {
+        ancestor: Some("base".into()),
+        current: Some("ours".into()),
+        other: Some("theirs".into()),
+    }
+}
+
+fn blob_id(repo: &Repository, content: &str) -> ObjectId {
+    gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, content.as_bytes())
+}
+
+/// Write a tree with blobs at the given slash-separated paths, along with all of its subtrees.
+fn write_tree(repo: &Repository, files: &[(&str, &str)]) -> crate::Result<ObjectId> {
+    let mut tree = gix::objs::Tree::empty();
+    let mut subtrees = Vec::<(&str, Vec<(&str, &str)>)>::new();
+    for (path, content) in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => match subtrees.iter_mut().find(|(name, _)| *name == dir) {
+                Some((_, files)) => files.push((rest, content)),
+                None => subtrees.push((dir, vec![(rest, content)])),
+            },
+            None => tree.entries.push(gix::objs::tree::Entry {
+                mode: gix::objs::tree::EntryKind::Blob.into(),
+                filename: (*path).into(),
+                oid: repo.write_blob(content)?.detach(),
+            }),
+        }
+    }
+    for (name, files) in subtrees {
+        tree.entries.push(gix::objs::tree::Entry {
+            mode: gix::objs::tree::EntryKind::Tree.into(),
+            filename: name.into(),
+            oid: write_tree(repo, &files)?,
+        });
+    }
+    tree.entries.sort();
+    Ok(repo.write_object(&tree)?.detach())
+}
+
+/// Return all files in `tree` along with their content, sorted by path.
+fn files(repo: &Repository, tree: ObjectId) -> crate::Result<Vec<(String, String)>> {
+    let mut recorder = gix::traverse::tree::Recorder::default();
+    repo.find_object(tree)?
+        .into_tree()
+        .traverse()
+        .breadthfirst(&mut recorder)?;
+    let mut out = Vec::new();
+    for entry in recorder.records.into_iter().filter(|entry| !entry.mode.is_tree()) {
+        let data = repo.find_object(entry.oid)?.detach().data;
+        out.push((entry.filepath.to_string(), data.to_str()?.to_owned()));
+    }
+    out.sort();
+    Ok(out)
+}
+
+fn expected(files: &[(&str, &str)]) -> Vec<(String, String)> {
+    files
+        .iter()
+        .map(|(path, content)| (path.to_string(), content.to_string()))
+        .collect()
+}
@@ -1,8 +1,22 @@
 use gix::Repository;
 
+#[cfg(feature = "apply")]
+mod apply;
+#[cfg(feature = "revision")]
+mod bisect;
+#[cfg(feature = "blame")]
+mod blame;
+#[cfg(all(feature = "bundle", feature = "blocking-network-client"))]
+mod bundle;
 mod config;
+#[cfg(feature = "blob-diff")]
+mod diff;
 #[cfg(feature = "attributes")]
 mod filter;
+#[cfg(all(unix, feature = "dirwalk"))]
+mod fs_monitor;
+#[cfg(feature = "merge")]
+mod merge;
 mod object;
 mod open;
 #[cfg(feature = "attributes")]
@@ -10,6 +24,10 @@ mod pathspec;
 mod reference;
 mod remote;
 mod shallow;
+#[cfg(all(feature = "worktree-mutation", feature = "dirwalk"))]
+mod sparse;
+#[cfg(feature = "dirwalk")]
+mod stage;
 mod state;
 #[cfg(feature = "attributes")]
 mod submodule;
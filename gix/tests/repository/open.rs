@@ -289,6 +289,51 @@ mod object_caches {
     }
 }
 
+mod pack_windows {
+    use crate::util::named_subrepo_opts;
+
+    #[test]
+    fn unset_maps_packs_as_a_whole() -> crate::Result {
+        let repo = named_subrepo_opts("make_config_repos.sh", "object-caches", gix::open::Options::isolated())?;
+        assert!(repo.objects.store_ref().pack_window_cache().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn configured_window_size_and_limit() -> crate::Result {
+        let opts =
+            gix::open::Options::isolated().config_overrides(["core.packedGitWindowSize=16k", "core.packedGitLimit=1m"]);
+        let repo = named_subrepo_opts("make_config_repos.sh", "object-caches", opts)?;
+        let options = repo
+            .objects
+            .store_ref()
+            .pack_window_cache()
+            .expect("configured")
+            .options();
+        assert_eq!(options.window_size, 16 * 1024);
+        assert_eq!(options.max_mapped_bytes, 1024 * 1024);
+        Ok(())
+    }
+
+    #[test]
+    fn limit_alone_uses_default_window_size() -> crate::Result {
+        let opts = gix::open::Options::isolated().config_overrides(["core.packedGitLimit=1m"]);
+        let repo = named_subrepo_opts("make_config_repos.sh", "object-caches", opts)?;
+        let options = repo
+            .objects
+            .store_ref()
+            .pack_window_cache()
+            .expect("configured")
+            .options();
+        assert_eq!(
+            options.window_size,
+            gix_pack::data::window::Options::default().window_size
+        );
+        assert_eq!(options.max_mapped_bytes, 1024 * 1024);
+        Ok(())
+    }
+}
+
 mod worktree {
     use gix::open;
 
@@ -389,3 +434,99 @@ mod worktree {
         Ok(())
     }
 }
+
+mod reftable {
+    use gix::refs::{
+        transaction::{Change, LogChange, PreviousValue, RefEdit},
+        Target,
+    };
+
+    use crate::util::{basic_rw_repo, restricted};
+
+    fn update(name: &str, new: Target) -> RefEdit {
+        RefEdit {
+            change: Change::Update {
+                log: LogChange {
+                    message: "import".into(),
+                    ..Default::default()
+                },
+                expected: PreviousValue::Any,
+                new,
+            },
+            name: name.try_into().expect("valid"),
+            deref: false,
+        }
+    }
+
+    #[test]
+    fn references_and_reflogs_are_read_from_the_reftable_stack() -> crate::Result {
+        let (repo, _tmp) = basic_rw_repo()?;
+        let head_id = repo.head_id()?.detach();
+        let git_dir = repo.git_dir().to_owned();
+        drop(repo);
+
+        // Convert the repository like `git refs migrate --ref-format=reftable` would.
+        let stack_dir = git_dir.join("reftable");
+        std::fs::create_dir(&stack_dir)?;
+        std::fs::write(stack_dir.join("tables.list"), "")?;
+        let mut stack = gix::refs::reftable::Stack::at(&stack_dir)?;
+        let committer = gix::actor::Signature {
+            name: "committer".into(),
+            email: "committer@example.com".into(),
+            time: gix_date::Time::new(1234, 0),
+        };
+        stack.commit(
+            [
+                update("refs/heads/main", Target::Peeled(head_id)),
+                update("HEAD", Target::Symbolic("refs/heads/main".try_into()?)),
+            ],
+            Some(committer.to_ref()),
+            gix_lock::acquire::Fail::Immediately,
+            Default::default(),
+        )?;
+        std::fs::remove_dir_all(git_dir.join("refs"))?;
+        std::fs::remove_dir_all(git_dir.join("logs"))?;
+        std::fs::create_dir(git_dir.join("refs"))?;
+        std::fs::write(
+            git_dir.join("refs").join("heads"),
+            "this repository uses the reftable format\n",
+        )?;
+        std::fs::write(git_dir.join("HEAD"), "ref: refs/heads/.invalid\n")?;
+        let config = std::fs::read_to_string(git_dir.join("config"))?
+            .replace("repositoryformatversion = 0", "repositoryformatversion = 1");
+        std::fs::write(
+            git_dir.join("config"),
+            format!("{config}[extensions]\n\trefStorage = reftable\n"),
+        )?;
+
+        let repo = gix::open_opts(&git_dir, restricted())?;
+        assert_eq!(
+            repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/main",
+            "`HEAD` is read from the stack, not from the file"
+        );
+        assert_eq!(repo.head_id()?, head_id);
+        let names = repo
+            .references()?
+            .all()?
+            .map(|r| r.map(|r| r.name().as_bstr().to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(names, ["refs/heads/main"]);
+
+        let main = repo.find_reference("main")?;
+        let messages = main
+            .log_iter()
+            .all()?
+            .expect("reflog exists")
+            .map(|line| line.map(|line| line.message.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(messages, ["import"]);
+
+        assert!(
+            repo.reference("refs/heads/new", head_id, PreviousValue::Any, "create")
+                .is_err(),
+            "references can't be changed yet"
+        );
+        Ok(())
+    }
+}
@@ -223,4 +223,181 @@ mod head {
         assert!(head.referent_name().is_none());
         Ok(())
     }
+
+    #[test]
+    fn detach_and_attach() -> crate::Result {
+        let (repo, _keep) = crate::basic_rw_repo()?;
+        let commit = repo.head_commit()?;
+        let (id, tree_id) = (commit.id, commit.tree_id()?.detach());
+
+        assert!(
+            matches!(
+                repo.head_detach(tree_id),
+                Err(gix::reference::head_detach::Error::PeelToCommit(_))
+            ),
+            "only commits can be checked out"
+        );
+        let head = repo.head_detach(id)?;
+        assert!(head.is_detached());
+        assert_eq!(head.id().expect("detached").detach(), id);
+        assert!(repo.head()?.is_detached(), "the change is visible on disk");
+        assert_eq!(
+            last_head_log_message(&repo)?,
+            format!("checkout: moving from main to {id}")
+        );
+
+        repo.reference("refs/heads/other", id, PreviousValue::MustNotExist, "")?;
+        let head = repo.head_attach("refs/heads/other")?;
+        assert_eq!(head.referent_name().expect("attached").as_bstr(), "refs/heads/other");
+        assert_eq!(repo.head_name()?.expect("attached").as_bstr(), "refs/heads/other");
+        assert_eq!(
+            last_head_log_message(&repo)?,
+            format!("checkout: moving from {id} to other")
+        );
+
+        assert!(matches!(
+            repo.head_attach("refs/tags/other"),
+            Err(gix::reference::head_attach::Error::NotABranch { .. })
+        ));
+        assert!(matches!(
+            repo.head_attach("refs/heads/missing"),
+            Err(gix::reference::head_attach::Error::BranchNotFound { .. })
+        ));
+        assert_eq!(
+            repo.head_name()?.expect("attached").as_bstr(),
+            "refs/heads/other",
+            "HEAD is unchanged on error"
+        );
+        Ok(())
+    }
+
+    fn last_head_log_message(repo: &gix::Repository) -> crate::Result<String> {
+        Ok(repo
+            .find_reference("HEAD")?
+            .log_iter()
+            .rev()?
+            .expect("log present")
+            .next()
+            .expect("at least one line")?
+            .message
+            .to_string())
+    }
+}
+
+mod symbolic_reference {
+    use gix::refs::transaction::PreviousValue;
+
+    #[test]
+    fn create_update_and_follow() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_references_repo.sh")?;
+        let r = repo.symbolic_reference(
+            "refs/remotes/origin/HEAD",
+            "refs/remotes/origin/multi-link-target3",
+            PreviousValue::MustExist,
+            "update default branch",
+        )?;
+        assert_eq!(r.name().as_bstr(), "refs/remotes/origin/HEAD");
+        let chain = repo.find_reference("refs/remotes/origin/HEAD")?.follow_chain(5)?;
+        assert_eq!(
+            chain.hops.iter().map(|r| r.name.as_bstr()).collect::<Vec<_>>(),
+            ["refs/remotes/origin/HEAD", "refs/remotes/origin/multi-link-target3"],
+            "the update is visible on disk"
+        );
+
+        let r = repo.symbolic_reference(
+            "refs/heads/alias",
+            "refs/heads/unborn",
+            PreviousValue::MustNotExist,
+            "create a symbolic reference to an unborn branch",
+        )?;
+        let chain = r.follow_chain(5)?;
+        assert_eq!(chain.hops.len(), 1);
+        assert_eq!(
+            chain.missing.as_ref().map(gix::refs::FullName::as_bstr),
+            Some("refs/heads/unborn".into())
+        );
+
+        assert!(
+            repo.symbolic_reference(
+                "refs/heads/alias",
+                "refs/heads/main",
+                PreviousValue::MustNotExist,
+                "fails",
+            )
+            .is_err(),
+            "constraints are respected"
+        );
+        Ok(())
+    }
+}
+
+mod prepare_reference_edits {
+    use gix::refs::{
+        transaction::{Change, LogChange, PreviousValue, RefEdit, RefLog},
+        Target,
+    };
+
+    #[test]
+    fn rename_branch_and_move_head_atomically() -> crate::Result {
+        let (repo, _keep) = crate::repo_rw("make_basic_repo.sh")?;
+        let id = repo.find_reference("refs/heads/main")?.id().detach();
+        let edits = || -> crate::Result<Vec<RefEdit>> {
+            Ok(vec![
+                RefEdit {
+                    change: Change::Delete {
+                        expected: PreviousValue::MustExistAndMatch(Target::Peeled(id)),
+                        log: RefLog::AndReference,
+                    },
+                    name: "refs/heads/main".try_into()?,
+                    deref: false,
+                },
+                RefEdit {
+                    change: Change::Update {
+                        log: LogChange {
+                            message: "branch: renamed refs/heads/main to refs/heads/renamed".into(),
+                            ..Default::default()
+                        },
+                        expected: PreviousValue::MustNotExist,
+                        new: Target::Peeled(id),
+                    },
+                    name: "refs/heads/renamed".try_into()?,
+                    deref: false,
+                },
+                RefEdit {
+                    change: Change::Update {
+                        log: Default::default(),
+                        expected: PreviousValue::MustExistAndMatch(Target::Symbolic("refs/heads/main".try_into()?)),
+                        new: Target::Symbolic("refs/heads/renamed".try_into()?),
+                    },
+                    name: "HEAD".try_into()?,
+                    deref: false,
+                },
+            ])
+        };
+
+        let prepared = repo.prepare_reference_edits(edits()?)?;
+        assert_eq!(
+            prepared.edits().count(),
+            3,
+            "all edits are prepared and can be inspected"
+        );
+        assert_eq!(prepared.rollback().len(), 3);
+        assert!(
+            repo.try_find_reference("refs/heads/renamed")?.is_none(),
+            "nothing changes on rollback"
+        );
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/main");
+
+        let performed = repo.prepare_reference_edits(edits()?)?.commit()?;
+        assert_eq!(performed.len(), 3);
+        assert!(repo.try_find_reference("refs/heads/main")?.is_none());
+        assert_eq!(repo.find_reference("refs/heads/renamed")?.id(), id);
+        assert_eq!(repo.head_name()?.expect("not detached").as_bstr(), "refs/heads/renamed");
+
+        assert!(
+            repo.prepare_reference_edits(edits()?).is_err(),
+            "expected values are verified during preparation"
+        );
+        Ok(())
+    }
 }
@@ -1,5 +1,5 @@
 mod remote_at {
-    use gix::remote::Direction;
+    use gix::remote::{url::Replacement, Direction};
 
     use crate::remote;
 
@@ -56,6 +56,17 @@ mod remote_at {
             rewritten_fetch_url,
             "push is the same as fetch was rewritten"
         );
+        let fetch_replacement = Replacement {
+            direction: Direction::Fetch,
+            find: "https://github.com/foobar/".into(),
+            with: "https://github.com/byron/".into(),
+        };
+        assert_eq!(
+            remote.url_replacement(Direction::Fetch),
+            Some(&fetch_replacement),
+            "the longest match wins over `https://`"
+        );
+        assert_eq!(remote.url_replacement(Direction::Push), Some(&fetch_replacement));
 
         let remote = repo
             .remote_at("https://github.com/foobar/gitoxide".to_owned())?
@@ -66,6 +77,56 @@ mod remote_at {
             "ssh://dev/null",
             "push-url rewrite rules are applied"
         );
+        assert_eq!(
+            remote.url_replacement(Direction::Push),
+            Some(&Replacement {
+                direction: Direction::Push,
+                find: "file://".into(),
+                with: "ssh://".into(),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn push_url_rewrites_apply_to_the_fetch_url_if_there_is_no_push_url() -> crate::Result {
+        let repo = remote::repo("url-rewriting");
+        let remote = repo.remote_at("ssh://example.com/gitoxide")?;
+        assert_eq!(
+            remote.url(Direction::Fetch).unwrap().to_bstring(),
+            "ssh://example.com/gitoxide",
+            "fetch isn't rewritten as no insteadOf matches"
+        );
+        assert_eq!(remote.url_replacement(Direction::Fetch), None);
+        assert_eq!(
+            remote.url(Direction::Push).unwrap().to_bstring(),
+            "https://github.com/byron/gitoxide",
+            "pushInsteadOf applies to the fetch url as there is no push url"
+        );
+        assert_eq!(
+            remote.url_replacement(Direction::Push),
+            Some(&Replacement {
+                direction: Direction::Push,
+                find: "ssh://example.com/".into(),
+                with: "https://github.com/byron/".into(),
+            })
+        );
+
+        let repo = gix::open_opts(
+            remote::repo_path("url-rewriting"),
+            gix::open::Options::isolated()
+                .config_overrides(["url.ssh://mirror.example.com/g.pushInsteadOf=ssh://example.com/g"]),
+        )?;
+        let remote = repo.remote_at("ssh://example.com/gitoxide")?;
+        assert_eq!(
+            remote.url(Direction::Push).unwrap().to_bstring(),
+            "ssh://mirror.example.com/gitoxide",
+            "the longest match wins"
+        );
+        assert_eq!(
+            remote.url_replacement(Direction::Push).map(|r| r.find.clone()),
+            Some("ssh://example.com/g".into())
+        );
         Ok(())
     }
 
@@ -96,6 +157,7 @@ mod remote_at {
             "file://dev/null",
             "push-url rewrite rules are not applied"
         );
+        assert_eq!(remote.url_replacement(Direction::Push), None);
         Ok(())
     }
 }
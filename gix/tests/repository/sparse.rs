@@ -0,0 +1,145 @@
+use gix::sparse::Patterns;
+
+mod patterns {
+    use gix::sparse::Patterns;
+
+    #[test]
+    fn cone_from_directories() {
+        let patterns = Patterns::from_directories(["b/c/", "/d", "b/c/e"]);
+        assert!(patterns.is_cone());
+        assert_eq!(
+            patterns.to_bytes(),
+            b"/*\n!/*/\n/b/\n!/b/*/\n/b/c/\n/d/\n",
+            "nested directories are subsumed, and parents are only included non-recursively"
+        );
+        for (path, expected) in [
+            ("root-file", true),
+            ("a/file", false),
+            ("b/file", true),
+            ("b/other/file", false),
+            ("b/c/file", true),
+            ("b/c/e/deep/file", true),
+            ("d/file", true),
+            ("dd/file", false),
+        ] {
+            assert_eq!(patterns.is_included(path.into()), expected, "{path}");
+        }
+
+        let reparsed = Patterns::from_bytes(&patterns.to_bytes(), true);
+        assert!(reparsed.is_cone());
+        assert_eq!(reparsed.to_bytes(), patterns.to_bytes(), "round-trips");
+    }
+
+    #[test]
+    fn cone_add() {
+        let mut patterns = Patterns::from_bytes(b"/*\n!/*/\n/a/\n!/a/*/\n/a/b/\n", true);
+        assert!(patterns.is_cone());
+        patterns.add(["a", "c"]);
+        assert_eq!(patterns.to_bytes(), b"/*\n!/*/\n/a/\n/c/\n");
+        assert!(patterns.is_included("a/x/y".into()));
+    }
+
+    #[test]
+    fn non_conforming_patterns_fall_back_to_non_cone_mode() {
+        let patterns = Patterns::from_bytes(b"/*\n!/*/\n*.txt\n", true);
+        assert!(!patterns.is_cone());
+        assert!(
+            !Patterns::from_bytes(b"/a/\n", true).is_cone(),
+            "the root pattern is required"
+        );
+        assert!(
+            Patterns::from_bytes(b"/*\n", true).is_included("a/b/c".into()),
+            "without exclusion, everything is included"
+        );
+    }
+
+    #[test]
+    fn non_cone() {
+        let mut patterns = Patterns::from_bytes(b"# comment\n*.txt\n!unwanted.txt\ndir/\n", false);
+        assert!(!patterns.is_cone());
+        for (path, expected) in [
+            ("a.txt", true),
+            ("sub/b.txt", true),
+            ("unwanted.txt", false),
+            ("sub/unwanted.txt", false),
+            ("file", false),
+            ("dir/file", true),
+            ("sub/dir/file", true),
+            ("other/file", false),
+        ] {
+            assert_eq!(patterns.is_included(path.into()), expected, "{path}");
+        }
+        patterns.add(["/other"]);
+        assert!(patterns.is_included("other/file".into()));
+        assert_eq!(patterns.to_bytes(), b"*.txt\n!unwanted.txt\ndir/\n/other\n");
+    }
+}
+
+#[test]
+fn set_removes_excluded_files_and_checks_out_included_ones() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    for (path, content) in [
+        ("root", "root\n"),
+        ("a/x", "x\n"),
+        ("a/modified", "original\n"),
+        ("b/z", "z\n"),
+        ("b/c/y", "y\n"),
+    ] {
+        let path = workdir.join(path);
+        std::fs::create_dir_all(path.parent().expect("has parent"))?;
+        std::fs::write(path, content)?;
+    }
+    repo.stage(None::<&str>)?;
+    std::fs::write(workdir.join("a/modified"), "changed\n")?;
+
+    let outcome = repo.sparse_checkout_set(&Patterns::from_directories(["b/c"]), &Default::default())?;
+    assert_eq!(outcome.entries_included, 0);
+    assert_eq!(outcome.entries_excluded, 1, "a/x");
+    assert_eq!(outcome.files_removed, 1);
+    assert_eq!(
+        outcome.modified,
+        ["a/modified"],
+        "modified files are kept, along with their entry"
+    );
+    assert!(!workdir.join("a/x").exists());
+    assert!(
+        workdir.join("b/z").is_file(),
+        "files in parent directories of included directories are included"
+    );
+    assert!(workdir.join("b/c/y").is_file());
+    assert!(workdir.join("root").is_file());
+    assert_eq!(
+        skipped_paths(&repo)?,
+        ["a/x"],
+        "the index was written with the skip-worktree bits"
+    );
+    assert_eq!(
+        std::fs::read(repo.git_dir().join("info/sparse-checkout"))?,
+        b"/*\n!/*/\n/b/\n!/b/*/\n/b/c/\n",
+        "the patterns are written as well"
+    );
+
+    let outcome = repo.sparse_checkout_set(&Patterns::from_directories(["a"]), &Default::default())?;
+    assert_eq!(outcome.entries_included, 1, "a/x");
+    assert_eq!(outcome.entries_excluded, 2, "b/z and b/c/y");
+    assert_eq!(outcome.checkout.bytes_written, 2, "only a/x was written");
+    assert_eq!(
+        std::fs::read(workdir.join("a/x"))?,
+        b"x\n",
+        "files are checked out again"
+    );
+    assert!(!workdir.join("b").exists(), "empty directories are removed");
+    assert_eq!(skipped_paths(&repo)?, ["b/c/y", "b/z"]);
+    Ok(())
+}
+
+fn skipped_paths(repo: &gix::Repository) -> crate::Result<Vec<String>> {
+    let index = repo.open_index()?;
+    Ok(index
+        .entries()
+        .iter()
+        .filter(|e| e.flags.contains(gix::index::entry::Flags::SKIP_WORKTREE))
+        .map(|e| e.path(&index).to_string())
+        .collect())
+}
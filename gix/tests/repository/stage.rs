@@ -0,0 +1,55 @@
+use gix::stage::Change::*;
+
+#[test]
+fn add_modify_and_remove_paths() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_empty_repo.sh")?;
+    let workdir = repo.work_dir().expect("non-bare").to_owned();
+    std::fs::write(workdir.join("a"), "a\n")?;
+    std::fs::create_dir(workdir.join("dir"))?;
+    std::fs::write(workdir.join("dir").join("b"), "b\n")?;
+    std::fs::write(workdir.join(".gitignore"), "ignored\n")?;
+    std::fs::write(workdir.join("ignored"), "")?;
+
+    let outcome = repo.stage(None::<&str>)?;
+    assert_eq!(
+        outcome.changes,
+        [
+            (".gitignore".into(), Added),
+            ("a".into(), Added),
+            ("dir/b".into(), Added)
+        ],
+        "all untracked files are added, but ignored ones"
+    );
+    let index = repo.index()?;
+    assert_eq!(
+        index.entries().iter().map(|e| e.path(&index)).collect::<Vec<_>>(),
+        [".gitignore", "a", "dir/b"]
+    );
+    let id = index.entry_by_path("a".into()).expect("present").id;
+    assert_eq!(
+        id,
+        gix::objs::compute_hash(repo.object_hash(), gix::object::Kind::Blob, b"a\n")
+    );
+    assert!(repo.has_object(id), "blobs are written to the object database");
+
+    std::fs::write(workdir.join("a"), "changed\n")?;
+    std::fs::remove_file(workdir.join("dir").join("b"))?;
+    let outcome = repo.stage(Some("dir"))?;
+    assert_eq!(
+        outcome.changes,
+        [("dir/b".into(), Removed)],
+        "only paths matching the pathspec are staged, and deleted files are removed"
+    );
+
+    let outcome = repo.stage(None::<&str>)?;
+    assert_eq!(outcome.changes, [("a".into(), Modified)]);
+    let index = repo.index()?;
+    assert_eq!(
+        index.entries().iter().map(|e| e.path(&index)).collect::<Vec<_>>(),
+        [".gitignore", "a"]
+    );
+
+    let outcome = repo.stage(None::<&str>)?;
+    assert!(outcome.changes.is_empty(), "nothing changed");
+    Ok(())
+}
@@ -300,6 +300,11 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         assert_eq!(proxy_is_locked, proxy_lock_reason.is_some());
         // TODO: check id of expected worktree, but need access to .gitdir from worktree base
         let proxy_id = actual.id().to_owned();
+        assert_eq!(
+            actual.prunable().is_some(),
+            expected.prunable.is_some(),
+            "we agree with git about which worktrees are prunable"
+        );
         assert_eq!(
             base.is_dir(),
             expected.prunable.is_none(),
@@ -336,3 +341,133 @@ fn run_assertions(main_repo: gix::Repository, should_be_bare: bool) {
         );
     }
 }
+
+#[cfg(all(feature = "worktree-mutation", feature = "status"))]
+mod management {
+    use gix::worktree::{
+        add::{Head, Options},
+        proxy::Prunable,
+    };
+
+    #[test]
+    fn add_lock_prune_and_remove() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+        let worktrees = gix_testtools::tempfile::tempdir()?;
+        let head_id = repo.head_id()?.detach();
+
+        let wt = repo.worktree_add(
+            worktrees.path().join("feature"),
+            Options::new(Head::NewBranch {
+                name: "refs/heads/feature".try_into()?,
+                target: head_id,
+            }),
+        )?;
+        assert_eq!(wt.head_name()?.expect("not detached").as_bstr(), "refs/heads/feature");
+        assert_eq!(wt.head_id()?, head_id);
+        assert_eq!(std::fs::read(worktrees.path().join("feature/this"))?, b"hello\n");
+        assert!(!wt.is_dirty()?, "the index was written as well");
+        assert_eq!(
+            wt.worktree().expect("linked").id(),
+            Some("feature".into()),
+            "the id is derived from the path"
+        );
+
+        assert!(
+            matches!(
+                repo.worktree_add(
+                    worktrees.path().join("other"),
+                    Options::new(Head::Branch("refs/heads/main".try_into()?))
+                ),
+                Err(gix::worktree::add::Error::BranchCheckedOut { .. })
+            ),
+            "branches can only be checked out once"
+        );
+
+        let mut options = Options::new(Head::Detached(head_id));
+        options.lock = Some("on a usb drive".into());
+        options.checkout = false;
+        let detached = repo.worktree_add(worktrees.path().join("nested").join("feature"), options)?;
+        assert!(detached.head()?.is_detached());
+        assert!(
+            !worktrees.path().join("nested/feature/this").exists(),
+            "nothing was checked out"
+        );
+
+        let proxies = repo.worktrees()?;
+        assert_eq!(
+            proxies.iter().map(|p| p.id().to_string()).collect::<Vec<_>>(),
+            ["feature", "feature1"],
+            "ids are made unique"
+        );
+        let locked = &proxies[1];
+        assert_eq!(locked.lock_reason(), Some("on a usb drive".into()));
+        assert!(!locked.lock("ignored".into())?, "already locked");
+        assert!(locked.unlock()?);
+        assert!(!locked.unlock()?, "already unlocked");
+        assert!(locked.lock("".into())?);
+
+        std::fs::remove_dir_all(worktrees.path().join("nested"))?;
+        std::fs::remove_dir_all(worktrees.path().join("feature"))?;
+        assert_eq!(proxies[0].prunable(), Some(Prunable::GitDirFileLocationMissing));
+        assert_eq!(proxies[1].prunable(), None, "locked worktrees are never prunable");
+        assert_eq!(
+            repo.prune_worktrees()?,
+            [("feature".into(), Prunable::GitDirFileLocationMissing)]
+        );
+        assert_eq!(repo.worktrees()?.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn remove_refuses_locked_and_dirty_worktrees_unless_forced() -> crate::Result {
+        let (repo, _tmp) = crate::repo_rw("make_basic_repo.sh")?;
+        let worktrees = gix_testtools::tempfile::tempdir()?;
+        let base = worktrees.path().join("wt");
+        repo.worktree_add(&base, Options::new(Head::Detached(repo.head_id()?.detach())))?;
+
+        let proxy = repo.worktrees()?.pop().expect("one worktree");
+        proxy.lock("".into())?;
+        assert!(matches!(
+            proxy.clone().remove(false),
+            Err(gix::worktree::proxy::remove::Error::Locked { .. })
+        ));
+        proxy.unlock()?;
+
+        std::fs::write(base.join("untracked"), b"")?;
+        assert!(matches!(
+            proxy.clone().remove(false),
+            Err(gix::worktree::proxy::remove::Error::Dirty { .. })
+        ));
+        std::fs::remove_file(base.join("untracked"))?;
+
+        let worktree = proxy.clone().into_repo()?;
+        std::fs::write(base.join("staged"), b"")?;
+        worktree.stage(Some("staged"))?;
+        assert!(
+            worktree
+                .status(gix::progress::Discard)?
+                .into_index_worktree_iter(Vec::new())?
+                .next()
+                .is_none(),
+            "the index matches the worktree"
+        );
+        assert!(
+            matches!(
+                proxy.clone().remove(false),
+                Err(gix::worktree::proxy::remove::Error::Dirty { .. })
+            ),
+            "changes between HEAD and the index are detected as well"
+        );
+        std::fs::remove_file(base.join("staged"))?;
+        worktree.stage(Some("staged"))?;
+
+        proxy.remove(false)?;
+        assert!(!base.exists());
+        assert!(repo.worktrees()?.is_empty());
+        assert!(
+            !repo.git_dir().join("worktrees/wt").exists(),
+            "administrative files are removed as well"
+        );
+        Ok(())
+    }
+}
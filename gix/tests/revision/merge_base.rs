@@ -0,0 +1,84 @@
+fn ids<'repo>(repo: &'repo gix::Repository, specs: &[&str]) -> crate::Result<Vec<gix::Id<'repo>>> {
+    specs.iter().map(|spec| Ok(repo.rev_parse_single(*spec)?)).collect()
+}
+
+#[test]
+fn best_and_all() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+    let [c3, branch, root]: [_; 3] = ids(&repo, &["main~2", "branch", "main~4"])?.try_into().expect("three");
+    assert_eq!(repo.merge_base(c3, branch)?, root);
+    assert_eq!(repo.merge_bases_many(c3, Some(branch))?, [root]);
+    assert_eq!(
+        repo.merge_bases_many(branch, Some(root))?,
+        [root],
+        "ancestors are their own merge-base"
+    );
+    Ok(())
+}
+
+#[test]
+fn octopus_and_independent() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+    let [head, branch, c3, root]: [_; 4] = ids(&repo, &["main", "branch", "main~2", "main~4"])?
+        .try_into()
+        .expect("four");
+    assert_eq!(repo.merge_bases_octopus([head, branch, c3])?, [root]);
+    assert_eq!(repo.merge_bases_octopus([head, branch])?, [branch]);
+    assert_eq!(repo.independent_commits([branch, head, c3, head])?, [head]);
+    assert_eq!(repo.independent_commits([branch, c3])?, [branch, c3]);
+    Ok(())
+}
+
+#[test]
+fn ahead_behind() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+    let [head, branch, c3]: [_; 3] = ids(&repo, &["main", "branch", "main~2"])?.try_into().expect("three");
+    assert_eq!(repo.ahead_behind(head, branch)?, (4, 0));
+    assert_eq!(repo.ahead_behind(branch, head)?, (0, 4));
+    assert_eq!(repo.ahead_behind(branch, c3)?, (2, 2));
+    assert_eq!(repo.ahead_behind(head, head)?, (0, 0));
+    Ok(())
+}
+
+#[test]
+fn is_ancestor() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+    let [head, branch, c3, root]: [_; 4] = ids(&repo, &["main", "branch", "main~2", "main~4"])?
+        .try_into()
+        .expect("four");
+    assert!(repo.is_ancestor(root, head)?);
+    assert!(repo.is_ancestor(branch, head)?);
+    assert!(!repo.is_ancestor(head, branch)?);
+    assert!(!repo.is_ancestor(c3, branch)?);
+    assert!(repo.is_ancestor(head, head)?, "commits are their own ancestors");
+    Ok(())
+}
+
+#[test]
+fn unrelated_histories_have_no_merge_base() -> crate::Result {
+    let (repo, _tmp) = crate::repo_rw("make_rev_walk_order_repo.sh")?;
+    let head = repo.head_id()?;
+    let signature = gix::actor::Signature {
+        name: "a".into(),
+        email: "a@example.com".into(),
+        time: gix::date::Time::new(1, 0),
+    };
+    let orphan = repo.commit_as(
+        &signature,
+        &signature,
+        "refs/heads/orphan",
+        "orphan",
+        repo.empty_tree().id,
+        gix::commit::NO_PARENT_IDS,
+    )?;
+    assert!(matches!(
+        repo.merge_base(head, orphan),
+        Err(gix::repository::merge_base::Error::NotFound { .. })
+    ));
+    assert!(repo.merge_bases_many(head, Some(orphan))?.is_empty());
+    assert!(repo.merge_bases_octopus([head, orphan])?.is_empty());
+    assert_eq!(repo.independent_commits([head.detach(), orphan.detach()])?.len(), 2);
+    assert_eq!(repo.ahead_behind(head, orphan)?, (7, 1), "all commits are counted");
+    assert!(!repo.is_ancestor(orphan, head)?);
+    Ok(())
+}
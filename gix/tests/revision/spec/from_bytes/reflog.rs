@@ -1,11 +1,8 @@
-use gix::{
-    prelude::ObjectIdExt,
-    revision::{spec::parse::Error, Spec},
-};
+use gix::{prelude::ObjectIdExt, revision::Spec};
 
 use crate::{
-    revision::spec::from_bytes::{parse_spec, parse_spec_no_baseline, repo},
-    util::hex_to_id,
+    revision::spec::from_bytes::{parse_spec, repo},
+    util::{hex_to_id, named_repo},
 };
 
 #[test]
@@ -78,10 +75,14 @@ fn by_index() {
 }
 
 #[test]
-fn by_date_is_planned_until_git_date_crate_is_implements_parsing() {
-    let repo = repo("complex_graph").unwrap();
-    assert!(matches!(
-        parse_spec_no_baseline("main@{1979-02-26 18:30:00}", &repo).unwrap_err(),
-        Error::Planned { .. }
-    ));
+fn by_date() -> crate::Result {
+    let repo = named_repo("make_reflog_date_repo.sh")?;
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join("baseline.git"))?;
+    let mut lines = baseline.lines();
+    while let Some(spec) = lines.next() {
+        let expected = lines.next().expect("spec is followed by its result");
+        let actual = repo.rev_parse_single(spec)?;
+        assert_eq!(actual.to_string(), expected, "{spec}");
+    }
+    Ok(())
 }
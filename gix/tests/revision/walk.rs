@@ -0,0 +1,272 @@
+use gix::{prelude::ObjectIdExt, revision::walk::Platform};
+use gix_traverse::commit::simple::Sorting;
+
+/// The commit time of the fixture commit made on `day` in January 2000.
+fn day(day: i64) -> gix_date::SecondsSinceUnixEpoch {
+    946_684_800 + (day - 1) * 24 * 60 * 60
+}
+
+fn assert_matches_baseline(
+    repo: &gix::Repository,
+    name: &str,
+    configure: impl FnOnce(Platform<'_>) -> Platform<'_>,
+) -> crate::Result {
+    let head = repo.head_id()?;
+    let walk = configure(repo.rev_walk(Some(head)).sorting(Sorting::ByCommitTimeNewestFirst));
+    let mut actual = Vec::new();
+    for info in walk.all()? {
+        let commit = info?.id.attach(repo).object()?.into_commit();
+        actual.push(commit.message()?.summary().to_string());
+    }
+    let baseline = std::fs::read_to_string(repo.work_dir().expect("non-bare").join(format!("{name}.baseline")))?;
+    let expected: Vec<_> = baseline.lines().collect();
+    assert_eq!(actual, expected, "{name}");
+    Ok(())
+}
+
+#[test]
+fn commit_time() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_filter_repo.sh")?;
+    assert_matches_baseline(&repo, "since", |walk| walk.since(day(3)))?;
+    assert_matches_baseline(&repo, "until", |walk| walk.until(day(4)))?;
+    assert_matches_baseline(&repo, "since-until", |walk| walk.since(day(2)).until(day(5)))?;
+    Ok(())
+}
+
+#[test]
+fn author_and_committer() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_filter_repo.sh")?;
+    assert_matches_baseline(&repo, "author", |walk| walk.author("Alice"))?;
+    assert_matches_baseline(&repo, "authors", |walk| walk.author("Bob").author("Alice@"))?;
+    assert_matches_baseline(&repo, "committer", |walk| walk.committer("Carol"))?;
+    assert_matches_baseline(&repo, "author-committer", |walk| walk.author("Alice").committer("Bob"))?;
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "revparse-regex")]
+fn author_regex() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_filter_repo.sh")?;
+    assert_matches_baseline(&repo, "author-regex", |walk| walk.author("^B.*example"))?;
+    assert!(
+        repo.rev_walk(Some(repo.head_id()?)).author("(").all().is_err(),
+        "invalid patterns are reported"
+    );
+    Ok(())
+}
+
+#[test]
+fn paths() -> crate::Result {
+    let repo = crate::named_repo("make_rev_walk_filter_repo.sh")?;
+    // The commit-graph has changed-path Bloom filters, which are used if the commit-graph is used.
+    for use_commit_graph in [true, false] {
+        assert_matches_baseline(&repo, "path-a", |walk| {
+            walk.use_commit_graph(use_commit_graph).paths(["a"])
+        })?;
+        assert_matches_baseline(&repo, "path-b", |walk| {
+            walk.use_commit_graph(use_commit_graph).paths(["b/"])
+        })?;
+        assert_matches_baseline(&repo, "path-file-c", |walk| {
+            walk.use_commit_graph(use_commit_graph).paths(["a/file", "c"])
+        })?;
+        assert_matches_baseline(&repo, "path-missing", |walk| {
+            walk.use_commit_graph(use_commit_graph).paths(["missing"])
+        })?;
+        assert_matches_baseline(&repo, "first-parent-b", |walk| {
+            walk.use_commit_graph(use_commit_graph).first_parent_only().paths(["b"])
+        })?;
+    }
+    Ok(())
+}
+
+mod topo_sorting {
+    use gix_traverse::commit::topo::Sorting;
+
+    use super::assert_matches_baseline;
+
+    #[test]
+    fn orders() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        for (sorting, name) in [
+            (Sorting::TopoOrder, "topo-order"),
+            (Sorting::DateOrder, "date-order"),
+            (Sorting::AuthorDateOrder, "author-date-order"),
+        ] {
+            assert_matches_baseline(&repo, name, |walk| walk.topo_sorting(sorting))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn with_first_parent_and_filters() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        assert_matches_baseline(&repo, "first-parent-topo-order", |walk| {
+            walk.topo_sorting(Sorting::TopoOrder).first_parent_only()
+        })?;
+        assert_matches_baseline(&repo, "topo-order-since", |walk| {
+            walk.topo_sorting(Sorting::TopoOrder).since(1_600_001_200)
+        })?;
+        Ok(())
+    }
+}
+
+mod spec {
+    use gix_traverse::commit::topo::Sorting;
+
+    #[test]
+    fn ranges_and_exclusions_match_git() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        for (spec, name) in [
+            ("branch..main", "range"),
+            ("main~2...branch", "symmetric-difference"),
+            ("main~1^@", "only-parents"),
+            ("main~1^!", "exclude-parents"),
+            ("^main", "exclude"),
+        ] {
+            let mut actual = Vec::new();
+            for info in repo
+                .rev_parse(spec)?
+                .rev_walk()?
+                .topo_sorting(Sorting::DateOrder)
+                .all()?
+            {
+                actual.push(info?.object()?.message()?.summary().to_string());
+            }
+            let baseline =
+                std::fs::read_to_string(repo.work_dir().expect("non-bare").join(format!("{name}.baseline")))?;
+            let expected: Vec<_> = baseline.lines().collect();
+            assert_eq!(actual, expected, "{spec}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_tips_exclude_their_ancestry() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        let head = repo.head_id()?;
+        let hidden = repo.rev_parse_single("main~1")?;
+        let ids = repo
+            .rev_walk(Some(head))
+            .hide(Some(hidden))
+            .all()?
+            .map(|info| info.map(|info| info.id))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(ids, [head.detach()], "only the commit after the merge remains");
+
+        let ids = repo.rev_walk(Some(head)).hide(Some(head)).all()?.count();
+        assert_eq!(ids, 0, "hiding a tip hides everything");
+        Ok(())
+    }
+
+    #[test]
+    fn boundary_commits_are_returned_last() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        let head = repo.head_id()?;
+        let hidden = repo.rev_parse_single("main~1")?;
+        for topo_sorting in [None, Some(Sorting::TopoOrder)] {
+            let commits = repo
+                .rev_walk(Some(head))
+                .hide(Some(hidden))
+                .boundary(true)
+                .topo_sorting(topo_sorting)
+                .since(i64::MAX)
+                .all()?
+                .map(|info| info.map(|info| (info.id, info.is_boundary)))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(
+                commits,
+                [(hidden.detach(), true)],
+                "boundary commits aren't filtered, and are also returned with {topo_sorting:?}"
+            );
+
+            let commits = repo
+                .rev_walk(Some(head))
+                .hide(Some(hidden))
+                .boundary(true)
+                .topo_sorting(topo_sorting)
+                .all()?
+                .map(|info| info.map(|info| (info.id, info.is_boundary)))
+                .collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(commits, [(head.detach(), false), (hidden.detach(), true)]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn ancestry_path_matches_git() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_ancestry_path_repo.sh")?;
+        for (hidden, boundary, name) in [
+            (&["b"][..], false, "ancestry-path-from-b"),
+            (&["c"], false, "ancestry-path-from-c"),
+            (&["c", "y1"], false, "ancestry-path-from-c-and-y1"),
+            (&["c"], true, "ancestry-path-from-c-with-boundary"),
+            (&["x1"], false, "ancestry-path-from-x1"),
+        ] {
+            let hidden = hidden
+                .iter()
+                .map(|spec| repo.rev_parse_single(*spec).map(gix::Id::detach))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut actual = Vec::new();
+            for info in repo
+                .rev_walk(Some(repo.rev_parse_single("e")?))
+                .hide(hidden)
+                .ancestry_path(true)
+                .boundary(boundary)
+                .topo_sorting(Sorting::DateOrder)
+                .all()?
+            {
+                let info = info?;
+                let mark = if info.is_boundary { '-' } else { '>' };
+                actual.push(format!("{mark}{}", info.object()?.message()?.summary()));
+            }
+            let baseline =
+                std::fs::read_to_string(repo.work_dir().expect("non-bare").join(format!("{name}.baseline")))?;
+            let expected: Vec<_> = baseline.lines().collect();
+            assert_eq!(actual, expected, "{name}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn objects_include_trees_and_blobs_unless_filtered() -> crate::Result {
+        let repo = crate::named_repo("make_rev_walk_order_repo.sh")?;
+        let head = repo.head_id()?;
+        let empty_tree = gix::ObjectId::empty_tree(repo.object_hash());
+        let kinds = |walk: gix::revision::walk::Platform<'_>,
+                     filter: Option<gix_traverse::objects::Filter>|
+         -> crate::Result<Vec<gix_object::Kind>> {
+            Ok(walk
+                .objects(filter)?
+                .map(|item| item.map(|item| item.kind))
+                .collect::<Result<_, _>>()?)
+        };
+
+        let all = kinds(repo.rev_walk(Some(head)), None)?;
+        assert_eq!(all.len(), 8, "all commits share the same empty tree");
+        assert_eq!(all.last(), Some(&gix_object::Kind::Tree));
+        let all_ids = repo
+            .rev_walk(Some(head))
+            .objects(None)?
+            .map(|item| item.map(|item| item.id))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(all_ids.last(), Some(&empty_tree));
+
+        assert_eq!(
+            kinds(
+                repo.rev_walk(Some(head)),
+                Some(gix_traverse::objects::Filter::TreeDepth(0))
+            )?,
+            [gix_object::Kind::Commit; 7],
+            "trees can be filtered"
+        );
+        assert_eq!(
+            kinds(
+                repo.rev_walk(Some(head)).hide(Some(repo.rev_parse_single("main~1")?)),
+                None
+            )?,
+            [gix_object::Kind::Commit],
+            "the tree of boundary commits is known to the other side, and the boundary isn't returned"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,646 @@
+#[cfg(unix)]
+mod receive_pack {
+    use std::{
+        os::unix::{fs::PermissionsExt, net::UnixStream},
+        path::Path,
+        sync::atomic::AtomicBool,
+    };
+
+    use gix::{
+        progress::Discard,
+        protocol::transport::{client::git, Protocol},
+        remote::Direction::Push,
+        server::{receive_pack, ReceivePack},
+    };
+
+    use crate::remote::fetch::blocking_and_async_io::repo_rw;
+
+    type ServerResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+    /// Push `specs` from `repo` to the bare repository at `remote_dir`, which is served by [`ReceivePack`] on another thread,
+    /// and return the outcome on both sides.
+    fn push(
+        repo: &gix::Repository,
+        remote_dir: &Path,
+        specs: &[&str],
+        configure: impl for<'a, 'b> FnOnce(
+            gix::remote::push::Prepare<'a, 'b, git::Connection<UnixStream, UnixStream>>,
+        )
+            -> gix::remote::push::Prepare<'a, 'b, git::Connection<UnixStream, UnixStream>>,
+    ) -> crate::Result<(gix::remote::push::Outcome, receive_pack::Outcome)> {
+        let (client, server) = UnixStream::pair()?;
+        let server =
+            std::thread::spawn({
+                let remote_dir = remote_dir.to_owned();
+                move || -> ServerResult<receive_pack::Outcome> {
+                    let remote_repo = gix::open_opts(remote_dir, crate::restricted())?;
+                    let mut input = server.try_clone()?;
+                    let mut out = server;
+                    Ok(ReceivePack::new(&remote_repo)?.serve(
+                        &mut input,
+                        &mut out,
+                        &mut Discard,
+                        &AtomicBool::default(),
+                    )?)
+                }
+            });
+        let transport = git::Connection::new(
+            client.try_clone()?,
+            client,
+            Protocol::V1,
+            remote_dir.to_str().expect("valid UTF-8"),
+            None::<(String, Option<u16>)>,
+            git::ConnectMode::Process,
+            false,
+        );
+        let remote = repo.find_remote("origin")?.with_refspecs(specs.iter().copied(), Push)?;
+        let prepare = remote.to_connection_with_transport(transport).prepare_push(Discard)?;
+        let client_outcome = configure(prepare).send(Discard, &AtomicBool::default())?;
+        let server_outcome = server.join().expect("no panic").map_err(|err| err.to_string())?;
+        Ok((client_outcome, server_outcome))
+    }
+
+    fn install_hook(remote_dir: &Path, name: &str, script: &str) -> crate::Result {
+        let hook = remote_dir.join("hooks").join(name);
+        std::fs::create_dir_all(hook.parent().expect("in hooks dir"))?;
+        std::fs::write(&hook, format!("#!/bin/sh\n{script}"))?;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    fn configure(remote_dir: &Path, config: &str) -> crate::Result {
+        let mut content = std::fs::read_to_string(remote_dir.join("config"))?;
+        content.push_str(config);
+        std::fs::write(remote_dir.join("config"), content)?;
+        Ok(())
+    }
+
+    fn errors(report: &gix::protocol::push::Report) -> Vec<Option<String>> {
+        report
+            .refs
+            .iter()
+            .map(|status| status.error.as_ref().map(ToString::to_string))
+            .collect()
+    }
+
+    #[test]
+    fn updates_are_applied_after_the_hooks_accepted_the_quarantined_objects() -> crate::Result {
+        let (repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        configure(&remote_dir, "[receive]\n\tadvertisePushOptions = true\n")?;
+        install_hook(
+            &remote_dir,
+            "pre-receive",
+            "test -d \"$GIT_QUARANTINE_PATH\" || exit 1\nwhile read old new name; do git cat-file -e $new || exit 1; done\necho \"$GIT_PUSH_OPTION_0\" > push-option\n",
+        )?;
+        install_hook(
+            &remote_dir,
+            "update",
+            "echo \"$1\" >> updated-refs\necho hello from update\n",
+        )?;
+        install_hook(&remote_dir, "post-receive", "cat > post-receive-input\n")?;
+
+        let (client, server) = push(&repo, &remote_dir, &["refs/heads/main", "v1.0"], |prepare| {
+            prepare.with_push_options(["ci.skip"])
+        })?;
+        assert_eq!(client.report, server.report, "the client sees the report of the server");
+        assert_eq!(errors(&server.report), [None, None]);
+        assert_eq!(server.updates, client.updates);
+        assert_eq!(server.push_options, ["ci.skip"]);
+
+        let remote_repo = gix::open_opts(&remote_dir, crate::restricted())?;
+        for name in ["refs/heads/main", "refs/tags/v1.0"] {
+            let id = repo.find_reference(name)?.id().detach();
+            assert_eq!(remote_repo.find_reference(name)?.id(), id);
+            assert!(
+                remote_repo.has_object(id),
+                "objects were migrated out of the quarantine"
+            );
+        }
+        assert_eq!(std::fs::read_to_string(remote_dir.join("push-option"))?, "ci.skip\n");
+        assert_eq!(
+            std::fs::read_to_string(remote_dir.join("updated-refs"))?,
+            "refs/heads/main\nrefs/tags/v1.0\n"
+        );
+        let expected_input: String = client
+            .updates
+            .iter()
+            .map(|update| format!("{} {} {}\n", update.old, update.new, update.name))
+            .collect();
+        assert_eq!(
+            std::fs::read_to_string(remote_dir.join("post-receive-input"))?,
+            expected_input
+        );
+        assert!(
+            std::fs::read_dir(remote_dir.join("objects"))?
+                .filter_map(Result::ok)
+                .all(|entry| !entry.file_name().to_string_lossy().starts_with("incoming-")),
+            "the quarantine directory is removed"
+        );
+
+        let (client, server) = push(&repo, &remote_dir, &["refs/heads/main"], |prepare| prepare)?;
+        assert_eq!(client.updates, [], "nothing to do");
+        assert_eq!(
+            server.updates,
+            [],
+            "the client only indicated the end of the interaction"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn declining_pre_receive_hook_keeps_objects_in_quarantine() -> crate::Result {
+        let (repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        install_hook(&remote_dir, "pre-receive", "echo rejected by policy >&2\nexit 1\n")?;
+        let previous_main = gix::open_opts(&remote_dir, crate::restricted())?
+            .find_reference("refs/heads/main")?
+            .id()
+            .detach();
+
+        let (client, server) = push(&repo, &remote_dir, &["refs/heads/main"], |prepare| prepare)?;
+        assert_eq!(client.report, server.report);
+        assert_eq!(errors(&server.report), [Some("pre-receive hook declined".into())]);
+
+        let remote_repo = gix::open_opts(&remote_dir, crate::restricted())?;
+        assert_eq!(remote_repo.find_reference("refs/heads/main")?.id(), previous_main);
+        assert!(
+            !remote_repo.has_object(client.updates[0].new),
+            "declined objects never leave the quarantine"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn configured_and_hook_rejections_with_and_without_atomic_mode() -> crate::Result {
+        let (repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_dir = tmp.path().join("base");
+        configure(
+            &remote_dir,
+            "[receive]\n\tdenyNonFastForwards = true\n\tdenyDeletes = true\n",
+        )?;
+        install_hook(&remote_dir, "update", "test \"$1\" != refs/tags/v1.0\n")?;
+
+        let (_, server) = push(&repo, &remote_dir, &["refs/heads/main", "v1.0"], |prepare| {
+            prepare.with_atomic(true)
+        })?;
+        assert_eq!(
+            errors(&server.report),
+            [Some("atomic push failure".into()), Some("hook declined".into())]
+        );
+        let remote_repo = gix::open_opts(&remote_dir, crate::restricted())?;
+        let previous_main = remote_repo.find_reference("refs/heads/main")?.id().detach();
+        assert_ne!(previous_main, repo.find_reference("refs/heads/main")?.id());
+
+        let (_, server) = push(&repo, &remote_dir, &["refs/heads/main", "v1.0"], |prepare| prepare)?;
+        assert_eq!(errors(&server.report), [None, Some("hook declined".into())]);
+        assert_eq!(
+            remote_repo.find_reference("refs/heads/main")?.id(),
+            repo.find_reference("refs/heads/main")?.id()
+        );
+
+        let (_, server) = push(
+            &repo,
+            &remote_dir,
+            &[&format!("+{previous_main}:refs/heads/main")],
+            |prepare| prepare,
+        )?;
+        assert_eq!(errors(&server.report), [Some("non-fast-forward".into())]);
+
+        let (_, server) = push(&repo, &remote_dir, &[":refs/heads/main"], |prepare| prepare)?;
+        assert_eq!(errors(&server.report), [Some("deletion prohibited".into())]);
+        assert!(remote_repo.try_find_reference("refs/heads/main")?.is_some());
+        Ok(())
+    }
+}
+
+mod http {
+    use std::{io::Write, sync::atomic::AtomicBool};
+
+    use gix::{
+        bstr::{BString, ByteSlice},
+        odb::pack,
+        progress::Discard,
+        protocol::transport::{
+            packetline::{decode, encode, BandRef, PacketLineRef},
+            Service,
+        },
+        server::{
+            http::{handle, Handler, Outcome, Request, Response},
+            upload_pack,
+        },
+    };
+
+    use crate::remote::fetch::blocking_and_async_io::repo_rw;
+
+    struct TestRequest {
+        method: &'static str,
+        path: &'static str,
+        query: Option<&'static str>,
+        content_type: Option<String>,
+        content_encoding: Option<&'static str>,
+        body: std::io::Cursor<Vec<u8>>,
+    }
+
+    impl TestRequest {
+        fn get(path: &'static str, query: Option<&'static str>) -> Self {
+            TestRequest {
+                method: "GET",
+                path,
+                query,
+                content_type: None,
+                content_encoding: None,
+                body: Default::default(),
+            }
+        }
+
+        /// A request to `service` with `lines` as packet lines in its body, where `None` is a flush packet.
+        fn post(service: &'static str, lines: &[Option<&str>]) -> Self {
+            let mut body = Vec::new();
+            for line in lines {
+                match line {
+                    Some(line) => encode::text_to_write(line.as_bytes(), &mut body),
+                    None => encode::flush_to_write(&mut body),
+                }
+                .expect("writing to memory works");
+            }
+            TestRequest {
+                method: "POST",
+                path: if service == "git-upload-pack" {
+                    "/git-upload-pack"
+                } else {
+                    "/git-receive-pack"
+                },
+                query: None,
+                content_type: Some(format!("application/x-{service}-request")),
+                content_encoding: None,
+                body: std::io::Cursor::new(body),
+            }
+        }
+
+        /// Compress the body with `gzip`, with a header that has optional fields like `git` may send them.
+        fn gzip(mut self) -> Self {
+            let mut encoder = flate2::GzBuilder::new()
+                .filename("request")
+                .comment("optional")
+                .write(Vec::new(), flate2::Compression::default());
+            encoder.write_all(self.body.get_ref()).expect("writing to memory works");
+            self.body = std::io::Cursor::new(encoder.finish().expect("writing to memory works"));
+            self.content_encoding = Some("gzip");
+            self
+        }
+    }
+
+    impl Request for TestRequest {
+        fn method(&self) -> &str {
+            self.method
+        }
+
+        fn path(&self) -> &str {
+            self.path
+        }
+
+        fn query(&self) -> Option<&str> {
+            self.query
+        }
+
+        fn header(&self, name: &str) -> Option<&str> {
+            if name.eq_ignore_ascii_case("content-type") {
+                self.content_type.as_deref()
+            } else if name.eq_ignore_ascii_case("content-encoding") {
+                self.content_encoding
+            } else {
+                None
+            }
+        }
+
+        fn body(&mut self) -> &mut dyn std::io::Read {
+            &mut self.body
+        }
+    }
+
+    #[derive(Default)]
+    struct TestResponse {
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: Vec<u8>,
+    }
+
+    impl TestResponse {
+        fn header(&self, name: &str) -> Option<&str> {
+            self.headers
+                .iter()
+                .find_map(|(key, value)| (key == name).then_some(value.as_str()))
+        }
+
+        /// Split the body into its packet lines.
+        fn lines(&self) -> Vec<PacketLineRef<'_>> {
+            let mut data = self.body.as_slice();
+            let mut lines = Vec::new();
+            while !data.is_empty() {
+                match decode::streaming(data).expect("valid packet lines") {
+                    decode::Stream::Complete { line, bytes_consumed } => {
+                        lines.push(line);
+                        data = &data[bytes_consumed..];
+                    }
+                    decode::Stream::Incomplete { .. } => unreachable!("responses are complete"),
+                }
+            }
+            lines
+        }
+    }
+
+    impl Response for TestResponse {
+        fn set_status(&mut self, status: u16) {
+            self.status = status;
+        }
+
+        fn set_header(&mut self, name: &str, value: &str) {
+            self.headers.push((name.into(), value.into()));
+        }
+
+        fn body(&mut self) -> &mut dyn std::io::Write {
+            &mut self.body
+        }
+    }
+
+    fn handle(handler: &Handler<'_>, mut request: TestRequest) -> crate::Result<(Outcome, TestResponse)> {
+        let mut response = TestResponse::default();
+        let outcome = handler.handle(&mut request, &mut response, &mut Discard, &AtomicBool::default())?;
+        Ok((outcome, response))
+    }
+
+    fn text(line: &PacketLineRef<'_>) -> BString {
+        line.as_bstr().map(|line| line.trim_end().into()).unwrap_or_default()
+    }
+
+    #[test]
+    fn references_are_advertised_for_enabled_services_only() -> crate::Result {
+        let (_repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let handler = Handler::new(&remote_repo)?;
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::get("/info/refs", Some("service=git-upload-pack")),
+        )?;
+        assert!(matches!(outcome, Outcome::Advertised(Service::UploadPack)));
+        assert_eq!(response.status, 200);
+        assert_eq!(
+            response.header("Content-Type"),
+            Some("application/x-git-upload-pack-advertisement")
+        );
+        let lines = response.lines();
+        assert_eq!(text(&lines[0]), "# service=git-upload-pack");
+        assert_eq!(lines[1], PacketLineRef::Flush);
+        let (head, capabilities) = lines[2]
+            .as_bstr()
+            .expect("text")
+            .split_once_str(b"\0")
+            .expect("capabilities");
+        let head_id = remote_repo.head_id()?;
+        assert_eq!(head.as_bstr(), format!("{head_id} HEAD"));
+        assert!(capabilities.contains_str("symref=HEAD:refs/heads/main"));
+        assert!(capabilities.contains_str("object-format=sha1"));
+        let tag = remote_repo.find_reference("refs/tags/b-tag")?.id();
+        let peeled = tag.object()?.peel_tags_to_end()?.id;
+        assert!(lines.iter().any(|line| text(line) == format!("{tag} refs/tags/b-tag")));
+        assert!(lines
+            .iter()
+            .any(|line| text(line) == format!("{peeled} refs/tags/b-tag^{{}}")));
+        assert_eq!(lines.last(), Some(&PacketLineRef::Flush));
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::get("info/refs", Some("service=git-receive-pack")),
+        )?;
+        assert!(
+            matches!(outcome, Outcome::Rejected { status: 403 }),
+            "pushing needs to be enabled explicitly"
+        );
+        assert_eq!(response.header("Content-Type"), Some("text/plain"));
+
+        let handler = handler.with_receive_pack(true);
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::get("info/refs", Some("service=git-receive-pack")),
+        )?;
+        assert!(matches!(outcome, Outcome::Advertised(Service::ReceivePack)));
+        assert_eq!(text(&response.lines()[0]), "# service=git-receive-pack");
+
+        for (request, expected_status) in [
+            (TestRequest::get("/info/refs", None), 404),
+            (TestRequest::get("/info/refs", Some("service=git-unknown")), 403),
+            (TestRequest::get("/objects/info/packs", None), 404),
+            (TestRequest::get("/git-upload-pack", None), 405),
+        ] {
+            let (outcome, response) = handle(&handler, request)?;
+            assert!(matches!(outcome, Outcome::Rejected { status } if status == expected_status));
+            assert_eq!(response.status, expected_status);
+        }
+        let mut request = TestRequest::post("git-upload-pack", &[None]);
+        request.content_type = Some("text/plain".into());
+        let (outcome, _) = handle(&handler, request)?;
+        assert!(matches!(outcome, Outcome::Rejected { status: 415 }));
+        Ok(())
+    }
+
+    #[test]
+    fn gzip_compressed_requests_are_decoded() -> crate::Result {
+        let (_repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let handler = Handler::new(&remote_repo)?;
+        let main = remote_repo.find_reference("refs/heads/main")?.id();
+        let want = format!("want {main} side-band-64k ofs-delta");
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::post("git-upload-pack", &[Some(&want), None, Some("done")]).gzip(),
+        )?;
+        let Outcome::Uploaded(outcome) = outcome else {
+            unreachable!("upload-pack ran")
+        };
+        assert_eq!(outcome.wants, [main.detach()]);
+        assert!(outcome.num_objects.is_some(), "a pack was sent");
+        assert_eq!(text(&response.lines()[0]), "NAK");
+
+        let mut request = TestRequest::post("git-upload-pack", &[Some(&want), None, Some("done")]);
+        request.content_encoding = Some("br");
+        let (outcome, _) = handle(&handler, request)?;
+        assert!(matches!(outcome, Outcome::Rejected { status: 415 }));
+        Ok(())
+    }
+
+    #[test]
+    fn fetch_in_stateless_rounds() -> crate::Result {
+        let (_repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let handler = Handler::new(&remote_repo)?;
+        let main = remote_repo.find_reference("refs/heads/main")?.id();
+        let first_want = format!("want {main} multi_ack_detailed side-band-64k ofs-delta");
+        let unknown = gix::hash::ObjectId::from_hex(b"1111111111111111111111111111111111111111")?;
+        let common_id = remote_repo.head_commit()?.parent_ids().next().expect("parent").detach();
+        let (common, have_unknown) = (format!("have {common_id}"), format!("have {unknown}"));
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::post("git-upload-pack", &[Some(&first_want), None, Some(&have_unknown), None]),
+        )?;
+        let Outcome::Uploaded(outcome) = outcome else {
+            unreachable!("upload-pack ran")
+        };
+        assert_eq!(outcome.wants, [main.detach()]);
+        assert!(outcome.common.is_empty());
+        assert_eq!(
+            outcome.num_objects, None,
+            "the client is expected to send another request"
+        );
+        assert_eq!(
+            response.header("Content-Type"),
+            Some("application/x-git-upload-pack-result")
+        );
+        assert_eq!(
+            response.lines().iter().map(text).collect::<Vec<_>>(),
+            ["NAK"],
+            "nothing in common yet"
+        );
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::post(
+                "git-upload-pack",
+                &[
+                    Some(&first_want),
+                    None,
+                    Some(&have_unknown),
+                    Some(&common),
+                    Some("done"),
+                ],
+            ),
+        )?;
+        let Outcome::Uploaded(outcome) = outcome else {
+            unreachable!("upload-pack ran")
+        };
+        assert_eq!(outcome.common, [common_id]);
+        let lines = response.lines();
+        assert_eq!(text(&lines[0]), format!("ACK {common_id} common"));
+        assert_eq!(
+            text(&lines[1]),
+            format!("ACK {common_id}"),
+            "the last common object is acknowledged when done"
+        );
+        assert_eq!(lines.last(), Some(&PacketLineRef::Flush));
+        let mut pack_data = Vec::new();
+        for line in &lines[2..lines.len() - 1] {
+            if let BandRef::Data(data) = line.decode_band()? {
+                pack_data.extend_from_slice(data);
+            }
+        }
+
+        let clone = gix::init_bare(tmp.path().join("clone"))?;
+        let pack_dir = clone.objects.store_ref().path().join("pack");
+        let written = pack::Bundle::write_to_directory(
+            &mut pack_data.as_slice(),
+            Some(&pack_dir),
+            &mut Discard,
+            &AtomicBool::default(),
+            None::<gix::odb::Handle>,
+            Default::default(),
+        )?;
+        assert_eq!(Some(written.index.num_objects as usize), outcome.num_objects);
+        let main_commit = clone.find_object(main)?.into_commit();
+        assert!(
+            clone.has_object(main_commit.tree_id()?),
+            "the tree of the merge commit was sent"
+        );
+        for entry in main_commit.tree()?.iter() {
+            assert!(
+                clone.has_object(entry?.oid()),
+                "objects that changed in merge commits compared to all parents were sent"
+            );
+        }
+        let only_reachable_from_common = remote_repo.find_reference("refs/heads/e")?.id();
+        assert!(
+            !clone.has_object(only_reachable_from_common),
+            "the history of objects the client has isn't sent"
+        );
+
+        let mut response = TestResponse::default();
+        let err = handler
+            .handle(
+                &mut TestRequest::post(
+                    "git-upload-pack",
+                    &[Some(&format!("want {unknown}")), None, Some("done")],
+                ),
+                &mut response,
+                &mut Discard,
+                &AtomicBool::default(),
+            )
+            .expect_err("only advertised objects can be wanted");
+        assert!(matches!(err, handle::Error::UploadPack(upload_pack::Error::NotOurRef { id }) if id == unknown));
+        assert_eq!(
+            text(&response.lines()[0]),
+            format!("ERR upload-pack: not our ref {unknown}")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn push_is_received_when_enabled() -> crate::Result {
+        let (_repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let handler = Handler::new(&remote_repo)?.with_receive_pack(true);
+        let j = remote_repo.find_reference("refs/heads/j")?.id();
+        let null = remote_repo.object_hash().null();
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::post(
+                "git-receive-pack",
+                &[Some(&format!("{j} {null} refs/heads/j\0report-status")), None],
+            ),
+        )?;
+        let Outcome::Received(outcome) = outcome else {
+            unreachable!("receive-pack ran")
+        };
+        assert_eq!(outcome.updates.len(), 1);
+        assert_eq!(
+            response.header("Content-Type"),
+            Some("application/x-git-receive-pack-result")
+        );
+        assert_eq!(
+            response.lines().iter().take(2).map(text).collect::<Vec<_>>(),
+            ["unpack ok", "ok refs/heads/j"]
+        );
+        assert!(remote_repo.try_find_reference("refs/heads/j")?.is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn push_without_report_status_receives_no_report() -> crate::Result {
+        let (_repo, tmp) = repo_rw("clone-as-base-with-changes");
+        let remote_repo = gix::open_opts(tmp.path().join("base"), crate::restricted())?;
+        let handler = Handler::new(&remote_repo)?.with_receive_pack(true);
+        let j = remote_repo.find_reference("refs/heads/j")?.id();
+        let null = remote_repo.object_hash().null();
+
+        let (outcome, response) = handle(
+            &handler,
+            TestRequest::post("git-receive-pack", &[Some(&format!("{j} {null} refs/heads/j")), None]),
+        )?;
+        let Outcome::Received(outcome) = outcome else {
+            unreachable!("receive-pack ran")
+        };
+        assert_eq!(
+            outcome.report.refs.len(),
+            1,
+            "the report is still available to the caller"
+        );
+        assert!(
+            response.lines().is_empty(),
+            "the client didn't ask for a report, so nothing is sent"
+        );
+        assert!(remote_repo.try_find_reference("refs/heads/j")?.is_none());
+        Ok(())
+    }
+}
@@ -26,7 +26,7 @@ mod index_worktree {
         fn item_size() {
             assert_eq!(
                 std::mem::size_of::<gix::status::index_worktree::iter::Item>(),
-                264,
+                304,
                 "The size is pretty huge and goes down ideally"
             );
         }
@@ -68,6 +68,7 @@ mod index_worktree {
                             disk_kind: Some(gix_dir::entry::Kind::Directory),
                             index_kind: None,
                             pathspec_match: Some(gix_dir::entry::PathspecMatch::Always),
+                            metadata: None,
                         },
                         collapsed_directory_status: None
                     },
@@ -79,6 +80,7 @@ mod index_worktree {
                             disk_kind: Some(gix_dir::entry::Kind::File),
                             index_kind: None,
                             pathspec_match: Some(gix_dir::entry::PathspecMatch::Always),
+                            metadata: None,
                         },
                         collapsed_directory_status: None
                     }
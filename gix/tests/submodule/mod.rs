@@ -342,3 +342,235 @@ mod open {
         Ok(())
     }
 }
+
+#[cfg(all(
+    feature = "blocking-network-client",
+    feature = "worktree-mutation",
+    feature = "status",
+    feature = "revision",
+    feature = "merge"
+))]
+mod update {
+    use gix::submodule::update::{Action, Error, Options};
+
+    fn writable_repo(name: &str) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        writable_repo_from("make_submodules.sh", name)
+    }
+
+    fn writable_repo_from(
+        script: &str,
+        name: &str,
+    ) -> crate::Result<(gix::Repository, gix_testtools::tempfile::TempDir)> {
+        let tmp = gix_testtools::scripted_fixture_writable(script)?;
+        let repo = gix::open_opts(tmp.path().join(name), crate::restricted())?;
+        Ok((repo, tmp))
+    }
+
+    fn checked_out_id(sm: &gix::Submodule<'_>) -> crate::Result<Option<gix::ObjectId>> {
+        Ok(Some(sm.open()?.expect("present").head_id()?.detach()))
+    }
+
+    fn first_submodule(repo: &gix::Repository) -> crate::Result<gix::Submodule<'_>> {
+        Ok(repo.submodules()?.into_iter().flatten().next().expect("one submodule"))
+    }
+
+    #[test]
+    fn clone_and_checkout_active_submodules_and_initialize_the_others_on_request() -> crate::Result {
+        // Not archived, as the clone and the initialized submodule refer to their remotes with absolute paths.
+        let (repo, _tmp) = writable_repo_from("make_submodule_update.sh", "super-clone")?;
+        let workdir = repo.work_dir().expect("non-bare").to_owned();
+
+        let outcome = repo.update_submodules(Options::default(), &Default::default())?;
+        assert_eq!(
+            outcome.submodules,
+            [("m1".into(), Action::Cloned), ("dir/m1".into(), Action::Skipped)],
+            "only the initialized submodule is active"
+        );
+        assert_eq!(std::fs::read(workdir.join("m1/this"))?, b"hello\n");
+        assert!(workdir.join("m1/subdir/that").is_file());
+        assert!(
+            !workdir.join("m1/untracked").exists(),
+            "only tracked files are checked out"
+        );
+
+        let sm = first_submodule(&repo)?;
+        let state = sm.state()?;
+        assert!(state.repository_exists && state.worktree_checkout && !state.is_old_form);
+        assert_eq!(checked_out_id(&sm)?, sm.index_id()?, "HEAD is at the recorded commit");
+        let sm_repo = sm.open()?.expect("cloned");
+        assert!(sm_repo.head()?.is_detached());
+        assert!(!sm_repo.is_dirty()?, "the index matches the worktree");
+
+        let outcome = sm.apply_update(Options::default(), &Default::default())?;
+        assert_eq!(outcome.submodules, [("m1".into(), Action::UpToDate)]);
+
+        let outcome = repo.update_submodules(
+            Options {
+                init: true,
+                ..Default::default()
+            },
+            &Default::default(),
+        )?;
+        assert_eq!(
+            outcome.submodules,
+            [("m1".into(), Action::UpToDate), ("dir/m1".into(), Action::Cloned)],
+            "the relative url of the uninitialized submodule is resolved against the url of the superproject remote"
+        );
+        assert_eq!(std::fs::read(workdir.join("dir/m1/this"))?, b"hello\n");
+
+        let repo = gix::open_opts(&workdir, crate::restricted())?;
+        let config = repo.config_snapshot();
+        assert!(config.string("submodule.dir/m1.url").is_some(), "it was initialized");
+        assert_eq!(config.boolean("submodule.dir/m1.active"), Some(true));
+        Ok(())
+    }
+
+    #[test]
+    fn checkout_the_recorded_commit() -> crate::Result {
+        let (repo, _tmp) = writable_repo("submodule-head-changed")?;
+        let sm = first_submodule(&repo)?;
+        let previous = checked_out_id(&sm)?;
+        assert_ne!(previous, sm.index_id()?);
+
+        let outcome = sm.apply_update(Options::default(), &Default::default())?;
+        assert_eq!(outcome.submodules, [("m1".into(), Action::CheckedOut { previous })]);
+        assert_eq!(checked_out_id(&sm)?, sm.index_id()?);
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("non-bare").join("m1/this"))?,
+            b"hello\n"
+        );
+        assert!(!sm.open()?.expect("present").is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn local_modifications_are_only_overwritten_if_forced() -> crate::Result {
+        let (repo, _tmp) = writable_repo("submodule-head-changed-and-modified")?;
+        let sm = first_submodule(&repo)?;
+        let previous = checked_out_id(&sm)?;
+
+        let err = sm
+            .apply_update(Options::default(), &Default::default())
+            .expect_err("dirty");
+        assert!(matches!(err, Error::Dirty { path } if path == "m1"));
+        assert_eq!(checked_out_id(&sm)?, previous, "nothing changed");
+
+        let outcome = sm.apply_update(
+            Options {
+                force: true,
+                ..Default::default()
+            },
+            &Default::default(),
+        )?;
+        assert_eq!(outcome.submodules, [("m1".into(), Action::CheckedOut { previous })]);
+        assert_eq!(
+            std::fs::read(repo.work_dir().expect("non-bare").join("m1/this"))?,
+            b"hello\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn merge_the_recorded_commit_into_a_diverged_branch() -> crate::Result {
+        let (repo, _tmp) = writable_repo_from("make_submodule_update.sh", "super-merge")?;
+        let sm = first_submodule(&repo)?;
+        let previous = checked_out_id(&sm)?.expect("checked out");
+        let id = sm.index_id()?.expect("recorded");
+
+        let outcome = sm.apply_update(Options::default(), &Default::default())?;
+        let merge_commit = match outcome.submodules.as_slice() {
+            [(
+                path,
+                Action::Merged {
+                    previous: actual,
+                    merge_commit,
+                },
+            )] if path == "m1" && *actual == previous => *merge_commit,
+            other => unreachable!("unexpected outcome: {other:?}"),
+        };
+        let sm_repo = sm.open()?.expect("present");
+        let head = sm_repo.head()?;
+        assert_eq!(
+            head.referent_name().map(|name| name.as_bstr().to_string()),
+            Some("refs/heads/local".into()),
+            "the branch is kept"
+        );
+        assert_eq!(head.id().expect("born").detach(), merge_commit);
+        let commit = sm_repo.find_object(merge_commit)?.into_commit();
+        assert_eq!(
+            commit.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(),
+            [previous, id]
+        );
+        assert_eq!(commit.message_raw()?, format!("Merge commit '{id}'\n"));
+
+        let workdir = repo.work_dir().expect("non-bare");
+        assert_eq!(std::fs::read(workdir.join("m1/this"))?, b"hello\n");
+        assert_eq!(std::fs::read(workdir.join("m1/other"))?, b"local\n");
+        assert!(!sm_repo.is_dirty()?);
+
+        let outcome = sm.apply_update(Options::default(), &Default::default())?;
+        assert_eq!(
+            outcome.submodules,
+            [("m1".into(), Action::UpToDate)],
+            "the recorded commit is contained in the merge commit"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn rebase_a_diverged_branch_onto_the_recorded_commit() -> crate::Result {
+        let (repo, _tmp) = writable_repo_from("make_submodule_update.sh", "super-rebase")?;
+        let sm = first_submodule(&repo)?;
+        let previous = checked_out_id(&sm)?.expect("checked out");
+        let id = sm.index_id()?.expect("recorded");
+
+        let outcome = sm.apply_update(Options::default(), &Default::default())?;
+        let head = match outcome.submodules.as_slice() {
+            [(path, Action::Rebased { previous: actual, head })] if path == "m1" && *actual == previous => *head,
+            other => unreachable!("unexpected outcome: {other:?}"),
+        };
+        let sm_repo = sm.open()?.expect("present");
+        assert_eq!(
+            sm_repo.head_name()?.expect("not detached").as_bstr(),
+            "refs/heads/local"
+        );
+        assert_eq!(sm_repo.head_id()?, head);
+        let (rebased, local) = (
+            sm_repo.find_object(head)?.into_commit(),
+            sm_repo.find_object(previous)?.into_commit(),
+        );
+        assert_eq!(rebased.parent_ids().map(gix::Id::detach).collect::<Vec<_>>(), [id]);
+        assert_eq!(rebased.message_raw()?, local.message_raw()?);
+        assert_eq!(rebased.author()?, local.author()?, "the author is kept");
+
+        let workdir = repo.work_dir().expect("non-bare");
+        assert_eq!(std::fs::read(workdir.join("m1/this"))?, b"hello\n");
+        assert_eq!(std::fs::read(workdir.join("m1/other"))?, b"local\n");
+        assert!(!sm_repo.is_dirty()?);
+        Ok(())
+    }
+
+    #[test]
+    fn merge_and_rebase_conflicts_leave_the_submodule_untouched() -> crate::Result {
+        for name in ["super-merge-conflict", "super-rebase-conflict"] {
+            let (repo, _tmp) = writable_repo_from("make_submodule_update.sh", name)?;
+            let sm = first_submodule(&repo)?;
+            let previous = checked_out_id(&sm)?;
+
+            let err = sm
+                .apply_update(Options::default(), &Default::default())
+                .expect_err("conflict");
+            assert!(
+                matches!(&err, Error::MergeConflict { path, .. } | Error::RebaseConflict { path, .. } if path == "m1"),
+                "{name}: {err:?}"
+            );
+            assert_eq!(checked_out_id(&sm)?, previous, "{name}: nothing changed");
+            assert_eq!(
+                std::fs::read(repo.work_dir().expect("non-bare").join("m1/this"))?,
+                b"local\n",
+                "{name}"
+            );
+        }
+        Ok(())
+    }
+}
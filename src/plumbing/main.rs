@@ -72,6 +72,7 @@ pub fn main() -> Result<()> {
     }
     let object_hash = args.object_hash;
     let config = args.config;
+    let namespace = args.namespace;
     let repository = args.repository;
     let repository_path = repository.clone();
     enum Mode {
@@ -117,6 +118,10 @@ pub fn main() -> Result<()> {
                     .append_config(config.iter(), gix::config::Source::Cli)
                     .context("Unable to parse command-line configuration")?;
             }
+            if let Some(namespace) = namespace.as_ref() {
+                repo.set_namespace(namespace)
+                    .context("Unable to use the reference namespace")?;
+            }
             Ok(repo)
         }
     };
@@ -1081,6 +1086,8 @@ pub fn main() -> Result<()> {
                 long,
                 statistics,
                 max_candidates,
+                patterns,
+                exclude_patterns,
                 rev_spec,
                 dirty_suffix,
             } => prepare_and_run(
@@ -1103,6 +1110,8 @@ pub fn main() -> Result<()> {
                             first_parent,
                             statistics,
                             max_candidates,
+                            patterns,
+                            exclude_patterns,
                             always,
                             dirty_suffix: dirty_suffix.map(|suffix| suffix.unwrap_or_else(|| "dirty".to_string())),
                         },
@@ -1151,6 +1160,58 @@ pub fn main() -> Result<()> {
                     )
                 },
             ),
+            tree::Subcommands::Diff {
+                no_renames,
+                find_renames,
+                rename_limit,
+                find_copies,
+                find_copies_harder,
+                patch,
+                unified,
+                binary,
+                numstat,
+                stat,
+                old_treeish,
+                new_treeish,
+            } => prepare_and_run(
+                "tree-diff",
+                trace,
+                verbose,
+                progress,
+                progress_keep_open,
+                None,
+                move |_progress, out, err| {
+                    core::repository::tree::diff(
+                        repository(Mode::Strict)?,
+                        &old_treeish,
+                        &new_treeish,
+                        core::repository::tree::diff::Options {
+                            no_renames,
+                            rename_threshold: find_renames.map(|percent| f32::from(percent) / 100.0),
+                            rename_limit,
+                            copy_threshold: find_copies.map(|percent| f32::from(percent) / 100.0),
+                            find_copies_harder,
+                            patch: (patch || unified.is_some() || binary).then(|| {
+                                unified.map_or_else(
+                                    gix::diff::blob::unified_diff::ContextSize::default,
+                                    gix::diff::blob::unified_diff::ContextSize::symmetrical,
+                                )
+                            }),
+                            binary,
+                            stat: if numstat {
+                                Some(core::repository::tree::diff::Stat::Numstat)
+                            } else if stat {
+                                Some(core::repository::tree::diff::Stat::Stat)
+                            } else {
+                                None
+                            },
+                        },
+                        format,
+                        out,
+                        err,
+                    )
+                },
+            ),
         },
         Subcommands::Odb(cmd) => match cmd {
             odb::Subcommands::Stats => prepare_and_run(
@@ -20,6 +20,10 @@ pub struct Args {
     #[clap(long, short = 'c', value_parser = gitoxide::shared::AsBString)]
     pub config: Vec<BString>,
 
+    /// The namespace to use for all references, similar to `git --namespace`, overriding the `GIT_NAMESPACE` environment variable.
+    #[clap(long, value_parser = gitoxide::shared::AsBString)]
+    pub namespace: Option<BString>,
+
     #[clap(long, short = 't')]
     /// The amount of threads to use for some operations.
     ///
@@ -622,6 +626,50 @@ pub mod tree {
             /// The revspec of the tree to traverse, or the tree at `HEAD` if unspecified.
             treeish: Option<String>,
         },
+        /// Show which files changed between two trees, with renames detected according to the configuration.
+        Diff {
+            /// Don't detect renames, even if `diff.renames` is configured.
+            #[clap(long, conflicts_with_all = ["find_renames", "rename_limit", "find_copies", "find_copies_harder"])]
+            no_renames: bool,
+            /// Detect renames of files with at least the given similarity in percent, with 100 meaning exact renames only.
+            #[clap(long, short = 'M', value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+            find_renames: Option<u8>,
+            /// The maximum amount of files on either side to consider for inexact rename detection, overriding `diff.renameLimit`.
+            ///
+            /// 0 means there is no limit.
+            #[clap(long, short = 'l', value_name = "COUNT")]
+            rename_limit: Option<usize>,
+            /// Detect copies of files with at least the given similarity in percent, along with renames.
+            ///
+            /// Only modified files are considered as copy sources, unless `--find-copies-harder` is set.
+            #[clap(long, short = 'C', value_name = "PERCENT", value_parser = clap::value_parser!(u8).range(0..=100))]
+            find_copies: Option<u8>,
+            /// Detect copies with all files of the old tree as possible sources, not only the modified ones.
+            ///
+            /// This is expensive on large trees, which is why similarity checks are skipped if the amount of files
+            /// exceeds the rename limit, so only exact copies are found among unmodified files.
+            #[clap(long)]
+            find_copies_harder: bool,
+            /// Print the changes as patch, instead of only listing the changed files.
+            #[clap(long, short = 'p')]
+            patch: bool,
+            /// The amount of unchanged lines to print around each change in a patch, implying `--patch`.
+            #[clap(long, short = 'U', value_name = "LINES")]
+            unified: Option<u32>,
+            /// Write binary patches for binary files that `git apply` can apply, instead of only noting that they differ, implying `--patch`.
+            #[clap(long)]
+            binary: bool,
+            /// Print the amount of inserted and removed lines of each file separated by tabs, like `git diff --numstat`.
+            #[clap(long, conflicts_with_all = ["patch", "unified", "binary", "stat"])]
+            numstat: bool,
+            /// Print a graph of the changes of each file followed by a summary, like `git diff --stat`.
+            #[clap(long, conflicts_with_all = ["patch", "unified", "binary"])]
+            stat: bool,
+            /// The revspec of the tree to diff from.
+            old_treeish: String,
+            /// The revspec of the tree to diff to.
+            new_treeish: String,
+        },
     }
 }
 
@@ -655,6 +703,14 @@ pub mod commit {
             #[clap(long, short = 'c', default_value = "10")]
             max_candidates: usize,
 
+            /// Only consider names matching the given glob pattern, like `v*`. Can be given multiple times.
+            #[clap(long = "match", short = 'm')]
+            patterns: Vec<String>,
+
+            /// Do not consider names matching the given glob pattern, even if they match otherwise. Can be given multiple times.
+            #[clap(long = "exclude", short = 'e')]
+            exclude_patterns: Vec<String>,
+
             /// Print information on stderr to inform about performance statistics
             #[clap(long, short = 's')]
             statistics: bool,
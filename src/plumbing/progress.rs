@@ -70,14 +70,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "core.hideDotFiles",
         usage: Planned("Seems useful, but needs demand from windows users")
     },
-    Record {
-        config: "core.packedGitWindowSize",
-        usage: NotPlanned("an optimization for handling many large packs more efficiently seems unnecessary")
-    },
-    Record {
-        config: "core.packedGitLimit",
-        usage: NotApplicable("we target 32bit systems only and don't use a windowing mechanism")
-    },
     Record {
         config: "core.alternateRefsCommand",
         usage: NotPlanned("there is no need as we can perform the required operation in-binary. This could happen though if there is a use-case and demand.")
@@ -102,14 +94,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "core.protectNTFS",
         usage: NotPlanned("lack of demand")
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned("we want to support huge repos and be the fastest in doing so")
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned("this is a nice improvement over spareCheckout alone and should one day be available too")
-    },
     Record {
         config: "core.gitProxy",
         usage: NotPlanned("the transport mechanism works differently enough to not support it for now, but of course it's possible to add support if there is demand")
@@ -146,10 +130,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "commitGraph.maxNewFilters",
         usage: NotPlanned("can be considered when the underlying feature is actually used or needed")
     },
-    Record {
-        config: "commitGraph.readChangedPaths",
-        usage: NotPlanned("can be considered when the underlying feature is actually used or needed")
-    },
     Record {
         config: "index.sparse",
         usage: Planned("we can read sparse indices and support for it will be added early on")
@@ -245,14 +225,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "fetch.unpackLimit",
         usage: Planned("")
     },
-    Record {
-        config: "fetch.prune",
-        usage: Planned("")
-    },
-    Record {
-        config: "fetch.pruneTags",
-        usage: Planned("")
-    },
     Record {
         config: "fetch.writeCommitGraph",
         usage: Planned("")
@@ -278,22 +250,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "advice.updateSparsePath",
         usage: NotApplicable("gitoxide does not yet have an 'advice' system")
     },
-    Record {
-        config: "core.sparseCheckout",
-        usage: Planned("together with 'index.sparse' and 'core.sparseCheckoutCone', configures if the index should be written sparse or not")
-    },
-    Record {
-        config: "core.sparseCheckoutCone",
-        usage: Planned("non-cone mode is deprecated but should still fail gracefully if encountered")
-    },
-    Record {
-        config: "core.splitIndex",
-        usage: NotPlanned("we don't want to be able to create split indices, but we will read them. It's (somewhat) superseded by sparse indices")
-    },
-    Record {
-        config: "splitIndex.maxPercentageChange",
-        usage: NotPlanned("seems like it's superseded by sparse indices")
-    },
     Record {
         config: "splitIndex.sharedIndexExpire",
         usage: NotPlanned("seems like it's superseded by sparse indices")
@@ -414,14 +370,6 @@ static GIT_CONFIG: &[Record] = &[
         config: "sparse.expectFilesOutsideOfPatterns",
         usage: NotPlanned("todo")
     },
-    Record {
-        config: "remote.<name>.promisor",
-        usage: Planned("required for big monorepos, and typically used in conjunction with sparse indices")
-    },
-    Record {
-        config: "remote.<name>.partialCloneFilter",
-        usage: Planned("required for big monorepos, and typically used in conjunction with sparse indices")
-    },
     Record {
         config: "merge.renameLimit",
         usage: Planned("The same as diff.renameLimit")